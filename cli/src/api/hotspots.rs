@@ -0,0 +1,171 @@
+//! Hotspots: the functions with the most incoming or outgoing calls.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::{check_range, Scope, DEFAULT_LIMIT, DEFAULT_PROJECT};
+use crate::config::{Config, DEFAULT_CONFIG_PATH};
+use crate::rules::EntryPointRules;
+use db::queries::adjacency::cached_index;
+use db::queries::entry_points::find_public_functions;
+use db::queries::hotspots::{find_hotspots, HotspotKind};
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+/// A hotspot search; the defaults are those of `hotspots`
+#[derive(Debug, Clone)]
+pub struct HotspotsRequest {
+    /// Module pattern (substring match, or regex with `regex`)
+    pub module: Option<String>,
+    pub kind: HotspotKind,
+    /// Leave out macro-generated functions
+    pub exclude_generated: bool,
+    /// Only modules of this OTP application (umbrella projects)
+    pub app: Option<String>,
+    pub project: String,
+    pub regex: bool,
+    /// Maximum number of functions (1-1000)
+    pub limit: u32,
+    /// Only functions at least this many calls away from an entry point; sets
+    /// `depth_from_entry` on each entry
+    pub min_depth_from_entry: Option<u32>,
+    /// Modules ranked; `generated_rules` decides what `exclude_generated` leaves out
+    pub scope: Scope,
+}
+
+impl Default for HotspotsRequest {
+    fn default() -> Self {
+        Self {
+            module: None,
+            kind: HotspotKind::Incoming,
+            exclude_generated: false,
+            app: None,
+            project: DEFAULT_PROJECT.to_string(),
+            regex: false,
+            limit: DEFAULT_LIMIT,
+            min_depth_from_entry: None,
+            scope: Scope::default(),
+        }
+    }
+}
+
+/// A function hotspot entry
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionHotspotEntry {
+    pub module: String,
+    pub function: String,
+    pub incoming: i64,
+    pub outgoing: i64,
+    pub total: i64,
+    pub ratio: f64,
+    /// Fewest calls from an entry point, with `min_depth_from_entry`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_from_entry: Option<i64>,
+}
+
+/// Hotspots, highest first by the requested kind
+#[derive(Debug, Serialize)]
+pub struct HotspotsResult {
+    pub kind: String,
+    pub total_items: usize,
+    pub entries: Vec<FunctionHotspotEntry>,
+}
+
+pub type HotspotsResponse = HotspotsResult;
+
+/// Find the functions with the most calls of the requested kind
+pub fn hotspots(db: &DbInstance, request: HotspotsRequest) -> Result<HotspotsResponse, Box<dyn Error>> {
+    check_range("limit", request.limit, 1..=1000)?;
+    let limit = request.limit;
+    hotspots_in_scope(db, request, limit)
+}
+
+/// Every hotspot matching the request, whatever its `limit`, for callers
+/// ordering them by something else before they keep the first ones
+pub(crate) fn all_hotspots(db: &DbInstance, request: HotspotsRequest) -> Result<HotspotsResponse, Box<dyn Error>> {
+    hotspots_in_scope(db, request, u32::MAX)
+}
+
+fn hotspots_in_scope(db: &DbInstance, request: HotspotsRequest, limit: u32) -> Result<HotspotsResponse, Box<dyn Error>> {
+    let settings = request.scope.settings();
+    // The depth filter applies before the limit, so rank every function
+    let depths = match request.min_depth_from_entry {
+        Some(_) => Some(entry_point_depths(db, &request.project, &settings)?),
+        None => None,
+    };
+    let query_limit = if depths.is_some() { u32::MAX } else { limit };
+
+    let hotspots = find_hotspots(
+        db,
+        request.kind,
+        request.module.as_deref(),
+        request.app.as_deref(),
+        &request.project,
+        request.regex,
+        query_limit,
+        request.exclude_generated,
+        false, // Don't require outgoing calls
+        &settings,
+    )?;
+
+    let kind_str = match request.kind {
+        HotspotKind::Incoming => "incoming",
+        HotspotKind::Outgoing => "outgoing",
+        HotspotKind::Total => "total",
+        HotspotKind::Ratio => "ratio",
+    };
+
+    let mut entries: Vec<FunctionHotspotEntry> = hotspots
+        .into_iter()
+        .map(|hotspot| FunctionHotspotEntry {
+            depth_from_entry: depths
+                .as_ref()
+                .and_then(|d| d.get(&(hotspot.module.clone(), hotspot.function.clone())).copied()),
+            module: hotspot.module,
+            function: hotspot.function,
+            incoming: hotspot.incoming,
+            outgoing: hotspot.outgoing,
+            total: hotspot.total,
+            ratio: hotspot.ratio,
+        })
+        .collect();
+    if let Some(min_depth) = request.min_depth_from_entry {
+        // Functions no entry point reaches have no depth and are left out
+        entries.retain(|e| e.depth_from_entry.is_some_and(|d| d >= i64::from(min_depth)));
+        entries.truncate(limit as usize);
+    }
+
+    let total_items = entries.len();
+
+    Ok(HotspotsResult {
+        kind: kind_str.to_string(),
+        total_items,
+        entries,
+    })
+}
+
+/// Fewest calls from an entry point to each function, by (module, name) over
+/// all arities. Entry points are found with the rules of `entry-points`.
+fn entry_point_depths(
+    db: &DbInstance,
+    project: &str,
+    settings: &QuerySettings,
+) -> Result<HashMap<(String, String), i64>, Box<dyn Error>> {
+    let config = Config::load_or_default(Path::new(DEFAULT_CONFIG_PATH))?;
+    let rules = EntryPointRules::new(&config.entry_points)?;
+    let roots: Vec<(String, String, i64)> = find_public_functions(db, project, None, false, settings)?
+        .into_iter()
+        .filter(|f| rules.kind_of(&f.module, &f.name, f.arity).is_some())
+        .map(|f| (f.module, f.name, f.arity))
+        .collect();
+
+    let mut depths: HashMap<(String, String), i64> = HashMap::new();
+    for ((module, name, _), depth) in cached_index(db, project)?.depths_from(&roots) {
+        let entry = depths.entry((module, name)).or_insert(depth);
+        *entry = (*entry).min(depth);
+    }
+    Ok(depths)
+}
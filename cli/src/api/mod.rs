@@ -0,0 +1,209 @@
+//! Typed entry points for embedding the analysis engine.
+//!
+//! Each function takes an open database and a request struct, and returns
+//! the result the matching command prints, without going through argument
+//! parsing or output formatting. Requests start from the command's defaults:
+//!
+//! ```no_run
+//! use code_search::api::{self, TraceRequest};
+//!
+//! let db = api::open_db("code_search.sqlite".as_ref())?;
+//! let response = api::trace(&db, TraceRequest { depth: 3, ..TraceRequest::new("MyApp.Web", "index") })?;
+//! for entry in &response.entries {
+//!     println!("{}{}.{}", "  ".repeat(entry.depth as usize), entry.module, entry.function);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+mod hotspots;
+mod path;
+mod reverse_trace;
+mod trace;
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+use db::queries::external_calls::{find_project_modules, find_test_modules};
+use db::query_builders::QuerySettings;
+
+pub use db::generated::GeneratedRules;
+pub use db::queries::hotspots::HotspotKind;
+pub use db::query_builders::TestScope;
+pub use db::types::{TraceDirection, TraceEntry};
+pub use db::{open_db, DbInstance};
+pub(crate) use hotspots::all_hotspots;
+pub use hotspots::{hotspots, FunctionHotspotEntry, HotspotsRequest, HotspotsResponse, HotspotsResult};
+pub use path::{path, PathRequest, PathResponse, PathResult, PathStrategy};
+pub use reverse_trace::{reverse_trace, ReverseTraceRequest, ReverseTraceResponse};
+pub use trace::{trace, TraceRequest, TraceResponse};
+
+const DEFAULT_PROJECT: &str = "default";
+const DEFAULT_DEPTH: u32 = 5;
+const DEFAULT_LIMIT: u32 = 100;
+
+/// Traversal engine for trace, reverse-trace and path
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Engine {
+    /// Recursive Datalog query per traversal
+    #[default]
+    Datalog,
+    /// In-memory adjacency index, loaded once per process and reused
+    Memory,
+}
+
+/// The modules a request looks at; the defaults keep every module, as the
+/// command line does without scope flags
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scope {
+    /// Only modules under these namespaces, and their submodules
+    pub namespaces: Vec<String>,
+    /// Leave out stdlib and dependency modules
+    pub only_project: bool,
+    pub test_scope: TestScope,
+    /// Rules telling generated functions apart
+    pub generated_rules: GeneratedRules,
+}
+
+impl Scope {
+    /// The query settings restricting a request to this scope
+    fn settings(&self) -> QuerySettings {
+        QuerySettings {
+            namespaces: self.namespaces.clone(),
+            only_project: self.only_project,
+            test_scope: self.test_scope,
+            generated_rules: self.generated_rules.clone(),
+            ..QuerySettings::default()
+        }
+    }
+}
+
+impl From<&QuerySettings> for Scope {
+    /// The scope of the command line or config settings
+    fn from(settings: &QuerySettings) -> Self {
+        Self {
+            namespaces: settings.namespaces.clone(),
+            only_project: settings.only_project,
+            test_scope: settings.test_scope,
+            generated_rules: settings.generated_rules.clone(),
+        }
+    }
+}
+
+/// The modules the memory engine keeps under the current scope, so its
+/// traversals drop what the Datalog scope conditions do
+struct ModuleFilter {
+    project_modules: Option<HashSet<String>>,
+    test_modules: Option<(HashSet<String>, bool)>,
+}
+
+impl ModuleFilter {
+    fn new(db: &DbInstance, project: &str, settings: &QuerySettings) -> Result<Self, Box<dyn Error>> {
+        let project_modules = if settings.only_project {
+            Some(find_project_modules(db, project)?)
+        } else {
+            None
+        };
+        let test_modules = match settings.test_scope {
+            TestScope::All => None,
+            scope => Some((find_test_modules(db, project)?, scope == TestScope::Only)),
+        };
+        Ok(Self { project_modules, test_modules })
+    }
+
+    fn keeps(&self, module: &str) -> bool {
+        self.project_modules.as_ref().is_none_or(|modules| modules.contains(module))
+            && self.test_modules.as_ref().is_none_or(|(tests, keep)| tests.contains(module) == *keep)
+    }
+}
+
+/// Reject a request field outside the range the command line accepts
+fn check_range<T: PartialOrd + Display>(field: &str, value: T, range: RangeInclusive<T>) -> Result<(), Box<dyn Error>> {
+    if range.contains(&value) {
+        Ok(())
+    } else {
+        Err(format!("{} must be between {} and {}, got {}", field, range.start(), range.end(), value).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> DbInstance {
+        db::test_utils::call_graph_db("default")
+    }
+
+    #[test]
+    fn test_trace_with_defaults() {
+        let response = trace(&db(), TraceRequest::new("MyApp.Controller", "index")).unwrap();
+        assert!(matches!(response.direction, TraceDirection::Forward));
+        assert_eq!(response.max_depth, DEFAULT_DEPTH);
+        assert_eq!(response.entries[0].module, "MyApp.Controller");
+        assert!(response.entries.iter().any(|e| e.module == "MyApp.Accounts" && e.function == "list_users"));
+    }
+
+    #[test]
+    fn test_requests_outside_command_ranges_fail() {
+        let request = TraceRequest {
+            depth: 0,
+            ..TraceRequest::new("MyApp.Controller", "index")
+        };
+        assert_eq!(trace(&db(), request).unwrap_err().to_string(), "depth must be between 1 and 20, got 0");
+
+        let request = HotspotsRequest {
+            limit: 5000,
+            ..Default::default()
+        };
+        assert!(hotspots(&db(), request).is_err());
+    }
+
+    // Repo.insert is only called from a test module
+    #[test]
+    fn test_reverse_trace_scope_in_both_engines() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Repo": {
+                    "insert/1:5": {"name": "insert", "arity": 1, "file": "lib/repo.ex", "kind": "def", "line": 5, "start_line": 5, "end_line": 6}
+                },
+                "MyApp.RepoTest": {
+                    "test_insert/0:3": {"name": "test_insert", "arity": 0, "file": "test/repo_test.exs", "kind": "def", "line": 3, "start_line": 3, "end_line": 5}
+                }
+            },
+            "calls": [
+                {"caller": {"module": "MyApp.RepoTest", "function": "test_insert/0", "file": "test/repo_test.exs", "line": 4}, "type": "remote", "callee": {"module": "MyApp.Repo", "function": "insert", "arity": 1}}
+            ]
+        }"#;
+        let db = db::test_utils::setup_test_db(json, "default");
+        for engine in [Engine::Datalog, Engine::Memory] {
+            let callers = |test_scope: TestScope| {
+                let request = ReverseTraceRequest {
+                    engine,
+                    scope: Scope { test_scope, ..Scope::default() },
+                    ..ReverseTraceRequest::new("MyApp.Repo", "insert")
+                };
+                reverse_trace(&db, request).unwrap().total_items
+            };
+            assert_eq!(callers(TestScope::All), 1, "{engine:?}");
+            assert_eq!(callers(TestScope::Exclude), 0, "{engine:?}");
+            assert_eq!(callers(TestScope::Only), 1, "{engine:?}");
+        }
+    }
+
+    #[test]
+    fn test_reverse_trace_path_and_hotspots() {
+        let db = db();
+        let callers = reverse_trace(&db, ReverseTraceRequest::new("MyApp.Accounts", "list_users")).unwrap();
+        assert!(callers.entries.iter().any(|e| e.module == "MyApp.Controller"));
+
+        let paths = path(&db, PathRequest::new("MyApp.Controller", "index", "MyApp.Accounts", "list_users")).unwrap();
+        assert_eq!(paths.strategy, "all");
+        assert!(!paths.paths.is_empty());
+
+        let hotspots = hotspots(&db, HotspotsRequest::default()).unwrap();
+        assert_eq!(hotspots.kind, "incoming");
+        assert_eq!(hotspots.total_items, hotspots.entries.len());
+    }
+}
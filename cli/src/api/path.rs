@@ -0,0 +1,237 @@
+//! Call paths between two functions.
+
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::{check_range, Engine, DEFAULT_LIMIT, DEFAULT_PROJECT};
+use db::queries::adjacency::cached_index;
+use db::queries::path::{find_frontier_edges, find_path_edges, find_paths, CallPath};
+use db::queries::path_algorithms::{bidirectional_shortest_path, k_shortest_paths, PathTarget};
+use db::DbInstance;
+
+/// How paths between the two functions are selected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PathStrategy {
+    /// Every path found up to the depth at which the target is first reached
+    #[default]
+    All,
+    /// The single cheapest path
+    Shortest,
+    /// Up to `limit` loopless paths, cheapest first
+    KShortest,
+    /// The single path with the fewest hops, searching from both ends
+    Bidirectional,
+}
+
+impl PathStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PathStrategy::All => "all",
+            PathStrategy::Shortest => "shortest",
+            PathStrategy::KShortest => "k-shortest",
+            PathStrategy::Bidirectional => "bidirectional",
+        }
+    }
+}
+
+/// A path search between two functions; the defaults are those of `path`.
+///
+/// Paths follow calls through every module of the project: no namespace,
+/// project or test scope applies.
+#[derive(Debug, Clone)]
+pub struct PathRequest {
+    pub from_module: String,
+    pub from_function: String,
+    pub from_arity: Option<i64>,
+    pub to_module: String,
+    pub to_function: String,
+    pub to_arity: Option<i64>,
+    pub project: String,
+    /// Maximum number of calls in a path (1-20)
+    pub depth: u32,
+    pub strategy: PathStrategy,
+    /// Weight calls by call-site count (shortest and k-shortest only)
+    pub weighted: bool,
+    /// Minimum number of call sites between a caller and callee for the call to be followed
+    pub min_weight: i64,
+    pub engine: Engine,
+    /// Also follow message edges (GenServer calls/casts, PubSub broadcasts)
+    pub include_messages: bool,
+    /// Maximum number of paths (1-1000)
+    pub limit: u32,
+}
+
+impl PathRequest {
+    /// Paths from `from_module.from_function` to `to_module.to_function`
+    pub fn new(
+        from_module: impl Into<String>,
+        from_function: impl Into<String>,
+        to_module: impl Into<String>,
+        to_function: impl Into<String>,
+    ) -> Self {
+        Self {
+            from_module: from_module.into(),
+            from_function: from_function.into(),
+            from_arity: None,
+            to_module: to_module.into(),
+            to_function: to_function.into(),
+            to_arity: None,
+            project: DEFAULT_PROJECT.to_string(),
+            depth: 10,
+            strategy: PathStrategy::default(),
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::default(),
+            include_messages: false,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+}
+
+/// Paths found between two functions
+#[derive(Debug, Default, Serialize)]
+pub struct PathResult {
+    pub from_module: String,
+    pub from_function: String,
+    pub to_module: String,
+    pub to_function: String,
+    pub max_depth: u32,
+    pub strategy: String,
+    pub paths: Vec<CallPath>,
+}
+
+pub type PathResponse = PathResult;
+
+/// Find call paths between two functions
+pub fn path(db: &DbInstance, request: PathRequest) -> Result<PathResponse, Box<dyn Error>> {
+    check_range("depth", request.depth, 1..=20)?;
+    check_range("limit", request.limit, 1..=1000)?;
+
+    let mut result = PathResult {
+        from_module: request.from_module.clone(),
+        from_function: request.from_function.clone(),
+        to_module: request.to_module.clone(),
+        to_function: request.to_function.clone(),
+        max_depth: request.depth,
+        strategy: request.strategy.as_str().to_string(),
+        ..Default::default()
+    };
+
+    let target = PathTarget {
+        module: &request.to_module,
+        function: &request.to_function,
+        arity: request.to_arity,
+    };
+
+    // The memory engine answers every query below from one adjacency index
+    let index = match request.engine {
+        Engine::Datalog => None,
+        Engine::Memory => Some(cached_index(db, &request.project)?),
+    };
+
+    let k = match request.strategy {
+        PathStrategy::All | PathStrategy::Bidirectional if request.weighted => {
+            return Err("--weighted requires --strategy shortest or k-shortest".into());
+        }
+        PathStrategy::All => {
+            result.paths = match &index {
+                Some(index) => index.find_paths(
+                    &request.from_module,
+                    &request.from_function,
+                    request.from_arity,
+                    &request.to_module,
+                    &request.to_function,
+                    request.to_arity,
+                    request.depth,
+                    request.limit,
+                    request.include_messages,
+                    request.min_weight,
+                ),
+                None => find_paths(
+                    db,
+                    &request.from_module,
+                    &request.from_function,
+                    request.from_arity,
+                    &request.to_module,
+                    &request.to_function,
+                    request.to_arity,
+                    &request.project,
+                    request.depth,
+                    request.limit,
+                    request.include_messages,
+                    request.min_weight,
+                )?,
+            };
+            return Ok(result);
+        }
+        PathStrategy::Bidirectional => {
+            // Only the source's own calls come from the depth-bounded trace
+            let start_edges = match &index {
+                Some(index) => index.path_edges(
+                    &request.from_module,
+                    &request.from_function,
+                    request.from_arity,
+                    1,
+                    request.include_messages,
+                    request.min_weight,
+                ),
+                None => find_path_edges(
+                    db,
+                    &request.from_module,
+                    &request.from_function,
+                    request.from_arity,
+                    &request.project,
+                    1,
+                    request.include_messages,
+                    request.min_weight,
+                )?,
+            };
+            let path = bidirectional_shortest_path(
+                (&request.from_module, &request.from_function),
+                start_edges,
+                target,
+                request.depth,
+                |nodes, direction| match &index {
+                    Some(index) => Ok(index.frontier_edges(nodes, direction, request.include_messages, request.min_weight)),
+                    None => find_frontier_edges(
+                        db,
+                        &request.project,
+                        nodes,
+                        direction,
+                        request.include_messages,
+                        request.min_weight,
+                    ),
+                },
+            )?;
+            result.paths = path.into_iter().collect();
+            return Ok(result);
+        }
+        PathStrategy::Shortest => 1,
+        PathStrategy::KShortest => request.limit as usize,
+    };
+
+    let edges = match &index {
+        Some(index) => index.path_edges(
+            &request.from_module,
+            &request.from_function,
+            request.from_arity,
+            request.depth,
+            request.include_messages,
+            request.min_weight,
+        ),
+        None => find_path_edges(
+            db,
+            &request.from_module,
+            &request.from_function,
+            request.from_arity,
+            &request.project,
+            request.depth,
+            request.include_messages,
+            request.min_weight,
+        )?,
+    };
+    result.paths = k_shortest_paths(&edges, target, k, request.weighted);
+
+    Ok(result)
+}
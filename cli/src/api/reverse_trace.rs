@@ -0,0 +1,227 @@
+//! Reverse traces: what calls a function, transitively.
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use super::{check_range, Engine, ModuleFilter, Scope, DEFAULT_DEPTH, DEFAULT_LIMIT, DEFAULT_PROJECT};
+use db::query_builders::PatternMatching;
+use db::queries::adjacency::cached_index;
+use db::queries::reverse_trace::{reverse_trace_calls, ReverseTraceStep};
+use db::types::{TraceDirection, TraceEntry, TraceResult};
+use db::DbInstance;
+
+/// A reverse trace to a target function; the defaults are those of `reverse-trace`
+#[derive(Debug, Clone)]
+pub struct ReverseTraceRequest {
+    /// Target module name (exact match, or pattern with `regex`)
+    pub module: String,
+    /// Target function name (exact match, or pattern with `regex`)
+    pub function: String,
+    pub arity: Option<i64>,
+    /// Calls to follow back from the target (1-20)
+    pub depth: u32,
+    pub project: String,
+    pub regex: bool,
+    /// Maximum number of calls (1-1000)
+    pub limit: u32,
+    pub engine: Engine,
+    /// Skip calls in conditional branches and comprehensions
+    pub certain_only: bool,
+    /// Modules the trace starts in (namespaces) and passes through
+    pub scope: Scope,
+}
+
+impl ReverseTraceRequest {
+    pub fn new(module: impl Into<String>, function: impl Into<String>) -> Self {
+        Self {
+            module: module.into(),
+            function: function.into(),
+            arity: None,
+            depth: DEFAULT_DEPTH,
+            project: DEFAULT_PROJECT.to_string(),
+            regex: false,
+            limit: DEFAULT_LIMIT,
+            engine: Engine::default(),
+            certain_only: false,
+            scope: Scope::default(),
+        }
+    }
+}
+
+/// The target followed by every function reaching it, each pointing at the
+/// function it calls through `parent_index`
+pub type ReverseTraceResponse = TraceResult;
+
+/// Build a flattened reverse-trace from ReverseTraceStep objects
+fn build_reverse_trace_result(
+    target_module: String,
+    target_function: String,
+    max_depth: u32,
+    steps: Vec<ReverseTraceStep>,
+) -> TraceResult {
+    let mut entries = Vec::new();
+    let mut entry_index_map: HashMap<(String, String, i64, i64), usize> = HashMap::new();
+
+    if steps.is_empty() {
+        return TraceResult::empty(target_module, target_function, max_depth, TraceDirection::Backward);
+    }
+
+    // Group steps by depth
+    let mut by_depth: HashMap<i64, Vec<&ReverseTraceStep>> = HashMap::new();
+    for step in &steps {
+        by_depth.entry(step.depth).or_default().push(step);
+    }
+
+    // Process depth 1 (direct callers of target function)
+    if let Some(depth1_steps) = by_depth.get(&1) {
+        for step in depth1_steps {
+            let caller_key = (
+                step.caller_module.clone(),
+                step.caller_function.clone(),
+                step.caller_arity,
+                1i64,
+            );
+
+            // Add caller as root entry if not already added (use HashMap for dedup check)
+            if !entry_index_map.contains_key(&caller_key) {
+                let entry_idx = entries.len();
+                // Insert into HashMap before pushing (reuse caller_key)
+                entry_index_map.insert(caller_key.clone(), entry_idx);
+
+                entries.push(TraceEntry {
+                    module: caller_key.0,
+                    function: caller_key.1,
+                    arity: caller_key.2,
+                    kind: step.caller_kind.clone(),
+                    start_line: step.caller_start_line,
+                    end_line: step.caller_end_line,
+                    file: step.file.clone(),
+                    depth: 1,
+                    line: step.line,
+                    parent_index: None,
+                });
+            }
+        }
+    }
+
+    // Process deeper levels (additional callers)
+    for depth in 2..=max_depth as i64 {
+        if let Some(depth_steps) = by_depth.get(&depth) {
+            for step in depth_steps {
+                let caller_key = (
+                    step.caller_module.clone(),
+                    step.caller_function.clone(),
+                    step.caller_arity,
+                    depth,
+                );
+
+                // Check if we already have this caller at this depth using HashMap
+                if !entry_index_map.contains_key(&caller_key) {
+                    // Find parent index using HashMap (O(1) lookup)
+                    let parent_key = (
+                        step.callee_module.clone(),
+                        step.callee_function.clone(),
+                        step.callee_arity,
+                        depth - 1,
+                    );
+                    let parent_index = entry_index_map.get(&parent_key).copied();
+
+                    if parent_index.is_some() {
+                        let entry_idx = entries.len();
+                        // Insert into HashMap before pushing (reuse caller_key)
+                        entry_index_map.insert(caller_key.clone(), entry_idx);
+
+                        entries.push(TraceEntry {
+                            module: caller_key.0,
+                            function: caller_key.1,
+                            arity: caller_key.2,
+                            kind: step.caller_kind.clone(),
+                            start_line: step.caller_start_line,
+                            end_line: step.caller_end_line,
+                            file: step.file.clone(),
+                            depth,
+                            line: step.line,
+                            parent_index,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let total_items = entries.len();
+
+    TraceResult {
+        module: target_module,
+        function: target_function,
+        max_depth,
+        direction: TraceDirection::Backward,
+        total_items,
+        entries,
+    }
+}
+
+/// Trace the callers of a function, up to `depth` calls back
+pub fn reverse_trace(db: &DbInstance, request: ReverseTraceRequest) -> Result<ReverseTraceResponse, Box<dyn Error>> {
+    check_range("depth", request.depth, 1..=20)?;
+    check_range("limit", request.limit, 1..=1000)?;
+    let settings = request.scope.settings();
+
+    let matching = PatternMatching::new(request.regex, [request.module.as_str(), request.function.as_str()]);
+    let (module, function) = (matching.pattern(&request.module), matching.pattern(&request.function));
+    let use_regex = matching.use_regex();
+
+    let steps = match request.engine {
+        Engine::Datalog => reverse_trace_calls(
+            db,
+            &module,
+            &function,
+            request.arity,
+            &request.project,
+            use_regex,
+            request.depth,
+            request.limit,
+            request.certain_only,
+            &settings,
+        )?,
+        Engine::Memory => {
+            // Like the query, the project and test scope apply to every caller
+            let filter = ModuleFilter::new(db, &request.project, &settings)?;
+            cached_index(db, &request.project)?.reverse_trace_calls(
+                &module,
+                &function,
+                request.arity,
+                use_regex,
+                request.depth,
+                request.limit,
+                request.certain_only,
+                |module| filter.keeps(module),
+                &settings,
+            )?
+        }
+    };
+
+    Ok(build_reverse_trace_result(
+        request.module,
+        request.function,
+        request.depth,
+        steps,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_reverse_trace() {
+        let result = build_reverse_trace_result(
+            "TestModule".to_string(),
+            "test_func".to_string(),
+            5,
+            vec![],
+        );
+        assert_eq!(result.total_items, 0);
+        assert_eq!(result.entries.len(), 0);
+    }
+}
@@ -0,0 +1,244 @@
+//! Forward traces: what a function calls, transitively.
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use super::{check_range, Engine, ModuleFilter, Scope, DEFAULT_DEPTH, DEFAULT_LIMIT, DEFAULT_PROJECT};
+use db::query_builders::PatternMatching;
+use db::queries::adjacency::cached_index;
+use db::queries::trace::trace_calls;
+use db::types::{Call, TraceDirection, TraceEntry, TraceResult};
+use db::DbInstance;
+
+/// A trace from a starting function; the defaults are those of `trace`
+#[derive(Debug, Clone)]
+pub struct TraceRequest {
+    /// Starting module name (exact match, or pattern with `regex`)
+    pub module: String,
+    /// Starting function name (exact match, or pattern with `regex`)
+    pub function: String,
+    pub arity: Option<i64>,
+    /// Calls to follow from the start (1-20)
+    pub depth: u32,
+    pub project: String,
+    pub regex: bool,
+    /// Maximum number of calls (1-1000)
+    pub limit: u32,
+    pub engine: Engine,
+    /// Also follow message edges (GenServer calls/casts, PubSub broadcasts)
+    pub include_messages: bool,
+    /// Minimum number of call sites between a caller and callee for the call to be followed
+    pub min_weight: i64,
+    /// Skip calls in conditional branches and comprehensions
+    pub certain_only: bool,
+    /// Modules the trace starts in (namespaces) and passes through
+    pub scope: Scope,
+}
+
+impl TraceRequest {
+    pub fn new(module: impl Into<String>, function: impl Into<String>) -> Self {
+        Self {
+            module: module.into(),
+            function: function.into(),
+            arity: None,
+            depth: DEFAULT_DEPTH,
+            project: DEFAULT_PROJECT.to_string(),
+            regex: false,
+            limit: DEFAULT_LIMIT,
+            engine: Engine::default(),
+            include_messages: false,
+            min_weight: 1,
+            certain_only: false,
+            scope: Scope::default(),
+        }
+    }
+}
+
+/// The start function followed by every function it reaches, each pointing
+/// at its caller through `parent_index`
+pub type TraceResponse = TraceResult;
+
+fn build_trace_result(
+    start_module: String,
+    start_function: String,
+    max_depth: u32,
+    calls: Vec<Call>,
+) -> TraceResult {
+    let mut entries = Vec::new();
+    let mut entry_index_map: HashMap<(String, String, i64, i64), usize> = HashMap::new();
+
+    // Add the starting function as the root entry at depth 0
+    entries.push(TraceEntry {
+        module: start_module.clone(),
+        function: start_function.clone(),
+        arity: 0, // Will be updated from first call if available
+        kind: String::new(),
+        start_line: 0,
+        end_line: 0,
+        file: String::new(),
+        depth: 0,
+        line: 0,
+        parent_index: None,
+    });
+    entry_index_map.insert((start_module.clone(), start_function.clone(), 0, 0), 0);
+
+    if calls.is_empty() {
+        return TraceResult::empty(start_module, start_function, max_depth, TraceDirection::Forward);
+    }
+
+    // Group calls by depth, consuming the Vec to take ownership
+    let mut by_depth: HashMap<i64, Vec<Call>> = HashMap::new();
+    for call in calls {
+        if let Some(depth) = call.depth {
+            by_depth.entry(depth).or_default().push(call);
+        }
+    }
+
+    // Process depth 1 (direct callees from start function)
+    if let Some(depth1_calls) = by_depth.remove(&1) {
+        // Track seen entries by index into entries vec (avoids storing strings)
+        let mut seen_at_depth: std::collections::HashSet<usize> = std::collections::HashSet::new();
+
+        for call in depth1_calls {
+            // Check if we already have this callee at this depth
+            let existing = entries.iter().position(|e| {
+                e.depth == 1
+                    && e.module == call.callee.module.as_ref()
+                    && e.function == call.callee.name.as_ref()
+                    && e.arity == call.callee.arity
+            });
+
+            if (existing.is_none() || seen_at_depth.insert(existing.unwrap_or(usize::MAX)))
+                && existing.is_none() {
+                    let entry_idx = entries.len();
+                    // Convert from Rc<str> to String for storage
+                    let module = call.callee.module.to_string();
+                    let function = call.callee.name.to_string();
+                    let arity = call.callee.arity;
+                    entry_index_map.insert((module.clone(), function.clone(), arity, 1i64), entry_idx);
+                    entries.push(TraceEntry {
+                        module,
+                        function,
+                        arity,
+                        kind: call.callee.kind.as_deref().unwrap_or("").to_string(),
+                        start_line: call.callee.start_line.unwrap_or(0),
+                        end_line: call.callee.end_line.unwrap_or(0),
+                        file: call.callee.file.as_deref().unwrap_or("").to_string(),
+                        depth: 1,
+                        line: call.line,
+                        parent_index: Some(0),
+                    });
+                }
+        }
+    }
+
+    // Process deeper levels
+    for depth in 2..=max_depth as i64 {
+        if let Some(depth_calls) = by_depth.remove(&depth) {
+            for call in depth_calls {
+                // Check if we already have this callee at this depth using HashMap
+                let callee_key = (
+                    call.callee.module.to_string(),
+                    call.callee.name.to_string(),
+                    call.callee.arity,
+                    depth,
+                );
+
+                if !entry_index_map.contains_key(&callee_key) {
+                    // Find parent index using HashMap (O(1) lookup)
+                    let parent_key = (
+                        call.caller.module.to_string(),
+                        call.caller.name.to_string(),
+                        call.caller.arity,
+                        depth - 1,
+                    );
+                    let parent_index = entry_index_map.get(&parent_key).copied();
+
+                    if parent_index.is_some() {
+                        let entry_idx = entries.len();
+                        // Insert into HashMap before pushing (reuse callee_key)
+                        entry_index_map.insert(callee_key.clone(), entry_idx);
+
+                        // Convert from Rc<str> to String for storage
+                        entries.push(TraceEntry {
+                            module: callee_key.0,
+                            function: callee_key.1,
+                            arity: callee_key.2,
+                            kind: call.callee.kind.as_deref().unwrap_or("").to_string(),
+                            start_line: call.callee.start_line.unwrap_or(0),
+                            end_line: call.callee.end_line.unwrap_or(0),
+                            file: call.callee.file.as_deref().unwrap_or("").to_string(),
+                            depth,
+                            line: call.line,
+                            parent_index,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let total_items = entries.len() - 1; // Exclude the root entry from count
+
+    TraceResult {
+        module: start_module,
+        function: start_function,
+        max_depth,
+        direction: TraceDirection::Forward,
+        total_items,
+        entries,
+    }
+}
+
+/// Trace the calls made from a function, up to `depth` calls deep
+pub fn trace(db: &DbInstance, request: TraceRequest) -> Result<TraceResponse, Box<dyn Error>> {
+    check_range("depth", request.depth, 1..=20)?;
+    check_range("limit", request.limit, 1..=1000)?;
+    let settings = request.scope.settings();
+
+    let matching = PatternMatching::new(request.regex, [request.module.as_str(), request.function.as_str()]);
+    let (module, function) = (matching.pattern(&request.module), matching.pattern(&request.function));
+    let use_regex = matching.use_regex();
+
+    let calls = match request.engine {
+        Engine::Datalog => trace_calls(
+            db,
+            &module,
+            &function,
+            request.arity,
+            &request.project,
+            use_regex,
+            request.depth,
+            request.limit,
+            request.include_messages,
+            request.min_weight,
+            request.certain_only,
+            &settings,
+        )?,
+        Engine::Memory => {
+            // The index holds every edge; the filter resolves --only-project
+            // and the test scope to the callee modules a chain may reach
+            let filter = ModuleFilter::new(db, &request.project, &settings)?;
+            cached_index(db, &request.project)?.trace_calls(
+                &module,
+                &function,
+                request.arity,
+                use_regex,
+                request.depth,
+                request.limit,
+                request.include_messages,
+                request.min_weight,
+                request.certain_only,
+                |module| filter.keeps(module),
+                &settings,
+            )?
+        }
+    };
+
+    Ok(build_trace_result(
+        request.module,
+        request.function,
+        request.depth,
+        calls,
+    ))
+}
@@ -0,0 +1,220 @@
+//! Baseline files for suppressing pre-existing findings.
+//!
+//! A baseline records the findings of a command at one point in time.
+//! Later runs with the same baseline only report findings that are not in it,
+//! which makes it possible to adopt strict checks on a legacy codebase.
+//!
+//! Findings are identified by a stable key (e.g. `MyApp.Accounts.get_user/1`)
+//! that does not include line numbers, so unrelated edits do not resurface them.
+
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+/// Baseline arguments shared by commands that report findings.
+///
+/// Use `#[command(flatten)]` to include these in a command struct.
+#[derive(Args, Debug, Clone, Default)]
+pub struct BaselineArgs {
+    /// Baseline file; findings recorded in it are not reported
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Record the current findings into the --baseline file
+    #[arg(long, requires = "baseline", default_value_t = false)]
+    pub write_baseline: bool,
+}
+
+impl BaselineArgs {
+    /// Filter findings against the baseline, or record them when `--write-baseline` is set.
+    ///
+    /// Without `--baseline` the findings are returned unchanged. When writing,
+    /// all current findings are recorded and returned.
+    pub fn apply<T>(
+        &self,
+        command: &str,
+        findings: Vec<T>,
+        key: impl Fn(&T) -> String,
+    ) -> Result<Vec<T>, Box<dyn Error>> {
+        let Some(path) = &self.baseline else {
+            return Ok(findings);
+        };
+
+        if self.write_baseline {
+            Baseline::new(command, findings.iter().map(&key)).write(path)?;
+            return Ok(findings);
+        }
+
+        let baseline = Baseline::load(path, command)?;
+        Ok(findings
+            .into_iter()
+            .filter(|finding| !baseline.contains(&key(finding)))
+            .collect())
+    }
+
+    /// Row limit for a command's query: none with a baseline, since filtering
+    /// and recording must see every finding, not only the first `limit`
+    pub fn query_limit(&self, limit: u32) -> u32 {
+        if self.baseline.is_some() {
+            u32::MAX
+        } else {
+            limit
+        }
+    }
+
+    /// [`apply`](Self::apply) to findings queried with [`query_limit`](Self::query_limit),
+    /// then keep the first `limit` of them
+    pub fn apply_limited<T>(
+        &self,
+        command: &str,
+        findings: Vec<T>,
+        key: impl Fn(&T) -> String,
+        limit: u32,
+    ) -> Result<Vec<T>, Box<dyn Error>> {
+        let mut findings = self.apply(command, findings, key)?;
+        findings.truncate(limit as usize);
+        Ok(findings)
+    }
+}
+
+/// Contents of a baseline file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Command that produced the baseline
+    pub command: String,
+    /// Keys of the recorded findings (sorted for stable diffs)
+    pub findings: BTreeSet<String>,
+}
+
+impl Baseline {
+    pub fn new(command: &str, keys: impl Iterator<Item = String>) -> Self {
+        Self {
+            command: command.to_string(),
+            findings: keys.collect(),
+        }
+    }
+
+    /// Load a baseline file, verifying it was written by `command`
+    pub fn load(path: &Path, command: &str) -> Result<Self, Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read baseline '{}': {}", path.display(), e))?;
+        let baseline: Baseline = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse baseline '{}': {}", path.display(), e))?;
+
+        if baseline.command != command {
+            return Err(format!(
+                "Baseline '{}' was written by '{}', not '{}'",
+                path.display(),
+                baseline.command,
+                command
+            )
+            .into());
+        }
+
+        Ok(baseline)
+    }
+
+    /// Write the baseline as pretty-printed JSON
+    pub fn write(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json + "\n")
+            .map_err(|e| format!("Failed to write baseline '{}': {}", path.display(), e))?;
+        Ok(())
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.findings.contains(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn args(path: &Path, write: bool) -> BaselineArgs {
+        BaselineArgs {
+            baseline: Some(path.to_path_buf()),
+            write_baseline: write,
+        }
+    }
+
+    #[test]
+    fn test_apply_without_baseline_returns_all() {
+        let findings = vec!["a", "b"];
+        let result = BaselineArgs::default()
+            .apply("unused", findings, |f| f.to_string())
+            .unwrap();
+        assert_eq!(result, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_write_then_filter_reports_only_new() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("baseline.json");
+
+        let written = args(&path, true)
+            .apply("unused", vec!["a", "b"], |f| f.to_string())
+            .unwrap();
+        assert_eq!(written, vec!["a", "b"]);
+
+        let filtered = args(&path, false)
+            .apply("unused", vec!["a", "b", "c"], |f| f.to_string())
+            .unwrap();
+        assert_eq!(filtered, vec!["c"]);
+    }
+
+    #[test]
+    fn test_limit_applies_after_the_baseline() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("baseline.json");
+
+        assert_eq!(BaselineArgs::default().query_limit(2), 2);
+        assert_eq!(args(&path, true).query_limit(2), u32::MAX);
+
+        // Every finding is recorded, only the first two are reported
+        let written = args(&path, true)
+            .apply_limited("unused", vec!["a", "b", "c"], |f| f.to_string(), 2)
+            .unwrap();
+        assert_eq!(written, vec!["a", "b"]);
+        assert_eq!(Baseline::load(&path, "unused").unwrap().findings.len(), 3);
+
+        let filtered = args(&path, false)
+            .apply_limited("unused", vec!["a", "b", "c", "d", "e", "f"], |f| f.to_string(), 2)
+            .unwrap();
+        assert_eq!(filtered, vec!["d", "e"]);
+    }
+
+    #[test]
+    fn test_baseline_file_is_sorted_json() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("baseline.json");
+
+        Baseline::new("cycles", vec!["b".to_string(), "a".to_string()].into_iter())
+            .write(&path)
+            .unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("\"command\": \"cycles\""));
+        assert!(content.find("\"a\"").unwrap() < content.find("\"b\"").unwrap());
+    }
+
+    #[test]
+    fn test_load_rejects_other_command() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("baseline.json");
+        Baseline::new("cycles", std::iter::empty()).write(&path).unwrap();
+
+        let err = Baseline::load(&path, "unused").unwrap_err();
+        assert!(err.to_string().contains("written by 'cycles'"));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let err = Baseline::load(Path::new("/nonexistent/baseline.json"), "unused").unwrap_err();
+        assert!(err.to_string().contains("Failed to read baseline"));
+    }
+}
@@ -0,0 +1,209 @@
+//! Last author and commit of findings, from `git blame`.
+//!
+//! Commands reporting findings take `--blame` to annotate each one with the
+//! most recent commit touching its line range, which helps route cleanup work
+//! to whoever changed the code last. Files are resolved relative to the
+//! directory the command runs in, normally the project root; findings in files
+//! git does not track are left without blame.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Command;
+
+use db::types::ModuleCollectionResult;
+use serde::Serialize;
+
+use crate::sort::SortSpec;
+use crate::utils::format_date;
+
+/// The most recent commit touching a line range
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlameInfo {
+    pub author: String,
+    pub commit: String,
+    /// Author date of the commit, `YYYY-MM-DD` (UTC)
+    pub date: String,
+}
+
+impl fmt::Display for BlameInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let commit = self.commit.get(..7).unwrap_or(&self.commit);
+        write!(f, "last changed by {} on {} ({})", self.author, self.date, commit)
+    }
+}
+
+/// Runs `git blame` for line ranges of the files of one working tree
+pub struct Blamer {
+    root: PathBuf,
+    cache: HashMap<(String, i64, i64), Option<BlameInfo>>,
+}
+
+impl Blamer {
+    /// Blame files relative to `root`, which must be inside a git working tree
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, Box<dyn Error>> {
+        let root = root.into();
+        let inside = Command::new("git")
+            .arg("-C")
+            .arg(&root)
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .output()?;
+        if !inside.status.success() {
+            return Err("--blame needs to run inside a git working tree".into());
+        }
+        Ok(Self {
+            root,
+            cache: HashMap::new(),
+        })
+    }
+
+    /// Most recent commit touching lines `start..=end` of a file, if git knows it
+    pub fn blame(&mut self, file: &str, start: i64, end: i64) -> Option<BlameInfo> {
+        let start = start.max(1);
+        let end = end.max(start);
+        let key = (file.to_string(), start, end);
+        if let Some(cached) = self.cache.get(&key) {
+            return cached.clone();
+        }
+
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(["blame", "--porcelain", "-L", &format!("{},{}", start, end), "--", file])
+            .output()
+            .ok()
+            .filter(|output| output.status.success());
+        let info = output.and_then(|output| latest_commit(&String::from_utf8_lossy(&output.stdout)));
+        self.cache.insert(key, info.clone());
+        info
+    }
+}
+
+/// Sort a module-grouped result for `--sort` and blame its entries for `--blame`.
+///
+/// Each entry is blamed in its group's file by the line range `lines` gives,
+/// and handed its blame through `set`. Blame runs git per entry, so only the
+/// entries the sort keeps are blamed, unless it sorts by a blame field.
+pub fn sort_and_blame<E: Serialize>(
+    result: &mut ModuleCollectionResult<E>,
+    sort: Option<&SortSpec>,
+    limit: u32,
+    blamer: Option<Blamer>,
+    lines: impl Fn(&E) -> (i64, i64),
+    set: impl Fn(&mut E, Option<BlameInfo>),
+) -> Result<(), Box<dyn Error>> {
+    let sorts_by_blame = sort.is_some_and(|sort| sort.sorts_by("blame"));
+    if let Some(sort) = sort.filter(|_| !sorts_by_blame) {
+        sort.sort_groups(result, limit)?;
+    }
+    if let Some(mut blamer) = blamer {
+        for group in &mut result.items {
+            for entry in &mut group.entries {
+                let (start, end) = lines(entry);
+                set(entry, blamer.blame(&group.file, start, end));
+            }
+        }
+    }
+    if let Some(sort) = sort.filter(|_| sorts_by_blame) {
+        sort.sort_groups(result, limit)?;
+    }
+    Ok(())
+}
+
+/// The commit with the latest author time in `git blame --porcelain` output
+fn latest_commit(porcelain: &str) -> Option<BlameInfo> {
+    let mut authors: HashMap<&str, &str> = HashMap::new();
+    let mut times: HashMap<&str, i64> = HashMap::new();
+    let mut commit = "";
+    for line in porcelain.lines() {
+        if line.starts_with('\t') {
+            continue;
+        }
+        if let Some(author) = line.strip_prefix("author ") {
+            authors.insert(commit, author);
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            times.insert(commit, time.parse().unwrap_or(0));
+        } else if let Some(sha) = line.split(' ').next().filter(|sha| is_sha(sha)) {
+            commit = sha;
+        }
+    }
+
+    let (commit, time) = times.into_iter().max_by_key(|&(commit, time)| (time, commit))?;
+    Some(BlameInfo {
+        author: authors.get(commit).unwrap_or(&"").to_string(),
+        commit: commit.to_string(),
+        date: format_date(time * 1000),
+    })
+}
+
+fn is_sha(word: &str) -> bool {
+    word.len() == 40 && word.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORCELAIN: &str = "\
+1111111111111111111111111111111111111111 3 3 1
+author Ada
+author-mail <ada@example.com>
+author-time 1700000000
+author-tz +0000
+summary Add get_user
+filename lib/accounts.ex
+\tdef get_user(id) do
+2222222222222222222222222222222222222222 4 4 1
+author Grace
+author-time 1760000000
+summary Fix lookup
+filename lib/accounts.ex
+\t  Repo.get(User, id)
+1111111111111111111111111111111111111111 5 5
+\tend
+";
+
+    #[test]
+    fn test_latest_commit_picks_most_recent_change() {
+        assert_eq!(
+            latest_commit(PORCELAIN),
+            Some(BlameInfo {
+                author: "Grace".to_string(),
+                commit: "2222222222222222222222222222222222222222".to_string(),
+                date: "2025-10-09".to_string(),
+            })
+        );
+        assert_eq!(latest_commit(""), None);
+    }
+
+    #[test]
+    fn test_blame_info_display() {
+        let info = latest_commit(PORCELAIN).unwrap();
+        assert_eq!(info.to_string(), "last changed by Grace on 2025-10-09 (2222222)");
+    }
+
+    #[test]
+    fn test_blamer_outside_git_tree() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(Blamer::open(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_blamer_in_git_tree() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git").arg("-C").arg(dir.path()).args(args).output().unwrap();
+        };
+        git(&["init", "-q"]);
+        std::fs::write(dir.path().join("a.ex"), "one\ntwo\nthree\n").unwrap();
+        git(&["add", "a.ex"]);
+        git(&["-c", "user.name=Ada", "-c", "user.email=ada@example.com", "commit", "-q", "-m", "Add a"]);
+
+        let mut blamer = Blamer::open(dir.path()).unwrap();
+        let info = blamer.blame("a.ex", 2, 3).unwrap();
+        assert_eq!(info.author, "Ada");
+        assert_eq!(info.commit.len(), 40);
+        assert_eq!(blamer.blame("missing.ex", 1, 1), None);
+    }
+}
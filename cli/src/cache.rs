@@ -0,0 +1,66 @@
+//! Cache keys for `--cache`.
+//!
+//! A key captures everything a cacheable command's output depends on besides
+//! the database: the parsed command with its arguments, the output format,
+//! the project and test scopes, whether rows are sorted and the default config
+//! file, whose rules commands such as entry-points and layers apply.
+
+use std::path::Path;
+
+use db::query_builders::TestScope;
+
+use crate::commands::Command;
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::output::OutputFormat;
+
+/// Cache key of a command run with the given format and scopes
+pub fn key(command: &Command, format: OutputFormat, only_project: bool, tests: TestScope, sorted: bool) -> String {
+    let config = std::fs::read_to_string(Path::new(DEFAULT_CONFIG_PATH)).unwrap_or_default();
+    format!(
+        "{:?}\nformat={:?} only_project={} tests={:?} sorted={}\n{}",
+        command, format, only_project, tests, sorted, config
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn command(args: &[&str]) -> Command {
+        let mut argv = vec!["code_search"];
+        argv.extend(args);
+        crate::cli::Args::parse_from(argv).command
+    }
+
+    #[test]
+    fn test_key_depends_on_arguments_format_and_scope() {
+        let hotspots = command(&["hotspots", "MyApp"]);
+        let base = key(&hotspots, OutputFormat::Table, false, TestScope::All, true);
+
+        assert_eq!(base, key(&command(&["hotspots", "MyApp"]), OutputFormat::Table, false, TestScope::All, true));
+        assert_ne!(base, key(&command(&["hotspots", "MyApp", "-l", "5"]), OutputFormat::Table, false, TestScope::All, true));
+        assert_ne!(base, key(&hotspots, OutputFormat::Json, false, TestScope::All, true));
+        assert_ne!(base, key(&hotspots, OutputFormat::Table, true, TestScope::All, true));
+        assert_ne!(base, key(&hotspots, OutputFormat::Table, false, TestScope::Exclude, true));
+        assert_ne!(base, key(&hotspots, OutputFormat::Table, false, TestScope::All, false));
+    }
+
+    #[test]
+    fn test_cacheable_commands() {
+        assert!(command(&["hotspots"]).cacheable());
+        assert!(command(&["unused"]).cacheable());
+        assert!(!command(&["unused", "--baseline", "unused.json"]).cacheable());
+        assert!(!command(&["unused", "--blame"]).cacheable());
+        assert!(!command(&["complexity", "--blame"]).cacheable());
+        assert!(!command(&["large-functions", "--blame"]).cacheable());
+        assert!(command(&["large-functions"]).cacheable());
+        assert!(!command(&["layers", "--config", "layers.json"]).cacheable());
+        assert!(!command(&["annotate", "MyApp.Accounts", "-m", "Owned by identity"]).cacheable());
+        assert!(command(&["annotate", "MyApp.Accounts", "-m", "Owned by identity"]).invalidates_cache());
+        assert!(!command(&["hotspots"]).invalidates_cache());
+        assert!(!command(&["daemon"]).cacheable());
+        assert!(!command(&["daemon"]).batchable());
+        assert!(!command(&["indexes", "--rebuild"]).batchable());
+    }
+}
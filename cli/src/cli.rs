@@ -4,10 +4,16 @@
 //! Individual command definitions are in the `commands` module.
 
 use clap::Parser;
-use std::path::PathBuf;
+use db::generated::GeneratedRules;
+use db::queries::cache::{bump_generation, cached_output, store_output};
+use db::query_builders::{QuerySettings, TestScope};
+use db::DbInstance;
+use std::error::Error;
+use std::path::{Path, PathBuf};
 
-use crate::commands::Command;
-use crate::output::OutputFormat;
+use crate::commands::{CheckFailed, Command, CommandRunner};
+use crate::config::{Config, DEFAULT_CONFIG_PATH};
+use crate::output::{self, OutputFormat};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -25,10 +31,200 @@ pub struct Args {
     #[arg(short = 'o', long, value_enum, default_value_t = OutputFormat::Table, global = true)]
     pub format: OutputFormat,
 
+    /// Only output these columns (comma-separated, e.g. module,function,arity,file,line)
+    ///
+    /// Applies to the table, csv, json and toon formats. Entries are flattened
+    /// into rows; nested fields use dotted names (caller.module).
+    #[arg(long, value_delimiter = ',', global = true)]
+    pub fields: Vec<String>,
+
+    /// Leave stdlib and dependency modules out of trace, hotspots and unused
+    ///
+    /// Dependency modules are those imported from vendored sources (deps/).
+    /// Defaults to `only_project` in .code_search/config.json.
+    #[arg(long, global = true, conflicts_with = "include_deps")]
+    pub only_project: bool,
+
+    /// Include stdlib and dependency modules, overriding `only_project` in the config
+    #[arg(long, global = true)]
+    pub include_deps: bool,
+
+    /// Leave test modules out of trace, hotspots and unused
+    ///
+    /// Test modules are those imported from test/ directories or _test.exs
+    /// files, or matching `import.test_paths` in the config. Calls from tests
+    /// are not counted either, so unused lists functions only tests call.
+    #[arg(long, global = true, conflicts_with = "only_tests")]
+    pub exclude_tests: bool,
+
+    /// Restrict trace, hotspots and unused to test modules
+    #[arg(long, global = true)]
+    pub only_tests: bool,
+
+    /// Skip ordering the rows of list queries, for the highest throughput
+    ///
+    /// By default list output is ordered by module, function and arity (or
+    /// the command's ranking), the same on every run and storage backend.
+    /// Without sorting, rows come in storage order; ranked queries keeping
+    /// their top rows (--limit) are still ordered.
+    #[arg(long, global = true)]
+    pub no_sort: bool,
+
+    /// Reuse the output of an identical earlier query until the next import
+    ///
+    /// Outputs are cached in the database, keyed by the command line, and
+    /// dropped by every command that changes the data (import, annotate,
+    /// restore, vacuum, index-embeddings). Defaults to `cache` in
+    /// .code_search/config.json.
+    #[arg(long, global = true, conflicts_with = "no_cache")]
+    pub cache: bool,
+
+    /// Always run the query, overriding `cache` in the config
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
     #[command(subcommand)]
     pub command: Command,
 }
 
+impl Args {
+    /// Parse the arguments of an invocation run by `batch` or `query run`.
+    ///
+    /// The invocation runs on the caller's database connection, so `--db` is
+    /// rejected, as are commands that cannot run inside another command.
+    pub fn parse_invocation(args: &[String]) -> Result<Self, String> {
+        let parsed = Self::try_parse_from(std::iter::once("code_search").chain(args.iter().map(String::as_str)))
+            .map_err(|e| e.to_string().trim_end().to_string())?;
+        if parsed.db.is_some() {
+            return Err("--db is not allowed here, the invocation uses the current database".to_string());
+        }
+        if let Command::Unknown(args) = &parsed.command {
+            return Err(format!("Unknown command: {}", args.first().map_or("", String::as_str)));
+        }
+        if !parsed.command.batchable() {
+            return Err(format!("'{}' cannot run inside another command", args[0]));
+        }
+        Ok(parsed)
+    }
+
+    /// Whether queries are restricted to the project's own modules.
+    ///
+    /// The flags win over the config file; an unreadable config counts as unset.
+    pub fn only_project(&self) -> bool {
+        if self.only_project || self.include_deps {
+            return self.only_project;
+        }
+        Config::load_or_default(Path::new(DEFAULT_CONFIG_PATH)).is_ok_and(|config| config.only_project)
+    }
+
+    /// How queries treat test modules, from `--exclude-tests` / `--only-tests`
+    pub fn test_scope(&self) -> TestScope {
+        if self.exclude_tests {
+            TestScope::Exclude
+        } else if self.only_tests {
+            TestScope::Only
+        } else {
+            TestScope::All
+        }
+    }
+
+    /// Rules telling generated functions apart, from `generated` in the config.
+    ///
+    /// An unreadable config counts as unset, as for [`Args::only_project`];
+    /// invalid patterns are an error.
+    pub fn generated_rules(&self) -> Result<GeneratedRules, Box<dyn std::error::Error>> {
+        let rules = Config::load_or_default(Path::new(DEFAULT_CONFIG_PATH))
+            .map(|config| config.generated)
+            .unwrap_or_default();
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    /// The scopes, ordering and generated-code rules the command's queries run with.
+    ///
+    /// The namespaces are the `--namespace` options of the command.
+    pub fn query_settings(&self) -> Result<QuerySettings, Box<dyn Error>> {
+        Ok(QuerySettings {
+            only_project: self.only_project(),
+            test_scope: self.test_scope(),
+            namespaces: self.command.common().map(|common| common.namespace.clone()).unwrap_or_default(),
+            sorted: !self.no_sort,
+            generated_rules: self.generated_rules()?,
+        })
+    }
+
+    /// Whether query outputs are cached, with the same precedence as [`Args::only_project`]
+    pub fn cache(&self) -> bool {
+        if self.cache || self.no_cache {
+            return self.cache;
+        }
+        Config::load_or_default(Path::new(DEFAULT_CONFIG_PATH)).is_ok_and(|config| config.cache)
+    }
+}
+
+/// What an invocation prints
+#[derive(Debug)]
+pub struct Outcome {
+    pub output: String,
+    /// `check` found violations: the output is its report and the process
+    /// exits with status 1
+    pub failed: bool,
+}
+
+/// Run an invocation on an open database.
+///
+/// Applies the project, test and namespace scopes, answers from the output
+/// cache when `--cache` allows it and selects the `--fields` columns. Used by
+/// the binary and by `daemon` for every request it serves.
+pub fn run(args: Args, db: &DbInstance) -> Result<Outcome, Box<dyn Error>> {
+    let settings = args.query_settings()?;
+    let cache = args.cache();
+
+    // Column selection works on the JSON form of any result
+    let select_columns = !args.fields.is_empty() && !matches!(args.format, OutputFormat::Github);
+    let run_format = if select_columns { OutputFormat::Json } else { args.format };
+    let finish = |output: String| -> Result<String, Box<dyn Error>> {
+        if !select_columns {
+            return Ok(output);
+        }
+        let value: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|_| "--fields is not supported by this command")?;
+        Ok(output::columns::render(&value, &args.fields, args.format)?)
+    };
+
+    let invalidates_cache = args.command.invalidates_cache();
+    let cache_key = (cache && args.command.cacheable()).then(|| {
+        crate::cache::key(&args.command, run_format, settings.only_project, settings.test_scope, settings.sorted)
+    });
+    let cached = match &cache_key {
+        Some(key) => cached_output(db, key)?,
+        None => None,
+    };
+
+    let result = match cached {
+        Some(output) => Ok(output),
+        None => args.command.run(db, run_format, &settings).and_then(|output| {
+            if let Some(key) = &cache_key {
+                store_output(db, key, &output)?;
+            }
+            Ok(output)
+        }),
+    };
+    // Even a failed import may have changed some of the data
+    if invalidates_cache {
+        bump_generation(db)?;
+    }
+
+    match result {
+        Ok(output) => Ok(Outcome { output: finish(output)?, failed: false }),
+        Err(e) => match e.downcast::<CheckFailed>() {
+            // Policy violations: print the report and signal failure via exit status
+            Ok(failed) => Ok(Outcome { output: finish(failed.report)?, failed: true }),
+            Err(e) => Err(e),
+        },
+    }
+}
+
 /// Resolve database path by checking multiple locations in order of preference
 pub fn resolve_db_path(explicit_path: Option<PathBuf>) -> PathBuf {
     // If explicitly specified, use that
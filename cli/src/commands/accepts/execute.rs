@@ -5,6 +5,7 @@ use serde::Serialize;
 use super::AcceptsCmd;
 use crate::commands::Execute;
 use db::queries::accepts::{find_accepts, AcceptsEntry};
+use db::query_builders::QuerySettings;
 use db::types::ModuleGroupResult;
 
 /// A function's input type information
@@ -47,14 +48,16 @@ fn build_accepts_result(
 impl Execute for AcceptsCmd {
     type Output = ModuleGroupResult<AcceptsInfo>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         let entries = find_accepts(
             db,
             &self.pattern,
+            self.returns.as_deref(),
             &self.common.project,
             self.common.regex,
             self.module.as_deref(),
             self.common.limit,
+            settings,
         )?;
 
         Ok(build_accepts_result(
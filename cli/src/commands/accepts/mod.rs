@@ -4,6 +4,7 @@ mod output;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
@@ -15,23 +16,34 @@ use crate::output::{OutputFormat, Outputable};
 Examples:
   code_search accepts \"User.t\"              # Find functions accepting User.t
   code_search accepts \"map()\"               # Find functions accepting maps
+  code_search accepts \"{:ok, _}\"            # Tuples of :ok and any type
   code_search accepts \"User.t\" MyApp        # Filter to module MyApp
+  code_search accepts \"Changeset.t\" --returns \"{:ok, _}\"  # Conversions
   code_search accepts -r \"list\\(.*\\)\"     # Regex pattern matching
 ")]
 pub struct AcceptsCmd {
-    /// Type pattern to search for in input types
+    /// Type pattern one of the parameters has to match
+    ///
+    /// Written like a type, with _ for any type: User.t, {:ok, _}, [User.t],
+    /// %{id: _}. Names match qualified (User.t matches MyApp.User.t()) and
+    /// unions match through any member. With --regex, a regex over the
+    /// input types instead.
     pub pattern: String,
 
     /// Module filter pattern
     pub module: Option<String>,
 
+    /// Type pattern the return type has to match as well
+    #[arg(long, value_name = "PATTERN")]
+    pub returns: Option<String>,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for AcceptsCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
@@ -0,0 +1,136 @@
+//! Oldest untouched complex functions and newest heavily used ones.
+
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::AgeCmd;
+use crate::commands::Execute;
+use db::query_builders::{compile_pattern, QuerySettings};
+use db::queries::timeline::{find_function_ages, FunctionAge};
+
+/// Result of the age command
+#[derive(Debug, Serialize)]
+pub struct AgeResult {
+    /// Time of the query in Unix seconds, for the ages in table output
+    pub now: i64,
+    pub min_complexity: i64,
+    pub min_fan_in: i64,
+    /// Complex functions by last modification, oldest first
+    pub untouched: Vec<FunctionAge>,
+    /// Functions with many callers by first import, newest first
+    pub new: Vec<FunctionAge>,
+    /// Functions without a known modification time
+    pub undated: usize,
+}
+
+impl Execute for AgeCmd {
+    type Output = AgeResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let filter = match &self.module {
+            Some(pattern) if self.common.regex => Some(compile_pattern(pattern)?),
+            Some(pattern) => Some(Regex::new(&regex::escape(pattern))?),
+            None => None,
+        };
+
+        let mut functions =
+            find_function_ages(db, &self.common.project, None, false, self.exclude_generated, settings)?;
+        if let Some(re) = &filter {
+            functions.retain(|f| re.is_match(&f.module));
+        }
+        let undated = functions.iter().filter(|f| f.modified_at == 0).count();
+
+        let mut untouched: Vec<FunctionAge> = functions
+            .iter()
+            .filter(|f| f.modified_at > 0 && f.complexity >= self.min_complexity)
+            .cloned()
+            .collect();
+        untouched.sort_by(|a, b| a.modified_at.cmp(&b.modified_at).then(b.complexity.cmp(&a.complexity)));
+        untouched.truncate(self.common.limit as usize);
+
+        let mut new: Vec<FunctionAge> = functions
+            .into_iter()
+            .filter(|f| f.first_seen > 0 && f.fan_in >= self.min_fan_in)
+            .collect();
+        new.sort_by(|a, b| b.first_seen.cmp(&a.first_seen).then(b.fan_in.cmp(&a.fan_in)));
+        new.truncate(self.common.limit as usize);
+
+        Ok(AgeResult {
+            now: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64,
+            min_complexity: self.min_complexity,
+            min_fan_in: self.min_fan_in,
+            untouched,
+            new,
+            undated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+
+    fn age_cmd(module: Option<&str>, min_complexity: i64, min_fan_in: i64) -> AgeCmd {
+        AgeCmd {
+            module: module.map(String::from),
+            min_complexity,
+            min_fan_in,
+            exclude_generated: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_age_without_modification_times() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = age_cmd(None, 1, 1).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        // The fixture has no modification times, so functions are dated by their import
+        assert!(result.untouched.is_empty());
+        assert!(result.undated > 0);
+        assert!(!result.new.is_empty());
+        assert!(result.new.iter().all(|f| f.fan_in >= 1 && f.first_seen > 0));
+        assert!(result.new.windows(2).all(|w| w[0].first_seen >= w[1].first_seen));
+    }
+
+    #[test]
+    fn test_age_orders_untouched_functions() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Accounts": {
+                    "get_user/1:2": {"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 9, "complexity": 6, "modified_at": 2000, "commit": "b2"},
+                    "list_users/0:12": {"name": "list_users", "arity": 0, "file": "lib/accounts.ex", "kind": "def", "line": 12, "start_line": 12, "end_line": 20, "complexity": 8, "modified_at": 1000, "commit": "a1"},
+                    "count/0:22": {"name": "count", "arity": 0, "file": "lib/accounts.ex", "kind": "def", "line": 22, "start_line": 22, "end_line": 23, "complexity": 1, "modified_at": 500, "commit": "a0"}
+                },
+                "MyApp.Repo": {
+                    "all/1:3": {"name": "all", "arity": 1, "file": "lib/repo.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 12, "complexity": 7, "modified_at": 100, "commit": "z9"}
+                }
+            },
+            "calls": []
+        }"#;
+        let db = db::test_utils::setup_test_db(json, "test_project");
+
+        let result = age_cmd(None, 5, 1).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        let names: Vec<&str> = result.untouched.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["all", "list_users", "get_user"]);
+        assert_eq!(result.untouched[0].commit, "z9");
+        assert_eq!(result.undated, 0);
+        assert!(result.new.is_empty());
+
+        let result = age_cmd(Some("Accounts"), 5, 1)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+        assert!(result.untouched.iter().all(|f| f.module == "MyApp.Accounts"));
+        assert_eq!(result.untouched.len(), 2);
+    }
+}
@@ -0,0 +1,55 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// List old complex functions and new heavily used ones
+///
+/// Uses the modification time and revision recorded for each function by the
+/// import (see `import --commit --modified-at` and the post-commit hook) and
+/// the time each function was first imported. Lists the complex functions
+/// left untouched the longest, and the newest functions that already have
+/// many callers.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search age                           # Stale complex and new popular functions
+  code_search age MyApp.Accounts            # Only functions of matching modules
+  code_search age --min-complexity 10       # Only functions with complexity >= 10 count as complex
+  code_search age --min-fan-in 5 -l 10      # Top 10 of each list, new functions with 5+ callers
+  code_search age --exclude-generated       # Leave out macro-generated functions
+")]
+pub struct AgeCmd {
+    /// Module filter pattern (substring match by default, regex with --regex)
+    pub module: Option<String>,
+
+    /// Minimum complexity for the list of untouched functions
+    #[arg(long, default_value = "5")]
+    pub min_complexity: i64,
+
+    /// Minimum number of calling functions for the list of new functions
+    #[arg(long, default_value = "3")]
+    pub min_fan_in: i64,
+
+    /// Exclude generated functions: macro-generated and compiler callbacks,
+    /// plus the `generated` rules of the config file
+    #[arg(long)]
+    pub exclude_generated: bool,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for AgeCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
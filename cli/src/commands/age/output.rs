@@ -0,0 +1,140 @@
+//! Output formatting for age command results.
+
+use super::execute::AgeResult;
+use crate::output::Outputable;
+use crate::utils::format_date;
+use db::queries::timeline::FunctionAge;
+
+impl Outputable for AgeResult {
+    fn to_table(&self) -> String {
+        let mut output = format!(
+            "Oldest untouched complex functions (complexity >= {}):\n",
+            self.min_complexity
+        );
+        if self.untouched.is_empty() {
+            output.push_str("  (none)\n");
+        }
+        for function in &self.untouched {
+            let commit = if function.commit.is_empty() {
+                String::new()
+            } else {
+                format!(" in {}", short_commit(&function.commit))
+            };
+            output.push_str(&format!(
+                "  {}  complexity {}  modified {}{}  {}\n",
+                signature(function),
+                function.complexity,
+                self.age(function.modified_at),
+                commit,
+                location(function),
+            ));
+        }
+
+        output.push_str(&format!(
+            "\nNewest functions with high fan-in (fan-in >= {}):\n",
+            self.min_fan_in
+        ));
+        if self.new.is_empty() {
+            output.push_str("  (none)\n");
+        }
+        for function in &self.new {
+            output.push_str(&format!(
+                "  {}  fan-in {}  first seen {}  {}\n",
+                signature(function),
+                function.fan_in,
+                self.age(function.first_seen),
+                location(function),
+            ));
+        }
+
+        if self.undated > 0 {
+            output.push_str(&format!(
+                "\n{} function(s) have no modification time; import with --modified-at or from the git hook to date them.\n",
+                self.undated
+            ));
+        }
+
+        output
+    }
+}
+
+impl AgeResult {
+    /// Date of a Unix time in seconds and the days since then
+    fn age(&self, seconds: i64) -> String {
+        let days = (self.now - seconds).max(0) / 86_400;
+        format!("{} ({} days ago)", format_date(seconds * 1000), days)
+    }
+}
+
+fn signature(function: &FunctionAge) -> String {
+    format!("{}.{}/{}", function.module, function.name, function.arity)
+}
+
+fn location(function: &FunctionAge) -> String {
+    format!("{}:{}", function.file, function.line)
+}
+
+/// First 7 characters of a git revision, like `git log --oneline`
+fn short_commit(commit: &str) -> &str {
+    commit.get(..7).unwrap_or(commit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function(name: &str, complexity: i64, fan_in: i64, first_seen: i64, modified_at: i64, commit: &str) -> FunctionAge {
+        FunctionAge {
+            module: "MyApp.Accounts".to_string(),
+            name: name.to_string(),
+            arity: 1,
+            file: "lib/accounts.ex".to_string(),
+            line: 10,
+            complexity,
+            fan_in,
+            first_seen,
+            modified_at,
+            commit: commit.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_age_table() {
+        let result = AgeResult {
+            now: 1_760_659_200,
+            min_complexity: 5,
+            min_fan_in: 3,
+            untouched: vec![function("get_user", 9, 1, 1_700_000_000, 1_700_000_000, "0123456789abcdef")],
+            new: vec![function("fetch", 2, 4, 1_760_572_800, 0, "")],
+            undated: 1,
+        };
+        assert_eq!(
+            result.to_table(),
+            "\
+Oldest untouched complex functions (complexity >= 5):
+  MyApp.Accounts.get_user/1  complexity 9  modified 2023-11-14 (702 days ago) in 0123456  lib/accounts.ex:10
+
+Newest functions with high fan-in (fan-in >= 3):
+  MyApp.Accounts.fetch/1  fan-in 4  first seen 2025-10-16 (1 days ago)  lib/accounts.ex:10
+
+1 function(s) have no modification time; import with --modified-at or from the git hook to date them.
+"
+        );
+    }
+
+    #[test]
+    fn test_age_table_empty() {
+        let result = AgeResult {
+            now: 0,
+            min_complexity: 5,
+            min_fan_in: 3,
+            untouched: Vec::new(),
+            new: Vec::new(),
+            undated: 0,
+        };
+        assert_eq!(
+            result.to_table(),
+            "Oldest untouched complex functions (complexity >= 5):\n  (none)\n\nNewest functions with high fan-in (fan-in >= 3):\n  (none)\n"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use super::AnnotateCmd;
+use crate::commands::Execute;
+use db::queries::annotations::{add_annotation, remove_annotations, Annotation};
+use db::query_builders::QuerySettings;
+
+/// Result of the annotate command
+#[derive(Debug, Serialize)]
+pub struct AnnotateResult {
+    /// `Module`, `Module.function` or `Module.function/arity`
+    pub target: String,
+    /// The annotation added, absent with --remove
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added: Option<Annotation>,
+    /// Number of annotations removed with --remove
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed: Option<usize>,
+}
+
+impl Execute for AnnotateCmd {
+    type Output = AnnotateResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let mut annotation = Annotation {
+            module: self.module,
+            function: self.function.unwrap_or_default(),
+            arity: self.arity,
+            kind: self.kind.unwrap_or_default(),
+            text: String::new(),
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64,
+        };
+        let target = annotation.target();
+
+        if self.remove {
+            let function = Some(annotation.function.as_str()).filter(|f| !f.is_empty());
+            let removed = remove_annotations(db, &self.project, &annotation.module, function, self.arity, self.kind)?;
+            return Ok(AnnotateResult {
+                target,
+                added: None,
+                removed: Some(removed),
+            });
+        }
+
+        annotation.text = self.message.ok_or("--message is required")?;
+        add_annotation(db, &self.project, &annotation)?;
+
+        Ok(AnnotateResult {
+            target,
+            added: Some(annotation),
+            removed: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::annotations::{find_annotations, AnnotationKind};
+
+    fn annotate_cmd(kind: Option<AnnotationKind>, message: Option<&str>, remove: bool) -> AnnotateCmd {
+        AnnotateCmd {
+            module: "MyApp.Accounts".to_string(),
+            function: Some("get_user".to_string()),
+            arity: Some(1),
+            message: message.map(str::to_string),
+            kind,
+            remove,
+            project: "default".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_annotate_adds_and_removes() {
+        let db = db::open_mem_db();
+
+        let result = annotate_cmd(Some(AnnotationKind::Todo), Some("Handle soft-deleted users"), false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Annotate should succeed");
+        assert_eq!(result.target, "MyApp.Accounts.get_user/1");
+        assert_eq!(result.added.as_ref().map(|a| a.kind), Some(AnnotationKind::Todo));
+
+        annotate_cmd(None, Some("Hot path"), false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Annotate should succeed");
+        let stored = find_annotations(&db, "default", None, false, None, 100, &QuerySettings::default()).unwrap();
+        assert_eq!(stored.len(), 2);
+
+        // Removing by kind keeps the note
+        let result = annotate_cmd(Some(AnnotationKind::Todo), None, true)
+            .execute(&db, &QuerySettings::default())
+            .expect("Remove should succeed");
+        assert_eq!(result.removed, Some(1));
+        let stored = find_annotations(&db, "default", None, false, None, 100, &QuerySettings::default()).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].kind, AnnotationKind::Note);
+    }
+}
@@ -0,0 +1,60 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::queries::annotations::AnnotationKind;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Attach a note, TODO or deprecation marker to a module or function
+///
+/// Annotations are kept across re-imports. List them with `annotations`, or
+/// next to results with --show-annotations on location, unused and complexity.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search annotate MyApp.Accounts -m 'Owned by the identity team'
+  code_search annotate MyApp.Accounts get_user -a 1 -k todo -m 'Handle soft-deleted users'
+  code_search annotate MyApp.Legacy fetch -k deprecated -m 'Use MyApp.Accounts.get_user/1'
+  code_search annotate MyApp.Accounts get_user -a 1 --remove          # Remove all its annotations
+  code_search annotate MyApp.Accounts get_user -a 1 --remove -k todo  # Remove only its TODOs
+")]
+pub struct AnnotateCmd {
+    /// Module to annotate
+    pub module: String,
+
+    /// Function to annotate; the module itself is annotated when omitted
+    pub function: Option<String>,
+
+    /// Function arity; the annotation applies to every arity when omitted
+    #[arg(short, long, requires = "function")]
+    pub arity: Option<i64>,
+
+    /// Annotation text
+    #[arg(short, long, required_unless_present = "remove")]
+    pub message: Option<String>,
+
+    /// Kind of annotation (note when adding; every kind when removing)
+    #[arg(short, long, value_enum)]
+    pub kind: Option<AnnotationKind>,
+
+    /// Remove the target's annotations instead of adding one
+    #[arg(long, conflicts_with = "message")]
+    pub remove: bool,
+
+    /// Project the annotation belongs to
+    #[arg(long, default_value = "default")]
+    pub project: String,
+}
+
+impl CommandRunner for AnnotateCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
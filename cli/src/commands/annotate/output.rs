@@ -0,0 +1,54 @@
+//! Output formatting for annotate command results.
+
+use super::execute::AnnotateResult;
+use crate::output::Outputable;
+
+impl Outputable for AnnotateResult {
+    fn to_table(&self) -> String {
+        match (&self.added, self.removed) {
+            (Some(annotation), _) => format!(
+                "Annotated {} [{}]: {}\n",
+                self.target,
+                annotation.kind.as_str(),
+                annotation.text
+            ),
+            (None, removed) => format!("Removed {} annotation(s) from {}\n", removed.unwrap_or(0), self.target),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::annotations::{Annotation, AnnotationKind};
+
+    #[test]
+    fn test_to_table_added() {
+        let result = AnnotateResult {
+            target: "MyApp.Legacy.fetch".to_string(),
+            added: Some(Annotation {
+                module: "MyApp.Legacy".to_string(),
+                function: "fetch".to_string(),
+                arity: None,
+                kind: AnnotationKind::Deprecated,
+                text: "Use MyApp.Accounts.get_user/1".to_string(),
+                created_at: 0,
+            }),
+            removed: None,
+        };
+        assert_eq!(
+            result.to_table(),
+            "Annotated MyApp.Legacy.fetch [deprecated]: Use MyApp.Accounts.get_user/1\n"
+        );
+    }
+
+    #[test]
+    fn test_to_table_removed() {
+        let result = AnnotateResult {
+            target: "MyApp.Accounts".to_string(),
+            added: None,
+            removed: Some(2),
+        };
+        assert_eq!(result.to_table(), "Removed 2 annotation(s) from MyApp.Accounts\n");
+    }
+}
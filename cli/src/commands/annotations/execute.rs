@@ -0,0 +1,77 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::AnnotationsCmd;
+use crate::commands::Execute;
+use db::queries::annotations::{find_annotations, Annotation};
+use db::query_builders::QuerySettings;
+
+/// Result of the annotations command
+#[derive(Debug, Serialize)]
+pub struct AnnotationsResult {
+    pub module_pattern: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub annotations: Vec<Annotation>,
+}
+
+impl Execute for AnnotationsCmd {
+    type Output = AnnotationsResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let annotations = find_annotations(
+            db,
+            &self.common.project,
+            self.module.as_deref(),
+            self.common.regex,
+            self.kind,
+            self.common.limit,
+            settings,
+        )?;
+
+        Ok(AnnotationsResult {
+            module_pattern: self.module.unwrap_or_else(|| "*".to_string()),
+            kind: self.kind.map(|k| k.as_str().to_string()),
+            annotations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+    use db::queries::annotations::{add_annotation, AnnotationKind};
+
+    #[test]
+    fn test_annotations_filters_by_kind() {
+        let db = db::open_mem_db();
+        for (function, kind) in [("get_user", AnnotationKind::Todo), ("", AnnotationKind::Note)] {
+            let annotation = Annotation {
+                module: "MyApp.Accounts".to_string(),
+                function: function.to_string(),
+                arity: None,
+                kind,
+                text: "text".to_string(),
+                created_at: 1,
+            };
+            add_annotation(&db, "default", &annotation).unwrap();
+        }
+
+        let cmd = AnnotationsCmd {
+            module: None,
+            kind: Some(AnnotationKind::Todo),
+            common: CommonArgs {
+                project: "default".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        };
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Annotations should succeed");
+        assert_eq!(result.annotations.len(), 1);
+        assert_eq!(result.annotations[0].function, "get_user");
+        assert_eq!(result.kind.as_deref(), Some("todo"));
+    }
+}
@@ -0,0 +1,40 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::queries::annotations::AnnotationKind;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// List the annotations attached with `annotate`
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search annotations                     # All annotations of the project
+  code_search annotations MyApp.Accounts      # Annotations of one module and its functions
+  code_search annotations -k todo             # Open TODOs
+  code_search annotations -r 'MyApp\\.Legacy.*' -k deprecated
+")]
+pub struct AnnotationsCmd {
+    /// Module filter (exact match, or pattern with --regex)
+    pub module: Option<String>,
+
+    /// Only list annotations of this kind
+    #[arg(short, long, value_enum)]
+    pub kind: Option<AnnotationKind>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for AnnotationsCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
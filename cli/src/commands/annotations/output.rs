@@ -0,0 +1,75 @@
+//! Output formatting for annotations command results.
+
+use super::execute::AnnotationsResult;
+use crate::output::Outputable;
+
+impl Outputable for AnnotationsResult {
+    fn to_table(&self) -> String {
+        if self.annotations.is_empty() {
+            return "No annotations found.\n".to_string();
+        }
+
+        let mut output = format!("Annotations ({}):\n", self.annotations.len());
+        let mut current_target = String::new();
+        for annotation in &self.annotations {
+            let target = annotation.target();
+            if target != current_target {
+                output.push_str(&format!("\n{}\n", target));
+                current_target = target;
+            }
+            output.push_str(&format!("  [{}] {}\n", annotation.kind.as_str(), annotation.text));
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::annotations::{Annotation, AnnotationKind};
+
+    #[test]
+    fn test_to_table_groups_by_target() {
+        let annotation = |function: &str, kind, text: &str| Annotation {
+            module: "MyApp.Accounts".to_string(),
+            function: function.to_string(),
+            arity: Some(1).filter(|_| !function.is_empty()),
+            kind,
+            text: text.to_string(),
+            created_at: 0,
+        };
+        let result = AnnotationsResult {
+            module_pattern: "*".to_string(),
+            kind: None,
+            annotations: vec![
+                annotation("", AnnotationKind::Note, "Owned by the identity team"),
+                annotation("get_user", AnnotationKind::Todo, "Handle soft-deleted users"),
+                annotation("get_user", AnnotationKind::Note, "Hot path"),
+            ],
+        };
+
+        assert_eq!(
+            result.to_table(),
+            "\
+Annotations (3):
+
+MyApp.Accounts
+  [note] Owned by the identity team
+
+MyApp.Accounts.get_user/1
+  [todo] Handle soft-deleted users
+  [note] Hot path
+"
+        );
+    }
+
+    #[test]
+    fn test_to_table_empty() {
+        let result = AnnotationsResult {
+            module_pattern: "*".to_string(),
+            kind: None,
+            annotations: vec![],
+        };
+        assert_eq!(result.to_table(), "No annotations found.\n");
+    }
+}
@@ -0,0 +1,155 @@
+//! Per-app module, size and dependency summaries.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::AppsCmd;
+use crate::commands::Execute;
+use db::queries::apps::{find_app_dependencies, find_module_apps};
+use db::queries::hotspots::{get_function_counts, get_module_loc};
+use db::query_builders::QuerySettings;
+
+/// Calls from or to another app
+#[derive(Debug, Clone, Serialize)]
+pub struct AppEdge {
+    pub app: String,
+    pub calls: i64,
+}
+
+/// Size and cross-app dependencies of one OTP application
+#[derive(Debug, Clone, Serialize)]
+pub struct AppSummary {
+    pub app: String,
+    pub modules: usize,
+    pub functions: i64,
+    pub loc: i64,
+    /// Apps this app calls into
+    pub depends_on: Vec<AppEdge>,
+    /// Apps calling into this app
+    pub depended_by: Vec<AppEdge>,
+}
+
+/// Result of the apps command
+#[derive(Debug, Serialize)]
+pub struct AppsResult {
+    /// Apps in the project, before filtering by name
+    pub total_apps: usize,
+    /// Modules that belong to no known app
+    pub unassigned_modules: usize,
+    pub apps: Vec<AppSummary>,
+}
+
+impl Execute for AppsCmd {
+    type Output = AppsResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let module_apps = find_module_apps(db, &self.project, settings)?;
+        let dependencies = find_app_dependencies(db, &self.project, settings)?;
+        let func_counts = get_function_counts(db, &self.project, None, false, settings)?;
+        let module_loc = get_module_loc(db, &self.project, None, false, settings)?;
+
+        let mut apps: BTreeMap<String, AppSummary> = BTreeMap::new();
+        let mut unassigned_modules = 0;
+        for module_app in module_apps {
+            if module_app.app.is_empty() {
+                unassigned_modules += 1;
+                continue;
+            }
+            let summary = apps.entry(module_app.app.clone()).or_insert_with(|| AppSummary {
+                app: module_app.app,
+                modules: 0,
+                functions: 0,
+                loc: 0,
+                depends_on: Vec::new(),
+                depended_by: Vec::new(),
+            });
+            summary.modules += 1;
+            summary.functions += func_counts.get(&module_app.module).copied().unwrap_or(0);
+            summary.loc += module_loc.get(&module_app.module).copied().unwrap_or(0);
+        }
+
+        for dependency in dependencies {
+            if let Some(summary) = apps.get_mut(&dependency.from_app) {
+                summary.depends_on.push(AppEdge {
+                    app: dependency.to_app.clone(),
+                    calls: dependency.calls,
+                });
+            }
+            if let Some(summary) = apps.get_mut(&dependency.to_app) {
+                summary.depended_by.push(AppEdge {
+                    app: dependency.from_app,
+                    calls: dependency.calls,
+                });
+            }
+        }
+
+        let total_apps = apps.len();
+        let apps = apps
+            .into_values()
+            .filter(|summary| self.app.as_ref().is_none_or(|app| &summary.app == app))
+            .collect();
+
+        Ok(AppsResult {
+            total_apps,
+            unassigned_modules,
+            apps,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apps_cmd(app: Option<&str>) -> AppsCmd {
+        AppsCmd {
+            app: app.map(String::from),
+            project: "test_project".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apps_summaries() {
+        let db = db::test_utils::umbrella_db("test_project");
+        let result = apps_cmd(None).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.total_apps, 3);
+        assert_eq!(result.unassigned_modules, 0);
+
+        let names: Vec<&str> = result.apps.iter().map(|a| a.app.as_str()).collect();
+        assert_eq!(names, ["billing", "core", "web"]);
+
+        let core = &result.apps[1];
+        assert_eq!(core.modules, 2);
+        assert_eq!(core.functions, 5);
+        assert_eq!(core.loc, 18);
+        assert!(core.depends_on.is_empty());
+        let callers: Vec<(&str, i64)> = core.depended_by.iter().map(|e| (e.app.as_str(), e.calls)).collect();
+        assert_eq!(callers, [("billing", 1), ("web", 2)]);
+
+        let web = &result.apps[2];
+        assert_eq!(web.depends_on.len(), 1);
+        assert_eq!((web.depends_on[0].app.as_str(), web.depends_on[0].calls), ("core", 2));
+    }
+
+    #[test]
+    fn test_apps_single_app() {
+        let db = db::test_utils::umbrella_db("test_project");
+        let result = apps_cmd(Some("billing")).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.total_apps, 3);
+        assert_eq!(result.apps.len(), 1);
+        assert_eq!(result.apps[0].loc, 13);
+    }
+
+    #[test]
+    fn test_apps_flat_project() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = apps_cmd(None).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.total_apps, 0);
+        assert!(result.unassigned_modules > 0);
+    }
+}
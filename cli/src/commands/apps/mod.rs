@@ -0,0 +1,40 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Summarize the OTP applications of an umbrella project
+///
+/// Groups modules by the app they belong to (an `app` on their function
+/// locations in the call graph JSON, or their `apps/<app>/` source path) and
+/// shows each app's size and the calls crossing into other apps. Use `--app`
+/// on hotspots, unused and large-functions to look inside a single app.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search apps                              # Every app with its size and dependencies
+  code_search apps billing                      # A single app
+  code_search unused --app billing              # Unused functions of one app
+")]
+pub struct AppsCmd {
+    /// Only show this app (exact name)
+    pub app: Option<String>,
+
+    /// Project to search in
+    #[arg(long, default_value = "default")]
+    pub project: String,
+}
+
+impl CommandRunner for AppsCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
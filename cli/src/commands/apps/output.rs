@@ -0,0 +1,102 @@
+//! Output formatting for apps command results.
+
+use super::execute::{AppEdge, AppsResult};
+use crate::output::Outputable;
+
+impl Outputable for AppsResult {
+    fn to_table(&self) -> String {
+        let mut output = format!("Apps: {}", self.total_apps);
+        if self.unassigned_modules > 0 {
+            output.push_str(&format!(" ({} module(s) without an app)", self.unassigned_modules));
+        }
+        output.push('\n');
+
+        if self.apps.is_empty() {
+            output.push_str("\nNo apps found.\n");
+            return output;
+        }
+
+        for app in &self.apps {
+            output.push_str(&format!(
+                "\n{}: {} module(s), {} function(s), {} LoC\n",
+                app.app, app.modules, app.functions, app.loc
+            ));
+            if !app.depends_on.is_empty() {
+                output.push_str(&format!("  depends on: {}\n", edges(&app.depends_on)));
+            }
+            if !app.depended_by.is_empty() {
+                output.push_str(&format!("  depended on by: {}\n", edges(&app.depended_by)));
+            }
+        }
+
+        output
+    }
+}
+
+fn edges(edges: &[AppEdge]) -> String {
+    edges
+        .iter()
+        .map(|e| format!("{} ({})", e.app, e.calls))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::apps::execute::AppSummary;
+
+    fn edge(app: &str, calls: i64) -> AppEdge {
+        AppEdge {
+            app: app.to_string(),
+            calls,
+        }
+    }
+
+    fn result() -> AppsResult {
+        AppsResult {
+            total_apps: 2,
+            unassigned_modules: 1,
+            apps: vec![
+                AppSummary {
+                    app: "core".to_string(),
+                    modules: 2,
+                    functions: 5,
+                    loc: 18,
+                    depends_on: vec![],
+                    depended_by: vec![edge("billing", 1), edge("web", 2)],
+                },
+                AppSummary {
+                    app: "web".to_string(),
+                    modules: 1,
+                    functions: 2,
+                    loc: 12,
+                    depends_on: vec![edge("core", 2)],
+                    depended_by: vec![],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_apps_table() {
+        let expected = [
+            "Apps: 2 (1 module(s) without an app)",
+            "",
+            "core: 2 module(s), 5 function(s), 18 LoC",
+            "  depended on by: billing (1), web (2)",
+            "",
+            "web: 1 module(s), 2 function(s), 12 LoC",
+            "  depends on: core (2)",
+            "",
+        ];
+        assert_eq!(result().to_table(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_apps_table_empty() {
+        let mut result = result();
+        result.apps.clear();
+        assert!(result.to_table().ends_with("\nNo apps found.\n"));
+    }
+}
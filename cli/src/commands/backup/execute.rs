@@ -0,0 +1,27 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::BackupCmd;
+use crate::commands::Execute;
+use db::queries::backup::{backup_database, RelationRows};
+use db::query_builders::QuerySettings;
+
+/// Result of the backup command
+#[derive(Debug, Serialize)]
+pub struct BackupResult {
+    pub path: String,
+    pub relations: Vec<RelationRows>,
+}
+
+impl Execute for BackupCmd {
+    type Output = BackupResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let relations = backup_database(db, &self.path)?;
+        Ok(BackupResult {
+            path: self.path.display().to_string(),
+            relations,
+        })
+    }
+}
@@ -0,0 +1,36 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+pub(crate) use output::format_relations;
+
+/// Back up the database to a file
+///
+/// Copies the whole database with Cozo's native backup into a standalone
+/// SQLite file. Bring it back with `restore`.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search backup code_search.bak                  # Back up the default database
+  code_search --db other.sqlite backup other.bak      # Back up another database
+")]
+pub struct BackupCmd {
+    /// Backup file to create; must not exist
+    pub path: PathBuf,
+}
+
+impl CommandRunner for BackupCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
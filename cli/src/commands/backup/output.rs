@@ -0,0 +1,49 @@
+//! Output formatting for backup and restore command results.
+
+use super::execute::BackupResult;
+use crate::output::Outputable;
+use db::queries::backup::RelationRows;
+
+/// Row counts per relation followed by the total
+pub(crate) fn format_relations(header: &str, relations: &[RelationRows]) -> String {
+    let width = relations.iter().map(|r| r.relation.len()).max().unwrap_or(0);
+    let mut output = format!("{}\n\n", header);
+    for relation in relations {
+        output.push_str(&format!("  {:<width$}  {:>8}\n", relation.relation, relation.rows, width = width));
+    }
+    let total: usize = relations.iter().map(|r| r.rows).sum();
+    output.push_str(&format!("\n{} relation(s), {} row(s)\n", relations.len(), total));
+    output
+}
+
+impl Outputable for BackupResult {
+    fn to_table(&self) -> String {
+        format_relations(&format!("Backed up to {}", self.path), &self.relations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_table() {
+        let result = BackupResult {
+            path: "code_search.bak".to_string(),
+            relations: vec![
+                RelationRows {
+                    relation: "modules".to_string(),
+                    rows: 12,
+                },
+                RelationRows {
+                    relation: "calls".to_string(),
+                    rows: 340,
+                },
+            ],
+        };
+        assert_eq!(
+            result.to_table(),
+            "Backed up to code_search.bak\n\n  modules        12\n  calls         340\n\n2 relation(s), 352 row(s)\n"
+        );
+    }
+}
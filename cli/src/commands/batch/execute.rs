@@ -0,0 +1,214 @@
+use std::error::Error;
+use std::io::Read;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::BatchCmd;
+use crate::cli::Args;
+use crate::commands::{CheckFailed, CommandRunner, Execute};
+use crate::output::{columns, OutputFormat};
+use db::query_builders::QuerySettings;
+
+/// One invocation listed in a batch file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Invocation {
+    Args(Vec<String>),
+    Named {
+        #[serde(default)]
+        name: Option<String>,
+        args: Vec<String>,
+    },
+}
+
+/// Outcome of one invocation
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub args: Vec<String>,
+    /// JSON output of the command; also set for a failed `check`, with its report
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of the batch command, entries in file order
+#[derive(Debug, Serialize)]
+pub struct BatchResult {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BatchEntry>,
+}
+
+impl Execute for BatchCmd {
+    type Output = BatchResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let invocations = read_invocations(&self.file)?;
+
+        let mut results = Vec::new();
+        for invocation in invocations {
+            let (name, args) = match invocation {
+                Invocation::Args(args) => (None, args),
+                Invocation::Named { name, args } => (name, args),
+            };
+            let (output, error) = match run_invocation(db, &args, settings) {
+                Ok(output) => (Some(output), None),
+                Err((output, error)) => (output, Some(error)),
+            };
+            results.push(BatchEntry {
+                name,
+                args,
+                output,
+                error,
+            });
+        }
+
+        let failed = results.iter().filter(|entry| entry.error.is_some()).count();
+        Ok(BatchResult {
+            succeeded: results.len() - failed,
+            failed,
+            results,
+        })
+    }
+}
+
+/// Read and parse the invocations of a batch file, YAML for `.yaml`/`.yml`, JSON otherwise
+fn read_invocations(path: &Path) -> Result<Vec<Invocation>, Box<dyn Error>> {
+    let display = path.display();
+    let mut content = String::new();
+    if path == Path::new("-") {
+        std::io::stdin().read_to_string(&mut content)?;
+    } else {
+        content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", display, e))?;
+    }
+
+    let yaml = matches!(path.extension().and_then(|e| e.to_str()), Some("yaml" | "yml"));
+    let invocations = if yaml {
+        serde_norway::from_str(&content).map_err(|e| format!("Invalid batch file '{}': {}", display, e))?
+    } else {
+        serde_json::from_str(&content).map_err(|e| format!("Invalid batch file '{}': {}", display, e))?
+    };
+    Ok(invocations)
+}
+
+/// Run one invocation, returning its JSON output or its error (with any output)
+fn run_invocation(
+    db: &db::DbInstance,
+    args: &[String],
+    settings: &QuerySettings,
+) -> Result<Value, (Option<Value>, String)> {
+    let parsed = Args::parse_invocation(args).map_err(|e| (None, e))?;
+
+    let fields = parsed.fields.clone();
+    let to_value = |output: String| -> Result<Value, String> {
+        let value = serde_json::from_str(&output).unwrap_or(Value::String(output));
+        if fields.is_empty() {
+            return Ok(value);
+        }
+        Ok(serde_json::from_str(&columns::render(&value, &fields, OutputFormat::Json)?).unwrap_or_default())
+    };
+
+    // Each invocation has its own scopes; the generated-code rules are the batch's
+    let settings = QuerySettings {
+        only_project: parsed.only_project(),
+        test_scope: parsed.test_scope(),
+        namespaces: parsed.command.common().map(|common| common.namespace.clone()).unwrap_or_default(),
+        sorted: settings.sorted && !parsed.no_sort,
+        generated_rules: settings.generated_rules.clone(),
+    };
+    let result = parsed.command.run(db, OutputFormat::Json, &settings);
+
+    match result {
+        Ok(output) => to_value(output).map_err(|e| (None, e)),
+        Err(e) => Err(match e.downcast::<CheckFailed>() {
+            Ok(failed) => (to_value(failed.report).ok(), "check failed".to_string()),
+            Err(e) => (None, e.to_string()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+    use std::io::Write;
+
+    crate::shared_fixture! {
+        fixture_name: populated_db,
+        fixture_type: call_graph,
+        project: "test_project",
+    }
+
+    fn batch(db: &db::DbInstance, suffix: &str, content: &str) -> BatchResult {
+        let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        let cmd = BatchCmd {
+            file: file.path().to_path_buf(),
+        };
+        cmd.execute(db, &QuerySettings::default()).expect("Batch should succeed")
+    }
+
+    #[rstest]
+    fn test_batch_json(populated_db: db::DbInstance) {
+        let result = batch(
+            &populated_db,
+            ".json",
+            r#"[
+                ["calls-to", "MyApp.Accounts", "get_user", "--project", "test_project"],
+                {"name": "modules", "args": ["search", "MyApp.Accounts", "--project", "test_project", "--fields", "name"]}
+            ]"#,
+        );
+
+        assert_eq!((result.succeeded, result.failed), (2, 0));
+        let calls = result.results[0].output.as_ref().expect("calls-to output");
+        assert!(calls.is_object());
+        assert_eq!(result.results[1].name.as_deref(), Some("modules"));
+        assert_eq!(
+            result.results[1].output,
+            Some(serde_json::json!([{"name": "MyApp.Accounts"}]))
+        );
+    }
+
+    #[rstest]
+    fn test_batch_yaml_reports_errors_per_entry(populated_db: db::DbInstance) {
+        let result = batch(
+            &populated_db,
+            ".yaml",
+            "
+- [hotspots, --project, test_project, -l, '3']
+- [no-such-command]
+- [import, --file, /nonexistent.json]
+- [location, get_user, --db, other.sqlite]
+",
+        );
+
+        assert_eq!((result.succeeded, result.failed), (1, 3));
+        assert!(result.results[0].error.is_none());
+        assert!(result.results[1].error.as_deref().unwrap().contains("Unknown command"));
+        assert!(result.results[2].error.is_some());
+        assert!(result.results[3].error.as_deref().unwrap().contains("--db"));
+    }
+
+    #[rstest]
+    fn test_batch_rejects_commands_changing_the_database(populated_db: db::DbInstance) {
+        let result = batch(&populated_db, ".json", r#"[["annotate", "MyApp.Accounts", "-m", "Owned by the identity team"]]"#);
+        assert!(result.results[0].error.as_deref().unwrap().contains("cannot run inside another command"));
+    }
+
+    #[test]
+    fn test_batch_invalid_file() {
+        let db = db::open_mem_db();
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        file.write_all(b"{\"not\": \"a list\"}").unwrap();
+        let cmd = BatchCmd {
+            file: file.path().to_path_buf(),
+        };
+        let err = cmd.execute(&db, &QuerySettings::default()).unwrap_err();
+        assert!(err.to_string().contains("Invalid batch file"));
+    }
+}
@@ -0,0 +1,43 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Run the queries listed in a file and combine their results into one document
+///
+/// The file is a JSON or YAML list (by extension; `-` reads JSON from stdin).
+/// Each entry is the argument list of one invocation, or an object with an
+/// optional `name` and the `args`. All queries run against one database
+/// connection; each result is its command's JSON output, or its error.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search batch queries.json             # Queries from a JSON file
+  code_search batch queries.yaml             # Queries from a YAML file
+  echo '[[\"hotspots\", \"-l\", \"5\"]]' | code_search batch -
+
+File format:
+  [
+    [\"calls-to\", \"MyApp.Accounts\", \"get_user\"],
+    {\"name\": \"top\", \"args\": [\"hotspots\", \"-l\", \"5\", \"--fields\", \"module,function\"]}
+  ]
+")]
+pub struct BatchCmd {
+    /// File listing the invocations, or - for stdin
+    pub file: PathBuf,
+}
+
+impl CommandRunner for BatchCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
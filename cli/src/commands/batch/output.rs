@@ -0,0 +1,71 @@
+//! Output formatting for batch command results.
+
+use super::execute::BatchResult;
+use crate::output::Outputable;
+
+impl Outputable for BatchResult {
+    fn to_table(&self) -> String {
+        let mut lines = vec![format!(
+            "Batch: {} succeeded, {} failed",
+            self.succeeded, self.failed
+        )];
+
+        for entry in &self.results {
+            lines.push(String::new());
+            let label = entry.name.clone().unwrap_or_else(|| entry.args.join(" "));
+            lines.push(format!("== {}", label));
+            if let Some(error) = &entry.error {
+                lines.push(format!("Error: {}", error));
+            }
+            if let Some(output) = &entry.output {
+                lines.push(serde_json::to_string_pretty(output).unwrap_or_default());
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::batch::execute::BatchEntry;
+
+    #[test]
+    fn test_to_table() {
+        let result = BatchResult {
+            succeeded: 1,
+            failed: 1,
+            results: vec![
+                BatchEntry {
+                    name: Some("modules".to_string()),
+                    args: vec!["search".to_string(), "MyApp.Accounts".to_string()],
+                    output: Some(serde_json::json!([{"name": "MyApp.Accounts"}])),
+                    error: None,
+                },
+                BatchEntry {
+                    name: None,
+                    args: vec!["no-such-command".to_string()],
+                    output: None,
+                    error: Some("Unknown command: no-such-command".to_string()),
+                },
+            ],
+        };
+
+        assert_eq!(
+            result.to_table(),
+            "\
+Batch: 1 succeeded, 1 failed
+
+== modules
+[
+  {
+    \"name\": \"MyApp.Accounts\"
+  }
+]
+
+== no-such-command
+Error: Unknown command: no-such-command"
+        );
+    }
+}
@@ -4,7 +4,9 @@ use serde::Serialize;
 
 use super::BoundariesCmd;
 use crate::commands::Execute;
+use crate::sort::query_limit;
 use db::queries::hotspots::{find_hotspots, HotspotKind};
+use db::query_builders::QuerySettings;
 use db::types::{ModuleCollectionResult, ModuleGroup};
 
 /// A single boundary module entry
@@ -18,16 +20,18 @@ pub struct BoundaryEntry {
 impl Execute for BoundariesCmd {
     type Output = ModuleCollectionResult<BoundaryEntry>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         let hotspots = find_hotspots(
             db,
             HotspotKind::Ratio,
             self.module.as_deref(),
+            None,
             &self.common.project,
             self.common.regex,
-            self.common.limit,
+            query_limit(self.sort.as_ref(), self.common.limit),
             false,
             true, // require_outgoing: exclude leaf nodes
+            settings,
         )?;
 
         // Build module groups, filtering by thresholds and deduplicating by module
@@ -57,14 +61,20 @@ impl Execute for BoundariesCmd {
 
         let total_items = items.len();
 
-        Ok(ModuleCollectionResult {
+        let mut result = ModuleCollectionResult {
             module_pattern: self.module.unwrap_or_else(|| "*".to_string()),
             function_pattern: None,
             kind_filter: Some("boundary".to_string()),
             name_filter: None,
             total_items,
             items,
-        })
+        };
+
+        if let Some(sort) = &self.sort {
+            sort.sort_groups(&mut result, self.common.limit)?;
+        }
+
+        Ok(result)
     }
 }
 
@@ -88,10 +98,12 @@ mod tests {
             min_incoming: 5,
             min_ratio: 2.0,
             module: None,
+            sort: None,
             common: crate::commands::CommonArgs {
                 project: "default".to_string(),
                 regex: false,
                 limit: 50,
+                namespace: Vec::new(),
             },
         };
 
@@ -4,10 +4,12 @@ mod output;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::sort::SortSpec;
 
 /// Find boundary modules - modules with high fan-in but low fan-out
 ///
@@ -22,6 +24,7 @@ Examples:
   code_search boundaries --min-incoming 5         # With minimum 5 incoming calls
   code_search boundaries --min-ratio 2.0          # With minimum 2.0 ratio
   code_search boundaries -l 20                    # Show top 20 boundary modules
+  code_search boundaries --sort module            # Sort by module name
 ")]
 pub struct BoundariesCmd {
     /// Module filter pattern (substring match by default, regex with --regex)
@@ -35,13 +38,17 @@ pub struct BoundariesCmd {
     #[arg(long, default_value = "2.0")]
     pub min_ratio: f64,
 
+    /// Sort by an output column as `column[:asc|desc]` (ascending by default)
+    #[arg(long, value_name = "COLUMN[:asc|desc]")]
+    pub sort: Option<SortSpec>,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for BoundariesCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
@@ -4,11 +4,12 @@ use std::error::Error;
 use serde::Serialize;
 
 use super::{BrowseModuleCmd, DefinitionKind};
-use crate::commands::Execute;
+use crate::commands::{parallel, Execute};
 use db::queries::file::find_functions_in_module;
 use db::queries::specs::find_specs;
 use db::queries::types::find_types;
 use db::queries::structs::{find_struct_fields, group_fields_into_structs, FieldInfo};
+use db::query_builders::QuerySettings;
 
 /// Result of browsing definitions in a module
 #[derive(Debug, Serialize)]
@@ -119,7 +120,7 @@ impl Definition {
 impl Execute for BrowseModuleCmd {
     type Output = BrowseModuleResult;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         let mut definitions = Vec::new();
 
         // Determine what to query based on kind filter
@@ -128,108 +129,104 @@ impl Execute for BrowseModuleCmd {
         let should_query_types = self.kind.is_none() || matches!(self.kind, Some(DefinitionKind::Types));
         let should_query_structs = self.kind.is_none() || matches!(self.kind, Some(DefinitionKind::Structs));
 
-        // Query functions (from function_locations table for file + line info)
-        if should_query_functions {
-            let funcs = find_functions_in_module(
-                db,
-                &self.module_or_file,
-                &self.common.project,
-                self.common.regex,
-                self.common.limit,
-            )?;
-
-            for func in funcs {
-                // Filter by name if specified
-                if let Some(ref name_filter) = self.name
-                    && !func.name.contains(name_filter) {
-                        continue;
-                    }
-
-                definitions.push(Definition::Function {
-                    module: func.module,
-                    file: func.file,
-                    name: func.name,
-                    arity: func.arity,
-                    line: func.line,
-                    start_line: func.start_line,
-                    end_line: func.end_line,
-                    kind: func.kind,
-                    args: String::new(), // Not in function_locations
-                    return_type: String::new(), // Not in function_locations
-                    pattern: func.pattern,
-                    guard: func.guard,
-                });
-            }
+        let term = self.module_or_file.as_str();
+        let name = self.name.as_deref();
+        let (project, regex, limit) = (self.common.project.as_str(), self.common.regex, self.common.limit);
+
+        // The four kinds are independent queries
+        let (funcs, specs, types, fields) = parallel::scope(|tasks| {
+            // Functions come from function_locations for file + line info
+            let funcs = tasks.spawn(|| {
+                if should_query_functions {
+                    find_functions_in_module(db, term, project, regex, limit, settings)
+                } else {
+                    Ok(Vec::new())
+                }
+            });
+            // Kind filters are optional and not used for browse
+            let specs = tasks.spawn(|| {
+                if should_query_specs {
+                    find_specs(db, term, name, None, project, regex, limit, settings)
+                } else {
+                    Ok(Vec::new())
+                }
+            });
+            let types = tasks.spawn(|| {
+                if should_query_types {
+                    find_types(db, term, name, None, project, regex, limit, settings)
+                } else {
+                    Ok(Vec::new())
+                }
+            });
+            let fields = tasks.spawn(|| {
+                if should_query_structs {
+                    find_struct_fields(db, term, project, regex, limit, settings)
+                } else {
+                    Ok(Vec::new())
+                }
+            });
+            Ok::<_, Box<dyn Error>>((funcs.join()?, specs.join()?, types.join()?, fields.join()?))
+        })?;
+
+        for func in funcs {
+            // Filter by name if specified
+            if let Some(name_filter) = name
+                && !func.name.contains(name_filter) {
+                    continue;
+                }
+
+            definitions.push(Definition::Function {
+                module: func.module,
+                file: func.file,
+                name: func.name,
+                arity: func.arity,
+                line: func.line,
+                start_line: func.start_line,
+                end_line: func.end_line,
+                kind: func.kind,
+                args: String::new(), // Not in function_locations
+                return_type: String::new(), // Not in function_locations
+                pattern: func.pattern,
+                guard: func.guard,
+            });
         }
 
-        // Query specs
-        if should_query_specs {
-            let specs = find_specs(
-                db,
-                &self.module_or_file,
-                self.name.as_deref(),
-                None, // kind filter (optional, not used for browse)
-                &self.common.project,
-                self.common.regex,
-                self.common.limit,
-            )?;
-
-            for spec in specs {
-                definitions.push(Definition::Spec {
-                    module: spec.module,
-                    name: spec.name,
-                    arity: spec.arity,
-                    line: spec.line,
-                    kind: spec.kind,
-                    inputs: spec.inputs_string,
-                    returns: spec.return_string,
-                    full: spec.full,
-                });
-            }
+        for spec in specs {
+            definitions.push(Definition::Spec {
+                module: spec.module,
+                name: spec.name,
+                arity: spec.arity,
+                line: spec.line,
+                kind: spec.kind,
+                inputs: spec.inputs_string,
+                returns: spec.return_string,
+                full: spec.full,
+            });
         }
 
-        // Query types
-        if should_query_types {
-            let types = find_types(
-                db,
-                &self.module_or_file,
-                self.name.as_deref(),
-                None, // kind filter (optional, not used for browse)
-                &self.common.project,
-                self.common.regex,
-                self.common.limit,
-            )?;
-
-            for type_def in types {
-                definitions.push(Definition::Type {
-                    module: type_def.module,
-                    name: type_def.name,
-                    line: type_def.line,
-                    kind: type_def.kind,
-                    params: type_def.params,
-                    definition: type_def.definition,
-                });
-            }
+        for type_def in types {
+            definitions.push(Definition::Type {
+                module: type_def.module,
+                name: type_def.name,
+                line: type_def.line,
+                kind: type_def.kind,
+                params: type_def.params,
+                definition: type_def.definition,
+            });
         }
 
-        // Query structs
-        if should_query_structs {
-            let fields = find_struct_fields(db, &self.module_or_file, &self.common.project, self.common.regex, self.common.limit)?;
-            let structs = group_fields_into_structs(fields);
-
-            for struct_def in structs {
-                // Filter by name if specified
-                if let Some(ref name_filter) = self.name
-                    && !struct_def.module.contains(name_filter) {
-                        continue;
-                    }
-
-                definitions.push(Definition::Struct {
-                    module: struct_def.module.clone(),
-                    name: struct_def.module.clone(), // Struct name is same as module for now
-                    fields: struct_def.fields,
-                });
-            }
+        for struct_def in group_fields_into_structs(fields) {
+            // Filter by name if specified
+            if let Some(name_filter) = name
+                && !struct_def.module.contains(name_filter) {
+                    continue;
+                }
+
+            definitions.push(Definition::Struct {
+                module: struct_def.module.clone(),
+                name: struct_def.module.clone(), // Struct name is same as module for now
+                fields: struct_def.fields,
+            });
         }
 
         // Sort by module, then by line number
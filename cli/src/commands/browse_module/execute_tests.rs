@@ -38,6 +38,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -51,6 +52,30 @@ mod tests {
         },
     }
 
+    // The subqueries run on worker threads, which see the namespace too
+    #[rstest]
+    fn test_browse_module_outside_namespace(call_graph_db: db::DbInstance) {
+        use crate::commands::Execute;
+        use db::query_builders::QuerySettings;
+
+        let cmd = BrowseModuleCmd {
+            module_or_file: "MyApp.Accounts".to_string(),
+            kind: None,
+            name: None,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: vec!["Nope.None".to_string()],
+            },
+        };
+        let settings = QuerySettings { namespaces: cmd.common.namespace.clone(), ..Default::default() };
+        let result = cmd.execute(&call_graph_db, &settings);
+
+        let result = result.expect("Execute should succeed");
+        assert!(result.definitions.is_empty(), "{:?}", result.definitions);
+    }
+
     crate::execute_test! {
         test_name: test_browse_module_with_name_filter,
         fixture: call_graph_db,
@@ -62,6 +87,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -90,6 +116,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -118,6 +145,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -146,6 +174,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -175,6 +204,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -207,6 +237,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -233,6 +264,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -273,6 +305,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 5,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -298,6 +331,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: definitions,
@@ -317,6 +351,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
@@ -1,6 +1,7 @@
 use std::error::Error;
 
 use clap::{Parser, ValueEnum};
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
@@ -72,8 +73,8 @@ impl std::fmt::Display for DefinitionKind {
 }
 
 impl CommandRunner for BrowseModuleCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
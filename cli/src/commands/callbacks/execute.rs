@@ -0,0 +1,174 @@
+//! Behaviour contract checks for each implementing module.
+
+use std::collections::BTreeSet;
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::CallbacksCmd;
+use crate::commands::Execute;
+use db::queries::callbacks::{Callback, find_behaviour_callbacks, find_behaviour_implementers};
+use db::query_builders::QuerySettings;
+
+/// An implementing module and how it departs from the behaviour's contract
+#[derive(Debug, Clone, Serialize)]
+pub struct CallbackImplementation {
+    pub module: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub file: String,
+    /// Callbacks the module does not define, as name/arity
+    pub missing: Vec<String>,
+    /// Public functions named like a callback with an arity the behaviour does not declare
+    pub extra: Vec<String>,
+}
+
+/// Result of the callbacks command
+#[derive(Debug, Serialize)]
+pub struct CallbacksResult {
+    pub behaviour: String,
+    pub callbacks: Vec<Callback>,
+    /// Modules declaring the behaviour, before --incomplete-only
+    pub total_implementations: usize,
+    pub implementations: Vec<CallbackImplementation>,
+}
+
+impl Execute for CallbacksCmd {
+    type Output = CallbacksResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let callbacks = find_behaviour_callbacks(db, &self.project, &self.behaviour, settings)?;
+        let implementers = find_behaviour_implementers(db, &self.project, &self.behaviour)?;
+
+        let contract: BTreeSet<(&str, i64)> = callbacks.iter().map(|c| (c.name.as_str(), c.arity)).collect();
+        let callback_names: BTreeSet<&str> = callbacks.iter().map(|c| c.name.as_str()).collect();
+
+        let total_implementations = implementers.len();
+        let implementations = implementers
+            .into_iter()
+            .map(|implementer| {
+                let defined: BTreeSet<(&str, i64)> = implementer
+                    .functions
+                    .iter()
+                    .map(|(name, arity)| (name.as_str(), *arity))
+                    .collect();
+
+                let missing = contract
+                    .iter()
+                    .filter(|callback| !defined.contains(*callback))
+                    .map(|(name, arity)| format!("{}/{}", name, arity))
+                    .collect();
+                let extra = defined
+                    .iter()
+                    .filter(|(name, _)| callback_names.contains(name))
+                    .filter(|function| !contract.contains(*function))
+                    .map(|(name, arity)| format!("{}/{}", name, arity))
+                    .collect();
+
+                CallbackImplementation {
+                    module: implementer.module,
+                    file: implementer.file,
+                    missing,
+                    extra,
+                }
+            })
+            .filter(|implementation| {
+                !self.incomplete_only || !implementation.missing.is_empty() || !implementation.extra.is_empty()
+            })
+            .collect();
+
+        Ok(CallbacksResult {
+            behaviour: self.behaviour,
+            callbacks,
+            total_implementations,
+            implementations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BEHAVIOUR_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.EmailJob": {
+                "perform/1:3": {"name": "perform", "arity": 1, "file": "lib/email_job.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 6},
+                "backoff/2:8": {"name": "backoff", "arity": 2, "file": "lib/email_job.ex", "kind": "def", "line": 8, "start_line": 8, "end_line": 9},
+                "format/1:11": {"name": "format", "arity": 1, "file": "lib/email_job.ex", "kind": "def", "line": 11, "start_line": 11, "end_line": 12}
+            },
+            "MyApp.ReportJob": {
+                "perform/1:3": {"name": "perform", "arity": 1, "file": "lib/report_job.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 6},
+                "backoff/1:8": {"name": "backoff", "arity": 1, "file": "lib/report_job.ex", "kind": "def", "line": 8, "start_line": 8, "end_line": 9}
+            }
+        },
+        "calls": [],
+        "specs": {
+            "MyApp.Job": [
+                {"name": "perform", "arity": 1, "kind": "callback", "line": 2, "clauses": []},
+                {"name": "backoff", "arity": 1, "kind": "callback", "line": 3, "clauses": []}
+            ]
+        },
+        "behaviours": {
+            "MyApp.EmailJob": ["MyApp.Job"],
+            "MyApp.EmptyJob": ["MyApp.Job"],
+            "MyApp.ReportJob": ["MyApp.Job"]
+        }
+    }"#;
+
+    fn callbacks_cmd(behaviour: &str, incomplete_only: bool) -> CallbacksCmd {
+        CallbacksCmd {
+            behaviour: behaviour.to_string(),
+            incomplete_only,
+            project: "test_project".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_callbacks_missing_and_extra() {
+        let db = db::test_utils::setup_test_db(BEHAVIOUR_JSON, "test_project");
+        let result = callbacks_cmd("MyApp.Job", false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.callbacks.len(), 2);
+        assert_eq!(result.total_implementations, 3);
+
+        let email = &result.implementations[0];
+        assert_eq!(email.module, "MyApp.EmailJob");
+        assert_eq!(email.missing, ["backoff/1"]);
+        // format/1 is not named like a callback, so it is not extra
+        assert_eq!(email.extra, ["backoff/2"]);
+
+        let empty = &result.implementations[1];
+        assert_eq!(empty.module, "MyApp.EmptyJob");
+        assert_eq!(empty.missing, ["backoff/1", "perform/1"]);
+        assert!(empty.extra.is_empty());
+
+        let report = &result.implementations[2];
+        assert!(report.missing.is_empty() && report.extra.is_empty());
+    }
+
+    #[test]
+    fn test_callbacks_incomplete_only() {
+        let db = db::test_utils::setup_test_db(BEHAVIOUR_JSON, "test_project");
+        let result = callbacks_cmd("MyApp.Job", true)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_implementations, 3);
+        let modules: Vec<&str> = result.implementations.iter().map(|i| i.module.as_str()).collect();
+        assert_eq!(modules, ["MyApp.EmailJob", "MyApp.EmptyJob"]);
+    }
+
+    #[test]
+    fn test_callbacks_unknown_behaviour() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = callbacks_cmd("MyApp.Nope", false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert!(result.callbacks.is_empty());
+        assert!(result.implementations.is_empty());
+    }
+}
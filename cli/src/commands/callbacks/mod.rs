@@ -0,0 +1,44 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Audit the modules implementing a behaviour against its callbacks
+///
+/// Lists the `@callback`/`@macrocallback` contract of a behaviour module and
+/// every module declaring `@behaviour` for it (from the `behaviours` map of the
+/// call graph JSON). Each implementer is checked for missing callbacks and for
+/// extra ones: public functions named like a callback but with an arity the
+/// behaviour does not declare, the usual leftover of a contract change.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search callbacks MyApp.Job                       # Contract and implementers of MyApp.Job
+  code_search callbacks GenServer --incomplete-only     # Only implementers with missing or extra callbacks
+")]
+pub struct CallbacksCmd {
+    /// Behaviour module (exact name)
+    pub behaviour: String,
+
+    /// Only show implementers with missing or extra callbacks
+    #[arg(long, default_value_t = false)]
+    pub incomplete_only: bool,
+
+    /// Project to search in
+    #[arg(long, default_value = "default")]
+    pub project: String,
+}
+
+impl CommandRunner for CallbacksCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,135 @@
+//! Output formatting for callbacks command results.
+
+use super::execute::CallbacksResult;
+use crate::output::Outputable;
+
+impl Outputable for CallbacksResult {
+    fn to_table(&self) -> String {
+        let mut output = format!(
+            "Behaviour {}: {} callback(s), {} implementation(s)\n",
+            self.behaviour,
+            self.callbacks.len(),
+            self.total_implementations
+        );
+
+        if self.callbacks.is_empty() && self.total_implementations == 0 {
+            output.push_str("\nNo callbacks or implementations found.\n");
+            return output;
+        }
+
+        if !self.callbacks.is_empty() {
+            output.push_str("\nCallbacks:\n");
+            for callback in &self.callbacks {
+                output.push_str(&format!(
+                    "  {}/{} [{}] L{}\n",
+                    callback.name, callback.arity, callback.kind, callback.line
+                ));
+            }
+        }
+
+        if self.implementations.is_empty() {
+            if self.total_implementations > 0 {
+                output.push_str("\nAll implementations are complete.\n");
+            }
+            return output;
+        }
+
+        output.push_str("\nImplementations:\n");
+        for implementation in &self.implementations {
+            output.push_str(&format!("  {}", implementation.module));
+            if !implementation.file.is_empty() {
+                output.push_str(&format!(" ({})", implementation.file));
+            }
+            if implementation.missing.is_empty() && implementation.extra.is_empty() {
+                output.push_str(": complete\n");
+                continue;
+            }
+            output.push('\n');
+            if !implementation.missing.is_empty() {
+                output.push_str(&format!("    missing: {}\n", implementation.missing.join(", ")));
+            }
+            if !implementation.extra.is_empty() {
+                output.push_str(&format!("    extra: {}\n", implementation.extra.join(", ")));
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::callbacks::execute::CallbackImplementation;
+    use db::queries::callbacks::Callback;
+
+    fn result() -> CallbacksResult {
+        CallbacksResult {
+            behaviour: "MyApp.Job".to_string(),
+            callbacks: vec![
+                Callback {
+                    name: "backoff".to_string(),
+                    arity: 1,
+                    kind: "callback".to_string(),
+                    line: 3,
+                },
+                Callback {
+                    name: "perform".to_string(),
+                    arity: 1,
+                    kind: "callback".to_string(),
+                    line: 2,
+                },
+            ],
+            total_implementations: 2,
+            implementations: vec![
+                CallbackImplementation {
+                    module: "MyApp.EmailJob".to_string(),
+                    file: "lib/email_job.ex".to_string(),
+                    missing: vec!["backoff/1".to_string()],
+                    extra: vec!["backoff/2".to_string()],
+                },
+                CallbackImplementation {
+                    module: "MyApp.ReportJob".to_string(),
+                    file: "lib/report_job.ex".to_string(),
+                    missing: vec![],
+                    extra: vec![],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_callbacks_table() {
+        let expected = [
+            "Behaviour MyApp.Job: 2 callback(s), 2 implementation(s)",
+            "",
+            "Callbacks:",
+            "  backoff/1 [callback] L3",
+            "  perform/1 [callback] L2",
+            "",
+            "Implementations:",
+            "  MyApp.EmailJob (lib/email_job.ex)",
+            "    missing: backoff/1",
+            "    extra: backoff/2",
+            "  MyApp.ReportJob (lib/report_job.ex): complete",
+            "",
+        ];
+        assert_eq!(result().to_table(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_callbacks_table_all_complete() {
+        let mut result = result();
+        result.implementations.clear();
+        assert!(result.to_table().ends_with("\nAll implementations are complete.\n"));
+    }
+
+    #[test]
+    fn test_callbacks_table_empty() {
+        let mut result = result();
+        result.callbacks.clear();
+        result.implementations.clear();
+        result.total_implementations = 0;
+        assert!(result.to_table().ends_with("No callbacks or implementations found.\n"));
+    }
+}
@@ -16,6 +16,24 @@ mod tests {
         required_arg: "<MODULE>",
     }
 
+    crate::cli_option_test! {
+        command: "calls-from",
+        variant: CallsFrom,
+        test_name: test_with_group_by,
+        args: ["MyApp.Service", "--group-by", "callee-module"],
+        field: group_by,
+        expected: Some(db::queries::calls::CallGrouping::CalleeModule),
+    }
+
+    crate::cli_option_test! {
+        command: "calls-from",
+        variant: CallsFrom,
+        test_name: test_with_kind,
+        args: ["MyApp.Service", "--kind", "local"],
+        field: kinds,
+        expected: vec![db::queries::calls::CallKind::Local],
+    }
+
     crate::cli_option_test! {
         command: "calls-from",
         variant: CallsFrom,
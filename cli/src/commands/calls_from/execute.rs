@@ -4,8 +4,10 @@ use serde::Serialize;
 
 use super::CallsFromCmd;
 use crate::commands::Execute;
+use db::query_builders::{PatternMatching, QuerySettings};
 use db::queries::calls_from::find_calls_from;
-use db::types::{Call, ModuleGroupResult};
+use db::queries::calls::{count_calls, CallDirection, CallGrouping};
+use db::types::{Call, CallGroupsResult, ModuleGroupResult};
 use crate::utils::group_calls;
 
 /// A caller function with all its outgoing calls
@@ -78,15 +80,23 @@ struct CallerFunctionKey {
 impl Execute for CallsFromCmd {
     type Output = ModuleGroupResult<CallerFunction>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let patterns = [Some(self.module.as_str()), self.function.as_deref()];
+        let matching = PatternMatching::new(self.common.regex, patterns.into_iter().flatten());
+        let module = matching.pattern(&self.module);
+        let function = self.function.as_deref().map(|f| matching.pattern(f));
+        let use_regex = matching.use_regex();
+
         let calls = find_calls_from(
             db,
-            &self.module,
-            self.function.as_deref(),
+            &module,
+            function.as_deref(),
             self.arity,
+            &self.kinds,
             &self.common.project,
-            self.common.regex,
+            use_regex,
             self.common.limit,
+            settings,
         )?;
 
         Ok(build_calls_from_result(
@@ -96,3 +106,37 @@ impl Execute for CallsFromCmd {
         ))
     }
 }
+
+impl CallsFromCmd {
+    /// Count the matched calls per group (`--group-by`)
+    pub fn count(
+        self,
+        db: &db::DbInstance,
+        grouping: CallGrouping,
+        settings: &QuerySettings,
+    ) -> Result<CallGroupsResult, Box<dyn Error>> {
+        let items = count_calls(
+            db,
+            CallDirection::From,
+            grouping,
+            &self.module,
+            self.function.as_deref(),
+            self.arity,
+            &self.kinds,
+            &self.common.project,
+            self.common.regex,
+            self.common.limit,
+            settings,
+        )?;
+
+        Ok(CallGroupsResult {
+            module_pattern: self.module,
+            function_pattern: self.function,
+            direction: "from".to_string(),
+            group_by: grouping.label().to_string(),
+            total_calls: items.iter().map(|g| g.calls).sum(),
+            total_items: items.len(),
+            items,
+        })
+    }
+}
@@ -4,6 +4,8 @@
 mod tests {
     use super::super::CallsFromCmd;
     use crate::commands::CommonArgs;
+    use db::queries::calls::{CallGrouping, CallKind};
+    use db::query_builders::QuerySettings;
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -25,10 +27,13 @@ mod tests {
             module: "MyApp.Accounts".to_string(),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -37,6 +42,31 @@ mod tests {
         },
     }
 
+    // Only fetch→do_fetch is a local call in MyApp.Service
+    crate::execute_test! {
+        test_name: test_calls_from_local_kind,
+        fixture: populated_db,
+        cmd: CallsFromCmd {
+            module: "MyApp.Service".to_string(),
+            function: None,
+            arity: None,
+            group_by: None,
+            kinds: vec![CallKind::Local],
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert_eq!(result.total_items, 1);
+            let call = &result.items[0].entries[0].calls[0];
+            assert_eq!(call.callee.name.as_ref(), "do_fetch");
+            assert_eq!(call.call_kind.as_deref(), Some("call"));
+        },
+    }
+
     // get_user functions (both arities) call Repo.get
     // Per-function deduplication: get_user/1 has 1 call, get_user/2 has 1 call = 2 displayed
     crate::execute_test! {
@@ -46,10 +76,13 @@ mod tests {
             module: "MyApp.Accounts".to_string(),
             function: Some("get_user".to_string()),
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -76,10 +109,13 @@ mod tests {
             module: "MyApp\\..*".to_string(),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -99,10 +135,13 @@ mod tests {
             module: "NonExistent".to_string(),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -122,10 +161,13 @@ mod tests {
             module: "MyApp.Accounts".to_string(),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -141,10 +183,13 @@ mod tests {
             module: "MyApp\\..*".to_string(),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 1,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -152,6 +197,62 @@ mod tests {
         },
     }
 
+    // =========================================================================
+    // Group-by tests
+    // =========================================================================
+
+    #[rstest]
+    fn test_calls_from_group_by(populated_db: db::DbInstance) {
+        let cmd = CallsFromCmd {
+            module: "MyApp.Service".to_string(),
+            function: None,
+            arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        };
+        let result = cmd
+            .count(&populated_db, CallGrouping::CalleeModule, &QuerySettings::default())
+            .expect("Count should succeed");
+
+        assert_eq!(result.total_items, 3);
+        assert_eq!(result.total_calls, 4);
+        assert_eq!(result.items[0].group, "MyApp.Service");
+    }
+
+    #[rstest]
+    fn test_calls_from_regex_query_text() {
+        use crate::commands::Execute;
+        use db::test_utils::MockDatabase;
+
+        let cmd = CallsFromCmd {
+            module: "^MyApp\\.".to_string(),
+            function: Some("get_.*".to_string()),
+            arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: true,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        };
+        let mock = MockDatabase::new();
+        mock.run(|db| cmd.execute(db, &QuerySettings::default())).expect("Execute should succeed");
+
+        let queries = mock.queries();
+        let query = queries.iter().find(|q| q.script.contains("*calls{")).expect("Expected a calls query");
+        assert!(query.script.contains("regex_matches(caller_module, $module_pattern)"));
+        assert_eq!(query.param("module_pattern"), Some(&"^MyApp\\.".into()));
+        assert_eq!(query.param("function_pattern"), Some(&"get_.*".into()));
+    }
+
     // =========================================================================
     // Error handling tests
     // =========================================================================
@@ -162,10 +263,13 @@ mod tests {
             module: "MyApp".to_string(),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
@@ -11,6 +11,8 @@ use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use db::queries::calls::{CallGrouping, CallKind};
+use db::query_builders::QuerySettings;
 
 /// Show what a module/function calls (outgoing edges)
 #[derive(Args, Debug)]
@@ -18,7 +20,9 @@ use crate::output::{OutputFormat, Outputable};
 Examples:
   code_search calls-from MyApp.Accounts              # All calls from module
   code_search calls-from MyApp.Accounts get_user     # Calls from specific function
-  code_search calls-from MyApp.Accounts get_user 1   # With specific arity")]
+  code_search calls-from MyApp.Accounts get_user 1   # With specific arity
+  code_search calls-from MyApp.Accounts --group-by callee-module # Which modules Accounts depends on most
+  code_search calls-from MyApp.Accounts --kind remote # Only calls into other modules")]
 pub struct CallsFromCmd {
     /// Module name (exact match or pattern with --regex)
     pub module: String,
@@ -29,13 +33,26 @@ pub struct CallsFromCmd {
     /// Function arity (optional, matches all arities if not specified)
     pub arity: Option<i64>,
 
+    /// Return call counts per group instead of individual calls
+    #[arg(long, value_enum)]
+    pub group_by: Option<CallGrouping>,
+
+    /// Only calls of this kind (repeatable: remote, local, pipe, capture)
+    #[arg(long = "kind", value_enum)]
+    pub kinds: Vec<CallKind>,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for CallsFromCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        if let Some(grouping) = self.group_by {
+            let result = self.count(db, grouping, settings)?;
+            return Ok(result.format(format));
+        }
+
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
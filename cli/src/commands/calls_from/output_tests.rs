@@ -72,7 +72,9 @@ MyApp.Accounts (lib/my_app/accounts.ex)
                 callee: FunctionRef::new("MyApp.Repo", "get", 2),
                 line: 12,
                 call_type: Some("remote".to_string()),
+                call_kind: None,
                 depth: None,
+                weight: None,
             }],
         };
 
@@ -112,7 +114,9 @@ MyApp.Accounts (lib/my_app/accounts.ex)
                 callee: FunctionRef::new("MyApp.Repo", "get", 2),
                 line: 12,
                 call_type: Some("remote".to_string()),
+                call_kind: None,
                 depth: None,
+                weight: None,
             }],
         };
 
@@ -135,7 +139,9 @@ MyApp.Accounts (lib/my_app/accounts.ex)
                 callee: FunctionRef::new("MyApp.Repo", "all", 1),
                 line: 22,
                 call_type: Some("remote".to_string()),
+                call_kind: None,
                 depth: None,
+                weight: None,
             }],
         };
 
@@ -16,13 +16,31 @@ mod tests {
         required_arg: "<MODULE>",
     }
 
+    crate::cli_option_test! {
+        command: "calls-to",
+        variant: CallsTo,
+        test_name: test_with_group_by,
+        args: ["MyApp.Repo", "--group-by", "caller-module"],
+        field: group_by,
+        expected: Some(db::queries::calls::CallGrouping::CallerModule),
+    }
+
+    crate::cli_option_test! {
+        command: "calls-to",
+        variant: CallsTo,
+        test_name: test_with_kinds,
+        args: ["MyApp.Repo", "--kind", "capture", "--kind", "pipe"],
+        field: kinds,
+        expected: vec![db::queries::calls::CallKind::Capture, db::queries::calls::CallKind::Pipe],
+    }
+
     crate::cli_option_test! {
         command: "calls-to",
         variant: CallsTo,
         test_name: test_with_module,
         args: ["MyApp.Repo"],
         field: module,
-        expected: "MyApp.Repo",
+        expected: Some("MyApp.Repo".to_string()),
     }
 
     crate::cli_option_test! {
@@ -61,6 +79,21 @@ mod tests {
         expected: 25,
     }
 
+    crate::cli_option_test! {
+        command: "calls-to",
+        variant: CallsTo,
+        test_name: test_with_stdin,
+        args: ["--stdin"],
+        field: stdin,
+        expected: true,
+    }
+
+    crate::cli_error_test! {
+        command: "calls-to",
+        test_name: test_stdin_conflicts_with_positional,
+        args: ["MyApp.Repo", "--stdin"],
+    }
+
     crate::cli_limit_tests! {
         command: "calls-to",
         variant: CallsTo,
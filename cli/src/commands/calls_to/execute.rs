@@ -4,8 +4,11 @@ use serde::Serialize;
 
 use super::CallsToCmd;
 use crate::commands::Execute;
-use db::queries::calls_to::find_calls_to;
-use db::types::{Call, ModuleGroupResult};
+use db::query_builders::{FunctionTarget, PatternMatching, QuerySettings};
+use db::queries::calls_to::{find_calls_to, find_calls_to_targets};
+use db::queries::calls::{count_calls, CallDirection, CallGrouping};
+use db::types::{Call, CallGroupsResult, ModuleGroupResult};
+use crate::targets::stdin_targets;
 use crate::utils::group_calls;
 
 /// A callee function (target) with all its callers
@@ -66,21 +69,81 @@ fn build_callee_result(module_pattern: String, function_pattern: String, calls:
 impl Execute for CallsToCmd {
     type Output = ModuleGroupResult<CalleeFunction>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        if self.stdin {
+            return self.execute_targets(db, &stdin_targets()?, settings);
+        }
+
+        let module_pattern = self.module.unwrap_or_default();
+        let patterns = [Some(module_pattern.as_str()), self.function.as_deref()];
+        let matching = PatternMatching::new(self.common.regex, patterns.into_iter().flatten());
+        let module = matching.pattern(&module_pattern);
+        let function = self.function.as_deref().map(|f| matching.pattern(f));
+        let use_regex = matching.use_regex();
+
         let calls = find_calls_to(
             db,
-            &self.module,
-            self.function.as_deref(),
+            &module,
+            function.as_deref(),
             self.arity,
+            &self.kinds,
             &self.common.project,
-            self.common.regex,
+            use_regex,
             self.common.limit,
+            settings,
         )?;
 
         Ok(build_callee_result(
-            self.module,
+            module_pattern,
             self.function.unwrap_or_default(),
             calls,
         ))
     }
 }
+
+impl CallsToCmd {
+    /// Callers of each of the targets, from one query (`--stdin`)
+    pub fn execute_targets(
+        self,
+        db: &db::DbInstance,
+        targets: &[FunctionTarget],
+        settings: &QuerySettings,
+    ) -> Result<ModuleGroupResult<CalleeFunction>, Box<dyn Error>> {
+        let calls = find_calls_to_targets(db, targets, &self.kinds, &self.common.project, self.common.limit, settings)?;
+        let labels: Vec<String> = targets.iter().map(FunctionTarget::label).collect();
+        Ok(build_callee_result(labels.join(", "), String::new(), calls))
+    }
+
+    /// Count the matched calls per group (`--group-by`)
+    pub fn count(
+        self,
+        db: &db::DbInstance,
+        grouping: CallGrouping,
+        settings: &QuerySettings,
+    ) -> Result<CallGroupsResult, Box<dyn Error>> {
+        let module = self.module.unwrap_or_default();
+        let items = count_calls(
+            db,
+            CallDirection::To,
+            grouping,
+            &module,
+            self.function.as_deref(),
+            self.arity,
+            &self.kinds,
+            &self.common.project,
+            self.common.regex,
+            self.common.limit,
+            settings,
+        )?;
+
+        Ok(CallGroupsResult {
+            module_pattern: module,
+            function_pattern: self.function,
+            direction: "to".to_string(),
+            group_by: grouping.label().to_string(),
+            total_calls: items.iter().map(|g| g.calls).sum(),
+            total_items: items.len(),
+            items,
+        })
+    }
+}
@@ -4,6 +4,8 @@
 mod tests {
     use super::super::CallsToCmd;
     use crate::commands::CommonArgs;
+    use db::queries::calls::CallGrouping;
+    use db::query_builders::{FunctionTarget, QuerySettings};
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -21,13 +23,17 @@ mod tests {
         test_name: test_calls_to_module,
         fixture: populated_db,
         cmd: CallsToCmd {
-            module: "MyApp.Repo".to_string(),
+            module: Some("MyApp.Repo".to_string()),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -41,13 +47,17 @@ mod tests {
         test_name: test_calls_to_function,
         fixture: populated_db,
         cmd: CallsToCmd {
-            module: "MyApp.Repo".to_string(),
+            module: Some("MyApp.Repo".to_string()),
             function: Some("get".to_string()),
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -56,17 +66,48 @@ mod tests {
         },
     }
 
+    // get_user calls Repo.get from 2 call sites, do_fetch from 1
+    crate::execute_test! {
+        test_name: test_calls_to_weights,
+        fixture: populated_db,
+        cmd: CallsToCmd {
+            module: Some("MyApp.Repo".to_string()),
+            function: Some("get".to_string()),
+            arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            let callers: Vec<_> = result.items.iter().flat_map(|m| &m.entries).flat_map(|f| &f.callers).collect();
+            for call in callers {
+                let expected = if call.caller.name.as_ref() == "get_user" { 2 } else { 1 };
+                assert_eq!(call.weight, Some(expected), "weight of {}", call.caller.name);
+            }
+        },
+    }
+
     crate::execute_test! {
         test_name: test_calls_to_function_with_arity,
         fixture: populated_db,
         cmd: CallsToCmd {
-            module: "MyApp.Repo".to_string(),
+            module: Some("MyApp.Repo".to_string()),
             function: Some("get".to_string()),
             arity: Some(2),
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -85,13 +126,17 @@ mod tests {
         test_name: test_calls_to_regex_function,
         fixture: populated_db,
         cmd: CallsToCmd {
-            module: "MyApp.Repo".to_string(),
+            module: Some("MyApp.Repo".to_string()),
             function: Some("get|all".to_string()),
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -100,6 +145,59 @@ mod tests {
         },
     }
 
+    // --namespace restricts the callers: Service.do_fetch is left out
+    #[rstest]
+    fn test_calls_to_in_namespace(populated_db: db::DbInstance) {
+        use crate::commands::Execute;
+
+        let cmd = CallsToCmd {
+            module: Some("MyApp.Repo".to_string()),
+            function: None,
+            arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: vec!["MyApp.Accounts".to_string()],
+            },
+        };
+        let settings = QuerySettings { namespaces: cmd.common.namespace.clone(), ..Default::default() };
+        let result = cmd.execute(&populated_db, &settings);
+
+        let result = result.expect("Execute should succeed");
+        assert_eq!(result.total_items, 3);
+    }
+
+    // The namespace and the module pattern are the same text; only calls from
+    // inside MyApp.Repo would match, and there are none
+    #[rstest]
+    fn test_calls_to_namespace_same_as_module(populated_db: db::DbInstance) {
+        use crate::commands::Execute;
+
+        let cmd = CallsToCmd {
+            module: Some("MyApp.Repo".to_string()),
+            function: None,
+            arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: vec!["MyApp.Repo".to_string()],
+            },
+        };
+        let settings = QuerySettings { namespaces: cmd.common.namespace.clone(), ..Default::default() };
+        let result = cmd.execute(&populated_db, &settings);
+
+        let result = result.expect("Execute should succeed");
+        assert_eq!(result.total_items, 0);
+    }
+
     // =========================================================================
     // No match / empty result tests
     // =========================================================================
@@ -108,13 +206,17 @@ mod tests {
         test_name: test_calls_to_no_match,
         fixture: populated_db,
         cmd: CallsToCmd {
-            module: "NonExistent".to_string(),
+            module: Some("NonExistent".to_string()),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -127,13 +229,17 @@ mod tests {
         test_name: test_calls_to_nonexistent_arity,
         fixture: populated_db,
         cmd: CallsToCmd {
-            module: "MyApp.Repo".to_string(),
+            module: Some("MyApp.Repo".to_string()),
             function: Some("get".to_string()),
             arity: Some(99),
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -150,13 +256,17 @@ mod tests {
         test_name: test_calls_to_with_project_filter,
         fixture: populated_db,
         cmd: CallsToCmd {
-            module: "MyApp.Repo".to_string(),
+            module: Some("MyApp.Repo".to_string()),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -168,13 +278,17 @@ mod tests {
         test_name: test_calls_to_with_limit,
         fixture: populated_db,
         cmd: CallsToCmd {
-            module: "MyApp.Repo".to_string(),
+            module: Some("MyApp.Repo".to_string()),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 2,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -182,6 +296,64 @@ mod tests {
         },
     }
 
+    // =========================================================================
+    // Group-by tests
+    // =========================================================================
+
+    #[rstest]
+    fn test_calls_to_group_by(populated_db: db::DbInstance) {
+        let cmd = CallsToCmd {
+            module: Some("MyApp.Repo".to_string()),
+            function: None,
+            arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        };
+        let result = cmd
+            .count(&populated_db, CallGrouping::CallerModule, &QuerySettings::default())
+            .expect("Count should succeed");
+
+        assert_eq!(result.total_items, 2);
+        assert_eq!(result.total_calls, 4);
+        assert_eq!(result.items[0].group, "MyApp.Accounts");
+    }
+
+    // =========================================================================
+    // --stdin target tests
+    // =========================================================================
+
+    #[rstest]
+    fn test_calls_to_targets(populated_db: db::DbInstance) {
+        let cmd = CallsToCmd {
+            module: None,
+            function: None,
+            arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: true,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        };
+        let targets = [FunctionTarget::parse("MyApp.Repo.get").unwrap(), FunctionTarget::parse("MyApp.Repo.all").unwrap()];
+        let result = cmd.execute_targets(&populated_db, &targets, &QuerySettings::default()).expect("Execute should succeed");
+
+        // get: get_user/1, get_user/2, do_fetch; all: list_users
+        assert_eq!(result.total_items, 4);
+        assert_eq!(result.module_pattern, "MyApp.Repo.get, MyApp.Repo.all");
+    }
+
+
     // =========================================================================
     // Error handling tests
     // =========================================================================
@@ -189,13 +361,17 @@ mod tests {
     crate::execute_empty_db_test! {
         cmd_type: CallsToCmd,
         cmd: CallsToCmd {
-            module: "MyApp.Repo".to_string(),
+            module: Some("MyApp.Repo".to_string()),
             function: None,
             arity: None,
+            group_by: None,
+            kinds: Vec::new(),
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
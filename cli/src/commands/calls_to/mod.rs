@@ -11,6 +11,9 @@ use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::suggest::Lookup;
+use db::queries::calls::{CallGrouping, CallKind};
+use db::query_builders::QuerySettings;
 
 /// Show what calls a module/function (incoming edges)
 #[derive(Args, Debug)]
@@ -19,10 +22,14 @@ Examples:
   code_search calls-to MyApp.Repo                    # All callers of module
   code_search calls-to MyApp.Repo get                # Callers of specific function
   code_search calls-to MyApp.Repo get 2              # With specific arity
-  code_search calls-to MyApp.Accounts get_user       # Find all call sites")]
+  code_search calls-to MyApp.Accounts get_user       # Find all call sites
+  code_search calls-to MyApp.Repo --group-by caller-module # Which modules call Repo most
+  code_search calls-to MyApp.Accounts active? --kind capture # Where it is passed as a callback
+  code_search unused -o csv --fields module,name,arity | code_search calls-to --stdin")]
 pub struct CallsToCmd {
     /// Module name (exact match or pattern with --regex)
-    pub module: String,
+    #[arg(required_unless_present = "stdin")]
+    pub module: Option<String>,
 
     /// Function name (optional, if not specified shows all calls to module)
     pub function: Option<String>,
@@ -30,13 +37,32 @@ pub struct CallsToCmd {
     /// Function arity (optional, matches all arities if not specified)
     pub arity: Option<i64>,
 
+    /// Return call counts per group instead of individual calls
+    #[arg(long, value_enum)]
+    pub group_by: Option<CallGrouping>,
+
+    /// Only calls of this kind (repeatable: remote, local, pipe, capture)
+    #[arg(long = "kind", value_enum)]
+    pub kinds: Vec<CallKind>,
+
+    /// Read the functions from stdin, one Module.function[/arity] per line, and query them together
+    #[arg(long, conflicts_with_all = ["module", "regex", "group_by"])]
+    pub stdin: bool,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for CallsToCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
-        Ok(result.format(format))
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        if let Some(grouping) = self.group_by {
+            let result = self.count(db, grouping, settings)?;
+            return Ok(result.format(format));
+        }
+
+        let lookup = Lookup::new(&self.common, self.module.as_deref(), self.function.as_deref());
+        let result = self.execute(db, settings)?;
+        let empty = result.items.is_empty();
+        Ok(lookup.suggest(db, result, empty, settings)?.format(format))
     }
 }
@@ -69,7 +69,9 @@ MyApp.Repo
                 callee: FunctionRef::new("MyApp.Repo", "get", 2),
                 line: 12,
                 call_type: Some("remote".to_string()),
+                call_kind: None,
                 depth: None,
+                weight: None,
             }],
         };
 
@@ -107,7 +109,9 @@ MyApp.Repo
                     callee: FunctionRef::new("MyApp.Repo", "get", 2),
                     line: 12,
                     call_type: Some("remote".to_string()),
+                    call_kind: None,
                     depth: None,
+                    weight: None,
                 },
                 Call {
                     caller: FunctionRef::with_definition(
@@ -122,7 +126,9 @@ MyApp.Repo
                     callee: FunctionRef::new("MyApp.Repo", "get", 2),
                     line: 40,
                     call_type: Some("remote".to_string()),
+                    call_kind: None,
                     depth: None,
+                    weight: None,
                 },
             ],
         };
@@ -0,0 +1,220 @@
+//! Centrality ranking over the function-level call graph.
+
+use std::error::Error;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::{CentralityCmd, CentralityKind};
+use crate::commands::Execute;
+use crate::graph::CallGraph;
+use db::query_builders::{compile_pattern, QuerySettings};
+use db::queries::centrality::find_function_edges;
+
+/// A function with its centrality score
+#[derive(Debug, Clone, Serialize)]
+pub struct CentralityEntry {
+    pub module: String,
+    pub function: String,
+    pub score: f64,
+    pub in_degree: usize,
+    pub out_degree: usize,
+}
+
+/// Result of the centrality command
+#[derive(Debug, Serialize)]
+pub struct CentralityResult {
+    pub kind: String,
+    /// Whether scores are estimates (`--approximate`)
+    pub approximate: bool,
+    /// Functions in the call graph
+    pub total_nodes: usize,
+    /// Distinct call edges in the call graph
+    pub total_edges: usize,
+    pub total_items: usize,
+    pub entries: Vec<CentralityEntry>,
+}
+
+impl Execute for CentralityCmd {
+    type Output = CentralityResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let filter = match &self.module {
+            Some(pattern) if self.common.regex => Some(compile_pattern(pattern)?),
+            Some(pattern) => Some(Regex::new(&regex::escape(pattern))?),
+            None => None,
+        };
+
+        let edges = find_function_edges(db, &self.common.project, settings)?;
+        let graph = CallGraph::from_edges(edges.into_iter().map(|e| {
+            (
+                (e.caller_module, e.caller_function),
+                (e.callee_module, e.callee_function),
+            )
+        }));
+
+        let scores = match self.kind {
+            CentralityKind::Pagerank => graph.pagerank(self.damping, self.iterations),
+            CentralityKind::Betweenness if self.approximate => graph.betweenness_sampled(self.samples as usize),
+            CentralityKind::Betweenness => graph.betweenness(),
+            CentralityKind::Degree => graph.degree_centrality(),
+            CentralityKind::Reach if self.approximate => graph.reach_approximate(),
+            CentralityKind::Reach => graph.reach(),
+        };
+
+        let mut entries: Vec<CentralityEntry> = graph
+            .nodes()
+            .enumerate()
+            .filter(|(_, (module, _))| filter.as_ref().is_none_or(|re| re.is_match(module)))
+            .map(|(i, (module, function))| CentralityEntry {
+                module: module.clone(),
+                function: function.clone(),
+                score: scores[i],
+                in_degree: graph.in_degree(i),
+                out_degree: graph.out_degree(i),
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.module.cmp(&b.module))
+                .then_with(|| a.function.cmp(&b.function))
+        });
+        entries.truncate(self.common.limit as usize);
+
+        Ok(CentralityResult {
+            kind: self.kind.as_str().to_string(),
+            approximate: self.approximate,
+            total_nodes: graph.node_count(),
+            total_edges: graph.edge_count(),
+            total_items: entries.len(),
+            entries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+
+    fn centrality_cmd(kind: CentralityKind, module: Option<&str>) -> CentralityCmd {
+        CentralityCmd {
+            module: module.map(String::from),
+            kind,
+            damping: 0.85,
+            iterations: 100,
+            approximate: false,
+            samples: 256,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_centrality_pagerank() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = centrality_cmd(CentralityKind::Pagerank, None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.kind, "pagerank");
+        assert_eq!(result.total_nodes, 12);
+        assert_eq!(result.total_edges, 10);
+        assert_eq!(result.total_items, 12);
+
+        let total: f64 = result.entries.iter().map(|e| e.score).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+        for pair in result.entries.windows(2) {
+            assert!(pair[0].score >= pair[1].score);
+        }
+    }
+
+    #[test]
+    fn test_centrality_betweenness() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = centrality_cmd(CentralityKind::Betweenness, None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        // Service.process bridges Controller.create to the fetch and notify chains
+        let top = &result.entries[0];
+        assert_eq!(top.module, "MyApp.Service");
+        assert_eq!(top.function, "process");
+        assert_eq!(top.in_degree, 1);
+        assert_eq!(top.out_degree, 2);
+    }
+
+    #[test]
+    fn test_centrality_degree() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = centrality_cmd(CentralityKind::Degree, None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        let top = &result.entries[0];
+        assert_eq!(top.function, "process");
+        assert!((top.score - 3.0 / 11.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_centrality_reach() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let exact = centrality_cmd(CentralityKind::Reach, None).execute(&db, &QuerySettings::default()).unwrap();
+        let mut cmd = centrality_cmd(CentralityKind::Reach, None);
+        cmd.approximate = true;
+        let approximate = cmd.execute(&db, &QuerySettings::default()).unwrap();
+
+        assert_eq!(exact.kind, "reach");
+        assert!(approximate.approximate);
+        // Leaves reach nothing
+        let get = exact.entries.iter().find(|e| e.module == "MyApp.Repo" && e.function == "get").unwrap();
+        assert_eq!(get.score, 0.0);
+        for entry in &exact.entries {
+            let estimate = approximate
+                .entries
+                .iter()
+                .find(|e| e.module == entry.module && e.function == entry.function)
+                .unwrap();
+            assert!((estimate.score - entry.score).abs() <= 0.2 * entry.score + 1e-9, "{}.{}", entry.module, entry.function);
+        }
+    }
+
+    #[test]
+    fn test_centrality_sampled_betweenness() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let exact = centrality_cmd(CentralityKind::Betweenness, None).execute(&db, &QuerySettings::default()).unwrap();
+        let mut cmd = centrality_cmd(CentralityKind::Betweenness, None);
+        cmd.approximate = true;
+        let sampled = cmd.execute(&db, &QuerySettings::default()).unwrap();
+
+        // With more samples than functions every function is a source
+        let scores = |r: &CentralityResult| r.entries.iter().map(|e| e.score).collect::<Vec<_>>();
+        assert_eq!(scores(&sampled), scores(&exact));
+    }
+
+    #[test]
+    fn test_centrality_module_filter() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = centrality_cmd(CentralityKind::Degree, Some("MyApp.Repo"))
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        // Scores are still computed over the whole graph
+        assert_eq!(result.total_nodes, 12);
+        assert_eq!(result.total_items, 2);
+        assert!(result.entries.iter().all(|e| e.module == "MyApp.Repo"));
+    }
+
+    #[test]
+    fn test_centrality_empty_db() {
+        let db = db::test_utils::setup_empty_test_db();
+        let result = centrality_cmd(CentralityKind::Pagerank, None).execute(&db, &QuerySettings::default());
+        assert!(result.is_err());
+    }
+}
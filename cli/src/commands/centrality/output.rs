@@ -0,0 +1,98 @@
+//! Output formatting for centrality command results.
+
+use super::execute::CentralityResult;
+use crate::output::Outputable;
+
+impl Outputable for CentralityResult {
+    fn to_table(&self) -> String {
+        let mut output = String::new();
+        let approximate = if self.approximate { ", approximate" } else { "" };
+        output.push_str(&format!(
+            "Centrality ({}{}) over {} function(s), {} call edge(s)\n\n",
+            self.kind, approximate, self.total_nodes, self.total_edges
+        ));
+
+        if self.entries.is_empty() {
+            output.push_str("No functions found.\n");
+            return output;
+        }
+
+        output.push_str(&format!("Top {} function(s):\n\n", self.total_items));
+
+        let names: Vec<String> = self
+            .entries
+            .iter()
+            .map(|e| format!("{}.{}", e.module, e.function))
+            .collect();
+        let name_width = names.iter().map(|n| n.len()).max().unwrap_or(0).max(8);
+
+        output.push_str(&format!(
+            "{:<name_width$}  {:>8}  {:>4}  {:>4}\n",
+            "Function",
+            "Score",
+            "In",
+            "Out",
+            name_width = name_width,
+        ));
+
+        for (name, entry) in names.iter().zip(&self.entries) {
+            output.push_str(&format!(
+                "{:<name_width$}  {:>8.4}  {:>4}  {:>4}\n",
+                name,
+                entry.score,
+                entry.in_degree,
+                entry.out_degree,
+                name_width = name_width,
+            ));
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::centrality::execute::CentralityEntry;
+
+    fn result(entries: Vec<CentralityEntry>) -> CentralityResult {
+        CentralityResult {
+            kind: "pagerank".to_string(),
+            approximate: false,
+            total_nodes: 12,
+            total_edges: 10,
+            total_items: entries.len(),
+            entries,
+        }
+    }
+
+    #[test]
+    fn test_centrality_output_empty() {
+        let output = result(vec![]).to_table();
+        assert!(output.contains("Centrality (pagerank) over 12 function(s), 10 call edge(s)"));
+        assert!(output.contains("No functions found"));
+    }
+
+    #[test]
+    fn test_centrality_output_entries() {
+        let output = result(vec![CentralityEntry {
+            module: "MyApp.Repo".to_string(),
+            function: "get".to_string(),
+            score: 0.21875,
+            in_degree: 2,
+            out_degree: 0,
+        }])
+        .to_table();
+
+        assert!(output.contains("Top 1 function(s)"));
+        assert!(output.contains("MyApp.Repo.get"));
+        assert!(output.contains("0.2188"));
+    }
+
+    #[test]
+    fn test_centrality_output_json() {
+        let json = serde_json::to_string(&result(vec![])).unwrap();
+        assert!(json.contains("\"kind\":\"pagerank\""));
+        assert!(json.contains("\"total_edges\":10"));
+    }
+}
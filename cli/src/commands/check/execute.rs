@@ -0,0 +1,429 @@
+//! Rule evaluation for the check command.
+
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::CheckCmd;
+use crate::baseline::BaselineArgs;
+use crate::commands::{CommonArgs, CyclesCmd, Execute};
+use crate::config::{Config, ForbiddenDependency};
+use crate::rules::{is_forbidden, LayerRules};
+use db::queries::hotspots::get_module_connectivity;
+use db::queries::layers::{find_module_call_edges, ModuleCallEdge};
+use db::queries::unused::find_unused_functions;
+use db::query_builders::QuerySettings;
+
+/// A single rule violation
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckViolation {
+    pub message: String,
+    /// Source location (file:line) when the violation maps to a call site or definition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Stable identity used for baseline matching (no line numbers)
+    #[serde(skip)]
+    pub key: String,
+}
+
+/// Outcome of evaluating one rule
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleOutcome {
+    pub rule: String,
+    pub passed: bool,
+    pub violations: Vec<CheckViolation>,
+}
+
+/// Result of evaluating all configured rules
+#[derive(Debug, Serialize)]
+pub struct CheckResult {
+    pub passed: bool,
+    pub total_violations: usize,
+    pub rules: Vec<RuleOutcome>,
+}
+
+impl CheckResult {
+    fn new(rules: Vec<RuleOutcome>) -> Self {
+        Self {
+            passed: rules.iter().all(|r| r.passed),
+            total_violations: rules.iter().map(|r| r.violations.len()).sum(),
+            rules,
+        }
+    }
+}
+
+impl RuleOutcome {
+    fn new(rule: String, violations: Vec<CheckViolation>) -> Self {
+        Self {
+            rule,
+            passed: violations.is_empty(),
+            violations,
+        }
+    }
+}
+
+impl Execute for CheckCmd {
+    type Output = CheckResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let config = Config::load(&self.config)?;
+        let rules = &config.check;
+
+        if rules.is_empty() && config.layers.is_empty() {
+            return Err(format!(
+                "No check rules or layers defined in config '{}'",
+                self.config.display()
+            )
+            .into());
+        }
+
+        let edges = if !config.layers.is_empty() || !rules.forbidden.is_empty() {
+            find_module_call_edges(db, &self.project, None, false, settings)?
+        } else {
+            Vec::new()
+        };
+
+        let mut outcomes = Vec::new();
+
+        if !config.layers.is_empty() {
+            let layer_rules = LayerRules::new(&config.layers)?;
+            outcomes.push(check_layers(&layer_rules, &edges));
+        }
+
+        for forbidden in &rules.forbidden {
+            outcomes.push(check_forbidden(forbidden, &edges));
+        }
+
+        if let Some(max) = rules.max_cycles {
+            outcomes.push(check_cycles(db, &self.project, max, settings)?);
+        }
+
+        if let Some(max) = rules.max_god_module_score {
+            outcomes.push(check_god_module_score(db, &self.project, max, settings)?);
+        }
+
+        if rules.no_unused_public {
+            outcomes.push(check_unused_public(db, &self.project, settings)?);
+        }
+
+        let outcomes = apply_baseline(&self.baseline, outcomes)?;
+        Ok(CheckResult::new(outcomes))
+    }
+}
+
+/// Drop violations recorded in the baseline (or record them with --write-baseline)
+fn apply_baseline(
+    baseline: &BaselineArgs,
+    outcomes: Vec<RuleOutcome>,
+) -> Result<Vec<RuleOutcome>, Box<dyn Error>> {
+    let mut rules = Vec::with_capacity(outcomes.len());
+    let mut findings = Vec::new();
+    for (idx, outcome) in outcomes.into_iter().enumerate() {
+        findings.extend(outcome.violations.into_iter().map(|v| (idx, v)));
+        rules.push(outcome.rule);
+    }
+
+    let findings = baseline.apply("check", findings, |(_, v)| v.key.clone())?;
+
+    let mut grouped: Vec<Vec<CheckViolation>> = vec![Vec::new(); rules.len()];
+    for (idx, violation) in findings {
+        grouped[idx].push(violation);
+    }
+
+    Ok(rules
+        .into_iter()
+        .zip(grouped)
+        .map(|(rule, violations)| RuleOutcome::new(rule, violations))
+        .collect())
+}
+
+fn edge_violation(edge: &ModuleCallEdge, rule_key: &str, prefix: String) -> CheckViolation {
+    CheckViolation {
+        message: format!(
+            "{}{}.{} -> {}.{}/{}",
+            prefix,
+            edge.caller_module,
+            edge.caller_function,
+            edge.callee_module,
+            edge.callee_function,
+            edge.callee_arity
+        ),
+        location: Some(format!("{}:{}", edge.file, edge.line)),
+        key: format!(
+            "{}:{}.{}->{}.{}/{}",
+            rule_key,
+            edge.caller_module,
+            edge.caller_function,
+            edge.callee_module,
+            edge.callee_function,
+            edge.callee_arity
+        ),
+    }
+}
+
+fn check_layers(rules: &LayerRules, edges: &[ModuleCallEdge]) -> RuleOutcome {
+    let violations = edges
+        .iter()
+        .filter_map(|edge| {
+            let (from, to) = rules.violation(&edge.caller_module, &edge.callee_module)?;
+            Some(edge_violation(edge, "layers", format!("[{} -> {}] ", from, to)))
+        })
+        .collect();
+
+    RuleOutcome::new(format!("layers ({})", rules.names().join(" -> ")), violations)
+}
+
+fn check_forbidden(rule: &ForbiddenDependency, edges: &[ModuleCallEdge]) -> RuleOutcome {
+    let rule_key = format!("forbidden:{}->{}", rule.from, rule.to);
+    let violations = edges
+        .iter()
+        .filter(|edge| is_forbidden(rule, &edge.caller_module, &edge.callee_module))
+        .map(|edge| edge_violation(edge, &rule_key, String::new()))
+        .collect();
+
+    let name = match &rule.reason {
+        Some(reason) => format!("forbidden {} -> {} ({})", rule.from, rule.to, reason),
+        None => format!("forbidden {} -> {}", rule.from, rule.to),
+    };
+    RuleOutcome::new(name, violations)
+}
+
+fn check_cycles(
+    db: &db::DbInstance,
+    project: &str,
+    max: usize,
+    settings: &QuerySettings,
+) -> Result<RuleOutcome, Box<dyn Error>> {
+    let cycles = CyclesCmd {
+        module: None,
+        max_length: None,
+        involving: None,
+        baseline: Default::default(),
+        common: CommonArgs {
+            project: project.to_string(),
+            regex: false,
+            limit: 1000,
+            namespace: Vec::new(),
+        },
+    }
+    .execute(db, settings)?;
+
+    let violations = if cycles.total_cycles > max {
+        cycles
+            .cycles
+            .iter()
+            .map(|cycle| {
+                let mut path = cycle.modules.clone();
+                if let Some(first) = cycle.modules.first() {
+                    path.push(first.clone());
+                }
+                let mut members = cycle.modules.clone();
+                members.sort();
+                CheckViolation {
+                    message: format!("cycle: {}", path.join(" -> ")),
+                    location: None,
+                    key: format!("cycle:{}", members.join(",")),
+                }
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(RuleOutcome::new(
+        format!("max_cycles <= {} (found {})", max, cycles.total_cycles),
+        violations,
+    ))
+}
+
+fn check_god_module_score(
+    db: &db::DbInstance,
+    project: &str,
+    max: i64,
+    settings: &QuerySettings,
+) -> Result<RuleOutcome, Box<dyn Error>> {
+    let connectivity = get_module_connectivity(db, project, None, false, settings)?;
+
+    let mut offenders: Vec<(String, i64, i64)> = connectivity
+        .into_iter()
+        .filter(|(_, (incoming, outgoing))| incoming + outgoing > max)
+        .map(|(module, (incoming, outgoing))| (module, incoming, outgoing))
+        .collect();
+    offenders.sort_by(|a, b| (b.1 + b.2).cmp(&(a.1 + a.2)).then_with(|| a.0.cmp(&b.0)));
+
+    let violations = offenders
+        .into_iter()
+        .map(|(module, incoming, outgoing)| CheckViolation {
+            message: format!(
+                "{} has score {} (incoming {}, outgoing {})",
+                module,
+                incoming + outgoing,
+                incoming,
+                outgoing
+            ),
+            location: None,
+            key: format!("god_module:{}", module),
+        })
+        .collect();
+
+    Ok(RuleOutcome::new(format!("max_god_module_score <= {}", max), violations))
+}
+
+fn check_unused_public(
+    db: &db::DbInstance,
+    project: &str,
+    settings: &QuerySettings,
+) -> Result<RuleOutcome, Box<dyn Error>> {
+    let unused = find_unused_functions(
+        db,
+        None,
+        None,
+        project,
+        false,
+        false,
+        true,
+        true,
+        None,
+        u32::MAX,
+        settings,
+    )?;
+
+    let violations = unused
+        .into_iter()
+        .map(|func| CheckViolation {
+            message: format!("{}.{}/{} is never called", func.module, func.name, func.arity),
+            location: Some(format!("{}:{}", func.file, func.line)),
+            key: format!("unused_public:{}.{}/{}", func.module, func.name, func.arity),
+        })
+        .collect();
+
+    Ok(RuleOutcome::new("no_unused_public".to_string(), violations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use tempfile::NamedTempFile;
+
+    fn write_config(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    fn check_cmd(config: &NamedTempFile) -> CheckCmd {
+        CheckCmd {
+            config: config.path().to_path_buf(),
+            project: "test_project".to_string(),
+            baseline: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_check_passes_when_rules_hold() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let config = write_config(
+            r#"{"check": {
+                "forbidden": [{"from": "MyApp.Repo", "to": "MyApp.Controller"}],
+                "max_cycles": 0
+            }}"#,
+        );
+
+        let result = check_cmd(&config).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert!(result.passed);
+        assert_eq!(result.total_violations, 0);
+        assert_eq!(result.rules.len(), 2);
+    }
+
+    #[test]
+    fn test_check_forbidden_dependency_fails() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let config = write_config(
+            r#"{"check": {"forbidden": [{"from": "MyApp.Controller", "to": "MyApp.Accounts"}]}}"#,
+        );
+
+        let result = check_cmd(&config).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert!(!result.passed);
+        assert!(result.total_violations > 0);
+        let rule = &result.rules[0];
+        assert!(!rule.passed);
+        assert!(rule.violations.iter().all(|v| v.location.is_some()));
+        assert!(rule.violations[0].message.starts_with("MyApp.Controller."));
+    }
+
+    #[test]
+    fn test_check_god_module_score() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let strict = write_config(r#"{"check": {"max_god_module_score": 0}}"#);
+        let lenient = write_config(r#"{"check": {"max_god_module_score": 100000}}"#);
+
+        assert!(!check_cmd(&strict).execute(&db, &QuerySettings::default()).unwrap().passed);
+        assert!(check_cmd(&lenient).execute(&db, &QuerySettings::default()).unwrap().passed);
+    }
+
+    #[test]
+    fn test_check_layers_included() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let config = write_config(
+            r#"{"layers": [
+                {"name": "Data", "modules": ["MyApp.Repo"]},
+                {"name": "Rest", "modules": ["MyApp"]}
+            ]}"#,
+        );
+
+        let result = check_cmd(&config).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert!(!result.passed);
+        assert!(result.rules[0].rule.starts_with("layers"));
+    }
+
+    #[test]
+    fn test_check_requires_rules() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let config = write_config("{}");
+        assert!(check_cmd(&config).execute(&db, &QuerySettings::default()).is_err());
+    }
+
+    #[test]
+    fn test_check_baseline_suppresses_existing_violations() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let config = write_config(
+            r#"{"check": {"forbidden": [{"from": "MyApp.Controller", "to": "MyApp.Accounts"}]}}"#,
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let baseline_path = dir.path().join("baseline.json");
+
+        let mut write = check_cmd(&config);
+        write.baseline = BaselineArgs {
+            baseline: Some(baseline_path.clone()),
+            write_baseline: true,
+        };
+        let written = write.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert!(!written.passed);
+        assert!(baseline_path.exists());
+
+        let mut filtered = check_cmd(&config);
+        filtered.baseline = BaselineArgs {
+            baseline: Some(baseline_path),
+            write_baseline: false,
+        };
+        let result = filtered.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert!(result.passed);
+        assert_eq!(result.total_violations, 0);
+    }
+
+    #[test]
+    fn test_check_missing_config() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let cmd = CheckCmd {
+            config: PathBuf::from("/nonexistent/config.json"),
+            project: "test_project".to_string(),
+            baseline: Default::default(),
+        };
+        assert!(cmd.execute(&db, &QuerySettings::default()).is_err());
+    }
+}
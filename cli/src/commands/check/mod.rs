@@ -0,0 +1,72 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::baseline::BaselineArgs;
+use crate::commands::{CommandRunner, Execute};
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::output::{OutputFormat, Outputable};
+
+/// Evaluate call graph policies from the config file (CI gate)
+///
+/// Runs the configured rules (layers, forbidden dependencies, max cycles,
+/// max god-module score, unused public functions) and exits with a nonzero
+/// status when any rule is violated.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search check                             # Evaluate rules from .code_search/config.json
+  code_search check --config ci/rules.json      # Use a different config file
+  code_search check -o json                     # Machine-readable report
+  code_search check --baseline ci/check-baseline.json --write-baseline  # Record existing violations
+  code_search check --baseline ci/check-baseline.json                   # Only fail on new violations
+")]
+pub struct CheckCmd {
+    /// Path to the config file containing check rules
+    #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+    pub config: PathBuf,
+
+    /// Project to check
+    #[arg(long, default_value = "default")]
+    pub project: String,
+
+    #[command(flatten)]
+    pub baseline: BaselineArgs,
+}
+
+/// Returned by `check` when at least one rule is violated.
+///
+/// Carries the formatted report so the caller can print it before exiting nonzero.
+#[derive(Debug)]
+pub struct CheckFailed {
+    pub report: String,
+}
+
+impl fmt::Display for CheckFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.report)
+    }
+}
+
+impl Error for CheckFailed {}
+
+impl CommandRunner for CheckCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        // Recording a baseline is an adoption step and should not fail the build
+        let writing_baseline = self.baseline.write_baseline;
+        let result = self.execute(db, settings)?;
+        let report = result.format(format);
+        if result.passed || writing_baseline {
+            Ok(report)
+        } else {
+            Err(Box::new(CheckFailed { report }))
+        }
+    }
+}
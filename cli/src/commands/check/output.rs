@@ -0,0 +1,141 @@
+//! Output formatting for check command results.
+
+use super::execute::CheckResult;
+use crate::output::{Annotation, AnnotationLevel, Outputable};
+
+impl Outputable for CheckResult {
+    fn to_table(&self) -> String {
+        let mut output = String::new();
+
+        if self.passed {
+            output.push_str(&format!("Check: PASSED ({} rule(s))\n", self.rules.len()));
+        } else {
+            output.push_str(&format!(
+                "Check: FAILED ({} violation(s))\n",
+                self.total_violations
+            ));
+        }
+
+        for rule in &self.rules {
+            let status = if rule.passed { "PASS" } else { "FAIL" };
+            output.push_str(&format!("\n[{}] {}\n", status, rule.rule));
+
+            for v in &rule.violations {
+                match &v.location {
+                    Some(location) => output.push_str(&format!("  {}  {}\n", location, v.message)),
+                    None => output.push_str(&format!("  {}\n", v.message)),
+                }
+            }
+        }
+
+        output
+    }
+
+    fn to_annotations(&self) -> Option<Vec<Annotation>> {
+        let mut annotations = Vec::new();
+        for rule in &self.rules {
+            for v in &rule.violations {
+                // Locations are "file:line"
+                let (file, line) = match v.location.as_deref().and_then(|l| l.rsplit_once(':')) {
+                    Some((file, line)) => (Some(file.to_string()), line.parse().ok()),
+                    None => (None, None),
+                };
+                annotations.push(Annotation {
+                    level: AnnotationLevel::Error,
+                    file,
+                    line,
+                    end_line: None,
+                    title: rule.rule.clone(),
+                    message: v.message.clone(),
+                });
+            }
+        }
+        Some(annotations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::check::execute::{CheckViolation, RuleOutcome};
+
+    fn failing_result() -> CheckResult {
+        CheckResult {
+            passed: false,
+            total_violations: 2,
+            rules: vec![
+                RuleOutcome {
+                    rule: "forbidden MyApp.Repo -> MyAppWeb".to_string(),
+                    passed: false,
+                    violations: vec![CheckViolation {
+                        message: "MyApp.Repo.get -> MyAppWeb.Router.path/1".to_string(),
+                        location: Some("lib/my_app/repo.ex:12".to_string()),
+                        key: "forbidden:MyApp.Repo->MyAppWeb:MyApp.Repo.get->MyAppWeb.Router.path/1".to_string(),
+                    }],
+                },
+                RuleOutcome {
+                    rule: "max_cycles <= 0 (found 1)".to_string(),
+                    passed: false,
+                    violations: vec![CheckViolation {
+                        message: "cycle: A -> B -> A".to_string(),
+                        location: None,
+                        key: "cycle:A,B".to_string(),
+                    }],
+                },
+                RuleOutcome {
+                    rule: "no_unused_public".to_string(),
+                    passed: true,
+                    violations: vec![],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_check_output_passed() {
+        let result = CheckResult {
+            passed: true,
+            total_violations: 0,
+            rules: vec![RuleOutcome {
+                rule: "max_cycles <= 0 (found 0)".to_string(),
+                passed: true,
+                violations: vec![],
+            }],
+        };
+
+        let output = result.to_table();
+        assert!(output.contains("Check: PASSED (1 rule(s))"));
+        assert!(output.contains("[PASS] max_cycles <= 0"));
+    }
+
+    #[test]
+    fn test_check_output_failed() {
+        let output = failing_result().to_table();
+        assert!(output.contains("Check: FAILED (2 violation(s))"));
+        assert!(output.contains("[FAIL] forbidden MyApp.Repo -> MyAppWeb"));
+        assert!(output.contains("  lib/my_app/repo.ex:12  MyApp.Repo.get -> MyAppWeb.Router.path/1"));
+        assert!(output.contains("  cycle: A -> B -> A"));
+        assert!(output.contains("[PASS] no_unused_public"));
+    }
+
+    #[test]
+    fn test_check_output_json() {
+        let json = serde_json::to_string(&failing_result()).unwrap();
+        assert!(json.contains("\"passed\":false"));
+        assert!(json.contains("\"total_violations\":2"));
+        assert!(json.contains("\"location\":\"lib/my_app/repo.ex:12\""));
+    }
+
+    #[test]
+    fn test_check_output_github() {
+        let output = failing_result().format(crate::output::OutputFormat::Github);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "::error file=lib/my_app/repo.ex,line=12,title=forbidden MyApp.Repo -> MyAppWeb::MyApp.Repo.get -> MyAppWeb.Router.path/1",
+                "::error title=max_cycles <= 0 (found 1)::cycle: A -> B -> A",
+            ]
+        );
+    }
+}
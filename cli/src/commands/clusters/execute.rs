@@ -5,7 +5,9 @@ use serde::Serialize;
 
 use super::ClustersCmd;
 use crate::commands::Execute;
+use crate::utils::extract_namespace;
 use db::queries::clusters::get_module_calls;
+use db::query_builders::QuerySettings;
 
 /// A single namespace cluster
 #[derive(Debug, Clone, Serialize)]
@@ -44,9 +46,9 @@ pub struct ClustersResult {
 impl Execute for ClustersCmd {
     type Output = ClustersResult;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         // Get all inter-module calls
-        let calls = get_module_calls(db, &self.common.project)?;
+        let calls = get_module_calls(db, &self.common.project, settings)?;
 
         // Extract namespace for each module and collect all unique modules
         let mut all_modules = HashSet::new();
@@ -154,12 +156,13 @@ impl Execute for ClustersCmd {
             });
         }
 
-        // Sort by cohesion descending, then by internal calls
+        // Sort by cohesion descending, then by internal calls and namespace
         clusters.sort_by(|a, b| {
             b.cohesion
                 .partial_cmp(&a.cohesion)
                 .unwrap_or(std::cmp::Ordering::Equal)
                 .then_with(|| b.internal_calls.cmp(&a.internal_calls))
+                .then_with(|| a.namespace.cmp(&b.namespace))
         });
 
         // Build cross-dependencies if requested
@@ -174,8 +177,12 @@ impl Execute for ClustersCmd {
                     });
                 }
             }
-            // Sort by call_count descending
-            deps.sort_by(|a, b| b.call_count.cmp(&a.call_count));
+            // Sort by call_count descending, then by namespaces
+            deps.sort_by(|a, b| {
+                b.call_count
+                    .cmp(&a.call_count)
+                    .then_with(|| (&a.from_namespace, &a.to_namespace).cmp(&(&b.from_namespace, &b.to_namespace)))
+            });
             deps
         } else {
             Vec::new()
@@ -192,17 +199,6 @@ impl Execute for ClustersCmd {
     }
 }
 
-/// Extract namespace from a module name at the specified depth
-///
-/// Example: "MyApp.Accounts.Users.Admin" at depth 2 becomes "MyApp.Accounts"
-fn extract_namespace(module: &str, depth: usize) -> String {
-    module
-        .split('.')
-        .take(depth)
-        .collect::<Vec<_>>()
-        .join(".")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,6 +322,7 @@ mod tests {
                 project: "default".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         };
 
@@ -345,6 +342,7 @@ mod tests {
                 project: "custom".to_string(),
                 regex: false,
                 limit: 50,
+                namespace: Vec::new(),
             },
         };
 
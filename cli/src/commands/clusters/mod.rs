@@ -4,6 +4,7 @@ mod output;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
@@ -39,8 +40,8 @@ pub struct ClustersCmd {
 }
 
 impl CommandRunner for ClustersCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
@@ -0,0 +1,323 @@
+//! Community detection over the weighted module call graph.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::{CommunitiesCmd, CommunityAlgorithm};
+use crate::commands::Execute;
+use crate::graph::WeightedGraph;
+use crate::utils::extract_namespace;
+use db::query_builders::{compile_pattern, QuerySettings};
+use db::queries::clusters::{get_module_call_counts, ModuleCallCount};
+
+/// A detected community of modules
+#[derive(Debug, Clone, Serialize)]
+pub struct Community {
+    pub id: usize,
+    pub size: usize,
+    pub modules: Vec<String>,
+    /// Distinct namespaces (at the configured depth) among the members
+    pub namespaces: Vec<String>,
+    /// Namespace holding the most members
+    pub dominant_namespace: String,
+    /// Fraction of members in the dominant namespace (1.0 = matches the package structure)
+    pub namespace_purity: f64,
+    /// Call sites between members
+    pub internal_calls: i64,
+    /// Call sites crossing the community boundary, in either direction
+    pub external_calls: i64,
+    /// internal / (internal + external)
+    pub cohesion: f64,
+}
+
+/// A namespace whose modules ended up in more than one community
+#[derive(Debug, Clone, Serialize)]
+pub struct SplitNamespace {
+    pub namespace: String,
+    pub communities: Vec<usize>,
+}
+
+/// Result of the communities command
+#[derive(Debug, Serialize)]
+pub struct CommunitiesResult {
+    pub algorithm: String,
+    pub depth: usize,
+    /// Modularity of the whole partition (higher = stronger community structure)
+    pub modularity: f64,
+    pub total_communities: usize,
+    pub communities: Vec<Community>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub split_namespaces: Vec<SplitNamespace>,
+}
+
+impl Execute for CommunitiesCmd {
+    type Output = CommunitiesResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let filter = match &self.module {
+            Some(pattern) if self.common.regex => Some(compile_pattern(pattern)?),
+            Some(pattern) => Some(Regex::new(&regex::escape(pattern))?),
+            None => None,
+        };
+
+        let calls = get_module_call_counts(db, &self.common.project, settings)?;
+
+        // Sorted module list gives stable node indices, and with them stable results
+        let modules: Vec<String> = calls
+            .iter()
+            .flat_map(|c| [c.caller_module.clone(), c.callee_module.clone()])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let index: BTreeMap<&str, usize> = modules
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.as_str(), i))
+            .collect();
+
+        let graph = WeightedGraph::new(
+            modules.len(),
+            calls.iter().map(|c| {
+                (
+                    index[c.caller_module.as_str()],
+                    index[c.callee_module.as_str()],
+                    c.call_count as f64,
+                )
+            }),
+        );
+
+        let membership = match self.algorithm {
+            CommunityAlgorithm::Louvain => graph.louvain(),
+            CommunityAlgorithm::LabelPropagation => graph.label_propagation(100),
+        };
+        let modularity = graph.modularity(&membership);
+
+        let mut communities = build_communities(&modules, &membership, &calls, &index, self.depth);
+        let split_namespaces = find_split_namespaces(&communities);
+
+        if let Some(re) = &filter {
+            communities.retain(|c| c.modules.iter().any(|m| re.is_match(m)));
+        }
+        communities.truncate(self.common.limit as usize);
+
+        Ok(CommunitiesResult {
+            algorithm: self.algorithm.as_str().to_string(),
+            depth: self.depth,
+            modularity,
+            total_communities: communities.len(),
+            communities,
+            split_namespaces,
+        })
+    }
+}
+
+/// Group modules by community and compute cohesion and namespace alignment.
+///
+/// Communities are ordered by size (largest first), then cohesion, and numbered from 1.
+fn build_communities(
+    modules: &[String],
+    membership: &[usize],
+    calls: &[ModuleCallCount],
+    index: &BTreeMap<&str, usize>,
+    depth: usize,
+) -> Vec<Community> {
+    let mut members: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for (module, &c) in modules.iter().zip(membership) {
+        members.entry(c).or_default().push(module.clone());
+    }
+
+    let mut internal: BTreeMap<usize, i64> = BTreeMap::new();
+    let mut external: BTreeMap<usize, i64> = BTreeMap::new();
+    for call in calls {
+        let from = membership[index[call.caller_module.as_str()]];
+        let to = membership[index[call.callee_module.as_str()]];
+        if from == to {
+            *internal.entry(from).or_default() += call.call_count;
+        } else {
+            *external.entry(from).or_default() += call.call_count;
+            *external.entry(to).or_default() += call.call_count;
+        }
+    }
+
+    let mut communities: Vec<Community> = members
+        .into_iter()
+        .map(|(c, modules)| {
+            let mut namespace_counts: BTreeMap<String, usize> = BTreeMap::new();
+            for module in &modules {
+                *namespace_counts
+                    .entry(extract_namespace(module, depth))
+                    .or_default() += 1;
+            }
+            // BTreeMap iteration keeps the alphabetically first namespace on ties
+            let (dominant_namespace, dominant_count) = namespace_counts
+                .iter()
+                .fold(None::<(&String, usize)>, |best, (ns, &count)| match best {
+                    Some((_, best_count)) if best_count >= count => best,
+                    _ => Some((ns, count)),
+                })
+                .map(|(ns, count)| (ns.clone(), count))
+                .unwrap_or_default();
+
+            let internal_calls = internal.get(&c).copied().unwrap_or(0);
+            let external_calls = external.get(&c).copied().unwrap_or(0);
+            let total = internal_calls + external_calls;
+
+            Community {
+                id: 0,
+                size: modules.len(),
+                namespace_purity: dominant_count as f64 / modules.len() as f64,
+                namespaces: namespace_counts.into_keys().collect(),
+                dominant_namespace,
+                modules,
+                internal_calls,
+                external_calls,
+                cohesion: if total > 0 {
+                    internal_calls as f64 / total as f64
+                } else {
+                    0.0
+                },
+            }
+        })
+        .collect();
+
+    communities.sort_by(|a, b| {
+        b.size
+            .cmp(&a.size)
+            .then_with(|| b.cohesion.total_cmp(&a.cohesion))
+            .then_with(|| a.modules.cmp(&b.modules))
+    });
+    for (i, community) in communities.iter_mut().enumerate() {
+        community.id = i + 1;
+    }
+
+    communities
+}
+
+/// Namespaces whose modules are spread over several communities
+fn find_split_namespaces(communities: &[Community]) -> Vec<SplitNamespace> {
+    let mut spread: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for community in communities {
+        for namespace in &community.namespaces {
+            spread.entry(namespace).or_default().push(community.id);
+        }
+    }
+
+    spread
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|(namespace, communities)| SplitNamespace {
+            namespace: namespace.to_string(),
+            communities,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+
+    fn communities_cmd(algorithm: CommunityAlgorithm, depth: usize) -> CommunitiesCmd {
+        CommunitiesCmd {
+            module: None,
+            algorithm,
+            depth,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    fn community(id: usize, namespaces: &[&str]) -> Community {
+        Community {
+            id,
+            size: namespaces.len(),
+            modules: vec![],
+            namespaces: namespaces.iter().map(|s| s.to_string()).collect(),
+            dominant_namespace: namespaces[0].to_string(),
+            namespace_purity: 1.0,
+            internal_calls: 0,
+            external_calls: 0,
+            cohesion: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_find_split_namespaces() {
+        let communities = vec![
+            community(1, &["MyApp.Accounts", "MyApp.Web"]),
+            community(2, &["MyApp.Web"]),
+        ];
+        let split = find_split_namespaces(&communities);
+        assert_eq!(split.len(), 1);
+        assert_eq!(split[0].namespace, "MyApp.Web");
+        assert_eq!(split[0].communities, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_communities_louvain() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = communities_cmd(CommunityAlgorithm::Louvain, 2)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.algorithm, "louvain");
+        assert_eq!(result.total_communities, 2);
+        assert!(result.modularity > 0.0);
+
+        // Controller -> Accounts -> Repo carry most of the call sites
+        let first = &result.communities[0];
+        assert_eq!(first.id, 1);
+        assert_eq!(first.modules, vec!["MyApp.Accounts", "MyApp.Controller", "MyApp.Repo"]);
+        assert_eq!(first.internal_calls, 5);
+        assert_eq!(first.external_calls, 2);
+
+        let second = &result.communities[1];
+        assert_eq!(second.modules, vec!["MyApp.Notifier", "MyApp.Service"]);
+
+        // At depth 2 every fixture module is its own namespace, so nothing is split
+        assert!(result.split_namespaces.is_empty());
+        assert!(first.namespace_purity < 1.0);
+    }
+
+    #[test]
+    fn test_communities_namespace_alignment() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = communities_cmd(CommunityAlgorithm::Louvain, 1)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        // At depth 1 everything lives in MyApp, which the communities split in two
+        assert_eq!(result.split_namespaces.len(), 1);
+        assert_eq!(result.split_namespaces[0].namespace, "MyApp");
+        assert_eq!(result.split_namespaces[0].communities, vec![1, 2]);
+        assert!(result.communities.iter().all(|c| c.namespace_purity == 1.0));
+    }
+
+    #[test]
+    fn test_communities_module_filter() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let mut cmd = communities_cmd(CommunityAlgorithm::LabelPropagation, 2);
+        cmd.module = Some("Notifier".to_string());
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.algorithm, "label-propagation");
+        assert_eq!(result.total_communities, 1);
+        assert!(result.communities[0]
+            .modules
+            .contains(&"MyApp.Notifier".to_string()));
+    }
+
+    #[test]
+    fn test_communities_empty_db() {
+        let db = db::test_utils::setup_empty_test_db();
+        let result = communities_cmd(CommunityAlgorithm::Louvain, 2).execute(&db, &QuerySettings::default());
+        assert!(result.is_err());
+    }
+}
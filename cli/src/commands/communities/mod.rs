@@ -0,0 +1,66 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::{Args, ValueEnum};
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Community detection algorithm
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum CommunityAlgorithm {
+    /// Louvain modularity optimization
+    #[default]
+    Louvain,
+    /// Label propagation (faster, less stable)
+    LabelPropagation,
+}
+
+impl CommunityAlgorithm {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CommunityAlgorithm::Louvain => "louvain",
+            CommunityAlgorithm::LabelPropagation => "label-propagation",
+        }
+    }
+}
+
+/// Detect module communities from actual call coupling
+///
+/// Runs community detection over the module call graph (weighted by call sites) and
+/// compares the detected communities with namespace-based clusters, showing where
+/// the package structure diverges from how modules are actually coupled.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search communities                       # Detect communities with Louvain
+  code_search communities -a label-propagation  # Use label propagation instead
+  code_search communities --depth 3             # Compare against depth-3 namespaces
+  code_search communities MyApp.Accounts        # Communities containing matching modules
+")]
+pub struct CommunitiesCmd {
+    /// Only show communities containing a matching module (substring match by default, regex with --regex)
+    pub module: Option<String>,
+
+    /// Detection algorithm
+    #[arg(short, long, value_enum, default_value_t = CommunityAlgorithm::Louvain)]
+    pub algorithm: CommunityAlgorithm,
+
+    /// Namespace depth to compare communities against (default: 2)
+    #[arg(long, default_value = "2")]
+    pub depth: usize,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for CommunitiesCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
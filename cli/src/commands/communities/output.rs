@@ -0,0 +1,111 @@
+//! Output formatting for communities command results.
+
+use super::execute::CommunitiesResult;
+use crate::output::Outputable;
+
+impl Outputable for CommunitiesResult {
+    fn to_table(&self) -> String {
+        let mut output = String::new();
+        output.push_str(&format!(
+            "Communities ({}, modularity {:.3})\n\n",
+            self.algorithm, self.modularity
+        ));
+
+        if self.communities.is_empty() {
+            output.push_str("No communities found.\n");
+            return output;
+        }
+
+        output.push_str(&format!("Found {} communit(ies):\n", self.total_communities));
+
+        for community in &self.communities {
+            output.push_str(&format!(
+                "\n#{}  {} module(s), cohesion {:.2}, {} internal / {} external call(s)\n",
+                community.id,
+                community.size,
+                community.cohesion,
+                community.internal_calls,
+                community.external_calls,
+            ));
+            output.push_str(&format!(
+                "  namespace: {} ({:.0}% of members)\n",
+                community.dominant_namespace,
+                community.namespace_purity * 100.0,
+            ));
+            for module in &community.modules {
+                output.push_str(&format!("  {}\n", module));
+            }
+        }
+
+        if !self.split_namespaces.is_empty() {
+            output.push_str(&format!(
+                "\nNamespaces split across communities (depth {}):\n",
+                self.depth
+            ));
+            for split in &self.split_namespaces {
+                let ids: Vec<String> = split.communities.iter().map(|id| format!("#{}", id)).collect();
+                output.push_str(&format!("  {} -> {}\n", split.namespace, ids.join(", ")));
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::communities::execute::{Community, SplitNamespace};
+
+    fn result() -> CommunitiesResult {
+        CommunitiesResult {
+            algorithm: "louvain".to_string(),
+            depth: 2,
+            modularity: 0.125,
+            total_communities: 1,
+            communities: vec![Community {
+                id: 1,
+                size: 2,
+                modules: vec!["MyApp.Accounts".to_string(), "MyApp.Repo".to_string()],
+                namespaces: vec!["MyApp.Accounts".to_string(), "MyApp.Repo".to_string()],
+                dominant_namespace: "MyApp.Accounts".to_string(),
+                namespace_purity: 0.5,
+                internal_calls: 3,
+                external_calls: 1,
+                cohesion: 0.75,
+            }],
+            split_namespaces: vec![SplitNamespace {
+                namespace: "MyApp.Repo".to_string(),
+                communities: vec![1, 2],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_communities_output_empty() {
+        let mut result = result();
+        result.communities.clear();
+        result.total_communities = 0;
+
+        let output = result.to_table();
+        assert!(output.contains("Communities (louvain, modularity 0.125)"));
+        assert!(output.contains("No communities found"));
+    }
+
+    #[test]
+    fn test_communities_output() {
+        let output = result().to_table();
+        assert!(output.contains("#1  2 module(s), cohesion 0.75, 3 internal / 1 external call(s)"));
+        assert!(output.contains("namespace: MyApp.Accounts (50% of members)"));
+        assert!(output.contains("  MyApp.Repo\n"));
+        assert!(output.contains("MyApp.Repo -> #1, #2"));
+    }
+
+    #[test]
+    fn test_communities_output_json() {
+        let json = serde_json::to_string(&result()).unwrap();
+        assert!(json.contains("\"algorithm\":\"louvain\""));
+        assert!(json.contains("\"namespace_purity\":0.5"));
+        assert!(json.contains("split_namespaces"));
+    }
+}
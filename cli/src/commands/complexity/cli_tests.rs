@@ -17,6 +17,7 @@ mod tests {
         defaults: {
             min: 1,
             min_depth: 0,
+            cognitive: false,
             exclude_generated: false,
             module: None,
             common.project: "default".to_string(),
@@ -116,4 +117,37 @@ mod tests {
         field: min,
         expected: 15,
     }
+
+    crate::cli_option_test! {
+        command: "complexity",
+        variant: Complexity,
+        test_name: test_with_metric_abc,
+        args: ["--metric", "abc", "--min-abc", "12.5"],
+        field: min_abc,
+        expected: 12.5,
+    }
+
+    crate::cli_option_test! {
+        command: "complexity",
+        variant: Complexity,
+        test_name: test_with_min_args,
+        args: ["--metric", "args", "--min-args", "4"],
+        field: min_args,
+        expected: 4,
+    }
+
+    crate::cli_option_test! {
+        command: "complexity",
+        variant: Complexity,
+        test_name: test_with_cognitive,
+        args: ["--cognitive", "--min", "15"],
+        field: cognitive,
+        expected: true,
+    }
+
+    #[test]
+    fn test_cognitive_conflicts_with_metric() {
+        let result = Args::try_parse_from(["code_search", "complexity", "--cognitive", "--metric", "abc"]);
+        assert!(result.is_err());
+    }
 }
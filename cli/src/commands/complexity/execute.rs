@@ -3,8 +3,12 @@ use std::error::Error;
 use serde::Serialize;
 
 use super::ComplexityCmd;
+use crate::blame::{sort_and_blame, BlameInfo, Blamer};
 use crate::commands::Execute;
-use db::queries::complexity::find_complexity_metrics;
+use crate::sort::query_limit;
+use db::queries::annotations::{AnnotationIndex, AnnotationNote};
+use db::queries::complexity::{find_complexity_metrics, ComplexityMetricKind, ComplexityThresholds};
+use db::query_builders::QuerySettings;
 use db::types::ModuleCollectionResult;
 
 /// A single complexity metric entry
@@ -16,27 +20,60 @@ pub struct ComplexityEntry {
     pub complexity: i64,
     pub max_nesting_depth: i64,
     pub lines: i64,
+    pub abc_score: f64,
+    pub halstead_volume: f64,
+    pub cognitive_complexity: i64,
+    /// Only with --show-annotations
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<AnnotationNote>,
+    /// Only with --blame, for functions in files git tracks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blame: Option<BlameInfo>,
 }
 
 impl Execute for ComplexityCmd {
     type Output = ModuleCollectionResult<ComplexityEntry>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        // With --cognitive, --min applies to cognitive complexity instead
+        let (metric, min_complexity, min_cognitive) = if self.cognitive {
+            (ComplexityMetricKind::Cognitive, 0, self.min)
+        } else {
+            (self.metric, self.min, 0)
+        };
+        let thresholds = ComplexityThresholds {
+            complexity: min_complexity,
+            cognitive: min_cognitive,
+            depth: self.min_depth,
+            abc: self.min_abc,
+            halstead: self.min_halstead,
+            args: self.min_args,
+        };
         let metrics = find_complexity_metrics(
             db,
-            self.min,
-            self.min_depth,
+            &thresholds,
+            metric,
             self.module.as_deref(),
             &self.common.project,
             self.common.regex,
             self.exclude_generated,
-            self.common.limit,
+            self.filter.as_ref(),
+            self.baseline.query_limit(query_limit(self.sort.as_ref(), self.common.limit)),
+            settings,
+        )?;
+
+        let metrics = self.baseline.apply_limited(
+            "complexity",
+            metrics,
+            |m| format!("{}.{}/{}", m.module, m.name, m.arity),
+            query_limit(self.sort.as_ref(), self.common.limit),
         )?;
 
         let total_items = metrics.len();
+        let blamer = if self.blame { Some(Blamer::open(".")?) } else { None };
 
-        // Group by module
-        let items = crate::utils::group_by_module(metrics, |metric| {
+        // Group by module, keeping the file for location-based output
+        let items = crate::utils::group_by_module_with_file(metrics, |metric| {
             let entry = ComplexityEntry {
                 name: metric.name,
                 arity: metric.arity,
@@ -44,18 +81,43 @@ impl Execute for ComplexityCmd {
                 complexity: metric.complexity,
                 max_nesting_depth: metric.max_nesting_depth,
                 lines: metric.lines,
+                abc_score: metric.abc_score,
+                halstead_volume: metric.halstead_volume,
+                cognitive_complexity: metric.cognitive_complexity,
+                annotations: vec![],
+                blame: None,
             };
-            (metric.module, entry)
+            (metric.module, entry, metric.file)
         });
 
-        Ok(ModuleCollectionResult {
+        let mut result = ModuleCollectionResult {
             module_pattern: self.module.clone().unwrap_or_else(|| "*".to_string()),
             function_pattern: None,
             kind_filter: None,
             name_filter: None,
             total_items,
             items,
-        })
+        };
+
+        if self.show_annotations {
+            let index = AnnotationIndex::load(db, &self.common.project, settings)?;
+            for module in &mut result.items {
+                for entry in &mut module.entries {
+                    entry.annotations = index.for_function(&module.name, &entry.name, entry.arity);
+                }
+            }
+        }
+
+        sort_and_blame(
+            &mut result,
+            self.sort.as_ref(),
+            self.common.limit,
+            blamer,
+            |e| (e.line, e.line + e.lines - 1),
+            |e, blame| e.blame = blame,
+        )?;
+
+        Ok(result)
     }
 }
 
@@ -68,12 +130,23 @@ mod tests {
         let cmd = ComplexityCmd {
             min: 10,
             min_depth: 3,
+            cognitive: false,
+            metric: Default::default(),
+            min_abc: 0.0,
+            min_halstead: 0.0,
+            min_args: 0,
             exclude_generated: false,
             module: Some("MyApp".to_string()),
+            sort: None,
+            filter: None,
+            show_annotations: false,
+            blame: false,
+            baseline: Default::default(),
             common: crate::commands::CommonArgs {
                 project: "default".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
 
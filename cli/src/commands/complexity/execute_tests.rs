@@ -23,12 +23,23 @@ mod tests {
         cmd: ComplexityCmd {
             min: 1,
             min_depth: 0,
+            cognitive: false,
+            metric: Default::default(),
+            min_abc: 0.0,
+            min_halstead: 0.0,
+            min_args: 0,
             exclude_generated: false,
             module: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -45,12 +56,23 @@ mod tests {
         cmd: ComplexityCmd {
             min: 10,
             min_depth: 0,
+            cognitive: false,
+            metric: Default::default(),
+            min_abc: 0.0,
+            min_halstead: 0.0,
+            min_args: 0,
             exclude_generated: false,
             module: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -67,12 +89,23 @@ mod tests {
         cmd: ComplexityCmd {
             min: 1,
             min_depth: 5,
+            cognitive: false,
+            metric: Default::default(),
+            min_abc: 0.0,
+            min_halstead: 0.0,
+            min_args: 0,
             exclude_generated: false,
             module: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -88,12 +121,23 @@ mod tests {
         cmd: ComplexityCmd {
             min: 1,
             min_depth: 0,
+            cognitive: false,
+            metric: Default::default(),
+            min_abc: 0.0,
+            min_halstead: 0.0,
+            min_args: 0,
             exclude_generated: false,
             module: Some("MyApp.Accounts".to_string()),
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -112,12 +156,23 @@ mod tests {
         cmd: ComplexityCmd {
             min: 1,
             min_depth: 0,
+            cognitive: false,
+            metric: Default::default(),
+            min_abc: 0.0,
+            min_halstead: 0.0,
+            min_args: 0,
             exclude_generated: false,
             module: Some("MyApp\\..*".to_string()),
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -134,12 +189,23 @@ mod tests {
         cmd: ComplexityCmd {
             min: 1,
             min_depth: 0,
+            cognitive: false,
+            metric: Default::default(),
+            min_abc: 0.0,
+            min_halstead: 0.0,
+            min_args: 0,
             exclude_generated: false,
             module: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 5,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -148,6 +214,40 @@ mod tests {
         },
     }
 
+    // Ranking by argument count keeps only functions with enough arguments
+    crate::execute_test! {
+        test_name: test_complexity_metric_args,
+        fixture: populated_db,
+        cmd: ComplexityCmd {
+            min: 1,
+            min_depth: 0,
+            cognitive: false,
+            metric: db::queries::complexity::ComplexityMetricKind::Args,
+            min_abc: 0.0,
+            min_halstead: 0.0,
+            min_args: 2,
+            exclude_generated: false,
+            module: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert!(result.total_items > 0);
+            for module in &result.items {
+                assert!(module.entries.iter().all(|e| e.arity >= 2));
+            }
+        },
+    }
+
     // =========================================================================
     // Empty database tests
     // =========================================================================
@@ -157,12 +257,23 @@ mod tests {
         cmd: ComplexityCmd {
             min: 1,
             min_depth: 0,
+            cognitive: false,
+            metric: Default::default(),
+            min_abc: 0.0,
+            min_halstead: 0.0,
+            min_args: 0,
             exclude_generated: false,
             module: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
@@ -11,16 +11,24 @@ mod output_tests;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
+use db::where_filter::WhereFilter;
 
+use crate::baseline::BaselineArgs;
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::sort::SortSpec;
+use db::queries::complexity::ComplexityMetricKind;
 
 /// Display complexity metrics for functions
 ///
 /// Shows functions with complexity scores and nesting depths.
 /// Complexity is a measure of the cyclomatic complexity of a function,
 /// and nesting depth is the maximum depth of nested control structures.
+/// Results can instead be ranked by ABC size, Halstead volume or argument
+/// count with --metric, or by cognitive complexity with --cognitive, when the
+/// extractor emitted those metrics.
 #[derive(Args, Debug)]
 #[command(after_help = "\
 Examples:
@@ -30,30 +38,80 @@ Examples:
   code_search complexity --min-depth 3        # Show functions with nesting depth >= 3
   code_search complexity --exclude-generated  # Exclude macro-generated functions
   code_search complexity -l 20                # Show top 20 most complex functions
+  code_search complexity --min 10 --baseline complexity.json --write-baseline  # Record current findings
+  code_search complexity --min 10 --baseline complexity.json                   # Only report new findings
+  code_search complexity --sort max_nesting_depth:desc                         # Deepest nesting first
+  code_search complexity --where 'lines > 20 && module =~ \"Web\"'               # Long Web functions only
+  code_search complexity --metric abc --min-abc 20        # Rank by ABC size, at least 20
+  code_search complexity --metric halstead -l 10          # Top 10 by Halstead volume
+  code_search complexity --metric args --min-args 5       # Functions taking 5+ arguments
+  code_search complexity --cognitive --min 15             # Cognitive complexity >= 15
+  code_search complexity --min 10 --show-annotations      # Include notes attached with annotate
+  code_search complexity --min 10 --blame                 # Who last changed each function, and when
 ")]
 pub struct ComplexityCmd {
     /// Module filter pattern (substring match by default, regex with --regex)
     pub module: Option<String>,
 
-    /// Minimum complexity threshold
+    /// Minimum complexity threshold (cognitive complexity with --cognitive)
     #[arg(long, default_value = "1")]
     pub min: i64,
 
+    /// Rank by cognitive complexity, which does not penalize pattern-matching clauses
+    #[arg(long, conflicts_with = "metric")]
+    pub cognitive: bool,
+
     /// Minimum nesting depth threshold
     #[arg(long, default_value = "0")]
     pub min_depth: i64,
 
-    /// Exclude macro-generated functions
+    /// Metric to rank functions by
+    #[arg(long, value_enum, default_value_t = ComplexityMetricKind::Cyclomatic)]
+    pub metric: ComplexityMetricKind,
+
+    /// Minimum ABC size threshold
+    #[arg(long, default_value = "0")]
+    pub min_abc: f64,
+
+    /// Minimum Halstead volume threshold
+    #[arg(long, default_value = "0")]
+    pub min_halstead: f64,
+
+    /// Minimum argument count threshold
+    #[arg(long, default_value = "0")]
+    pub min_args: i64,
+
+    /// Exclude generated functions: macro-generated and compiler callbacks,
+    /// plus the `generated` rules of the config file
     #[arg(long)]
     pub exclude_generated: bool,
 
+    /// Sort by an output column as `column[:asc|desc]` (ascending by default)
+    #[arg(long, value_name = "COLUMN[:asc|desc]")]
+    pub sort: Option<SortSpec>,
+
+    /// Only include results matching an expression over the output columns, e.g. 'arity > 2 && module =~ "Web"'
+    #[arg(long = "where", value_name = "EXPR")]
+    pub filter: Option<WhereFilter>,
+
+    /// Include annotations attached with `annotate`
+    #[arg(long)]
+    pub show_annotations: bool,
+
+    /// Annotate each finding with the last author and commit date of its lines, from `git blame`
+    #[arg(long)]
+    pub blame: bool,
+
+    #[command(flatten)]
+    pub baseline: BaselineArgs,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for ComplexityCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
@@ -1,7 +1,7 @@
 //! Output formatting for complexity command results.
 
 use super::execute::ComplexityEntry;
-use crate::output::TableFormatter;
+use crate::output::{Annotation, TableFormatter};
 use db::types::ModuleCollectionResult;
 
 impl TableFormatter for ModuleCollectionResult<ComplexityEntry> {
@@ -24,10 +24,30 @@ impl TableFormatter for ModuleCollectionResult<ComplexityEntry> {
     }
 
     fn format_entry(&self, entry: &ComplexityEntry, _module: &str, _file: &str) -> String {
-        format!(
+        let mut line = format!(
             "{}/{} complexity: {}, depth: {}, lines: {}",
             entry.name, entry.arity, entry.complexity, entry.max_nesting_depth, entry.lines
-        )
+        );
+        // Only shown when the extractor emitted them
+        if entry.cognitive_complexity > 0 {
+            line.push_str(&format!(", cognitive: {}", entry.cognitive_complexity));
+        }
+        if entry.abc_score > 0.0 {
+            line.push_str(&format!(", abc: {:.1}", entry.abc_score));
+        }
+        if entry.halstead_volume > 0.0 {
+            line.push_str(&format!(", halstead: {:.1}", entry.halstead_volume));
+        }
+        line
+    }
+
+    fn format_entry_details(&self, entry: &ComplexityEntry, _module: &str, _file: &str) -> Vec<String> {
+        entry
+            .annotations
+            .iter()
+            .map(|note| note.to_string())
+            .chain(entry.blame.iter().map(|blame| blame.to_string()))
+            .collect()
     }
 
     fn blank_before_module(&self) -> bool {
@@ -37,4 +57,20 @@ impl TableFormatter for ModuleCollectionResult<ComplexityEntry> {
     fn blank_after_summary(&self) -> bool {
         false
     }
+
+    fn format_annotation(&self, entry: &ComplexityEntry, module: &str, file: &str) -> Option<Annotation> {
+        Some(Annotation::warning(
+            file,
+            entry.line,
+            "High complexity",
+            format!(
+                "{}.{}/{} has complexity {} (nesting depth {})",
+                module, entry.name, entry.arity, entry.complexity, entry.max_nesting_depth
+            ),
+        ))
+    }
+
+    fn annotates(&self) -> bool {
+        true
+    }
 }
@@ -24,6 +24,11 @@ mod tests {
                     complexity: 12,
                     max_nesting_depth: 4,
                     lines: 45,
+                    abc_score: 0.0,
+                    halstead_volume: 0.0,
+                    cognitive_complexity: 0,
+                    annotations: vec![],
+                    blame: None,
                 }],
                 function_count: None,
             }],
@@ -36,6 +41,39 @@ mod tests {
         assert!(output.contains("complexity: 12"));
         assert!(output.contains("depth: 4"));
         assert!(output.contains("lines: 45"));
+        assert!(!output.contains("abc:"));
+    }
+
+    #[test]
+    fn test_format_table_extra_metrics() {
+        let result = ModuleCollectionResult {
+            module_pattern: "*".to_string(),
+            function_pattern: None,
+            kind_filter: None,
+            name_filter: None,
+            total_items: 1,
+            items: vec![ModuleGroup {
+                name: "MyApp.Accounts".to_string(),
+                file: "lib/my_app/accounts.ex".to_string(),
+                entries: vec![ComplexityEntry {
+                    name: "create_user".to_string(),
+                    arity: 1,
+                    line: 10,
+                    complexity: 12,
+                    max_nesting_depth: 4,
+                    lines: 45,
+                    abc_score: 21.36,
+                    halstead_volume: 480.0,
+                    cognitive_complexity: 7,
+                    annotations: vec![],
+                    blame: None,
+                }],
+                function_count: None,
+            }],
+        };
+
+        let output = result.format(crate::output::OutputFormat::Table);
+        assert!(output.contains("lines: 45, cognitive: 7, abc: 21.4, halstead: 480.0"));
     }
 
     #[test]
@@ -72,6 +110,11 @@ mod tests {
                     complexity: 12,
                     max_nesting_depth: 4,
                     lines: 45,
+                    abc_score: 0.0,
+                    halstead_volume: 0.0,
+                    cognitive_complexity: 0,
+                    annotations: vec![],
+                    blame: None,
                 }],
                 function_count: None,
             }],
@@ -109,6 +152,11 @@ mod tests {
                     complexity: 8,
                     max_nesting_depth: 3,
                     lines: 25,
+                    abc_score: 0.0,
+                    halstead_volume: 0.0,
+                    cognitive_complexity: 0,
+                    annotations: vec![],
+                    blame: None,
                 }],
                 function_count: None,
             }],
@@ -120,4 +168,39 @@ mod tests {
         assert!(output.contains("process"));
         assert!(output.contains("8")); // complexity
     }
+
+    #[test]
+    fn test_format_github() {
+        let result = ModuleCollectionResult {
+            module_pattern: "*".to_string(),
+            function_pattern: None,
+            kind_filter: None,
+            name_filter: None,
+            total_items: 1,
+            items: vec![ModuleGroup {
+                name: "MyApp.Accounts".to_string(),
+                file: "lib/my_app/accounts.ex".to_string(),
+                entries: vec![ComplexityEntry {
+                    name: "create_user".to_string(),
+                    arity: 1,
+                    line: 10,
+                    complexity: 12,
+                    max_nesting_depth: 4,
+                    lines: 45,
+                    abc_score: 0.0,
+                    halstead_volume: 0.0,
+                    cognitive_complexity: 0,
+                    annotations: vec![],
+                    blame: None,
+                }],
+                function_count: None,
+            }],
+        };
+
+        let output = result.format(crate::output::OutputFormat::Github);
+        assert_eq!(
+            output,
+            "::warning file=lib/my_app/accounts.ex,line=10,title=High complexity::MyApp.Accounts.create_user/1 has complexity 12 (nesting depth 4)"
+        );
+    }
 }
@@ -0,0 +1,334 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::ContextCmd;
+use crate::commands::Execute;
+use db::queries::centrality::find_function_edges;
+use db::queries::location::find_locations;
+use db::queries::search::fuzzy_score;
+use db::query_builders::QuerySettings;
+
+/// Words too common in task descriptions to say anything about names
+const STOPWORDS: &[&str] = &[
+    "and", "the", "for", "from", "with", "into", "when", "that", "this", "after", "before", "all", "add",
+];
+
+/// Lowest fuzzy score (0-1) a name part needs to match a task word
+///
+/// Stricter than fuzzy search: single words are short, and `users` is 0.5
+/// away from `insert`.
+const MIN_WORD_SCORE: f64 = 0.6;
+
+/// A function identified by module and name, the granularity of the call edges
+type FunctionKey = (String, String);
+
+/// Why a function is in the context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Relevance {
+    /// Named with --seed
+    Seed,
+    /// Its name resembles words of the task
+    NameMatch,
+    /// Reached over call edges from an anchor
+    Graph,
+}
+
+/// A relevant function with its location
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextFunction {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub file: String,
+    pub start_line: i64,
+    pub end_line: i64,
+    /// From 0 to 1: the anchor's weight divided by 1 + the call distance to it
+    pub score: f64,
+    pub relevance: Relevance,
+    /// Anchor the score comes from, as `Module.function`
+    pub via: String,
+    /// Call edges between the function and the anchor
+    pub distance: usize,
+}
+
+/// A file holding relevant functions
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextFile {
+    pub file: String,
+    /// Best score of its functions
+    pub score: f64,
+    pub functions: usize,
+}
+
+/// Result of the context command
+#[derive(Debug, Serialize)]
+pub struct ContextResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    pub seeds: Vec<String>,
+    /// Best first
+    pub functions: Vec<ContextFunction>,
+    /// Best first
+    pub files: Vec<ContextFile>,
+}
+
+/// Best reach of an anchor found so far for a function
+#[derive(Debug, Clone)]
+struct Reach {
+    score: f64,
+    relevance: Relevance,
+    via: String,
+    distance: usize,
+}
+
+impl Execute for ContextCmd {
+    type Output = ContextResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let locations = find_locations(db, None, ".*", None, &self.project, true, u32::MAX, settings)?;
+
+        // Clauses merged per function; arities share the score of their name
+        let mut definitions: BTreeMap<(String, String, i64), (String, i64, i64)> = BTreeMap::new();
+        for location in locations {
+            let key = (location.module, location.name, location.arity);
+            let entry = definitions
+                .entry(key)
+                .or_insert((location.file, location.start_line, location.end_line));
+            entry.1 = entry.1.min(location.start_line);
+            entry.2 = entry.2.max(location.end_line);
+        }
+
+        let mut anchors: Vec<(FunctionKey, f64, Relevance)> = self
+            .seeds
+            .iter()
+            .map(|seed| ((seed.module.clone(), seed.function.clone()), 1.0, Relevance::Seed))
+            .collect();
+        let words = self.task.as_deref().map(task_words).unwrap_or_default();
+        if !words.is_empty() {
+            let names: BTreeSet<(&String, &String)> = definitions.keys().map(|(module, name, _)| (module, name)).collect();
+            for (module, name) in names {
+                let score = name_score(&words, module, name);
+                if score > 0.0 {
+                    anchors.push(((module.clone(), name.clone()), score, Relevance::NameMatch));
+                }
+            }
+        }
+
+        let mut neighbors: HashMap<FunctionKey, Vec<FunctionKey>> = HashMap::new();
+        for edge in find_function_edges(db, &self.project, settings)? {
+            let caller = (edge.caller_module, edge.caller_function);
+            let callee = (edge.callee_module, edge.callee_function);
+            neighbors.entry(caller.clone()).or_default().push(callee.clone());
+            neighbors.entry(callee).or_default().push(caller);
+        }
+
+        let reach = spread(&anchors, &neighbors, self.depth);
+
+        let mut functions: Vec<ContextFunction> = definitions
+            .into_iter()
+            .filter_map(|((module, name, arity), (file, start_line, end_line))| {
+                let reach = reach.get(&(module.clone(), name.clone()))?;
+                Some(ContextFunction {
+                    module,
+                    name,
+                    arity,
+                    file,
+                    start_line,
+                    end_line,
+                    score: (reach.score * 100.0).round() / 100.0,
+                    relevance: reach.relevance,
+                    via: reach.via.clone(),
+                    distance: reach.distance,
+                })
+            })
+            .collect();
+        // Stable sort: equal scores stay ordered by module, name and arity
+        functions.sort_by(|a, b| b.score.total_cmp(&a.score));
+        functions.truncate(self.limit as usize);
+
+        let mut files: BTreeMap<&str, ContextFile> = BTreeMap::new();
+        for function in &functions {
+            let file = files.entry(&function.file).or_insert_with(|| ContextFile {
+                file: function.file.clone(),
+                score: function.score,
+                functions: 0,
+            });
+            file.score = file.score.max(function.score);
+            file.functions += 1;
+        }
+        let mut files: Vec<ContextFile> = files.into_values().collect();
+        files.sort_by(|a, b| b.score.total_cmp(&a.score).then(b.functions.cmp(&a.functions)));
+
+        Ok(ContextResult {
+            task: self.task,
+            seeds: self.seeds.iter().map(|seed| seed.label()).collect(),
+            functions,
+            files,
+        })
+    }
+}
+
+/// Lowercase words of a task description worth matching against names
+fn task_words(task: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for word in task.split(|c: char| !c.is_alphanumeric()).map(str::to_lowercase) {
+        if word.chars().count() >= 3 && !STOPWORDS.contains(&word.as_str()) && !words.contains(&word) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// Share of the task words found among a function's name parts, from 0 to 1
+///
+/// The parts are the module segments and the underscore-separated words of
+/// the function name; each task word counts with its best fuzzy score, or not
+/// at all under [`MIN_WORD_SCORE`].
+fn name_score(words: &[String], module: &str, name: &str) -> f64 {
+    let parts: Vec<&str> = module
+        .split('.')
+        .chain(name.trim_end_matches(['?', '!']).split('_'))
+        .filter(|part| !part.is_empty())
+        .collect();
+
+    let total: f64 = words
+        .iter()
+        .map(|word| {
+            let best = parts.iter().map(|part| fuzzy_score(word, part)).fold(0.0, f64::max);
+            if best >= MIN_WORD_SCORE { best } else { 0.0 }
+        })
+        .sum();
+    total / words.len() as f64
+}
+
+/// Best score each function gets from the anchors within `depth` call edges
+///
+/// An anchor of weight `w` gives `w / (1 + d)` to a function `d` edges away,
+/// following calls in both directions.
+fn spread(
+    anchors: &[(FunctionKey, f64, Relevance)],
+    neighbors: &HashMap<FunctionKey, Vec<FunctionKey>>,
+    depth: usize,
+) -> HashMap<FunctionKey, Reach> {
+    let mut best: HashMap<FunctionKey, Reach> = HashMap::new();
+
+    for (anchor, weight, relevance) in anchors {
+        let via = format!("{}.{}", anchor.0, anchor.1);
+        let mut distances: HashMap<&FunctionKey, usize> = HashMap::from([(anchor, 0)]);
+        let mut queue = VecDeque::from([anchor]);
+
+        while let Some(function) = queue.pop_front() {
+            let distance = distances[function];
+            let score = weight / (1 + distance) as f64;
+            if best.get(function).is_none_or(|reach| score > reach.score) {
+                best.insert(
+                    function.clone(),
+                    Reach {
+                        score,
+                        relevance: if distance == 0 { *relevance } else { Relevance::Graph },
+                        via: via.clone(),
+                        distance,
+                    },
+                );
+            }
+
+            if distance == depth {
+                continue;
+            }
+            for next in neighbors.get(function).into_iter().flatten() {
+                if !distances.contains_key(next) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::context::Seed;
+    use rstest::{fixture, rstest};
+
+    crate::shared_fixture! {
+        fixture_name: populated_db,
+        fixture_type: call_graph,
+        project: "test_project",
+    }
+
+    fn context(db: &db::DbInstance, task: Option<&str>, seeds: Vec<Seed>, depth: usize) -> ContextResult {
+        let cmd = ContextCmd {
+            task: task.map(str::to_string),
+            seeds,
+            depth,
+            project: "test_project".to_string(),
+            limit: 100,
+        };
+        cmd.execute(db, &QuerySettings::default()).expect("Context should succeed")
+    }
+
+    #[test]
+    fn test_task_words() {
+        assert_eq!(task_words("Notify the users, after login!"), ["notify", "users", "login"]);
+        assert!(task_words("a to of").is_empty());
+    }
+
+    #[test]
+    fn test_name_score() {
+        let words = task_words("get user");
+        assert_eq!(name_score(&words, "MyApp.Accounts", "get_user"), 1.0);
+        assert!(name_score(&words, "MyApp.Accounts", "list_users") > 0.0);
+        assert_eq!(name_score(&words, "MyApp.Notifier", "notify"), 0.0);
+    }
+
+    #[rstest]
+    fn test_context_from_seed(populated_db: db::DbInstance) {
+        let seed = Seed {
+            module: "MyApp.Accounts".to_string(),
+            function: "get_user".to_string(),
+        };
+        let result = context(&populated_db, None, vec![seed], 1);
+
+        let first = &result.functions[0];
+        assert_eq!((first.module.as_str(), first.name.as_str()), ("MyApp.Accounts", "get_user"));
+        assert_eq!(first.relevance, Relevance::Seed);
+        assert_eq!(first.score, 1.0);
+
+        let caller = result
+            .functions
+            .iter()
+            .find(|f| f.module == "MyApp.Controller" && f.name == "show")
+            .expect("Caller within one edge");
+        assert_eq!((caller.relevance, caller.distance, caller.score), (Relevance::Graph, 1, 0.5));
+        assert!(result.functions.iter().all(|f| f.distance <= 1));
+        assert!(result.functions.windows(2).all(|w| w[0].score >= w[1].score));
+        assert_eq!(result.files[0].file, first.file);
+    }
+
+    #[rstest]
+    fn test_context_from_task(populated_db: db::DbInstance) {
+        let result = context(&populated_db, Some("list users"), vec![], 0);
+
+        let first = &result.functions[0];
+        assert_eq!(first.name, "list_users");
+        assert_eq!(first.relevance, Relevance::NameMatch);
+        assert!(result.functions.iter().all(|f| f.relevance == Relevance::NameMatch));
+    }
+
+    #[rstest]
+    fn test_context_unknown_seed(populated_db: db::DbInstance) {
+        let seed = Seed {
+            module: "MyApp.Missing".to_string(),
+            function: "run".to_string(),
+        };
+        let result = context(&populated_db, None, vec![seed], 2);
+        assert!(result.functions.is_empty());
+        assert!(result.files.is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// A function to start from, named `Module.function` or `Module.function/arity`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seed {
+    pub module: String,
+    pub function: String,
+}
+
+impl Seed {
+    pub fn label(&self) -> String {
+        format!("{}.{}", self.module, self.function)
+    }
+}
+
+fn parse_seed(s: &str) -> Result<Seed, String> {
+    match db::db::strip_arity(s).rsplit_once('.') {
+        Some((module, function)) if !module.is_empty() && !function.is_empty() => Ok(Seed {
+            module: module.to_string(),
+            function: function.to_string(),
+        }),
+        _ => Err(format!("`{s}` is not Module.function or Module.function/arity")),
+    }
+}
+
+/// Rank the functions and files relevant to a task, for an LLM context window
+///
+/// Functions whose module or function names resemble the words of the task,
+/// and the seeds, are the anchors. Relevance then spreads along call edges in
+/// both directions, halving at the first step and shrinking with each further
+/// one. The table output is Markdown, ready to paste into a prompt.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search context 'notify users after login'              # From a task description
+  code_search context --seed MyApp.Accounts.get_user           # From a function
+  code_search context 'email' --seed MyApp.Service.process/1   # Both
+  code_search context 'user accounts' --depth 1 -l 10 -o json  # Nearby only, as JSON
+")]
+pub struct ContextCmd {
+    /// Task description; its words are matched against module and function names
+    #[arg(required_unless_present = "seeds")]
+    pub task: Option<String>,
+
+    /// Function to start from, as Module.function or Module.function/arity (repeatable;
+    /// the arity is ignored, all arities are relevant)
+    #[arg(short, long = "seed", value_parser = parse_seed)]
+    pub seeds: Vec<Seed>,
+
+    /// Call edges to follow out from the anchors
+    #[arg(short, long, default_value_t = 2)]
+    pub depth: usize,
+
+    /// Project to search in
+    #[arg(long, default_value = "default")]
+    pub project: String,
+
+    /// Maximum functions to list (1-1000)
+    #[arg(short, long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..=1000))]
+    pub limit: u32,
+}
+
+impl CommandRunner for ContextCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_seed() {
+        let seed = parse_seed("MyApp.Accounts.get_user/1").unwrap();
+        assert_eq!(seed.module, "MyApp.Accounts");
+        assert_eq!(seed.function, "get_user");
+        assert_eq!(parse_seed("MyApp.Accounts.get_user").unwrap(), seed);
+        assert!(parse_seed("get_user/1").is_err());
+    }
+}
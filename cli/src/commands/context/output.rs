@@ -0,0 +1,141 @@
+//! Output formatting for context command results.
+//!
+//! The table format is Markdown, meant to be pasted into an LLM prompt.
+
+use super::execute::{ContextFunction, ContextResult, Relevance};
+use crate::output::Outputable;
+
+/// Why a function is listed, for the last column
+fn reason(function: &ContextFunction) -> String {
+    match function.relevance {
+        Relevance::Seed => "seed".to_string(),
+        Relevance::NameMatch => "name matches the task".to_string(),
+        Relevance::Graph => {
+            let edges = if function.distance == 1 { "call" } else { "calls" };
+            format!("{} {} from `{}`", function.distance, edges, function.via)
+        }
+    }
+}
+
+impl Outputable for ContextResult {
+    fn to_table(&self) -> String {
+        let mut lines = vec!["# Code context".to_string(), String::new()];
+        if let Some(task) = &self.task {
+            lines.push(format!("Task: {}", task));
+        }
+        if !self.seeds.is_empty() {
+            let seeds: Vec<String> = self.seeds.iter().map(|seed| format!("`{}`", seed)).collect();
+            lines.push(format!("Seeds: {}", seeds.join(", ")));
+        }
+
+        if self.functions.is_empty() {
+            lines.push(String::new());
+            lines.push("No relevant functions found.".to_string());
+            return lines.join("\n");
+        }
+
+        lines.push(String::new());
+        lines.push("## Files".to_string());
+        lines.push(String::new());
+        for file in &self.files {
+            let functions = if file.functions == 1 { "function" } else { "functions" };
+            lines.push(format!(
+                "- `{}` ({} {}, score {:.2})",
+                file.file, file.functions, functions, file.score
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("## Functions".to_string());
+        lines.push(String::new());
+        lines.push("| Function | Location | Score | Why |".to_string());
+        lines.push("|---|---|---|---|".to_string());
+        for function in &self.functions {
+            lines.push(format!(
+                "| `{}.{}/{}` | {}:{}-{} | {:.2} | {} |",
+                function.module,
+                function.name,
+                function.arity,
+                function.file,
+                function.start_line,
+                function.end_line,
+                function.score,
+                reason(function)
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::context::execute::ContextFile;
+
+    fn function(name: &str, score: f64, relevance: Relevance, distance: usize) -> ContextFunction {
+        ContextFunction {
+            module: "MyApp.Accounts".to_string(),
+            name: name.to_string(),
+            arity: 1,
+            file: "lib/accounts.ex".to_string(),
+            start_line: 10,
+            end_line: 14,
+            score,
+            relevance,
+            via: "MyApp.Accounts.get_user".to_string(),
+            distance,
+        }
+    }
+
+    #[test]
+    fn test_to_table_markdown() {
+        let result = ContextResult {
+            task: Some("load users".to_string()),
+            seeds: vec!["MyApp.Accounts.get_user".to_string()],
+            functions: vec![
+                function("get_user", 1.0, Relevance::Seed, 0),
+                function("load", 0.5, Relevance::Graph, 1),
+            ],
+            files: vec![ContextFile {
+                file: "lib/accounts.ex".to_string(),
+                score: 1.0,
+                functions: 2,
+            }],
+        };
+
+        assert_eq!(
+            result.to_table(),
+            "\
+# Code context
+
+Task: load users
+Seeds: `MyApp.Accounts.get_user`
+
+## Files
+
+- `lib/accounts.ex` (2 functions, score 1.00)
+
+## Functions
+
+| Function | Location | Score | Why |
+|---|---|---|---|
+| `MyApp.Accounts.get_user/1` | lib/accounts.ex:10-14 | 1.00 | seed |
+| `MyApp.Accounts.load/1` | lib/accounts.ex:10-14 | 0.50 | 1 call from `MyApp.Accounts.get_user` |"
+        );
+    }
+
+    #[test]
+    fn test_to_table_empty() {
+        let result = ContextResult {
+            task: Some("billing".to_string()),
+            seeds: vec![],
+            functions: vec![],
+            files: vec![],
+        };
+        assert_eq!(
+            result.to_table(),
+            "# Code context\n\nTask: billing\n\nNo relevant functions found."
+        );
+    }
+}
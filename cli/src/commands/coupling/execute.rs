@@ -0,0 +1,287 @@
+//! Coupling metric computation per module or namespace cluster.
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::{CouplingCmd, CouplingSort};
+use crate::commands::Execute;
+use crate::utils::extract_namespace;
+use db::query_builders::{compile_pattern, QuerySettings};
+use db::queries::clusters::{get_module_calls, ModuleCall};
+use db::queries::coupling::{find_module_coupling, get_abstractness_counts};
+
+/// Coupling metrics for a module or namespace
+#[derive(Debug, Clone, Serialize)]
+pub struct CouplingEntry {
+    pub name: String,
+    /// Afferent coupling (Ca): distinct incoming dependencies
+    pub afferent: i64,
+    /// Efferent coupling (Ce): distinct outgoing dependencies
+    pub efferent: i64,
+    /// Ce / (Ca + Ce), 0 = stable, 1 = unstable
+    pub instability: f64,
+    /// callbacks / (callbacks + public functions)
+    pub abstractness: f64,
+    /// |A + I - 1|, distance from the main sequence
+    pub distance: f64,
+}
+
+impl CouplingEntry {
+    fn new(name: String, afferent: i64, efferent: i64, callbacks: i64, public: i64) -> Self {
+        let instability = ratio(efferent, afferent + efferent);
+        let abstractness = ratio(callbacks, callbacks + public);
+        Self {
+            name,
+            afferent,
+            efferent,
+            instability,
+            abstractness,
+            distance: (abstractness + instability - 1.0).abs(),
+        }
+    }
+}
+
+/// Result of the coupling command
+#[derive(Debug, Serialize)]
+pub struct CouplingResult {
+    pub sort_by: String,
+    pub depth: Option<usize>,
+    pub total_items: usize,
+    pub entries: Vec<CouplingEntry>,
+}
+
+impl Execute for CouplingCmd {
+    type Output = CouplingResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let mut entries = match self.depth {
+            None => {
+                let abstractness = get_abstractness_counts(
+                    db,
+                    &self.common.project,
+                    self.module.as_deref(),
+                    self.common.regex,
+                    settings,
+                )?;
+                find_module_coupling(
+                    db,
+                    &self.common.project,
+                    self.module.as_deref(),
+                    self.common.regex,
+                    settings,
+                )?
+                .into_iter()
+                .map(|c| {
+                    let (callbacks, public) =
+                        abstractness.get(&c.module).copied().unwrap_or((0, 0));
+                    CouplingEntry::new(c.module, c.afferent, c.efferent, callbacks, public)
+                })
+                .collect()
+            }
+            Some(depth) => {
+                let abstractness =
+                    get_abstractness_counts(db, &self.common.project, None, false, settings)?;
+                let calls = get_module_calls(db, &self.common.project, settings)?;
+                let mut entries = namespace_coupling(&calls, &abstractness, depth);
+
+                if let Some(pattern) = &self.module {
+                    if self.common.regex {
+                        let re = compile_pattern(pattern)?;
+                        entries.retain(|e| re.is_match(&e.name));
+                    } else {
+                        entries.retain(|e| e.name.contains(pattern.as_str()));
+                    }
+                }
+                entries
+            }
+        };
+
+        sort_entries(&mut entries, self.by);
+        entries.truncate(self.common.limit as usize);
+
+        Ok(CouplingResult {
+            sort_by: self.by.as_str().to_string(),
+            depth: self.depth,
+            total_items: entries.len(),
+            entries,
+        })
+    }
+}
+
+fn ratio(numerator: i64, denominator: i64) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Aggregate module calls and abstractness counts into namespace clusters
+fn namespace_coupling(
+    calls: &[ModuleCall],
+    abstractness: &HashMap<String, (i64, i64)>,
+    depth: usize,
+) -> Vec<CouplingEntry> {
+    let mut deps: HashSet<(String, String)> = HashSet::new();
+    let mut namespaces: HashSet<String> = HashSet::new();
+
+    for call in calls {
+        let from = extract_namespace(&call.caller_module, depth);
+        let to = extract_namespace(&call.callee_module, depth);
+        namespaces.insert(from.clone());
+        namespaces.insert(to.clone());
+        if from != to {
+            deps.insert((from, to));
+        }
+    }
+
+    let mut afferent: HashMap<&str, i64> = HashMap::new();
+    let mut efferent: HashMap<&str, i64> = HashMap::new();
+    for (from, to) in &deps {
+        *efferent.entry(from.as_str()).or_default() += 1;
+        *afferent.entry(to.as_str()).or_default() += 1;
+    }
+
+    let mut counts: HashMap<String, (i64, i64)> = HashMap::new();
+    for (module, (callbacks, public)) in abstractness {
+        let entry = counts.entry(extract_namespace(module, depth)).or_default();
+        entry.0 += callbacks;
+        entry.1 += public;
+    }
+
+    namespaces
+        .iter()
+        .map(|ns| {
+            let (callbacks, public) = counts.get(ns).copied().unwrap_or((0, 0));
+            CouplingEntry::new(
+                ns.clone(),
+                afferent.get(ns.as_str()).copied().unwrap_or(0),
+                efferent.get(ns.as_str()).copied().unwrap_or(0),
+                callbacks,
+                public,
+            )
+        })
+        .collect()
+}
+
+/// Sort entries by the selected metric (descending), breaking ties by name
+fn sort_entries(entries: &mut [CouplingEntry], by: CouplingSort) {
+    entries.sort_by(|a, b| {
+        let ordering = match by {
+            CouplingSort::Afferent => b.afferent.cmp(&a.afferent),
+            CouplingSort::Efferent => b.efferent.cmp(&a.efferent),
+            CouplingSort::Instability => b.instability.total_cmp(&a.instability),
+            CouplingSort::Abstractness => b.abstractness.total_cmp(&a.abstractness),
+            CouplingSort::Distance => b.distance.total_cmp(&a.distance),
+        };
+        ordering.then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+
+    fn module_call(from: &str, to: &str) -> ModuleCall {
+        ModuleCall {
+            caller_module: from.to_string(),
+            callee_module: to.to_string(),
+        }
+    }
+
+    fn coupling_cmd(by: CouplingSort, depth: Option<usize>) -> CouplingCmd {
+        CouplingCmd {
+            module: None,
+            by,
+            depth,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_entry_metrics() {
+        let entry = CouplingEntry::new("A".to_string(), 1, 3, 1, 3);
+        assert_eq!(entry.instability, 0.75);
+        assert_eq!(entry.abstractness, 0.25);
+        assert_eq!(entry.distance, 0.0);
+    }
+
+    #[test]
+    fn test_entry_metrics_isolated_module() {
+        let entry = CouplingEntry::new("A".to_string(), 0, 0, 0, 0);
+        assert_eq!(entry.instability, 0.0);
+        assert_eq!(entry.abstractness, 0.0);
+        assert_eq!(entry.distance, 1.0);
+    }
+
+    #[test]
+    fn test_namespace_coupling_counts_distinct_namespaces() {
+        let calls = vec![
+            module_call("MyApp.Web.UserController", "MyApp.Accounts.Users"),
+            module_call("MyApp.Web.PageController", "MyApp.Accounts.Auth"),
+            module_call("MyApp.Accounts.Users", "MyApp.Repo"),
+            module_call("MyApp.Accounts.Users", "MyApp.Accounts.Auth"),
+        ];
+        let entries = namespace_coupling(&calls, &HashMap::new(), 2);
+        let get = |name: &str| entries.iter().find(|e| e.name == name).unwrap();
+
+        assert_eq!(get("MyApp.Web").efferent, 1);
+        assert_eq!(get("MyApp.Web").afferent, 0);
+        assert_eq!(get("MyApp.Accounts").afferent, 1);
+        assert_eq!(get("MyApp.Accounts").efferent, 1);
+        assert_eq!(get("MyApp.Repo").afferent, 1);
+    }
+
+    #[test]
+    fn test_sort_entries_by_instability() {
+        let mut entries = vec![
+            CouplingEntry::new("Stable".to_string(), 4, 0, 0, 1),
+            CouplingEntry::new("Unstable".to_string(), 0, 4, 0, 1),
+        ];
+        sort_entries(&mut entries, CouplingSort::Instability);
+        assert_eq!(entries[0].name, "Unstable");
+    }
+
+    #[test]
+    fn test_coupling_execute_modules() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = coupling_cmd(CouplingSort::Afferent, None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.sort_by, "afferent");
+        assert!(result.total_items > 0);
+        // Repo is called by both Accounts and Service
+        assert_eq!(result.entries[0].name, "MyApp.Repo");
+        assert_eq!(result.entries[0].afferent, 2);
+        assert_eq!(result.entries[0].instability, 0.0);
+    }
+
+    #[test]
+    fn test_coupling_execute_namespaces() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = coupling_cmd(CouplingSort::Efferent, Some(1))
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        // Every fixture module lives under MyApp, so depth 1 collapses them into one namespace
+        assert_eq!(result.total_items, 1);
+        assert_eq!(result.entries[0].name, "MyApp");
+        assert_eq!(result.entries[0].efferent, 0);
+    }
+
+    #[test]
+    fn test_coupling_execute_empty_db() {
+        let db = db::test_utils::setup_empty_test_db();
+        let result = coupling_cmd(CouplingSort::Afferent, None).execute(&db, &QuerySettings::default());
+        assert!(result.is_err());
+    }
+}
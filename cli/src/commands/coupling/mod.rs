@@ -0,0 +1,76 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::{Args, ValueEnum};
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Metric used to rank coupling results
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum CouplingSort {
+    /// Most depended-upon first (Ca)
+    #[default]
+    Afferent,
+    /// Most dependencies first (Ce)
+    Efferent,
+    /// Most unstable first (I = Ce / (Ca + Ce))
+    Instability,
+    /// Most abstract first (callbacks / (callbacks + public functions))
+    Abstractness,
+    /// Furthest from the main sequence first (D = |A + I - 1|)
+    Distance,
+}
+
+impl CouplingSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CouplingSort::Afferent => "afferent",
+            CouplingSort::Efferent => "efferent",
+            CouplingSort::Instability => "instability",
+            CouplingSort::Abstractness => "abstractness",
+            CouplingSort::Distance => "distance",
+        }
+    }
+}
+
+/// Compute afferent/efferent coupling, instability and abstractness per module
+///
+/// Ca counts distinct modules that call into a module, Ce distinct modules it calls.
+/// Instability I = Ce / (Ca + Ce); abstractness A = callbacks / (callbacks + public functions);
+/// distance from the main sequence D = |A + I - 1|.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search coupling                      # Rank modules by afferent coupling
+  code_search coupling -b efferent          # Modules with the most dependencies
+  code_search coupling -b distance          # Furthest from the main sequence
+  code_search coupling --depth 2            # Aggregate by namespace (e.g., MyApp.Accounts)
+  code_search coupling MyApp.Core -l 10     # Top 10 in MyApp.Core namespace
+")]
+pub struct CouplingCmd {
+    /// Module filter pattern (substring match by default, regex with --regex)
+    pub module: Option<String>,
+
+    /// Metric to rank by
+    #[arg(short, long, value_enum, default_value_t = CouplingSort::Afferent)]
+    pub by: CouplingSort,
+
+    /// Aggregate modules into namespace clusters at this depth
+    #[arg(long)]
+    pub depth: Option<usize>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for CouplingCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,132 @@
+//! Output formatting for coupling command results.
+
+use super::execute::CouplingResult;
+use crate::output::Outputable;
+
+impl Outputable for CouplingResult {
+    fn to_table(&self) -> String {
+        let mut output = String::new();
+        output.push_str(&format!("Coupling (by {})\n\n", self.sort_by));
+
+        if self.entries.is_empty() {
+            output.push_str("No modules found.\n");
+            return output;
+        }
+
+        let label = if self.depth.is_some() { "Namespace" } else { "Module" };
+        let noun = if self.depth.is_some() { "namespace(s)" } else { "module(s)" };
+        output.push_str(&format!("Found {} {}:\n\n", self.total_items, noun));
+
+        let name_width = self
+            .entries
+            .iter()
+            .map(|e| e.name.len())
+            .max()
+            .unwrap_or(0)
+            .max(label.len());
+
+        output.push_str(&format!(
+            "{:<name_width$}  {:>5}  {:>5}  {:>6}  {:>6}  {:>6}\n",
+            label,
+            "Ca",
+            "Ce",
+            "Instab",
+            "Abstr",
+            "Dist",
+            name_width = name_width,
+        ));
+
+        for entry in &self.entries {
+            output.push_str(&format!(
+                "{:<name_width$}  {:>5}  {:>5}  {:>6.2}  {:>6.2}  {:>6.2}\n",
+                entry.name,
+                entry.afferent,
+                entry.efferent,
+                entry.instability,
+                entry.abstractness,
+                entry.distance,
+                name_width = name_width,
+            ));
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::coupling::execute::CouplingEntry;
+
+    fn entry(name: &str) -> CouplingEntry {
+        CouplingEntry {
+            name: name.to_string(),
+            afferent: 2,
+            efferent: 1,
+            instability: 1.0 / 3.0,
+            abstractness: 0.0,
+            distance: 2.0 / 3.0,
+        }
+    }
+
+    #[test]
+    fn test_coupling_output_empty() {
+        let result = CouplingResult {
+            sort_by: "afferent".to_string(),
+            depth: None,
+            total_items: 0,
+            entries: vec![],
+        };
+
+        let output = result.to_table();
+        assert!(output.contains("Coupling (by afferent)"));
+        assert!(output.contains("No modules found"));
+    }
+
+    #[test]
+    fn test_coupling_output_modules() {
+        let result = CouplingResult {
+            sort_by: "afferent".to_string(),
+            depth: None,
+            total_items: 1,
+            entries: vec![entry("MyApp.Repo")],
+        };
+
+        let output = result.to_table();
+        assert!(output.contains("Found 1 module(s)"));
+        assert!(output.contains("Module"));
+        assert!(output.contains("Instab"));
+        assert!(output.contains("MyApp.Repo"));
+        assert!(output.contains("0.33"));
+        assert!(output.contains("0.67"));
+    }
+
+    #[test]
+    fn test_coupling_output_namespaces() {
+        let result = CouplingResult {
+            sort_by: "distance".to_string(),
+            depth: Some(2),
+            total_items: 1,
+            entries: vec![entry("MyApp.Accounts")],
+        };
+
+        let output = result.to_table();
+        assert!(output.contains("Found 1 namespace(s)"));
+        assert!(output.starts_with("Coupling (by distance)"));
+    }
+
+    #[test]
+    fn test_coupling_output_json() {
+        let result = CouplingResult {
+            sort_by: "afferent".to_string(),
+            depth: None,
+            total_items: 1,
+            entries: vec![entry("MyApp.Repo")],
+        };
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"afferent\":2"));
+        assert!(json.contains("instability"));
+        assert!(json.contains("abstractness"));
+    }
+}
@@ -8,6 +8,7 @@ use serde::Serialize;
 use super::CyclesCmd;
 use crate::commands::Execute;
 use db::queries::cycles::find_cycle_edges;
+use db::query_builders::QuerySettings;
 
 /// A single cycle found in the module dependency graph
 #[derive(Debug, Clone, Serialize)]
@@ -32,15 +33,17 @@ pub struct CyclesResult {
 impl Execute for CyclesCmd {
     type Output = CyclesResult;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         // Get cycle edges from the database
         let edges = find_cycle_edges(
             db,
             &self.common.project,
             self.module.as_deref(),
+            settings,
         )?;
 
         if edges.is_empty() {
+            self.baseline.apply("cycles", Vec::new(), cycle_key)?;
             return Ok(CyclesResult {
                 total_cycles: 0,
                 modules_in_cycles: 0,
@@ -77,6 +80,9 @@ impl Execute for CyclesCmd {
         // Deduplicate cycles (same modules in different starting positions are the same cycle)
         cycles = deduplicate_cycles(cycles);
 
+        // Drop cycles recorded in the baseline (or record them)
+        cycles = self.baseline.apply("cycles", cycles, cycle_key)?;
+
         // Count unique modules in cycles
         let modules_in_cycles: HashSet<_> = cycles
             .iter()
@@ -91,11 +97,21 @@ impl Execute for CyclesCmd {
     }
 }
 
+/// Baseline key for a cycle: its member modules in sorted order
+fn cycle_key(cycle: &Cycle) -> String {
+    let mut members = cycle.modules.clone();
+    members.sort();
+    members.join(",")
+}
+
 /// Find all cycles starting from each node in the graph using DFS
 fn find_all_cycles(graph: &HashMap<String, Vec<String>>, all_modules: &HashSet<String>) -> Vec<Cycle> {
     let mut cycles = Vec::new();
 
-    for start_node in all_modules {
+    // Start nodes in name order, so each cycle is first found from its smallest module
+    let mut start_nodes: Vec<&String> = all_modules.iter().collect();
+    start_nodes.sort();
+    for start_node in start_nodes {
         let found = dfs_find_cycles(graph, start_node, start_node, vec![]);
         cycles.extend(found);
     }
@@ -214,6 +230,8 @@ mod tests {
 
         assert_eq!(unique.len(), 1);
         assert_eq!(unique[0].length, 3);
+        // Reported from its smallest module whatever the hash order
+        assert_eq!(unique[0].modules, ["A", "B", "C"]);
     }
 
     #[test]
@@ -4,8 +4,10 @@ mod output;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
+use crate::baseline::BaselineArgs;
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
 
@@ -20,6 +22,8 @@ Examples:
   code_search cycles MyApp.Core                 # Filter to MyApp.Core namespace
   code_search cycles --max-length 3             # Only show cycles of length <= 3
   code_search cycles --involving MyApp.Accounts # Only cycles involving Accounts
+  code_search cycles --baseline cycles.json --write-baseline  # Record current cycles
+  code_search cycles --baseline cycles.json                   # Only report new cycles
 ")]
 pub struct CyclesCmd {
     /// Module filter pattern (substring or regex with -r)
@@ -33,13 +37,16 @@ pub struct CyclesCmd {
     #[arg(long)]
     pub involving: Option<String>,
 
+    #[command(flatten)]
+    pub baseline: BaselineArgs,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for CyclesCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
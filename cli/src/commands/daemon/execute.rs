@@ -0,0 +1,58 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use super::DaemonCmd;
+use crate::commands::Execute;
+use crate::daemon::{ping, serve, socket_path, stop};
+use db::query_builders::QuerySettings;
+
+/// Result of the daemon command, once it has stopped
+#[derive(Debug, Serialize)]
+pub struct DaemonResult {
+    pub socket: String,
+    /// Whether this invocation stopped a running daemon (`--stop`)
+    pub stopped_other: bool,
+    /// Whether this invocation checked a running daemon (`--status`)
+    pub checked_other: bool,
+    /// Invocations answered before stopping, or so far with `--status`
+    pub requests_served: usize,
+}
+
+impl Execute for DaemonCmd {
+    type Output = DaemonResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let db_path = self.db_path.unwrap_or_else(|| PathBuf::from(".code_search/cozo.sqlite"));
+        let socket = socket_path(&db_path);
+
+        if self.stop {
+            stop(&socket)?;
+            return Ok(DaemonResult {
+                socket: socket.display().to_string(),
+                stopped_other: true,
+                checked_other: false,
+                requests_served: 0,
+            });
+        }
+
+        if self.status {
+            let requests_served = ping(&socket)?;
+            return Ok(DaemonResult {
+                socket: socket.display().to_string(),
+                stopped_other: false,
+                checked_other: true,
+                requests_served,
+            });
+        }
+
+        let requests_served = serve(db, &db_path, &socket)?;
+        Ok(DaemonResult {
+            socket: socket.display().to_string(),
+            stopped_other: false,
+            checked_other: false,
+            requests_served,
+        })
+    }
+}
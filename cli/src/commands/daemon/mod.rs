@@ -0,0 +1,50 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Keep the database open and answer queries over a unix socket
+///
+/// Listens on a socket next to the database file (`.code_search/cozo.sock`
+/// for the default database) until stopped. While it runs, read-only commands
+/// in the same directory are forwarded to it and answered from the open
+/// database and the warm adjacency indexes; commands changing the database
+/// still run on their own.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search daemon &                        # Serve queries in the background
+  code_search trace MyApp.Web index           # Answered by the daemon
+  code_search daemon --status                 # Check the daemon is healthy
+  code_search daemon --stop                   # Stop the daemon
+  code_search --db other.sqlite daemon        # Serve another database
+")]
+pub struct DaemonCmd {
+    /// Stop the daemon serving this database instead of starting one
+    #[arg(long, default_value_t = false, conflicts_with = "status")]
+    pub stop: bool,
+
+    /// Check the daemon serving this database answers queries, failing when
+    /// none is listening or its database does not answer
+    #[arg(long, default_value_t = false)]
+    pub status: bool,
+
+    /// Database file to serve, set from the resolved --db path
+    #[arg(skip)]
+    pub db_path: Option<PathBuf>,
+}
+
+impl CommandRunner for DaemonCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
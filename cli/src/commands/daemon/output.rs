@@ -0,0 +1,22 @@
+//! Output formatting for daemon command results.
+
+use super::execute::DaemonResult;
+use crate::output::Outputable;
+
+impl Outputable for DaemonResult {
+    fn to_table(&self) -> String {
+        if self.stopped_other {
+            format!("Stopped the daemon on {}", self.socket)
+        } else if self.checked_other {
+            format!(
+                "Daemon on {} is healthy, {} request(s) served",
+                self.socket, self.requests_served
+            )
+        } else {
+            format!(
+                "Daemon on {} stopped after {} request(s)",
+                self.socket, self.requests_served
+            )
+        }
+    }
+}
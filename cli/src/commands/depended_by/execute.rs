@@ -5,6 +5,7 @@ use serde::Serialize;
 
 use super::DependedByCmd;
 use crate::commands::Execute;
+use db::query_builders::{PatternMatching, QuerySettings};
 use db::queries::depended_by::find_dependents;
 use db::types::{Call, ModuleGroupResult, ModuleGroup};
 
@@ -111,13 +112,17 @@ fn build_dependent_caller_result(target_module: String, calls: Vec<Call>) -> Mod
 impl Execute for DependedByCmd {
     type Output = ModuleGroupResult<DependentCaller>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let matching = PatternMatching::new(self.common.regex, [self.module.as_str()]);
+        let (module, use_regex) = (matching.pattern(&self.module), matching.use_regex());
+
         let calls = find_dependents(
             db,
-            &self.module,
+            &module,
             &self.common.project,
-            self.common.regex,
+            use_regex,
             self.common.limit,
+            settings,
         )?;
 
         Ok(build_dependent_caller_result(self.module, calls))
@@ -26,6 +26,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -44,6 +45,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -70,6 +72,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: items,
@@ -88,6 +91,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         collection: items,
@@ -106,6 +110,7 @@ mod tests {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
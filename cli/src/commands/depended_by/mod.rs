@@ -7,6 +7,7 @@ mod output_tests;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
@@ -27,8 +28,8 @@ pub struct DependedByCmd {
 }
 
 impl CommandRunner for DependedByCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
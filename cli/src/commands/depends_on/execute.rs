@@ -5,6 +5,7 @@ use serde::Serialize;
 
 use super::DependsOnCmd;
 use crate::commands::Execute;
+use db::query_builders::{PatternMatching, QuerySettings};
 use db::queries::depends_on::find_dependencies;
 use db::types::{Call, ModuleGroupResult};
 use crate::utils::convert_to_module_groups;
@@ -67,13 +68,17 @@ fn build_dependency_result(source_module: String, calls: Vec<Call>) -> ModuleGro
 impl Execute for DependsOnCmd {
     type Output = ModuleGroupResult<DependencyFunction>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let matching = PatternMatching::new(self.common.regex, [self.module.as_str()]);
+        let (module, use_regex) = (matching.pattern(&self.module), matching.use_regex());
+
         let calls = find_dependencies(
             db,
-            &self.module,
+            &module,
             &self.common.project,
-            self.common.regex,
+            use_regex,
             self.common.limit,
+            settings,
         )?;
 
         Ok(build_dependency_result(self.module, calls))
@@ -76,7 +76,9 @@ Phoenix.View:
                         callee: FunctionRef::new("MyApp.Service", "process", 1),
                         line: 7,
                         call_type: None,
+                        call_kind: None,
                         depth: None,
+                        weight: None,
                     }],
                 }],
                 function_count: None,
@@ -110,7 +112,9 @@ Phoenix.View:
                             callee: FunctionRef::new("MyApp.Service", "process", 1),
                             line: 7,
                             call_type: None,
+                            call_kind: None,
                             depth: None,
+                            weight: None,
                         }],
                     }],
                     function_count: None,
@@ -134,7 +138,9 @@ Phoenix.View:
                             callee: FunctionRef::new("Phoenix.View", "render", 2),
                             line: 20,
                             call_type: None,
+                            call_kind: None,
                             depth: None,
+                            weight: None,
                         }],
                     }],
                     function_count: None,
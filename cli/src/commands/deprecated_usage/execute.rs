@@ -0,0 +1,116 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::DeprecatedUsageCmd;
+use crate::commands::Execute;
+use db::queries::deprecated::find_deprecated_calls;
+use db::query_builders::QuerySettings;
+use db::types::ModuleCollectionResult;
+
+/// A call to a deprecated function, within its caller module
+#[derive(Debug, Clone, Serialize)]
+pub struct DeprecatedUsageEntry {
+    pub caller_function: String,
+    pub line: i64,
+    pub callee_module: String,
+    pub callee_function: String,
+    pub callee_arity: i64,
+    pub message: String,
+}
+
+impl Execute for DeprecatedUsageCmd {
+    type Output = ModuleCollectionResult<DeprecatedUsageEntry>;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let calls = find_deprecated_calls(
+            db,
+            &self.common.project,
+            self.module.as_deref(),
+            self.common.regex,
+            self.common.limit,
+            settings,
+        )?;
+
+        let total_items = calls.len();
+
+        let items = crate::utils::group_by_module_with_file(calls, |call| {
+            let entry = DeprecatedUsageEntry {
+                caller_function: call.caller_function,
+                line: call.line,
+                callee_module: call.callee_module,
+                callee_function: call.callee_function,
+                callee_arity: call.callee_arity,
+                message: call.message,
+            };
+            (call.caller_module, entry, call.file)
+        });
+
+        Ok(ModuleCollectionResult {
+            module_pattern: self.module.unwrap_or_else(|| "*".to_string()),
+            function_pattern: None,
+            kind_filter: None,
+            name_filter: None,
+            total_items,
+            items,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CheckFailed, CommandRunner, CommonArgs};
+    use crate::output::OutputFormat;
+
+    const DEPRECATED_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Legacy": {
+                "fetch/1:3": {"name": "fetch", "arity": 1, "file": "lib/legacy.ex", "kind": "def", "line": 3,
+                    "start_line": 3, "end_line": 5, "deprecated": "Use MyApp.Accounts.get_user/1"}
+            },
+            "MyApp.Web": {
+                "show/2:4": {"name": "show", "arity": 2, "file": "lib/web.ex", "kind": "def", "line": 4, "start_line": 4, "end_line": 12}
+            }
+        },
+        "calls": [
+            {"caller": {"module": "MyApp.Web", "function": "show", "file": "lib/web.ex", "line": 8, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Legacy", "function": "fetch", "arity": 1}}
+        ]
+    }"#;
+
+    fn cmd(module: Option<&str>, fail: bool) -> DeprecatedUsageCmd {
+        DeprecatedUsageCmd {
+            module: module.map(str::to_string),
+            fail,
+            common: CommonArgs {
+                project: "default".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_deprecated_usage_groups_by_caller_module() {
+        let db = db::test_utils::setup_test_db(DEPRECATED_JSON, "default");
+        let result = cmd(None, false).execute(&db, &QuerySettings::default()).expect("Deprecated usage should succeed");
+
+        assert_eq!(result.total_items, 1);
+        assert_eq!(result.items[0].name, "MyApp.Web");
+        assert_eq!(result.items[0].file, "lib/web.ex");
+        assert_eq!(result.items[0].entries[0].caller_function, "show");
+        assert_eq!(result.items[0].entries[0].message, "Use MyApp.Accounts.get_user/1");
+    }
+
+    #[test]
+    fn test_deprecated_usage_fail_only_with_findings() {
+        let db = db::test_utils::setup_test_db(DEPRECATED_JSON, "default");
+
+        let err = cmd(None, true).run(&db, OutputFormat::Table, &QuerySettings::default()).unwrap_err();
+        assert!(err.downcast::<CheckFailed>().is_ok());
+
+        assert!(cmd(Some("MyApp.Legacy"), true).run(&db, OutputFormat::Table, &QuerySettings::default()).is_ok());
+    }
+}
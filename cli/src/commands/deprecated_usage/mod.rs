@@ -0,0 +1,50 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CheckFailed, CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// List call sites of deprecated functions, grouped by caller module
+///
+/// A function is deprecated when the extractor emitted its @deprecated
+/// message. With --fail the command exits with a nonzero status when any
+/// call site is found, so CI can keep new code off deprecated APIs.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search deprecated-usage                      # All calls to deprecated functions
+  code_search deprecated-usage MyApp.Web            # Only calls made from MyApp.Web
+  code_search deprecated-usage -r 'MyApp\\.Web.*'    # Calls made from matching modules
+  code_search deprecated-usage --fail               # Exit nonzero when any call is found (CI gate)
+  code_search deprecated-usage --fail -o github     # CI gate with GitHub annotations
+")]
+pub struct DeprecatedUsageCmd {
+    /// Caller module filter (exact match, or pattern with --regex)
+    pub module: Option<String>,
+
+    /// Exit with a nonzero status when any deprecated call is found
+    #[arg(long)]
+    pub fail: bool,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for DeprecatedUsageCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let fail = self.fail;
+        let result = self.execute(db, settings)?;
+        let report = result.format(format);
+        if fail && result.total_items > 0 {
+            Err(Box::new(CheckFailed { report }))
+        } else {
+            Ok(report)
+        }
+    }
+}
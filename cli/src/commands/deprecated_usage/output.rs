@@ -0,0 +1,99 @@
+//! Output formatting for deprecated-usage command results.
+
+use super::execute::DeprecatedUsageEntry;
+use crate::output::{Annotation, TableFormatter};
+use db::types::ModuleCollectionResult;
+
+impl TableFormatter for ModuleCollectionResult<DeprecatedUsageEntry> {
+    type Entry = DeprecatedUsageEntry;
+
+    fn format_header(&self) -> String {
+        if self.module_pattern == "*" {
+            "Deprecated usage".to_string()
+        } else {
+            format!("Deprecated usage (module: {})", self.module_pattern)
+        }
+    }
+
+    fn format_empty_message(&self) -> String {
+        "No calls to deprecated functions found.".to_string()
+    }
+
+    fn format_summary(&self, total: usize, module_count: usize) -> String {
+        format!("Found {} call(s) to deprecated functions in {} module(s):", total, module_count)
+    }
+
+    fn format_module_header(&self, module_name: &str, module_file: &str) -> String {
+        format!("{} ({}):", module_name, module_file)
+    }
+
+    fn format_entry(&self, entry: &DeprecatedUsageEntry, _module: &str, _file: &str) -> String {
+        format!(
+            "{} L{} -> {}.{}/{}",
+            entry.caller_function, entry.line, entry.callee_module, entry.callee_function, entry.callee_arity
+        )
+    }
+
+    fn format_entry_details(&self, entry: &DeprecatedUsageEntry, _module: &str, _file: &str) -> Vec<String> {
+        vec![entry.message.clone()]
+    }
+
+    fn format_annotation(&self, entry: &DeprecatedUsageEntry, _module: &str, file: &str) -> Option<Annotation> {
+        Some(Annotation::warning(
+            file,
+            entry.line,
+            "Deprecated call",
+            format!(
+                "{}.{}/{} is deprecated: {}",
+                entry.callee_module, entry.callee_function, entry.callee_arity, entry.message
+            ),
+        ))
+    }
+
+    fn annotates(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Outputable;
+    use db::types::ModuleGroup;
+
+    #[test]
+    fn test_to_table() {
+        let result = ModuleCollectionResult {
+            module_pattern: "*".to_string(),
+            function_pattern: None,
+            kind_filter: None,
+            name_filter: None,
+            total_items: 1,
+            items: vec![ModuleGroup {
+                name: "MyApp.Web".to_string(),
+                file: "lib/web.ex".to_string(),
+                entries: vec![DeprecatedUsageEntry {
+                    caller_function: "show".to_string(),
+                    line: 8,
+                    callee_module: "MyApp.Legacy".to_string(),
+                    callee_function: "fetch".to_string(),
+                    callee_arity: 1,
+                    message: "Use MyApp.Accounts.get_user/1".to_string(),
+                }],
+                function_count: None,
+            }],
+        };
+
+        assert_eq!(
+            result.to_table(),
+            "\
+Deprecated usage
+
+Found 1 call(s) to deprecated functions in 1 module(s):
+
+MyApp.Web (lib/web.ex):
+  show L8 -> MyApp.Legacy.fetch/1
+    Use MyApp.Accounts.get_user/1"
+        );
+    }
+}
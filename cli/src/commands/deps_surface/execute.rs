@@ -0,0 +1,267 @@
+//! External library surface grouped by library, function and calling module.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::DepsSurfaceCmd;
+use crate::commands::Execute;
+use db::queries::external_calls::find_external_calls;
+use db::query_builders::QuerySettings;
+
+/// Elixir standard library top-level modules (skipped without --include-stdlib)
+const ELIXIR_STDLIB: &[&str] = &[
+    "Access", "Agent", "Application", "Atom", "Base", "Bitwise", "Calendar", "Code", "Collectable",
+    "Config", "Date", "DateTime", "Duration", "DynamicSupervisor", "EEx", "Enum", "Enumerable", "ExUnit",
+    "Exception", "File", "Float", "Function", "GenServer", "IO", "Inspect", "Integer", "JSON", "Kernel",
+    "Keyword", "List", "Logger", "Macro", "Map", "MapSet", "Mix", "Module", "NaiveDateTime", "Node",
+    "OptionParser", "PartitionSupervisor", "Path", "Port", "Process", "Protocol", "Range", "Record",
+    "Regex", "Registry", "Stream", "String", "StringIO", "Supervisor", "System", "Task", "Time", "Tuple",
+    "URI", "Version",
+];
+
+/// Erlang/OTP modules shipped with the runtime (skipped without --include-stdlib)
+const ERLANG_STDLIB: &[&str] = &[
+    "application", "array", "atomics", "base64", "binary", "calendar", "code", "counters", "crypto",
+    "dets", "digraph", "erlang", "ets", "file", "filename", "gen_server", "gen_statem", "inet", "io",
+    "io_lib", "lists", "logger", "maps", "math", "os", "persistent_term", "proplists", "queue", "rand",
+    "re", "sets", "ssl", "string", "supervisor", "sys", "timer", "unicode", "zlib",
+];
+
+/// An external function and the project modules calling it
+#[derive(Debug, Clone, Serialize)]
+pub struct UsedFunction {
+    pub module: String,
+    pub function: String,
+    pub arity: i64,
+    /// Call sites
+    pub calls: usize,
+    pub callers: Vec<String>,
+}
+
+/// Everything the project uses from one library
+#[derive(Debug, Clone, Serialize)]
+pub struct LibrarySurface {
+    pub library: String,
+    /// Call sites into the library
+    pub calls: usize,
+    /// Project modules calling the library
+    pub callers: Vec<String>,
+    /// Functions used, most called first
+    pub functions: Vec<UsedFunction>,
+}
+
+/// Result of the deps-surface command
+#[derive(Debug, Serialize)]
+pub struct DepsSurfaceResult {
+    pub total_libraries: usize,
+    pub total_calls: usize,
+    /// Libraries ordered by call sites, most called first
+    pub libraries: Vec<LibrarySurface>,
+}
+
+/// Callers per call site of each (module, function, arity) of a library
+type LibraryCalls = BTreeMap<(String, String, i64), Vec<String>>;
+
+impl Execute for DepsSurfaceCmd {
+    type Output = DepsSurfaceResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let calls = find_external_calls(db, &self.common.project, self.module.as_deref(), self.common.regex, settings)?;
+
+        let mut by_library: BTreeMap<String, LibraryCalls> = BTreeMap::new();
+        for call in calls {
+            if !self.include_stdlib && is_stdlib(&call.callee_module) {
+                continue;
+            }
+            let library = library_of(&call.callee_module, self.depth as usize);
+            if self.library.as_ref().is_some_and(|wanted| wanted != &library) {
+                continue;
+            }
+            by_library
+                .entry(library)
+                .or_default()
+                .entry((call.callee_module, call.callee_function, call.callee_arity))
+                .or_default()
+                .push(call.caller_module);
+        }
+
+        let mut libraries: Vec<LibrarySurface> = by_library
+            .into_iter()
+            .map(|(library, functions)| {
+                let mut library_callers = BTreeSet::new();
+                let mut functions: Vec<UsedFunction> = functions
+                    .into_iter()
+                    .map(|((module, function, arity), callers)| {
+                        let calls = callers.len();
+                        let callers: BTreeSet<String> = callers.into_iter().collect();
+                        library_callers.extend(callers.iter().cloned());
+                        UsedFunction {
+                            module,
+                            function,
+                            arity,
+                            calls,
+                            callers: callers.into_iter().collect(),
+                        }
+                    })
+                    .collect();
+                functions.sort_by_key(|f| Reverse(f.calls));
+
+                LibrarySurface {
+                    library,
+                    calls: functions.iter().map(|f| f.calls).sum(),
+                    callers: library_callers.into_iter().collect(),
+                    functions,
+                }
+            })
+            .collect();
+        libraries.sort_by_key(|l| Reverse(l.calls));
+
+        let total_libraries = libraries.len();
+        let total_calls = libraries.iter().map(|l| l.calls).sum();
+        libraries.truncate(self.common.limit as usize);
+
+        Ok(DepsSurfaceResult {
+            total_libraries,
+            total_calls,
+            libraries,
+        })
+    }
+}
+
+/// Library name of a module: its first `depth` namespace segments.
+///
+/// Erlang modules (`:crypto` or `crypto`) are their own library, named with a
+/// leading colon.
+fn library_of(module: &str, depth: usize) -> String {
+    if let Some(erlang) = erlang_module(module) {
+        return format!(":{}", erlang);
+    }
+    module.split('.').take(depth).collect::<Vec<_>>().join(".")
+}
+
+fn is_stdlib(module: &str) -> bool {
+    match erlang_module(module) {
+        Some(erlang) => ERLANG_STDLIB.contains(&erlang),
+        None => ELIXIR_STDLIB.contains(&module.split('.').next().unwrap_or(module)),
+    }
+}
+
+/// The bare name of an Erlang module, None for Elixir modules
+fn erlang_module(module: &str) -> Option<&str> {
+    let name = module.strip_prefix(':').unwrap_or(module);
+    if module.starts_with(':') || name.starts_with(|c: char| c.is_ascii_lowercase()) {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+
+    fn deps_db() -> db::DbInstance {
+        db::fixtures::FixtureGraph::new()
+            .module("MyApp.Accounts")
+            .function("create", 1)
+            .module("MyApp.Web.UserLive")
+            .function("mount", 3)
+            .calls("MyApp.Accounts", "create", 1, "Ecto.Changeset", "cast", 3)
+            .calls("MyApp.Accounts", "create", 1, "Ecto.Changeset", "cast", 3)
+            .calls("MyApp.Web.UserLive", "mount", 3, "Ecto.Changeset", "cast", 3)
+            .calls("MyApp.Accounts", "create", 1, "Ecto.Repo", "insert", 2)
+            .calls("MyApp.Web.UserLive", "mount", 3, "Phoenix.LiveView", "assign", 3)
+            .calls("MyApp.Web.UserLive", "mount", 3, "Phoenix.PubSub", "subscribe", 2)
+            .calls("MyApp.Accounts", "create", 1, "Enum", "map", 2)
+            .calls("MyApp.Accounts", "create", 1, ":crypto", "strong_rand_bytes", 1)
+            .db("test_project")
+    }
+
+    fn deps_surface_cmd(library: Option<&str>, depth: u32, include_stdlib: bool) -> DepsSurfaceCmd {
+        DepsSurfaceCmd {
+            library: library.map(String::from),
+            module: None,
+            depth,
+            include_stdlib,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_deps_surface_groups_libraries() {
+        let db = deps_db();
+        let result = deps_surface_cmd(None, 1, false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        // Enum and :crypto are standard library
+        assert_eq!(result.total_libraries, 2);
+        assert_eq!(result.total_calls, 6);
+
+        let ecto = &result.libraries[0];
+        assert_eq!(ecto.library, "Ecto");
+        assert_eq!(ecto.calls, 4);
+        assert_eq!(ecto.callers, ["MyApp.Accounts", "MyApp.Web.UserLive"]);
+        assert_eq!(ecto.functions[0].function, "cast");
+        assert_eq!(ecto.functions[0].calls, 3);
+        assert_eq!(ecto.functions[0].callers, ["MyApp.Accounts", "MyApp.Web.UserLive"]);
+
+        assert_eq!(result.libraries[1].library, "Phoenix");
+        assert_eq!(result.libraries[1].functions.len(), 2);
+    }
+
+    #[test]
+    fn test_deps_surface_depth_and_library() {
+        let db = deps_db();
+        let result = deps_surface_cmd(Some("Phoenix.PubSub"), 2, false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_libraries, 1);
+        assert_eq!(result.libraries[0].library, "Phoenix.PubSub");
+        assert_eq!(result.libraries[0].callers, ["MyApp.Web.UserLive"]);
+    }
+
+    #[test]
+    fn test_deps_surface_include_stdlib() {
+        let db = deps_db();
+        let result = deps_surface_cmd(None, 1, true)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        let libraries: BTreeSet<&str> = result.libraries.iter().map(|l| l.library.as_str()).collect();
+        assert_eq!(libraries, BTreeSet::from([":crypto", "Ecto", "Enum", "Phoenix"]));
+    }
+
+    #[test]
+    fn test_deps_surface_caller_filter() {
+        let db = deps_db();
+        let mut cmd = deps_surface_cmd(None, 1, false);
+        cmd.module = Some("MyApp.Web.UserLive".to_string());
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.total_calls, 3);
+        assert_eq!(result.libraries[0].library, "Phoenix");
+    }
+
+    #[test]
+    fn test_library_of() {
+        assert_eq!(library_of("Ecto.Changeset", 1), "Ecto");
+        assert_eq!(library_of("Phoenix.LiveView.JS", 2), "Phoenix.LiveView");
+        assert_eq!(library_of(":crypto", 1), ":crypto");
+        assert_eq!(library_of("jason_native", 2), ":jason_native");
+        assert!(is_stdlib("Enum"));
+        assert!(is_stdlib(":ets"));
+        assert!(!is_stdlib("Ecto.Repo"));
+        assert!(!is_stdlib(":jsx"));
+    }
+}
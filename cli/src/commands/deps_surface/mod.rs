@@ -0,0 +1,54 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Show which external library functions the project calls, and from where
+///
+/// A call is external when its callee module is not defined in the project.
+/// Callees are grouped into libraries by their leading namespace (`Ecto`,
+/// `Phoenix`, `:crypto`), and each library lists the functions used with the
+/// project modules calling them: the blast radius of upgrading or replacing it.
+/// Elixir and Erlang standard library calls are left out unless asked for.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search deps-surface                        # Every library the project calls into
+  code_search deps-surface Ecto                   # Only the Ecto surface
+  code_search deps-surface Phoenix --depth 2      # Split Phoenix.LiveView, Phoenix.PubSub, ...
+  code_search deps-surface -m 'MyApp\\.Web\\.' -r  # Libraries called from the MyApp.Web namespace
+  code_search deps-surface --include-stdlib       # Also Enum, Map, :ets, ...
+")]
+pub struct DepsSurfaceCmd {
+    /// Only show this library (exact name as reported, e.g. Ecto or :crypto)
+    pub library: Option<String>,
+
+    /// Only count calls from modules matching this pattern (exact, regex with --regex)
+    #[arg(short, long)]
+    pub module: Option<String>,
+
+    /// Namespace segments naming a library (1 groups Phoenix.* together)
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..=5))]
+    pub depth: u32,
+
+    /// Include Elixir and Erlang standard library modules
+    #[arg(long, default_value_t = false)]
+    pub include_stdlib: bool,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for DepsSurfaceCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,94 @@
+//! Output formatting for deps-surface command results.
+
+use super::execute::DepsSurfaceResult;
+use crate::output::Outputable;
+
+impl Outputable for DepsSurfaceResult {
+    fn to_table(&self) -> String {
+        let mut output = format!(
+            "Dependency surface: {} librar(ies), {} call(s)\n",
+            self.total_libraries, self.total_calls
+        );
+
+        if self.libraries.is_empty() {
+            output.push_str("\nNo external calls found.\n");
+            return output;
+        }
+
+        for library in &self.libraries {
+            output.push_str(&format!(
+                "\n{}: {} call(s) to {} function(s) from {} module(s)\n",
+                library.library,
+                library.calls,
+                library.functions.len(),
+                library.callers.len()
+            ));
+            for function in &library.functions {
+                output.push_str(&format!(
+                    "  {}.{}/{} ({}): {}\n",
+                    function.module,
+                    function.function,
+                    function.arity,
+                    function.calls,
+                    function.callers.join(", ")
+                ));
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::deps_surface::execute::{LibrarySurface, UsedFunction};
+
+    fn result() -> DepsSurfaceResult {
+        DepsSurfaceResult {
+            total_libraries: 1,
+            total_calls: 4,
+            libraries: vec![LibrarySurface {
+                library: "Ecto".to_string(),
+                calls: 4,
+                callers: vec!["MyApp.Accounts".to_string(), "MyApp.Web.UserLive".to_string()],
+                functions: vec![
+                    UsedFunction {
+                        module: "Ecto.Changeset".to_string(),
+                        function: "cast".to_string(),
+                        arity: 3,
+                        calls: 3,
+                        callers: vec!["MyApp.Accounts".to_string(), "MyApp.Web.UserLive".to_string()],
+                    },
+                    UsedFunction {
+                        module: "Ecto.Repo".to_string(),
+                        function: "insert".to_string(),
+                        arity: 2,
+                        calls: 1,
+                        callers: vec!["MyApp.Accounts".to_string()],
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_deps_surface_table() {
+        let expected = [
+            "Dependency surface: 1 librar(ies), 4 call(s)",
+            "",
+            "Ecto: 4 call(s) to 2 function(s) from 2 module(s)",
+            "  Ecto.Changeset.cast/3 (3): MyApp.Accounts, MyApp.Web.UserLive",
+            "  Ecto.Repo.insert/2 (1): MyApp.Accounts",
+            "",
+        ];
+        assert_eq!(result().to_table(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_deps_surface_table_empty() {
+        let mut result = result();
+        result.libraries.clear();
+        assert!(result.to_table().ends_with("\nNo external calls found.\n"));
+    }
+}
@@ -90,12 +90,17 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             "calls-to",
             "Find callers of a given function",
             CommandCategory::Query,
-            "Finds all functions that call a specific function. Use this to answer: 'Who calls this function?'",
+            "Finds all functions that call a specific function. Use this to answer: 'Who calls this function?' \
+             A caller calling the function from several call sites is marked with the count (×3). \
+             --kind keeps remote, local, pipe or capture calls; captures (&mod.fun/1) pass the function as a callback.",
             "code_search calls-to <MODULE> [FUNCTION] [ARITY] [OPTIONS]",
         )
         .with_examples(vec![
             Example::new("Find all callers of MyApp.Repo.get/2", "code_search calls-to MyApp.Repo get 2"),
             Example::new("Find callers of any function in a module", "code_search calls-to MyApp.Repo"),
+            Example::new("Count calls to a module per calling module", "code_search calls-to MyApp.Repo --group-by caller-module"),
+            Example::new("Find where a function is passed as a callback", "code_search calls-to MyApp.Mailer deliver --kind capture"),
+            Example::new("Find callers of the functions listed by another query", "code_search unused -o csv --fields module,name,arity | code_search calls-to --stdin"),
         ])
         .with_related(vec!["calls-from", "trace", "path"]),
 
@@ -103,12 +108,16 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             "calls-from",
             "Find what a function calls",
             CommandCategory::Query,
-            "Finds all functions that are called by a specific function. Use this to answer: 'What does this function call?'",
+            "Finds all functions that are called by a specific function. Use this to answer: 'What does this function call?' \
+             A callee called from several call sites is marked with the count (×3). \
+             --kind keeps remote, local, pipe or capture calls.",
             "code_search calls-from <MODULE> [FUNCTION] [ARITY] [OPTIONS]",
         )
         .with_examples(vec![
             Example::new("Find all functions called by MyApp.Repo.get/2", "code_search calls-from MyApp.Repo get 2"),
             Example::new("Find what a module calls", "code_search calls-from MyApp.Accounts"),
+            Example::new("Count a module's calls per file", "code_search calls-from MyApp.Accounts --group-by file"),
+            Example::new("Find a module's calls into other modules", "code_search calls-from MyApp.Accounts --kind remote"),
         ])
         .with_related(vec!["calls-to", "trace", "path"]),
 
@@ -116,12 +125,19 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             "trace",
             "Forward call trace from a function",
             CommandCategory::Query,
-            "Traces call chains forward from a starting function. Shows the full path of calls that can be reached from a given function.",
+            "Traces call chains forward from a starting function. Shows the full path of calls that can be reached from a given function. \
+             Use --engine memory to answer repeated traversals from an in-memory adjacency index. \
+             --include-messages also follows GenServer calls/casts and PubSub broadcasts. \
+             --min-weight N only follows calls a caller makes from at least N call sites, keeping the strongest couplings. \
+             --certain-only skips calls in conditional branches and comprehensions, leaving the chains that run on every path.",
             "code_search trace <MODULE> <FUNCTION> [OPTIONS]",
         )
         .with_examples(vec![
             Example::new("Trace all calls from a function", "code_search trace MyApp.API create_user"),
             Example::new("Limit trace depth to 3 levels", "code_search trace MyApp.API create_user --depth 3"),
+            Example::new("Follow work handed to other processes", "code_search trace MyApp.API create_user --include-messages"),
+            Example::new("Follow only calls made from 3 or more call sites", "code_search trace MyApp.API create_user --min-weight 3"),
+            Example::new("Follow only calls made on every path", "code_search trace MyApp.API create_user --certain-only"),
         ])
         .with_related(vec!["calls-from", "reverse-trace", "path"]),
 
@@ -129,27 +145,115 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             "reverse-trace",
             "Backward call trace to a function",
             CommandCategory::Query,
-            "Traces call chains backward to a target function. Shows all code paths that can lead to a given function.",
+            "Traces call chains backward to a target function. Shows all code paths that can lead to a given function. \
+             With --engine memory the call edges are loaded into memory once and reused by later traversals. \
+             --certain-only skips calls in conditional branches and comprehensions, so the remaining callers always reach the target.",
             "code_search reverse-trace <MODULE> <FUNCTION> [OPTIONS]",
         )
         .with_examples(vec![
             Example::new("Find all paths leading to a function", "code_search reverse-trace MyApp.API validate_token"),
             Example::new("Limit trace depth to 2 levels", "code_search reverse-trace MyApp.API validate_token --depth 2"),
+            Example::new("Find callers that always reach a function", "code_search reverse-trace MyApp.API validate_token --certain-only"),
         ])
         .with_related(vec!["calls-to", "trace", "path"]),
 
+        CommandDescription::new(
+            "test-surface",
+            "Map tests to the functions they exercise",
+            CommandCategory::Query,
+            "For a module or function, lists the test functions that call it directly or through other functions, \
+             grouped by test module. With --file, lists the production functions the functions of a test file reach. \
+             Tests are the modules imported from test/ directories, _test.exs files or the import.test_paths globs of \
+             the config. Each function is reported once, at its shortest call depth; --depth bounds the traversal.",
+            "code_search test-surface <MODULE> [FUNCTION] [OPTIONS] | code_search test-surface --file <FILE>",
+        )
+        .with_examples(vec![
+            Example::new("Tests reaching a function", "code_search test-surface MyApp.Accounts register"),
+            Example::new("Tests reaching any function of a module", "code_search test-surface MyApp.Accounts"),
+            Example::new("What a test file exercises", "code_search test-surface --file test/my_app/accounts_test.exs"),
+        ])
+        .with_related(vec!["reverse-trace", "trace", "unused"]),
+
         CommandDescription::new(
             "path",
             "Find a call path between two functions",
             CommandCategory::Query,
-            "Finds one or more call paths connecting two functions. Useful for understanding how code flows from a source to a target.",
-            "code_search path --from-module <MODULE> --from-function <FUNCTION> --to-module <MODULE> --to-function <FUNCTION>",
+            "Finds one or more call paths connecting two functions. Useful for understanding how code flows from a source to a target. \
+             Use --strategy shortest for the single cheapest path or --strategy k-shortest for up to --limit paths ranked by cost; \
+             add --weighted to prefer calls with many call sites, or --min-weight N to skip calls made from fewer than N call sites. For deep searches, --strategy bidirectional searches from both ends and meets in the middle. \
+             --engine memory answers edge lookups from an in-memory adjacency index. \
+             --include-messages lets paths cross processes through GenServer and PubSub message edges.",
+            "code_search path --from-module <MODULE> --from-function <FUNCTION> --to-module <MODULE> --to-function <FUNCTION> [OPTIONS]",
         )
         .with_examples(vec![
             Example::new("Find call path between two functions", "code_search path --from-module MyApp.API --from-function create_user --to-module MyApp.DB --to-function insert"),
+            Example::new("Three shortest paths", "code_search path --from-module MyApp.API --from-function create_user --to-module MyApp.DB --to-function insert --strategy k-shortest -l 3"),
+            Example::new("Deep search from both ends", "code_search path --from-module MyApp.API --from-function create_user --to-module MyApp.DB --to-function insert --strategy bidirectional --depth 20"),
         ])
         .with_related(vec!["trace", "reverse-trace", "calls-from"]),
 
+        CommandDescription::new(
+            "rename-impact",
+            "List every definition, call site, spec and type a rename has to touch",
+            CommandCategory::Query,
+            "Collects the edits a rename needs, sorted by file and line. With --function: every clause \
+             definition, every call site (local calls included) and the function's @spec; --arity narrows it \
+             to one arity. Without --function the module is renamed: its definition, every remote call into \
+             it, and the specs and types of any module mentioning it. With -o json each reference is an object \
+             with kind, file, line, column, module, function and text, ready for a patch planner.",
+            "code_search rename-impact --module <MODULE> [--function <NAME>] [--arity <N>]",
+        )
+        .with_examples(vec![
+            Example::new("Rename a module", "code_search rename-impact --module MyApp.Accounts"),
+            Example::new(
+                "Rename one function",
+                "code_search rename-impact --module MyApp.Accounts --function get_user --arity 1",
+            ),
+            Example::new("Edits as JSON", "code_search rename-impact --module MyApp.Accounts --function get_user -o json"),
+        ])
+        .with_related(vec!["calls-to", "location", "struct-usage"]),
+
+        CommandDescription::new(
+            "explore",
+            "Bundle a module or function's definition, top callers and top callees",
+            CommandCategory::Query,
+            "Answers the first questions about a seed in one call, for LLM agents: where it is defined and its \
+             @spec (or recorded argument and return types), which functions call it and which it calls. Callers \
+             and callees are grouped by function, ranked by call sites and carry the file:line of the first one. \
+             Without --function every function of the module is a definition. --budget caps the estimated size \
+             in tokens (4 characters each): the first definition is always shown, then callers, callees and \
+             further definitions are added by rank while they fit, and the number left out is reported.",
+            "code_search explore --module <MODULE> [--function <NAME>] [--arity <N>] [--budget <TOKENS>]",
+        )
+        .with_examples(vec![
+            Example::new("Explore a function", "code_search explore --module MyApp.Accounts --function get_user"),
+            Example::new("Explore a module", "code_search explore --module MyApp.Accounts"),
+            Example::new(
+                "Tight budget as JSON",
+                "code_search explore --module MyApp.Accounts --function get_user --budget 200 -o json",
+            ),
+        ])
+        .with_related(vec!["function", "calls-to", "calls-from", "location"]),
+
+        CommandDescription::new(
+            "context",
+            "Rank the functions and files relevant to a task, for an LLM context window",
+            CommandCategory::Query,
+            "Assembles a ranked code map for a task. The anchors are the --seed functions (score 1) and the \
+             functions whose module segments or name words resemble the words of the task (score = share of \
+             task words matched). Relevance then spreads along call edges in both directions up to --depth: a \
+             function d calls away from an anchor scores the anchor's score / (1 + d). Each function is listed \
+             with its file, line range, score and the anchor it was reached from, and files are ranked by their \
+             best function. The default output is Markdown for a prompt; -o json gives the same data.",
+            "code_search context [TASK] [--seed <MODULE.FUNCTION>]... [--depth <N>] [--limit <N>]",
+        )
+        .with_examples(vec![
+            Example::new("From a task description", "code_search context 'notify users after login'"),
+            Example::new("From a seed function", "code_search context --seed MyApp.Accounts.get_user"),
+            Example::new("Nearby only, as JSON", "code_search context 'user accounts' --depth 1 -l 10 -o json"),
+        ])
+        .with_related(vec!["explore", "search", "trace"]),
+
         // Analysis Commands
         CommandDescription::new(
             "hotspots",
@@ -157,7 +261,9 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             CommandCategory::Analysis,
             "Identifies functions with the most incoming or outgoing calls. \
              Use -k incoming (default) for most-called functions, -k outgoing for functions that call many others, \
-             -k total for highest combined connectivity, or -k ratio for boundary functions.",
+             -k total for highest combined connectivity, or -k ratio for boundary functions. \
+             --min-depth-from-entry N keeps functions at least N calls away from the nearest entry point \
+             (see entry-points), separating core utilities from shallow dispatchers.",
             "code_search hotspots [MODULE] [OPTIONS]",
         )
         .with_examples(vec![
@@ -166,16 +272,57 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             Example::new("Highest total connections", "code_search hotspots -k total"),
             Example::new("Boundary functions (high ratio)", "code_search hotspots -k ratio"),
             Example::new("Filter to namespace", "code_search hotspots MyApp -l 20"),
+            Example::new("Most called functions deep below entry points", "code_search hotspots --min-depth-from-entry 3"),
         ])
         .with_related(vec!["god-modules", "boundaries", "complexity"]),
 
+        CommandDescription::new(
+            "centrality",
+            "Rank functions by PageRank, betweenness or degree centrality",
+            CommandCategory::Analysis,
+            "Computes graph centrality over the complete function-level call graph to find architecturally \
+             critical functions that simple fan-in counts miss. Use -k pagerank (default) for importance \
+             propagated along calls, -k betweenness for functions bridging many call paths, -k degree \
+             for normalized fan-in plus fan-out, or -k reach for the share of functions transitively called. \
+             The module pattern filters reported functions only. On very large graphs, --approximate \
+             samples --samples N sources for betweenness and uses HyperLogLog sketches for reach.",
+            "code_search centrality [MODULE] [-k pagerank|betweenness|degree|reach] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Top functions by PageRank", "code_search centrality"),
+            Example::new("Functions bridging many call paths", "code_search centrality -k betweenness"),
+            Example::new("Only report one namespace", "code_search centrality MyApp.Accounts -l 10"),
+            Example::new("Functions with the largest call trees", "code_search centrality -k reach"),
+            Example::new("Sampled betweenness for large graphs", "code_search centrality -k betweenness --approximate"),
+        ])
+        .with_related(vec!["hotspots", "boundaries", "path"]),
+
+        CommandDescription::new(
+            "fan-in-fan-out",
+            "Export per-function fan-in, fan-out, size and complexity for scatter plots",
+            CommandCategory::Analysis,
+            "Emits one row per function with its fan-in, fan-out, lines of code and cyclomatic complexity \
+             (summed over clauses and arities), plus a criticality score: fan-in times complexity, each \
+             relative to the highest value, from 0 to 1. Rows are ranked by criticality. The medians of \
+             fan-in and fan-out over all matching functions split the plot into quadrants. \
+             Use -o csv for a file with exactly these columns.",
+            "code_search fan-in-fan-out [MODULE] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Most critical functions", "code_search fan-in-fan-out"),
+            Example::new("Scatter plot data as CSV", "code_search -o csv fan-in-fan-out -l 1000"),
+            Example::new("Only one namespace", "code_search fan-in-fan-out MyApp.Accounts"),
+        ])
+        .with_related(vec!["hotspots", "complexity", "centrality"]),
+
         CommandDescription::new(
             "unused",
             "Find functions that are never called",
             CommandCategory::Analysis,
             "Identifies functions with no incoming calls. Use -p to find dead code (unused private functions) \
              or -P to find entry points (public functions not called internally). Use -x to exclude \
-             compiler-generated functions like __struct__, __info__, etc.",
+             compiler-generated functions like __struct__, __info__, etc. With --exclude-tests, test modules \
+             are skipped and calls from tests do not count, so functions only tests call are listed too.",
             "code_search unused [MODULE] [OPTIONS]",
         )
         .with_examples(vec![
@@ -183,9 +330,29 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             Example::new("Filter to a specific module", "code_search unused MyApp.Utils"),
             Example::new("Find dead code (unused private)", "code_search unused -p"),
             Example::new("Find entry points (unused public)", "code_search unused -Px"),
+            Example::new("Only functions taking 3+ arguments", "code_search unused --where 'arity > 2'"),
+            Example::new("Who last changed each unused function", "code_search unused --blame"),
+            Example::new("Functions only called from tests", "code_search unused --exclude-tests"),
         ])
         .with_related(vec!["hotspots", "duplicates", "large-functions"]),
 
+        CommandDescription::new(
+            "entry-points",
+            "Find framework entry points (controllers, LiveViews, OTP callbacks, workers, tasks)",
+            CommandCategory::Analysis,
+            "Matches public functions against entry point rules: Phoenix controller actions and LiveView \
+             callbacks, GenServer and Supervisor callbacks, Oban workers and Mix tasks. Extra rules can be \
+             added under entry_points in the config file. Use --roots-only to keep entry points with no callers \
+             in the project.",
+            "code_search entry-points [MODULE] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Find all entry points", "code_search entry-points"),
+            Example::new("Only web modules", "code_search entry-points MyAppWeb"),
+            Example::new("Only uncalled entry points", "code_search entry-points --roots-only"),
+        ])
+        .with_related(vec!["unused", "reverse-trace", "trace"]),
+
         CommandDescription::new(
             "god-modules",
             "Find god modules - modules with high function count, LoC, and connectivity",
@@ -201,7 +368,28 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             Example::new("With minimum 500 LoC", "code_search god-modules --min-loc 500"),
             Example::new("With minimum 30 functions", "code_search god-modules --min-functions 30"),
         ])
-        .with_related(vec!["hotspots", "boundaries", "complexity"]),
+        .with_related(vec!["hotspots", "boundaries", "complexity", "extract-module"]),
+
+        CommandDescription::new(
+            "extract-module",
+            "Show what moving functions out of a module into a new one would change",
+            CommandCategory::Analysis,
+            "Evaluates a module split before doing it, e.g. to break up a god module. Given the functions to \
+             move (--functions, comma-separated), lists the calls between moved and remaining functions that \
+             would cross the new boundary, the external callers that must switch to the new module, the modules \
+             the new module would depend on, and the cohesion (internal / all calls) of the module before and \
+             after, and of the new module. Without --functions the module's functions are clustered by their \
+             internal calls (Louvain) and the largest cluster apart from the main one is proposed.",
+            "code_search extract-module <MODULE> [-f FUNCTIONS] [--into NAME]",
+        )
+        .with_examples(vec![
+            Example::new("Suggest a split", "code_search extract-module MyApp.Accounts"),
+            Example::new(
+                "Evaluate a chosen set",
+                "code_search extract-module MyApp.Accounts -f hash_password,verify_password --into MyApp.Accounts.Password",
+            ),
+        ])
+        .with_related(vec!["god-modules", "communities", "rename-impact"]),
 
         CommandDescription::new(
             "boundaries",
@@ -219,6 +407,45 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
         ])
         .with_related(vec!["god-modules", "hotspots", "depends-on"]),
 
+        CommandDescription::new(
+            "suggest-facade",
+            "Suggest the public interface of a namespace from how other modules use it",
+            CommandCategory::Analysis,
+            "Takes a namespace (a module and its nested modules, or a module regex with --regex) and splits its \
+             public functions (def/defmacro) by their callers. The facade is the minimal set called from modules \
+             outside the namespace, with its external callers. Functions only called from inside the namespace \
+             are flagged as unnecessarily public; those only called from their own module could be defp. \
+             Public functions with no callers at all are counted and left to unused. -o github annotates the \
+             unnecessarily public functions.",
+            "code_search suggest-facade <NAMESPACE> [-r] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Facade of a context", "code_search suggest-facade MyApp.Accounts"),
+            Example::new("Several modules as one namespace", "code_search suggest-facade '^MyApp\\.(Accounts|Auth)' -r"),
+        ])
+        .with_related(vec!["boundaries", "unused", "depended-by"]),
+
+        CommandDescription::new(
+            "coupling",
+            "Compute afferent/efferent coupling, instability and abstractness per module",
+            CommandCategory::Analysis,
+            "Computes package-level coupling metrics for each module, or for namespace clusters with --depth.\n\n\
+             Output columns:\n\
+             - Ca: afferent coupling, distinct modules calling into this one\n\
+             - Ce: efferent coupling, distinct modules this one calls\n\
+             - Instab: Ce / (Ca + Ce) — 0 = stable, 1 = unstable\n\
+             - Abstr: callbacks / (callbacks + public functions)\n\
+             - Dist: |Abstr + Instab - 1|, distance from the main sequence",
+            "code_search coupling [MODULE] [-b afferent|efferent|instability|abstractness|distance] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Rank modules by afferent coupling", "code_search coupling"),
+            Example::new("Modules with the most dependencies", "code_search coupling -b efferent"),
+            Example::new("Furthest from the main sequence", "code_search coupling -b distance"),
+            Example::new("Aggregate by namespace", "code_search coupling --depth 2"),
+        ])
+        .with_related(vec!["clusters", "boundaries", "god-modules"]),
+
         CommandDescription::new(
             "duplicates",
             "Find functions with identical or near-identical implementations",
@@ -236,14 +463,35 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             Example::new("Rank modules by duplication", "code_search duplicates --by-module"),
             Example::new("Exclude generated functions", "code_search duplicates --exclude-generated"),
         ])
-        .with_related(vec!["unused", "large-functions", "hotspots"]),
+        .with_related(vec!["unused", "large-functions", "hotspots", "similar-functions"]),
+
+        CommandDescription::new(
+            "similar-functions",
+            "Find the functions structurally most similar to a given function",
+            CommandCategory::Analysis,
+            "Ranks every other function of the project by the estimated Jaccard similarity of its AST shingles \
+             to the given function, from the MinHash signatures the extractor emits per clause (ast_minhash). \
+             Clause signatures are merged so multi-clause functions compare as a whole. Finds copy-pasted \
+             variants that were adapted afterwards and no longer hash identically for duplicates. \
+             --min-similarity sets the threshold (default 0.5); -l caps the number of results (default 10).",
+            "code_search similar-functions <MODULE> <FUNCTION> [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Top 10 lookalikes", "code_search similar-functions MyApp.Accounts get_user"),
+            Example::new("A specific arity", "code_search similar-functions MyApp.Accounts get_user -a 2"),
+            Example::new("Only close matches", "code_search similar-functions MyApp.Repo insert --min-similarity 0.8"),
+        ])
+        .with_related(vec!["duplicates", "large-functions"]),
 
         CommandDescription::new(
             "complexity",
             "Display complexity metrics for functions",
             CommandCategory::Analysis,
             "Shows cyclomatic complexity and nesting depth for functions. \
-             Use --min and --min-depth to filter by thresholds. Generated functions are excluded by default.",
+             Use --min and --min-depth to filter by thresholds. Use --metric abc|halstead|args to rank by \
+             ABC size, Halstead volume or argument count instead, with --min-abc, --min-halstead and \
+             --min-args as thresholds, or --cognitive to rank by cognitive complexity (--min then applies \
+             to it), which does not penalize pattern-matching clauses. Generated functions are excluded by default.",
             "code_search complexity [MODULE] [OPTIONS]",
         )
         .with_examples(vec![
@@ -251,6 +499,10 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             Example::new("Filter to a namespace", "code_search complexity MyApp.Accounts"),
             Example::new("Find highly complex functions", "code_search complexity --min 10"),
             Example::new("Find deeply nested functions", "code_search complexity --min-depth 3"),
+            Example::new("Rank by Halstead volume", "code_search complexity --metric halstead -l 10"),
+            Example::new("Find hard-to-read functions", "code_search complexity --cognitive --min 15"),
+            Example::new("Filter on any output column", "code_search complexity --where 'lines > 20 && module =~ \"Web\"'"),
+            Example::new("Who last changed each complex function", "code_search complexity --min 10 --blame"),
         ])
         .with_related(vec!["large-functions", "many-clauses", "hotspots"]),
 
@@ -267,6 +519,8 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             Example::new("Filter to a namespace", "code_search large-functions MyApp.Web"),
             Example::new("Find functions with 100+ lines", "code_search large-functions --min-lines 100"),
             Example::new("Include generated functions", "code_search large-functions --include-generated"),
+            Example::new("Combine column conditions", "code_search large-functions --where 'lines >= 80 || arity > 4'"),
+            Example::new("Who last changed each large function", "code_search large-functions --blame"),
         ])
         .with_related(vec!["complexity", "many-clauses", "hotspots"]),
 
@@ -283,6 +537,7 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             Example::new("Filter to a namespace", "code_search many-clauses MyApp.Web"),
             Example::new("Find functions with 10+ clauses", "code_search many-clauses --min-clauses 10"),
             Example::new("Include generated functions", "code_search many-clauses --include-generated"),
+            Example::new("Leave out callbacks by name", "code_search many-clauses --where 'name !~ \"^handle_\"'"),
         ])
         .with_related(vec!["complexity", "large-functions", "hotspots"]),
 
@@ -307,15 +562,21 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             "search",
             "Search for modules or functions by name pattern",
             CommandCategory::Search,
-            "Finds modules or functions matching a given pattern. Use this as a starting point for other analyses.",
-            "code_search search <PATTERN> [-k modules|functions] [OPTIONS]",
+            "Finds modules or functions matching a given pattern. Use this as a starting point for other analyses. \
+             With --semantic, returns the functions closest in meaning to the pattern, using the embeddings \
+             built by index-embeddings.",
+            "code_search search <PATTERN> [-k modules|functions|docs] [OPTIONS]",
         )
         .with_examples(vec![
             Example::new("Find modules containing 'User'", "code_search search User"),
             Example::new("Find functions starting with 'get_'", "code_search search get_ -k functions"),
             Example::new("Use regex pattern", "code_search search -r '^MyApp\\.API'"),
+            Example::new("Match regardless of case", "code_search search myapp.accounts -i"),
+            Example::new("Find names close to a misspelled one", "code_search search get_usr -k functions --fuzzy"),
+            Example::new("Full-text search @moduledoc/@doc text", "code_search search 'password reset' -k docs"),
+            Example::new("Functions closest in meaning", "code_search search 'load user from database' --semantic"),
         ])
-        .with_related(vec!["location", "function", "browse-module"]),
+        .with_related(vec!["location", "function", "browse-module", "index-embeddings"]),
 
         CommandDescription::new(
             "location",
@@ -327,6 +588,7 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
         .with_examples(vec![
             Example::new("Find any function named 'validate'", "code_search location validate"),
             Example::new("Find location of a function in a module", "code_search location get MyApp.Repo"),
+            Example::new("Locate a list of functions read from stdin", "printf 'MyApp.Repo.get/2\\nMyApp.Accounts.get_user\\n' | code_search location --stdin"),
         ])
         .with_related(vec!["search", "function", "browse-module"]),
 
@@ -339,6 +601,7 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
         )
         .with_examples(vec![
             Example::new("Show function signature", "code_search function MyApp.Repo get -a 2"),
+            Example::new("Show the signatures of functions listed by another query", "code_search hotspots -o csv --fields module,function | code_search function --stdin"),
         ])
         .with_related(vec!["search", "location", "accepts"]),
 
@@ -360,11 +623,16 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             "accepts",
             "Find functions accepting a specific type pattern",
             CommandCategory::Type,
-            "Finds all functions that have a parameter matching a type pattern. Useful for finding consumers of a type.",
+            "Finds all functions that have a parameter matching a type pattern. Useful for finding consumers of a type. \
+             Patterns are written like types with _ for any type, e.g. {:ok, _}; add --returns to find conversions.",
             "code_search accepts <PATTERN> [OPTIONS]",
         )
         .with_examples(vec![
             Example::new("Find functions accepting a type", "code_search accepts User.t"),
+            Example::new(
+                "Find conversions between two types",
+                "code_search accepts Changeset.t --returns '{:ok, _}'",
+            ),
             Example::new("Use regex for type pattern", "code_search accepts 'list\\(.*\\)' -r"),
         ])
         .with_related(vec!["returns", "struct-usage", "function"]),
@@ -373,11 +641,14 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             "returns",
             "Find functions returning a specific type pattern",
             CommandCategory::Type,
-            "Finds all functions that return a type matching a pattern. Useful for finding providers of a type.",
+            "Finds all functions that return a type matching a pattern. Useful for finding providers of a type. \
+             Patterns are written like types with _ for any type, e.g. {:ok, _} | {:error, _}; add --accepts to \
+             require a parameter type too.",
             "code_search returns <PATTERN> [OPTIONS]",
         )
         .with_examples(vec![
             Example::new("Find functions returning a type", "code_search returns ':ok'"),
+            Example::new("Match tuple structure", "code_search returns '{:ok, User.t}' --accepts integer"),
             Example::new("Use regex for type pattern", "code_search returns 'tuple\\(.*\\)' -r"),
         ])
         .with_related(vec!["accepts", "struct-usage", "function"]),
@@ -393,14 +664,74 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
             Example::new("Find all functions using a struct", "code_search struct-usage User.t"),
             Example::new("Summarize by module", "code_search struct-usage User.t --by-module"),
         ])
-        .with_related(vec!["accepts", "returns", "browse-module"]),
+        .with_related(vec!["accepts", "returns", "type-usage"]),
+
+        CommandDescription::new(
+            "struct-fields-usage",
+            "Show which functions read or write each struct field",
+            CommandCategory::Type,
+            "Lists the read and write sites of each field of a struct, from the field access records of the import data. \
+             Fields that are defined but never accessed are listed too. Use --access to keep only reads or writes.",
+            "code_search struct-fields-usage <MODULE> [FIELD] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Every field of a struct", "code_search struct-fields-usage MyApp.User"),
+            Example::new("Functions writing a field", "code_search struct-fields-usage MyApp.User email --access write"),
+        ])
+        .with_related(vec!["struct-usage", "calls-to", "browse-module"]),
+
+        CommandDescription::new(
+            "type-usage",
+            "Find every spec and type referencing a type",
+            CommandCategory::Type,
+            "Searches the full text of every @spec, @callback, @type, @typep and @opaque for a type reference, \
+             wherever it appears: nested in a tuple, list or map, or in a type definition. Qualified references \
+             (MyApp.User.t for User.t) match too. Use it to find everything a type refactor has to touch.",
+            "code_search type-usage <TYPE> [MODULE] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Everything referencing a type", "code_search type-usage User.t"),
+            Example::new("Within one module", "code_search type-usage User.t MyApp.Accounts"),
+        ])
+        .with_related(vec!["struct-usage", "accepts", "returns"]),
+
+        CommandDescription::new(
+            "spec-coverage",
+            "Report functions lacking a @spec",
+            CommandCategory::Type,
+            "Joins function definitions with imported specs and reports, per module, the share of public functions \
+             with a @spec and the ones without, lowest coverage first. @callback declarations do not count. \
+             --include-private also counts private functions; --drift lists specs whose arity matches no definition.",
+            "code_search spec-coverage [MODULE] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Coverage of every module", "code_search spec-coverage"),
+            Example::new("Also find specs with a stale arity", "code_search spec-coverage MyApp.Accounts --drift"),
+        ])
+        .with_related(vec!["function", "accepts", "returns"]),
+
+        CommandDescription::new(
+            "callbacks",
+            "Check behaviour implementers for missing or extra callbacks",
+            CommandCategory::Type,
+            "Lists the @callback and @macrocallback specs of a behaviour module and every module declaring \
+             @behaviour for it. Each implementer gets its missing callbacks and its extra ones: public functions \
+             named like a callback with an arity the behaviour does not declare. --incomplete-only hides complete ones.",
+            "code_search callbacks <BEHAVIOUR> [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Audit a behaviour", "code_search callbacks MyApp.Job"),
+            Example::new("Only implementers needing changes", "code_search callbacks MyApp.Job --incomplete-only"),
+        ])
+        .with_related(vec!["spec-coverage", "function", "browse-module"]),
 
         // Module Commands
         CommandDescription::new(
             "depends-on",
             "Show what modules a given module depends on",
             CommandCategory::Module,
-            "Lists all modules that a given module calls or depends on. Shows outgoing module dependencies.",
+            "Lists all modules that a given module calls or depends on. Shows outgoing module dependencies, \
+             marking calls made from several call sites with the count (×3).",
             "code_search depends-on <MODULE> [OPTIONS]",
         )
         .with_examples(vec![
@@ -441,6 +772,262 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
         ])
         .with_related(vec!["god-modules", "boundaries", "depends-on"]),
 
+        CommandDescription::new(
+            "communities",
+            "Detect module communities from call coupling and compare them with namespaces",
+            CommandCategory::Module,
+            "Runs community detection (Louvain by default, or label propagation) over the module call graph, \
+             weighted by call sites. Each community reports its cohesion and the namespace most of its members \
+             share; namespaces split across several communities are listed separately. Contrast with clusters, \
+             which groups modules by namespace instead of by actual coupling.",
+            "code_search communities [MODULE] [-a louvain|label-propagation] [--depth N] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Detect communities", "code_search communities"),
+            Example::new("Use label propagation", "code_search communities -a label-propagation"),
+            Example::new("Compare with depth-3 namespaces", "code_search communities --depth 3"),
+        ])
+        .with_related(vec!["clusters", "coupling", "cycles"]),
+
+        CommandDescription::new(
+            "matrix",
+            "Build a module-by-module dependency structure matrix (DSM)",
+            CommandCategory::Module,
+            "Counts the call sites between every pair of selected modules; row modules call column modules. \
+             With --order cluster, modules are grouped by detected community so coupled modules form blocks \
+             along the diagonal and cross-block dependencies stand out. -o csv writes the matrix as a grid \
+             for spreadsheets; --limit keeps the most connected modules.",
+            "code_search matrix [MODULE] [--order name|cluster] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Matrix of all modules", "code_search matrix"),
+            Example::new("Group coupled modules together", "code_search matrix --order cluster"),
+            Example::new("Export as CSV", "code_search -o csv matrix > dsm.csv"),
+        ])
+        .with_related(vec!["communities", "coupling", "depends-on", "diagram"]),
+
+        CommandDescription::new(
+            "diagram",
+            "Generate a PlantUML diagram of namespace clusters and their dependencies",
+            CommandCategory::Module,
+            "Groups modules by namespace at --depth, like clusters, and writes a PlantUML diagram with one \
+             arrow per namespace dependency, labelled with its call sites. --style component draws each \
+             namespace as a component; --style package draws it as a package listing its modules. A module \
+             pattern keeps the namespaces with a matching module and their direct dependencies, --min-calls \
+             drops thin dependencies and --limit keeps the most connected namespaces. -o json returns the \
+             namespaces and weighted dependencies instead.",
+            "code_search diagram [MODULE] [--style component|package] [--depth N] [--min-calls N] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Component diagram for the docs", "code_search diagram > architecture.puml"),
+            Example::new("Packages listing their modules", "code_search diagram --style package"),
+            Example::new("Only heavy dependencies", "code_search diagram --depth 3 --min-calls 5"),
+        ])
+        .with_related(vec!["clusters", "matrix", "layers"]),
+
+        CommandDescription::new(
+            "supervision-tree",
+            "Show the OTP supervision tree with each process module's call-graph footprint",
+            CommandCategory::Module,
+            "Renders supervisor -> child spec edges imported with import --supervision (or a supervision \
+             list in the call graph JSON), starting from supervisors that nothing else supervises. Each node \
+             shows its child type, restart type and strategy, plus the module's file, function count, lines \
+             of code and incoming/outgoing calls when the module is in the call graph.",
+            "code_search supervision-tree [SUPERVISOR] [--depth N] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("All supervision trees", "code_search supervision-tree"),
+            Example::new("Tree under one supervisor", "code_search supervision-tree MyApp.Application"),
+            Example::new("Direct children only", "code_search supervision-tree MyApp.Application --depth 1"),
+        ])
+        .with_related(vec!["entry-points", "depends-on", "god-modules"]),
+
+        CommandDescription::new(
+            "apps",
+            "Summarize umbrella apps with their size and cross-app dependencies",
+            CommandCategory::Module,
+            "Groups modules by OTP application: an app given on their function locations in the call graph \
+             JSON, or the apps/<app>/ directory of their source files. Each app shows its module and function \
+             counts, lines of code and the calls into and from other apps. hotspots, unused and \
+             large-functions take --app to stay within one app.",
+            "code_search apps [APP] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("All apps", "code_search apps"),
+            Example::new("A single app", "code_search apps billing"),
+            Example::new("Dead code in one app", "code_search unused --app billing"),
+        ])
+        .with_related(vec!["depends-on", "god-modules", "layers"]),
+
+        CommandDescription::new(
+            "deps-surface",
+            "Show which external library functions the project calls, and from where",
+            CommandCategory::Module,
+            "Finds calls into modules the project does not define and groups them into libraries by leading \
+             namespace (--depth 2 splits Phoenix.LiveView from Phoenix.PubSub; Erlang modules are named :mod). \
+             Each library lists the functions used, with call counts and calling modules, most used first. \
+             Elixir and Erlang standard library modules are skipped unless --include-stdlib is given.",
+            "code_search deps-surface [LIBRARY] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("All libraries", "code_search deps-surface"),
+            Example::new("Ecto upgrade blast radius", "code_search deps-surface Ecto"),
+            Example::new("Libraries used by the web layer", "code_search deps-surface -m 'MyApp\\.Web\\.' -r"),
+        ])
+        .with_related(vec!["depends-on", "apps", "boundaries"]),
+
+        CommandDescription::new(
+            "layers",
+            "Check call edges against architectural layer rules",
+            CommandCategory::Module,
+            "Reads layer definitions from .code_search/config.json (or --config) and reports every call \
+             that crosses layers in a disallowed direction, with file:line.\n\n\
+             Each layer lists its module namespaces and the layers it may depend on (may_depend_on). \
+             When may_depend_on is omitted, a layer may call any layer listed after it. \
+             Calls within a layer and calls to modules outside all layers are always allowed.",
+            "code_search layers [MODULE] [--config <FILE>] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Check all calls against configured layers", "code_search layers"),
+            Example::new("Only check calls from the web layer", "code_search layers MyAppWeb"),
+            Example::new("Use a different config file", "code_search layers --config architecture.json"),
+        ])
+        .with_related(vec!["cycles", "depends-on", "clusters"]),
+
+        CommandDescription::new(
+            "check",
+            "Evaluate call graph policies from the config file (CI gate)",
+            CommandCategory::Analysis,
+            "Runs the rules defined in .code_search/config.json (or --config) and exits with status 1 \
+             when any rule is violated, printing a report of every violation.\n\n\
+             Supported rules:\n\
+             - layers: architectural layer definitions (see `describe layers`)\n\
+             - check.forbidden: list of {from, to, reason} module dependencies that must not exist\n\
+             - check.max_cycles: maximum number of module dependency cycles\n\
+             - check.max_god_module_score: maximum incoming + outgoing calls per module\n\
+             - check.no_unused_public: fail when a public function is never called",
+            "code_search check [--config <FILE>] [--project <NAME>]",
+        )
+        .with_examples(vec![
+            Example::new("Evaluate all configured rules", "code_search check"),
+            Example::new("Use a CI-specific rules file", "code_search check --config ci/rules.json"),
+            Example::new("Machine-readable report", "code_search check -o json"),
+        ])
+        .with_related(vec!["layers", "cycles", "god-modules", "unused"]),
+
+        CommandDescription::new(
+            "summary",
+            "One-page architectural overview of a project",
+            CommandCategory::Analysis,
+            "Combines several analyses into a single report: module, function and call counts, the top \
+             hotspots by incoming calls, the top god modules (default god-modules thresholds), the number of \
+             module cycles, the number of unused functions (compiler-generated ones excluded) and the average \
+             cyclomatic complexity. Use -o json to feed dashboards.",
+            "code_search summary [--project <NAME>] [--top N]",
+        )
+        .with_examples(vec![
+            Example::new("Overview of the default project", "code_search summary"),
+            Example::new("List the top 10 hotspots and god modules", "code_search summary --top 10"),
+            Example::new("Machine-readable report", "code_search summary -o json"),
+        ])
+        .with_related(vec!["hotspots", "god-modules", "cycles", "unused", "complexity"]),
+
+        CommandDescription::new(
+            "deprecated-usage",
+            "List call sites of deprecated functions, grouped by caller module",
+            CommandCategory::Analysis,
+            "A function is deprecated when the extractor emits the message of its @deprecated attribute \
+             (`\"deprecated\"` on its function location). deprecated-usage lists every call to such a function \
+             with the deprecation message, grouped by caller module; the MODULE argument narrows the callers. \
+             With --fail the command exits with a nonzero status when any call is found, so CI can keep new \
+             code off deprecated APIs. -o github emits one warning annotation per call site.",
+            "code_search deprecated-usage [MODULE] [--fail] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("All calls to deprecated functions", "code_search deprecated-usage"),
+            Example::new("Calls made from one module", "code_search deprecated-usage MyApp.Web"),
+            Example::new("CI gate", "code_search deprecated-usage --fail -o github"),
+        ])
+        .with_related(vec!["calls-to", "unused", "check"]),
+
+        CommandDescription::new(
+            "trends",
+            "Show how project metrics evolve across imports",
+            CommandCategory::Analysis,
+            "Every import records a snapshot of module, function and call counts, unused functions, module \
+             cycles and average cyclomatic complexity (skip it with import --no-snapshot, label it with \
+             import --label). trends lists the latest snapshots as one series per metric, drawn as a sparkline \
+             with the first and last values, so teams can check that refactoring actually improves the \
+             codebase. Use -o json for the raw series.",
+            "code_search trends [--project <NAME>] [-l N]",
+        )
+        .with_examples(vec![
+            Example::new("Trends of the default project", "code_search trends"),
+            Example::new("Only the last 10 imports", "code_search trends -l 10"),
+            Example::new("Series for dashboards", "code_search trends -o json"),
+        ])
+        .with_related(vec!["summary", "import", "unused", "cycles", "complexity"]),
+
+        CommandDescription::new(
+            "age",
+            "List old complex functions and new heavily used ones",
+            CommandCategory::Analysis,
+            "Each function clause can carry the time and revision of its last change, from the extractor or \
+             from import --modified-at and --commit (the post-commit hook passes both); clauses whose source_sha \
+             is unchanged keep their recorded values, and others fall back to their file's modification time. \
+             Imports also record when each function was first seen. age lists the complex functions left \
+             untouched the longest (--min-complexity) and the newest functions that already have many callers \
+             (--min-fan-in), each with its date, age in days and revision.",
+            "code_search age [MODULE] [--min-complexity N] [--min-fan-in N] [--exclude-generated] [-l N]",
+        )
+        .with_examples(vec![
+            Example::new("Stale complex and new popular functions", "code_search age"),
+            Example::new("Only functions of matching modules", "code_search age MyApp.Accounts"),
+            Example::new("Top 10 of each list, complexity 10+", "code_search age --min-complexity 10 -l 10"),
+        ])
+        .with_related(vec!["complexity", "hotspots", "trends", "import"]),
+
+        CommandDescription::new(
+            "pr",
+            "Review the functions changed by a pull request",
+            CommandCategory::Analysis,
+            "Maps the changed lines of a unified diff to the function clauses whose line ranges contain them. \
+             The diff comes from --diff FILE (- for stdin) or from git diff --unified=0 BASE...HEAD. Each changed \
+             function is reported with its callers, its complexity summed over clauses and the functions it newly \
+             calls. With --base-project naming an import of the base revision, complexity deltas, new functions \
+             and new dependencies compare against it; otherwise new dependencies are callees only called from \
+             added lines. -o github emits one notice per function for review bots.",
+            "code_search pr (--base REF [--head REF] | --diff FILE) [--base-project NAME] [--project NAME] [-l N]",
+        )
+        .with_examples(vec![
+            Example::new("Functions changed since main", "code_search pr --base main"),
+            Example::new("Complexity deltas against an import of main", "code_search pr --base main --base-project main"),
+            Example::new("Notices from a diff on stdin", "git diff main | code_search pr --diff - -o github"),
+        ])
+        .with_related(vec!["calls-to", "complexity", "check"]),
+
+        CommandDescription::new(
+            "report",
+            "Render a multi-section architecture report as Markdown or HTML",
+            CommandCategory::Analysis,
+            "Runs hotspots, cycles, god-modules, large-functions, duplicates and boundaries and renders each \
+             result as a titled table in one Markdown document (or HTML with --render html), ready to attach \
+             to a PR or an architecture review. The HTML report is a single self-contained file that also \
+             embeds an interactive module dependency graph (the 200 most connected modules): clicking a module \
+             highlights its dependencies and lists the report rows mentioning it, and clicking a row selects \
+             its module in the graph. --sections picks and orders the sections; --min-lines, \
+             --min-functions, --min-total and --min-ratio set the thresholds; -l caps the rows per section. \
+             With -o json the sections are returned as column/row data.",
+            "code_search report [--sections LIST] [--render markdown|html] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Full Markdown report", "code_search report > ARCHITECTURE.md"),
+            Example::new("Self-contained HTML report with a dependency graph", "code_search report --render html > report.html"),
+            Example::new("Only hotspots and cycles", "code_search report --sections hotspots,cycles"),
+            Example::new("Stricter thresholds", "code_search report --min-lines 80 --min-functions 30"),
+        ])
+        .with_related(vec!["summary", "hotspots", "cycles", "god-modules", "duplicates"]),
+
         // Other Commands
         CommandDescription::new(
             "setup",
@@ -455,17 +1042,322 @@ pub fn all_descriptions() -> Vec<CommandDescription> {
         ])
         .with_related(vec!["import"]),
 
+        CommandDescription::new(
+            "index-embeddings",
+            "Compute vector embeddings of functions for `search --semantic`",
+            CommandCategory::Other,
+            "Embeds every function of the project from its name, @spec and @doc text and stores the vectors in \
+             an HNSW index for search --semantic. The provider comes from the `embeddings` section of the config \
+             file: the built-in `hashing` model (offline, matches shared vocabulary) or `http`, an \
+             OpenAI-compatible /embeddings endpoint such as Ollama or OpenAI. --provider, --url and --model \
+             override the config. Re-run after importing; search refuses embeddings built by another model.",
+            "code_search index-embeddings [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("Index with the built-in model", "code_search index-embeddings"),
+            Example::new(
+                "Index with a local Ollama model",
+                "code_search index-embeddings --provider http --url http://localhost:11434/v1/embeddings --model nomic-embed-text",
+            ),
+        ])
+        .with_related(vec!["search", "import"]),
+
         CommandDescription::new(
             "import",
             "Import a call graph JSON file into the database",
             CommandCategory::Other,
-            "Loads call graph data from a JSON file into the database. Must run setup first.",
-            "code_search import --file <FILE> [OPTIONS]",
+            "Loads call graph data from a JSON file, optionally gzip or zstd compressed (.json.gz, .json.zst), \
+             into the database. Must run setup first. \
+             --schema generic reads the language-agnostic format (versioned nodes and edges with kinds) \
+             that extractors for other languages, such as Ruby or Python, can emit. --schema erlang reads the \
+             Erlang dialect (module:function/arity names, records instead of structs) and names modules as \
+             Elixir does (:mod), so Erlang and Elixir apps imported into one project connect. --dry-run \
+             parses and validates the files, reports the rows each relation would get, lists every malformed record by \
+             its JSON pointer and any stored relation whose columns differ from this version's schema, and \
+             writes nothing; it exits nonzero when the import would fail. Imports are strict by default and \
+             refuse a file with any malformed record; --lenient skips those records and reports them per relation. \
+             Calls, messages and field accesses repeating a key are dropped and counted; --dedup \
+             RELATION=COLUMNS (or import.dedup in the config file) narrows the key of a relation. \
+             Progress is checkpointed per chunk; --resume finishes an interrupted import of the same file. \
+             --replace-file PATH first removes the stored clauses, calls and field accesses of a source file. \
+             Records of files under deps/ and _build/ (kept with --include-deps), ignored by the \
+             .gitignore files of the current directory and below (read with negations and nesting as git \
+             does; skipped with --no-gitignore), import.exclude_paths of the config file or an \
+             --exclude-path GLOB are left out and counted; a glob without a slash matches a name at any depth.",
+            "code_search import --file <FILE> [--schema elixir|generic|erlang] [OPTIONS]",
         )
         .with_examples(vec![
             Example::new("Import call graph data", "code_search import --file call_graph.json"),
+            Example::new("Import a generic nodes/edges graph", "code_search import --file graph.json --schema generic"),
+            Example::new("Add the Erlang apps of an umbrella", "code_search import --file otp.json --schema erlang"),
+            Example::new("Validate a file without importing it", "code_search import --file call_graph.json --dry-run"),
+            Example::new("Import the valid records of a file", "code_search import --file call_graph.json --lenient"),
+            Example::new(
+                "Keep one call edge per caller and callee",
+                "code_search import --file call_graph.json --dedup calls=caller_module,caller_function,callee_module,callee_function,callee_arity",
+            ),
+            Example::new("Finish an interrupted import", "code_search import --file call_graph.json --resume"),
+            Example::new(
+                "Keep test support and generated code out",
+                "code_search import --file call_graph.json --exclude-path test/support --exclude-path '*.pb.ex'",
+            ),
+        ])
+        .with_related(vec!["setup", "update", "extract"]),
+
+        CommandDescription::new(
+            "update",
+            "Re-extract and import only the files that changed",
+            CommandCategory::Other,
+            "Runs the extractors configured under extractors in .code_search/config.json over the given changed \
+             files, each file going to the first extractor listing its extension, and imports each extractor's \
+             output in place of the stored rows of its files. Without the section the Elixir extractor runs: \
+             mix compile --debug-info as the prepare command, then ex_ast. In a command, {files} is the files, \
+             {output} a temporary JSON file read instead of stdout and {mix_env} the --mix-env value; both \
+             commands run with MIX_ENV set. Files no extractor reads are ignored, and changed files that no \
+             longer exist lose their clauses, calls and field accesses. --commit and --modified-at date the \
+             changed functions as with import. The post-commit hook runs update for the files of each commit.",
+            "code_search update --files <PATH>... [--project NAME] [--mix-env ENV] [--commit SHA] [--modified-at UNIX_SECONDS]",
+        )
+        .with_examples(vec![
+            Example::new("Refresh two files", "code_search update --files lib/a.ex lib/b.ex"),
+            Example::new(
+                "Refresh the files of the last commit",
+                "code_search update --files $(git diff --name-only --no-renames HEAD~1 HEAD)",
+            ),
+        ])
+        .with_related(vec!["import", "extract", "setup", "age"]),
+
+        CommandDescription::new(
+            "extract",
+            "Extract a source tree with the configured extractors and import the result",
+            CommandCategory::Other,
+            "Walks the given files and directories (default: the current directory), skipping hidden \
+             directories and each extractor's exclude directories, and hands every source file to the first \
+             extractor under extractors in .code_search/config.json that lists its extension (default: the \
+             Elixir extractor, mix compile --debug-info then ex_ast). An extractor receives the files as \
+             arguments and prints its call graph as JSON on stdout, or writes it to the {output} file; it is \
+             imported with the extractor's schema in place of the stored rows of those files. --only runs the \
+             named extractors, --clear empties the project first and --dry-run lists the files without running \
+             anything. --builtin parses the Elixir files with a built-in tree-sitter extractor instead, for \
+             repositories that cannot be compiled: definitions and qualified or same-module calls are found, \
+             but macros are not expanded and calls to imported functions are missed.",
+            "code_search extract [PATH]... [--only NAME] [--builtin] [--project NAME] [--clear] [--mix-env ENV] [--dry-run]",
+        )
+        .with_examples(vec![
+            Example::new("Extract and import the current directory", "code_search extract"),
+            Example::new("Replace the project with lib/ and test/", "code_search extract lib test --clear"),
+            Example::new("List the files each extractor would read", "code_search extract --dry-run"),
+            Example::new("Approximate graph without compiling", "code_search extract --builtin --clear"),
+        ])
+        .with_related(vec!["import", "update", "setup"]),
+
+        CommandDescription::new(
+            "annotate",
+            "Attach a note, TODO or deprecation marker to a module or function",
+            CommandCategory::Other,
+            "Stores an annotation of kind note (default), todo, done or deprecated on a module, a function \
+             (every arity) or a function/arity. Annotations live next to the call graph and survive re-imports. \
+             --remove deletes the target's annotations, or only those of one kind with -k.",
+            "code_search annotate <MODULE> [FUNCTION] [-a ARITY] -m <TEXT> [-k KIND]",
+        )
+        .with_examples(vec![
+            Example::new("Note on a module", "code_search annotate MyApp.Accounts -m 'Owned by the identity team'"),
+            Example::new(
+                "TODO on one function",
+                "code_search annotate MyApp.Accounts get_user -a 1 -k todo -m 'Handle soft-deleted users'",
+            ),
+            Example::new("Remove a function's TODOs", "code_search annotate MyApp.Accounts get_user -a 1 --remove -k todo"),
+        ])
+        .with_related(vec!["annotations", "location", "unused", "complexity"]),
+
+        CommandDescription::new(
+            "annotations",
+            "List annotations attached with `annotate`",
+            CommandCategory::Other,
+            "Lists the project's annotations grouped by module or function, optionally only those of one module \
+             (exact match, or pattern with --regex) or one kind. location, unused and complexity show the \
+             annotations of each function next to their results with --show-annotations.",
+            "code_search annotations [MODULE] [-k KIND] [OPTIONS]",
+        )
+        .with_examples(vec![
+            Example::new("All annotations", "code_search annotations"),
+            Example::new("Open TODOs", "code_search annotations -k todo"),
+            Example::new("Unused functions with their notes", "code_search unused --show-annotations"),
+        ])
+        .with_related(vec!["annotate", "location", "unused", "complexity"]),
+
+        CommandDescription::new(
+            "export",
+            "Export function definitions and calls as a ctags or cscope index",
+            CommandCategory::Other,
+            "Writes an editor index of the imported codebase to stdout. ctags produces a sorted tags file of \
+             function definitions, tagged by name and by qualified name (MyApp.Accounts.get_user), with private \
+             functions scoped to their file. cscope produces an uncompressed cross-reference of definitions and \
+             call sites for find-callers; it stores no source text, so open it with cscope -d. File paths are \
+             the ones recorded at import, so run it from the project root. snapshot writes every stored row of \
+             the project as one JSON document, which the WebAssembly build of the query engine loads for \
+             in-browser search, trace and path. cytoscape (Cytoscape.js elements) and d3 (nodes and links) \
+             write the function call graph with fan-in, fan-out, complexity and a Louvain cluster id on each \
+             node; --module keeps the functions of matching modules and those within --depth calls of them, \
+             while the metrics always describe the whole project.",
+            "code_search export <ctags|cscope|snapshot|cytoscape|d3> [--project <NAME>] [-m <PATTERN>] [--depth <N>]",
+        )
+        .with_examples(vec![
+            Example::new("Tags file for jump-to-definition", "code_search export ctags > tags"),
+            Example::new("Cross-reference for find-callers", "code_search export cscope > cscope.out"),
+            Example::new("Graph snapshot for a browser dashboard", "code_search export snapshot > graph.json"),
+            Example::new(
+                "Call graph around a module for Cytoscape.js",
+                "code_search export cytoscape -m MyApp.Accounts --depth 2 > accounts.json",
+            ),
+            Example::new("Whole call graph for a D3 force layout", "code_search export d3 > graph.json"),
+        ])
+        .with_related(vec!["location", "calls-to", "rename-impact", "centrality", "communities"]),
+
+        CommandDescription::new(
+            "backup",
+            "Back up the database to a file",
+            CommandCategory::Other,
+            "Copies the whole database (every project, snapshot, annotation and embedding) into a standalone \
+             SQLite file with Cozo's native backup. The file must not exist yet. Prints the row count of each \
+             relation backed up.",
+            "code_search backup <PATH>",
+        )
+        .with_examples(vec![
+            Example::new("Back up the default database", "code_search backup code_search.bak"),
+            Example::new("Back up another database", "code_search --db other.sqlite backup other.bak"),
+        ])
+        .with_related(vec!["restore", "setup", "import"]),
+
+        CommandDescription::new(
+            "restore",
+            "Restore the database from a backup file",
+            CommandCategory::Other,
+            "Creates the current schema in the database and replaces the rows of every relation found in the \
+             backup; relations missing from the backup are left as they are. Data already in the database is \
+             overwritten, and a backup taken by an older version restores into the newer schema, with added \
+             columns taking their defaults.",
+            "code_search restore <PATH>",
+        )
+        .with_examples(vec![
+            Example::new("Restore into the default database", "code_search restore code_search.bak"),
+            Example::new("Restore into a new database", "code_search --db fresh.sqlite restore code_search.bak"),
+        ])
+        .with_related(vec!["backup", "setup", "import"]),
+
+        CommandDescription::new(
+            "vacuum",
+            "Compact the database and drop orphaned rows",
+            CommandCategory::Other,
+            "Maintenance for long-lived databases that grow with every re-import. Removes calls whose caller \
+             module is no longer defined (calls to undefined functions, like struct construction or the stdlib, \
+             are kept), rebuilds the hotspot aggregates of every project and runs VACUUM on the SQLite file, reporting \
+             the space reclaimed. --dry-run only counts the orphaned calls.",
+            "code_search vacuum [--dry-run]",
+        )
+        .with_examples(vec![
+            Example::new("Clean up and compact", "code_search vacuum"),
+            Example::new("Count orphaned calls only", "code_search vacuum --dry-run"),
+        ])
+        .with_related(vec!["backup", "import", "hotspots"]),
+
+        CommandDescription::new(
+            "daemon",
+            "Keep the database open and answer queries over a unix socket",
+            CommandCategory::Other,
+            "Opens the database once and serves invocations on a unix socket next to the database file \
+             (.code_search/cozo.sock) until stopped, keeping the adjacency indexes of trace, reverse-trace \
+             and path loaded between queries. While it runs, read-only commands from the same directory are \
+             forwarded to it and print the same output. Commands changing the database run on their own; \
+             the daemon reloads its indexes after an import. --status checks the daemon and its database \
+             answer, and --stop stops the daemon of the database.",
+            "code_search daemon [--status|--stop]",
+        )
+        .with_examples(vec![
+            Example::new("Serve queries in the background", "code_search daemon &"),
+            Example::new("Check the daemon is healthy", "code_search daemon --status"),
+            Example::new("Stop the daemon", "code_search daemon --stop"),
+        ])
+        .with_related(vec!["batch", "trace", "vacuum"]),
+
+        CommandDescription::new(
+            "indexes",
+            "List or rebuild the secondary indexes of the database",
+            CommandCategory::Other,
+            "Lists the secondary indexes defined by the schema (calls and messages by callee, function \
+             locations by file, field accesses by caller) and whether each exists. setup and import create \
+             them; --rebuild drops and recreates every index, which also adds those missing from databases \
+             created by older versions.",
+            "code_search indexes [--rebuild]",
+        )
+        .with_examples(vec![
+            Example::new("Show index status", "code_search indexes"),
+            Example::new("Recreate all indexes", "code_search indexes --rebuild"),
+        ])
+        .with_related(vec!["setup", "vacuum"]),
+
+        CommandDescription::new(
+            "schema",
+            "Compare the stored relations with the schema of this version",
+            CommandCategory::Other,
+            "schema verify lists the relations of the schema missing from the database and stored relations \
+             whose columns differ from it: missing columns, columns the schema no longer defines and columns \
+             stored with another type. It exits nonzero while any remain. --repair first applies the fixes \
+             that keep every row, creating missing relations and adding missing columns that have a default; \
+             extra and retyped columns are left for a fresh import.",
+            "code_search schema verify [--repair]",
+        )
+        .with_examples(vec![
+            Example::new("Report schema drift", "code_search schema verify"),
+            Example::new("Apply the safe fixes", "code_search schema verify --repair"),
+        ])
+        .with_related(vec!["setup", "indexes", "import"]),
+
+        CommandDescription::new(
+            "batch",
+            "Run the queries listed in a file and combine their results into one document",
+            CommandCategory::Other,
+            "Runs many queries with one process and one database connection, for scripts and agent tool use. \
+             The file is a JSON list (YAML for .yaml/.yml files; - reads JSON from stdin) whose entries are \
+             argument lists, or objects with an optional name and the args. Each query runs as if given on the \
+             command line with -o json, honouring --fields and --only-project; its result holds the parsed \
+             output or the error, and a failing query does not stop the others. Commands that change the \
+             database (import, annotate, restore, vacuum, setup, ...), backup and --db are rejected.",
+            "code_search batch <FILE>",
+        )
+        .with_examples(vec![
+            Example::new("Queries from a JSON file", "code_search batch queries.json"),
+            Example::new("Queries from a YAML file", "code_search batch queries.yaml"),
+            Example::new("Queries from stdin", "echo '[[\"hotspots\", \"-l\", \"5\"]]' | code_search batch -"),
+        ])
+        .with_related(vec!["explore", "context", "describe"]),
+        CommandDescription::new(
+            "query",
+            "Save, list and run named queries",
+            CommandCategory::Other,
+            "Stores a command invocation (the arguments after --) or, with --script, a CozoScript under a name \
+             in the database, so a team can rerun a standard analysis by name. {name} placeholders in the \
+             arguments and $name parameters in a script are filled in with -p name=value when the query runs; \
+             missing and unknown parameters are errors. Scripts run read-only, and system operations \
+             (::relations, ...) and the CsvReader and JsonReader fixed rules are rejected. Queries listed \
+             under `queries` in .code_search/config.json run the same way, and a saved query of the same \
+             name takes precedence. Invocations cannot use --db or commands that change the database.",
+            "code_search query <save|run|list|delete> [NAME] [-p NAME=VALUE]... [-- ARGS...]",
+        )
+        .with_examples(vec![
+            Example::new(
+                "Save an invocation",
+                "code_search query save repo-leaks -d 'Web modules calling the Repo' -- calls-to MyApp.Repo --group-by caller-module",
+            ),
+            Example::new("Save a parameterized invocation", "code_search query save callers -- calls-to '{module}' '{function}'"),
+            Example::new("Run it", "code_search query run callers -p module=MyApp.Repo -p function=get"),
+            Example::new(
+                "Save a script",
+                "code_search query save web-count --script '?[count(m)] := *modules{name: m}, starts_with(m, $prefix)'",
+            ),
+            Example::new("List saved and configured queries", "code_search query list"),
         ])
-        .with_related(vec!["setup"]),
+        .with_related(vec!["batch", "describe"]),
     ]
 }
 
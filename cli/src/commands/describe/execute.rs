@@ -4,6 +4,7 @@ use serde::Serialize;
 use super::DescribeCmd;
 use super::descriptions::{CommandDescription, get_description, descriptions_by_category};
 use crate::commands::Execute;
+use db::query_builders::QuerySettings;
 
 /// Output for listing all commands by category
 #[derive(Debug, Clone, Serialize)]
@@ -34,7 +35,7 @@ pub struct DescribeResult {
 impl Execute for DescribeCmd {
     type Output = DescribeResult;
 
-    fn execute(self, _db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, _db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         if self.commands.is_empty() {
             // List all commands grouped by category
             let categories_map = descriptions_by_category();
@@ -80,7 +81,7 @@ mod tests {
             commands: vec![],
         };
 
-        let result = cmd.execute(&Default::default()).expect("Should succeed");
+        let result = cmd.execute(&Default::default(), &QuerySettings::default()).expect("Should succeed");
 
         match result.mode {
             DescribeMode::ListAll { ref categories } => {
@@ -99,7 +100,7 @@ mod tests {
             commands: vec!["calls-to".to_string()],
         };
 
-        let result = cmd.execute(&Default::default()).expect("Should succeed");
+        let result = cmd.execute(&Default::default(), &QuerySettings::default()).expect("Should succeed");
 
         match result.mode {
             DescribeMode::Specific { ref descriptions } => {
@@ -120,7 +121,7 @@ mod tests {
             ],
         };
 
-        let result = cmd.execute(&Default::default()).expect("Should succeed");
+        let result = cmd.execute(&Default::default(), &QuerySettings::default()).expect("Should succeed");
 
         match result.mode {
             DescribeMode::Specific { ref descriptions } => {
@@ -140,7 +141,7 @@ mod tests {
             commands: vec!["nonexistent".to_string()],
         };
 
-        let result = cmd.execute(&Default::default());
+        let result = cmd.execute(&Default::default(), &QuerySettings::default());
         assert!(result.is_err());
     }
 }
@@ -5,6 +5,7 @@ mod output;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, Execute};
@@ -23,8 +24,8 @@ pub struct DescribeCmd {
 }
 
 impl CommandRunner for DescribeCmd {
-    fn run(self, _db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(_db)?;
+    fn run(self, _db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(_db, settings)?;
         Ok(result.format(format))
     }
 }
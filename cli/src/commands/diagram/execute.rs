@@ -0,0 +1,201 @@
+//! Namespace dependency graph for PlantUML diagrams.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::DiagramCmd;
+use crate::commands::Execute;
+use crate::utils::extract_namespace;
+use db::query_builders::{compile_pattern, QuerySettings};
+use db::queries::clusters::get_module_call_counts;
+
+/// A namespace drawn in the diagram
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagramNamespace {
+    pub name: String,
+    pub modules: Vec<String>,
+    /// Call sites between modules of the namespace
+    pub internal_calls: i64,
+}
+
+/// Call sites from the modules of one namespace to another
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagramDependency {
+    pub from: String,
+    pub to: String,
+    pub calls: i64,
+}
+
+/// Result of the diagram command
+#[derive(Debug, Serialize)]
+pub struct DiagramResult {
+    pub style: String,
+    pub depth: u32,
+    pub namespaces: Vec<DiagramNamespace>,
+    pub dependencies: Vec<DiagramDependency>,
+}
+
+impl Execute for DiagramCmd {
+    type Output = DiagramResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let filter = match &self.module {
+            Some(pattern) if self.common.regex => Some(compile_pattern(pattern)?),
+            Some(pattern) => Some(Regex::new(&regex::escape(pattern))?),
+            None => None,
+        };
+
+        let calls = get_module_call_counts(db, &self.common.project, settings)?;
+        let namespace = |module: &str| extract_namespace(module, self.depth as usize);
+
+        let mut modules: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut internal: BTreeMap<String, i64> = BTreeMap::new();
+        let mut dependencies: BTreeMap<(String, String), i64> = BTreeMap::new();
+        for call in &calls {
+            let from = namespace(&call.caller_module);
+            let to = namespace(&call.callee_module);
+            modules.entry(from.clone()).or_default().insert(call.caller_module.clone());
+            modules.entry(to.clone()).or_default().insert(call.callee_module.clone());
+            if from == to {
+                *internal.entry(from).or_default() += call.call_count;
+            } else {
+                *dependencies.entry((from, to)).or_default() += call.call_count;
+            }
+        }
+        dependencies.retain(|_, calls| *calls >= self.min_calls);
+
+        // With a filter, matching namespaces keep their direct dependencies in both directions
+        if let Some(re) = &filter {
+            let matching: BTreeSet<String> = modules
+                .iter()
+                .filter(|(_, members)| members.iter().any(|m| re.is_match(m)))
+                .map(|(name, _)| name.clone())
+                .collect();
+            dependencies.retain(|(from, to), _| matching.contains(from) || matching.contains(to));
+            let drawn: BTreeSet<&String> = dependencies.keys().flat_map(|(from, to)| [from, to]).collect();
+            modules.retain(|name, _| matching.contains(name) || drawn.contains(name));
+        }
+
+        // Call volume across the namespace boundary decides which namespaces survive the limit
+        let mut volume: BTreeMap<&str, i64> = modules.keys().map(|name| (name.as_str(), 0)).collect();
+        for ((from, to), calls) in &dependencies {
+            *volume.entry(from).or_default() += calls;
+            *volume.entry(to).or_default() += calls;
+        }
+        let mut ranked: Vec<(&str, i64)> = volume.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        let kept: BTreeSet<String> = ranked
+            .into_iter()
+            .take(self.common.limit as usize)
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        let namespaces = modules
+            .into_iter()
+            .filter(|(name, _)| kept.contains(name))
+            .map(|(name, members)| DiagramNamespace {
+                internal_calls: internal.get(&name).copied().unwrap_or(0),
+                name,
+                modules: members.into_iter().collect(),
+            })
+            .collect();
+        let dependencies = dependencies
+            .into_iter()
+            .filter(|((from, to), _)| kept.contains(from) && kept.contains(to))
+            .map(|((from, to), calls)| DiagramDependency { from, to, calls })
+            .collect();
+
+        Ok(DiagramResult {
+            style: self.style.as_str().to_string(),
+            depth: self.depth,
+            namespaces,
+            dependencies,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::diagram::DiagramStyle;
+    use crate::commands::CommonArgs;
+
+    fn diagram_cmd(module: Option<&str>, depth: u32, min_calls: i64, limit: u32) -> DiagramCmd {
+        DiagramCmd {
+            module: module.map(String::from),
+            style: DiagramStyle::Component,
+            depth,
+            min_calls,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    fn calls(result: &DiagramResult, from: &str, to: &str) -> Option<i64> {
+        result
+            .dependencies
+            .iter()
+            .find(|d| d.from == from && d.to == to)
+            .map(|d| d.calls)
+    }
+
+    #[test]
+    fn test_diagram_namespaces_and_weights() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = diagram_cmd(None, 2, 1, 100)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        let names: Vec<&str> = result.namespaces.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["MyApp.Accounts", "MyApp.Controller", "MyApp.Notifier", "MyApp.Repo", "MyApp.Service"]
+        );
+        assert_eq!(calls(&result, "MyApp.Accounts", "MyApp.Repo"), Some(3));
+        assert_eq!(calls(&result, "MyApp.Controller", "MyApp.Accounts"), Some(2));
+        assert_eq!(result.dependencies.iter().map(|d| d.calls).sum::<i64>(), 8);
+    }
+
+    #[test]
+    fn test_diagram_depth_merges_namespaces() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = diagram_cmd(None, 1, 1, 100)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.namespaces.len(), 1);
+        assert_eq!(result.namespaces[0].name, "MyApp");
+        assert_eq!(result.namespaces[0].modules.len(), 5);
+        assert_eq!(result.namespaces[0].internal_calls, 8);
+        assert!(result.dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_diagram_filters() {
+        let db = db::test_utils::call_graph_db("test_project");
+
+        let result = diagram_cmd(None, 2, 3, 100)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+        assert!(result.dependencies.iter().all(|d| d.calls >= 3));
+        assert_eq!(calls(&result, "MyApp.Controller", "MyApp.Accounts"), None);
+
+        let result = diagram_cmd(Some("Repo"), 2, 1, 100).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert!(result.dependencies.iter().all(|d| d.to == "MyApp.Repo" || d.from == "MyApp.Repo"));
+        assert!(result.namespaces.iter().any(|n| n.name == "MyApp.Accounts"));
+        assert!(!result.namespaces.iter().any(|n| n.name == "MyApp.Controller"));
+
+        let result = diagram_cmd(None, 2, 1, 2)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+        let names: Vec<&str> = result.namespaces.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, ["MyApp.Accounts", "MyApp.Repo"]);
+    }
+}
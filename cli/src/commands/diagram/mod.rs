@@ -0,0 +1,71 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::{Args, ValueEnum};
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// PlantUML diagram style
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum DiagramStyle {
+    /// One component per namespace
+    #[default]
+    Component,
+    /// One package per namespace, listing its modules as components
+    Package,
+}
+
+impl DiagramStyle {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DiagramStyle::Component => "component",
+            DiagramStyle::Package => "package",
+        }
+    }
+}
+
+/// Generate a PlantUML diagram of namespace clusters and their dependencies
+///
+/// Groups modules by namespace like `clusters` and draws one arrow per
+/// namespace dependency, labelled with its call sites, ready to render with
+/// PlantUML for architecture documentation.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search diagram > architecture.puml          # Component diagram at depth 2
+  code_search diagram --style package              # Packages listing their modules
+  code_search diagram --depth 3 --min-calls 5      # Finer namespaces, only heavy dependencies
+  code_search diagram MyApp.Accounts               # Namespaces depending on or used by matching modules
+  code_search diagram -l 15                        # The 15 most connected namespaces
+")]
+pub struct DiagramCmd {
+    /// Only draw namespaces with a matching module and their direct dependencies (substring match by default, regex with --regex)
+    pub module: Option<String>,
+
+    /// Diagram style
+    #[arg(long, value_enum, default_value_t = DiagramStyle::Component)]
+    pub style: DiagramStyle,
+
+    /// Namespace depth for clustering (default: 2)
+    #[arg(long, default_value = "2", value_parser = clap::value_parser!(u32).range(1..=10))]
+    pub depth: u32,
+
+    /// Minimum call sites for a dependency to be drawn
+    #[arg(long, default_value = "1")]
+    pub min_calls: i64,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for DiagramCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
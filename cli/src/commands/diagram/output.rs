@@ -0,0 +1,140 @@
+//! PlantUML rendering for diagram command results.
+
+use std::collections::BTreeMap;
+
+use super::execute::DiagramResult;
+use crate::output::Outputable;
+
+impl Outputable for DiagramResult {
+    fn to_table(&self) -> String {
+        let mut lines = vec![
+            "@startuml".to_string(),
+            format!("title Namespace dependencies (depth: {})", self.depth),
+        ];
+
+        if self.namespaces.is_empty() {
+            lines.push("note \"No namespaces found.\" as empty".to_string());
+            lines.push("@enduml".to_string());
+            return lines.join("\n");
+        }
+
+        // PlantUML identifiers cannot contain dots, so every namespace gets an alias
+        let aliases: BTreeMap<&str, String> = self
+            .namespaces
+            .iter()
+            .enumerate()
+            .map(|(i, ns)| (ns.name.as_str(), format!("N{}", i + 1)))
+            .collect();
+
+        lines.push(String::new());
+        for namespace in &self.namespaces {
+            let alias = &aliases[namespace.name.as_str()];
+            let summary = format!(
+                "{} module(s), {} internal call(s)",
+                namespace.modules.len(),
+                namespace.internal_calls
+            );
+            if self.style == "package" {
+                lines.push(format!("package \"{}\" as {} {{", quote(&namespace.name), alias));
+                for (j, module) in namespace.modules.iter().enumerate() {
+                    lines.push(format!("  component \"{}\" as {}_{}", quote(module), alias, j + 1));
+                }
+                lines.push("}".to_string());
+                lines.push(format!("note top of {} : {}", alias, summary));
+            } else {
+                lines.push(format!(
+                    "component \"{}\\n<size:10>{}</size>\" as {}",
+                    quote(&namespace.name),
+                    summary,
+                    alias
+                ));
+            }
+        }
+
+        if !self.dependencies.is_empty() {
+            lines.push(String::new());
+        }
+        for dependency in &self.dependencies {
+            lines.push(format!(
+                "{} --> {} : {}",
+                aliases[dependency.from.as_str()],
+                aliases[dependency.to.as_str()],
+                dependency.calls
+            ));
+        }
+
+        lines.push("@enduml".to_string());
+        lines.join("\n")
+    }
+}
+
+/// Namespace and module names are quoted; PlantUML has no escape for `"`
+fn quote(name: &str) -> String {
+    name.replace('"', "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::execute::{DiagramDependency, DiagramNamespace};
+    use super::*;
+
+    fn result(style: &str) -> DiagramResult {
+        DiagramResult {
+            style: style.to_string(),
+            depth: 2,
+            namespaces: vec![
+                DiagramNamespace {
+                    name: "MyApp.Accounts".to_string(),
+                    modules: vec!["MyApp.Accounts".to_string(), "MyApp.Accounts.User".to_string()],
+                    internal_calls: 4,
+                },
+                DiagramNamespace {
+                    name: "MyApp.Repo".to_string(),
+                    modules: vec!["MyApp.Repo".to_string()],
+                    internal_calls: 0,
+                },
+            ],
+            dependencies: vec![DiagramDependency {
+                from: "MyApp.Accounts".to_string(),
+                to: "MyApp.Repo".to_string(),
+                calls: 3,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_component_diagram() {
+        assert_eq!(
+            result("component").to_table(),
+            "\
+@startuml
+title Namespace dependencies (depth: 2)
+
+component \"MyApp.Accounts\\n<size:10>2 module(s), 4 internal call(s)</size>\" as N1
+component \"MyApp.Repo\\n<size:10>1 module(s), 0 internal call(s)</size>\" as N2
+
+N1 --> N2 : 3
+@enduml"
+        );
+    }
+
+    #[test]
+    fn test_package_diagram() {
+        let output = result("package").to_table();
+        assert!(output.contains(
+            "package \"MyApp.Accounts\" as N1 {\n  component \"MyApp.Accounts\" as N1_1\n  component \"MyApp.Accounts.User\" as N1_2\n}\nnote top of N1 : 2 module(s), 4 internal call(s)\n"
+        ));
+        assert!(output.ends_with("N1 --> N2 : 3\n@enduml"));
+    }
+
+    #[test]
+    fn test_empty_diagram() {
+        let mut result = result("component");
+        result.namespaces.clear();
+        result.dependencies.clear();
+        assert_eq!(
+            result.to_table(),
+            "@startuml\ntitle Namespace dependencies (depth: 2)\nnote \"No namespaces found.\" as empty\n@enduml"
+        );
+    }
+}
@@ -6,6 +6,7 @@ use serde::Serialize;
 use super::DuplicatesCmd;
 use crate::commands::Execute;
 use db::queries::duplicates::find_duplicates;
+use db::query_builders::QuerySettings;
 
 // =============================================================================
 // Detailed mode types (default)
@@ -83,7 +84,7 @@ pub enum DuplicatesOutput {
 impl Execute for DuplicatesCmd {
     type Output = DuplicatesOutput;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         let functions = find_duplicates(
             db,
             &self.common.project,
@@ -91,6 +92,15 @@ impl Execute for DuplicatesCmd {
             self.common.regex,
             self.exact,
             self.exclude_generated,
+            settings,
+        )?;
+
+        // The query is not limited, so --limit is applied to what the baseline leaves
+        let functions = self.baseline.apply_limited(
+            "duplicates",
+            functions,
+            |f| format!("{}.{}/{}", f.module, f.name, f.arity),
+            self.common.limit,
         )?;
 
         if self.by_module {
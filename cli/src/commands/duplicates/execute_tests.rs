@@ -25,10 +25,12 @@ mod tests {
             by_module: false,
             exact: false,
             exclude_generated: false,
+            baseline: Default::default(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -51,10 +53,12 @@ mod tests {
             by_module: false,
             exact: false,
             exclude_generated: false,
+            baseline: Default::default(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -75,10 +79,12 @@ mod tests {
             by_module: false,
             exact: true,
             exclude_generated: false,
+            baseline: Default::default(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -99,10 +105,12 @@ mod tests {
             by_module: false,
             exact: false,
             exclude_generated: false,
+            baseline: Default::default(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -123,10 +131,12 @@ mod tests {
             by_module: false,
             exact: false,
             exclude_generated: false,
+            baseline: Default::default(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -156,10 +166,12 @@ mod tests {
             by_module: true,
             exact: false,
             exclude_generated: false,
+            baseline: Default::default(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -185,10 +197,12 @@ mod tests {
             by_module: true,
             exact: false,
             exclude_generated: false,
+            baseline: Default::default(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -211,10 +225,12 @@ mod tests {
             by_module: false,
             exact: false,
             exclude_generated: true,
+            baseline: Default::default(),
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -7,11 +7,15 @@ mod output_tests;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
+use crate::baseline::BaselineArgs;
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
 
+pub use execute::DuplicatesOutput;
+
 /// Find functions with identical or near-identical implementations
 #[derive(Args, Debug)]
 #[command(after_help = "\
@@ -20,7 +24,9 @@ Examples:
   code_search duplicates MyApp            # Filter to specific module
   code_search duplicates --by-module      # Rank modules by duplication
   code_search duplicates --exact          # Use exact source matching
-  code_search duplicates --exclude-generated  # Exclude macro-generated functions")]
+  code_search duplicates --exclude-generated  # Exclude macro-generated functions
+  code_search duplicates --baseline dups.json --write-baseline  # Record current findings
+  code_search duplicates --baseline dups.json                   # Only report new findings")]
 pub struct DuplicatesCmd {
     /// Module filter pattern (substring match by default, regex with -r)
     pub module: Option<String>,
@@ -33,17 +39,21 @@ pub struct DuplicatesCmd {
     #[arg(long)]
     pub exact: bool,
 
-    /// Exclude macro-generated functions
+    /// Exclude generated functions: macro-generated and compiler callbacks,
+    /// plus the `generated` rules of the config file
     #[arg(long)]
     pub exclude_generated: bool,
 
+    #[command(flatten)]
+    pub baseline: BaselineArgs,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for DuplicatesCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
@@ -1,4 +1,4 @@
-use crate::output::Outputable;
+use crate::output::{Annotation, Outputable};
 
 use super::execute::{DuplicatesByModuleResult, DuplicatesOutput, DuplicatesResult};
 
@@ -45,6 +45,33 @@ impl Outputable for DuplicatesResult {
 
         lines.join("\n")
     }
+
+    fn to_annotations(&self) -> Option<Vec<Annotation>> {
+        let mut annotations = Vec::new();
+        for group in &self.groups {
+            for func in &group.functions {
+                let others: Vec<String> = group
+                    .functions
+                    .iter()
+                    .filter(|other| !std::ptr::eq(*other, func))
+                    .map(|other| format!("{}.{}/{}", other.module, other.name, other.arity))
+                    .collect();
+                annotations.push(Annotation::warning(
+                    &func.file,
+                    func.line,
+                    "Duplicate function",
+                    format!(
+                        "{}.{}/{} duplicates {}",
+                        func.module,
+                        func.name,
+                        func.arity,
+                        others.join(", ")
+                    ),
+                ));
+            }
+        }
+        Some(annotations)
+    }
 }
 
 impl Outputable for DuplicatesByModuleResult {
@@ -87,4 +114,11 @@ impl Outputable for DuplicatesOutput {
             DuplicatesOutput::ByModule(result) => result.to_table(),
         }
     }
+
+    fn to_annotations(&self) -> Option<Vec<Annotation>> {
+        match self {
+            DuplicatesOutput::Detailed(result) => result.to_annotations(),
+            DuplicatesOutput::ByModule(result) => result.to_annotations(),
+        }
+    }
 }
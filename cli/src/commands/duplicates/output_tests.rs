@@ -538,4 +538,48 @@ mod tests {
         assert!(json.contains("\"arity\""));
         assert!(json.contains("\"copy_count\""));
     }
+
+    #[test]
+    fn test_format_github() {
+        let result = DuplicatesOutput::Detailed(DuplicatesResult {
+            total_groups: 1,
+            total_duplicates: 2,
+            groups: vec![DuplicateGroup {
+                hash: "hash_a".to_string(),
+                functions: vec![
+                    DuplicateFunctionEntry {
+                        module: "A".to_string(),
+                        name: "f1".to_string(),
+                        arity: 1,
+                        line: 10,
+                        file: "a.ex".to_string(),
+                    },
+                    DuplicateFunctionEntry {
+                        module: "B".to_string(),
+                        name: "f1".to_string(),
+                        arity: 1,
+                        line: 20,
+                        file: "b.ex".to_string(),
+                    },
+                ],
+            }],
+        });
+
+        let output = result.format(OutputFormat::Github);
+        assert_eq!(
+            output,
+            "::warning file=a.ex,line=10,title=Duplicate function::A.f1/1 duplicates B.f1/1\n\
+             ::warning file=b.ex,line=20,title=Duplicate function::B.f1/1 duplicates A.f1/1"
+        );
+    }
+
+    #[test]
+    fn test_by_module_format_github_falls_back_to_table() {
+        let result = DuplicatesOutput::ByModule(DuplicatesByModuleResult {
+            total_modules: 0,
+            total_duplicates: 0,
+            modules: vec![],
+        });
+        assert_eq!(result.format(OutputFormat::Github), result.to_table());
+    }
 }
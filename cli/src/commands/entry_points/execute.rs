@@ -0,0 +1,151 @@
+//! Entry point detection over public functions.
+
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::EntryPointsCmd;
+use crate::commands::Execute;
+use crate::config::Config;
+use crate::rules::EntryPointRules;
+use db::queries::entry_points::find_public_functions;
+use db::query_builders::QuerySettings;
+use db::types::ModuleCollectionResult;
+
+/// A function the framework calls into
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryPoint {
+    pub name: String,
+    pub arity: i64,
+    /// The rule that matched, e.g. "Phoenix controller"
+    pub kind: String,
+    pub line: i64,
+    /// Distinct functions in the project calling it
+    pub callers: i64,
+}
+
+impl Execute for EntryPointsCmd {
+    type Output = ModuleCollectionResult<EntryPoint>;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let config = Config::load_or_default(&self.config)?;
+        let rules = EntryPointRules::new(&config.entry_points)?;
+
+        let functions = find_public_functions(
+            db,
+            &self.common.project,
+            self.module.as_deref(),
+            self.common.regex,
+            settings,
+        )?;
+
+        let mut entry_points: Vec<_> = functions
+            .into_iter()
+            .filter(|f| !self.roots_only || f.callers == 0)
+            .filter_map(|f| {
+                let kind = rules.kind_of(&f.module, &f.name, f.arity)?.to_string();
+                Some((f, kind))
+            })
+            .collect();
+        let total_items = entry_points.len();
+        entry_points.truncate(self.common.limit as usize);
+
+        let items = crate::utils::group_by_module_with_file(entry_points, |(f, kind)| {
+            let entry = EntryPoint {
+                name: f.name,
+                arity: f.arity,
+                kind,
+                line: f.line,
+                callers: f.callers,
+            };
+            (f.module, entry, f.file)
+        });
+
+        Ok(ModuleCollectionResult {
+            module_pattern: self.module.unwrap_or_else(|| "*".to_string()),
+            function_pattern: None,
+            kind_filter: None,
+            name_filter: None,
+            total_items,
+            items,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+    use std::path::PathBuf;
+
+    fn entry_points_cmd(config: PathBuf, roots_only: bool) -> EntryPointsCmd {
+        EntryPointsCmd {
+            module: None,
+            config,
+            roots_only,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    fn default_cmd(roots_only: bool) -> EntryPointsCmd {
+        entry_points_cmd(PathBuf::from(crate::config::DEFAULT_CONFIG_PATH), roots_only)
+    }
+
+    #[test]
+    fn test_entry_points_detects_controller_actions() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = default_cmd(false).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.total_items, 3);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].name, "MyApp.Controller");
+        let names: Vec<&str> = result.items[0].entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["create", "index", "show"]);
+        assert!(result.items[0].entries.iter().all(|e| e.kind == "Phoenix controller"));
+    }
+
+    #[test]
+    fn test_entry_points_roots_only() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = default_cmd(true).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        // Nothing in the fixture calls the controller actions
+        assert_eq!(result.total_items, 3);
+        assert!(result.items[0].entries.iter().all(|e| e.callers == 0));
+    }
+
+    #[test]
+    fn test_entry_points_custom_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(
+            &path,
+            r#"{"entry_points": {"include_defaults": false, "rules": [
+                {"kind": "Notifier", "module": "Notifier$", "functions": ["notify/1"]}
+            ]}}"#,
+        )
+        .unwrap();
+
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = entry_points_cmd(path, false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_items, 1);
+        assert_eq!(result.items[0].name, "MyApp.Notifier");
+        assert_eq!(result.items[0].entries[0].kind, "Notifier");
+        assert_eq!(result.items[0].entries[0].callers, 1);
+    }
+
+    #[test]
+    fn test_entry_points_missing_explicit_config() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let cmd = entry_points_cmd(PathBuf::from("/nonexistent/config.json"), false);
+        assert!(cmd.execute(&db, &QuerySettings::default()).is_err());
+    }
+}
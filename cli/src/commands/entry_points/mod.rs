@@ -0,0 +1,50 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::output::{OutputFormat, Outputable};
+
+/// Find framework entry points (controllers, LiveViews, OTP callbacks, workers, tasks)
+///
+/// Public functions are matched against entry point rules: built-in patterns for
+/// Phoenix controllers and LiveViews, GenServer and Supervisor callbacks, Oban
+/// workers and Mix tasks, plus any rules under `entry_points` in the config file.
+/// These are the roots of the call graph that the framework calls into.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search entry-points                          # All detected entry points
+  code_search entry-points MyAppWeb                 # Only modules matching MyAppWeb
+  code_search entry-points --roots-only             # Only entry points nothing in the project calls
+  code_search entry-points --config app.json        # Use rules from a different config file
+")]
+pub struct EntryPointsCmd {
+    /// Only check modules matching this pattern (substring or regex with -r)
+    pub module: Option<String>,
+
+    /// Path to the config file containing entry point rules
+    #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+    pub config: PathBuf,
+
+    /// Only include entry points with no callers in the project
+    #[arg(long, default_value_t = false)]
+    pub roots_only: bool,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for EntryPointsCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
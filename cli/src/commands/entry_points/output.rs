@@ -0,0 +1,72 @@
+//! Output formatting for entry-points command results.
+
+use super::execute::EntryPoint;
+use crate::output::TableFormatter;
+use db::types::ModuleCollectionResult;
+
+impl TableFormatter for ModuleCollectionResult<EntryPoint> {
+    type Entry = EntryPoint;
+
+    fn format_header(&self) -> String {
+        if self.module_pattern == "*" {
+            "Entry points".to_string()
+        } else {
+            format!("Entry points (module: {})", self.module_pattern)
+        }
+    }
+
+    fn format_empty_message(&self) -> String {
+        "No entry points found.".to_string()
+    }
+
+    fn format_summary(&self, total: usize, module_count: usize) -> String {
+        format!("Found {} entry point(s) in {} module(s):", total, module_count)
+    }
+
+    fn format_module_header(&self, module_name: &str, module_file: &str) -> String {
+        format!("{} ({}):", module_name, module_file)
+    }
+
+    fn format_entry(&self, entry: &EntryPoint, _module: &str, _file: &str) -> String {
+        format!(
+            "{}/{} [{}] L{}, {} caller(s)",
+            entry.name, entry.arity, entry.kind, entry.line, entry.callers
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Outputable;
+    use db::types::ModuleGroup;
+
+    #[test]
+    fn test_entry_points_table() {
+        let result = ModuleCollectionResult {
+            module_pattern: "*".to_string(),
+            function_pattern: None,
+            kind_filter: None,
+            name_filter: None,
+            total_items: 1,
+            items: vec![ModuleGroup {
+                name: "MyAppWeb.UserController".to_string(),
+                file: "lib/my_app_web/controllers/user_controller.ex".to_string(),
+                entries: vec![EntryPoint {
+                    name: "index".to_string(),
+                    arity: 2,
+                    kind: "Phoenix controller".to_string(),
+                    line: 8,
+                    callers: 0,
+                }],
+                function_count: None,
+            }],
+        };
+
+        let output = result.to_table();
+        assert!(output.starts_with("Entry points\n"));
+        assert!(output.contains("Found 1 entry point(s) in 1 module(s):"));
+        assert!(output.contains("MyAppWeb.UserController (lib/my_app_web/controllers/user_controller.ex):"));
+        assert!(output.contains("index/2 [Phoenix controller] L8, 0 caller(s)"));
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::ExploreCmd;
+use crate::commands::Execute;
+use db::queries::calls_from::find_calls_from;
+use db::queries::calls_to::find_calls_to;
+use db::queries::function::find_functions;
+use db::queries::location::find_locations;
+use db::queries::specs::find_specs;
+use db::query_builders::QuerySettings;
+use db::types::{Call, FunctionRef};
+
+/// Rows fetched per query before ranking and trimming to the budget
+const QUERY_LIMIT: u32 = 1000;
+
+/// Characters per token when estimating the size of the bundle
+const CHARS_PER_TOKEN: usize = 4;
+
+/// A function of the seed, with its clauses merged
+#[derive(Debug, Clone, Serialize)]
+pub struct ExploreDefinition {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub kind: String,
+    pub file: String,
+    pub start_line: i64,
+    pub end_line: i64,
+    /// The `@spec`, or the argument and return types recorded for the function
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub signature: String,
+}
+
+impl ExploreDefinition {
+    /// Text of the entry in the table output, signature on its own line
+    pub fn text(&self) -> String {
+        let head = format!(
+            "{} {}/{}  {}:{}-{}",
+            self.kind, self.name, self.arity, self.file, self.start_line, self.end_line
+        );
+        if self.signature.is_empty() {
+            head
+        } else {
+            format!("{}\n  {}", head, self.signature)
+        }
+    }
+}
+
+/// A caller or callee of the seed
+#[derive(Debug, Clone, Serialize)]
+pub struct ExploreNeighbor {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    /// Call sites between the neighbor and the seed
+    pub calls: usize,
+    /// `file:line` of the first call site
+    pub pointer: String,
+}
+
+impl ExploreNeighbor {
+    /// Text of the entry in the table output
+    pub fn text(&self) -> String {
+        format!(
+            "{}.{}/{}  x{}  {}",
+            self.module, self.name, self.arity, self.calls, self.pointer
+        )
+    }
+}
+
+/// Result of the explore command
+#[derive(Debug, Serialize)]
+pub struct ExploreResult {
+    /// `Module`, `Module.function` or `Module.function/arity`
+    pub seed: String,
+    pub budget: usize,
+    /// Estimated tokens used by the entries included
+    pub estimated_tokens: usize,
+    pub definitions: Vec<ExploreDefinition>,
+    /// Sorted by number of call sites, most first
+    pub callers: Vec<ExploreNeighbor>,
+    /// Sorted by number of call sites, most first
+    pub callees: Vec<ExploreNeighbor>,
+    pub omitted_definitions: usize,
+    pub omitted_callers: usize,
+    pub omitted_callees: usize,
+}
+
+impl ExploreResult {
+    /// Header line of the table output
+    pub fn header(seed: &str) -> String {
+        format!("Explore: {}", seed)
+    }
+}
+
+/// Estimated tokens of a piece of output
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+impl Execute for ExploreCmd {
+    type Output = ExploreResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        // A module seed matches every function of the module
+        let (module, function, use_regex) = match &self.function {
+            Some(function) => (self.module.clone(), function.clone(), false),
+            None => (format!("^{}$", regex::escape(&self.module)), ".*".to_string(), true),
+        };
+        let project = self.project.as_str();
+
+        let locations = find_locations(
+            db,
+            Some(&module),
+            &function,
+            self.arity,
+            project,
+            use_regex,
+            QUERY_LIMIT,
+            settings,
+        )?;
+        let specs = find_specs(
+            db,
+            &module,
+            Some(&function),
+            Some("spec"),
+            project,
+            use_regex,
+            QUERY_LIMIT,
+            settings,
+        )?;
+        let signatures = find_functions(
+            db,
+            &module,
+            &function,
+            self.arity,
+            project,
+            use_regex,
+            QUERY_LIMIT,
+            settings,
+        )?;
+
+        let mut signature_texts: BTreeMap<(String, i64), String> = BTreeMap::new();
+        for signature in signatures {
+            if !signature.args.is_empty() || !signature.return_type.is_empty() {
+                let text = format!("{}({}) :: {}", signature.name, signature.args, signature.return_type);
+                signature_texts.insert((signature.name, signature.arity), text);
+            }
+        }
+        for spec in specs {
+            signature_texts.insert((spec.name, spec.arity), spec.full);
+        }
+
+        let mut definitions: BTreeMap<(String, String, i64), ExploreDefinition> = BTreeMap::new();
+        for location in locations {
+            let key = (location.module.clone(), location.name.clone(), location.arity);
+            let definition = definitions.entry(key).or_insert_with(|| ExploreDefinition {
+                signature: signature_texts
+                    .get(&(location.name.clone(), location.arity))
+                    .cloned()
+                    .unwrap_or_default(),
+                module: location.module,
+                name: location.name,
+                arity: location.arity,
+                kind: location.kind,
+                file: location.file,
+                start_line: location.start_line,
+                end_line: location.end_line,
+            });
+            definition.start_line = definition.start_line.min(location.start_line);
+            definition.end_line = definition.end_line.max(location.end_line);
+        }
+        let mut definitions: Vec<ExploreDefinition> = definitions.into_values().collect();
+        definitions.sort_by(|a, b| (&a.file, a.start_line).cmp(&(&b.file, b.start_line)));
+
+        let function = self.function.as_deref().map(|_| function.as_str());
+        let callers = rank_neighbors(
+            find_calls_to(db, &module, function, self.arity, &[], project, use_regex, QUERY_LIMIT, settings)?,
+            |call| &call.caller,
+        );
+        let callees = rank_neighbors(
+            find_calls_from(db, &module, function, self.arity, &[], project, use_regex, QUERY_LIMIT, settings)?,
+            |call| &call.callee,
+        );
+
+        let seed = match (&self.function, self.arity) {
+            (None, _) => self.module.clone(),
+            (Some(function), None) => format!("{}.{}", self.module, function),
+            (Some(function), Some(arity)) => format!("{}.{}/{}", self.module, function, arity),
+        };
+
+        Ok(fit_to_budget(seed, self.budget, definitions, callers, callees))
+    }
+}
+
+/// Group calls by the function on the other side of the seed, most call sites first
+fn rank_neighbors(calls: Vec<Call>, neighbor: impl Fn(&Call) -> &FunctionRef) -> Vec<ExploreNeighbor> {
+    let mut grouped: BTreeMap<(String, String, i64), ExploreNeighbor> = BTreeMap::new();
+    for call in &calls {
+        let function = neighbor(call);
+        let key = (function.module.to_string(), function.name.to_string(), function.arity);
+        let entry = grouped.entry(key).or_insert_with(|| ExploreNeighbor {
+            module: function.module.to_string(),
+            name: function.name.to_string(),
+            arity: function.arity,
+            calls: 0,
+            pointer: format!("{}:{}", call.caller.file.as_deref().unwrap_or(""), call.line),
+        });
+        entry.calls += 1;
+    }
+
+    let mut neighbors: Vec<ExploreNeighbor> = grouped.into_values().collect();
+    neighbors.sort_by_key(|neighbor| std::cmp::Reverse(neighbor.calls));
+    neighbors
+}
+
+/// Keep the highest ranked entries whose estimated size fits the budget
+///
+/// The first definition is always kept. The others, callers and callees are
+/// then taken in turn, each section closing at its first entry that no longer
+/// fits.
+fn fit_to_budget(
+    seed: String,
+    budget: usize,
+    definitions: Vec<ExploreDefinition>,
+    callers: Vec<ExploreNeighbor>,
+    callees: Vec<ExploreNeighbor>,
+) -> ExploreResult {
+    let mut used = estimate_tokens(&ExploreResult::header(&seed));
+    let (total_definitions, total_callers, total_callees) = (definitions.len(), callers.len(), callees.len());
+
+    let mut definitions = definitions.into_iter();
+    let mut kept_definitions: Vec<ExploreDefinition> = definitions.next().into_iter().collect();
+    used += kept_definitions.iter().map(|d| estimate_tokens(&d.text())).sum::<usize>();
+
+    let mut callers = callers.into_iter().peekable();
+    let mut callees = callees.into_iter().peekable();
+    let mut definitions = definitions.peekable();
+    let (mut kept_callers, mut kept_callees) = (Vec::new(), Vec::new());
+    let mut open = [true; 3];
+
+    while open.iter().any(|o| *o) {
+        open[0] = open[0] && take_if_fits(&mut callers, &mut kept_callers, |c| c.text(), budget, &mut used);
+        open[1] = open[1] && take_if_fits(&mut callees, &mut kept_callees, |c| c.text(), budget, &mut used);
+        open[2] = open[2] && take_if_fits(&mut definitions, &mut kept_definitions, |d| d.text(), budget, &mut used);
+    }
+
+    ExploreResult {
+        seed,
+        budget,
+        estimated_tokens: used,
+        omitted_definitions: total_definitions - kept_definitions.len(),
+        omitted_callers: total_callers - kept_callers.len(),
+        omitted_callees: total_callees - kept_callees.len(),
+        definitions: kept_definitions,
+        callers: kept_callers,
+        callees: kept_callees,
+    }
+}
+
+/// Move the next entry into `kept` if it fits, returning whether the section stays open
+fn take_if_fits<T>(
+    entries: &mut std::iter::Peekable<impl Iterator<Item = T>>,
+    kept: &mut Vec<T>,
+    text: impl Fn(&T) -> String,
+    budget: usize,
+    used: &mut usize,
+) -> bool {
+    let Some(entry) = entries.peek() else {
+        return false;
+    };
+    let tokens = estimate_tokens(&text(entry));
+    if *used + tokens > budget {
+        return false;
+    }
+    *used += tokens;
+    kept.extend(entries.next());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    crate::shared_fixture! {
+        fixture_name: populated_db,
+        fixture_type: call_graph,
+        project: "test_project",
+    }
+
+    fn explore(db: &db::DbInstance, function: Option<&str>, budget: usize) -> ExploreResult {
+        let cmd = ExploreCmd {
+            module: "MyApp.Accounts".to_string(),
+            function: function.map(str::to_string),
+            arity: None,
+            budget,
+            project: "test_project".to_string(),
+        };
+        cmd.execute(db, &QuerySettings::default()).expect("Explore should succeed")
+    }
+
+    #[rstest]
+    fn test_explore_function(populated_db: db::DbInstance) {
+        let result = explore(&populated_db, Some("get_user"), 10_000);
+
+        assert_eq!(result.seed, "MyApp.Accounts.get_user");
+        assert!(!result.definitions.is_empty());
+        assert!(result.definitions.iter().all(|d| d.name == "get_user"));
+        let spec = result.definitions.iter().find(|d| d.arity == 1).expect("get_user/1 defined");
+        assert!(spec.signature.starts_with("@spec get_user("));
+        assert!(result.callers.iter().any(|c| c.module == "MyApp.Controller" && c.name == "show"));
+        assert!(result.callers.windows(2).all(|w| w[0].calls >= w[1].calls));
+        assert_eq!(result.omitted_callers + result.omitted_callees + result.omitted_definitions, 0);
+    }
+
+    #[rstest]
+    fn test_explore_module(populated_db: db::DbInstance) {
+        let result = explore(&populated_db, None, 10_000);
+
+        assert_eq!(result.seed, "MyApp.Accounts");
+        assert!(result.definitions.len() > 1);
+        assert!(result.definitions.iter().all(|d| d.module == "MyApp.Accounts"));
+        assert!(!result.callers.is_empty());
+    }
+
+    #[rstest]
+    fn test_explore_budget_trims_but_keeps_definition(populated_db: db::DbInstance) {
+        let full = explore(&populated_db, None, 10_000);
+        let trimmed = explore(&populated_db, None, 1);
+
+        assert_eq!(trimmed.definitions.len(), 1);
+        assert!(trimmed.callers.is_empty() && trimmed.callees.is_empty());
+        assert_eq!(trimmed.omitted_definitions, full.definitions.len() - 1);
+        assert_eq!(trimmed.omitted_callers, full.callers.len());
+        assert!(full.estimated_tokens <= full.budget);
+    }
+
+    #[rstest]
+    fn test_explore_unknown_seed(populated_db: db::DbInstance) {
+        let result = explore(&populated_db, Some("missing"), 800);
+        assert!(result.definitions.is_empty());
+        assert!(result.callers.is_empty() && result.callees.is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Bundle a module or function's definition, top callers and top callees
+///
+/// Made for LLM agents: one call returns what several queries would, each
+/// entry with a file:line pointer to read next. Entries are added by rank
+/// until the estimated size (4 characters per token) reaches --budget.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search explore --module MyApp.Accounts --function get_user   # One function
+  code_search explore --module MyApp.Accounts                       # Whole module
+  code_search explore --module MyApp.Accounts --function get_user --budget 200
+  code_search explore --module MyApp.Accounts --function get_user -o json
+")]
+pub struct ExploreCmd {
+    /// Module to explore, or containing the function (exact name)
+    #[arg(short, long)]
+    pub module: String,
+
+    /// Function to explore (exact name)
+    #[arg(short, long)]
+    pub function: Option<String>,
+
+    /// Only explore the function of this arity
+    #[arg(short, long, requires = "function")]
+    pub arity: Option<i64>,
+
+    /// Approximate number of tokens the bundle may use
+    #[arg(short, long, default_value_t = 800)]
+    pub budget: usize,
+
+    /// Project to search in
+    #[arg(long, default_value = "default")]
+    pub project: String,
+}
+
+impl CommandRunner for ExploreCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
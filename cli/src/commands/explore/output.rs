@@ -0,0 +1,125 @@
+//! Output formatting for explore command results.
+
+use super::execute::ExploreResult;
+use crate::output::Outputable;
+
+/// Section title, noting how many entries the budget left out
+fn section_title(title: &str, shown: usize, omitted: usize) -> String {
+    if omitted == 0 {
+        format!("{}:", title)
+    } else {
+        format!("{} ({} of {}, raise --budget for more):", title, shown, shown + omitted)
+    }
+}
+
+/// Indent every line of an entry's text under its section
+fn push_entry(lines: &mut Vec<String>, text: &str) {
+    lines.extend(text.lines().map(|line| format!("  {}", line)));
+}
+
+impl Outputable for ExploreResult {
+    fn to_table(&self) -> String {
+        let mut lines = vec![
+            ExploreResult::header(&self.seed),
+            format!("(~{} of {} tokens)", self.estimated_tokens, self.budget),
+        ];
+
+        if self.definitions.is_empty() && self.callers.is_empty() && self.callees.is_empty() {
+            lines.push(String::new());
+            lines.push("No definition or calls found.".to_string());
+            return lines.join("\n");
+        }
+
+        let sections = [
+            ("Definition", self.definitions.iter().map(|d| d.text()).collect::<Vec<_>>(), self.omitted_definitions),
+            ("Callers", self.callers.iter().map(|c| c.text()).collect(), self.omitted_callers),
+            ("Callees", self.callees.iter().map(|c| c.text()).collect(), self.omitted_callees),
+        ];
+        for (title, entries, omitted) in sections {
+            if entries.is_empty() && omitted == 0 {
+                continue;
+            }
+            lines.push(String::new());
+            lines.push(section_title(title, entries.len(), omitted));
+            for entry in &entries {
+                push_entry(&mut lines, entry);
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::explore::execute::{ExploreDefinition, ExploreNeighbor};
+
+    fn neighbor(module: &str, name: &str, calls: usize, pointer: &str) -> ExploreNeighbor {
+        ExploreNeighbor {
+            module: module.to_string(),
+            name: name.to_string(),
+            arity: 1,
+            calls,
+            pointer: pointer.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_to_table_sections() {
+        let result = ExploreResult {
+            seed: "MyApp.Accounts.get_user".to_string(),
+            budget: 100,
+            estimated_tokens: 62,
+            definitions: vec![ExploreDefinition {
+                module: "MyApp.Accounts".to_string(),
+                name: "get_user".to_string(),
+                arity: 1,
+                kind: "def".to_string(),
+                file: "lib/accounts.ex".to_string(),
+                start_line: 10,
+                end_line: 14,
+                signature: "@spec get_user(integer()) :: User.t()".to_string(),
+            }],
+            callers: vec![neighbor("MyApp.Web", "show", 2, "lib/web.ex:8")],
+            callees: vec![],
+            omitted_definitions: 0,
+            omitted_callers: 3,
+            omitted_callees: 0,
+        };
+
+        assert_eq!(
+            result.to_table(),
+            "\
+Explore: MyApp.Accounts.get_user
+(~62 of 100 tokens)
+
+Definition:
+  def get_user/1  lib/accounts.ex:10-14
+    @spec get_user(integer()) :: User.t()
+
+Callers (1 of 4, raise --budget for more):
+  MyApp.Web.show/1  x2  lib/web.ex:8"
+        );
+    }
+
+    #[test]
+    fn test_to_table_empty() {
+        let result = ExploreResult {
+            seed: "MyApp.Missing".to_string(),
+            budget: 800,
+            estimated_tokens: 6,
+            definitions: vec![],
+            callers: vec![],
+            callees: vec![],
+            omitted_definitions: 0,
+            omitted_callers: 0,
+            omitted_callees: 0,
+        };
+
+        assert_eq!(
+            result.to_table(),
+            "Explore: MyApp.Missing\n(~6 of 800 tokens)\n\nNo definition or calls found."
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use std::error::Error;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::graph::{build_graph, GraphEdge, GraphNode};
+use super::{ExportCmd, ExportFormat};
+use crate::commands::Execute;
+use db::query_builders::{compile_pattern, QuerySettings};
+use db::queries::export::{find_call_sites, find_definitions, CallSite, Definition};
+use db::queries::graph_snapshot::export_graph_snapshot;
+
+/// Result of the export command
+#[derive(Debug, Serialize)]
+pub struct ExportResult {
+    pub kind: ExportFormat,
+    /// Directory the file paths are relative to, recorded in the cscope header
+    pub directory: String,
+    pub definitions: Vec<Definition>,
+    /// Call sites, only collected for cscope
+    pub calls: Vec<CallSite>,
+    /// Graph snapshot document, only written for snapshot
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub snapshot: String,
+    /// Call graph functions, only collected for cytoscape and d3
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<GraphNode>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub edges: Vec<GraphEdge>,
+}
+
+impl Execute for ExportCmd {
+    type Output = ExportResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        if self.kind == ExportFormat::Snapshot {
+            return Ok(ExportResult {
+                kind: self.kind,
+                directory: std::env::current_dir()?.display().to_string(),
+                definitions: Vec::new(),
+                calls: Vec::new(),
+                snapshot: export_graph_snapshot(db, &self.project)?,
+                nodes: Vec::new(),
+                edges: Vec::new(),
+            });
+        }
+
+        if self.kind.is_graph() {
+            let filter = match &self.module {
+                Some(pattern) if self.regex => Some(compile_pattern(pattern)?),
+                Some(pattern) => Some(Regex::new(&regex::escape(pattern))?),
+                None => None,
+            };
+            let (nodes, edges) = build_graph(db, &self.project, filter.as_ref(), self.depth, settings)?;
+            return Ok(ExportResult {
+                kind: self.kind,
+                directory: std::env::current_dir()?.display().to_string(),
+                definitions: Vec::new(),
+                calls: Vec::new(),
+                snapshot: String::new(),
+                nodes,
+                edges,
+            });
+        }
+
+        let definitions = find_definitions(db, &self.project)?;
+        let calls = match self.kind {
+            ExportFormat::Cscope => find_call_sites(db, &self.project)?,
+            _ => Vec::new(),
+        };
+
+        Ok(ExportResult {
+            kind: self.kind,
+            directory: std::env::current_dir()?.display().to_string(),
+            definitions,
+            calls,
+            snapshot: String::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    crate::shared_fixture! {
+        fixture_name: populated_db,
+        fixture_type: call_graph,
+        project: "test_project",
+    }
+
+    #[rstest]
+    fn test_export_collects_calls_for_cscope_only(populated_db: db::DbInstance) {
+        let export = |kind| {
+            ExportCmd {
+                kind,
+                project: "test_project".to_string(),
+                module: None,
+                regex: false,
+                depth: 1,
+            }
+            .execute(&populated_db, &QuerySettings::default())
+            .expect("Export should succeed")
+        };
+
+        let ctags = export(ExportFormat::Ctags);
+        assert!(ctags.definitions.iter().any(|d| d.name == "get_user"));
+        assert!(ctags.calls.is_empty());
+
+        let cscope = export(ExportFormat::Cscope);
+        assert_eq!(cscope.definitions.len(), ctags.definitions.len());
+        assert!(!cscope.calls.is_empty());
+
+        let snapshot = export(ExportFormat::Snapshot);
+        assert!(snapshot.definitions.is_empty());
+        let loaded = db::open_mem_db();
+        let project = db::queries::graph_snapshot::load_graph_snapshot(&loaded, &snapshot.snapshot).unwrap();
+        assert_eq!(project, "test_project");
+
+        let d3 = export(ExportFormat::D3);
+        assert!(d3.definitions.is_empty());
+        assert_eq!(d3.edges.len(), 10);
+        assert!(d3.nodes.iter().any(|n| n.id == "MyApp.Controller.index"));
+    }
+}
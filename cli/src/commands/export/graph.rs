@@ -0,0 +1,181 @@
+//! Function call graph with node metrics for the visualization formats.
+//!
+//! Nodes are functions (module and name, like centrality), edges the distinct
+//! calls between them. Metrics and clusters are computed over the whole
+//! project before the graph is scoped, so they do not change with the scope.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::error::Error;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::graph::WeightedGraph;
+use db::queries::centrality::find_function_edges;
+use db::queries::export::find_function_complexity;
+use db::query_builders::QuerySettings;
+
+/// A function of the exported graph
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphNode {
+    /// Qualified name, `Module.function`
+    pub id: String,
+    pub module: String,
+    pub function: String,
+    /// Distinct calling functions
+    pub fan_in: usize,
+    /// Distinct called functions
+    pub fan_out: usize,
+    /// Cyclomatic complexity summed over clauses, 0 for functions without a location
+    pub complexity: i64,
+    /// Louvain community of the function in the project's call graph
+    pub cluster: usize,
+}
+
+/// A call between two functions of the exported graph
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+}
+
+/// Build the call graph of a project.
+///
+/// With a module filter, the graph holds the functions of matching modules
+/// and those within `depth` calls of them, in either direction.
+pub fn build_graph(
+    db: &db::DbInstance,
+    project: &str,
+    filter: Option<&Regex>,
+    depth: u32,
+    settings: &QuerySettings,
+) -> Result<(Vec<GraphNode>, Vec<GraphEdge>), Box<dyn Error>> {
+    let complexity: HashMap<(String, String), i64> = find_function_complexity(db, project)?
+        .into_iter()
+        .map(|m| ((m.module, m.name), m.complexity))
+        .collect();
+    let calls: Vec<((String, String), (String, String))> = find_function_edges(db, project, settings)?
+        .into_iter()
+        .map(|e| ((e.caller_module, e.caller_function), (e.callee_module, e.callee_function)))
+        .collect();
+
+    // Sorted functions give stable node indices, and with them stable clusters
+    let functions: Vec<(String, String)> = complexity
+        .keys()
+        .cloned()
+        .chain(calls.iter().flat_map(|(from, to)| [from.clone(), to.clone()]))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let index: HashMap<&(String, String), usize> = functions.iter().enumerate().map(|(i, f)| (f, i)).collect();
+    let edges: Vec<(usize, usize)> = calls.iter().map(|(from, to)| (index[from], index[to])).collect();
+
+    let mut fan_in = vec![0; functions.len()];
+    let mut fan_out = vec![0; functions.len()];
+    let mut neighbours = vec![Vec::new(); functions.len()];
+    for &(from, to) in &edges {
+        fan_out[from] += 1;
+        fan_in[to] += 1;
+        neighbours[from].push(to);
+        neighbours[to].push(from);
+    }
+
+    let membership = WeightedGraph::new(functions.len(), edges.iter().map(|&(a, b)| (a, b, 1.0))).louvain();
+    // Number clusters by their first function, so ids do not depend on the algorithm's labels
+    let mut clusters: BTreeMap<usize, usize> = BTreeMap::new();
+    for &label in &membership {
+        let next = clusters.len();
+        clusters.entry(label).or_insert(next);
+    }
+
+    let included = match filter {
+        Some(re) => within_depth(&functions, &neighbours, re, depth),
+        None => vec![true; functions.len()],
+    };
+
+    let id = |(module, function): &(String, String)| format!("{}.{}", module, function);
+    let nodes = functions
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| included[*i])
+        .map(|(i, function)| GraphNode {
+            id: id(function),
+            module: function.0.clone(),
+            function: function.1.clone(),
+            fan_in: fan_in[i],
+            fan_out: fan_out[i],
+            complexity: complexity.get(function).copied().unwrap_or(0),
+            cluster: clusters[&membership[i]],
+        })
+        .collect();
+    let edges = edges
+        .iter()
+        .filter(|(from, to)| included[*from] && included[*to])
+        .map(|&(from, to)| GraphEdge {
+            source: id(&functions[from]),
+            target: id(&functions[to]),
+        })
+        .collect();
+
+    Ok((nodes, edges))
+}
+
+/// Mark the functions of matching modules and those within `depth` calls of them
+fn within_depth(functions: &[(String, String)], neighbours: &[Vec<usize>], re: &Regex, depth: u32) -> Vec<bool> {
+    let mut included = vec![false; functions.len()];
+    let mut queue = VecDeque::new();
+    for (i, (module, _)) in functions.iter().enumerate() {
+        if re.is_match(module) {
+            included[i] = true;
+            queue.push_back((i, 0));
+        }
+    }
+    while let Some((i, distance)) = queue.pop_front() {
+        if distance == depth {
+            continue;
+        }
+        for &j in &neighbours[i] {
+            if !included[j] {
+                included[j] = true;
+                queue.push_back((j, distance + 1));
+            }
+        }
+    }
+    included
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_graph_computes_metrics() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let (nodes, edges) = build_graph(&db, "test_project", None, 1, &QuerySettings::default()).unwrap();
+
+        assert_eq!(edges.len(), 10);
+        let node = |id: &str| nodes.iter().find(|n| n.id == id).unwrap();
+        let index = node("MyApp.Controller.index");
+        assert_eq!(index.fan_out, edges.iter().filter(|e| e.source == index.id).count());
+        assert!(index.complexity > 0);
+        for edge in &edges {
+            assert!(node(&edge.source).fan_out > 0);
+            assert!(node(&edge.target).fan_in > 0);
+        }
+    }
+
+    #[test]
+    fn test_build_graph_scopes_by_module_and_depth() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let re = Regex::new("MyApp\\.Controller").unwrap();
+
+        let (direct, _) = build_graph(&db, "test_project", Some(&re), 0, &QuerySettings::default()).unwrap();
+        assert!(direct.iter().all(|n| n.module == "MyApp.Controller"));
+
+        let (nearby, edges) = build_graph(&db, "test_project", Some(&re), 1, &QuerySettings::default()).unwrap();
+        assert!(nearby.len() > direct.len());
+        assert!(nearby.iter().any(|n| n.module == "MyApp.Accounts"));
+        let ids: BTreeSet<&str> = nearby.iter().map(|n| n.id.as_str()).collect();
+        assert!(edges.iter().all(|e| ids.contains(e.source.as_str()) && ids.contains(e.target.as_str())));
+    }
+}
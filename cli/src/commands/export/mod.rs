@@ -0,0 +1,84 @@
+mod execute;
+mod graph;
+mod output;
+
+use std::error::Error;
+
+use clap::{Args, ValueEnum};
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+use serde::Serialize;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Format of the export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Tags file of function definitions (vi, Emacs, most editors)
+    Ctags,
+    /// Uncompressed cscope cross-reference of definitions and calls
+    Cscope,
+    /// JSON graph snapshot of the project, loaded by the WebAssembly build
+    Snapshot,
+    /// Cytoscape.js elements JSON of the function call graph
+    Cytoscape,
+    /// D3 force-layout nodes and links JSON of the function call graph
+    D3,
+}
+
+impl ExportFormat {
+    /// Whether the format is a call graph for web visualizers
+    fn is_graph(self) -> bool {
+        matches!(self, ExportFormat::Cytoscape | ExportFormat::D3)
+    }
+}
+
+/// Export function definitions and calls as a ctags or cscope index
+///
+/// Gives editors jump-to-definition and find-callers over the imported
+/// codebase without running a language-specific indexer. The index is built
+/// from the call graph, so it covers functions and calls only. `snapshot`
+/// writes every row of the project as JSON instead, for the WebAssembly
+/// build of the query engine to load in a browser. `cytoscape` and `d3`
+/// write the function call graph for web graph visualizers, with fan-in,
+/// fan-out, complexity and cluster id on every node.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search export ctags > tags                 # Tags file for jump-to-definition
+  code_search export cscope > cscope.out          # Open with: cscope -d
+  code_search export ctags --project my_app > tags
+  code_search export snapshot > graph.json        # For the in-browser engine
+  code_search export cytoscape --module MyApp.Accounts --depth 2 > accounts.json
+  code_search export d3 > graph.json              # Whole call graph for a D3 force layout
+")]
+pub struct ExportCmd {
+    /// Index format to generate
+    #[arg(value_enum)]
+    pub kind: ExportFormat,
+
+    /// Project to export
+    #[arg(long, default_value = "default")]
+    pub project: String,
+
+    /// Only graph functions of matching modules (cytoscape and d3; substring match by default, regex with --regex)
+    #[arg(short, long)]
+    pub module: Option<String>,
+
+    /// Treat the module pattern as a regular expression
+    #[arg(long)]
+    pub regex: bool,
+
+    /// Also graph functions within this many calls of the matching modules (0-20)
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(u32).range(0..=20))]
+    pub depth: u32,
+}
+
+impl CommandRunner for ExportCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
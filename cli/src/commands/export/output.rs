@@ -0,0 +1,273 @@
+//! ctags, cscope and graph visualization rendering for export command results.
+
+use std::collections::BTreeMap;
+
+use serde_json::json;
+
+use super::execute::ExportResult;
+use super::ExportFormat;
+use crate::output::Outputable;
+
+/// Version of the cscope cross-reference format written
+const CSCOPE_VERSION: u32 = 15;
+
+/// Symbols of a cscope file by line, each with its order within the line
+type CscopeLines = BTreeMap<i64, Vec<(u8, String)>>;
+
+impl Outputable for ExportResult {
+    fn to_table(&self) -> String {
+        match self.kind {
+            ExportFormat::Ctags => self.to_ctags(),
+            ExportFormat::Cscope => self.to_cscope(),
+            ExportFormat::Snapshot => self.snapshot.clone(),
+            ExportFormat::Cytoscape => self.to_cytoscape(),
+            ExportFormat::D3 => self.to_d3(),
+        }
+    }
+}
+
+/// Single-letter ctags kind: `a` for macros, `f` for functions
+fn ctags_kind(kind: &str) -> &'static str {
+    if kind.starts_with("defmacro") {
+        "a"
+    } else {
+        "f"
+    }
+}
+
+impl ExportResult {
+    /// Sorted tags file in the extended format.
+    ///
+    /// Every function is tagged by its name and its qualified name
+    /// (`MyApp.Accounts.get_user`); private ones carry the `file:` scope.
+    fn to_ctags(&self) -> String {
+        let mut tags = Vec::new();
+        for definition in &self.definitions {
+            let mut fields = format!(
+                "{};\"\t{}\tmodule:{}\tarity:{}",
+                definition.start_line,
+                ctags_kind(&definition.kind),
+                definition.module,
+                definition.arity
+            );
+            if definition.kind.ends_with('p') {
+                fields.push_str("\tfile:");
+            }
+            let qualified = format!("{}.{}", definition.module, definition.name);
+            for tag in [&definition.name, &qualified] {
+                tags.push(format!("{}\t{}\t{}", tag, definition.file, fields));
+            }
+        }
+        tags.sort();
+        tags.dedup();
+
+        let mut lines = vec![
+            "!_TAG_FILE_FORMAT\t2\t/extended format; --format=1 will not append ;\" to lines/".to_string(),
+            "!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted, 2=foldcase/".to_string(),
+            "!_TAG_PROGRAM_NAME\tcode_search\t//".to_string(),
+        ];
+        lines.extend(tags);
+        lines.join("\n")
+    }
+
+    /// Uncompressed (`-c`) cscope cross-reference.
+    ///
+    /// Each file lists, by line, function definitions (`$`), calls (`` ` ``)
+    /// and function ends (`}`); cscope attributes a call to the definition
+    /// preceding it. No source text is stored, so open it with `cscope -d`.
+    fn to_cscope(&self) -> String {
+        // file -> line -> (order, mark and symbol); definitions open a line, ends close it
+        let mut files: BTreeMap<&str, CscopeLines> = BTreeMap::new();
+        for definition in &self.definitions {
+            let lines = files.entry(&definition.file).or_default();
+            lines
+                .entry(definition.start_line)
+                .or_default()
+                .push((0, format!("${}", definition.name)));
+            lines.entry(definition.end_line).or_default().push((2, "}".to_string()));
+        }
+        for call in &self.calls {
+            files
+                .entry(&call.file)
+                .or_default()
+                .entry(call.line)
+                .or_default()
+                .push((1, format!("`{}", call.callee_function)));
+        }
+
+        let mut body = String::new();
+        for (file, lines) in &files {
+            body.push_str(&format!("\t@{}\n\n", file));
+            for (line, symbols) in lines {
+                let mut symbols = symbols.clone();
+                symbols.sort();
+                let symbols: Vec<String> = symbols.into_iter().map(|(_, symbol)| format!("\t{}\n", symbol)).collect();
+                body.push_str(&format!("{} \n{}\n\n", line, symbols.join(" \n")));
+            }
+        }
+        body.push_str("\t@\n");
+
+        let header_len = format!("cscope {} {} -c {:010}\n", CSCOPE_VERSION, self.directory, 0).len();
+        let names: usize = files.keys().map(|file| file.len() + 1).sum();
+        let mut trailer = format!("1\n.\n0\n{}\n{}\n", files.len(), names);
+        for file in files.keys() {
+            trailer.push_str(file);
+            trailer.push('\n');
+        }
+
+        format!(
+            "cscope {} {} -c {:010}\n{}{}",
+            CSCOPE_VERSION,
+            self.directory,
+            header_len + body.len(),
+            body,
+            trailer
+        )
+    }
+
+    /// Cytoscape.js elements: node metrics and edge endpoints under `data`
+    fn to_cytoscape(&self) -> String {
+        let nodes: Vec<_> = self.nodes.iter().map(|node| json!({ "data": node })).collect();
+        let edges: Vec<_> = self
+            .edges
+            .iter()
+            .map(|edge| json!({ "data": { "id": format!("{}->{}", edge.source, edge.target), "source": edge.source, "target": edge.target } }))
+            .collect();
+        json!({ "elements": { "nodes": nodes, "edges": edges } }).to_string()
+    }
+
+    /// D3 force-layout graph: nodes with their metrics, links by node id
+    fn to_d3(&self) -> String {
+        json!({ "nodes": self.nodes, "links": self.edges }).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::export::{CallSite, Definition};
+    use crate::commands::export::graph::{GraphEdge, GraphNode};
+
+    fn definition(name: &str, kind: &str, start_line: i64, end_line: i64) -> Definition {
+        Definition {
+            module: "MyApp.Accounts".to_string(),
+            name: name.to_string(),
+            arity: 1,
+            kind: kind.to_string(),
+            file: "lib/accounts.ex".to_string(),
+            start_line,
+            end_line,
+        }
+    }
+
+    fn result(kind: ExportFormat) -> ExportResult {
+        ExportResult {
+            kind,
+            directory: "/src/my_app".to_string(),
+            definitions: vec![definition("get_user", "def", 2, 7), definition("load", "defp", 9, 11)],
+            calls: vec![
+                CallSite {
+                    file: "lib/accounts.ex".to_string(),
+                    line: 3,
+                    caller_module: "MyApp.Accounts".to_string(),
+                    caller_function: "get_user/1".to_string(),
+                    callee_module: "MyApp.Repo".to_string(),
+                    callee_function: "get".to_string(),
+                },
+                CallSite {
+                    file: "lib/accounts.ex".to_string(),
+                    line: 3,
+                    caller_module: "MyApp.Accounts".to_string(),
+                    caller_function: "get_user/1".to_string(),
+                    callee_module: "MyApp.Accounts".to_string(),
+                    callee_function: "load".to_string(),
+                },
+            ],
+            snapshot: String::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_to_ctags() {
+        assert_eq!(
+            result(ExportFormat::Ctags).to_table(),
+            "\
+!_TAG_FILE_FORMAT\t2\t/extended format; --format=1 will not append ;\" to lines/
+!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted, 2=foldcase/
+!_TAG_PROGRAM_NAME\tcode_search\t//
+MyApp.Accounts.get_user\tlib/accounts.ex\t2;\"\tf\tmodule:MyApp.Accounts\tarity:1
+MyApp.Accounts.load\tlib/accounts.ex\t9;\"\tf\tmodule:MyApp.Accounts\tarity:1\tfile:
+get_user\tlib/accounts.ex\t2;\"\tf\tmodule:MyApp.Accounts\tarity:1
+load\tlib/accounts.ex\t9;\"\tf\tmodule:MyApp.Accounts\tarity:1\tfile:"
+        );
+    }
+
+    fn graph_result(kind: ExportFormat) -> ExportResult {
+        let node = |function: &str, fan_in, fan_out| GraphNode {
+            id: format!("MyApp.Accounts.{}", function),
+            module: "MyApp.Accounts".to_string(),
+            function: function.to_string(),
+            fan_in,
+            fan_out,
+            complexity: 2,
+            cluster: 0,
+        };
+        ExportResult {
+            nodes: vec![node("get_user", 0, 1), node("load", 1, 0)],
+            edges: vec![GraphEdge {
+                source: "MyApp.Accounts.get_user".to_string(),
+                target: "MyApp.Accounts.load".to_string(),
+            }],
+            ..result(kind)
+        }
+    }
+
+    #[test]
+    fn test_to_cytoscape() {
+        let output: serde_json::Value = serde_json::from_str(&graph_result(ExportFormat::Cytoscape).to_table()).unwrap();
+        let elements = &output["elements"];
+        assert_eq!(elements["nodes"][1]["data"]["id"], "MyApp.Accounts.load");
+        assert_eq!(elements["nodes"][1]["data"]["fan_in"], 1);
+        assert_eq!(elements["nodes"][1]["data"]["cluster"], 0);
+        assert_eq!(
+            elements["edges"][0]["data"],
+            json!({
+                "id": "MyApp.Accounts.get_user->MyApp.Accounts.load",
+                "source": "MyApp.Accounts.get_user",
+                "target": "MyApp.Accounts.load"
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_d3() {
+        let output: serde_json::Value = serde_json::from_str(&graph_result(ExportFormat::D3).to_table()).unwrap();
+        assert_eq!(output["nodes"][0]["complexity"], 2);
+        assert_eq!(output["links"][0]["target"], "MyApp.Accounts.load");
+    }
+
+    #[test]
+    fn test_to_cscope() {
+        let output = result(ExportFormat::Cscope).to_table();
+        let (header, rest) = output.split_once('\n').expect("Header line");
+        assert!(header.starts_with("cscope 15 /src/my_app -c "));
+
+        // The header ends with the byte offset of the trailer
+        let offset: usize = header.rsplit(' ').next().unwrap().parse().expect("Trailer offset");
+        assert_eq!(&output[offset..], "1\n.\n0\n1\n16\nlib/accounts.ex\n");
+
+        let body = &rest[..offset - header.len() - 1];
+        assert_eq!(
+            body,
+            "\t@lib/accounts.ex\n\n\
+             2 \n\t$get_user\n\n\n\
+             3 \n\t`get\n \n\t`load\n\n\n\
+             7 \n\t}\n\n\n\
+             9 \n\t$load\n\n\n\
+             11 \n\t}\n\n\n\
+             \t@\n"
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Source tree walking, extraction and import.
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::ExtractCmd;
+use crate::commands::Execute;
+use crate::config::{Config, ExtractorConfig};
+use crate::extractors::{BUILTIN, BUILTIN_UNAVAILABLE, ExtractorImport, ImportSettings, group_by_extractor};
+use db::query_builders::QuerySettings;
+
+/// Source files of one extractor
+#[derive(Debug, Serialize)]
+pub struct ExtractedFiles {
+    pub extractor: String,
+    pub files: Vec<String>,
+}
+
+/// Result of the extract command
+#[derive(Debug, Serialize)]
+pub struct ExtractResult {
+    pub dry_run: bool,
+    /// Files found for each extractor, in configuration order
+    pub extractors: Vec<ExtractedFiles>,
+    /// One import per extractor, empty on a dry run
+    pub imports: Vec<ExtractorImport>,
+}
+
+impl Execute for ExtractCmd {
+    type Output = ExtractResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        if self.builtin && !cfg!(feature = "builtin-extractor") {
+            return Err(BUILTIN_UNAVAILABLE.into());
+        }
+        let extractors = if self.builtin {
+            // Walks the files the Elixir extractor would read
+            vec![ExtractorConfig {
+                name: BUILTIN.to_string(),
+                ..ExtractorConfig::elixir()
+            }]
+        } else {
+            selected(Config::load_or_default(&self.config)?.extractors(), &self.only)?
+        };
+
+        let mut files = Vec::new();
+        for path in &self.paths {
+            if !path.exists() {
+                return Err(format!("Path '{}' does not exist", path.display()).into());
+            }
+            walk(path, &extractors, &mut Vec::new(), &mut files)?;
+        }
+        let groups = group_by_extractor(&extractors, &files);
+
+        let found = groups
+            .iter()
+            .map(|(extractor, files)| ExtractedFiles {
+                extractor: extractor.name.clone(),
+                files: files.clone(),
+            })
+            .collect();
+        if self.dry_run {
+            return Ok(ExtractResult {
+                dry_run: true,
+                extractors: found,
+                imports: Vec::new(),
+            });
+        }
+
+        let import_settings = ImportSettings {
+            project: self.project,
+            mix_env: self.mix_env,
+            commit: None,
+            modified_at: None,
+            config: self.config,
+        };
+        let mut imports = Vec::new();
+        for (i, (extractor, files)) in groups.iter().enumerate() {
+            // Clear before the first import only, and snapshot after the last
+            let (clear, snapshot) = (self.clear && i == 0, i + 1 == groups.len());
+            imports.push(if self.builtin {
+                import_settings.extract_builtin_and_import(db, files, clear, snapshot, settings)?
+            } else {
+                import_settings.extract_and_import(db, extractor, files, clear, snapshot, settings)?
+            });
+        }
+
+        Ok(ExtractResult {
+            dry_run: false,
+            extractors: found,
+            imports,
+        })
+    }
+}
+
+/// The extractors named by `--only`, or all of them
+fn selected(extractors: Vec<ExtractorConfig>, only: &[String]) -> Result<Vec<ExtractorConfig>, Box<dyn Error>> {
+    if let Some(unknown) = only.iter().find(|name| !extractors.iter().any(|e| &e.name == *name)) {
+        let names: Vec<&str> = extractors.iter().map(|e| e.name.as_str()).collect();
+        return Err(format!("Unknown extractor '{}' (configured: {})", unknown, names.join(", ")).into());
+    }
+    Ok(extractors
+        .into_iter()
+        .filter(|e| only.is_empty() || only.contains(&e.name))
+        .collect())
+}
+
+/// Collect the files under `path` that an extractor reads, in sorted order.
+///
+/// Hidden directories are skipped. `dirs` are the directories walked into
+/// so far; a file is left out when the extractor reading it excludes one of
+/// them, and directories every extractor excludes are not walked at all.
+fn walk(path: &Path, extractors: &[ExtractorConfig], dirs: &mut Vec<String>, files: &mut Vec<String>) -> Result<(), Box<dyn Error>> {
+    if path.is_file() {
+        let excludes = |e: &ExtractorConfig| dirs.iter().any(|dir| e.exclude.contains(dir));
+        if extractors.iter().find(|e| e.reads(path)).is_some_and(|e| !excludes(e)) {
+            let file = path.display().to_string();
+            files.push(file.strip_prefix("./").map(str::to_string).unwrap_or(file));
+        }
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)
+        .map_err(|e| format!("Failed to read directory '{}': {}", path.display(), e))?
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = entry.path();
+        if path.is_dir() {
+            if name.starts_with('.') || extractors.iter().all(|e| e.exclude.contains(&name)) {
+                continue;
+            }
+            dirs.push(name);
+            walk(&path, extractors, dirs, files)?;
+            dirs.pop();
+        } else {
+            walk(&path, extractors, dirs, files)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use db::queries::changes::find_function_clauses;
+    use tempfile::TempDir;
+
+    /// A source tree with Elixir and Python files, and a config whose
+    /// extractors copy `graph` to their output
+    fn tree(graph: &str) -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        for file in ["lib/a.ex", "lib/b.exs", "lib/README.md", "deps/dep/c.ex", ".elixir_ls/d.ex", "scripts/e.py", "scripts/deps/f.py"] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let graph_path = dir.path().join("graph.json");
+        fs::write(&graph_path, graph).unwrap();
+        let config = serde_json::json!({
+            "extractors": [
+                {"name": "elixir", "command": ["sh", "-c", "cp \"$0\" \"$1\"", graph_path, "{output}"], "extensions": ["ex", "exs"], "exclude": ["deps"]},
+                {"name": "python", "command": ["sh", "-c", "cp \"$0\" \"$1\"", graph_path, "{output}"], "extensions": ["py"]}
+            ]
+        });
+        let path = dir.path().join("config.json");
+        fs::write(&path, config.to_string()).unwrap();
+        (dir, path)
+    }
+
+    fn extract_cmd(dir: &TempDir, config: PathBuf) -> ExtractCmd {
+        ExtractCmd {
+            paths: vec![dir.path().to_path_buf()],
+            only: Vec::new(),
+            builtin: false,
+            project: "test_project".to_string(),
+            clear: false,
+            mix_env: "dev".to_string(),
+            dry_run: true,
+            config,
+        }
+    }
+
+    fn relative(dir: &TempDir, files: &[String]) -> Vec<String> {
+        let root = format!("{}/", dir.path().display());
+        files.iter().map(|file| file.strip_prefix(&root).unwrap().to_string()).collect()
+    }
+
+    #[test]
+    fn test_extract_dry_run_lists_files_per_extractor() {
+        let (dir, config) = tree("{}");
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = extract_cmd(&dir, config).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert!(result.imports.is_empty());
+        assert_eq!(result.extractors.len(), 2);
+        assert_eq!(result.extractors[0].extractor, "elixir");
+        assert_eq!(relative(&dir, &result.extractors[0].files), ["lib/a.ex", "lib/b.exs"]);
+        // deps is only excluded from the elixir extractor
+        assert_eq!(relative(&dir, &result.extractors[1].files), ["scripts/deps/f.py", "scripts/e.py"]);
+    }
+
+    #[test]
+    fn test_extract_only() {
+        let (dir, config) = tree("{}");
+        let db = db::test_utils::call_graph_db("test_project");
+        let mut cmd = extract_cmd(&dir, config.clone());
+        cmd.only = vec!["python".to_string()];
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert_eq!(result.extractors.len(), 1);
+        assert_eq!(result.extractors[0].extractor, "python");
+
+        let mut cmd = extract_cmd(&dir, config);
+        cmd.only = vec!["rust".to_string()];
+        let err = cmd.execute(&db, &QuerySettings::default()).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown extractor 'rust' (configured: elixir, python)");
+    }
+
+    #[test]
+    fn test_extract_imports_and_clears() {
+        let graph = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Fresh": {
+                    "run/0:3": {"name": "run", "arity": 0, "file": "lib/fresh.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 4}
+                }
+            },
+            "calls": []
+        }"#;
+        let (dir, config) = tree(graph);
+        let db = db::test_utils::call_graph_db("test_project");
+        let mut cmd = extract_cmd(&dir, config);
+        cmd.dry_run = false;
+        cmd.clear = true;
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.imports.len(), 2);
+        assert!(result.imports[0].import.result.cleared);
+        assert!(!result.imports[1].import.result.cleared);
+        assert_eq!(result.imports[1].files, 2);
+
+        let clauses = find_function_clauses(&db, "test_project").unwrap();
+        assert!(clauses.iter().all(|c| c.file == "lib/fresh.ex"));
+        assert!(!clauses.is_empty());
+    }
+
+    #[cfg(feature = "builtin-extractor")]
+    #[test]
+    fn test_extract_builtin() {
+        let (dir, config) = tree("{}");
+        fs::write(
+            dir.path().join("lib/a.ex"),
+            "defmodule MyApp.Fresh do\n  def run, do: helper(1)\n  defp helper(x), do: MyApp.Repo.get(x)\nend\n",
+        )
+        .unwrap();
+        let db = db::test_utils::call_graph_db("test_project");
+        let mut cmd = extract_cmd(&dir, config);
+        cmd.builtin = true;
+        cmd.dry_run = false;
+        cmd.clear = true;
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.imports.len(), 1);
+        assert_eq!(result.imports[0].extractor, "builtin");
+        assert_eq!(result.imports[0].files, 2);
+        assert_eq!(result.imports[0].import.result.calls_imported, 2);
+
+        let clauses = find_function_clauses(&db, "test_project").unwrap();
+        let names: Vec<&str> = clauses.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["run", "helper"]);
+    }
+}
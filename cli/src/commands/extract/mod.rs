@@ -0,0 +1,74 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::output::{OutputFormat, Outputable};
+
+/// Extract a source tree with the configured extractors and import the result
+///
+/// Walks the given files and directories, hands each source file to the first
+/// extractor under `extractors` in the config file that reads its extension
+/// (by default the Elixir extractor: `mix compile --debug-info`, then
+/// `ex_ast`), and imports each extractor's call graph in place of the stored
+/// rows of its files. Hidden directories and each extractor's `exclude`
+/// directories are skipped. `--builtin` parses Elixir files with the built-in
+/// tree-sitter extractor instead, for projects that cannot be compiled.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search extract                              # Extract and import the current directory
+  code_search extract lib test --clear             # Replace the project with lib/ and test/
+  code_search extract --only python                # Run only the python extractor
+  code_search extract --dry-run                    # List the files each extractor would read
+  code_search extract --builtin --clear            # Approximate graph without compiling
+  code_search extract -p my_app --mix-env test     # Extract in the test environment
+")]
+pub struct ExtractCmd {
+    /// Files and directories to extract
+    #[arg(default_value = ".")]
+    pub paths: Vec<PathBuf>,
+
+    /// Run only the named extractor (repeatable)
+    #[arg(long, value_name = "NAME")]
+    pub only: Vec<String>,
+
+    /// Parse Elixir files with the built-in tree-sitter extractor instead of
+    /// the configured ones; approximate, but needs no compile
+    #[arg(long, conflicts_with = "only")]
+    pub builtin: bool,
+
+    /// Project name for namespacing (allows multiple projects in same DB)
+    #[arg(short, long, default_value = "default")]
+    pub project: String,
+
+    /// Clear the project's data before importing
+    #[arg(long)]
+    pub clear: bool,
+
+    /// Mix environment the extractors run in (`MIX_ENV` and `{mix_env}`)
+    #[arg(long, default_value = "dev")]
+    pub mix_env: String,
+
+    /// List the files each extractor would read without running anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Path to the config file with the `extractors` settings
+    #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+    pub config: PathBuf,
+}
+
+impl CommandRunner for ExtractCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
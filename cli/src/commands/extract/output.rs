@@ -0,0 +1,60 @@
+//! Output formatting for extract command results.
+
+use super::execute::ExtractResult;
+use crate::output::Outputable;
+
+impl Outputable for ExtractResult {
+    fn to_table(&self) -> String {
+        if self.extractors.is_empty() {
+            return "No source files found for the configured extractors.\n".to_string();
+        }
+
+        let mut output = String::new();
+        if self.dry_run {
+            for found in &self.extractors {
+                output.push_str(&format!("{} ({} file(s)):\n", found.extractor, found.files.len()));
+                for file in &found.files {
+                    output.push_str(&format!("  {}\n", file));
+                }
+            }
+            return output;
+        }
+
+        let files: usize = self.imports.iter().map(|import| import.files).sum();
+        output.push_str(&format!("Extracted {} file(s) with {} extractor(s).\n", files, self.imports.len()));
+        for import in &self.imports {
+            output.push_str(&format!("\n{} ({} file(s)):\n", import.extractor, import.files));
+            output.push_str(&import.import.to_table());
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::extract::execute::ExtractedFiles;
+
+    #[test]
+    fn test_extract_table_dry_run() {
+        let result = ExtractResult {
+            dry_run: true,
+            extractors: vec![ExtractedFiles {
+                extractor: "elixir".to_string(),
+                files: vec!["lib/a.ex".to_string(), "lib/b.ex".to_string()],
+            }],
+            imports: Vec::new(),
+        };
+        assert_eq!(result.to_table(), "elixir (2 file(s)):\n  lib/a.ex\n  lib/b.ex\n");
+    }
+
+    #[test]
+    fn test_extract_table_without_files() {
+        let result = ExtractResult {
+            dry_run: false,
+            extractors: Vec::new(),
+            imports: Vec::new(),
+        };
+        assert_eq!(result.to_table(), "No source files found for the configured extractors.\n");
+    }
+}
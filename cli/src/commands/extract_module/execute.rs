@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::ExtractModuleCmd;
+use crate::commands::Execute;
+use crate::graph::WeightedGraph;
+use db::queries::centrality::{find_function_edges, FunctionEdge};
+use db::queries::file::find_functions_in_module;
+use db::query_builders::QuerySettings;
+
+/// A call that crosses the boundary of the new module
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct BoundaryCall {
+    /// `Module.function`
+    pub caller: String,
+    /// `Module.function`
+    pub callee: String,
+}
+
+/// Cohesion of a module: internal calls / (internal + outgoing + incoming)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Cohesion {
+    pub internal_calls: usize,
+    pub outgoing_calls: usize,
+    pub incoming_calls: usize,
+    pub cohesion: f64,
+}
+
+impl Cohesion {
+    /// Cohesion of the function set `members`, counting distinct function edges
+    fn of(members: &HashSet<(String, String)>, edges: &[FunctionEdge]) -> Self {
+        let mut result = Cohesion::default();
+        for edge in edges {
+            let caller_in = members.contains(&(edge.caller_module.clone(), edge.caller_function.clone()));
+            let callee_in = members.contains(&(edge.callee_module.clone(), edge.callee_function.clone()));
+            match (caller_in, callee_in) {
+                (true, true) => result.internal_calls += 1,
+                (true, false) => result.outgoing_calls += 1,
+                (false, true) => result.incoming_calls += 1,
+                (false, false) => {}
+            }
+        }
+        let total = result.internal_calls + result.outgoing_calls + result.incoming_calls;
+        if total > 0 {
+            result.cohesion = result.internal_calls as f64 / total as f64;
+        }
+        result
+    }
+}
+
+/// Result of the extract-module command
+#[derive(Debug, Serialize)]
+pub struct ExtractModuleResult {
+    pub module: String,
+    pub new_module: String,
+    /// True when the functions were picked from internal call clusters
+    pub automatic: bool,
+    /// Functions that move to the new module
+    pub moved: Vec<String>,
+    /// Functions that stay in the module
+    pub remaining: Vec<String>,
+    /// Calls between the functions that stay and those that move
+    pub internal_crossings: Vec<BoundaryCall>,
+    /// Calls from other modules into moved functions; their callers must be updated
+    pub external_callers: Vec<BoundaryCall>,
+    /// Modules the moved functions call, which the new module will depend on
+    pub new_dependencies: Vec<String>,
+    pub cohesion_before: Cohesion,
+    pub cohesion_after: Cohesion,
+    pub new_module_cohesion: Cohesion,
+    /// Internal call clusters the automatic pick chose from
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub clusters: Vec<Vec<String>>,
+}
+
+impl Execute for ExtractModuleCmd {
+    type Output = ExtractModuleResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let functions: Vec<String> =
+            find_functions_in_module(db, &self.module, &self.project, false, u32::MAX, settings)?
+                .into_iter()
+                .map(|f| f.name)
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+        if functions.is_empty() {
+            return Err(format!("Module {} has no functions", self.module).into());
+        }
+
+        let edges = find_function_edges(db, &self.project, settings)?;
+
+        let automatic = self.functions.is_empty();
+        let (moved, clusters): (BTreeSet<String>, Vec<Vec<String>>) = if automatic {
+            let clusters = internal_clusters(&self.module, &functions, &edges);
+            let moved = pick_cluster(&clusters).ok_or_else(|| {
+                format!(
+                    "No split found: the internal calls of {} form a single cluster; choose functions with --functions",
+                    self.module
+                )
+            })?;
+            (moved.iter().cloned().collect(), clusters)
+        } else {
+            let unknown: Vec<&str> = self
+                .functions
+                .iter()
+                .filter(|f| !functions.contains(f))
+                .map(String::as_str)
+                .collect();
+            if !unknown.is_empty() {
+                return Err(format!("Not functions of {}: {}", self.module, unknown.join(", ")).into());
+            }
+            (self.functions.iter().cloned().collect(), Vec::new())
+        };
+        if moved.len() == functions.len() {
+            return Err("Moving every function would only rename the module; use rename-impact instead".into());
+        }
+
+        let new_module = self.into.clone().unwrap_or_else(|| format!("{}.Extracted", self.module));
+        let remaining: Vec<String> = functions.iter().filter(|f| !moved.contains(*f)).cloned().collect();
+
+        let key = |function: &str| (self.module.clone(), function.to_string());
+        let before: HashSet<(String, String)> = functions.iter().map(|f| key(f)).collect();
+        let moved_set: HashSet<(String, String)> = moved.iter().map(|f| key(f)).collect();
+        let remaining_set: HashSet<(String, String)> = remaining.iter().map(|f| key(f)).collect();
+
+        let qualified = |module: &str, function: &str| format!("{}.{}", module, function);
+        let mut internal_crossings = BTreeSet::new();
+        let mut external_callers = BTreeSet::new();
+        let mut new_dependencies = BTreeSet::new();
+        for edge in &edges {
+            let caller = (edge.caller_module.clone(), edge.caller_function.clone());
+            let callee = (edge.callee_module.clone(), edge.callee_function.clone());
+            let crossing = (moved_set.contains(&caller) && remaining_set.contains(&callee))
+                || (remaining_set.contains(&caller) && moved_set.contains(&callee));
+            if crossing {
+                internal_crossings.insert(BoundaryCall {
+                    caller: qualified(&edge.caller_module, &edge.caller_function),
+                    callee: qualified(&edge.callee_module, &edge.callee_function),
+                });
+            } else if moved_set.contains(&callee) && !before.contains(&caller) {
+                external_callers.insert(BoundaryCall {
+                    caller: qualified(&edge.caller_module, &edge.caller_function),
+                    callee: qualified(&edge.callee_module, &edge.callee_function),
+                });
+            } else if moved_set.contains(&caller) && !before.contains(&callee) {
+                new_dependencies.insert(edge.callee_module.clone());
+            }
+        }
+
+        Ok(ExtractModuleResult {
+            cohesion_before: Cohesion::of(&before, &edges),
+            cohesion_after: Cohesion::of(&remaining_set, &edges),
+            new_module_cohesion: Cohesion::of(&moved_set, &edges),
+            module: self.module,
+            new_module,
+            automatic,
+            moved: moved.into_iter().collect(),
+            remaining,
+            internal_crossings: internal_crossings.into_iter().collect(),
+            external_callers: external_callers.into_iter().collect(),
+            new_dependencies: new_dependencies.into_iter().collect(),
+            clusters,
+        })
+    }
+}
+
+/// Louvain clusters of the module's functions over its internal calls, largest first
+fn internal_clusters(module: &str, functions: &[String], edges: &[FunctionEdge]) -> Vec<Vec<String>> {
+    let index: BTreeMap<&str, usize> = functions.iter().enumerate().map(|(i, f)| (f.as_str(), i)).collect();
+    let internal = edges
+        .iter()
+        .filter(|e| e.caller_module == module && e.callee_module == module)
+        .filter_map(|e| Some((*index.get(e.caller_function.as_str())?, *index.get(e.callee_function.as_str())?, 1.0)));
+    let communities = WeightedGraph::new(functions.len(), internal).louvain();
+
+    let mut clusters: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for (i, community) in communities.into_iter().enumerate() {
+        clusters.entry(community).or_default().push(functions[i].clone());
+    }
+    let mut clusters: Vec<Vec<String>> = clusters.into_values().collect();
+    clusters.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    clusters
+}
+
+/// The largest cluster after the module's main one, when it has more than one function
+fn pick_cluster(clusters: &[Vec<String>]) -> Option<&Vec<String>> {
+    clusters.get(1).filter(|cluster| cluster.len() > 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPLIT_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Accounts": {
+                "get_user/1:2": {"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 4},
+                "list_users/0:6": {"name": "list_users", "arity": 0, "file": "lib/accounts.ex", "kind": "def", "line": 6, "start_line": 6, "end_line": 8},
+                "load/1:10": {"name": "load", "arity": 1, "file": "lib/accounts.ex", "kind": "defp", "line": 10, "start_line": 10, "end_line": 12},
+                "hash_password/1:20": {"name": "hash_password", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 20, "start_line": 20, "end_line": 22},
+                "salt/0:24": {"name": "salt", "arity": 0, "file": "lib/accounts.ex", "kind": "defp", "line": 24, "start_line": 24, "end_line": 26}
+            },
+            "MyApp.Web": {
+                "register/1:3": {"name": "register", "arity": 1, "file": "lib/web.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 9}
+            }
+        },
+        "calls": [
+            {"caller": {"module": "MyApp.Accounts", "function": "get_user", "file": "lib/accounts.ex", "line": 3, "column": 5}, "type": "local", "callee": {"module": "MyApp.Accounts", "function": "load", "arity": 1}},
+            {"caller": {"module": "MyApp.Accounts", "function": "list_users", "file": "lib/accounts.ex", "line": 7, "column": 5}, "type": "local", "callee": {"module": "MyApp.Accounts", "function": "load", "arity": 1}},
+            {"caller": {"module": "MyApp.Accounts", "function": "hash_password", "file": "lib/accounts.ex", "line": 21, "column": 5}, "type": "local", "callee": {"module": "MyApp.Accounts", "function": "salt", "arity": 0}},
+            {"caller": {"module": "MyApp.Accounts", "function": "hash_password", "file": "lib/accounts.ex", "line": 21, "column": 9}, "type": "remote", "callee": {"module": "Bcrypt", "function": "hash", "arity": 2}},
+            {"caller": {"module": "MyApp.Accounts", "function": "get_user", "file": "lib/accounts.ex", "line": 3, "column": 9}, "type": "local", "callee": {"module": "MyApp.Accounts", "function": "salt", "arity": 0}},
+            {"caller": {"module": "MyApp.Web", "function": "register", "file": "lib/web.ex", "line": 5, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Accounts", "function": "hash_password", "arity": 1}}
+        ]
+    }"#;
+
+    fn cmd(functions: &[&str]) -> ExtractModuleCmd {
+        ExtractModuleCmd {
+            module: "MyApp.Accounts".to_string(),
+            functions: functions.iter().map(|f| f.to_string()).collect(),
+            into: Some("MyApp.Accounts.Password".to_string()),
+            project: "default".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_extract_module_chosen_functions() {
+        let db = db::test_utils::setup_test_db(SPLIT_JSON, "default");
+        let result = cmd(&["hash_password", "salt"])
+            .execute(&db, &QuerySettings::default())
+            .expect("Extract module should succeed");
+
+        assert!(!result.automatic);
+        assert_eq!(result.remaining, ["get_user", "list_users", "load"]);
+        assert_eq!(
+            result.internal_crossings,
+            [BoundaryCall {
+                caller: "MyApp.Accounts.get_user".to_string(),
+                callee: "MyApp.Accounts.salt".to_string(),
+            }]
+        );
+        assert_eq!(result.external_callers.len(), 1);
+        assert_eq!(result.external_callers[0].caller, "MyApp.Web.register");
+        assert_eq!(result.new_dependencies, ["Bcrypt"]);
+
+        // Before: 4 internal, 1 outgoing (Bcrypt), 1 incoming (Web)
+        assert_eq!(result.cohesion_before.internal_calls, 4);
+        assert!((result.cohesion_before.cohesion - 4.0 / 6.0).abs() < 1e-9);
+        // Remaining: load calls stay internal, get_user -> salt goes out
+        assert_eq!(result.cohesion_after.internal_calls, 2);
+        assert_eq!(result.cohesion_after.outgoing_calls, 1);
+        assert_eq!(result.new_module_cohesion.internal_calls, 1);
+    }
+
+    #[test]
+    fn test_extract_module_automatic_pick() {
+        let db = db::test_utils::setup_test_db(SPLIT_JSON, "default");
+        let result = cmd(&[]).execute(&db, &QuerySettings::default()).expect("Extract module should succeed");
+
+        assert!(result.automatic);
+        assert_eq!(result.clusters.len(), 2);
+        assert_eq!(result.moved, ["hash_password", "salt"]);
+        assert_eq!(result.remaining, ["get_user", "list_users", "load"]);
+    }
+
+    #[test]
+    fn test_extract_module_rejects_unknown_functions() {
+        let db = db::test_utils::setup_test_db(SPLIT_JSON, "default");
+        let err = cmd(&["missing"]).execute(&db, &QuerySettings::default()).unwrap_err();
+        assert!(err.to_string().contains("missing"));
+    }
+}
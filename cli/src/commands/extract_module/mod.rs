@@ -0,0 +1,48 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Show what moving functions out of a module into a new one would change
+///
+/// Lists the calls that would cross the new module boundary and compares the
+/// cohesion of the module before and after the split. Without --functions the
+/// functions to move are picked from clusters of the module's internal calls
+/// (Louvain): the largest cluster apart from the module's main one.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search extract-module MyApp.Accounts                                  # Suggest a split automatically
+  code_search extract-module MyApp.Accounts -f hash_password,verify_password # Evaluate a chosen set
+  code_search extract-module MyApp.Accounts -f hash_password --into MyApp.Accounts.Password
+")]
+pub struct ExtractModuleCmd {
+    /// Module to split (exact name)
+    pub module: String,
+
+    /// Functions to move, comma-separated; picked automatically when omitted
+    #[arg(short, long, value_delimiter = ',')]
+    pub functions: Vec<String>,
+
+    /// Name of the new module (default: <MODULE>.Extracted)
+    #[arg(long)]
+    pub into: Option<String>,
+
+    /// Project to search in
+    #[arg(long, default_value = "default")]
+    pub project: String,
+}
+
+impl CommandRunner for ExtractModuleCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,103 @@
+//! Output formatting for extract-module command results.
+
+use super::execute::{BoundaryCall, Cohesion, ExtractModuleResult};
+use crate::output::Outputable;
+
+fn format_cohesion(cohesion: &Cohesion) -> String {
+    format!(
+        "{:.2} (internal: {}, outgoing: {}, incoming: {})",
+        cohesion.cohesion, cohesion.internal_calls, cohesion.outgoing_calls, cohesion.incoming_calls
+    )
+}
+
+fn push_calls(lines: &mut Vec<String>, title: &str, calls: &[BoundaryCall]) {
+    lines.push(String::new());
+    lines.push(format!("{} ({}):", title, calls.len()));
+    if calls.is_empty() {
+        lines.push("  none".to_string());
+    }
+    for call in calls {
+        lines.push(format!("  {} -> {}", call.caller, call.callee));
+    }
+}
+
+impl Outputable for ExtractModuleResult {
+    fn to_table(&self) -> String {
+        let mut lines = vec![format!("Extract from {} into {}", self.module, self.new_module)];
+        lines.push(String::new());
+
+        let picked = if self.automatic { " (picked from internal call clusters)" } else { "" };
+        lines.push(format!("Move{}: {}", picked, self.moved.join(", ")));
+        lines.push(format!("Keep: {}", self.remaining.join(", ")));
+
+        push_calls(&mut lines, "Calls crossing the new boundary", &self.internal_crossings);
+        push_calls(&mut lines, "External callers to update", &self.external_callers);
+
+        if !self.new_dependencies.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("New module depends on: {}", self.new_dependencies.join(", ")));
+        }
+
+        lines.push(String::new());
+        lines.push("Cohesion:".to_string());
+        lines.push(format!("  before:      {}", format_cohesion(&self.cohesion_before)));
+        lines.push(format!("  after:       {}", format_cohesion(&self.cohesion_after)));
+        lines.push(format!("  new module:  {}", format_cohesion(&self.new_module_cohesion)));
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_table() {
+        let cohesion = |internal_calls, outgoing_calls, incoming_calls, cohesion| Cohesion {
+            internal_calls,
+            outgoing_calls,
+            incoming_calls,
+            cohesion,
+        };
+        let result = ExtractModuleResult {
+            module: "MyApp.Accounts".to_string(),
+            new_module: "MyApp.Accounts.Password".to_string(),
+            automatic: false,
+            moved: vec!["hash_password".to_string(), "salt".to_string()],
+            remaining: vec!["get_user".to_string(), "load".to_string()],
+            internal_crossings: vec![BoundaryCall {
+                caller: "MyApp.Accounts.get_user".to_string(),
+                callee: "MyApp.Accounts.salt".to_string(),
+            }],
+            external_callers: vec![],
+            new_dependencies: vec!["Bcrypt".to_string()],
+            cohesion_before: cohesion(3, 1, 0, 0.75),
+            cohesion_after: cohesion(1, 1, 0, 0.5),
+            new_module_cohesion: cohesion(1, 1, 1, 1.0 / 3.0),
+            clusters: vec![],
+        };
+
+        assert_eq!(
+            result.to_table(),
+            "\
+Extract from MyApp.Accounts into MyApp.Accounts.Password
+
+Move: hash_password, salt
+Keep: get_user, load
+
+Calls crossing the new boundary (1):
+  MyApp.Accounts.get_user -> MyApp.Accounts.salt
+
+External callers to update (0):
+  none
+
+New module depends on: Bcrypt
+
+Cohesion:
+  before:      0.75 (internal: 3, outgoing: 1, incoming: 0)
+  after:       0.50 (internal: 1, outgoing: 1, incoming: 0)
+  new module:  0.33 (internal: 1, outgoing: 1, incoming: 1)"
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Per-function fan-in/fan-out tuples for quadrant scatter plots.
+
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::FanInFanOutCmd;
+use crate::commands::Execute;
+use db::queries::hotspots::{find_hotspots, get_function_sizes, HotspotKind};
+use db::query_builders::QuerySettings;
+
+/// One point of the scatter plot
+#[derive(Debug, Clone, Serialize)]
+pub struct FanInFanOutEntry {
+    pub module: String,
+    pub function: String,
+    pub fan_in: i64,
+    pub fan_out: i64,
+    /// Lines of code over all clauses and arities
+    pub loc: i64,
+    /// Cyclomatic complexity summed over all clauses and arities
+    pub complexity: i64,
+    /// Relative fan-in times relative complexity, from 0 to 1
+    pub criticality: f64,
+}
+
+/// Result of the fan-in-fan-out command
+#[derive(Debug, Serialize)]
+pub struct FanInFanOutResult {
+    /// Functions matching the filters, before the limit
+    pub total_functions: usize,
+    /// Median fan-in of the matching functions, the vertical quadrant split
+    pub fan_in_median: f64,
+    /// Median fan-out of the matching functions, the horizontal quadrant split
+    pub fan_out_median: f64,
+    pub total_items: usize,
+    pub entries: Vec<FanInFanOutEntry>,
+}
+
+impl Execute for FanInFanOutCmd {
+    type Output = FanInFanOutResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let hotspots = find_hotspots(
+            db,
+            HotspotKind::Total,
+            self.module.as_deref(),
+            None,
+            &self.common.project,
+            self.common.regex,
+            u32::MAX,
+            self.exclude_generated,
+            false,
+            settings,
+        )?;
+        let sizes = get_function_sizes(db, &self.common.project, self.module.as_deref(), self.common.regex, settings)?;
+
+        let max_fan_in = hotspots.iter().map(|h| h.incoming).max().unwrap_or(0);
+        let max_complexity = hotspots
+            .iter()
+            .filter_map(|h| sizes.get(&(h.module.clone(), h.function.clone())))
+            .map(|&(_, complexity)| complexity)
+            .max()
+            .unwrap_or(0);
+
+        let mut entries: Vec<FanInFanOutEntry> = hotspots
+            .into_iter()
+            .map(|hotspot| {
+                let (loc, complexity) = sizes
+                    .get(&(hotspot.module.clone(), hotspot.function.clone()))
+                    .copied()
+                    .unwrap_or((0, 0));
+                FanInFanOutEntry {
+                    criticality: relative(hotspot.incoming, max_fan_in) * relative(complexity, max_complexity),
+                    module: hotspot.module,
+                    function: hotspot.function,
+                    fan_in: hotspot.incoming,
+                    fan_out: hotspot.outgoing,
+                    loc,
+                    complexity,
+                }
+            })
+            .collect();
+
+        let total_functions = entries.len();
+        let fan_in_median = median(entries.iter().map(|e| e.fan_in).collect());
+        let fan_out_median = median(entries.iter().map(|e| e.fan_out).collect());
+
+        entries.sort_by(|a, b| {
+            b.criticality
+                .total_cmp(&a.criticality)
+                .then_with(|| b.fan_in.cmp(&a.fan_in))
+                .then_with(|| a.module.cmp(&b.module))
+                .then_with(|| a.function.cmp(&b.function))
+        });
+        entries.truncate(self.common.limit as usize);
+
+        Ok(FanInFanOutResult {
+            total_functions,
+            fan_in_median,
+            fan_out_median,
+            total_items: entries.len(),
+            entries,
+        })
+    }
+}
+
+fn relative(value: i64, max: i64) -> f64 {
+    if max == 0 { 0.0 } else { value as f64 / max as f64 }
+}
+
+fn median(mut values: Vec<i64>) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) as f64 / 2.0
+    } else {
+        values[mid] as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+
+    fn fan_in_fan_out_cmd(module: Option<&str>, limit: u32) -> FanInFanOutCmd {
+        FanInFanOutCmd {
+            module: module.map(String::from),
+            exclude_generated: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_fan_in_fan_out_entries() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = fan_in_fan_out_cmd(None, 100)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_items, result.total_functions);
+        assert!(result.entries.iter().all(|e| (0.0..=1.0).contains(&e.criticality)));
+        for pair in result.entries.windows(2) {
+            assert!(pair[0].criticality >= pair[1].criticality);
+        }
+
+        let get = result.entries.iter().find(|e| e.module == "MyApp.Repo" && e.function == "get").unwrap();
+        // get_user/1 and get_user/2 count as one caller
+        assert_eq!(get.fan_in, 2);
+        assert_eq!(get.fan_out, 0);
+        assert!(get.loc > 0);
+        assert!(get.complexity > 0);
+    }
+
+    #[test]
+    fn test_fan_in_fan_out_limit_keeps_medians() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let all = fan_in_fan_out_cmd(None, 100).execute(&db, &QuerySettings::default()).unwrap();
+        let top = fan_in_fan_out_cmd(None, 2).execute(&db, &QuerySettings::default()).unwrap();
+
+        assert_eq!(top.total_items, 2);
+        assert_eq!(top.total_functions, all.total_functions);
+        assert_eq!(top.fan_in_median, all.fan_in_median);
+        assert_eq!(top.entries[0].module, all.entries[0].module);
+        assert_eq!(top.entries[0].function, all.entries[0].function);
+    }
+
+    #[test]
+    fn test_fan_in_fan_out_module_filter() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = fan_in_fan_out_cmd(Some("MyApp.Accounts"), 100).execute(&db, &QuerySettings::default()).unwrap();
+
+        assert!(!result.entries.is_empty());
+        assert!(result.entries.iter().all(|e| e.module == "MyApp.Accounts"));
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(vec![]), 0.0);
+        assert_eq!(median(vec![3, 1, 2]), 2.0);
+        assert_eq!(median(vec![4, 1, 2, 3]), 2.5);
+    }
+}
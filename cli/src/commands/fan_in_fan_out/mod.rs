@@ -0,0 +1,44 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Export per-function fan-in, fan-out, size and complexity for scatter plots
+///
+/// One row per function, ranked by criticality: fan-in times complexity, each
+/// relative to the highest value among the reported functions. The medians of
+/// fan-in and fan-out split the plot into quadrants.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search fan-in-fan-out                        # Most critical functions
+  code_search fan-in-fan-out MyApp.Accounts -l 50   # Only matching modules
+  code_search fan-in-fan-out -o csv -l 1000 > scatter.csv
+  code_search fan-in-fan-out -o json --exclude-generated
+")]
+pub struct FanInFanOutCmd {
+    /// Module pattern to filter results (substring match by default, regex with --regex)
+    pub module: Option<String>,
+
+    /// Exclude generated functions: macro-generated and compiler callbacks,
+    /// plus the `generated` rules of the config file
+    #[arg(long)]
+    pub exclude_generated: bool,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for FanInFanOutCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,133 @@
+//! Output formatting for fan-in-fan-out command results.
+
+use super::execute::FanInFanOutResult;
+use crate::output::columns::to_csv;
+use crate::output::Outputable;
+
+impl Outputable for FanInFanOutResult {
+    fn to_table(&self) -> String {
+        let mut output = String::new();
+        output.push_str(&format!(
+            "Fan-in/fan-out of {} function(s), medians {} in, {} out\n\n",
+            self.total_functions, self.fan_in_median, self.fan_out_median
+        ));
+
+        if self.entries.is_empty() {
+            output.push_str("No functions found.\n");
+            return output;
+        }
+
+        output.push_str(&format!("Top {} function(s) by criticality:\n\n", self.total_items));
+
+        let names: Vec<String> = self
+            .entries
+            .iter()
+            .map(|e| format!("{}.{}", e.module, e.function))
+            .collect();
+        let name_width = names.iter().map(|n| n.len()).max().unwrap_or(0).max(8);
+
+        output.push_str(&format!(
+            "{:<name_width$}  {:>6}  {:>7}  {:>5}  {:>10}  {:>11}\n",
+            "Function",
+            "Fan-in",
+            "Fan-out",
+            "LOC",
+            "Complexity",
+            "Criticality",
+            name_width = name_width,
+        ));
+
+        for (name, entry) in names.iter().zip(&self.entries) {
+            output.push_str(&format!(
+                "{:<name_width$}  {:>6}  {:>7}  {:>5}  {:>10}  {:>11.3}\n",
+                name,
+                entry.fan_in,
+                entry.fan_out,
+                entry.loc,
+                entry.complexity,
+                entry.criticality,
+                name_width = name_width,
+            ));
+        }
+
+        output
+    }
+
+    /// One row per point, in plotting order of the columns
+    fn to_csv(&self) -> Option<String> {
+        let header: Vec<String> = ["module", "function", "fan_in", "fan_out", "loc", "complexity", "criticality"]
+            .iter()
+            .map(|column| column.to_string())
+            .collect();
+
+        let rows: Vec<Vec<String>> = self
+            .entries
+            .iter()
+            .map(|e| {
+                vec![
+                    e.module.clone(),
+                    e.function.clone(),
+                    e.fan_in.to_string(),
+                    e.fan_out.to_string(),
+                    e.loc.to_string(),
+                    e.complexity.to_string(),
+                    e.criticality.to_string(),
+                ]
+            })
+            .collect();
+
+        Some(to_csv(&header, &rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::fan_in_fan_out::execute::FanInFanOutEntry;
+    use crate::output::OutputFormat;
+
+    fn result(entries: Vec<FanInFanOutEntry>) -> FanInFanOutResult {
+        FanInFanOutResult {
+            total_functions: 12,
+            fan_in_median: 1.0,
+            fan_out_median: 0.5,
+            total_items: entries.len(),
+            entries,
+        }
+    }
+
+    fn entry() -> FanInFanOutEntry {
+        FanInFanOutEntry {
+            module: "MyApp.Repo".to_string(),
+            function: "get".to_string(),
+            fan_in: 3,
+            fan_out: 0,
+            loc: 4,
+            complexity: 2,
+            criticality: 0.75,
+        }
+    }
+
+    #[test]
+    fn test_fan_in_fan_out_output_empty() {
+        let output = result(vec![]).to_table();
+        assert!(output.contains("Fan-in/fan-out of 12 function(s), medians 1 in, 0.5 out"));
+        assert!(output.contains("No functions found"));
+    }
+
+    #[test]
+    fn test_fan_in_fan_out_output_entries() {
+        let output = result(vec![entry()]).to_table();
+        assert!(output.contains("Top 1 function(s) by criticality"));
+        assert!(output.contains("MyApp.Repo.get"));
+        assert!(output.contains("0.750"));
+    }
+
+    #[test]
+    fn test_fan_in_fan_out_output_csv() {
+        let csv = result(vec![entry()]).format(OutputFormat::Csv);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("module,function,fan_in,fan_out,loc,complexity,criticality"));
+        assert_eq!(lines.next(), Some("MyApp.Repo,get,3,0,4,2,0.75"));
+    }
+}
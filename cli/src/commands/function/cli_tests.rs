@@ -32,7 +32,7 @@ mod tests {
         test_name: test_with_module_and_function,
         args: ["MyApp.Accounts", "get_user"],
         field: module,
-        expected: "MyApp.Accounts",
+        expected: Some("MyApp.Accounts".to_string()),
     }
 
     crate::cli_option_test! {
@@ -41,7 +41,7 @@ mod tests {
         test_name: test_function_name,
         args: ["MyApp.Accounts", "get_user"],
         field: function,
-        expected: "get_user",
+        expected: Some("get_user".to_string()),
     }
 
     crate::cli_option_test! {
@@ -75,6 +75,21 @@ mod tests {
     // Limit validation tests
     // =========================================================================
 
+    crate::cli_option_test! {
+        command: "function",
+        variant: Function,
+        test_name: test_with_stdin,
+        args: ["--stdin"],
+        field: stdin,
+        expected: true,
+    }
+
+    crate::cli_error_test! {
+        command: "function",
+        test_name: test_stdin_conflicts_with_positional,
+        args: ["MyApp.Accounts", "get_user", "--stdin"],
+    }
+
     crate::cli_limit_tests! {
         command: "function",
         variant: Function,
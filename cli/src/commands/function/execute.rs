@@ -4,7 +4,9 @@ use serde::Serialize;
 
 use super::FunctionCmd;
 use crate::commands::Execute;
-use db::queries::function::{find_functions, FunctionSignature};
+use crate::targets::stdin_targets;
+use db::query_builders::{FunctionTarget, PatternMatching, QuerySettings};
+use db::queries::function::{find_functions, find_functions_for_targets, FunctionSignature};
 use db::types::ModuleGroupResult;
 
 /// A function signature within a module
@@ -51,21 +53,44 @@ fn build_function_signatures_result(
 impl Execute for FunctionCmd {
     type Output = ModuleGroupResult<FuncSig>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        if self.stdin {
+            return self.execute_targets(db, &stdin_targets()?);
+        }
+
+        let (module_pattern, function_pattern) = (self.module.unwrap_or_default(), self.function.unwrap_or_default());
+        let matching = PatternMatching::new(self.common.regex, [module_pattern.as_str(), function_pattern.as_str()]);
+        let (module, function) = (matching.pattern(&module_pattern), matching.pattern(&function_pattern));
+        let use_regex = matching.use_regex();
+
         let signatures = find_functions(
             db,
-            &self.module,
-            &self.function,
+            &module,
+            &function,
             self.arity,
             &self.common.project,
-            self.common.regex,
+            use_regex,
             self.common.limit,
+            settings,
         )?;
 
         Ok(build_function_signatures_result(
-            self.module,
-            self.function,
+            module_pattern,
+            function_pattern,
             signatures,
         ))
     }
 }
+
+impl FunctionCmd {
+    /// Signatures of each of the targets, from one query (`--stdin`)
+    pub fn execute_targets(
+        self,
+        db: &db::DbInstance,
+        targets: &[FunctionTarget],
+    ) -> Result<ModuleGroupResult<FuncSig>, Box<dyn Error>> {
+        let signatures = find_functions_for_targets(db, targets, &self.common.project, self.common.limit)?;
+        let labels: Vec<String> = targets.iter().map(FunctionTarget::label).collect();
+        Ok(build_function_signatures_result(labels.join(", "), String::new(), signatures))
+    }
+}
@@ -4,6 +4,7 @@
 mod tests {
     use super::super::FunctionCmd;
     use crate::commands::CommonArgs;
+    use db::query_builders::FunctionTarget;
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -21,13 +22,15 @@ mod tests {
         test_name: test_function_exact_match,
         fixture: populated_db,
         cmd: FunctionCmd {
-            module: "MyApp.Accounts".to_string(),
-            function: "get_user".to_string(),
+            module: Some("MyApp.Accounts".to_string()),
+            function: Some("get_user".to_string()),
             arity: None,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -41,13 +44,15 @@ mod tests {
         test_name: test_function_with_arity,
         fixture: populated_db,
         cmd: FunctionCmd {
-            module: "MyApp.Accounts".to_string(),
-            function: "get_user".to_string(),
+            module: Some("MyApp.Accounts".to_string()),
+            function: Some("get_user".to_string()),
             arity: Some(1),
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -64,13 +69,15 @@ mod tests {
         test_name: test_function_regex_match,
         fixture: populated_db,
         cmd: FunctionCmd {
-            module: "MyApp\\..*".to_string(),
-            function: ".*user.*".to_string(),
+            module: Some("MyApp\\..*".to_string()),
+            function: Some(".*user.*".to_string()),
             arity: None,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -86,13 +93,15 @@ mod tests {
         test_name: test_function_no_match,
         fixture: populated_db,
         cmd: FunctionCmd {
-            module: "NonExistent".to_string(),
-            function: "foo".to_string(),
+            module: Some("NonExistent".to_string()),
+            function: Some("foo".to_string()),
             arity: None,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: items,
@@ -106,13 +115,15 @@ mod tests {
         test_name: test_function_with_project_filter,
         fixture: populated_db,
         cmd: FunctionCmd {
-            module: "MyApp.Accounts".to_string(),
-            function: "get_user".to_string(),
+            module: Some("MyApp.Accounts".to_string()),
+            function: Some("get_user".to_string()),
             arity: None,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -125,13 +136,15 @@ mod tests {
         test_name: test_function_with_limit,
         fixture: populated_db,
         cmd: FunctionCmd {
-            module: "MyApp\\..*".to_string(),
-            function: ".*".to_string(),
+            module: Some("MyApp\\..*".to_string()),
+            function: Some(".*".to_string()),
             arity: None,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 2,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -140,6 +153,36 @@ mod tests {
         },
     }
 
+    // =========================================================================
+    // --stdin target tests
+    // =========================================================================
+
+    #[rstest]
+    fn test_function_targets(populated_db: db::DbInstance) {
+        let cmd = FunctionCmd {
+            module: None,
+            function: None,
+            arity: None,
+            stdin: true,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        };
+        let targets = [FunctionTarget::parse("MyApp.Accounts.get_user/1").unwrap(), FunctionTarget::parse("MyApp.Accounts.list_users").unwrap()];
+        let result = cmd.execute_targets(&populated_db, &targets).expect("Execute should succeed");
+
+        let names: Vec<(&str, i64)> = result
+            .items
+            .iter()
+            .flat_map(|m| m.entries.iter().map(|f| (f.name.as_str(), f.arity)))
+            .collect();
+        assert_eq!(names, [("get_user", 1), ("list_users", 0)]);
+    }
+
+
     // =========================================================================
     // Error handling tests
     // =========================================================================
@@ -147,13 +190,15 @@ mod tests {
     crate::execute_empty_db_test! {
         cmd_type: FunctionCmd,
         cmd: FunctionCmd {
-            module: "MyApp".to_string(),
-            function: "foo".to_string(),
+            module: Some("MyApp".to_string()),
+            function: Some("foo".to_string()),
             arity: None,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
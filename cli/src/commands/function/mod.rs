@@ -7,10 +7,12 @@ mod output_tests;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::suggest::Lookup;
 
 /// Show function signature (args, return type)
 #[derive(Args, Debug)]
@@ -19,25 +21,34 @@ Examples:
   code_search function MyApp.Accounts get_user       # Show signature
   code_search function MyApp.Accounts get_user -a 1  # Specific arity
   code_search function -r 'MyApp\\..*' 'get_.*'      # Regex matching
+  code_search unused -o csv --fields module,name,arity | code_search function --stdin
 ")]
 pub struct FunctionCmd {
     /// Module name (exact match or pattern with --regex)
-    pub module: String,
+    #[arg(required_unless_present = "stdin")]
+    pub module: Option<String>,
 
     /// Function name (exact match or pattern with --regex)
-    pub function: String,
+    #[arg(required_unless_present = "stdin")]
+    pub function: Option<String>,
 
     /// Function arity (optional, matches all arities if not specified)
     #[arg(short, long)]
     pub arity: Option<i64>,
 
+    /// Read the functions from stdin, one Module.function[/arity] per line, and query them together
+    #[arg(long, conflicts_with_all = ["module", "function", "arity", "regex"])]
+    pub stdin: bool,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for FunctionCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
-        Ok(result.format(format))
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let lookup = Lookup::new(&self.common, self.module.as_deref(), self.function.as_deref());
+        let result = self.execute(db, settings)?;
+        let empty = result.items.is_empty();
+        Ok(lookup.suggest(db, result, empty, settings)?.format(format))
     }
 }
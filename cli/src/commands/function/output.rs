@@ -8,8 +8,11 @@ impl TableFormatter for ModuleGroupResult<FuncSig> {
     type Entry = FuncSig;
 
     fn format_header(&self) -> String {
-        let function_pattern = self.function_pattern.as_deref().unwrap_or("*");
-        format!("Function: {}.{}", self.module_pattern, function_pattern)
+        // A --stdin query lists its targets in the module pattern
+        match self.function_pattern.as_deref() {
+            Some("") => format!("Function: {}", self.module_pattern),
+            function_pattern => format!("Function: {}.{}", self.module_pattern, function_pattern.unwrap_or("*")),
+        }
     }
 
     fn format_empty_message(&self) -> String {
@@ -4,7 +4,9 @@ use serde::Serialize;
 
 use super::GodModulesCmd;
 use crate::commands::Execute;
+use crate::sort::query_limit;
 use db::queries::hotspots::{get_function_counts, get_module_connectivity, get_module_loc};
+use db::query_builders::QuerySettings;
 use db::types::{ModuleCollectionResult, ModuleGroup};
 
 /// A single god module entry
@@ -20,13 +22,14 @@ pub struct GodModuleEntry {
 impl Execute for GodModulesCmd {
     type Output = ModuleCollectionResult<GodModuleEntry>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         // Get function counts for all modules
         let func_counts = get_function_counts(
             db,
             &self.common.project,
             self.module.as_deref(),
             self.common.regex,
+            settings,
         )?;
 
         // Get lines of code per module
@@ -35,6 +38,7 @@ impl Execute for GodModulesCmd {
             &self.common.project,
             self.module.as_deref(),
             self.common.regex,
+            settings,
         )?;
 
         // Get module-level connectivity (aggregated at database level)
@@ -43,6 +47,7 @@ impl Execute for GodModulesCmd {
             &self.common.project,
             self.module.as_deref(),
             self.common.regex,
+            settings,
         )?;
 
         // Build god modules: filter by thresholds and sort by total connectivity
@@ -78,16 +83,15 @@ impl Execute for GodModulesCmd {
             god_modules.push((module_name, func_count, loc, incoming, outgoing));
         }
 
-        // Sort by total connectivity (descending)
+        // Sort by total connectivity (descending), then by module name
         god_modules.sort_by(|a, b| {
             let total_a = a.3 + a.4;
             let total_b = b.3 + b.4;
-            total_b.cmp(&total_a)
+            total_b.cmp(&total_a).then_with(|| a.0.cmp(&b.0))
         });
 
-        // Apply limit
-        let limit = self.common.limit as usize;
-        god_modules.truncate(limit);
+        // Apply limit, or leave it to --sort
+        god_modules.truncate(query_limit(self.sort.as_ref(), self.common.limit) as usize);
 
         // Convert to ModuleGroup entries
         let total_items = god_modules.len();
@@ -110,14 +114,20 @@ impl Execute for GodModulesCmd {
             })
             .collect();
 
-        Ok(ModuleCollectionResult {
+        let mut result = ModuleCollectionResult {
             module_pattern: self.module.clone().unwrap_or_else(|| "*".to_string()),
             function_pattern: None,
             kind_filter: Some("god".to_string()),
             name_filter: None,
             total_items,
             items,
-        })
+        };
+
+        if let Some(sort) = &self.sort {
+            sort.sort_groups(&mut result, self.common.limit)?;
+        }
+
+        Ok(result)
     }
 }
 
@@ -133,10 +143,12 @@ mod tests {
             min_loc: 500,
             min_total: 15,
             module: Some("MyApp".to_string()),
+            sort: None,
             common: crate::commands::CommonArgs {
                 project: "default".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
 
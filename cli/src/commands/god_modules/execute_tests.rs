@@ -5,6 +5,7 @@ mod tests {
     use super::super::GodModulesCmd;
     use crate::commands::CommonArgs;
     use crate::commands::Execute;
+    use db::query_builders::QuerySettings;
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -24,13 +25,15 @@ mod tests {
             min_loc: 1,
             min_total: 1,
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         assert_eq!(result.kind_filter, Some("god".to_string()));
         // Should have some modules that meet the criteria
@@ -44,13 +47,15 @@ mod tests {
             min_loc: 1,
             min_total: 1,
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         // With high threshold, might have no results
         for item in &result.items {
@@ -66,13 +71,15 @@ mod tests {
             min_loc: 1000, // High LoC threshold
             min_total: 1,
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         for item in &result.items {
             let entry = &item.entries[0];
@@ -87,13 +94,15 @@ mod tests {
             min_loc: 1,
             min_total: 10, // Require at least 10 total calls
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         for item in &result.items {
             let entry = &item.entries[0];
@@ -109,13 +118,15 @@ mod tests {
             min_loc: 1,
             min_total: 1,
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         if result.items.len() > 1 {
             // Check that results are sorted by total connectivity (descending)
@@ -139,13 +150,15 @@ mod tests {
             min_loc: 1,
             min_total: 1,
             module: Some("Accounts".to_string()),
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         // All results should contain "Accounts"
         for item in &result.items {
@@ -160,13 +173,15 @@ mod tests {
             min_loc: 1,
             min_total: 1,
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 2,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         assert!(result.items.len() <= 2, "Expected at most 2 results, got {}", result.items.len());
     }
@@ -178,13 +193,15 @@ mod tests {
             min_loc: 1,
             min_total: 1,
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         for item in &result.items {
             // Each module should have exactly one entry
@@ -213,13 +230,15 @@ mod tests {
             min_loc: 999999,
             min_total: 999999,
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         // Should return empty results, not error
         assert_eq!(result.total_items, 0);
@@ -233,13 +252,15 @@ mod tests {
             min_loc: 1,
             min_total: 1,
             module: Some("NonExistentModule".to_string()),
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         // Should return empty results
         assert_eq!(result.total_items, 0);
@@ -254,13 +275,15 @@ mod tests {
             min_loc: 1,
             min_total: 1,
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "wrong_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         // Should return empty results for non-existent project
         assert_eq!(result.total_items, 0);
@@ -274,13 +297,15 @@ mod tests {
             min_loc: 1,
             min_total: 1,
             module: Some("Accounts".to_string()),
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         // Verify result metadata is correct
         assert_eq!(result.module_pattern, "Accounts");
@@ -296,13 +321,15 @@ mod tests {
             min_loc: 10,
             min_total: 2,
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         // All results must satisfy ALL three criteria
         for item in &result.items {
@@ -324,10 +351,12 @@ mod tests {
             min_loc: 1,
             min_total: 1,
             module: None,
+            sort: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         },
     }
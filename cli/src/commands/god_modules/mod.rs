@@ -5,10 +5,12 @@ mod output;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::sort::SortSpec;
 
 /// Find god modules - modules with high function count and high connectivity
 ///
@@ -23,6 +25,7 @@ Examples:
   code_search god-modules --min-loc 500           # With minimum 500 lines of code
   code_search god-modules --min-total 15          # With minimum 15 total connectivity
   code_search god-modules -l 20                   # Show top 20 god modules
+  code_search god-modules --sort loc:desc         # Largest modules first
 ")]
 pub struct GodModulesCmd {
     /// Module filter pattern (substring match by default, regex with --regex)
@@ -40,13 +43,17 @@ pub struct GodModulesCmd {
     #[arg(long, default_value = "10")]
     pub min_total: i64,
 
+    /// Sort by an output column as `column[:asc|desc]` (ascending by default)
+    #[arg(long, value_name = "COLUMN[:asc|desc]")]
+    pub sort: Option<SortSpec>,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for GodModulesCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
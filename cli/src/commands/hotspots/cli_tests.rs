@@ -4,6 +4,7 @@
 mod tests {
     use crate::cli::Args;
     use db::queries::hotspots::HotspotKind;
+    use db::query_builders::TestScope;
     use clap::Parser;
     use rstest::rstest;
 
@@ -34,6 +35,15 @@ mod tests {
         expected: Some("MyApp".to_string()),
     }
 
+    crate::cli_option_test! {
+        command: "hotspots",
+        variant: Hotspots,
+        test_name: test_with_app,
+        args: ["--app", "web"],
+        field: app,
+        expected: Some("web".to_string()),
+    }
+
     crate::cli_option_test! {
         command: "hotspots",
         variant: Hotspots,
@@ -52,6 +62,18 @@ mod tests {
         expected: true,
     }
 
+    crate::cli_option_test! {
+        command: "hotspots",
+        variant: Hotspots,
+        test_name: test_with_sort,
+        args: ["--sort", "ratio:desc"],
+        field: sort,
+        expected: Some(crate::sort::SortSpec {
+            column: "ratio".to_string(),
+            descending: true,
+        }),
+    }
+
     crate::cli_option_test! {
         command: "hotspots",
         variant: Hotspots,
@@ -70,6 +92,15 @@ mod tests {
         expected: true,
     }
 
+    crate::cli_option_test! {
+        command: "hotspots",
+        variant: Hotspots,
+        test_name: test_with_min_depth_from_entry,
+        args: ["--min-depth-from-entry", "3"],
+        field: min_depth_from_entry,
+        expected: Some(3),
+    }
+
     // Test limit validation
     crate::cli_limit_tests! {
         command: "hotspots",
@@ -130,4 +161,46 @@ mod tests {
             _ => panic!("Expected Hotspots command"),
         }
     }
+
+    #[rstest]
+    fn test_only_project_is_global() {
+        let args = Args::try_parse_from(["code_search", "hotspots", "--only-project"]).unwrap();
+        assert!(args.only_project);
+        assert!(args.only_project());
+
+        let args = Args::try_parse_from(["code_search", "hotspots", "--include-deps"]).unwrap();
+        assert!(!args.only_project());
+    }
+
+    #[rstest]
+    fn test_namespace_is_repeatable() {
+        let args = Args::try_parse_from([
+            "code_search", "hotspots", "--namespace", "MyApp.Web", "--namespace", "MyApp.Accounts",
+        ])
+        .unwrap();
+        let common = args.command.common().expect("hotspots has common arguments");
+        assert_eq!(common.namespace, ["MyApp.Web", "MyApp.Accounts"]);
+
+        let args = Args::try_parse_from(["code_search", "hotspots"]).unwrap();
+        assert!(args.command.common().unwrap().namespace.is_empty());
+    }
+
+    #[rstest]
+    fn test_only_project_conflicts_with_include_deps() {
+        let result = Args::try_parse_from(["code_search", "hotspots", "--only-project", "--include-deps"]);
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_test_scope_flags_are_global() {
+        let args = Args::try_parse_from(["code_search", "hotspots", "--exclude-tests"]).unwrap();
+        assert_eq!(args.test_scope(), TestScope::Exclude);
+        let args = Args::try_parse_from(["code_search", "--only-tests", "hotspots"]).unwrap();
+        assert_eq!(args.test_scope(), TestScope::Only);
+        let args = Args::try_parse_from(["code_search", "hotspots"]).unwrap();
+        assert_eq!(args.test_scope(), TestScope::All);
+
+        let result = Args::try_parse_from(["code_search", "hotspots", "--exclude-tests", "--only-tests"]);
+        assert!(result.is_err());
+    }
 }
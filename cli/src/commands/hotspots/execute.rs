@@ -1,30 +1,12 @@
 use std::error::Error;
 
-use serde::Serialize;
-
 use super::HotspotsCmd;
+use crate::api::{all_hotspots, hotspots, HotspotsRequest, Scope};
 use crate::commands::Execute;
 use crate::output::Outputable;
-use db::queries::hotspots::find_hotspots;
-
-/// A function hotspot entry
-#[derive(Debug, Clone, Serialize)]
-pub struct FunctionHotspotEntry {
-    pub module: String,
-    pub function: String,
-    pub incoming: i64,
-    pub outgoing: i64,
-    pub total: i64,
-    pub ratio: f64,
-}
+use db::query_builders::QuerySettings;
 
-/// Result type for hotspots command
-#[derive(Debug, Serialize)]
-pub struct HotspotsResult {
-    pub kind: String,
-    pub total_items: usize,
-    pub entries: Vec<FunctionHotspotEntry>,
-}
+pub use crate::api::HotspotsResult;
 
 impl Outputable for HotspotsResult {
     fn to_table(&self) -> String {
@@ -79,13 +61,18 @@ impl Outputable for HotspotsResult {
             } else {
                 format!("{:.2}", entry.ratio)
             };
+            let depth_str = entry
+                .depth_from_entry
+                .map(|depth| format!("  depth {}", depth))
+                .unwrap_or_default();
             lines.push(format!(
-                "{:<name_width$}  {:>in_width$} in  {:>out_width$} out  {:>total_width$} total  {:>6} ratio",
+                "{:<name_width$}  {:>in_width$} in  {:>out_width$} out  {:>total_width$} total  {:>6} ratio{}",
                 name,
                 entry.incoming,
                 entry.outgoing,
                 entry.total,
                 ratio_str,
+                depth_str,
                 name_width = name_width,
                 in_width = in_width,
                 out_width = out_width,
@@ -97,46 +84,34 @@ impl Outputable for HotspotsResult {
     }
 }
 
+impl From<&HotspotsCmd> for HotspotsRequest {
+    fn from(cmd: &HotspotsCmd) -> Self {
+        Self {
+            module: cmd.module.clone(),
+            kind: cmd.kind,
+            exclude_generated: cmd.exclude_generated,
+            app: cmd.app.clone(),
+            project: cmd.common.project.clone(),
+            regex: cmd.common.regex,
+            limit: cmd.common.limit,
+            min_depth_from_entry: cmd.min_depth_from_entry,
+            scope: Scope::default(),
+        }
+    }
+}
+
 impl Execute for HotspotsCmd {
     type Output = HotspotsResult;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
-        let hotspots = find_hotspots(
-            db,
-            self.kind,
-            self.module.as_deref(),
-            &self.common.project,
-            self.common.regex,
-            self.common.limit,
-            self.exclude_generated,
-            false, // Don't require outgoing calls
-        )?;
-
-        let kind_str = match self.kind {
-            db::queries::hotspots::HotspotKind::Incoming => "incoming",
-            db::queries::hotspots::HotspotKind::Outgoing => "outgoing",
-            db::queries::hotspots::HotspotKind::Total => "total",
-            db::queries::hotspots::HotspotKind::Ratio => "ratio",
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let request = HotspotsRequest { scope: Scope::from(settings), ..HotspotsRequest::from(&self) };
+        let Some(sort) = &self.sort else {
+            return hotspots(db, request);
         };
-
-        let entries: Vec<FunctionHotspotEntry> = hotspots
-            .into_iter()
-            .map(|hotspot| FunctionHotspotEntry {
-                module: hotspot.module,
-                function: hotspot.function,
-                incoming: hotspot.incoming,
-                outgoing: hotspot.outgoing,
-                total: hotspot.total,
-                ratio: hotspot.ratio,
-            })
-            .collect();
-
-        let total_items = entries.len();
-
-        Ok(HotspotsResult {
-            kind: kind_str.to_string(),
-            total_items,
-            entries,
-        })
+        // Rank every function, so the limit keeps the first by the sort column
+        let mut result = all_hotspots(db, request)?;
+        sort.sort(&mut result.entries, self.common.limit)?;
+        result.total_items = result.entries.len();
+        Ok(result)
     }
 }
@@ -6,6 +6,7 @@ mod tests {
     use crate::commands::CommonArgs;
     use crate::commands::Execute;
     use db::queries::hotspots::HotspotKind;
+    use db::query_builders::QuerySettings;
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -14,6 +15,12 @@ mod tests {
         project: "test_project",
     }
 
+    crate::shared_fixture! {
+        fixture_name: umbrella_db,
+        fixture_type: umbrella,
+        project: "test_project",
+    }
+
     // =========================================================================
     // Core functionality tests
     // =========================================================================
@@ -24,31 +31,64 @@ mod tests {
             module: None,
             kind: HotspotKind::Incoming,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            min_depth_from_entry: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         assert_eq!(result.kind, "incoming");
         assert!(!result.entries.is_empty());
     }
 
+    // Service.process has the most outgoing calls but the fewest incoming,
+    // so the command's own order puts it last
+    #[rstest]
+    fn test_hotspots_sort_before_limit(populated_db: db::DbInstance) {
+        let cmd = HotspotsCmd {
+            module: None,
+            kind: HotspotKind::Incoming,
+            exclude_generated: false,
+            app: None,
+            sort: Some("outgoing:desc".parse().unwrap()),
+            min_depth_from_entry: None,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 3,
+                namespace: Vec::new(),
+            },
+        };
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.total_items, 3);
+        assert_eq!(result.entries[0].module, "MyApp.Service");
+        assert_eq!(result.entries[0].function, "process");
+    }
+
     #[rstest]
     fn test_hotspots_outgoing(populated_db: db::DbInstance) {
         let cmd = HotspotsCmd {
             module: None,
             kind: HotspotKind::Outgoing,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            min_depth_from_entry: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         assert_eq!(result.kind, "outgoing");
         assert!(!result.entries.is_empty());
@@ -60,13 +100,17 @@ mod tests {
             module: None,
             kind: HotspotKind::Total,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            min_depth_from_entry: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         assert_eq!(result.kind, "total");
         assert!(!result.entries.is_empty());
@@ -78,13 +122,17 @@ mod tests {
             module: None,
             kind: HotspotKind::Ratio,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            min_depth_from_entry: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         assert_eq!(result.kind, "ratio");
         assert!(!result.entries.is_empty());
@@ -102,13 +150,17 @@ mod tests {
             module: Some("Accounts".to_string()),
             kind: HotspotKind::Incoming,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            min_depth_from_entry: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         // All entries should have Accounts in the module name
         assert!(result.entries.iter().all(|e| e.module.contains("Accounts")));
@@ -120,13 +172,17 @@ mod tests {
             module: None,
             kind: HotspotKind::Incoming,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            min_depth_from_entry: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 2,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         assert!(result.entries.len() <= 2);
     }
@@ -137,19 +193,118 @@ mod tests {
             module: None,
             kind: HotspotKind::Incoming,
             exclude_generated: true,
+            app: None,
+            sort: None,
+            min_depth_from_entry: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
-        let result = cmd.execute(&populated_db).expect("Execute should succeed");
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
 
         // With exclude_generated, generated functions should be filtered out
         // Result may or may not be empty depending on test data
         assert_eq!(result.kind, "incoming");
     }
 
+    #[rstest]
+    fn test_hotspots_sort_by_function(populated_db: db::DbInstance) {
+        let cmd = HotspotsCmd {
+            module: None,
+            kind: HotspotKind::Incoming,
+            exclude_generated: false,
+            app: None,
+            sort: Some("function".parse().unwrap()),
+            min_depth_from_entry: None,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 20,
+                namespace: Vec::new(),
+            },
+        };
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
+
+        let functions: Vec<&str> = result.entries.iter().map(|e| e.function.as_str()).collect();
+        let mut sorted = functions.clone();
+        sorted.sort();
+        assert_eq!(functions, sorted);
+    }
+
+    #[rstest]
+    fn test_hotspots_sort_unknown_column(populated_db: db::DbInstance) {
+        let cmd = HotspotsCmd {
+            module: None,
+            kind: HotspotKind::Incoming,
+            exclude_generated: false,
+            app: None,
+            sort: Some("nope:desc".parse().unwrap()),
+            min_depth_from_entry: None,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 20,
+                namespace: Vec::new(),
+            },
+        };
+        let err = cmd.execute(&populated_db, &QuerySettings::default()).unwrap_err();
+
+        assert!(err.to_string().contains("Unknown sort column 'nope'"));
+    }
+
+    #[rstest]
+    fn test_hotspots_app_filter(umbrella_db: db::DbInstance) {
+        let cmd = HotspotsCmd {
+            module: None,
+            kind: HotspotKind::Incoming,
+            exclude_generated: false,
+            app: Some("core".to_string()),
+            sort: None,
+            min_depth_from_entry: None,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 20,
+                namespace: Vec::new(),
+            },
+        };
+        let result = cmd.execute(&umbrella_db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert!(result.entries.iter().all(|e| e.module.starts_with("Core.")));
+        // get_user is called from both web and billing
+        assert_eq!(result.entries[0].function, "get_user");
+        assert_eq!(result.entries[0].incoming, 2);
+    }
+
+    // Controller actions are the entry points: Accounts.get_user is one call
+    // away from show/2, Repo.get two
+    #[rstest]
+    fn test_hotspots_min_depth_from_entry(populated_db: db::DbInstance) {
+        let cmd = HotspotsCmd {
+            module: None,
+            kind: HotspotKind::Incoming,
+            exclude_generated: false,
+            app: None,
+            sort: None,
+            min_depth_from_entry: Some(2),
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 20,
+                namespace: Vec::new(),
+            },
+        };
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Execute should succeed");
+
+        let get = result.entries.iter().find(|e| e.module == "MyApp.Repo" && e.function == "get").unwrap();
+        assert_eq!(get.depth_from_entry, Some(2));
+        assert!(result.entries.iter().all(|e| e.depth_from_entry.is_some_and(|d| d >= 2)));
+        assert!(!result.entries.iter().any(|e| e.module == "MyApp.Accounts" && e.function == "get_user"));
+    }
+
     // =========================================================================
     // Error handling tests
     // =========================================================================
@@ -160,10 +315,14 @@ mod tests {
             module: None,
             kind: HotspotKind::Incoming,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            min_depth_from_entry: None,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         },
     }
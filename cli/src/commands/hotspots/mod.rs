@@ -11,7 +11,9 @@ use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::sort::SortSpec;
 use db::queries::hotspots::HotspotKind;
+use db::query_builders::QuerySettings;
 
 /// Find functions with the most incoming/outgoing calls
 #[derive(Args, Debug)]
@@ -23,6 +25,7 @@ Examples:
   code_search hotspots -k ratio              # Boundary functions (high incoming/outgoing ratio)
   code_search hotspots MyApp -l 10           # Top 10 in MyApp namespace
   code_search hotspots --exclude-generated   # Exclude macro-generated functions
+  code_search hotspots --app web             # Only modules of the web umbrella app
 
   # Find wide functions (high fan-out):
   code_search hotspots -k outgoing -l 20     # Top 20 functions calling many others
@@ -31,7 +34,11 @@ Examples:
   code_search hotspots -k incoming -l 20     # Top 20 most-called functions
 
   # Find boundary functions (many callers, few dependencies):
-  code_search hotspots -k ratio -l 20        # Top 20 boundary functions")]
+  code_search hotspots -k ratio -l 20        # Top 20 boundary functions
+
+  # Separate core utilities from shallow dispatchers:
+  code_search hotspots --min-depth-from-entry 3   # Called 3+ hops from any entry point
+  code_search hotspots --sort ratio:desc     # Sort the top hotspots by ratio")]
 pub struct HotspotsCmd {
     /// Module pattern to filter results (substring match by default, regex with --regex)
     pub module: Option<String>,
@@ -40,17 +47,31 @@ pub struct HotspotsCmd {
     #[arg(short, long, value_enum, default_value_t = HotspotKind::Incoming)]
     pub kind: HotspotKind,
 
-    /// Exclude macro-generated functions
+    /// Exclude generated functions: macro-generated and compiler callbacks,
+    /// plus the `generated` rules of the config file
     #[arg(long)]
     pub exclude_generated: bool,
 
+    /// Only include modules of this OTP application (umbrella projects)
+    #[arg(long)]
+    pub app: Option<String>,
+
+    /// Sort by an output column as `column[:asc|desc]` (ascending by default)
+    #[arg(long, value_name = "COLUMN[:asc|desc]")]
+    pub sort: Option<SortSpec>,
+
+    /// Only functions at least N calls away from an entry point (as detected
+    /// by `entry-points`); unreachable functions are left out
+    #[arg(long, value_name = "N")]
+    pub min_depth_from_entry: Option<u32>,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for HotspotsCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
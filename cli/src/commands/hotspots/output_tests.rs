@@ -2,7 +2,8 @@
 
 #[cfg(test)]
 mod tests {
-    use super::super::execute::{FunctionHotspotEntry, HotspotsResult};
+    use super::super::execute::HotspotsResult;
+    use crate::api::FunctionHotspotEntry;
     use crate::output::{OutputFormat, Outputable};
     use rstest::{fixture, rstest};
 
@@ -55,6 +56,7 @@ MyApp.Users.create        5 in  3 out   8 total    0.38 ratio";
                 outgoing: 1,
                 total: 4,
                 ratio: 0.25,
+                depth_from_entry: None,
             }],
         }
     }
@@ -72,6 +74,7 @@ MyApp.Users.create        5 in  3 out   8 total    0.38 ratio";
                     outgoing: 2,
                     total: 12,
                     ratio: 0.17,
+                    depth_from_entry: None,
                 },
                 FunctionHotspotEntry {
                     module: "MyApp.Users".to_string(),
@@ -80,6 +83,7 @@ MyApp.Users.create        5 in  3 out   8 total    0.38 ratio";
                     outgoing: 3,
                     total: 8,
                     ratio: 0.38,
+                    depth_from_entry: None,
                 },
             ],
         }
@@ -73,4 +73,66 @@ mod tests {
         .unwrap();
         assert_eq!(args.db, Some(PathBuf::from("/custom/path.db")));
     }
+
+    #[rstest]
+    fn test_schema_defaults_to_elixir(temp_file: (TempDir, PathBuf)) {
+        let (_dir, path) = temp_file;
+        let args =
+            Args::try_parse_from(["code_search", "import", "--file", path.to_str().unwrap()])
+                .unwrap();
+        match args.command {
+            crate::commands::Command::Import(cmd) => {
+                assert_eq!(cmd.schema, crate::commands::import::ImportSchema::Elixir)
+            }
+            _ => panic!("Expected Import command"),
+        }
+    }
+
+    #[rstest]
+    fn test_schema_generic(temp_file: (TempDir, PathBuf)) {
+        let (_dir, path) = temp_file;
+        let args = Args::try_parse_from([
+            "code_search",
+            "import",
+            "--file",
+            path.to_str().unwrap(),
+            "--schema",
+            "generic",
+        ])
+        .unwrap();
+        match args.command {
+            crate::commands::Command::Import(cmd) => {
+                assert_eq!(cmd.schema, crate::commands::import::ImportSchema::Generic)
+            }
+            _ => panic!("Expected Import command"),
+        }
+    }
+
+    #[rstest]
+    fn test_strict_conflicts_with_lenient(temp_file: (TempDir, PathBuf)) {
+        let (_dir, path) = temp_file;
+        let result = Args::try_parse_from([
+            "code_search",
+            "import",
+            "--file",
+            path.to_str().unwrap(),
+            "--strict",
+            "--lenient",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_resume_conflicts_with_clear(temp_file: (TempDir, PathBuf)) {
+        let (_dir, path) = temp_file;
+        let result = Args::try_parse_from([
+            "code_search",
+            "import",
+            "--file",
+            path.to_str().unwrap(),
+            "--resume",
+            "--clear",
+        ]);
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,292 @@
+//! Deduplication of call site records before they are written.
+//!
+//! Calls, messages and field accesses are stored one row per key, so records
+//! repeating a key would overwrite each other while still being counted. Each
+//! relation keeps the first record of every key, in file order, and counts the
+//! rest as dropped. The key defaults to the relation's stored key and can be
+//! narrowed per relation, e.g. to collapse the identical calls of the heads of
+//! a multi-clause function by leaving out `line` and `column`:
+//!
+//! ```json
+//! { "import": { "dedup": { "calls": ["caller_module", "caller_function", "callee_module", "callee_function", "callee_arity"] } } }
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+
+use db::queries::import_models::{Call, CallGraph, Caller, FieldAccess, Message};
+
+/// Columns of the relations that are deduplicated, stored key first
+const CALL_COLUMNS: &[&str] = &[
+    "caller_module",
+    "caller_function",
+    "callee_module",
+    "callee_function",
+    "callee_arity",
+    "file",
+    "line",
+    "column",
+    "call_type",
+    "caller_kind",
+    "callee_args",
+    "call_kind",
+    "conditional",
+];
+const MESSAGE_COLUMNS: &[&str] = &[
+    "caller_module",
+    "caller_function",
+    "callee_module",
+    "callee_function",
+    "callee_arity",
+    "file",
+    "line",
+    "column",
+    "kind",
+    "topic",
+    "conditional",
+];
+const FIELD_ACCESS_COLUMNS: &[&str] = &[
+    "struct_module",
+    "field",
+    "caller_module",
+    "caller_function",
+    "file",
+    "line",
+    "column",
+    "access",
+];
+
+/// Relations that are deduplicated, with their columns and the length of their
+/// stored key, in the order [`Deduper`] indexes them
+const RELATIONS: &[(&str, &[&str], usize)] = &[
+    ("calls", CALL_COLUMNS, 8),
+    ("messages", MESSAGE_COLUMNS, 8),
+    ("field_accesses", FIELD_ACCESS_COLUMNS, 8),
+];
+
+/// Duplicate records left out of one relation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DroppedDuplicates {
+    pub relation: String,
+    /// Columns the records were compared on
+    pub key: Vec<String>,
+    pub dropped: usize,
+}
+
+/// Parse `--dedup RELATION=COLUMN,COLUMN` options into dedup keys
+pub fn parse_dedup_keys(options: &[String]) -> Result<BTreeMap<String, Vec<String>>, String> {
+    options
+        .iter()
+        .map(|option| {
+            let (relation, columns) = option
+                .split_once('=')
+                .ok_or_else(|| format!("expected RELATION=COLUMNS, got '{}'", option))?;
+            let columns = columns.split(',').map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+            Ok((relation.trim().to_string(), columns.collect()))
+        })
+        .collect()
+}
+
+/// Drop the records of the graph repeating a key, keeping the first of each.
+///
+/// `keys` overrides the stored key of some relations. Only relations that
+/// lost records are listed.
+pub fn dedup_graph(
+    graph: &mut CallGraph,
+    keys: &BTreeMap<String, Vec<String>>,
+) -> Result<Vec<DroppedDuplicates>, String> {
+    let mut deduper = Deduper::new(keys)?;
+    graph.calls.retain(|call| deduper.keep_call(call));
+    graph.messages.retain(|message| deduper.keep_message(message));
+    graph.field_accesses.retain(|access| deduper.keep_field_access(access));
+    Ok(deduper.dropped())
+}
+
+/// Keys seen so far per relation, for records that arrive one at a time
+pub struct Deduper {
+    relations: Vec<SeenKeys>,
+}
+
+struct SeenKeys {
+    relation: &'static str,
+    key: Vec<String>,
+    seen: HashSet<Vec<String>>,
+    dropped: usize,
+}
+
+impl SeenKeys {
+    /// Whether the key of a record is new, by the value of each key column
+    fn keep(&mut self, value: impl Fn(&str) -> String) -> bool {
+        let key = self.key.iter().map(|column| value(column)).collect();
+        let new = self.seen.insert(key);
+        if !new {
+            self.dropped += 1;
+        }
+        new
+    }
+}
+
+impl Deduper {
+    /// `keys` overrides the stored key of some relations
+    pub fn new(keys: &BTreeMap<String, Vec<String>>) -> Result<Self, String> {
+        for (relation, key) in keys {
+            let Some((_, columns, _)) = RELATIONS.iter().find(|(name, _, _)| name == relation) else {
+                let names: Vec<&str> = RELATIONS.iter().map(|(name, _, _)| *name).collect();
+                return Err(format!("cannot deduplicate '{}' (supported: {})", relation, names.join(", ")));
+            };
+            if key.is_empty() {
+                return Err(format!("the key of '{}' has no columns", relation));
+            }
+            if let Some(unknown) = key.iter().find(|column| !columns.contains(&column.as_str())) {
+                return Err(format!(
+                    "'{}' has no column '{}' (columns: {})",
+                    relation,
+                    unknown,
+                    columns.join(", ")
+                ));
+            }
+        }
+
+        let relations = RELATIONS
+            .iter()
+            .map(|(relation, columns, key_len)| SeenKeys {
+                relation,
+                key: match keys.get(*relation) {
+                    Some(key) => key.clone(),
+                    None => columns[..*key_len].iter().map(|c| c.to_string()).collect(),
+                },
+                seen: HashSet::new(),
+                dropped: 0,
+            })
+            .collect();
+        Ok(Self { relations })
+    }
+
+    pub fn keep_call(&mut self, call: &Call) -> bool {
+        self.relations[0].keep(|column| call_value(call, column))
+    }
+
+    pub fn keep_message(&mut self, message: &Message) -> bool {
+        self.relations[1].keep(|column| message_value(message, column))
+    }
+
+    pub fn keep_field_access(&mut self, access: &FieldAccess) -> bool {
+        self.relations[2].keep(|column| field_access_value(access, column))
+    }
+
+    /// Relations that lost records
+    pub fn dropped(self) -> Vec<DroppedDuplicates> {
+        self.relations
+            .into_iter()
+            .filter(|relation| relation.dropped > 0)
+            .map(|relation| DroppedDuplicates {
+                relation: relation.relation.to_string(),
+                key: relation.key,
+                dropped: relation.dropped,
+            })
+            .collect()
+    }
+}
+
+/// Value of a caller column, as stored
+fn caller_value(caller: &Caller, column: &str) -> Option<String> {
+    Some(match column {
+        "caller_module" => caller.module.clone(),
+        "caller_function" => caller.function.clone().unwrap_or_else(|| "<module>".to_string()),
+        "file" => caller.file.clone(),
+        "line" => caller.line.unwrap_or(0).to_string(),
+        "column" => caller.column.unwrap_or(0).to_string(),
+        _ => return None,
+    })
+}
+
+fn call_value(call: &Call, column: &str) -> String {
+    caller_value(&call.caller, column).unwrap_or_else(|| match column {
+        "callee_module" => call.callee.module.clone(),
+        "callee_function" => call.callee.function.clone(),
+        "callee_arity" => call.callee.arity.to_string(),
+        "call_type" => call.call_type.clone(),
+        "caller_kind" => call.caller.kind.clone().unwrap_or_default(),
+        "call_kind" => call.kind.clone(),
+        "conditional" => call.conditional.to_string(),
+        _ => call.callee.args.clone().unwrap_or_default(),
+    })
+}
+
+fn message_value(message: &Message, column: &str) -> String {
+    caller_value(&message.caller, column).unwrap_or_else(|| match column {
+        "callee_module" => message.callee.module.clone(),
+        "callee_function" => message.callee.function.clone(),
+        "callee_arity" => message.callee.arity.to_string(),
+        "kind" => message.kind.clone(),
+        "conditional" => message.conditional.to_string(),
+        _ => message.topic.clone().unwrap_or_default(),
+    })
+}
+
+fn field_access_value(access: &FieldAccess, column: &str) -> String {
+    caller_value(&access.caller, column).unwrap_or_else(|| match column {
+        "struct_module" => access.struct_module.clone(),
+        "field" => access.field.clone(),
+        _ => access.access.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two clause heads of `A.run/1` calling `B.f/0`, the second listed twice
+    const CLAUSE_CALLS: &str = r#"{
+        "structs": {},
+        "function_locations": {},
+        "calls": [
+            {"caller": {"module": "A", "function": "run", "file": "a.ex", "line": 3, "column": 5}, "callee": {"module": "B", "function": "f", "arity": 0}, "type": "remote"},
+            {"caller": {"module": "A", "function": "run", "file": "a.ex", "line": 7, "column": 5}, "callee": {"module": "B", "function": "f", "arity": 0}, "type": "remote"},
+            {"caller": {"module": "A", "function": "run", "file": "a.ex", "line": 7, "column": 5}, "callee": {"module": "B", "function": "f", "arity": 0}, "type": "remote"}
+        ]
+    }"#;
+
+    fn graph() -> CallGraph {
+        serde_json::from_str(CLAUSE_CALLS).unwrap()
+    }
+
+    #[test]
+    fn test_dedup_graph_drops_repeated_keys() {
+        let mut graph = graph();
+        let dropped = dedup_graph(&mut graph, &BTreeMap::new()).unwrap();
+
+        assert_eq!(graph.calls.len(), 2);
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].relation, "calls");
+        assert_eq!(dropped[0].dropped, 1);
+        assert_eq!(dropped[0].key.len(), 8);
+    }
+
+    #[test]
+    fn test_dedup_graph_custom_key_keeps_first() {
+        let mut graph = graph();
+        let keys = parse_dedup_keys(&["calls=caller_module,caller_function,callee_module,callee_function,callee_arity".to_string()])
+            .unwrap();
+        let dropped = dedup_graph(&mut graph, &keys).unwrap();
+
+        assert_eq!(graph.calls.len(), 1);
+        assert_eq!(graph.calls[0].caller.line, Some(3));
+        assert_eq!(dropped[0].dropped, 2);
+    }
+
+    #[test]
+    fn test_dedup_graph_rejects_unknown_keys() {
+        let keys = parse_dedup_keys(&["specs=name".to_string()]).unwrap();
+        assert!(dedup_graph(&mut graph(), &keys).unwrap_err().contains("cannot deduplicate 'specs'"));
+
+        let keys = parse_dedup_keys(&["calls=caller,line".to_string()]).unwrap();
+        assert!(dedup_graph(&mut graph(), &keys).unwrap_err().contains("no column 'caller'"));
+
+        let keys = parse_dedup_keys(&["calls=".to_string()]).unwrap();
+        assert!(dedup_graph(&mut graph(), &keys).is_err());
+
+        assert!(parse_dedup_keys(&["calls".to_string()]).is_err());
+    }
+}
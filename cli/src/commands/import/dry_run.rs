@@ -0,0 +1,223 @@
+//! `import --dry-run`: check a call graph file without writing to the database.
+//!
+//! The file is parsed as the import would parse it, and every record is also
+//! checked on its own, so one run lists all the records the import would
+//! reject instead of stopping at the first. The stored relations are compared
+//! with the schema of this build: the import adds the newer columns a
+//! relation lacks, but other differences make it fail part-way through.
+
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::dedup::{dedup_graph, DroppedDuplicates};
+use super::exclude::{ExcludedRecords, PathExclusions};
+use super::execute::{dedup_keys, load_graph, read_json};
+use super::validation::{check_records, check_supervision, RejectedRecord};
+use super::ImportCmd;
+use db::queries::import::{planned_rows, ImportError};
+use db::queries::schema::{schema_mismatches, SchemaMismatch};
+use db::DbInstance;
+
+/// Rows the import would write to one relation
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedRelation {
+    pub relation: String,
+    pub rows: usize,
+}
+
+/// Result of `import --dry-run`
+#[derive(Debug, Default, Serialize)]
+pub struct ImportPlan {
+    pub file: String,
+    pub project: String,
+    /// Whether the project's data would be cleared first (`--clear`)
+    pub clear: bool,
+    /// Whether malformed records would be skipped (`--lenient`)
+    pub lenient: bool,
+    /// Rows per relation; empty when the file cannot be imported
+    pub relations: Vec<PlannedRelation>,
+    /// Duplicate records that would be dropped
+    pub duplicates: Vec<DroppedDuplicates>,
+    /// Records of excluded paths that would be left out
+    pub excluded: Vec<ExcludedRecords>,
+    /// Error the import would stop with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Malformed records, refused by a strict import and skipped by a lenient one
+    pub rejected: Vec<RejectedRecord>,
+    pub schema_mismatches: Vec<SchemaMismatch>,
+}
+
+impl ImportPlan {
+    /// Whether the import would succeed; it adds the columns relations of an
+    /// older version lack itself
+    pub fn importable(&self) -> bool {
+        self.error.is_none() && self.schema_mismatches.iter().all(SchemaMismatch::repairable)
+    }
+}
+
+/// Check the files of an import and count the rows it would write
+pub fn plan_import(cmd: &ImportCmd, db: &DbInstance) -> Result<ImportPlan, Box<dyn Error>> {
+    let keys = dedup_keys(cmd)?;
+    let mut plan = ImportPlan {
+        file: cmd.file.display().to_string(),
+        project: cmd.project.clone(),
+        clear: cmd.clear,
+        lenient: cmd.lenient,
+        rejected: malformed_records(cmd)?,
+        schema_mismatches: schema_mismatches(db)?,
+        ..Default::default()
+    };
+
+    match load_graph(cmd) {
+        Ok(mut loaded) => {
+            let mut exclusions = PathExclusions::for_import(cmd)?;
+            exclusions.filter_graph(&mut loaded.graph);
+            plan.excluded = exclusions.excluded();
+            plan.duplicates = dedup_graph(&mut loaded.graph, &keys)?;
+            plan.relations = planned_rows(&cmd.project, &loaded.graph)
+                .into_iter()
+                .map(|(relation, rows)| PlannedRelation {
+                    relation: relation.to_string(),
+                    rows,
+                })
+                .collect();
+        }
+        Err(e) => plan.error = Some(e.to_string()),
+    }
+    Ok(plan)
+}
+
+/// Every malformed record of the import's files.
+///
+/// Files that are not JSON objects have no records to list; loading them
+/// reports the error.
+fn malformed_records(cmd: &ImportCmd) -> Result<Vec<RejectedRecord>, ImportError> {
+    let mut rejected = match read_json(&cmd.file) {
+        Ok(mut value) => check_records(&mut value, cmd.schema, false).unwrap_or_default(),
+        Err(ImportError::JsonParseFailed { .. }) => Vec::new(),
+        Err(e) => return Err(e),
+    };
+
+    if let Some(path) = &cmd.supervision {
+        let records = match read_json(path) {
+            Ok(mut value) => check_supervision(&mut value, false).unwrap_or_default(),
+            Err(ImportError::JsonParseFailed { .. }) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        rejected.extend(records.into_iter().map(|r| RejectedRecord {
+            path: format!("{}#{}", path.display(), r.path),
+            ..r
+        }));
+    }
+    Ok(rejected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ImportSchema;
+    use crate::config::DEFAULT_CONFIG_PATH;
+    use std::fs;
+
+    fn import_cmd(file: &std::path::Path) -> ImportCmd {
+        ImportCmd {
+            file: file.to_path_buf(),
+            schema: ImportSchema::Elixir,
+            project: "test_project".to_string(),
+            clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: false,
+            dry_run: true,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
+        }
+    }
+
+    #[test]
+    fn test_plan_import_writes_nothing() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let json = r#"{"structs": {}, "function_locations": {}, "calls": [
+            {"caller": {"module": "A", "file": "a.ex"}, "callee": {"module": "B", "function": "f", "arity": 0}, "type": "remote"}
+        ]}"#;
+        fs::write(file.path(), json).unwrap();
+        let db = db::open_mem_db();
+
+        let plan = plan_import(&import_cmd(file.path()), &db).unwrap();
+        assert!(plan.importable());
+        let calls = plan.relations.iter().find(|r| r.relation == "calls").unwrap();
+        assert_eq!(calls.rows, 1);
+        assert!(schema_mismatches(&db).unwrap().is_empty());
+        assert!(db.run_script_str("::relations", "", true).contains(r#""rows":[]"#));
+    }
+
+    #[test]
+    fn test_plan_import_reports_invalid_files() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(
+            file.path(),
+            r#"{"structs": {}, "function_locations": {}, "calls": [{"type": "remote"}, {"type": "local"}]}"#,
+        )
+        .unwrap();
+
+        let plan = plan_import(&import_cmd(file.path()), &db::open_mem_db()).unwrap();
+        assert!(!plan.importable());
+        assert!(plan.relations.is_empty());
+        assert_eq!(
+            plan.error.as_deref().unwrap(),
+            "Failed to parse call graph JSON: /calls/0: missing field `caller` \
+             (1 more malformed records, listed by `import --dry-run`)"
+        );
+        assert_eq!(plan.rejected.len(), 2);
+        assert_eq!(plan.rejected[0].path, "/calls/0");
+
+        fs::write(file.path(), "{not json").unwrap();
+        let plan = plan_import(&import_cmd(file.path()), &db::open_mem_db()).unwrap();
+        assert!(plan.error.is_some() && plan.rejected.is_empty());
+    }
+
+    #[test]
+    fn test_plan_import_lenient() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let json = r#"{"structs": {}, "function_locations": {}, "calls": [
+            {"caller": {"module": "A", "file": "a.ex"}, "callee": {"module": "B", "function": "f", "arity": 0}, "type": "remote"},
+            {"type": "remote"}
+        ]}"#;
+        fs::write(file.path(), json).unwrap();
+        let cmd = ImportCmd {
+            lenient: true,
+            ..import_cmd(file.path())
+        };
+
+        let plan = plan_import(&cmd, &db::open_mem_db()).unwrap();
+        assert!(plan.importable());
+        assert_eq!(plan.rejected.len(), 1);
+        let calls = plan.relations.iter().find(|r| r.relation == "calls").unwrap();
+        assert_eq!(calls.rows, 1);
+    }
+
+    #[test]
+    fn test_plan_import_generic_version() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(file.path(), r#"{"version": 2, "nodes": []}"#).unwrap();
+        let cmd = ImportCmd {
+            schema: ImportSchema::Generic,
+            ..import_cmd(file.path())
+        };
+
+        let plan = plan_import(&cmd, &db::open_mem_db()).unwrap();
+        assert!(plan.rejected.is_empty());
+        assert!(plan.error.as_deref().unwrap().contains("version 2"));
+    }
+}
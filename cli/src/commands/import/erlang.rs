@@ -0,0 +1,377 @@
+//! Erlang call graph dialect.
+//!
+//! Erlang extractors name functions `module:function/arity` and have no
+//! structs or specs sections; records take the place of structs. Import with
+//! `code_search import --schema erlang`:
+//!
+//! ```json
+//! {
+//!   "functions": [
+//!     {"mfa": "user_store:fetch/1", "file": "src/user_store.erl", "line": 12, "end_line": 20,
+//!      "exported": true, "complexity": 2, "doc": "Look up a user"}
+//!   ],
+//!   "calls": [
+//!     {"caller": "user_store:fetch/1", "callee": "ets:lookup/2", "line": 14, "column": 5},
+//!     {"caller": "user_store:fetch/1", "callee": "'Elixir.MyApp.Accounts':normalize/1", "line": 16}
+//!   ],
+//!   "records": {"user": {"fields": [{"name": "id"}, {"name": "email", "default": "<<>>", "type": "binary()"}]}},
+//!   "record_accesses": [{"caller": "user_store:fetch/1", "record": "user", "field": "email", "line": 15}],
+//!   "behaviours": {"user_store": ["gen_server"]}
+//! }
+//! ```
+//!
+//! Modules are stored as Elixir code refers to them, `:user_store`, and
+//! `'Elixir.MyApp.Accounts'` becomes `MyApp.Accounts`, so the calls of a
+//! mixed Erlang/Elixir umbrella connect across languages. Exported functions
+//! are stored with kind `def` and the others with `defp`. Records become
+//! structs named `#record`, and record field reads and writes become field
+//! accesses.
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::Deserialize;
+
+use db::queries::import::ImportError;
+use db::queries::import_models::{CallGraph, Caller, FieldAccess, StructDef, StructField};
+
+use super::models::{
+    CallEdge, FunctionNode, GenericEdge, GenericGraph, GenericNode, Visibility, GENERIC_SCHEMA_VERSION,
+};
+
+/// A call graph in the Erlang dialect
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ErlangGraph {
+    pub functions: Vec<ErlangFunction>,
+    pub calls: Vec<ErlangCall>,
+    /// Record definitions by record name
+    pub records: HashMap<String, ErlangRecord>,
+    pub record_accesses: Vec<RecordAccess>,
+    /// `-behaviour` declarations per module
+    pub behaviours: HashMap<String, Vec<String>>,
+}
+
+/// A function definition, all clauses included
+#[derive(Debug, Deserialize)]
+pub struct ErlangFunction {
+    /// `module:function/arity`
+    pub mfa: String,
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub line: u32,
+    #[serde(default)]
+    pub end_line: Option<u32>,
+    /// Listed in the module's `-export` attribute
+    #[serde(default)]
+    pub exported: bool,
+    #[serde(default = "default_complexity")]
+    pub complexity: u32,
+    /// `-doc` attribute or EDoc comment
+    #[serde(default)]
+    pub doc: Option<String>,
+}
+
+fn default_complexity() -> u32 {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ErlangCall {
+    /// Calling function, `module:function/arity`
+    pub caller: String,
+    /// Called function, `module:function/arity`
+    pub callee: String,
+    /// File of the call site; defaults to the caller's file
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub line: Option<u32>,
+    #[serde(default)]
+    pub column: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ErlangRecord {
+    pub fields: Vec<RecordField>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordField {
+    pub name: String,
+    /// Default value expression; `undefined` when absent
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(rename = "type", default)]
+    pub field_type: Option<String>,
+}
+
+/// A read (`User#user.email`, pattern) or write (`#user{email = E}`) of a record field
+#[derive(Debug, Deserialize)]
+pub struct RecordAccess {
+    /// Accessing function, `module:function/arity`
+    pub caller: String,
+    pub record: String,
+    pub field: String,
+    #[serde(default = "default_access")]
+    pub access: String,
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub line: Option<u32>,
+    #[serde(default)]
+    pub column: Option<u32>,
+}
+
+fn default_access() -> String {
+    "read".to_string()
+}
+
+fn invalid(message: String) -> ImportError {
+    ImportError::JsonParseFailed {
+        message: format!("erlang schema: {}", message),
+    }
+}
+
+/// Module name as Elixir code refers to it: `:lists`, or `MyApp.Accounts`
+/// for `'Elixir.MyApp.Accounts'`
+pub fn module_name(erlang: &str) -> String {
+    let name = erlang.trim_matches('\'');
+    match name.strip_prefix("Elixir.") {
+        Some(elixir) => elixir.to_string(),
+        None => format!(":{}", name),
+    }
+}
+
+/// Split `module:function/arity` into its normalized parts
+fn parse_mfa(mfa: &str) -> Result<(String, String, u32), ImportError> {
+    let malformed = || invalid(format!("malformed function reference '{}', expected module:function/arity", mfa));
+    let (module_function, arity) = mfa.rsplit_once('/').ok_or_else(malformed)?;
+    let arity = arity.parse().map_err(|_| malformed())?;
+    // Quoted module atoms may contain dots but not colons
+    let (module, function) = module_function.split_once(':').ok_or_else(malformed)?;
+    if module.is_empty() || function.is_empty() {
+        return Err(malformed());
+    }
+    Ok((module_name(module), function.trim_matches('\'').to_string(), arity))
+}
+
+/// Node id of a parsed function reference
+fn node_id((module, function, arity): &(String, String, u32)) -> String {
+    format!("{}.{}/{}", module, function, arity)
+}
+
+impl ErlangGraph {
+    /// Convert to the call graph model the importer stores.
+    ///
+    /// Functions and calls go through the generic schema; called functions
+    /// that are not defined in the file become external functions.
+    pub fn into_call_graph(self) -> Result<CallGraph, ImportError> {
+        let mut nodes = Vec::new();
+        let mut defined = BTreeSet::new();
+        let mut files = HashMap::new();
+        for function in self.functions {
+            let mfa = parse_mfa(&function.mfa)?;
+            let id = node_id(&mfa);
+            if !defined.insert(id.clone()) {
+                return Err(invalid(format!("function '{}' is defined twice", function.mfa)));
+            }
+            files.insert(id.clone(), function.file.clone());
+            let (module, name, arity) = mfa;
+            nodes.push(GenericNode::Function(FunctionNode {
+                id,
+                module,
+                name,
+                arity,
+                file: function.file,
+                line: function.line,
+                end_line: function.end_line,
+                column: None,
+                visibility: if function.exported { Visibility::Public } else { Visibility::Private },
+                complexity: function.complexity,
+                doc: function.doc,
+                external: false,
+                modified_at: 0,
+                commit: None,
+            }));
+        }
+
+        let mut external = BTreeSet::new();
+        let mut edges = Vec::new();
+        for call in self.calls {
+            let caller = node_id(&parse_mfa(&call.caller)?);
+            if !defined.contains(&caller) {
+                return Err(invalid(format!("call from undefined function '{}'", call.caller)));
+            }
+            let callee = parse_mfa(&call.callee)?;
+            let callee_id = node_id(&callee);
+            if !defined.contains(&callee_id) && external.insert(callee_id.clone()) {
+                let (module, name, arity) = callee;
+                nodes.push(GenericNode::Function(FunctionNode {
+                    id: callee_id.clone(),
+                    module,
+                    name,
+                    arity,
+                    file: None,
+                    line: 0,
+                    end_line: None,
+                    column: None,
+                    visibility: Visibility::Public,
+                    complexity: 1,
+                    doc: None,
+                    external: true,
+                    modified_at: 0,
+                    commit: None,
+                }));
+            }
+            edges.push(GenericEdge::Call(CallEdge {
+                from: caller,
+                to: callee_id,
+                file: call.file,
+                line: call.line,
+                column: call.column,
+            }));
+        }
+
+        let mut graph = GenericGraph {
+            version: GENERIC_SCHEMA_VERSION,
+            nodes,
+            edges,
+        }
+        .into_call_graph()?;
+
+        for (record, def) in self.records {
+            let fields = def
+                .fields
+                .into_iter()
+                .map(|field| StructField {
+                    default: field.default.unwrap_or_else(|| "undefined".to_string()),
+                    field: field.name,
+                    required: false,
+                    inferred_type: field.field_type,
+                })
+                .collect();
+            graph.structs.insert(format!("#{}", record), StructDef { fields });
+        }
+
+        for access in self.record_accesses {
+            let (module, function, arity) = parse_mfa(&access.caller)?;
+            let file = access
+                .file
+                .or_else(|| files.get(&node_id(&(module.clone(), function.clone(), arity))).cloned().flatten())
+                .unwrap_or_default();
+            graph.field_accesses.push(FieldAccess {
+                caller: Caller {
+                    module,
+                    function: Some(format!("{}/{}", function, arity)),
+                    file,
+                    line: access.line,
+                    column: access.column,
+                    kind: None,
+                },
+                struct_module: format!("#{}", access.record),
+                field: access.field,
+                access: access.access,
+            });
+        }
+
+        for (module, behaviours) in self.behaviours {
+            graph
+                .behaviours
+                .insert(module_name(&module), behaviours.iter().map(|b| module_name(b)).collect());
+        }
+
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ERLANG_JSON: &str = r#"{
+        "functions": [
+            {"mfa": "user_store:fetch/1", "file": "src/user_store.erl", "line": 12, "end_line": 20,
+             "exported": true, "complexity": 2},
+            {"mfa": "user_store:lookup/2", "file": "src/user_store.erl", "line": 22, "end_line": 25}
+        ],
+        "calls": [
+            {"caller": "user_store:fetch/1", "callee": "user_store:lookup/2", "line": 13},
+            {"caller": "user_store:fetch/1", "callee": "'Elixir.MyApp.Accounts':normalize/1", "line": 16},
+            {"caller": "user_store:lookup/2", "callee": "ets:lookup/2", "line": 23, "column": 5}
+        ],
+        "records": {"user": {"fields": [{"name": "id"}, {"name": "email", "default": "<<>>", "type": "binary()"}]}},
+        "record_accesses": [{"caller": "user_store:fetch/1", "record": "user", "field": "email", "line": 15}],
+        "behaviours": {"user_store": ["gen_server"]}
+    }"#;
+
+    fn convert(json: &str) -> Result<CallGraph, ImportError> {
+        serde_json::from_str::<ErlangGraph>(json).expect("Valid JSON").into_call_graph()
+    }
+
+    #[test]
+    fn test_module_name() {
+        assert_eq!(module_name("user_store"), ":user_store");
+        assert_eq!(module_name("'Elixir.MyApp.Accounts'"), "MyApp.Accounts");
+        assert_eq!(module_name("Elixir.MyApp"), "MyApp");
+    }
+
+    #[test]
+    fn test_parse_mfa() {
+        let parsed = parse_mfa("'Elixir.MyApp':'do it'/2").expect("Should parse");
+        assert_eq!(parsed, ("MyApp".to_string(), "do it".to_string(), 2));
+        assert!(parse_mfa("user_store:fetch").is_err());
+        assert!(parse_mfa("fetch/1").is_err());
+    }
+
+    #[test]
+    fn test_into_call_graph_functions_and_calls() {
+        let graph = convert(ERLANG_JSON).expect("Conversion should succeed");
+
+        let locations = &graph.function_locations[":user_store"];
+        assert_eq!(locations["fetch/1:12"].kind, "def");
+        assert_eq!(locations["fetch/1:12"].complexity, 2);
+        assert_eq!(locations["lookup/2:22"].kind, "defp");
+
+        let calls: Vec<(&str, &str, &str)> = graph
+            .calls
+            .iter()
+            .map(|c| (c.callee.module.as_str(), c.callee.function.as_str(), c.call_type.as_str()))
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                (":user_store", "lookup", "local"),
+                ("MyApp.Accounts", "normalize", "remote"),
+                (":ets", "lookup", "remote"),
+            ]
+        );
+        assert_eq!(graph.calls[2].caller.file, "src/user_store.erl");
+        // Called modules are not imported as project modules
+        assert!(!graph.function_locations.contains_key(":ets"));
+    }
+
+    #[test]
+    fn test_into_call_graph_records() {
+        let graph = convert(ERLANG_JSON).expect("Conversion should succeed");
+
+        let user = &graph.structs["#user"];
+        assert_eq!(user.fields[0].default, "undefined");
+        assert_eq!(user.fields[1].inferred_type.as_deref(), Some("binary()"));
+
+        let access = &graph.field_accesses[0];
+        assert_eq!(access.struct_module, "#user");
+        assert_eq!(access.caller.function.as_deref(), Some("fetch/1"));
+        assert_eq!(access.caller.file, "src/user_store.erl");
+        assert_eq!(access.access, "read");
+
+        assert_eq!(graph.behaviours[":user_store"], [":gen_server"]);
+    }
+
+    #[test]
+    fn test_into_call_graph_rejects_call_from_undefined_function() {
+        let json = r#"{"calls": [{"caller": "a:b/0", "callee": "c:d/0"}]}"#;
+        let error = convert(json).unwrap_err();
+        assert!(error.to_string().contains("call from undefined function 'a:b/0'"));
+    }
+}
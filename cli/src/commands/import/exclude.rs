@@ -0,0 +1,342 @@
+//! Leaving source paths out of an import.
+//!
+//! Records of excluded files are dropped before anything is written, so
+//! vendored code, generated modules or test support never reach the database
+//! and no query has to filter them. A path is excluded when it matches one of
+//! the globs, gathered from:
+//!
+//! - `deps` and `_build`, unless `--include-deps`;
+//! - `import.exclude_paths` of the config file;
+//! - `--exclude-path`.
+//!
+//! Globs follow `.gitignore`: `*` and `?` stay within a path segment and `**`
+//! spans segments; a glob without a slash matches a file or directory name at
+//! any depth, and one with a slash matches from the project root. A matching
+//! directory excludes everything below it.
+//!
+//! Unless `--no-gitignore`, a path is also excluded when git would ignore it:
+//! the `.gitignore` files of the current directory and its subdirectories are
+//! read as git reads them, with patterns relative to their own directory, the
+//! last matching pattern deciding, deeper files overriding shallower ones and
+//! `!` re-including a path, though not one below an ignored directory.
+
+use std::error::Error;
+use std::fs;
+use std::iter;
+use std::path::Path;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::ImportCmd;
+use crate::config::Config;
+use db::query_builders::path_glob_to_regex;
+use db::queries::import_models::{Call, CallGraph, FieldAccess, Message};
+
+/// Directories of dependencies and build artifacts, excluded by default
+const VENDORED: [&str; 2] = ["deps", "_build"];
+
+/// Records left out of one relation because of their path
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExcludedRecords {
+    pub relation: String,
+    pub count: usize,
+}
+
+/// One pattern of a `.gitignore`
+struct IgnoreRule {
+    /// Directory holding the `.gitignore`, relative to the project root and
+    /// ending in a slash; empty at the root
+    base: String,
+    /// Matches a path relative to `base`, or a path below it
+    pattern: Regex,
+    /// A `!` pattern, re-including what it matches
+    negated: bool,
+}
+
+impl IgnoreRule {
+    fn matches(&self, path: &str) -> bool {
+        path.strip_prefix(&self.base)
+            .is_some_and(|rest| !rest.is_empty() && self.pattern.is_match(rest))
+    }
+}
+
+/// Path globs of an import, counting the records they leave out
+pub struct PathExclusions {
+    patterns: Vec<Regex>,
+    /// `.gitignore` patterns, shallower files first and each in file order,
+    /// so the last one matching decides
+    ignore_rules: Vec<IgnoreRule>,
+    /// Records left out per relation, in the order they were first counted
+    excluded: Vec<ExcludedRecords>,
+}
+
+impl PathExclusions {
+    /// The exclusions of an import, from its options, config file and `.gitignore`
+    pub fn for_import(cmd: &ImportCmd) -> Result<Self, Box<dyn Error>> {
+        let mut globs: Vec<String> = Vec::new();
+        if !cmd.include_deps {
+            globs.extend(VENDORED.iter().map(|dir| dir.to_string()));
+        }
+        globs.extend(Config::load_or_default(&cmd.config)?.import.exclude_paths);
+        globs.extend(cmd.exclude_paths.iter().cloned());
+        let mut exclusions = Self::new(&globs)?;
+        if !cmd.no_gitignore {
+            exclusions.add_gitignores(Path::new("."))?;
+        }
+        Ok(exclusions)
+    }
+
+    pub fn new(globs: &[String]) -> Result<Self, String> {
+        let patterns = globs
+            .iter()
+            .filter(|glob| !glob.trim().trim_matches('/').is_empty())
+            .map(|glob| Regex::new(&path_glob_to_regex(glob)).map_err(|e| format!("Invalid exclude path '{}': {}", glob, e)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            patterns,
+            ignore_rules: Vec::new(),
+            excluded: Vec::new(),
+        })
+    }
+
+    /// Add the patterns of every `.gitignore` under `root`, skipping the
+    /// directories already excluded, whose files git does not read either
+    pub fn add_gitignores(&mut self, root: &Path) -> Result<(), String> {
+        let mut dirs = vec![String::new()];
+        while let Some(dir) = dirs.pop() {
+            let path = root.join(&dir);
+            self.ignore_rules.extend(gitignore_rules(&path.join(".gitignore"), &dir)?);
+            let Ok(entries) = fs::read_dir(&path) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name == ".git" || !entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                    continue;
+                }
+                let child = format!("{dir}{name}");
+                if !self.excludes(&child) {
+                    dirs.push(format!("{child}/"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `path` matches one of the globs or git ignores it
+    pub fn excludes(&self, path: &str) -> bool {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        self.patterns.iter().any(|pattern| pattern.is_match(path)) || self.ignored(path)
+    }
+
+    /// Whether the `.gitignore` patterns leave out `path`, deciding each
+    /// directory on the way down: below an ignored directory nothing is
+    /// re-included
+    fn ignored(&self, path: &str) -> bool {
+        if self.ignore_rules.is_empty() {
+            return false;
+        }
+        path.match_indices('/')
+            .map(|(end, _)| &path[..end])
+            .chain(iter::once(path))
+            .any(|prefix| {
+                self.ignore_rules
+                    .iter()
+                    .rev()
+                    .find(|rule| rule.matches(prefix))
+                    .is_some_and(|rule| !rule.negated)
+            })
+    }
+
+    pub fn keep_call(&mut self, call: &Call) -> bool {
+        self.keep("calls", &call.caller.file)
+    }
+
+    pub fn keep_message(&mut self, message: &Message) -> bool {
+        self.keep("messages", &message.caller.file)
+    }
+
+    pub fn keep_field_access(&mut self, access: &FieldAccess) -> bool {
+        self.keep("field_accesses", &access.caller.file)
+    }
+
+    /// Drop the records of excluded files from the graph.
+    ///
+    /// Modules whose every function location is excluded also lose their
+    /// structs, specs, types, behaviours, docs and aliases.
+    pub fn filter_graph(&mut self, graph: &mut CallGraph) {
+        if self.patterns.is_empty() && self.ignore_rules.is_empty() {
+            return;
+        }
+
+        let mut locations = 0;
+        let mut modules = Vec::new();
+        for (module, functions) in &mut graph.function_locations {
+            let before = functions.len();
+            functions.retain(|_, location| !location.file.as_deref().is_some_and(|file| self.excludes(file)));
+            locations += before - functions.len();
+            if before > 0 && functions.is_empty() {
+                modules.push(module.clone());
+            }
+        }
+        for module in &modules {
+            graph.function_locations.remove(module);
+            graph.structs.remove(module);
+            graph.specs.remove(module);
+            graph.types.remove(module);
+            graph.behaviours.remove(module);
+            graph.docs.remove(module);
+            graph.aliases.remove(module);
+        }
+        self.count("modules", modules.len());
+        self.count("function_locations", locations);
+
+        graph.calls.retain(|call| self.keep_call(call));
+        graph.messages.retain(|message| self.keep_message(message));
+        graph.field_accesses.retain(|access| self.keep_field_access(access));
+    }
+
+    /// Records left out per relation, for relations that lost any
+    pub fn excluded(&self) -> Vec<ExcludedRecords> {
+        self.excluded.clone()
+    }
+
+    fn keep(&mut self, relation: &str, file: &str) -> bool {
+        let excluded = self.excludes(file);
+        if excluded {
+            self.count(relation, 1);
+        }
+        !excluded
+    }
+
+    fn count(&mut self, relation: &str, count: usize) {
+        if count == 0 {
+            return;
+        }
+        match self.excluded.iter_mut().find(|records| records.relation == relation) {
+            Some(records) => records.count += count,
+            None => self.excluded.push(ExcludedRecords {
+                relation: relation.to_string(),
+                count,
+            }),
+        }
+    }
+}
+
+/// The patterns of a `.gitignore` in the directory `base`, without comments.
+/// A missing file has none.
+fn gitignore_rules(path: &Path, base: &str) -> Result<Vec<IgnoreRule>, String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(Vec::new());
+    };
+    let mut rules = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (negated, glob) = match line.strip_prefix('!') {
+            Some(glob) => (true, glob),
+            None => (false, line),
+        };
+        // `\#` and `\!` start patterns with a literal `#` or `!`
+        let glob = glob.strip_prefix('\\').unwrap_or(glob);
+        if glob.trim_matches('/').is_empty() {
+            continue;
+        }
+        let pattern = Regex::new(&path_glob_to_regex(glob))
+            .map_err(|e| format!("Invalid pattern '{}' in {}: {}", line, path.display(), e))?;
+        rules.push(IgnoreRule {
+            base: base.to_string(),
+            pattern,
+            negated,
+        });
+    }
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exclusions(globs: &[&str]) -> PathExclusions {
+        PathExclusions::new(&globs.iter().map(|glob| glob.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn test_glob_matching() {
+        let exclusions = exclusions(&["deps", "/test/support/", "*.pb.ex", "lib/**/generated/*.ex"]);
+        assert!(exclusions.excludes("deps/jason/lib/jason.ex"));
+        assert!(exclusions.excludes("apps/billing/deps/x.ex"));
+        assert!(exclusions.excludes("./test/support/factory.ex"));
+        assert!(exclusions.excludes("lib/proto/user.pb.ex"));
+        assert!(exclusions.excludes("lib/generated/a.ex"));
+        assert!(exclusions.excludes("lib/my_app/api/generated/a.ex"));
+
+        assert!(!exclusions.excludes("lib/my_app/deps_resolver.ex"));
+        assert!(!exclusions.excludes("apps/web/test/support/conn_case.ex"));
+        assert!(!exclusions.excludes("lib/generated/nested/a.ex"));
+    }
+
+    #[test]
+    fn test_gitignore_rules_skip_comments() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(".gitignore");
+        fs::write(&path, "# Build output\n/_build/\n\n!keep.ex\n*.ez\n").unwrap();
+        let rules = gitignore_rules(&path, "").unwrap();
+        assert_eq!(rules.iter().map(|rule| rule.negated).collect::<Vec<_>>(), [false, true, false]);
+        assert!(gitignore_rules(&dir.path().join("missing"), "").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_gitignore_negation_and_nesting() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join(".gitignore"), "*.pb.ex\n!keep.pb.ex\n/build/\n!/build/keep.ex\nsecret/\n").unwrap();
+        fs::create_dir_all(dir.path().join("lib/proto")).unwrap();
+        fs::write(dir.path().join("lib/proto/.gitignore"), "/local.ex\n!user.pb.ex\n").unwrap();
+        fs::create_dir_all(dir.path().join("secret/nested")).unwrap();
+        fs::write(dir.path().join("secret/nested/.gitignore"), "!*.ex\n").unwrap();
+
+        let mut exclusions = exclusions(&[]);
+        exclusions.add_gitignores(dir.path()).unwrap();
+
+        assert!(exclusions.excludes("lib/api.pb.ex"));
+        assert!(!exclusions.excludes("lib/keep.pb.ex"));
+        // Nested patterns are relative to their directory and override the root's
+        assert!(exclusions.excludes("lib/proto/local.ex"));
+        assert!(!exclusions.excludes("lib/local.ex"));
+        assert!(!exclusions.excludes("lib/proto/user.pb.ex"));
+        assert!(exclusions.excludes("lib/proto/order.pb.ex"));
+        // Nothing below an ignored directory comes back, and its files are not read
+        assert!(exclusions.excludes("build/keep.ex"));
+        assert!(exclusions.excludes("./secret/nested/a.ex"));
+    }
+
+    #[test]
+    fn test_filter_graph() {
+        let mut graph: CallGraph = serde_json::from_str(
+            r#"{
+                "structs": {"Jason.Encoder": {"fields": []}},
+                "function_locations": {
+                    "Jason": {"encode/1:5": {"name": "encode", "arity": 1, "file": "deps/jason/lib/jason.ex", "kind": "def", "line": 5, "start_line": 5, "end_line": 9}},
+                    "Jason.Encoder": {"encode/2:3": {"name": "encode", "arity": 2, "file": "deps/jason/lib/encoder.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 4}},
+                    "MyApp": {"run/0:2": {"name": "run", "arity": 0, "file": "lib/my_app.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}}
+                },
+                "calls": [
+                    {"caller": {"module": "MyApp", "function": "run/0", "file": "lib/my_app.ex", "line": 2}, "callee": {"module": "Jason", "function": "encode", "arity": 1}, "type": "remote"},
+                    {"caller": {"module": "Jason", "function": "encode/1", "file": "deps/jason/lib/jason.ex", "line": 6}, "callee": {"module": "Jason.Encoder", "function": "encode", "arity": 2}, "type": "remote"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let mut exclusions = exclusions(&["deps"]);
+        exclusions.filter_graph(&mut graph);
+
+        assert_eq!(graph.function_locations.keys().collect::<Vec<_>>(), ["MyApp"]);
+        assert!(graph.structs.is_empty());
+        assert_eq!(graph.calls.len(), 1);
+        let counts: Vec<(&str, usize)> = exclusions.excluded.iter().map(|r| (r.relation.as_str(), r.count)).collect();
+        assert_eq!(counts, [("modules", 2), ("function_locations", 2), ("calls", 1)]);
+    }
+}
@@ -1,44 +1,308 @@
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fs;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use db::DbInstance;
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::dedup::{dedup_graph, parse_dedup_keys, DroppedDuplicates};
+use super::erlang::ErlangGraph;
+use super::exclude::{ExcludedRecords, PathExclusions};
+use super::history::stamp_modifications;
+use super::models::GenericGraph;
+use super::validation::{check_records, check_supervision, RejectedRecord};
+use super::stream::stream_import;
+use super::test_paths;
+use super::{ImportCmd, ImportSchema};
+use crate::commands::{Execute, SummaryCmd};
+use crate::config::Config;
+use db::queries::import::{clear_file_data, clear_project_data, import_graph_with_call_sites, ImportError, ImportResult};
+use db::queries::import_checkpoints::Checkpoint;
+use db::queries::import_models::{CallGraph, SupervisionDump, SupervisorDef};
+use db::queries::snapshots::{record_snapshot, MetricSnapshot};
+use db::query_builders::QuerySettings;
+
+/// Result of the import command: the rows written, the duplicates dropped, the
+/// records of excluded paths and the records `--lenient` skipped
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    #[serde(flatten)]
+    pub result: ImportResult,
+    /// Whether the import finished an interrupted one (`--resume`)
+    pub resumed: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<DroppedDuplicates>,
+    /// Records of excluded paths left out, per relation
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub excluded: Vec<ExcludedRecords>,
+    /// Skipped records per relation, in file order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedRelation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_records: Vec<RejectedRecord>,
+}
+
+/// Number of malformed records left out of one relation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkippedRelation {
+    pub relation: String,
+    pub count: usize,
+}
 
-use super::ImportCmd;
-use crate::commands::Execute;
-use db::queries::import::{clear_project_data, import_graph, ImportError, ImportResult};
-use db::queries::import_models::CallGraph;
+/// The call graph of an import, with the supervision dump merged in
+pub struct LoadedGraph {
+    pub graph: CallGraph,
+    /// Malformed records left out by `--lenient`
+    pub skipped: Vec<RejectedRecord>,
+}
+
+/// Outcome of writing a call graph, before the snapshot
+pub struct Imported {
+    pub result: ImportResult,
+    pub duplicates: Vec<DroppedDuplicates>,
+    pub excluded: Vec<ExcludedRecords>,
+    pub skipped: Vec<RejectedRecord>,
+}
 
 impl Execute for ImportCmd {
-    type Output = ImportResult;
+    type Output = ImportReport;
+
+    fn execute(self, db: &DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let keys = dedup_keys(&self)?;
+        let Imported {
+            mut result,
+            duplicates,
+            excluded,
+            skipped,
+        } = match self.schema {
+            ImportSchema::Elixir => stream_import(&self, db, &keys)?,
+            // Converted schemas resolve names across the whole graph
+            ImportSchema::Generic | ImportSchema::Erlang => {
+                let LoadedGraph { mut graph, skipped } = load_graph(&self)?;
+                let mut exclusions = PathExclusions::for_import(&self)?;
+                exclusions.filter_graph(&mut graph);
+                test_paths::mark_for_import(&self, &mut graph)?;
+                let duplicates = dedup_graph(&mut graph, &keys)?;
+                stamp_modifications(&self, db, &mut graph);
+                let checkpoint = begin_writes(&self, db)?;
+                let result = import_graph_with_call_sites(db, &self.project, &graph, Some(&checkpoint), |_| Ok(()))?;
+                checkpoint.finish(db)?;
+
+                Imported {
+                    result,
+                    duplicates,
+                    excluded: exclusions.excluded(),
+                    skipped,
+                }
+            }
+        };
+        result.cleared = self.clear;
 
-    fn execute(self, db: &DbInstance) -> Result<Self::Output, Box<dyn Error>> {
-        // Read and parse call graph
-        let content = fs::read_to_string(&self.file).map_err(|e| ImportError::FileReadFailed {
-            path: self.file.display().to_string(),
-            message: e.to_string(),
-        })?;
+        if !self.no_snapshot {
+            record_import_snapshot(db, &self.project, self.label)?;
+            result.snapshot_recorded = true;
+        }
+
+        Ok(ImportReport {
+            result,
+            resumed: self.resume,
+            duplicates,
+            excluded,
+            skipped: skipped_relations(&skipped),
+            skipped_records: skipped,
+        })
+    }
+}
+
+/// Prepare the database once the files are checked.
+///
+/// Resumes the checkpoint of the interrupted import of the file, or starts a
+/// new one, clearing the project's data or the replaced files' rows first if
+/// requested.
+pub fn begin_writes(cmd: &ImportCmd, db: &DbInstance) -> Result<Checkpoint, Box<dyn Error>> {
+    let size = fs::metadata(&cmd.file)?.len();
+    let source = format!("{} ({} bytes)", cmd.file.display(), size);
+    if cmd.resume {
+        return Checkpoint::resume(db, &cmd.project, &source);
+    }
+
+    let checkpoint = Checkpoint::start(db, &cmd.project, &source)?;
+    if cmd.clear {
+        clear_project_data(db, &cmd.project)?;
+    }
+    clear_file_data(db, &cmd.project, &cmd.replace_files)?;
+    Ok(checkpoint)
+}
+
+/// Read, validate and parse the files of an import.
+///
+/// Strict imports fail on the first malformed record, naming it by its JSON
+/// pointer; lenient ones leave malformed records out and return them.
+pub fn load_graph(cmd: &ImportCmd) -> Result<LoadedGraph, ImportError> {
+    let mut value = read_json(&cmd.file)?;
+    let mut skipped = validate(check_records(&mut value, cmd.schema, cmd.lenient), None, cmd.lenient)?;
+    let mut graph = parse_call_graph(value, cmd.schema)?;
+
+    // A separate supervision dump adds to any supervision data in the call graph
+    if let Some(path) = &cmd.supervision {
+        let (supervision, rejected) = load_supervision(path, cmd.lenient)?;
+        graph.supervision.extend(supervision);
+        skipped.extend(rejected);
+    }
 
-        let graph: CallGraph =
-            serde_json::from_str(&content).map_err(|e| ImportError::JsonParseFailed {
+    Ok(LoadedGraph { graph, skipped })
+}
+
+/// Read and validate a `--supervision` dump, like [`load_graph`]
+pub fn load_supervision(path: &Path, lenient: bool) -> Result<(Vec<SupervisorDef>, Vec<RejectedRecord>), ImportError> {
+    let mut value = read_json(path)?;
+    let skipped = validate(check_supervision(&mut value, lenient), Some(path), lenient)?;
+    let dump: SupervisionDump = serde_json::from_value(value).map_err(|e| ImportError::JsonParseFailed {
+        message: e.to_string(),
+    })?;
+    Ok((dump.supervision, skipped))
+}
+
+/// Open a JSON file, decompressing `.gz` and `.zst` files as they are read
+pub fn open_json(path: &Path) -> Result<BufReader<Box<dyn Read>>, ImportError> {
+    let read_failed = |message: String| ImportError::FileReadFailed {
+        path: path.display().to_string(),
+        message,
+    };
+    let file = fs::File::open(path).map_err(|e| read_failed(e.to_string()))?;
+
+    let reader: Box<dyn Read> = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gz") => Box::new(GzDecoder::new(file)),
+        #[cfg(feature = "zstd")]
+        Some("zst") => Box::new(zstd::Decoder::new(file).map_err(|e| read_failed(e.to_string()))?),
+        #[cfg(not(feature = "zstd"))]
+        Some("zst") => {
+            return Err(read_failed("reading .zst files needs code_search built with the `zstd` feature".to_string()));
+        }
+        _ => Box::new(file),
+    };
+    Ok(BufReader::new(reader))
+}
+
+/// Parse a JSON file, see [`open_json`]
+pub fn read_json(path: &Path) -> Result<Value, ImportError> {
+    serde_json::from_reader(open_json(path)?).map_err(|e| {
+        if e.is_io() {
+            ImportError::FileReadFailed {
+                path: path.display().to_string(),
                 message: e.to_string(),
-            })?;
+            }
+        } else {
+            ImportError::JsonParseFailed {
+                message: e.to_string(),
+            }
+        }
+    })
+}
 
-        // Clear existing data if requested
-        if self.clear {
-            clear_project_data(db, &self.project)?;
+/// Dedup keys of the import: those of the config file, overridden by `--dedup`
+pub fn dedup_keys(cmd: &ImportCmd) -> Result<BTreeMap<String, Vec<String>>, Box<dyn Error>> {
+    let mut keys = Config::load_or_default(&cmd.config)?.import.dedup;
+    keys.extend(parse_dedup_keys(&cmd.dedup)?);
+    Ok(keys)
+}
+
+/// The records a lenient import skips, or the error of a strict one.
+///
+/// Records of the `--supervision` file are named `<file>#<pointer>`.
+pub fn validate(
+    checked: Result<Vec<RejectedRecord>, String>,
+    file: Option<&Path>,
+    lenient: bool,
+) -> Result<Vec<RejectedRecord>, ImportError> {
+    let parse_failed = |message: String| ImportError::JsonParseFailed { message };
+    let mut rejected = checked.map_err(parse_failed)?;
+    if let Some(file) = file {
+        for record in &mut rejected {
+            record.path = format!("{}#{}", file.display(), record.path);
         }
+    }
 
-        // Import data
-        let mut result = import_graph(db, &self.project, &graph)?;
-        result.cleared = self.clear;
+    match rejected.first() {
+        Some(first) if !lenient => {
+            let mut message = format!("{}: {}", first.path, first.reason);
+            if rejected.len() > 1 {
+                message.push_str(&format!(
+                    " ({} more malformed records, listed by `import --dry-run`)",
+                    rejected.len() - 1
+                ));
+            }
+            Err(parse_failed(message))
+        }
+        _ => Ok(rejected),
+    }
+}
 
-        Ok(result)
+/// Group skipped records by relation, in order of first appearance
+fn skipped_relations(records: &[RejectedRecord]) -> Vec<SkippedRelation> {
+    let mut relations: Vec<SkippedRelation> = Vec::new();
+    for record in records {
+        match relations.iter_mut().find(|r| r.relation == record.relation) {
+            Some(relation) => relation.count += 1,
+            None => relations.push(SkippedRelation {
+                relation: record.relation.clone(),
+                count: 1,
+            }),
+        }
+    }
+    relations
+}
+
+/// Parse the call graph according to its schema
+pub fn parse_call_graph(value: Value, schema: ImportSchema) -> Result<CallGraph, ImportError> {
+    let parse_error = |e: serde_json::Error| ImportError::JsonParseFailed {
+        message: e.to_string(),
+    };
+    match schema {
+        ImportSchema::Elixir => serde_json::from_value(value).map_err(parse_error),
+        ImportSchema::Generic => serde_json::from_value::<GenericGraph>(value)
+            .map_err(parse_error)?
+            .into_call_graph(),
+        ImportSchema::Erlang => serde_json::from_value::<ErlangGraph>(value)
+            .map_err(parse_error)?
+            .into_call_graph(),
+    }
+}
+
+/// Record the project's summary metrics after an import, for `trends`
+///
+/// Snapshots always cover the whole project, whatever scope the import runs
+/// with, so that every snapshot in a trend counts the same modules.
+fn record_import_snapshot(db: &DbInstance, project: &str, label: String) -> Result<(), Box<dyn Error>> {
+    let summary = SummaryCmd {
+        project: project.to_string(),
+        top: 1,
     }
+    .execute(db, &QuerySettings::default())?;
+
+    let taken_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64;
+    let snapshot = MetricSnapshot {
+        taken_at,
+        label,
+        modules: summary.counts.modules,
+        functions: summary.counts.functions,
+        calls: summary.counts.calls,
+        unused: summary.unused_functions as i64,
+        cycles: summary.cycles as i64,
+        average_complexity: summary.average_complexity,
+    };
+    record_snapshot(db, project, &snapshot)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::DEFAULT_CONFIG_PATH;
     use db::open_db;
     use rstest::{fixture, rstest};
     use std::io::Write;
@@ -126,11 +390,27 @@ mod tests {
     fn import_result(json_file: NamedTempFile, db_file: NamedTempFile) -> ImportResult {
         let cmd = ImportCmd {
             file: json_file.path().to_path_buf(),
+            schema: ImportSchema::Elixir,
             project: "test_project".to_string(),
             clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: false,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
         };
         let db = open_db(db_file.path()).expect("Failed to open db");
-        cmd.execute(&db).expect("Import should succeed")
+        cmd.execute(&db, &QuerySettings::default()).expect("Import should succeed").result
     }
 
     #[rstest]
@@ -168,22 +448,55 @@ mod tests {
         // First import
         let cmd1 = ImportCmd {
             file: json_file.path().to_path_buf(),
+            schema: ImportSchema::Elixir,
             project: "test_project".to_string(),
             clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: false,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
         };
         let db = open_db(db_file.path()).expect("Failed to open db");
-        cmd1.execute(&db)
+        cmd1.execute(&db, &QuerySettings::default())
             .expect("First import should succeed");
 
         // Second import with clear
         let cmd2 = ImportCmd {
             file: json_file.path().to_path_buf(),
+            schema: ImportSchema::Elixir,
             project: "test_project".to_string(),
             clear: true,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: false,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
         };
         let result = cmd2
-            .execute(&db)
-            .expect("Second import should succeed");
+            .execute(&db, &QuerySettings::default())
+            .expect("Second import should succeed")
+            .result;
 
         assert!(result.cleared);
         assert_eq!(result.modules_imported, 2);
@@ -202,12 +515,28 @@ mod tests {
 
         let cmd = ImportCmd {
             file: json_file.path().to_path_buf(),
+            schema: ImportSchema::Elixir,
             project: "test_project".to_string(),
             clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: false,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
         };
 
         let db = open_db(db_file.path()).expect("Failed to open db");
-        let result = cmd.execute(&db).expect("Import should succeed");
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Import should succeed").result;
 
         assert_eq!(result.modules_imported, 0);
         assert_eq!(result.functions_imported, 0);
@@ -223,25 +552,462 @@ mod tests {
 
         let cmd = ImportCmd {
             file: json_file.path().to_path_buf(),
+            schema: ImportSchema::Elixir,
             project: "test_project".to_string(),
             clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: false,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
         };
 
         let db = open_db(db_file.path()).expect("Failed to open db");
-        let result = cmd.execute(&db);
+        let result = cmd.execute(&db, &QuerySettings::default());
         assert!(result.is_err());
     }
 
+    /// A call graph whose second call lacks its caller
+    const MALFORMED_CALL_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {},
+        "calls": [
+            {"caller": {"module": "A", "file": "a.ex"}, "callee": {"module": "B", "function": "f", "arity": 0}, "type": "remote"},
+            {"callee": {"module": "B", "function": "g", "arity": 0}, "type": "remote"}
+        ]
+    }"#;
+
+    fn malformed_import(json_file: &NamedTempFile, lenient: bool) -> ImportCmd {
+        ImportCmd {
+            file: json_file.path().to_path_buf(),
+            schema: ImportSchema::Elixir,
+            project: "test_project".to_string(),
+            clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: true,
+            dry_run: false,
+            strict: !lenient,
+            lenient,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
+        }
+    }
+
+    #[rstest]
+    fn test_strict_import_names_malformed_record(db_file: NamedTempFile) {
+        let json_file = create_temp_json_file(MALFORMED_CALL_JSON);
+        let db = open_db(db_file.path()).expect("Failed to open db");
+
+        let err = malformed_import(&json_file, false).execute(&db, &QuerySettings::default()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse call graph JSON: /calls/1: missing field `caller`"
+        );
+    }
+
+    #[rstest]
+    fn test_lenient_import_skips_malformed_records(db_file: NamedTempFile) {
+        let json_file = create_temp_json_file(MALFORMED_CALL_JSON);
+        let db = open_db(db_file.path()).expect("Failed to open db");
+
+        let report = malformed_import(&json_file, true)
+            .execute(&db, &QuerySettings::default())
+            .expect("Import should succeed");
+        assert_eq!(report.result.calls_imported, 1);
+        assert_eq!(
+            report.skipped,
+            [SkippedRelation {
+                relation: "calls".to_string(),
+                count: 1
+            }]
+        );
+        assert_eq!(report.skipped_records[0].path, "/calls/1");
+    }
+
+    #[rstest]
+    fn test_import_drops_duplicate_calls(db_file: NamedTempFile) {
+        let call = r#"{"caller": {"module": "A", "function": "run", "file": "a.ex", "line": 3}, "callee": {"module": "B", "function": "f", "arity": 0}, "type": "remote"}"#;
+        let json_file = create_temp_json_file(&format!(
+            r#"{{"structs": {{}}, "function_locations": {{}}, "calls": [{call}, {call}, {}]}}"#,
+            call.replace("\"line\": 3", "\"line\": 9")
+        ));
+        let db = open_db(db_file.path()).expect("Failed to open db");
+
+        let report = malformed_import(&json_file, false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Import should succeed");
+        assert_eq!(report.result.calls_imported, 2);
+        assert_eq!(report.duplicates[0].dropped, 1);
+
+        let cmd = ImportCmd {
+            dedup: vec!["calls=caller_module,caller_function,callee_module,callee_function,callee_arity".to_string()],
+            clear: true,
+            ..malformed_import(&json_file, false)
+        };
+        let report = cmd.execute(&db, &QuerySettings::default()).expect("Import should succeed");
+        assert_eq!(report.result.calls_imported, 1);
+        assert_eq!(report.duplicates[0].dropped, 2);
+    }
+
+    #[rstest]
+    fn test_import_adds_columns_of_older_databases(json_file: NamedTempFile, db_file: NamedTempFile) {
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        db::queries::schema::create_schema(&db).unwrap();
+        db.run_script_str("::remove modules", "", false);
+        db.run_script_str(":create modules { project: String, name: String => file: String }", "", false);
+
+        let report = malformed_import(&json_file, false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Import should succeed");
+        assert_eq!(report.result.schemas.repaired[0].relation, "modules");
+        assert_eq!(report.result.modules_imported, 2);
+    }
+
+    #[rstest]
+    fn test_import_excludes_paths(db_file: NamedTempFile) {
+        let location = |file: &str| format!(r#"{{"name": "run", "arity": 0, "file": "{file}", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}}"#);
+        let call = |file: &str| format!(r#"{{"caller": {{"module": "A", "function": "run", "file": "{file}", "line": 2}}, "callee": {{"module": "B", "function": "f", "arity": 0}}, "type": "remote"}}"#);
+        let json = format!(
+            r#"{{"structs": {{}}, "function_locations": {{"A": {{"run/0:2": {}}}, "Dep": {{"run/0:2": {}}}, "Factory": {{"run/0:2": {}}}}}, "calls": [{}, {}, {}]}}"#,
+            location("lib/a.ex"),
+            location("deps/dep/lib/dep.ex"),
+            location("test/support/factory.ex"),
+            call("lib/a.ex"),
+            call("deps/dep/lib/dep.ex"),
+            call("test/support/factory.ex"),
+        );
+        let json_file = create_temp_json_file(&json);
+        let db = open_db(db_file.path()).expect("Failed to open db");
+
+        let cmd = ImportCmd {
+            exclude_paths: vec!["test/support".to_string()],
+            no_gitignore: true,
+            ..malformed_import(&json_file, false)
+        };
+        let report = cmd.execute(&db, &QuerySettings::default()).expect("Import should succeed");
+        assert_eq!(report.result.calls_imported, 1);
+        assert_eq!(report.result.function_locations_imported, 1);
+        let excluded: Vec<(&str, usize)> = report.excluded.iter().map(|r| (r.relation.as_str(), r.count)).collect();
+        assert_eq!(excluded, [("modules", 2), ("function_locations", 2), ("calls", 2)]);
+
+        let cmd = ImportCmd {
+            include_deps: true,
+            no_gitignore: true,
+            clear: true,
+            ..malformed_import(&json_file, false)
+        };
+        let report = cmd.execute(&db, &QuerySettings::default()).expect("Import should succeed");
+        assert_eq!(report.result.calls_imported, 3);
+        assert!(report.excluded.is_empty());
+    }
+
+    fn compressed_import(json_file: &NamedTempFile) -> ImportCmd {
+        ImportCmd {
+            no_snapshot: true,
+            ..malformed_import(json_file, false)
+        }
+    }
+
+    #[rstest]
+    #[case::gzip(".json.gz")]
+    #[cfg_attr(feature = "zstd", case::zstd(".json.zst"))]
+    fn test_import_compressed_file(#[case] suffix: &str, db_file: NamedTempFile) {
+        let json = sample_call_graph_json().as_bytes();
+        let compressed = match suffix {
+            #[cfg(feature = "zstd")]
+            ".json.zst" => zstd::encode_all(json, 0).unwrap(),
+            _ => {
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(json).unwrap();
+                encoder.finish().unwrap()
+            }
+        };
+        let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+        file.write_all(&compressed).unwrap();
+        let db = open_db(db_file.path()).expect("Failed to open db");
+
+        let report = compressed_import(&file).execute(&db, &QuerySettings::default()).expect("Import should succeed");
+        assert_eq!(report.result.calls_imported, 1);
+    }
+
+    #[rstest]
+    fn test_import_corrupt_compressed_file_fails(db_file: NamedTempFile) {
+        let mut file = tempfile::Builder::new().suffix(".json.gz").tempfile().unwrap();
+        file.write_all(b"not gzip").unwrap();
+        let db = open_db(db_file.path()).expect("Failed to open db");
+
+        let err = compressed_import(&file).execute(&db, &QuerySettings::default()).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to read call graph file"));
+    }
+
+    #[rstest]
+    fn test_resume_skips_recorded_chunks(json_file: NamedTempFile, db_file: NamedTempFile) {
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        let cmd = compressed_import(&json_file);
+
+        // An import interrupted after recording the modules chunk, before writing it
+        let checkpoint = begin_writes(&cmd, &db).unwrap();
+        checkpoint.record(&db, "modules", 1).unwrap();
+
+        let resume = ImportCmd { resume: true, ..compressed_import(&json_file) };
+        let report = resume.execute(&db, &QuerySettings::default()).expect("Resume should succeed");
+        assert!(report.resumed);
+        assert_eq!(report.result.calls_imported, 1);
+        let modules = db.run_script_str("?[name] := *modules{name}", "", true);
+        assert!(modules.contains(r#""rows":[]"#), "{}", modules);
+
+        // The completed import removed its checkpoint
+        let err = ImportCmd { resume: true, ..compressed_import(&json_file) }.execute(&db, &QuerySettings::default()).unwrap_err();
+        assert_eq!(err.to_string(), "No interrupted import of project 'test_project' to resume");
+    }
+
+    #[rstest]
+    fn test_resume_refuses_changed_file(json_file: NamedTempFile, db_file: NamedTempFile) {
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        begin_writes(&compressed_import(&json_file), &db).unwrap();
+
+        fs::write(json_file.path(), format!("{}\n", sample_call_graph_json())).unwrap();
+        let cmd = ImportCmd { resume: true, ..compressed_import(&json_file) };
+        let err = cmd.execute(&db, &QuerySettings::default()).unwrap_err();
+        assert!(err.to_string().contains("The interrupted import of project 'test_project' was from"));
+    }
+
     #[rstest]
     fn test_import_nonexistent_file_fails(db_file: NamedTempFile) {
         let cmd = ImportCmd {
             file: "/nonexistent/path/call_graph.json".into(),
+            schema: ImportSchema::Elixir,
             project: "test_project".to_string(),
             clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: false,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
         };
 
         let db = open_db(db_file.path()).expect("Failed to open db");
-        let result = cmd.execute(&db);
+        let result = cmd.execute(&db, &QuerySettings::default());
         assert!(result.is_err());
     }
+
+    #[rstest]
+    fn test_import_with_supervision_dump(json_file: NamedTempFile, db_file: NamedTempFile) {
+        let dump = create_temp_json_file(
+            r#"{"supervision": [{"supervisor": "MyApp.Application", "children": [{"id": "MyApp.Repo"}]}]}"#,
+        );
+
+        let cmd = ImportCmd {
+            file: json_file.path().to_path_buf(),
+            schema: ImportSchema::Elixir,
+            project: "test_project".to_string(),
+            clear: false,
+            supervision: Some(dump.path().to_path_buf()),
+            label: String::new(),
+            no_snapshot: false,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
+        };
+
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Import should succeed").result;
+        assert_eq!(result.supervision_imported, 1);
+        assert_eq!(result.calls_imported, 1);
+    }
+
+    #[rstest]
+    fn test_import_records_snapshot(json_file: NamedTempFile, db_file: NamedTempFile) {
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        for (label, no_snapshot) in [("v1", false), ("v2", true)] {
+            let cmd = ImportCmd {
+                file: json_file.path().to_path_buf(),
+                schema: ImportSchema::Elixir,
+                project: "test_project".to_string(),
+                clear: false,
+                supervision: None,
+                label: label.to_string(),
+                no_snapshot,
+                dry_run: false,
+                strict: false,
+                lenient: false,
+                dedup: Vec::new(),
+                resume: false,
+                commit: None,
+                modified_at: None,
+                replace_files: Vec::new(),
+                exclude_paths: Vec::new(),
+                include_deps: false,
+                no_gitignore: false,
+                config: DEFAULT_CONFIG_PATH.into(),
+            };
+            let result = cmd.execute(&db, &QuerySettings::default()).expect("Import should succeed").result;
+            assert_eq!(result.snapshot_recorded, !no_snapshot);
+        }
+
+        let snapshots = db::queries::snapshots::find_snapshots(&db, "test_project", 10).expect("Query should succeed");
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].label, "v1");
+        assert_eq!(snapshots[0].modules, 2);
+        assert_eq!(snapshots[0].calls, 1);
+    }
+
+    // The snapshot counts the whole project, not the scope the import runs with
+    #[rstest]
+    fn test_import_snapshot_ignores_scope(json_file: NamedTempFile, db_file: NamedTempFile) {
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        let cmd = ImportCmd {
+            file: json_file.path().to_path_buf(),
+            schema: ImportSchema::Elixir,
+            project: "test_project".to_string(),
+            clear: false,
+            supervision: None,
+            label: "v1".to_string(),
+            no_snapshot: false,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
+        };
+        let settings = QuerySettings { namespaces: vec!["Elsewhere".to_string()], ..Default::default() };
+        cmd.execute(&db, &settings).expect("Import should succeed");
+
+        let snapshots = db::queries::snapshots::find_snapshots(&db, "test_project", 10).expect("Query should succeed");
+        let summary = SummaryCmd { project: "test_project".to_string(), top: 1 }
+            .execute(&db, &QuerySettings::default())
+            .expect("Summary should succeed");
+        assert!(summary.unused_functions > 0);
+        assert_eq!(snapshots[0].unused, summary.unused_functions as i64);
+    }
+
+    #[rstest]
+    fn test_import_generic_schema(db_file: NamedTempFile) {
+        let json_file = create_temp_json_file(
+            r#"{
+                "version": 1,
+                "language": "ruby",
+                "nodes": [
+                    {"id": "f1", "kind": "function", "module": "Billing::Invoice", "name": "total", "arity": 0,
+                     "file": "app/billing/invoice.rb", "line": 5, "end_line": 9},
+                    {"id": "f2", "kind": "function", "module": "Billing::Tax", "name": "rate", "arity": 1,
+                     "file": "app/billing/tax.rb", "line": 2, "end_line": 4}
+                ],
+                "edges": [{"kind": "call", "from": "f1", "to": "f2", "line": 7}]
+            }"#,
+        );
+
+        let cmd = ImportCmd {
+            file: json_file.path().to_path_buf(),
+            schema: ImportSchema::Generic,
+            project: "test_project".to_string(),
+            clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: true,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
+        };
+
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Import should succeed").result;
+        assert_eq!(result.modules_imported, 2);
+        assert_eq!(result.function_locations_imported, 2);
+        assert_eq!(result.calls_imported, 1);
+    }
+
+    #[rstest]
+    fn test_import_erlang_schema(db_file: NamedTempFile) {
+        let json_file = create_temp_json_file(
+            r#"{
+                "functions": [
+                    {"mfa": "user_store:fetch/1", "file": "src/user_store.erl", "line": 12, "end_line": 20, "exported": true}
+                ],
+                "calls": [{"caller": "user_store:fetch/1", "callee": "'Elixir.MyApp.Accounts':get_user/1", "line": 14}],
+                "records": {"user": {"fields": [{"name": "id"}, {"name": "email"}]}}
+            }"#,
+        );
+
+        let cmd = ImportCmd {
+            file: json_file.path().to_path_buf(),
+            schema: ImportSchema::Erlang,
+            project: "test_project".to_string(),
+            clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: true,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
+        };
+
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Import should succeed").result;
+        assert_eq!(result.modules_imported, 2); // :user_store + #user
+        assert_eq!(result.structs_imported, 2);
+        assert_eq!(result.calls_imported, 1);
+    }
 }
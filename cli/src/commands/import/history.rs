@@ -0,0 +1,149 @@
+//! Modification times and revisions of imported function clauses.
+//!
+//! Extractors may record when each clause last changed (`modified_at`) and in
+//! which revision (`commit`). Clauses without them are dated here, before
+//! anything is written: a clause whose `source_sha` matches the stored one has
+//! not changed and keeps its recorded time and revision; any other clause gets
+//! `--modified-at`, or else the modification time of its source file, and
+//! `--commit`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use db::queries::import_models::CallGraph;
+use db::queries::timeline::find_clause_modifications;
+use db::DbInstance;
+
+use super::ImportCmd;
+
+/// Fill in the modification time and revision of clauses that carry none
+pub fn stamp_modifications(cmd: &ImportCmd, db: &DbInstance, graph: &mut CallGraph) {
+    // A new database has no relations yet, an old one no timeline columns
+    let stored: HashMap<(String, String, i64, String), (i64, String)> = find_clause_modifications(db, &cmd.project)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|m| ((m.module, m.name, m.arity, m.source_sha), (m.modified_at, m.commit)))
+        .collect();
+    let mut file_times: HashMap<String, Option<i64>> = HashMap::new();
+
+    for (module, clauses) in graph.function_locations.iter_mut() {
+        for clause in clauses.values_mut() {
+            if clause.modified_at == 0 {
+                let key = (
+                    module.clone(),
+                    clause.name.clone(),
+                    clause.arity as i64,
+                    clause.source_sha.clone().unwrap_or_default(),
+                );
+                match stored.get(&key) {
+                    Some((modified_at, commit)) if !key.3.is_empty() => {
+                        clause.modified_at = *modified_at;
+                        if clause.commit.is_none() && !commit.is_empty() {
+                            clause.commit = Some(commit.clone());
+                        }
+                    }
+                    _ => {
+                        let path = clause.source_file_absolute.as_ref().or(clause.file.as_ref());
+                        clause.modified_at = cmd
+                            .modified_at
+                            .or_else(|| {
+                                let path = path?;
+                                *file_times.entry(path.clone()).or_insert_with(|| file_time(Path::new(path)))
+                            })
+                            .unwrap_or(0);
+                    }
+                }
+            }
+            if clause.commit.is_none() {
+                clause.commit = cmd.commit.clone();
+            }
+        }
+    }
+}
+
+/// Modification time of a file in Unix seconds, if it can be read
+fn file_time(path: &Path) -> Option<i64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_secs() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEFAULT_CONFIG_PATH;
+    use crate::commands::import::ImportSchema;
+
+    fn graph(source_sha: &str, file: &str) -> CallGraph {
+        serde_json::from_str(&format!(
+            r#"{{
+                "structs": {{}},
+                "function_locations": {{
+                    "MyApp.Accounts": {{
+                        "get_user/1:2": {{"name": "get_user", "arity": 1, "file": "{file}", "kind": "def", "line": 2, "start_line": 2, "end_line": 4, "source_sha": "{source_sha}"}}
+                    }}
+                }},
+                "calls": []
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    fn clause(graph: &CallGraph) -> (i64, Option<&str>) {
+        let clause = &graph.function_locations["MyApp.Accounts"]["get_user/1:2"];
+        (clause.modified_at, clause.commit.as_deref())
+    }
+
+    fn import_cmd(commit: &str, modified_at: Option<i64>) -> ImportCmd {
+        ImportCmd {
+            file: "call_graph.json".into(),
+            schema: ImportSchema::Elixir,
+            project: "default".to_string(),
+            clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: true,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: Some(commit.to_string()),
+            modified_at,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
+        }
+    }
+
+    #[test]
+    fn test_stamp_keeps_unchanged_clauses() {
+        let db = db::open_mem_db();
+        let mut first = graph("a1", "missing.ex");
+        stamp_modifications(&import_cmd("c1", Some(100)), &db, &mut first);
+        assert_eq!(clause(&first), (100, Some("c1")));
+        db::queries::import::import_graph(&db, "default", &first).unwrap();
+
+        let mut unchanged = graph("a1", "missing.ex");
+        stamp_modifications(&import_cmd("c2", Some(200)), &db, &mut unchanged);
+        assert_eq!(clause(&unchanged), (100, Some("c1")));
+
+        let mut changed = graph("a2", "missing.ex");
+        stamp_modifications(&import_cmd("c2", Some(200)), &db, &mut changed);
+        assert_eq!(clause(&changed), (200, Some("c2")));
+    }
+
+    #[test]
+    fn test_stamp_falls_back_to_file_time() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let mut graph = graph("a1", &file.path().display().to_string());
+        stamp_modifications(&import_cmd("c1", None), &db::open_mem_db(), &mut graph);
+
+        let (modified_at, _) = clause(&graph);
+        assert_eq!(Some(modified_at), file_time(file.path()));
+        assert!(modified_at > 0);
+    }
+}
@@ -1,17 +1,31 @@
 mod cli_tests;
+mod dedup;
+mod dry_run;
+mod erlang;
+mod exclude;
 mod execute;
+mod history;
+mod models;
 mod output;
 mod output_tests;
+mod stream;
+mod test_paths;
+mod validation;
 
 use std::error::Error;
 use std::path::PathBuf;
 
-use clap::Args;
+use clap::{Args, ValueEnum};
+use db::query_builders::QuerySettings;
 use db::DbInstance;
+use serde::Deserialize;
 
-use crate::commands::{CommandRunner, Execute};
+use crate::commands::{CheckFailed, CommandRunner, Execute};
+use crate::config::DEFAULT_CONFIG_PATH;
 use crate::output::{OutputFormat, Outputable};
 
+pub use execute::ImportReport;
+
 const DEFAULT_PROJECT: &str = "default";
 
 fn validate_file_exists(s: &str) -> Result<PathBuf, String> {
@@ -23,28 +37,136 @@ fn validate_file_exists(s: &str) -> Result<PathBuf, String> {
     }
 }
 
+/// Format of the call graph file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSchema {
+    /// Output of the Elixir call graph extractor
+    #[default]
+    Elixir,
+    /// Language-agnostic nodes/edges format (see `commands/import/models.rs`)
+    Generic,
+    /// Erlang dialect with `module:function/arity` names and records (see `commands/import/erlang.rs`)
+    Erlang,
+}
+
 /// Import a call graph JSON file into the database
 #[derive(Args, Debug)]
 #[command(after_help = "\
 Examples:
   code_search import -f call_graph.json      # Import with default project name
   code_search import -f cg.json -p my_app    # Import into 'my_app' project
-  code_search import -f cg.json --clear      # Clear DB before importing")]
+  code_search import -f cg.json.zst          # Import a compressed export
+  code_search import -f cg.json --clear      # Clear DB before importing
+  code_search import -f cg.json --supervision sup.json   # Also import a supervision tree dump
+  code_search import -f cg.json --label $(git rev-parse --short HEAD)  # Label the metrics snapshot
+  code_search import -f cg.json --commit $(git rev-parse HEAD) --modified-at $(git log -1 --format=%ct)
+                                             # Date changed functions by the last commit
+  code_search import -f cg.json --replace-file lib/a.ex   # Drop what lib/a.ex no longer defines
+  code_search import -f cg.json --exclude-path test/support --exclude-path '*.pb.ex'
+                                             # Keep test support and generated code out
+  code_search import -f graph.json --schema generic   # Import a graph from a non-Elixir extractor
+  code_search import -f otp.json --schema erlang -p my_umbrella  # Add the Erlang apps of an umbrella
+  code_search import -f cg.json --dry-run    # Validate the file and count rows, writing nothing
+  code_search import -f cg.json --lenient    # Skip malformed records and report them
+  code_search import -f cg.json --resume     # Finish an import that was interrupted
+  code_search import -f cg.json --dedup calls=caller_module,caller_function,callee_module,callee_function,callee_arity
+                                             # Keep one call per caller and callee")]
 pub struct ImportCmd {
-    /// Path to the call graph JSON file
+    /// Path to the call graph JSON file, optionally compressed (`.json.gz`, `.json.zst`)
     #[arg(short, long, value_parser = validate_file_exists)]
     pub file: PathBuf,
+    /// Format of the call graph file
+    #[arg(long, value_enum, default_value_t = ImportSchema::Elixir)]
+    pub schema: ImportSchema,
     /// Project name for namespacing (allows multiple projects in same DB)
     #[arg(short, long, default_value = DEFAULT_PROJECT)]
     pub project: String,
     /// Clear all existing data before import (or just project data if --project is set)
     #[arg(long, default_value_t = false)]
     pub clear: bool,
+    /// Supervision tree dump (JSON with a `supervision` list) to import with the call graph
+    #[arg(long, value_parser = validate_file_exists)]
+    pub supervision: Option<PathBuf>,
+    /// Label stored with the metrics snapshot of this import (e.g. a git revision)
+    #[arg(long, default_value = "")]
+    pub label: String,
+    /// Do not record a metrics snapshot for `trends`
+    #[arg(long, default_value_t = false)]
+    pub no_snapshot: bool,
+    /// Validate the files and report the rows each relation would get, without writing anything
+    ///
+    /// Lists every record the import would reject and any stored relation
+    /// whose columns differ from this version's schema; exits nonzero when
+    /// the import would fail.
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+    /// Refuse the whole file when any record is malformed, naming it by JSON pointer (default)
+    #[arg(long, default_value_t = false, conflicts_with = "lenient")]
+    pub strict: bool,
+    /// Skip malformed records, import the rest and report what was skipped per relation
+    #[arg(long, default_value_t = false)]
+    pub lenient: bool,
+    /// Columns identifying duplicate records of a relation, as RELATION=COLUMN,COLUMN (repeatable)
+    ///
+    /// Applies to calls, messages and field_accesses, whose first record of
+    /// each key is kept. Overrides `import.dedup` of the config file; the
+    /// default key is the relation's stored key.
+    #[arg(long, value_name = "RELATION=COLUMNS")]
+    pub dedup: Vec<String>,
+    /// Resume an interrupted import of the same file, skipping the chunks it wrote
+    #[arg(long, default_value_t = false, conflicts_with_all = ["clear", "dry_run"])]
+    pub resume: bool,
+    /// Revision recorded for changed functions that carry none (e.g. `git rev-parse HEAD`)
+    #[arg(long, value_name = "SHA")]
+    pub commit: Option<String>,
+    /// Modification time, in Unix seconds, of changed functions that carry none
+    ///
+    /// Defaults to the modification time of each function's source file.
+    /// Functions whose `source_sha` matches the stored one keep their
+    /// recorded time and revision.
+    #[arg(long, value_name = "UNIX_SECONDS")]
+    pub modified_at: Option<i64>,
+    /// Source file whose stored rows this import replaces (repeatable)
+    ///
+    /// Removes the file's clauses, calls and field accesses before writing,
+    /// so the ones the new data no longer has are gone. `update` passes the
+    /// files a commit changed, including deleted ones.
+    #[arg(long = "replace-file", value_name = "PATH", conflicts_with = "resume")]
+    pub replace_files: Vec<String>,
+    /// Glob of source paths whose records are left out (repeatable)
+    ///
+    /// Adds to `import.exclude_paths` of the config file, the `.gitignore`
+    /// files of the current directory and below, and the default `deps` and
+    /// `_build`. A glob without a slash matches a file or directory name at
+    /// any depth (`*.pb.ex`), one with a slash matches from the project root
+    /// (`test/support`).
+    #[arg(long = "exclude-path", value_name = "GLOB")]
+    pub exclude_paths: Vec<String>,
+    /// Import files under deps/ and _build/ as well
+    #[arg(long, default_value_t = false)]
+    pub include_deps: bool,
+    /// Do not leave out the paths git ignores through `.gitignore` files
+    #[arg(long, default_value_t = false)]
+    pub no_gitignore: bool,
+    /// Path to the config file with `import.dedup` keys, `import.exclude_paths` and `import.test_paths`
+    #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+    pub config: PathBuf,
 }
 
 impl CommandRunner for ImportCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        if self.dry_run {
+            let plan = dry_run::plan_import(&self, db)?;
+            let report = plan.format(format);
+            return if plan.importable() {
+                Ok(report)
+            } else {
+                Err(Box::new(CheckFailed { report }))
+            };
+        }
+
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
@@ -0,0 +1,411 @@
+//! Generic, language-agnostic call graph schema.
+//!
+//! Extractors for languages other than Elixir (tree-sitter based tools for
+//! Ruby, Python, ...) can emit this format instead of the Elixir extractor's
+//! output, and import it with `code_search import --schema generic`:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "language": "python",
+//!   "nodes": [
+//!     {"id": "m1", "kind": "module", "name": "app.accounts", "doc": "User accounts"},
+//!     {"id": "f1", "kind": "function", "module": "app.accounts", "name": "get_user", "arity": 1,
+//!      "file": "app/accounts.py", "line": 10, "end_line": 18, "complexity": 3},
+//!     {"id": "f2", "kind": "function", "module": "app.accounts", "name": "_load", "arity": 1,
+//!      "visibility": "private", "file": "app/accounts.py", "line": 20, "end_line": 24},
+//!     {"id": "x1", "kind": "function", "module": "json", "name": "loads", "arity": 1, "external": true}
+//!   ],
+//!   "edges": [
+//!     {"kind": "call", "from": "f1", "to": "f2", "line": 12, "column": 9},
+//!     {"kind": "call", "from": "f2", "to": "x1", "line": 22}
+//!   ]
+//! }
+//! ```
+//!
+//! `language` is informational and not stored. Nodes are identified by an
+//! `id` unique within the file; edges refer to them. Module nodes are
+//! optional: any module named by a function node is imported. Functions
+//! marked `external` belong to libraries outside the project; they can be
+//! called but have no location of their own.
+//!
+//! Public functions are stored with kind `def` and private ones with `defp`,
+//! so the analysis commands treat them like their Elixir counterparts.
+//! Function nodes may carry `modified_at` (Unix seconds) and `commit`, the
+//! time and revision of their last change, for `age`.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use db::queries::import::ImportError;
+use db::queries::import_models::{Call, CallGraph, Callee, Caller, FunctionDoc, FunctionLocation};
+
+/// Version of the generic schema this build reads
+pub const GENERIC_SCHEMA_VERSION: u32 = 1;
+
+/// A call graph in the generic schema
+#[derive(Debug, Deserialize)]
+pub struct GenericGraph {
+    /// Schema version, must be `GENERIC_SCHEMA_VERSION`
+    pub version: u32,
+    #[serde(default)]
+    pub nodes: Vec<GenericNode>,
+    #[serde(default)]
+    pub edges: Vec<GenericEdge>,
+}
+
+/// A node of the graph, tagged by `kind`
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GenericNode {
+    Module(ModuleNode),
+    Function(FunctionNode),
+}
+
+impl GenericNode {
+    fn id(&self) -> &str {
+        match self {
+            GenericNode::Module(node) => &node.id,
+            GenericNode::Function(node) => &node.id,
+        }
+    }
+}
+
+/// A module, class or namespace
+#[derive(Debug, Deserialize)]
+pub struct ModuleNode {
+    pub id: String,
+    /// Fully qualified name, e.g. `app.accounts` or `Billing::Invoice`
+    pub name: String,
+    /// Module documentation (docstring, RDoc comment)
+    #[serde(default)]
+    pub doc: Option<String>,
+}
+
+/// A function or method
+#[derive(Debug, Deserialize)]
+pub struct FunctionNode {
+    pub id: String,
+    /// Fully qualified name of the defining module
+    pub module: String,
+    pub name: String,
+    #[serde(default)]
+    pub arity: u32,
+    /// File path relative to the project root
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub line: u32,
+    /// Last line of the body; defaults to `line`
+    #[serde(default)]
+    pub end_line: Option<u32>,
+    #[serde(default)]
+    pub column: Option<u32>,
+    #[serde(default)]
+    pub visibility: Visibility,
+    #[serde(default = "default_complexity")]
+    pub complexity: u32,
+    #[serde(default)]
+    pub doc: Option<String>,
+    /// Defined outside the project (standard library, dependency)
+    #[serde(default)]
+    pub external: bool,
+    /// When the function last changed, in Unix seconds (0 when unknown)
+    #[serde(default)]
+    pub modified_at: i64,
+    /// Revision that last changed the function
+    #[serde(default)]
+    pub commit: Option<String>,
+}
+
+fn default_complexity() -> u32 {
+    1
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    #[default]
+    Public,
+    Private,
+}
+
+impl Visibility {
+    fn kind(self) -> &'static str {
+        match self {
+            Visibility::Public => "def",
+            Visibility::Private => "defp",
+        }
+    }
+}
+
+/// An edge of the graph, tagged by `kind`
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GenericEdge {
+    Call(CallEdge),
+}
+
+/// A call from a function, or from module-level code, to a function
+#[derive(Debug, Deserialize)]
+pub struct CallEdge {
+    /// Calling function node, or a module node for top-level code
+    pub from: String,
+    /// Called function node
+    pub to: String,
+    /// File of the call site; defaults to the caller's file
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub line: Option<u32>,
+    #[serde(default)]
+    pub column: Option<u32>,
+}
+
+fn invalid(message: String) -> ImportError {
+    ImportError::JsonParseFailed {
+        message: format!("generic schema: {}", message),
+    }
+}
+
+impl GenericGraph {
+    /// Convert to the call graph model the importer stores.
+    ///
+    /// Fails on an unsupported version, duplicate node ids, or edges whose
+    /// endpoints are unknown or of the wrong kind.
+    pub fn into_call_graph(self) -> Result<CallGraph, ImportError> {
+        if self.version != GENERIC_SCHEMA_VERSION {
+            return Err(invalid(format!(
+                "unsupported version {} (supported: {})",
+                self.version, GENERIC_SCHEMA_VERSION
+            )));
+        }
+
+        let mut nodes: HashMap<&str, &GenericNode> = HashMap::new();
+        for node in &self.nodes {
+            if nodes.insert(node.id(), node).is_some() {
+                return Err(invalid(format!("duplicate node id '{}'", node.id())));
+            }
+        }
+
+        let mut graph = CallGraph::default();
+
+        for node in &self.nodes {
+            match node {
+                GenericNode::Module(module) => {
+                    // Registers modules that define no functions
+                    graph.function_locations.entry(module.name.clone()).or_default();
+                    if let Some(doc) = &module.doc {
+                        graph.docs.entry(module.name.clone()).or_default().moduledoc = Some(doc.clone());
+                    }
+                }
+                GenericNode::Function(function) if !function.external => {
+                    graph
+                        .function_locations
+                        .entry(function.module.clone())
+                        .or_default()
+                        .insert(
+                            format!("{}/{}:{}", function.name, function.arity, function.line),
+                            function_location(function),
+                        );
+                    if let Some(doc) = &function.doc {
+                        graph.docs.entry(function.module.clone()).or_default().functions.push(FunctionDoc {
+                            name: function.name.clone(),
+                            arity: function.arity,
+                            doc: doc.clone(),
+                        });
+                    }
+                }
+                GenericNode::Function(_) => {}
+            }
+        }
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            let GenericEdge::Call(call) = edge;
+            let caller = match nodes.get(call.from.as_str()) {
+                Some(GenericNode::Function(function)) => Caller {
+                    module: function.module.clone(),
+                    function: Some(format!("{}/{}", function.name, function.arity)),
+                    file: call.file.clone().or_else(|| function.file.clone()).unwrap_or_default(),
+                    line: call.line,
+                    column: call.column,
+                    kind: Some(function.visibility.kind().to_string()),
+                },
+                Some(GenericNode::Module(module)) => Caller {
+                    module: module.name.clone(),
+                    function: None,
+                    file: call.file.clone().unwrap_or_default(),
+                    line: call.line,
+                    column: call.column,
+                    kind: None,
+                },
+                None => return Err(invalid(format!("edge {} calls from unknown node '{}'", index, call.from))),
+            };
+            let callee = match nodes.get(call.to.as_str()) {
+                Some(GenericNode::Function(function)) => function,
+                Some(GenericNode::Module(_)) => {
+                    return Err(invalid(format!("edge {} calls module node '{}'", index, call.to)))
+                }
+                None => return Err(invalid(format!("edge {} calls unknown node '{}'", index, call.to))),
+            };
+
+            let call_type = if caller.module == callee.module { "local" } else { "remote" };
+            graph.calls.push(Call {
+                caller,
+                callee: Callee {
+                    module: callee.module.clone(),
+                    function: callee.name.clone(),
+                    arity: callee.arity,
+                    args: None,
+                },
+                call_type: call_type.to_string(),
+                kind: "call".to_string(),
+                conditional: false,
+            });
+        }
+
+        Ok(graph)
+    }
+}
+
+fn function_location(function: &FunctionNode) -> FunctionLocation {
+    FunctionLocation {
+        name: function.name.clone(),
+        arity: function.arity,
+        file: function.file.clone(),
+        source_file_absolute: None,
+        column: function.column,
+        kind: function.visibility.kind().to_string(),
+        line: function.line,
+        start_line: function.line,
+        end_line: function.end_line.unwrap_or(function.line),
+        pattern: None,
+        guard: None,
+        source_sha: None,
+        ast_sha: None,
+        ast_minhash: Vec::new(),
+        complexity: function.complexity,
+        max_nesting_depth: 0,
+        cognitive_complexity: 0,
+        abc_score: 0.0,
+        halstead_volume: 0.0,
+        generated_by: None,
+        macro_source: None,
+        app: None,
+        deprecated: None,
+        modified_at: function.modified_at,
+        commit: function.commit.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GENERIC_JSON: &str = r#"{
+        "version": 1,
+        "language": "python",
+        "nodes": [
+            {"id": "m1", "kind": "module", "name": "app.accounts", "doc": "User accounts"},
+            {"id": "m2", "kind": "module", "name": "app.empty"},
+            {"id": "f1", "kind": "function", "module": "app.accounts", "name": "get_user", "arity": 1,
+             "file": "app/accounts.py", "line": 10, "end_line": 18, "complexity": 3, "doc": "Fetch a user"},
+            {"id": "f2", "kind": "function", "module": "app.accounts", "name": "_load", "arity": 1,
+             "visibility": "private", "file": "app/accounts.py", "line": 20},
+            {"id": "x1", "kind": "function", "module": "json", "name": "loads", "arity": 1, "external": true}
+        ],
+        "edges": [
+            {"kind": "call", "from": "f1", "to": "f2", "line": 12, "column": 9},
+            {"kind": "call", "from": "f2", "to": "x1", "line": 22},
+            {"kind": "call", "from": "m1", "to": "f1", "file": "app/accounts.py", "line": 30}
+        ]
+    }"#;
+
+    fn convert(json: &str) -> Result<CallGraph, ImportError> {
+        serde_json::from_str::<GenericGraph>(json).expect("Valid JSON").into_call_graph()
+    }
+
+    #[test]
+    fn test_into_call_graph_locations() {
+        let graph = convert(GENERIC_JSON).expect("Conversion should succeed");
+
+        let mut modules: Vec<&String> = graph.function_locations.keys().collect();
+        modules.sort();
+        assert_eq!(modules, ["app.accounts", "app.empty"]);
+
+        let accounts = &graph.function_locations["app.accounts"];
+        let get_user = &accounts["get_user/1:10"];
+        assert_eq!(get_user.kind, "def");
+        assert_eq!((get_user.start_line, get_user.end_line), (10, 18));
+        assert_eq!(get_user.complexity, 3);
+        let load = &accounts["_load/1:20"];
+        assert_eq!(load.kind, "defp");
+        assert_eq!(load.end_line, 20);
+        assert_eq!(load.complexity, 1);
+
+        let docs = &graph.docs["app.accounts"];
+        assert_eq!(docs.moduledoc.as_deref(), Some("User accounts"));
+        assert_eq!(docs.functions[0].doc, "Fetch a user");
+    }
+
+    #[test]
+    fn test_into_call_graph_calls() {
+        let graph = convert(GENERIC_JSON).expect("Conversion should succeed");
+
+        let calls: Vec<(&str, Option<&str>, &str, &str, &str)> = graph
+            .calls
+            .iter()
+            .map(|c| {
+                (
+                    c.caller.module.as_str(),
+                    c.caller.function.as_deref(),
+                    c.callee.module.as_str(),
+                    c.callee.function.as_str(),
+                    c.call_type.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                ("app.accounts", Some("get_user/1"), "app.accounts", "_load", "local"),
+                ("app.accounts", Some("_load/1"), "json", "loads", "remote"),
+                ("app.accounts", None, "app.accounts", "get_user", "local"),
+            ]
+        );
+        // The call site file defaults to the caller's file
+        assert_eq!(graph.calls[1].caller.file, "app/accounts.py");
+        assert_eq!(graph.calls[1].caller.kind.as_deref(), Some("defp"));
+    }
+
+    #[test]
+    fn test_into_call_graph_rejects_unsupported_version() {
+        let error = convert(r#"{"version": 2, "nodes": [], "edges": []}"#).unwrap_err();
+        assert!(error.to_string().contains("unsupported version 2 (supported: 1)"));
+    }
+
+    #[test]
+    fn test_into_call_graph_rejects_unknown_node() {
+        let json = r#"{
+            "version": 1,
+            "nodes": [{"id": "f1", "kind": "function", "module": "a", "name": "run"}],
+            "edges": [{"kind": "call", "from": "f1", "to": "f9"}]
+        }"#;
+        let error = convert(json).unwrap_err();
+        assert!(error.to_string().contains("edge 0 calls unknown node 'f9'"));
+    }
+
+    #[test]
+    fn test_into_call_graph_rejects_duplicate_ids() {
+        let json = r#"{
+            "version": 1,
+            "nodes": [
+                {"id": "a", "kind": "module", "name": "a"},
+                {"id": "a", "kind": "function", "module": "a", "name": "run"}
+            ]
+        }"#;
+        let error = convert(json).unwrap_err();
+        assert!(error.to_string().contains("duplicate node id 'a'"));
+    }
+}
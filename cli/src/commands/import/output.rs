@@ -1,8 +1,27 @@
 //! Output formatting for import command results.
 
+use super::dedup::DroppedDuplicates;
+use super::exclude::ExcludedRecords;
+use super::dry_run::ImportPlan;
+use super::execute::ImportReport;
+use super::validation::RejectedRecord;
 use crate::output::Outputable;
 use db::queries::import::ImportResult;
 
+/// Rejected records listed in the table; JSON lists them all
+const MAX_LISTED_REJECTIONS: usize = 20;
+
+/// The first malformed records under `title`
+fn list_records(output: &mut String, title: &str, records: &[RejectedRecord]) {
+    output.push_str(&format!("\n{} ({}):\n", title, records.len()));
+    for record in records.iter().take(MAX_LISTED_REJECTIONS) {
+        output.push_str(&format!("  {}: {}\n", record.path, record.reason));
+    }
+    if records.len() > MAX_LISTED_REJECTIONS {
+        output.push_str(&format!("  ... and {} more (see -o json)\n", records.len() - MAX_LISTED_REJECTIONS));
+    }
+}
+
 impl Outputable for ImportResult {
     fn to_table(&self) -> String {
         let mut output = String::new();
@@ -15,10 +34,32 @@ impl Outputable for ImportResult {
         output.push_str(&format!("  Modules: {}\n", self.modules_imported));
         output.push_str(&format!("  Functions: {}\n", self.functions_imported));
         output.push_str(&format!("  Calls: {}\n", self.calls_imported));
+        if self.messages_imported > 0 {
+            output.push_str(&format!("  Messages: {}\n", self.messages_imported));
+        }
         output.push_str(&format!("  Structs: {}\n", self.structs_imported));
+        if self.field_accesses_imported > 0 {
+            output.push_str(&format!("  Field accesses: {}\n", self.field_accesses_imported));
+        }
         output.push_str(&format!("  Locations: {}\n", self.function_locations_imported));
         output.push_str(&format!("  Specs: {}\n", self.specs_imported));
         output.push_str(&format!("  Types: {}\n", self.types_imported));
+        if self.behaviours_imported > 0 {
+            output.push_str(&format!("  Behaviours: {}\n", self.behaviours_imported));
+        }
+        if self.docs_imported > 0 {
+            output.push_str(&format!("  Docs: {}\n", self.docs_imported));
+        }
+        if self.supervision_imported > 0 {
+            output.push_str(&format!("  Supervision: {}\n", self.supervision_imported));
+        }
+        if self.aliases_imported > 0 {
+            output.push_str(&format!("  Aliases: {}\n", self.aliases_imported));
+        }
+
+        if self.snapshot_recorded {
+            output.push_str("\nRecorded metrics snapshot (see `trends`).\n");
+        }
 
         if !self.schemas.created.is_empty() {
             output.push_str("\nCreated Schemas:\n");
@@ -27,6 +68,109 @@ impl Outputable for ImportResult {
             }
         }
 
+        if !self.schemas.repaired.is_empty() {
+            output.push_str("\nColumns added to relations of an older version:\n");
+            for repair in &self.schemas.repaired {
+                output.push_str(&format!("  - {} ({})\n", repair.relation, repair.added_columns.join(", ")));
+            }
+        }
+
+        output
+    }
+}
+
+/// Duplicates dropped per relation under `title`
+fn list_duplicates(output: &mut String, title: &str, duplicates: &[DroppedDuplicates]) {
+    output.push_str(&format!("\n{}:\n", title));
+    for duplicate in duplicates {
+        output.push_str(&format!(
+            "  {}: {} (key: {})\n",
+            duplicate.relation,
+            duplicate.dropped,
+            duplicate.key.join(", ")
+        ));
+    }
+}
+
+/// Records of excluded paths left out per relation under `title`
+fn list_excluded(output: &mut String, title: &str, excluded: &[ExcludedRecords]) {
+    output.push_str(&format!("\n{}:\n", title));
+    for records in excluded {
+        output.push_str(&format!("  {}: {}\n", records.relation, records.count));
+    }
+}
+
+impl Outputable for ImportReport {
+    fn to_table(&self) -> String {
+        let mut output = String::new();
+        if self.resumed {
+            output.push_str("Resumed the interrupted import.\n\n");
+        }
+        output.push_str(&self.result.to_table());
+        if !self.excluded.is_empty() {
+            list_excluded(&mut output, "Excluded by path", &self.excluded);
+        }
+        if !self.duplicates.is_empty() {
+            list_duplicates(&mut output, "Dropped duplicates", &self.duplicates);
+        }
+        if !self.skipped.is_empty() {
+            output.push_str("\nSkipped malformed records:\n");
+            for relation in &self.skipped {
+                output.push_str(&format!("  {}: {}\n", relation.relation, relation.count));
+            }
+            list_records(&mut output, "Skipped records", &self.skipped_records);
+        }
+        output
+    }
+}
+
+impl Outputable for ImportPlan {
+    fn to_table(&self) -> String {
+        let mut output = format!("Dry run of {} into project '{}', nothing was written.\n", self.file, self.project);
+
+        if !self.relations.is_empty() {
+            if self.clear {
+                output.push_str("\nWould clear existing project data first.\n");
+            }
+            output.push_str("\nWould import:\n");
+            for relation in &self.relations {
+                output.push_str(&format!("  {}: {}\n", relation.relation, relation.rows));
+            }
+        }
+
+        if !self.excluded.is_empty() {
+            list_excluded(&mut output, "Would exclude by path", &self.excluded);
+        }
+        if !self.duplicates.is_empty() {
+            list_duplicates(&mut output, "Would drop duplicates", &self.duplicates);
+        }
+
+        if !self.rejected.is_empty() {
+            let title = if self.lenient { "Would skip records" } else { "Rejected records" };
+            list_records(&mut output, title, &self.rejected);
+        }
+
+        if let Some(error) = &self.error {
+            output.push_str(&format!("\nError: {}\n", error));
+        }
+
+        let (repairable, unrepairable): (Vec<_>, Vec<_>) =
+            self.schema_mismatches.iter().partition(|mismatch| mismatch.repairable());
+        if !repairable.is_empty() {
+            output.push_str("\nWould add the columns relations of an older version lack:\n");
+            for mismatch in repairable {
+                output.push_str(&format!("  {}: {}\n", mismatch.relation, mismatch.missing.join(", ")));
+            }
+        }
+        if !unrepairable.is_empty() {
+            output.push_str("\nStored relations differ from this version's schema:\n");
+            for mismatch in unrepairable {
+                output.push_str(&format!("  {}: {}\n", mismatch.relation, mismatch.summary()));
+            }
+            output.push_str("Back up the data and import into a new database.\n");
+        }
+
+        output.push_str(if self.importable() { "\nThe import would succeed.\n" } else { "\nThe import would fail.\n" });
         output
     }
 }
@@ -61,15 +61,23 @@ Created Schemas:
             schemas: SchemaResult {
                 created: vec!["modules".to_string(), "functions".to_string()],
                 already_existed: vec!["calls".to_string()],
+                repaired: Vec::new(),
             },
             cleared: true,
             modules_imported: 10,
             functions_imported: 50,
             calls_imported: 100,
+            messages_imported: 0,
             structs_imported: 5,
+            field_accesses_imported: 0,
             function_locations_imported: 45,
             specs_imported: 25,
             types_imported: 12,
+            behaviours_imported: 0,
+            docs_imported: 0,
+            supervision_imported: 0,
+            aliases_imported: 0,
+            snapshot_recorded: false,
         }
     }
 
@@ -116,4 +124,151 @@ Created Schemas:
         use crate::output::Outputable;
         assert_eq!(full_result.format(OutputFormat::Table), FULL_TABLE_OUTPUT);
     }
+
+    #[rstest]
+    fn test_to_table_with_supervision(full_result: ImportResult) {
+        use crate::output::Outputable;
+        let mut result = full_result;
+        result.supervision_imported = 4;
+        assert!(result.to_table().contains("  Types: 12\n  Supervision: 4\n"));
+    }
+
+    #[rstest]
+    fn test_to_table_with_aliases(full_result: ImportResult) {
+        use crate::output::Outputable;
+        let mut result = full_result;
+        result.aliases_imported = 3;
+        assert!(result.to_table().contains("  Types: 12\n  Aliases: 3\n"));
+    }
+
+    #[rstest]
+    fn test_to_table_with_field_accesses(full_result: ImportResult) {
+        use crate::output::Outputable;
+        let mut result = full_result;
+        result.field_accesses_imported = 7;
+        assert!(result.to_table().contains("  Structs: 5\n  Field accesses: 7\n"));
+    }
+
+    #[rstest]
+    fn test_to_table_with_snapshot(full_result: ImportResult) {
+        use crate::output::Outputable;
+        let mut result = full_result;
+        result.snapshot_recorded = true;
+        assert!(result.to_table().contains("  Types: 12\n\nRecorded metrics snapshot (see `trends`).\n"));
+    }
+
+    #[test]
+    fn test_dry_run_to_table() {
+        use crate::commands::import::dry_run::{ImportPlan, PlannedRelation};
+        use crate::commands::import::validation::RejectedRecord;
+        use crate::output::Outputable;
+        use db::queries::schema::SchemaMismatch;
+
+        let plan = ImportPlan {
+            file: "cg.json".to_string(),
+            project: "default".to_string(),
+            relations: vec![PlannedRelation {
+                relation: "calls".to_string(),
+                rows: 3,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            plan.to_table(),
+            "Dry run of cg.json into project 'default', nothing was written.\n\nWould import:\n  calls: 3\n\nThe import would succeed.\n"
+        );
+
+        let plan = ImportPlan {
+            file: "cg.json".to_string(),
+            project: "default".to_string(),
+            error: Some("Failed to parse call graph JSON: /calls/0: missing field `callee`".to_string()),
+            rejected: vec![RejectedRecord {
+                relation: "calls".to_string(),
+                path: "/calls/0".to_string(),
+                reason: "missing field `callee`".to_string(),
+            }],
+            schema_mismatches: vec![
+                SchemaMismatch {
+                    relation: "modules".to_string(),
+                    missing: vec!["app".to_string()],
+                    unexpected: Vec::new(),
+                    type_drift: Vec::new(),
+                },
+                SchemaMismatch {
+                    relation: "docs".to_string(),
+                    missing: vec!["kind".to_string()],
+                    unexpected: Vec::new(),
+                    type_drift: Vec::new(),
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            plan.to_table(),
+            "\
+Dry run of cg.json into project 'default', nothing was written.
+
+Rejected records (1):
+  /calls/0: missing field `callee`
+
+Error: Failed to parse call graph JSON: /calls/0: missing field `callee`
+
+Would add the columns relations of an older version lack:
+  modules: app
+
+Stored relations differ from this version's schema:
+  docs: missing kind
+Back up the data and import into a new database.
+
+The import would fail.
+"
+        );
+    }
+
+    #[test]
+    fn test_to_table_with_added_columns() {
+        use crate::output::Outputable;
+        use db::queries::schema::SchemaRepair;
+
+        let mut result = ImportResult::default();
+        result.schemas.repaired = vec![SchemaRepair {
+            relation: "calls".to_string(),
+            created: false,
+            added_columns: vec!["call_kind".to_string(), "conditional".to_string()],
+        }];
+        assert!(result
+            .to_table()
+            .ends_with("\nColumns added to relations of an older version:\n  - calls (call_kind, conditional)\n"));
+    }
+
+    #[test]
+    fn test_import_report_to_table() {
+        use crate::commands::import::exclude::ExcludedRecords;
+        use crate::commands::import::execute::{ImportReport, SkippedRelation};
+        use crate::commands::import::validation::RejectedRecord;
+        use crate::output::Outputable;
+
+        let report = ImportReport {
+            result: ImportResult::default(),
+            resumed: false,
+            duplicates: Vec::new(),
+            excluded: vec![ExcludedRecords {
+                relation: "calls".to_string(),
+                count: 2,
+            }],
+            skipped: vec![SkippedRelation {
+                relation: "calls".to_string(),
+                count: 1,
+            }],
+            skipped_records: vec![RejectedRecord {
+                relation: "calls".to_string(),
+                path: "/calls/1".to_string(),
+                reason: "missing field `caller`".to_string(),
+            }],
+        };
+        let expected = format!(
+            "{EMPTY_TABLE_OUTPUT}\nExcluded by path:\n  calls: 2\n\nSkipped malformed records:\n  calls: 1\n\nSkipped records (1):\n  /calls/1: missing field `caller`\n"
+        );
+        assert_eq!(report.to_table(), expected);
+    }
 }
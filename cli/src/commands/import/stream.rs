@@ -0,0 +1,374 @@
+//! Streaming import of Elixir call graph files.
+//!
+//! The calls, messages and field accesses of a large project outnumber its
+//! definitions by far, so they are never held in memory together. The file is
+//! read twice: the first read checks every call site on its own and keeps the
+//! other sections, the second writes the call sites in chunks as they are
+//! parsed. Nothing is written until the whole file has been checked, so a
+//! strict import still refuses a file with any malformed record.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+use serde::de::{self, DeserializeSeed, Deserializer as _, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::dedup::Deduper;
+use super::exclude::PathExclusions;
+use super::execute::{begin_writes, load_supervision, open_json, validate, Imported};
+use super::history::stamp_modifications;
+use super::test_paths;
+use super::validation::{check_records, RejectedRecord};
+use super::{ImportCmd, ImportSchema};
+use db::queries::import::{import_graph_with_call_sites, ImportError};
+use db::queries::import_models::{Call, CallGraph, FieldAccess, Message};
+use db::DbInstance;
+
+/// Sections read one record at a time, each stored in the relation of the same name
+const STREAMED_SECTIONS: [&str; 3] = ["calls", "messages", "field_accesses"];
+
+/// Import an Elixir call graph file, streaming its call sites.
+///
+/// Like [`super::execute::load_graph`] followed by the path exclusions, a
+/// dedup and an import, but
+/// holding only one chunk of call sites at a time.
+pub fn stream_import(
+    cmd: &ImportCmd,
+    db: &DbInstance,
+    keys: &BTreeMap<String, Vec<String>>,
+) -> Result<Imported, Box<dyn Error>> {
+    let mut deduper = Deduper::new(keys)?;
+    let mut exclusions = PathExclusions::for_import(cmd)?;
+
+    // First read: check the call sites and keep the other sections
+    let mut rejected = Vec::new();
+    let sections = read_sections(&cmd.file, true, &mut |section, index, value| {
+        if let Err(reason) = check_call_site(section, value) {
+            rejected.push(RejectedRecord {
+                relation: section.to_string(),
+                path: format!("/{}/{}", section, index),
+                reason,
+            });
+        }
+        Ok(())
+    })?;
+    let Sections { mut rest, present, mistyped } = sections;
+    for section in mistyped {
+        rejected.push(RejectedRecord {
+            relation: section.to_string(),
+            path: format!("/{}", section),
+            reason: "expected a list".to_string(),
+        });
+    }
+    // Streamed sections are checked; empty ones stand in for them
+    for section in present {
+        rest.insert(section.to_string(), Value::Array(Vec::new()));
+    }
+    let mut rest = Value::Object(rest);
+    let checked = check_records(&mut rest, ImportSchema::Elixir, cmd.lenient).map(|more| {
+        rejected.extend(more);
+        rejected
+    });
+    let mut skipped = validate(checked, None, cmd.lenient)?;
+    let mut graph: CallGraph = serde_json::from_value(rest).map_err(|e| ImportError::JsonParseFailed {
+        message: e.to_string(),
+    })?;
+    if let Some(path) = &cmd.supervision {
+        let (supervision, rejected) = load_supervision(path, cmd.lenient)?;
+        graph.supervision.extend(supervision);
+        skipped.extend(rejected);
+    }
+    exclusions.filter_graph(&mut graph);
+    test_paths::mark_for_import(cmd, &mut graph)?;
+
+    stamp_modifications(cmd, db, &mut graph);
+    let checkpoint = begin_writes(cmd, db)?;
+
+    // Second read: write the call sites, leaving out the malformed ones
+    let result = import_graph_with_call_sites(db, &cmd.project, &graph, Some(&checkpoint), |writer| {
+        read_sections(&cmd.file, false, &mut |section, _, value| {
+            match section {
+                "calls" => match Call::deserialize(value) {
+                    Ok(call) if exclusions.keep_call(&call) && deduper.keep_call(&call) => writer.add_call(&call)?,
+                    _ => {}
+                },
+                "messages" => match Message::deserialize(value) {
+                    Ok(message) if exclusions.keep_message(&message) && deduper.keep_message(&message) => writer.add_message(&message)?,
+                    _ => {}
+                },
+                _ => match FieldAccess::deserialize(value) {
+                    Ok(access) if exclusions.keep_field_access(&access) && deduper.keep_field_access(&access) => writer.add_field_access(&access)?,
+                    _ => {}
+                },
+            }
+            Ok(())
+        })?;
+        Ok(())
+    })?;
+    checkpoint.finish(db)?;
+
+    Ok(Imported {
+        result,
+        duplicates: deduper.dropped(),
+        excluded: exclusions.excluded(),
+        skipped,
+    })
+}
+
+/// Whether a call site deserializes, or why not
+fn check_call_site(section: &str, value: &Value) -> Result<(), String> {
+    let checked = match section {
+        "calls" => Call::deserialize(value).map(drop),
+        "messages" => Message::deserialize(value).map(drop),
+        _ => FieldAccess::deserialize(value).map(drop),
+    };
+    checked.map_err(|e| e.to_string())
+}
+
+/// Receives each record of a streamed section with its index
+type OnRecord<'a> = dyn FnMut(&'static str, usize, &Value) -> Result<(), Box<dyn Error>> + 'a;
+
+/// What a read of the file found besides the streamed records
+struct Sections {
+    /// The other sections, when kept
+    rest: Map<String, Value>,
+    /// Streamed sections the file has
+    present: Vec<&'static str>,
+    /// Streamed sections that are not lists
+    mistyped: Vec<&'static str>,
+}
+
+/// Read a call graph file, passing the records of the streamed sections to `on_record`
+fn read_sections(path: &Path, keep_rest: bool, on_record: &mut OnRecord) -> Result<Sections, Box<dyn Error>> {
+    let mut reader = SectionReader {
+        sections: Sections {
+            rest: Map::new(),
+            present: Vec::new(),
+            mistyped: Vec::new(),
+        },
+        keep_rest,
+        on_record,
+        failed: None,
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_reader(open_json(path)?);
+    let read = deserializer
+        .deserialize_any(&mut reader)
+        .and_then(|_| deserializer.end());
+    if let Some(e) = reader.failed {
+        return Err(e);
+    }
+    read.map_err(|e| {
+        if e.is_io() {
+            ImportError::FileReadFailed {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            }
+        } else {
+            ImportError::JsonParseFailed {
+                message: e.to_string(),
+            }
+        }
+    })?;
+    Ok(reader.sections)
+}
+
+struct SectionReader<'r, 'a> {
+    sections: Sections,
+    keep_rest: bool,
+    on_record: &'r mut OnRecord<'a>,
+    /// Error of `on_record`, which stops the read
+    failed: Option<Box<dyn Error>>,
+}
+
+impl<'de> Visitor<'de> for &mut SectionReader<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            match STREAMED_SECTIONS.into_iter().find(|section| *section == key) {
+                Some(section) => {
+                    self.sections.present.push(section);
+                    map.next_value_seed(StreamedSection { reader: self, section })?;
+                }
+                None if self.keep_rest => {
+                    let value = map.next_value::<Value>()?;
+                    self.sections.rest.insert(key, value);
+                }
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One streamed section, read record by record when it is a list
+struct StreamedSection<'s, 'r, 'a> {
+    reader: &'s mut SectionReader<'r, 'a>,
+    section: &'static str,
+}
+
+impl StreamedSection<'_, '_, '_> {
+    fn mistyped(self) {
+        self.reader.sections.mistyped.push(self.section);
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for StreamedSection<'_, '_, '_> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StreamedSection<'_, '_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of records")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut index = 0;
+        while let Some(value) = seq.next_element::<Value>()? {
+            if let Err(e) = (self.reader.on_record)(self.section, index, &value) {
+                self.reader.failed = Some(e);
+                return Err(de::Error::custom("import stopped"));
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        self.mistyped();
+        Ok(())
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        self.mistyped();
+        Ok(())
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<(), E> {
+        self.mistyped();
+        Ok(())
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> Result<(), E> {
+        self.mistyped();
+        Ok(())
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> Result<(), E> {
+        self.mistyped();
+        Ok(())
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<(), E> {
+        self.mistyped();
+        Ok(())
+    }
+
+    fn visit_str<E: de::Error>(self, _: &str) -> Result<(), E> {
+        self.mistyped();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEFAULT_CONFIG_PATH;
+    use db::queries::import::IMPORT_CHUNK_SIZE;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn call(line: usize) -> String {
+        format!(
+            r#"{{"caller": {{"module": "A", "function": "run", "file": "a.ex", "line": {line}}}, "callee": {{"module": "B", "function": "f", "arity": 0}}, "type": "remote"}}"#
+        )
+    }
+
+    fn import_cmd(json: &str, lenient: bool) -> (NamedTempFile, ImportCmd) {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(json.as_bytes()).unwrap();
+        let cmd = ImportCmd {
+            file: file.path().to_path_buf(),
+            schema: ImportSchema::Elixir,
+            project: "test_project".to_string(),
+            clear: false,
+            supervision: None,
+            label: String::new(),
+            no_snapshot: true,
+            dry_run: false,
+            strict: !lenient,
+            lenient,
+            dedup: Vec::new(),
+            resume: false,
+            commit: None,
+            modified_at: None,
+            replace_files: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: DEFAULT_CONFIG_PATH.into(),
+        };
+        (file, cmd)
+    }
+
+    #[test]
+    fn test_stream_import_writes_every_chunk() {
+        // Call sites come before the definitions, and the last call repeats the first
+        let mut calls: Vec<String> = (1..=IMPORT_CHUNK_SIZE * 2 + 1).map(call).collect();
+        calls.push(call(1));
+        let json = format!(
+            r#"{{"calls": [{}], "structs": {{}}, "function_locations": {{}}, "messages": []}}"#,
+            calls.join(", ")
+        );
+        let (_file, cmd) = import_cmd(&json, false);
+        let db = db::open_mem_db();
+
+        let imported = stream_import(&cmd, &db, &BTreeMap::new()).unwrap();
+        assert_eq!(imported.result.calls_imported, IMPORT_CHUNK_SIZE * 2 + 1);
+        assert_eq!(imported.duplicates[0].dropped, 1);
+        assert!(imported.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_stream_import_strict_writes_nothing() {
+        let json = format!(r#"{{"structs": {{}}, "function_locations": {{}}, "calls": [{}], "messages": {{}}}}"#, call(1));
+        let (_file, cmd) = import_cmd(&json, false);
+        let db = db::open_mem_db();
+
+        let err = stream_import(&cmd, &db, &BTreeMap::new()).err().unwrap();
+        assert_eq!(err.to_string(), "Failed to parse call graph JSON: /messages: expected a list");
+        assert!(!db.run_script_str("::relations", "", true).contains("calls"));
+    }
+
+    #[test]
+    fn test_stream_import_lenient_skips_records() {
+        let json = format!(
+            r#"{{"structs": {{"Broken": {{}}}}, "function_locations": {{}}, "calls": [{}, {{"type": "remote"}}], "messages": 1}}"#,
+            call(1)
+        );
+        let (_file, cmd) = import_cmd(&json, true);
+        let db = db::open_mem_db();
+
+        let imported = stream_import(&cmd, &db, &BTreeMap::new()).unwrap();
+        assert_eq!(imported.result.calls_imported, 1);
+        let paths: Vec<&str> = imported.skipped.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["/calls/1", "/messages", "/structs/Broken"]);
+    }
+}
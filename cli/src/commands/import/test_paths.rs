@@ -0,0 +1,68 @@
+//! Classifying imported modules as test code.
+//!
+//! Modules whose every function location is in a `test/` directory or a
+//! `_test.exs` file are marked `is_test` by the import itself. The globs of
+//! `import.test_paths` in the config add test support living elsewhere, such
+//! as `lib/my_app/testing`; they follow the syntax of `--exclude-path`.
+
+use std::error::Error;
+
+use regex::Regex;
+
+use super::ImportCmd;
+use crate::config::Config;
+use db::queries::import_models::CallGraph;
+use db::query_builders::path_glob_to_regex;
+
+/// Mark the test modules of an import by the `import.test_paths` of its config file
+pub fn mark_for_import(cmd: &ImportCmd, graph: &mut CallGraph) -> Result<(), Box<dyn Error>> {
+    let globs = Config::load_or_default(&cmd.config)?.import.test_paths;
+    Ok(mark_test_modules(graph, &globs)?)
+}
+
+/// Add the modules whose every function location matches one of `globs` to
+/// the graph's test modules
+pub fn mark_test_modules(graph: &mut CallGraph, globs: &[String]) -> Result<(), String> {
+    let patterns = globs
+        .iter()
+        .filter(|glob| !glob.trim().trim_matches('/').is_empty())
+        .map(|glob| Regex::new(&path_glob_to_regex(glob)).map_err(|e| format!("Invalid test path '{}': {}", glob, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if patterns.is_empty() {
+        return Ok(());
+    }
+
+    let is_test = |file: &str| {
+        let file = file.strip_prefix("./").unwrap_or(file);
+        patterns.iter().any(|pattern| pattern.is_match(file))
+    };
+    for (module, functions) in &graph.function_locations {
+        if !functions.is_empty() && functions.values().all(|loc| loc.file.as_deref().is_some_and(is_test)) {
+            graph.test_modules.insert(module.clone());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark_test_modules() {
+        let mut graph: CallGraph = serde_json::from_str(
+            r#"{
+                "structs": {},
+                "function_locations": {
+                    "MyApp.Testing.Factory": {"build/1:2": {"name": "build", "arity": 1, "file": "lib/my_app/testing/factory.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}},
+                    "MyApp.Accounts": {"get/1:2": {"name": "get", "arity": 1, "file": "lib/my_app/accounts.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}}
+                },
+                "calls": []
+            }"#,
+        )
+        .unwrap();
+
+        mark_test_modules(&mut graph, &["lib/my_app/testing".to_string()]).unwrap();
+        assert_eq!(graph.test_modules.iter().collect::<Vec<_>>(), ["MyApp.Testing.Factory"]);
+    }
+}
@@ -0,0 +1,281 @@
+//! Record-level validation of call graph files.
+//!
+//! Each record of a file is deserialized on its own, so a malformed record is
+//! reported by its JSON pointer (`/calls/3`) instead of a byte offset, and all
+//! of them are found in one pass. `import --lenient` drops the malformed
+//! records and imports the rest; strict imports refuse the file.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::erlang::{ErlangCall, ErlangFunction, ErlangRecord, RecordAccess};
+use super::models::{GenericEdge, GenericNode};
+use super::ImportSchema;
+use db::queries::import_models::{
+    AliasDirective, Call, FieldAccess, FunctionLocation, Message, ModuleDocs, Spec, StructDef, SupervisorDef, TypeDef,
+};
+
+/// A malformed record
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RejectedRecord {
+    /// Relation the record would have been stored in
+    pub relation: String,
+    /// JSON pointer to the record, e.g. `/calls/3`
+    pub path: String,
+    pub reason: String,
+}
+
+/// Check every record of a call graph file.
+///
+/// With `skip`, malformed records are removed from `value` (and missing or
+/// mistyped sections replaced by empty ones) so that the rest parses. Fails
+/// only when the file is not a JSON object.
+pub fn check_records(value: &mut Value, schema: ImportSchema, skip: bool) -> Result<Vec<RejectedRecord>, String> {
+    let root = value.as_object_mut().ok_or("the file is not a JSON object")?;
+    let mut checker = Checker {
+        skip,
+        rejected: Vec::new(),
+    };
+
+    match schema {
+        ImportSchema::Elixir => {
+            checker.map::<StructDef>(root, "structs", "struct_fields", true);
+            checker.map_of_maps::<FunctionLocation>(root, "function_locations", "function_locations", true);
+            checker.list::<Call>(root, "calls", "calls", true);
+            checker.list::<Message>(root, "messages", "messages", false);
+            checker.list::<FieldAccess>(root, "field_accesses", "field_accesses", false);
+            checker.map_of_lists::<Spec>(root, "specs", "specs");
+            checker.map_of_lists::<TypeDef>(root, "types", "types");
+            checker.map_of_lists::<String>(root, "behaviours", "behaviours");
+            checker.map::<ModuleDocs>(root, "docs", "docs", false);
+            checker.list::<SupervisorDef>(root, "supervision", "supervision", false);
+            checker.map_of_lists::<AliasDirective>(root, "aliases", "aliases");
+        }
+        ImportSchema::Generic => {
+            checker.list::<GenericNode>(root, "nodes", "function_locations", false);
+            checker.list::<GenericEdge>(root, "edges", "calls", false);
+        }
+        ImportSchema::Erlang => {
+            checker.list::<ErlangFunction>(root, "functions", "function_locations", false);
+            checker.list::<ErlangCall>(root, "calls", "calls", false);
+            checker.map::<ErlangRecord>(root, "records", "struct_fields", false);
+            checker.list::<RecordAccess>(root, "record_accesses", "field_accesses", false);
+            checker.map_of_lists::<String>(root, "behaviours", "behaviours");
+        }
+    }
+    Ok(checker.rejected)
+}
+
+/// Check the supervisors of a `--supervision` dump, like [`check_records`]
+pub fn check_supervision(value: &mut Value, skip: bool) -> Result<Vec<RejectedRecord>, String> {
+    let root = value.as_object_mut().ok_or("the file is not a JSON object")?;
+    let mut checker = Checker {
+        skip,
+        rejected: Vec::new(),
+    };
+    checker.list::<SupervisorDef>(root, "supervision", "supervision", true);
+    Ok(checker.rejected)
+}
+
+/// JSON pointer of a path, escaping `~` and `/` in its parts (RFC 6901)
+fn pointer(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .map(|part| format!("/{}", part.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+struct Checker {
+    skip: bool,
+    rejected: Vec<RejectedRecord>,
+}
+
+impl Checker {
+    fn reject(&mut self, relation: &str, path: String, reason: impl Into<String>) {
+        self.rejected.push(RejectedRecord {
+            relation: relation.to_string(),
+            path,
+            reason: reason.into(),
+        });
+    }
+
+    /// Whether the record deserializes; rejects it otherwise
+    fn record<T: DeserializeOwned>(&mut self, value: &Value, relation: &str, path: &[&str]) -> bool {
+        match T::deserialize(value) {
+            Ok(_) => true,
+            Err(e) => {
+                self.reject(relation, pointer(path), e.to_string());
+                false
+            }
+        }
+    }
+
+    /// The section `key` if it has the kind of `empty`.
+    ///
+    /// A missing required section or one of another kind is rejected, and
+    /// replaced by `empty` when skipping.
+    fn section<'a>(
+        &mut self,
+        root: &'a mut Map<String, Value>,
+        key: &str,
+        relation: &str,
+        required: bool,
+        empty: Value,
+    ) -> Option<&'a mut Value> {
+        let reason = match root.get(key) {
+            None if !required => return None,
+            None => "missing section",
+            Some(value) if value.is_array() == empty.is_array() && value.is_object() == empty.is_object() => {
+                return root.get_mut(key);
+            }
+            Some(_) if empty.is_array() => "expected a list",
+            Some(_) => "expected an object",
+        };
+        self.reject(relation, pointer(&[key]), reason);
+        if self.skip {
+            root.insert(key.to_string(), empty);
+        }
+        None
+    }
+
+    fn list<T: DeserializeOwned>(&mut self, root: &mut Map<String, Value>, key: &str, relation: &str, required: bool) {
+        let Some(Value::Array(items)) = self.section(root, key, relation, required, Value::Array(Vec::new())) else {
+            return;
+        };
+        let mut index = 0;
+        let skip = self.skip;
+        items.retain(|item| {
+            let valid = self.record::<T>(item, relation, &[key, &index.to_string()]);
+            index += 1;
+            valid || !skip
+        });
+    }
+
+    fn map<T: DeserializeOwned>(&mut self, root: &mut Map<String, Value>, key: &str, relation: &str, required: bool) {
+        let Some(Value::Object(entries)) = self.section(root, key, relation, required, Value::Object(Map::new())) else {
+            return;
+        };
+        let skip = self.skip;
+        entries.retain(|name, entry| self.record::<T>(entry, relation, &[key, name]) || !skip);
+    }
+
+    fn map_of_lists<T: DeserializeOwned>(&mut self, root: &mut Map<String, Value>, key: &str, relation: &str) {
+        let Some(Value::Object(entries)) = self.section(root, key, relation, false, Value::Object(Map::new())) else {
+            return;
+        };
+        let skip = self.skip;
+        entries.retain(|name, entry| {
+            let Value::Array(items) = entry else {
+                self.reject(relation, pointer(&[key, name]), "expected a list");
+                return !skip;
+            };
+            let mut index = 0;
+            items.retain(|item| {
+                let valid = self.record::<T>(item, relation, &[key, name, &index.to_string()]);
+                index += 1;
+                valid || !skip
+            });
+            true
+        });
+    }
+
+    fn map_of_maps<T: DeserializeOwned>(&mut self, root: &mut Map<String, Value>, key: &str, relation: &str, required: bool) {
+        let Some(Value::Object(entries)) = self.section(root, key, relation, required, Value::Object(Map::new())) else {
+            return;
+        };
+        let skip = self.skip;
+        entries.retain(|name, entry| {
+            let Value::Object(records) = entry else {
+                self.reject(relation, pointer(&[key, name]), "expected an object");
+                return !skip;
+            };
+            records.retain(|id, record| self.record::<T>(record, relation, &[key, name, id]) || !skip);
+            true
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MALFORMED: &str = r#"{
+        "structs": {"MyApp.User": {"fields": []}, "MyApp.Broken": {}},
+        "function_locations": {
+            "MyApp.Accounts": {
+                "get_user/1:10": {"name": "get_user", "arity": 1, "kind": "def", "line": 10, "start_line": 10, "end_line": 15},
+                "list/0:20": {"name": "list", "arity": -1, "kind": "def", "line": 20, "start_line": 20, "end_line": 21}
+            }
+        },
+        "calls": [
+            {"caller": {"module": "A", "file": "a.ex"}, "callee": {"module": "B", "function": "f", "arity": 0}, "type": "remote"},
+            {"caller": {"module": "A", "file": "a.ex"}, "type": "remote"}
+        ],
+        "specs": {"MyApp.Accounts": "not a list"}
+    }"#;
+
+    #[test]
+    fn test_check_records_reports_pointers() {
+        let mut value: Value = serde_json::from_str(MALFORMED).unwrap();
+        let rejected = check_records(&mut value, ImportSchema::Elixir, false).unwrap();
+
+        let paths: Vec<(&str, &str)> = rejected.iter().map(|r| (r.relation.as_str(), r.path.as_str())).collect();
+        assert_eq!(
+            paths,
+            [
+                ("struct_fields", "/structs/MyApp.Broken"),
+                ("function_locations", "/function_locations/MyApp.Accounts/list~10:20"),
+                ("calls", "/calls/1"),
+                ("specs", "/specs/MyApp.Accounts"),
+            ]
+        );
+        assert!(rejected[2].reason.contains("missing field `callee`"));
+        // Without skipping the value is left as it was
+        assert_eq!(value["calls"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_check_records_skips_malformed_records() {
+        let mut value: Value = serde_json::from_str(MALFORMED).unwrap();
+        check_records(&mut value, ImportSchema::Elixir, true).unwrap();
+
+        let graph: db::queries::import_models::CallGraph = serde_json::from_value(value).unwrap();
+        assert_eq!(graph.calls.len(), 1);
+        assert_eq!(graph.structs.len(), 1);
+        assert_eq!(graph.function_locations["MyApp.Accounts"].len(), 1);
+        assert!(graph.specs.is_empty());
+    }
+
+    #[test]
+    fn test_check_records_sections() {
+        let mut value: Value = serde_json::from_str(r#"{"calls": {}, "messages": null}"#).unwrap();
+        let rejected = check_records(&mut value, ImportSchema::Elixir, true).unwrap();
+
+        let reasons: Vec<(&str, &str)> = rejected.iter().map(|r| (r.path.as_str(), r.reason.as_str())).collect();
+        assert_eq!(
+            reasons,
+            [
+                ("/structs", "missing section"),
+                ("/function_locations", "missing section"),
+                ("/calls", "expected a list"),
+                ("/messages", "expected a list"),
+            ]
+        );
+        assert!(serde_json::from_value::<db::queries::import_models::CallGraph>(value).is_ok());
+
+        let mut value = Value::Array(Vec::new());
+        assert!(check_records(&mut value, ImportSchema::Elixir, false).is_err());
+    }
+
+    #[test]
+    fn test_check_records_generic() {
+        let json = r#"{"version": 1, "nodes": [{"id": "m1", "kind": "module", "name": "app"}, {"id": "x", "kind": "class"}]}"#;
+        let mut value: Value = serde_json::from_str(json).unwrap();
+        let rejected = check_records(&mut value, ImportSchema::Generic, false).unwrap();
+
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].path, "/nodes/1");
+        assert_eq!(rejected[0].relation, "function_locations");
+    }
+}
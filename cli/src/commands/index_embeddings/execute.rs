@@ -0,0 +1,113 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::IndexEmbeddingsCmd;
+use crate::commands::Execute;
+use crate::config::Config;
+use crate::embed::embedding_text;
+use db::queries::embeddings::{ensure_embeddings_relation, find_embedding_inputs, store_embeddings};
+use db::query_builders::QuerySettings;
+
+/// Result of the index-embeddings command
+#[derive(Debug, Serialize)]
+pub struct IndexEmbeddingsResult {
+    pub project: String,
+    pub model: String,
+    pub dimensions: usize,
+    pub functions_indexed: usize,
+}
+
+impl Execute for IndexEmbeddingsCmd {
+    type Output = IndexEmbeddingsResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let mut settings = Config::load_or_default(&self.config)?.embeddings;
+        if let Some(provider) = self.provider {
+            settings.provider = provider;
+        }
+        if self.url.is_some() {
+            settings.url = self.url;
+        }
+        if self.model.is_some() {
+            settings.model = self.model;
+        }
+        let embedder = settings.embedder()?;
+
+        let inputs = find_embedding_inputs(db, &self.project)?;
+        let texts: Vec<String> = inputs.iter().map(embedding_text).collect();
+
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(self.batch_size as usize) {
+            vectors.extend(embedder.embed(batch)?);
+        }
+
+        let dimensions = vectors.first().map(Vec::len).unwrap_or(0);
+        if vectors.iter().any(|v| v.len() != dimensions) {
+            return Err("Embedding provider returned vectors of different sizes".into());
+        }
+
+        let model = embedder.model_id();
+        let functions_indexed = if dimensions > 0 {
+            ensure_embeddings_relation(db, dimensions)?;
+            store_embeddings(db, &self.project, &model, &inputs, &vectors)?
+        } else {
+            0
+        };
+
+        Ok(IndexEmbeddingsResult {
+            project: self.project,
+            model,
+            dimensions,
+            functions_indexed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embed::Provider;
+    use std::path::PathBuf;
+
+    fn index_cmd(project: &str) -> IndexEmbeddingsCmd {
+        IndexEmbeddingsCmd {
+            project: project.to_string(),
+            config: PathBuf::from(crate::config::DEFAULT_CONFIG_PATH),
+            provider: Some(Provider::Hashing),
+            url: None,
+            model: None,
+            batch_size: 4,
+        }
+    }
+
+    #[test]
+    fn test_index_embeddings() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = index_cmd("test_project").execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.model, "hashing-256");
+        assert_eq!(result.dimensions, 256);
+        assert_eq!(result.functions_indexed, 15);
+
+        // Re-indexing replaces rather than duplicates
+        let again = index_cmd("test_project").execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert_eq!(again.functions_indexed, 15);
+    }
+
+    #[test]
+    fn test_index_embeddings_empty_project() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = index_cmd("other").execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert_eq!(result.functions_indexed, 0);
+    }
+
+    #[test]
+    fn test_index_embeddings_http_without_url() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let mut cmd = index_cmd("test_project");
+        cmd.provider = Some(Provider::Http);
+        cmd.model = Some("nomic-embed-text".to_string());
+        assert!(cmd.execute(&db, &QuerySettings::default()).is_err());
+    }
+}
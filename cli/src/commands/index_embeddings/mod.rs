@@ -0,0 +1,60 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::embed::Provider;
+use crate::output::{OutputFormat, Outputable};
+
+/// Compute vector embeddings of functions for `search --semantic`
+///
+/// Each function is embedded from its name, spec and @doc text. The provider comes
+/// from the `embeddings` section of the config file (the built-in `hashing` model
+/// by default) and can be overridden with flags. Re-run after importing new data.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search index-embeddings                      # Built-in hashing model, no network
+  code_search index-embeddings --provider http \\
+      --url http://localhost:11434/v1/embeddings --model nomic-embed-text
+  code_search search 'load user from database' --semantic
+")]
+pub struct IndexEmbeddingsCmd {
+    /// Project to index
+    #[arg(long, default_value = "default")]
+    pub project: String,
+
+    /// Path to the config file containing the embedding provider
+    #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+    pub config: PathBuf,
+
+    /// Embedding provider, overriding the config file
+    #[arg(long, value_enum)]
+    pub provider: Option<Provider>,
+
+    /// Endpoint of the http provider, overriding the config file
+    #[arg(long)]
+    pub url: Option<String>,
+
+    /// Model name for the http provider, overriding the config file
+    #[arg(long)]
+    pub model: Option<String>,
+
+    /// Functions embedded per provider request
+    #[arg(long, default_value_t = 64, value_parser = clap::value_parser!(u32).range(1..=2048))]
+    pub batch_size: u32,
+}
+
+impl CommandRunner for IndexEmbeddingsCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,46 @@
+//! Output formatting for index-embeddings command results.
+
+use super::execute::IndexEmbeddingsResult;
+use crate::output::Outputable;
+
+impl Outputable for IndexEmbeddingsResult {
+    fn to_table(&self) -> String {
+        if self.functions_indexed == 0 {
+            return format!("No functions to index in project {}.", self.project);
+        }
+        format!(
+            "Indexed {} function(s) in project {}\n  Model:      {}\n  Dimensions: {}",
+            self.functions_indexed, self.project, self.model, self.dimensions
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_table() {
+        let result = IndexEmbeddingsResult {
+            project: "default".to_string(),
+            model: "hashing-256".to_string(),
+            dimensions: 256,
+            functions_indexed: 15,
+        };
+        assert_eq!(
+            result.to_table(),
+            "Indexed 15 function(s) in project default\n  Model:      hashing-256\n  Dimensions: 256"
+        );
+    }
+
+    #[test]
+    fn test_to_table_empty() {
+        let result = IndexEmbeddingsResult {
+            project: "default".to_string(),
+            model: "hashing-256".to_string(),
+            dimensions: 0,
+            functions_indexed: 0,
+        };
+        assert_eq!(result.to_table(), "No functions to index in project default.");
+    }
+}
@@ -0,0 +1,73 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::IndexesCmd;
+use crate::commands::Execute;
+use db::queries::schema::{index_exists, rebuild_indexes, INDEXES};
+use db::query_builders::QuerySettings;
+
+/// A secondary index and whether it exists
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexStatus {
+    /// `relation:name`
+    pub index: String,
+    pub columns: Vec<String>,
+    pub exists: bool,
+}
+
+/// Result of the indexes command
+#[derive(Debug, Serialize)]
+pub struct IndexesResult {
+    pub rebuilt: bool,
+    pub indexes: Vec<IndexStatus>,
+}
+
+impl Execute for IndexesCmd {
+    type Output = IndexesResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        if self.rebuild {
+            rebuild_indexes(db)?;
+        }
+
+        let mut indexes = Vec::new();
+        for index in INDEXES {
+            indexes.push(IndexStatus {
+                index: index.qualified_name(),
+                columns: index.columns.iter().map(|c| c.to_string()).collect(),
+                exists: index_exists(db, index)?,
+            });
+        }
+
+        Ok(IndexesResult {
+            rebuilt: self.rebuild,
+            indexes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebuild_creates_missing_indexes() {
+        let db = db::open_mem_db();
+
+        let listed = IndexesCmd { rebuild: false }
+            .execute(&db, &QuerySettings::default())
+            .expect("Listing should succeed");
+        assert!(listed.indexes.iter().all(|i| !i.exists));
+
+        // Indexes cannot be built before their relations exist
+        assert!(IndexesCmd { rebuild: true }.execute(&db, &QuerySettings::default()).is_err());
+
+        db::queries::import::create_schema(&db).unwrap();
+        let rebuilt = IndexesCmd { rebuild: true }
+            .execute(&db, &QuerySettings::default())
+            .expect("Rebuild should succeed");
+        assert!(rebuilt.rebuilt);
+        assert!(rebuilt.indexes.iter().all(|i| i.exists));
+    }
+}
@@ -0,0 +1,34 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// List or rebuild the secondary indexes of the database
+///
+/// `setup` and `import` create the indexes; databases created before an index
+/// was added get it with --rebuild, which also recreates existing ones.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search indexes                         # Show which indexes exist
+  code_search indexes --rebuild               # Drop and recreate every index
+")]
+pub struct IndexesCmd {
+    /// Drop and recreate every index, creating missing ones
+    #[arg(long, default_value_t = false)]
+    pub rebuild: bool,
+}
+
+impl CommandRunner for IndexesCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
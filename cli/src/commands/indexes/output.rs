@@ -0,0 +1,62 @@
+//! Output formatting for indexes command results.
+
+use super::execute::IndexesResult;
+use crate::output::Outputable;
+
+impl Outputable for IndexesResult {
+    fn to_table(&self) -> String {
+        let mut output = String::from("Secondary Indexes\n\n");
+
+        let width = self.indexes.iter().map(|i| i.index.len()).max().unwrap_or(0);
+        for index in &self.indexes {
+            let symbol = if index.exists { "✓" } else { "✗" };
+            output.push_str(&format!(
+                "  {} {:<width$}  {{{}}}\n",
+                symbol,
+                index.index,
+                index.columns.join(", "),
+                width = width
+            ));
+        }
+
+        let missing = self.indexes.iter().filter(|i| !i.exists).count();
+        if self.rebuilt {
+            output.push_str(&format!("\nRebuilt {} index(es).\n", self.indexes.len() - missing));
+        } else if missing > 0 {
+            output.push_str(&format!("\n{} index(es) missing; create them with `indexes --rebuild`.\n", missing));
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::indexes::execute::IndexStatus;
+
+    fn status(index: &str, columns: &[&str], exists: bool) -> IndexStatus {
+        IndexStatus {
+            index: index.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            exists,
+        }
+    }
+
+    #[test]
+    fn test_to_table_with_missing_index() {
+        let result = IndexesResult {
+            rebuilt: false,
+            indexes: vec![
+                status("calls:by_callee", &["callee_module", "callee_function"], true),
+                status("function_locations:by_file", &["file"], false),
+            ],
+        };
+        assert_eq!(
+            result.to_table(),
+            "Secondary Indexes\n\n  \
+             ✓ calls:by_callee             {callee_module, callee_function}\n  \
+             ✗ function_locations:by_file  {file}\n\n\
+             1 index(es) missing; create them with `indexes --rebuild`.\n"
+        );
+    }
+}
@@ -4,8 +4,11 @@ use std::error::Error;
 use serde::Serialize;
 
 use super::LargeFunctionsCmd;
+use crate::blame::{sort_and_blame, BlameInfo, Blamer};
 use crate::commands::Execute;
+use crate::sort::query_limit;
 use db::queries::large_functions::find_large_functions;
+use db::query_builders::QuerySettings;
 use db::types::{ModuleCollectionResult, ModuleGroup};
 
 /// A single large function entry
@@ -17,23 +20,30 @@ pub struct LargeFunctionEntry {
     pub end_line: i64,
     pub lines: i64,
     pub file: String,
+    /// Only with --blame, for functions in files git tracks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blame: Option<BlameInfo>,
 }
 
 impl Execute for LargeFunctionsCmd {
     type Output = ModuleCollectionResult<LargeFunctionEntry>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         let large_functions = find_large_functions(
             db,
             self.min_lines,
             self.module.as_deref(),
+            self.app.as_deref(),
             &self.common.project,
             self.common.regex,
             self.include_generated,
-            self.common.limit,
+            self.filter.as_ref(),
+            query_limit(self.sort.as_ref(), self.common.limit),
+            settings,
         )?;
 
         let total_items = large_functions.len();
+        let blamer = if self.blame { Some(Blamer::open(".")?) } else { None };
 
         // Group by module while preserving sort order (largest functions first)
         // Track module order separately to maintain insertion order
@@ -48,6 +58,7 @@ impl Execute for LargeFunctionsCmd {
                 end_line: func.end_line,
                 lines: func.lines,
                 file: func.file.clone(),
+                blame: None,
             };
 
             if !module_map.contains_key(&func.module) {
@@ -73,14 +84,25 @@ impl Execute for LargeFunctionsCmd {
             })
             .collect();
 
-        Ok(ModuleCollectionResult {
+        let mut result = ModuleCollectionResult {
             module_pattern: self.module.clone().unwrap_or_else(|| "*".to_string()),
             function_pattern: None,
             kind_filter: None,
             name_filter: None,
             total_items,
             items,
-        })
+        };
+
+        sort_and_blame(
+            &mut result,
+            self.sort.as_ref(),
+            self.common.limit,
+            blamer,
+            |f| (f.start_line, f.end_line),
+            |f, blame| f.blame = blame,
+        )?;
+
+        Ok(result)
     }
 }
 
@@ -93,11 +115,16 @@ mod tests {
         let cmd = LargeFunctionsCmd {
             min_lines: 100,
             include_generated: false,
+            app: None,
             module: Some("MyApp".to_string()),
+            sort: None,
+            filter: None,
+            blame: false,
             common: crate::commands::CommonArgs {
                 project: "default".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
 
@@ -105,4 +132,30 @@ mod tests {
         assert!(!cmd.include_generated);
         assert_eq!(cmd.module, Some("MyApp".to_string()));
     }
+
+    #[test]
+    fn test_large_functions_app_filter() {
+        let db = db::test_utils::umbrella_db("default");
+        let cmd = LargeFunctionsCmd {
+            min_lines: 5,
+            include_generated: false,
+            app: Some("billing".to_string()),
+            module: None,
+            sort: None,
+            filter: None,
+            blame: false,
+            common: crate::commands::CommonArgs {
+                project: "default".to_string(),
+                regex: false,
+                limit: 20,
+                namespace: Vec::new(),
+            },
+        };
+
+        // Billing.Invoice.charge/1 (10 lines); Web and Core functions of 5+ lines are filtered out
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert_eq!(result.total_items, 1);
+        assert_eq!(result.items[0].name, "Billing.Invoice");
+        assert_eq!(result.items[0].entries[0].name, "charge");
+    }
 }
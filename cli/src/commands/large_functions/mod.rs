@@ -4,10 +4,13 @@ mod output;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
+use db::where_filter::WhereFilter;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::sort::SortSpec;
 
 /// Find large functions that may need refactoring
 ///
@@ -20,7 +23,11 @@ Examples:
   code_search large-functions MyApp.Web           # Filter to MyApp.Web namespace
   code_search large-functions --min-lines 100     # Find functions with 100+ lines
   code_search large-functions --include-generated # Include macro-generated functions
+  code_search large-functions --app billing       # Only modules of the billing umbrella app
   code_search large-functions -l 20               # Show top 20 largest functions
+  code_search large-functions --sort name         # Sort by function name
+  code_search large-functions --where 'lines >= 80 || arity > 4' # Combine conditions
+  code_search large-functions --blame             # Who last changed each function, and when
 ")]
 pub struct LargeFunctionsCmd {
     /// Module filter pattern (substring match by default, regex with --regex)
@@ -30,17 +37,34 @@ pub struct LargeFunctionsCmd {
     #[arg(long, default_value = "50")]
     pub min_lines: i64,
 
-    /// Include macro-generated functions (excluded by default)
+    /// Include generated functions (excluded by default): macro-generated and
+    /// compiler callbacks, plus the `generated` rules of the config file
     #[arg(long)]
     pub include_generated: bool,
 
+    /// Only include modules of this OTP application (umbrella projects)
+    #[arg(long)]
+    pub app: Option<String>,
+
+    /// Sort by an output column as `column[:asc|desc]` (ascending by default)
+    #[arg(long, value_name = "COLUMN[:asc|desc]")]
+    pub sort: Option<SortSpec>,
+
+    /// Only include results matching an expression over the output columns, e.g. 'arity > 2 && module =~ "Web"'
+    #[arg(long = "where", value_name = "EXPR")]
+    pub filter: Option<WhereFilter>,
+
+    /// Annotate each finding with the last author and commit date of its lines, from `git blame`
+    #[arg(long)]
+    pub blame: bool,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for LargeFunctionsCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
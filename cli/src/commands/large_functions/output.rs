@@ -1,7 +1,7 @@
 //! Output formatting for large functions command results.
 
 use super::execute::LargeFunctionEntry;
-use crate::output::TableFormatter;
+use crate::output::{Annotation, TableFormatter};
 use db::types::ModuleCollectionResult;
 
 impl TableFormatter for ModuleCollectionResult<LargeFunctionEntry> {
@@ -30,6 +30,10 @@ impl TableFormatter for ModuleCollectionResult<LargeFunctionEntry> {
         )
     }
 
+    fn format_entry_details(&self, entry: &LargeFunctionEntry, _module: &str, _file: &str) -> Vec<String> {
+        entry.blame.iter().map(|blame| blame.to_string()).collect()
+    }
+
     fn blank_before_module(&self) -> bool {
         true
     }
@@ -37,4 +41,20 @@ impl TableFormatter for ModuleCollectionResult<LargeFunctionEntry> {
     fn blank_after_summary(&self) -> bool {
         false
     }
+
+    fn format_annotation(&self, entry: &LargeFunctionEntry, module: &str, _file: &str) -> Option<Annotation> {
+        Some(
+            Annotation::warning(
+                &entry.file,
+                entry.start_line,
+                "Large function",
+                format!("{}.{}/{} is {} lines long", module, entry.name, entry.arity, entry.lines),
+            )
+            .with_end_line(entry.end_line),
+        )
+    }
+
+    fn annotates(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,201 @@
+//! Layer rule evaluation over cross-module call edges.
+
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::LayersCmd;
+use crate::commands::Execute;
+use crate::config::Config;
+use crate::rules::LayerRules;
+use db::queries::layers::{ModuleCallEdge, find_module_call_edges};
+use db::query_builders::QuerySettings;
+
+/// A call edge that crosses layers in a disallowed direction
+#[derive(Debug, Clone, Serialize)]
+pub struct LayerViolation {
+    pub from_layer: String,
+    pub to_layer: String,
+    pub caller_module: String,
+    pub caller_function: String,
+    pub callee_module: String,
+    pub callee_function: String,
+    pub callee_arity: i64,
+    pub file: String,
+    pub line: i64,
+}
+
+/// Result of checking the call graph against layer rules
+#[derive(Debug, Serialize)]
+pub struct LayersResult {
+    /// Configured layer names in declaration order
+    pub layers: Vec<String>,
+    /// Total number of violations found (before limit)
+    pub total_violations: usize,
+    /// Violating call edges
+    pub violations: Vec<LayerViolation>,
+}
+
+impl Execute for LayersCmd {
+    type Output = LayersResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let config = Config::load(&self.config)?;
+        let rules = LayerRules::new(&config.layers)?;
+
+        let edges = find_module_call_edges(
+            db,
+            &self.common.project,
+            self.module.as_deref(),
+            self.common.regex,
+            settings,
+        )?;
+
+        let mut violations = find_violations(&rules, edges);
+        let total_violations = violations.len();
+        violations.truncate(self.common.limit as usize);
+
+        Ok(LayersResult {
+            layers: rules.names().to_vec(),
+            total_violations,
+            violations,
+        })
+    }
+}
+
+/// Keep only the edges that violate the layer rules, grouped by layer pair
+fn find_violations(rules: &LayerRules, edges: Vec<ModuleCallEdge>) -> Vec<LayerViolation> {
+    let mut violations: Vec<LayerViolation> = edges
+        .into_iter()
+        .filter_map(|edge| {
+            let (from_layer, to_layer) =
+                rules.violation(&edge.caller_module, &edge.callee_module)?;
+            Some(LayerViolation {
+                from_layer: from_layer.to_string(),
+                to_layer: to_layer.to_string(),
+                caller_module: edge.caller_module,
+                caller_function: edge.caller_function,
+                callee_module: edge.callee_module,
+                callee_function: edge.callee_function,
+                callee_arity: edge.callee_arity,
+                file: edge.file,
+                line: edge.line,
+            })
+        })
+        .collect();
+
+    // Stable sort keeps the query's module/file/line order within each pair
+    violations.sort_by(|a, b| (&a.from_layer, &a.to_layer).cmp(&(&b.from_layer, &b.to_layer)));
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use tempfile::NamedTempFile;
+
+    fn write_config(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    fn layers_cmd(config: PathBuf, module: Option<&str>) -> LayersCmd {
+        LayersCmd {
+            module: module.map(String::from),
+            config,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    // Fixture edges: Controller -> Accounts, Controller -> Service,
+    // Service -> Repo, Service -> Notifier, Accounts -> Repo
+    const LAYERED: &str = r#"{"layers": [
+        {"name": "Web", "modules": ["MyApp.Controller"], "may_depend_on": ["Domain"]},
+        {"name": "Domain", "modules": ["MyApp.Accounts", "MyApp.Service", "MyApp.Notifier"], "may_depend_on": ["Data"]},
+        {"name": "Data", "modules": ["MyApp.Repo"], "may_depend_on": []}
+    ]}"#;
+
+    #[test]
+    fn test_layers_no_violations() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let config = write_config(LAYERED);
+
+        let result = layers_cmd(config.path().to_path_buf(), None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.layers, vec!["Web", "Domain", "Data"]);
+        assert_eq!(result.total_violations, 0);
+        assert!(result.violations.is_empty());
+    }
+
+    #[test]
+    fn test_layers_reports_violations_with_location() {
+        let db = db::test_utils::call_graph_db("test_project");
+        // Accounts moved into the data layer: Controller -> Accounts now skips Domain
+        let config = write_config(
+            r#"{"layers": [
+                {"name": "Web", "modules": ["MyApp.Controller"], "may_depend_on": ["Domain"]},
+                {"name": "Domain", "modules": ["MyApp.Service", "MyApp.Notifier"]},
+                {"name": "Data", "modules": ["MyApp.Repo", "MyApp.Accounts"]}
+            ]}"#,
+        );
+
+        let result = layers_cmd(config.path().to_path_buf(), None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert!(result.total_violations > 0);
+        for v in &result.violations {
+            assert_eq!(v.from_layer, "Web");
+            assert_eq!(v.to_layer, "Data");
+            assert_eq!(v.caller_module, "MyApp.Controller");
+            assert_eq!(v.callee_module, "MyApp.Accounts");
+            assert!(!v.file.is_empty());
+            assert!(v.line > 0);
+        }
+    }
+
+    #[test]
+    fn test_layers_module_filter() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let config = write_config(
+            r#"{"layers": [
+                {"name": "Data", "modules": ["MyApp.Repo"]},
+                {"name": "Rest", "modules": ["MyApp"]}
+            ]}"#,
+        );
+
+        let all = layers_cmd(config.path().to_path_buf(), None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+        let filtered = layers_cmd(config.path().to_path_buf(), Some("MyApp.Service"))
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert!(filtered.total_violations > 0);
+        assert!(filtered.total_violations < all.total_violations);
+        assert!(
+            filtered
+                .violations
+                .iter()
+                .all(|v| v.caller_module == "MyApp.Service")
+        );
+    }
+
+    #[test]
+    fn test_layers_missing_config() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = layers_cmd(PathBuf::from("/nonexistent/config.json"), None).execute(&db, &QuerySettings::default());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,44 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::output::{OutputFormat, Outputable};
+
+/// Check call edges against architectural layer rules
+///
+/// Reads layer definitions (e.g. Web -> Domain -> Data) from the config file and
+/// reports every call that crosses layers in a disallowed direction, with file:line.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search layers                                # Check all calls against configured layers
+  code_search layers MyAppWeb.Router                # Only check calls made from MyAppWeb.Router
+  code_search layers 'MyAppWeb\\..*' -r              # Only check calls made from MyAppWeb modules
+  code_search layers --config architecture.json     # Use a different config file
+")]
+pub struct LayersCmd {
+    /// Only check calls made from this module (exact match, or pattern with --regex)
+    pub module: Option<String>,
+
+    /// Path to the config file containing layer definitions
+    #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+    pub config: PathBuf,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for LayersCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,128 @@
+//! Output formatting for layers command results.
+
+use super::execute::LayersResult;
+use crate::output::Outputable;
+
+impl Outputable for LayersResult {
+    fn to_table(&self) -> String {
+        let mut output = String::new();
+        output.push_str("Layer Violations\n\n");
+        output.push_str(&format!("Layers: {}\n\n", self.layers.join(" -> ")));
+
+        if self.violations.is_empty() {
+            output.push_str("No layer violations found.\n");
+            return output;
+        }
+
+        output.push_str(&format!("Found {} violation(s):\n", self.total_violations));
+
+        let mut current_pair: Option<(&str, &str)> = None;
+        for v in &self.violations {
+            let pair = (v.from_layer.as_str(), v.to_layer.as_str());
+            if current_pair != Some(pair) {
+                output.push_str(&format!("\n{} -> {}:\n", v.from_layer, v.to_layer));
+                current_pair = Some(pair);
+            }
+            output.push_str(&format!(
+                "  {}:{}  {}.{} -> {}.{}/{}\n",
+                v.file,
+                v.line,
+                v.caller_module,
+                v.caller_function,
+                v.callee_module,
+                v.callee_function,
+                v.callee_arity
+            ));
+        }
+
+        if self.violations.len() < self.total_violations {
+            output.push_str(&format!(
+                "\nShowing {} of {} violation(s)\n",
+                self.violations.len(),
+                self.total_violations
+            ));
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::layers::execute::LayerViolation;
+
+    fn violation(from: &str, to: &str, line: i64) -> LayerViolation {
+        LayerViolation {
+            from_layer: from.to_string(),
+            to_layer: to.to_string(),
+            caller_module: "MyAppWeb.UserController".to_string(),
+            caller_function: "index".to_string(),
+            callee_module: "MyApp.Repo".to_string(),
+            callee_function: "all".to_string(),
+            callee_arity: 1,
+            file: "lib/my_app_web/user_controller.ex".to_string(),
+            line,
+        }
+    }
+
+    #[test]
+    fn test_layers_output_empty() {
+        let result = LayersResult {
+            layers: vec!["Web".to_string(), "Data".to_string()],
+            total_violations: 0,
+            violations: vec![],
+        };
+
+        let output = result.to_table();
+        assert!(output.contains("Layers: Web -> Data"));
+        assert!(output.contains("No layer violations found"));
+    }
+
+    #[test]
+    fn test_layers_output_groups_by_layer_pair() {
+        let result = LayersResult {
+            layers: vec!["Web".to_string(), "Domain".to_string(), "Data".to_string()],
+            total_violations: 3,
+            violations: vec![
+                violation("Web", "Data", 10),
+                violation("Web", "Data", 12),
+                violation("Data", "Domain", 4),
+            ],
+        };
+
+        let output = result.to_table();
+        assert!(output.contains("Found 3 violation(s)"));
+        assert_eq!(output.matches("Web -> Data:").count(), 1);
+        assert!(output.contains("Data -> Domain:"));
+        assert!(output.contains(
+            "lib/my_app_web/user_controller.ex:10  MyAppWeb.UserController.index -> MyApp.Repo.all/1"
+        ));
+    }
+
+    #[test]
+    fn test_layers_output_truncated() {
+        let result = LayersResult {
+            layers: vec!["Web".to_string(), "Data".to_string()],
+            total_violations: 5,
+            violations: vec![violation("Web", "Data", 10)],
+        };
+
+        let output = result.to_table();
+        assert!(output.contains("Showing 1 of 5 violation(s)"));
+    }
+
+    #[test]
+    fn test_layers_output_json() {
+        let result = LayersResult {
+            layers: vec!["Web".to_string(), "Data".to_string()],
+            total_violations: 1,
+            violations: vec![violation("Web", "Data", 10)],
+        };
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("total_violations"));
+        assert!(json.contains("from_layer"));
+        assert!(json.contains("\"line\":10"));
+    }
+}
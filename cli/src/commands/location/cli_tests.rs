@@ -26,7 +26,7 @@ mod tests {
         test_name: test_with_function_only,
         args: ["get_user"],
         field: function,
-        expected: "get_user",
+        expected: Some("get_user".to_string()),
     }
 
     crate::cli_option_test! {
@@ -78,6 +78,21 @@ mod tests {
     // Limit validation tests
     // =========================================================================
 
+    crate::cli_option_test! {
+        command: "location",
+        variant: Location,
+        test_name: test_with_stdin,
+        args: ["--stdin"],
+        field: stdin,
+        expected: true,
+    }
+
+    crate::cli_error_test! {
+        command: "location",
+        test_name: test_stdin_conflicts_with_positional,
+        args: ["get_user", "--stdin"],
+    }
+
     crate::cli_limit_tests! {
         command: "location",
         variant: Location,
@@ -5,7 +5,10 @@ use serde::Serialize;
 
 use super::LocationCmd;
 use crate::commands::Execute;
-use db::queries::location::{find_locations, FunctionLocation};
+use crate::targets::stdin_targets;
+use db::query_builders::{FunctionTarget, PatternMatching, QuerySettings};
+use db::queries::annotations::{AnnotationIndex, AnnotationNote};
+use db::queries::location::{find_locations, find_locations_for_targets, FunctionLocation};
 
 /// A single clause (definition) of a function
 #[derive(Debug, Clone, Serialize)]
@@ -27,6 +30,9 @@ pub struct LocationFunction {
     pub kind: String,
     pub file: String,
     pub clauses: Vec<LocationClause>,
+    /// Only with --show-annotations
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<AnnotationNote>,
 }
 
 /// A module containing functions
@@ -90,6 +96,7 @@ impl LocationResult {
                         kind,
                         file,
                         clauses,
+                        annotations: vec![],
                     })
                     .collect();
                 LocationModule {
@@ -111,21 +118,64 @@ impl LocationResult {
 impl Execute for LocationCmd {
     type Output = LocationResult;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        if self.stdin {
+            return self.execute_targets(db, &stdin_targets()?, settings);
+        }
+
+        let function_pattern = self.function.clone().unwrap_or_default();
+        let patterns = [self.module.as_deref(), Some(function_pattern.as_str())];
+        let matching = PatternMatching::new(self.common.regex, patterns.into_iter().flatten());
+        let module = self.module.as_deref().map(|m| matching.pattern(m));
+        let function = matching.pattern(&function_pattern);
+        let use_regex = matching.use_regex();
+
         let locations = find_locations(
             db,
-            self.module.as_deref(),
-            &self.function,
+            module.as_deref(),
+            &function,
             self.arity,
             &self.common.project,
-            self.common.regex,
+            use_regex,
             self.common.limit,
+            settings,
         )?;
 
-        Ok(LocationResult::from_locations(
-            self.module.unwrap_or_default(),
-            self.function,
-            locations,
-        ))
+        let result = LocationResult::from_locations(self.module.clone().unwrap_or_default(), function_pattern, locations);
+        self.annotate(db, result, settings)
+    }
+}
+
+impl LocationCmd {
+    /// Locations of each of the targets, from one query (`--stdin`)
+    pub fn execute_targets(
+        self,
+        db: &db::DbInstance,
+        targets: &[FunctionTarget],
+        settings: &QuerySettings,
+    ) -> Result<LocationResult, Box<dyn Error>> {
+        let locations = find_locations_for_targets(db, targets, &self.common.project, self.common.limit)?;
+        let labels: Vec<String> = targets.iter().map(FunctionTarget::label).collect();
+        let result = LocationResult::from_locations(labels.join(", "), String::new(), locations);
+        self.annotate(db, result, settings)
+    }
+
+    /// Attach the annotations of each function with --show-annotations
+    fn annotate(
+        &self,
+        db: &db::DbInstance,
+        mut result: LocationResult,
+        settings: &QuerySettings,
+    ) -> Result<LocationResult, Box<dyn Error>> {
+        if self.show_annotations {
+            let index = AnnotationIndex::load(db, &self.common.project, settings)?;
+            for module in &mut result.modules {
+                for func in &mut module.functions {
+                    func.annotations = index.for_function(&module.name, &func.name, func.arity);
+                }
+            }
+        }
+
+        Ok(result)
     }
 }
@@ -4,6 +4,7 @@
 mod tests {
     use super::super::LocationCmd;
     use crate::commands::CommonArgs;
+    use db::query_builders::{FunctionTarget, QuerySettings};
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -21,12 +22,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: Some("MyApp.Accounts".to_string()),
-            function: "get_user".to_string(),
+            function: Some("get_user".to_string()),
             arity: Some(1),
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -45,12 +49,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: None,
-            function: "get_user".to_string(),
+            function: Some("get_user".to_string()),
             arity: None,
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -68,12 +75,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: None,
-            function: ".*user.*".to_string(),
+            function: Some(".*user.*".to_string()),
             arity: None,
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -87,12 +97,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: Some("MyApp.Accounts".to_string()),
-            function: "get_user".to_string(),
+            function: Some("get_user".to_string()),
             arity: None,
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -105,12 +118,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: Some("MyApp\\..*".to_string()),
-            function: ".*user.*".to_string(),
+            function: Some(".*user.*".to_string()),
             arity: None,
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -118,17 +134,44 @@ mod tests {
         },
     }
 
+    // Globs match like the regex above and are reported as given
+    crate::execute_test! {
+        test_name: test_location_with_glob,
+        fixture: populated_db,
+        cmd: LocationCmd {
+            module: Some("MyApp.*".to_string()),
+            function: Some("*user*".to_string()),
+            arity: None,
+            show_annotations: false,
+            stdin: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert_eq!(result.total_clauses, 3);
+            assert_eq!(result.module_pattern, "MyApp.*");
+            assert_eq!(result.function_pattern, "*user*");
+        },
+    }
+
     crate::execute_test! {
         test_name: test_location_format,
         fixture: populated_db,
         cmd: LocationCmd {
             module: Some("MyApp.Accounts".to_string()),
-            function: "get_user".to_string(),
+            function: Some("get_user".to_string()),
             arity: Some(1),
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -149,12 +192,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: Some("NonExistent".to_string()),
-            function: "foo".to_string(),
+            function: Some("foo".to_string()),
             arity: None,
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: modules,
@@ -165,12 +211,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: None,
-            function: "get_user".to_string(),
+            function: Some("get_user".to_string()),
             arity: None,
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "nonexistent_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: modules,
@@ -185,12 +234,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: Some("MyApp.Accounts".to_string()),
-            function: "get_user".to_string(),
+            function: Some("get_user".to_string()),
             arity: Some(1),
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -205,12 +257,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: None,
-            function: ".*".to_string(),
+            function: Some(".*".to_string()),
             arity: Some(1),
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -230,12 +285,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: None,
-            function: "get_user".to_string(),
+            function: Some("get_user".to_string()),
             arity: None,
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -249,12 +307,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: Some("MyApp.Accounts".to_string()),
-            function: ".*user.*".to_string(),
+            function: Some(".*user.*".to_string()),
             arity: None,
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -267,12 +328,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: None,
-            function: "list_users".to_string(),
+            function: Some("list_users".to_string()),
             arity: Some(0),
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -286,12 +350,15 @@ mod tests {
         fixture: populated_db,
         cmd: LocationCmd {
             module: None,
-            function: ".*user.*".to_string(),
+            function: Some(".*user.*".to_string()),
             arity: None,
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 1,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -300,6 +367,38 @@ mod tests {
         },
     }
 
+    // =========================================================================
+    // --stdin target tests
+    // =========================================================================
+
+    #[rstest]
+    fn test_location_targets(populated_db: db::DbInstance) {
+        let cmd = LocationCmd {
+            module: None,
+            function: None,
+            arity: None,
+            show_annotations: false,
+            stdin: true,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        };
+        let targets = [FunctionTarget::parse("MyApp.Accounts.get_user/1").unwrap(), FunctionTarget::parse("MyApp.Accounts.list_users").unwrap()];
+        let result = cmd.execute_targets(&populated_db, &targets, &QuerySettings::default()).expect("Execute should succeed");
+
+        let functions: Vec<(&str, i64)> = result
+            .modules
+            .iter()
+            .flat_map(|m| m.functions.iter().map(|f| (f.name.as_str(), f.arity)))
+            .collect();
+        assert_eq!(functions, [("get_user", 1), ("list_users", 0)]);
+        assert_eq!(result.function_pattern, "");
+    }
+
+
     // =========================================================================
     // Error handling tests
     // =========================================================================
@@ -308,12 +407,15 @@ mod tests {
         cmd_type: LocationCmd,
         cmd: LocationCmd {
             module: Some("MyApp".to_string()),
-            function: "foo".to_string(),
+            function: Some("foo".to_string()),
             arity: None,
+            show_annotations: false,
+            stdin: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
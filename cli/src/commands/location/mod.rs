@@ -7,10 +7,12 @@ mod output_tests;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::suggest::Lookup;
 
 /// Find where a function is defined (file:line_start:line_end)
 #[derive(Args, Debug)]
@@ -20,10 +22,14 @@ Examples:
   code_search location get_user MyApp        # In specific module
   code_search location get_user -a 1         # With specific arity
   code_search location -r 'get_.*'           # Regex pattern matching
+  code_search location 'get_*' 'MyApp.*'     # Glob pattern matching
+  code_search location get_user --show-annotations  # Include notes attached with annotate
+  code_search unused -o csv --fields module,name,arity | code_search location --stdin
 ")]
 pub struct LocationCmd {
     /// Function name (exact match or pattern with --regex)
-    pub function: String,
+    #[arg(required_unless_present = "stdin")]
+    pub function: Option<String>,
 
     /// Module name (exact match or pattern with --regex). If not specified, searches all modules.
     pub module: Option<String>,
@@ -32,13 +38,23 @@ pub struct LocationCmd {
     #[arg(short, long)]
     pub arity: Option<i64>,
 
+    /// Include annotations attached with `annotate`
+    #[arg(long)]
+    pub show_annotations: bool,
+
+    /// Read the functions from stdin, one Module.function[/arity] per line, and query them together
+    #[arg(long, conflicts_with_all = ["function", "module", "arity", "regex"])]
+    pub stdin: bool,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for LocationCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
-        Ok(result.format(format))
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let lookup = Lookup::new(&self.common, self.module.as_deref(), self.function.as_deref());
+        let result = self.execute(db, settings)?;
+        let empty = result.modules.is_empty();
+        Ok(lookup.suggest(db, result, empty, settings)?.format(format))
     }
 }
@@ -7,7 +7,12 @@ impl Outputable for LocationResult {
     fn to_table(&self) -> String {
         let mut lines = Vec::new();
 
-        lines.push(format!("Location: {}.{}", self.module_pattern, self.function_pattern));
+        // A --stdin query lists its targets in the module pattern
+        if self.function_pattern.is_empty() {
+            lines.push(format!("Location: {}", self.module_pattern));
+        } else {
+            lines.push(format!("Location: {}.{}", self.module_pattern, self.function_pattern));
+        }
         lines.push(String::new());
 
         if !self.modules.is_empty() {
@@ -27,6 +32,9 @@ impl Outputable for LocationResult {
                         "  {}/{} [{}] ({})",
                         func.name, func.arity, func.kind, func.file
                     ));
+                    for note in &func.annotations {
+                        lines.push(format!("    {}", note));
+                    }
                     for clause in &func.clauses {
                         let pattern_str = if clause.pattern.is_empty() {
                             String::new()
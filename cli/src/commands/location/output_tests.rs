@@ -63,6 +63,7 @@ MyApp.Users:
                     arity: 1,
                     kind: "def".to_string(),
                     file: "lib/my_app/accounts.ex".to_string(),
+                    annotations: vec![],
                     clauses: vec![LocationClause {
                         line: 10,
                         start_line: 10,
@@ -89,6 +90,7 @@ MyApp.Users:
                         arity: 1,
                         kind: "def".to_string(),
                         file: "lib/my_app/accounts.ex".to_string(),
+                        annotations: vec![],
                         clauses: vec![LocationClause {
                             line: 10,
                             start_line: 10,
@@ -105,6 +107,7 @@ MyApp.Users:
                         arity: 1,
                         kind: "def".to_string(),
                         file: "lib/my_app/users.ex".to_string(),
+                        annotations: vec![],
                         clauses: vec![LocationClause {
                             line: 5,
                             start_line: 5,
@@ -5,7 +5,9 @@ use serde::Serialize;
 
 use super::ManyClausesCmd;
 use crate::commands::Execute;
+use crate::sort::query_limit;
 use db::queries::many_clauses::find_many_clauses;
+use db::query_builders::QuerySettings;
 use db::types::{ModuleCollectionResult, ModuleGroup};
 
 /// A single function with many clauses entry
@@ -22,7 +24,7 @@ pub struct ManyClausesEntry {
 impl Execute for ManyClausesCmd {
     type Output = ModuleCollectionResult<ManyClausesEntry>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         let many_clauses = find_many_clauses(
             db,
             self.min_clauses,
@@ -30,7 +32,9 @@ impl Execute for ManyClausesCmd {
             &self.common.project,
             self.common.regex,
             self.include_generated,
-            self.common.limit,
+            self.filter.as_ref(),
+            query_limit(self.sort.as_ref(), self.common.limit),
+            settings,
         )?;
 
         let total_items = many_clauses.len();
@@ -72,14 +76,20 @@ impl Execute for ManyClausesCmd {
             })
             .collect();
 
-        Ok(ModuleCollectionResult {
+        let mut result = ModuleCollectionResult {
             module_pattern: self.module.clone().unwrap_or_else(|| "*".to_string()),
             function_pattern: None,
             kind_filter: None,
             name_filter: None,
             total_items,
             items,
-        })
+        };
+
+        if let Some(sort) = &self.sort {
+            sort.sort_groups(&mut result, self.common.limit)?;
+        }
+
+        Ok(result)
     }
 }
 
@@ -93,10 +103,13 @@ mod tests {
             min_clauses: 10,
             include_generated: false,
             module: Some("MyApp".to_string()),
+            sort: None,
+            filter: None,
             common: crate::commands::CommonArgs {
                 project: "default".to_string(),
                 regex: false,
                 limit: 20,
+                namespace: Vec::new(),
             },
         };
 
@@ -4,10 +4,13 @@ mod output;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
+use db::where_filter::WhereFilter;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::sort::SortSpec;
 
 /// Find functions with many pattern-matched heads
 ///
@@ -22,6 +25,8 @@ Examples:
   code_search many-clauses --min-clauses 10    # Find functions with 10+ clauses
   code_search many-clauses --include-generated # Include macro-generated functions
   code_search many-clauses -l 20               # Show top 20 functions with most clauses
+  code_search many-clauses --sort line         # Sort by line within each module
+  code_search many-clauses --where 'name !~ \"^handle_\"' # Leave out handle_* callbacks
 ")]
 pub struct ManyClausesCmd {
     /// Module filter pattern (substring match by default, regex with --regex)
@@ -31,17 +36,26 @@ pub struct ManyClausesCmd {
     #[arg(long, default_value = "5")]
     pub min_clauses: i64,
 
-    /// Include macro-generated functions (excluded by default)
+    /// Include generated functions (excluded by default): macro-generated and
+    /// compiler callbacks, plus the `generated` rules of the config file
     #[arg(long)]
     pub include_generated: bool,
 
+    /// Sort by an output column as `column[:asc|desc]` (ascending by default)
+    #[arg(long, value_name = "COLUMN[:asc|desc]")]
+    pub sort: Option<SortSpec>,
+
+    /// Only include results matching an expression over the output columns, e.g. 'arity > 2 && module =~ "Web"'
+    #[arg(long = "where", value_name = "EXPR")]
+    pub filter: Option<WhereFilter>,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for ManyClausesCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
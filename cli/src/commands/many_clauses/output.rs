@@ -1,7 +1,7 @@
 //! Output formatting for many clauses command results.
 
 use super::execute::ManyClausesEntry;
-use crate::output::TableFormatter;
+use crate::output::{Annotation, TableFormatter};
 use db::types::ModuleCollectionResult;
 
 impl TableFormatter for ModuleCollectionResult<ManyClausesEntry> {
@@ -37,4 +37,20 @@ impl TableFormatter for ModuleCollectionResult<ManyClausesEntry> {
     fn blank_after_summary(&self) -> bool {
         false
     }
+
+    fn format_annotation(&self, entry: &ManyClausesEntry, module: &str, _file: &str) -> Option<Annotation> {
+        Some(
+            Annotation::warning(
+                &entry.file,
+                entry.first_line,
+                "Many clauses",
+                format!("{}.{}/{} has {} clauses", module, entry.name, entry.arity, entry.clauses),
+            )
+            .with_end_line(entry.last_line),
+        )
+    }
+
+    fn annotates(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,210 @@
+//! Dependency structure matrix over the module call graph.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::{MatrixCmd, MatrixOrder};
+use crate::commands::Execute;
+use crate::graph::WeightedGraph;
+use db::query_builders::{compile_pattern, QuerySettings};
+use db::queries::clusters::get_module_call_counts;
+
+/// Result of the matrix command
+#[derive(Debug, Serialize)]
+pub struct MatrixResult {
+    pub order: String,
+    pub total_modules: usize,
+    /// Call sites between the included modules
+    pub total_calls: i64,
+    pub modules: Vec<String>,
+    /// Community of each module (numbered from 1, largest first) when ordered by cluster
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub clusters: Vec<usize>,
+    /// `cells[i][j]` is the number of call sites from `modules[i]` to `modules[j]`
+    pub cells: Vec<Vec<i64>>,
+}
+
+impl Execute for MatrixCmd {
+    type Output = MatrixResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let filter = match &self.module {
+            Some(pattern) if self.common.regex => Some(compile_pattern(pattern)?),
+            Some(pattern) => Some(Regex::new(&regex::escape(pattern))?),
+            None => None,
+        };
+
+        let calls = get_module_call_counts(db, &self.common.project, settings)?;
+
+        // Call volume (in + out) per module decides which modules survive the limit
+        let mut volume: BTreeMap<&str, i64> = BTreeMap::new();
+        for call in &calls {
+            *volume.entry(call.caller_module.as_str()).or_default() += call.call_count;
+            *volume.entry(call.callee_module.as_str()).or_default() += call.call_count;
+        }
+
+        let mut selected: Vec<(&str, i64)> = volume
+            .into_iter()
+            .filter(|(module, _)| filter.as_ref().is_none_or(|re| re.is_match(module)))
+            .collect();
+        selected.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        selected.truncate(self.common.limit as usize);
+
+        let mut modules: Vec<String> = selected.into_iter().map(|(m, _)| m.to_string()).collect();
+        modules.sort();
+
+        let index: BTreeMap<&str, usize> = modules
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.as_str(), i))
+            .collect();
+        let edges: Vec<(usize, usize, i64)> = calls
+            .iter()
+            .filter_map(|c| {
+                let from = *index.get(c.caller_module.as_str())?;
+                let to = *index.get(c.callee_module.as_str())?;
+                Some((from, to, c.call_count))
+            })
+            .collect();
+
+        let (order, clusters) = match self.order {
+            MatrixOrder::Name => ((0..modules.len()).collect(), Vec::new()),
+            MatrixOrder::Cluster => cluster_order(&modules, &edges),
+        };
+
+        let mut position = vec![0; modules.len()];
+        for (pos, &i) in order.iter().enumerate() {
+            position[i] = pos;
+        }
+
+        let mut cells = vec![vec![0; modules.len()]; modules.len()];
+        for &(from, to, count) in &edges {
+            cells[position[from]][position[to]] += count;
+        }
+
+        let modules: Vec<String> = order.iter().map(|&i| modules[i].clone()).collect();
+
+        Ok(MatrixResult {
+            order: self.order.as_str().to_string(),
+            total_modules: modules.len(),
+            total_calls: edges.iter().map(|e| e.2).sum(),
+            modules,
+            clusters,
+            cells,
+        })
+    }
+}
+
+/// Order modules by Louvain community.
+///
+/// Communities are placed largest first (ties by their first module name) and
+/// numbered from 1; modules keep their alphabetical order within a community.
+/// Returns the module indices in matrix order and the community of each position.
+fn cluster_order(modules: &[String], edges: &[(usize, usize, i64)]) -> (Vec<usize>, Vec<usize>) {
+    let graph = WeightedGraph::new(
+        modules.len(),
+        edges.iter().map(|&(from, to, count)| (from, to, count as f64)),
+    );
+    let membership = graph.louvain();
+
+    let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (i, &c) in membership.iter().enumerate() {
+        members.entry(c).or_default().push(i);
+    }
+
+    let mut communities: Vec<Vec<usize>> = members.into_values().collect();
+    communities.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| modules[a[0]].cmp(&modules[b[0]])));
+
+    let mut order = Vec::with_capacity(modules.len());
+    let mut clusters = Vec::with_capacity(modules.len());
+    for (id, community) in communities.into_iter().enumerate() {
+        for i in community {
+            order.push(i);
+            clusters.push(id + 1);
+        }
+    }
+
+    (order, clusters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+
+    fn matrix_cmd(module: Option<&str>, order: MatrixOrder, limit: u32) -> MatrixCmd {
+        MatrixCmd {
+            module: module.map(String::from),
+            order,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    fn cell(result: &MatrixResult, from: &str, to: &str) -> i64 {
+        let row = result.modules.iter().position(|m| m == from).unwrap();
+        let col = result.modules.iter().position(|m| m == to).unwrap();
+        result.cells[row][col]
+    }
+
+    #[test]
+    fn test_matrix_by_name() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = matrix_cmd(None, MatrixOrder::Name, 100)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(
+            result.modules,
+            ["MyApp.Accounts", "MyApp.Controller", "MyApp.Notifier", "MyApp.Repo", "MyApp.Service"]
+        );
+        assert_eq!(result.total_calls, 8);
+        assert_eq!(cell(&result, "MyApp.Accounts", "MyApp.Repo"), 3);
+        assert_eq!(cell(&result, "MyApp.Repo", "MyApp.Accounts"), 0);
+        assert!(result.clusters.is_empty());
+    }
+
+    #[test]
+    fn test_matrix_cluster_order_keeps_cells() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = matrix_cmd(None, MatrixOrder::Cluster, 100)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_modules, 5);
+        assert_eq!(result.clusters.len(), 5);
+        assert_eq!(result.clusters[0], 1);
+        assert!(result.clusters.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(cell(&result, "MyApp.Controller", "MyApp.Accounts"), 2);
+        assert_eq!(result.total_calls, 8);
+    }
+
+    #[test]
+    fn test_matrix_filter_and_limit() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = matrix_cmd(Some("Service"), MatrixOrder::Name, 100)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+        assert_eq!(result.modules, ["MyApp.Service"]);
+        assert_eq!(result.total_calls, 0);
+
+        let result = matrix_cmd(None, MatrixOrder::Name, 2)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+        assert_eq!(result.modules, ["MyApp.Accounts", "MyApp.Repo"]);
+        assert_eq!(result.cells, vec![vec![0, 3], vec![0, 0]]);
+    }
+
+    #[test]
+    fn test_matrix_empty_db() {
+        let db = db::test_utils::setup_empty_test_db();
+        assert!(matrix_cmd(None, MatrixOrder::Name, 100).execute(&db, &QuerySettings::default()).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::{Args, ValueEnum};
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Order of the matrix rows and columns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum MatrixOrder {
+    /// Alphabetical by module name
+    #[default]
+    Name,
+    /// Grouped by detected community (Louvain), so coupled modules sit together
+    Cluster,
+}
+
+impl MatrixOrder {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MatrixOrder::Name => "name",
+            MatrixOrder::Cluster => "cluster",
+        }
+    }
+}
+
+/// Build a module-by-module dependency structure matrix (DSM)
+///
+/// Each cell counts the call sites from the row module to the column module.
+/// Ordering by cluster groups tightly coupled modules into blocks along the
+/// diagonal, so coupling hot zones and cross-block dependencies stand out.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search matrix                         # Matrix of all modules, by name
+  code_search matrix --order cluster         # Group coupled modules together
+  code_search matrix MyApp.Accounts          # Only modules matching the pattern
+  code_search matrix -l 30                   # The 30 most connected modules
+  code_search -o csv matrix > dsm.csv        # Matrix as CSV for a spreadsheet
+")]
+pub struct MatrixCmd {
+    /// Only include modules matching this pattern (substring match by default, regex with --regex)
+    pub module: Option<String>,
+
+    /// Order of rows and columns
+    #[arg(long, value_enum, default_value_t = MatrixOrder::Name)]
+    pub order: MatrixOrder,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for MatrixCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
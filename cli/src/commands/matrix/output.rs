@@ -0,0 +1,149 @@
+//! Output formatting for matrix command results.
+
+use super::execute::MatrixResult;
+use crate::output::Outputable;
+use crate::output::columns::to_csv;
+
+impl Outputable for MatrixResult {
+    fn to_table(&self) -> String {
+        let mut output = format!(
+            "Dependency matrix ({} module(s), {} call(s), ordered by {})\n\n",
+            self.total_modules, self.total_calls, self.order
+        );
+
+        if self.modules.is_empty() {
+            output.push_str("No modules found.\n");
+            return output;
+        }
+
+        output.push_str("Rows call columns; cells count call sites.\n\n");
+
+        let labels: Vec<String> = self
+            .modules
+            .iter()
+            .enumerate()
+            .map(|(i, module)| match self.clusters.get(i) {
+                Some(cluster) => format!("[{}] {}", cluster, module),
+                None => module.clone(),
+            })
+            .collect();
+
+        let n = self.modules.len();
+        let index_width = n.to_string().len();
+        let label_width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let max_cell = self.cells.iter().flatten().copied().max().unwrap_or(0);
+        let cell_width = index_width.max(max_cell.to_string().len());
+
+        let header: Vec<String> = (1..=n).map(|i| format!("{:>cell_width$}", i)).collect();
+        output.push_str(&format!(
+            "{:index_width$}  {:label_width$}  {}\n",
+            "",
+            "",
+            header.join(" ")
+        ));
+
+        for (i, (label, row)) in labels.iter().zip(&self.cells).enumerate() {
+            let cells: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(j, &count)| {
+                    let cell = match count {
+                        _ if i == j => "-".to_string(),
+                        0 => ".".to_string(),
+                        count => count.to_string(),
+                    };
+                    format!("{:>cell_width$}", cell)
+                })
+                .collect();
+            output.push_str(&format!(
+                "{:>index_width$}  {:label_width$}  {}\n",
+                i + 1,
+                label,
+                cells.join(" ")
+            ));
+        }
+
+        output
+    }
+
+    fn to_csv(&self) -> Option<String> {
+        let mut header = vec!["module".to_string()];
+        header.extend(self.modules.iter().cloned());
+
+        let rows: Vec<Vec<String>> = self
+            .modules
+            .iter()
+            .zip(&self.cells)
+            .map(|(module, row)| {
+                let mut cells = vec![module.clone()];
+                cells.extend(row.iter().map(|count| count.to_string()));
+                cells
+            })
+            .collect();
+
+        Some(to_csv(&header, &rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::OutputFormat;
+
+    fn result(clusters: Vec<usize>) -> MatrixResult {
+        MatrixResult {
+            order: "name".to_string(),
+            total_modules: 3,
+            total_calls: 14,
+            modules: vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            clusters,
+            cells: vec![vec![0, 12, 0], vec![0, 0, 2], vec![0, 0, 0]],
+        }
+    }
+
+    #[test]
+    fn test_matrix_table() {
+        let output = result(vec![]).to_table();
+        let expected = [
+            "Dependency matrix (3 module(s), 14 call(s), ordered by name)",
+            "",
+            "Rows call columns; cells count call sites.",
+            "",
+            "       1  2  3",
+            "1  A   - 12  .",
+            "2  B   .  -  2",
+            "3  C   .  .  -",
+            "",
+        ];
+        assert_eq!(output, expected.join("\n"));
+    }
+
+    #[test]
+    fn test_matrix_table_with_clusters() {
+        let output = result(vec![1, 1, 2]).to_table();
+        assert!(output.contains("1  [1] A   - 12  .\n"));
+        assert!(output.contains("3  [2] C   .  .  -\n"));
+    }
+
+    #[test]
+    fn test_matrix_table_empty() {
+        let mut result = result(vec![]);
+        result.modules.clear();
+        result.cells.clear();
+        assert!(result.to_table().ends_with("No modules found.\n"));
+    }
+
+    #[test]
+    fn test_matrix_csv() {
+        let output = result(vec![]).format(OutputFormat::Csv);
+        assert_eq!(output, "module,A,B,C\nA,0,12,0\nB,0,0,2\nC,0,0,0");
+    }
+
+    #[test]
+    fn test_matrix_json() {
+        let output = result(vec![]).format(OutputFormat::Json);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["cells"][0][1], 12);
+        assert!(parsed.get("clusters").is_none());
+    }
+}
@@ -4,7 +4,7 @@
 //! - The command struct with clap attributes for CLI parsing
 //! - Common arguments shared via [`CommonArgs`]
 
-use clap::Args;
+use clap::{Args, ValueEnum};
 
 /// Common arguments shared across most commands.
 ///
@@ -29,68 +29,183 @@ pub struct CommonArgs {
     /// Maximum number of results to return (1-1000)
     #[arg(short, long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..=1000))]
     pub limit: u32,
+
+    /// Only include modules under this namespace (repeatable)
+    ///
+    /// Matches the namespace itself and its submodules with a prefix test,
+    /// faster than a regex: `--namespace MyApp.Web` keeps MyApp.Web and
+    /// MyApp.Web.UserController but not MyApp.WebHooks.
+    #[arg(long = "namespace", value_name = "NAMESPACE")]
+    pub namespace: Vec<String>,
+}
+
+/// Traversal engine for trace, reverse-trace and path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Engine {
+    /// Recursive Datalog query per traversal
+    #[default]
+    Datalog,
+    /// In-memory adjacency index, loaded once per process and reused
+    Memory,
+}
+
+impl From<Engine> for crate::api::Engine {
+    fn from(engine: Engine) -> Self {
+        match engine {
+            Engine::Datalog => Self::Datalog,
+            Engine::Memory => Self::Memory,
+        }
+    }
 }
 
 mod accepts;
+mod age;
+mod annotate;
+mod annotations;
+mod apps;
+mod backup;
+mod batch;
 mod boundaries;
 mod browse_module;
+mod callbacks;
 mod calls_from;
 mod calls_to;
+mod centrality;
+mod check;
 mod clusters;
+mod communities;
 mod complexity;
+mod context;
+mod coupling;
 mod cycles;
+mod daemon;
 mod depended_by;
 mod depends_on;
+mod deprecated_usage;
+mod deps_surface;
 mod describe;
+mod diagram;
 mod duplicates;
+mod entry_points;
+mod explore;
+mod export;
+mod extract;
+mod extract_module;
+mod fan_in_fan_out;
 mod function;
 mod god_modules;
 mod hotspots;
 pub mod import;
+mod index_embeddings;
+mod indexes;
 mod large_functions;
+mod layers;
 mod location;
 mod many_clauses;
+mod matrix;
+mod parallel;
 mod path;
+mod pr;
+mod query;
+mod rename_impact;
+mod report;
+mod restore;
 mod returns;
 mod reverse_trace;
+mod schema;
 mod search;
 pub mod setup;
+mod similar_functions;
+mod spec_coverage;
+mod struct_fields_usage;
 mod struct_usage;
+mod suggest_facade;
+mod summary;
+mod supervision_tree;
+mod test_surface;
 mod trace;
+mod trends;
+mod type_usage;
 mod unused;
+mod update;
+mod vacuum;
 
 pub use accepts::AcceptsCmd;
+pub use age::AgeCmd;
+pub use annotate::AnnotateCmd;
+pub use annotations::AnnotationsCmd;
+pub use apps::AppsCmd;
+pub use backup::BackupCmd;
+pub use batch::BatchCmd;
 pub use boundaries::BoundariesCmd;
 pub use browse_module::BrowseModuleCmd;
+pub use callbacks::CallbacksCmd;
 pub use calls_from::CallsFromCmd;
 pub use calls_to::CallsToCmd;
+pub use centrality::CentralityCmd;
+pub use check::{CheckCmd, CheckFailed};
 pub use clusters::ClustersCmd;
+pub use communities::CommunitiesCmd;
 pub use complexity::ComplexityCmd;
+pub use context::ContextCmd;
+pub use coupling::CouplingCmd;
 pub use cycles::CyclesCmd;
+pub use daemon::DaemonCmd;
 pub use depended_by::DependedByCmd;
 pub use depends_on::DependsOnCmd;
+pub use deprecated_usage::DeprecatedUsageCmd;
+pub use deps_surface::DepsSurfaceCmd;
 pub use describe::DescribeCmd;
+pub use diagram::DiagramCmd;
 pub use duplicates::DuplicatesCmd;
+pub use entry_points::EntryPointsCmd;
+pub use explore::ExploreCmd;
+pub use export::ExportCmd;
+pub use extract::ExtractCmd;
+pub use extract_module::ExtractModuleCmd;
+pub use fan_in_fan_out::FanInFanOutCmd;
 pub use function::FunctionCmd;
 pub use god_modules::GodModulesCmd;
 pub use hotspots::HotspotsCmd;
-pub use import::ImportCmd;
+pub use import::{ImportCmd, ImportSchema};
+pub use index_embeddings::IndexEmbeddingsCmd;
+pub use indexes::IndexesCmd;
 pub use large_functions::LargeFunctionsCmd;
+pub use layers::LayersCmd;
 pub use location::LocationCmd;
 pub use many_clauses::ManyClausesCmd;
-pub use path::PathCmd;
+pub use matrix::MatrixCmd;
+pub use path::{PathCmd, PathStrategy};
+pub use pr::PrCmd;
+pub use query::QueryCmd;
+pub use rename_impact::RenameImpactCmd;
+pub use report::ReportCmd;
+pub use restore::RestoreCmd;
 pub use returns::ReturnsCmd;
 pub use reverse_trace::ReverseTraceCmd;
+pub use schema::SchemaCmd;
 pub use search::SearchCmd;
 pub use setup::SetupCmd;
+pub use similar_functions::SimilarFunctionsCmd;
+pub use spec_coverage::SpecCoverageCmd;
+pub use struct_fields_usage::StructFieldsUsageCmd;
 pub use struct_usage::StructUsageCmd;
+pub use suggest_facade::SuggestFacadeCmd;
+pub use summary::SummaryCmd;
+pub use supervision_tree::SupervisionTreeCmd;
+pub use test_surface::TestSurfaceCmd;
 pub use trace::TraceCmd;
+pub use trends::TrendsCmd;
+pub use type_usage::TypeUsageCmd;
 pub use unused::UnusedCmd;
+pub use update::UpdateCmd;
+pub use vacuum::VacuumCmd;
 
 use clap::Subcommand;
 use enum_dispatch::enum_dispatch;
 use std::error::Error;
 
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::output::{OutputFormat, Outputable};
@@ -99,14 +214,14 @@ use crate::output::{OutputFormat, Outputable};
 pub trait Execute {
     type Output: Outputable;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>>;
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>>;
 }
 
 /// Trait for commands that can be executed and formatted.
 /// Auto-implemented for all Command variants via enum_dispatch.
 #[enum_dispatch]
 pub trait CommandRunner {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>>;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>>;
 }
 
 #[derive(Subcommand, Debug)]
@@ -118,12 +233,21 @@ pub enum Command {
     /// Import a call graph JSON file into the database
     Import(ImportCmd),
 
+    /// Re-extract and import only the files that changed
+    Update(UpdateCmd),
+
+    /// Extract a source tree with the configured extractors and import the result
+    Extract(ExtractCmd),
+
     /// Browse all definitions in a module or file
     BrowseModule(BrowseModuleCmd),
 
     /// Search for modules or functions by name pattern
     Search(SearchCmd),
 
+    /// Compute vector embeddings of functions for `search --semantic`
+    IndexEmbeddings(IndexEmbeddingsCmd),
+
     /// Find where a function is defined (file:line_start:line_end)
     Location(LocationCmd),
 
@@ -154,9 +278,21 @@ pub enum Command {
     /// Trace call chains backwards - who calls the callers of a target
     ReverseTrace(ReverseTraceCmd),
 
+    /// Map tests to the functions they exercise
+    TestSurface(TestSurfaceCmd),
+
     /// Find a call path between two functions
     Path(PathCmd),
 
+    /// List every definition, call site, spec and type a rename has to touch
+    RenameImpact(RenameImpactCmd),
+
+    /// Bundle a module or function's definition, top callers and top callees
+    Explore(ExploreCmd),
+
+    /// Rank the functions and files relevant to a task, for an LLM context window
+    Context(ContextCmd),
+
     /// Find functions accepting a specific type pattern
     Accepts(AcceptsCmd),
 
@@ -166,44 +302,286 @@ pub enum Command {
     /// Find functions that accept or return a specific type pattern
     StructUsage(StructUsageCmd),
 
+    /// Show which functions read or write each field of a struct
+    StructFieldsUsage(StructFieldsUsageCmd),
+
+    /// Find every @spec, @callback and @type referencing a type
+    TypeUsage(TypeUsageCmd),
+
+    /// Report functions lacking a @spec, per module
+    SpecCoverage(SpecCoverageCmd),
+
+    /// Check the modules implementing a behaviour for missing or extra callbacks
+    Callbacks(CallbacksCmd),
+
     /// Show what modules a given module depends on (outgoing module dependencies)
     DependsOn(DependsOnCmd),
 
     /// Show what modules depend on a given module (incoming module dependencies)
     DependedBy(DependedByCmd),
 
+    /// Show which external library functions the project calls, and from where
+    DepsSurface(DepsSurfaceCmd),
+
     /// Find functions that are never called
     Unused(UnusedCmd),
 
+    /// List call sites of deprecated functions, grouped by caller module
+    DeprecatedUsage(DeprecatedUsageCmd),
+
+    /// Find framework entry points (controllers, LiveViews, OTP callbacks, workers, tasks)
+    EntryPoints(EntryPointsCmd),
+
     /// Find functions with identical or near-identical implementations
     Duplicates(DuplicatesCmd),
 
+    /// Find the functions structurally most similar to a given function
+    SimilarFunctions(SimilarFunctionsCmd),
+
     /// Find functions with the most incoming/outgoing calls
     Hotspots(HotspotsCmd),
 
     /// Find boundary modules - modules with high fan-in but low fan-out
     Boundaries(BoundariesCmd),
 
+    /// Suggest the public interface of a namespace from how other modules use it
+    SuggestFacade(SuggestFacadeCmd),
+
+    /// Compute afferent/efferent coupling, instability and abstractness per module
+    Coupling(CouplingCmd),
+
+    /// Rank functions by PageRank, betweenness or degree centrality
+    Centrality(CentralityCmd),
+
+    /// Export per-function fan-in, fan-out, size and complexity for scatter plots
+    FanInFanOut(FanInFanOutCmd),
+
+    /// Detect module communities from call coupling and compare them with namespaces
+    Communities(CommunitiesCmd),
+
+    /// Build a module-by-module dependency structure matrix (DSM)
+    Matrix(MatrixCmd),
+
+    /// Generate a PlantUML diagram of namespace clusters and their dependencies
+    Diagram(DiagramCmd),
+
+    /// Show the OTP supervision tree with each process module's call-graph footprint
+    SupervisionTree(SupervisionTreeCmd),
+
+    /// Summarize umbrella apps with their size and cross-app dependencies
+    Apps(AppsCmd),
+
     /// Find god modules - modules with high function count and high connectivity
     GodModules(GodModulesCmd),
 
+    /// Show what moving functions out of a module into a new one would change
+    ExtractModule(ExtractModuleCmd),
+
     /// Find large functions that may need refactoring
     LargeFunctions(LargeFunctionsCmd),
 
     /// Find functions with many pattern-matched heads
     ManyClauses(ManyClausesCmd),
 
+    /// Check call edges against architectural layer rules
+    Layers(LayersCmd),
+
+    /// Evaluate call graph policies from the config file (CI gate)
+    Check(CheckCmd),
+
+    /// One-page architectural overview of a project
+    Summary(SummaryCmd),
+
+    /// Render a multi-section architecture report as Markdown or HTML
+    Report(ReportCmd),
+
+    /// Export function definitions and calls as a ctags or cscope index
+    Export(ExportCmd),
+
+    /// Back up the database to a file
+    Backup(BackupCmd),
+
+    /// Restore the database from a backup file
+    Restore(RestoreCmd),
+
+    /// Run the queries listed in a file and combine their results into one document
+    Batch(BatchCmd),
+
+    /// Save, list and run named queries
+    Query(QueryCmd),
+
+    /// Compact the database and drop orphaned rows
+    Vacuum(VacuumCmd),
+
+    /// Keep the database open and answer queries over a unix socket
+    Daemon(DaemonCmd),
+
+    /// List or rebuild the secondary indexes of the database
+    Indexes(IndexesCmd),
+
+    /// Compare the stored relations with the schema of this version
+    Schema(SchemaCmd),
+
+    /// Show how project metrics evolve across imports
+    Trends(TrendsCmd),
+
+    /// List old complex functions and new heavily used ones
+    Age(AgeCmd),
+
+    /// Review the functions changed by a pull request
+    Pr(PrCmd),
+
+    /// Attach a note, TODO or deprecation marker to a module or function
+    Annotate(AnnotateCmd),
+
+    /// List the annotations attached with `annotate`
+    Annotations(AnnotationsCmd),
+
     /// Catch-all for unknown commands
     #[command(external_subcommand)]
     Unknown(Vec<String>),
 }
 
+impl Command {
+    /// The common arguments of commands querying a project, if this is one
+    pub fn common(&self) -> Option<&CommonArgs> {
+        match self {
+            Command::Accepts(cmd) => Some(&cmd.common),
+            Command::Age(cmd) => Some(&cmd.common),
+            Command::Annotations(cmd) => Some(&cmd.common),
+            Command::Boundaries(cmd) => Some(&cmd.common),
+            Command::BrowseModule(cmd) => Some(&cmd.common),
+            Command::CallsFrom(cmd) => Some(&cmd.common),
+            Command::CallsTo(cmd) => Some(&cmd.common),
+            Command::Centrality(cmd) => Some(&cmd.common),
+            Command::Clusters(cmd) => Some(&cmd.common),
+            Command::Communities(cmd) => Some(&cmd.common),
+            Command::Complexity(cmd) => Some(&cmd.common),
+            Command::Coupling(cmd) => Some(&cmd.common),
+            Command::Cycles(cmd) => Some(&cmd.common),
+            Command::DependedBy(cmd) => Some(&cmd.common),
+            Command::DependsOn(cmd) => Some(&cmd.common),
+            Command::DeprecatedUsage(cmd) => Some(&cmd.common),
+            Command::DepsSurface(cmd) => Some(&cmd.common),
+            Command::Diagram(cmd) => Some(&cmd.common),
+            Command::Duplicates(cmd) => Some(&cmd.common),
+            Command::EntryPoints(cmd) => Some(&cmd.common),
+            Command::FanInFanOut(cmd) => Some(&cmd.common),
+            Command::Function(cmd) => Some(&cmd.common),
+            Command::GodModules(cmd) => Some(&cmd.common),
+            Command::Hotspots(cmd) => Some(&cmd.common),
+            Command::LargeFunctions(cmd) => Some(&cmd.common),
+            Command::Layers(cmd) => Some(&cmd.common),
+            Command::Location(cmd) => Some(&cmd.common),
+            Command::ManyClauses(cmd) => Some(&cmd.common),
+            Command::Matrix(cmd) => Some(&cmd.common),
+            Command::Returns(cmd) => Some(&cmd.common),
+            Command::ReverseTrace(cmd) => Some(&cmd.common),
+            Command::Search(cmd) => Some(&cmd.common),
+            Command::SpecCoverage(cmd) => Some(&cmd.common),
+            Command::StructFieldsUsage(cmd) => Some(&cmd.common),
+            Command::StructUsage(cmd) => Some(&cmd.common),
+            Command::TestSurface(cmd) => Some(&cmd.common),
+            Command::Trace(cmd) => Some(&cmd.common),
+            Command::TypeUsage(cmd) => Some(&cmd.common),
+            Command::Unused(cmd) => Some(&cmd.common),
+            _ => None,
+        }
+    }
+
+    /// Whether the output depends only on the database, the arguments and the
+    /// default config file, so `--cache` may reuse it.
+    ///
+    /// Commands that write files, read other files (baselines, explicit
+    /// --config paths, --stdin targets, git history for --blame) or change the
+    /// database are always run.
+    pub fn cacheable(&self) -> bool {
+        let default_config = |path: &std::path::Path| path == std::path::Path::new(crate::config::DEFAULT_CONFIG_PATH);
+        match self {
+            Command::Complexity(cmd) => cmd.baseline.baseline.is_none() && !cmd.blame,
+            Command::Cycles(cmd) => cmd.baseline.baseline.is_none(),
+            Command::Duplicates(cmd) => cmd.baseline.baseline.is_none(),
+            Command::Unused(cmd) => cmd.baseline.baseline.is_none() && !cmd.blame,
+            Command::LargeFunctions(cmd) => !cmd.blame,
+            Command::EntryPoints(cmd) => default_config(&cmd.config),
+            Command::Layers(cmd) => default_config(&cmd.config),
+            Command::CallsTo(cmd) => !cmd.stdin,
+            Command::Location(cmd) => !cmd.stdin,
+            Command::Function(cmd) => !cmd.stdin,
+            Command::Setup(_)
+            | Command::Import(_)
+            | Command::Update(_)
+            | Command::Extract(_)
+            | Command::IndexEmbeddings(_)
+            | Command::Check(_)
+            | Command::Export(_)
+            | Command::Backup(_)
+            | Command::Restore(_)
+            | Command::Vacuum(_)
+            | Command::Daemon(_)
+            | Command::Indexes(_)
+            | Command::Schema(_)
+            | Command::Annotate(_)
+            | Command::Describe(_)
+            // Ages are relative to the time of the query
+            | Command::Age(_)
+            // Reads a diff file or the git repository
+            | Command::Pr(_)
+            | Command::Batch(_)
+            | Command::Query(_)
+            | Command::Unknown(_) => false,
+            _ => true,
+        }
+    }
+
+    /// Whether `batch` may run the command: it leaves the database unchanged,
+    /// reads nothing from stdin and needs nothing main sets up beyond the
+    /// connection.
+    pub fn batchable(&self) -> bool {
+        let reads_stdin = match self {
+            Command::CallsTo(cmd) => cmd.stdin,
+            Command::Location(cmd) => cmd.stdin,
+            Command::Function(cmd) => cmd.stdin,
+            Command::Pr(cmd) => cmd.diff.as_deref() == Some(std::path::Path::new("-")),
+            _ => false,
+        };
+        !self.invalidates_cache()
+            && !reads_stdin
+            && !matches!(
+                self,
+                Command::Setup(_)
+                    | Command::Vacuum(_)
+                    | Command::Daemon(_)
+                    | Command::Backup(_)
+                    // --rebuild drops and recreates indexes
+                    | Command::Indexes(_)
+                    | Command::Batch(_)
+                    | Command::Query(_)
+                    | Command::Schema(_)
+            )
+    }
+
+    /// Whether the command changes data that cached outputs were computed from
+    pub fn invalidates_cache(&self) -> bool {
+        matches!(
+            self,
+            Command::Import(ImportCmd { dry_run: false, .. })
+                | Command::Update(_)
+                | Command::Extract(ExtractCmd { dry_run: false, .. })
+                | Command::IndexEmbeddings(_)
+                | Command::Restore(_)
+                | Command::Vacuum(_)
+                | Command::Annotate(_)
+        )
+    }
+}
+
 // CommandRunner implementations are provided by each command's module.
 // The enum_dispatch macro automatically generates dispatch logic for the Command enum.
 
 // Special handling for Unknown variant - not a real command
 impl CommandRunner for Vec<String> {
-    fn run(self, _db: &DbInstance, _format: OutputFormat) -> Result<String, Box<dyn Error>> {
+    fn run(self, _db: &DbInstance, _format: OutputFormat, _settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
         Err(format!("Unknown command: {}", self.first().unwrap_or(&String::new())).into())
     }
 }
@@ -0,0 +1,75 @@
+//! Running the independent subqueries of a command concurrently.
+//!
+//! Commands such as `summary` and `browse-module` assemble their result from
+//! queries that do not depend on each other. [`scope`] runs each on its own
+//! scoped thread, so storage backends serving reads concurrently answer them
+//! in the time of the slowest one instead of their sum.
+
+use std::error::Error;
+use std::panic;
+use std::thread::{Scope, ScopedJoinHandle};
+
+/// Error of a task, as its message: the query errors are not `Send`
+type TaskError = Box<dyn Error + Send + Sync>;
+
+/// Spawns the tasks of a [`scope`]
+pub struct Tasks<'scope, 'env> {
+    scope: &'scope Scope<'scope, 'env>,
+}
+
+/// A running task; [`Task::join`] waits for its result
+pub struct Task<'scope, T> {
+    handle: ScopedJoinHandle<'scope, Result<T, TaskError>>,
+}
+
+/// Run `f`, which spawns tasks and joins them.
+///
+/// Tasks left unjoined are waited for before `scope` returns.
+pub fn scope<'env, R>(f: impl for<'scope> FnOnce(&Tasks<'scope, 'env>) -> R) -> R {
+    std::thread::scope(|scope| f(&Tasks { scope }))
+}
+
+impl<'scope, 'env> Tasks<'scope, 'env> {
+    /// Start `task` on a new thread
+    pub fn spawn<T: Send + 'scope>(
+        &self,
+        task: impl FnOnce() -> Result<T, Box<dyn Error>> + Send + 'scope,
+    ) -> Task<'scope, T> {
+        let handle = self.scope.spawn(move || task().map_err(|e| TaskError::from(e.to_string())));
+        Task { handle }
+    }
+}
+
+impl<T> Task<'_, T> {
+    /// Wait for the task, passing on its error or panic
+    pub fn join(self) -> Result<T, Box<dyn Error>> {
+        match self.handle.join() {
+            Ok(result) => result.map_err(|e| e as Box<dyn Error>),
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_joins_results_in_order() {
+        let (a, b) = scope(|tasks| {
+            let a = tasks.spawn(|| Ok(1 + 1));
+            let b = tasks.spawn(|| Ok("two".to_string()));
+            (a.join().unwrap(), b.join().unwrap())
+        });
+        assert_eq!((a, b), (2, "two".to_string()));
+    }
+
+    #[test]
+    fn test_tasks_pass_on_errors() {
+        let error = scope(|tasks| {
+            let failed = tasks.spawn(|| -> Result<(), Box<dyn Error>> { Err("Query failed".into()) });
+            failed.join().unwrap_err().to_string()
+        });
+        assert_eq!(error, "Query failed");
+    }
+}
@@ -58,6 +58,97 @@ mod tests {
         expected: Some(2),
     }
 
+    crate::cli_option_test! {
+        command: "path",
+        variant: Path,
+        test_name: test_with_strategy,
+        args: [
+            "--from-module", "MyApp",
+            "--from-function", "foo",
+            "--to-module", "MyApp",
+            "--to-function", "bar",
+            "--strategy", "k-shortest"
+        ],
+        field: strategy,
+        expected: super::super::PathStrategy::KShortest,
+    }
+
+    crate::cli_option_test! {
+        command: "path",
+        variant: Path,
+        test_name: test_with_bidirectional_strategy,
+        args: [
+            "--from-module", "MyApp",
+            "--from-function", "foo",
+            "--to-module", "MyApp",
+            "--to-function", "bar",
+            "--strategy", "bidirectional"
+        ],
+        field: strategy,
+        expected: super::super::PathStrategy::Bidirectional,
+    }
+
+    crate::cli_option_test! {
+        command: "path",
+        variant: Path,
+        test_name: test_with_weighted,
+        args: [
+            "--from-module", "MyApp",
+            "--from-function", "foo",
+            "--to-module", "MyApp",
+            "--to-function", "bar",
+            "--strategy", "shortest",
+            "--weighted"
+        ],
+        field: weighted,
+        expected: true,
+    }
+
+    crate::cli_option_test! {
+        command: "path",
+        variant: Path,
+        test_name: test_with_min_weight,
+        args: [
+            "--from-module", "MyApp",
+            "--from-function", "foo",
+            "--to-module", "MyApp",
+            "--to-function", "bar",
+            "--min-weight", "3"
+        ],
+        field: min_weight,
+        expected: 3,
+    }
+
+    crate::cli_option_test! {
+        command: "path",
+        variant: Path,
+        test_name: test_with_memory_engine,
+        args: [
+            "--from-module", "MyApp",
+            "--from-function", "foo",
+            "--to-module", "MyApp",
+            "--to-function", "bar",
+            "--engine", "memory"
+        ],
+        field: engine,
+        expected: crate::commands::Engine::Memory,
+    }
+
+    crate::cli_option_test! {
+        command: "path",
+        variant: Path,
+        test_name: test_with_include_messages,
+        args: [
+            "--from-module", "MyApp",
+            "--from-function", "foo",
+            "--to-module", "MyApp",
+            "--to-function", "bar",
+            "--include-messages"
+        ],
+        field: include_messages,
+        expected: true,
+    }
+
     // =========================================================================
     // Edge case tests (multiple required args, depth validation)
     // =========================================================================
@@ -104,6 +195,8 @@ mod tests {
                 assert_eq!(cmd.to_function, "get");
                 assert_eq!(cmd.depth, 10); // default
                 assert_eq!(cmd.limit, 100); // default
+                assert_eq!(cmd.strategy, super::super::PathStrategy::All); // default
+                assert!(!cmd.weighted);
             }
             _ => panic!("Expected Path command"),
         }
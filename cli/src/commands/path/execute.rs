@@ -1,48 +1,37 @@
 use std::error::Error;
 
-use serde::Serialize;
-
 use super::PathCmd;
+use crate::api::{path, PathRequest};
 use crate::commands::Execute;
-use db::queries::path::{find_paths, CallPath};
+use db::query_builders::QuerySettings;
+
+pub use crate::api::PathResult;
 
-/// Result of the path command execution
-#[derive(Debug, Default, Serialize)]
-pub struct PathResult {
-    pub from_module: String,
-    pub from_function: String,
-    pub to_module: String,
-    pub to_function: String,
-    pub max_depth: u32,
-    pub paths: Vec<CallPath>,
+impl From<PathCmd> for PathRequest {
+    fn from(cmd: PathCmd) -> Self {
+        Self {
+            from_module: cmd.from_module,
+            from_function: cmd.from_function,
+            from_arity: cmd.from_arity,
+            to_module: cmd.to_module,
+            to_function: cmd.to_function,
+            to_arity: cmd.to_arity,
+            project: cmd.project,
+            depth: cmd.depth,
+            strategy: cmd.strategy.into(),
+            weighted: cmd.weighted,
+            min_weight: cmd.min_weight,
+            engine: cmd.engine.into(),
+            include_messages: cmd.include_messages,
+            limit: cmd.limit,
+        }
+    }
 }
 
 impl Execute for PathCmd {
     type Output = PathResult;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
-        let mut result = PathResult {
-            from_module: self.from_module.clone(),
-            from_function: self.from_function.clone(),
-            to_module: self.to_module.clone(),
-            to_function: self.to_function.clone(),
-            max_depth: self.depth,
-            ..Default::default()
-        };
-
-        result.paths = find_paths(
-            db,
-            &self.from_module,
-            &self.from_function,
-            self.from_arity,
-            &self.to_module,
-            &self.to_function,
-            self.to_arity,
-            &self.project,
-            self.depth,
-            self.limit,
-        )?;
-
-        Ok(result)
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        path(db, self.into())
     }
-}
\ No newline at end of file
+}
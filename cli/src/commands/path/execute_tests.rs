@@ -2,7 +2,9 @@
 
 #[cfg(test)]
 mod tests {
-    use super::super::PathCmd;
+    use super::super::{PathCmd, PathStrategy};
+    use crate::commands::Engine;
+    use db::query_builders::QuerySettings;
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -28,6 +30,11 @@ mod tests {
             to_arity: None,
             project: "test_project".to_string(),
             depth: 10,
+            strategy: PathStrategy::All,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
             limit: 10,
         },
         assertions: |result| {
@@ -51,6 +58,11 @@ mod tests {
             to_arity: None,
             project: "test_project".to_string(),
             depth: 10,
+            strategy: PathStrategy::All,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
             limit: 10,
         },
         assertions: |result| {
@@ -73,6 +85,11 @@ mod tests {
             to_arity: None,
             project: "test_project".to_string(),
             depth: 10,
+            strategy: PathStrategy::All,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
             limit: 10,
         },
         assertions: |result| {
@@ -98,6 +115,11 @@ mod tests {
             to_arity: None,
             project: "test_project".to_string(),
             depth: 10,
+            strategy: PathStrategy::All,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
             limit: 10,
         },
         assertions: |result| {
@@ -121,6 +143,11 @@ mod tests {
             to_arity: None,
             project: "test_project".to_string(),
             depth: 10,
+            strategy: PathStrategy::All,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
             limit: 10,
         },
         assertions: |result| {
@@ -143,11 +170,158 @@ mod tests {
             to_arity: None,
             project: "test_project".to_string(),
             depth: 10,
+            strategy: PathStrategy::All,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
             limit: 10,
         },
         empty_field: paths,
     }
 
+    // =========================================================================
+    // Strategy tests
+    // =========================================================================
+
+    // Both get_user call sites collapse into one edge, so only one distinct path
+    crate::execute_test! {
+        test_name: test_path_shortest,
+        fixture: populated_db,
+        cmd: PathCmd {
+            from_module: "MyApp.Controller".to_string(),
+            from_function: "show".to_string(),
+            from_arity: None,
+            to_module: "MyApp.Repo".to_string(),
+            to_function: "get".to_string(),
+            to_arity: None,
+            project: "test_project".to_string(),
+            depth: 10,
+            strategy: PathStrategy::Shortest,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
+            limit: 10,
+        },
+        assertions: |result| {
+            assert_eq!(result.strategy, "shortest");
+            assert_eq!(result.paths.len(), 1);
+            assert_eq!(result.paths[0].steps.len(), 2);
+            assert_eq!(result.paths[0].steps[1].depth, 2);
+            assert!(result.paths[0].cost.is_none());
+        },
+    }
+
+    // Controller.create -> Service.process -> fetch -> do_fetch -> Repo.get (4 hops)
+    crate::execute_test! {
+        test_name: test_path_k_shortest_weighted,
+        fixture: populated_db,
+        cmd: PathCmd {
+            from_module: "MyApp.Controller".to_string(),
+            from_function: "create".to_string(),
+            from_arity: None,
+            to_module: "MyApp.Repo".to_string(),
+            to_function: "get".to_string(),
+            to_arity: None,
+            project: "test_project".to_string(),
+            depth: 10,
+            strategy: PathStrategy::KShortest,
+            weighted: true,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
+            limit: 5,
+        },
+        assertions: |result| {
+            assert_eq!(result.paths.len(), 1);
+            assert_eq!(result.paths[0].steps.len(), 4);
+            assert_eq!(result.paths[0].steps[3].caller_function, "do_fetch");
+            assert_eq!(result.paths[0].cost, Some(4.0));
+        },
+    }
+
+    // Same 4-hop path as above, found by meeting in the middle
+    crate::execute_test! {
+        test_name: test_path_bidirectional,
+        fixture: populated_db,
+        cmd: PathCmd {
+            from_module: "MyApp.Controller".to_string(),
+            from_function: "create".to_string(),
+            from_arity: None,
+            to_module: "MyApp.Repo".to_string(),
+            to_function: "get".to_string(),
+            to_arity: None,
+            project: "test_project".to_string(),
+            depth: 10,
+            strategy: PathStrategy::Bidirectional,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
+            limit: 10,
+        },
+        assertions: |result| {
+            assert_eq!(result.strategy, "bidirectional");
+            assert_eq!(result.paths.len(), 1);
+            let steps = &result.paths[0].steps;
+            assert_eq!(steps.len(), 4);
+            assert_eq!(steps[0].caller_function, "create");
+            assert_eq!(steps[3].caller_function, "do_fetch");
+            assert_eq!(steps[3].callee_module, "MyApp.Repo");
+            assert_eq!(steps[3].depth, 4);
+            assert!(result.paths[0].cost.is_none());
+        },
+    }
+
+    // Bidirectional search over the in-memory adjacency index
+    crate::execute_test! {
+        test_name: test_path_bidirectional_memory_engine,
+        fixture: populated_db,
+        cmd: PathCmd {
+            from_module: "MyApp.Controller".to_string(),
+            from_function: "create".to_string(),
+            from_arity: None,
+            to_module: "MyApp.Repo".to_string(),
+            to_function: "get".to_string(),
+            to_arity: None,
+            project: "test_project".to_string(),
+            depth: 10,
+            strategy: PathStrategy::Bidirectional,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Memory,
+            include_messages: false,
+            limit: 10,
+        },
+        assertions: |result| {
+            assert_eq!(result.paths.len(), 1);
+            assert_eq!(result.paths[0].steps.len(), 4);
+        },
+    }
+
+    #[rstest]
+    fn test_path_weighted_requires_ranked_strategy(populated_db: db::DbInstance) {
+        use crate::commands::Execute;
+        let cmd = PathCmd {
+            from_module: "MyApp.Controller".to_string(),
+            from_function: "index".to_string(),
+            from_arity: None,
+            to_module: "MyApp.Repo".to_string(),
+            to_function: "all".to_string(),
+            to_arity: None,
+            project: "test_project".to_string(),
+            depth: 10,
+            strategy: PathStrategy::All,
+            weighted: true,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
+            limit: 10,
+        };
+        assert!(cmd.execute(&populated_db, &QuerySettings::default()).is_err());
+    }
+
     // =========================================================================
     // No match / empty result tests
     // =========================================================================
@@ -165,6 +339,56 @@ mod tests {
             to_arity: None,
             project: "test_project".to_string(),
             depth: 10,
+            strategy: PathStrategy::All,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
+            limit: 10,
+        },
+        empty_field: paths,
+    }
+
+    crate::execute_no_match_test! {
+        test_name: test_path_bidirectional_no_path_exists,
+        fixture: populated_db,
+        cmd: PathCmd {
+            from_module: "MyApp.Repo".to_string(),
+            from_function: "get".to_string(),
+            from_arity: None,
+            to_module: "MyApp.Controller".to_string(),
+            to_function: "index".to_string(),
+            to_arity: None,
+            project: "test_project".to_string(),
+            depth: 10,
+            strategy: PathStrategy::Bidirectional,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
+            limit: 10,
+        },
+        empty_field: paths,
+    }
+
+    // The 4-hop path does not fit in 3
+    crate::execute_no_match_test! {
+        test_name: test_path_bidirectional_depth_limit,
+        fixture: populated_db,
+        cmd: PathCmd {
+            from_module: "MyApp.Controller".to_string(),
+            from_function: "create".to_string(),
+            from_arity: None,
+            to_module: "MyApp.Repo".to_string(),
+            to_function: "get".to_string(),
+            to_arity: None,
+            project: "test_project".to_string(),
+            depth: 3,
+            strategy: PathStrategy::Bidirectional,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
             limit: 10,
         },
         empty_field: paths,
@@ -183,6 +407,11 @@ mod tests {
             to_arity: None,
             project: "test_project".to_string(),
             depth: 1,
+            strategy: PathStrategy::All,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
             limit: 10,
         },
         empty_field: paths,
@@ -203,6 +432,11 @@ mod tests {
             to_arity: None,
             project: "test_project".to_string(),
             depth: 10,
+            strategy: PathStrategy::All,
+            weighted: false,
+            min_weight: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
             limit: 10,
         },
     }
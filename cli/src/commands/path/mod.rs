@@ -6,12 +6,39 @@ mod output_tests;
 
 use std::error::Error;
 
-use clap::Args;
+use clap::{Args, ValueEnum};
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
-use crate::commands::{CommandRunner, Execute};
+use crate::commands::{CommandRunner, Engine, Execute};
 use crate::output::{OutputFormat, Outputable};
 
+/// How paths between the two functions are selected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum PathStrategy {
+    /// Every path found up to the depth at which the target is first reached
+    #[default]
+    All,
+    /// The single cheapest path
+    Shortest,
+    /// Up to --limit loopless paths, cheapest first
+    KShortest,
+    /// The single path with the fewest hops, searching from both ends
+    /// (loads far fewer edges on deep searches)
+    Bidirectional,
+}
+
+impl From<PathStrategy> for crate::api::PathStrategy {
+    fn from(strategy: PathStrategy) -> Self {
+        match strategy {
+            PathStrategy::All => Self::All,
+            PathStrategy::Shortest => Self::Shortest,
+            PathStrategy::KShortest => Self::KShortest,
+            PathStrategy::Bidirectional => Self::Bidirectional,
+        }
+    }
+}
+
 /// Find a call path between two functions
 #[derive(Args, Debug)]
 #[command(after_help = "\
@@ -19,7 +46,17 @@ Examples:
   code_search path --from-module MyApp.Web --from-function index \\
                    --to-module MyApp.Repo --to-function get
   code_search path --from-module MyApp.API --from-function create \\
-                   --to-module Ecto.Repo --to-function insert --depth 15")]
+                   --to-module Ecto.Repo --to-function insert --depth 15
+  code_search path --from-module MyApp.Web --from-function index \\
+                   --to-module MyApp.Repo --to-function get --strategy k-shortest -l 5
+  code_search path --from-module MyApp.Web --from-function index \\
+                   --to-module MyApp.Repo --to-function get --strategy shortest --weighted
+  code_search path --from-module MyApp.Web --from-function index \\
+                   --to-module MyApp.Repo --to-function get --min-weight 2
+  code_search path --from-module MyApp.Web --from-function index \\
+                   --to-module MyApp.Repo --to-function get --strategy bidirectional --depth 20
+  code_search path --from-module MyApp.Web --from-function create \\
+                   --to-module MyApp.Mailer --to-function deliver --include-messages")]
 pub struct PathCmd {
     /// Source module name
     #[arg(long)]
@@ -53,14 +90,35 @@ pub struct PathCmd {
     #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..=20))]
     pub depth: u32,
 
+    /// Path selection strategy
+    #[arg(long, value_enum, default_value_t = PathStrategy::All)]
+    pub strategy: PathStrategy,
+
+    /// Weight calls by call-site count, preferring heavily used call relationships
+    /// (shortest and k-shortest only)
+    #[arg(long)]
+    pub weighted: bool,
+
+    /// Only follow calls whose caller calls the callee from at least this many call sites
+    #[arg(long, default_value = "1")]
+    pub min_weight: i64,
+
+    /// Traversal engine (memory reuses an adjacency index across traversals)
+    #[arg(long, value_enum, default_value_t = Engine::Datalog)]
+    pub engine: Engine,
+
+    /// Also follow message edges (GenServer calls/casts, PubSub broadcasts)
+    #[arg(long, default_value_t = false)]
+    pub include_messages: bool,
+
     /// Maximum number of paths to return (1-1000)
     #[arg(short, long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..=1000))]
     pub limit: u32,
 }
 
 impl CommandRunner for PathCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
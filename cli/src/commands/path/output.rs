@@ -13,20 +13,25 @@ impl Outputable for PathResult {
         );
         lines.push(header);
         lines.push(format!("Max depth: {}", self.max_depth));
+        lines.push(format!("Strategy: {}", self.strategy));
         lines.push(String::new());
 
         if !self.paths.is_empty() {
             lines.push(format!("Found {} path(s):", self.paths.len()));
             for (i, path) in self.paths.iter().enumerate() {
                 lines.push(String::new());
-                lines.push(format!("Path {}:", i + 1));
+                match path.cost {
+                    Some(cost) => lines.push(format!("Path {} (cost {:.2}):", i + 1, cost)),
+                    None => lines.push(format!("Path {}:", i + 1)),
+                }
                 for step in &path.steps {
                     let indent = "  ".repeat(step.depth as usize);
                     let caller = format!("{}.{}", step.caller_module, step.caller_function);
                     let callee = format!("{}.{}/{}", step.callee_module, step.callee_function, step.callee_arity);
+                    let weight = if step.weight > 1 { format!(" ×{}", step.weight) } else { String::new() };
                     lines.push(format!(
-                        "{}[{}] {} ({}:{}) -> {}",
-                        indent, step.depth, caller, step.file, step.line, callee
+                        "{}[{}] {} ({}:{}) -> {}{}",
+                        indent, step.depth, caller, step.file, step.line, callee, weight
                     ));
                 }
             }
@@ -13,18 +13,20 @@ mod tests {
     const EMPTY_TABLE: &str = "\
 Path from: MyApp.Controller.index to: MyApp.Repo.get
 Max depth: 10
+Strategy: all
 
 No path found.";
 
     const SINGLE_PATH_TABLE: &str = "\
 Path from: MyApp.Controller.index to: MyApp.Repo.get
 Max depth: 10
+Strategy: all
 
 Found 1 path(s):
 
 Path 1:
   [1] MyApp.Controller.index (lib/controller.ex:7) -> MyApp.Service.fetch/1
-    [2] MyApp.Service.fetch (lib/service.ex:15) -> MyApp.Repo.get/2";
+    [2] MyApp.Service.fetch (lib/service.ex:15) -> MyApp.Repo.get/2 ×3";
 
 
     // =========================================================================
@@ -39,6 +41,7 @@ Path 1:
             to_module: "MyApp.Repo".to_string(),
             to_function: "get".to_string(),
             max_depth: 10,
+            strategy: "all".to_string(),
             paths: vec![],
         }
     }
@@ -51,6 +54,7 @@ Path 1:
             to_module: "MyApp.Repo".to_string(),
             to_function: "get".to_string(),
             max_depth: 10,
+            strategy: "all".to_string(),
             paths: vec![CallPath {
                 steps: vec![
                     PathStep {
@@ -62,6 +66,7 @@ Path 1:
                         callee_arity: 1,
                         file: "lib/controller.ex".to_string(),
                         line: 7,
+                        weight: 1,
                     },
                     PathStep {
                         depth: 2,
@@ -72,16 +77,41 @@ Path 1:
                         callee_arity: 2,
                         file: "lib/service.ex".to_string(),
                         line: 15,
+                        weight: 3,
                     },
                 ],
+                cost: None,
             }],
         }
     }
 
+    #[fixture]
+    fn weighted_path_result() -> PathResult {
+        let mut result = single_path_result();
+        result.strategy = "k-shortest".to_string();
+        result.paths[0].cost = Some(0.5);
+        result
+    }
+
     // =========================================================================
     // Tests
     // =========================================================================
 
+    #[rstest]
+    fn test_to_table_weighted_path(weighted_path_result: PathResult) {
+        use crate::output::Outputable;
+        let output = weighted_path_result.to_table();
+        assert!(output.contains("Strategy: k-shortest"));
+        assert!(output.contains("Path 1 (cost 0.50):"));
+    }
+
+    #[rstest]
+    fn test_format_json_weighted_path(weighted_path_result: PathResult) {
+        let json = serde_json::to_string(&weighted_path_result).unwrap();
+        assert!(json.contains("\"cost\":0.5"));
+        assert!(json.contains("\"strategy\":\"k-shortest\""));
+    }
+
     crate::output_table_test! {
         test_name: test_to_table_empty,
         fixture: empty_result,
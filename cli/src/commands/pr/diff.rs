@@ -0,0 +1,199 @@
+//! Changed lines of a unified diff.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Lines a diff changes in one file, numbered as in the new version
+#[derive(Debug, Default, PartialEq)]
+pub struct FileChanges {
+    pub path: String,
+    /// Lines added or rewritten
+    pub added: BTreeSet<i64>,
+    /// Number of lines removed before each line
+    pub removed: BTreeMap<i64, usize>,
+    /// Text of the removed lines
+    pub removed_text: Vec<String>,
+}
+
+impl FileChanges {
+    /// Number of lines changed within `start..=end`.
+    ///
+    /// Removals count when something remains of the range on both sides of
+    /// them, so deleting the lines just before a function leaves it untouched.
+    pub fn changed_in(&self, start: i64, end: i64) -> usize {
+        let added = self.added.range(start..=end).count();
+        let removed: usize = self.removed.range(start + 1..=end).map(|(_, count)| count).sum();
+        added + removed
+    }
+
+    /// Whether `file`, as stored by the import, names the file of this diff.
+    ///
+    /// Imports may store paths relative to a subdirectory of the repository,
+    /// so either path may be a suffix of the other.
+    pub fn matches(&self, file: &str) -> bool {
+        let file = file.trim_start_matches("./");
+        self.path == file
+            || self.path.ends_with(&format!("/{}", file))
+            || file.ends_with(&format!("/{}", self.path))
+    }
+}
+
+/// Changed lines per file of a unified diff, as printed by `git diff` or `diff -u`.
+///
+/// Deleted files are left out, since none of their functions remain.
+pub fn parse_unified_diff(diff: &str) -> Vec<FileChanges> {
+    let mut files: Vec<FileChanges> = Vec::new();
+    let mut current: Option<FileChanges> = None;
+    // Lines left in the current hunk, on the old and new side
+    let mut old_left = 0;
+    let mut new_left = 0;
+    let mut line = 0;
+
+    for text in diff.lines() {
+        if old_left > 0 || new_left > 0 {
+            let Some(file) = current.as_mut() else {
+                old_left = 0;
+                new_left = 0;
+                continue;
+            };
+            match text.as_bytes().first() {
+                Some(b'+') => {
+                    file.added.insert(line);
+                    line += 1;
+                    new_left -= 1;
+                }
+                Some(b'-') => {
+                    *file.removed.entry(line).or_insert(0) += 1;
+                    file.removed_text.push(text[1..].to_string());
+                    old_left -= 1;
+                }
+                Some(b'\\') => {}
+                _ => {
+                    line += 1;
+                    old_left -= 1;
+                    new_left -= 1;
+                }
+            }
+            continue;
+        }
+
+        if let Some(path) = text.strip_prefix("+++ ") {
+            files.extend(current.take());
+            current = new_path(path).map(|path| FileChanges {
+                path,
+                ..FileChanges::default()
+            });
+        } else if let Some((old_count, start, new_count)) = parse_hunk_header(text) {
+            old_left = old_count;
+            new_left = new_count;
+            // A hunk adding no lines names the line before the removal
+            line = if new_count == 0 { start + 1 } else { start };
+        }
+    }
+    files.extend(current);
+    files.retain(|file| !file.added.is_empty() || !file.removed.is_empty());
+    files
+}
+
+/// Path of the new side from a `+++` line, `None` for deleted files
+fn new_path(path: &str) -> Option<String> {
+    // `diff -u` follows the path with a tab and a timestamp
+    let path = path.split('\t').next().unwrap_or(path).trim_end();
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.strip_prefix("b/").unwrap_or(path).to_string())
+}
+
+/// Old line count, new start line and new line count of `@@ -a,b +c,d @@`
+fn parse_hunk_header(text: &str) -> Option<(i64, i64, i64)> {
+    let rest = text.strip_prefix("@@ -")?;
+    let (old, rest) = rest.split_once(" +")?;
+    let (new, _) = rest.split_once(" @@")?;
+    let count = |range: &str| -> Option<(i64, i64)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (_, old_count) = count(old)?;
+    let (new_start, new_count) = count(new)?;
+    Some((old_count, new_start, new_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/lib/my_app/accounts.ex b/lib/my_app/accounts.ex
+index 1111111..2222222 100644
+--- a/lib/my_app/accounts.ex
++++ b/lib/my_app/accounts.ex
+@@ -12 +12,2 @@ defmodule MyApp.Accounts do
+-    Repo.get(User, id)
++    User
++    |> Repo.get(id)
+@@ -40,2 +41,0 @@ defmodule MyApp.Accounts do
+-  def old, do: :ok
+-
+diff --git a/lib/gone.ex b/lib/gone.ex
+deleted file mode 100644
+--- a/lib/gone.ex
++++ /dev/null
+@@ -1,3 +0,0 @@
+-defmodule Gone do
+-end
+-
+";
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let files = parse_unified_diff(DIFF);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "lib/my_app/accounts.ex");
+        assert_eq!(files[0].added, BTreeSet::from([12, 13]));
+        assert_eq!(files[0].removed, BTreeMap::from([(12, 1), (42, 2)]));
+        assert_eq!(files[0].removed_text, ["    Repo.get(User, id)", "  def old, do: :ok", ""]);
+    }
+
+    #[test]
+    fn test_parse_diff_with_context_lines() {
+        let diff = "\
+--- lib/a.ex\t2025-10-01 10:00:00
++++ lib/a.ex\t2025-10-02 10:00:00
+@@ -5,3 +5,4 @@
+ def f(x) do
+-  x + 1
++  x + 2
+ end
++# +++ not a header
+";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files[0].path, "lib/a.ex");
+        assert_eq!(files[0].added, BTreeSet::from([6, 8]));
+        assert_eq!(files[0].removed, BTreeMap::from([(6, 1)]));
+    }
+
+    #[test]
+    fn test_changed_in() {
+        let changes = &parse_unified_diff(DIFF)[0];
+        assert_eq!(changes.changed_in(10, 15), 3);
+        assert_eq!(changes.changed_in(14, 20), 0);
+        // Lines removed right after a function's last line
+        assert_eq!(changes.changed_in(30, 41), 0);
+        assert_eq!(changes.changed_in(30, 42), 2);
+    }
+
+    #[test]
+    fn test_matches_path_suffixes() {
+        let changes = FileChanges {
+            path: "apps/core/lib/a.ex".to_string(),
+            ..FileChanges::default()
+        };
+        assert!(changes.matches("apps/core/lib/a.ex"));
+        assert!(changes.matches("lib/a.ex"));
+        assert!(changes.matches("./lib/a.ex"));
+        assert!(!changes.matches("b/lib/a.ex"));
+        assert!(!changes.matches("ib/a.ex"));
+    }
+}
@@ -0,0 +1,471 @@
+//! Functions changed by a diff, with their callers, complexity and new dependencies.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+
+use serde::Serialize;
+
+use super::diff::{parse_unified_diff, FileChanges};
+use super::PrCmd;
+use crate::commands::Execute;
+use db::queries::calls::{find_calls_for_targets, CallDirection};
+use db::queries::changes::{find_function_clauses, FunctionClause};
+use db::query_builders::{FunctionTarget, QuerySettings};
+use db::types::Call;
+
+/// Upper bound on the calls fetched for the changed functions
+const CALL_LIMIT: u32 = 100_000;
+
+type FunctionKey = (String, String, i64);
+
+/// Result of the pr command
+#[derive(Debug, Serialize)]
+pub struct PrResult {
+    /// Where the diff came from
+    pub source: String,
+    /// Files the diff changes
+    pub files: usize,
+    pub base_project: Option<String>,
+    /// Changed functions, by file and line
+    pub functions: Vec<ChangedFunction>,
+}
+
+/// A function with changed lines
+#[derive(Debug, Serialize)]
+pub struct ChangedFunction {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub file: String,
+    /// First line of the first changed clause
+    pub start_line: i64,
+    /// Last line of the last changed clause
+    pub end_line: i64,
+    /// Lines added or removed within the function
+    pub changed_lines: usize,
+    /// Whether the function did not exist before the change
+    pub new: bool,
+    /// Complexity summed over all clauses
+    pub complexity: i64,
+    /// Change in complexity against the base project, if one was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complexity_delta: Option<i64>,
+    /// Functions the change starts calling, as `Module.function/arity`
+    pub new_dependencies: Vec<String>,
+    /// Number of functions calling this one
+    pub total_callers: usize,
+    /// Calling functions, up to the limit
+    pub callers: Vec<Caller>,
+}
+
+/// A function calling a changed function
+#[derive(Debug, Serialize)]
+pub struct Caller {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub file: String,
+    /// Line of the first call
+    pub line: i64,
+}
+
+impl Execute for PrCmd {
+    type Output = PrResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let (source, diff) = self.read_diff()?;
+        let files = parse_unified_diff(&diff);
+        let clauses = find_function_clauses(db, &self.project)?;
+        let mut functions = changed_functions(&files, &clauses);
+
+        let base_complexity = match &self.base_project {
+            Some(base) => Some(complexity_by_function(&find_function_clauses(db, base)?)),
+            None => None,
+        };
+        if let Some(base_complexity) = &base_complexity {
+            for function in &mut functions {
+                let before = base_complexity.get(&key(function));
+                function.new = before.is_none();
+                function.complexity_delta = before.map(|before| function.complexity - before);
+            }
+        }
+
+        let targets: Vec<FunctionTarget> = functions
+            .iter()
+            .map(|f| FunctionTarget {
+                module: f.module.clone(),
+                function: f.name.clone(),
+                arity: Some(f.arity),
+            })
+            .collect();
+        if targets.is_empty() {
+            return Ok(PrResult {
+                source,
+                files: files.len(),
+                base_project: self.base_project,
+                functions,
+            });
+        }
+
+        let incoming = find_calls_for_targets(
+            db,
+            CallDirection::To,
+            &targets,
+            &[],
+            &self.project,
+            CALL_LIMIT,
+            settings,
+        )?;
+        let mut callers = callers_by_function(&incoming);
+
+        let outgoing = find_calls_for_targets(
+            db,
+            CallDirection::From,
+            &targets,
+            &[],
+            &self.project,
+            CALL_LIMIT,
+            settings,
+        )?;
+        let mut dependencies = match &self.base_project {
+            Some(base) => {
+                let before =
+                    find_calls_for_targets(db, CallDirection::From, &targets, &[], base, CALL_LIMIT, settings)?;
+                dependencies_since_base(&outgoing, &before)
+            }
+            None => dependencies_from_added_lines(&outgoing, &files),
+        };
+
+        for function in &mut functions {
+            let key = key(function);
+            let mut function_callers = callers.remove(&key).unwrap_or_default();
+            function.total_callers = function_callers.len();
+            function_callers.truncate(self.limit as usize);
+            function.callers = function_callers;
+            function.new_dependencies = dependencies.remove(&key).unwrap_or_default().into_iter().collect();
+        }
+
+        Ok(PrResult {
+            source,
+            files: files.len(),
+            base_project: self.base_project,
+            functions,
+        })
+    }
+}
+
+impl PrCmd {
+    /// Description of the diff source and the diff itself
+    fn read_diff(&self) -> Result<(String, String), Box<dyn Error>> {
+        if let Some(path) = &self.diff {
+            if path == Path::new("-") {
+                let mut diff = String::new();
+                std::io::stdin().read_to_string(&mut diff)?;
+                return Ok(("stdin".to_string(), diff));
+            }
+            let diff = std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read diff '{}': {}", path.display(), e))?;
+            return Ok((path.display().to_string(), diff));
+        }
+
+        let base = self.base.as_deref().ok_or("pr needs --diff or --base")?;
+        // A ref starting with a dash would reach git as an option
+        for (flag, reference) in [("--base", base), ("--head", self.head.as_str())] {
+            if reference.starts_with('-') {
+                return Err(format!("{} must name a git ref, got '{}'", flag, reference).into());
+            }
+        }
+        let range = format!("{}...{}", base, self.head);
+        let output = Command::new("git")
+            .args(["diff", "--unified=0", "--no-color", "--no-ext-diff", &range])
+            .output()
+            .map_err(|e| format!("Failed to run git diff: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git diff {} failed: {}",
+                range,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
+        }
+        Ok((format!("git diff {}", range), String::from_utf8_lossy(&output.stdout).into_owned()))
+    }
+}
+
+fn key(function: &ChangedFunction) -> FunctionKey {
+    (function.module.clone(), function.name.clone(), function.arity)
+}
+
+/// Functions with at least one changed clause, in the order of their first changed clause.
+///
+/// A function is new when every line of every clause was added.
+fn changed_functions(files: &[FileChanges], clauses: &[FunctionClause]) -> Vec<ChangedFunction> {
+    let complexity = complexity_by_function(clauses);
+    let mut all_added: HashMap<FunctionKey, bool> = HashMap::new();
+    let mut functions: Vec<ChangedFunction> = Vec::new();
+    let mut index: HashMap<FunctionKey, usize> = HashMap::new();
+
+    for clause in clauses {
+        let key = (clause.module.clone(), clause.name.clone(), clause.arity);
+        let changes = files.iter().find(|f| f.matches(&clause.file));
+        let lines = (clause.end_line - clause.start_line + 1) as usize;
+        let added = changes.is_some_and(|f| f.added.range(clause.start_line..=clause.end_line).count() == lines);
+        *all_added.entry(key.clone()).or_insert(true) &= added;
+
+        let changed = changes.map_or(0, |f| f.changed_in(clause.start_line, clause.end_line));
+        if changed == 0 {
+            continue;
+        }
+        match index.get(&key) {
+            Some(&i) => {
+                let function = &mut functions[i];
+                function.changed_lines += changed;
+                if function.file == clause.file {
+                    function.start_line = function.start_line.min(clause.start_line);
+                    function.end_line = function.end_line.max(clause.end_line);
+                }
+            }
+            None => {
+                index.insert(key.clone(), functions.len());
+                functions.push(ChangedFunction {
+                    module: clause.module.clone(),
+                    name: clause.name.clone(),
+                    arity: clause.arity,
+                    file: clause.file.clone(),
+                    start_line: clause.start_line,
+                    end_line: clause.end_line,
+                    changed_lines: changed,
+                    new: false,
+                    complexity: complexity.get(&key).copied().unwrap_or(0),
+                    complexity_delta: None,
+                    new_dependencies: Vec::new(),
+                    total_callers: 0,
+                    callers: Vec::new(),
+                });
+            }
+        }
+    }
+
+    for function in &mut functions {
+        function.new = all_added.get(&key(function)).copied().unwrap_or(false);
+    }
+    functions
+}
+
+/// Complexity of each function, summed over its clauses
+fn complexity_by_function(clauses: &[FunctionClause]) -> HashMap<FunctionKey, i64> {
+    let mut complexity = HashMap::new();
+    for clause in clauses {
+        *complexity
+            .entry((clause.module.clone(), clause.name.clone(), clause.arity))
+            .or_insert(0) += clause.complexity;
+    }
+    complexity
+}
+
+/// Distinct calling functions of each callee, leaving out recursion
+fn callers_by_function(calls: &[Call]) -> HashMap<FunctionKey, Vec<Caller>> {
+    let mut callers: HashMap<FunctionKey, Vec<Caller>> = HashMap::new();
+    let mut seen: HashSet<(FunctionKey, FunctionKey)> = HashSet::new();
+    for call in calls {
+        let callee = callee_key(call);
+        let caller = caller_key(call);
+        if caller == callee || !seen.insert((callee.clone(), caller.clone())) {
+            continue;
+        }
+        callers.entry(callee).or_default().push(Caller {
+            module: caller.0,
+            name: caller.1,
+            arity: caller.2,
+            file: call.caller.file.as_deref().unwrap_or("").to_string(),
+            line: call.line,
+        });
+    }
+    callers
+}
+
+/// Callees of each caller that the base project's version does not call
+fn dependencies_since_base(after: &[Call], before: &[Call]) -> HashMap<FunctionKey, BTreeSet<String>> {
+    let known: HashSet<(FunctionKey, String)> = before.iter().map(|call| (caller_key(call), callee_label(call))).collect();
+    let mut dependencies: HashMap<FunctionKey, BTreeSet<String>> = HashMap::new();
+    for call in after {
+        let entry = (caller_key(call), callee_label(call));
+        if !known.contains(&entry) {
+            dependencies.entry(entry.0).or_default().insert(entry.1);
+        }
+    }
+    dependencies
+}
+
+/// Callees of each caller that are only called from added lines.
+///
+/// Callees whose name appears on a removed line of the same file count as
+/// called before, so rewriting a line keeps its calls out of the result.
+fn dependencies_from_added_lines(calls: &[Call], files: &[FileChanges]) -> HashMap<FunctionKey, BTreeSet<String>> {
+    let changes_of = |call: &Call| {
+        let file = call.caller.file.as_deref().unwrap_or("");
+        files.iter().find(|f| f.matches(file))
+    };
+    let on_added_line = |call: &Call| changes_of(call).is_some_and(|f| f.added.contains(&call.line));
+    let existing: HashSet<(FunctionKey, String)> = calls
+        .iter()
+        .filter(|call| !on_added_line(call))
+        .map(|call| (caller_key(call), callee_label(call)))
+        .collect();
+
+    let mut dependencies: HashMap<FunctionKey, BTreeSet<String>> = HashMap::new();
+    for call in calls.iter().filter(|call| on_added_line(call)) {
+        let entry = (caller_key(call), callee_label(call));
+        let removed = changes_of(call)
+            .is_some_and(|f| f.removed_text.iter().any(|text| text.contains(call.callee.name.as_ref())));
+        if !existing.contains(&entry) && !removed {
+            dependencies.entry(entry.0).or_default().insert(entry.1);
+        }
+    }
+    dependencies
+}
+
+fn caller_key(call: &Call) -> FunctionKey {
+    (call.caller.module.to_string(), call.caller.name.to_string(), call.caller.arity)
+}
+
+fn callee_key(call: &Call) -> FunctionKey {
+    (call.callee.module.to_string(), call.callee.name.to_string(), call.callee.arity)
+}
+
+fn callee_label(call: &Call) -> String {
+    format!("{}.{}/{}", call.callee.module, call.callee.name, call.callee.arity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rewrites `get_user/1` so it calls `validate_email/1`, and adds a line to `index/2`
+    const DIFF: &str = "\
+--- a/lib/my_app/accounts.ex
++++ b/lib/my_app/accounts.ex
+@@ -12 +12 @@ def get_user(id) do
+-    Repo.get(User, id)
++    Repo.get(User, validate_email(id))
+--- a/lib/my_app/controller.ex
++++ b/lib/my_app/controller.ex
+@@ -8,0 +9 @@ def index(conn, params) do
++    Logger.info(\"index\")
+";
+
+    fn pr_cmd(diff: &Path, base_project: Option<&str>) -> PrCmd {
+        PrCmd {
+            diff: Some(diff.to_path_buf()),
+            base: None,
+            head: "HEAD".to_string(),
+            base_project: base_project.map(String::from),
+            project: "test_project".to_string(),
+            limit: 20,
+        }
+    }
+
+    fn write_diff(diff: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, diff.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_pr_maps_changed_lines_to_functions() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let file = write_diff(DIFF);
+        let result = pr_cmd(file.path(), None).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.files, 2);
+        let names: Vec<String> = result.functions.iter().map(|f| format!("{}.{}/{}", f.module, f.name, f.arity)).collect();
+        assert_eq!(names, ["MyApp.Accounts.get_user/1", "MyApp.Controller.index/2"]);
+
+        let get_user = &result.functions[0];
+        assert_eq!(get_user.changed_lines, 2);
+        assert!(!get_user.new);
+        assert_eq!(get_user.complexity_delta, None);
+        assert_eq!(get_user.total_callers, 1);
+        assert_eq!(get_user.callers[0].name, "show");
+        assert_eq!(get_user.callers[0].line, 14);
+        // The call on line 12 sits on a rewritten line that already called Repo.get/2
+        assert!(get_user.new_dependencies.is_empty());
+        assert!(result.functions[1].new_dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_pr_new_dependencies_from_added_lines() {
+        let db = db::test_utils::call_graph_db("test_project");
+        // Line 7 of index/2 holds its only call, to list_users/0
+        let file = write_diff("+++ b/lib/my_app/controller.ex\n@@ -7 +7 @@\n-    old()\n+    Accounts.list_users()\n");
+        let result = pr_cmd(file.path(), None).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.functions.len(), 1);
+        assert_eq!(result.functions[0].new_dependencies, ["MyApp.Accounts.list_users/0"]);
+        assert_eq!(result.functions[0].total_callers, 0);
+    }
+
+    #[test]
+    fn test_pr_against_base_project() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let base = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Accounts": {
+                    "get_user/1:10": {"name": "get_user", "arity": 1, "file": "lib/my_app/accounts.ex", "kind": "def", "line": 10, "start_line": 10, "end_line": 15, "complexity": 3}
+                }
+            },
+            "calls": []
+        }"#;
+        db::queries::import::import_json_str(&db, base, "base").unwrap();
+
+        let file = write_diff(DIFF);
+        let result = pr_cmd(file.path(), Some("base"))
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        let get_user = &result.functions[0];
+        assert_eq!(get_user.complexity_delta, Some(get_user.complexity - 3));
+        assert_eq!(get_user.new_dependencies, ["MyApp.Repo.get/2"]);
+        let index = &result.functions[1];
+        assert!(index.new);
+        assert_eq!(index.complexity_delta, None);
+        assert_eq!(index.new_dependencies, ["MyApp.Accounts.list_users/0"]);
+    }
+
+    #[test]
+    fn test_pr_rejects_refs_starting_with_dash() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let cmd = PrCmd {
+            diff: None,
+            base: Some("--output=/tmp/pwned".to_string()),
+            ..pr_cmd(Path::new("-"), None)
+        };
+        let err = cmd.execute(&db, &QuerySettings::default()).unwrap_err();
+        assert_eq!(err.to_string(), "--base must name a git ref, got '--output=/tmp/pwned'");
+
+        let cmd = PrCmd {
+            diff: None,
+            base: Some("main".to_string()),
+            head: "-p".to_string(),
+            ..pr_cmd(Path::new("-"), None)
+        };
+        assert!(cmd
+            .execute(&db, &QuerySettings::default())
+            .unwrap_err()
+            .to_string()
+            .starts_with("--head must name a git ref"));
+    }
+
+    #[test]
+    fn test_pr_missing_diff_file() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let err = pr_cmd(Path::new("/nonexistent/changes.patch"), None)
+            .execute(&db, &QuerySettings::default())
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to read diff"));
+    }
+}
@@ -0,0 +1,66 @@
+mod diff;
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Review the functions changed by a pull request
+///
+/// Maps the changed lines of a git diff to the functions they fall in, and
+/// reports for each changed function its callers, its complexity and how it
+/// moved, and the functions it started calling. The diff comes from a file
+/// (`--diff`, `-` for stdin) or from `git diff --base...--head`, run in the
+/// current directory.
+///
+/// Complexity deltas and new dependencies are exact when the base revision
+/// was imported as its own project (`--base-project`). Without one, new
+/// dependencies are the functions only called from added lines.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search pr --base main                          # Functions changed since main
+  code_search pr --base main --base-project main      # With complexity deltas against an import of main
+  git diff main | code_search pr --diff -             # Read the diff from stdin
+  code_search pr --diff changes.patch -o github       # Notices for review bots
+  code_search pr --base origin/main -o json           # Machine-readable report
+")]
+pub struct PrCmd {
+    /// Unified diff to read, or `-` for stdin
+    #[arg(long, conflicts_with_all = ["base", "head"], required_unless_present = "base")]
+    pub diff: Option<PathBuf>,
+
+    /// Revision the changes are compared against
+    #[arg(long)]
+    pub base: Option<String>,
+
+    /// Revision holding the changes
+    #[arg(long, default_value = "HEAD")]
+    pub head: String,
+
+    /// Project the base revision was imported as, for complexity deltas and new dependencies
+    #[arg(long)]
+    pub base_project: Option<String>,
+
+    /// Project holding the changes
+    #[arg(long, default_value = "default")]
+    pub project: String,
+
+    /// Maximum number of callers listed per function (1-1000)
+    #[arg(short, long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..=1000))]
+    pub limit: u32,
+}
+
+impl CommandRunner for PrCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,181 @@
+//! Output formatting for pr command results.
+
+use super::execute::{ChangedFunction, PrResult};
+use crate::output::{Annotation, Outputable};
+
+impl Outputable for PrResult {
+    fn to_table(&self) -> String {
+        let mut output = format!("Changed functions ({})\n\n", self.source);
+        if self.functions.is_empty() {
+            output.push_str(&format!("No changed functions found in {} file(s).\n", self.files));
+            return output;
+        }
+
+        output.push_str(&format!(
+            "Found {} changed function(s) in {} file(s):\n",
+            self.functions.len(),
+            self.files
+        ));
+        for function in &self.functions {
+            output.push_str(&format!(
+                "\n{}{}  {}:{}-{}  {} line(s) changed\n",
+                signature(function),
+                if function.new { " (new)" } else { "" },
+                function.file,
+                function.start_line,
+                function.end_line,
+                function.changed_lines,
+            ));
+            output.push_str(&format!("  complexity: {}\n", complexity(function)));
+            if !function.new_dependencies.is_empty() {
+                output.push_str(&format!("  new dependencies: {}\n", function.new_dependencies.join(", ")));
+            }
+            output.push_str(&format!("  callers: {}\n", function.total_callers));
+            for caller in &function.callers {
+                output.push_str(&format!(
+                    "    {}.{}/{}  {}:{}\n",
+                    caller.module, caller.name, caller.arity, caller.file, caller.line
+                ));
+            }
+            let hidden = function.total_callers - function.callers.len();
+            if hidden > 0 {
+                output.push_str(&format!("    ... and {} more\n", hidden));
+            }
+        }
+        output
+    }
+
+    fn to_annotations(&self) -> Option<Vec<Annotation>> {
+        let annotations = self
+            .functions
+            .iter()
+            .map(|function| {
+                let mut message = format!(
+                    "{} has {} caller(s), complexity {}",
+                    signature(function),
+                    function.total_callers,
+                    complexity(function)
+                );
+                if !function.new_dependencies.is_empty() {
+                    message.push_str(&format!(", new dependencies: {}", function.new_dependencies.join(", ")));
+                }
+                let title = if function.new { "New function" } else { "Changed function" };
+                Annotation::notice(&function.file, function.start_line, title, message)
+                    .with_end_line(function.end_line)
+            })
+            .collect();
+        Some(annotations)
+    }
+}
+
+fn signature(function: &ChangedFunction) -> String {
+    format!("{}.{}/{}", function.module, function.name, function.arity)
+}
+
+/// Complexity with its delta against the base project, as in `7 (+2)`
+fn complexity(function: &ChangedFunction) -> String {
+    match function.complexity_delta {
+        Some(delta) => format!("{} ({:+})", function.complexity, delta),
+        None => function.complexity.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::execute::Caller;
+    use super::*;
+    use crate::output::OutputFormat;
+
+    fn result() -> PrResult {
+        PrResult {
+            source: "git diff main...HEAD".to_string(),
+            files: 2,
+            base_project: Some("main".to_string()),
+            functions: vec![
+                ChangedFunction {
+                    module: "MyApp.Accounts".to_string(),
+                    name: "get_user".to_string(),
+                    arity: 1,
+                    file: "lib/my_app/accounts.ex".to_string(),
+                    start_line: 10,
+                    end_line: 15,
+                    changed_lines: 2,
+                    new: false,
+                    complexity: 5,
+                    complexity_delta: Some(2),
+                    new_dependencies: vec!["MyApp.Accounts.validate_email/1".to_string()],
+                    total_callers: 3,
+                    callers: vec![Caller {
+                        module: "MyApp.Controller".to_string(),
+                        name: "show".to_string(),
+                        arity: 2,
+                        file: "lib/my_app/controller.ex".to_string(),
+                        line: 14,
+                    }],
+                },
+                ChangedFunction {
+                    module: "MyApp.Accounts".to_string(),
+                    name: "count".to_string(),
+                    arity: 0,
+                    file: "lib/my_app/accounts.ex".to_string(),
+                    start_line: 40,
+                    end_line: 42,
+                    changed_lines: 3,
+                    new: true,
+                    complexity: 1,
+                    complexity_delta: None,
+                    new_dependencies: vec![],
+                    total_callers: 0,
+                    callers: vec![],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_pr_table() {
+        assert_eq!(
+            result().to_table(),
+            "\
+Changed functions (git diff main...HEAD)
+
+Found 2 changed function(s) in 2 file(s):
+
+MyApp.Accounts.get_user/1  lib/my_app/accounts.ex:10-15  2 line(s) changed
+  complexity: 5 (+2)
+  new dependencies: MyApp.Accounts.validate_email/1
+  callers: 3
+    MyApp.Controller.show/2  lib/my_app/controller.ex:14
+    ... and 2 more
+
+MyApp.Accounts.count/0 (new)  lib/my_app/accounts.ex:40-42  3 line(s) changed
+  complexity: 1
+  callers: 0
+"
+        );
+    }
+
+    #[test]
+    fn test_pr_table_empty() {
+        let result = PrResult {
+            source: "changes.patch".to_string(),
+            files: 1,
+            base_project: None,
+            functions: vec![],
+        };
+        assert_eq!(
+            result.to_table(),
+            "Changed functions (changes.patch)\n\nNo changed functions found in 1 file(s).\n"
+        );
+    }
+
+    #[test]
+    fn test_pr_github() {
+        assert_eq!(
+            result().format(OutputFormat::Github),
+            "\
+::notice file=lib/my_app/accounts.ex,line=10,endLine=15,title=Changed function::MyApp.Accounts.get_user/1 has 3 caller(s), complexity 5 (+2), new dependencies: MyApp.Accounts.validate_email/1
+::notice file=lib/my_app/accounts.ex,line=40,endLine=42,title=New function::MyApp.Accounts.count/0 has 0 caller(s), complexity 1"
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{QueryAction, QueryCmd};
+use crate::cli::Args;
+use crate::commands::{CommandRunner, Execute};
+use crate::config::{Config, QueryConfig};
+use crate::output::{columns, OutputFormat};
+use db::queries::saved_queries::{
+    delete_saved_query, find_saved_queries, run_read_only_script, save_query, SavedQuery,
+};
+use db::query_builders::QuerySettings;
+
+/// Where a query is defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuerySource {
+    /// Saved with `query save`
+    Database,
+    /// Listed under `queries` in the config file
+    Config,
+}
+
+/// A saved or configured query
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryEntry {
+    pub name: String,
+    pub source: QuerySource,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub script: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Parameters the query needs, `{name}` in arguments or `$name` in scripts
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<String>,
+}
+
+/// All queries, saved ones first, each group ordered by name
+#[derive(Debug, Serialize)]
+pub struct QueryList {
+    pub queries: Vec<QueryEntry>,
+}
+
+/// A deleted saved query
+#[derive(Debug, Serialize)]
+pub struct QueryDeleted {
+    pub deleted: String,
+}
+
+/// Rows returned by a script query
+#[derive(Debug, Serialize)]
+pub struct ScriptRows {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// Result of the query command
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum QueryResult {
+    Saved(QueryEntry),
+    Deleted(QueryDeleted),
+    List(QueryList),
+    Script(ScriptRows),
+    /// Arguments of a command invocation, parameters filled in, still to run
+    Invocation(Vec<String>),
+}
+
+impl Execute for QueryCmd {
+    type Output = QueryResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        match self.action {
+            QueryAction::Save {
+                name,
+                description,
+                script,
+                args,
+            } => {
+                let query = SavedQuery {
+                    name,
+                    args,
+                    script: script.unwrap_or_default(),
+                    description: description.unwrap_or_default(),
+                    created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as i64,
+                };
+                validate(&query)?;
+                save_query(db, &query)?;
+                Ok(QueryResult::Saved(saved_entry(query)))
+            }
+            QueryAction::Run { name, params } => {
+                let entry = find_query(db, &configured_queries(&self.config)?, &name)?;
+                if entry.script.is_empty() {
+                    Ok(QueryResult::Invocation(fill_placeholders(&entry, &params)?))
+                } else {
+                    run_script(db, &entry, &params).map(QueryResult::Script)
+                }
+            }
+            QueryAction::List => {
+                let mut queries: Vec<QueryEntry> = find_saved_queries(db)?.into_iter().map(saved_entry).collect();
+                for (name, query) in configured_queries(&self.config)? {
+                    if !queries.iter().any(|entry| entry.name == name) {
+                        queries.push(configured_entry(name, query));
+                    }
+                }
+                Ok(QueryResult::List(QueryList { queries }))
+            }
+            QueryAction::Delete { name } => {
+                if !delete_saved_query(db, &name)? {
+                    return Err(format!("No saved query named '{}'", name).into());
+                }
+                Ok(QueryResult::Deleted(QueryDeleted { deleted: name }))
+            }
+        }
+    }
+}
+
+/// Run a command invocation produced by `query run`.
+///
+/// The invocation's own `--only-project`/`--include-deps` and
+/// `--exclude-tests`/`--only-tests` override the caller's settings, and its
+/// `--fields` select columns in the caller's format.
+pub fn run_invocation(
+    db: &db::DbInstance,
+    args: &[String],
+    format: OutputFormat,
+    settings: &QuerySettings,
+) -> Result<String, Box<dyn Error>> {
+    let parsed = Args::parse_invocation(args)?;
+
+    let scoped = parsed.only_project || parsed.include_deps;
+    let tests_scoped = parsed.exclude_tests || parsed.only_tests;
+    let select_columns = !parsed.fields.is_empty() && !matches!(format, OutputFormat::Github);
+    let run_format = if select_columns { OutputFormat::Json } else { format };
+
+    let settings = QuerySettings {
+        only_project: if scoped { parsed.only_project } else { settings.only_project },
+        test_scope: if tests_scoped { parsed.test_scope() } else { settings.test_scope },
+        namespaces: parsed.command.common().map(|common| common.namespace.clone()).unwrap_or_default(),
+        sorted: settings.sorted && !parsed.no_sort,
+        generated_rules: settings.generated_rules.clone(),
+    };
+    let output = parsed.command.run(db, run_format, &settings)?;
+    if !select_columns {
+        return Ok(output);
+    }
+    let value: Value = serde_json::from_str(&output).map_err(|_| "--fields is not supported by this command")?;
+    Ok(columns::render(&value, &parsed.fields, format)?)
+}
+
+/// Reject queries that could never run
+fn validate(query: &SavedQuery) -> Result<(), Box<dyn Error>> {
+    if query.name.trim().is_empty() {
+        return Err("A saved query needs a name".into());
+    }
+    // Invocations with placeholders are checked when they run
+    if query.script.is_empty() && placeholders(&query.args).is_empty() {
+        Args::parse_invocation(&query.args)?;
+    }
+    Ok(())
+}
+
+/// Queries listed in the config file
+fn configured_queries(path: &std::path::Path) -> Result<BTreeMap<String, QueryConfig>, Box<dyn Error>> {
+    Ok(Config::load_or_default(path)?.queries)
+}
+
+/// The query named `name`, saved ones taking precedence over configured ones
+fn find_query(
+    db: &db::DbInstance,
+    configured: &BTreeMap<String, QueryConfig>,
+    name: &str,
+) -> Result<QueryEntry, Box<dyn Error>> {
+    if let Some(query) = find_saved_queries(db)?.into_iter().find(|query| query.name == name) {
+        return Ok(saved_entry(query));
+    }
+    match configured.get(name) {
+        Some(query) if !query.args.is_empty() || !query.script.is_empty() => {
+            Ok(configured_entry(name.to_string(), query.clone()))
+        }
+        Some(_) => Err(format!("Query '{}' in the config has neither args nor a script", name).into()),
+        None => Err(format!("No query named '{}', see `code_search query list`", name).into()),
+    }
+}
+
+fn saved_entry(query: SavedQuery) -> QueryEntry {
+    entry(query.name, QuerySource::Database, query.args, query.script, query.description)
+}
+
+fn configured_entry(name: String, query: QueryConfig) -> QueryEntry {
+    entry(name, QuerySource::Config, query.args, query.script, query.description)
+}
+
+fn entry(name: String, source: QuerySource, args: Vec<String>, script: String, description: String) -> QueryEntry {
+    let params = if script.is_empty() { placeholders(&args) } else { script_params(&script) };
+    QueryEntry {
+        name,
+        source,
+        args,
+        script,
+        description,
+        params,
+    }
+}
+
+/// Names of the `{name}` placeholders in the arguments, in first-seen order
+fn placeholders(args: &[String]) -> Vec<String> {
+    let mut names = Vec::new();
+    for arg in args {
+        let mut rest = arg.as_str();
+        while let Some(start) = rest.find('{') {
+            rest = &rest[start + 1..];
+            let Some(end) = rest.find('}') else { break };
+            let name = &rest[..end];
+            if is_param_name(name) && !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Names of the `$name` parameters of a script, in first-seen order
+fn script_params(script: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (start, _) in script.match_indices('$') {
+        let name: String = script[start + 1..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect();
+        if is_param_name(&name) && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Placeholder names start with a letter or underscore, so regex repetitions like `{2}` are left alone
+fn is_param_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Reject parameters the query does not take
+fn check_params(entry: &QueryEntry, params: &[(String, String)]) -> Result<(), Box<dyn Error>> {
+    if let Some((unknown, _)) = params.iter().find(|(name, _)| !entry.params.contains(name)) {
+        let expected = if entry.params.is_empty() { "none".to_string() } else { entry.params.join(", ") };
+        return Err(format!("Query '{}' has no parameter '{}' (parameters: {})", entry.name, unknown, expected).into());
+    }
+    if let Some(missing) = entry.params.iter().find(|name| !params.iter().any(|(n, _)| n == *name)) {
+        return Err(format!("Query '{}' needs --param {}=VALUE", entry.name, missing).into());
+    }
+    Ok(())
+}
+
+/// The invocation's arguments with every placeholder replaced by its value
+fn fill_placeholders(entry: &QueryEntry, params: &[(String, String)]) -> Result<Vec<String>, Box<dyn Error>> {
+    check_params(entry, params)?;
+    Ok(entry
+        .args
+        .iter()
+        .map(|arg| {
+            params
+                .iter()
+                .fold(arg.clone(), |arg, (name, value)| arg.replace(&format!("{{{}}}", name), value))
+        })
+        .collect())
+}
+
+/// Run a script query read-only, numbers in parameters passed as numbers
+fn run_script(db: &db::DbInstance, entry: &QueryEntry, params: &[(String, String)]) -> Result<ScriptRows, Box<dyn Error>> {
+    check_params(entry, params)?;
+    let bindings: serde_json::Map<String, Value> = params
+        .iter()
+        .map(|(name, value)| {
+            let value = if let Ok(n) = value.parse::<i64>() {
+                Value::from(n)
+            } else if let Ok(n) = value.parse::<f64>() {
+                Value::from(n)
+            } else {
+                Value::from(value.as_str())
+            };
+            (name.clone(), value)
+        })
+        .collect();
+
+    let result: Value = serde_json::from_str(&run_read_only_script(db, &entry.script, &Value::Object(bindings).to_string()))?;
+    if result["ok"] != Value::Bool(true) {
+        let message = result["message"].as_str().unwrap_or("unknown error");
+        return Err(format!("Query '{}' failed: {}", entry.name, message).into());
+    }
+
+    let strings = |value: &Value| -> Vec<Value> { value.as_array().cloned().unwrap_or_default() };
+    Ok(ScriptRows {
+        headers: strings(&result["headers"])
+            .iter()
+            .map(|header| header.as_str().unwrap_or_default().to_string())
+            .collect(),
+        rows: strings(&result["rows"]).iter().map(strings).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+    use std::io::Write;
+
+    crate::shared_fixture! {
+        fixture_name: populated_db,
+        fixture_type: call_graph,
+        project: "test_project",
+    }
+
+    /// A config file sharing one query
+    #[fixture]
+    fn config() -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(br#"{"queries": {"top": {"args": ["hotspots", "-l", "{limit}"], "description": "Busiest"}}}"#)
+            .unwrap();
+        file
+    }
+
+    fn query(db: &db::DbInstance, config: &tempfile::NamedTempFile, action: QueryAction) -> Result<QueryResult, Box<dyn Error>> {
+        let cmd = QueryCmd {
+            config: config.path().to_path_buf(),
+            action,
+        };
+        cmd.execute(db, &QuerySettings::default())
+    }
+
+    fn save(db: &db::DbInstance, name: &str, script: Option<&str>, args: &[&str]) -> Result<QueryResult, Box<dyn Error>> {
+        let action = QueryAction::Save {
+            name: name.to_string(),
+            description: None,
+            script: script.map(str::to_string),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        };
+        query(db, &config(), action)
+    }
+
+    fn run(
+        db: &db::DbInstance,
+        config: &tempfile::NamedTempFile,
+        name: &str,
+        params: &[(&str, &str)],
+    ) -> Result<QueryResult, Box<dyn Error>> {
+        let action = QueryAction::Run {
+            name: name.to_string(),
+            params: params.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        };
+        query(db, config, action)
+    }
+
+    #[rstest]
+    fn test_query_run_fills_placeholders(populated_db: db::DbInstance, config: tempfile::NamedTempFile) {
+        save(&populated_db, "callers", None, &["calls-to", "{module}", "{function}", "-l", "5"]).unwrap();
+
+        let result = run(&populated_db, &config, "callers", &[("module", "MyApp.Repo"), ("function", "get")]);
+        let Ok(QueryResult::Invocation(args)) = result else {
+            panic!("expected an invocation, got {:?}", result);
+        };
+        assert_eq!(args, ["calls-to", "MyApp.Repo", "get", "-l", "5"]);
+
+        let missing = run(&populated_db, &config, "callers", &[("module", "MyApp.Repo")]).unwrap_err();
+        assert!(missing.to_string().contains("needs --param function=VALUE"), "{missing}");
+        let unknown = run(&populated_db, &config, "callers", &[("module", "A"), ("function", "b"), ("arity", "1")]);
+        assert!(unknown.unwrap_err().to_string().contains("no parameter 'arity'"));
+    }
+
+    #[rstest]
+    fn test_query_run_configured(populated_db: db::DbInstance, config: tempfile::NamedTempFile) {
+        let result = run(&populated_db, &config, "top", &[("limit", "3")]);
+        let Ok(QueryResult::Invocation(args)) = result else {
+            panic!("expected an invocation, got {:?}", result);
+        };
+        assert_eq!(args, ["hotspots", "-l", "3"]);
+
+        // A saved query shadows the configured one
+        save(&populated_db, "top", None, &["hotspots", "-l", "1"]).unwrap();
+        let result = run(&populated_db, &config, "top", &[]);
+        assert!(matches!(result, Ok(QueryResult::Invocation(args)) if args == ["hotspots", "-l", "1"]));
+    }
+
+    #[rstest]
+    fn test_query_run_invocation_output(populated_db: db::DbInstance) {
+        let args: Vec<String> = ["calls-to", "MyApp.Repo", "--project", "test_project", "--fields", "caller.module"].map(String::from).to_vec();
+
+        let output = run_invocation(&populated_db, &args, OutputFormat::Csv, &QuerySettings::default()).unwrap();
+        assert!(output.starts_with("caller.module\n"), "{output}");
+        assert!(output.contains("MyApp.Accounts"));
+    }
+
+    #[rstest]
+    fn test_query_run_script(populated_db: db::DbInstance, config: tempfile::NamedTempFile) {
+        save(&populated_db, "count", Some("?[count(m)] := *modules{project: $project, name: m}"), &[]).unwrap();
+
+        let result = run(&populated_db, &config, "count", &[("project", "test_project")]);
+        let Ok(QueryResult::Script(rows)) = result else {
+            panic!("expected script rows, got {:?}", result);
+        };
+        assert_eq!(rows.headers, ["count(m)"]);
+        assert!(rows.rows[0][0].as_i64().unwrap() > 0);
+
+        save(&populated_db, "write", Some("?[project, name] <- [['x', 'y']] :put modules {project, name}"), &[]).unwrap();
+        let err = run(&populated_db, &config, "write", &[]).unwrap_err();
+        assert!(err.to_string().starts_with("Query 'write' failed"), "{err}");
+    }
+
+    #[rstest]
+    fn test_query_list_and_delete(populated_db: db::DbInstance, config: tempfile::NamedTempFile) {
+        save(&populated_db, "b", None, &["hotspots", "-l", "5"]).unwrap();
+        save(&populated_db, "a", None, &["calls-to", "{module}"]).unwrap();
+
+        let result = query(&populated_db, &config, QueryAction::List);
+        let Ok(QueryResult::List(list)) = result else {
+            panic!("expected a list, got {:?}", result);
+        };
+        let names: Vec<(&str, QuerySource)> = list.queries.iter().map(|q| (q.name.as_str(), q.source)).collect();
+        assert_eq!(names, [("a", QuerySource::Database), ("b", QuerySource::Database), ("top", QuerySource::Config)]);
+        assert_eq!(list.queries[0].params, ["module"]);
+
+        let delete = || query(&populated_db, &config, QueryAction::Delete { name: "a".to_string() });
+        delete().unwrap();
+        assert!(delete().unwrap_err().to_string().contains("No saved query named 'a'"));
+        assert!(run(&populated_db, &config, "a", &[]).unwrap_err().to_string().contains("No query named 'a'"));
+    }
+
+    #[rstest]
+    fn test_query_save_rejects_invalid_invocations(populated_db: db::DbInstance) {
+        let err = save(&populated_db, "bad", None, &["no-such-command"]).unwrap_err();
+        assert!(err.to_string().contains("Unknown command: no-such-command"), "{err}");
+        let err = save(&populated_db, "nested", None, &["batch", "queries.json"]).unwrap_err();
+        assert!(err.to_string().contains("cannot run inside another command"), "{err}");
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let args = ["calls-to", "{module}", "-r", "a{2}{fn}{module}"].map(String::from);
+        assert_eq!(placeholders(&args), ["module", "fn"]);
+        assert_eq!(script_params("?[x] := x = $a + $b_1, y = $a"), ["a", "b_1"]);
+    }
+}
@@ -0,0 +1,115 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::output::{OutputFormat, Outputable};
+use execute::{run_invocation, QueryResult};
+
+/// Save, list and run named queries
+///
+/// A saved query is a command invocation, or with --script a read-only
+/// CozoScript. Write `{name}` placeholders in the arguments of an invocation
+/// and `$name` parameters in a script, and fill them in with
+/// `query run <NAME> -p name=value`. Queries saved in the database and those
+/// listed under `queries` in .code_search/config.json run the same way; a
+/// saved query takes precedence over a configured one of the same name.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search query save repo-leaks -d 'Web modules calling the Repo' -- \\
+    calls-to MyApp.Repo --group-by caller-module
+  code_search query save callers -- calls-to '{module}' '{function}'
+  code_search query save web-count --script '?[count(m)] := *modules{name: m}, starts_with(m, $prefix)'
+  code_search query run repo-leaks
+  code_search query run callers -p module=MyApp.Repo -p function=get
+  code_search query run web-count -p prefix=MyAppWeb
+  code_search query list
+  code_search query delete repo-leaks
+")]
+pub struct QueryCmd {
+    /// Path to the config file listing shared queries
+    #[arg(long, default_value = DEFAULT_CONFIG_PATH, global = true)]
+    pub config: PathBuf,
+
+    #[command(subcommand)]
+    pub action: QueryAction,
+}
+
+/// What the query command does
+#[derive(Subcommand, Debug)]
+pub enum QueryAction {
+    /// Save a command invocation (the arguments after --) or a CozoScript under a name
+    Save {
+        /// Name to run the query by
+        name: String,
+
+        /// What the query answers, shown by `query list`
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Save a read-only CozoScript instead of a command invocation
+        #[arg(long, conflicts_with = "args")]
+        script: Option<String>,
+
+        /// Command and arguments, as after `code_search`; `{name}` marks a parameter
+        #[arg(last = true, required_unless_present = "script")]
+        args: Vec<String>,
+    },
+
+    /// Run a saved or configured query
+    Run {
+        /// Name of the query
+        name: String,
+
+        /// Parameter value as name=value, filling `{name}` in arguments or `$name` in scripts
+        #[arg(short, long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+    },
+
+    /// List saved and configured queries
+    List,
+
+    /// Delete a saved query
+    Delete {
+        /// Name of the query
+        name: String,
+    },
+}
+
+/// Parse a `name=value` parameter
+fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.to_string())),
+        _ => Err(format!("expected NAME=VALUE, got '{}'", s)),
+    }
+}
+
+impl CommandRunner for QueryCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        match self.execute(db, settings)? {
+            QueryResult::Invocation(args) => run_invocation(db, &args, format, settings),
+            result => Ok(result.format(format)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_param() {
+        assert_eq!(parse_param("module=MyApp.Repo"), Ok(("module".to_string(), "MyApp.Repo".to_string())));
+        assert_eq!(parse_param("expr=a=b"), Ok(("expr".to_string(), "a=b".to_string())));
+        assert!(parse_param("module").is_err());
+        assert!(parse_param("=value").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Output formatting for query command results.
+
+use serde_json::Value;
+
+use super::execute::{QueryEntry, QueryResult, QuerySource, ScriptRows};
+use crate::output::{columns, Outputable};
+
+impl Outputable for QueryResult {
+    fn to_table(&self) -> String {
+        match self {
+            QueryResult::Saved(entry) => format!("Saved query {}\n{}", entry.name, describe(entry)),
+            QueryResult::Deleted(deleted) => format!("Deleted query {}\n", deleted.deleted),
+            QueryResult::List(list) => {
+                if list.queries.is_empty() {
+                    return "No saved or configured queries.\n".to_string();
+                }
+                let mut output = format!("Queries ({}):\n", list.queries.len());
+                for entry in &list.queries {
+                    let source = match entry.source {
+                        QuerySource::Database => "saved",
+                        QuerySource::Config => "config",
+                    };
+                    output.push_str(&format!("\n{} [{}]\n{}", entry.name, source, describe(entry)));
+                }
+                output
+            }
+            QueryResult::Script(rows) => {
+                if rows.rows.is_empty() {
+                    return "No rows.\n".to_string();
+                }
+                columns::to_table(&rows.headers, &cells(rows))
+            }
+            QueryResult::Invocation(args) => args.join(" "),
+        }
+    }
+
+    fn to_csv(&self) -> Option<String> {
+        match self {
+            QueryResult::Script(rows) => Some(columns::to_csv(&rows.headers, &cells(rows))),
+            _ => None,
+        }
+    }
+}
+
+/// Indented description, parameters and definition of a query
+fn describe(entry: &QueryEntry) -> String {
+    let mut lines = Vec::new();
+    if !entry.description.is_empty() {
+        lines.push(format!("  {}", entry.description));
+    }
+    if !entry.params.is_empty() {
+        lines.push(format!("  params: {}", entry.params.join(", ")));
+    }
+    if entry.script.is_empty() {
+        lines.push(format!("  code_search {}", entry.args.join(" ")));
+    } else {
+        lines.push(format!("  script: {}", entry.script));
+    }
+    lines.join("\n") + "\n"
+}
+
+fn cells(rows: &ScriptRows) -> Vec<Vec<String>> {
+    rows.rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|value| match value {
+                    Value::Null => String::new(),
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::query::execute::QueryList;
+
+    #[test]
+    fn test_to_table_list() {
+        let result = QueryResult::List(QueryList {
+            queries: vec![
+                QueryEntry {
+                    name: "callers".to_string(),
+                    source: QuerySource::Database,
+                    args: vec!["calls-to".to_string(), "{module}".to_string()],
+                    script: String::new(),
+                    description: "Who calls a module".to_string(),
+                    params: vec!["module".to_string()],
+                },
+                QueryEntry {
+                    name: "count".to_string(),
+                    source: QuerySource::Config,
+                    args: Vec::new(),
+                    script: "?[count(m)] := *modules{name: m}".to_string(),
+                    description: String::new(),
+                    params: Vec::new(),
+                },
+            ],
+        });
+
+        assert_eq!(
+            result.to_table(),
+            "\
+Queries (2):
+
+callers [saved]
+  Who calls a module
+  params: module
+  code_search calls-to {module}
+
+count [config]
+  script: ?[count(m)] := *modules{name: m}
+"
+        );
+    }
+
+    #[test]
+    fn test_script_rows() {
+        let result = QueryResult::Script(ScriptRows {
+            headers: vec!["name".to_string(), "count".to_string()],
+            rows: vec![vec![Value::from("MyApp.Accounts"), Value::from(3)]],
+        });
+
+        assert_eq!(result.to_table(), "name            count\nMyApp.Accounts  3");
+        assert_eq!(result.to_csv().unwrap(), "name,count\nMyApp.Accounts,3");
+    }
+}
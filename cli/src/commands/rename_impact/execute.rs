@@ -0,0 +1,96 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::RenameImpactCmd;
+use crate::commands::Execute;
+use db::queries::rename_impact::{find_rename_references, RenameReference};
+use db::query_builders::QuerySettings;
+
+/// Result of the rename-impact command
+#[derive(Debug, Serialize)]
+pub struct RenameImpactResult {
+    /// `Module`, `Module.function` or `Module.function/arity`
+    pub target: String,
+    pub total_references: usize,
+    /// Files with at least one reference, sorted
+    pub files: Vec<String>,
+    /// Sorted by file, line and column
+    pub references: Vec<RenameReference>,
+}
+
+impl Execute for RenameImpactCmd {
+    type Output = RenameImpactResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let references = find_rename_references(
+            db,
+            &self.project,
+            &self.module,
+            self.function.as_deref(),
+            self.arity,
+            settings,
+        )?;
+
+        let target = match (&self.function, self.arity) {
+            (None, _) => self.module.clone(),
+            (Some(function), None) => format!("{}.{}", self.module, function),
+            (Some(function), Some(arity)) => format!("{}.{}/{}", self.module, function, arity),
+        };
+        let files: BTreeSet<String> = references.iter().map(|r| r.file.clone()).collect();
+
+        Ok(RenameImpactResult {
+            target,
+            total_references: references.len(),
+            files: files.into_iter().collect(),
+            references,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    crate::shared_fixture! {
+        fixture_name: populated_db,
+        fixture_type: call_graph,
+        project: "test_project",
+    }
+
+    #[rstest]
+    fn test_rename_impact_function(populated_db: db::DbInstance) {
+        let cmd = RenameImpactCmd {
+            module: "MyApp.Accounts".to_string(),
+            function: Some("get_user".to_string()),
+            arity: Some(1),
+            project: "test_project".to_string(),
+        };
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Rename impact should succeed");
+
+        assert_eq!(result.target, "MyApp.Accounts.get_user/1");
+        assert_eq!(result.total_references, result.references.len());
+        assert!(result.references.iter().any(|r| r.kind.as_str() == "definition"));
+        assert!(result.references.iter().any(|r| r.kind.as_str() == "call"));
+        let sorted = result
+            .references
+            .windows(2)
+            .all(|w| (&w[0].file, w[0].line) <= (&w[1].file, w[1].line));
+        assert!(sorted);
+    }
+
+    #[rstest]
+    fn test_rename_impact_unknown_target(populated_db: db::DbInstance) {
+        let cmd = RenameImpactCmd {
+            module: "MyApp.Missing".to_string(),
+            function: None,
+            arity: None,
+            project: "test_project".to_string(),
+        };
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Rename impact should succeed");
+        assert_eq!(result.total_references, 0);
+        assert!(result.files.is_empty());
+    }
+}
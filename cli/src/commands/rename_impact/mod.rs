@@ -0,0 +1,49 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// List every definition, call site, spec and type a rename has to touch
+///
+/// Without --function the module itself is renamed: its definition, remote
+/// calls into it, and specs and types mentioning it. References are sorted by
+/// file and line; -o json gives one object per edit for patch planners.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search rename-impact --module MyApp.Accounts                           # Rename a module
+  code_search rename-impact --module MyApp.Accounts --function get_user       # Rename every arity
+  code_search rename-impact --module MyApp.Accounts --function get_user --arity 1
+  code_search rename-impact --module MyApp.Accounts --function get_user -o json
+")]
+pub struct RenameImpactCmd {
+    /// Module to rename, or containing the function to rename (exact name)
+    #[arg(short, long)]
+    pub module: String,
+
+    /// Function to rename (exact name)
+    #[arg(short, long)]
+    pub function: Option<String>,
+
+    /// Only rename the function of this arity
+    #[arg(short, long, requires = "function")]
+    pub arity: Option<i64>,
+
+    /// Project to search in
+    #[arg(long, default_value = "default")]
+    pub project: String,
+}
+
+impl CommandRunner for RenameImpactCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
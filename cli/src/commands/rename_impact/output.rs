@@ -0,0 +1,87 @@
+//! Output formatting for rename-impact command results.
+
+use super::execute::RenameImpactResult;
+use crate::output::Outputable;
+
+impl Outputable for RenameImpactResult {
+    fn to_table(&self) -> String {
+        let mut lines = vec![format!("Rename impact: {}", self.target), String::new()];
+
+        if self.references.is_empty() {
+            lines.push("No references found.".to_string());
+            return lines.join("\n");
+        }
+
+        lines.push(format!(
+            "{} reference(s) in {} file(s):",
+            self.total_references,
+            self.files.len()
+        ));
+
+        let mut current_file = None;
+        for reference in &self.references {
+            if current_file != Some(&reference.file) {
+                lines.push(String::new());
+                lines.push(reference.file.clone());
+                current_file = Some(&reference.file);
+            }
+            let position = match (reference.line, reference.column) {
+                (0, _) => "-".to_string(),
+                (line, 0) => format!("L{}", line),
+                (line, column) => format!("L{}:{}", line, column),
+            };
+            lines.push(format!(
+                "  {:<8} {:<10} {}",
+                position,
+                reference.kind.as_str(),
+                reference.text
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::rename_impact::{ReferenceKind, RenameReference};
+
+    #[test]
+    fn test_to_table_groups_by_file() {
+        let reference = |kind, file: &str, line, column, text: &str| RenameReference {
+            kind,
+            file: file.to_string(),
+            line,
+            column,
+            module: "MyApp.Accounts".to_string(),
+            function: String::new(),
+            text: text.to_string(),
+        };
+        let result = RenameImpactResult {
+            target: "MyApp.Accounts.get_user/1".to_string(),
+            total_references: 3,
+            files: vec!["lib/accounts.ex".to_string(), "lib/web.ex".to_string()],
+            references: vec![
+                reference(ReferenceKind::Spec, "lib/accounts.ex", 9, 0, "@spec get_user(integer()) :: term()"),
+                reference(ReferenceKind::Definition, "lib/accounts.ex", 10, 7, "def get_user/1"),
+                reference(ReferenceKind::Call, "lib/web.ex", 8, 5, "MyApp.Accounts.get_user/1"),
+            ],
+        };
+
+        assert_eq!(
+            result.to_table(),
+            "\
+Rename impact: MyApp.Accounts.get_user/1
+
+3 reference(s) in 2 file(s):
+
+lib/accounts.ex
+  L9       spec       @spec get_user(integer()) :: term()
+  L10:7    definition def get_user/1
+
+lib/web.ex
+  L8:5     call       MyApp.Accounts.get_user/1"
+        );
+    }
+}
@@ -0,0 +1,481 @@
+//! Report assembly from the individual analysis commands.
+//!
+//! Each section runs the corresponding command and flattens its result into a
+//! titled table, which the output module renders as Markdown or HTML. The HTML
+//! render also embeds the module dependency graph.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::{ReportCmd, ReportRender, ReportSection};
+use crate::commands::{
+    BoundariesCmd, CommonArgs, CyclesCmd, DuplicatesCmd, Execute, GodModulesCmd, HotspotsCmd,
+    LargeFunctionsCmd,
+};
+use crate::commands::duplicates::DuplicatesOutput;
+use crate::utils::extract_namespace;
+use db::queries::clusters::get_module_call_counts;
+use db::queries::hotspots::HotspotKind;
+use db::query_builders::QuerySettings;
+
+/// Most connected modules drawn in the HTML dependency graph
+pub const MAX_GRAPH_MODULES: usize = 200;
+
+/// One titled table of the report
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportTable {
+    pub title: String,
+    /// Thresholds the section was computed with
+    pub criteria: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// A module of the dependency graph, with the call sites crossing its boundary
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphModule {
+    pub name: String,
+    /// Namespace at depth 2, used to color the module
+    pub namespace: String,
+    pub incoming: i64,
+    pub outgoing: i64,
+}
+
+/// Call sites from one module of the dependency graph to another
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphDependency {
+    pub source: String,
+    pub target: String,
+    pub calls: i64,
+}
+
+/// Module dependency graph embedded in the HTML report
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReportGraph {
+    /// Modules with any cross-module call, before the graph was capped
+    pub total_modules: usize,
+    pub modules: Vec<GraphModule>,
+    pub dependencies: Vec<GraphDependency>,
+}
+
+/// Result of the report command
+#[derive(Debug, Serialize)]
+pub struct ReportResult {
+    pub project: String,
+    #[serde(skip)]
+    pub render: ReportRender,
+    pub sections: Vec<ReportTable>,
+    /// Only built for the HTML render
+    #[serde(skip)]
+    pub graph: Option<ReportGraph>,
+}
+
+impl ReportTable {
+    fn new(section: ReportSection, criteria: String, columns: &[&str]) -> Self {
+        Self {
+            title: section.title().to_string(),
+            criteria,
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    fn with_rows(mut self, rows: Vec<Vec<String>>) -> Self {
+        self.rows = rows;
+        self
+    }
+}
+
+impl Execute for ReportCmd {
+    type Output = ReportResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let mut sections = Vec::new();
+        let mut seen = Vec::new();
+        for section in &self.sections {
+            if seen.contains(section) {
+                continue;
+            }
+            seen.push(*section);
+            sections.push(self.build_section(db, *section, settings)?);
+        }
+
+        let graph = match self.render {
+            ReportRender::Html => Some(build_graph(db, &self.project, settings)?),
+            ReportRender::Markdown => None,
+        };
+
+        Ok(ReportResult {
+            project: self.project,
+            render: self.render,
+            sections,
+            graph,
+        })
+    }
+}
+
+/// Module dependency graph of the most connected modules.
+///
+/// Modules are ranked by call sites crossing their boundary, so a large
+/// project keeps the modules that shape its structure.
+fn build_graph(db: &db::DbInstance, project: &str, settings: &QuerySettings) -> Result<ReportGraph, Box<dyn Error>> {
+    let calls = get_module_call_counts(db, project, settings)?;
+
+    let mut totals: BTreeMap<&str, (i64, i64)> = BTreeMap::new();
+    for call in &calls {
+        totals.entry(&call.caller_module).or_default().1 += call.call_count;
+        totals.entry(&call.callee_module).or_default().0 += call.call_count;
+    }
+
+    let mut modules: Vec<GraphModule> = totals
+        .iter()
+        .map(|(name, &(incoming, outgoing))| GraphModule {
+            name: name.to_string(),
+            namespace: extract_namespace(name, 2),
+            incoming,
+            outgoing,
+        })
+        .collect();
+    let total_modules = modules.len();
+    modules.sort_by(|a, b| (b.incoming + b.outgoing).cmp(&(a.incoming + a.outgoing)).then_with(|| a.name.cmp(&b.name)));
+    modules.truncate(MAX_GRAPH_MODULES);
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let kept = |name: &str| modules.binary_search_by(|m| m.name.as_str().cmp(name)).is_ok();
+    let dependencies = calls
+        .iter()
+        .filter(|call| kept(&call.caller_module) && kept(&call.callee_module))
+        .map(|call| GraphDependency {
+            source: call.caller_module.clone(),
+            target: call.callee_module.clone(),
+            calls: call.call_count,
+        })
+        .collect();
+
+    Ok(ReportGraph {
+        total_modules,
+        modules,
+        dependencies,
+    })
+}
+
+impl ReportCmd {
+    fn common(&self, limit: u32) -> CommonArgs {
+        CommonArgs {
+            project: self.project.clone(),
+            regex: false,
+            limit,
+            namespace: Vec::new(),
+        }
+    }
+
+    fn build_section(
+        &self,
+        db: &db::DbInstance,
+        section: ReportSection,
+        settings: &QuerySettings,
+    ) -> Result<ReportTable, Box<dyn Error>> {
+        let limit = self.limit as usize;
+
+        let table = match section {
+            ReportSection::Hotspots => {
+                let result = HotspotsCmd {
+                    module: None,
+                    kind: HotspotKind::Incoming,
+                    exclude_generated: true,
+                    app: None,
+                    sort: None,
+                    min_depth_from_entry: None,
+                    common: self.common(self.limit),
+                }
+                .execute(db, settings)?;
+
+                let rows = result
+                    .entries
+                    .iter()
+                    .map(|e| {
+                        vec![
+                            format!("{}.{}", e.module, e.function),
+                            e.incoming.to_string(),
+                            e.outgoing.to_string(),
+                            e.total.to_string(),
+                        ]
+                    })
+                    .collect();
+
+                ReportTable::new(
+                    section,
+                    "most incoming calls, generated code excluded".to_string(),
+                    &["Function", "Incoming", "Outgoing", "Total"],
+                )
+                .with_rows(rows)
+            }
+            ReportSection::Cycles => {
+                let mut result = CyclesCmd {
+                    module: None,
+                    max_length: None,
+                    involving: None,
+                    baseline: Default::default(),
+                    common: self.common(1000),
+                }
+                .execute(db, settings)?;
+
+                result.cycles.sort_by(|a, b| a.length.cmp(&b.length).then_with(|| a.modules.cmp(&b.modules)));
+                let rows = result
+                    .cycles
+                    .iter()
+                    .take(limit)
+                    .map(|cycle| {
+                        let mut path = cycle.modules.clone();
+                        if let Some(first) = cycle.modules.first() {
+                            path.push(first.clone());
+                        }
+                        vec![cycle.length.to_string(), path.join(" → ")]
+                    })
+                    .collect();
+
+                ReportTable::new(
+                    section,
+                    format!(
+                        "{} cycle(s), {} module(s) involved",
+                        result.total_cycles, result.modules_in_cycles
+                    ),
+                    &["Length", "Cycle"],
+                )
+                .with_rows(rows)
+            }
+            ReportSection::GodModules => {
+                let result = GodModulesCmd {
+                    module: None,
+                    min_functions: self.min_functions,
+                    min_loc: 0,
+                    min_total: self.min_total,
+                    sort: None,
+                    common: self.common(self.limit),
+                }
+                .execute(db, settings)?;
+
+                let rows = result
+                    .items
+                    .iter()
+                    .flat_map(|group| {
+                        group.entries.iter().map(|e| {
+                            vec![
+                                group.name.clone(),
+                                e.function_count.to_string(),
+                                e.loc.to_string(),
+                                e.incoming.to_string(),
+                                e.outgoing.to_string(),
+                                e.total.to_string(),
+                            ]
+                        })
+                    })
+                    .collect();
+
+                ReportTable::new(
+                    section,
+                    format!(
+                        "at least {} functions and total connectivity {}",
+                        self.min_functions, self.min_total
+                    ),
+                    &["Module", "Functions", "LoC", "Incoming", "Outgoing", "Total"],
+                )
+                .with_rows(rows)
+            }
+            ReportSection::LargeFunctions => {
+                let result = LargeFunctionsCmd {
+                    module: None,
+                    min_lines: self.min_lines,
+                    include_generated: false,
+                    app: None,
+                    sort: None,
+                    filter: None,
+                    blame: false,
+                    common: self.common(self.limit),
+                }
+                .execute(db, settings)?;
+
+                let rows = result
+                    .items
+                    .iter()
+                    .flat_map(|group| {
+                        group.entries.iter().map(|e| {
+                            vec![
+                                format!("{}.{}/{}", group.name, e.name, e.arity),
+                                e.lines.to_string(),
+                                format!("{}:{}-{}", e.file, e.start_line, e.end_line),
+                            ]
+                        })
+                    })
+                    .collect();
+
+                ReportTable::new(
+                    section,
+                    format!("at least {} lines", self.min_lines),
+                    &["Function", "Lines", "Location"],
+                )
+                .with_rows(rows)
+            }
+            ReportSection::Duplicates => {
+                let result = DuplicatesCmd {
+                    module: None,
+                    by_module: false,
+                    exact: false,
+                    exclude_generated: true,
+                    baseline: Default::default(),
+                    common: self.common(self.limit),
+                }
+                .execute(db, settings)?;
+
+                let DuplicatesOutput::Detailed(result) = result else {
+                    return Err("duplicates returned a per-module result".into());
+                };
+
+                let rows = result
+                    .groups
+                    .iter()
+                    .take(limit)
+                    .map(|group| {
+                        let functions: Vec<String> = group
+                            .functions
+                            .iter()
+                            .map(|f| format!("{}.{}/{}", f.module, f.name, f.arity))
+                            .collect();
+                        vec![group.functions.len().to_string(), functions.join(", ")]
+                    })
+                    .collect();
+
+                ReportTable::new(
+                    section,
+                    format!("{} group(s) with identical AST, generated code excluded", result.total_groups),
+                    &["Copies", "Functions"],
+                )
+                .with_rows(rows)
+            }
+            ReportSection::Boundaries => {
+                let result = BoundariesCmd {
+                    module: None,
+                    min_incoming: 1,
+                    min_ratio: self.min_ratio,
+                    sort: None,
+                    common: self.common(self.limit),
+                }
+                .execute(db, settings)?;
+
+                let rows = result
+                    .items
+                    .iter()
+                    .flat_map(|group| {
+                        group.entries.iter().map(|e| {
+                            vec![
+                                group.name.clone(),
+                                e.incoming.to_string(),
+                                e.outgoing.to_string(),
+                                format!("{:.2}", e.ratio),
+                            ]
+                        })
+                    })
+                    .collect();
+
+                ReportTable::new(
+                    section,
+                    format!("incoming/outgoing ratio of at least {}", self.min_ratio),
+                    &["Module", "Incoming", "Outgoing", "Ratio"],
+                )
+                .with_rows(rows)
+            }
+        };
+
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report_cmd(sections: &[ReportSection]) -> ReportCmd {
+        ReportCmd {
+            sections: sections.to_vec(),
+            render: ReportRender::Markdown,
+            min_lines: 50,
+            min_functions: 20,
+            min_total: 10,
+            min_ratio: 2.0,
+            project: "test_project".to_string(),
+            limit: 10,
+        }
+    }
+
+    #[test]
+    fn test_report_all_sections() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = report_cmd(&ReportSection::ALL)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        let titles: Vec<&str> = result.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            ["Hotspots", "Cycles", "God Modules", "Large Functions", "Duplicates", "Boundaries"]
+        );
+        for table in &result.sections {
+            assert!(table.rows.iter().all(|row| row.len() == table.columns.len()));
+        }
+        assert!(!result.sections[0].rows.is_empty());
+    }
+
+    #[test]
+    fn test_report_selected_sections_in_order() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = report_cmd(&[ReportSection::Cycles, ReportSection::Hotspots, ReportSection::Cycles])
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        let titles: Vec<&str> = result.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["Cycles", "Hotspots"]);
+        assert!(result.graph.is_none());
+    }
+
+    #[test]
+    fn test_report_html_builds_dependency_graph() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let mut cmd = report_cmd(&[ReportSection::Hotspots]);
+        cmd.render = ReportRender::Html;
+
+        let graph = cmd
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed")
+            .graph
+            .expect("HTML builds the graph");
+        assert_eq!(graph.total_modules, graph.modules.len());
+        let controller = graph.modules.iter().find(|m| m.name == "MyApp.Controller").unwrap();
+        assert_eq!(controller.namespace, "MyApp.Controller");
+        assert!(controller.outgoing > 0);
+        assert!(graph
+            .dependencies
+            .iter()
+            .any(|d| d.source == "MyApp.Controller" && d.target == "MyApp.Accounts" && d.calls > 0));
+    }
+
+    #[test]
+    fn test_report_limit_and_thresholds() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let mut cmd = report_cmd(&[ReportSection::Hotspots, ReportSection::LargeFunctions]);
+        cmd.limit = 2;
+        cmd.min_lines = 1;
+
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert_eq!(result.sections[0].rows.len(), 2);
+        assert_eq!(result.sections[1].rows.len(), 2);
+        assert_eq!(result.sections[1].criteria, "at least 1 lines");
+    }
+
+    #[test]
+    fn test_report_empty_db() {
+        let db = db::test_utils::setup_empty_test_db();
+        assert!(report_cmd(&ReportSection::ALL).execute(&db, &QuerySettings::default()).is_err());
+    }
+}
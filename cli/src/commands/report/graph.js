@@ -0,0 +1,250 @@
+// Interactive module dependency graph of the HTML report.
+//
+// Embedded verbatim by `code_search report --render html`, so the report has
+// no external dependencies. Reads the graph from the #report-graph JSON
+// script, lays it out with a force simulation and draws it into svg#graph.
+// Clicking a module highlights its dependencies and lists the report rows
+// mentioning it; clicking such a row selects its module in the graph.
+(function () {
+  "use strict";
+
+  var SVG = "http://www.w3.org/2000/svg";
+  var data = JSON.parse(document.getElementById("report-graph").textContent);
+  var svg = document.getElementById("graph");
+  var details = document.getElementById("graph-details");
+  if (!svg || data.modules.length === 0) return;
+
+  var width = svg.clientWidth || 800;
+  var height = svg.clientHeight || 520;
+  var view = { x: 0, y: 0, scale: 1 };
+  var selected = null;
+
+  var nodes = data.modules.map(function (module, i) {
+    var angle = (2 * Math.PI * i) / data.modules.length;
+    var radius = Math.min(width, height) / 3;
+    return {
+      module: module,
+      x: width / 2 + radius * Math.cos(angle),
+      y: height / 2 + radius * Math.sin(angle),
+      r: 4 + Math.sqrt(module.incoming),
+      out: [],
+      in: []
+    };
+  });
+  var byName = {};
+  nodes.forEach(function (node) { byName[node.module.name] = node; });
+  var links = data.dependencies.map(function (dep) {
+    var link = { source: byName[dep.source], target: byName[dep.target], calls: dep.calls };
+    link.source.out.push(link);
+    link.target.in.push(link);
+    return link;
+  });
+
+  function el(name, attrs, parent) {
+    var e = document.createElementNS(SVG, name);
+    for (var key in attrs) e.setAttribute(key, attrs[key]);
+    if (parent) parent.appendChild(e);
+    return e;
+  }
+
+  function color(namespace) {
+    var hash = 0;
+    for (var i = 0; i < namespace.length; i++) hash = (hash * 31 + namespace.charCodeAt(i)) | 0;
+    return "hsl(" + (Math.abs(hash) % 360) + ", 60%, 50%)";
+  }
+
+  // Force layout: pairwise repulsion, springs along dependencies, pull to the center
+  function simulate(iterations) {
+    var k = Math.sqrt((width * height) / nodes.length) * 0.6;
+    for (var step = 0; step < iterations; step++) {
+      var temperature = (width / 10) * (1 - step / iterations);
+      nodes.forEach(function (n) { n.dx = (width / 2 - n.x) * 0.01; n.dy = (height / 2 - n.y) * 0.01; });
+      for (var i = 0; i < nodes.length; i++) {
+        for (var j = i + 1; j < nodes.length; j++) {
+          var a = nodes[i], b = nodes[j];
+          var dx = a.x - b.x, dy = a.y - b.y;
+          var d2 = Math.max(dx * dx + dy * dy, 1);
+          var f = (k * k) / d2;
+          a.dx += dx * f; a.dy += dy * f;
+          b.dx -= dx * f; b.dy -= dy * f;
+        }
+      }
+      links.forEach(function (l) {
+        var dx = l.target.x - l.source.x, dy = l.target.y - l.source.y;
+        var d = Math.max(Math.sqrt(dx * dx + dy * dy), 1);
+        var f = d / k;
+        l.source.dx += dx * f; l.source.dy += dy * f;
+        l.target.dx -= dx * f; l.target.dy -= dy * f;
+      });
+      nodes.forEach(function (n) {
+        var d = Math.max(Math.sqrt(n.dx * n.dx + n.dy * n.dy), 1);
+        var move = Math.min(d, temperature);
+        n.x += (n.dx / d) * move;
+        n.y += (n.dy / d) * move;
+      });
+    }
+  }
+
+  var scene = el("g", {}, svg);
+  var linkLayer = el("g", { "class": "links" }, scene);
+  var nodeLayer = el("g", { "class": "nodes" }, scene);
+
+  links.forEach(function (l) {
+    l.line = el("line", { "stroke-width": 1 + Math.log(l.calls) }, linkLayer);
+    el("title", {}, l.line).textContent = l.source.module.name + " → " + l.target.module.name + ": " + l.calls + " call(s)";
+  });
+  nodes.forEach(function (n) {
+    n.group = el("g", { "class": "node" }, nodeLayer);
+    n.circle = el("circle", { r: n.r, fill: color(n.module.namespace) }, n.group);
+    n.label = el("text", { dx: n.r + 2, dy: 4 }, n.group);
+    n.label.textContent = n.module.name;
+    el("title", {}, n.group).textContent =
+      n.module.name + "\nincoming calls: " + n.module.incoming + "\noutgoing calls: " + n.module.outgoing;
+    n.group.addEventListener("mousedown", function (event) { startDrag(event, n); });
+  });
+
+  function draw() {
+    scene.setAttribute("transform", "translate(" + view.x + "," + view.y + ") scale(" + view.scale + ")");
+    links.forEach(function (l) {
+      l.line.setAttribute("x1", l.source.x); l.line.setAttribute("y1", l.source.y);
+      l.line.setAttribute("x2", l.target.x); l.line.setAttribute("y2", l.target.y);
+    });
+    nodes.forEach(function (n) { n.group.setAttribute("transform", "translate(" + n.x + "," + n.y + ")"); });
+  }
+
+  // Report rows mentioning a module, by module name; a longer name wins over its parent namespace
+  var rows = Array.prototype.slice.call(document.querySelectorAll("tr[id]"));
+  var names = nodes.map(function (n) { return n.module.name; }).sort(function (a, b) { return b.length - a.length; });
+  var mentions = {};
+  rows.forEach(function (row) {
+    var text = row.textContent;
+    var first = null;
+    names.forEach(function (name) {
+      var at = text.indexOf(name);
+      while (at !== -1) {
+        var next = text.charAt(at + name.length);
+        var before = at === 0 ? "" : text.charAt(at - 1);
+        if (!/[A-Za-z0-9_]/.test(next) && !/[A-Za-z0-9_.]/.test(before)) {
+          (mentions[name] = mentions[name] || []).push(row);
+          if (first === null) first = name;
+          break;
+        }
+        at = text.indexOf(name, at + 1);
+      }
+    });
+    if (first !== null) {
+      row.classList.add("linked");
+      row.title = "Show " + first + " in the dependency graph";
+      row.addEventListener("click", function () {
+        select(byName[first]);
+        svg.scrollIntoView({ behavior: "smooth" });
+      });
+    }
+  });
+
+  function moduleList(title, entries) {
+    if (entries.length === 0) return;
+    var heading = document.createElement("h4");
+    heading.textContent = title;
+    details.appendChild(heading);
+    var list = document.createElement("ul");
+    entries.forEach(function (entry) {
+      var item = document.createElement("li");
+      var a = document.createElement("a");
+      a.href = entry.href || "#";
+      a.textContent = entry.text;
+      if (entry.node) a.addEventListener("click", function (event) { event.preventDefault(); select(entry.node); });
+      item.appendChild(a);
+      list.appendChild(item);
+    });
+    details.appendChild(list);
+  }
+
+  function select(node) {
+    selected = node;
+    var neighbours = {};
+    if (node) {
+      neighbours[node.module.name] = true;
+      node.out.forEach(function (l) { neighbours[l.target.module.name] = true; });
+      node.in.forEach(function (l) { neighbours[l.source.module.name] = true; });
+    }
+    nodes.forEach(function (n) {
+      n.group.classList.toggle("dimmed", !!node && !neighbours[n.module.name]);
+      n.group.classList.toggle("selected", n === node);
+    });
+    links.forEach(function (l) {
+      l.line.classList.toggle("dimmed", !!node && l.source !== node && l.target !== node);
+    });
+    rows.forEach(function (row) { row.classList.remove("mentioned"); });
+
+    details.textContent = "";
+    if (!node) {
+      details.textContent = "Click a module to see its dependencies and the report rows mentioning it.";
+      return;
+    }
+    var heading = document.createElement("h3");
+    heading.textContent = node.module.name;
+    details.appendChild(heading);
+    var counts = document.createElement("p");
+    counts.textContent = "Incoming calls: " + node.module.incoming + ", outgoing calls: " + node.module.outgoing;
+    details.appendChild(counts);
+
+    var byCalls = function (a, b) { return b.calls - a.calls; };
+    moduleList("Depends on", node.out.slice().sort(byCalls).map(function (l) {
+      return { text: l.target.module.name + " (" + l.calls + ")", node: l.target };
+    }));
+    moduleList("Used by", node.in.slice().sort(byCalls).map(function (l) {
+      return { text: l.source.module.name + " (" + l.calls + ")", node: l.source };
+    }));
+    var mentioned = mentions[node.module.name] || [];
+    mentioned.forEach(function (row) { row.classList.add("mentioned"); });
+    moduleList("In this report", mentioned.map(function (row) {
+      return { text: row.closest("section").querySelector("h2").textContent + ": " + row.cells[0].textContent, href: "#" + row.id };
+    }));
+  }
+
+  // Pan by dragging the background, move a module by dragging it, zoom with the wheel
+  var drag = null;
+  function point(event) {
+    return { x: (event.clientX - view.x) / view.scale, y: (event.clientY - view.y) / view.scale };
+  }
+  function startDrag(event, node) {
+    event.stopPropagation();
+    drag = { node: node, start: point(event), moved: false };
+  }
+  svg.addEventListener("mousedown", function (event) {
+    drag = { node: null, start: { x: event.clientX - view.x, y: event.clientY - view.y }, moved: false };
+  });
+  window.addEventListener("mousemove", function (event) {
+    if (!drag) return;
+    drag.moved = true;
+    if (drag.node) {
+      var p = point(event);
+      drag.node.x += p.x - drag.start.x;
+      drag.node.y += p.y - drag.start.y;
+      drag.start = p;
+    } else {
+      view.x = event.clientX - drag.start.x;
+      view.y = event.clientY - drag.start.y;
+    }
+    draw();
+  });
+  window.addEventListener("mouseup", function () {
+    if (drag && !drag.moved) select(drag.node === selected ? null : drag.node);
+    drag = null;
+  });
+  svg.addEventListener("wheel", function (event) {
+    event.preventDefault();
+    var rect = svg.getBoundingClientRect();
+    var mx = event.clientX - rect.left, my = event.clientY - rect.top;
+    var factor = event.deltaY < 0 ? 1.1 : 1 / 1.1;
+    view.x = mx - (mx - view.x) * factor;
+    view.y = my - (my - view.y) * factor;
+    view.scale *= factor;
+    draw();
+  }, { passive: false });
+
+  simulate(300);
+  draw();
+  select(null);
+})();
@@ -0,0 +1,112 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::{Args, ValueEnum};
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// A section of the report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportSection {
+    /// Functions with the most incoming calls
+    Hotspots,
+    /// Circular module dependencies
+    Cycles,
+    /// Modules with many functions and high connectivity
+    GodModules,
+    /// Functions over the line threshold
+    LargeFunctions,
+    /// Groups of functions with identical implementations
+    Duplicates,
+    /// Modules with high fan-in but low fan-out
+    Boundaries,
+}
+
+impl ReportSection {
+    pub const ALL: [ReportSection; 6] = [
+        ReportSection::Hotspots,
+        ReportSection::Cycles,
+        ReportSection::GodModules,
+        ReportSection::LargeFunctions,
+        ReportSection::Duplicates,
+        ReportSection::Boundaries,
+    ];
+
+    fn title(&self) -> &'static str {
+        match self {
+            ReportSection::Hotspots => "Hotspots",
+            ReportSection::Cycles => "Cycles",
+            ReportSection::GodModules => "God Modules",
+            ReportSection::LargeFunctions => "Large Functions",
+            ReportSection::Duplicates => "Duplicates",
+            ReportSection::Boundaries => "Boundaries",
+        }
+    }
+}
+
+/// Document format of the rendered report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ReportRender {
+    #[default]
+    Markdown,
+    Html,
+}
+
+/// Render a multi-section architecture report as Markdown or HTML
+///
+/// Combines hotspots, cycles, god modules, large functions, duplicates and
+/// boundaries into one document, suitable for a PR or an architecture review.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search report > ARCHITECTURE.md                 # Full Markdown report
+  code_search report --render html > report.html       # Same report as HTML
+  code_search report --sections hotspots,cycles        # Only selected sections
+  code_search report --min-lines 80 --min-functions 30 # Stricter thresholds
+  code_search report -l 20                             # Up to 20 rows per section
+")]
+pub struct ReportCmd {
+    /// Sections to include, in order
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = ReportSection::ALL)]
+    pub sections: Vec<ReportSection>,
+
+    /// Document format of the report
+    #[arg(long, value_enum, default_value_t = ReportRender::Markdown)]
+    pub render: ReportRender,
+
+    /// Minimum lines for the large functions section
+    #[arg(long, default_value = "50")]
+    pub min_lines: i64,
+
+    /// Minimum function count for the god modules section
+    #[arg(long, default_value = "20")]
+    pub min_functions: i64,
+
+    /// Minimum total connectivity for the god modules section
+    #[arg(long, default_value = "10")]
+    pub min_total: i64,
+
+    /// Minimum incoming/outgoing ratio for the boundaries section
+    #[arg(long, default_value = "2.0")]
+    pub min_ratio: f64,
+
+    /// Project to report on
+    #[arg(long, default_value = "default")]
+    pub project: String,
+
+    /// Maximum rows per section (1-1000)
+    #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..=1000))]
+    pub limit: u32,
+}
+
+impl CommandRunner for ReportCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,279 @@
+//! Markdown and HTML rendering for report command results.
+//!
+//! The HTML report is a single self-contained file: styles, the dependency
+//! graph data and the script drawing it (`graph.js`) are all inlined.
+
+use super::ReportRender;
+use super::execute::{ReportGraph, ReportResult, ReportTable, MAX_GRAPH_MODULES};
+use crate::output::Outputable;
+
+/// Script drawing the interactive dependency graph
+const GRAPH_SCRIPT: &str = include_str!("graph.js");
+
+const HTML_STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em auto; max-width: 72em; padding: 0 1em; color: #222; }
+nav a { margin-right: 1em; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+tr.linked { cursor: pointer; }
+tr.linked:hover, tr.mentioned, tr:target { background: #fff6d5; }
+.graph { display: flex; gap: 1em; }
+#graph { flex: 1; height: 520px; border: 1px solid #ccc; cursor: grab; }
+#graph-details { width: 20em; max-height: 520px; overflow-y: auto; font-size: 0.9em; }
+#graph line { stroke: #999; stroke-opacity: 0.6; }
+#graph .node { cursor: pointer; }
+#graph .node text { font-size: 10px; fill: #333; pointer-events: none; }
+#graph .selected circle { stroke: #000; stroke-width: 2px; }
+#graph .dimmed { opacity: 0.15; }
+";
+
+impl Outputable for ReportResult {
+    fn to_table(&self) -> String {
+        match self.render {
+            ReportRender::Markdown => self.to_markdown(),
+            ReportRender::Html => self.to_html(),
+        }
+    }
+}
+
+impl ReportResult {
+    fn to_markdown(&self) -> String {
+        let mut output = format!("# Architecture report: {}\n", self.project);
+
+        for table in &self.sections {
+            output.push_str(&format!("\n## {}\n\n_{}_\n\n", table.title, table.criteria));
+
+            if table.rows.is_empty() {
+                output.push_str("None found.\n");
+                continue;
+            }
+
+            output.push_str(&markdown_row(&table.columns));
+            let separator: Vec<String> = table.columns.iter().map(|_| "---".to_string()).collect();
+            output.push_str(&markdown_row(&separator));
+            for row in &table.rows {
+                output.push_str(&markdown_row(row));
+            }
+        }
+
+        output
+    }
+
+    fn to_html(&self) -> String {
+        let title = format!("Architecture report: {}", html_escape(&self.project));
+        let mut output = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{HTML_STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+        );
+
+        let mut links = Vec::new();
+        if self.graph.is_some() {
+            links.push("<a href=\"#dependency-graph\">Dependency graph</a>".to_string());
+        }
+        for table in &self.sections {
+            links.push(format!("<a href=\"#{}\">{}</a>", section_id(&table.title), html_escape(&table.title)));
+        }
+        output.push_str(&format!("<nav>{}</nav>\n", links.join("")));
+
+        if let Some(graph) = &self.graph {
+            output.push_str(&html_graph(graph));
+        }
+        for table in &self.sections {
+            output.push_str(&html_section(table));
+        }
+
+        if let Some(graph) = self.graph.as_ref().filter(|g| !g.modules.is_empty()) {
+            output.push_str(&format!(
+                "<script type=\"application/json\" id=\"report-graph\">{}</script>\n<script>\n{}</script>\n",
+                script_json(graph),
+                GRAPH_SCRIPT
+            ));
+        }
+
+        output.push_str("</body>\n</html>\n");
+        output
+    }
+}
+
+fn markdown_row(cells: &[String]) -> String {
+    let cells: Vec<String> = cells.iter().map(|c| c.replace('|', "\\|")).collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+/// Anchor of a section, its title in kebab case
+fn section_id(title: &str) -> String {
+    title.to_lowercase().replace(' ', "-")
+}
+
+fn html_graph(graph: &ReportGraph) -> String {
+    let criteria = if graph.total_modules > MAX_GRAPH_MODULES {
+        format!(
+            "the {} most connected of {} modules, by call sites crossing module boundaries",
+            graph.modules.len(),
+            graph.total_modules
+        )
+    } else {
+        format!("{} module(s) calling or called by other modules", graph.modules.len())
+    };
+    let mut output = format!(
+        "<section id=\"dependency-graph\">\n<h2>Dependency graph</h2>\n<p><em>{}</em></p>\n",
+        html_escape(&criteria)
+    );
+    if graph.modules.is_empty() {
+        output.push_str("<p>None found.</p>\n");
+    } else {
+        output.push_str("<div class=\"graph\"><svg id=\"graph\"></svg><aside id=\"graph-details\"></aside></div>\n");
+    }
+    output.push_str("</section>\n");
+    output
+}
+
+/// Graph as JSON safe to inline in a script element
+fn script_json(graph: &ReportGraph) -> String {
+    serde_json::to_string(graph)
+        .unwrap_or_else(|_| "{\"modules\":[],\"dependencies\":[]}".to_string())
+        .replace('<', "\\u003c")
+}
+
+fn html_section(table: &ReportTable) -> String {
+    let id = section_id(&table.title);
+    let mut output = format!(
+        "<section id=\"{}\">\n<h2>{}</h2>\n<p><em>{}</em></p>\n",
+        id,
+        html_escape(&table.title),
+        html_escape(&table.criteria)
+    );
+
+    if table.rows.is_empty() {
+        output.push_str("<p>None found.</p>\n</section>\n");
+        return output;
+    }
+
+    output.push_str("<table>\n<tr>");
+    for column in &table.columns {
+        output.push_str(&format!("<th>{}</th>", html_escape(column)));
+    }
+    output.push_str("</tr>\n");
+    for (i, row) in table.rows.iter().enumerate() {
+        output.push_str(&format!("<tr id=\"{}-{}\">", id, i + 1));
+        for cell in row {
+            output.push_str(&format!("<td>{}</td>", html_escape(cell)));
+        }
+        output.push_str("</tr>\n");
+    }
+    output.push_str("</table>\n</section>\n");
+    output
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::report::execute::{GraphDependency, GraphModule};
+
+    fn result(render: ReportRender) -> ReportResult {
+        ReportResult {
+            project: "default".to_string(),
+            render,
+            sections: vec![
+                ReportTable {
+                    title: "Hotspots".to_string(),
+                    criteria: "most incoming calls".to_string(),
+                    columns: vec!["Function".to_string(), "Incoming".to_string()],
+                    rows: vec![vec!["MyApp.Repo.get".to_string(), "3".to_string()]],
+                },
+                ReportTable {
+                    title: "Cycles".to_string(),
+                    criteria: "0 cycle(s)".to_string(),
+                    columns: vec!["Length".to_string(), "Cycle".to_string()],
+                    rows: vec![],
+                },
+            ],
+            graph: None,
+        }
+    }
+
+    #[test]
+    fn test_report_markdown() {
+        let output = result(ReportRender::Markdown).to_table();
+        assert!(output.starts_with("# Architecture report: default\n"));
+        assert!(output.contains("## Hotspots\n\n_most incoming calls_\n\n"));
+        assert!(output.contains("| Function | Incoming |\n| --- | --- |\n| MyApp.Repo.get | 3 |\n"));
+        assert!(output.contains("## Cycles\n\n_0 cycle(s)_\n\nNone found.\n"));
+    }
+
+    #[test]
+    fn test_report_markdown_escapes_pipes() {
+        assert_eq!(markdown_row(&["a|b".to_string()]), "| a\\|b |\n");
+    }
+
+    #[test]
+    fn test_report_html() {
+        let mut result = result(ReportRender::Html);
+        result.sections[0].rows[0][0] = "Kernel.<>/2".to_string();
+
+        let output = result.to_table();
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains("<h2>Hotspots</h2>"));
+        assert!(output.contains("<tr><th>Function</th><th>Incoming</th></tr>"));
+        assert!(output.contains("<td>Kernel.&lt;&gt;/2</td>"));
+        assert!(output.contains("<p>None found.</p>"));
+        assert!(output.ends_with("</body>\n</html>\n"));
+    }
+
+    #[test]
+    fn test_report_html_anchors() {
+        let output = result(ReportRender::Html).to_table();
+        assert!(output.contains("<nav><a href=\"#hotspots\">Hotspots</a><a href=\"#cycles\">Cycles</a></nav>"));
+        assert!(output.contains("<section id=\"hotspots\">\n<h2>Hotspots</h2>"));
+        assert!(output.contains("<tr id=\"hotspots-1\"><td>MyApp.Repo.get</td>"));
+        assert!(!output.contains("<script"));
+    }
+
+    #[test]
+    fn test_report_html_embeds_graph() {
+        let mut result = result(ReportRender::Html);
+        result.graph = Some(ReportGraph {
+            total_modules: 2,
+            modules: vec![
+                GraphModule {
+                    name: "MyApp.Accounts".to_string(),
+                    namespace: "MyApp.Accounts".to_string(),
+                    incoming: 0,
+                    outgoing: 3,
+                },
+                GraphModule {
+                    name: "MyApp.Repo</script>".to_string(),
+                    namespace: "MyApp.Repo</script>".to_string(),
+                    incoming: 3,
+                    outgoing: 0,
+                },
+            ],
+            dependencies: vec![GraphDependency {
+                source: "MyApp.Accounts".to_string(),
+                target: "MyApp.Repo</script>".to_string(),
+                calls: 3,
+            }],
+        });
+
+        let output = result.to_table();
+        assert!(output.contains("<a href=\"#dependency-graph\">Dependency graph</a>"));
+        assert!(output.contains("<em>2 module(s) calling or called by other modules</em>"));
+        assert!(output.contains("<svg id=\"graph\"></svg>"));
+        assert!(output.contains("\"source\":\"MyApp.Accounts\",\"target\":\"MyApp.Repo\\u003c/script>\""));
+        assert_eq!(output.matches("</script>").count(), 2);
+        assert!(output.contains(GRAPH_SCRIPT));
+    }
+
+    #[test]
+    fn test_report_json() {
+        let json = serde_json::to_string(&result(ReportRender::Html)).unwrap();
+        assert!(json.contains("\"title\":\"Hotspots\""));
+        assert!(!json.contains("render"));
+    }
+}
@@ -0,0 +1,56 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::RestoreCmd;
+use crate::commands::Execute;
+use db::queries::backup::{restore_database, RelationRows};
+use db::query_builders::QuerySettings;
+
+/// Result of the restore command
+#[derive(Debug, Serialize)]
+pub struct RestoreResult {
+    pub path: String,
+    pub relations: Vec<RelationRows>,
+}
+
+impl Execute for RestoreCmd {
+    type Output = RestoreResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let relations = restore_database(db, &self.path)?;
+        Ok(RestoreResult {
+            path: self.path.display().to_string(),
+            relations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::BackupCmd;
+    use rstest::{fixture, rstest};
+
+    crate::shared_fixture! {
+        fixture_name: populated_db,
+        fixture_type: call_graph,
+        project: "test_project",
+    }
+
+    #[rstest]
+    fn test_backup_then_restore(populated_db: db::DbInstance) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("code_search.bak");
+
+        let backup = BackupCmd { path: path.clone() }
+            .execute(&populated_db, &QuerySettings::default())
+            .expect("Backup should succeed");
+
+        let target = db::open_mem_db();
+        let restored = RestoreCmd { path }.execute(&target, &QuerySettings::default()).expect("Restore should succeed");
+
+        let rows = |relations: &[RelationRows]| relations.iter().map(|r| (r.relation.clone(), r.rows)).collect::<Vec<_>>();
+        assert_eq!(rows(&restored.relations), rows(&backup.relations));
+    }
+}
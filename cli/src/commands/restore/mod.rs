@@ -0,0 +1,35 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Restore the database from a backup file
+///
+/// Creates the current schema and replaces every relation found in the
+/// backup, so data already in the database is overwritten and backups from
+/// older versions restore into newer schemas.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search restore code_search.bak                 # Restore into the default database
+  code_search --db fresh.sqlite restore code_search.bak
+")]
+pub struct RestoreCmd {
+    /// Backup file created by `backup`
+    pub path: PathBuf,
+}
+
+impl CommandRunner for RestoreCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
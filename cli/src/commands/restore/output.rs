@@ -0,0 +1,11 @@
+//! Output formatting for restore command results.
+
+use super::execute::RestoreResult;
+use crate::commands::backup::format_relations;
+use crate::output::Outputable;
+
+impl Outputable for RestoreResult {
+    fn to_table(&self) -> String {
+        format_relations(&format!("Restored from {}", self.path), &self.relations)
+    }
+}
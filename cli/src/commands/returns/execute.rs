@@ -5,6 +5,7 @@ use serde::Serialize;
 use super::ReturnsCmd;
 use crate::commands::Execute;
 use db::queries::returns::{find_returns, ReturnEntry};
+use db::query_builders::QuerySettings;
 use db::types::ModuleGroupResult;
 
 /// A function's return type information
@@ -46,14 +47,16 @@ fn build_return_info_result(
 impl Execute for ReturnsCmd {
     type Output = ModuleGroupResult<ReturnInfo>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         let entries = find_returns(
             db,
             &self.pattern,
+            self.accepts.as_deref(),
             &self.common.project,
             self.common.regex,
             self.module.as_deref(),
             self.common.limit,
+            settings,
         )?;
 
         Ok(build_return_info_result(
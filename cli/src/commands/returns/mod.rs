@@ -4,6 +4,7 @@ mod output;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
@@ -15,23 +16,33 @@ use crate::output::{OutputFormat, Outputable};
 Examples:
   code_search returns \"User.t\"              # Find functions returning User.t
   code_search returns \"nil\"                 # Find functions returning nil
-  code_search returns \"{:error\" MyApp       # Filter to module MyApp
+  code_search returns \"{:error, _}\" MyApp   # Filter to module MyApp
+  code_search returns \"{:ok, _} | {:error, _}\"
+  code_search returns \"{:ok, User.t}\" --accepts \"map\"  # Conversions
   code_search returns -r \"list\\(.*\\)\"     # Regex pattern matching
 ")]
 pub struct ReturnsCmd {
-    /// Type pattern to search for in return types
+    /// Type pattern the return type has to match
+    ///
+    /// Written like a type, with _ for any type: User.t, {:ok, _},
+    /// {:ok, _} | {:error, _}. A union pattern needs each of its members among
+    /// the returned types. With --regex, a regex over the return type instead.
     pub pattern: String,
 
     /// Module filter pattern
     pub module: Option<String>,
 
+    /// Type pattern one of the parameters has to match as well
+    #[arg(long, value_name = "PATTERN")]
+    pub accepts: Option<String>,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for ReturnsCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
@@ -44,6 +44,15 @@ mod tests {
         expected: "get",
     }
 
+    crate::cli_option_test! {
+        command: "reverse-trace",
+        variant: ReverseTrace,
+        test_name: test_with_certain_only,
+        args: ["MyApp.Repo", "get", "--certain-only"],
+        field: certain_only,
+        expected: true,
+    }
+
     crate::cli_option_test! {
         command: "reverse-trace",
         variant: ReverseTrace,
@@ -62,6 +71,15 @@ mod tests {
         expected: 50,
     }
 
+    crate::cli_option_test! {
+        command: "reverse-trace",
+        variant: ReverseTrace,
+        test_name: test_with_memory_engine,
+        args: ["MyApp", "foo", "--engine", "memory"],
+        field: engine,
+        expected: crate::commands::Engine::Memory,
+    }
+
     // =========================================================================
     // Limit validation tests
     // =========================================================================
@@ -3,7 +3,7 @@
 #[cfg(test)]
 mod tests {
     use super::super::ReverseTraceCmd;
-    use crate::commands::CommonArgs;
+    use crate::commands::{CommonArgs, Engine};
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -25,10 +25,13 @@ mod tests {
             function: "get".to_string(),
             arity: None,
             depth: 1,
+            engine: Engine::Datalog,
+            certain_only: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -47,10 +50,13 @@ mod tests {
             function: "get".to_string(),
             arity: None,
             depth: 2,
+            engine: Engine::Datalog,
+            certain_only: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -67,10 +73,13 @@ mod tests {
             function: "send_email".to_string(),
             arity: None,
             depth: 5,
+            engine: Engine::Datalog,
+            certain_only: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -78,6 +87,119 @@ mod tests {
         },
     }
 
+    // Same traversal answered from the in-memory adjacency index
+    crate::execute_test! {
+        test_name: test_reverse_trace_memory_engine,
+        fixture: populated_db,
+        cmd: ReverseTraceCmd {
+            module: "MyApp.Repo".to_string(),
+            function: "get".to_string(),
+            arity: None,
+            depth: 2,
+            engine: Engine::Memory,
+            certain_only: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert_eq!(result.total_items, 5);
+        },
+    }
+
+    // Both engines start the trace only from a target in the namespace
+    #[rstest]
+    #[case(Engine::Datalog, "MyApp.Accounts", 0)]
+    #[case(Engine::Memory, "MyApp.Accounts", 0)]
+    #[case(Engine::Datalog, "MyApp.Repo", 5)]
+    #[case(Engine::Memory, "MyApp.Repo", 5)]
+    fn test_reverse_trace_in_namespace(
+        populated_db: db::DbInstance,
+        #[case] engine: Engine,
+        #[case] namespace: &str,
+        #[case] expected: usize,
+    ) {
+        use crate::commands::Execute;
+        use db::query_builders::QuerySettings;
+
+        let cmd = ReverseTraceCmd {
+            module: "MyApp.Repo".to_string(),
+            function: "get".to_string(),
+            arity: None,
+            depth: 2,
+            engine,
+            certain_only: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: vec![namespace.to_string()],
+            },
+        };
+        let settings = QuerySettings { namespaces: cmd.common.namespace.clone(), ..Default::default() };
+        let result = cmd.execute(&populated_db, &settings);
+
+        assert_eq!(result.expect("Execute should succeed").total_items, expected);
+    }
+
+    // The memory engine prunes by namespace before applying the limit, so a
+    // small limit returns the same rows from both engines
+    #[rstest]
+    fn test_reverse_trace_in_namespace_with_limit_engines_agree(populated_db: db::DbInstance) {
+        use crate::commands::Execute;
+        use db::query_builders::QuerySettings;
+
+        let run = |engine| {
+            let cmd = ReverseTraceCmd {
+                module: "MyApp\\..*".to_string(),
+                function: ".*".to_string(),
+                arity: None,
+                depth: 2,
+                engine,
+                certain_only: false,
+                common: CommonArgs {
+                    project: "test_project".to_string(),
+                    regex: true,
+                    limit: 1,
+                    namespace: vec!["MyApp.Service".to_string()],
+                },
+            };
+            let settings = QuerySettings { namespaces: cmd.common.namespace.clone(), ..Default::default() };
+            cmd.execute(&populated_db, &settings).expect("Execute should succeed")
+        };
+
+        let (expected, actual) = (run(Engine::Datalog), run(Engine::Memory));
+        assert_eq!(expected.total_items, 1);
+        assert_eq!(serde_json::to_string(&actual).unwrap(), serde_json::to_string(&expected).unwrap());
+    }
+
+    // Service.fetch calls do_fetch in a branch: fetch and its callers drop out
+    crate::execute_test! {
+        test_name: test_reverse_trace_certain_only,
+        fixture: populated_db,
+        cmd: ReverseTraceCmd {
+            module: "MyApp.Repo".to_string(),
+            function: "get".to_string(),
+            arity: None,
+            depth: 5,
+            engine: Engine::Memory,
+            certain_only: true,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert_eq!(result.total_items, 4);
+            assert!(result.entries.iter().all(|e| e.function != "fetch" && e.function != "process"));
+        },
+    }
+
     // =========================================================================
     // No match / empty result tests
     // =========================================================================
@@ -90,10 +212,13 @@ mod tests {
             function: "foo".to_string(),
             arity: None,
             depth: 5,
+            engine: Engine::Datalog,
+            certain_only: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: entries,
@@ -110,10 +235,13 @@ mod tests {
             function: "foo".to_string(),
             arity: None,
             depth: 5,
+            engine: Engine::Datalog,
+            certain_only: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
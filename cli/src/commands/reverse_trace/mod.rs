@@ -7,9 +7,10 @@ mod output_tests;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
-use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::commands::{CommandRunner, CommonArgs, Engine, Execute};
 use crate::output::{OutputFormat, Outputable};
 
 /// Trace call chains backwards - who calls the callers of a target
@@ -19,6 +20,8 @@ Examples:
   code_search reverse-trace MyApp.Repo get           # Who ultimately calls Repo.get?
   code_search reverse-trace Ecto.Repo insert --depth 10  # Deeper traversal
   code_search reverse-trace -r 'MyApp\\..*' 'handle_.*'  # Regex pattern
+  code_search reverse-trace MyApp.Repo get --engine memory  # In-memory traversal
+  code_search reverse-trace MyApp.Repo get --certain-only   # Callers that always reach it
 ")]
 pub struct ReverseTraceCmd {
     /// Target module name (exact match or pattern with --regex)
@@ -35,13 +38,21 @@ pub struct ReverseTraceCmd {
     #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..=20))]
     pub depth: u32,
 
+    /// Traversal engine (memory reuses an adjacency index across traversals)
+    #[arg(long, value_enum, default_value_t = Engine::Datalog)]
+    pub engine: Engine,
+
+    /// Only follow calls made on every path, not in a conditional branch or comprehension
+    #[arg(long)]
+    pub certain_only: bool,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for ReverseTraceCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
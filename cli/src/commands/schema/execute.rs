@@ -0,0 +1,59 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::{SchemaAction, SchemaCmd};
+use crate::commands::Execute;
+use db::queries::schema::{repair_schema, verify_schema, SchemaRepair, SchemaVerification};
+use db::query_builders::QuerySettings;
+
+/// Result of `schema verify`
+#[derive(Debug, Serialize)]
+pub struct SchemaResult {
+    /// Fixes applied with --repair, before verifying
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub repairs: Vec<SchemaRepair>,
+    #[serde(flatten)]
+    pub verification: SchemaVerification,
+}
+
+impl Execute for SchemaCmd {
+    type Output = SchemaResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let SchemaAction::Verify { repair } = self.action;
+        let repairs = if repair { repair_schema(db)? } else { Vec::new() };
+        Ok(SchemaResult {
+            repairs,
+            verification: verify_schema(db)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::import::create_schema;
+
+    fn verify(repair: bool) -> SchemaCmd {
+        SchemaCmd {
+            action: SchemaAction::Verify { repair },
+        }
+    }
+
+    #[test]
+    fn test_verify_reports_missing_relations() {
+        let db = db::open_mem_db();
+        create_schema(&db).unwrap();
+        db.run_script_str("::remove annotations", "", false);
+
+        let result = verify(false).execute(&db, &QuerySettings::default()).expect("Verify should succeed");
+        assert!(result.repairs.is_empty());
+        assert_eq!(result.verification.missing_relations, ["annotations"]);
+
+        let result = verify(true).execute(&db, &QuerySettings::default()).expect("Repair should succeed");
+        assert_eq!(result.repairs.len(), 1);
+        assert!(result.repairs[0].created);
+        assert!(result.verification.matches());
+    }
+}
@@ -0,0 +1,51 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::{Args, Subcommand};
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CheckFailed, CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Compare the stored relations with the schema of this version
+///
+/// `schema verify` lists managed relations the database lacks and stored
+/// relations with missing, extra or retyped columns, and exits nonzero when
+/// any remain. --repair applies the fixes that keep every row: creating
+/// missing relations and adding missing columns that have a default.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search schema verify                   # Report schema drift
+  code_search schema verify --repair          # Apply the safe fixes, then report
+")]
+pub struct SchemaCmd {
+    #[command(subcommand)]
+    pub action: SchemaAction,
+}
+
+/// What the schema command does
+#[derive(Subcommand, Debug)]
+pub enum SchemaAction {
+    /// Report relations and columns that differ from the schema
+    Verify {
+        /// Create missing relations and add missing columns that have a default
+        #[arg(long, default_value_t = false)]
+        repair: bool,
+    },
+}
+
+impl CommandRunner for SchemaCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        let report = result.format(format);
+        if result.verification.matches() {
+            Ok(report)
+        } else {
+            Err(Box::new(CheckFailed { report }))
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Output formatting for schema command results.
+
+use super::execute::SchemaResult;
+use crate::output::Outputable;
+
+impl Outputable for SchemaResult {
+    fn to_table(&self) -> String {
+        let mut output = String::from("Schema Verification\n");
+
+        if !self.repairs.is_empty() {
+            output.push_str("\nRepaired:\n");
+            for repair in &self.repairs {
+                if repair.created {
+                    output.push_str(&format!("  {}: created\n", repair.relation));
+                } else {
+                    output.push_str(&format!("  {}: added {}\n", repair.relation, repair.added_columns.join(", ")));
+                }
+            }
+        }
+
+        let verification = &self.verification;
+        if !verification.missing_relations.is_empty() {
+            output.push_str(&format!("\nMissing relations: {}\n", verification.missing_relations.join(", ")));
+        }
+        if !verification.mismatches.is_empty() {
+            output.push_str("\nRelations differing from this version's schema:\n");
+            for mismatch in &verification.mismatches {
+                output.push_str(&format!("  {}: {}\n", mismatch.relation, mismatch.summary()));
+            }
+        }
+
+        if verification.matches() {
+            output.push_str("\nThe database matches the schema.\n");
+        } else if self.repairs.is_empty() {
+            output.push_str("\nRun `schema verify --repair` to apply the safe fixes; back up the data and import into a new database for the rest.\n");
+        } else {
+            output.push_str("\nThe rest cannot be fixed without losing data; back up the data and import into a new database.\n");
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::schema::{SchemaMismatch, SchemaRepair, SchemaVerification, TypeDrift};
+
+    #[test]
+    fn test_to_table_with_drift() {
+        let result = SchemaResult {
+            repairs: vec![SchemaRepair {
+                relation: "modules".to_string(),
+                created: false,
+                added_columns: vec!["app".to_string(), "is_external".to_string()],
+            }],
+            verification: SchemaVerification {
+                missing_relations: Vec::new(),
+                mismatches: vec![SchemaMismatch {
+                    relation: "behaviours".to_string(),
+                    missing: Vec::new(),
+                    unexpected: vec!["legacy".to_string()],
+                    type_drift: vec![TypeDrift {
+                        column: "behaviour".to_string(),
+                        expected: "String".to_string(),
+                        stored: "Int".to_string(),
+                    }],
+                }],
+            },
+        };
+        assert_eq!(
+            result.to_table(),
+            "\
+Schema Verification
+
+Repaired:
+  modules: added app, is_external
+
+Relations differing from this version's schema:
+  behaviours: unexpected legacy; behaviour is Int, expected String
+
+The rest cannot be fixed without losing data; back up the data and import into a new database.
+"
+        );
+    }
+}
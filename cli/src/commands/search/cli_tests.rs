@@ -48,6 +48,45 @@ mod tests {
         expected: 50,
     }
 
+    crate::cli_option_test! {
+        command: "search",
+        variant: Search,
+        test_name: test_search_with_ignore_case,
+        args: ["user", "-i"],
+        field: ignore_case,
+        expected: true,
+    }
+
+    crate::cli_option_test! {
+        command: "search",
+        variant: Search,
+        test_name: test_search_with_fuzzy,
+        args: ["usr", "--fuzzy"],
+        field: fuzzy,
+        expected: true,
+    }
+
+    #[rstest]
+    fn test_search_fuzzy_conflicts_with_regex() {
+        let result = Args::try_parse_from(["code_search", "search", "usr", "--fuzzy", "--regex"]);
+        assert!(result.is_err());
+    }
+
+    crate::cli_option_test! {
+        command: "search",
+        variant: Search,
+        test_name: test_search_with_semantic,
+        args: ["load user", "--semantic"],
+        field: semantic,
+        expected: true,
+    }
+
+    #[rstest]
+    fn test_search_semantic_conflicts_with_fuzzy() {
+        let result = Args::try_parse_from(["code_search", "search", "usr", "--semantic", "--fuzzy"]);
+        assert!(result.is_err());
+    }
+
     // =========================================================================
     // Limit validation tests
     // =========================================================================
@@ -89,4 +128,15 @@ mod tests {
             _ => panic!("Expected Search command"),
         }
     }
+
+    #[rstest]
+    fn test_search_kind_docs() {
+        let args = Args::try_parse_from(["code_search", "search", "password", "--kind", "docs"]).unwrap();
+        match args.command {
+            crate::commands::Command::Search(cmd) => {
+                assert!(matches!(cmd.kind, SearchKind::Docs));
+            }
+            _ => panic!("Expected Search command"),
+        }
+    }
 }
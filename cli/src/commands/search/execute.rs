@@ -1,11 +1,17 @@
-use std::collections::BTreeMap;
 use std::error::Error;
 
 use serde::Serialize;
 
 use super::{SearchCmd, SearchKind};
 use crate::commands::Execute;
-use db::queries::search::{search_functions, search_modules, FunctionResult as RawFunctionResult, ModuleResult};
+use crate::config::{Config, DEFAULT_CONFIG_PATH};
+use db::queries::docs::{search_docs, DocMatch};
+use db::queries::embeddings::semantic_search;
+use db::queries::search::{
+    fuzzy_search_functions, fuzzy_search_modules, search_functions, search_functions_ignore_case, search_modules,
+    search_modules_ignore_case, FunctionResult as RawFunctionResult, ModuleResult,
+};
+use db::query_builders::QuerySettings;
 
 /// A function found in search results
 #[derive(Debug, Clone, Serialize)]
@@ -14,6 +20,9 @@ pub struct SearchFunc {
     pub arity: i64,
     #[serde(skip_serializing_if = "String::is_empty")]
     pub return_type: String,
+    /// Similarity to the pattern, only with --fuzzy or --semantic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
 }
 
 /// A module containing functions in search results
@@ -28,43 +37,54 @@ pub struct SearchFuncModule {
 pub struct SearchResult {
     pub pattern: String,
     pub kind: String,
+    /// "ignore-case", "fuzzy" or "semantic"; absent for plain matching
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub modules: Vec<ModuleResult>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total_functions: Option<usize>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub function_modules: Vec<SearchFuncModule>,
+    /// Matching docs, most relevant first (only with --kind docs)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<DocMatch>,
 }
 
 impl SearchResult {
     /// Build grouped function result from flat list
-    fn from_functions(pattern: String, functions: Vec<RawFunctionResult>) -> Self {
+    fn from_functions(pattern: String, mode: Option<String>, functions: Vec<RawFunctionResult>) -> Self {
         let total = functions.len();
 
-        // Group by module (BTreeMap for consistent ordering)
-        let mut module_map: BTreeMap<String, Vec<SearchFunc>> = BTreeMap::new();
+        // Group by module in result order: queries sort by module, fuzzy and
+        // semantic search by score, so modules with the best matches come first
+        let mut function_modules: Vec<SearchFuncModule> = Vec::new();
 
         for func in functions {
             let search_func = SearchFunc {
                 name: func.name,
                 arity: func.arity,
                 return_type: func.return_type,
+                score: func.score,
             };
 
-            module_map.entry(func.module).or_default().push(search_func);
+            match function_modules.iter_mut().find(|m| m.name == func.module) {
+                Some(module) => module.functions.push(search_func),
+                None => function_modules.push(SearchFuncModule {
+                    name: func.module,
+                    functions: vec![search_func],
+                }),
+            }
         }
 
-        let function_modules: Vec<SearchFuncModule> = module_map
-            .into_iter()
-            .map(|(name, functions)| SearchFuncModule { name, functions })
-            .collect();
-
         SearchResult {
             pattern,
             kind: "functions".to_string(),
+            mode,
             modules: vec![],
             total_functions: if total > 0 { Some(total) } else { None },
             function_modules,
+            docs: vec![],
         }
     }
 }
@@ -72,22 +92,92 @@ impl SearchResult {
 impl Execute for SearchCmd {
     type Output = SearchResult;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let (project, limit, regex) = (&self.common.project, self.common.limit, self.common.regex);
+        if self.semantic {
+            return semantic_search_functions(db, self.pattern, project, limit);
+        }
+
+        let mode = if self.fuzzy {
+            Some("fuzzy".to_string())
+        } else if self.ignore_case {
+            Some("ignore-case".to_string())
+        } else {
+            None
+        };
+
         match self.kind {
             SearchKind::Modules => {
-                let modules = search_modules(db, &self.pattern, &self.common.project, self.common.limit, self.common.regex)?;
+                let modules = if self.fuzzy {
+                    fuzzy_search_modules(db, &self.pattern, project, limit, settings)?
+                } else if self.ignore_case {
+                    search_modules_ignore_case(db, &self.pattern, project, limit, regex, settings)?
+                } else {
+                    search_modules(db, &self.pattern, project, limit, regex, settings)?
+                };
                 Ok(SearchResult {
                     pattern: self.pattern,
                     kind: "modules".to_string(),
+                    mode,
                     modules,
                     total_functions: None,
                     function_modules: vec![],
+                    docs: vec![],
                 })
             }
             SearchKind::Functions => {
-                let functions = search_functions(db, &self.pattern, &self.common.project, self.common.limit, self.common.regex)?;
-                Ok(SearchResult::from_functions(self.pattern, functions))
+                let functions = if self.fuzzy {
+                    fuzzy_search_functions(db, &self.pattern, project, limit, settings)?
+                } else if self.ignore_case {
+                    search_functions_ignore_case(db, &self.pattern, project, limit, regex, settings)?
+                } else {
+                    search_functions(db, &self.pattern, project, limit, regex, settings)?
+                };
+                Ok(SearchResult::from_functions(self.pattern, mode, functions))
+            }
+            SearchKind::Docs => {
+                // The FTS index matches words (lowercased and stemmed), not patterns
+                if regex || self.fuzzy {
+                    return Err("--kind docs does not support --regex or --fuzzy".into());
+                }
+                let docs = search_docs(db, &self.pattern, project, limit)?;
+                Ok(SearchResult {
+                    pattern: self.pattern,
+                    kind: "docs".to_string(),
+                    docs,
+                    ..SearchResult::default()
+                })
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Embed the query with the configured provider and rank functions by similarity
+fn semantic_search_functions(
+    db: &db::DbInstance,
+    pattern: String,
+    project: &str,
+    limit: u32,
+) -> Result<SearchResult, Box<dyn Error>> {
+    let embedder = Config::load_or_default(std::path::Path::new(DEFAULT_CONFIG_PATH))?
+        .embeddings
+        .embedder()?;
+    let query = embedder
+        .embed(std::slice::from_ref(&pattern))?
+        .pop()
+        .ok_or("Embedding provider returned no vector for the query")?;
+
+    let functions = semantic_search(db, project, &embedder.model_id(), &query, limit)?
+        .into_iter()
+        .map(|m| RawFunctionResult {
+            project: project.to_string(),
+            module: m.module,
+            name: m.name,
+            arity: m.arity,
+            return_type: String::new(),
+            score: Some(m.score),
+        })
+        .collect();
+
+    Ok(SearchResult::from_functions(pattern, Some("semantic".to_string()), functions))
+}
@@ -4,6 +4,7 @@
 mod tests {
     use super::super::{SearchCmd, SearchKind};
     use crate::commands::CommonArgs;
+    use db::query_builders::QuerySettings;
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -23,10 +24,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: ".*MyApp.*".to_string(), // Use regex for substring matching
             kind: SearchKind::Modules,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -42,10 +47,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: ".*user.*".to_string(), // Use regex for substring matching
             kind: SearchKind::Functions,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -61,10 +70,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: ".*get.*".to_string(), // Use regex for substring matching
             kind: SearchKind::Functions,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -78,10 +91,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: "^get_user$".to_string(),
             kind: SearchKind::Functions,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -102,10 +119,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: "\\.(Accounts|Users)$".to_string(),
             kind: SearchKind::Modules,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -120,10 +141,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: "MyApp.Accounts".to_string(),
             kind: SearchKind::Modules,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -139,10 +164,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: "get_user".to_string(),
             kind: SearchKind::Functions,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -163,10 +192,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: "user".to_string(), // Won't match get_user, list_users, etc.
             kind: SearchKind::Functions,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: function_modules,
@@ -182,10 +215,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: "NonExistent".to_string(),
             kind: SearchKind::Modules,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: modules,
@@ -197,10 +234,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: "^xyz".to_string(),
             kind: SearchKind::Functions,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: function_modules,
@@ -216,10 +257,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: "App".to_string(),
             kind: SearchKind::Modules,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         collection: modules,
@@ -232,10 +277,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: ".*user.*".to_string(), // Use regex for substring matching
             kind: SearchKind::Functions,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 1,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -244,6 +293,55 @@ mod tests {
         },
     }
 
+    crate::execute_test! {
+        test_name: test_search_modules_ignore_case,
+        fixture: populated_db,
+        cmd: SearchCmd {
+            pattern: "myapp.accounts".to_string(),
+            kind: SearchKind::Modules,
+            ignore_case: true,
+            fuzzy: false,
+            semantic: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert_eq!(result.mode.as_deref(), Some("ignore-case"));
+            assert_eq!(result.modules.len(), 1);
+            assert_eq!(result.modules[0].name, "MyApp.Accounts");
+        },
+    }
+
+    // The closest names come first, grouped by module in rank order
+    crate::execute_test! {
+        test_name: test_search_functions_fuzzy,
+        fixture: populated_db,
+        cmd: SearchCmd {
+            pattern: "get_usr".to_string(),
+            kind: SearchKind::Functions,
+            ignore_case: false,
+            fuzzy: true,
+            semantic: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert_eq!(result.mode.as_deref(), Some("fuzzy"));
+            let best = &result.function_modules[0];
+            assert_eq!(best.name, "MyApp.Accounts");
+            assert_eq!(best.functions[0].name, "get_user");
+            assert!(best.functions[0].score.is_some());
+        },
+    }
+
     // =========================================================================
     // Error handling tests
     // =========================================================================
@@ -253,10 +351,14 @@ mod tests {
         cmd: SearchCmd {
             pattern: "test".to_string(),
             kind: SearchKind::Modules,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
@@ -268,14 +370,18 @@ mod tests {
         let cmd = SearchCmd {
             pattern: "[invalid".to_string(), // Unclosed bracket
             kind: SearchKind::Modules,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         };
 
-        let result = cmd.execute(&populated_db);
+        let result = cmd.execute(&populated_db, &QuerySettings::default());
         assert!(result.is_err(), "Should reject invalid regex pattern");
 
         let err = result.unwrap_err();
@@ -291,14 +397,18 @@ mod tests {
         let cmd = SearchCmd {
             pattern: "*invalid".to_string(), // Invalid repetition
             kind: SearchKind::Functions,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         };
 
-        let result = cmd.execute(&populated_db);
+        let result = cmd.execute(&populated_db, &QuerySettings::default());
         assert!(result.is_err(), "Should reject invalid regex pattern");
 
         let err = result.unwrap_err();
@@ -315,14 +425,129 @@ mod tests {
         let cmd = SearchCmd {
             pattern: "[invalid".to_string(),
             kind: SearchKind::Modules,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false, // Not using regex mode
                 limit: 100,
+                namespace: Vec::new(),
             },
         };
 
-        let result = cmd.execute(&populated_db);
+        let result = cmd.execute(&populated_db, &QuerySettings::default());
         assert!(result.is_ok(), "Should accept any pattern in non-regex mode: {:?}", result.err());
     }
+
+    // =========================================================================
+    // Docs search
+    // =========================================================================
+
+    const DOCS_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {},
+        "calls": [],
+        "docs": {
+            "MyApp.Accounts": {
+                "moduledoc": "Manages user accounts.",
+                "functions": [{"name": "reset_password", "arity": 1, "doc": "Sends a password reset email."}]
+            }
+        }
+    }"#;
+
+    fn docs_cmd(pattern: &str, regex: bool) -> SearchCmd {
+        SearchCmd {
+            pattern: pattern.to_string(),
+            kind: SearchKind::Docs,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_search_docs() {
+        use crate::commands::Execute;
+
+        let db = db::test_utils::setup_test_db(DOCS_JSON, "test_project");
+        let result = docs_cmd("password", false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.kind, "docs");
+        assert_eq!(result.docs.len(), 1);
+        assert_eq!(result.docs[0].name.as_deref(), Some("reset_password"));
+        assert!(result.docs[0].snippet.contains("**password**"));
+    }
+
+    #[test]
+    fn test_search_docs_rejects_regex() {
+        use crate::commands::Execute;
+
+        let db = db::test_utils::setup_test_db(DOCS_JSON, "test_project");
+        let err = docs_cmd("pass.*", true).execute(&db, &QuerySettings::default()).unwrap_err();
+        assert!(err.to_string().contains("--kind docs"));
+    }
+
+    // =========================================================================
+    // Semantic search
+    // =========================================================================
+
+    fn semantic_cmd(pattern: &str) -> SearchCmd {
+        SearchCmd {
+            pattern: pattern.to_string(),
+            kind: SearchKind::Modules,
+            ignore_case: false,
+            fuzzy: false,
+            semantic: true,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 3,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_search_semantic() {
+        use crate::commands::{Execute, IndexEmbeddingsCmd};
+        use crate::embed::Provider;
+
+        // type_signatures has no function locations to embed
+        let db = db::test_utils::call_graph_db("test_project");
+
+        IndexEmbeddingsCmd {
+            project: "test_project".to_string(),
+            config: std::path::PathBuf::from(crate::config::DEFAULT_CONFIG_PATH),
+            provider: Some(Provider::Hashing),
+            url: None,
+            model: None,
+            batch_size: 64,
+        }
+        .execute(&db, &QuerySettings::default())
+        .expect("Indexing should succeed");
+
+        let result = semantic_cmd("get user").execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert_eq!(result.kind, "functions");
+        assert_eq!(result.mode.as_deref(), Some("semantic"));
+        assert_eq!(result.total_functions, Some(3));
+        assert_eq!(result.function_modules[0].functions[0].name, "get_user");
+        assert!(result.function_modules[0].functions[0].score.is_some());
+    }
+
+    #[rstest]
+    fn test_search_semantic_requires_index(populated_db: db::DbInstance) {
+        use crate::commands::Execute;
+
+        let err = semantic_cmd("get user").execute(&populated_db, &QuerySettings::default()).unwrap_err();
+        assert!(err.to_string().contains("index-embeddings"));
+    }
 }
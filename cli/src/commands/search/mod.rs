@@ -7,6 +7,7 @@ mod output_tests;
 use std::error::Error;
 
 use clap::{Args, ValueEnum};
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
 use crate::commands::{CommandRunner, CommonArgs, Execute};
@@ -20,6 +21,8 @@ pub enum SearchKind {
     Modules,
     /// Search for functions
     Functions,
+    /// Search @moduledoc/@doc text (full-text, ranked by relevance)
+    Docs,
 }
 
 /// Search for modules or functions by name pattern
@@ -29,22 +32,39 @@ Examples:
   code_search search User                    # Find modules containing 'User'
   code_search search get_ -k functions       # Find functions starting with 'get_'
   code_search search -r '^MyApp\\.API'       # Regex match for module prefix
+  code_search search myapp.accounts -i       # Case-insensitive match
+  code_search search get_usr -k functions --fuzzy  # Closest function names
+  code_search search 'password reset' -k docs      # Docs mentioning both words
+  code_search search 'load user from database' --semantic  # Closest functions by meaning
 ")]
 pub struct SearchCmd {
-    /// Pattern to search for (substring match by default, regex with --regex)
+    /// Pattern to search for (substring match by default, regex with --regex, words with --kind docs)
     pub pattern: String,
 
     /// What to search for
     #[arg(short, long, value_enum, default_value_t = SearchKind::Modules)]
     pub kind: SearchKind,
 
+    /// Match names regardless of case
+    #[arg(short, long)]
+    pub ignore_case: bool,
+
+    /// Rank names by similarity to the pattern instead of matching it (always ignores case)
+    #[arg(long, conflicts_with = "regex")]
+    pub fuzzy: bool,
+
+    /// Find functions whose meaning is closest to the pattern, using embeddings built by
+    /// `index-embeddings` (always searches functions)
+    #[arg(long, conflicts_with_all = ["regex", "fuzzy", "ignore_case"])]
+    pub semantic: bool,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for SearchCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
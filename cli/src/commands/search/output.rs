@@ -7,13 +7,19 @@ impl Outputable for SearchResult {
     fn to_table(&self) -> String {
         let mut lines = Vec::new();
 
-        lines.push(format!("Search: {} ({})", self.pattern, self.kind));
+        match &self.mode {
+            Some(mode) => lines.push(format!("Search: {} ({}, {})", self.pattern, self.kind, mode)),
+            None => lines.push(format!("Search: {} ({})", self.pattern, self.kind)),
+        }
         lines.push(String::new());
 
         if !self.modules.is_empty() {
             lines.push(format!("Modules ({}):", self.modules.len()));
             for m in &self.modules {
-                lines.push(format!("  {}", m.name));
+                match m.score {
+                    Some(score) => lines.push(format!("  {} ({:.2})", m.name, score)),
+                    None => lines.push(format!("  {}", m.name)),
+                }
             }
         }
 
@@ -29,17 +35,32 @@ impl Outputable for SearchResult {
             for module in &self.function_modules {
                 lines.push(format!("{}:", module.name));
                 for f in &module.functions {
-                    let sig = if f.return_type.is_empty() {
+                    let mut sig = if f.return_type.is_empty() {
                         format!("{}/{}", f.name, f.arity)
                     } else {
                         format!("{}/{} -> {}", f.name, f.arity, f.return_type)
                     };
+                    if let Some(score) = f.score {
+                        sig.push_str(&format!(" ({:.2})", score));
+                    }
                     lines.push(format!("  {}", sig));
                 }
             }
         }
 
-        if self.modules.is_empty() && self.function_modules.is_empty() {
+        if !self.docs.is_empty() {
+            lines.push(format!("Docs ({}):", self.docs.len()));
+            for d in &self.docs {
+                let target = match (&d.name, d.arity) {
+                    (Some(name), Some(arity)) => format!("{}.{}/{}", d.module, name, arity),
+                    _ => d.module.clone(),
+                };
+                lines.push(format!("  {} [{}] ({:.2})", target, d.kind, d.score));
+                lines.push(format!("    {}", d.snippet));
+            }
+        }
+
+        if self.modules.is_empty() && self.function_modules.is_empty() && self.docs.is_empty() {
             lines.push("No results found.".to_string());
         }
 
@@ -3,6 +3,7 @@
 #[cfg(test)]
 mod tests {
     use super::super::execute::{SearchFunc, SearchFuncModule, SearchResult};
+    use db::queries::docs::DocMatch;
     use db::queries::search::ModuleResult;
     use rstest::{fixture, rstest};
 
@@ -30,6 +31,20 @@ Functions (1) in 1 module(s):
 MyApp.Accounts:
   get_user/1 -> User.t()";
 
+    const FUZZY_TABLE: &str = "\
+Search: acounts (modules, fuzzy)
+
+Modules (1):
+  MyApp.Accounts (0.88)";
+
+    const DOCS_TABLE: &str = "\
+Search: users (docs)
+
+Docs (2):
+  MyApp.Accounts.list_users/0 [doc] (1.25)
+    Lists all **users**.
+  MyApp.Accounts [moduledoc] (0.50)
+    Manages **user** accounts and their credentials.";
 
     // =========================================================================
     // Fixtures
@@ -40,9 +55,11 @@ MyApp.Accounts:
         SearchResult {
             pattern: "test".to_string(),
             kind: "modules".to_string(),
+            mode: None,
             modules: vec![],
             total_functions: None,
             function_modules: vec![],
+            docs: vec![],
         }
     }
 
@@ -51,20 +68,24 @@ MyApp.Accounts:
         SearchResult {
             pattern: "MyApp".to_string(),
             kind: "modules".to_string(),
+            mode: None,
             modules: vec![
                 ModuleResult {
                     project: "default".to_string(),
                     name: "MyApp.Accounts".to_string(),
                     source: "unknown".to_string(),
+                    score: None,
                 },
                 ModuleResult {
                     project: "default".to_string(),
                     name: "MyApp.Users".to_string(),
                     source: "unknown".to_string(),
+                    score: None,
                 },
             ],
             total_functions: None,
             function_modules: vec![],
+            docs: vec![],
         }
     }
 
@@ -73,6 +94,7 @@ MyApp.Accounts:
         SearchResult {
             pattern: "get_".to_string(),
             kind: "functions".to_string(),
+            mode: None,
             modules: vec![],
             total_functions: Some(1),
             function_modules: vec![SearchFuncModule {
@@ -81,8 +103,55 @@ MyApp.Accounts:
                     name: "get_user".to_string(),
                     arity: 1,
                     return_type: "User.t()".to_string(),
+                    score: None,
                 }],
             }],
+            docs: vec![],
+        }
+    }
+
+    #[fixture]
+    fn fuzzy_result() -> SearchResult {
+        SearchResult {
+            pattern: "acounts".to_string(),
+            kind: "modules".to_string(),
+            mode: Some("fuzzy".to_string()),
+            modules: vec![ModuleResult {
+                project: "default".to_string(),
+                name: "MyApp.Accounts".to_string(),
+                source: "unknown".to_string(),
+                score: Some(0.88),
+            }],
+            total_functions: None,
+            function_modules: vec![],
+            docs: vec![],
+        }
+    }
+
+    #[fixture]
+    fn docs_result() -> SearchResult {
+        SearchResult {
+            pattern: "users".to_string(),
+            kind: "docs".to_string(),
+            docs: vec![
+                DocMatch {
+                    module: "MyApp.Accounts".to_string(),
+                    name: Some("list_users".to_string()),
+                    arity: Some(0),
+                    kind: "doc".to_string(),
+                    score: 1.25,
+                    snippet: "Lists all **users**.".to_string(),
+                },
+                DocMatch {
+                    module: "MyApp.Accounts".to_string(),
+                    name: None,
+                    arity: None,
+                    kind: "moduledoc".to_string(),
+                    score: 0.5,
+                    snippet: "Manages **user** accounts and their credentials.".to_string(),
+                },
+            ],
+            ..SearchResult::default()
         }
     }
 
@@ -90,6 +159,20 @@ MyApp.Accounts:
     // Tests
     // =========================================================================
 
+    crate::output_table_test! {
+        test_name: test_to_table_docs,
+        fixture: docs_result,
+        fixture_type: SearchResult,
+        expected: DOCS_TABLE,
+    }
+
+    crate::output_table_test! {
+        test_name: test_to_table_fuzzy,
+        fixture: fuzzy_result,
+        fixture_type: SearchResult,
+        expected: FUZZY_TABLE,
+    }
+
     crate::output_table_test! {
         test_name: test_to_table_empty,
         fixture: empty_result,
@@ -6,7 +6,8 @@ use serde::Serialize;
 
 use super::SetupCmd;
 use crate::commands::Execute;
-use db::queries::schema;
+use db::queries::schema::{self, SchemaMismatch, SchemaRepair};
+use db::query_builders::QuerySettings;
 
 /// Embedded skill templates directory
 static SKILL_TEMPLATES: Dir = include_dir!("$CARGO_MANIFEST_DIR/../templates/skills");
@@ -94,6 +95,12 @@ pub struct SetupResult {
     pub templates: Option<TemplatesInstallResult>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hooks: Option<HooksInstallResult>,
+    /// Columns added to relations set up by an older version
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub repairs: Vec<SchemaRepair>,
+    /// Stored relations that still differ from the schema, which setup cannot fix
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub schema_mismatches: Vec<SchemaMismatch>,
 }
 
 /// Recursively process a directory and install all files
@@ -305,7 +312,7 @@ fn install_hooks(
 impl Execute for SetupCmd {
     type Output = SetupResult;
 
-    fn execute(self, db: &DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         let mut relations = Vec::new();
 
         if self.dry_run {
@@ -316,6 +323,12 @@ impl Execute for SetupCmd {
                     status: RelationState::WouldCreate,
                 });
             }
+            for index in schema::INDEXES {
+                relations.push(RelationStatus {
+                    name: index.qualified_name(),
+                    status: RelationState::WouldCreate,
+                });
+            }
 
             return Ok(SetupResult {
                 relations,
@@ -323,6 +336,8 @@ impl Execute for SetupCmd {
                 dry_run: true,
                 templates: None,
                 hooks: None,
+                repairs: Vec::new(),
+                schema_mismatches: Vec::new(),
             });
         }
 
@@ -351,6 +366,10 @@ impl Execute for SetupCmd {
             .iter()
             .any(|r| matches!(r.status, RelationState::Created));
 
+        // Relations set up by an older version get the columns added since
+        let repairs = schema::add_missing_columns(db)?;
+        let schema_mismatches = schema::schema_mismatches(db)?;
+
         // Install templates (skills and agents) if requested
         let templates = if self.install_skills {
             Some(install_templates(self.force)?)
@@ -375,6 +394,8 @@ impl Execute for SetupCmd {
             dry_run: false,
             templates,
             hooks,
+            repairs,
+            schema_mismatches,
         })
     }
 }
@@ -403,10 +424,10 @@ mod tests {
         };
 
         let db = open_db(db_file.path()).expect("Failed to open db");
-        let result = cmd.execute(&db).expect("Setup should succeed");
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Setup should succeed");
 
-        // Should create 7 relations
-        assert_eq!(result.relations.len(), 7);
+        // Should create 17 relations (12 imported + 2 derived stats + snapshots, annotations and saved queries) and 4 indexes
+        assert_eq!(result.relations.len(), 23);
 
         // All should be created
         assert!(result
@@ -432,7 +453,7 @@ mod tests {
             project_name: None,
             mix_env: None,
         };
-        let result1 = cmd1.execute(&db).expect("First setup should succeed");
+        let result1 = cmd1.execute(&db, &QuerySettings::default()).expect("First setup should succeed");
         assert!(result1.created_new);
 
         // Second setup should find existing relations
@@ -444,10 +465,10 @@ mod tests {
             project_name: None,
             mix_env: None,
         };
-        let result2 = cmd2.execute(&db).expect("Second setup should succeed");
+        let result2 = cmd2.execute(&db, &QuerySettings::default()).expect("Second setup should succeed");
 
-        // Should still have 7 relations, but all already existing
-        assert_eq!(result2.relations.len(), 7);
+        // Should still have 23 relations and indexes, but all already existing
+        assert_eq!(result2.relations.len(), 23);
         assert!(result2
             .relations
             .iter()
@@ -456,6 +477,29 @@ mod tests {
         assert!(!result2.created_new);
     }
 
+    #[rstest]
+    fn test_setup_adds_columns_of_older_databases(db_file: NamedTempFile) {
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        schema::create_schema(&db).unwrap();
+        db.run_script_str("::remove modules", "", false);
+        db.run_script_str(":create modules { project: String, name: String => file: String }", "", false);
+        let cmd = SetupCmd {
+            force: false,
+            dry_run: false,
+            install_skills: false,
+            install_hooks: false,
+            project_name: None,
+            mix_env: None,
+        };
+
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Setup should succeed");
+        assert!(!result.created_new);
+        assert_eq!(result.repairs.len(), 1);
+        assert_eq!(result.repairs[0].relation, "modules");
+        assert!(result.schema_mismatches.is_empty());
+        assert!(schema::verify_schema(&db).unwrap().matches());
+    }
+
     #[rstest]
     fn test_setup_dry_run(db_file: NamedTempFile) {
         let cmd = SetupCmd {
@@ -468,10 +512,10 @@ mod tests {
         };
 
         let db = open_db(db_file.path()).expect("Failed to open db");
-        let result = cmd.execute(&db).expect("Setup should succeed");
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Setup should succeed");
 
         assert!(result.dry_run);
-        assert_eq!(result.relations.len(), 7);
+        assert_eq!(result.relations.len(), 23);
 
         // All should be in would_create state
         assert!(result
@@ -495,17 +539,29 @@ mod tests {
         };
 
         let db = open_db(db_file.path()).expect("Failed to open db");
-        let result = cmd.execute(&db).expect("Setup should succeed");
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Setup should succeed");
 
         let relation_names: Vec<_> = result.relations.iter().map(|r| r.name.as_str()).collect();
 
         assert!(relation_names.contains(&"modules"));
         assert!(relation_names.contains(&"functions"));
         assert!(relation_names.contains(&"calls"));
+        assert!(relation_names.contains(&"messages"));
         assert!(relation_names.contains(&"struct_fields"));
+        assert!(relation_names.contains(&"field_accesses"));
         assert!(relation_names.contains(&"function_locations"));
         assert!(relation_names.contains(&"specs"));
         assert!(relation_names.contains(&"types"));
+        assert!(relation_names.contains(&"behaviours"));
+        assert!(relation_names.contains(&"docs"));
+        assert!(relation_names.contains(&"supervision"));
+        assert!(relation_names.contains(&"aliases"));
+        assert!(relation_names.contains(&"function_stats"));
+        assert!(relation_names.contains(&"module_stats"));
+        assert!(relation_names.contains(&"metric_snapshots"));
+        assert!(relation_names.contains(&"annotations"));
+        assert!(relation_names.contains(&"calls:by_callee"));
+        assert!(relation_names.contains(&"function_locations:by_file"));
     }
 
     #[test]
@@ -520,7 +576,7 @@ mod tests {
             .expect("Install should succeed");
 
         // All files should be installed (not skipped or overwritten)
-        assert_eq!(result.skills_installed, 34, "Should install all 34 skill files");
+        assert_eq!(result.skills_installed, 60, "Should install all 60 skill files");
         assert_eq!(result.skills_skipped, 0);
         assert_eq!(result.skills_overwritten, 0);
 
@@ -542,14 +598,14 @@ mod tests {
         // First installation
         let result1 = install_templates_to(temp_dir.path(), false)
             .expect("First install should succeed");
-        assert_eq!(result1.skills_installed, 34);
+        assert_eq!(result1.skills_installed, 60);
         assert_eq!(result1.agents_installed, 1);
 
         // Second installation without force - should skip all files
         let result2 = install_templates_to(temp_dir.path(), false)
             .expect("Second install should succeed");
         assert_eq!(result2.skills_installed, 0, "Should not install any skill files");
-        assert_eq!(result2.skills_skipped, 34, "Should skip all 34 existing skill files");
+        assert_eq!(result2.skills_skipped, 60, "Should skip all 50 existing skill files");
         assert_eq!(result2.skills_overwritten, 0);
 
         assert_eq!(result2.agents_installed, 0, "Should not install any agent files");
@@ -566,7 +622,7 @@ mod tests {
         // First installation
         let result1 = install_templates_to(temp_dir.path(), false)
             .expect("First install should succeed");
-        assert_eq!(result1.skills_installed, 34);
+        assert_eq!(result1.skills_installed, 60);
         assert_eq!(result1.agents_installed, 1);
 
         // Second installation with force - should overwrite all files
@@ -574,7 +630,7 @@ mod tests {
             .expect("Second install with force should succeed");
         assert_eq!(result2.skills_installed, 0, "Should not install new skill files");
         assert_eq!(result2.skills_skipped, 0, "Should not skip any skill files");
-        assert_eq!(result2.skills_overwritten, 34, "Should overwrite all 34 existing skill files");
+        assert_eq!(result2.skills_overwritten, 60, "Should overwrite all 50 existing skill files");
 
         assert_eq!(result2.agents_installed, 0, "Should not install new agent files");
         assert_eq!(result2.agents_skipped, 0, "Should not skip any agent files");
@@ -593,7 +649,7 @@ mod tests {
         };
 
         let db = open_db(db_file.path()).expect("Failed to open db");
-        let result = cmd.execute(&db).expect("Setup should succeed");
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Setup should succeed");
 
         // Templates and hooks should be None when not requested
         assert!(result.templates.is_none());
@@ -634,7 +690,7 @@ mod tests {
             mix_env: Some("test".to_string()),
         };
 
-        let result = cmd.execute(&db).expect("Setup with hooks should succeed");
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Setup with hooks should succeed");
 
         // Verify hook file exists and is executable BEFORE restoring directory
         let hook_path = temp_path.join(".git").join("hooks").join("post-commit");
@@ -654,7 +710,7 @@ mod tests {
         // Verify hook content
         let hook_content = fs::read_to_string(&hook_path).expect("Failed to read hook");
         assert!(hook_content.contains("#!/usr/bin/env bash"));
-        assert!(hook_content.contains("ex_ast --git-diff"));
+        assert!(hook_content.contains("update --files"));
         assert!(hook_content.contains("code_search"));
         assert!(hook_content.contains("GIT_REF")); // Uses variable for git reference
 
@@ -721,7 +777,7 @@ mod tests {
             mix_env: None,
         };
 
-        let result = cmd.execute(&db).expect("Setup with hooks should succeed");
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Setup with hooks should succeed");
 
         assert!(result.hooks.is_some());
         let hooks = result.hooks.unwrap();
@@ -773,7 +829,7 @@ mod tests {
             mix_env: None,
         };
 
-        let result1 = cmd1.execute(&db).expect("First install should succeed");
+        let result1 = cmd1.execute(&db, &QuerySettings::default()).expect("First install should succeed");
         assert_eq!(result1.hooks.as_ref().unwrap().hooks_installed, 1);
 
         // Second installation without force
@@ -786,7 +842,7 @@ mod tests {
             mix_env: None,
         };
 
-        let result2 = cmd2.execute(&db).expect("Second install should succeed");
+        let result2 = cmd2.execute(&db, &QuerySettings::default()).expect("Second install should succeed");
 
         // Should skip existing hook
         assert_eq!(result2.hooks.as_ref().unwrap().hooks_installed, 0);
@@ -828,7 +884,7 @@ mod tests {
             mix_env: None,
         };
 
-        cmd1.execute(&db).expect("First install should succeed");
+        cmd1.execute(&db, &QuerySettings::default()).expect("First install should succeed");
 
         // Second installation with force
         let cmd2 = SetupCmd {
@@ -840,7 +896,7 @@ mod tests {
             mix_env: None,
         };
 
-        let result2 = cmd2.execute(&db).expect("Second install with force should succeed");
+        let result2 = cmd2.execute(&db, &QuerySettings::default()).expect("Second install with force should succeed");
 
         // Should overwrite existing hook
         assert_eq!(result2.hooks.as_ref().unwrap().hooks_installed, 0);
@@ -874,7 +930,7 @@ mod tests {
             mix_env: None,
         };
 
-        let result = cmd.execute(&db);
+        let result = cmd.execute(&db, &QuerySettings::default());
 
         // Restore original directory
         std::env::set_current_dir(&original_dir).ok(); // Ignore error if original_dir was deleted
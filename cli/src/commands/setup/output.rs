@@ -31,9 +31,22 @@ impl Outputable for SetupResult {
             output.push_str(&format!("  {} {} ({})\n", symbol, relation.name, status_text));
         }
 
+        if !self.repairs.is_empty() {
+            output.push_str("\nColumns added to relations of an older version:\n");
+            for repair in &self.repairs {
+                output.push_str(&format!("  ✓ {} ({})\n", repair.relation, repair.added_columns.join(", ")));
+            }
+        }
+
         if self.dry_run {
             output.push_str("\nNo changes made (dry-run mode).\n");
-        } else if self.created_new {
+        } else if !self.schema_mismatches.is_empty() {
+            output.push_str("\nStored relations differ from this version's schema:\n");
+            for mismatch in &self.schema_mismatches {
+                output.push_str(&format!("  {}: {}\n", mismatch.relation, mismatch.summary()));
+            }
+            output.push_str("Back up the data and import into a new database.\n");
+        } else if self.created_new || !self.repairs.is_empty() {
             output.push_str("\nDatabase ready.\n");
         } else {
             output.push_str("\nDatabase already configured.\n");
@@ -0,0 +1,180 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::SimilarFunctionsCmd;
+use crate::commands::Execute;
+use db::queries::similarity::{find_function_fingerprints, minhash_similarity, FunctionFingerprint};
+use db::query_builders::QuerySettings;
+
+/// A function similar to the target
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarFunction {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub file: String,
+    pub line: i64,
+    /// Estimated Jaccard similarity of the AST shingles (0.0-1.0)
+    pub similarity: f64,
+}
+
+/// Result of the similar-functions command
+#[derive(Debug, Serialize)]
+pub struct SimilarFunctionsResult {
+    pub target: FunctionFingerprint,
+    /// Most similar first
+    pub similar: Vec<SimilarFunction>,
+}
+
+impl Execute for SimilarFunctionsCmd {
+    type Output = SimilarFunctionsResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        if !(0.0..=1.0).contains(&self.min_similarity) {
+            return Err("--min-similarity must be between 0.0 and 1.0".into());
+        }
+
+        let fingerprints = find_function_fingerprints(db, &self.project, self.exclude_generated, settings)?;
+        let target = find_target(&fingerprints, &self.module, &self.function, self.arity)?.clone();
+
+        let mut similar: Vec<SimilarFunction> = fingerprints
+            .into_iter()
+            .filter(|f| !(f.module == target.module && f.name == target.name && f.arity == target.arity))
+            .filter_map(|f| {
+                let similarity = minhash_similarity(&target.signature, &f.signature);
+                (similarity >= self.min_similarity).then(|| SimilarFunction {
+                    module: f.module,
+                    name: f.name,
+                    arity: f.arity,
+                    file: f.file,
+                    line: f.line,
+                    similarity: (similarity * 1000.0).round() / 1000.0,
+                })
+            })
+            .collect();
+
+        // Fingerprints come in module/name/arity order, which the stable sort keeps for ties
+        similar.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        similar.truncate(self.limit as usize);
+
+        Ok(SimilarFunctionsResult { target, similar })
+    }
+}
+
+/// The target function's fingerprint, or an error explaining why there is none
+fn find_target<'a>(
+    fingerprints: &'a [FunctionFingerprint],
+    module: &str,
+    function: &str,
+    arity: Option<i64>,
+) -> Result<&'a FunctionFingerprint, Box<dyn Error>> {
+    let candidates: Vec<&FunctionFingerprint> = fingerprints
+        .iter()
+        .filter(|f| f.module == module && f.name == function && arity.is_none_or(|a| f.arity == a))
+        .collect();
+
+    match candidates.as_slice() {
+        [target] => Ok(target),
+        [] => Err(format!(
+            "No AST fingerprint for {}.{}{}; the function does not exist or was imported without ast_minhash signatures",
+            module,
+            function,
+            arity.map(|a| format!("/{}", a)).unwrap_or_default()
+        )
+        .into()),
+        _ => {
+            let arities: Vec<String> = candidates.iter().map(|f| f.arity.to_string()).collect();
+            Err(format!(
+                "{}.{} has several arities ({}); pick one with --arity",
+                module,
+                function,
+                arities.join(", ")
+            )
+            .into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIMILAR_JSON: &str = r#"{
+        "structs": {},
+        "calls": [],
+        "function_locations": {
+            "MyApp.Accounts": {
+                "get_user/1:10": {"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "kind": "def",
+                    "line": 10, "start_line": 10, "end_line": 12, "ast_minhash": [1, 2, 3, 4]},
+                "get_user/2:14": {"name": "get_user", "arity": 2, "file": "lib/accounts.ex", "kind": "def",
+                    "line": 14, "start_line": 14, "end_line": 16, "ast_minhash": [1, 2, 3, 5]}
+            },
+            "MyApp.Users": {
+                "fetch/1:5": {"name": "fetch", "arity": 1, "file": "lib/users.ex", "kind": "def",
+                    "line": 5, "start_line": 5, "end_line": 8, "ast_minhash": [1, 2, 8, 4]},
+                "render/1:20": {"name": "render", "arity": 1, "file": "lib/users.ex", "kind": "def",
+                    "line": 20, "start_line": 20, "end_line": 30, "ast_minhash": [9, 9, 9, 9]}
+            }
+        }
+    }"#;
+
+    fn similar_cmd(function: &str, arity: Option<i64>) -> SimilarFunctionsCmd {
+        SimilarFunctionsCmd {
+            module: "MyApp.Accounts".to_string(),
+            function: function.to_string(),
+            arity,
+            min_similarity: 0.5,
+            exclude_generated: false,
+            project: "test_project".to_string(),
+            limit: 10,
+        }
+    }
+
+    #[test]
+    fn test_similar_functions_ranked() {
+        let db = db::test_utils::setup_test_db(SIMILAR_JSON, "test_project");
+        let result = similar_cmd("get_user", Some(1))
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.target.arity, 1);
+        let names: Vec<(&str, i64, f64)> = result
+            .similar
+            .iter()
+            .map(|f| (f.name.as_str(), f.arity, f.similarity))
+            .collect();
+        // render shares nothing and falls below the threshold
+        assert_eq!(names, [("get_user", 2, 0.75), ("fetch", 1, 0.75)]);
+    }
+
+    #[test]
+    fn test_similar_functions_limit() {
+        let db = db::test_utils::setup_test_db(SIMILAR_JSON, "test_project");
+        let mut cmd = similar_cmd("get_user", Some(1));
+        cmd.limit = 1;
+        assert_eq!(cmd.execute(&db, &QuerySettings::default()).unwrap().similar.len(), 1);
+    }
+
+    #[test]
+    fn test_similar_functions_ambiguous_arity() {
+        let db = db::test_utils::setup_test_db(SIMILAR_JSON, "test_project");
+        let err = similar_cmd("get_user", None).execute(&db, &QuerySettings::default()).unwrap_err();
+        assert!(err.to_string().contains("--arity"));
+    }
+
+    #[test]
+    fn test_similar_functions_without_fingerprint() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let err = similar_cmd("get_user", Some(1)).execute(&db, &QuerySettings::default()).unwrap_err();
+        assert!(err.to_string().contains("ast_minhash"));
+    }
+
+    #[test]
+    fn test_similar_functions_rejects_bad_threshold() {
+        let db = db::test_utils::setup_test_db(SIMILAR_JSON, "test_project");
+        let mut cmd = similar_cmd("get_user", Some(1));
+        cmd.min_similarity = 1.5;
+        assert!(cmd.execute(&db, &QuerySettings::default()).is_err());
+    }
+}
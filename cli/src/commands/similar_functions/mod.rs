@@ -0,0 +1,60 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Find the functions structurally most similar to a given function
+///
+/// Compares AST MinHash signatures emitted by the extractor (`ast_minhash` on each
+/// clause) and ranks every other function of the project by estimated Jaccard
+/// similarity of their AST shingles. Unlike `duplicates`, which only groups identical
+/// hashes, this finds copy-pasted functions that were adapted afterwards.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search similar-functions MyApp.Accounts get_user         # Top 10 lookalikes
+  code_search similar-functions MyApp.Accounts get_user -a 2    # A specific arity
+  code_search similar-functions MyApp.Repo insert --min-similarity 0.8 -l 25
+")]
+pub struct SimilarFunctionsCmd {
+    /// Module of the function to compare against (exact match)
+    pub module: String,
+
+    /// Function name (exact match)
+    pub function: String,
+
+    /// Function arity (required when the function has several arities)
+    #[arg(short, long)]
+    pub arity: Option<i64>,
+
+    /// Minimum estimated similarity (0.0-1.0) for a function to be listed
+    #[arg(long, default_value_t = 0.5)]
+    pub min_similarity: f64,
+
+    /// Exclude generated functions: macro-generated and compiler callbacks,
+    /// plus the `generated` rules of the config file
+    #[arg(long)]
+    pub exclude_generated: bool,
+
+    /// Project to search in
+    #[arg(long, default_value = "default")]
+    pub project: String,
+
+    /// Maximum number of similar functions to return (1-1000)
+    #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..=1000))]
+    pub limit: u32,
+}
+
+impl CommandRunner for SimilarFunctionsCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,78 @@
+//! Output formatting for similar-functions command results.
+
+use super::execute::SimilarFunctionsResult;
+use crate::output::Outputable;
+
+impl Outputable for SimilarFunctionsResult {
+    fn to_table(&self) -> String {
+        let target = &self.target;
+        let mut lines = vec![
+            format!(
+                "Functions similar to {}.{}/{} ({}:{})",
+                target.module, target.name, target.arity, target.file, target.line
+            ),
+            String::new(),
+        ];
+
+        if self.similar.is_empty() {
+            lines.push("No similar functions found.".to_string());
+        }
+        for f in &self.similar {
+            lines.push(format!(
+                "  {:.2}  {}.{}/{}  {}:{}",
+                f.similarity, f.module, f.name, f.arity, f.file, f.line
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::similar_functions::execute::SimilarFunction;
+    use db::queries::similarity::FunctionFingerprint;
+
+    fn result(similar: Vec<SimilarFunction>) -> SimilarFunctionsResult {
+        SimilarFunctionsResult {
+            target: FunctionFingerprint {
+                module: "MyApp.Accounts".to_string(),
+                name: "get_user".to_string(),
+                arity: 1,
+                file: "lib/accounts.ex".to_string(),
+                line: 10,
+                signature: vec![1, 2, 3],
+            },
+            similar,
+        }
+    }
+
+    #[test]
+    fn test_to_table() {
+        let output = result(vec![SimilarFunction {
+            module: "MyApp.Users".to_string(),
+            name: "fetch".to_string(),
+            arity: 1,
+            file: "lib/users.ex".to_string(),
+            line: 5,
+            similarity: 0.75,
+        }])
+        .to_table();
+        assert_eq!(
+            output,
+            "Functions similar to MyApp.Accounts.get_user/1 (lib/accounts.ex:10)\n\n  0.75  MyApp.Users.fetch/1  lib/users.ex:5"
+        );
+    }
+
+    #[test]
+    fn test_to_table_empty() {
+        assert!(result(vec![]).to_table().ends_with("No similar functions found."));
+    }
+
+    #[test]
+    fn test_json_omits_signature() {
+        let json = serde_json::to_string(&result(vec![])).unwrap();
+        assert!(!json.contains("signature"));
+    }
+}
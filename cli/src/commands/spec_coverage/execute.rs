@@ -0,0 +1,195 @@
+//! Spec coverage per module and spec drift.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::SpecCoverageCmd;
+use crate::commands::Execute;
+use db::queries::spec_coverage::{FunctionSpecStatus, SpecDrift, find_spec_coverage, find_spec_drift};
+use db::query_builders::QuerySettings;
+
+/// A function without a @spec
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingSpec {
+    pub name: String,
+    pub arity: i64,
+    pub kind: String,
+    pub line: i64,
+}
+
+/// Spec coverage of one module
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleSpecCoverage {
+    pub module: String,
+    pub file: String,
+    pub functions: usize,
+    pub specced: usize,
+    /// Percentage of functions with a @spec
+    pub coverage: f64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<MissingSpec>,
+}
+
+/// Result of the spec-coverage command
+#[derive(Debug, Serialize)]
+pub struct SpecCoverageResult {
+    pub module_pattern: String,
+    pub total_functions: usize,
+    pub specced_functions: usize,
+    /// Percentage of functions with a @spec across all checked modules
+    pub coverage: f64,
+    /// Modules ordered by coverage, lowest first
+    pub modules: Vec<ModuleSpecCoverage>,
+    /// Specs without a definition of the same arity (only with --drift)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drift: Option<Vec<SpecDrift>>,
+}
+
+impl Execute for SpecCoverageCmd {
+    type Output = SpecCoverageResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let functions = find_spec_coverage(
+            db,
+            &self.common.project,
+            self.module.as_deref(),
+            self.common.regex,
+            self.include_private,
+            settings,
+        )?;
+
+        let drift = if self.drift {
+            Some(find_spec_drift(
+                db,
+                &self.common.project,
+                self.module.as_deref(),
+                self.common.regex,
+                settings,
+            )?)
+        } else {
+            None
+        };
+
+        let total_functions = functions.len();
+        let specced_functions = functions.iter().filter(|f| f.has_spec).count();
+
+        let mut by_module: BTreeMap<String, Vec<FunctionSpecStatus>> = BTreeMap::new();
+        for function in functions {
+            by_module.entry(function.module.clone()).or_default().push(function);
+        }
+
+        let mut modules: Vec<ModuleSpecCoverage> = by_module
+            .into_iter()
+            .map(|(module, functions)| {
+                let specced = functions.iter().filter(|f| f.has_spec).count();
+                ModuleSpecCoverage {
+                    file: functions[0].file.clone(),
+                    functions: functions.len(),
+                    specced,
+                    coverage: percentage(specced, functions.len()),
+                    missing: functions
+                        .into_iter()
+                        .filter(|f| !f.has_spec)
+                        .map(|f| MissingSpec {
+                            name: f.name,
+                            arity: f.arity,
+                            kind: f.kind,
+                            line: f.line,
+                        })
+                        .collect(),
+                    module,
+                }
+            })
+            .collect();
+        modules.sort_by(|a, b| a.coverage.total_cmp(&b.coverage).then_with(|| a.module.cmp(&b.module)));
+        modules.truncate(self.common.limit as usize);
+
+        Ok(SpecCoverageResult {
+            module_pattern: self.module.unwrap_or_else(|| "*".to_string()),
+            total_functions,
+            specced_functions,
+            coverage: percentage(specced_functions, total_functions),
+            modules,
+            drift,
+        })
+    }
+}
+
+/// Share of `part` in `total` as a percentage rounded to one decimal (100 when empty)
+fn percentage(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 100.0;
+    }
+    (part as f64 * 1000.0 / total as f64).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+
+    fn spec_coverage_cmd(module: Option<&str>, include_private: bool, drift: bool) -> SpecCoverageCmd {
+        SpecCoverageCmd {
+            module: module.map(String::from),
+            include_private,
+            drift,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_spec_coverage_all_modules() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = spec_coverage_cmd(None, false, false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        // 12 public functions, of which get_user/1 and list_users/0 have a @spec
+        assert_eq!(result.total_functions, 12);
+        assert_eq!(result.specced_functions, 2);
+        assert_eq!(result.coverage, 16.7);
+        assert!(result.drift.is_none());
+
+        // Lowest coverage first; Accounts is the only module with specs
+        let last = result.modules.last().unwrap();
+        assert_eq!(last.module, "MyApp.Accounts");
+        assert_eq!(last.coverage, 66.7);
+        assert_eq!(last.missing.len(), 1);
+        assert_eq!((last.missing[0].name.as_str(), last.missing[0].arity), ("get_user", 2));
+    }
+
+    #[test]
+    fn test_spec_coverage_include_private() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = spec_coverage_cmd(Some("MyApp.Accounts"), true, false)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_functions, 4);
+        assert_eq!(result.modules.len(), 1);
+        assert!(result.modules[0].missing.iter().any(|m| m.name == "validate_email"));
+    }
+
+    #[test]
+    fn test_spec_coverage_with_drift() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = spec_coverage_cmd(None, false, true)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.drift.map(|d| d.len()), Some(0));
+    }
+
+    #[test]
+    fn test_percentage() {
+        assert_eq!(percentage(1, 3), 33.3);
+        assert_eq!(percentage(0, 0), 100.0);
+    }
+}
@@ -0,0 +1,47 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Report functions lacking a @spec, per module
+///
+/// Coverage counts public functions (`def`, `defmacro`) with a matching @spec;
+/// @callback declarations do not count. With --drift, specs whose arity no
+/// longer matches any definition of the function are listed as well.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search spec-coverage                       # Coverage of every module, lowest first
+  code_search spec-coverage MyApp.Accounts        # A single module
+  code_search spec-coverage --include-private     # Count defp/defmacrop too
+  code_search spec-coverage --drift               # Also list specs with a stale arity
+")]
+pub struct SpecCoverageCmd {
+    /// Only check modules matching this pattern (exact match or regex with -r)
+    pub module: Option<String>,
+
+    /// Also count private functions
+    #[arg(long, default_value_t = false)]
+    pub include_private: bool,
+
+    /// Also report specs whose arity matches no function definition
+    #[arg(long, default_value_t = false)]
+    pub drift: bool,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for SpecCoverageCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,130 @@
+//! Output formatting for spec-coverage command results.
+
+use super::execute::SpecCoverageResult;
+use crate::output::Outputable;
+
+impl Outputable for SpecCoverageResult {
+    fn to_table(&self) -> String {
+        let mut output = format!(
+            "Spec coverage: {}/{} function(s) ({:.1}%)\n\n",
+            self.specced_functions, self.total_functions, self.coverage
+        );
+
+        if self.modules.is_empty() {
+            output.push_str("No functions found.\n");
+        }
+
+        for module in &self.modules {
+            output.push_str(&format!(
+                "{} ({}): {}/{} ({:.1}%)\n",
+                module.module, module.file, module.specced, module.functions, module.coverage
+            ));
+            for missing in &module.missing {
+                output.push_str(&format!(
+                    "  missing: {}/{} [{}] L{}\n",
+                    missing.name, missing.arity, missing.kind, missing.line
+                ));
+            }
+        }
+
+        if let Some(drift) = &self.drift {
+            output.push_str(&format!("\nSpec drift ({}):\n", drift.len()));
+            if drift.is_empty() {
+                output.push_str("  none\n");
+            }
+            for d in drift {
+                let defined = if d.defined_arities.is_empty() {
+                    "no definition".to_string()
+                } else {
+                    let arities: Vec<String> = d
+                        .defined_arities
+                        .iter()
+                        .map(|a| format!("{}/{}", d.name, a))
+                        .collect();
+                    format!("defined as {}", arities.join(", "))
+                };
+                output.push_str(&format!(
+                    "  {}.{}/{} L{} - {}\n",
+                    d.module, d.name, d.spec_arity, d.line, defined
+                ));
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::spec_coverage::execute::{MissingSpec, ModuleSpecCoverage};
+    use db::queries::spec_coverage::SpecDrift;
+
+    fn result() -> SpecCoverageResult {
+        SpecCoverageResult {
+            module_pattern: "*".to_string(),
+            total_functions: 3,
+            specced_functions: 2,
+            coverage: 66.7,
+            modules: vec![ModuleSpecCoverage {
+                module: "MyApp.Accounts".to_string(),
+                file: "lib/my_app/accounts.ex".to_string(),
+                functions: 3,
+                specced: 2,
+                coverage: 66.7,
+                missing: vec![MissingSpec {
+                    name: "get_user".to_string(),
+                    arity: 2,
+                    kind: "def".to_string(),
+                    line: 17,
+                }],
+            }],
+            drift: None,
+        }
+    }
+
+    #[test]
+    fn test_spec_coverage_table() {
+        let expected = [
+            "Spec coverage: 2/3 function(s) (66.7%)",
+            "",
+            "MyApp.Accounts (lib/my_app/accounts.ex): 2/3 (66.7%)",
+            "  missing: get_user/2 [def] L17",
+            "",
+        ];
+        assert_eq!(result().to_table(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_spec_coverage_table_with_drift() {
+        let mut result = result();
+        result.drift = Some(vec![
+            SpecDrift {
+                module: "MyApp.Users".to_string(),
+                name: "archive".to_string(),
+                spec_arity: 1,
+                line: 20,
+                defined_arities: vec![],
+            },
+            SpecDrift {
+                module: "MyApp.Users".to_string(),
+                name: "create".to_string(),
+                spec_arity: 1,
+                line: 4,
+                defined_arities: vec![2, 3],
+            },
+        ]);
+
+        let output = result.to_table();
+        assert!(output.contains(
+            "\nSpec drift (2):\n  MyApp.Users.archive/1 L20 - no definition\n  MyApp.Users.create/1 L4 - defined as create/2, create/3\n"
+        ));
+    }
+
+    #[test]
+    fn test_spec_coverage_table_empty() {
+        let mut result = result();
+        result.modules.clear();
+        assert!(result.to_table().ends_with("No functions found.\n"));
+    }
+}
@@ -0,0 +1,211 @@
+//! Struct field usage from field access records and struct definitions.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::StructFieldsUsageCmd;
+use crate::commands::Execute;
+use db::query_builders::{compile_pattern, QuerySettings};
+use db::queries::field_accesses::find_field_accesses;
+use db::queries::structs::find_struct_fields;
+
+/// A function reading or writing a field
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldAccessSite {
+    pub caller_module: String,
+    pub caller_function: String,
+    /// "read" or "write"
+    pub access: String,
+    pub file: String,
+    pub line: i64,
+}
+
+/// Accesses of one struct field
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldUsage {
+    pub field: String,
+    pub reads: usize,
+    pub writes: usize,
+    pub accesses: Vec<FieldAccessSite>,
+}
+
+/// Fields of one struct
+#[derive(Debug, Clone, Serialize)]
+pub struct StructFieldsUsage {
+    pub module: String,
+    pub fields: Vec<FieldUsage>,
+}
+
+/// Result of the struct-fields-usage command
+#[derive(Debug, Serialize)]
+pub struct StructFieldsUsageResult {
+    pub module_pattern: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_filter: Option<String>,
+    pub total_accesses: usize,
+    pub structs: Vec<StructFieldsUsage>,
+}
+
+impl Execute for StructFieldsUsageCmd {
+    type Output = StructFieldsUsageResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let access_filter = self.access.map(|a| a.as_str());
+
+        let accesses = find_field_accesses(
+            db,
+            &self.module,
+            self.field.as_deref(),
+            access_filter,
+            &self.common.project,
+            self.common.regex,
+            self.common.limit,
+            settings,
+        )?;
+        let definitions = find_struct_fields(
+            db,
+            &self.module,
+            &self.common.project,
+            self.common.regex,
+            1000,
+            settings,
+        )?;
+
+        let field_filter = match &self.field {
+            Some(pattern) if self.common.regex => Some(compile_pattern(pattern)?),
+            Some(pattern) => Some(Regex::new(&format!("^{}$", regex::escape(pattern)))?),
+            None => None,
+        };
+
+        // Defined fields come first so untouched ones still show up
+        let mut structs: BTreeMap<String, BTreeMap<String, FieldUsage>> = BTreeMap::new();
+        for def in definitions {
+            if field_filter.as_ref().is_some_and(|re| !re.is_match(&def.field)) {
+                continue;
+            }
+            structs
+                .entry(def.module)
+                .or_default()
+                .insert(def.field.clone(), empty_usage(def.field));
+        }
+
+        let total_accesses = accesses.len();
+        for access in accesses {
+            let usage = structs
+                .entry(access.struct_module)
+                .or_default()
+                .entry(access.field.clone())
+                .or_insert_with(|| empty_usage(access.field));
+            match access.access.as_str() {
+                "write" => usage.writes += 1,
+                _ => usage.reads += 1,
+            }
+            usage.accesses.push(FieldAccessSite {
+                caller_module: access.caller_module,
+                caller_function: access.caller_function,
+                access: access.access,
+                file: access.file,
+                line: access.line,
+            });
+        }
+
+        Ok(StructFieldsUsageResult {
+            module_pattern: self.module,
+            field_filter: self.field,
+            access_filter: access_filter.map(String::from),
+            total_accesses,
+            structs: structs
+                .into_iter()
+                .map(|(module, fields)| StructFieldsUsage {
+                    module,
+                    fields: fields.into_values().collect(),
+                })
+                .collect(),
+        })
+    }
+}
+
+fn empty_usage(field: String) -> FieldUsage {
+    FieldUsage {
+        field,
+        reads: 0,
+        writes: 0,
+        accesses: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+    use crate::commands::struct_fields_usage::FieldAccessKind;
+
+    fn struct_fields_usage_cmd(module: &str, field: Option<&str>, access: Option<FieldAccessKind>) -> StructFieldsUsageCmd {
+        StructFieldsUsageCmd {
+            module: module.to_string(),
+            field: field.map(String::from),
+            access,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    fn field<'a>(result: &'a StructFieldsUsageResult, name: &str) -> &'a FieldUsage {
+        result.structs[0].fields.iter().find(|f| f.field == name).unwrap()
+    }
+
+    #[test]
+    fn test_struct_fields_usage_lists_all_fields() {
+        let db = db::test_utils::structs_db("test_project");
+        let result = struct_fields_usage_cmd("MyApp.User", None, None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_accesses, 4);
+        assert_eq!(result.structs.len(), 1);
+
+        let fields: Vec<&str> = result.structs[0].fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(fields, ["admin", "email", "id", "inserted_at", "name"]);
+
+        let email = field(&result, "email");
+        assert_eq!((email.reads, email.writes), (2, 1));
+        assert_eq!(email.accesses[1].caller_function, "update_email");
+        assert!(field(&result, "admin").accesses.is_empty());
+    }
+
+    #[test]
+    fn test_struct_fields_usage_field_and_access_filters() {
+        let db = db::test_utils::structs_db("test_project");
+        let result = struct_fields_usage_cmd("MyApp.User", Some("email"), Some(FieldAccessKind::Write))
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.access_filter.as_deref(), Some("write"));
+        assert_eq!(result.total_accesses, 1);
+        assert_eq!(result.structs[0].fields.len(), 1);
+
+        let email = field(&result, "email");
+        assert_eq!((email.reads, email.writes), (0, 1));
+        assert_eq!(email.accesses[0].line, 40);
+    }
+
+    #[test]
+    fn test_struct_fields_usage_unknown_struct() {
+        let db = db::test_utils::structs_db("test_project");
+        let result = struct_fields_usage_cmd("MyApp.Missing", None, None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_accesses, 0);
+        assert!(result.structs.is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::{Args, ValueEnum};
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Kind of field access to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FieldAccessKind {
+    /// Field reads (`user.email`, pattern matches)
+    Read,
+    /// Field writes (struct literals, update syntax)
+    Write,
+}
+
+impl FieldAccessKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FieldAccessKind::Read => "read",
+            FieldAccessKind::Write => "write",
+        }
+    }
+}
+
+/// Show which functions read or write each field of a struct
+///
+/// Uses the field access records of the import data. Fields defined on the
+/// struct but never accessed are listed too, so the output doubles as a
+/// rename or removal checklist.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search struct-fields-usage MyApp.User                  # Every field of MyApp.User
+  code_search struct-fields-usage MyApp.User email            # Who touches :email
+  code_search struct-fields-usage MyApp.User email --access write
+  code_search struct-fields-usage -r 'MyApp\\..*' 'user_id'     # Regex patterns
+")]
+pub struct StructFieldsUsageCmd {
+    /// Struct module name (exact match or pattern with --regex)
+    pub module: String,
+
+    /// Field name (exact match or pattern with --regex)
+    pub field: Option<String>,
+
+    /// Only show reads or only writes
+    #[arg(long, value_enum)]
+    pub access: Option<FieldAccessKind>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for StructFieldsUsageCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,125 @@
+//! Output formatting for struct-fields-usage command results.
+
+use super::execute::{FieldUsage, StructFieldsUsageResult};
+use crate::output::Outputable;
+
+impl Outputable for StructFieldsUsageResult {
+    fn to_table(&self) -> String {
+        let mut output = format!("Struct field usage: {}", self.module_pattern);
+        if let Some(field) = &self.field_filter {
+            output.push_str(&format!(".{}", field));
+        }
+        if let Some(access) = &self.access_filter {
+            output.push_str(&format!(" ({} only)", access));
+        }
+        output.push_str("\n\n");
+
+        if self.structs.is_empty() {
+            output.push_str("No struct fields found.\n");
+            return output;
+        }
+
+        output.push_str(&format!(
+            "Found {} access(es) in {} struct(s):\n",
+            self.total_accesses,
+            self.structs.len()
+        ));
+
+        for s in &self.structs {
+            output.push_str(&format!("\n{}:\n", s.module));
+            for field in &s.fields {
+                output.push_str(&format!("  {}: {}\n", field.field, field_summary(field)));
+                for site in &field.accesses {
+                    output.push_str(&format!(
+                        "    {}.{} ({}) {}:{}\n",
+                        site.caller_module, site.caller_function, site.access, site.file, site.line
+                    ));
+                }
+            }
+        }
+
+        output
+    }
+}
+
+fn field_summary(field: &FieldUsage) -> String {
+    if field.accesses.is_empty() {
+        "no accesses".to_string()
+    } else {
+        format!("{} read(s), {} write(s)", field.reads, field.writes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::struct_fields_usage::execute::{FieldAccessSite, StructFieldsUsage};
+
+    fn site(caller_function: &str, access: &str, line: i64) -> FieldAccessSite {
+        FieldAccessSite {
+            caller_module: "MyApp.Accounts".to_string(),
+            caller_function: caller_function.to_string(),
+            access: access.to_string(),
+            file: "lib/my_app/accounts.ex".to_string(),
+            line,
+        }
+    }
+
+    fn result() -> StructFieldsUsageResult {
+        StructFieldsUsageResult {
+            module_pattern: "MyApp.User".to_string(),
+            field_filter: None,
+            access_filter: None,
+            total_accesses: 2,
+            structs: vec![StructFieldsUsage {
+                module: "MyApp.User".to_string(),
+                fields: vec![
+                    FieldUsage {
+                        field: "admin".to_string(),
+                        reads: 0,
+                        writes: 0,
+                        accesses: vec![],
+                    },
+                    FieldUsage {
+                        field: "email".to_string(),
+                        reads: 1,
+                        writes: 1,
+                        accesses: vec![site("get_user", "read", 12), site("update_email", "write", 40)],
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_struct_fields_usage_table() {
+        let expected = [
+            "Struct field usage: MyApp.User",
+            "",
+            "Found 2 access(es) in 1 struct(s):",
+            "",
+            "MyApp.User:",
+            "  admin: no accesses",
+            "  email: 1 read(s), 1 write(s)",
+            "    MyApp.Accounts.get_user (read) lib/my_app/accounts.ex:12",
+            "    MyApp.Accounts.update_email (write) lib/my_app/accounts.ex:40",
+            "",
+        ];
+        assert_eq!(result().to_table(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_struct_fields_usage_table_header_with_filters() {
+        let mut result = result();
+        result.field_filter = Some("email".to_string());
+        result.access_filter = Some("write".to_string());
+        assert!(result.to_table().starts_with("Struct field usage: MyApp.User.email (write only)\n"));
+    }
+
+    #[test]
+    fn test_struct_fields_usage_table_empty() {
+        let mut result = result();
+        result.structs.clear();
+        assert!(result.to_table().ends_with("No struct fields found.\n"));
+    }
+}
@@ -6,6 +6,7 @@ use serde::Serialize;
 use super::StructUsageCmd;
 use crate::commands::Execute;
 use db::queries::struct_usage::{find_struct_usage, StructUsageEntry};
+use db::query_builders::QuerySettings;
 use db::types::ModuleGroupResult;
 
 /// A function that uses a struct type
@@ -150,7 +151,7 @@ fn build_struct_modules_result(pattern: String, entries: Vec<StructUsageEntry>)
 impl Execute for StructUsageCmd {
     type Output = StructUsageOutput;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         let entries = find_struct_usage(
             db,
             &self.pattern,
@@ -158,6 +159,7 @@ impl Execute for StructUsageCmd {
             self.common.regex,
             self.module.as_deref(),
             self.common.limit,
+            settings,
         )?;
 
         if self.by_module {
@@ -0,0 +1,81 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::SuggestFacadeCmd;
+use crate::commands::Execute;
+use db::queries::facade::{find_facade_usage, FacadeFunction};
+use db::query_builders::QuerySettings;
+
+/// Result of the suggest-facade command
+#[derive(Debug, Serialize)]
+pub struct SuggestFacadeResult {
+    pub namespace: String,
+    pub public_functions: usize,
+    /// Public functions called from outside the namespace
+    pub facade: Vec<FacadeFunction>,
+    /// Public functions only called from inside the namespace
+    pub unnecessarily_public: Vec<FacadeFunction>,
+    /// Public functions with no callers at all (see `unused`)
+    pub uncalled: usize,
+}
+
+impl Execute for SuggestFacadeCmd {
+    type Output = SuggestFacadeResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let functions = find_facade_usage(db, &self.project, &self.namespace, self.regex)?;
+        let public_functions = functions.len();
+
+        let mut facade = Vec::new();
+        let mut unnecessarily_public = Vec::new();
+        let mut uncalled = 0;
+        for function in functions {
+            if function.external_calls > 0 {
+                facade.push(function);
+            } else if function.internal_calls > 0 {
+                unnecessarily_public.push(function);
+            } else {
+                uncalled += 1;
+            }
+        }
+
+        Ok(SuggestFacadeResult {
+            namespace: self.namespace,
+            public_functions,
+            facade,
+            unnecessarily_public,
+            uncalled,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    crate::shared_fixture! {
+        fixture_name: populated_db,
+        fixture_type: call_graph,
+        project: "test_project",
+    }
+
+    #[rstest]
+    fn test_suggest_facade_partitions_public_functions(populated_db: db::DbInstance) {
+        let cmd = SuggestFacadeCmd {
+            namespace: "MyApp.Accounts".to_string(),
+            regex: false,
+            project: "test_project".to_string(),
+        };
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).expect("Suggest facade should succeed");
+
+        assert_eq!(
+            result.public_functions,
+            result.facade.len() + result.unnecessarily_public.len() + result.uncalled
+        );
+        assert!(result.facade.iter().all(|f| f.external_calls > 0));
+        assert!(result.facade.iter().any(|f| f.name == "get_user"));
+        assert!(result.unnecessarily_public.iter().all(|f| f.external_calls == 0 && f.internal_calls > 0));
+    }
+}
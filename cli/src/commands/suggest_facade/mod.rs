@@ -0,0 +1,43 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Suggest the public interface of a namespace from how other modules use it
+///
+/// The facade is the set of public functions called from outside the
+/// namespace (the module and its nested modules). Public functions only
+/// called from inside it are flagged as unnecessarily public.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search suggest-facade MyApp.Accounts                 # MyApp.Accounts and MyApp.Accounts.*
+  code_search suggest-facade '^MyApp\\.(Accounts|Auth)' -r   # Several modules as one namespace
+  code_search suggest-facade MyApp.Accounts -o github       # Annotate unnecessarily public functions
+")]
+pub struct SuggestFacadeCmd {
+    /// Namespace: a module and its nested modules, or a module regex with --regex
+    pub namespace: String,
+
+    /// Treat the namespace as a regular expression over module names
+    #[arg(short, long, default_value_t = false)]
+    pub regex: bool,
+
+    /// Project to search in
+    #[arg(long, default_value = "default")]
+    pub project: String,
+}
+
+impl CommandRunner for SuggestFacadeCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,127 @@
+//! Output formatting for suggest-facade command results.
+
+use super::execute::SuggestFacadeResult;
+use crate::output::{Annotation, Outputable};
+
+impl Outputable for SuggestFacadeResult {
+    fn to_table(&self) -> String {
+        let mut lines = vec![format!(
+            "Facade of {}: {} of {} public function(s) used from outside",
+            self.namespace,
+            self.facade.len(),
+            self.public_functions
+        )];
+
+        if self.public_functions == 0 {
+            lines.push(String::new());
+            lines.push("No public functions found.".to_string());
+            return lines.join("\n");
+        }
+
+        lines.push(String::new());
+        lines.push("Facade:".to_string());
+        if self.facade.is_empty() {
+            lines.push("  none".to_string());
+        }
+        for function in &self.facade {
+            lines.push(format!(
+                "  {}.{}/{}  {} call(s) from {}",
+                function.module,
+                function.name,
+                function.arity,
+                function.external_calls,
+                function.external_callers.join(", ")
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push("Unnecessarily public (only called inside the namespace):".to_string());
+        if self.unnecessarily_public.is_empty() {
+            lines.push("  none".to_string());
+        }
+        for function in &self.unnecessarily_public {
+            let hint = if function.own_module_only { ", could be defp" } else { "" };
+            lines.push(format!(
+                "  {}.{}/{}  {}:{} ({} internal call(s){})",
+                function.module,
+                function.name,
+                function.arity,
+                function.file,
+                function.line,
+                function.internal_calls,
+                hint
+            ));
+        }
+
+        if self.uncalled > 0 {
+            lines.push(String::new());
+            lines.push(format!("{} public function(s) are never called (see `unused`).", self.uncalled));
+        }
+
+        lines.join("\n")
+    }
+
+    fn to_annotations(&self) -> Option<Vec<Annotation>> {
+        Some(
+            self.unnecessarily_public
+                .iter()
+                .map(|function| {
+                    Annotation::warning(
+                        &function.file,
+                        function.line,
+                        "Unnecessarily public function",
+                        format!(
+                            "{}.{}/{} is only called from inside {}",
+                            function.module, function.name, function.arity, self.namespace
+                        ),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::facade::FacadeFunction;
+
+    fn function(module: &str, name: &str, external_callers: &[&str], internal_calls: usize) -> FacadeFunction {
+        FacadeFunction {
+            module: module.to_string(),
+            name: name.to_string(),
+            arity: 1,
+            file: "lib/accounts.ex".to_string(),
+            line: 6,
+            external_calls: external_callers.len(),
+            external_callers: external_callers.iter().map(|m| m.to_string()).collect(),
+            internal_calls,
+            own_module_only: external_callers.is_empty(),
+        }
+    }
+
+    #[test]
+    fn test_to_table() {
+        let result = SuggestFacadeResult {
+            namespace: "MyApp.Accounts".to_string(),
+            public_functions: 3,
+            facade: vec![function("MyApp.Accounts", "get_user", &["MyApp.Web"], 0)],
+            unnecessarily_public: vec![function("MyApp.Accounts", "normalize", &[], 2)],
+            uncalled: 1,
+        };
+
+        assert_eq!(
+            result.to_table(),
+            "\
+Facade of MyApp.Accounts: 1 of 3 public function(s) used from outside
+
+Facade:
+  MyApp.Accounts.get_user/1  1 call(s) from MyApp.Web
+
+Unnecessarily public (only called inside the namespace):
+  MyApp.Accounts.normalize/1  lib/accounts.ex:6 (2 internal call(s), could be defp)
+
+1 public function(s) are never called (see `unused`)."
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Summary report assembled from the individual analysis queries.
+
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::SummaryCmd;
+use crate::commands::{parallel, CommonArgs, CyclesCmd, Execute, GodModulesCmd};
+use db::queries::complexity::{find_complexity_metrics, ComplexityMetricKind, ComplexityThresholds};
+use db::queries::hotspots::{find_hotspots, Hotspot, HotspotKind};
+use db::queries::summary::{find_project_counts, ProjectCounts};
+use db::queries::unused::find_unused_functions;
+use db::query_builders::QuerySettings;
+
+/// A god module listed in the summary
+#[derive(Debug, Clone, Serialize)]
+pub struct SummaryGodModule {
+    pub module: String,
+    pub function_count: i64,
+    pub loc: i64,
+    pub incoming: i64,
+    pub outgoing: i64,
+    pub total: i64,
+}
+
+/// Result of the summary command
+#[derive(Debug, Serialize)]
+pub struct SummaryResult {
+    pub project: String,
+    pub counts: ProjectCounts,
+    /// Functions with the most incoming calls
+    pub hotspots: Vec<Hotspot>,
+    /// Modules over the default god-modules thresholds, by connectivity
+    pub god_modules: Vec<SummaryGodModule>,
+    pub cycles: usize,
+    pub modules_in_cycles: usize,
+    /// Functions never called (compiler-generated functions excluded)
+    pub unused_functions: usize,
+    /// Mean cyclomatic complexity over all function clauses
+    pub average_complexity: f64,
+}
+
+impl Execute for SummaryCmd {
+    type Output = SummaryResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let common = CommonArgs {
+            project: self.project.clone(),
+            regex: false,
+            limit: self.top,
+            namespace: Vec::new(),
+        };
+        let project = self.project.as_str();
+        let top = self.top;
+
+        // The sections are independent queries
+        let (counts, hotspots, god_modules, cycles, unused, metrics) = parallel::scope(|tasks| {
+            let counts = tasks.spawn(|| find_project_counts(db, project));
+            let hotspots = tasks.spawn(|| {
+                find_hotspots(db, HotspotKind::Incoming, None, None, project, false, top, false, false, settings)
+            });
+            // Same thresholds as the god-modules command defaults
+            let god_modules = tasks.spawn(|| {
+                GodModulesCmd {
+                    module: None,
+                    min_functions: 20,
+                    min_loc: 0,
+                    min_total: 10,
+                    sort: None,
+                    common: common.clone(),
+                }
+                .execute(db, settings)
+            });
+            let cycles = tasks.spawn(|| {
+                CyclesCmd {
+                    module: None,
+                    max_length: None,
+                    involving: None,
+                    baseline: Default::default(),
+                    common: CommonArgs { limit: 1000, ..common.clone() },
+                }
+                .execute(db, settings)
+            });
+            let unused = tasks.spawn(|| {
+                find_unused_functions(db, None, None, project, false, false, false, true, None, u32::MAX, settings)
+            });
+            let metrics = tasks.spawn(|| {
+                find_complexity_metrics(
+                    db,
+                    &ComplexityThresholds::default(),
+                    ComplexityMetricKind::Cyclomatic,
+                    None,
+                    project,
+                    false,
+                    false,
+                    None,
+                    u32::MAX,
+                    settings,
+                )
+            });
+            Ok::<_, Box<dyn Error>>((
+                counts.join()?,
+                hotspots.join()?,
+                god_modules.join()?,
+                cycles.join()?,
+                unused.join()?,
+                metrics.join()?,
+            ))
+        })?;
+
+        let god_modules = god_modules
+            .items
+            .into_iter()
+            .filter_map(|group| {
+                let entry = group.entries.into_iter().next()?;
+                Some(SummaryGodModule {
+                    module: group.name,
+                    function_count: entry.function_count,
+                    loc: entry.loc,
+                    incoming: entry.incoming,
+                    outgoing: entry.outgoing,
+                    total: entry.total,
+                })
+            })
+            .collect();
+
+        let average_complexity = if metrics.is_empty() {
+            0.0
+        } else {
+            metrics.iter().map(|m| m.complexity as f64).sum::<f64>() / metrics.len() as f64
+        };
+
+        Ok(SummaryResult {
+            project: self.project,
+            counts,
+            hotspots,
+            god_modules,
+            cycles: cycles.total_cycles,
+            modules_in_cycles: cycles.modules_in_cycles,
+            unused_functions: unused.len(),
+            average_complexity,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary_cmd(top: u32) -> SummaryCmd {
+        SummaryCmd {
+            project: "test_project".to_string(),
+            top,
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = summary_cmd(5).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.project, "test_project");
+        assert_eq!(result.counts.modules, 5);
+        assert_eq!(result.counts.functions, 15);
+        assert_eq!(result.counts.calls, 11);
+        assert_eq!(result.hotspots.len(), 5);
+        for pair in result.hotspots.windows(2) {
+            assert!(pair[0].incoming >= pair[1].incoming);
+        }
+        // No fixture module reaches the default 20-function threshold
+        assert!(result.god_modules.is_empty());
+        assert!(result.average_complexity > 0.0);
+    }
+
+    #[test]
+    fn test_summary_top_limits_hotspots() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = summary_cmd(2).execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert_eq!(result.hotspots.len(), 2);
+    }
+
+    #[test]
+    fn test_summary_wrong_project() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let mut cmd = summary_cmd(5);
+        cmd.project = "other".to_string();
+
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert_eq!(result.counts.functions, 0);
+        assert!(result.hotspots.is_empty());
+        assert_eq!(result.cycles, 0);
+        assert_eq!(result.unused_functions, 0);
+        assert_eq!(result.average_complexity, 0.0);
+    }
+
+    #[test]
+    fn test_summary_empty_db() {
+        let db = db::test_utils::setup_empty_test_db();
+        assert!(summary_cmd(5).execute(&db, &QuerySettings::default()).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// One-page architectural overview of a project
+///
+/// Combines size totals, the top hotspots and god modules, cycle and unused
+/// function counts, and the average cyclomatic complexity in a single report.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search summary                     # Overview of the default project
+  code_search summary --project my_app    # Overview of another project
+  code_search summary --top 10            # Show the top 10 hotspots and god modules
+  code_search summary -o json             # Machine-readable report for dashboards
+")]
+pub struct SummaryCmd {
+    /// Project to summarize
+    #[arg(long, default_value = "default")]
+    pub project: String,
+
+    /// Number of hotspots and god modules to list
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..=100))]
+    pub top: u32,
+}
+
+impl CommandRunner for SummaryCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,118 @@
+//! Output formatting for summary command results.
+
+use super::execute::SummaryResult;
+use crate::output::Outputable;
+
+impl Outputable for SummaryResult {
+    fn to_table(&self) -> String {
+        let mut output = String::new();
+        output.push_str(&format!("Project summary: {}\n\n", self.project));
+
+        output.push_str(&format!("Modules:            {}\n", self.counts.modules));
+        output.push_str(&format!("Functions:          {}\n", self.counts.functions));
+        output.push_str(&format!("Calls:              {}\n", self.counts.calls));
+        output.push_str(&format!(
+            "Cycles:             {} ({} module(s) involved)\n",
+            self.cycles, self.modules_in_cycles
+        ));
+        output.push_str(&format!("Unused functions:   {}\n", self.unused_functions));
+        output.push_str(&format!("Average complexity: {:.2}\n", self.average_complexity));
+
+        output.push_str("\nTop hotspots (incoming calls):\n");
+        if self.hotspots.is_empty() {
+            output.push_str("  (none)\n");
+        }
+        for hotspot in &self.hotspots {
+            output.push_str(&format!(
+                "  {}.{}  in: {}, out: {}\n",
+                hotspot.module, hotspot.function, hotspot.incoming, hotspot.outgoing
+            ));
+        }
+
+        output.push_str("\nTop god modules:\n");
+        if self.god_modules.is_empty() {
+            output.push_str("  (none)\n");
+        }
+        for module in &self.god_modules {
+            output.push_str(&format!(
+                "  {}  functions: {}, loc: {}, in: {}, out: {}, total: {}\n",
+                module.module,
+                module.function_count,
+                module.loc,
+                module.incoming,
+                module.outgoing,
+                module.total
+            ));
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::summary::execute::SummaryGodModule;
+    use db::queries::hotspots::Hotspot;
+    use db::queries::summary::ProjectCounts;
+
+    fn result() -> SummaryResult {
+        SummaryResult {
+            project: "default".to_string(),
+            counts: ProjectCounts {
+                modules: 5,
+                functions: 15,
+                calls: 11,
+            },
+            hotspots: vec![Hotspot {
+                module: "MyApp.Repo".to_string(),
+                function: "get".to_string(),
+                incoming: 3,
+                outgoing: 0,
+                total: 3,
+                ratio: 9999.0,
+            }],
+            god_modules: vec![SummaryGodModule {
+                module: "MyApp.Core".to_string(),
+                function_count: 40,
+                loc: 900,
+                incoming: 12,
+                outgoing: 8,
+                total: 20,
+            }],
+            cycles: 1,
+            modules_in_cycles: 2,
+            unused_functions: 4,
+            average_complexity: 1.5,
+        }
+    }
+
+    #[test]
+    fn test_summary_output() {
+        let output = result().to_table();
+        assert!(output.contains("Project summary: default"));
+        assert!(output.contains("Functions:          15"));
+        assert!(output.contains("Cycles:             1 (2 module(s) involved)"));
+        assert!(output.contains("Average complexity: 1.50"));
+        assert!(output.contains("  MyApp.Repo.get  in: 3, out: 0"));
+        assert!(output.contains("  MyApp.Core  functions: 40, loc: 900, in: 12, out: 8, total: 20"));
+    }
+
+    #[test]
+    fn test_summary_output_empty_lists() {
+        let mut result = result();
+        result.hotspots.clear();
+        result.god_modules.clear();
+
+        let output = result.to_table();
+        assert_eq!(output.matches("  (none)").count(), 2);
+    }
+
+    #[test]
+    fn test_summary_output_json() {
+        let json = serde_json::to_string(&result()).unwrap();
+        assert!(json.contains("\"counts\":{\"modules\":5,\"functions\":15,\"calls\":11}"));
+        assert!(json.contains("\"average_complexity\":1.5"));
+        assert!(json.contains("\"god_modules\""));
+    }
+}
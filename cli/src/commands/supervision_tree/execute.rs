@@ -0,0 +1,205 @@
+//! Supervision tree assembly from supervisor -> child spec edges.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::SupervisionTreeCmd;
+use crate::commands::Execute;
+use db::query_builders::{compile_pattern, QuerySettings};
+use db::queries::supervision::{
+    ModuleFootprint, SupervisionChild, find_supervision_children, find_supervision_footprints,
+};
+
+/// A supervisor or worker in the tree
+#[derive(Debug, Clone, Serialize)]
+pub struct SupervisionNode {
+    /// Child spec id (the module name for top-level supervisors)
+    pub id: String,
+    pub module: String,
+    /// "supervisor" or "worker"
+    pub child_type: String,
+    /// Restart type; empty for top-level supervisors
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub restart: String,
+    /// Restart strategy when the node supervises children
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub strategy: String,
+    /// Size and call counts of the module; absent when it is not in the call graph
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footprint: Option<ModuleFootprint>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<SupervisionNode>,
+}
+
+/// Result of the supervision-tree command
+#[derive(Debug, Serialize)]
+pub struct SupervisionTreeResult {
+    pub project: String,
+    /// Distinct supervisors in the imported data
+    pub total_supervisors: usize,
+    /// Child specs in the imported data
+    pub total_children: usize,
+    pub roots: Vec<SupervisionNode>,
+}
+
+/// Supervision edges indexed for tree building
+struct Tree<'a> {
+    children: BTreeMap<&'a str, Vec<&'a SupervisionChild>>,
+    strategies: BTreeMap<&'a str, &'a str>,
+    footprints: BTreeMap<String, ModuleFootprint>,
+    max_depth: u32,
+}
+
+impl Tree<'_> {
+    fn node(&self, id: &str, module: &str, child_type: &str, restart: &str, depth: u32, path: &mut Vec<String>) -> SupervisionNode {
+        let mut children = Vec::new();
+
+        // A module restarting itself further down would otherwise recurse forever
+        if depth < self.max_depth && !path.iter().any(|m| m == module) {
+            path.push(module.to_string());
+            for child in self.children.get(module).into_iter().flatten() {
+                children.push(self.node(
+                    &child.child_id,
+                    &child.child_module,
+                    &child.child_type,
+                    &child.restart,
+                    depth + 1,
+                    path,
+                ));
+            }
+            path.pop();
+        }
+
+        SupervisionNode {
+            id: id.to_string(),
+            module: module.to_string(),
+            child_type: child_type.to_string(),
+            restart: restart.to_string(),
+            strategy: self.strategies.get(module).copied().unwrap_or_default().to_string(),
+            footprint: self.footprints.get(module).cloned(),
+            children,
+        }
+    }
+}
+
+impl Execute for SupervisionTreeCmd {
+    type Output = SupervisionTreeResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let filter = match &self.supervisor {
+            Some(pattern) if self.regex => Some(compile_pattern(pattern)?),
+            Some(pattern) => Some(Regex::new(&regex::escape(pattern))?),
+            None => None,
+        };
+
+        let edges = find_supervision_children(db, &self.project)?;
+        let footprints = find_supervision_footprints(db, &self.project)?;
+
+        let mut children: BTreeMap<&str, Vec<&SupervisionChild>> = BTreeMap::new();
+        let mut strategies = BTreeMap::new();
+        for edge in &edges {
+            children.entry(edge.supervisor.as_str()).or_default().push(edge);
+            strategies.insert(edge.supervisor.as_str(), edge.strategy.as_str());
+        }
+
+        // Without a pattern, start from supervisors that nothing else supervises
+        let supervised: BTreeSet<&str> = edges.iter().map(|e| e.child_module.as_str()).collect();
+        let roots: Vec<&str> = children
+            .keys()
+            .copied()
+            .filter(|s| match &filter {
+                Some(re) => re.is_match(s),
+                None => !supervised.contains(s),
+            })
+            .collect();
+
+        let tree = Tree {
+            children,
+            strategies,
+            footprints,
+            max_depth: self.depth,
+        };
+        let roots = roots
+            .into_iter()
+            .map(|s| tree.node(s, s, "supervisor", "", 0, &mut Vec::new()))
+            .collect();
+
+        Ok(SupervisionTreeResult {
+            project: self.project,
+            total_supervisors: tree.children.len(),
+            total_children: edges.len(),
+            roots,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supervision_tree_cmd(supervisor: Option<&str>, depth: u32) -> SupervisionTreeCmd {
+        SupervisionTreeCmd {
+            supervisor: supervisor.map(String::from),
+            depth,
+            regex: false,
+            project: "test_project".to_string(),
+        }
+    }
+
+    fn ids(nodes: &[SupervisionNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_supervision_tree_from_top_level() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = supervision_tree_cmd(None, 10)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_supervisors, 2);
+        assert_eq!(result.total_children, 4);
+        assert_eq!(ids(&result.roots), ["MyApp.Application"]);
+
+        let app = &result.roots[0];
+        assert_eq!(app.strategy, "one_for_one");
+        assert!(app.footprint.is_none());
+        assert_eq!(ids(&app.children), ["MyApp.Repo", "MyApp.Notifier", "MyApp.WorkerSupervisor"]);
+
+        let workers = &app.children[2];
+        assert_eq!(workers.strategy, "one_for_all");
+        assert_eq!(ids(&workers.children), ["MyApp.Service"]);
+        assert_eq!(workers.children[0].restart, "transient");
+
+        let repo = app.children[0].footprint.as_ref().expect("Repo is in the call graph");
+        assert_eq!(repo.function_count, 3);
+    }
+
+    #[test]
+    fn test_supervision_tree_pattern_and_depth() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let result = supervision_tree_cmd(Some("WorkerSupervisor"), 10)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+        assert_eq!(ids(&result.roots), ["MyApp.WorkerSupervisor"]);
+
+        let result = supervision_tree_cmd(None, 1)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+        assert!(result.roots[0].children[2].children.is_empty());
+    }
+
+    #[test]
+    fn test_supervision_tree_without_data() {
+        let db = db::test_utils::call_graph_db("test_project");
+        let mut cmd = supervision_tree_cmd(None, 10);
+        cmd.project = "other".to_string();
+
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert_eq!(result.total_supervisors, 0);
+        assert!(result.roots.is_empty());
+    }
+}
@@ -0,0 +1,48 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Show the OTP supervision tree with each process module's call-graph footprint
+///
+/// Renders the supervisor -> child spec edges imported from a supervision dump
+/// (`import --supervision`, or a `supervision` list in the call graph JSON).
+/// Each module in the tree is shown with its file, function count, lines of
+/// code and call counts, so process structure can be read next to call structure.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search supervision-tree                          # Every tree, from its top-level supervisors
+  code_search supervision-tree MyApp.Application        # Only the tree under MyApp.Application
+  code_search supervision-tree Worker --depth 1         # Supervisors matching Worker, direct children only
+")]
+pub struct SupervisionTreeCmd {
+    /// Start from supervisors matching this pattern (substring or regex with -r)
+    pub supervisor: Option<String>,
+
+    /// Maximum depth of children to show
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..=50))]
+    pub depth: u32,
+
+    /// Treat patterns as regular expressions
+    #[arg(short, long, default_value_t = false)]
+    pub regex: bool,
+
+    /// Project to search in
+    #[arg(long, default_value = "default")]
+    pub project: String,
+}
+
+impl CommandRunner for SupervisionTreeCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
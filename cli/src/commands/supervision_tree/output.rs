@@ -0,0 +1,131 @@
+//! Output formatting for supervision-tree command results.
+
+use super::execute::{SupervisionNode, SupervisionTreeResult};
+use crate::output::Outputable;
+
+impl Outputable for SupervisionTreeResult {
+    fn to_table(&self) -> String {
+        let mut output = format!(
+            "Supervision tree ({} supervisor(s), {} child spec(s))\n\n",
+            self.total_supervisors, self.total_children
+        );
+
+        if self.roots.is_empty() {
+            output.push_str("No supervision data found. Import a dump with `import --supervision <FILE>`.\n");
+            return output;
+        }
+
+        for root in &self.roots {
+            push_node(&mut output, root, 0);
+        }
+
+        output
+    }
+}
+
+/// Push a node and, indented below it, its children in start order
+fn push_node(output: &mut String, node: &SupervisionNode, depth: usize) {
+    output.push_str(&format!("{}{}\n", "  ".repeat(depth), node_label(node)));
+    for child in &node.children {
+        push_node(output, child, depth + 1);
+    }
+}
+
+fn node_label(node: &SupervisionNode) -> String {
+    let mut label = if node.id == node.module {
+        node.module.clone()
+    } else {
+        format!("{} ({})", node.id, node.module)
+    };
+
+    let mut spec = vec![node.child_type.as_str()];
+    if !node.restart.is_empty() {
+        spec.push(&node.restart);
+    }
+    if !node.strategy.is_empty() {
+        spec.push(&node.strategy);
+    }
+    label.push_str(&format!(" [{}]", spec.join(", ")));
+
+    match &node.footprint {
+        Some(f) => label.push_str(&format!(
+            " - {}, {} fn, {} LoC, {} in / {} out",
+            f.file, f.function_count, f.loc, f.incoming, f.outgoing
+        )),
+        None => label.push_str(" - not in call graph"),
+    }
+
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::supervision::ModuleFootprint;
+
+    fn node(id: &str, child_type: &str, restart: &str, children: Vec<SupervisionNode>) -> SupervisionNode {
+        SupervisionNode {
+            id: id.to_string(),
+            module: id.to_string(),
+            child_type: child_type.to_string(),
+            restart: restart.to_string(),
+            strategy: if children.is_empty() { String::new() } else { "one_for_one".to_string() },
+            footprint: None,
+            children,
+        }
+    }
+
+    #[test]
+    fn test_supervision_tree_table() {
+        let mut repo = node("MyApp.Repo", "worker", "permanent", vec![]);
+        repo.footprint = Some(ModuleFootprint {
+            file: "lib/my_app/repo.ex".to_string(),
+            function_count: 3,
+            loc: 21,
+            incoming: 3,
+            outgoing: 0,
+        });
+        let workers = node(
+            "MyApp.WorkerSupervisor",
+            "supervisor",
+            "permanent",
+            vec![node("MyApp.Service", "worker", "transient", vec![])],
+        );
+        let result = SupervisionTreeResult {
+            project: "default".to_string(),
+            total_supervisors: 2,
+            total_children: 3,
+            roots: vec![node("MyApp.Application", "supervisor", "", vec![repo, workers])],
+        };
+
+        let expected = [
+            "Supervision tree (2 supervisor(s), 3 child spec(s))",
+            "",
+            "MyApp.Application [supervisor, one_for_one] - not in call graph",
+            "  MyApp.Repo [worker, permanent] - lib/my_app/repo.ex, 3 fn, 21 LoC, 3 in / 0 out",
+            "  MyApp.WorkerSupervisor [supervisor, permanent, one_for_one] - not in call graph",
+            "    MyApp.Service [worker, transient] - not in call graph",
+            "",
+        ];
+        assert_eq!(result.to_table(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_supervision_tree_table_empty() {
+        let result = SupervisionTreeResult {
+            project: "default".to_string(),
+            total_supervisors: 0,
+            total_children: 0,
+            roots: vec![],
+        };
+        assert!(result.to_table().contains("No supervision data found."));
+    }
+
+    #[test]
+    fn test_supervision_tree_json_skips_empty_fields() {
+        let json = serde_json::to_value(node("MyApp.Service", "worker", "transient", vec![])).unwrap();
+        assert!(json.get("children").is_none());
+        assert!(json.get("footprint").is_none());
+        assert!(json.get("strategy").is_none());
+    }
+}
@@ -0,0 +1,184 @@
+//! Tests reaching a function, or functions reached from a test file, grouped by module.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::TestSurfaceCmd;
+use crate::commands::Execute;
+use db::queries::test_surface::{find_covering_tests, find_reached_functions, SurfaceFunction};
+use db::query_builders::QuerySettings;
+use db::types::ModuleGroup;
+
+/// What the functions of a test surface are
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SurfaceDirection {
+    /// Test functions reaching the module or function
+    Tests,
+    /// Production functions the test file reaches
+    Reached,
+}
+
+/// A function of the surface
+#[derive(Debug, Clone, Serialize)]
+pub struct SurfaceEntry {
+    pub name: String,
+    pub arity: i64,
+    /// Calls between it and the target, 1 for a direct call
+    pub depth: i64,
+    pub line: i64,
+}
+
+/// Result of the test-surface command
+#[derive(Debug, Serialize)]
+pub struct TestSurfaceResult {
+    /// The module or function under test, or the test file
+    pub target: String,
+    pub direction: SurfaceDirection,
+    pub depth: u32,
+    pub total_items: usize,
+    /// Modules in order of their closest function
+    pub items: Vec<ModuleGroup<SurfaceEntry>>,
+}
+
+impl Execute for TestSurfaceCmd {
+    type Output = TestSurfaceResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let project = &self.common.project;
+        let (target, direction, functions) = match &self.file {
+            Some(file) => (
+                file.clone(),
+                SurfaceDirection::Reached,
+                find_reached_functions(db, project, file, self.depth, self.common.limit, settings)?,
+            ),
+            None => {
+                let module = self.module.as_deref().unwrap_or_default();
+                let target = match (&self.function, self.arity) {
+                    (Some(function), Some(arity)) => format!("{}.{}/{}", module, function, arity),
+                    (Some(function), None) => format!("{}.{}", module, function),
+                    (None, _) => module.to_string(),
+                };
+                let functions = find_covering_tests(
+                    db,
+                    project,
+                    module,
+                    self.function.as_deref(),
+                    self.arity,
+                    self.common.regex,
+                    self.depth,
+                    self.common.limit,
+                    settings,
+                )?;
+                (target, SurfaceDirection::Tests, functions)
+            }
+        };
+
+        Ok(TestSurfaceResult {
+            target,
+            direction,
+            depth: self.depth,
+            total_items: functions.len(),
+            items: group_by_module(functions),
+        })
+    }
+}
+
+/// Group functions by module, keeping the order in which modules first appear
+fn group_by_module(functions: Vec<SurfaceFunction>) -> Vec<ModuleGroup<SurfaceEntry>> {
+    let mut positions: BTreeMap<String, usize> = BTreeMap::new();
+    let mut groups: Vec<ModuleGroup<SurfaceEntry>> = Vec::new();
+    for function in functions {
+        let index = *positions.entry(function.module.clone()).or_insert_with(|| {
+            groups.push(ModuleGroup {
+                name: function.module.clone(),
+                file: function.file.clone(),
+                entries: Vec::new(),
+                function_count: None,
+            });
+            groups.len() - 1
+        });
+        groups[index].entries.push(SurfaceEntry {
+            name: function.name,
+            arity: function.arity,
+            depth: function.depth,
+            line: function.line,
+        });
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+
+    const SURFACE_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Accounts": {
+                "register/1:3": {"name": "register", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 6}
+            },
+            "MyApp.Repo": {
+                "insert/1:2": {"name": "insert", "arity": 1, "file": "lib/repo.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 4}
+            },
+            "MyApp.AccountsTest": {
+                "test_register/0:4": {"name": "test_register", "arity": 0, "file": "test/accounts_test.exs", "kind": "def", "line": 4, "start_line": 4, "end_line": 6},
+                "test_duplicate/0:8": {"name": "test_duplicate", "arity": 0, "file": "test/accounts_test.exs", "kind": "def", "line": 8, "start_line": 8, "end_line": 9}
+            }
+        },
+        "calls": [
+            {"caller": {"module": "MyApp.Accounts", "function": "register/1", "file": "lib/accounts.ex", "line": 4}, "type": "remote", "callee": {"module": "MyApp.Repo", "function": "insert", "arity": 1}},
+            {"caller": {"module": "MyApp.AccountsTest", "function": "test_register/0", "file": "test/accounts_test.exs", "line": 5}, "type": "remote", "callee": {"module": "MyApp.Accounts", "function": "register", "arity": 1}},
+            {"caller": {"module": "MyApp.AccountsTest", "function": "test_duplicate/0", "file": "test/accounts_test.exs", "line": 9}, "type": "remote", "callee": {"module": "MyApp.Accounts", "function": "register", "arity": 1}}
+        ]
+    }"#;
+
+    fn test_surface_cmd(module: Option<&str>, function: Option<&str>, file: Option<&str>) -> TestSurfaceCmd {
+        TestSurfaceCmd {
+            module: module.map(String::from),
+            function: function.map(String::from),
+            arity: None,
+            file: file.map(String::from),
+            depth: 5,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_test_surface_of_function() {
+        let db = db::test_utils::setup_test_db(SURFACE_JSON, "test_project");
+        let result = test_surface_cmd(Some("MyApp.Repo"), Some("insert"), None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.target, "MyApp.Repo.insert");
+        assert_eq!(result.direction, SurfaceDirection::Tests);
+        assert_eq!(result.total_items, 2);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].name, "MyApp.AccountsTest");
+        assert_eq!(result.items[0].file, "test/accounts_test.exs");
+        let entries: Vec<(&str, i64)> = result.items[0].entries.iter().map(|e| (e.name.as_str(), e.depth)).collect();
+        assert_eq!(entries, [("test_duplicate", 2), ("test_register", 2)]);
+    }
+
+    #[test]
+    fn test_test_surface_of_file() {
+        let db = db::test_utils::setup_test_db(SURFACE_JSON, "test_project");
+        let result = test_surface_cmd(None, None, Some("test/accounts_test.exs"))
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.direction, SurfaceDirection::Reached);
+        let names: Vec<&str> = result.items.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["MyApp.Accounts", "MyApp.Repo"]);
+        assert_eq!(result.items[1].entries[0].depth, 2);
+    }
+}
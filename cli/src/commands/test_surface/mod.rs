@@ -0,0 +1,57 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Map tests to the functions they exercise
+///
+/// For a module or function, lists the test functions calling it directly or
+/// through other functions. With --file, lists the production functions the
+/// functions of a test file reach. Tests are the modules imported from test/
+/// directories, _test.exs files or `import.test_paths`; each function is
+/// reported once, at the shortest call depth.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search test-surface MyApp.Accounts register           # Tests reaching Accounts.register
+  code_search test-surface MyApp.Accounts                    # Tests reaching any function of the module
+  code_search test-surface MyApp.Repo insert -a 1 --depth 2  # Only direct callers and their callers
+  code_search test-surface --file test/my_app/accounts_test.exs  # What the test file exercises
+")]
+pub struct TestSurfaceCmd {
+    /// Module under test (exact match or pattern with --regex)
+    #[arg(required_unless_present = "file", conflicts_with = "file")]
+    pub module: Option<String>,
+
+    /// Function under test (exact match or pattern with --regex), every function of the module if omitted
+    pub function: Option<String>,
+
+    /// Function arity
+    #[arg(short, long, requires = "function")]
+    pub arity: Option<i64>,
+
+    /// Test file whose reach into production code to list
+    #[arg(long)]
+    pub file: Option<String>,
+
+    /// Maximum call depth to traverse (1-20)
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..=20))]
+    pub depth: u32,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for TestSurfaceCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
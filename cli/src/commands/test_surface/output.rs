@@ -0,0 +1,100 @@
+//! Output formatting for test-surface command results.
+
+use super::execute::{SurfaceDirection, TestSurfaceResult};
+use crate::output::Outputable;
+
+impl Outputable for TestSurfaceResult {
+    fn to_table(&self) -> String {
+        let mut output = match self.direction {
+            SurfaceDirection::Tests => format!("Tests reaching {} (depth {})\n", self.target, self.depth),
+            SurfaceDirection::Reached => format!("Functions reached from {} (depth {})\n", self.target, self.depth),
+        };
+
+        if self.items.is_empty() {
+            output.push_str(match self.direction {
+                SurfaceDirection::Tests => "\nNo tests reach it.\n",
+                SurfaceDirection::Reached => "\nNo production functions reached.\n",
+            });
+            return output;
+        }
+
+        output.push_str(&format!(
+            "\nFound {} function(s) in {} module(s):\n",
+            self.total_items,
+            self.items.len()
+        ));
+        for module in &self.items {
+            output.push_str(&format!("\n{} ({}):\n", module.name, module.file));
+            for entry in &module.entries {
+                output.push_str(&format!(
+                    "  {}/{} L{} (depth {})\n",
+                    entry.name, entry.arity, entry.line, entry.depth
+                ));
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_surface::execute::SurfaceEntry;
+    use db::types::ModuleGroup;
+
+    fn result() -> TestSurfaceResult {
+        TestSurfaceResult {
+            target: "MyApp.Repo.insert".to_string(),
+            direction: SurfaceDirection::Tests,
+            depth: 5,
+            total_items: 2,
+            items: vec![ModuleGroup {
+                name: "MyApp.AccountsTest".to_string(),
+                file: "test/accounts_test.exs".to_string(),
+                entries: vec![
+                    SurfaceEntry {
+                        name: "test_register".to_string(),
+                        arity: 0,
+                        depth: 2,
+                        line: 4,
+                    },
+                    SurfaceEntry {
+                        name: "test_duplicate".to_string(),
+                        arity: 0,
+                        depth: 2,
+                        line: 8,
+                    },
+                ],
+                function_count: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_test_surface_table() {
+        let expected = [
+            "Tests reaching MyApp.Repo.insert (depth 5)",
+            "",
+            "Found 2 function(s) in 1 module(s):",
+            "",
+            "MyApp.AccountsTest (test/accounts_test.exs):",
+            "  test_register/0 L4 (depth 2)",
+            "  test_duplicate/0 L8 (depth 2)",
+            "",
+        ];
+        assert_eq!(result().to_table(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_test_surface_table_empty() {
+        let mut result = result();
+        result.items.clear();
+        result.direction = SurfaceDirection::Reached;
+        result.target = "test/accounts_test.exs".to_string();
+        assert_eq!(
+            result.to_table(),
+            "Functions reached from test/accounts_test.exs (depth 5)\n\nNo production functions reached.\n"
+        );
+    }
+}
@@ -71,6 +71,33 @@ mod tests {
         expected: 50,
     }
 
+    crate::cli_option_test! {
+        command: "trace",
+        variant: Trace,
+        test_name: test_with_memory_engine,
+        args: ["MyApp", "foo", "--engine", "memory"],
+        field: engine,
+        expected: crate::commands::Engine::Memory,
+    }
+
+    crate::cli_option_test! {
+        command: "trace",
+        variant: Trace,
+        test_name: test_with_include_messages,
+        args: ["MyApp", "foo", "--include-messages"],
+        field: include_messages,
+        expected: true,
+    }
+
+    crate::cli_option_test! {
+        command: "trace",
+        variant: Trace,
+        test_name: test_with_certain_only,
+        args: ["MyApp", "foo", "--certain-only"],
+        field: certain_only,
+        expected: true,
+    }
+
     // =========================================================================
     // Limit validation tests
     // =========================================================================
@@ -3,7 +3,7 @@
 #[cfg(test)]
 mod tests {
     use super::super::TraceCmd;
-    use crate::commands::CommonArgs;
+    use crate::commands::{CommonArgs, Engine};
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -25,10 +25,15 @@ mod tests {
             function: "index".to_string(),
             arity: None,
             depth: 1,
+            engine: Engine::Datalog,
+            include_messages: false,
+            min_weight: 1,
+            certain_only: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -51,10 +56,15 @@ mod tests {
             function: "index".to_string(),
             arity: None,
             depth: 3,
+            engine: Engine::Datalog,
+            include_messages: false,
+            min_weight: 1,
+            certain_only: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -62,6 +72,31 @@ mod tests {
         },
     }
 
+    // Controller.show -> get_user is a single call site, so nothing is followed
+    crate::execute_test! {
+        test_name: test_trace_min_weight,
+        fixture: populated_db,
+        cmd: TraceCmd {
+            module: "MyApp.Controller".to_string(),
+            function: "show".to_string(),
+            arity: None,
+            depth: 3,
+            engine: Engine::Datalog,
+            include_messages: false,
+            min_weight: 2,
+            certain_only: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert_eq!(result.total_items, 0);
+        },
+    }
+
     crate::execute_test! {
         test_name: test_trace_with_depth_limit,
         fixture: populated_db,
@@ -70,10 +105,15 @@ mod tests {
             function: "index".to_string(),
             arity: None,
             depth: 2,
+            engine: Engine::Datalog,
+            include_messages: false,
+            min_weight: 1,
+            certain_only: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -82,6 +122,130 @@ mod tests {
         },
     }
 
+    // Same traversal answered from the in-memory adjacency index
+    crate::execute_test! {
+        test_name: test_trace_memory_engine,
+        fixture: populated_db,
+        cmd: TraceCmd {
+            module: "MyApp.Controller".to_string(),
+            function: "index".to_string(),
+            arity: None,
+            depth: 3,
+            engine: Engine::Memory,
+            include_messages: false,
+            min_weight: 1,
+            certain_only: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert_eq!(result.total_items, 2);
+            assert_eq!(result.entries[1].function, "list_users");
+            assert_eq!(result.entries[2].module, "MyApp.Repo");
+            assert_eq!(result.entries[2].parent_index, Some(1));
+        },
+    }
+
+    // Both engines start the trace only from a function in the namespace
+    #[rstest]
+    #[case(Engine::Datalog, "MyApp.Accounts", 0)]
+    #[case(Engine::Memory, "MyApp.Accounts", 0)]
+    #[case(Engine::Datalog, "MyApp.Controller", 2)]
+    #[case(Engine::Memory, "MyApp.Controller", 2)]
+    fn test_trace_in_namespace(
+        populated_db: db::DbInstance,
+        #[case] engine: Engine,
+        #[case] namespace: &str,
+        #[case] expected: usize,
+    ) {
+        use crate::commands::Execute;
+        use db::query_builders::QuerySettings;
+
+        let cmd = TraceCmd {
+            module: "MyApp.Controller".to_string(),
+            function: "index".to_string(),
+            arity: None,
+            depth: 3,
+            engine,
+            include_messages: false,
+            min_weight: 1,
+            certain_only: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: vec![namespace.to_string()],
+            },
+        };
+        let settings = QuerySettings { namespaces: cmd.common.namespace.clone(), ..Default::default() };
+        let result = cmd.execute(&populated_db, &settings);
+
+        assert_eq!(result.expect("Execute should succeed").total_items, expected);
+    }
+
+    // The memory engine prunes by namespace before applying the limit, so a
+    // small limit returns the same rows from both engines
+    #[rstest]
+    fn test_trace_in_namespace_with_limit_engines_agree(populated_db: db::DbInstance) {
+        use crate::commands::Execute;
+        use db::query_builders::QuerySettings;
+
+        let run = |engine| {
+            let cmd = TraceCmd {
+                module: "MyApp\\..*".to_string(),
+                function: ".*".to_string(),
+                arity: None,
+                depth: 3,
+                engine,
+                include_messages: false,
+                min_weight: 1,
+                certain_only: false,
+                common: CommonArgs {
+                    project: "test_project".to_string(),
+                    regex: true,
+                    limit: 1,
+                    namespace: vec!["MyApp.Controller".to_string()],
+                },
+            };
+            let settings = QuerySettings { namespaces: cmd.common.namespace.clone(), ..Default::default() };
+            cmd.execute(&populated_db, &settings).expect("Execute should succeed")
+        };
+
+        let (expected, actual) = (run(Engine::Datalog), run(Engine::Memory));
+        assert_eq!(expected.total_items, 1);
+        assert_eq!(serde_json::to_string(&actual).unwrap(), serde_json::to_string(&expected).unwrap());
+    }
+
+    // Service.fetch calls do_fetch in a branch, so the trace stops at fetch
+    crate::execute_test! {
+        test_name: test_trace_certain_only,
+        fixture: populated_db,
+        cmd: TraceCmd {
+            module: "MyApp.Controller".to_string(),
+            function: "create".to_string(),
+            arity: None,
+            depth: 5,
+            engine: Engine::Datalog,
+            include_messages: false,
+            min_weight: 1,
+            certain_only: true,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            let functions: Vec<&str> = result.entries.iter().skip(1).map(|e| e.function.as_str()).collect();
+            assert_eq!(functions, ["process", "fetch", "notify", "send_email"]);
+        },
+    }
+
     // =========================================================================
     // No match / empty result tests
     // =========================================================================
@@ -94,10 +258,15 @@ mod tests {
             function: "foo".to_string(),
             arity: None,
             depth: 5,
+            engine: Engine::Datalog,
+            include_messages: false,
+            min_weight: 1,
+            certain_only: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: entries,
@@ -114,10 +283,15 @@ mod tests {
             function: "foo".to_string(),
             arity: None,
             depth: 5,
+            engine: Engine::Datalog,
+            include_messages: false,
+            min_weight: 1,
+            certain_only: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
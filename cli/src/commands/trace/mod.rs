@@ -7,10 +7,12 @@ mod output_tests;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
 
-use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::commands::{CommandRunner, CommonArgs, Engine, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::suggest::Lookup;
 
 /// Trace call chains from a starting function (forward traversal)
 #[derive(Args, Debug)]
@@ -19,6 +21,10 @@ Examples:
   code_search trace MyApp.Web index                  # Trace from controller action
   code_search trace MyApp handle_call --depth 10    # Deeper traversal
   code_search trace -r 'MyApp\\..*' 'handle_.*'      # Regex pattern
+  code_search trace MyApp.Web index --engine memory  # In-memory traversal
+  code_search trace Chat send --include-messages     # Follow GenServer/PubSub messages
+  code_search trace MyApp.Web index --min-weight 3   # Only calls made from 3+ call sites
+  code_search trace MyApp.Web index --certain-only   # Only calls made on every path
 ")]
 pub struct TraceCmd {
     /// Starting module name (exact match or pattern with --regex)
@@ -35,13 +41,31 @@ pub struct TraceCmd {
     #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..=20))]
     pub depth: u32,
 
+    /// Traversal engine (memory reuses an adjacency index across traversals)
+    #[arg(long, value_enum, default_value_t = Engine::Datalog)]
+    pub engine: Engine,
+
+    /// Also follow message edges (GenServer calls/casts, PubSub broadcasts)
+    #[arg(long, default_value_t = false)]
+    pub include_messages: bool,
+
+    /// Only follow calls whose caller calls the callee from at least this many call sites
+    #[arg(long, default_value = "1")]
+    pub min_weight: i64,
+
+    /// Only follow calls made on every path, not in a conditional branch or comprehension
+    #[arg(long)]
+    pub certain_only: bool,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for TraceCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
-        Ok(result.format(format))
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let lookup = Lookup::new(&self.common, Some(&self.module), Some(&self.function));
+        let result = self.execute(db, settings)?;
+        let empty = result.entries.is_empty();
+        Ok(lookup.suggest(db, result, empty, settings)?.format(format))
     }
 }
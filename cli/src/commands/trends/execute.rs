@@ -0,0 +1,125 @@
+//! Metric series built from the snapshots recorded on import.
+
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::TrendsCmd;
+use crate::commands::Execute;
+use db::queries::snapshots::{find_snapshots, MetricSnapshot};
+use db::query_builders::QuerySettings;
+
+/// One metric across the snapshots, oldest first
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendSeries {
+    pub metric: String,
+    pub values: Vec<f64>,
+    /// Last value minus first value
+    pub change: f64,
+}
+
+/// Result of the trends command
+#[derive(Debug, Serialize)]
+pub struct TrendsResult {
+    pub project: String,
+    pub snapshots: Vec<MetricSnapshot>,
+    pub series: Vec<TrendSeries>,
+}
+
+/// Reads one metric from a snapshot
+type MetricValue = fn(&MetricSnapshot) -> f64;
+
+/// Metrics shown as series, in display order
+const METRICS: [(&str, MetricValue); 6] = [
+    ("modules", |s| s.modules as f64),
+    ("functions", |s| s.functions as f64),
+    ("calls", |s| s.calls as f64),
+    ("unused", |s| s.unused as f64),
+    ("cycles", |s| s.cycles as f64),
+    ("average_complexity", |s| s.average_complexity),
+];
+
+impl Execute for TrendsCmd {
+    type Output = TrendsResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let snapshots = find_snapshots(db, &self.project, self.limit)?;
+
+        let series = if snapshots.is_empty() {
+            vec![]
+        } else {
+            METRICS
+                .iter()
+                .map(|(metric, value)| {
+                    let values: Vec<f64> = snapshots.iter().map(value).collect();
+                    let change = values[values.len() - 1] - values[0];
+                    TrendSeries {
+                        metric: metric.to_string(),
+                        values,
+                        change,
+                    }
+                })
+                .collect()
+        };
+
+        Ok(TrendsResult {
+            project: self.project,
+            snapshots,
+            series,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::snapshots::record_snapshot;
+
+    fn snapshot(taken_at: i64, unused: i64, average_complexity: f64) -> MetricSnapshot {
+        MetricSnapshot {
+            taken_at,
+            label: String::new(),
+            modules: 4,
+            functions: 20,
+            calls: 30,
+            unused,
+            cycles: 1,
+            average_complexity,
+        }
+    }
+
+    #[test]
+    fn test_trends_builds_series() {
+        let db = db::test_utils::call_graph_db("default");
+        record_snapshot(&db, "default", &snapshot(1_000, 8, 2.5)).unwrap();
+        record_snapshot(&db, "default", &snapshot(2_000, 5, 2.0)).unwrap();
+
+        let result = TrendsCmd {
+            project: "default".to_string(),
+            limit: 20,
+        }
+        .execute(&db, &QuerySettings::default())
+        .expect("Trends should succeed");
+
+        assert_eq!(result.snapshots.len(), 2);
+        let unused = result.series.iter().find(|s| s.metric == "unused").unwrap();
+        assert_eq!(unused.values, vec![8.0, 5.0]);
+        assert_eq!(unused.change, -3.0);
+        let complexity = result.series.iter().find(|s| s.metric == "average_complexity").unwrap();
+        assert_eq!(complexity.change, -0.5);
+    }
+
+    #[test]
+    fn test_trends_without_snapshots() {
+        let db = db::test_utils::call_graph_db("default");
+        let result = TrendsCmd {
+            project: "default".to_string(),
+            limit: 20,
+        }
+        .execute(&db, &QuerySettings::default())
+        .expect("Trends should succeed");
+
+        assert!(result.snapshots.is_empty());
+        assert!(result.series.is_empty());
+    }
+}
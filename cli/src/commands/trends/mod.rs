@@ -0,0 +1,41 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Show how project metrics evolve across imports
+///
+/// Every import records a snapshot of the project's size, unused function
+/// count, cycle count and average complexity. This command lists the latest
+/// snapshots as one series per metric, drawn as a sparkline in table output.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search trends                      # Trends of the default project
+  code_search trends --project my_app     # Trends of another project
+  code_search trends -l 10                # Only the last 10 imports
+  code_search trends -o json              # Series for dashboards
+")]
+pub struct TrendsCmd {
+    /// Project to show trends for
+    #[arg(long, default_value = "default")]
+    pub project: String,
+
+    /// Number of most recent snapshots to include (1-1000)
+    #[arg(short, long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..=1000))]
+    pub limit: u32,
+}
+
+impl CommandRunner for TrendsCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,131 @@
+//! Output formatting for trends command results.
+
+use super::execute::{TrendSeries, TrendsResult};
+use crate::output::Outputable;
+use crate::utils::format_date;
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+impl Outputable for TrendsResult {
+    fn to_table(&self) -> String {
+        let (Some(first), Some(last)) = (self.snapshots.first(), self.snapshots.last()) else {
+            return format!(
+                "No metrics snapshots recorded for project {}.\nEach import records one unless --no-snapshot is given.\n",
+                self.project
+            );
+        };
+
+        let mut output = format!(
+            "Trends for {} over {} snapshot(s), {} to {}:\n\n",
+            self.project,
+            self.snapshots.len(),
+            snapshot_name(first.taken_at, &first.label),
+            snapshot_name(last.taken_at, &last.label),
+        );
+
+        for series in &self.series {
+            output.push_str(&format!(
+                "{:<20}{}  {} -> {} ({})\n",
+                series.metric,
+                sparkline(&series.values),
+                format_value(series, series.values[0]),
+                format_value(series, series.values[series.values.len() - 1]),
+                format_change(series),
+            ));
+        }
+
+        output
+    }
+}
+
+/// Label of a snapshot, or its UTC date when it has none
+fn snapshot_name(taken_at: i64, label: &str) -> String {
+    if label.is_empty() {
+        format_date(taken_at)
+    } else {
+        label.to_string()
+    }
+}
+
+/// One block character per value, scaled between the series minimum and maximum
+fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            if max > min {
+                let level = ((v - min) / (max - min) * (SPARK_CHARS.len() - 1) as f64).round();
+                SPARK_CHARS[level as usize]
+            } else {
+                SPARK_CHARS[0]
+            }
+        })
+        .collect()
+}
+
+fn format_value(series: &TrendSeries, value: f64) -> String {
+    if series.metric == "average_complexity" {
+        format!("{:.2}", value)
+    } else {
+        format!("{}", value)
+    }
+}
+
+fn format_change(series: &TrendSeries) -> String {
+    let sign = if series.change > 0.0 { "+" } else { "" };
+    format!("{}{}", sign, format_value(series, series.change))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::snapshots::MetricSnapshot;
+
+    fn result() -> TrendsResult {
+        let snapshot = |taken_at: i64, label: &str| MetricSnapshot {
+            taken_at,
+            label: label.to_string(),
+            ..Default::default()
+        };
+        TrendsResult {
+            project: "default".to_string(),
+            snapshots: vec![snapshot(1_760_572_800_000, ""), snapshot(1_760_659_200_000, "abc123")],
+            series: vec![
+                TrendSeries {
+                    metric: "unused".to_string(),
+                    values: vec![12.0, 9.0, 4.0],
+                    change: -8.0,
+                },
+                TrendSeries {
+                    metric: "average_complexity".to_string(),
+                    values: vec![2.0, 2.25],
+                    change: 0.25,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_to_table() {
+        let output = result().to_table();
+        assert!(output.starts_with("Trends for default over 2 snapshot(s), 2025-10-16 to abc123:\n\n"));
+        assert!(output.contains("unused              █▅▁  12 -> 4 (-8)\n"));
+        assert!(output.contains("average_complexity  ▁█  2.00 -> 2.25 (+0.25)\n"));
+    }
+
+    #[test]
+    fn test_to_table_empty() {
+        let result = TrendsResult {
+            project: "default".to_string(),
+            snapshots: vec![],
+            series: vec![],
+        };
+        assert!(result.to_table().starts_with("No metrics snapshots recorded for project default."));
+    }
+
+    #[test]
+    fn test_sparkline_flat_series() {
+        assert_eq!(sparkline(&[3.0, 3.0]), "▁▁");
+    }
+}
@@ -0,0 +1,119 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::TypeUsageCmd;
+use crate::commands::Execute;
+use db::queries::type_usage::{find_type_usage, TypeUsageEntry};
+use db::query_builders::QuerySettings;
+use db::types::ModuleGroupResult;
+
+/// A spec or type definition referencing the type
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeUsageInfo {
+    /// "spec", "callback", "type", "typep" or "opaque"
+    pub kind: String,
+    pub name: String,
+    pub arity: i64,
+    pub line: i64,
+    pub definition: String,
+}
+
+fn build_type_usage_result(
+    type_name: String,
+    module_filter: Option<String>,
+    entries: Vec<TypeUsageEntry>,
+) -> ModuleGroupResult<TypeUsageInfo> {
+    let total_items = entries.len();
+
+    let items = crate::utils::group_by_module(entries, |entry| {
+        let info = TypeUsageInfo {
+            kind: entry.kind,
+            name: entry.name,
+            arity: entry.arity,
+            line: entry.line,
+            definition: entry.definition,
+        };
+        (entry.module, info)
+    });
+
+    ModuleGroupResult {
+        module_pattern: module_filter.unwrap_or_else(|| "*".to_string()),
+        function_pattern: Some(type_name),
+        total_items,
+        items,
+    }
+}
+
+impl Execute for TypeUsageCmd {
+    type Output = ModuleGroupResult<TypeUsageInfo>;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let entries = find_type_usage(
+            db,
+            &self.type_name,
+            &self.common.project,
+            self.common.regex,
+            self.module.as_deref(),
+            self.common.limit,
+            settings,
+        )?;
+
+        Ok(build_type_usage_result(self.type_name, self.module, entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommonArgs;
+    use crate::output::Outputable;
+
+    fn type_usage_cmd(type_name: &str, module: Option<&str>) -> TypeUsageCmd {
+        TypeUsageCmd {
+            type_name: type_name.to_string(),
+            module: module.map(String::from),
+            common: CommonArgs {
+                project: "default".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_type_usage_groups_by_module() {
+        let db = db::test_utils::call_graph_db("default");
+        let result = type_usage_cmd("User.t", None)
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_items, 2);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].name, "MyApp.Accounts");
+        assert_eq!(result.items[0].entries[0].name, "get_user");
+        assert_eq!(
+            result.to_table(),
+            "\
+References to \"User.t\"
+
+Found 2 reference(s) in 1 module(s):
+
+MyApp.Accounts:
+  L8 spec get_user/1: @spec get_user(integer()) :: {:ok, User.t()} | {:error, :not_found}
+  L22 spec list_users/0: @spec list_users() :: [User.t()]"
+        );
+    }
+
+    #[test]
+    fn test_type_usage_empty() {
+        let db = db::test_utils::call_graph_db("default");
+        let result = type_usage_cmd("User.t", Some("MyApp.Repo"))
+            .execute(&db, &QuerySettings::default())
+            .expect("Execute should succeed");
+
+        assert_eq!(result.total_items, 0);
+        assert!(result.to_table().contains("No specs or types found."));
+    }
+}
@@ -0,0 +1,38 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, CommonArgs, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Find every @spec, @callback and @type referencing a type
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search type-usage \"User.t\"              # Specs and types referencing User.t
+  code_search type-usage \"MyApp.User.t\"        # Only the qualified reference
+  code_search type-usage \"User.t\" MyApp.Web    # Filter to module MyApp.Web
+  code_search type-usage -r \"User\\.t\\(\"        # Regex over the definition text
+")]
+pub struct TypeUsageCmd {
+    /// Type to look for, e.g. User.t; also matches it qualified (MyApp.User.t)
+    pub type_name: String,
+
+    /// Module filter pattern
+    pub module: Option<String>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl CommandRunner for TypeUsageCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
@@ -0,0 +1,30 @@
+//! Output formatting for type-usage command results.
+
+use crate::output::TableFormatter;
+use db::types::ModuleGroupResult;
+use super::execute::TypeUsageInfo;
+
+impl TableFormatter for ModuleGroupResult<TypeUsageInfo> {
+    type Entry = TypeUsageInfo;
+
+    fn format_header(&self) -> String {
+        let type_name = self.function_pattern.as_deref().unwrap_or("*");
+        format!("References to \"{}\"", type_name)
+    }
+
+    fn format_empty_message(&self) -> String {
+        "No specs or types found.".to_string()
+    }
+
+    fn format_summary(&self, total: usize, module_count: usize) -> String {
+        format!("Found {} reference(s) in {} module(s):", total, module_count)
+    }
+
+    fn format_module_header(&self, module_name: &str, _module_file: &str) -> String {
+        format!("{}:", module_name)
+    }
+
+    fn format_entry(&self, info: &TypeUsageInfo, _module: &str, _file: &str) -> String {
+        format!("L{} {} {}/{}: {}", info.line, info.kind, info.name, info.arity, info.definition)
+    }
+}
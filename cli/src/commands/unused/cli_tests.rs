@@ -34,6 +34,15 @@ mod tests {
         expected: Some("MyApp".to_string()),
     }
 
+    crate::cli_option_test! {
+        command: "unused",
+        variant: Unused,
+        test_name: test_with_app,
+        args: ["--app", "billing"],
+        field: app,
+        expected: Some("billing".to_string()),
+    }
+
     crate::cli_option_test! {
         command: "unused",
         variant: Unused,
@@ -126,10 +135,51 @@ mod tests {
         }
     }
 
+    crate::cli_option_test! {
+        command: "unused",
+        variant: Unused,
+        test_name: test_with_baseline,
+        args: ["--baseline", "unused.json"],
+        field: baseline.baseline,
+        expected: Some(std::path::PathBuf::from("unused.json")),
+    }
+
+    crate::cli_option_test! {
+        command: "unused",
+        variant: Unused,
+        test_name: test_with_write_baseline,
+        args: ["--baseline", "unused.json", "--write-baseline"],
+        field: baseline.write_baseline,
+        expected: true,
+    }
+
+    #[rstest]
+    fn test_write_baseline_requires_baseline() {
+        let result = Args::try_parse_from(["code_search", "unused", "--write-baseline"]);
+        assert!(result.is_err());
+    }
+
     #[rstest]
     fn test_private_and_public_conflict() {
         let result =
             Args::try_parse_from(["code_search", "unused", "--private-only", "--public-only"]);
         assert!(result.is_err());
     }
+
+    #[rstest]
+    fn test_with_where() {
+        let args = Args::try_parse_from(["code_search", "unused", "--where", "arity > 2"]).unwrap();
+        match args.command {
+            crate::commands::Command::Unused(cmd) => {
+                assert_eq!(cmd.filter.map(|f| f.to_string()).as_deref(), Some("arity > 2"));
+            }
+            _ => panic!("Expected Unused command"),
+        }
+    }
+
+    crate::cli_error_test! {
+        command: "unused",
+        test_name: test_invalid_where_rejected,
+        args: ["--where", "arity >"],
+    }
 }
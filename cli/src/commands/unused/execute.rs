@@ -3,8 +3,12 @@ use std::error::Error;
 use serde::Serialize;
 
 use super::UnusedCmd;
+use crate::blame::{sort_and_blame, BlameInfo, Blamer};
 use crate::commands::Execute;
+use crate::sort::query_limit;
+use db::queries::annotations::{AnnotationIndex, AnnotationNote};
 use db::queries::unused::{find_unused_functions, UnusedFunction};
+use db::query_builders::QuerySettings;
 use db::types::ModuleCollectionResult;
 
 /// An unused function within a module
@@ -14,6 +18,15 @@ pub struct UnusedFunc {
     pub arity: i64,
     pub kind: String,
     pub line: i64,
+    /// Last line of the function, for --blame
+    #[serde(skip)]
+    pub end_line: i64,
+    /// Only with --show-annotations
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<AnnotationNote>,
+    /// Only with --blame, for functions in files git tracks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blame: Option<BlameInfo>,
 }
 
 /// Build grouped result from flat UnusedFunction list
@@ -30,6 +43,9 @@ fn build_unused_functions_result(
             arity: func.arity,
             kind: func.kind,
             line: func.line,
+            end_line: func.end_line,
+            annotations: vec![],
+            blame: None,
         };
         (func.module, unused_func, func.file)
     });
@@ -47,22 +63,50 @@ fn build_unused_functions_result(
 impl Execute for UnusedCmd {
     type Output = ModuleCollectionResult<UnusedFunc>;
 
-    fn execute(self, db: &db::DbInstance) -> Result<Self::Output, Box<dyn Error>> {
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
         let functions = find_unused_functions(
             db,
             self.module.as_deref(),
+            self.app.as_deref(),
             &self.common.project,
             self.common.regex,
             self.private_only,
             self.public_only,
             self.exclude_generated,
-            self.common.limit,
+            self.filter.as_ref(),
+            self.baseline.query_limit(query_limit(self.sort.as_ref(), self.common.limit)),
+            settings,
         )?;
 
-        Ok(build_unused_functions_result(
-            self.module.unwrap_or_else(|| "*".to_string()),
+        let functions = self.baseline.apply_limited(
+            "unused",
             functions,
-        ))
+            |f| format!("{}.{}/{}", f.module, f.name, f.arity),
+            query_limit(self.sort.as_ref(), self.common.limit),
+        )?;
+
+        let blamer = if self.blame { Some(Blamer::open(".")?) } else { None };
+        let mut result = build_unused_functions_result(self.module.unwrap_or_else(|| "*".to_string()), functions);
+
+        if self.show_annotations {
+            let index = AnnotationIndex::load(db, &self.common.project, settings)?;
+            for module in &mut result.items {
+                for func in &mut module.entries {
+                    func.annotations = index.for_function(&module.name, &func.name, func.arity);
+                }
+            }
+        }
+
+        sort_and_blame(
+            &mut result,
+            self.sort.as_ref(),
+            self.common.limit,
+            blamer,
+            |f| (f.line, f.end_line),
+            |f, blame| f.blame = blame,
+        )?;
+
+        Ok(result)
     }
 }
 
@@ -4,6 +4,7 @@
 mod tests {
     use super::super::UnusedCmd;
     use crate::commands::CommonArgs;
+    use db::query_builders::QuerySettings;
     use rstest::{fixture, rstest};
 
     crate::shared_fixture! {
@@ -12,6 +13,12 @@ mod tests {
         project: "test_project",
     }
 
+    crate::shared_fixture! {
+        fixture_name: umbrella_db,
+        fixture_type: umbrella,
+        project: "test_project",
+    }
+
     // =========================================================================
     // Core functionality tests
     // =========================================================================
@@ -26,10 +33,17 @@ mod tests {
             private_only: false,
             public_only: false,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -51,10 +65,17 @@ mod tests {
             private_only: false,
             public_only: false,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -71,10 +92,17 @@ mod tests {
             private_only: false,
             public_only: false,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: true,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -91,10 +119,17 @@ mod tests {
             private_only: false,
             public_only: false,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -115,10 +150,17 @@ mod tests {
             private_only: false,
             public_only: false,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: items,
@@ -136,10 +178,17 @@ mod tests {
             private_only: false,
             public_only: false,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         empty_field: items,
@@ -157,10 +206,17 @@ mod tests {
             private_only: false,
             public_only: false,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 1,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -178,10 +234,17 @@ mod tests {
             private_only: true,
             public_only: false,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -200,10 +263,17 @@ mod tests {
             private_only: false,
             public_only: true,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
         assertions: |result| {
@@ -216,6 +286,291 @@ mod tests {
         },
     }
 
+    // Uncalled across the umbrella: show/2, create/2 (web) and charge/1 (billing)
+    crate::execute_test! {
+        test_name: test_unused_app_filter,
+        fixture: umbrella_db,
+        cmd: UnusedCmd {
+            module: None,
+            private_only: false,
+            public_only: false,
+            exclude_generated: false,
+            app: Some("web".to_string()),
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert_eq!(result.total_items, 2);
+            assert_eq!(result.items.len(), 1);
+            assert_eq!(result.items[0].name, "Web.UserController");
+        },
+    }
+
+    // 6 unused functions and a limit of 2: the baseline records all 6, so none resurface
+    #[rstest]
+    fn test_unused_baseline_sees_past_limit(populated_db: db::DbInstance) {
+        use crate::baseline::BaselineArgs;
+        use crate::commands::Execute;
+        let dir = tempfile::tempdir().unwrap();
+        let cmd = |write_baseline: bool| UnusedCmd {
+            module: None,
+            private_only: false,
+            public_only: false,
+            exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: BaselineArgs {
+                baseline: Some(dir.path().join("baseline.json")),
+                write_baseline,
+            },
+            show_annotations: false,
+            blame: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 2,
+                namespace: Vec::new(),
+            },
+        };
+
+        let written = cmd(true).execute(&populated_db, &QuerySettings::default()).unwrap();
+        assert_eq!(written.total_items, 2);
+        let baseline = crate::baseline::Baseline::load(&dir.path().join("baseline.json"), "unused").unwrap();
+        assert_eq!(baseline.findings.len(), 6);
+
+        let filtered = cmd(false).execute(&populated_db, &QuerySettings::default()).unwrap();
+        assert_eq!(filtered.total_items, 0);
+    }
+
+    // Repo.insert is only called from the test: unused once tests are excluded,
+    // while the test function itself is left out
+    #[rstest]
+    fn test_unused_exclude_tests() {
+        use crate::commands::Execute;
+        use db::query_builders::TestScope;
+
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Repo": {
+                    "get/1:2": {"name": "get", "arity": 1, "file": "lib/repo.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3},
+                    "insert/1:5": {"name": "insert", "arity": 1, "file": "lib/repo.ex", "kind": "def", "line": 5, "start_line": 5, "end_line": 6}
+                },
+                "MyApp.Web": {
+                    "show/1:2": {"name": "show", "arity": 1, "file": "lib/web.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 4}
+                },
+                "MyApp.RepoTest": {
+                    "test_insert/0:3": {"name": "test_insert", "arity": 0, "file": "test/repo_test.exs", "kind": "def", "line": 3, "start_line": 3, "end_line": 5}
+                }
+            },
+            "calls": [
+                {"caller": {"module": "MyApp.Web", "function": "show/1", "file": "lib/web.ex", "line": 3}, "type": "remote", "callee": {"module": "MyApp.Repo", "function": "get", "arity": 1}},
+                {"caller": {"module": "MyApp.RepoTest", "function": "test_insert/0", "file": "test/repo_test.exs", "line": 4}, "type": "remote", "callee": {"module": "MyApp.Repo", "function": "insert", "arity": 1}}
+            ]
+        }"#;
+        let db = db::test_utils::setup_test_db(json, "test_project");
+        let unused = |scope: TestScope| -> Vec<String> {
+            let cmd = UnusedCmd {
+                module: None,
+                private_only: false,
+                public_only: false,
+                exclude_generated: false,
+                app: None,
+                sort: None,
+                filter: None,
+                baseline: Default::default(),
+                show_annotations: false,
+                blame: false,
+                common: CommonArgs {
+                    project: "test_project".to_string(),
+                    regex: false,
+                    limit: 100,
+                    namespace: Vec::new(),
+                },
+            };
+            let settings = QuerySettings { test_scope: scope, ..Default::default() };
+            let result = cmd.execute(&db, &settings).unwrap();
+            result
+                .items
+                .iter()
+                .flat_map(|m| m.entries.iter().map(move |f| format!("{}.{}", m.name, f.name)))
+                .collect()
+        };
+
+        assert_eq!(unused(TestScope::All), ["MyApp.RepoTest.test_insert", "MyApp.Web.show"]);
+        assert_eq!(unused(TestScope::Exclude), ["MyApp.Repo.insert", "MyApp.Web.show"]);
+        assert_eq!(unused(TestScope::Only), ["MyApp.RepoTest.test_insert"]);
+    }
+
+    #[rstest]
+    fn test_unused_show_annotations(populated_db: db::DbInstance) {
+        use crate::commands::Execute;
+        use db::queries::annotations::{add_annotation, Annotation, AnnotationKind};
+
+        let annotation = Annotation {
+            module: "MyApp.Accounts".to_string(),
+            function: "validate_email".to_string(),
+            arity: None,
+            kind: AnnotationKind::Todo,
+            text: "Move to MyApp.Validation".to_string(),
+            created_at: 1,
+        };
+        add_annotation(&populated_db, "test_project", &annotation).unwrap();
+
+        let cmd = UnusedCmd {
+            module: None,
+            private_only: false,
+            public_only: false,
+            exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: true,
+            blame: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        };
+        let result = cmd.execute(&populated_db, &QuerySettings::default()).unwrap();
+        let notes: Vec<String> = result
+            .items
+            .iter()
+            .flat_map(|m| m.entries.iter())
+            .flat_map(|f| f.annotations.iter().map(|n| format!("{}: {}", f.name, n)))
+            .collect();
+        assert_eq!(notes, ["validate_email: [todo] Move to MyApp.Validation"]);
+    }
+
+    // Generated functions come from `generated_by`, the compiler callback
+    // names and the configured rules
+    #[rstest]
+    fn test_unused_exclude_generated_rules() {
+        use crate::commands::Execute;
+        use db::generated::GeneratedRules;
+
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.User": {
+                    "__struct__/0:1": {"name": "__struct__", "arity": 0, "file": "lib/user.ex", "kind": "def", "line": 1, "start_line": 1, "end_line": 1},
+                    "__schema__/1:1": {"name": "__schema__", "arity": 1, "file": "lib/user.ex", "kind": "def", "line": 1, "start_line": 1, "end_line": 1, "generated_by": "Ecto.Schema"},
+                    "changeset/2:5": {"name": "changeset", "arity": 2, "file": "lib/user.ex", "kind": "def", "line": 5, "start_line": 5, "end_line": 8}
+                },
+                "MyApp.Proto.User": {
+                    "encode/1:3": {"name": "encode", "arity": 1, "file": "lib/proto/user.pb.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 4}
+                }
+            },
+            "calls": []
+        }"#;
+        let db = db::test_utils::setup_test_db(json, "test_project");
+        let unused = |exclude_generated: bool, rules: GeneratedRules| -> Vec<String> {
+            let cmd = UnusedCmd {
+                module: None,
+                private_only: false,
+                public_only: false,
+                exclude_generated,
+                app: None,
+                sort: None,
+                filter: None,
+                baseline: Default::default(),
+                show_annotations: false,
+                blame: false,
+                common: CommonArgs {
+                    project: "test_project".to_string(),
+                    regex: false,
+                    limit: 100,
+                    namespace: Vec::new(),
+                },
+            };
+            let settings = QuerySettings { generated_rules: rules, ..Default::default() };
+            let result = cmd.execute(&db, &settings).unwrap();
+            result.items.iter().flat_map(|m| m.entries.iter().map(|f| f.name.clone())).collect()
+        };
+
+        assert_eq!(unused(false, GeneratedRules::default()), ["encode", "__schema__", "__struct__", "changeset"]);
+        assert_eq!(unused(true, GeneratedRules::default()), ["encode", "changeset"]);
+        let proto = GeneratedRules { files: vec!["*.pb.ex".to_string()], ..Default::default() };
+        assert_eq!(unused(true, proto), ["changeset"]);
+        let only_files = GeneratedRules {
+            include_defaults: false,
+            files: vec!["lib/proto".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(unused(true, only_files), ["__schema__", "__struct__", "changeset"]);
+    }
+
+    // Controller: index/2, show/2, create/2
+    crate::execute_test! {
+        test_name: test_unused_with_where_filter,
+        fixture: populated_db,
+        cmd: UnusedCmd {
+            module: None,
+            private_only: false,
+            public_only: false,
+            exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: Some("arity == 2 && module =~ \"Controller\"".parse().unwrap()),
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: Vec::new(),
+            },
+        },
+        assertions: |result| {
+            assert_eq!(result.total_items, 3);
+            assert!(result.items.iter().all(|m| m.name == "MyApp.Controller"));
+        },
+    }
+
+    // The namespace and the filter value are the same text, both bound as parameters
+    #[rstest]
+    fn test_unused_where_filter_in_namespace(populated_db: db::DbInstance) {
+        use crate::commands::Execute;
+
+        let cmd = UnusedCmd {
+            module: None,
+            private_only: false,
+            public_only: false,
+            exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: Some("module == \"MyApp.Controller\"".parse().unwrap()),
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
+            common: CommonArgs {
+                project: "test_project".to_string(),
+                regex: false,
+                limit: 100,
+                namespace: vec!["MyApp.Controller".to_string()],
+            },
+        };
+        let settings = QuerySettings { namespaces: cmd.common.namespace.clone(), ..Default::default() };
+        let result = cmd.execute(&populated_db, &settings);
+
+        let result = result.expect("Execute should succeed");
+        assert_eq!(result.total_items, 3);
+    }
+
     // =========================================================================
     // Error handling tests
     // =========================================================================
@@ -227,10 +582,17 @@ mod tests {
             private_only: false,
             public_only: false,
             exclude_generated: false,
+            app: None,
+            sort: None,
+            filter: None,
+            baseline: Default::default(),
+            show_annotations: false,
+            blame: false,
             common: CommonArgs {
                 project: "test_project".to_string(),
                 regex: false,
                 limit: 100,
+                namespace: Vec::new(),
             },
         },
     }
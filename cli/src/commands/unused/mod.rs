@@ -7,10 +7,14 @@ mod output_tests;
 use std::error::Error;
 
 use clap::Args;
+use db::query_builders::QuerySettings;
 use db::DbInstance;
+use db::where_filter::WhereFilter;
 
+use crate::baseline::BaselineArgs;
 use crate::commands::{CommandRunner, CommonArgs, Execute};
 use crate::output::{OutputFormat, Outputable};
+use crate::sort::SortSpec;
 
 /// Find functions that are never called
 #[derive(Args, Debug)]
@@ -21,7 +25,14 @@ Examples:
   code_search unused -P                    # Unused public functions (entry points)
   code_search unused -p                    # Unused private functions (dead code)
   code_search unused -Px                   # Public only, exclude generated
-  code_search unused 'Accounts.*' -r       # Match module with regex")]
+  code_search unused 'Accounts.*' -r       # Match module with regex
+  code_search unused --app billing         # Only modules of the billing umbrella app
+  code_search unused --baseline unused.json --write-baseline  # Record current findings
+  code_search unused --baseline unused.json                   # Only report new findings
+  code_search unused --sort line                              # Sort by line within each module
+  code_search unused --where 'arity > 2'                      # Only functions taking 3+ args
+  code_search unused --show-annotations                       # Include notes attached with annotate
+  code_search unused --blame                                  # Who last changed each function, and when")]
 pub struct UnusedCmd {
     /// Module pattern to filter results (substring match by default, regex with -r)
     pub module: Option<String>,
@@ -34,17 +45,41 @@ pub struct UnusedCmd {
     #[arg(short = 'P', long, default_value_t = false, conflicts_with = "private_only")]
     pub public_only: bool,
 
-    /// Exclude compiler-generated functions (__struct__, __info__, etc.)
+    /// Exclude generated functions: macro-generated and compiler callbacks,
+    /// plus the `generated` rules of the config file
     #[arg(short = 'x', long, default_value_t = false)]
     pub exclude_generated: bool,
 
+    /// Only include modules of this OTP application (umbrella projects)
+    #[arg(long)]
+    pub app: Option<String>,
+
+    /// Sort by an output column as `column[:asc|desc]` (ascending by default)
+    #[arg(long, value_name = "COLUMN[:asc|desc]")]
+    pub sort: Option<SortSpec>,
+
+    /// Only include results matching an expression over the output columns, e.g. 'arity > 2 && module =~ "Web"'
+    #[arg(long = "where", value_name = "EXPR")]
+    pub filter: Option<WhereFilter>,
+
+    /// Include annotations attached with `annotate`
+    #[arg(long)]
+    pub show_annotations: bool,
+
+    /// Annotate each finding with the last author and commit date of its lines, from `git blame`
+    #[arg(long)]
+    pub blame: bool,
+
+    #[command(flatten)]
+    pub baseline: BaselineArgs,
+
     #[command(flatten)]
     pub common: CommonArgs,
 }
 
 impl CommandRunner for UnusedCmd {
-    fn run(self, db: &DbInstance, format: OutputFormat) -> Result<String, Box<dyn Error>> {
-        let result = self.execute(db)?;
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
         Ok(result.format(format))
     }
 }
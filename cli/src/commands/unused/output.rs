@@ -1,6 +1,6 @@
 //! Output formatting for unused command results.
 
-use crate::output::Outputable;
+use crate::output::{Annotation, Outputable};
 use db::types::ModuleCollectionResult;
 use super::execute::UnusedFunc;
 
@@ -32,6 +32,12 @@ impl Outputable for ModuleCollectionResult<UnusedFunc> {
                         "  {}/{} [{}] L{}",
                         func.name, func.arity, func.kind, func.line
                     ));
+                    for note in &func.annotations {
+                        lines.push(format!("    {}", note));
+                    }
+                    if let Some(blame) = &func.blame {
+                        lines.push(format!("    {}", blame));
+                    }
                 }
             }
         } else {
@@ -40,4 +46,22 @@ impl Outputable for ModuleCollectionResult<UnusedFunc> {
 
         lines.join("\n")
     }
+
+    fn to_annotations(&self) -> Option<Vec<Annotation>> {
+        Some(
+            self.items
+                .iter()
+                .flat_map(|module| {
+                    module.entries.iter().map(|func| {
+                        Annotation::warning(
+                            &module.file,
+                            func.line,
+                            "Unused function",
+                            format!("{}.{}/{} is never called", module.name, func.name, func.arity),
+                        )
+                    })
+                })
+                .collect(),
+        )
+    }
 }
@@ -23,6 +23,15 @@ Found 1 unused function(s) in 1 module(s):
 MyApp.Accounts (lib/accounts.ex):
   unused_helper/0 [defp] L35";
 
+    const BLAMED_TABLE: &str = "\
+Unused functions
+
+Found 1 unused function(s) in 1 module(s):
+
+MyApp.Accounts (lib/accounts.ex):
+  unused_helper/0 [defp] L35
+    last changed by Ada on 2025-10-09 (2222222)";
+
     const FILTERED_TABLE: &str = "\
 Unused functions (module: Accounts)
 
@@ -64,6 +73,9 @@ MyApp.Accounts (lib/accounts.ex):
                     arity: 0,
                     kind: "defp".to_string(),
                     line: 35,
+                    end_line: 40,
+                    annotations: vec![],
+                    blame: None,
                 }],
                 function_count: None,
             }],
@@ -86,12 +98,26 @@ MyApp.Accounts (lib/accounts.ex):
                     arity: 0,
                     kind: "defp".to_string(),
                     line: 35,
+                    end_line: 40,
+                    annotations: vec![],
+                    blame: None,
                 }],
                 function_count: None,
             }],
         }
     }
 
+    #[fixture]
+    fn blamed_result() -> ModuleCollectionResult<UnusedFunc> {
+        let mut result = single_result();
+        result.items[0].entries[0].blame = Some(crate::blame::BlameInfo {
+            author: "Ada".to_string(),
+            commit: "2222222222222222222222222222222222222222".to_string(),
+            date: "2025-10-09".to_string(),
+        });
+        result
+    }
+
     // =========================================================================
     // Tests
     // =========================================================================
@@ -117,6 +143,13 @@ MyApp.Accounts (lib/accounts.ex):
         expected: FILTERED_TABLE,
     }
 
+    crate::output_table_test! {
+        test_name: test_to_table_blamed,
+        fixture: blamed_result,
+        fixture_type: ModuleCollectionResult<UnusedFunc>,
+        expected: BLAMED_TABLE,
+    }
+
     crate::output_table_test! {
         test_name: test_format_json,
         fixture: single_result,
@@ -140,4 +173,29 @@ MyApp.Accounts (lib/accounts.ex):
         expected: db::test_utils::load_output_fixture("unused", "empty.toon"),
         format: Toon,
     }
+
+    crate::output_table_test! {
+        test_name: test_format_github,
+        fixture: single_result,
+        fixture_type: ModuleCollectionResult<UnusedFunc>,
+        expected: "::warning file=lib/accounts.ex,line=35,title=Unused function::MyApp.Accounts.unused_helper/0 is never called",
+        format: Github,
+    }
+
+    crate::output_table_test! {
+        test_name: test_format_github_empty,
+        fixture: empty_result,
+        fixture_type: ModuleCollectionResult<UnusedFunc>,
+        expected: "",
+        format: Github,
+    }
+
+    crate::output_table_test! {
+        test_name: test_format_csv,
+        fixture: single_result,
+        fixture_type: ModuleCollectionResult<UnusedFunc>,
+        expected: "module_pattern,total_items,file,module,arity,kind,line,name,function\n\
+                   *,1,lib/accounts.ex,MyApp.Accounts,0,defp,35,unused_helper,unused_helper",
+        format: Csv,
+    }
 }
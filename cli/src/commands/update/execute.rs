@@ -0,0 +1,157 @@
+//! Extraction and import of changed files.
+
+use std::error::Error;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::UpdateCmd;
+use crate::commands::Execute;
+use crate::config::Config;
+use crate::extractors::{ExtractorImport, ImportSettings, extractor_for, group_by_extractor};
+use db::query_builders::QuerySettings;
+
+/// Result of the update command
+#[derive(Debug, Serialize)]
+pub struct UpdateResult {
+    /// Changed source files whose rows were replaced
+    pub files: Vec<String>,
+    /// Changed source files that no longer exist, whose rows were removed
+    pub deleted: Vec<String>,
+    /// One import per extractor reading changed files, empty when no source
+    /// file changed
+    pub imports: Vec<ExtractorImport>,
+}
+
+impl Execute for UpdateCmd {
+    type Output = UpdateResult;
+
+    fn execute(self, db: &db::DbInstance, settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let extractors = Config::load_or_default(&self.config)?.extractors();
+        let files: Vec<String> = self
+            .files
+            .iter()
+            .filter(|file| extractor_for(&extractors, file).is_some())
+            .cloned()
+            .collect();
+        let deleted = files.iter().filter(|file| !Path::new(file).exists()).cloned().collect();
+
+        let groups = group_by_extractor(&extractors, &files);
+
+        let import_settings = ImportSettings {
+            project: self.project,
+            mix_env: self.mix_env,
+            commit: self.commit,
+            modified_at: self.modified_at,
+            config: self.config,
+        };
+        let mut imports = Vec::new();
+        for (i, (extractor, group)) in groups.iter().enumerate() {
+            // One metrics snapshot per update, after the last import
+            imports.push(import_settings.extract_and_import(
+                db,
+                extractor,
+                group,
+                false,
+                i + 1 == groups.len(),
+                settings,
+            )?);
+        }
+
+        Ok(UpdateResult { files, deleted, imports })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use db::queries::changes::find_function_clauses;
+    use tempfile::TempDir;
+
+    /// A config whose extractor copies `graph` to the output file, ignoring
+    /// the files it is given
+    fn config(dir: &TempDir, prepare: &[&str], graph: &str) -> std::path::PathBuf {
+        let graph_path = dir.path().join("graph.json");
+        fs::write(&graph_path, graph).unwrap();
+        let config = serde_json::json!({
+            "extractors": [{
+                "name": "test",
+                "prepare": prepare,
+                "command": ["sh", "-c", "cp \"$0\" \"$1\"", graph_path, "{output}"],
+                "extensions": ["ex"],
+            }]
+        });
+        let path = dir.path().join("config.json");
+        fs::write(&path, config.to_string()).unwrap();
+        path
+    }
+
+    fn update_cmd(files: &[&str], config: std::path::PathBuf) -> UpdateCmd {
+        UpdateCmd {
+            files: files.iter().map(|f| f.to_string()).collect(),
+            project: "test_project".to_string(),
+            mix_env: "dev".to_string(),
+            commit: Some("abc123".to_string()),
+            modified_at: Some(1_700_000_000),
+            config,
+        }
+    }
+
+    #[test]
+    fn test_update_replaces_changed_files() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("accounts.ex");
+        fs::write(&source, "defmodule MyApp.Accounts do end").unwrap();
+        let source = source.display().to_string();
+        let graph = format!(
+            r#"{{
+                "structs": {{}},
+                "function_locations": {{
+                    "MyApp.Accounts": {{
+                        "fresh/0:3": {{"name": "fresh", "arity": 0, "file": "{source}", "kind": "def", "line": 3, "start_line": 3, "end_line": 4}}
+                    }}
+                }},
+                "calls": []
+            }}"#
+        );
+        let db = db::test_utils::call_graph_db("test_project");
+
+        // lib/my_app/accounts.ex does not exist here, so it counts as deleted
+        let cmd = update_cmd(&[&source, "lib/my_app/accounts.ex", "README.md"], config(&dir, &[], &graph));
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+
+        assert_eq!(result.files, [source.as_str(), "lib/my_app/accounts.ex"]);
+        assert_eq!(result.deleted, ["lib/my_app/accounts.ex"]);
+        assert_eq!(result.imports.len(), 1);
+        assert_eq!(result.imports[0].import.result.function_locations_imported, 1);
+
+        let clauses = find_function_clauses(&db, "test_project").unwrap();
+        assert!(clauses.iter().all(|c| c.file != "lib/my_app/accounts.ex"));
+        assert!(clauses.iter().any(|c| c.name == "fresh" && c.file == source));
+        assert!(clauses.iter().any(|c| c.file == "lib/my_app/repo.ex"));
+    }
+
+    #[test]
+    fn test_update_without_source_files() {
+        let dir = TempDir::new().unwrap();
+        let db = db::test_utils::call_graph_db("test_project");
+        let cmd = update_cmd(&["README.md", "mix.lock"], config(&dir, &["false"], "{}"));
+        let result = cmd.execute(&db, &QuerySettings::default()).expect("Execute should succeed");
+        assert!(result.files.is_empty());
+        assert!(result.imports.is_empty());
+    }
+
+    #[test]
+    fn test_update_reports_failed_prepare() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("a.ex");
+        fs::write(&source, "").unwrap();
+        let db = db::test_utils::call_graph_db("test_project");
+
+        let cmd = update_cmd(&[&source.display().to_string()], config(&dir, &["false"], "{}"));
+        let err = cmd.execute(&db, &QuerySettings::default()).unwrap_err();
+        assert!(err.to_string().starts_with("The prepare of extractor 'test' (false) failed"), "{}", err);
+    }
+}
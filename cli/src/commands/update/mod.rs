@@ -0,0 +1,64 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::output::{OutputFormat, Outputable};
+
+/// Re-extract and import only the files that changed
+///
+/// Runs the extractors configured under `extractors` in the config file (by
+/// default `mix compile --debug-info`, then `ex_ast`) over the changed source
+/// files they read, and imports each one's output in place of the stored rows
+/// of its files. Rows of changed files that no longer exist
+/// are removed. The post-commit hook installed by `setup --install-hooks`
+/// runs it for the files of each commit.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search update --files lib/a.ex lib/b.ex         # Refresh two files
+  code_search update --files $(git diff --name-only --no-renames HEAD~1 HEAD)
+                                                       # Refresh the files of the last commit
+  code_search update --files lib/a.ex --commit $(git rev-parse HEAD) --modified-at $(git log -1 --format=%ct)
+                                                       # Date the changed functions by the commit
+  code_search update --files lib/a.ex --mix-env test   # Compile and extract in the test environment
+")]
+pub struct UpdateCmd {
+    /// Changed files, relative to the project root; files no extractor reads are ignored
+    #[arg(long, num_args = 1.., required = true)]
+    pub files: Vec<String>,
+
+    /// Project name for namespacing (allows multiple projects in same DB)
+    #[arg(short, long, default_value = "default")]
+    pub project: String,
+
+    /// Mix environment the extractors run in (`MIX_ENV` and `{mix_env}`)
+    #[arg(long, default_value = "dev")]
+    pub mix_env: String,
+
+    /// Revision recorded for the changed functions (e.g. `git rev-parse HEAD`)
+    #[arg(long, value_name = "SHA")]
+    pub commit: Option<String>,
+
+    /// Modification time, in Unix seconds, recorded for the changed functions
+    #[arg(long, value_name = "UNIX_SECONDS")]
+    pub modified_at: Option<i64>,
+
+    /// Path to the config file with the `extractors` settings
+    #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+    pub config: PathBuf,
+}
+
+impl CommandRunner for UpdateCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
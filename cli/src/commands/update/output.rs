@@ -0,0 +1,38 @@
+//! Output formatting for update command results.
+
+use super::execute::UpdateResult;
+use crate::output::Outputable;
+
+impl Outputable for UpdateResult {
+    fn to_table(&self) -> String {
+        if self.imports.is_empty() {
+            return "No changed source files to update.\n".to_string();
+        }
+
+        let mut output = format!("Updated {} changed file(s)", self.files.len());
+        if !self.deleted.is_empty() {
+            output.push_str(&format!(", {} deleted", self.deleted.len()));
+        }
+        output.push_str(".\n");
+        for import in &self.imports {
+            output.push_str(&format!("\n{} ({} file(s)):\n", import.extractor, import.files));
+            output.push_str(&import.import.to_table());
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_table_without_files() {
+        let result = UpdateResult {
+            files: Vec::new(),
+            deleted: Vec::new(),
+            imports: Vec::new(),
+        };
+        assert_eq!(result.to_table(), "No changed source files to update.\n");
+    }
+}
@@ -0,0 +1,82 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::VacuumCmd;
+use crate::commands::Execute;
+use db::queries::vacuum::{compact_store, rebuild_aggregates, remove_orphaned_calls, CompactedSize};
+use db::query_builders::QuerySettings;
+
+/// Result of the vacuum command
+#[derive(Debug, Serialize)]
+pub struct VacuumResult {
+    pub dry_run: bool,
+    /// Orphaned calls removed, or found with --dry-run
+    pub orphaned_calls: usize,
+    /// Projects whose aggregates were rebuilt
+    pub projects_rebuilt: Vec<String>,
+    /// Absent with --dry-run and for in-memory databases
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compacted: Option<CompactedSize>,
+}
+
+impl Execute for VacuumCmd {
+    type Output = VacuumResult;
+
+    fn execute(self, db: &db::DbInstance, _settings: &QuerySettings) -> Result<Self::Output, Box<dyn Error>> {
+        let orphaned_calls = remove_orphaned_calls(db, self.dry_run)?;
+        if self.dry_run {
+            return Ok(VacuumResult {
+                dry_run: true,
+                orphaned_calls,
+                projects_rebuilt: Vec::new(),
+                compacted: None,
+            });
+        }
+
+        let projects_rebuilt = rebuild_aggregates(db)?;
+        let compacted = match self.db_path.filter(|path| path.exists()) {
+            Some(path) => Some(compact_store(db, &path)?),
+            None => None,
+        };
+
+        Ok(VacuumResult {
+            dry_run: false,
+            orphaned_calls,
+            projects_rebuilt,
+            compacted,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    crate::shared_fixture! {
+        fixture_name: populated_db,
+        fixture_type: call_graph,
+        project: "test_project",
+    }
+
+    #[rstest]
+    fn test_vacuum_keeps_defined_calls(populated_db: db::DbInstance) {
+        let vacuum = |dry_run| {
+            VacuumCmd { dry_run, db_path: None }
+                .execute(&populated_db, &QuerySettings::default())
+                .expect("Vacuum should succeed")
+        };
+
+        // A fresh import has nothing to remove
+        let dry_run = vacuum(true);
+        assert_eq!(dry_run.orphaned_calls, 0);
+        assert!(dry_run.projects_rebuilt.is_empty());
+
+        let result = vacuum(false);
+        assert_eq!(result.orphaned_calls, dry_run.orphaned_calls);
+        assert_eq!(result.projects_rebuilt, ["test_project"]);
+        assert!(result.compacted.is_none());
+        assert_eq!(vacuum(true).orphaned_calls, 0);
+    }
+}
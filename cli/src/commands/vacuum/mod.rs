@@ -0,0 +1,42 @@
+mod execute;
+mod output;
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::Args;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+use crate::commands::{CommandRunner, Execute};
+use crate::output::{OutputFormat, Outputable};
+
+/// Compact the database and drop orphaned rows
+///
+/// Removes calls whose caller module is no longer defined, rebuilds the
+/// aggregate relations of every project and compacts the SQLite file,
+/// reporting the space reclaimed. Databases re-imported many times keep
+/// growing without it.
+#[derive(Args, Debug)]
+#[command(after_help = "\
+Examples:
+  code_search vacuum                          # Clean up and compact the database
+  code_search vacuum --dry-run                # Only count orphaned calls
+  code_search --db other.sqlite vacuum
+")]
+pub struct VacuumCmd {
+    /// Count orphaned calls without changing the database
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+
+    /// Database file to compact, set from the resolved --db path
+    #[arg(skip)]
+    pub db_path: Option<PathBuf>,
+}
+
+impl CommandRunner for VacuumCmd {
+    fn run(self, db: &DbInstance, format: OutputFormat, settings: &QuerySettings) -> Result<String, Box<dyn Error>> {
+        let result = self.execute(db, settings)?;
+        Ok(result.format(format))
+    }
+}
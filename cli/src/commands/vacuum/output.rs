@@ -0,0 +1,92 @@
+//! Output formatting for vacuum command results.
+
+use super::execute::VacuumResult;
+use crate::output::Outputable;
+
+/// Byte count in the largest unit that keeps it above 1
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+impl Outputable for VacuumResult {
+    fn to_table(&self) -> String {
+        let mut output = String::from("Database Maintenance\n\n");
+
+        if self.dry_run {
+            output.push_str(&format!("Orphaned calls: {}\n", self.orphaned_calls));
+            output.push_str("\nNo changes made (dry-run mode).\n");
+            return output;
+        }
+
+        output.push_str(&format!("Orphaned calls removed: {}\n", self.orphaned_calls));
+        output.push_str(&format!("Aggregates rebuilt: {} project(s)\n", self.projects_rebuilt.len()));
+        match &self.compacted {
+            Some(size) => output.push_str(&format!(
+                "Compacted: {} -> {} ({} reclaimed)\n",
+                format_bytes(size.bytes_before),
+                format_bytes(size.bytes_after),
+                format_bytes(size.reclaimed())
+            )),
+            None => output.push_str("Compacted: skipped (no database file)\n"),
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::queries::vacuum::CompactedSize;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+
+    #[test]
+    fn test_to_table() {
+        let result = VacuumResult {
+            dry_run: false,
+            orphaned_calls: 3,
+            projects_rebuilt: vec!["my_app".to_string()],
+            compacted: Some(CompactedSize {
+                bytes_before: 4 * 1024 * 1024,
+                bytes_after: 1024 * 1024,
+            }),
+        };
+        assert_eq!(
+            result.to_table(),
+            "Database Maintenance\n\n\
+             Orphaned calls removed: 3\n\
+             Aggregates rebuilt: 1 project(s)\n\
+             Compacted: 4.0 MB -> 1.0 MB (3.0 MB reclaimed)\n"
+        );
+    }
+
+    #[test]
+    fn test_to_table_dry_run() {
+        let result = VacuumResult {
+            dry_run: true,
+            orphaned_calls: 2,
+            projects_rebuilt: Vec::new(),
+            compacted: None,
+        };
+        assert_eq!(
+            result.to_table(),
+            "Database Maintenance\n\nOrphaned calls: 2\n\nNo changes made (dry-run mode).\n"
+        );
+    }
+}
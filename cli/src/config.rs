@@ -0,0 +1,435 @@
+//! Project configuration file.
+//!
+//! Rule-driven commands (such as `layers`, `check` and `entry-points`) read their definitions
+//! from a JSON file, by default `.code_search/config.json`:
+//!
+//! ```json
+//! {
+//!   "only_project": true,
+//!   "cache": true,
+//!   "layers": [
+//!     { "name": "Web",    "modules": ["MyAppWeb"],      "may_depend_on": ["Domain"] },
+//!     { "name": "Domain", "modules": ["MyApp.Accounts"], "may_depend_on": ["Data"] },
+//!     { "name": "Data",   "modules": ["MyApp.Repo"] }
+//!   ],
+//!   "check": {
+//!     "forbidden": [{ "from": "MyApp.Repo", "to": "MyAppWeb" }],
+//!     "max_cycles": 0,
+//!     "max_god_module_score": 200,
+//!     "no_unused_public": true
+//!   },
+//!   "entry_points": {
+//!     "rules": [{ "kind": "Absinthe resolver", "module": "Resolvers\\.", "functions": ["*/3"] }]
+//!   },
+//!   "generated": { "names": ["^grpc_"], "files": ["*.pb.ex"], "generators": ["^Protobuf\\."] },
+//!   "embeddings": { "provider": "http", "url": "http://localhost:11434/v1/embeddings", "model": "nomic-embed-text" },
+//!   "queries": {
+//!     "repo-leaks": { "args": ["calls-to", "MyApp.Repo", "--group-by", "caller-module"], "description": "Who calls the Repo" }
+//!   },
+//!   "import": {
+//!     "dedup": { "calls": ["caller_module", "caller_function", "callee_module", "callee_function", "callee_arity"] },
+//!     "exclude_paths": ["test/support", "*.pb.ex"],
+//!     "test_paths": ["lib/my_app/testing"]
+//!   },
+//!   "extractors": [
+//!     {
+//!       "name": "elixir",
+//!       "prepare": ["mix", "compile", "--debug-info"],
+//!       "command": ["ex_ast", "--env", "{mix_env}", "--format", "json", "--output", "{output}", "{files}"],
+//!       "extensions": ["ex", "exs"],
+//!       "exclude": ["_build", "deps"]
+//!     },
+//!     { "name": "python", "command": ["py_callgraph", "{files}"], "extensions": ["py"], "schema": "generic" }
+//!   ]
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::commands::ImportSchema;
+use crate::embed::EmbeddingConfig;
+use db::generated::GeneratedRules;
+
+/// Default location of the project configuration file
+pub const DEFAULT_CONFIG_PATH: &str = ".code_search/config.json";
+
+/// Top-level configuration file contents
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Leave stdlib and dependency modules out of trace, hotspots and unused
+    /// by default (`--include-deps` overrides it)
+    pub only_project: bool,
+    /// Cache query outputs until the next import (`--no-cache` overrides it)
+    pub cache: bool,
+    /// Architectural layers, listed from the outermost to the innermost
+    pub layers: Vec<LayerConfig>,
+    /// Rules evaluated by the `check` command
+    pub check: CheckConfig,
+    /// Entry point detection used by the `entry-points` command
+    pub entry_points: EntryPointsConfig,
+    /// Generated code left out by `--exclude-generated`, and by default by
+    /// many-clauses and large-functions
+    pub generated: GeneratedRules,
+    /// Embedding provider used by `index-embeddings` and `search --semantic`
+    pub embeddings: EmbeddingConfig,
+    /// Named queries shared through the config file, run with `query run`
+    pub queries: BTreeMap<String, QueryConfig>,
+    /// Settings applied by the `import` command
+    pub import: ImportConfig,
+    /// External extractors run by `extract` and `update`; the Elixir
+    /// extractor when empty (see [`ExtractorConfig::elixir`])
+    pub extractors: Vec<ExtractorConfig>,
+}
+
+/// Definition of a single architectural layer
+#[derive(Debug, Clone, Deserialize)]
+pub struct LayerConfig {
+    /// Layer name used in reports and in `may_depend_on` references
+    pub name: String,
+    /// Module namespaces belonging to this layer (a namespace also matches its submodules)
+    pub modules: Vec<String>,
+    /// Layers this layer may call into. When omitted, every layer listed after it is allowed.
+    #[serde(default)]
+    pub may_depend_on: Option<Vec<String>>,
+}
+
+/// Rules evaluated by the `check` command
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct CheckConfig {
+    /// Module dependencies that must not exist
+    pub forbidden: Vec<ForbiddenDependency>,
+    /// Maximum number of module dependency cycles
+    pub max_cycles: Option<usize>,
+    /// Maximum god-module score (incoming + outgoing calls of a module)
+    pub max_god_module_score: Option<i64>,
+    /// Fail when any public function is never called
+    pub no_unused_public: bool,
+}
+
+impl CheckConfig {
+    /// Whether any check rule is configured
+    pub fn is_empty(&self) -> bool {
+        self.forbidden.is_empty()
+            && self.max_cycles.is_none()
+            && self.max_god_module_score.is_none()
+            && !self.no_unused_public
+    }
+}
+
+/// A forbidden dependency between two module namespaces
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForbiddenDependency {
+    /// Caller namespace (matches the module and its submodules)
+    pub from: String,
+    /// Callee namespace (matches the module and its submodules)
+    pub to: String,
+    /// Optional explanation shown in the report
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Entry point detection used by the `entry-points` command
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct EntryPointsConfig {
+    /// Keep the built-in framework rules (Phoenix, OTP, Oban, Mix)
+    pub include_defaults: bool,
+    /// Additional rules, checked before the built-in ones
+    pub rules: Vec<EntryPointRule>,
+}
+
+impl Default for EntryPointsConfig {
+    fn default() -> Self {
+        Self {
+            include_defaults: true,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// A pattern identifying framework entry points
+#[derive(Debug, Clone, Deserialize)]
+pub struct EntryPointRule {
+    /// Label reported for matching functions (e.g. "Oban worker")
+    pub kind: String,
+    /// Regex the module name must match; any module when omitted
+    #[serde(default)]
+    pub module: Option<String>,
+    /// Public functions as `name` or `name/arity` (`*` matches any name); all public functions when empty
+    #[serde(default)]
+    pub functions: Vec<String>,
+}
+
+/// A named query: a command invocation, or a CozoScript
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct QueryConfig {
+    /// Arguments of the command invocation, without `code_search`
+    pub args: Vec<String>,
+    /// Read-only CozoScript, used when `args` is empty
+    pub script: String,
+    /// What the query answers, shown by `query list`
+    pub description: String,
+}
+
+/// Settings applied by the `import` command
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct ImportConfig {
+    /// Columns identifying duplicate records, per relation (`--dedup` overrides them)
+    pub dedup: BTreeMap<String, Vec<String>>,
+    /// Globs of source paths left out of every import (`--exclude-path` adds to them)
+    pub exclude_paths: Vec<String>,
+    /// Globs of source paths whose modules are test code, besides `test/` and `*_test.exs`
+    pub test_paths: Vec<String>,
+}
+
+/// An external program turning source files into call graph JSON.
+///
+/// The command gets the files to extract as arguments, in place of a `{files}`
+/// argument or else after the others, and prints the call graph in the format
+/// of `schema` on stdout, or writes it to the `{output}` file when one of its
+/// arguments names it. `{mix_env}` is replaced by the Mix environment, which
+/// is also set as `MIX_ENV`. A nonzero exit status fails the extraction.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExtractorConfig {
+    /// Name shown in reports and selected with `extract --only`
+    pub name: String,
+    /// Command run before extracting, such as a compile; skipped when empty
+    #[serde(default)]
+    pub prepare: Vec<String>,
+    /// Extractor invocation
+    pub command: Vec<String>,
+    /// Extensions of the source files the extractor reads
+    pub extensions: Vec<String>,
+    /// Directories skipped when `extract` walks a source tree
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Format of the call graph the extractor emits
+    #[serde(default)]
+    pub schema: ImportSchema,
+}
+
+impl ExtractorConfig {
+    /// The Elixir extractor, used when no extractor is configured
+    pub fn elixir() -> Self {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect();
+        Self {
+            name: "elixir".to_string(),
+            prepare: args(&["mix", "compile", "--debug-info"]),
+            command: args(&["ex_ast", "--env", "{mix_env}", "--format", "json", "--output", "{output}", "{files}"]),
+            extensions: args(&["ex", "exs"]),
+            exclude: args(&["_build", "deps"]),
+            schema: ImportSchema::Elixir,
+        }
+    }
+
+    /// Whether the extractor reads `file`, judging by its extension
+    pub fn reads(&self, file: &Path) -> bool {
+        file.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|e| e == ext))
+    }
+}
+
+impl Config {
+    /// Load configuration from a JSON file
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read config file '{}': {}", path.display(), e))?;
+        Self::from_json(&content)
+            .map_err(|e| format!("Failed to parse config file '{}': {}", path.display(), e).into())
+    }
+
+    /// Load configuration, using defaults when the default config file does not exist.
+    ///
+    /// For commands that work without a config file. An explicitly given path must exist.
+    pub fn load_or_default(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if path == Path::new(DEFAULT_CONFIG_PATH) && !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::load(path)
+    }
+
+    /// The configured extractors, or the Elixir extractor when none is
+    pub fn extractors(&self) -> Vec<ExtractorConfig> {
+        if self.extractors.is_empty() {
+            vec![ExtractorConfig::elixir()]
+        } else {
+            self.extractors.clone()
+        }
+    }
+
+    /// Parse configuration from a JSON string
+    pub fn from_json(content: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(content)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_json_parses_layers() {
+        let config = Config::from_json(
+            r#"{"layers": [
+                {"name": "Web", "modules": ["MyAppWeb"], "may_depend_on": ["Domain"]},
+                {"name": "Domain", "modules": ["MyApp"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.layers.len(), 2);
+        assert_eq!(config.layers[0].name, "Web");
+        assert_eq!(
+            config.layers[0].may_depend_on,
+            Some(vec!["Domain".to_string()])
+        );
+        assert_eq!(config.layers[1].may_depend_on, None);
+    }
+
+    #[test]
+    fn test_from_json_parses_check_rules() {
+        let config = Config::from_json(
+            r#"{"check": {
+                "forbidden": [{"from": "MyApp.Repo", "to": "MyAppWeb", "reason": "no upward calls"}],
+                "max_cycles": 0,
+                "no_unused_public": true
+            }}"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.check.forbidden.len(), 1);
+        assert_eq!(config.check.forbidden[0].from, "MyApp.Repo");
+        assert_eq!(config.check.forbidden[0].reason.as_deref(), Some("no upward calls"));
+        assert_eq!(config.check.max_cycles, Some(0));
+        assert_eq!(config.check.max_god_module_score, None);
+        assert!(config.check.no_unused_public);
+        assert!(!config.check.is_empty());
+    }
+
+    #[test]
+    fn test_from_json_empty_object_uses_defaults() {
+        let config = Config::from_json("{}").unwrap();
+        assert!(config.layers.is_empty());
+        assert!(config.check.is_empty());
+        assert!(!config.only_project);
+    }
+
+    #[test]
+    fn test_from_json_parses_only_project() {
+        let config = Config::from_json(r#"{"only_project": true}"#).unwrap();
+        assert!(config.only_project);
+    }
+
+    #[test]
+    fn test_from_json_parses_cache() {
+        let config = Config::from_json(r#"{"cache": true}"#).unwrap();
+        assert!(config.cache);
+        assert!(!Config::from_json("{}").unwrap().cache);
+    }
+
+    #[test]
+    fn test_from_json_parses_entry_points() {
+        let config = Config::from_json(
+            r#"{"entry_points": {
+                "include_defaults": false,
+                "rules": [{"kind": "Resolver", "module": "Resolvers$", "functions": ["resolve/3"]}]
+            }}"#,
+        )
+        .unwrap();
+
+        assert!(!config.entry_points.include_defaults);
+        assert_eq!(config.entry_points.rules[0].kind, "Resolver");
+        assert_eq!(config.entry_points.rules[0].functions, vec!["resolve/3".to_string()]);
+        assert!(Config::from_json("{}").unwrap().entry_points.include_defaults);
+    }
+
+    #[test]
+    fn test_from_json_parses_generated() {
+        let config = Config::from_json(
+            r#"{"generated": {"include_defaults": false, "files": ["*.pb.ex"], "generators": ["^Protobuf"]}}"#,
+        )
+        .unwrap();
+
+        assert!(!config.generated.include_defaults);
+        assert_eq!(config.generated.files, ["*.pb.ex"]);
+        assert!(config.generated.names.is_empty());
+        assert!(config.generated.is_generated("encode", "lib/user.pb.ex", ""));
+        assert!(Config::from_json("{}").unwrap().generated.include_defaults);
+    }
+
+    #[test]
+    fn test_from_json_parses_queries() {
+        let config = Config::from_json(
+            r#"{"queries": {
+                "leaks": {"args": ["calls-to", "MyApp.Repo"], "description": "Repo callers"},
+                "count": {"script": "?[n] := n = 1"}
+            }}"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.queries["leaks"].args, ["calls-to", "MyApp.Repo"]);
+        assert_eq!(config.queries["leaks"].description, "Repo callers");
+        assert_eq!(config.queries["count"].script, "?[n] := n = 1");
+        assert!(config.queries["count"].args.is_empty());
+    }
+
+    #[test]
+    fn test_from_json_parses_import_dedup() {
+        let config = Config::from_json(r#"{"import": {"dedup": {"calls": ["caller_module", "callee_module"]}}}"#).unwrap();
+        assert_eq!(config.import.dedup["calls"], ["caller_module", "callee_module"]);
+        assert!(Config::from_json("{}").unwrap().import.dedup.is_empty());
+    }
+
+    #[test]
+    fn test_from_json_parses_import_exclude_paths() {
+        let config = Config::from_json(r#"{"import": {"exclude_paths": ["test/support"]}}"#).unwrap();
+        assert_eq!(config.import.exclude_paths, ["test/support"]);
+        assert!(Config::from_json("{}").unwrap().import.exclude_paths.is_empty());
+    }
+
+    #[test]
+    fn test_from_json_parses_import_test_paths() {
+        let config = Config::from_json(r#"{"import": {"test_paths": ["lib/my_app/testing"]}}"#).unwrap();
+        assert_eq!(config.import.test_paths, ["lib/my_app/testing"]);
+    }
+
+    #[test]
+    fn test_from_json_parses_extractors() {
+        let config = Config::from_json(
+            r#"{"extractors": [{"name": "python", "command": ["py_callgraph", "{files}"], "extensions": ["py"], "schema": "generic"}]}"#,
+        )
+        .unwrap();
+        let extractors = config.extractors();
+        assert_eq!(extractors.len(), 1);
+        assert_eq!(extractors[0].command, ["py_callgraph", "{files}"]);
+        assert!(extractors[0].prepare.is_empty() && extractors[0].exclude.is_empty());
+        assert_eq!(extractors[0].schema, ImportSchema::Generic);
+        assert!(extractors[0].reads(Path::new("src/app.py")));
+        assert!(!extractors[0].reads(Path::new("lib/app.ex")));
+
+        let default = Config::from_json("{}").unwrap().extractors();
+        assert_eq!(default[0].name, "elixir");
+        assert_eq!(default[0].command[0], "ex_ast");
+        assert!(Config::from_json(r#"{"extractors": [{"name": "x"}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_from_json_invalid() {
+        assert!(Config::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let err = Config::load(Path::new("/nonexistent/config.json")).unwrap_err();
+        assert!(err.to_string().contains("Failed to read config file"));
+    }
+}
@@ -0,0 +1,351 @@
+//! Persistent query daemon.
+//!
+//! `code_search daemon` keeps the database open and the adjacency indexes
+//! loaded, and answers invocations over a unix socket next to the database
+//! file (`cozo.sqlite` listens on `cozo.sock`). The binary forwards read-only
+//! commands to a running daemon and prints its answer, skipping the cost of
+//! opening the database and rebuilding indexes on every call.
+//!
+//! Each request and response is one line of JSON. The daemon declines
+//! requests for another database or working directory, and the binary then
+//! runs the command itself, as it does when no daemon is listening. A ping
+//! request checks that the daemon and its database still answer.
+//!
+//! Both sides time out: a client that stalls while sending its request is
+//! dropped without holding up anyone else, and an invocation arriving while
+//! the daemon is busy with another, or not answered in time, runs on its own.
+
+use std::error::Error;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::Outcome;
+
+/// Socket a daemon serving the database at `db_path` listens on
+pub fn socket_path(db_path: &Path) -> PathBuf {
+    db_path.with_extension("sock")
+}
+
+/// How long either side waits for a request to be sent
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a client waits for the answer before running the command itself
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "request", rename_all = "snake_case")]
+enum Request {
+    /// Run the arguments of an invocation, as given on the command line
+    Run { args: Vec<String>, db: PathBuf, cwd: PathBuf },
+    /// Check the daemon's database answers queries
+    Ping,
+    Stop,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum Response {
+    Output { output: String, failed: bool },
+    Error { message: String },
+    /// The caller has to run the invocation itself
+    Declined { reason: String },
+    Healthy { requests_served: usize },
+    Stopped,
+}
+
+/// Run an invocation on the daemon of the database, if one is listening.
+///
+/// `None` when no daemon answers in time or it declines, so the caller runs
+/// the command itself.
+#[cfg(unix)]
+pub fn forward(db_path: &Path, args: &[String]) -> Option<Result<Outcome, Box<dyn Error>>> {
+    let socket = socket_path(db_path);
+    if !socket.exists() {
+        return None;
+    }
+    let request = Request::Run {
+        args: args.to_vec(),
+        db: std::fs::canonicalize(db_path).ok()?,
+        cwd: std::env::current_dir().ok()?,
+    };
+    match send(&socket, &request).ok()? {
+        Response::Output { output, failed } => Some(Ok(Outcome { output, failed })),
+        Response::Error { message } => Some(Err(message.into())),
+        Response::Declined { .. } | Response::Healthy { .. } | Response::Stopped => None,
+    }
+}
+
+#[cfg(not(unix))]
+pub fn forward(_db_path: &Path, _args: &[String]) -> Option<Result<Outcome, Box<dyn Error>>> {
+    None
+}
+
+/// Ask the daemon listening on `socket` to exit
+#[cfg(unix)]
+pub fn stop(socket: &Path) -> Result<(), Box<dyn Error>> {
+    match send(socket, &Request::Stop) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("No daemon is listening on {}: {}", socket.display(), e).into()),
+    }
+}
+
+/// Check the daemon listening on `socket` is healthy, returning how many
+/// invocations it has answered
+#[cfg(unix)]
+pub fn ping(socket: &Path) -> Result<usize, Box<dyn Error>> {
+    match send(socket, &Request::Ping) {
+        Ok(Response::Healthy { requests_served }) => Ok(requests_served),
+        Ok(Response::Error { message }) => Err(format!("The daemon on {} is unhealthy: {}", socket.display(), message).into()),
+        Ok(response) => Err(format!("Unexpected answer from the daemon on {}: {:?}", socket.display(), response).into()),
+        Err(e) => Err(format!("No daemon is listening on {}: {}", socket.display(), e).into()),
+    }
+}
+
+#[cfg(unix)]
+fn send(socket: &Path, request: &Request) -> Result<Response, Box<dyn Error>> {
+    let mut stream = std::os::unix::net::UnixStream::connect(socket)?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_read_timeout(Some(ANSWER_TIMEOUT))?;
+    writeln!(stream, "{}", serde_json::to_string(request)?)?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+/// Serve invocations on `socket` until a stop request, returning how many were answered.
+///
+/// Connections are accepted on a separate thread and each request is read on
+/// a thread of its own, so a stalled client blocks no one. Requests are then
+/// handled one at a time on the calling thread, which owns the cached
+/// adjacency indexes; invocations arriving while it is busy are declined. The
+/// indexes are dropped whenever an import or another change to the data has
+/// bumped the cache generation since the last request.
+#[cfg(unix)]
+pub fn serve(db: &db::DbInstance, db_path: &Path, socket: &Path) -> Result<usize, Box<dyn Error>> {
+    use std::os::unix::net::UnixStream;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    if socket.exists() {
+        if UnixStream::connect(socket).is_ok() {
+            return Err(format!("A daemon is already listening on {}", socket.display()).into());
+        }
+        // Left behind by a daemon that was killed
+        std::fs::remove_file(socket)?;
+    }
+    let listener = bind_private(socket)?;
+    let db_path = std::fs::canonicalize(db_path)?;
+    let cwd = std::env::current_dir()?;
+
+    let busy = Arc::new(AtomicBool::new(false));
+    let stopped = Arc::new(AtomicBool::new(false));
+    let (sender, requests) = std::sync::mpsc::channel();
+    let acceptor = {
+        let (busy, stopped) = (busy.clone(), stopped.clone());
+        std::thread::spawn(move || accept(listener, sender, &busy, &stopped))
+    };
+
+    let mut generation = db::queries::cache::current_generation(db)?;
+    let mut served = 0;
+    for (request, stream) in requests {
+        let response = match request {
+            Ok(Request::Stop) => {
+                respond(&stream, &Response::Stopped);
+                break;
+            }
+            Ok(Request::Ping) => match db::ping(db) {
+                Ok(()) => Response::Healthy { requests_served: served },
+                Err(e) => Response::Error { message: e.to_string() },
+            },
+            Ok(Request::Run { args, db: requested_db, cwd: requested_cwd }) => {
+                if requested_db != db_path {
+                    Response::Declined { reason: "different database".to_string() }
+                } else if requested_cwd != cwd {
+                    Response::Declined { reason: "different working directory".to_string() }
+                } else {
+                    let current = db::queries::cache::current_generation(db).unwrap_or(generation);
+                    if current != generation {
+                        db::queries::adjacency::clear_cache();
+                        generation = current;
+                    }
+                    served += 1;
+                    busy.store(true, Ordering::SeqCst);
+                    let response = run(db, &args);
+                    busy.store(false, Ordering::SeqCst);
+                    response
+                }
+            }
+            Err(message) => Response::Error { message },
+        };
+        respond(&stream, &response);
+    }
+
+    // Wake the acceptor so it sees the daemon has stopped
+    stopped.store(true, Ordering::SeqCst);
+    UnixStream::connect(socket).ok();
+    acceptor.join().ok();
+    std::fs::remove_file(socket).ok();
+    Ok(served)
+}
+
+/// Bind `socket` so that only the current user can connect.
+///
+/// Any local user could otherwise stop the daemon or query the database. The
+/// socket is bound inside a fresh directory only the user can enter, narrowed
+/// to mode 0600 and only then moved into place, so no one else can connect
+/// in between.
+#[cfg(unix)]
+fn bind_private(socket: &Path) -> Result<std::os::unix::net::UnixListener, Box<dyn Error>> {
+    use std::fs::Permissions;
+    use std::os::unix::fs::PermissionsExt;
+
+    let parent = socket.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let staging = tempfile::Builder::new()
+        .prefix(".daemon")
+        .permissions(Permissions::from_mode(0o700))
+        .tempdir_in(parent)?;
+    let staged = staging.path().join("socket");
+    let listener = std::os::unix::net::UnixListener::bind(&staged)?;
+    std::fs::set_permissions(&staged, Permissions::from_mode(0o600))?;
+    std::fs::rename(&staged, socket)?;
+    Ok(listener)
+}
+
+/// A request as read from a connection, or why it could not be read
+#[cfg(unix)]
+type Incoming = (Result<Request, String>, std::os::unix::net::UnixStream);
+
+/// Accept connections until `stopped`, reading each request on its own thread
+#[cfg(unix)]
+fn accept(
+    listener: std::os::unix::net::UnixListener,
+    sender: std::sync::mpsc::Sender<Incoming>,
+    busy: &std::sync::Arc<std::sync::atomic::AtomicBool>,
+    stopped: &std::sync::atomic::AtomicBool,
+) {
+    use std::sync::atomic::Ordering;
+
+    for stream in listener.incoming() {
+        if stopped.load(Ordering::SeqCst) {
+            break;
+        }
+        let Ok(stream) = stream else { continue };
+        let (sender, busy) = (sender.clone(), busy.clone());
+        std::thread::spawn(move || {
+            if stream.set_read_timeout(Some(REQUEST_TIMEOUT)).is_err() {
+                return;
+            }
+            let mut line = String::new();
+            // A client that does not send its request in time is dropped
+            if BufReader::new(&stream).read_line(&mut line).is_err() {
+                return;
+            }
+            let request = serde_json::from_str::<Request>(&line).map_err(|e| format!("Invalid request: {}", e));
+            if matches!(request, Ok(Request::Run { .. })) && busy.load(Ordering::SeqCst) {
+                respond(&stream, &Response::Declined { reason: "busy with another request".to_string() });
+                return;
+            }
+            sender.send((request, stream)).ok();
+        });
+    }
+}
+
+#[cfg(not(unix))]
+pub fn serve(_db: &db::DbInstance, _db_path: &Path, _socket: &Path) -> Result<usize, Box<dyn Error>> {
+    Err("The daemon needs unix domain sockets, which this platform lacks".into())
+}
+
+#[cfg(not(unix))]
+pub fn stop(_socket: &Path) -> Result<(), Box<dyn Error>> {
+    Err("The daemon needs unix domain sockets, which this platform lacks".into())
+}
+
+#[cfg(not(unix))]
+pub fn ping(_socket: &Path) -> Result<usize, Box<dyn Error>> {
+    Err("The daemon needs unix domain sockets, which this platform lacks".into())
+}
+
+/// Run one forwarded invocation
+fn run(db: &db::DbInstance, args: &[String]) -> Response {
+    use clap::Parser;
+
+    let parsed = match crate::cli::Args::try_parse_from(std::iter::once("code_search").chain(args.iter().map(String::as_str))) {
+        Ok(parsed) => parsed,
+        Err(e) => return Response::Error { message: e.to_string().trim_end().to_string() },
+    };
+    if !parsed.command.batchable() {
+        return Response::Declined { reason: "command changes the database or reads stdin".to_string() };
+    }
+    match crate::cli::run(parsed, db) {
+        Ok(outcome) => Response::Output { output: outcome.output, failed: outcome.failed },
+        Err(e) => Response::Error { message: e.to_string() },
+    }
+}
+
+#[cfg(unix)]
+fn respond(mut stream: &std::os::unix::net::UnixStream, response: &Response) {
+    // The client may have gone away or stopped reading; the next request is unaffected
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT)).ok();
+    if let Ok(json) = serde_json::to_string(response) {
+        writeln!(stream, "{}", json).ok();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_socket_path() {
+        assert_eq!(socket_path(Path::new(".code_search/cozo.sqlite")), PathBuf::from(".code_search/cozo.sock"));
+    }
+
+    #[test]
+    fn test_forward_to_daemon() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("cozo.sqlite");
+        let socket = socket_path(&db_path);
+        let db = db::open_db(&db_path).unwrap();
+        db::queries::import::import_json_str(&db, db::fixtures::CALL_GRAPH, "default").unwrap();
+
+        assert!(forward(&db_path, &["hotspots".to_string()]).is_none(), "no daemon yet");
+
+        let server = {
+            let (db_path, socket) = (db_path.clone(), socket.clone());
+            std::thread::spawn(move || serve(&db, &db_path, &socket).unwrap())
+        };
+        while std::os::unix::net::UnixStream::connect(&socket).is_err() {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        // Only the user running the daemon may connect
+        assert_eq!(std::fs::metadata(&socket).unwrap().permissions().mode() & 0o777, 0o600);
+
+        // A client that connects and never sends its request holds up no one
+        let _stalled = std::os::unix::net::UnixStream::connect(&socket).unwrap();
+
+        let args: Vec<String> = ["hotspots", "-l", "1", "-o", "csv", "--fields", "module,function"].map(String::from).into();
+        let outcome = forward(&db_path, &args).unwrap().unwrap();
+        assert_eq!(outcome.output, "module,function\nMyApp.Repo,get");
+        assert!(!outcome.failed);
+
+        let error = forward(&db_path, &["location".to_string()]).unwrap().unwrap_err();
+        assert!(error.to_string().contains("required"));
+
+        // Another database is not this daemon's to answer
+        let other = Request::Run { args, db: PathBuf::from("/elsewhere/cozo.sqlite"), cwd: std::env::current_dir().unwrap() };
+        assert!(matches!(send(&socket, &other).unwrap(), Response::Declined { .. }));
+        assert_eq!(ping(&socket).unwrap(), 2);
+
+        stop(&socket).unwrap();
+        assert_eq!(server.join().unwrap(), 2);
+        assert!(!socket.exists());
+        assert!(stop(&socket).is_err());
+        assert!(ping(&socket).is_err());
+    }
+}
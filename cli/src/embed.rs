@@ -0,0 +1,317 @@
+//! Embedding providers for semantic search.
+//!
+//! An [`Embedder`] turns text into vectors. Two providers are available,
+//! selected by the `embeddings` section of the config file:
+//!
+//! - `hashing` (default): a built-in bag-of-words model that hashes identifier
+//!   words and their trigrams into a fixed-size vector. It needs no model files
+//!   or network access, and matches on shared vocabulary rather than meaning.
+//! - `http`: an OpenAI-compatible `/embeddings` endpoint, such as OpenAI itself
+//!   or a local model served by Ollama or text-embeddings-inference.
+
+use std::error::Error;
+
+use db::queries::embeddings::EmbeddingInput;
+use serde::Deserialize;
+#[cfg(feature = "http-embeddings")]
+use serde_json::{json, Value};
+
+/// Default vector size of the hashing provider
+pub const DEFAULT_HASHING_DIMENSIONS: usize = 256;
+
+/// Computes vector embeddings for text
+pub trait Embedder {
+    /// Identifier stored with the vectors; queries must use the same model
+    fn model_id(&self) -> String;
+
+    /// One vector per text, all of the same dimension
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, Box<dyn Error>>;
+}
+
+/// Embedding provider
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    /// Built-in hashed bag-of-words vectors (offline)
+    #[default]
+    Hashing,
+    /// OpenAI-compatible HTTP embedding API
+    Http,
+}
+
+/// The `embeddings` section of the config file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct EmbeddingConfig {
+    pub provider: Provider,
+    /// Endpoint URL for the http provider (e.g. `http://localhost:11434/v1/embeddings`)
+    pub url: Option<String>,
+    /// Model name sent to the http provider
+    pub model: Option<String>,
+    /// Environment variable holding the API key sent as a bearer token
+    pub api_key_env: Option<String>,
+    /// Vector size of the hashing provider
+    pub dimensions: Option<usize>,
+}
+
+impl EmbeddingConfig {
+    /// Build the configured embedder
+    pub fn embedder(&self) -> Result<Box<dyn Embedder>, Box<dyn Error>> {
+        match self.provider {
+            Provider::Hashing => Ok(Box::new(HashingEmbedder::new(
+                self.dimensions.unwrap_or(DEFAULT_HASHING_DIMENSIONS),
+            )?)),
+            #[cfg(feature = "http-embeddings")]
+            Provider::Http => {
+                let url = self.url.clone().ok_or("The http embedding provider needs a `url`")?;
+                let model = self.model.clone().ok_or("The http embedding provider needs a `model`")?;
+                let api_key = match &self.api_key_env {
+                    Some(var) => Some(std::env::var(var).map_err(|_| format!("Environment variable {} is not set", var))?),
+                    None => None,
+                };
+                Ok(Box::new(HttpEmbedder { url, model, api_key }))
+            }
+            #[cfg(not(feature = "http-embeddings"))]
+            Provider::Http => {
+                Err("The http embedding provider needs code_search built with the `http-embeddings` feature".into())
+            }
+        }
+    }
+}
+
+/// Text embedded for a function: its qualified name split into words, then its spec and doc
+pub fn embedding_text(input: &EmbeddingInput) -> String {
+    let mut text = format!(
+        "{} {} {}",
+        split_identifier(&input.module).join(" "),
+        split_identifier(&input.name).join(" "),
+        input.arity
+    );
+    for extra in [&input.spec, &input.doc] {
+        if !extra.is_empty() {
+            text.push_str(". ");
+            text.push_str(extra);
+        }
+    }
+    text
+}
+
+/// Lowercase words of an identifier or sentence: `MyApp.get_userById` -> my, app, get, user, by, id
+fn split_identifier(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            prev_lower = false;
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Built-in embedder hashing words and character trigrams into a fixed-size vector
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl HashingEmbedder {
+    pub fn new(dimensions: usize) -> Result<Self, Box<dyn Error>> {
+        if !(16..=4096).contains(&dimensions) {
+            return Err(format!("Hashing embedding dimensions must be 16-4096, got {}", dimensions).into());
+        }
+        Ok(Self { dimensions })
+    }
+
+    fn embed_one(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+        let mut add = |feature: &str, weight: f32| {
+            let hash = fnv1a(feature.as_bytes());
+            let bucket = (hash % self.dimensions as u64) as usize;
+            // The sign bit spreads collisions out instead of piling them up
+            let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            vector[bucket] += sign * weight;
+        };
+
+        for word in split_identifier(text).iter().filter(|w| w.len() > 1) {
+            let stem = word.strip_suffix('s').filter(|s| s.len() > 2).unwrap_or(word);
+            add(stem, 1.0);
+            // Trigrams let related forms (create/creation, auth/authenticate) overlap
+            let padded: Vec<char> = format!("^{}$", stem).chars().collect();
+            for trigram in padded.windows(3) {
+                add(&trigram.iter().collect::<String>(), 0.3);
+            }
+        }
+
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|x| *x /= norm);
+        }
+        vector
+    }
+}
+
+impl Embedder for HashingEmbedder {
+    fn model_id(&self) -> String {
+        format!("hashing-{}", self.dimensions)
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+        Ok(texts.iter().map(|text| self.embed_one(text)).collect())
+    }
+}
+
+/// 64-bit FNV-1a, stable across runs and platforms
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325u64, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Embedder calling an OpenAI-compatible embeddings endpoint
+#[cfg(feature = "http-embeddings")]
+pub struct HttpEmbedder {
+    url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+#[cfg(feature = "http-embeddings")]
+impl Embedder for HttpEmbedder {
+    fn model_id(&self) -> String {
+        format!("http:{}", self.model)
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+        let mut request = ureq::post(&self.url).set("Content-Type", "application/json");
+        if let Some(key) = &self.api_key {
+            request = request.set("Authorization", &format!("Bearer {}", key));
+        }
+        let body = json!({ "model": self.model, "input": texts });
+        let response = request
+            .send_string(&body.to_string())
+            .map_err(|e| format!("Embedding request to {} failed: {}", self.url, e))?
+            .into_string()?;
+        let response: Value = serde_json::from_str(&response)?;
+        parse_embeddings_response(&response, texts.len())
+    }
+}
+
+/// Vectors from an OpenAI-style response (`data[].embedding`, ordered by `index`)
+#[cfg(feature = "http-embeddings")]
+fn parse_embeddings_response(response: &Value, expected: usize) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    let data = response["data"]
+        .as_array()
+        .ok_or("Embedding response has no `data` array")?;
+
+    let mut indexed: Vec<(u64, Vec<f32>)> = Vec::with_capacity(data.len());
+    for (position, item) in data.iter().enumerate() {
+        let vector = item["embedding"]
+            .as_array()
+            .ok_or("Embedding response item has no `embedding` array")?
+            .iter()
+            .map(|x| x.as_f64().map(|x| x as f32).ok_or("Embedding values must be numbers"))
+            .collect::<Result<Vec<f32>, _>>()?;
+        indexed.push((item["index"].as_u64().unwrap_or(position as u64), vector));
+    }
+    indexed.sort_by_key(|(index, _)| *index);
+
+    if indexed.len() != expected {
+        return Err(format!("Expected {} embeddings, got {}", expected, indexed.len()).into());
+    }
+    Ok(indexed.into_iter().map(|(_, vector)| vector).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cosine(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b).map(|(x, y)| x * y).sum()
+    }
+
+    #[test]
+    fn test_split_identifier() {
+        assert_eq!(
+            split_identifier("MyApp.Accounts.get_userById/1"),
+            ["my", "app", "accounts", "get", "user", "by", "id", "1"]
+        );
+    }
+
+    #[test]
+    fn test_hashing_embedder_is_normalized_and_deterministic() {
+        let embedder = HashingEmbedder::new(64).unwrap();
+        let texts = vec!["fetch user by id".to_string(), "fetch user by id".to_string()];
+        let vectors = embedder.embed(&texts).unwrap();
+
+        assert_eq!(vectors[0].len(), 64);
+        assert_eq!(vectors[0], vectors[1]);
+        assert!((cosine(&vectors[0], &vectors[0]) - 1.0).abs() < 1e-5);
+        assert_eq!(embedder.model_id(), "hashing-64");
+    }
+
+    #[test]
+    fn test_hashing_embedder_ranks_shared_vocabulary_higher() {
+        let embedder = HashingEmbedder::new(256).unwrap();
+        let texts: Vec<String> = ["load users from database", "get users", "render invoice pdf"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let v = embedder.embed(&texts).unwrap();
+        assert!(cosine(&v[0], &v[1]) > cosine(&v[0], &v[2]));
+    }
+
+    #[test]
+    fn test_hashing_embedder_rejects_bad_dimensions() {
+        assert!(HashingEmbedder::new(4).is_err());
+    }
+
+    #[test]
+    fn test_embedding_text() {
+        let input = EmbeddingInput {
+            module: "MyApp.Accounts".to_string(),
+            name: "get_user".to_string(),
+            arity: 1,
+            doc: "Fetches a user.".to_string(),
+            spec: String::new(),
+        };
+        assert_eq!(embedding_text(&input), "my app accounts get user 1. Fetches a user.");
+    }
+
+    #[cfg(feature = "http-embeddings")]
+    #[test]
+    fn test_parse_embeddings_response_orders_by_index() {
+        let response = json!({
+            "data": [
+                {"index": 1, "embedding": [0.0, 1.0]},
+                {"index": 0, "embedding": [1.0, 0.0]}
+            ]
+        });
+        let vectors = parse_embeddings_response(&response, 2).unwrap();
+        assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+        assert!(parse_embeddings_response(&response, 3).is_err());
+        assert!(parse_embeddings_response(&json!({}), 0).is_err());
+    }
+
+    #[test]
+    fn test_http_provider_requires_url_and_model() {
+        let config = EmbeddingConfig {
+            provider: Provider::Http,
+            model: Some("nomic-embed-text".to_string()),
+            ..Default::default()
+        };
+        assert!(config.embedder().is_err());
+    }
+}
@@ -0,0 +1,734 @@
+//! Built-in Elixir extractor, for `extract --builtin`.
+//!
+//! Parses source files with tree-sitter-elixir instead of compiling the
+//! project, producing a call graph in the Elixir import format. The result is
+//! approximate: macros are not expanded, so functions a macro defines are
+//! missing; calls are recorded when they are qualified (`Repo.get(...)`,
+//! `:ets.lookup(...)`, through `alias`es and `__MODULE__`) or name a function
+//! the same module defines, while calls to imported functions are not.
+//! `alias`, `import` and `require` directives are listed per module.
+//! Complexity counts branches in the syntax tree.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+
+use serde_json::{Value, json};
+use tree_sitter::{Node, Parser};
+
+const DEF_KINDS: [&str; 4] = ["def", "defp", "defmacro", "defmacrop"];
+
+/// Parse `files` and build their call graph
+pub fn extract(files: &[String]) -> Result<Value, Box<dyn Error>> {
+    let mut parser = Parser::new();
+    parser.set_language(&tree_sitter_elixir::LANGUAGE.into())?;
+
+    let mut graph = Graph::default();
+    for file in files {
+        let source = fs::read_to_string(file).map_err(|e| format!("Failed to read '{}': {}", file, e))?;
+        let tree = parser
+            .parse(&source, None)
+            .ok_or_else(|| format!("Failed to parse '{}'", file))?;
+        let mut source_file = SourceFile {
+            path: file,
+            source: &source,
+            graph: &mut graph,
+        };
+        source_file.find_modules(tree.root_node(), None);
+    }
+
+    Ok(json!({
+        "structs": graph.structs,
+        "function_locations": graph.function_locations,
+        "calls": graph.calls,
+        "aliases": graph.aliases,
+    }))
+}
+
+#[derive(Default)]
+struct Graph {
+    structs: BTreeMap<String, Value>,
+    function_locations: BTreeMap<String, BTreeMap<String, Value>>,
+    calls: Vec<Value>,
+    /// `alias`, `import` and `require` directives per module
+    aliases: BTreeMap<String, Vec<Value>>,
+}
+
+/// Module being extracted
+#[derive(Clone)]
+struct Scope {
+    module: String,
+    /// Short names of aliased modules, to their full names
+    aliases: HashMap<String, String>,
+}
+
+impl Scope {
+    /// Full name of a module reference, expanding aliases and `__MODULE__`
+    fn resolve(&self, name: &str) -> String {
+        let (first, rest) = name.split_once('.').map_or((name, None), |(first, rest)| (first, Some(rest)));
+        let first = match first {
+            "__MODULE__" => self.module.clone(),
+            _ => self.aliases.get(first).cloned().unwrap_or_else(|| first.to_string()),
+        };
+        match rest {
+            Some(rest) => format!("{}.{}", first, rest),
+            None => first,
+        }
+    }
+}
+
+/// A `def`, `defp`, `defmacro` or `defmacrop` clause
+struct Clause<'t> {
+    node: Node<'t>,
+    kind: &'static str,
+    name: String,
+    arity: usize,
+    pattern: String,
+    guard: Option<String>,
+}
+
+struct SourceFile<'a> {
+    path: &'a str,
+    source: &'a str,
+    graph: &'a mut Graph,
+}
+
+impl SourceFile<'_> {
+    fn text(&self, node: Node) -> &str {
+        &self.source[node.byte_range()]
+    }
+
+    /// Extract the `defmodule`s under `node`, nested in `parent` if given
+    fn find_modules(&mut self, node: Node, parent: Option<&Scope>) {
+        if let Some(("defmodule", arguments)) = self.macro_call(node)
+            && let Some(name) = arguments.named_child(0).filter(|name| name.kind() == "alias")
+        {
+            let name = self.text(name).to_string();
+            let scope = match parent {
+                Some(parent) => Scope {
+                    module: format!("{}.{}", parent.module, name),
+                    aliases: parent.aliases.clone(),
+                },
+                None => Scope {
+                    module: name,
+                    aliases: HashMap::new(),
+                },
+            };
+            if let Some(body) = child_of_kind(node, "do_block") {
+                self.module(body, scope);
+            }
+            return;
+        }
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            self.find_modules(child, parent);
+        }
+    }
+
+    /// Extract the definitions and calls of the module whose `do` block is `body`
+    fn module(&mut self, body: Node, mut scope: Scope) {
+        let mut clauses = Vec::new();
+        let mut nested = Vec::new();
+        let mut cursor = body.walk();
+        for statement in body.named_children(&mut cursor) {
+            match self.macro_call(statement) {
+                Some(("alias", arguments)) => self.alias(statement, arguments, &mut scope),
+                Some(("import", arguments)) => self.import(statement, "import", arguments, &mut scope),
+                Some(("require", arguments)) => self.import(statement, "require", arguments, &mut scope),
+                Some(("defstruct", arguments)) => self.defstruct(arguments, &scope.module),
+                Some(("defmodule", arguments)) => {
+                    // A nested module is aliased in its parent by its last segment
+                    if let Some(name) = arguments.named_child(0).filter(|name| name.kind() == "alias") {
+                        let name = self.text(name);
+                        scope.aliases.insert(last_segment(name).to_string(), format!("{}.{}", scope.module, name));
+                    }
+                    nested.push(statement);
+                }
+                Some((kind, arguments)) => {
+                    if let Some(kind) = DEF_KINDS.iter().find(|k| **k == kind)
+                        && let Some(clause) = self.clause(statement, kind, arguments)
+                    {
+                        clauses.push(clause);
+                    }
+                }
+                None => {}
+            }
+        }
+
+        // Functions with default arguments can be called with fewer
+        let mut defined: HashSet<(String, usize)> = HashSet::new();
+        for clause in &clauses {
+            let defaults = self.defaults(clause.node);
+            for arity in clause.arity - defaults..=clause.arity {
+                defined.insert((clause.name.clone(), arity));
+            }
+        }
+
+        for clause in &clauses {
+            self.function(clause, &scope, &defined);
+        }
+        for module in nested {
+            self.find_modules(module, Some(&scope));
+        }
+    }
+
+    /// The name and arguments of a call to `name` without a receiver, such as
+    /// `def` or `alias`
+    fn macro_call<'t>(&self, node: Node<'t>) -> Option<(&str, Node<'t>)> {
+        if node.kind() != "call" {
+            return None;
+        }
+        let target = node.child_by_field_name("target").filter(|t| t.kind() == "identifier")?;
+        Some((self.text(target), child_of_kind(node, "arguments")?))
+    }
+
+    /// Record the modules an `alias` makes available by a short name
+    fn alias(&mut self, statement: Node, arguments: Node, scope: &mut Scope) {
+        let Some(target) = arguments.named_child(0) else {
+            return;
+        };
+        match target.kind() {
+            // alias MyApp.{Repo, User}
+            "dot" => {
+                let (Some(left), Some(right)) = (target.child_by_field_name("left"), target.child_by_field_name("right")) else {
+                    return;
+                };
+                let base = scope.resolve(self.text(left));
+                let mut cursor = right.walk();
+                for name in right.named_children(&mut cursor).filter(|n| n.kind() == "alias") {
+                    let name = self.text(name);
+                    let (short, module) = (last_segment(name).to_string(), format!("{}.{}", base, name));
+                    self.directive(statement, scope, "alias", &module, Some(&short));
+                    scope.aliases.insert(short, module);
+                }
+            }
+            "alias" => {
+                let module = scope.resolve(self.text(target));
+                let short = self
+                    .keyword(arguments, "as")
+                    .map(|value| self.text(value).to_string())
+                    .unwrap_or_else(|| last_segment(&module).to_string());
+                self.directive(statement, scope, "alias", &module, Some(&short));
+                scope.aliases.insert(short, module);
+            }
+            _ => {}
+        }
+    }
+
+    /// Record an `import` or `require`; `require ..., as:` also aliases
+    fn import(&mut self, statement: Node, kind: &str, arguments: Node, scope: &mut Scope) {
+        let Some(target) = arguments.named_child(0).filter(|target| target.kind() == "alias") else {
+            return;
+        };
+        let module = scope.resolve(self.text(target));
+        let short = self.keyword(arguments, "as").map(|value| self.text(value).to_string());
+        self.directive(statement, scope, kind, &module, short.as_deref());
+        if let Some(short) = short {
+            scope.aliases.insert(short, module);
+        }
+    }
+
+    /// Add a directive of the scope's module to the graph
+    fn directive(&mut self, statement: Node, scope: &Scope, kind: &str, target: &str, short: Option<&str>) {
+        let mut directive = json!({"kind": kind, "target": target, "line": statement.start_position().row + 1});
+        if let Some(short) = short {
+            directive["as"] = json!(short);
+        }
+        self.graph.aliases.entry(scope.module.clone()).or_default().push(directive);
+    }
+
+    /// The value of keyword `key` among `arguments`
+    fn keyword<'t>(&self, arguments: Node<'t>, key: &str) -> Option<Node<'t>> {
+        let keywords = child_of_kind(arguments, "keywords")?;
+        let mut cursor = keywords.walk();
+        let pair = keywords
+            .named_children(&mut cursor)
+            .find(|pair| pair.child_by_field_name("key").is_some_and(|k| keyword_name(self.text(k)) == key))?;
+        pair.child_by_field_name("value")
+    }
+
+    /// Record the fields of a `defstruct [:a, b: 1]` or `defstruct a: nil`
+    fn defstruct(&mut self, arguments: Node, module: &str) {
+        let mut fields = Vec::new();
+        let mut add = |field: &str, default: &str| {
+            fields.push(json!({"field": field, "default": default, "required": false}));
+        };
+        let items = match arguments.named_child(0) {
+            Some(list) if list.kind() == "list" => list,
+            _ => arguments,
+        };
+        let mut cursor = items.walk();
+        for item in items.named_children(&mut cursor) {
+            match item.kind() {
+                "atom" => add(self.text(item).trim_start_matches(':'), "nil"),
+                "keywords" => {
+                    let mut cursor = item.walk();
+                    for pair in item.named_children(&mut cursor) {
+                        if let (Some(key), Some(value)) = (pair.child_by_field_name("key"), pair.child_by_field_name("value")) {
+                            add(keyword_name(self.text(key)), self.text(value));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.graph.structs.insert(module.to_string(), json!({"fields": fields}));
+    }
+
+    /// Parse the head of a definition: `name(args)`, `name(args) when guard`
+    /// or a bare `name`
+    fn clause<'t>(&self, node: Node<'t>, kind: &'static str, arguments: Node<'t>) -> Option<Clause<'t>> {
+        let mut head = arguments.named_child(0)?;
+        let mut guard = None;
+        if head.kind() == "binary_operator" && operator(head).is_some_and(|op| self.text(op) == "when") {
+            guard = head.child_by_field_name("right").map(|g| self.text(g).to_string());
+            head = head.child_by_field_name("left")?;
+        }
+        let (name, params) = match head.kind() {
+            "identifier" => (self.text(head).to_string(), Vec::new()),
+            "call" => {
+                let (name, params) = self.macro_call(head)?;
+                let mut cursor = params.walk();
+                (name.to_string(), params.named_children(&mut cursor).collect())
+            }
+            _ => return None,
+        };
+        Some(Clause {
+            node,
+            kind,
+            name,
+            arity: params.len(),
+            pattern: params.iter().map(|p| self.text(*p)).collect::<Vec<_>>().join(", "),
+            guard,
+        })
+    }
+
+    /// Number of parameters of a clause with a default value (`b \\ 1`)
+    fn defaults(&self, clause: Node) -> usize {
+        let params = child_of_kind(clause, "arguments")
+            .and_then(|arguments| arguments.named_child(0))
+            .map(|head| match head.kind() {
+                "binary_operator" => head.child_by_field_name("left").unwrap_or(head),
+                _ => head,
+            })
+            .and_then(|head| child_of_kind(head, "arguments"));
+        let Some(params) = params else {
+            return 0;
+        };
+        let mut cursor = params.walk();
+        params
+            .named_children(&mut cursor)
+            .filter(|p| p.kind() == "binary_operator" && operator(*p).is_some_and(|op| self.text(op) == "\\\\"))
+            .count()
+    }
+
+    /// Record a clause's location and the calls in its body
+    fn function(&mut self, clause: &Clause, scope: &Scope, defined: &HashSet<(String, usize)>) {
+        let caller = format!("{}/{}", clause.name, clause.arity);
+        let mut body = Body {
+            caller: json!({
+                "module": scope.module,
+                "function": caller,
+                "file": self.path,
+                "kind": clause.kind,
+            }),
+            file: self,
+            scope,
+            defined,
+            complexity: 1,
+            branches: 0,
+            calls: Vec::new(),
+        };
+
+        // Walk everything but the head: the do block or `do:` keywords
+        let mut cursor = clause.node.walk();
+        for child in clause.node.named_children(&mut cursor) {
+            if child.kind() == "arguments" {
+                let mut cursor = child.walk();
+                for argument in child.named_children(&mut cursor).skip(1) {
+                    body.walk(argument);
+                }
+            } else if child.kind() != "identifier" {
+                body.walk(child);
+            }
+        }
+        let (complexity, calls) = (body.complexity, body.calls);
+
+        let start = clause.node.start_position();
+        let location = json!({
+            "name": clause.name,
+            "arity": clause.arity,
+            "file": self.path,
+            "kind": clause.kind,
+            "line": start.row + 1,
+            "column": start.column + 1,
+            "start_line": start.row + 1,
+            "end_line": clause.node.end_position().row + 1,
+            "pattern": clause.pattern,
+            "guard": clause.guard,
+            "complexity": complexity,
+        });
+        self.graph
+            .function_locations
+            .entry(scope.module.clone())
+            .or_default()
+            .insert(format!("{}:{}", caller, start.row + 1), location);
+        self.graph.calls.extend(calls);
+    }
+}
+
+/// Walk over a clause body, collecting calls and counting branches
+struct Body<'a, 'f> {
+    file: &'a SourceFile<'f>,
+    scope: &'a Scope,
+    defined: &'a HashSet<(String, usize)>,
+    caller: Value,
+    complexity: usize,
+    /// Branches and comprehensions enclosing the node being walked
+    branches: usize,
+    calls: Vec<Value>,
+}
+
+impl Body<'_, '_> {
+    fn walk(&mut self, node: Node) {
+        match node.kind() {
+            "call" => self.call(node),
+            // x |> helper
+            "identifier" if piped(node, self.file) => self.record(node, None, self.file.text(node), 1),
+            // &helper/2
+            "identifier" => {
+                if let Some(arity) = captured_arity(node, self.file) {
+                    self.record(node, None, self.file.text(node), arity);
+                }
+            }
+            "binary_operator" if operator(node).is_some_and(|op| matches!(self.file.text(op), "and" | "or" | "&&" | "||")) => {
+                self.complexity += 1;
+            }
+            "do_block" | "anonymous_function" | "else_block" | "rescue_block" | "catch_block" => {
+                let mut cursor = node.walk();
+                let clauses = node.named_children(&mut cursor).filter(|c| c.kind() == "stab_clause").count();
+                self.complexity += clauses.saturating_sub(1);
+            }
+            _ => {}
+        }
+        let branching = node.kind() == "call" && branching(node, self.file);
+        let short_circuit = node.kind() == "binary_operator"
+            && operator(node).is_some_and(|op| matches!(self.file.text(op), "and" | "or" | "&&" | "||"));
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            match child.kind() {
+                "do_block" if branching => self.walk_branch(child),
+                "else_block" | "rescue_block" | "catch_block" => self.walk_branch(child),
+                // `if x, do: a, else: b` and `for x <- xs, do: f(x)`
+                "arguments" if branching => {
+                    let mut cursor = child.walk();
+                    for argument in child.named_children(&mut cursor) {
+                        if argument.kind() == "keywords" {
+                            self.walk_branch(argument);
+                        } else {
+                            self.walk(argument);
+                        }
+                    }
+                }
+                _ if short_circuit && node.child_by_field_name("right") == Some(child) => self.walk_branch(child),
+                _ => self.walk(child),
+            }
+        }
+    }
+
+    /// Walk a node that runs only on some paths
+    fn walk_branch(&mut self, node: Node) {
+        self.branches += 1;
+        self.walk(node);
+        self.branches -= 1;
+    }
+
+    fn call(&mut self, node: Node) {
+        let Some(target) = node.child_by_field_name("target") else {
+            return;
+        };
+        let arguments = child_of_kind(node, "arguments");
+        let mut arity = match arguments {
+            Some(arguments) => arguments.named_child_count(),
+            // &Module.function/2
+            None => captured_arity(node, self.file).unwrap_or(0),
+        };
+        // A do block is a trailing keyword argument
+        if child_of_kind(node, "do_block").is_some() {
+            arity += 1;
+        }
+        if piped(node, self.file) {
+            arity += 1;
+        }
+
+        match target.kind() {
+            "identifier" => {
+                let name = self.file.text(target);
+                if matches!(name, "if" | "unless") {
+                    self.complexity += 1;
+                }
+                self.record(node, None, name, arity);
+            }
+            "dot" => {
+                let (Some(left), Some(right)) = (target.child_by_field_name("left"), target.child_by_field_name("right")) else {
+                    return;
+                };
+                if right.kind() != "identifier" {
+                    return;
+                }
+                let module = match left.kind() {
+                    "alias" => self.scope.resolve(self.file.text(left)),
+                    "identifier" if self.file.text(left) == "__MODULE__" => self.scope.module.clone(),
+                    "atom" => self.file.text(left).to_string(),
+                    // Calls on variables and expressions have unknown targets
+                    _ => return,
+                };
+                self.record(node, Some(module), self.file.text(right), arity);
+            }
+            _ => {}
+        }
+    }
+
+    /// Record a call of `module.name/arity`, or of a local function when
+    /// `module` is `None` and the module defines it
+    fn record(&mut self, node: Node, module: Option<String>, name: &str, arity: usize) {
+        let (module, call_type) = match module {
+            Some(module) => (module, "remote"),
+            None if self.defined.contains(&(name.to_string(), arity)) => (self.scope.module.clone(), "local"),
+            None => return,
+        };
+        let kind = if piped(node, self.file) {
+            "pipe"
+        } else if captured_arity(node, self.file).is_some() {
+            "capture"
+        } else {
+            "call"
+        };
+        let position = node.start_position();
+        let mut caller = self.caller.clone();
+        caller["line"] = json!(position.row + 1);
+        caller["column"] = json!(position.column + 1);
+        self.calls.push(json!({
+            "caller": caller,
+            "callee": {"module": module, "function": name, "arity": arity},
+            "type": call_type,
+            "kind": kind,
+            "conditional": self.branches > 0,
+        }));
+    }
+}
+
+/// Whether `node` is a conditional or comprehension (`if`, `case`, `for`, ...),
+/// whose blocks run only on some paths
+fn branching(node: Node, file: &SourceFile) -> bool {
+    node.child_by_field_name("target").is_some_and(|target| {
+        target.kind() == "identifier"
+            && matches!(file.text(target), "if" | "unless" | "case" | "cond" | "with" | "for" | "receive")
+    })
+}
+
+/// Whether `node` is the right side of a `|>`
+fn piped(node: Node, file: &SourceFile) -> bool {
+    node.parent().is_some_and(|parent| {
+        parent.kind() == "binary_operator"
+            && operator(parent).is_some_and(|op| file.text(op) == "|>")
+            && parent.child_by_field_name("right") == Some(node)
+    })
+}
+
+/// The arity of a capture `&name/arity` or `&Module.name/arity` whose name
+/// is `node`
+fn captured_arity(node: Node, file: &SourceFile) -> Option<usize> {
+    let parent = node.parent().filter(|p| p.kind() == "binary_operator")?;
+    let capture = parent.parent().filter(|p| p.kind() == "unary_operator")?;
+    if operator(parent).is_none_or(|op| file.text(op) != "/") || operator(capture).is_none_or(|op| file.text(op) != "&") {
+        return None;
+    }
+    let arity = parent.child_by_field_name("right").filter(|r| r.kind() == "integer")?;
+    file.text(arity).parse().ok()
+}
+
+fn operator(node: Node) -> Option<Node> {
+    node.child_by_field_name("operator")
+}
+
+fn child_of_kind<'t>(node: Node<'t>, kind: &str) -> Option<Node<'t>> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).find(|child| child.kind() == kind)
+}
+
+fn last_segment(module: &str) -> &str {
+    module.rsplit('.').next().unwrap_or(module)
+}
+
+/// `as` for the keyword `as: `
+fn keyword_name(key: &str) -> &str {
+    key.trim().trim_end_matches(':')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    const SOURCE: &str = r#"defmodule MyApp.Accounts do
+  alias MyApp.{Repo, User}
+  alias MyApp.Mailer, as: M
+
+  defstruct [:id, name: "x"]
+
+  def get(id) when is_integer(id) do
+    case Repo.get(User, id) |> normalize(1) do
+      nil -> :error
+      user -> {:ok, user}
+    end
+  end
+
+  defp normalize(user, _), do: user
+
+  def notify(user, opts \\ []) do
+    if opts[:now] && user do
+      Enum.each([user], &M.deliver/1)
+    end
+    :ets.lookup(:users, user)
+    __MODULE__.get(1)
+    notify(user)
+  end
+
+  def warm, do: Cache.warm(&normalize/2)
+
+  defmodule Cache do
+    def warm(_), do: Accounts.get(1)
+    require Logger, as: Log
+  end
+end
+"#;
+
+    fn source(dir: &TempDir) -> String {
+        let path: PathBuf = dir.path().join("accounts.ex");
+        fs::write(&path, SOURCE).unwrap();
+        path.display().to_string()
+    }
+
+    fn calls_from(graph: &Value, caller: &str) -> Vec<String> {
+        graph["calls"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|call| call["caller"]["function"] == caller)
+            .map(|call| {
+                let callee = &call["callee"];
+                format!("{}.{}/{} ({})", callee["module"].as_str().unwrap(), callee["function"].as_str().unwrap(), callee["arity"], call["type"].as_str().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_function_locations() {
+        let dir = TempDir::new().unwrap();
+        let file = source(&dir);
+        let graph = extract(std::slice::from_ref(&file)).unwrap();
+
+        let accounts = &graph["function_locations"]["MyApp.Accounts"];
+        let keys: Vec<&String> = accounts.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["get/1:7", "normalize/2:14", "notify/2:16", "warm/0:25"]);
+
+        let get = &accounts["get/1:7"];
+        assert_eq!(get["file"], file.as_str());
+        assert_eq!((get["start_line"].as_u64(), get["end_line"].as_u64()), (Some(7), Some(12)));
+        assert_eq!(get["guard"], "is_integer(id)");
+        assert_eq!(get["pattern"], "id");
+        // One branch beyond the first case clause
+        assert_eq!(get["complexity"], 2);
+        assert_eq!(accounts["normalize/2:14"]["kind"], "defp");
+        // if, and &&
+        assert_eq!(accounts["notify/2:16"]["complexity"], 3);
+
+        assert!(graph["function_locations"]["MyApp.Accounts.Cache"]["warm/1:28"].is_object());
+        assert_eq!(graph["structs"]["MyApp.Accounts"]["fields"][1]["default"], "\"x\"");
+    }
+
+    #[test]
+    fn test_extract_calls() {
+        let dir = TempDir::new().unwrap();
+        let graph = extract(&[source(&dir)]).unwrap();
+
+        assert_eq!(
+            calls_from(&graph, "get/1"),
+            ["MyApp.Repo.get/2 (remote)", "MyApp.Accounts.normalize/2 (local)"]
+        );
+        // Captures, Erlang modules, __MODULE__ and default arguments
+        assert_eq!(
+            calls_from(&graph, "notify/2"),
+            [
+                "Enum.each/2 (remote)",
+                "MyApp.Mailer.deliver/1 (remote)",
+                ":ets.lookup/2 (remote)",
+                "MyApp.Accounts.get/1 (remote)",
+                "MyApp.Accounts.notify/1 (local)",
+            ]
+        );
+        // Nested modules are aliased in their parent, which they do not alias
+        assert_eq!(
+            calls_from(&graph, "warm/0"),
+            ["MyApp.Accounts.Cache.warm/1 (remote)", "MyApp.Accounts.normalize/2 (local)"]
+        );
+        assert_eq!(calls_from(&graph, "warm/1"), ["Accounts.get/1 (remote)"]);
+    }
+
+    #[test]
+    fn test_extract_call_kinds() {
+        let dir = TempDir::new().unwrap();
+        let graph = extract(&[source(&dir)]).unwrap();
+
+        let kinds: Vec<(&str, &str)> = graph["calls"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|call| call["kind"] != "call")
+            .map(|call| (call["callee"]["function"].as_str().unwrap(), call["kind"].as_str().unwrap()))
+            .collect();
+        assert_eq!(kinds, [("normalize", "pipe"), ("deliver", "capture"), ("normalize", "capture")]);
+    }
+
+    #[test]
+    fn test_extract_conditional_calls() {
+        let dir = TempDir::new().unwrap();
+        let graph = extract(&[source(&dir)]).unwrap();
+
+        // The case subject runs on every path, the body of the if does not
+        let conditional: Vec<&str> = graph["calls"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|call| call["conditional"] == true)
+            .map(|call| call["callee"]["function"].as_str().unwrap())
+            .collect();
+        assert_eq!(conditional, ["each", "deliver"]);
+    }
+
+    #[test]
+    fn test_extract_aliases() {
+        let dir = TempDir::new().unwrap();
+        let graph = extract(&[source(&dir)]).unwrap();
+
+        let directives = |module: &str| -> Vec<String> {
+            graph["aliases"][module]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|d| format!("{} {} as {} ({})", d["kind"].as_str().unwrap(), d["target"].as_str().unwrap(), d["as"], d["line"]))
+                .collect()
+        };
+        assert_eq!(
+            directives("MyApp.Accounts"),
+            [
+                "alias MyApp.Repo as \"Repo\" (2)",
+                "alias MyApp.User as \"User\" (2)",
+                "alias MyApp.Mailer as \"M\" (3)",
+            ]
+        );
+        assert_eq!(directives("MyApp.Accounts.Cache"), ["require Logger as \"Log\" (29)"]);
+    }
+}
@@ -0,0 +1,318 @@
+//! Running external extractors and importing what they emit.
+//!
+//! Extractors are configured under `extractors` in the config file (see
+//! [`ExtractorConfig`]): a per-language executable gets source files as
+//! arguments and prints a call graph as JSON. `extract` runs them over a
+//! source tree and `update` over the files a commit changed; both import each
+//! extractor's output in place of the stored rows of the files it read.
+
+#[cfg(feature = "builtin-extractor")]
+pub mod elixir;
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::Serialize;
+use tempfile::NamedTempFile;
+
+use crate::commands::import::ImportReport;
+use crate::commands::{Execute, ImportCmd, ImportSchema};
+use crate::config::ExtractorConfig;
+use db::query_builders::QuerySettings;
+use db::DbInstance;
+
+/// Name the built-in Elixir extractor reports its imports under
+pub const BUILTIN: &str = "builtin";
+
+/// Why `extract --builtin` fails in builds without the built-in extractor
+pub const BUILTIN_UNAVAILABLE: &str =
+    "The built-in extractor needs code_search built with the `builtin-extractor` feature";
+
+/// Call graph imported for an extractor left without files to read
+const EMPTY_GRAPH: &str = r#"{"structs": {}, "function_locations": {}, "calls": []}"#;
+
+/// Import of one extractor's output
+#[derive(Debug, Serialize)]
+pub struct ExtractorImport {
+    pub extractor: String,
+    /// Files whose rows the import replaced
+    pub files: usize,
+    pub import: ImportReport,
+}
+
+/// How the extracted call graphs are imported
+pub struct ImportSettings {
+    pub project: String,
+    /// Mix environment of the extractors (`MIX_ENV` and `{mix_env}`)
+    pub mix_env: String,
+    pub commit: Option<String>,
+    pub modified_at: Option<i64>,
+    /// Config file with the import's dedup keys
+    pub config: PathBuf,
+}
+
+impl ImportSettings {
+    /// Run `extractor` over the existing `files` and import its output in place
+    /// of the rows of all of them.
+    ///
+    /// Files that no longer exist are not passed to the extractor; their rows
+    /// are removed all the same. `clear` empties the project first, and the
+    /// metrics snapshot is only recorded with `snapshot`.
+    pub fn extract_and_import(
+        &self,
+        db: &DbInstance,
+        extractor: &ExtractorConfig,
+        files: &[String],
+        clear: bool,
+        snapshot: bool,
+        settings: &QuerySettings,
+    ) -> Result<ExtractorImport, Box<dyn Error>> {
+        let present = existing(files);
+        let (output, schema) = if present.is_empty() {
+            let output = NamedTempFile::new()?;
+            fs::write(output.path(), EMPTY_GRAPH)?;
+            (output, ImportSchema::Elixir)
+        } else {
+            (run_extractor(extractor, &self.mix_env, &present)?, extractor.schema)
+        };
+        Ok(ExtractorImport {
+            extractor: extractor.name.clone(),
+            files: files.len(),
+            import: self.import(db, output.path(), schema, files, clear, snapshot, settings)?,
+        })
+    }
+
+    /// Like [`Self::extract_and_import`], parsing the files with the built-in
+    /// Elixir extractor instead of running an external one
+    #[cfg(feature = "builtin-extractor")]
+    pub fn extract_builtin_and_import(
+        &self,
+        db: &DbInstance,
+        files: &[String],
+        clear: bool,
+        snapshot: bool,
+        settings: &QuerySettings,
+    ) -> Result<ExtractorImport, Box<dyn Error>> {
+        let graph = elixir::extract(&existing(files))?;
+        let output = NamedTempFile::new()?;
+        fs::write(output.path(), serde_json::to_string(&graph)?)?;
+        Ok(ExtractorImport {
+            extractor: BUILTIN.to_string(),
+            files: files.len(),
+            import: self.import(db, output.path(), ImportSchema::Elixir, files, clear, snapshot, settings)?,
+        })
+    }
+
+    #[cfg(not(feature = "builtin-extractor"))]
+    pub fn extract_builtin_and_import(
+        &self,
+        _db: &DbInstance,
+        _files: &[String],
+        _clear: bool,
+        _snapshot: bool,
+        _settings: &QuerySettings,
+    ) -> Result<ExtractorImport, Box<dyn Error>> {
+        Err(BUILTIN_UNAVAILABLE.into())
+    }
+
+    /// Import the call graph in `graph` in place of the rows of `files`
+    #[allow(clippy::too_many_arguments)]
+    fn import(
+        &self,
+        db: &DbInstance,
+        graph: &Path,
+        schema: ImportSchema,
+        files: &[String],
+        clear: bool,
+        snapshot: bool,
+        settings: &QuerySettings,
+    ) -> Result<ImportReport, Box<dyn Error>> {
+        ImportCmd {
+            file: graph.to_path_buf(),
+            schema,
+            project: self.project.clone(),
+            clear,
+            supervision: None,
+            label: self.commit.clone().unwrap_or_default(),
+            no_snapshot: !snapshot,
+            dry_run: false,
+            strict: false,
+            lenient: false,
+            dedup: Vec::new(),
+            resume: false,
+            commit: self.commit.clone(),
+            modified_at: self.modified_at,
+            replace_files: files.to_vec(),
+            exclude_paths: Vec::new(),
+            include_deps: false,
+            no_gitignore: false,
+            config: self.config.clone(),
+        }
+        .execute(db, settings)
+    }
+}
+
+/// The files that still exist
+fn existing(files: &[String]) -> Vec<String> {
+    files.iter().filter(|file| Path::new(file).exists()).cloned().collect()
+}
+
+/// The first extractor reading `file`, by extension
+pub fn extractor_for<'a>(extractors: &'a [ExtractorConfig], file: &str) -> Option<&'a ExtractorConfig> {
+    extractors.iter().find(|extractor| extractor.reads(Path::new(file)))
+}
+
+/// `files` grouped by the extractor reading them, in configuration order.
+///
+/// Extractors reading none of the files are left out, as are files no
+/// extractor reads.
+pub fn group_by_extractor<'a>(extractors: &'a [ExtractorConfig], files: &[String]) -> Vec<(&'a ExtractorConfig, Vec<String>)> {
+    let mut groups: Vec<(&ExtractorConfig, Vec<String>)> = extractors.iter().map(|e| (e, Vec::new())).collect();
+    for file in files {
+        if let Some(i) = extractors.iter().position(|e| e.reads(Path::new(file))) {
+            groups[i].1.push(file.clone());
+        }
+    }
+    groups.retain(|(_, files)| !files.is_empty());
+    groups
+}
+
+/// Run the prepare command, then the extractor over `files`, returning the
+/// file holding the call graph it emitted
+pub fn run_extractor(extractor: &ExtractorConfig, mix_env: &str, files: &[String]) -> Result<NamedTempFile, Box<dyn Error>> {
+    let output = tempfile::Builder::new()
+        .prefix("code_search_extract_")
+        .suffix(".json")
+        .tempfile()?;
+
+    if !extractor.prepare.is_empty() {
+        let args = expand(&extractor.prepare, mix_env, &[], output.path());
+        run("prepare", &extractor.name, &args, mix_env, Stdio::null())?;
+    }
+
+    let mut args = expand(&extractor.command, mix_env, files, output.path());
+    if !extractor.command.iter().any(|arg| arg == "{files}") {
+        args.extend(files.iter().cloned());
+    }
+    let writes_output = extractor.command.iter().any(|arg| arg.contains("{output}"));
+    let stdout = if writes_output {
+        Stdio::null()
+    } else {
+        Stdio::from(output.reopen()?)
+    };
+    run("extractor", &extractor.name, &args, mix_env, stdout)?;
+
+    // Extractors may write nothing when the files define no modules
+    if fs::metadata(output.path())?.len() == 0 {
+        fs::write(output.path(), EMPTY_GRAPH)?;
+    }
+    Ok(output)
+}
+
+/// Arguments with their placeholders filled in
+fn expand(args: &[String], mix_env: &str, files: &[String], output: &Path) -> Vec<String> {
+    let output = output.display().to_string();
+    args.iter()
+        .flat_map(|arg| match arg.as_str() {
+            "{files}" => files.to_vec(),
+            _ => vec![arg.replace("{output}", &output).replace("{mix_env}", mix_env)],
+        })
+        .collect()
+}
+
+/// Run a configured command, failing with its stderr when it exits nonzero
+fn run(step: &str, extractor: &str, args: &[String], mix_env: &str, stdout: Stdio) -> Result<(), Box<dyn Error>> {
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| format!("The {} command of extractor '{}' is empty", step, extractor))?;
+    let output = Command::new(program)
+        .args(args)
+        .env("MIX_ENV", mix_env)
+        .stdout(stdout)
+        .output()
+        .map_err(|e| format!("Failed to run the {} of extractor '{}' ({}): {}", step, extractor, program, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "The {} of extractor '{}' ({}) failed ({}): {}",
+            step,
+            extractor,
+            program,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extractor(prepare: &[&str], command: &[&str]) -> ExtractorConfig {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect();
+        ExtractorConfig {
+            name: "test".to_string(),
+            prepare: args(prepare),
+            command: args(command),
+            extensions: args(&["ex"]),
+            exclude: Vec::new(),
+            schema: ImportSchema::Elixir,
+        }
+    }
+
+    #[test]
+    fn test_group_by_extractor() {
+        let mut python = extractor(&[], &["py_callgraph"]);
+        python.name = "python".to_string();
+        python.extensions = vec!["py".to_string()];
+        let extractors = [extractor(&[], &["echo"]), python];
+        let files: Vec<String> = ["a.py", "README.md", "b.ex", "c.py"].iter().map(|f| f.to_string()).collect();
+
+        let groups = group_by_extractor(&extractors, &files);
+        let groups: Vec<(&str, &[String])> = groups.iter().map(|(e, files)| (e.name.as_str(), files.as_slice())).collect();
+        assert_eq!(groups, [("test", &files[2..3]), ("python", &[files[0].clone(), files[3].clone()][..])]);
+        assert!(group_by_extractor(&extractors, &files[1..2]).is_empty());
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        let args: Vec<String> = ["ex_ast", "--env", "{mix_env}", "--output", "{output}", "{files}"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let files = vec!["lib/a.ex".to_string(), "lib/b.ex".to_string()];
+        assert_eq!(
+            expand(&args, "test", &files, Path::new("/tmp/out.json")),
+            ["ex_ast", "--env", "test", "--output", "/tmp/out.json", "lib/a.ex", "lib/b.ex"]
+        );
+    }
+
+    #[test]
+    fn test_run_extractor_reads_stdout() {
+        // Files are appended when the command has no {files} argument
+        let output = run_extractor(&extractor(&[], &["echo"]), "dev", &["a.ex".to_string()]).unwrap();
+        assert_eq!(fs::read_to_string(output.path()).unwrap(), "a.ex\n");
+    }
+
+    #[test]
+    fn test_run_extractor_writes_output_file() {
+        let command = ["sh", "-c", "echo \"$MIX_ENV\" > \"$0\"", "{output}"];
+        let output = run_extractor(&extractor(&[], &command), "test", &[]).unwrap();
+        assert_eq!(fs::read_to_string(output.path()).unwrap(), "test\n");
+
+        let output = run_extractor(&extractor(&[], &["true"]), "dev", &[]).unwrap();
+        assert_eq!(fs::read_to_string(output.path()).unwrap(), EMPTY_GRAPH);
+    }
+
+    #[test]
+    fn test_run_extractor_failures() {
+        let err = run_extractor(&extractor(&["false"], &["echo"]), "dev", &[]).unwrap_err();
+        assert!(err.to_string().starts_with("The prepare of extractor 'test' (false) failed"), "{}", err);
+
+        let err = run_extractor(&extractor(&[], &["code_search_missing_extractor"]), "dev", &[]).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to run the extractor of extractor 'test'"), "{}", err);
+    }
+}
@@ -0,0 +1,666 @@
+//! In-memory graphs and whole-graph algorithms.
+//!
+//! Commands that need whole-graph algorithms (which Datalog expresses poorly)
+//! fetch edges from the database and build a [`CallGraph`] (directed, for
+//! centrality) or a [`WeightedGraph`] (undirected, for community detection).
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use petgraph::Direction;
+use petgraph::graph::{DiGraph, NodeIndex};
+
+/// A directed graph with deduplicated nodes and edges
+#[derive(Debug)]
+pub struct CallGraph<N> {
+    graph: DiGraph<N, ()>,
+    index: HashMap<N, NodeIndex>,
+}
+
+impl<N: Clone + Eq + Hash> CallGraph<N> {
+    /// Build a graph from (from, to) pairs. Duplicate edges are collapsed.
+    pub fn from_edges(edges: impl IntoIterator<Item = (N, N)>) -> Self {
+        let mut graph = Self {
+            graph: DiGraph::new(),
+            index: HashMap::new(),
+        };
+        for (from, to) in edges {
+            let a = graph.node(from);
+            let b = graph.node(to);
+            graph.graph.update_edge(a, b, ());
+        }
+        graph
+    }
+
+    fn node(&mut self, label: N) -> NodeIndex {
+        if let Some(&idx) = self.index.get(&label) {
+            return idx;
+        }
+        let idx = self.graph.add_node(label.clone());
+        self.index.insert(label, idx);
+        idx
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    /// Node labels in index order, matching the score vectors returned below
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.graph.node_indices().map(|i| &self.graph[i])
+    }
+
+    pub fn in_degree(&self, i: usize) -> usize {
+        self.graph
+            .neighbors_directed(NodeIndex::new(i), Direction::Incoming)
+            .count()
+    }
+
+    pub fn out_degree(&self, i: usize) -> usize {
+        self.graph
+            .neighbors_directed(NodeIndex::new(i), Direction::Outgoing)
+            .count()
+    }
+
+    /// Degree centrality: (in + out) / (n - 1)
+    pub fn degree_centrality(&self) -> Vec<f64> {
+        let n = self.node_count();
+        let denom = n.saturating_sub(1).max(1) as f64;
+        (0..n)
+            .map(|i| (self.in_degree(i) + self.out_degree(i)) as f64 / denom)
+            .collect()
+    }
+
+    /// PageRank by power iteration.
+    ///
+    /// Rank of nodes without outgoing edges is spread evenly over all nodes.
+    /// Iteration stops early once the total change drops below `1e-9`.
+    pub fn pagerank(&self, damping: f64, max_iterations: usize) -> Vec<f64> {
+        let n = self.node_count();
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let base = (1.0 - damping) / n as f64;
+        let out_degrees: Vec<usize> = (0..n).map(|i| self.out_degree(i)).collect();
+        let mut ranks = vec![1.0 / n as f64; n];
+
+        for _ in 0..max_iterations {
+            let dangling: f64 = (0..n)
+                .filter(|&i| out_degrees[i] == 0)
+                .map(|i| ranks[i])
+                .sum();
+            let mut next = vec![base + damping * dangling / n as f64; n];
+
+            for edge in self.graph.raw_edges() {
+                let from = edge.source().index();
+                next[edge.target().index()] += damping * ranks[from] / out_degrees[from] as f64;
+            }
+
+            let delta: f64 = next.iter().zip(&ranks).map(|(a, b)| (a - b).abs()).sum();
+            ranks = next;
+            if delta < 1e-9 {
+                break;
+            }
+        }
+
+        ranks
+    }
+
+    /// Betweenness centrality (Brandes' algorithm, unweighted, directed).
+    ///
+    /// Scores are normalized by (n - 1)(n - 2), the number of ordered pairs
+    /// a node could lie between.
+    pub fn betweenness(&self) -> Vec<f64> {
+        let n = self.node_count();
+        let mut centrality = self.brandes(0..n);
+        normalize_betweenness(&mut centrality, n, 1.0);
+        centrality
+    }
+
+    /// Betweenness centrality estimated from `samples` source nodes.
+    ///
+    /// Dependencies are accumulated from a pseudo-random sample of sources
+    /// (fixed seed, so results are reproducible) and scaled by n / samples.
+    /// With as many samples as nodes this is the exact [`Self::betweenness`].
+    pub fn betweenness_sampled(&self, samples: usize) -> Vec<f64> {
+        let n = self.node_count();
+        if samples >= n {
+            return self.betweenness();
+        }
+
+        let mut sources: Vec<usize> = (0..n).collect();
+        let mut rng = SplitMix64(BETWEENNESS_SEED);
+        for i in 0..samples {
+            let j = i + (rng.next() % (n - i) as u64) as usize;
+            sources.swap(i, j);
+        }
+
+        let mut centrality = self.brandes(sources[..samples].iter().copied());
+        normalize_betweenness(&mut centrality, n, n as f64 / samples.max(1) as f64);
+        centrality
+    }
+
+    /// Unnormalized dependency sums of Brandes' algorithm over some sources
+    fn brandes(&self, sources: impl Iterator<Item = usize>) -> Vec<f64> {
+        let n = self.node_count();
+        let mut centrality = vec![0.0; n];
+
+        for s in sources {
+            let mut stack = Vec::with_capacity(n);
+            let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+            let mut sigma = vec![0.0_f64; n];
+            let mut distance = vec![-1_i64; n];
+            sigma[s] = 1.0;
+            distance[s] = 0;
+
+            let mut queue = VecDeque::from([s]);
+            while let Some(v) = queue.pop_front() {
+                stack.push(v);
+                for w in self
+                    .graph
+                    .neighbors_directed(NodeIndex::new(v), Direction::Outgoing)
+                {
+                    let w = w.index();
+                    if distance[w] < 0 {
+                        distance[w] = distance[v] + 1;
+                        queue.push_back(w);
+                    }
+                    if distance[w] == distance[v] + 1 {
+                        sigma[w] += sigma[v];
+                        predecessors[w].push(v);
+                    }
+                }
+            }
+
+            let mut delta = vec![0.0_f64; n];
+            while let Some(w) = stack.pop() {
+                for &v in &predecessors[w] {
+                    delta[v] += sigma[v] / sigma[w] * (1.0 + delta[w]);
+                }
+                if w != s {
+                    centrality[w] += delta[w];
+                }
+            }
+        }
+
+        centrality
+    }
+
+    /// Reach: share of the other nodes reachable through outgoing edges.
+    ///
+    /// One breadth-first search per node, O(n · m).
+    pub fn reach(&self) -> Vec<f64> {
+        let n = self.node_count();
+        let denom = n.saturating_sub(1).max(1) as f64;
+        let mut seen = vec![usize::MAX; n];
+
+        (0..n)
+            .map(|s| {
+                let mut reached = 0;
+                let mut queue = VecDeque::from([s]);
+                seen[s] = s;
+                while let Some(v) = queue.pop_front() {
+                    for w in self
+                        .graph
+                        .neighbors_directed(NodeIndex::new(v), Direction::Outgoing)
+                    {
+                        let w = w.index();
+                        if seen[w] != s {
+                            seen[w] = s;
+                            reached += 1;
+                            queue.push_back(w);
+                        }
+                    }
+                }
+                reached as f64 / denom
+            })
+            .collect()
+    }
+
+    /// Reach estimated with one HyperLogLog sketch per node.
+    ///
+    /// Each sketch starts with its own node and absorbs the sketches of its
+    /// callees until none changes, so the cost is O(m · diameter) register
+    /// merges rather than a search per node. Estimates are within about 10%.
+    pub fn reach_approximate(&self) -> Vec<f64> {
+        let n = self.node_count();
+        let denom = n.saturating_sub(1).max(1) as f64;
+        let mut sketches: Vec<HyperLogLog> = (0..n).map(HyperLogLog::of).collect();
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for edge in self.graph.raw_edges() {
+                let (from, to) = (edge.source().index(), edge.target().index());
+                if from != to {
+                    let callee = sketches[to].clone();
+                    changed |= sketches[from].merge(&callee);
+                }
+            }
+        }
+
+        sketches
+            .iter()
+            .map(|sketch| ((sketch.estimate().round() - 1.0).max(0.0) / denom).min(1.0))
+            .collect()
+    }
+}
+
+/// Seed of the source sample of [`CallGraph::betweenness_sampled`]
+const BETWEENNESS_SEED: u64 = 0x5eed;
+
+fn normalize_betweenness(centrality: &mut [f64], n: usize, scale: f64) {
+    let pairs = if n > 2 { ((n - 1) * (n - 2)) as f64 } else { 1.0 };
+    for c in centrality {
+        *c *= scale / pairs;
+    }
+}
+
+/// Small deterministic pseudo-random generator (SplitMix64), also used to
+/// hash node indexes into sketches
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Register index bits of [`HyperLogLog`]: 2^7 registers, ~9% standard error
+const HLL_PRECISION: u32 = 7;
+const HLL_REGISTERS: usize = 1 << HLL_PRECISION;
+
+/// A HyperLogLog sketch estimating the number of distinct node indexes added
+#[derive(Debug, Clone)]
+struct HyperLogLog {
+    registers: [u8; HLL_REGISTERS],
+}
+
+impl HyperLogLog {
+    /// A sketch holding a single node
+    fn of(node: usize) -> Self {
+        let mut sketch = Self { registers: [0; HLL_REGISTERS] };
+        let hash = SplitMix64(node as u64).next();
+        let register = (hash >> (64 - HLL_PRECISION)) as usize;
+        let rank = ((hash << HLL_PRECISION) | (1 << (HLL_PRECISION - 1))).leading_zeros() + 1;
+        sketch.registers[register] = rank as u8;
+        sketch
+    }
+
+    /// Union with another sketch, returning whether any register grew
+    fn merge(&mut self, other: &Self) -> bool {
+        let mut changed = false;
+        for (mine, theirs) in self.registers.iter_mut().zip(&other.registers) {
+            if *theirs > *mine {
+                *mine = *theirs;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    fn estimate(&self) -> f64 {
+        let m = HLL_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self.registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+        let raw = alpha * m * m / sum;
+
+        // Linear counting is more accurate while many registers are empty
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+}
+
+/// An undirected weighted graph over nodes `0..n`.
+///
+/// Stored as a symmetric adjacency matrix in sparse form. A self-loop of weight
+/// `w` is stored as `2w` on the diagonal so that a node's degree is the sum of its row.
+#[derive(Debug, Clone)]
+pub struct WeightedGraph {
+    adjacency: Vec<BTreeMap<usize, f64>>,
+}
+
+impl WeightedGraph {
+    /// Build a graph from (a, b, weight) edges. Parallel edges are summed,
+    /// so directed calls in both directions merge into one undirected edge.
+    pub fn new(node_count: usize, edges: impl IntoIterator<Item = (usize, usize, f64)>) -> Self {
+        let mut adjacency = vec![BTreeMap::new(); node_count];
+        for (a, b, w) in edges {
+            if a == b {
+                *adjacency[a].entry(a).or_insert(0.0) += 2.0 * w;
+            } else {
+                *adjacency[a].entry(b).or_insert(0.0) += w;
+                *adjacency[b].entry(a).or_insert(0.0) += w;
+            }
+        }
+        Self { adjacency }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    fn degree(&self, i: usize) -> f64 {
+        self.adjacency[i].values().sum()
+    }
+
+    fn total_degree(&self) -> f64 {
+        (0..self.node_count()).map(|i| self.degree(i)).sum()
+    }
+
+    /// Newman modularity of a partition (community id per node)
+    pub fn modularity(&self, communities: &[usize]) -> f64 {
+        let m2 = self.total_degree();
+        if m2 == 0.0 {
+            return 0.0;
+        }
+
+        let mut internal: HashMap<usize, f64> = HashMap::new();
+        let mut totals: HashMap<usize, f64> = HashMap::new();
+        for (i, row) in self.adjacency.iter().enumerate() {
+            *totals.entry(communities[i]).or_default() += self.degree(i);
+            for (&j, &w) in row {
+                if communities[i] == communities[j] {
+                    *internal.entry(communities[i]).or_default() += w;
+                }
+            }
+        }
+
+        totals
+            .iter()
+            .map(|(c, tot)| internal.get(c).copied().unwrap_or(0.0) / m2 - (tot / m2).powi(2))
+            .sum()
+    }
+
+    /// Louvain community detection.
+    ///
+    /// Alternates greedy local modularity optimization with aggregation of
+    /// communities into single nodes until no node moves. Nodes are visited in
+    /// index order and ties go to the lowest community id, so results are deterministic.
+    pub fn louvain(&self) -> Vec<usize> {
+        let mut membership: Vec<usize> = (0..self.node_count()).collect();
+        let mut level = self.clone();
+
+        loop {
+            let (communities, moved) = level.local_moves();
+            if !moved {
+                break;
+            }
+
+            let (renumbered, count) = renumber(&communities);
+            for c in &mut membership {
+                *c = renumbered[*c];
+            }
+            level = level.aggregate(&renumbered, count);
+        }
+
+        renumber(&membership).0
+    }
+
+    /// One Louvain phase: move single nodes between communities while modularity improves
+    fn local_moves(&self) -> (Vec<usize>, bool) {
+        let n = self.node_count();
+        let m2 = self.total_degree();
+        let mut communities: Vec<usize> = (0..n).collect();
+        if m2 == 0.0 {
+            return (communities, false);
+        }
+
+        let degrees: Vec<f64> = (0..n).map(|i| self.degree(i)).collect();
+        let mut totals = degrees.clone();
+        let mut moved_any = false;
+
+        loop {
+            let mut moved = false;
+            for i in 0..n {
+                let current = communities[i];
+                let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+                for (&j, &w) in &self.adjacency[i] {
+                    if j != i {
+                        *links.entry(communities[j]).or_insert(0.0) += w;
+                    }
+                }
+
+                totals[current] -= degrees[i];
+                let gain = |c: usize, k_in: f64| k_in - totals[c] * degrees[i] / m2;
+
+                let mut best = current;
+                let mut best_gain = gain(current, links.get(&current).copied().unwrap_or(0.0));
+                for (&c, &k_in) in &links {
+                    let g = gain(c, k_in);
+                    if g > best_gain + 1e-12 {
+                        best = c;
+                        best_gain = g;
+                    }
+                }
+
+                totals[best] += degrees[i];
+                if best != current {
+                    communities[i] = best;
+                    moved = true;
+                    moved_any = true;
+                }
+            }
+            if !moved {
+                break;
+            }
+        }
+
+        (communities, moved_any)
+    }
+
+    /// Collapse each community into a single node
+    fn aggregate(&self, communities: &[usize], count: usize) -> Self {
+        let mut adjacency = vec![BTreeMap::new(); count];
+        for (i, row) in self.adjacency.iter().enumerate() {
+            for (&j, &w) in row {
+                *adjacency[communities[i]].entry(communities[j]).or_insert(0.0) += w;
+            }
+        }
+        Self { adjacency }
+    }
+
+    /// Label propagation community detection.
+    ///
+    /// Each node repeatedly adopts the label with the highest total edge weight
+    /// among its neighbours until labels stop changing. A node keeps its label
+    /// when it is among the best; other ties go to the highest label.
+    pub fn label_propagation(&self, max_iterations: usize) -> Vec<usize> {
+        let n = self.node_count();
+        let mut labels: Vec<usize> = (0..n).collect();
+
+        for _ in 0..max_iterations {
+            let mut changed = false;
+            for i in 0..n {
+                let mut weights: BTreeMap<usize, f64> = BTreeMap::new();
+                for (&j, &w) in &self.adjacency[i] {
+                    if j != i {
+                        *weights.entry(labels[j]).or_insert(0.0) += w;
+                    }
+                }
+
+                let Some(max) = weights.values().copied().reduce(f64::max) else {
+                    continue;
+                };
+                if weights.get(&labels[i]).is_some_and(|&w| w >= max) {
+                    continue;
+                }
+                if let Some((&label, _)) = weights.iter().rev().find(|(_, w)| **w >= max) {
+                    labels[i] = label;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        renumber(&labels).0
+    }
+}
+
+/// Map arbitrary community ids to `0..count` in order of first appearance
+fn renumber(communities: &[usize]) -> (Vec<usize>, usize) {
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    let renumbered = communities
+        .iter()
+        .map(|c| {
+            let next = ids.len();
+            *ids.entry(*c).or_insert(next)
+        })
+        .collect();
+    (renumbered, ids.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&'static str, &'static str)]) -> CallGraph<&'static str> {
+        CallGraph::from_edges(edges.iter().copied())
+    }
+
+    fn score(g: &CallGraph<&'static str>, scores: &[f64], label: &str) -> f64 {
+        let i = g.nodes().position(|n| *n == label).unwrap();
+        scores[i]
+    }
+
+    #[test]
+    fn test_from_edges_deduplicates() {
+        let g = graph(&[("a", "b"), ("a", "b"), ("b", "c")]);
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_degree_centrality() {
+        let g = graph(&[("a", "b"), ("c", "b")]);
+        let scores = g.degree_centrality();
+        assert_eq!(score(&g, &scores, "b"), 1.0);
+        assert_eq!(score(&g, &scores, "a"), 0.5);
+    }
+
+    #[test]
+    fn test_pagerank_sums_to_one() {
+        let g = graph(&[("a", "b"), ("b", "c"), ("c", "a"), ("d", "c")]);
+        let scores = g.pagerank(0.85, 100);
+        let total: f64 = scores.iter().sum();
+        assert!((total - 1.0).abs() < 1e-6);
+        // c receives links from both b and d
+        assert!(score(&g, &scores, "c") > score(&g, &scores, "b"));
+    }
+
+    #[test]
+    fn test_pagerank_empty_graph() {
+        let g = graph(&[]);
+        assert!(g.pagerank(0.85, 100).is_empty());
+    }
+
+    #[test]
+    fn test_betweenness_chain() {
+        // a -> b -> c: b lies on the only a->c path
+        let g = graph(&[("a", "b"), ("b", "c")]);
+        let scores = g.betweenness();
+        assert_eq!(score(&g, &scores, "b"), 0.5);
+        assert_eq!(score(&g, &scores, "a"), 0.0);
+        assert_eq!(score(&g, &scores, "c"), 0.0);
+    }
+
+    #[test]
+    fn test_betweenness_sampled() {
+        let g = graph(&[("a", "b"), ("b", "c"), ("c", "d"), ("a", "d")]);
+        assert_eq!(g.betweenness_sampled(10), g.betweenness());
+        assert_eq!(g.betweenness_sampled(2), g.betweenness_sampled(2));
+        assert!(g.betweenness_sampled(0).iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_reach() {
+        // a -> b -> c -> b, d isolated from the cycle
+        let g = graph(&[("a", "b"), ("b", "c"), ("c", "b"), ("d", "a")]);
+        let scores = g.reach();
+        assert_eq!(score(&g, &scores, "d"), 1.0);
+        assert_eq!(score(&g, &scores, "a"), 2.0 / 3.0);
+        assert_eq!(score(&g, &scores, "b"), 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_reach_approximate() {
+        // A chain of 300 nodes: node i reaches the 299 - i after it
+        let edges: Vec<(usize, usize)> = (0..299).map(|i| (i, i + 1)).collect();
+        let g = CallGraph::from_edges(edges);
+        let exact = g.reach();
+        let approximate = g.reach_approximate();
+        assert_eq!(approximate[299], 0.0);
+        for i in [0, 100, 200, 290] {
+            assert!((approximate[i] - exact[i]).abs() <= 0.2 * exact[i] + 0.01, "reach of {i}");
+        }
+    }
+
+    /// Two triangles (0-1-2 and 3-4-5) joined by a single 2-3 bridge
+    fn two_triangles() -> WeightedGraph {
+        WeightedGraph::new(
+            6,
+            [
+                (0, 1, 1.0),
+                (1, 2, 1.0),
+                (2, 0, 1.0),
+                (3, 4, 1.0),
+                (4, 5, 1.0),
+                (5, 3, 1.0),
+                (2, 3, 1.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_louvain_two_triangles() {
+        let communities = two_triangles().louvain();
+        assert_eq!(communities, vec![0, 0, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_label_propagation_two_triangles() {
+        let communities = two_triangles().label_propagation(100);
+        assert_eq!(communities[0], communities[1]);
+        assert_eq!(communities[3], communities[4]);
+        assert_ne!(communities[0], communities[5]);
+    }
+
+    #[test]
+    fn test_modularity() {
+        let g = two_triangles();
+        let split = g.modularity(&[0, 0, 0, 1, 1, 1]);
+        let single = g.modularity(&[0; 6]);
+        assert!((split - 5.0 / 14.0).abs() < 1e-9);
+        assert!(single.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_louvain_isolated_nodes() {
+        let g = WeightedGraph::new(3, []);
+        assert_eq!(g.louvain(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_betweenness_splits_between_shortest_paths() {
+        // Two shortest paths from a to d, through b and through c
+        let g = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")]);
+        let scores = g.betweenness();
+        assert_eq!(score(&g, &scores, "b"), score(&g, &scores, "c"));
+        assert!(score(&g, &scores, "b") > 0.0);
+    }
+}
@@ -0,0 +1,32 @@
+//! Call graph analysis for Elixir and other languages, backed by CozoDB.
+//!
+//! The `code_search` binary is a thin layer over this library. Other Rust
+//! tools embed the engine through [`api`], whose functions take a database
+//! and a typed request and return the same results the commands print.
+//! The remaining modules back the command line and are not a stable API.
+
+pub mod api;
+mod baseline;
+mod blame;
+#[doc(hidden)]
+pub mod cache;
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod commands;
+mod config;
+#[doc(hidden)]
+pub mod daemon;
+mod dedup;
+mod embed;
+mod extractors;
+mod graph;
+#[doc(hidden)]
+pub mod output;
+mod rules;
+mod sort;
+mod suggest;
+mod targets;
+mod utils;
+#[macro_use]
+mod test_macros;
@@ -1,27 +1,40 @@
 use clap::Parser;
 
-mod cli;
-mod commands;
-mod dedup;
-pub mod output;
-mod utils;
-#[macro_use]
-mod test_macros;
-use cli::Args;
-use commands::CommandRunner;
+use code_search::cli::{self, Args};
+use code_search::commands::Command;
+use code_search::daemon;
 use db::open_db;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
-    let db_path = cli::resolve_db_path(args.db);
+    let mut args = Args::parse();
+    let db_path = cli::resolve_db_path(args.db.clone());
 
     // Create .code_search directory if using default path
     if db_path.as_path() == std::path::Path::new(".code_search/cozo.sqlite") {
         std::fs::create_dir_all(".code_search").ok();
     }
 
-    let db = open_db(&db_path)?;
-    let output = args.command.run(&db, args.format)?;
-    println!("{}", output);
+    // vacuum compacts the database file itself, daemon listens next to it
+    match &mut args.command {
+        Command::Vacuum(vacuum) => vacuum.db_path = Some(db_path.clone()),
+        Command::Daemon(daemon) => daemon.db_path = Some(db_path.clone()),
+        _ => {}
+    }
+
+    // A running daemon answers read-only queries from its warm caches
+    let forwarded = if args.command.batchable() {
+        daemon::forward(&db_path, &std::env::args().skip(1).collect::<Vec<_>>())
+    } else {
+        None
+    };
+    let outcome = match forwarded {
+        Some(outcome) => outcome?,
+        None => cli::run(args, &open_db(&db_path)?)?,
+    };
+
+    println!("{}", outcome.output);
+    if outcome.failed {
+        std::process::exit(1);
+    }
     Ok(())
 }
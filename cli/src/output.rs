@@ -1,10 +1,14 @@
 //! Output formatting for command results.
 //!
-//! Supports multiple output formats: table (human-readable), JSON, and toon.
+//! Supports multiple output formats: table (human-readable), JSON, toon, CSV,
+//! and GitHub Actions workflow annotations. Column selection (`--fields`) and CSV
+//! go through the shared projection in [`columns`].
+
+pub mod columns;
 
 use clap::ValueEnum;
 use serde::Serialize;
-use db::types::{ModuleGroupResult, ModuleCollectionResult};
+use db::types::{CallGroupsResult, ModuleGroupResult, ModuleCollectionResult};
 
 /// Output format for command results
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -16,6 +20,112 @@ pub enum OutputFormat {
     Json,
     /// Token-efficient toon format
     Toon,
+    /// GitHub Actions workflow commands (`::warning file=...,line=...::message`)
+    Github,
+    /// Comma-separated values, one row per entry
+    Csv,
+}
+
+/// Severity of a GitHub annotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationLevel {
+    Notice,
+    Warning,
+    Error,
+}
+
+impl AnnotationLevel {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AnnotationLevel::Notice => "notice",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Error => "error",
+        }
+    }
+}
+
+/// A finding rendered as a GitHub Actions workflow command.
+///
+/// GitHub (and reviewdog's `github-actions` format) turns these into inline
+/// annotations on the PR diff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub level: AnnotationLevel,
+    pub file: Option<String>,
+    pub line: Option<i64>,
+    pub end_line: Option<i64>,
+    pub title: String,
+    pub message: String,
+}
+
+impl Annotation {
+    /// Warning anchored at a file and line
+    pub fn warning(file: &str, line: i64, title: &str, message: String) -> Self {
+        Self::at(AnnotationLevel::Warning, file, line, title, message)
+    }
+
+    /// Informational notice anchored at a file and line
+    pub fn notice(file: &str, line: i64, title: &str, message: String) -> Self {
+        Self::at(AnnotationLevel::Notice, file, line, title, message)
+    }
+
+    fn at(level: AnnotationLevel, file: &str, line: i64, title: &str, message: String) -> Self {
+        Self {
+            level,
+            file: (!file.is_empty()).then(|| file.to_string()),
+            line: (line > 0).then_some(line),
+            end_line: None,
+            title: title.to_string(),
+            message,
+        }
+    }
+
+    /// Extend the annotated range to `end_line`
+    pub fn with_end_line(mut self, end_line: i64) -> Self {
+        if self.line.is_some_and(|line| end_line > line) {
+            self.end_line = Some(end_line);
+        }
+        self
+    }
+
+    /// Render as a single workflow command line
+    pub fn render(&self) -> String {
+        let mut properties = Vec::new();
+        if let Some(file) = &self.file {
+            properties.push(format!("file={}", escape_property(file)));
+            if let Some(line) = self.line {
+                properties.push(format!("line={}", line));
+            }
+            if let Some(end_line) = self.end_line {
+                properties.push(format!("endLine={}", end_line));
+            }
+        }
+        properties.push(format!("title={}", escape_property(&self.title)));
+
+        format!(
+            "::{} {}::{}",
+            self.level.as_str(),
+            properties.join(","),
+            escape_data(&self.message)
+        )
+    }
+}
+
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Render annotations, one workflow command per line
+pub fn format_annotations(annotations: &[Annotation]) -> String {
+    annotations
+        .iter()
+        .map(Annotation::render)
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// Trait for types that can be formatted for output
@@ -23,6 +133,22 @@ pub trait Outputable: Serialize {
     /// Format as a human-readable table
     fn to_table(&self) -> String;
 
+    /// Findings as GitHub annotations.
+    ///
+    /// `None` for results that have no file locations; those fall back to the
+    /// table format under `--format github`.
+    fn to_annotations(&self) -> Option<Vec<Annotation>> {
+        None
+    }
+
+    /// Custom CSV rendering.
+    ///
+    /// `None` for results that flatten naturally into one row per entry; those
+    /// go through the shared projection in [`columns`].
+    fn to_csv(&self) -> Option<String> {
+        None
+    }
+
     /// Format according to the specified output format
     fn format(&self, format: OutputFormat) -> String {
         match format {
@@ -32,6 +158,14 @@ pub trait Outputable: Serialize {
                 let json_value = serde_json::to_value(self).unwrap_or_default();
                 toon::encode(&json_value, None)
             }
+            OutputFormat::Csv => self.to_csv().unwrap_or_else(|| {
+                let json_value = serde_json::to_value(self).unwrap_or_default();
+                columns::render(&json_value, &[], OutputFormat::Csv).unwrap_or_default()
+            }),
+            OutputFormat::Github => match self.to_annotations() {
+                Some(annotations) => format_annotations(&annotations),
+                None => self.to_table(),
+            },
         }
     }
 }
@@ -112,6 +246,47 @@ pub trait TableFormatter {
     fn blank_before_module(&self) -> bool {
         false
     }
+
+    /// GitHub annotation for an entry
+    ///
+    /// Only consulted when [`annotates`](Self::annotates) returns true.
+    fn format_annotation(
+        &self,
+        _entry: &Self::Entry,
+        _module_name: &str,
+        _module_file: &str,
+    ) -> Option<Annotation> {
+        None
+    }
+
+    /// Whether entries produce GitHub annotations
+    ///
+    /// When false, `--format github` falls back to the table format.
+    fn annotates(&self) -> bool {
+        false
+    }
+}
+
+/// Collect the annotations of module-grouped results
+fn module_annotations<F>(formatter: &F, items: &[db::types::ModuleGroup<F::Entry>]) -> Option<Vec<Annotation>>
+where
+    F: TableFormatter,
+{
+    if !formatter.annotates() {
+        return None;
+    }
+
+    Some(
+        items
+            .iter()
+            .flat_map(|module| {
+                module
+                    .entries
+                    .iter()
+                    .filter_map(|entry| formatter.format_annotation(entry, &module.name, &module.file))
+            })
+            .collect(),
+    )
 }
 
 /// Format module-grouped results as a table.
@@ -172,6 +347,10 @@ where
     fn to_table(&self) -> String {
         format_module_table(self, &self.items, self.total_items)
     }
+
+    fn to_annotations(&self) -> Option<Vec<Annotation>> {
+        module_annotations(self, &self.items)
+    }
 }
 
 /// Default implementation of Outputable for ModuleCollectionResult using TableFormatter
@@ -183,4 +362,88 @@ where
     fn to_table(&self) -> String {
         format_module_table(self, &self.items, self.total_items)
     }
+
+    fn to_annotations(&self) -> Option<Vec<Annotation>> {
+        module_annotations(self, &self.items)
+    }
+}
+
+/// Call counts per group, shared by calls-to and calls-from with `--group-by`
+impl Outputable for CallGroupsResult {
+    fn to_table(&self) -> String {
+        let target = match self.function_pattern.as_deref() {
+            Some(function) if !function.is_empty() => format!("{}.{}", self.module_pattern, function),
+            _ => self.module_pattern.clone(),
+        };
+
+        let mut lines = vec![format!("Calls {}: {} (by {})", self.direction, target, self.group_by)];
+        lines.push(String::new());
+
+        if self.items.is_empty() {
+            lines.push("No calls found.".to_string());
+            return lines.join("\n");
+        }
+
+        lines.push(format!(
+            "Found {} call(s) in {} group(s):",
+            self.total_calls, self.total_items
+        ));
+        lines.push(String::new());
+
+        let group_width = self.items.iter().map(|g| g.group.len()).max().unwrap_or(0);
+        let count_width = self.items.iter().map(|g| g.calls.to_string().len()).max().unwrap_or(0);
+        for item in &self.items {
+            lines.push(format!(
+                "{:<group_width$}  {:>count_width$}",
+                item.group,
+                item.calls,
+                group_width = group_width,
+                count_width = count_width,
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotation_render() {
+        let annotation = Annotation::warning("lib/a.ex", 5, "Large function", "A.f/1 is 60 lines long".to_string())
+            .with_end_line(64);
+        assert_eq!(
+            annotation.render(),
+            "::warning file=lib/a.ex,line=5,endLine=64,title=Large function::A.f/1 is 60 lines long"
+        );
+    }
+
+    #[test]
+    fn test_annotation_render_escapes() {
+        let annotation = Annotation::warning("", 0, "a: b, c", "100%\ndone".to_string());
+        assert_eq!(annotation.render(), "::warning title=a%3A b%2C c::100%25%0Adone");
+    }
+
+    #[test]
+    fn test_call_groups_table() {
+        let result = CallGroupsResult {
+            module_pattern: "MyApp.Repo".to_string(),
+            function_pattern: Some(String::new()),
+            direction: "to".to_string(),
+            group_by: "caller module".to_string(),
+            total_calls: 4,
+            total_items: 2,
+            items: vec![
+                db::types::CallGroupCount { group: "MyApp.Accounts".to_string(), calls: 3 },
+                db::types::CallGroupCount { group: "MyApp.Service".to_string(), calls: 1 },
+            ],
+        };
+
+        assert_eq!(
+            result.to_table(),
+            "Calls to: MyApp.Repo (by caller module)\n\nFound 4 call(s) in 2 group(s):\n\nMyApp.Accounts  3\nMyApp.Service   1"
+        );
+    }
 }
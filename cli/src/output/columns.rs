@@ -0,0 +1,358 @@
+//! Column projection for `--fields` and CSV output.
+//!
+//! Any result is flattened from its JSON form into rows of named cells: every
+//! array of objects is expanded into one row per element, and each row inherits
+//! the scalar fields of the objects that contain it. Grouping objects expose
+//! their `name` as `module`, and entries named by `name` also answer to
+//! `function`, so `module,function,arity,file,line` works across list commands.
+//! Nested objects are flattened with dotted names (`caller.module`). An
+//! inherited field that an entry also has is kept as `parent.<field>`.
+
+use serde_json::Value;
+
+use super::OutputFormat;
+
+/// A flattened result row: (column, value) pairs in discovery order
+pub type Row = Vec<(String, Value)>;
+
+/// A row whose cells are marked with whether they belong to an entry, as
+/// opposed to the envelope of the result (`total_items`, the patterns queried)
+type MarkedRow = Vec<(String, Value, bool)>;
+
+/// Flatten a result into rows
+pub fn flatten(value: &Value) -> Vec<Row> {
+    unmark(flatten_marked(value))
+}
+
+fn flatten_marked(value: &Value) -> Vec<MarkedRow> {
+    let mut rows = Vec::new();
+    walk(value, &MarkedRow::new(), false, &mut rows);
+    rows
+}
+
+fn unmark(rows: Vec<MarkedRow>) -> Vec<Row> {
+    rows.into_iter()
+        .map(|row| row.into_iter().map(|(key, value, _)| (key, value)).collect())
+        .collect()
+}
+
+/// Flatten an object or array; the fields of objects that are array elements,
+/// or that have no entries of their own, belong to entries.
+fn walk(value: &Value, inherited: &MarkedRow, in_array: bool, rows: &mut Vec<MarkedRow>) {
+    let map = match value {
+        Value::Object(map) => map,
+        Value::Array(items) => {
+            for item in items {
+                walk(item, inherited, true, rows);
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    let mut own = Row::new();
+    let mut children = Vec::new();
+    for (key, v) in map {
+        match v {
+            // Empty arrays are entry lists without entries
+            Value::Array(items) if items.is_empty() || items.iter().any(Value::is_object) => children.push(v),
+            Value::Object(_) => flatten_object(key, v, &mut own),
+            _ => own.push((key.clone(), v.clone())),
+        }
+    }
+
+    if !children.is_empty() {
+        // A group of entries: its name is the module the entries belong to
+        if !own.iter().any(|(k, _)| k == "module") {
+            for (key, _) in own.iter_mut().filter(|(k, _)| k == "name") {
+                *key = "module".to_string();
+            }
+        }
+    } else if !own.iter().any(|(k, _)| k == "function")
+        && inherited.iter().any(|(k, _, _)| k == "module")
+        && let Some((_, name)) = own.iter().find(|(k, _)| k == "name")
+    {
+        own.push(("function".to_string(), name.clone()));
+    }
+
+    let entry = in_array || children.is_empty();
+    let mut row = inherited.clone();
+    for (key, value) in own {
+        if let Some(position) = row.iter().position(|(k, _, _)| *k == key) {
+            let mut renamed = format!("parent.{}", key);
+            while row.iter().any(|(k, _, _)| *k == renamed) {
+                renamed = format!("parent.{}", renamed);
+            }
+            row[position].0 = renamed;
+        }
+        row.push((key, value, entry));
+    }
+
+    if children.is_empty() {
+        rows.push(row);
+    } else {
+        for child in children {
+            walk(child, &row, false, rows);
+        }
+    }
+}
+
+fn flatten_object(prefix: &str, value: &Value, row: &mut Row) {
+    let Value::Object(map) = value else { return };
+    for (key, v) in map {
+        let name = format!("{}.{}", prefix, key);
+        match v {
+            Value::Object(_) => flatten_object(&name, v, row),
+            _ => row.push((name, v.clone())),
+        }
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(cell).collect::<Vec<_>>().join(" "),
+        other => other.to_string(),
+    }
+}
+
+/// All column names of the rows, in first-seen order
+pub fn columns(rows: &[Row]) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+    for row in rows {
+        for (key, _) in row {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    columns
+}
+
+/// Values of the given columns for each row (empty when a row lacks a column)
+pub fn select(rows: &[Row], fields: &[String]) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| fields.iter().map(|field| value_of(row, field).map(cell).unwrap_or_default()).collect())
+        .collect()
+}
+
+fn value_of<'a>(row: &'a Row, field: &str) -> Option<&'a Value> {
+    row.iter().find(|(k, _)| k == field).map(|(_, v)| v)
+}
+
+/// Render the selected columns of a result.
+///
+/// With no fields, all columns are included. Table and CSV render the cells;
+/// JSON and toon render an array of objects with just the selected fields,
+/// keeping their JSON values (`null` when a row lacks a field). A field no
+/// row has fails, unless there are no rows to tell from; the error offers
+/// the columns of the entries.
+pub fn render(value: &Value, fields: &[String], format: OutputFormat) -> Result<String, String> {
+    let marked = flatten_marked(value);
+    if !marked.is_empty()
+        && let Some(unknown) = fields
+            .iter()
+            .find(|field| !marked.iter().flatten().any(|(k, _, _)| k == *field))
+    {
+        let mut choices: Vec<&str> = Vec::new();
+        for (key, _, _) in marked.iter().flatten().filter(|(_, _, entry)| *entry) {
+            if !choices.contains(&key.as_str()) {
+                choices.push(key);
+            }
+        }
+        return Err(format!("Unknown field '{}'. Available fields: {}", unknown, choices.join(", ")));
+    }
+    let rows = unmark(marked);
+    let fields = if fields.is_empty() { columns(&rows) } else { fields.to_vec() };
+
+    Ok(match format {
+        OutputFormat::Csv => to_csv(&fields, &select(&rows, &fields)),
+        OutputFormat::Json | OutputFormat::Toon => {
+            let objects: Vec<Value> = rows
+                .iter()
+                .map(|row| {
+                    Value::Object(
+                        fields
+                            .iter()
+                            .map(|field| (field.clone(), value_of(row, field).cloned().unwrap_or(Value::Null)))
+                            .collect(),
+                    )
+                })
+                .collect();
+            let value = Value::Array(objects);
+            match format {
+                OutputFormat::Json => serde_json::to_string_pretty(&value).unwrap_or_default(),
+                _ => toon::encode(&value, None),
+            }
+        }
+        OutputFormat::Table | OutputFormat::Github => to_table(&fields, &select(&rows, &fields)),
+    })
+}
+
+/// Aligned columns with a header line
+pub fn to_table(fields: &[String], cells: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = fields.iter().map(|f| f.chars().count()).collect();
+    for row in cells {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String]| {
+        let padded: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{:<width$}", value, width = width))
+            .collect();
+        padded.join("  ").trim_end().to_string()
+    };
+
+    let mut lines = vec![format_row(fields)];
+    for row in cells {
+        lines.push(format_row(row));
+    }
+    lines.join("\n")
+}
+
+/// RFC 4180 CSV with a header line
+pub fn to_csv(fields: &[String], cells: &[Vec<String>]) -> String {
+    let mut lines = vec![csv_line(fields)];
+    for row in cells {
+        lines.push(csv_line(row));
+    }
+    lines.join("\n")
+}
+
+fn csv_line(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| {
+            if v.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", v.replace('"', "\"\""))
+            } else {
+                v.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn grouped() -> Value {
+        json!({
+            "module_pattern": "*",
+            "total_items": 2,
+            "items": [
+                {
+                    "name": "MyApp.Accounts",
+                    "file": "lib/accounts.ex",
+                    "entries": [
+                        {"name": "get_user", "arity": 1, "line": 10},
+                        {"name": "list_users", "arity": 0, "line": 20}
+                    ]
+                }
+            ]
+        })
+    }
+
+    fn fields(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_flatten_grouped_entries() {
+        let rows = flatten(&grouped());
+        assert_eq!(rows.len(), 2);
+
+        let cells = select(&rows, &fields(&["module", "function", "arity", "file", "line"]));
+        assert_eq!(cells[0], ["MyApp.Accounts", "get_user", "1", "lib/accounts.ex", "10"]);
+        assert_eq!(cells[1], ["MyApp.Accounts", "list_users", "0", "lib/accounts.ex", "20"]);
+    }
+
+    #[test]
+    fn test_flatten_nested_objects() {
+        let value = json!({"calls": [{"caller": {"module": "A", "name": "f"}, "line": 3}]});
+        let cells = select(&flatten(&value), &fields(&["caller.module", "caller.name", "line"]));
+        assert_eq!(cells, [["A", "f", "3"]]);
+    }
+
+    #[test]
+    fn test_select_missing_field_is_empty() {
+        let cells = select(&flatten(&grouped()), &fields(&["function", "nope"]));
+        assert_eq!(cells[0], ["get_user", ""]);
+    }
+
+    #[test]
+    fn test_render_table() {
+        let output = render(&grouped(), &fields(&["function", "line"]), OutputFormat::Table).unwrap();
+        assert_eq!(output, "function    line\nget_user    10\nlist_users  20");
+    }
+
+    #[test]
+    fn test_render_csv_quotes_values() {
+        let value = json!({"entries": [{"name": "a,b", "note": "say \"hi\""}]});
+        let output = render(&value, &fields(&["name", "note"]), OutputFormat::Csv).unwrap();
+        assert_eq!(output, "name,note\n\"a,b\",\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_render_csv_all_columns() {
+        let output = render(&grouped(), &[], OutputFormat::Csv).unwrap();
+        let header = output.lines().next().unwrap();
+        for column in ["module", "function", "arity", "file", "line", "total_items"] {
+            assert!(header.split(',').any(|c| c == column), "missing {column}");
+        }
+    }
+
+    #[test]
+    fn test_render_unknown_field() {
+        let err = render(&grouped(), &fields(&["function", "nope"]), OutputFormat::Csv).unwrap_err();
+        assert_eq!(
+            err,
+            "Unknown field 'nope'. Available fields: file, module, arity, line, name, function"
+        );
+    }
+
+    #[test]
+    fn test_render_empty_result() {
+        let value = json!({"total_items": 0, "items": []});
+        assert_eq!(render(&value, &fields(&["module"]), OutputFormat::Csv).unwrap(), "module");
+    }
+
+    #[test]
+    fn test_render_json() {
+        let output = render(&grouped(), &fields(&["module", "function"]), OutputFormat::Json).unwrap();
+        let parsed: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed[1], json!({"module": "MyApp.Accounts", "function": "list_users"}));
+    }
+
+    #[test]
+    fn test_render_json_keeps_value_types() {
+        let value = json!({"entries": [{"name": "f", "arity": 1, "generated": false, "tags": ["a"], "doc": null}]});
+        let selected = fields(&["name", "arity", "generated", "tags", "doc", "nope"]);
+        let err = render(&value, &selected, OutputFormat::Json).unwrap_err();
+        assert!(err.starts_with("Unknown field 'nope'"));
+
+        let output = render(&value, &selected[..5], OutputFormat::Json).unwrap();
+        let parsed: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed[0], json!({"name": "f", "arity": 1, "generated": false, "tags": ["a"], "doc": null}));
+    }
+
+    #[test]
+    fn test_flatten_keeps_shadowed_parent_fields() {
+        // As in trace: the queried function and the entries share field names
+        let value = json!({
+            "module": "MyApp.Web",
+            "function": "index",
+            "entries": [{"module": "MyApp.Accounts", "function": "get_user", "line": 3}]
+        });
+        let rows = flatten(&value);
+        let cells = select(&rows, &fields(&["parent.module", "parent.function", "module", "function"]));
+        assert_eq!(cells, [["MyApp.Web", "index", "MyApp.Accounts", "get_user"]]);
+    }
+}
@@ -0,0 +1,407 @@
+//! Rules engine for architectural policies.
+//!
+//! Evaluates module-level dependency rules loaded from the project
+//! configuration (see [`crate::config`]) against call edges from the database,
+//! and classifies functions as framework entry points.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+use regex::Regex;
+
+use crate::config::{EntryPointRule, EntryPointsConfig, ForbiddenDependency, LayerConfig};
+
+/// Whether `module` is `namespace` itself or one of its submodules.
+///
+/// `MyApp.Repo` contains `MyApp.Repo` and `MyApp.Repo.Users`, but not `MyApp.RepoHelpers`.
+pub fn in_namespace(module: &str, namespace: &str) -> bool {
+    module == namespace
+        || (module.starts_with(namespace) && module[namespace.len()..].starts_with('.'))
+}
+
+/// Whether a call from `caller_module` to `callee_module` matches a forbidden dependency
+pub fn is_forbidden(rule: &ForbiddenDependency, caller_module: &str, callee_module: &str) -> bool {
+    in_namespace(caller_module, &rule.from) && in_namespace(callee_module, &rule.to)
+}
+
+/// Compiled layer definitions used to classify modules and check call edges.
+#[derive(Debug)]
+pub struct LayerRules {
+    /// Layer names in declaration order
+    names: Vec<String>,
+    /// (namespace, layer index), sorted by namespace length descending so the most specific wins
+    namespaces: Vec<(String, usize)>,
+    /// Allowed target layer indices for each layer
+    allowed: Vec<HashSet<usize>>,
+}
+
+impl LayerRules {
+    /// Build rules from layer configuration.
+    ///
+    /// Fails if layer names are duplicated or `may_depend_on` references an unknown layer.
+    pub fn new(layers: &[LayerConfig]) -> Result<Self, Box<dyn Error>> {
+        if layers.is_empty() {
+            return Err("No layers defined in config".into());
+        }
+
+        let mut index_by_name = HashMap::new();
+        for (idx, layer) in layers.iter().enumerate() {
+            if index_by_name.insert(layer.name.as_str(), idx).is_some() {
+                return Err(format!("Duplicate layer name '{}' in config", layer.name).into());
+            }
+        }
+
+        let mut allowed = Vec::with_capacity(layers.len());
+        for (idx, layer) in layers.iter().enumerate() {
+            let targets = match &layer.may_depend_on {
+                Some(names) => names
+                    .iter()
+                    .map(|name| {
+                        index_by_name.get(name.as_str()).copied().ok_or_else(|| {
+                            format!("Layer '{}' depends on unknown layer '{}'", layer.name, name)
+                        })
+                    })
+                    .collect::<Result<HashSet<_>, _>>()?,
+                None => (idx + 1..layers.len()).collect(),
+            };
+            allowed.push(targets);
+        }
+
+        let mut namespaces: Vec<(String, usize)> = layers
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, layer)| layer.modules.iter().map(move |m| (m.clone(), idx)))
+            .collect();
+        namespaces.sort_by_key(|(namespace, _)| Reverse(namespace.len()));
+
+        Ok(Self {
+            names: layers.iter().map(|l| l.name.clone()).collect(),
+            namespaces,
+            allowed,
+        })
+    }
+
+    /// Layer names in declaration order
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Index of the layer a module belongs to, if any.
+    ///
+    /// A namespace matches the module itself and all of its submodules (see [`in_namespace`]).
+    fn layer_index(&self, module: &str) -> Option<usize> {
+        self.namespaces
+            .iter()
+            .find(|(ns, _)| in_namespace(module, ns))
+            .map(|(_, idx)| *idx)
+    }
+
+    /// Check a call edge between two modules.
+    ///
+    /// Returns the `(caller_layer, callee_layer)` pair when the call violates the
+    /// layering. Calls within a layer and calls involving unlayered modules are allowed.
+    pub fn violation(&self, caller_module: &str, callee_module: &str) -> Option<(&str, &str)> {
+        let from = self.layer_index(caller_module)?;
+        let to = self.layer_index(callee_module)?;
+
+        if from == to || self.allowed[from].contains(&to) {
+            return None;
+        }
+
+        Some((self.names[from].as_str(), self.names[to].as_str()))
+    }
+}
+
+/// Built-in entry point rules as (kind, module regex, functions).
+///
+/// Order matters: the first matching rule names the kind, so the more specific
+/// module-based rules come before the generic OTP callbacks.
+const DEFAULT_ENTRY_POINTS: &[(&str, Option<&str>, &[&str])] = &[
+    ("Phoenix controller", Some(r"Controller$"), &["*/2"]),
+    (
+        "Phoenix LiveView",
+        Some(r"Live\b"),
+        &["mount/3", "handle_params/3", "handle_event/3", "handle_info/2", "render/1"],
+    ),
+    ("Mix task", Some(r"^Mix\.Tasks\."), &["run/1"]),
+    ("Application", Some(r"Application$"), &["start/2"]),
+    ("Supervisor", Some(r"Supervisor$"), &["init/1", "start_link/1"]),
+    ("Oban worker", None, &["perform/1"]),
+    (
+        "GenServer",
+        None,
+        &[
+            "init/1",
+            "handle_call/3",
+            "handle_cast/2",
+            "handle_info/2",
+            "handle_continue/2",
+            "terminate/2",
+            "code_change/3",
+        ],
+    ),
+];
+
+/// A compiled entry point rule
+#[derive(Debug)]
+struct EntryPointMatcher {
+    kind: String,
+    module: Option<Regex>,
+    /// (name, arity); `None` name or arity matches any
+    functions: Vec<(Option<String>, Option<i64>)>,
+}
+
+impl EntryPointMatcher {
+    fn new(kind: &str, module: Option<&str>, functions: &[String]) -> Result<Self, Box<dyn Error>> {
+        let module = module
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| format!("Invalid module pattern in entry point rule '{}': {}", kind, e))?;
+
+        let functions = functions
+            .iter()
+            .map(|f| {
+                let (name, arity) = match f.rsplit_once('/') {
+                    Some((name, arity)) => {
+                        let arity = arity.parse::<i64>().map_err(|_| {
+                            format!("Invalid arity in entry point rule '{}': '{}'", kind, f)
+                        })?;
+                        (name, Some(arity))
+                    }
+                    None => (f.as_str(), None),
+                };
+                let name = (name != "*").then(|| name.to_string());
+                Ok((name, arity))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self {
+            kind: kind.to_string(),
+            module,
+            functions,
+        })
+    }
+
+    fn matches(&self, module: &str, name: &str, arity: i64) -> bool {
+        if self.module.as_ref().is_some_and(|re| !re.is_match(module)) {
+            return false;
+        }
+
+        self.functions.is_empty()
+            || self.functions.iter().any(|(n, a)| {
+                n.as_deref().is_none_or(|n| n == name) && a.is_none_or(|a| a == arity)
+            })
+    }
+}
+
+/// Compiled entry point rules: configured rules first, then the built-in ones.
+#[derive(Debug)]
+pub struct EntryPointRules {
+    matchers: Vec<EntryPointMatcher>,
+}
+
+impl EntryPointRules {
+    /// Build rules from configuration.
+    ///
+    /// Fails if a module pattern is not a valid regex or an arity is not a number.
+    pub fn new(config: &EntryPointsConfig) -> Result<Self, Box<dyn Error>> {
+        let mut matchers = Vec::new();
+        for EntryPointRule { kind, module, functions } in &config.rules {
+            matchers.push(EntryPointMatcher::new(kind, module.as_deref(), functions)?);
+        }
+
+        if config.include_defaults {
+            for (kind, module, functions) in DEFAULT_ENTRY_POINTS {
+                let functions: Vec<String> = functions.iter().map(|f| f.to_string()).collect();
+                matchers.push(EntryPointMatcher::new(kind, *module, &functions)?);
+            }
+        }
+
+        if matchers.is_empty() {
+            return Err("No entry point rules: built-in rules are disabled and none are configured".into());
+        }
+
+        Ok(Self { matchers })
+    }
+
+    /// Kind of entry point a public function is, if any (first matching rule wins)
+    pub fn kind_of(&self, module: &str, name: &str, arity: i64) -> Option<&str> {
+        self.matchers
+            .iter()
+            .find(|m| m.matches(module, name, arity))
+            .map(|m| m.kind.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(name: &str, modules: &[&str], may_depend_on: Option<&[&str]>) -> LayerConfig {
+        LayerConfig {
+            name: name.to_string(),
+            modules: modules.iter().map(|s| s.to_string()).collect(),
+            may_depend_on: may_depend_on.map(|l| l.iter().map(|s| s.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn test_in_namespace() {
+        assert!(in_namespace("MyApp.Repo", "MyApp.Repo"));
+        assert!(in_namespace("MyApp.Repo.Users", "MyApp.Repo"));
+        assert!(!in_namespace("MyApp.RepoHelpers", "MyApp.Repo"));
+        assert!(!in_namespace("MyApp", "MyApp.Repo"));
+    }
+
+    #[test]
+    fn test_is_forbidden() {
+        let rule = ForbiddenDependency {
+            from: "MyApp.Repo".to_string(),
+            to: "MyAppWeb".to_string(),
+            reason: None,
+        };
+        assert!(is_forbidden(&rule, "MyApp.Repo.Users", "MyAppWeb.Router"));
+        assert!(!is_forbidden(&rule, "MyAppWeb.Router", "MyApp.Repo"));
+        assert!(!is_forbidden(&rule, "MyApp.Accounts", "MyAppWeb.Router"));
+    }
+
+    fn web_domain_data() -> LayerRules {
+        LayerRules::new(&[
+            layer("Web", &["MyAppWeb"], Some(&["Domain"])),
+            layer("Domain", &["MyApp"], Some(&["Data"])),
+            layer("Data", &["MyApp.Repo"], Some(&[])),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_violation_explicit_directions() {
+        let rules = web_domain_data();
+        assert_eq!(
+            rules.violation("MyAppWeb.Controller", "MyApp.Accounts"),
+            None
+        );
+        assert_eq!(rules.violation("MyApp.Accounts", "MyApp.Repo"), None);
+        assert_eq!(
+            rules.violation("MyAppWeb.Controller", "MyApp.Repo"),
+            Some(("Web", "Data"))
+        );
+        assert_eq!(
+            rules.violation("MyApp.Repo", "MyApp.Accounts"),
+            Some(("Data", "Domain"))
+        );
+    }
+
+    #[test]
+    fn test_violation_same_layer_and_unlayered_allowed() {
+        let rules = web_domain_data();
+        assert_eq!(rules.violation("MyApp.Accounts", "MyApp.Billing"), None);
+        assert_eq!(rules.violation("MyApp.Repo", "Ecto.Query"), None);
+        assert_eq!(rules.violation("Mix.Tasks.Seed", "MyApp.Repo"), None);
+    }
+
+    #[test]
+    fn test_default_directions_allow_all_lower_layers() {
+        let rules = LayerRules::new(&[
+            layer("Web", &["MyAppWeb"], None),
+            layer("Domain", &["MyApp.Accounts"], None),
+            layer("Data", &["MyApp.Repo"], None),
+        ])
+        .unwrap();
+
+        assert_eq!(rules.violation("MyAppWeb.Controller", "MyApp.Repo"), None);
+        assert_eq!(
+            rules.violation("MyApp.Repo", "MyAppWeb.Router"),
+            Some(("Data", "Web"))
+        );
+    }
+
+    #[test]
+    fn test_new_rejects_unknown_layer_reference() {
+        let err = LayerRules::new(&[layer("Web", &["MyAppWeb"], Some(&["Nope"]))]).unwrap_err();
+        assert!(err.to_string().contains("unknown layer 'Nope'"));
+    }
+
+    #[test]
+    fn test_new_rejects_duplicate_names() {
+        let err = LayerRules::new(&[
+            layer("Web", &["MyAppWeb"], None),
+            layer("Web", &["MyApp"], None),
+        ])
+        .unwrap_err();
+        assert!(err.to_string().contains("Duplicate layer name"));
+    }
+
+    #[test]
+    fn test_new_rejects_empty_config() {
+        assert!(LayerRules::new(&[]).is_err());
+    }
+
+    fn entry_rule(kind: &str, module: Option<&str>, functions: &[&str]) -> EntryPointRule {
+        EntryPointRule {
+            kind: kind.to_string(),
+            module: module.map(String::from),
+            functions: functions.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_entry_point_defaults() {
+        let rules = EntryPointRules::new(&EntryPointsConfig::default()).unwrap();
+
+        assert_eq!(rules.kind_of("MyAppWeb.UserController", "index", 2), Some("Phoenix controller"));
+        assert_eq!(rules.kind_of("MyAppWeb.UserController", "helper", 1), None);
+        assert_eq!(rules.kind_of("MyAppWeb.UserLive.Index", "handle_info", 2), Some("Phoenix LiveView"));
+        assert_eq!(rules.kind_of("MyApp.Cache", "handle_info", 2), Some("GenServer"));
+        assert_eq!(rules.kind_of("Mix.Tasks.Seed", "run", 1), Some("Mix task"));
+        assert_eq!(rules.kind_of("MyApp.Workers.Email", "perform", 1), Some("Oban worker"));
+        assert_eq!(rules.kind_of("MyApp.Accounts", "get_user", 1), None);
+    }
+
+    #[test]
+    fn test_entry_point_configured_rules_come_first() {
+        let config = EntryPointsConfig {
+            include_defaults: true,
+            rules: vec![entry_rule("Resolver", Some("Resolvers"), &["*/3"])],
+        };
+        let rules = EntryPointRules::new(&config).unwrap();
+
+        assert_eq!(rules.kind_of("MyApp.Resolvers.User", "find", 3), Some("Resolver"));
+        assert_eq!(rules.kind_of("MyApp.Resolvers.User", "find", 2), None);
+        assert_eq!(rules.kind_of("MyApp.Cache", "init", 1), Some("GenServer"));
+    }
+
+    #[test]
+    fn test_entry_point_rule_without_functions_matches_all() {
+        let config = EntryPointsConfig {
+            include_defaults: false,
+            rules: vec![entry_rule("API", Some("^MyApp\\.Api$"), &[])],
+        };
+        let rules = EntryPointRules::new(&config).unwrap();
+
+        assert_eq!(rules.kind_of("MyApp.Api", "anything", 4), Some("API"));
+        assert_eq!(rules.kind_of("MyApp.Cache", "init", 1), None);
+    }
+
+    #[test]
+    fn test_entry_point_invalid_rules() {
+        let bad_regex = EntryPointsConfig {
+            include_defaults: false,
+            rules: vec![entry_rule("Bad", Some("("), &[])],
+        };
+        assert!(EntryPointRules::new(&bad_regex).is_err());
+
+        let bad_arity = EntryPointsConfig {
+            include_defaults: false,
+            rules: vec![entry_rule("Bad", None, &["run/x"])],
+        };
+        assert!(EntryPointRules::new(&bad_arity).is_err());
+
+        let empty = EntryPointsConfig {
+            include_defaults: false,
+            rules: vec![],
+        };
+        assert!(EntryPointRules::new(&empty).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! Result ordering for `--sort <column>[:asc|desc]`.
+//!
+//! Columns are the field names of a command's JSON output, so any field shown
+//! by `--format json` can be used for sorting. Sorting comes before `--limit`:
+//! a sorting command fetches every row ([`query_limit`]) and keeps the first
+//! `--limit` in sort order; ties keep the command's own order.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::str::FromStr;
+
+use db::types::ModuleCollectionResult;
+use serde::Serialize;
+use serde_json::Value;
+
+/// A sort column and direction
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortSpec {
+    pub column: String,
+    pub descending: bool,
+}
+
+impl FromStr for SortSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (column, direction) = match s.rsplit_once(':') {
+            Some((column, direction)) => (column, Some(direction)),
+            None => (s, None),
+        };
+
+        let descending = match direction.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("asc") => false,
+            Some("desc") => true,
+            Some(other) => {
+                return Err(format!("invalid sort direction '{}', expected 'asc' or 'desc'", other));
+            }
+        };
+
+        let column = column.trim();
+        if column.is_empty() {
+            return Err("sort column must not be empty".to_string());
+        }
+
+        Ok(Self {
+            column: column.to_string(),
+            descending,
+        })
+    }
+}
+
+/// Row limit for a command's query: none when sorting, since the first rows
+/// by the sort column may come anywhere in the command's own order
+pub fn query_limit(sort: Option<&SortSpec>, limit: u32) -> u32 {
+    if sort.is_some() { u32::MAX } else { limit }
+}
+
+impl SortSpec {
+    /// Whether the column is `field` or one of its nested fields
+    pub fn sorts_by(&self, field: &str) -> bool {
+        self.column.strip_prefix(field).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    }
+
+    /// Sort items by the column and keep the first `limit`, failing if no
+    /// item has the column
+    pub fn sort<T: Serialize>(&self, items: &mut Vec<T>, limit: u32) -> Result<(), Box<dyn Error>> {
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        let values: Vec<Value> = items
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<_, _>>()?;
+        let keys: Vec<Option<&Value>> = values.iter().map(|v| lookup(v, &self.column)).collect();
+        if keys.iter().all(Option::is_none) {
+            return Err(unknown_column(&self.column, &values[0]).into());
+        }
+
+        let mut order: Vec<usize> = (0..items.len()).collect();
+        order.sort_by(|&a, &b| self.direction(compare(keys[a], keys[b])));
+        order.truncate(limit as usize);
+
+        let mut slots: Vec<Option<T>> = items.drain(..).map(Some).collect();
+        items.extend(order.into_iter().filter_map(|i| slots[i].take()));
+        Ok(())
+    }
+
+    /// Sort a module-grouped result, keeping the first `limit` entries of all
+    /// groups in sort order and counting them in `total_items`.
+    ///
+    /// `module` orders the groups by name. Any other column orders the entries
+    /// within each group, and the groups by their first entry.
+    pub fn sort_groups<E: Serialize>(
+        &self,
+        result: &mut ModuleCollectionResult<E>,
+        limit: u32,
+    ) -> Result<(), Box<dyn Error>> {
+        self.keep_first_entries(result, limit)?;
+        result.total_items = result.items.iter().map(|group| group.entries.len()).sum();
+
+        if self.column == "module" {
+            result.items.sort_by(|a, b| self.direction(a.name.cmp(&b.name)));
+            return Ok(());
+        }
+
+        for group in &mut result.items {
+            self.sort(&mut group.entries, u32::MAX)?;
+        }
+
+        let firsts: Vec<Option<Value>> = result
+            .items
+            .iter()
+            .map(|group| group.entries.first().and_then(|e| serde_json::to_value(e).ok()))
+            .collect();
+        let keys: Vec<Option<&Value>> = firsts
+            .iter()
+            .map(|v| v.as_ref().and_then(|v| lookup(v, &self.column)))
+            .collect();
+
+        let mut order: Vec<usize> = (0..result.items.len()).collect();
+        order.sort_by(|&a, &b| self.direction(compare(keys[a], keys[b])));
+
+        let mut slots: Vec<Option<_>> = result.items.drain(..).map(Some).collect();
+        result.items.extend(order.into_iter().filter_map(|i| slots[i].take()));
+        Ok(())
+    }
+
+    /// Drop every entry but the first `limit` in sort order across all groups,
+    /// and the groups left empty
+    fn keep_first_entries<E: Serialize>(
+        &self,
+        result: &mut ModuleCollectionResult<E>,
+        limit: u32,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut keys: Vec<((usize, usize), Option<Value>)> = Vec::new();
+        for (g, group) in result.items.iter().enumerate() {
+            for (e, entry) in group.entries.iter().enumerate() {
+                let key = if self.column == "module" {
+                    Some(Value::String(group.name.clone()))
+                } else {
+                    let value = serde_json::to_value(entry)?;
+                    lookup(&value, &self.column).cloned()
+                };
+                keys.push(((g, e), key));
+            }
+        }
+        if keys.iter().all(|(_, key)| key.is_none())
+            && let Some(entry) = result.items.iter().flat_map(|group| &group.entries).next()
+        {
+            return Err(unknown_column(&self.column, &serde_json::to_value(entry)?).into());
+        }
+        if keys.len() <= limit as usize {
+            return Ok(());
+        }
+
+        keys.sort_by(|(_, a), (_, b)| self.direction(compare(a.as_ref(), b.as_ref())));
+        let mut kept: Vec<Vec<bool>> = result.items.iter().map(|group| vec![false; group.entries.len()]).collect();
+        for ((g, e), _) in keys.into_iter().take(limit as usize) {
+            kept[g][e] = true;
+        }
+        for (group, kept) in result.items.iter_mut().zip(kept) {
+            let mut kept = kept.into_iter();
+            group.entries.retain(|_| kept.next().unwrap_or(false));
+        }
+        result.items.retain(|group| !group.entries.is_empty());
+        Ok(())
+    }
+
+    fn direction(&self, ordering: Ordering) -> Ordering {
+        if self.descending { ordering.reverse() } else { ordering }
+    }
+}
+
+/// Look up a column, following dotted names into nested objects
+fn lookup<'a>(value: &'a Value, column: &str) -> Option<&'a Value> {
+    column
+        .split('.')
+        .try_fold(value, |v, key| v.get(key))
+        .filter(|v| !v.is_null())
+}
+
+/// Numbers compare numerically, everything else by its text; missing values sort first
+fn compare(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(Value::Number(a)), Some(Value::Number(b))) => {
+            let (a, b) = (a.as_f64().unwrap_or(0.0), b.as_f64().unwrap_or(0.0));
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        }
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        (Some(a), Some(b)) => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+fn unknown_column(column: &str, sample: &Value) -> String {
+    let available = match sample {
+        Value::Object(map) => map
+            .iter()
+            .filter(|(_, v)| !v.is_array() && !v.is_object())
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => String::new(),
+    };
+    format!("Unknown sort column '{}'. Available columns: {}", column, available)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db::types::ModuleGroup;
+
+    #[derive(Debug, Serialize, PartialEq)]
+    struct Entry {
+        name: &'static str,
+        lines: i64,
+    }
+
+    fn entries() -> Vec<Entry> {
+        vec![
+            Entry { name: "b", lines: 10 },
+            Entry { name: "a", lines: 30 },
+            Entry { name: "c", lines: 20 },
+        ]
+    }
+
+    fn names(entries: &[Entry]) -> Vec<&str> {
+        entries.iter().map(|e| e.name).collect()
+    }
+
+    fn group(name: &str, entries: Vec<Entry>) -> ModuleGroup<Entry> {
+        ModuleGroup {
+            name: name.to_string(),
+            file: String::new(),
+            entries,
+            function_count: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_sort_spec() {
+        assert_eq!(
+            "lines".parse::<SortSpec>().unwrap(),
+            SortSpec { column: "lines".to_string(), descending: false }
+        );
+        assert!("lines:DESC".parse::<SortSpec>().unwrap().descending);
+        assert!(!"lines:asc".parse::<SortSpec>().unwrap().descending);
+        assert!("lines:down".parse::<SortSpec>().is_err());
+        assert!(":desc".parse::<SortSpec>().is_err());
+    }
+
+    #[test]
+    fn test_sorts_by_field() {
+        let spec = "blame.date:desc".parse::<SortSpec>().unwrap();
+        assert!(spec.sorts_by("blame"));
+        assert!(spec.sorts_by("blame.date"));
+        assert!(!spec.sorts_by("bla"));
+        assert!(!"blamed".parse::<SortSpec>().unwrap().sorts_by("blame"));
+    }
+
+    #[test]
+    fn test_sort_numeric_desc() {
+        let mut items = entries();
+        "lines:desc".parse::<SortSpec>().unwrap().sort(&mut items, 100).unwrap();
+        assert_eq!(names(&items), ["a", "c", "b"]);
+    }
+
+    #[test]
+    fn test_sort_text_asc() {
+        let mut items = entries();
+        "name".parse::<SortSpec>().unwrap().sort(&mut items, 100).unwrap();
+        assert_eq!(names(&items), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_sort_unknown_column() {
+        let mut items = entries();
+        let err = "nope".parse::<SortSpec>().unwrap().sort(&mut items, 100).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown sort column 'nope'. Available columns: lines, name");
+    }
+
+    #[test]
+    fn test_sort_groups() {
+        let mut result = ModuleCollectionResult {
+            module_pattern: "*".to_string(),
+            function_pattern: None,
+            kind_filter: None,
+            name_filter: None,
+            total_items: 4,
+            items: vec![
+                group("MyApp.A", vec![Entry { name: "x", lines: 5 }]),
+                group("MyApp.B", entries()),
+            ],
+        };
+
+        "lines:desc".parse::<SortSpec>().unwrap().sort_groups(&mut result, 100).unwrap();
+        assert_eq!(result.items[0].name, "MyApp.B");
+        assert_eq!(names(&result.items[0].entries), ["a", "c", "b"]);
+
+        "module".parse::<SortSpec>().unwrap().sort_groups(&mut result, 100).unwrap();
+        assert_eq!(result.items[0].name, "MyApp.A");
+    }
+
+    #[test]
+    fn test_sort_keeps_first_rows_in_sort_order() {
+        // The row with the most lines comes last in the command's own order
+        let mut items = entries();
+        items.push(Entry { name: "d", lines: 40 });
+        "lines:desc".parse::<SortSpec>().unwrap().sort(&mut items, 2).unwrap();
+        assert_eq!(names(&items), ["d", "a"]);
+    }
+
+    #[test]
+    fn test_sort_groups_keeps_first_entries_across_groups() {
+        let mut result = ModuleCollectionResult {
+            module_pattern: "*".to_string(),
+            function_pattern: None,
+            kind_filter: None,
+            name_filter: None,
+            total_items: 4,
+            items: vec![
+                group("MyApp.A", vec![Entry { name: "x", lines: 5 }]),
+                group("MyApp.B", entries()),
+            ],
+        };
+
+        "lines:desc".parse::<SortSpec>().unwrap().sort_groups(&mut result, 2).unwrap();
+        assert_eq!(result.total_items, 2);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(names(&result.items[0].entries), ["a", "c"]);
+
+        let mut result = ModuleCollectionResult {
+            items: vec![
+                group("MyApp.B", entries()),
+                group("MyApp.A", vec![Entry { name: "x", lines: 5 }]),
+            ],
+            ..result
+        };
+        "module".parse::<SortSpec>().unwrap().sort_groups(&mut result, 1).unwrap();
+        assert_eq!(result.items[0].name, "MyApp.A");
+        assert_eq!(result.total_items, 1);
+    }
+}
@@ -0,0 +1,148 @@
+//! "Did you mean" suggestions for lookups that match nothing.
+//!
+//! `location`, `function`, `calls-to` and `trace` take a module and function
+//! name. When they find nothing, the result carries the near-miss names from
+//! [`suggest_names`] instead of a bare "not found": the most similar modules
+//! when the module does not exist, otherwise the most similar functions in it.
+
+use std::error::Error;
+
+use db::queries::search::suggest_names;
+use db::query_builders::{is_glob, QuerySettings};
+use serde::Serialize;
+
+use crate::commands::CommonArgs;
+use crate::output::{Annotation, Outputable};
+
+/// Most near-miss names reported
+const MAX_SUGGESTIONS: u32 = 3;
+
+/// A lookup result, with the names the user may have meant when it is empty
+#[derive(Debug, Serialize)]
+pub struct Suggested<T> {
+    #[serde(flatten)]
+    pub result: T,
+    /// Near-miss module or function names, best first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+}
+
+/// The names a lookup was given, kept to suggest others if it finds nothing
+pub struct Lookup {
+    project: String,
+    module: Option<String>,
+    function: Option<String>,
+    regex: bool,
+}
+
+impl Lookup {
+    pub fn new(common: &CommonArgs, module: Option<&str>, function: Option<&str>) -> Self {
+        Self {
+            project: common.project.clone(),
+            module: module.map(String::from),
+            function: function.map(String::from),
+            regex: common.regex,
+        }
+    }
+
+    /// Wrap `result`, with suggestions when it is `empty`.
+    ///
+    /// Regex and glob patterns get none: they are not names.
+    pub fn suggest<T>(
+        self,
+        db: &db::DbInstance,
+        result: T,
+        empty: bool,
+        settings: &QuerySettings,
+    ) -> Result<Suggested<T>, Box<dyn Error>> {
+        let names = [self.module.as_deref(), self.function.as_deref()];
+        let patterns = self.regex || names.into_iter().flatten().any(is_glob);
+        let suggestions = if empty && !patterns {
+            suggest_names(
+                db,
+                &self.project,
+                self.module.as_deref(),
+                self.function.as_deref(),
+                MAX_SUGGESTIONS,
+                settings,
+            )?
+        } else {
+            Vec::new()
+        };
+        Ok(Suggested { result, suggestions })
+    }
+}
+
+impl<T: Outputable> Outputable for Suggested<T> {
+    fn to_table(&self) -> String {
+        let table = self.result.to_table();
+        match self.suggestions.as_slice() {
+            [] => table,
+            [only] => format!("{}\nDid you mean {}?", table.trim_end(), only),
+            [rest @ .., last] => format!("{}\nDid you mean {} or {}?", table.trim_end(), rest.join(", "), last),
+        }
+    }
+
+    fn to_annotations(&self) -> Option<Vec<Annotation>> {
+        self.result.to_annotations()
+    }
+
+    fn to_csv(&self) -> Option<String> {
+        self.result.to_csv()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn common() -> CommonArgs {
+        CommonArgs {
+            project: "default".to_string(),
+            regex: false,
+            limit: 100,
+            namespace: Vec::new(),
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Empty {
+        total_items: usize,
+    }
+
+    impl Outputable for Empty {
+        fn to_table(&self) -> String {
+            "Nothing found.\n".to_string()
+        }
+    }
+
+    #[test]
+    fn test_suggests_modules_for_empty_lookup() {
+        let db = db::test_utils::call_graph_db("default");
+        let suggested = Lookup::new(&common(), Some("MyApp.Acounts"), Some("get_user"))
+            .suggest(&db, Empty { total_items: 0 }, true, &QuerySettings::default())
+            .unwrap();
+
+        assert_eq!(suggested.suggestions[0], "MyApp.Accounts");
+        assert!(suggested.to_table().starts_with("Nothing found.\nDid you mean MyApp.Accounts"));
+        let json = serde_json::to_value(&suggested).unwrap();
+        assert_eq!(json["total_items"], 0);
+        assert_eq!(json["suggestions"][0], "MyApp.Accounts");
+    }
+
+    #[test]
+    fn test_no_suggestions_for_results_or_patterns() {
+        let db = db::test_utils::call_graph_db("default");
+        let found = Lookup::new(&common(), Some("MyApp.Acounts"), None)
+            .suggest(&db, Empty { total_items: 1 }, false, &QuerySettings::default())
+            .unwrap();
+        assert!(found.suggestions.is_empty());
+        assert_eq!(found.to_table(), "Nothing found.\n");
+
+        let glob = Lookup::new(&common(), Some("MyApp.Acc*"), None)
+            .suggest(&db, Empty { total_items: 0 }, true, &QuerySettings::default())
+            .unwrap();
+        assert!(glob.suggestions.is_empty());
+        assert!(serde_json::to_value(&glob).unwrap().get("suggestions").is_none());
+    }
+}
@@ -0,0 +1,96 @@
+//! Function targets read from stdin (`--stdin`).
+//!
+//! One target per line, as `Module.function` or `Module.function/arity`, or as
+//! the CSV rows of `-o csv --fields module,function,arity` (the header line is
+//! skipped), so the functions listed by one command can be piped into another.
+//! Blank lines and lines starting with `#` are ignored.
+
+use std::error::Error;
+use std::io::BufRead;
+
+use db::query_builders::FunctionTarget;
+
+/// Read the targets piped into the process
+pub fn stdin_targets() -> Result<Vec<FunctionTarget>, Box<dyn Error>> {
+    read_targets(std::io::stdin().lock())
+}
+
+/// Read one target per line, dropping repeated targets
+pub fn read_targets(reader: impl BufRead) -> Result<Vec<FunctionTarget>, Box<dyn Error>> {
+    let mut targets: Vec<FunctionTarget> = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let target = if line.contains(',') {
+            match parse_csv_row(line) {
+                Ok(Some(target)) => target,
+                Ok(None) => continue,
+                Err(e) => return Err(format!("stdin line {}: {}", index + 1, e).into()),
+            }
+        } else {
+            FunctionTarget::parse(line).map_err(|e| format!("stdin line {}: {}", index + 1, e))?
+        };
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+
+    if targets.is_empty() {
+        return Err("--stdin: no targets read, expected Module.function[/arity] lines".into());
+    }
+    Ok(targets)
+}
+
+/// A `module,function[,arity]` row, or `None` for the header
+fn parse_csv_row(line: &str) -> Result<Option<FunctionTarget>, String> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields[0] == "module" {
+        return Ok(None);
+    }
+    let (module, function) = match fields[..] {
+        [module, function, ..] if !module.is_empty() && !function.is_empty() => (module, function),
+        _ => return Err(format!("`{line}` is not module,function[,arity]")),
+    };
+    let arity = match fields.get(2) {
+        None | Some(&"") => None,
+        Some(arity) => Some(arity.parse::<i64>().map_err(|_| format!("`{arity}` is not an arity"))?),
+    };
+
+    Ok(Some(FunctionTarget {
+        module: module.to_string(),
+        function: function.to_string(),
+        arity,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(input: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(read_targets(input.as_bytes())?.iter().map(FunctionTarget::label).collect())
+    }
+
+    #[test]
+    fn test_read_targets_lines() {
+        let targets = read("MyApp.Accounts.get_user/1\n\n# comment\nMyApp.Repo.get\nMyApp.Repo.get\n").unwrap();
+        assert_eq!(targets, ["MyApp.Accounts.get_user/1", "MyApp.Repo.get"]);
+    }
+
+    #[test]
+    fn test_read_targets_csv() {
+        let targets = read("module,function,arity\nMyApp.Accounts,get_user,1\nMyApp.Repo,get,\n").unwrap();
+        assert_eq!(targets, ["MyApp.Accounts.get_user/1", "MyApp.Repo.get"]);
+    }
+
+    #[test]
+    fn test_read_targets_errors() {
+        assert!(read("get_user\n").unwrap_err().to_string().contains("stdin line 1"));
+        assert!(read("MyApp.Repo,get,two\n").unwrap_err().to_string().contains("not an arity"));
+        assert!(read("\n# nothing\n").unwrap_err().to_string().contains("no targets"));
+    }
+}
@@ -227,7 +227,7 @@ macro_rules! execute_test_fixture {
 
 /// Generate a fixture using a shared fixture file.
 ///
-/// Available fixtures: `call_graph`, `type_signatures`, `structs`
+/// Available fixtures: `call_graph`, `type_signatures`, `structs`, `umbrella`
 ///
 /// # Example
 /// ```ignore
@@ -269,6 +269,16 @@ macro_rules! shared_fixture {
             db::test_utils::structs_db($project)
         }
     };
+    (
+        fixture_name: $name:ident,
+        fixture_type: umbrella,
+        project: $project:literal $(,)?
+    ) => {
+        #[fixture]
+        fn $name() -> db::DbInstance {
+            db::test_utils::umbrella_db($project)
+        }
+    };
 }
 
 /// Generate a test that verifies command execution against an empty database fails.
@@ -282,7 +292,7 @@ macro_rules! execute_empty_db_test {
         fn test_empty_db() {
             use $crate::commands::Execute;
             let db = db::test_utils::setup_empty_test_db();
-            let result = $cmd.execute(&db);
+            let result = $cmd.execute(&db, &db::query_builders::QuerySettings::default());
             assert!(result.is_err());
         }
     };
@@ -322,7 +332,9 @@ macro_rules! execute_test {
         #[rstest]
         fn $test_name($fixture: db::DbInstance) {
             use $crate::commands::Execute;
-            let $result = $cmd.execute(&$fixture).expect("Execute should succeed");
+            let $result = $cmd
+                .execute(&$fixture, &db::query_builders::QuerySettings::default())
+                .expect("Execute should succeed");
             $assertions
         }
     };
@@ -350,7 +362,9 @@ macro_rules! execute_no_match_test {
         #[rstest]
         fn $test_name($fixture: db::DbInstance) {
             use $crate::commands::Execute;
-            let result = $cmd.execute(&$fixture).expect("Execute should succeed");
+            let result = $cmd
+                .execute(&$fixture, &db::query_builders::QuerySettings::default())
+                .expect("Execute should succeed");
             assert!(
                 result.$field.is_empty(),
                 concat!(stringify!($field), " should be empty")
@@ -383,7 +397,9 @@ macro_rules! execute_count_test {
         #[rstest]
         fn $test_name($fixture: db::DbInstance) {
             use $crate::commands::Execute;
-            let result = $cmd.execute(&$fixture).expect("Execute should succeed");
+            let result = $cmd
+                .execute(&$fixture, &db::query_builders::QuerySettings::default())
+                .expect("Execute should succeed");
             assert_eq!(
                 result.$field.len(),
                 $expected,
@@ -417,7 +433,9 @@ macro_rules! execute_field_test {
         #[rstest]
         fn $test_name($fixture: db::DbInstance) {
             use $crate::commands::Execute;
-            let result = $cmd.execute(&$fixture).expect("Execute should succeed");
+            let result = $cmd
+                .execute(&$fixture, &db::query_builders::QuerySettings::default())
+                .expect("Execute should succeed");
             assert_eq!(
                 result.$field, $expected,
                 concat!("Field ", stringify!($field), " mismatch")
@@ -452,7 +470,9 @@ macro_rules! execute_first_item_test {
         #[rstest]
         fn $test_name($fixture: db::DbInstance) {
             use $crate::commands::Execute;
-            let result = $cmd.execute(&$fixture).expect("Execute should succeed");
+            let result = $cmd
+                .execute(&$fixture, &db::query_builders::QuerySettings::default())
+                .expect("Execute should succeed");
             assert!(
                 !result.$collection.is_empty(),
                 concat!(stringify!($collection), " should not be empty")
@@ -489,7 +509,9 @@ macro_rules! execute_all_match_test {
         #[rstest]
         fn $test_name($fixture: db::DbInstance) {
             use $crate::commands::Execute;
-            let result = $cmd.execute(&$fixture).expect("Execute should succeed");
+            let result = $cmd
+                .execute(&$fixture, &db::query_builders::QuerySettings::default())
+                .expect("Execute should succeed");
             assert!(
                 result.$collection.iter().all(|$item| $cond),
                 concat!("Not all ", stringify!($collection), " matched condition")
@@ -522,7 +544,9 @@ macro_rules! execute_limit_test {
         #[rstest]
         fn $test_name($fixture: db::DbInstance) {
             use $crate::commands::Execute;
-            let result = $cmd.execute(&$fixture).expect("Execute should succeed");
+            let result = $cmd
+                .execute(&$fixture, &db::query_builders::QuerySettings::default())
+                .expect("Execute should succeed");
             assert!(
                 result.$collection.len() <= $limit,
                 concat!(
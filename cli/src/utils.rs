@@ -6,6 +6,17 @@ use regex::Regex;
 use db::types::{ModuleGroup, Call};
 use crate::dedup::sort_and_deduplicate;
 
+/// Extract namespace from a module name at the specified depth
+///
+/// Example: "MyApp.Accounts.Users.Admin" at depth 2 becomes "MyApp.Accounts"
+pub fn extract_namespace(module: &str, depth: usize) -> String {
+    module
+        .split('.')
+        .take(depth)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 /// Groups items by module into a structured result
 ///
 /// Transforms a vector of source items into (module, entry) tuples and groups them by module
@@ -37,9 +48,9 @@ where
 ///
 /// # Returns
 /// A vector of ModuleGroup structs, one per module in sorted order
-pub fn group_by_module_with_file<T, E, F>(items: Vec<T>, transform: F) -> Vec<ModuleGroup<E>>
+pub fn group_by_module_with_file<T, E, F>(items: Vec<T>, mut transform: F) -> Vec<ModuleGroup<E>>
 where
-    F: Fn(T) -> (String, E, String),
+    F: FnMut(T) -> (String, E, String),
 {
     let mut module_map: BTreeMap<String, (String, Vec<E>)> = BTreeMap::new();
 
@@ -338,6 +349,22 @@ fn parse_single_field(field_str: &str) -> Option<(String, String)> {
     Some((name, typ))
 }
 
+/// `YYYY-MM-DD` of a Unix time in milliseconds (UTC)
+pub fn format_date(millis: i64) -> String {
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let days = millis.div_euclid(86_400_000);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -488,4 +515,10 @@ mod tests {
         assert_eq!(result[1].entries.len(), 2); // math has 2 items
         assert_eq!(result[2].entries.len(), 2); // string has 2 items
     }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400_000), "2000-02-29");
+    }
 }
@@ -0,0 +1,108 @@
+//! Allocations and time spent extracting a 100k-row trace result, with a
+//! fresh string per cell versus names interned across rows.
+//!
+//! Run with `cargo bench -p db --bench interned_rows`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use cozo::{DataValue, NamedRows};
+use db::db::{extract_call_from_row, extract_i64, extract_string, CallRowLayout};
+use db::types::{Call, FunctionRef, Interner};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const ROWS: usize = 100_000;
+
+/// Trace rows over 200 modules of 10 functions each
+fn trace_rows() -> NamedRows {
+    let headers = [
+        "caller_module", "caller_name", "caller_arity", "caller_kind", "caller_start_line",
+        "caller_end_line", "callee_module", "callee_function", "callee_arity", "file", "call_line",
+    ];
+    let str = |s: String| DataValue::Str(s.into());
+    let rows = (0..ROWS)
+        .map(|i| {
+            let (caller, callee) = (i % 200, (i * 7) % 200);
+            vec![
+                str(format!("MyApp.Module{caller}")),
+                str(format!("function_{}", i % 10)),
+                DataValue::from(1),
+                str("def".to_string()),
+                DataValue::from(10),
+                DataValue::from(20),
+                str(format!("MyApp.Module{callee}")),
+                str(format!("function_{}", (i / 10) % 10)),
+                DataValue::from(2),
+                str(format!("lib/my_app/module{caller}.ex")),
+                DataValue::from(15),
+            ]
+        })
+        .collect();
+    NamedRows::new(headers.iter().map(|h| h.to_string()).collect(), rows)
+}
+
+/// A fresh `Rc<str>` per cell, as before interning
+fn extract_per_cell(row: &[DataValue]) -> Option<Call> {
+    let shared = |value: &DataValue| extract_string(value).map(|s| Rc::from(s.into_boxed_str()));
+    let caller = FunctionRef::with_definition(
+        shared(&row[0])?,
+        shared(&row[1])?,
+        extract_i64(&row[2], 0),
+        shared(&row[3])?,
+        shared(&row[9])?,
+        extract_i64(&row[4], 0),
+        extract_i64(&row[5], 0),
+    );
+    let callee = FunctionRef::new(shared(&row[6])?, shared(&row[7])?, extract_i64(&row[8], 0));
+    Some(Call {
+        caller,
+        callee,
+        line: extract_i64(&row[10], 0),
+        call_type: None,
+        call_kind: None,
+        depth: None,
+        weight: None,
+    })
+}
+
+fn measure(label: &str, extract: impl FnOnce() -> Vec<Call>) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let calls = extract();
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!("{label:<10} {:>7} calls {allocations:>9} allocations {elapsed:>10.2?}", calls.len());
+}
+
+fn main() {
+    let rows = trace_rows();
+    let layout = CallRowLayout::from_headers(&rows.headers).unwrap();
+
+    measure("per cell", || rows.rows.iter().filter_map(|row| extract_per_cell(row)).collect());
+    measure("interned", || {
+        let mut interner = Interner::default();
+        rows.rows
+            .iter()
+            .filter_map(|row| extract_call_from_row(row, &layout, &mut interner))
+            .collect()
+    });
+}
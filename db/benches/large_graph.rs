@@ -0,0 +1,151 @@
+//! Import, trace, path and hotspots over generated call graphs, on the
+//! in-memory and SQLite backends and, for traversals, the adjacency index.
+//!
+//! Run with `cargo bench -p db --features test-utils --bench large_graph`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use db::fixtures::{synthetic_call_graph, synthetic_function, synthetic_module, GraphSize};
+use db::queries::adjacency::AdjacencyIndex;
+use db::queries::hotspots::{find_hotspots, HotspotKind};
+use db::queries::import::import_json_str;
+use db::queries::path::find_paths;
+use db::queries::trace::trace_calls;
+use db::query_builders::QuerySettings;
+use db::{open_db, open_mem_db, DbInstance};
+use tempfile::TempDir;
+
+const PROJECT: &str = "bench";
+
+/// Graph the query benchmarks run on: 1k functions, 2k calls
+const QUERY_GRAPH: GraphSize = GraphSize {
+    modules: 100,
+    functions_per_module: 10,
+    calls_per_function: 2,
+};
+
+const IMPORT_GRAPHS: [GraphSize; 2] = [
+    QUERY_GRAPH,
+    GraphSize {
+        modules: 100,
+        functions_per_module: 20,
+        calls_per_function: 3,
+    },
+];
+
+/// An empty database of each backend; the directory keeps the SQLite file alive
+fn backends() -> (TempDir, Vec<(&'static str, DbInstance)>) {
+    let dir = TempDir::new().unwrap();
+    let sqlite = open_db(&dir.path().join("bench.db")).unwrap();
+    (dir, vec![("mem", open_mem_db()), ("sqlite", sqlite)])
+}
+
+fn bench_import(c: &mut Criterion) {
+    let mut group = c.benchmark_group("import");
+    group.sample_size(10);
+    for size in IMPORT_GRAPHS {
+        let json = synthetic_call_graph(size);
+        for backend in ["mem", "sqlite"] {
+            let id = BenchmarkId::new(backend, size.call_count());
+            group.bench_function(id, |b| {
+                b.iter_with_setup(
+                    || {
+                        let dir = TempDir::new().unwrap();
+                        let db = match backend {
+                            "mem" => open_mem_db(),
+                            _ => open_db(&dir.path().join("bench.db")).unwrap(),
+                        };
+                        (dir, db)
+                    },
+                    |(_dir, db)| import_json_str(&db, &json, PROJECT).unwrap(),
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_queries(c: &mut Criterion) {
+    let json = synthetic_call_graph(QUERY_GRAPH);
+    let (_dir, dbs) = backends();
+    for (_, db) in &dbs {
+        import_json_str(db, &json, PROJECT).unwrap();
+    }
+    let index = AdjacencyIndex::load(&dbs[0].1, PROJECT).unwrap();
+
+    let module = synthetic_module(0);
+    let function = synthetic_function(0);
+    let target = synthetic_module(QUERY_GRAPH.modules / 50);
+    let settings = QuerySettings::default();
+
+    let mut group = c.benchmark_group("trace_depth_10");
+    group.sample_size(10);
+    for (backend, db) in &dbs {
+        group.bench_function(*backend, |b| {
+            b.iter(|| {
+                trace_calls(
+                    db,
+                    &module,
+                    &function,
+                    Some(1),
+                    PROJECT,
+                    false,
+                    10,
+                    1000,
+                    false,
+                    1,
+                    false,
+                    &settings,
+                )
+                .unwrap()
+            })
+        });
+    }
+    group.bench_function("adjacency", |b| {
+        b.iter(|| index.trace_calls(&module, &function, Some(1), false, 10, 1000, false, 1, false, |_| true, &settings).unwrap())
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("path");
+    group.sample_size(10);
+    for (backend, db) in &dbs {
+        group.bench_function(*backend, |b| {
+            b.iter(|| {
+                find_paths(db, &module, &function, Some(1), &target, "", None, PROJECT, 10, 10, false, 1).unwrap()
+            })
+        });
+    }
+    group.bench_function("adjacency", |b| {
+        b.iter(|| index.find_paths(&module, &function, Some(1), &target, "", None, 10, 10, false, 1))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("hotspots");
+    group.sample_size(10);
+    for (backend, db) in &dbs {
+        group.bench_function(*backend, |b| {
+            b.iter(|| {
+                find_hotspots(
+                    db,
+                    HotspotKind::Incoming,
+                    None,
+                    None,
+                    PROJECT,
+                    false,
+                    20,
+                    false,
+                    false,
+                    &settings,
+                )
+                .unwrap()
+            })
+        });
+    }
+    group.finish();
+
+    black_box(dbs);
+}
+
+criterion_group!(benches, bench_import, bench_queries);
+criterion_main!(benches);
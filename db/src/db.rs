@@ -23,6 +23,13 @@
 //! struct documents column positions for each query type, centralizing the
 //! mapping in two factory methods rather than scattering magic numbers.
 //!
+//! **Why `FromRow` next to the `extract_*` helpers?**
+//! Queries loading a whole relation (the adjacency index, test surfaces) read
+//! every cell once, in order. `TypedRows::into_typed` moves the cells of each
+//! row into a tuple or a `from_row!` struct without cloning strings, and maps
+//! struct fields to columns by header name once per result. Queries that pick
+//! a few cells or default bad ones keep using the helpers.
+//!
 //! **Why bare `String` for module/function names instead of newtypes?**
 //! For a CLI tool, the complexity of newtype wrappers (`.0` access, `Into` impls,
 //! derive macro limitations) outweighs the type safety benefit. Field names
@@ -31,12 +38,10 @@
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::path::Path;
-use std::rc::Rc;
-
 use cozo::{DataValue, DbInstance, NamedRows, ScriptMutability};
 use thiserror::Error;
 
-use crate::types::{Call, FunctionRef};
+use crate::types::{Call, FunctionRef, Interner, SharedStr};
 
 #[derive(Error, Debug)]
 pub enum DbError {
@@ -63,18 +68,47 @@ pub fn open_db(path: &Path) -> Result<DbInstance, Box<dyn Error>> {
 
 /// Create an in-memory database instance.
 ///
-/// Used for tests to avoid disk I/O and temp file management.
-#[cfg(any(test, feature = "test-utils"))]
+/// Used for tests to avoid disk I/O and temp file management, and by the
+/// WebAssembly build, which has no file storage.
+#[cfg(any(test, feature = "test-utils", feature = "wasm"))]
 pub fn open_mem_db() -> DbInstance {
     DbInstance::new("mem", "", "").expect("Failed to create in-memory DB")
 }
 
+/// Sees each query before it runs and may answer it instead of the database
+#[cfg(any(test, feature = "test-utils"))]
+pub(crate) type QueryHook = Box<dyn FnMut(&str, &Params) -> Option<Result<NamedRows, String>>>;
+
+#[cfg(any(test, feature = "test-utils"))]
+thread_local! {
+    /// Installed by `test_utils::MockDatabase` for the duration of a test
+    pub(crate) static QUERY_HOOK: std::cell::RefCell<Option<QueryHook>> = const { std::cell::RefCell::new(None) };
+}
+
 /// Run a mutable query (insert, delete, create, etc.)
+///
+/// Debug builds assert that every bound parameter is used (see
+/// [`crate::sanitize::check_placeholders`]), and tests also that no bound
+/// value is written into the script (see [`crate::sanitize::check_not_inlined`]).
 pub fn run_query(
     db: &DbInstance,
     script: &str,
     params: Params,
 ) -> Result<NamedRows, Box<dyn Error>> {
+    #[cfg(debug_assertions)]
+    if let Err(message) = crate::sanitize::check_placeholders(script, &params) {
+        panic!("unsanitized query, {message}:\n{script}");
+    }
+    #[cfg(any(test, feature = "test-utils"))]
+    if let Err(message) = crate::sanitize::check_not_inlined(script, &params) {
+        panic!("unsanitized query, {message}:\n{script}");
+    }
+
+    #[cfg(any(test, feature = "test-utils"))]
+    if let Some(result) = QUERY_HOOK.with(|hook| hook.borrow_mut().as_mut().and_then(|hook| hook(script, &params))) {
+        return result.map_err(|message| Box::new(DbError::QueryFailed { message }) as Box<dyn Error>);
+    }
+
     // Convert &'static str keys to String for CozoDB
     let params_owned: BTreeMap<String, DataValue> = params
         .into_iter()
@@ -83,17 +117,43 @@ pub fn run_query(
 
     db.run_script(script, params_owned, ScriptMutability::Mutable)
         .map_err(|e| {
-            Box::new(DbError::QueryFailed {
-                message: format!("{:?}", e),
-            }) as Box<dyn Error>
+            let mut message = format!("{:?}", e);
+            if names_missing_column(&message) && schema_outdated(db) {
+                message.push('\n');
+                message.push_str(OUTDATED_SCHEMA_HINT);
+            }
+            Box::new(DbError::QueryFailed { message }) as Box<dyn Error>
         })
 }
 
+/// Added to query errors caused by columns a database set up by an older build lacks
+pub const OUTDATED_SCHEMA_HINT: &str = "The database was set up by an older version of code_search; \
+     run `code_search schema verify --repair` to add the missing columns.";
+
+/// Whether a Cozo error is about a column a stored relation does not have
+fn names_missing_column(message: &str) -> bool {
+    message.contains("does not have field") || (message.contains("required column") && message.contains("not found"))
+}
+
+/// Whether stored relations lack columns of this build's schema
+fn schema_outdated(db: &DbInstance) -> bool {
+    crate::queries::schema::schema_mismatches(db).is_ok_and(|mismatches| mismatches.iter().any(|m| !m.missing.is_empty()))
+}
+
 /// Run a mutable query with no parameters
 pub fn run_query_no_params(db: &DbInstance, script: &str) -> Result<NamedRows, Box<dyn Error>> {
     run_query(db, script, Params::new())
 }
 
+/// Check that the database answers queries from its storage.
+///
+/// Reads one row of `modules`, so it fails when the storage does (a deleted
+/// or unreadable database file, a closed instance) or holds no schema.
+pub fn ping(db: &DbInstance) -> Result<(), Box<dyn Error>> {
+    run_query_no_params(db, "?[name] := *modules{name} :limit 1")?;
+    Ok(())
+}
+
 /// Escape a string for use in CozoDB string literals.
 ///
 /// # Arguments
@@ -122,25 +182,35 @@ pub fn escape_string_for_quote(s: &str, quote_char: char) -> String {
 }
 
 /// Escape a string for use in CozoDB double-quoted string literals (JSON-compatible)
+///
+/// CozoDB tries raw strings first, and `"..."` is a raw string with no
+/// underscores: its escapes are kept as written and it ends at the first `"`.
+/// Only strings without quotes, backslashes or control characters survive a
+/// double-quoted literal; use [`escape_string_single`] for anything else.
 #[inline]
 pub fn escape_string(s: &str) -> String {
     escape_string_for_quote(s, '"')
 }
 
 /// Escape a string for use in CozoDB single-quoted string literals.
-/// Use this for strings that may contain double quotes or complex content.
+/// Use this for any string that is not known to be plain, such as names and
+/// source text read from an import. [`crate::sanitize::literal`] adds the
+/// quotes.
 #[inline]
 pub fn escape_string_single(s: &str) -> String {
     escape_string_for_quote(s, '\'')
 }
 
-/// Try to create a relation, returning Ok(true) if created, Ok(false) if already exists
+/// Try to create a relation (or index), returning Ok(true) if created, Ok(false) if already exists
 pub fn try_create_relation(db: &DbInstance, script: &str) -> Result<bool, Box<dyn Error>> {
     match run_query_no_params(db, script) {
         Ok(_) => Ok(true),
         Err(e) => {
             let err_str = e.to_string();
-            if err_str.contains("AlreadyExists") || err_str.contains("stored_relation_conflict") {
+            if err_str.contains("AlreadyExists")
+                || err_str.contains("stored_relation_conflict")
+                || err_str.contains("already exists")
+            {
                 Ok(false)
             } else {
                 Err(e)
@@ -195,6 +265,36 @@ pub fn extract_f64(value: &DataValue, default: f64) -> f64 {
     }
 }
 
+/// Extract a string from a DataValue as its interned copy, returning None if not a string
+pub fn extract_shared(value: &DataValue, interner: &mut Interner) -> Option<SharedStr> {
+    match value {
+        DataValue::Str(s) => Some(interner.intern(s)),
+        _ => None,
+    }
+}
+
+/// Extract a string from a DataValue as its interned copy, returning the default if not a string
+pub fn extract_shared_or(value: &DataValue, default: &str, interner: &mut Interner) -> SharedStr {
+    match value {
+        DataValue::Str(s) => interner.intern(s),
+        _ => interner.intern(default),
+    }
+}
+
+/// Strip a trailing `/N` arity suffix from a function name ("format/1" -> "format").
+///
+/// `caller_function` in the calls relation may carry the arity; callee names never do.
+pub fn strip_arity(function: &str) -> &str {
+    match function.rsplit_once('/') {
+        Some((name, arity))
+            if !name.is_empty() && !arity.is_empty() && arity.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            name
+        }
+        _ => function,
+    }
+}
+
 /// Layout descriptor for extracting call data from query result rows
 #[derive(Debug)]
 pub struct CallRowLayout {
@@ -210,6 +310,8 @@ pub struct CallRowLayout {
     pub file_idx: usize,
     pub line_idx: usize,
     pub call_type_idx: Option<usize>,
+    pub call_kind_idx: Option<usize>,
+    pub weight_idx: Option<usize>,
 }
 
 impl CallRowLayout {
@@ -223,7 +325,7 @@ impl CallRowLayout {
     /// - caller_start_line, caller_end_line
     /// - callee_module, callee_function, callee_arity
     /// - file, call_line
-    /// - call_type (optional)
+    /// - call_type, call_kind, weight (optional)
     pub fn from_headers(headers: &[String]) -> Result<Self, DbError> {
         // Build lookup map once: O(m) where m = number of headers
         let header_map: HashMap<&str, usize> = headers
@@ -255,6 +357,8 @@ impl CallRowLayout {
             file_idx: find("file")?,
             line_idx: find("call_line")?,
             call_type_idx: header_map.get("call_type").copied(),
+            call_kind_idx: header_map.get("call_kind").copied(),
+            weight_idx: header_map.get("weight").copied(),
         })
     }
 }
@@ -262,23 +366,24 @@ impl CallRowLayout {
 /// Extract call data from a query result row
 ///
 /// Returns Option<Call> if all required fields are present. Uses early return
-/// (None) if any required string field cannot be extracted.
-pub fn extract_call_from_row(row: &[DataValue], layout: &CallRowLayout) -> Option<Call> {
+/// (None) if any required string field cannot be extracted. Names and files
+/// come from `interner`, so rows of one result share them.
+pub fn extract_call_from_row(row: &[DataValue], layout: &CallRowLayout, interner: &mut Interner) -> Option<Call> {
     // Extract caller information
-    let caller_module = extract_string(&row[layout.caller_module_idx])?;
-    let caller_name = extract_string(&row[layout.caller_name_idx])?;
+    let caller_module = extract_shared(&row[layout.caller_module_idx], interner)?;
+    let caller_name = extract_shared(&row[layout.caller_name_idx], interner)?;
     let caller_arity = extract_i64(&row[layout.caller_arity_idx], 0);
-    let caller_kind = extract_string_or(&row[layout.caller_kind_idx], "");
+    let caller_kind = extract_shared_or(&row[layout.caller_kind_idx], "", interner);
     let caller_start_line = extract_i64(&row[layout.caller_start_line_idx], 0);
     let caller_end_line = extract_i64(&row[layout.caller_end_line_idx], 0);
 
     // Extract callee information
-    let callee_module = extract_string(&row[layout.callee_module_idx])?;
-    let callee_name = extract_string(&row[layout.callee_name_idx])?;
+    let callee_module = extract_shared(&row[layout.callee_module_idx], interner)?;
+    let callee_name = extract_shared(&row[layout.callee_name_idx], interner)?;
     let callee_arity = extract_i64(&row[layout.callee_arity_idx], 0);
 
     // Extract file and line
-    let file = extract_string(&row[layout.file_idx])?;
+    let file = extract_shared(&row[layout.file_idx], interner)?;
     let line = extract_i64(&row[layout.line_idx], 0);
 
     // Extract optional call_type
@@ -290,22 +395,20 @@ pub fn extract_call_from_row(row: &[DataValue], layout: &CallRowLayout) -> Optio
         }
     });
 
-    // Create FunctionRef objects with Rc<str> to reduce memory allocations
+    let call_kind = layout.call_kind_idx.and_then(|idx| row.get(idx)).map(|v| extract_string_or(v, "call"));
+    let weight = layout.weight_idx.and_then(|idx| row.get(idx)).map(|v| extract_i64(v, 1));
+
     let caller = FunctionRef::with_definition(
-        Rc::from(caller_module.into_boxed_str()),
-        Rc::from(caller_name.into_boxed_str()),
+        caller_module,
+        caller_name,
         caller_arity,
-        Rc::from(caller_kind.into_boxed_str()),
-        Rc::from(file.into_boxed_str()),
+        caller_kind,
+        file,
         caller_start_line,
         caller_end_line,
     );
 
-    let callee = FunctionRef::new(
-        Rc::from(callee_module.into_boxed_str()),
-        Rc::from(callee_name.into_boxed_str()),
-        callee_arity,
-    );
+    let callee = FunctionRef::new(callee_module, callee_name, callee_arity);
 
     // Return Call
     Some(Call {
@@ -313,10 +416,184 @@ pub fn extract_call_from_row(row: &[DataValue], layout: &CallRowLayout) -> Optio
         callee,
         line,
         call_type,
+        call_kind,
         depth: None,
+        weight,
     })
 }
 
+/// A Rust value read from one result cell.
+///
+/// Strings are moved out of the cell. Numbers accept ints and floats, like
+/// `extract_i64`; a cell of another type does not convert.
+pub trait FromValue: Sized {
+    fn from_value(value: DataValue) -> Option<Self>;
+}
+
+impl FromValue for DataValue {
+    fn from_value(value: DataValue) -> Option<Self> {
+        Some(value)
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: DataValue) -> Option<Self> {
+        match value {
+            DataValue::Str(s) => Some(s.into()),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: DataValue) -> Option<Self> {
+        match value {
+            DataValue::Num(Num::Int(i)) => Some(i),
+            DataValue::Num(Num::Float(f)) => Some(f as i64),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(value: DataValue) -> Option<Self> {
+        match value {
+            DataValue::Num(Num::Int(i)) => Some(i as f64),
+            DataValue::Num(Num::Float(f)) => Some(f),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: DataValue) -> Option<Self> {
+        match value {
+            DataValue::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+/// A null cell is `None`; any other cell must convert to `T`
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: DataValue) -> Option<Self> {
+        match value {
+            DataValue::Null => Some(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+}
+
+/// A Rust value read from a whole result row.
+///
+/// Tuples read cells by position; structs declared with `from_row!` read them
+/// by column name.
+pub trait FromRow: Sized {
+    /// Columns the values are read from, in order; `None` reads the row's cells by position
+    const COLUMNS: Option<&'static [&'static str]> = None;
+
+    /// Build a value from its cells, in `COLUMNS` order. `None` if a cell is
+    /// missing or does not convert.
+    fn from_cells<I: Iterator<Item = DataValue>>(cells: &mut I) -> Option<Self>;
+}
+
+macro_rules! tuple_from_row {
+    ($($t:ident),+) => {
+        impl<$($t: FromValue),+> FromRow for ($($t,)+) {
+            fn from_cells<I: Iterator<Item = DataValue>>(cells: &mut I) -> Option<Self> {
+                Some(($($t::from_value(cells.next()?)?,)+))
+            }
+        }
+    };
+}
+
+tuple_from_row!(A);
+tuple_from_row!(A, B);
+tuple_from_row!(A, B, C);
+tuple_from_row!(A, B, C, D);
+tuple_from_row!(A, B, C, D, E);
+tuple_from_row!(A, B, C, D, E, F);
+tuple_from_row!(A, B, C, D, E, F, G);
+tuple_from_row!(A, B, C, D, E, F, G, H);
+tuple_from_row!(A, B, C, D, E, F, G, H, J);
+tuple_from_row!(A, B, C, D, E, F, G, H, J, K);
+tuple_from_row!(A, B, C, D, E, F, G, H, J, K, L);
+tuple_from_row!(A, B, C, D, E, F, G, H, J, K, L, M);
+
+/// Implement `FromRow` for a struct, reading each field from the column of the
+/// same name, or of the name given after `=`:
+///
+/// ```
+/// use db::from_row;
+///
+/// struct Location {
+///     name: String,
+///     arity: i64,
+///     line: i64,
+/// }
+///
+/// from_row!(Location { name: String, arity: i64, line: i64 = "min(line)" });
+/// ```
+#[macro_export]
+macro_rules! from_row {
+    ($ty:ident { $($field:ident: $fty:ty $(= $column:literal)?),+ $(,)? }) => {
+        impl $crate::db::FromRow for $ty {
+            const COLUMNS: Option<&'static [&'static str]> =
+                Some(&[$($crate::from_row!(@column $field $($column)?)),+]);
+
+            fn from_cells<I: Iterator<Item = $crate::DataValue>>(cells: &mut I) -> Option<Self> {
+                Some(Self {
+                    $($field: <$fty as $crate::db::FromValue>::from_value(cells.next()?)?),+
+                })
+            }
+        }
+    };
+    (@column $field:ident $column:literal) => { $column };
+    (@column $field:ident) => { stringify!($field) };
+}
+
+/// Bulk conversion of a query result into typed rows
+pub trait TypedRows {
+    /// Convert every row into `T`, skipping rows whose cells are missing or do
+    /// not convert. Errors if a column named by `T` is not in the result.
+    fn into_typed<T: FromRow>(self) -> Result<Vec<T>, DbError>;
+}
+
+impl TypedRows for NamedRows {
+    fn into_typed<T: FromRow>(self) -> Result<Vec<T>, DbError> {
+        let Some(columns) = T::COLUMNS else {
+            return Ok(self
+                .rows
+                .into_iter()
+                .filter_map(|row| T::from_cells(&mut row.into_iter()))
+                .collect());
+        };
+
+        let indices = columns
+            .iter()
+            .map(|name| {
+                self.headers
+                    .iter()
+                    .position(|header| header == name)
+                    .ok_or_else(|| DbError::MissingColumn {
+                        name: name.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<usize>, DbError>>()?;
+
+        Ok(self
+            .rows
+            .into_iter()
+            .filter_map(|mut row| {
+                let mut cells = indices
+                    .iter()
+                    .map(|&i| std::mem::replace(&mut row[i], DataValue::Null));
+                T::from_cells(&mut cells)
+            })
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -392,6 +669,30 @@ mod tests {
         assert_eq!(extract_bool(&value, false), false);
     }
 
+    #[rstest]
+    fn test_ping_reads_storage() {
+        let db = open_mem_db();
+        assert!(ping(&db).is_err(), "no schema to read");
+        crate::queries::schema::create_schema(&db).unwrap();
+        assert!(ping(&db).is_ok());
+    }
+
+    #[rstest]
+    fn test_outdated_schema_errors_point_at_repair() {
+        let db = open_mem_db();
+        crate::queries::schema::create_schema(&db).unwrap();
+        let err = run_query_no_params(&db, "?[nope] := *modules{nope}").unwrap_err();
+        assert!(!err.to_string().contains(OUTDATED_SCHEMA_HINT), "the schema is current");
+
+        run_query_no_params(&db, "::remove modules").unwrap();
+        run_query_no_params(&db, ":create modules { project: String, name: String => file: String }").unwrap();
+        let err = run_query_no_params(&db, "?[app] := *modules{app}").unwrap_err();
+        assert!(err.to_string().contains(OUTDATED_SCHEMA_HINT), "{}", err);
+        let err = run_query_no_params(&db, "?[project, name, file, app] <- [['p', 'A', 'a.ex', 'app']] :put modules {project, name => file, app}")
+            .unwrap_err();
+        assert!(err.to_string().contains(OUTDATED_SCHEMA_HINT), "{}", err);
+    }
+
     // CallRowLayout::from_headers tests
 
     fn standard_headers() -> Vec<String> {
@@ -507,4 +808,77 @@ mod tests {
             "Missing column 'caller_name' in query result"
         );
     }
+
+    #[rstest]
+    fn test_strip_arity() {
+        assert_eq!(strip_arity("format/1"), "format");
+        assert_eq!(strip_arity("format"), "format");
+        assert_eq!(strip_arity("/"), "/");
+        assert_eq!(strip_arity("//2"), "/");
+    }
+
+    fn str(s: &str) -> DataValue {
+        DataValue::Str(s.into())
+    }
+
+    #[rstest]
+    fn test_into_typed_tuples_skip_rows_that_do_not_convert() {
+        let rows = NamedRows::new(
+            vec!["name".to_string(), "arity".to_string(), "doc".to_string()],
+            vec![
+                vec![str("run"), DataValue::Num(Num::Int(1)), DataValue::Null],
+                vec![DataValue::Null, DataValue::Num(Num::Int(2)), DataValue::Null],
+                vec![str("stop"), DataValue::Num(Num::Float(0.0)), str("Stops")],
+                vec![str("short")],
+            ],
+        );
+
+        let typed: Vec<(String, i64, Option<String>)> = rows.into_typed().unwrap();
+        assert_eq!(
+            typed,
+            [
+                ("run".to_string(), 1, None),
+                ("stop".to_string(), 0, Some("Stops".to_string())),
+            ]
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Named {
+        module: String,
+        line: i64,
+    }
+
+    crate::from_row!(Named { module: String, line: i64 = "min(line)" });
+
+    #[rstest]
+    fn test_into_typed_struct_reads_columns_by_name() {
+        let rows = NamedRows::new(
+            vec!["min(line)".to_string(), "extra".to_string(), "module".to_string()],
+            vec![vec![DataValue::Num(Num::Int(7)), DataValue::Bool(true), str("MyApp")]],
+        );
+
+        let typed: Vec<Named> = rows.into_typed().unwrap();
+        assert_eq!(typed, [Named { module: "MyApp".to_string(), line: 7 }]);
+    }
+
+    #[rstest]
+    fn test_into_typed_struct_missing_column() {
+        let rows = NamedRows::new(vec!["module".to_string()], vec![]);
+
+        let err = rows.into_typed::<Named>().unwrap_err();
+        assert_eq!(err.to_string(), "Missing column 'min(line)' in query result");
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_escaped_strings_round_trip_through_cozo(s in crate::test_utils::name_strategy()) {
+            let db = open_mem_db();
+            let script = format!("?[s] <- [['{}']]", escape_string_single(&s));
+            let rows = run_query_no_params(&db, &script).unwrap();
+            proptest::prop_assert_eq!(extract_string(&rows.rows[0][0]), Some(s));
+        }
+    }
 }
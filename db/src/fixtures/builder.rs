@@ -0,0 +1,244 @@
+//! Call graphs for tests, built in Rust rather than written as JSON.
+
+use std::error::Error;
+
+use cozo::DbInstance;
+use serde_json::{json, Map, Value};
+
+use crate::queries::import::{import_graph, ImportResult};
+use crate::queries::import_models::CallGraph;
+
+/// A call graph under construction.
+///
+/// Functions belong to the module last named with [`module`](Self::module)
+/// and get consecutive 10-line spans in its file; calls from a known
+/// function take the lines after the caller's first line, one per call.
+///
+/// ```
+/// use db::fixtures::FixtureGraph;
+///
+/// let graph = FixtureGraph::new()
+///     .module("A")
+///     .function("f", 1)
+///     .module("B")
+///     .function("g", 2)
+///     .calls("A", "f", 1, "B", "g", 2);
+/// assert!(graph.to_json().contains("\"B\""));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct FixtureGraph {
+    /// Modules in the order they were named, with their file and functions
+    modules: Vec<FixtureModule>,
+    calls: Vec<Value>,
+}
+
+#[derive(Debug, Clone)]
+struct FixtureModule {
+    name: String,
+    file: String,
+    /// Name, arity, kind and first line of each function
+    functions: Vec<(String, u32, String, u32)>,
+}
+
+impl FixtureGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a module, or return to one already added; its file is derived
+    /// from the name: `MyApp.Accounts` is `lib/my_app/accounts.ex`
+    pub fn module(mut self, name: &str) -> Self {
+        match self.modules.iter().position(|m| m.name == name) {
+            Some(i) => {
+                let module = self.modules.remove(i);
+                self.modules.push(module);
+            }
+            None => self.modules.push(FixtureModule {
+                name: name.to_string(),
+                file: module_file(name),
+                functions: Vec::new(),
+            }),
+        }
+        self
+    }
+
+    /// Set the file of the current module
+    pub fn file(mut self, file: &str) -> Self {
+        self.current().file = file.to_string();
+        self
+    }
+
+    /// Add a public function to the current module
+    pub fn function(self, name: &str, arity: u32) -> Self {
+        self.function_of_kind(name, arity, "def")
+    }
+
+    /// Add a private function to the current module
+    pub fn private_function(self, name: &str, arity: u32) -> Self {
+        self.function_of_kind(name, arity, "defp")
+    }
+
+    /// Add a function of any kind (`def`, `defp`, `defmacro`, ...) to the current module
+    pub fn function_of_kind(mut self, name: &str, arity: u32, kind: &str) -> Self {
+        let module = self.current();
+        let line = 10 * module.functions.len() as u32 + 1;
+        module.functions.push((name.to_string(), arity, kind.to_string(), line));
+        self
+    }
+
+    /// Add a remote call from `caller_module.caller_function/caller_arity` to
+    /// `callee_module.callee_function/callee_arity`
+    pub fn calls(
+        mut self,
+        caller_module: &str,
+        caller_function: &str,
+        caller_arity: u32,
+        callee_module: &str,
+        callee_function: &str,
+        callee_arity: u32,
+    ) -> Self {
+        let caller = self.modules.iter().find(|m| m.name == caller_module);
+        let file = caller.map_or_else(|| module_file(caller_module), |m| m.file.clone());
+        let caller_key = format!("{}/{}", caller_function, caller_arity);
+        let earlier = self
+            .calls
+            .iter()
+            .filter(|call| call["caller"]["module"] == caller_module && call["caller"]["function"] == caller_key.as_str())
+            .count() as u32;
+        let line = caller
+            .and_then(|m| m.functions.iter().find(|f| f.0 == caller_function && f.1 == caller_arity))
+            .map_or(1, |f| f.3)
+            + 1
+            + earlier;
+        let call_type = if caller_module == callee_module { "local" } else { "remote" };
+        self.calls.push(json!({
+            "caller": {
+                "module": caller_module,
+                "function": caller_key,
+                "file": file,
+                "line": line
+            },
+            "callee": {"module": callee_module, "function": callee_function, "arity": callee_arity},
+            "type": call_type
+        }));
+        self
+    }
+
+    /// The graph in the import JSON format
+    pub fn to_json(&self) -> String {
+        self.to_value().to_string()
+    }
+
+    /// The graph as the importer reads it
+    pub fn to_call_graph(&self) -> CallGraph {
+        serde_json::from_value(self.to_value()).expect("Fixture graph should deserialize")
+    }
+
+    /// Import the graph into `db` under `project`, without going through JSON text
+    pub fn import(&self, db: &DbInstance, project: &str) -> Result<ImportResult, Box<dyn Error>> {
+        import_graph(db, project, &self.to_call_graph())
+    }
+
+    /// An in-memory database holding the graph under `project`
+    pub fn db(&self, project: &str) -> DbInstance {
+        let db = crate::db::open_mem_db();
+        self.import(&db, project).expect("Import should succeed");
+        db
+    }
+
+    fn current(&mut self) -> &mut FixtureModule {
+        self.modules.last_mut().expect("Name a module before adding to it")
+    }
+
+    fn to_value(&self) -> Value {
+        let mut function_locations = Map::new();
+        for module in &self.modules {
+            let functions: Map<String, Value> = module
+                .functions
+                .iter()
+                .map(|(name, arity, kind, line)| {
+                    let location = json!({
+                        "name": name,
+                        "arity": arity,
+                        "file": module.file,
+                        "kind": kind,
+                        "line": line,
+                        "start_line": line,
+                        "end_line": line + 8
+                    });
+                    (format!("{}/{}:{}", name, arity, line), location)
+                })
+                .collect();
+            function_locations.insert(module.name.clone(), Value::Object(functions));
+        }
+        json!({
+            "structs": {},
+            "function_locations": function_locations,
+            "calls": self.calls
+        })
+    }
+}
+
+/// `MyApp.Accounts` -> `lib/my_app/accounts.ex`
+fn module_file(module: &str) -> String {
+    let segments: Vec<String> = module.split('.').map(snake_case).collect();
+    format!("lib/{}.ex", segments.join("/"))
+}
+
+fn snake_case(segment: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in segment.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::calls_from::find_calls_from;
+    use crate::query_builders::QuerySettings;
+
+    #[test]
+    fn test_builder_imports_functions_and_calls() {
+        let db = FixtureGraph::new()
+            .module("MyApp.Accounts")
+            .function("get_user", 1)
+            .private_function("normalize", 1)
+            .module("MyApp.Repo")
+            .function("get", 2)
+            .calls("MyApp.Accounts", "get_user", 1, "MyApp.Repo", "get", 2)
+            .calls("MyApp.Accounts", "get_user", 1, "MyApp.Accounts", "normalize", 1)
+            .db("default");
+
+        let calls = find_calls_from(&db, "MyApp.Accounts", None, None, &[], "default", false, 10, &QuerySettings::default()).unwrap();
+        let mut callees: Vec<String> = calls.iter().map(|c| format!("{}.{}", c.callee.module, c.callee.name)).collect();
+        callees.sort();
+        assert_eq!(callees, ["MyApp.Accounts.normalize", "MyApp.Repo.get"]);
+        assert_eq!(calls[0].caller.file.as_deref(), Some("lib/my_app/accounts.ex"));
+        assert_eq!(calls[0].line, 2);
+    }
+
+    #[test]
+    fn test_returning_to_a_module_appends_functions() {
+        let graph = FixtureGraph::new()
+            .module("A")
+            .function("f", 0)
+            .module("B")
+            .module("A")
+            .function("g", 0)
+            .to_call_graph();
+
+        let mut lines: Vec<u32> = graph.function_locations["A"].values().map(|l| l.start_line).collect();
+        lines.sort();
+        assert_eq!(lines, [1, 11]);
+        assert!(graph.function_locations["B"].is_empty());
+    }
+}
@@ -8,6 +8,9 @@
 //! - [`CALL_GRAPH`] - Function locations and call relationships
 //! - [`TYPE_SIGNATURES`] - Function type signatures
 //! - [`STRUCTS`] - Struct definitions with fields
+//! - [`UMBRELLA`] - Call graph of an umbrella project with three apps
+//! - [`synthetic_call_graph`] - Generated call graph of any size, for benchmarks
+//! - [`FixtureGraph`] - Builder for small call graphs written in the test itself
 //!
 //! ## Usage
 //!
@@ -21,15 +24,24 @@
 //! }
 //! ```
 
+mod builder;
+mod synthetic;
+
+pub use builder::FixtureGraph;
+
+pub use synthetic::{synthetic_call_graph, synthetic_function, synthetic_module, GraphSize};
+
 /// Call graph fixture with function locations and call relationships.
 ///
 /// Contains:
 /// - 5 modules: Controller, Accounts, Service, Repo, Notifier
 /// - 15 functions with various arities and kinds (def/defp)
 /// - 11 call edges forming a realistic call graph
+/// - A supervision tree: Application supervising Repo, Notifier and a
+///   WorkerSupervisor, which supervises Service
 ///
 /// Use for: trace, reverse_trace, calls_from, calls_to, path, hotspots,
-/// unused, depends_on, depended_by
+/// unused, depends_on, depended_by, supervision
 pub const CALL_GRAPH: &str = include_str!("call_graph.json");
 
 /// Type signatures fixture with function specs.
@@ -46,10 +58,22 @@ pub const TYPE_SIGNATURES: &str = include_str!("type_signatures.json");
 /// Contains:
 /// - 3 structs: User, Post, Comment
 /// - Various field types with defaults and required flags
+/// - 6 field accesses: User.email (2 reads, 1 write), User.name, Post.status
+///   (write) and Post.title
 ///
-/// Use for: struct command
+/// Use for: struct command, struct-fields-usage
 pub const STRUCTS: &str = include_str!("structs.json");
 
+/// Umbrella project fixture with modules under `apps/<app>/`.
+///
+/// Contains:
+/// - 3 apps: core (Accounts, Repo), web (UserController), billing (Invoice)
+/// - 9 functions, 7 calls; web and billing call into core, core calls
+///   neither
+///
+/// Use for: apps, --app filters
+pub const UMBRELLA: &str = include_str!("umbrella.json");
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,4 +95,10 @@ mod tests {
         let _: serde_json::Value = serde_json::from_str(STRUCTS)
             .expect("STRUCTS should be valid JSON");
     }
+
+    #[test]
+    fn test_umbrella_is_valid_json() {
+        let _: serde_json::Value = serde_json::from_str(UMBRELLA)
+            .expect("UMBRELLA should be valid JSON");
+    }
 }
@@ -0,0 +1,128 @@
+//! Generated call graphs of a chosen size, for benchmarks.
+
+use serde_json::{json, Map, Value};
+
+/// Shape of a generated call graph
+#[derive(Debug, Clone, Copy)]
+pub struct GraphSize {
+    pub modules: usize,
+    pub functions_per_module: usize,
+    /// Outgoing calls of each function
+    pub calls_per_function: usize,
+}
+
+impl GraphSize {
+    pub fn function_count(&self) -> usize {
+        self.modules * self.functions_per_module
+    }
+
+    pub fn call_count(&self) -> usize {
+        self.function_count() * self.calls_per_function
+    }
+}
+
+/// Module `i` of a generated graph
+pub fn synthetic_module(i: usize) -> String {
+    format!("Bench.Module{}", i)
+}
+
+/// Function `j` of every module of a generated graph; all have arity 1
+pub fn synthetic_function(j: usize) -> String {
+    format!("fun_{}", j)
+}
+
+/// A call graph JSON in the extracted format, with `size.modules` modules of
+/// `size.functions_per_module` functions each.
+///
+/// Module `i` only calls modules `i + 1` to `i + 8`, wrapping around, so call
+/// chains run deep across the graph rather than fanning out at once. The
+/// graph depends only on `size`.
+pub fn synthetic_call_graph(size: GraphSize) -> String {
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed as usize
+    };
+
+    let mut function_locations = Map::new();
+    let mut calls = Vec::with_capacity(size.call_count());
+    for i in 0..size.modules {
+        let module = synthetic_module(i);
+        let file = format!("lib/bench/module{}.ex", i);
+
+        let mut functions = Map::new();
+        for j in 0..size.functions_per_module {
+            let name = synthetic_function(j);
+            let start_line = 10 * j + 1;
+            let end_line = start_line + 8;
+            functions.insert(
+                format!("{}/1:{}", name, start_line),
+                json!({
+                    "file": file,
+                    "column": 3,
+                    "kind": if j % 4 == 3 { "defp" } else { "def" },
+                    "line": start_line,
+                    "start_line": start_line,
+                    "end_line": end_line,
+                    "pattern": "arg",
+                    "guard": null,
+                    "source_sha": "",
+                    "ast_sha": "",
+                    "name": name,
+                    "arity": 1
+                }),
+            );
+
+            for k in 0..size.calls_per_function {
+                let callee_module = (i + 1 + next() % 8) % size.modules;
+                let callee_function = next() % size.functions_per_module;
+                calls.push(json!({
+                    "caller": {
+                        "module": module,
+                        "function": format!("{}/1", name),
+                        "file": file,
+                        "line": start_line + 1 + k % 7
+                    },
+                    "callee": {
+                        "module": synthetic_module(callee_module),
+                        "function": synthetic_function(callee_function),
+                        "arity": 1
+                    },
+                    "type": "remote"
+                }));
+            }
+        }
+        function_locations.insert(module, Value::Object(functions));
+    }
+
+    json!({
+        "structs": {},
+        "function_locations": function_locations,
+        "calls": calls
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::import_models::CallGraph;
+
+    #[test]
+    fn test_synthetic_call_graph_has_requested_size() {
+        let size = GraphSize {
+            modules: 12,
+            functions_per_module: 5,
+            calls_per_function: 3,
+        };
+        let json = synthetic_call_graph(size);
+        let graph: CallGraph = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(graph.function_locations.len(), 12);
+        assert_eq!(graph.function_locations.values().map(|f| f.len()).sum::<usize>(), 60);
+        assert_eq!(graph.calls.len(), size.call_count());
+        assert_eq!(json, synthetic_call_graph(size));
+    }
+}
@@ -0,0 +1,176 @@
+//! Rules telling generated functions from hand-written ones.
+//!
+//! Commands with `--exclude-generated` (or `--include-generated`) leave out
+//! the functions these rules match. A function is generated when its name
+//! matches a name pattern, its file a file pattern, or the macro recorded in
+//! its `generated_by` attribute a generator pattern. The built-in rules match
+//! every function with a `generated_by` attribute and Elixir's compiler
+//! callbacks such as `__struct__` and `__info__`; the CLI extends them from
+//! the `generated` section of the config file.
+
+use std::error::Error;
+
+use serde::Deserialize;
+
+use crate::sanitize::literal;
+use crate::query_builders::{compile_pattern, path_glob_to_regex};
+
+/// Names of the functions the Elixir compiler and common macros define
+const DEFAULT_NAME_PATTERN: &str = "^__(struct|using|before_compile|after_compile|on_definition|impl|info|protocol|deriving|changeset|schema|meta)__";
+
+/// Any macro recorded in `generated_by`
+const DEFAULT_GENERATOR_PATTERN: &str = ".";
+
+/// Patterns identifying generated functions
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct GeneratedRules {
+    /// Keep the built-in rules (any `generated_by` macro, compiler callbacks)
+    pub include_defaults: bool,
+    /// Regexes matched against function names
+    pub names: Vec<String>,
+    /// Globs of source files holding generated code, such as `*.pb.ex`
+    pub files: Vec<String>,
+    /// Regexes matched against the macro a function was generated by
+    pub generators: Vec<String>,
+}
+
+impl Default for GeneratedRules {
+    fn default() -> Self {
+        Self {
+            include_defaults: true,
+            names: Vec::new(),
+            files: Vec::new(),
+            generators: Vec::new(),
+        }
+    }
+}
+
+impl GeneratedRules {
+    /// Check that every pattern compiles
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        for pattern in self.names.iter().chain(&self.generators) {
+            compile_pattern(pattern).map_err(|e| format!("Invalid generated code pattern '{}': {}", pattern, e))?;
+        }
+        for glob in &self.files {
+            compile_pattern(&path_glob_to_regex(glob))
+                .map_err(|e| format!("Invalid generated file pattern '{}': {}", glob, e))?;
+        }
+        Ok(())
+    }
+
+    /// Whether a function with this name, file and `generated_by` is generated
+    pub fn is_generated(&self, name: &str, file: &str, generated_by: &str) -> bool {
+        let matches = |pattern: Option<String>, value: &str| {
+            pattern.is_some_and(|pattern| compile_pattern(&pattern).is_ok_and(|regex| regex.is_match(value)))
+        };
+        matches(self.name_pattern(), name)
+            || matches(self.file_pattern(), file)
+            || matches(self.generator_pattern(), generated_by)
+    }
+
+    /// CozoScript condition keeping the functions these rules do not match.
+    ///
+    /// Takes the variables bound to the function's name, file and
+    /// `generated_by`, and starts with a comma. Empty when no rule applies.
+    ///
+    /// # Examples
+    /// ```
+    /// use db::generated::GeneratedRules;
+    ///
+    /// let rules = GeneratedRules { include_defaults: false, names: vec!["^grpc_".into()], ..Default::default() };
+    /// assert_eq!(rules.condition("name", "file", "generated_by"), ", !(regex_matches(name, '^grpc_'))");
+    /// ```
+    pub fn condition(&self, name_var: &str, file_var: &str, generated_by_var: &str) -> String {
+        let tests: Vec<String> = [
+            (name_var, self.name_pattern()),
+            (file_var, self.file_pattern()),
+            (generated_by_var, self.generator_pattern()),
+        ]
+        .into_iter()
+        .filter_map(|(var, pattern)| {
+            pattern.map(|pattern| format!("regex_matches({}, {})", var, literal(&pattern)))
+        })
+        .collect();
+
+        if tests.is_empty() {
+            String::new()
+        } else {
+            format!(", !({})", tests.join(" || "))
+        }
+    }
+
+    fn name_pattern(&self) -> Option<String> {
+        let default = self.include_defaults.then_some(DEFAULT_NAME_PATTERN.to_string());
+        alternation(default.into_iter().chain(self.names.iter().cloned()))
+    }
+
+    fn file_pattern(&self) -> Option<String> {
+        alternation(self.files.iter().map(|glob| path_glob_to_regex(glob)))
+    }
+
+    fn generator_pattern(&self) -> Option<String> {
+        let default = self.include_defaults.then_some(DEFAULT_GENERATOR_PATTERN.to_string());
+        alternation(default.into_iter().chain(self.generators.iter().cloned()))
+    }
+}
+
+/// Join regexes into one matching any of them
+fn alternation(patterns: impl Iterator<Item = String>) -> Option<String> {
+    let patterns: Vec<String> = patterns.collect();
+    match patterns.as_slice() {
+        [] => None,
+        [pattern] => Some(pattern.clone()),
+        _ => Some(patterns.iter().map(|pattern| format!("(?:{})", pattern)).collect::<Vec<_>>().join("|")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rules() {
+        let rules = GeneratedRules::default();
+        assert!(rules.is_generated("__struct__", "lib/user.ex", ""));
+        assert!(rules.is_generated("changeset", "lib/user.ex", "Ecto.Schema"));
+        assert!(!rules.is_generated("changeset", "lib/user.ex", ""));
+        assert!(!rules.is_generated("__helper", "lib/user.ex", ""));
+    }
+
+    #[test]
+    fn test_configured_rules() {
+        let rules: GeneratedRules = serde_json::from_str(
+            r#"{"include_defaults": false, "names": ["^grpc_"], "files": ["lib/proto"], "generators": ["^Phoenix\\."]}"#,
+        )
+        .unwrap();
+        assert!(rules.is_generated("grpc_call", "lib/user.ex", ""));
+        assert!(rules.is_generated("encode", "lib/proto/user.pb.ex", ""));
+        assert!(rules.is_generated("call", "lib/router.ex", "Phoenix.Router"));
+        assert!(!rules.is_generated("__struct__", "lib/user.ex", "Ecto.Schema"));
+
+        let none = GeneratedRules { include_defaults: false, ..Default::default() };
+        assert_eq!(none.condition("name", "file", "generated_by"), "");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(GeneratedRules::default().validate().is_ok());
+        let invalid = GeneratedRules { names: vec!["(".to_string()], ..Default::default() };
+        assert!(invalid.validate().unwrap_err().to_string().contains("Invalid generated code pattern"));
+    }
+
+    #[test]
+    fn test_condition_in_query() {
+        let db = crate::test_utils::call_graph_db("default");
+        let rules = GeneratedRules { include_defaults: false, names: vec!["^list_".to_string()], ..Default::default() };
+        let script = format!(
+            "?[name] := *function_locations{{project: 'default', module: 'MyApp.Accounts', name, file, generated_by}}{}",
+            rules.condition("name", "file", "generated_by")
+        );
+        let rows = crate::db::run_query_no_params(&db, &script).unwrap();
+        let names: Vec<String> = rows.rows.iter().filter_map(|row| crate::db::extract_string(&row[0])).collect();
+        assert!(!names.is_empty());
+        assert!(names.iter().all(|name| !name.starts_with("list_")));
+    }
+}
@@ -3,19 +3,29 @@
 pub mod db;
 pub mod types;
 pub mod query_builders;
+pub mod where_filter;
+pub mod type_pattern;
+pub mod generated;
+pub mod sanitize;
 pub mod queries;
 
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 #[cfg(feature = "test-utils")]
 pub mod test_utils;
 
 #[cfg(feature = "test-utils")]
 pub mod fixtures;
 
+#[cfg(feature = "test-utils")]
+pub mod test_suite;
+
 // Re-export commonly used items
-pub use db::{open_db, run_query, run_query_no_params, DbError, Params};
-pub use cozo::DbInstance;
+pub use db::{open_db, ping, run_query, run_query_no_params, DbError, Params};
+pub use cozo::{DataValue, DbInstance};
 
-#[cfg(any(test, feature = "test-utils"))]
+#[cfg(any(test, feature = "test-utils", feature = "wasm"))]
 pub use db::open_mem_db;
 
 pub use types::{
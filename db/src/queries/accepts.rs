@@ -5,11 +5,12 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+use crate::type_pattern::TypePattern;
 
 #[derive(Error, Debug)]
 pub enum AcceptsError {
-    #[error("Accepts query failed: {message}")]
+    #[error("Signature query failed: {message}")]
     QueryFailed { message: String },
 }
 
@@ -25,39 +26,90 @@ pub struct AcceptsEntry {
     pub line: i64,
 }
 
+/// Find functions with a parameter matching `pattern`.
+///
+/// Without `use_regex` the pattern is a [`TypePattern`] matched against each
+/// parameter's type; with it, a regex over all the input types. With
+/// `returns_pattern`, the return type has to match it as well, which finds
+/// the conversions from one type to another.
+#[allow(clippy::too_many_arguments)]
 pub fn find_accepts(
     db: &cozo::DbInstance,
     pattern: &str,
+    returns_pattern: Option<&str>,
     project: &str,
     use_regex: bool,
     module_pattern: Option<&str>,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<AcceptsEntry>, Box<dyn Error>> {
-    validate_regex_patterns(use_regex, &[Some(pattern), module_pattern])?;
+    find_signatures(db, Some(pattern), returns_pattern, project, use_regex, module_pattern, limit, settings)
+}
+
+/// Find specs whose inputs match `accepts` and whose return matches `returns`.
+///
+/// Regexes are matched by the query; type patterns are matched here, on every
+/// spec of the project, before the limit applies.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn find_signatures(
+    db: &cozo::DbInstance,
+    accepts: Option<&str>,
+    returns: Option<&str>,
+    project: &str,
+    use_regex: bool,
+    module_pattern: Option<&str>,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<AcceptsEntry>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[accepts, returns, module_pattern])?;
+    let patterns = if use_regex {
+        None
+    } else {
+        let parse = |pattern: Option<&str>| pattern.map(str::parse::<TypePattern>).transpose();
+        Some((parse(accepts)?, parse(returns)?))
+    };
 
     // Build conditions using query builders
-    let pattern_cond = ConditionBuilder::new("inputs_string", "pattern").build(use_regex);
+    let accepts_cond = OptionalConditionBuilder::new("inputs_string", "pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(use_regex && accepts.is_some(), true);
+    let returns_cond = OptionalConditionBuilder::new("return_string", "returns_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(use_regex && returns.is_some(), true);
     let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+    let limit_clause = if use_regex { format!(":limit {limit}") } else { String::new() };
 
     let script = format!(
         r#"
         ?[project, module, name, arity, inputs_string, return_string, line] :=
             *specs{{project, module, name, arity, inputs_string, return_string, line}},
-            project == $project,
-            {pattern_cond}
+            project == $project
+            {accepts_cond}
+            {returns_cond}
             {module_cond}
+            {namespace_cond}
 
         :order module, name, arity
-        :limit {limit}
+        {limit_clause}
         "#,
     );
 
     let mut params = Params::new();
-    params.insert("pattern", DataValue::Str(pattern.into()));
     params.insert("project", DataValue::Str(project.into()));
+    if use_regex {
+        if let Some(accepts) = accepts {
+            params.insert("pattern", DataValue::Str(accepts.into()));
+        }
+        if let Some(returns) = returns {
+            params.insert("returns_pattern", DataValue::Str(returns.into()));
+        }
+    }
 
     if let Some(mod_pat) = module_pattern {
         params.insert(
@@ -66,6 +118,8 @@ pub fn find_accepts(
         );
     }
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| AcceptsError::QueryFailed {
         message: e.to_string(),
     })?;
@@ -99,5 +153,54 @@ pub fn find_accepts(
         }
     }
 
+    if let Some((accepts, returns)) = patterns {
+        results.retain(|entry: &AcceptsEntry| {
+            accepts.as_ref().is_none_or(|p| p.accepted_by(&entry.inputs_string))
+                && returns.as_ref().is_none_or(|p| p.returned_by(&entry.return_string))
+        });
+        results.truncate(limit as usize);
+    }
+
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(entries: &[AcceptsEntry]) -> Vec<String> {
+        entries
+            .iter()
+            .map(|e| format!("{}.{}/{}", e.module, e.name, e.arity))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_accepts_structural() {
+        let db = crate::test_utils::call_graph_db("default");
+        let entries = find_accepts(&db, "integer", None, "default", false, None, 100, &QuerySettings::default()).unwrap();
+        assert_eq!(names(&entries), ["MyApp.Accounts.get_user/1"]);
+
+        let entries = find_accepts(&db, "module", None, "default", false, Some("MyApp.Repo"), 100, &QuerySettings::default()).unwrap();
+        assert_eq!(names(&entries), ["MyApp.Repo.get/2"]);
+    }
+
+    #[test]
+    fn test_find_accepts_with_returns() {
+        let db = crate::test_utils::call_graph_db("default");
+        let entries = find_accepts(&db, "integer", Some("{:ok, User.t}"), "default", false, None, 100, &QuerySettings::default()).unwrap();
+        assert_eq!(names(&entries), ["MyApp.Accounts.get_user/1"]);
+
+        let entries = find_accepts(&db, "integer", Some("{:ok, _, _}"), "default", false, None, 100, &QuerySettings::default()).unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_find_signatures_regex() {
+        let db = crate::test_utils::call_graph_db("default");
+        let entries = find_signatures(&db, None, Some(r"\{:error"), "default", true, None, 100, &QuerySettings::default()).unwrap();
+        assert_eq!(names(&entries), ["MyApp.Accounts.get_user/1"]);
+
+        assert!(find_accepts(&db, "(", None, "default", false, None, 100, &QuerySettings::default()).is_err());
+    }
+}
@@ -0,0 +1,849 @@
+//! In-memory adjacency index over the calls and messages relations.
+//!
+//! Loads a project's calls, message edges and function locations once and answers trace,
+//! reverse-trace and path traversals in Rust instead of recursive Datalog.
+//! Each traversal mirrors the matching query in [`super::trace`],
+//! [`super::reverse_trace`] or [`super::path`], including result order and limits,
+//! so the two engines are interchangeable.
+//!
+//! [`cached_index`] keeps the index for the lifetime of the thread, so a process
+//! running many traversals against the same project only loads it once.
+
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::error::Error;
+use std::rc::Rc;
+
+use cozo::DataValue;
+use regex::Regex;
+use thiserror::Error;
+
+use super::path::{build_paths, CallPath, FrontierDirection, PathStep};
+use super::reverse_trace::ReverseTraceStep;
+use crate::db::{run_query, Params, TypedRows};
+use crate::query_builders::{compile_pattern, validate_regex_patterns, QuerySettings};
+use crate::types::{Call, FunctionRef, Interner};
+
+#[derive(Error, Debug)]
+pub enum AdjacencyError {
+    #[error("Adjacency index query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A row of the calls or messages relation
+#[derive(Debug, Clone)]
+struct CallEdge {
+    caller_module: String,
+    caller_function: String,
+    callee_module: String,
+    callee_function: String,
+    callee_arity: i64,
+    file: String,
+    line: i64,
+    /// Call sites between the same caller and callee
+    weight: i64,
+    /// Inside a conditional branch or comprehension
+    conditional: bool,
+    /// Message edge (GenServer call/cast, PubSub broadcast) rather than a call
+    message: bool,
+}
+
+/// A row of the function_locations relation
+#[derive(Debug, Clone)]
+struct Location {
+    name: String,
+    arity: i64,
+    kind: String,
+    start_line: i64,
+    end_line: i64,
+}
+
+/// Call or message row in query column order: caller module/function,
+/// callee module/function/arity, file, line, weight, conditional
+type EdgeRow = (String, String, String, String, i64, String, i64, i64, bool);
+
+/// Trace row in query column order: depth, caller module/name/arity/kind/start/end,
+/// callee module/function/arity, file, line
+type TraceRow = (i64, String, String, i64, String, i64, i64, String, String, i64, String, i64);
+
+/// Path edge in query column order: depth, caller module/function,
+/// callee module/function/arity, file, line, weight
+type PathRow = (i64, String, String, String, String, i64, String, i64, i64);
+
+/// Call edges of one project, indexed for traversal in both directions
+#[derive(Debug, Default)]
+pub struct AdjacencyIndex {
+    edges: Vec<CallEdge>,
+    /// Edge indices by caller module
+    outgoing: HashMap<String, Vec<usize>>,
+    /// Edge indices by callee (module, function)
+    incoming: HashMap<(String, String), Vec<usize>>,
+    /// Function locations by module
+    locations: HashMap<String, Vec<Location>>,
+}
+
+impl AdjacencyIndex {
+    /// Load all calls, message edges and function locations of a project.
+    ///
+    /// Databases created before the messages relation existed load without message edges.
+    pub fn load(db: &cozo::DbInstance, project: &str) -> Result<Self, Box<dyn Error>> {
+        let mut params = Params::new();
+        params.insert("project", DataValue::Str(project.into()));
+
+        let calls_script = r#"
+            ?[caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight, conditional] :=
+                *calls{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight, conditional},
+                project == $project
+            "#;
+        let rows = run_query(db, calls_script, params.clone()).map_err(|e| AdjacencyError::QueryFailed {
+            message: e.to_string(),
+        })?;
+
+        let mut index = Self::default();
+        index.add_edges(rows.into_typed()?, false);
+
+        let messages_script = calls_script.replace("*calls{", "*messages{");
+        if let Ok(rows) = run_query(db, &messages_script, params.clone()) {
+            index.add_edges(rows.into_typed()?, true);
+        }
+
+        let locations_script = r#"
+            ?[module, name, arity, kind, start_line, end_line] :=
+                *function_locations{project, module, name, arity, kind, start_line, end_line},
+                project == $project
+            "#;
+        let rows = run_query(db, locations_script, params).map_err(|e| AdjacencyError::QueryFailed {
+            message: e.to_string(),
+        })?;
+
+        let locations: Vec<(String, String, i64, String, i64, i64)> = rows.into_typed()?;
+        for (module, name, arity, kind, start_line, end_line) in locations {
+            index.locations.entry(module).or_default().push(Location {
+                name,
+                arity,
+                kind,
+                start_line,
+                end_line,
+            });
+        }
+
+        Ok(index)
+    }
+
+    fn add_edges(&mut self, rows: Vec<EdgeRow>, message: bool) {
+        for (caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight, conditional) in rows {
+            let i = self.edges.len();
+            self.outgoing.entry(caller_module.clone()).or_default().push(i);
+            self
+                .incoming
+                .entry((callee_module.clone(), callee_function.clone()))
+                .or_default()
+                .push(i);
+            self.edges.push(CallEdge {
+                caller_module,
+                caller_function,
+                callee_module,
+                callee_function,
+                callee_arity,
+                file,
+                line,
+                weight,
+                conditional,
+                message,
+            });
+        }
+    }
+
+    /// Number of call edges in the index
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// In-memory equivalent of [`super::trace::trace_calls`].
+    ///
+    /// The index cannot resolve the project and test scope of `settings` on
+    /// its own, so `in_scope` decides which callee modules a chain reaches.
+    #[allow(clippy::too_many_arguments)]
+    pub fn trace_calls(
+        &self,
+        module_pattern: &str,
+        function_pattern: &str,
+        arity: Option<i64>,
+        use_regex: bool,
+        max_depth: u32,
+        limit: u32,
+        include_messages: bool,
+        min_weight: i64,
+        certain_only: bool,
+        in_scope: impl Fn(&str) -> bool,
+        settings: &QuerySettings,
+    ) -> Result<Vec<Call>, Box<dyn Error>> {
+        validate_regex_patterns(use_regex, &[Some(module_pattern), Some(function_pattern)])?;
+        let module = Pattern::new(module_pattern, use_regex)?;
+        let function = Pattern::new(function_pattern, use_regex)?;
+
+        let mut level = BTreeSet::new();
+        for edge in &self.edges {
+            if edge.callee_function == "%"
+                || edge.weight < min_weight
+                || (edge.conditional && certain_only)
+                || (edge.message && !include_messages)
+                || !module.matches(&edge.caller_module)
+                || !settings.in_namespaces(&edge.caller_module)
+                || !in_scope(&edge.callee_module)
+            {
+                continue;
+            }
+            for loc in self.enclosing(edge) {
+                if function.matches(&loc.name) && arity.is_none_or(|a| loc.arity == a) {
+                    level.insert(trace_row(1, edge, loc));
+                }
+            }
+        }
+
+        let rows = expand_levels(level, max_depth, |rows, depth| {
+            let frontier: BTreeSet<(&str, &str)> =
+                rows.iter().map(|r| (r.7.as_str(), r.8.as_str())).collect();
+            let mut next = BTreeSet::new();
+            for (module, function) in frontier {
+                for edge in self.calls_from(module, include_messages) {
+                    if edge.callee_function == "%"
+                        || edge.weight < min_weight
+                        || (edge.conditional && certain_only)
+                        || !edge.caller_function.starts_with(function)
+                        || !in_scope(&edge.callee_module)
+                    {
+                        continue;
+                    }
+                    for loc in self.enclosing(edge) {
+                        next.insert(trace_row(depth, edge, loc));
+                    }
+                }
+            }
+            next
+        });
+
+        let mut interner = Interner::default();
+        Ok(sort_trace_rows(rows, limit)
+            .into_iter()
+            .map(|r| {
+                let caller = FunctionRef::with_definition(
+                    interner.intern(&r.1),
+                    interner.intern(&r.2),
+                    r.3,
+                    interner.intern(&r.4),
+                    interner.intern(&r.10),
+                    r.5,
+                    r.6,
+                );
+                let callee = FunctionRef::new(interner.intern(&r.7), interner.intern(&r.8), r.9);
+                Call {
+                    caller,
+                    callee,
+                    line: r.11,
+                    call_type: None,
+                    call_kind: None,
+                    depth: Some(r.0),
+                    weight: None,
+                }
+            })
+            .collect())
+    }
+
+    /// In-memory equivalent of [`super::reverse_trace::reverse_trace_calls`].
+    ///
+    /// As in [`trace_calls`](Self::trace_calls), `in_scope` stands in for the
+    /// project and test scope of `settings`, here applied to the callers.
+    #[allow(clippy::too_many_arguments)]
+    pub fn reverse_trace_calls(
+        &self,
+        module_pattern: &str,
+        function_pattern: &str,
+        arity: Option<i64>,
+        use_regex: bool,
+        max_depth: u32,
+        limit: u32,
+        certain_only: bool,
+        in_scope: impl Fn(&str) -> bool,
+        settings: &QuerySettings,
+    ) -> Result<Vec<ReverseTraceStep>, Box<dyn Error>> {
+        validate_regex_patterns(use_regex, &[Some(module_pattern), Some(function_pattern)])?;
+        let module = Pattern::new(module_pattern, use_regex)?;
+        let function = Pattern::new(function_pattern, use_regex)?;
+
+        let mut level = BTreeSet::new();
+        for edge in &self.edges {
+            if edge.message
+                || (edge.conditional && certain_only)
+                || !module.matches(&edge.callee_module)
+                || !function.matches(&edge.callee_function)
+                || arity.is_some_and(|a| edge.callee_arity != a)
+                || !settings.in_namespaces(&edge.callee_module)
+                || !in_scope(&edge.caller_module)
+            {
+                continue;
+            }
+            for loc in self.enclosing(edge) {
+                level.insert(trace_row(1, edge, loc));
+            }
+        }
+
+        let rows = expand_levels(level, max_depth, |rows, depth| {
+            let frontier: BTreeSet<(&str, &str, i64)> =
+                rows.iter().map(|r| (r.1.as_str(), r.2.as_str(), r.3)).collect();
+            let mut next = BTreeSet::new();
+            for (module, name, arity) in frontier {
+                for edge in self.calls_to(module, name, false) {
+                    if edge.callee_arity != arity
+                        || (edge.conditional && certain_only)
+                        || !in_scope(&edge.caller_module)
+                    {
+                        continue;
+                    }
+                    for loc in self.enclosing(edge) {
+                        next.insert(trace_row(depth, edge, loc));
+                    }
+                }
+            }
+            next
+        });
+
+        Ok(sort_trace_rows(rows, limit)
+            .into_iter()
+            .map(|r| ReverseTraceStep {
+                depth: r.0,
+                caller_module: r.1,
+                caller_function: r.2,
+                caller_arity: r.3,
+                caller_kind: r.4,
+                caller_start_line: r.5,
+                caller_end_line: r.6,
+                callee_module: r.7,
+                callee_function: r.8,
+                callee_arity: r.9,
+                file: r.10,
+                line: r.11,
+            })
+            .collect())
+    }
+
+    /// In-memory equivalent of [`super::path::find_path_edges`].
+    pub fn path_edges(
+        &self,
+        from_module: &str,
+        from_function: &str,
+        from_arity: Option<i64>,
+        max_depth: u32,
+        include_messages: bool,
+        min_weight: i64,
+    ) -> Vec<PathStep> {
+        let mut level = BTreeSet::new();
+        for edge in self.calls_from(from_module, include_messages) {
+            if edge.weight < min_weight || !edge.caller_function.starts_with(from_function) {
+                continue;
+            }
+            let has_caller = self.locations_in(&edge.caller_module).iter().any(|loc| {
+                edge.caller_function.starts_with(&loc.name)
+                    && from_arity.is_none_or(|a| loc.arity == a)
+            });
+            if has_caller {
+                level.insert(path_row(1, edge));
+            }
+        }
+
+        let rows = expand_levels(level, max_depth, |rows, depth| {
+            let frontier: BTreeSet<(&str, &str)> =
+                rows.iter().map(|r| (r.3.as_str(), r.4.as_str())).collect();
+            let mut next = BTreeSet::new();
+            for (module, function) in frontier {
+                for edge in self.calls_from(module, include_messages) {
+                    if edge.weight >= min_weight && edge.caller_function.starts_with(function) {
+                        next.insert(path_row(depth, edge));
+                    }
+                }
+            }
+            next
+        });
+
+        let mut rows: Vec<PathRow> = rows.into_iter().collect();
+        rows.sort_by(|a, b| {
+            (a.0, &a.1, &a.2, &a.3, &a.4, &a.6, a.7).cmp(&(b.0, &b.1, &b.2, &b.3, &b.4, &b.6, b.7))
+        });
+        rows.into_iter().map(path_step).collect()
+    }
+
+    /// In-memory equivalent of [`super::path::find_paths`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn find_paths(
+        &self,
+        from_module: &str,
+        from_function: &str,
+        from_arity: Option<i64>,
+        to_module: &str,
+        to_function: &str,
+        to_arity: Option<i64>,
+        max_depth: u32,
+        limit: u32,
+        include_messages: bool,
+        min_weight: i64,
+    ) -> Vec<CallPath> {
+        let edges = self.path_edges(from_module, from_function, from_arity, max_depth, include_messages, min_weight);
+
+        // Keep edges up to the depth at which the target is first reached
+        let Some(target_depth) = edges
+            .iter()
+            .filter(|e| {
+                e.callee_module == to_module
+                    && e.callee_function.starts_with(to_function)
+                    && to_arity.is_none_or(|a| e.callee_arity == a)
+            })
+            .map(|e| e.depth)
+            .min()
+        else {
+            return vec![];
+        };
+
+        let mut edges: Vec<PathStep> = edges.into_iter().filter(|e| e.depth <= target_depth).collect();
+        edges.sort_by(|a, b| {
+            (a.depth, &a.caller_module, &a.caller_function, &a.callee_module, &a.callee_function, a.callee_arity, &a.file, a.line)
+                .cmp(&(b.depth, &b.caller_module, &b.caller_function, &b.callee_module, &b.callee_function, b.callee_arity, &b.file, b.line))
+        });
+        edges.truncate(limit as usize);
+
+        build_paths(&edges, to_module, to_function, to_arity, limit)
+    }
+
+    /// In-memory equivalent of [`super::path::find_frontier_edges`].
+    pub fn frontier_edges(
+        &self,
+        nodes: &[(String, String)],
+        direction: FrontierDirection,
+        include_messages: bool,
+        min_weight: i64,
+    ) -> Vec<PathStep> {
+        let mut rows = BTreeSet::new();
+        for (module, name) in nodes {
+            match direction {
+                FrontierDirection::Outgoing => {
+                    let prefix = format!("{}/", name);
+                    for edge in self.calls_from(module, include_messages) {
+                        if edge.caller_function == *name || edge.caller_function.starts_with(&prefix) {
+                            rows.insert(path_row(0, edge));
+                        }
+                    }
+                }
+                FrontierDirection::Incoming => {
+                    rows.extend(self.calls_to(module, name, include_messages).map(|edge| path_row(0, edge)));
+                }
+            }
+        }
+        rows.retain(|r| r.4 != "%" && r.8 >= min_weight);
+
+        let mut rows: Vec<PathRow> = rows.into_iter().collect();
+        rows.sort_by(|a, b| (&a.1, &a.2, &a.3, &a.4, &a.6, a.7).cmp(&(&b.1, &b.2, &b.3, &b.4, &b.6, b.7)));
+        rows.into_iter().map(path_step).collect()
+    }
+
+    /// Fewest calls from any of `roots` to each function they reach, by
+    /// (module, name, arity).
+    ///
+    /// Roots are at depth 0. Message edges and struct calls are not followed.
+    pub fn depths_from(&self, roots: &[(String, String, i64)]) -> HashMap<(String, String, i64), i64> {
+        let mut depths: HashMap<(String, String, i64), i64> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for root in roots {
+            if !depths.contains_key(root) {
+                depths.insert(root.clone(), 0);
+                queue.push_back(root.clone());
+            }
+        }
+
+        while let Some((module, name, arity)) = queue.pop_front() {
+            let depth = depths[&(module.clone(), name.clone(), arity)];
+            for edge in self.calls_from(&module, false) {
+                if edge.callee_function == "%" || !self.enclosing(edge).any(|loc| loc.name == name && loc.arity == arity) {
+                    continue;
+                }
+                let callee = (edge.callee_module.clone(), edge.callee_function.clone(), edge.callee_arity);
+                if !depths.contains_key(&callee) {
+                    depths.insert(callee.clone(), depth + 1);
+                    queue.push_back(callee);
+                }
+            }
+        }
+        depths
+    }
+
+    fn calls_from<'a>(&'a self, module: &str, include_messages: bool) -> impl Iterator<Item = &'a CallEdge> + 'a {
+        self.outgoing
+            .get(module)
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.edges[i])
+            .filter(move |e| include_messages || !e.message)
+    }
+
+    fn calls_to<'a>(
+        &'a self,
+        module: &str,
+        function: &str,
+        include_messages: bool,
+    ) -> impl Iterator<Item = &'a CallEdge> + 'a {
+        self.incoming
+            .get(&(module.to_string(), function.to_string()))
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.edges[i])
+            .filter(move |e| include_messages || !e.message)
+    }
+
+    fn locations_in(&self, module: &str) -> &[Location] {
+        self.locations.get(module).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Definitions of the caller that contain the call site
+    fn enclosing<'a>(&'a self, edge: &'a CallEdge) -> impl Iterator<Item = &'a Location> + 'a {
+        self.locations_in(&edge.caller_module).iter().filter(move |loc| {
+            edge.caller_function.starts_with(&loc.name)
+                && edge.line >= loc.start_line
+                && edge.line <= loc.end_line
+        })
+    }
+}
+
+/// Exact or regex match, as built by [`crate::query_builders::ConditionBuilder`]
+enum Pattern<'a> {
+    Exact(&'a str),
+    Regex(Regex),
+}
+
+impl<'a> Pattern<'a> {
+    fn new(pattern: &'a str, use_regex: bool) -> Result<Self, Box<dyn Error>> {
+        Ok(if use_regex {
+            Pattern::Regex(compile_pattern(pattern)?)
+        } else {
+            Pattern::Exact(pattern)
+        })
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            Pattern::Exact(pattern) => value == *pattern,
+            Pattern::Regex(re) => re.is_match(value),
+        }
+    }
+}
+
+/// Run the recursive step of a depth-bounded trace level by level.
+///
+/// `step` receives the rows found at one depth and the depth to assign to the
+/// rows it returns. Like the Datalog rules, rows are distinct per depth and
+/// expansion stops at `max_depth`.
+fn expand_levels<R: Ord>(
+    first: BTreeSet<R>,
+    max_depth: u32,
+    mut step: impl FnMut(&BTreeSet<R>, i64) -> BTreeSet<R>,
+) -> BTreeSet<R> {
+    let mut rows = BTreeSet::new();
+    let mut level = first;
+    let mut depth = 1;
+    while !level.is_empty() && depth < max_depth as i64 {
+        depth += 1;
+        let next = step(&level, depth);
+        rows.append(&mut level);
+        level = next;
+    }
+    rows.append(&mut level);
+    rows
+}
+
+fn trace_row(depth: i64, edge: &CallEdge, loc: &Location) -> TraceRow {
+    (
+        depth,
+        edge.caller_module.clone(),
+        loc.name.clone(),
+        loc.arity,
+        loc.kind.clone(),
+        loc.start_line,
+        loc.end_line,
+        edge.callee_module.clone(),
+        edge.callee_function.clone(),
+        edge.callee_arity,
+        edge.file.clone(),
+        edge.line,
+    )
+}
+
+/// Order trace rows like the `:order` clause of the trace queries, then apply the limit.
+///
+/// Rows come out of the set in full-tuple order, which the stable sort keeps for ties.
+fn sort_trace_rows(rows: BTreeSet<TraceRow>, limit: u32) -> Vec<TraceRow> {
+    let mut rows: Vec<TraceRow> = rows.into_iter().collect();
+    rows.sort_by(|a, b| {
+        (a.0, &a.1, &a.2, a.3, a.11, &a.7, &a.8, a.9).cmp(&(b.0, &b.1, &b.2, b.3, b.11, &b.7, &b.8, b.9))
+    });
+    rows.truncate(limit as usize);
+    rows
+}
+
+fn path_row(depth: i64, edge: &CallEdge) -> PathRow {
+    (
+        depth,
+        edge.caller_module.clone(),
+        edge.caller_function.clone(),
+        edge.callee_module.clone(),
+        edge.callee_function.clone(),
+        edge.callee_arity,
+        edge.file.clone(),
+        edge.line,
+        edge.weight,
+    )
+}
+
+fn path_step(r: PathRow) -> PathStep {
+    PathStep {
+        depth: r.0,
+        caller_module: r.1,
+        caller_function: r.2,
+        callee_module: r.3,
+        callee_function: r.4,
+        callee_arity: r.5,
+        file: r.6,
+        line: r.7,
+        weight: r.8,
+    }
+}
+
+thread_local! {
+    static CACHE: RefCell<HashMap<(usize, String), Rc<AdjacencyIndex>>> = RefCell::new(HashMap::new());
+}
+
+/// Get the index for a project, loading it on first use.
+///
+/// Indexes are cached per database instance and project for the rest of the thread.
+pub fn cached_index(db: &cozo::DbInstance, project: &str) -> Result<Rc<AdjacencyIndex>, Box<dyn Error>> {
+    let key = (db as *const cozo::DbInstance as usize, project.to_string());
+    if let Some(index) = CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+        return Ok(index);
+    }
+
+    let index = Rc::new(AdjacencyIndex::load(db, project)?);
+    CACHE.with(|cache| cache.borrow_mut().insert(key, Rc::clone(&index)));
+    Ok(index)
+}
+
+/// Drop all cached indexes, e.g. after an import has changed the calls relation.
+pub fn clear_cache() {
+    CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::path::{find_frontier_edges, find_path_edges, find_paths};
+    use crate::queries::reverse_trace::reverse_trace_calls;
+    use crate::queries::trace::trace_calls;
+    use rstest::{fixture, rstest};
+    use crate::query_builders::QuerySettings;
+
+    #[fixture]
+    fn populated_db() -> cozo::DbInstance {
+        crate::test_utils::call_graph_db("default")
+    }
+
+    fn json<T: serde::Serialize>(value: &T) -> String {
+        serde_json::to_string(value).unwrap()
+    }
+
+    #[rstest]
+    fn test_load(populated_db: cozo::DbInstance) {
+        let index = AdjacencyIndex::load(&populated_db, "default").expect("Load should succeed");
+        assert_eq!(index.edge_count(), 11);
+
+        let other = AdjacencyIndex::load(&populated_db, "other").expect("Load should succeed");
+        assert_eq!(other.edge_count(), 0);
+    }
+
+    #[rstest]
+    #[case("MyApp.Controller", "index", None, false)]
+    #[case("MyApp.Controller", "create", Some(2), false)]
+    #[case("MyApp\\..*", "show|create", None, true)]
+    fn test_trace_matches_query(
+        populated_db: cozo::DbInstance,
+        #[case] module: &str,
+        #[case] function: &str,
+        #[case] arity: Option<i64>,
+        #[case] regex: bool,
+    ) {
+        let index = AdjacencyIndex::load(&populated_db, "default").unwrap();
+        let expected = trace_calls(&populated_db, module, function, arity, "default", regex, 5, 100, false, 1, false, &QuerySettings::default()).unwrap();
+        let actual = index.trace_calls(module, function, arity, regex, 5, 100, false, 1, false, |_| true, &QuerySettings::default()).unwrap();
+
+        assert!(!expected.is_empty());
+        assert_eq!(json(&actual), json(&expected));
+    }
+
+    #[rstest]
+    #[case("MyApp.Accounts", "get_user", 2)]
+    #[case("MyApp.Controller", "show", 0)]
+    fn test_trace_min_weight_matches_query(
+        populated_db: cozo::DbInstance,
+        #[case] module: &str,
+        #[case] function: &str,
+        #[case] expected_calls: usize,
+    ) {
+        let index = AdjacencyIndex::load(&populated_db, "default").unwrap();
+        let expected = trace_calls(&populated_db, module, function, None, "default", false, 5, 100, false, 2, false, &QuerySettings::default()).unwrap();
+        let actual = index.trace_calls(module, function, None, false, 5, 100, false, 2, false, |_| true, &QuerySettings::default()).unwrap();
+
+        assert_eq!(expected.len(), expected_calls);
+        assert_eq!(json(&actual), json(&expected));
+    }
+
+    // Service.fetch calls do_fetch in a branch, so it cuts the chain from create
+    #[rstest]
+    fn test_trace_certain_only_matches_query(populated_db: cozo::DbInstance) {
+        let index = AdjacencyIndex::load(&populated_db, "default").unwrap();
+        let all = trace_calls(&populated_db, "MyApp.Controller", "create", None, "default", false, 5, 100, false, 1, false, &QuerySettings::default()).unwrap();
+        let expected = trace_calls(&populated_db, "MyApp.Controller", "create", None, "default", false, 5, 100, false, 1, true, &QuerySettings::default()).unwrap();
+        let actual = index.trace_calls("MyApp.Controller", "create", None, false, 5, 100, false, 1, true, |_| true, &QuerySettings::default()).unwrap();
+
+        assert_eq!((all.len(), expected.len()), (6, 4));
+        assert!(expected.iter().all(|c| c.callee.name.as_ref() != "do_fetch"));
+        assert_eq!(json(&actual), json(&expected));
+    }
+
+    #[rstest]
+    fn test_reverse_trace_certain_only_matches_query(populated_db: cozo::DbInstance) {
+        let index = AdjacencyIndex::load(&populated_db, "default").unwrap();
+        let expected =
+            reverse_trace_calls(&populated_db, "MyApp.Repo", "get", None, "default", false, 5, 100, true, &QuerySettings::default()).unwrap();
+        let actual = index.reverse_trace_calls("MyApp.Repo", "get", None, false, 5, 100, true, |_| true, &QuerySettings::default()).unwrap();
+
+        let callers: Vec<&str> = expected.iter().map(|s| s.caller_function.as_str()).collect();
+        assert_eq!(callers, ["get_user", "get_user", "do_fetch", "show"]);
+        assert_eq!(json(&actual), json(&expected));
+    }
+
+    #[rstest]
+    fn test_depths_from_controller_actions(populated_db: cozo::DbInstance) {
+        let index = AdjacencyIndex::load(&populated_db, "default").unwrap();
+        let roots: Vec<(String, String, i64)> = ["index", "show", "create"]
+            .iter()
+            .map(|name| ("MyApp.Controller".to_string(), name.to_string(), 2))
+            .collect();
+        let depths = index.depths_from(&roots);
+        let depth = |module: &str, name: &str, arity: i64| depths.get(&(module.to_string(), name.to_string(), arity)).copied();
+
+        assert_eq!(depth("MyApp.Controller", "show", 2), Some(0));
+        assert_eq!(depth("MyApp.Accounts", "get_user", 1), Some(1));
+        // Reached through get_user/1 before Service.do_fetch
+        assert_eq!(depth("MyApp.Repo", "get", 2), Some(2));
+        assert_eq!(depth("MyApp.Notifier", "send_email", 2), Some(3));
+        assert_eq!(depth("MyApp.Accounts", "get_user", 2), None);
+    }
+
+    #[rstest]
+    fn test_reverse_trace_matches_query(populated_db: cozo::DbInstance) {
+        let index = AdjacencyIndex::load(&populated_db, "default").unwrap();
+        let expected =
+            reverse_trace_calls(&populated_db, "MyApp.Repo", "get", None, "default", false, 5, 100, false, &QuerySettings::default()).unwrap();
+        let actual = index.reverse_trace_calls("MyApp.Repo", "get", None, false, 5, 100, false, |_| true, &QuerySettings::default()).unwrap();
+
+        assert!(!expected.is_empty());
+        assert_eq!(json(&actual), json(&expected));
+    }
+
+    #[rstest]
+    fn test_path_queries_match(populated_db: cozo::DbInstance) {
+        let index = AdjacencyIndex::load(&populated_db, "default").unwrap();
+
+        let expected = find_path_edges(&populated_db, "MyApp.Controller", "create", None, "default", 10, false, 1).unwrap();
+        let actual = index.path_edges("MyApp.Controller", "create", None, 10, false, 1);
+        assert!(!expected.is_empty());
+        assert_eq!(json(&actual), json(&expected));
+
+        let expected = find_paths(
+            &populated_db, "MyApp.Controller", "show", None, "MyApp.Repo", "get", None, "default", 10, 100, false, 1,
+        )
+        .unwrap();
+        let actual = index.find_paths("MyApp.Controller", "show", None, "MyApp.Repo", "get", None, 10, 100, false, 1);
+        assert!(!expected.is_empty());
+        assert_eq!(json(&actual), json(&expected));
+    }
+
+    #[rstest]
+    fn test_frontier_edges_match(populated_db: cozo::DbInstance) {
+        let index = AdjacencyIndex::load(&populated_db, "default").unwrap();
+        let nodes = vec![
+            ("MyApp.Service".to_string(), "process".to_string()),
+            ("MyApp.Repo".to_string(), "get".to_string()),
+        ];
+
+        for direction in [FrontierDirection::Outgoing, FrontierDirection::Incoming] {
+            let expected = find_frontier_edges(&populated_db, "default", &nodes, direction, false, 1).unwrap();
+            let actual = index.frontier_edges(&nodes, direction, false, 1);
+            assert!(!expected.is_empty());
+            assert_eq!(json(&actual), json(&expected));
+        }
+    }
+
+    #[rstest]
+    fn test_cached_index_is_reused(populated_db: cozo::DbInstance) {
+        let first = cached_index(&populated_db, "default").unwrap();
+        let second = cached_index(&populated_db, "default").unwrap();
+        assert!(Rc::ptr_eq(&first, &second));
+
+        clear_cache();
+        let third = cached_index(&populated_db, "default").unwrap();
+        assert!(!Rc::ptr_eq(&first, &third));
+    }
+
+    const MESSAGES_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Web": {
+                "create/2:1": {"name": "create", "arity": 2, "file": "lib/web.ex", "kind": "def", "line": 1, "start_line": 1, "end_line": 10}
+            },
+            "MyApp.Worker": {
+                "handle_cast/2:5": {"name": "handle_cast", "arity": 2, "file": "lib/worker.ex", "kind": "def", "line": 5, "start_line": 5, "end_line": 15}
+            }
+        },
+        "calls": [
+            {
+                "caller": {"module": "MyApp.Worker", "function": "handle_cast", "file": "lib/worker.ex", "line": 8, "column": 5},
+                "type": "remote",
+                "callee": {"module": "MyApp.Repo", "function": "insert", "arity": 1}
+            }
+        ],
+        "messages": [
+            {
+                "caller": {"module": "MyApp.Web", "function": "create", "file": "lib/web.ex", "line": 4, "column": 5},
+                "callee": {"module": "MyApp.Worker", "function": "handle_cast", "arity": 2},
+                "kind": "cast"
+            }
+        ]
+    }"#;
+
+    #[rstest]
+    #[case(false, 0)]
+    #[case(true, 2)]
+    fn test_message_edges_followed_on_request(#[case] include_messages: bool, #[case] expected_calls: usize) {
+        let db = crate::test_utils::setup_test_db(MESSAGES_JSON, "default");
+        let index = AdjacencyIndex::load(&db, "default").unwrap();
+        assert_eq!(index.edge_count(), 2);
+
+        let expected = trace_calls(&db, "MyApp.Web", "create", None, "default", false, 5, 100, include_messages, 1, false, &QuerySettings::default()).unwrap();
+        let actual = index.trace_calls("MyApp.Web", "create", None, false, 5, 100, include_messages, 1, false, |_| true, &QuerySettings::default()).unwrap();
+        assert_eq!(expected.len(), expected_calls);
+        assert_eq!(json(&actual), json(&expected));
+
+        let expected = find_paths(
+            &db, "MyApp.Web", "create", None, "MyApp.Repo", "insert", None, "default", 10, 100, include_messages, 1,
+        )
+        .unwrap();
+        let actual = index.find_paths("MyApp.Web", "create", None, "MyApp.Repo", "insert", None, 10, 100, include_messages, 1);
+        assert_eq!(expected.len(), usize::from(include_messages));
+        assert_eq!(json(&actual), json(&expected));
+    }
+}
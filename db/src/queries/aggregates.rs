@@ -0,0 +1,338 @@
+//! Materialized call-count aggregates.
+//!
+//! `function_stats` and `module_stats` hold per-function fan-in/fan-out and
+//! per-module totals. The import pipeline refreshes a project's rows after its
+//! data is written, so hotspot-style queries can read them directly instead of
+//! recounting the calls table. Readers fall back to the full computation when a
+//! project has no aggregates (databases imported before the relations existed).
+//!
+//! The `weight` column of `calls` and `messages` is refreshed alongside: each
+//! edge records how many call sites its caller has to the same callee.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use thiserror::Error;
+
+use crate::db::{extract_i64, run_query, Params};
+
+#[derive(Error, Debug)]
+pub enum AggregatesError {
+    #[error("Failed to refresh {relation}: {message}")]
+    RefreshFailed { relation: String, message: String },
+}
+
+/// Number of aggregate rows written for a project
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AggregateCounts {
+    pub function_stats: usize,
+    pub module_stats: usize,
+}
+
+/// Recompute the aggregate rows of one project.
+///
+/// Existing rows for the project are removed first, so the aggregates always
+/// reflect the current calls and function_locations data.
+pub fn refresh_aggregates(db: &cozo::DbInstance, project: &str) -> Result<AggregateCounts, Box<dyn Error>> {
+    clear_aggregates(db, project)?;
+    refresh_call_weights(db, project)?;
+
+    // Same counting rules as hotspots: canonical names are how functions appear
+    // as callees, and recursive calls are counted like any other call
+    let function_stats = r#"
+        canonical[module, function] :=
+            *calls{project, callee_module, callee_function},
+            *function_locations{project, module: callee_module, name: callee_function},
+            project == $project,
+            module = callee_module,
+            function = callee_function
+
+        handwritten[module, function] :=
+            canonical[module, function],
+            *function_locations{project, module, name: function, generated_by},
+            project == $project,
+            generated_by == ""
+
+        distinct_outgoing[caller_module, canonical_name, callee_module, callee_function] :=
+            *calls{project, caller_module, caller_function, callee_module, callee_function},
+            canonical[caller_module, canonical_name],
+            project == $project,
+            (caller_function == canonical_name or starts_with(caller_function, concat(canonical_name, "/")))
+
+        outgoing_counts[module, function, count(callee_function)] :=
+            distinct_outgoing[module, function, callee_module, callee_function]
+
+        distinct_incoming[callee_module, callee_function, caller_module, caller_function] :=
+            *calls{project, caller_module, caller_function, callee_module, callee_function},
+            canonical[callee_module, callee_function],
+            project == $project
+
+        incoming_counts[module, function, count(caller_function)] :=
+            distinct_incoming[module, function, caller_module, caller_function]
+
+        stats[module, function, incoming, outgoing] :=
+            incoming_counts[module, function, incoming],
+            outgoing_counts[module, function, outgoing]
+
+        stats[module, function, incoming, outgoing] :=
+            incoming_counts[module, function, incoming],
+            not outgoing_counts[module, function, _],
+            outgoing = 0
+
+        stats[module, function, incoming, outgoing] :=
+            outgoing_counts[module, function, outgoing],
+            not incoming_counts[module, function, _],
+            incoming = 0
+
+        ?[project, module, function, incoming, outgoing, generated] :=
+            stats[module, function, incoming, outgoing],
+            handwritten[module, function],
+            project = $project,
+            generated = false
+
+        # Every clause of the function is generated code
+        ?[project, module, function, incoming, outgoing, generated] :=
+            stats[module, function, incoming, outgoing],
+            not handwritten[module, function],
+            project = $project,
+            generated = true
+
+        :put function_stats { project, module, function => incoming, outgoing, generated }
+        "#;
+
+    let module_stats = r#"
+        module_loc[module, sum(lines)] :=
+            *function_locations{project, module, start_line, end_line},
+            project == $project,
+            lines = end_line - start_line + 1
+
+        func_counts[module, count(name)] :=
+            *function_locations{project, module, name},
+            project == $project
+
+        connectivity[module, sum(incoming), sum(outgoing)] :=
+            *function_stats{project, module, incoming, outgoing, generated},
+            project == $project,
+            generated == false
+
+        totals[module, function_count, loc, incoming, outgoing] :=
+            func_counts[module, function_count],
+            module_loc[module, loc],
+            connectivity[module, incoming, outgoing]
+
+        totals[module, function_count, loc, incoming, outgoing] :=
+            func_counts[module, function_count],
+            module_loc[module, loc],
+            not connectivity[module, _, _],
+            incoming = 0,
+            outgoing = 0
+
+        ?[project, module, function_count, loc, incoming, outgoing] :=
+            totals[module, function_count, loc_sum, incoming_sum, outgoing_sum],
+            project = $project,
+            loc = to_int(loc_sum),
+            incoming = to_int(incoming_sum),
+            outgoing = to_int(outgoing_sum)
+
+        :put module_stats { project, module => function_count, loc, incoming, outgoing }
+        "#;
+
+    run_refresh(db, project, "function_stats", function_stats)?;
+    run_refresh(db, project, "module_stats", module_stats)?;
+
+    Ok(AggregateCounts {
+        function_stats: count_rows(db, project, "function_stats", "project, module, function")?,
+        module_stats: count_rows(db, project, "module_stats", "project, module")?,
+    })
+}
+
+/// Set the `weight` of every call and message edge of a project to the number
+/// of call sites with the same caller and callee.
+pub fn refresh_call_weights(db: &cozo::DbInstance, project: &str) -> Result<(), Box<dyn Error>> {
+    const KEYS: &str = "project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column";
+
+    for relation in ["calls", "messages"] {
+        let script = format!(
+            r#"
+            sites[caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column] :=
+                *{relation}{{{KEYS}}},
+                project == $project
+
+            weights[caller_module, caller_function, callee_module, callee_function, callee_arity, count(line)] :=
+                sites[caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column]
+
+            ?[{KEYS}, weight] :=
+                *{relation}{{{KEYS}}},
+                project == $project,
+                weights[caller_module, caller_function, callee_module, callee_function, callee_arity, weight]
+
+            :update {relation} {{{KEYS} => weight}}
+            "#,
+        );
+        run_refresh(db, project, relation, &script)?;
+    }
+    Ok(())
+}
+
+/// Remove a project's aggregate rows.
+pub fn clear_aggregates(db: &cozo::DbInstance, project: &str) -> Result<(), Box<dyn Error>> {
+    for (relation, keys) in [
+        ("function_stats", "project, module, function"),
+        ("module_stats", "project, module"),
+    ] {
+        let script = format!(
+            r#"
+            ?[{keys}] := *{relation}{{{keys}}}, project == $project
+            :rm {relation} {{{keys}}}
+            "#,
+        );
+        run_refresh(db, project, relation, &script)?;
+    }
+    Ok(())
+}
+
+/// Whether a project has materialized aggregates to read from.
+///
+/// False when the relations are missing or hold no rows for the project.
+pub fn has_aggregates(db: &cozo::DbInstance, project: &str) -> bool {
+    count_rows(db, project, "module_stats", "project, module").is_ok_and(|n| n > 0)
+}
+
+fn run_refresh(db: &cozo::DbInstance, project: &str, relation: &str, script: &str) -> Result<(), Box<dyn Error>> {
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    run_query(db, script, params).map_err(|e| AggregatesError::RefreshFailed {
+        relation: relation.to_string(),
+        message: e.to_string(),
+    })?;
+    Ok(())
+}
+
+fn count_rows(db: &cozo::DbInstance, project: &str, relation: &str, keys: &str) -> Result<usize, Box<dyn Error>> {
+    let script = format!(
+        r#"
+        rows[{keys}] := *{relation}{{{keys}}}, project == $project
+        ?[count(project)] := rows[{keys}]
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, &script, params).map_err(|e| AggregatesError::RefreshFailed {
+        relation: relation.to_string(),
+        message: e.to_string(),
+    })?;
+
+    Ok(rows
+        .rows
+        .first()
+        .and_then(|row| row.first())
+        .map(|v| extract_i64(v, 0) as usize)
+        .unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::extract_string;
+    use crate::queries::hotspots::{find_hotspots_computed, get_module_connectivity_computed, HotspotKind};
+    use rstest::{fixture, rstest};
+    use crate::query_builders::QuerySettings;
+
+    #[fixture]
+    fn populated_db() -> cozo::DbInstance {
+        crate::test_utils::call_graph_db("default")
+    }
+
+    #[rstest]
+    fn test_import_refreshes_aggregates(populated_db: cozo::DbInstance) {
+        assert!(has_aggregates(&populated_db, "default"));
+        assert!(!has_aggregates(&populated_db, "other"));
+    }
+
+    #[rstest]
+    fn test_refresh_is_idempotent(populated_db: cozo::DbInstance) {
+        let first = refresh_aggregates(&populated_db, "default").unwrap();
+        let second = refresh_aggregates(&populated_db, "default").unwrap();
+        assert_eq!(first, second);
+        assert!(first.function_stats > 0);
+        assert!(first.module_stats > 0);
+    }
+
+    #[rstest]
+    fn test_call_weights_count_call_sites(populated_db: cozo::DbInstance) {
+        let script = r#"
+            ?[caller_module, caller_function, callee_module, callee_function, callee_arity, weight, count(line)] :=
+                *calls{project, caller_module, caller_function, callee_module, callee_function, callee_arity, line, weight},
+                project == "default"
+            "#;
+        let rows = run_query(&populated_db, script, Params::new()).unwrap();
+        assert!(!rows.rows.is_empty());
+        for row in &rows.rows {
+            assert_eq!(extract_i64(&row[5], 0), extract_i64(&row[6], -1), "weight of {:?}", row);
+        }
+        assert!(rows.rows.iter().any(|row| extract_i64(&row[5], 0) > 1));
+    }
+
+    #[rstest]
+    fn test_function_stats_match_computed_hotspots(populated_db: cozo::DbInstance) {
+        let computed = find_hotspots_computed(
+            &populated_db, HotspotKind::Total, None, None, "default", false, u32::MAX, false, false,
+            &QuerySettings::default(),
+        )
+        .unwrap();
+
+        let script = r#"
+            ?[module, function, incoming, outgoing] :=
+                *function_stats{project, module, function, incoming, outgoing},
+                project == "default"
+            "#;
+        let rows = run_query(&populated_db, script, Params::new()).unwrap();
+        assert_eq!(rows.rows.len(), computed.len());
+
+        for hotspot in computed {
+            let found = rows.rows.iter().any(|row| {
+                extract_string(&row[0]).as_deref() == Some(hotspot.module.as_str())
+                    && extract_string(&row[1]).as_deref() == Some(hotspot.function.as_str())
+                    && extract_i64(&row[2], -1) == hotspot.incoming
+                    && extract_i64(&row[3], -1) == hotspot.outgoing
+            });
+            assert!(found, "{}.{} missing from function_stats", hotspot.module, hotspot.function);
+        }
+    }
+
+    #[rstest]
+    fn test_module_stats_match_computed_connectivity(populated_db: cozo::DbInstance) {
+        let computed = get_module_connectivity_computed(&populated_db, "default", None, false, &QuerySettings::default()).unwrap();
+
+        let script = r#"
+            ?[module, incoming, outgoing] :=
+                *module_stats{project, module, incoming, outgoing},
+                project == "default",
+                incoming > 0
+            "#;
+        let rows = run_query(&populated_db, script, Params::new()).unwrap();
+        assert_eq!(rows.rows.len(), computed.len());
+
+        for row in rows.rows {
+            let module = extract_string(&row[0]).unwrap();
+            assert_eq!(computed[&module], (extract_i64(&row[1], 0), extract_i64(&row[2], 0)));
+        }
+    }
+
+    #[rstest]
+    fn test_clear_aggregates(populated_db: cozo::DbInstance) {
+        clear_aggregates(&populated_db, "default").unwrap();
+        assert!(!has_aggregates(&populated_db, "default"));
+    }
+
+    #[rstest]
+    fn test_clear_aggregates_keeps_other_projects(populated_db: cozo::DbInstance) {
+        crate::queries::import::import_json_str(&populated_db, crate::fixtures::CALL_GRAPH, "other").unwrap();
+        clear_aggregates(&populated_db, "other").unwrap();
+        assert!(!has_aggregates(&populated_db, "other"));
+        assert!(has_aggregates(&populated_db, "default"));
+    }
+}
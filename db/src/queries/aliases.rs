@@ -0,0 +1,98 @@
+//! Resolution of module aliases recorded by `alias`, `import` and `require`.
+//!
+//! The `aliases` relation holds the directives of each module. The import
+//! already stores calls by the full name of their callee; these lookups let
+//! queries accept a short name such as `Repo` for the modules it aliases.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use thiserror::Error;
+
+use crate::db::{extract_string, run_query, Params};
+
+#[derive(Error, Debug)]
+pub enum AliasesError {
+    #[error("Aliases query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// The modules a module name may stand for.
+///
+/// A name that is a module of the project stands for itself. Otherwise the
+/// full names it aliases in any module come first, followed by the name as
+/// given, which still matches calls recorded under the alias. Names with no
+/// alias stand for themselves, as do qualified names: an alias is a single
+/// segment.
+pub fn resolve_module(db: &cozo::DbInstance, project: &str, name: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    if name.contains('.') {
+        return Ok(vec![name.to_string()]);
+    }
+
+    let script = r#"
+        defined[name] := *modules{project: $project, name}, name == $name
+        ?[target] := *aliases{project: $project, alias: $name, target}, not defined[$name]
+
+        :order target
+        "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("name", DataValue::Str(name.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| AliasesError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut modules: Vec<String> = rows
+        .rows
+        .iter()
+        .filter_map(|row| row.first().and_then(extract_string))
+        .collect();
+    if !modules.iter().any(|module| module == name) {
+        modules.push(name.to_string());
+    }
+    Ok(modules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::import::import_json_str;
+
+    fn aliases_db() -> cozo::DbInstance {
+        let db = crate::open_mem_db();
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Repo": {"get/2:1": {"name": "get", "arity": 2, "line": 1, "start_line": 1, "end_line": 2, "kind": "def", "file": "lib/repo.ex"}}
+            },
+            "calls": [],
+            "aliases": {
+                "MyApp.Accounts": [
+                    {"kind": "alias", "target": "MyApp.Repo", "line": 2},
+                    {"kind": "import", "target": "Ecto.Query", "line": 3}
+                ],
+                "MyApp.Billing": [{"kind": "alias", "target": "MyApp.Billing.Repo", "as": "Repo", "line": 2}]
+            }
+        }"#;
+        import_json_str(&db, json, "default").expect("Import should succeed");
+        db
+    }
+
+    #[test]
+    fn test_resolve_module_alias() {
+        let db = aliases_db();
+        let modules = resolve_module(&db, "default", "Repo").expect("Query should succeed");
+        assert_eq!(modules, ["MyApp.Billing.Repo", "MyApp.Repo", "Repo"]);
+    }
+
+    #[test]
+    fn test_resolve_module_without_alias() {
+        let db = aliases_db();
+        // Modules of the project and unknown names stand for themselves
+        assert_eq!(resolve_module(&db, "default", "MyApp.Repo").unwrap(), ["MyApp.Repo"]);
+        assert_eq!(resolve_module(&db, "default", "Query").unwrap(), ["Query"]);
+        assert_eq!(resolve_module(&db, "other", "Repo").unwrap(), ["Repo"]);
+    }
+}
@@ -0,0 +1,360 @@
+//! User annotations attached to modules and functions.
+//!
+//! Annotations are notes, TODO markers or deprecation markers written by users
+//! or agents with `annotate`. They live in the `annotations` relation, which is
+//! not touched by re-imports: clearing a project's call graph keeps its notes.
+//! A function annotation without an arity applies to every arity; one without
+//! a function applies to the module.
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use clap::ValueEnum;
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, extract_string_or, run_query, try_create_relation, Params};
+use crate::queries::schema::SCHEMA_ANNOTATIONS;
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum AnnotationsError {
+    #[error("Annotations query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// What an annotation marks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationKind {
+    /// Free-form note
+    #[default]
+    Note,
+    /// Work still to be done
+    Todo,
+    /// Finished work, kept for the record
+    Done,
+    /// Marked for removal; callers should migrate
+    Deprecated,
+}
+
+impl AnnotationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnotationKind::Note => "note",
+            AnnotationKind::Todo => "todo",
+            AnnotationKind::Done => "done",
+            AnnotationKind::Deprecated => "deprecated",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "todo" => AnnotationKind::Todo,
+            "done" => AnnotationKind::Done,
+            "deprecated" => AnnotationKind::Deprecated,
+            _ => AnnotationKind::Note,
+        }
+    }
+}
+
+/// A note attached to a module or function
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Annotation {
+    pub module: String,
+    /// Empty for module annotations
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub function: String,
+    /// None when the annotation applies to every arity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arity: Option<i64>,
+    pub kind: AnnotationKind,
+    pub text: String,
+    /// Unix time in milliseconds
+    pub created_at: i64,
+}
+
+impl Annotation {
+    /// `Module`, `Module.function` or `Module.function/arity`
+    pub fn target(&self) -> String {
+        match (self.function.as_str(), self.arity) {
+            ("", _) => self.module.clone(),
+            (function, None) => format!("{}.{}", self.module, function),
+            (function, Some(arity)) => format!("{}.{}/{}", self.module, function, arity),
+        }
+    }
+}
+
+/// Kind and text of an annotation, attached to results by `--show-annotations`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnnotationNote {
+    pub kind: AnnotationKind,
+    pub text: String,
+}
+
+impl std::fmt::Display for AnnotationNote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.kind.as_str(), self.text)
+    }
+}
+
+/// Create the relation in databases set up before annotations existed
+fn ensure_relation(db: &cozo::DbInstance) -> Result<(), Box<dyn Error>> {
+    try_create_relation(db, SCHEMA_ANNOTATIONS)?;
+    Ok(())
+}
+
+/// Store an annotation for `project`
+pub fn add_annotation(db: &cozo::DbInstance, project: &str, annotation: &Annotation) -> Result<(), Box<dyn Error>> {
+    ensure_relation(db)?;
+
+    let script = r#"
+        ?[project, module, function, arity, created_at, kind, text] <-
+            [[$project, $module, $function, $arity, $created_at, $kind, $text]]
+        :put annotations {project, module, function, arity, created_at => kind, text}
+    "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("module", DataValue::Str(annotation.module.as_str().into()));
+    params.insert("function", DataValue::Str(annotation.function.as_str().into()));
+    params.insert("arity", DataValue::from(annotation.arity.unwrap_or(-1)));
+    params.insert("created_at", DataValue::from(annotation.created_at));
+    params.insert("kind", DataValue::Str(annotation.kind.as_str().into()));
+    params.insert("text", DataValue::Str(annotation.text.as_str().into()));
+
+    run_query(db, script, params).map_err(|e| AnnotationsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+    Ok(())
+}
+
+/// Remove the annotations of exactly one target, optionally only of one kind.
+///
+/// Returns the number of annotations removed.
+pub fn remove_annotations(
+    db: &cozo::DbInstance,
+    project: &str,
+    module: &str,
+    function: Option<&str>,
+    arity: Option<i64>,
+    kind: Option<AnnotationKind>,
+) -> Result<usize, Box<dyn Error>> {
+    ensure_relation(db)?;
+
+    let kind_filter = if kind.is_some() { ", kind == $kind" } else { "" };
+    let script = format!(
+        r#"
+        ?[project, module, function, arity, created_at] :=
+            *annotations{{project, module, function, arity, created_at, kind}},
+            project == $project,
+            module == $module,
+            function == $function,
+            arity == $arity
+            {kind_filter}
+        :rm annotations {{project, module, function, arity, created_at}}
+        :returning
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("module", DataValue::Str(module.into()));
+    params.insert("function", DataValue::Str(function.unwrap_or("").into()));
+    params.insert("arity", DataValue::from(arity.unwrap_or(-1)));
+    if let Some(kind) = kind {
+        params.insert("kind", DataValue::Str(kind.as_str().into()));
+    }
+
+    let rows = run_query(db, &script, params).map_err(|e| AnnotationsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+    // :returning lists each key twice, as "requested" and as "deleted"
+    let deleted = rows
+        .rows
+        .iter()
+        .filter(|row| row.first().and_then(extract_string).as_deref() == Some("deleted"))
+        .count();
+    Ok(deleted)
+}
+
+/// Annotations of `project`, ordered by target then creation time
+pub fn find_annotations(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    kind: Option<AnnotationKind>,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<Annotation>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+    ensure_relation(db)?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+    let kind_filter = if kind.is_some() { ", kind == $kind" } else { "" };
+
+    let script = format!(
+        r#"
+        ?[module, function, arity, created_at, kind, text] :=
+            *annotations{{project, module, function, arity, created_at, kind, text}},
+            project == $project
+            {module_cond}
+            {namespace_cond}
+            {kind_filter}
+
+        :order module, function, arity, created_at
+        :limit {limit}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+    if let Some(kind) = kind {
+        params.insert("kind", DataValue::Str(kind.as_str().into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| AnnotationsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut annotations = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 6 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let arity = extract_i64(&row[2], -1);
+            annotations.push(Annotation {
+                module,
+                function: extract_string_or(&row[1], ""),
+                arity: (arity >= 0).then_some(arity),
+                created_at: extract_i64(&row[3], 0),
+                kind: AnnotationKind::parse(&extract_string_or(&row[4], "")),
+                text: extract_string_or(&row[5], ""),
+            });
+        }
+    }
+
+    Ok(annotations)
+}
+
+/// Function annotations of a project, looked up by function for `--show-annotations`
+#[derive(Debug, Default)]
+pub struct AnnotationIndex {
+    by_function: HashMap<(String, String), Vec<Annotation>>,
+}
+
+impl AnnotationIndex {
+    pub fn load(db: &cozo::DbInstance, project: &str, settings: &QuerySettings) -> Result<Self, Box<dyn Error>> {
+        let mut by_function: HashMap<(String, String), Vec<Annotation>> = HashMap::new();
+        for annotation in find_annotations(db, project, None, false, None, u32::MAX, settings)? {
+            if !annotation.function.is_empty() {
+                by_function
+                    .entry((annotation.module.clone(), annotation.function.clone()))
+                    .or_default()
+                    .push(annotation);
+            }
+        }
+        Ok(Self { by_function })
+    }
+
+    /// Annotations of one function, including those without an arity
+    pub fn for_function(&self, module: &str, function: &str, arity: i64) -> Vec<AnnotationNote> {
+        self.by_function
+            .get(&(module.to_string(), function.to_string()))
+            .map(|annotations| {
+                annotations
+                    .iter()
+                    .filter(|a| a.arity.is_none_or(|a| a == arity))
+                    .map(|a| AnnotationNote {
+                        kind: a.kind,
+                        text: a.text.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotation(function: &str, arity: Option<i64>, kind: AnnotationKind, created_at: i64) -> Annotation {
+        Annotation {
+            module: "MyApp.Accounts".to_string(),
+            function: function.to_string(),
+            arity,
+            kind,
+            text: format!("{} note", kind.as_str()),
+            created_at,
+        }
+    }
+
+    fn annotated_db() -> cozo::DbInstance {
+        let db = crate::open_mem_db();
+        add_annotation(&db, "default", &annotation("", None, AnnotationKind::Note, 1)).unwrap();
+        add_annotation(&db, "default", &annotation("get_user", Some(1), AnnotationKind::Todo, 2)).unwrap();
+        add_annotation(&db, "default", &annotation("get_user", None, AnnotationKind::Deprecated, 3)).unwrap();
+        add_annotation(&db, "other", &annotation("get_user", Some(1), AnnotationKind::Note, 4)).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_find_annotations() {
+        let db = annotated_db();
+        let annotations = find_annotations(&db, "default", None, false, None, 100, &QuerySettings::default()).unwrap();
+        let targets: Vec<String> = annotations.iter().map(Annotation::target).collect();
+        assert_eq!(targets, ["MyApp.Accounts", "MyApp.Accounts.get_user", "MyApp.Accounts.get_user/1"]);
+
+        let todos = find_annotations(
+            &db,
+            "default",
+            Some("MyApp.Accounts"),
+            false,
+            Some(AnnotationKind::Todo),
+            100,
+            &QuerySettings::default(),
+        )
+        .unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].text, "todo note");
+    }
+
+    #[test]
+    fn test_remove_annotations() {
+        let db = annotated_db();
+        let removed = remove_annotations(&db, "default", "MyApp.Accounts", Some("get_user"), Some(1), None).unwrap();
+        assert_eq!(removed, 1);
+        let removed = remove_annotations(&db, "default", "MyApp.Accounts", Some("missing"), None, None).unwrap();
+        assert_eq!(removed, 0);
+        assert_eq!(
+            find_annotations(&db, "default", None, false, None, 100, &QuerySettings::default())
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_annotation_index_matches_any_arity() {
+        let db = annotated_db();
+        let index = AnnotationIndex::load(&db, "default", &QuerySettings::default()).unwrap();
+
+        let kinds = |arity| -> Vec<AnnotationKind> {
+            index.for_function("MyApp.Accounts", "get_user", arity).iter().map(|a| a.kind).collect()
+        };
+        assert_eq!(kinds(1), [AnnotationKind::Deprecated, AnnotationKind::Todo]);
+        assert_eq!(kinds(2), [AnnotationKind::Deprecated]);
+        assert!(index.for_function("MyApp.Accounts", "list_users", 0).is_empty());
+    }
+}
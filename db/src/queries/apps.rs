@@ -0,0 +1,161 @@
+//! OTP applications of umbrella projects and the calls between them.
+//!
+//! The app of each module is stored in the `modules` relation at import,
+//! from an explicit `app` on its function locations or its `apps/<app>/` path.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+use crate::query_builders::QuerySettings;
+
+#[derive(Error, Debug)]
+pub enum AppsError {
+    #[error("Apps query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A module and the OTP application owning it
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleApp {
+    pub module: String,
+    /// Empty when the module belongs to no known app
+    pub app: String,
+}
+
+/// Calls from the modules of one app into the modules of another
+#[derive(Debug, Clone, Serialize)]
+pub struct AppDependency {
+    pub from_app: String,
+    pub to_app: String,
+    /// Distinct caller -> callee function pairs
+    pub calls: i64,
+}
+
+/// Find the app of every module in the project, ordered by module
+pub fn find_module_apps(
+    db: &cozo::DbInstance,
+    project: &str,
+    settings: &QuerySettings,
+) -> Result<Vec<ModuleApp>, Box<dyn Error>> {
+    let order = settings.order_by("module");
+    let script = format!(
+        r#"
+        ?[module, app] := *modules{{project, name: module, app}}, project == $project
+
+        {order}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, &script, params).map_err(|e| AppsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 2 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(app) = extract_string(&row[1]) else { continue };
+            results.push(ModuleApp { module, app });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Find the calls crossing app boundaries, ordered by caller and callee app.
+///
+/// Calls within an app and calls involving modules without an app are left out.
+pub fn find_app_dependencies(
+    db: &cozo::DbInstance,
+    project: &str,
+    settings: &QuerySettings,
+) -> Result<Vec<AppDependency>, Box<dyn Error>> {
+    let order = settings.order_by("from_app, to_app");
+    let script = format!(
+        r#"
+        app_of[module, app] :=
+            *modules{{project, name: module, app}},
+            project == $project,
+            app != ""
+
+        edge[from_app, to_app, caller_module, caller_function, callee_module, callee_function, callee_arity] :=
+            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity}},
+            project == $project,
+            app_of[caller_module, from_app],
+            app_of[callee_module, to_app],
+            from_app != to_app
+
+        ?[from_app, to_app, count(callee_function)] :=
+            edge[from_app, to_app, caller_module, caller_function, callee_module, callee_function, callee_arity]
+
+        {order}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, &script, params).map_err(|e| AppsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 3 {
+            let Some(from_app) = extract_string(&row[0]) else { continue };
+            let Some(to_app) = extract_string(&row[1]) else { continue };
+            let calls = extract_i64(&row[2], 0);
+            results.push(AppDependency { from_app, to_app, calls });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_module_apps() {
+        let db = crate::test_utils::umbrella_db("default");
+        let apps = find_module_apps(&db, "default", &QuerySettings::default()).expect("Query should succeed");
+
+        let pairs: Vec<(&str, &str)> = apps.iter().map(|m| (m.module.as_str(), m.app.as_str())).collect();
+        assert_eq!(
+            pairs,
+            [
+                ("Billing.Invoice", "billing"),
+                ("Core.Accounts", "core"),
+                ("Core.Repo", "core"),
+                ("Web.UserController", "web"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_app_dependencies() {
+        let db = crate::test_utils::umbrella_db("default");
+        let deps = find_app_dependencies(&db, "default", &QuerySettings::default()).expect("Query should succeed");
+
+        let edges: Vec<(&str, &str, i64)> = deps
+            .iter()
+            .map(|d| (d.from_app.as_str(), d.to_app.as_str(), d.calls))
+            .collect();
+        assert_eq!(edges, [("billing", "core", 1), ("web", "core", 2)]);
+    }
+
+    #[test]
+    fn test_find_app_dependencies_without_apps() {
+        let db = crate::test_utils::call_graph_db("default");
+        assert!(find_app_dependencies(&db, "default", &QuerySettings::default()).unwrap().is_empty());
+        assert!(find_module_apps(&db, "default", &QuerySettings::default()).unwrap().iter().all(|m| m.app.is_empty()));
+    }
+}
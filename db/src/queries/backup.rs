@@ -0,0 +1,245 @@
+//! Database backup and restore.
+//!
+//! Backups use Cozo's native backup API, which copies the whole store into a
+//! standalone SQLite file. Restoring goes through the schema instead of the
+//! raw store: the current schema is created in the target, and every managed
+//! relation found in the backup is replaced row by row. This restores into a
+//! database that already holds data, and into one created by a newer version
+//! (columns missing from the backup take their defaults).
+
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::path::Path;
+
+use cozo::{DataValue, DbInstance};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_bool, extract_i64, extract_string, run_query, run_query_no_params, Params};
+use crate::queries::schema;
+
+const RESTORE_CHUNK_SIZE: usize = 500;
+
+#[derive(Error, Debug)]
+pub enum BackupError {
+    #[error("Backup file '{path}' already exists")]
+    TargetExists { path: String },
+
+    #[error("Backup file '{path}' not found")]
+    NotFound { path: String },
+
+    #[error("Backup failed: {message}")]
+    BackupFailed { message: String },
+
+    #[error("Failed to open backup '{path}': {message}")]
+    OpenFailed { path: String, message: String },
+
+    #[error("'{path}' contains no code_search relations")]
+    NotABackup { path: String },
+
+    #[error("Cannot restore {relation}: key column '{column}' is missing from the backup")]
+    IncompatibleRelation { relation: String, column: String },
+
+    #[error("Restore of {relation} failed: {message}")]
+    RestoreFailed { relation: String, message: String },
+}
+
+/// Number of rows backed up or restored for a relation
+#[derive(Debug, Clone, Serialize)]
+pub struct RelationRows {
+    pub relation: String,
+    pub rows: usize,
+}
+
+/// Column of a stored relation
+struct Column {
+    name: String,
+    is_key: bool,
+}
+
+/// Copy the database into a new SQLite backup file.
+///
+/// Refuses to overwrite an existing file. Returns the row count of each
+/// managed relation in the backup.
+pub fn backup_database(db: &DbInstance, path: &Path) -> Result<Vec<RelationRows>, Box<dyn Error>> {
+    if path.exists() {
+        return Err(BackupError::TargetExists {
+            path: path.display().to_string(),
+        }
+        .into());
+    }
+
+    db.backup_db(path).map_err(|e| BackupError::BackupFailed {
+        message: format!("{:?}", e),
+    })?;
+
+    let mut counts = Vec::new();
+    for relation in managed_relations(db)? {
+        let key = &relation_columns(db, relation)?[0].name;
+        let script = format!("?[count({key})] := *{relation}{{{key}}}");
+        let rows = run_query_no_params(db, &script)?;
+        let count = rows.rows.first().map_or(0, |row| extract_i64(&row[0], 0));
+        counts.push(RelationRows {
+            relation: relation.to_string(),
+            rows: count as usize,
+        });
+    }
+
+    Ok(counts)
+}
+
+/// Replace the contents of the database's relations with those of a backup.
+///
+/// Relations absent from the backup are left untouched. Returns the number of
+/// rows restored into each relation.
+pub fn restore_database(db: &DbInstance, path: &Path) -> Result<Vec<RelationRows>, Box<dyn Error>> {
+    let display = path.display().to_string();
+    if !path.exists() {
+        return Err(BackupError::NotFound { path: display }.into());
+    }
+
+    let backup = DbInstance::new("sqlite", path, "").map_err(|e| BackupError::OpenFailed {
+        path: display.clone(),
+        message: format!("{:?}", e),
+    })?;
+    let relations = managed_relations(&backup)?;
+    if relations.is_empty() {
+        return Err(BackupError::NotABackup { path: display }.into());
+    }
+
+    schema::create_schema(db)?;
+
+    let mut restored = Vec::new();
+    for relation in relations {
+        let rows = restore_relation(db, &backup, relation)?;
+        restored.push(RelationRows {
+            relation: relation.to_string(),
+            rows,
+        });
+    }
+
+    Ok(restored)
+}
+
+/// Replace one relation's rows with those of the backup, returning the row count
+fn restore_relation(db: &DbInstance, backup: &DbInstance, relation: &str) -> Result<usize, Box<dyn Error>> {
+    let failed = |e: Box<dyn Error>| BackupError::RestoreFailed {
+        relation: relation.to_string(),
+        message: e.to_string(),
+    };
+
+    let available: BTreeSet<String> = relation_columns(backup, relation)?.into_iter().map(|c| c.name).collect();
+    let target = relation_columns(db, relation)?;
+    if let Some(missing) = target.iter().find(|c| c.is_key && !available.contains(&c.name)) {
+        return Err(BackupError::IncompatibleRelation {
+            relation: relation.to_string(),
+            column: missing.name.clone(),
+        }
+        .into());
+    }
+
+    let keys: Vec<&str> = target.iter().filter(|c| c.is_key).map(|c| c.name.as_str()).collect();
+    let values: Vec<&str> = target
+        .iter()
+        .filter(|c| !c.is_key && available.contains(&c.name))
+        .map(|c| c.name.as_str())
+        .collect();
+    let keys = keys.join(", ");
+    let columns = if values.is_empty() { keys.clone() } else { format!("{}, {}", keys, values.join(", ")) };
+    let spec = if values.is_empty() { keys.clone() } else { format!("{} => {}", keys, values.join(", ")) };
+
+    let clear = format!("?[{keys}] := *{relation}{{{keys}}}\n:rm {relation} {{{keys}}}");
+    run_query_no_params(db, &clear).map_err(failed)?;
+
+    let rows = run_query_no_params(backup, &format!("?[{columns}] := *{relation}{{{columns}}}")).map_err(failed)?;
+    let count = rows.rows.len();
+    for chunk in rows.rows.chunks(RESTORE_CHUNK_SIZE) {
+        let mut params = Params::new();
+        params.insert("rows", DataValue::List(chunk.iter().cloned().map(DataValue::List).collect()));
+        let script = format!("?[{columns}] <- $rows\n:put {relation} {{{spec}}}");
+        run_query(db, &script, params).map_err(failed)?;
+    }
+
+    Ok(count)
+}
+
+/// Managed relations present in the database, in schema order
+fn managed_relations(db: &DbInstance) -> Result<Vec<&'static str>, Box<dyn Error>> {
+    let rows = run_query_no_params(db, "::relations")?;
+    let existing: BTreeSet<String> = rows.rows.iter().filter_map(|row| extract_string(&row[0])).collect();
+    Ok(schema::relation_names()
+        .into_iter()
+        .filter(|name| existing.contains(*name))
+        .collect())
+}
+
+/// Columns of a stored relation, keys first
+fn relation_columns(db: &DbInstance, relation: &str) -> Result<Vec<Column>, Box<dyn Error>> {
+    let rows = run_query_no_params(db, &format!("::columns {relation}"))?;
+    Ok(rows
+        .rows
+        .iter()
+        .filter_map(|row| {
+            Some(Column {
+                name: extract_string(row.first()?)?,
+                is_key: extract_bool(row.get(1)?, false),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::annotations::{add_annotation, find_annotations, Annotation, AnnotationKind};
+    use crate::query_builders::QuerySettings;
+
+    fn count(db: &DbInstance, relation: &str) -> usize {
+        let rows = run_query_no_params(db, &format!("?[count(project)] := *{relation}{{project}}")).unwrap();
+        extract_i64(&rows.rows[0][0], 0) as usize
+    }
+
+    #[test]
+    fn test_backup_and_restore_round_trip() {
+        let source = crate::test_utils::call_graph_db("default");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.sqlite");
+
+        let backed_up = backup_database(&source, &path).expect("Backup should succeed");
+        let calls = backed_up.iter().find(|r| r.relation == "calls").expect("calls backed up");
+        assert_eq!(calls.rows, count(&source, "calls"));
+
+        // Restoring replaces data already in the target
+        let target = crate::db::open_mem_db();
+        let annotation = Annotation {
+            module: "Stale".to_string(),
+            function: String::new(),
+            arity: None,
+            kind: AnnotationKind::Note,
+            text: "Removed by restore".to_string(),
+            created_at: 0,
+        };
+        add_annotation(&target, "default", &annotation).unwrap();
+
+        let restored = restore_database(&target, &path).expect("Restore should succeed");
+        assert_eq!(restored.len(), backed_up.len());
+        assert_eq!(count(&target, "calls"), calls.rows);
+        assert_eq!(count(&target, "function_locations"), count(&source, "function_locations"));
+        assert!(find_annotations(&target, "default", None, false, None, 100, &QuerySettings::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_backup_refuses_existing_file() {
+        let db = crate::db::open_mem_db();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let err = backup_database(&db, file.path()).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[test]
+    fn test_restore_missing_file() {
+        let db = crate::db::open_mem_db();
+        let err = restore_database(&db, Path::new("/nonexistent/backup.sqlite")).unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+}
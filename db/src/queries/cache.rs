@@ -0,0 +1,98 @@
+//! Command output cache.
+//!
+//! Opt-in with `--cache`: agents often repeat identical queries, and the
+//! output of a read-only command only changes when the data does. Entries are
+//! keyed by the caller-built command key and stamped with the generation they
+//! were computed at; commands that change the data bump the generation, which
+//! invalidates and drops every entry.
+
+use std::error::Error;
+
+use cozo::DataValue;
+
+use crate::db::{extract_i64, extract_string, run_query, run_query_no_params, try_create_relation, Params};
+use crate::queries::schema::{SCHEMA_CACHE, SCHEMA_CACHE_GENERATION};
+
+/// Create the cache relations on first use
+fn ensure_relations(db: &cozo::DbInstance) -> Result<(), Box<dyn Error>> {
+    try_create_relation(db, SCHEMA_CACHE)?;
+    try_create_relation(db, SCHEMA_CACHE_GENERATION)?;
+    Ok(())
+}
+
+/// Current cache generation, 0 before the first bump
+pub fn current_generation(db: &cozo::DbInstance) -> Result<i64, Box<dyn Error>> {
+    ensure_relations(db)?;
+    let rows = run_query_no_params(db, "?[generation] := *cache_generation{generation}")?;
+    Ok(rows.rows.iter().map(|row| extract_i64(&row[0], 0)).max().unwrap_or(0))
+}
+
+/// Output cached for `key` at the current generation
+pub fn cached_output(db: &cozo::DbInstance, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let generation = current_generation(db)?;
+
+    let mut params = Params::new();
+    params.insert("key", DataValue::Str(key.into()));
+    params.insert("generation", DataValue::from(generation));
+
+    let rows = run_query(
+        db,
+        "?[output] := *cache{key: $key, generation: $generation, output}",
+        params,
+    )?;
+    Ok(rows.rows.first().and_then(|row| extract_string(&row[0])))
+}
+
+/// Cache `output` for `key` at the current generation
+pub fn store_output(db: &cozo::DbInstance, key: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let generation = current_generation(db)?;
+
+    let mut params = Params::new();
+    params.insert("key", DataValue::Str(key.into()));
+    params.insert("generation", DataValue::from(generation));
+    params.insert("output", DataValue::Str(output.into()));
+
+    run_query(
+        db,
+        r#"
+        ?[key, generation, output] <- [[$key, $generation, $output]]
+        :put cache {key => generation, output}
+        "#,
+        params,
+    )?;
+    Ok(())
+}
+
+/// Start a new generation, dropping every cached output; returns the new generation
+pub fn bump_generation(db: &cozo::DbInstance) -> Result<i64, Box<dyn Error>> {
+    let generation = current_generation(db)? + 1;
+
+    let mut params = Params::new();
+    params.insert("generation", DataValue::from(generation));
+
+    run_query_no_params(db, "?[generation] := *cache_generation{generation} :rm cache_generation {generation}")?;
+    run_query(db, "?[generation] <- [[$generation]] :put cache_generation {generation}", params)?;
+    run_query_no_params(db, "?[key] := *cache{key} :rm cache {key}")?;
+
+    Ok(generation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hit_until_bump() {
+        let db = crate::db::open_mem_db();
+        assert_eq!(current_generation(&db).unwrap(), 0);
+        assert_eq!(cached_output(&db, "hotspots").unwrap(), None);
+
+        store_output(&db, "hotspots", "Hotspots (incoming)").unwrap();
+        assert_eq!(cached_output(&db, "hotspots").unwrap().as_deref(), Some("Hotspots (incoming)"));
+        assert_eq!(cached_output(&db, "unused").unwrap(), None);
+
+        assert_eq!(bump_generation(&db).unwrap(), 1);
+        assert_eq!(cached_output(&db, "hotspots").unwrap(), None);
+        assert_eq!(bump_generation(&db).unwrap(), 2);
+    }
+}
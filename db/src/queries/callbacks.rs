@@ -0,0 +1,213 @@
+//! Behaviour callbacks and the modules implementing them.
+//!
+//! A behaviour's contract is the set of `@callback`/`@macrocallback` specs of
+//! its module; implementers are the modules declaring it with `@behaviour`.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+use crate::query_builders::QuerySettings;
+
+#[derive(Error, Debug)]
+pub enum CallbacksError {
+    #[error("Callbacks query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A callback declared by a behaviour
+#[derive(Debug, Clone, Serialize)]
+pub struct Callback {
+    pub name: String,
+    pub arity: i64,
+    /// "callback" or "macrocallback"
+    pub kind: String,
+    pub line: i64,
+}
+
+/// A module declaring `@behaviour` and the public functions it defines
+#[derive(Debug, Clone, Serialize)]
+pub struct BehaviourImplementer {
+    pub module: String,
+    /// First file defining the module (empty when it defines no public functions)
+    pub file: String,
+    /// Public functions as (name, arity), ordered
+    pub functions: Vec<(String, i64)>,
+}
+
+/// Find the callbacks declared by `behaviour`, ordered by name and arity
+pub fn find_behaviour_callbacks(
+    db: &cozo::DbInstance,
+    project: &str,
+    behaviour: &str,
+    settings: &QuerySettings,
+) -> Result<Vec<Callback>, Box<dyn Error>> {
+    let order = settings.order_by("name, arity");
+    let script = format!(
+        r#"
+        ?[name, arity, kind, line] :=
+            *specs{{project, module, name, arity, kind, line}},
+            project == $project,
+            module == $behaviour,
+            is_in(kind, ["callback", "macrocallback"])
+
+        {order}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("behaviour", DataValue::Str(behaviour.into()));
+
+    let rows = run_query(db, &script, params).map_err(|e| CallbacksError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 4 {
+            let Some(name) = extract_string(&row[0]) else { continue };
+            let arity = extract_i64(&row[1], 0);
+            let Some(kind) = extract_string(&row[2]) else { continue };
+            let line = extract_i64(&row[3], 0);
+
+            results.push(Callback { name, arity, kind, line });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Find the modules implementing `behaviour` with their public functions, ordered by module
+pub fn find_behaviour_implementers(
+    db: &cozo::DbInstance,
+    project: &str,
+    behaviour: &str,
+) -> Result<Vec<BehaviourImplementer>, Box<dyn Error>> {
+    // Implementers without any public function still get a row
+    let script = r#"
+        implementer[module] :=
+            *behaviours{project, module, behaviour},
+            project == $project,
+            behaviour == $behaviour
+
+        ?[module, file, name, arity] :=
+            implementer[module],
+            *function_locations{project, module, name, arity, kind, file},
+            project == $project,
+            is_in(kind, ["def", "defmacro"])
+        ?[module, file, name, arity] :=
+            implementer[module],
+            file = "",
+            name = "",
+            arity = -1
+
+        :order module, file, name, arity
+        "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("behaviour", DataValue::Str(behaviour.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| CallbacksError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut implementers: BTreeMap<String, BehaviourImplementer> = BTreeMap::new();
+    for row in rows.rows {
+        if row.len() >= 4 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(file) = extract_string(&row[1]) else { continue };
+            let Some(name) = extract_string(&row[2]) else { continue };
+            let arity = extract_i64(&row[3], 0);
+
+            let implementer = implementers
+                .entry(module.clone())
+                .or_insert_with(|| BehaviourImplementer {
+                    module,
+                    file: String::new(),
+                    functions: Vec::new(),
+                });
+            if arity < 0 {
+                continue;
+            }
+            if implementer.file.is_empty() {
+                implementer.file = file;
+            }
+            implementer.functions.push((name, arity));
+        }
+    }
+
+    // A function with clauses in several files shows up once per file
+    for implementer in implementers.values_mut() {
+        implementer.functions.sort();
+        implementer.functions.dedup();
+    }
+
+    Ok(implementers.into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BEHAVIOUR_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.EmailJob": {
+                "perform/1:3": {"name": "perform", "arity": 1, "file": "lib/email_job.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 6},
+                "perform/1:7": {"name": "perform", "arity": 1, "file": "lib/email_job.ex", "kind": "def", "line": 7, "start_line": 7, "end_line": 9},
+                "backoff/2:11": {"name": "backoff", "arity": 2, "file": "lib/email_job.ex", "kind": "def", "line": 11, "start_line": 11, "end_line": 12}
+            }
+        },
+        "calls": [],
+        "specs": {
+            "MyApp.Job": [
+                {"name": "perform", "arity": 1, "kind": "callback", "line": 2, "clauses": []},
+                {"name": "backoff", "arity": 1, "kind": "callback", "line": 3, "clauses": []},
+                {"name": "new", "arity": 1, "kind": "spec", "line": 8, "clauses": []}
+            ]
+        },
+        "behaviours": {
+            "MyApp.EmailJob": ["MyApp.Job"],
+            "MyApp.EmptyJob": ["MyApp.Job"]
+        }
+    }"#;
+
+    #[test]
+    fn test_find_behaviour_callbacks() {
+        let db = crate::test_utils::setup_test_db(BEHAVIOUR_JSON, "default");
+        let callbacks = find_behaviour_callbacks(&db, "default", "MyApp.Job", &QuerySettings::default()).expect("Query should succeed");
+
+        // The plain @spec is not part of the contract
+        let names: Vec<(&str, i64)> = callbacks.iter().map(|c| (c.name.as_str(), c.arity)).collect();
+        assert_eq!(names, [("backoff", 1), ("perform", 1)]);
+    }
+
+    #[test]
+    fn test_find_behaviour_implementers() {
+        let db = crate::test_utils::setup_test_db(BEHAVIOUR_JSON, "default");
+        let implementers = find_behaviour_implementers(&db, "default", "MyApp.Job").expect("Query should succeed");
+
+        assert_eq!(implementers.len(), 2);
+        assert_eq!(implementers[0].module, "MyApp.EmailJob");
+        assert_eq!(implementers[0].file, "lib/email_job.ex");
+        assert_eq!(
+            implementers[0].functions,
+            [("backoff".to_string(), 2), ("perform".to_string(), 1)]
+        );
+        assert_eq!(implementers[1].module, "MyApp.EmptyJob");
+        assert!(implementers[1].functions.is_empty());
+    }
+
+    #[test]
+    fn test_find_behaviour_unknown() {
+        let db = crate::test_utils::call_graph_db("default");
+        assert!(find_behaviour_callbacks(&db, "default", "MyApp.Nope", &QuerySettings::default()).unwrap().is_empty());
+        assert!(find_behaviour_implementers(&db, "default", "MyApp.Nope").unwrap().is_empty());
+    }
+}
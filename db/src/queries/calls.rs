@@ -3,15 +3,23 @@
 //! This module provides a single query function that can find calls in either direction:
 //! - `From`: Find all calls made BY the matched functions (outgoing calls)
 //! - `To`: Find all calls made TO the matched functions (incoming calls)
+//!
+//! [`count_calls`] matches the same calls and counts them per [`CallGrouping`].
+//! Each query can keep only calls of some [`CallKind`]s.
 
 use std::error::Error;
 
+use clap::ValueEnum;
 use cozo::DataValue;
 use thiserror::Error;
 
-use crate::db::{extract_call_from_row, run_query, CallRowLayout, Params};
-use crate::types::Call;
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder};
+use crate::db::{extract_call_from_row, extract_i64, extract_string, run_query, CallRowLayout, Params};
+use crate::queries::aliases::resolve_module;
+use crate::types::{Call, CallGroupCount, Interner};
+use crate::query_builders::{
+    target_condition, targets_param, validate_regex_patterns, ConditionBuilder, FunctionTarget,
+    OptionalConditionBuilder, QuerySettings, TARGET_RULE,
+};
 
 #[derive(Error, Debug)]
 pub enum CallsError {
@@ -37,6 +45,16 @@ impl CallDirection {
         }
     }
 
+    /// The module on the other end of the matched calls, which `--namespace`
+    /// restricts: the callees of calls from a function, the callers of calls
+    /// to it
+    fn other_module_field(&self) -> &'static str {
+        match self {
+            CallDirection::From => "callee_module",
+            CallDirection::To => "caller_module",
+        }
+    }
+
     /// Returns the ORDER BY clause based on direction
     fn order_clause(&self) -> &'static str {
         match self {
@@ -50,28 +68,106 @@ impl CallDirection {
     }
 }
 
+/// Attribute used to bucket calls when counting them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CallGrouping {
+    /// Module of the calling function
+    CallerModule,
+    /// Module of the called function
+    CalleeModule,
+    /// File containing the call site
+    File,
+}
+
+impl CallGrouping {
+    fn field(&self) -> &'static str {
+        match self {
+            CallGrouping::CallerModule => "caller_module",
+            CallGrouping::CalleeModule => "callee_module",
+            CallGrouping::File => "file",
+        }
+    }
+
+    /// Human-readable name of the grouping
+    pub fn label(&self) -> &'static str {
+        match self {
+            CallGrouping::CallerModule => "caller module",
+            CallGrouping::CalleeModule => "callee module",
+            CallGrouping::File => "file",
+        }
+    }
+}
+
+/// How a call references its callee
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CallKind {
+    /// Call of a function in another module
+    Remote,
+    /// Call of a function of the same module
+    Local,
+    /// Right side of a pipe (`x |> f()`)
+    Pipe,
+    /// Function capture passed as a value (`&f/1`)
+    Capture,
+}
+
+impl CallKind {
+    fn condition(&self) -> &'static str {
+        match self {
+            CallKind::Remote => "call_type == 'remote'",
+            CallKind::Local => "call_type == 'local'",
+            CallKind::Pipe => "call_kind == 'pipe'",
+            CallKind::Capture => "call_kind == 'capture'",
+        }
+    }
+}
+
+/// Condition keeping calls of any of `kinds`, with a leading comma, or an
+/// empty string when `kinds` is empty
+fn kind_condition(kinds: &[CallKind]) -> String {
+    if kinds.is_empty() {
+        return String::new();
+    }
+    let conditions: Vec<&str> = kinds.iter().map(CallKind::condition).collect();
+    format!(", ({})", conditions.join(" or "))
+}
+
 /// Find calls in the specified direction.
 ///
 /// - `From`: Returns all calls made by functions matching the pattern
 /// - `To`: Returns all calls to functions matching the pattern
+///
+/// An exact module name that is not a module of the project also matches
+/// the modules it is an alias of (see [`resolve_module`]). Non-empty `kinds`
+/// keep only calls of those kinds.
+#[allow(clippy::too_many_arguments)]
 pub fn find_calls(
     db: &cozo::DbInstance,
     direction: CallDirection,
     module_pattern: &str,
     function_pattern: Option<&str>,
     arity: Option<i64>,
+    kinds: &[CallKind],
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<Call>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[Some(module_pattern), function_pattern])?;
 
     let (module_field, function_field, arity_field) = direction.filter_fields();
-    let order_clause = direction.order_clause();
+
+    let modules = if use_regex {
+        vec![module_pattern.to_string()]
+    } else {
+        resolve_module(db, project, module_pattern)?
+    };
 
     // Build conditions using the appropriate field names
-    let module_cond =
-        ConditionBuilder::new(module_field, "module_pattern").build(use_regex);
+    let module_cond = match modules.as_slice() {
+        [_] => ConditionBuilder::new(module_field, "module_pattern").build(use_regex),
+        _ => format!("is_in({module_field}, $modules)"),
+    };
     let function_cond =
         OptionalConditionBuilder::new(function_field, "function_pattern")
             .with_leading_comma()
@@ -81,14 +177,148 @@ pub fn find_calls(
         .with_leading_comma()
         .build(arity.is_some());
 
-    let project_cond = ", project == $project";
+    let mut params = Params::new();
+    if modules.len() > 1 {
+        params.insert(
+            "modules",
+            DataValue::List(modules.into_iter().map(|m| DataValue::Str(m.into())).collect()),
+        );
+    } else {
+        params.insert(
+            "module_pattern",
+            DataValue::Str(module_pattern.into()),
+        );
+    }
+    if let Some(fn_pat) = function_pattern {
+        params.insert(
+            "function_pattern",
+            DataValue::Str(fn_pat.into()),
+        );
+    }
+    if let Some(a) = arity {
+        params.insert("arity", DataValue::from(a));
+    }
+
+    let filter = format!("{module_cond}\n{function_cond}\n{arity_cond}{}", kind_condition(kinds));
+    query_calls(db, direction, "", &filter, params, project, limit, settings)
+}
+
+/// Find calls in the specified direction for a list of exactly named functions.
+///
+/// Matches the same calls as [`find_calls`] for each target, in one query.
+pub fn find_calls_for_targets(
+    db: &cozo::DbInstance,
+    direction: CallDirection,
+    targets: &[FunctionTarget],
+    kinds: &[CallKind],
+    project: &str,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<Call>, Box<dyn Error>> {
+    let (module_field, function_field, arity_field) = direction.filter_fields();
+    let filter = format!("{}{}", target_condition(module_field, function_field, arity_field), kind_condition(kinds));
+
+    let mut params = Params::new();
+    params.insert("targets", targets_param(targets));
+
+    query_calls(db, direction, TARGET_RULE, &filter, params, project, limit, settings)
+}
+
+/// Run the calls query with `filter` restricting the matched functions
+#[allow(clippy::too_many_arguments)]
+fn query_calls(
+    db: &cozo::DbInstance,
+    direction: CallDirection,
+    rules: &str,
+    filter: &str,
+    mut params: Params,
+    project: &str,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<Call>, Box<dyn Error>> {
+    let order_clause = direction.order_clause();
+    let namespace_cond = settings.namespace_condition(direction.other_module_field());
 
     // Join calls with function_locations to get caller's arity and line range
     // Filter out struct calls (callee_function == '%')
     let script = format!(
         r#"
-        ?[project, caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line, call_type] :=
-            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line, call_type, caller_kind}},
+        {rules}
+        ?[project, caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line, call_type, call_kind, weight] :=
+            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line, call_type, call_kind, caller_kind, weight}},
+            *function_locations{{project, module: caller_module, name: caller_name, arity: caller_arity, start_line: caller_start_line, end_line: caller_end_line}},
+            starts_with(caller_function, caller_name),
+            call_line >= caller_start_line,
+            call_line <= caller_end_line,
+            callee_function != '%',
+            {filter}
+            {namespace_cond},
+            project == $project
+        :order {order_clause}
+        :limit {limit}
+        "#,
+    );
+
+    params.insert("project", DataValue::Str(project.into()));
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| CallsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let layout = CallRowLayout::from_headers(&rows.headers)?;
+    let mut interner = Interner::default();
+    let results = rows
+        .rows
+        .iter()
+        .filter_map(|row| extract_call_from_row(row, &layout, &mut interner))
+        .collect();
+
+    Ok(results)
+}
+
+/// Count calls in the specified direction, per group.
+///
+/// Matches the same calls as [`find_calls`], but returns one row per group
+/// (most calls first) instead of the individual edges. Each call site counts
+/// once. The limit applies to the number of groups.
+#[allow(clippy::too_many_arguments)]
+pub fn count_calls(
+    db: &cozo::DbInstance,
+    direction: CallDirection,
+    grouping: CallGrouping,
+    module_pattern: &str,
+    function_pattern: Option<&str>,
+    arity: Option<i64>,
+    kinds: &[CallKind],
+    project: &str,
+    use_regex: bool,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<CallGroupCount>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[Some(module_pattern), function_pattern])?;
+
+    let (module_field, function_field, arity_field) = direction.filter_fields();
+    let group_field = grouping.field();
+    let namespace_cond = settings.namespace_condition(direction.other_module_field());
+
+    let module_cond =
+        ConditionBuilder::new(module_field, "module_pattern").build(use_regex);
+    let function_cond =
+        OptionalConditionBuilder::new(function_field, "function_pattern")
+            .with_leading_comma()
+            .with_regex()
+            .build_with_regex(function_pattern.is_some(), use_regex);
+    let arity_cond = OptionalConditionBuilder::new(arity_field, "arity")
+        .with_leading_comma()
+        .build(arity.is_some());
+    let kind_cond = kind_condition(kinds);
+
+    let script = format!(
+        r#"
+        call_sites[group_key, caller_module, caller_name, caller_arity, file, call_line, column] :=
+            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line, column, call_type, call_kind}},
             *function_locations{{project, module: caller_module, name: caller_name, arity: caller_arity, start_line: caller_start_line, end_line: caller_end_line}},
             starts_with(caller_function, caller_name),
             call_line >= caller_start_line,
@@ -97,38 +327,272 @@ pub fn find_calls(
             {module_cond}
             {function_cond}
             {arity_cond}
-            {project_cond}
-        :order {order_clause}
+            {kind_cond}
+            {namespace_cond},
+            project == $project,
+            group_key = {group_field}
+
+        group_counts[group_key, count(call_line)] :=
+            call_sites[group_key, caller_module, caller_name, caller_arity, file, call_line, column]
+
+        ?[group_key, calls] := group_counts[group_key, calls]
+
+        :order -calls, group_key
         :limit {limit}
         "#,
     );
 
     let mut params = Params::new();
-    params.insert(
-        "module_pattern",
-        DataValue::Str(module_pattern.into()),
-    );
+    params.insert("module_pattern", DataValue::Str(module_pattern.into()));
     if let Some(fn_pat) = function_pattern {
-        params.insert(
-            "function_pattern",
-            DataValue::Str(fn_pat.into()),
-        );
+        params.insert("function_pattern", DataValue::Str(fn_pat.into()));
     }
     if let Some(a) = arity {
         params.insert("arity", DataValue::from(a));
     }
     params.insert("project", DataValue::Str(project.into()));
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| CallsError::QueryFailed {
         message: e.to_string(),
     })?;
 
-    let layout = CallRowLayout::from_headers(&rows.headers)?;
     let results = rows
         .rows
         .iter()
-        .filter_map(|row| extract_call_from_row(row, &layout))
+        .filter(|row| row.len() >= 2)
+        .filter_map(|row| {
+            Some(CallGroupCount {
+                group: extract_string(&row[0])?,
+                calls: extract_i64(&row[1], 0),
+            })
+        })
         .collect();
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(module: &str, function: &str, arity: Option<i64>) -> FunctionTarget {
+        FunctionTarget {
+            module: module.to_string(),
+            function: function.to_string(),
+            arity,
+        }
+    }
+
+    #[test]
+    fn test_find_calls_resolves_aliases() {
+        let db = crate::open_mem_db();
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Accounts": {"get/1:3": {"name": "get", "arity": 1, "line": 3, "start_line": 3, "end_line": 6, "kind": "def", "file": "lib/accounts.ex"}},
+                "MyApp.Web": {"show/1:2": {"name": "show", "arity": 1, "line": 2, "start_line": 2, "end_line": 5, "kind": "def", "file": "lib/web.ex"}}
+            },
+            "calls": [
+                {
+                    "caller": {"module": "MyApp.Accounts", "function": "get/1", "file": "lib/accounts.ex", "line": 4},
+                    "callee": {"module": "Repo", "function": "get", "arity": 2},
+                    "type": "remote"
+                },
+                {
+                    "caller": {"module": "MyApp.Web", "function": "show/1", "file": "lib/web.ex", "line": 3},
+                    "callee": {"module": "Repo", "function": "get", "arity": 2},
+                    "type": "remote"
+                }
+            ],
+            "aliases": {"MyApp.Accounts": [{"kind": "alias", "target": "MyApp.Repo", "line": 2}]}
+        }"#;
+        crate::queries::import::import_json_str(&db, json, "default").unwrap();
+
+        // The aliased call is stored by its full name, the other as recorded
+        let calls = find_calls(
+            &db,
+            CallDirection::To,
+            "Repo",
+            Some("get"),
+            None,
+            &[],
+            "default",
+            false,
+            100,
+            &QuerySettings::default(),
+        )
+        .unwrap();
+        let callees: Vec<&str> = calls.iter().map(|c| c.callee.module.as_ref()).collect();
+        assert_eq!(callees, ["MyApp.Repo", "Repo"]);
+
+        let calls = find_calls(
+            &db,
+            CallDirection::To,
+            "MyApp.Repo",
+            None,
+            None,
+            &[],
+            "default",
+            false,
+            100,
+            &QuerySettings::default(),
+        )
+        .unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].caller.module.as_ref(), "MyApp.Accounts");
+    }
+
+    #[test]
+    fn test_find_calls_filters_by_kind() {
+        let db = crate::open_mem_db();
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Accounts": {
+                    "list/0:3": {"name": "list", "arity": 0, "line": 3, "start_line": 3, "end_line": 8, "kind": "def", "file": "lib/accounts.ex"},
+                    "active?/1:10": {"name": "active?", "arity": 1, "line": 10, "start_line": 10, "end_line": 10, "kind": "defp", "file": "lib/accounts.ex"}
+                }
+            },
+            "calls": [
+                {
+                    "caller": {"module": "MyApp.Accounts", "function": "list/0", "file": "lib/accounts.ex", "line": 4},
+                    "callee": {"module": "MyApp.Repo", "function": "all", "arity": 1},
+                    "type": "remote"
+                },
+                {
+                    "caller": {"module": "MyApp.Accounts", "function": "list/0", "file": "lib/accounts.ex", "line": 5},
+                    "callee": {"module": "Enum", "function": "filter", "arity": 2},
+                    "type": "remote",
+                    "kind": "pipe"
+                },
+                {
+                    "caller": {"module": "MyApp.Accounts", "function": "list/0", "file": "lib/accounts.ex", "line": 5},
+                    "callee": {"module": "MyApp.Accounts", "function": "active?", "arity": 1},
+                    "type": "local",
+                    "kind": "capture"
+                }
+            ]
+        }"#;
+        crate::queries::import::import_json_str(&db, json, "default").unwrap();
+
+        let callees = |kinds: &[CallKind]| -> Vec<String> {
+            find_calls(&db, CallDirection::From, "MyApp.Accounts", None, None, kinds, "default", false, 100, &QuerySettings::default())
+                .unwrap()
+                .iter()
+                .map(|c| format!("{}/{}", c.callee.name, c.call_kind.as_deref().unwrap_or("")))
+                .collect()
+        };
+        assert_eq!(callees(&[]).len(), 3);
+        assert_eq!(callees(&[CallKind::Capture]), ["active?/capture"]);
+        assert_eq!(callees(&[CallKind::Remote]), ["all/call", "filter/pipe"]);
+        assert_eq!(callees(&[CallKind::Local, CallKind::Pipe]), ["filter/pipe", "active?/capture"]);
+
+        let counts = count_calls(
+            &db,
+            CallDirection::From,
+            CallGrouping::CalleeModule,
+            "MyApp.Accounts",
+            None,
+            None,
+            &[CallKind::Pipe],
+            "default",
+            false,
+            100,
+            &QuerySettings::default(),
+        )
+        .unwrap();
+        assert_eq!(counts, vec![CallGroupCount { group: "Enum".to_string(), calls: 1 }]);
+    }
+
+    #[test]
+    fn test_find_calls_for_targets_matches_each_target() {
+        let db = crate::test_utils::call_graph_db("default");
+        let targets = [target("MyApp.Repo", "get", Some(2)), target("MyApp.Accounts", "get_user", None)];
+        let calls = find_calls_for_targets(&db, CallDirection::To, &targets, &[], "default", 100, &QuerySettings::default()).unwrap();
+
+        let separately: usize = [("MyApp.Repo", "get", Some(2)), ("MyApp.Accounts", "get_user", None)]
+            .into_iter()
+            .map(|(module, function, arity)| {
+                find_calls(&db, CallDirection::To, module, Some(function), arity, &[], "default", false, 100, &QuerySettings::default())
+                    .unwrap()
+                    .len()
+            })
+            .sum();
+        assert_eq!(calls.len(), separately);
+        assert!(calls.iter().all(|c| (c.callee.name.as_ref() == "get" && c.callee.arity == 2)
+            || c.callee.name.as_ref() == "get_user"));
+        assert!(find_calls_for_targets(&db, CallDirection::To, &[], &[], "default", 100, &QuerySettings::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_count_calls_to_by_caller_module() {
+        let db = crate::test_utils::call_graph_db("default");
+        let counts = count_calls(
+            &db,
+            CallDirection::To,
+            CallGrouping::CallerModule,
+            "MyApp.Repo",
+            None,
+            None,
+            &[],
+            "default",
+            false,
+            100,
+            &QuerySettings::default(),
+        )
+        .expect("Query should succeed");
+
+        assert_eq!(
+            counts,
+            vec![
+                CallGroupCount { group: "MyApp.Accounts".to_string(), calls: 3 },
+                CallGroupCount { group: "MyApp.Service".to_string(), calls: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_calls_from_by_callee_module() {
+        let db = crate::test_utils::call_graph_db("default");
+        let counts = count_calls(
+            &db,
+            CallDirection::From,
+            CallGrouping::CalleeModule,
+            "MyApp.Service",
+            None,
+            None,
+            &[],
+            "default",
+            false,
+            100,
+            &QuerySettings::default(),
+        )
+        .expect("Query should succeed");
+
+        assert_eq!(counts[0], CallGroupCount { group: "MyApp.Service".to_string(), calls: 2 });
+        assert_eq!(counts.len(), 3);
+    }
+
+    #[test]
+    fn test_count_calls_by_file_with_limit() {
+        let db = crate::test_utils::call_graph_db("default");
+        let counts = count_calls(
+            &db,
+            CallDirection::To,
+            CallGrouping::File,
+            "MyApp.Repo",
+            None,
+            None,
+            &[],
+            "default",
+            false,
+            1,
+            &QuerySettings::default(),
+        )
+        .expect("Query should succeed");
+
+        assert_eq!(counts, vec![CallGroupCount { group: "lib/my_app/accounts.ex".to_string(), calls: 3 }]);
+    }
+}
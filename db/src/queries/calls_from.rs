@@ -5,17 +5,21 @@
 
 use std::error::Error;
 
-use super::calls::{find_calls, CallDirection};
+use super::calls::{find_calls, CallDirection, CallKind};
+use crate::query_builders::QuerySettings;
 use crate::types::Call;
 
+#[allow(clippy::too_many_arguments)]
 pub fn find_calls_from(
     db: &cozo::DbInstance,
     module_pattern: &str,
     function_pattern: Option<&str>,
     arity: Option<i64>,
+    kinds: &[CallKind],
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<Call>, Box<dyn Error>> {
     find_calls(
         db,
@@ -23,8 +27,10 @@ pub fn find_calls_from(
         module_pattern,
         function_pattern,
         arity,
+        kinds,
         project,
         use_regex,
         limit,
+        settings,
     )
 }
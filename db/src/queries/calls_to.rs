@@ -5,17 +5,21 @@
 
 use std::error::Error;
 
-use super::calls::{find_calls, CallDirection};
+use super::calls::{find_calls, find_calls_for_targets, CallDirection, CallKind};
+use crate::query_builders::{FunctionTarget, QuerySettings};
 use crate::types::Call;
 
+#[allow(clippy::too_many_arguments)]
 pub fn find_calls_to(
     db: &cozo::DbInstance,
     module_pattern: &str,
     function_pattern: Option<&str>,
     arity: Option<i64>,
+    kinds: &[CallKind],
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<Call>, Box<dyn Error>> {
     find_calls(
         db,
@@ -23,8 +27,22 @@ pub fn find_calls_to(
         module_pattern,
         function_pattern,
         arity,
+        kinds,
         project,
         use_regex,
         limit,
+        settings,
     )
 }
+
+/// Incoming calls to each of a list of exactly named functions, in one query
+pub fn find_calls_to_targets(
+    db: &cozo::DbInstance,
+    targets: &[FunctionTarget],
+    kinds: &[CallKind],
+    project: &str,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<Call>, Box<dyn Error>> {
+    find_calls_for_targets(db, CallDirection::To, targets, kinds, project, limit, settings)
+}
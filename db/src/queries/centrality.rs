@@ -0,0 +1,152 @@
+//! Function-level call edges for graph centrality analysis.
+//!
+//! Returns the distinct caller -> callee function pairs of a project. Functions
+//! are identified by module and name (caller arity suffixes are stripped), the
+//! same granularity used by hotspots. Centrality scores are computed by the command.
+
+use std::collections::HashSet;
+use std::error::Error;
+
+use cozo::DataValue;
+use thiserror::Error;
+
+use crate::db::{extract_string, run_query, strip_arity, Params};
+use crate::query_builders::QuerySettings;
+
+#[derive(Error, Debug)]
+pub enum CentralityError {
+    #[error("Centrality query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A distinct call edge between two functions
+#[derive(Debug, Clone)]
+pub struct FunctionEdge {
+    pub caller_module: String,
+    pub caller_function: String,
+    pub callee_module: String,
+    pub callee_function: String,
+}
+
+/// Find all distinct function-to-function call edges in a project.
+///
+/// Struct construction edges (`callee_function == '%'`) and direct recursion are
+/// excluded. Edges are returned in a stable order.
+pub fn find_function_edges(
+    db: &cozo::DbInstance,
+    project: &str,
+    settings: &QuerySettings,
+) -> Result<Vec<FunctionEdge>, Box<dyn Error>> {
+    let order = settings.order_by("caller_module, caller_function, callee_module, callee_function");
+    let namespace_cond = format!(
+        "{}{}",
+        settings.namespace_condition("caller_module"),
+        settings.namespace_condition("callee_module")
+    );
+    let script = format!(
+        r#"
+        ?[caller_module, caller_function, callee_module, callee_function] :=
+            *calls{{project, caller_module, caller_function, callee_module, callee_function}},
+            project == $project,
+            callee_function != '%'
+            {namespace_cond}
+        {order}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| CentralityError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut seen = HashSet::new();
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 4 {
+            let Some(caller_module) = extract_string(&row[0]) else {
+                continue;
+            };
+            let Some(caller_function) = extract_string(&row[1]) else {
+                continue;
+            };
+            let Some(callee_module) = extract_string(&row[2]) else {
+                continue;
+            };
+            let Some(callee_function) = extract_string(&row[3]) else {
+                continue;
+            };
+
+            let caller_function = strip_arity(&caller_function).to_string();
+            if caller_module == callee_module && caller_function == callee_function {
+                continue;
+            }
+
+            // Stripping the arity can collapse rows that were distinct in the database
+            let key = (
+                caller_module.clone(),
+                caller_function.clone(),
+                callee_module.clone(),
+                callee_function.clone(),
+            );
+            if !seen.insert(key) {
+                continue;
+            }
+
+            results.push(FunctionEdge {
+                caller_module,
+                caller_function,
+                callee_module,
+                callee_function,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn populated_db() -> cozo::DbInstance {
+        crate::test_utils::call_graph_db("default")
+    }
+
+    #[rstest]
+    fn test_find_function_edges(populated_db: cozo::DbInstance) {
+        let edges = find_function_edges(&populated_db, "default", &QuerySettings::default()).expect("Query should succeed");
+
+        // The fixture has 11 calls, one of them duplicated (get_user -> Repo.get)
+        assert_eq!(edges.len(), 10);
+        assert!(edges.iter().any(|e| e.caller_module == "MyApp.Service"
+            && e.caller_function == "process"
+            && e.callee_module == "MyApp.Notifier"
+            && e.callee_function == "notify"));
+    }
+
+    #[rstest]
+    fn test_find_function_edges_unsorted(populated_db: cozo::DbInstance) {
+        let sorted = find_function_edges(&populated_db, "default", &QuerySettings::default()).expect("Query should succeed");
+        let unsorted = find_function_edges(&populated_db, "default", &QuerySettings { sorted: false, ..Default::default() });
+
+        // Same edges, whatever their order
+        let edge_set = |edges: &[FunctionEdge]| -> std::collections::BTreeSet<String> {
+            edges.iter().map(|e| format!("{:?}", e)).collect()
+        };
+        let unsorted = unsorted.expect("Query should succeed");
+        assert_eq!(unsorted.len(), sorted.len());
+        assert_eq!(edge_set(&unsorted), edge_set(&sorted));
+    }
+
+    #[rstest]
+    fn test_find_function_edges_wrong_project(populated_db: cozo::DbInstance) {
+        let edges = find_function_edges(&populated_db, "other", &QuerySettings::default()).expect("Query should succeed");
+        assert!(edges.is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+//! Function clauses with their line ranges, for mapping changed lines to functions.
+//!
+//! Used by `pr`, which matches the lines of a diff against these ranges.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+
+#[derive(Error, Debug)]
+pub enum ChangesError {
+    #[error("Changes query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// One clause of a function and the lines it spans
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionClause {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub file: String,
+    pub start_line: i64,
+    pub end_line: i64,
+    pub complexity: i64,
+}
+
+/// Every clause of a project that has a file, ordered by file and line
+pub fn find_function_clauses(db: &cozo::DbInstance, project: &str) -> Result<Vec<FunctionClause>, Box<dyn Error>> {
+    let script = r#"
+        ?[file, start_line, end_line, module, name, arity, complexity] :=
+            *function_locations{project, module, name, arity, file, start_line, end_line, complexity},
+            project == $project,
+            file != ""
+
+        :order file, start_line, module, name, arity
+    "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| ChangesError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 7 {
+            let Some(file) = extract_string(&row[0]) else { continue };
+            let Some(module) = extract_string(&row[3]) else { continue };
+            let Some(name) = extract_string(&row[4]) else { continue };
+            results.push(FunctionClause {
+                module,
+                name,
+                arity: extract_i64(&row[5], 0),
+                file,
+                start_line: extract_i64(&row[1], 0),
+                end_line: extract_i64(&row[2], 0),
+                complexity: extract_i64(&row[6], 0),
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_function_clauses() {
+        let db = crate::test_utils::call_graph_db("default");
+        let clauses = find_function_clauses(&db, "default").unwrap();
+
+        assert!(!clauses.is_empty());
+        assert!(clauses.iter().all(|c| !c.file.is_empty() && c.start_line <= c.end_line));
+        assert!(clauses.windows(2).all(|w| (&w[0].file, w[0].start_line) <= (&w[1].file, w[1].start_line)));
+        assert!(find_function_clauses(&db, "other").unwrap().is_empty());
+    }
+}
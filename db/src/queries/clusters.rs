@@ -1,13 +1,15 @@
 //! Query to get all module calls for cluster analysis.
 //!
 //! Returns calls between different modules (no self-calls).
-//! Clusters are computed in Rust by grouping modules by namespace.
+//! Clusters are computed in Rust by grouping modules by namespace;
+//! communities are detected in Rust from the weighted module graph.
 
 use std::error::Error;
 
 use cozo::DataValue;
 
-use crate::db::{run_query, Params};
+use crate::db::{extract_i64, extract_string, run_query, Params};
+use crate::query_builders::QuerySettings;
 
 /// Represents a call between two different modules
 #[derive(Debug, Clone)]
@@ -18,20 +20,32 @@ pub struct ModuleCall {
 
 /// Get all inter-module calls (calls between different modules)
 ///
-/// Returns calls where caller_module != callee_module.
-/// These are used to compute internal vs external connectivity per namespace cluster.
-pub fn get_module_calls(db: &cozo::DbInstance, project: &str) -> Result<Vec<ModuleCall>, Box<dyn Error>> {
-    let script = r#"
+/// Returns calls where caller_module != callee_module, both within the
+/// `--namespace` scope. These are used to compute internal vs external
+/// connectivity per namespace cluster.
+pub fn get_module_calls(
+    db: &cozo::DbInstance,
+    project: &str,
+    settings: &QuerySettings,
+) -> Result<Vec<ModuleCall>, Box<dyn Error>> {
+    let script = format!(
+        r#"
         ?[caller_module, callee_module] :=
-            *calls{project, caller_module, callee_module},
+            *calls{{project, caller_module, callee_module}},
             project == $project,
             caller_module != callee_module
-    "#;
+            {}{}
+    "#,
+        settings.namespace_condition("caller_module"),
+        settings.namespace_condition("callee_module"),
+    );
 
     let mut params = Params::new();
     params.insert("project", DataValue::Str(project.into()));
 
-    let rows = run_query(db, script, params)?;
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params)?;
 
     let caller_idx = rows.headers.iter().position(|h| h == "caller_module")
         .ok_or("Missing caller_module column")?;
@@ -56,3 +70,101 @@ pub fn get_module_calls(db: &cozo::DbInstance, project: &str) -> Result<Vec<Modu
 
     Ok(results)
 }
+
+/// Number of call sites from one module to another
+#[derive(Debug, Clone)]
+pub struct ModuleCallCount {
+    pub caller_module: String,
+    pub callee_module: String,
+    pub call_count: i64,
+}
+
+/// Get inter-module call counts (one row per caller/callee module pair)
+///
+/// Each call site counts once, so the count can be used as an edge weight.
+pub fn get_module_call_counts(
+    db: &cozo::DbInstance,
+    project: &str,
+    settings: &QuerySettings,
+) -> Result<Vec<ModuleCallCount>, Box<dyn Error>> {
+    let order = settings.order_by("caller_module, callee_module");
+    let namespace_cond = format!(
+        "{}{}",
+        settings.namespace_condition("caller_module"),
+        settings.namespace_condition("callee_module")
+    );
+    let script = format!(
+        r#"
+        call_sites[caller_module, callee_module, file, line, column] :=
+            *calls{{project, caller_module, callee_module, file, line, column}},
+            project == $project,
+            caller_module != callee_module
+            {namespace_cond}
+
+        ?[caller_module, callee_module, count(line)] :=
+            call_sites[caller_module, callee_module, file, line, column]
+
+        {order}
+    "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params)?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 3 {
+            let Some(caller_module) = extract_string(&row[0]) else { continue };
+            let Some(callee_module) = extract_string(&row[1]) else { continue };
+            let call_count = extract_i64(&row[2], 0);
+
+            results.push(ModuleCallCount {
+                caller_module,
+                callee_module,
+                call_count,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_module_call_counts() {
+        let db = crate::test_utils::call_graph_db("default");
+        let counts = get_module_call_counts(&db, "default", &QuerySettings::default()).expect("Query should succeed");
+
+        // Accounts.get_user calls Repo.get twice, Accounts.list_users calls Repo.all once
+        let accounts_repo = counts
+            .iter()
+            .find(|c| c.caller_module == "MyApp.Accounts" && c.callee_module == "MyApp.Repo")
+            .expect("Accounts -> Repo edge");
+        assert_eq!(accounts_repo.call_count, 3);
+        assert_eq!(counts.len(), 5);
+    }
+
+    #[test]
+    fn test_get_module_call_counts_in_namespace() {
+        let db = crate::test_utils::call_graph_db("default");
+        let settings = QuerySettings {
+            namespaces: vec!["MyApp.Accounts".to_string(), "MyApp.Repo".to_string()],
+            ..Default::default()
+        };
+        let counts = get_module_call_counts(&db, "default", &settings);
+
+        let edges: Vec<_> = counts
+            .expect("Query should succeed")
+            .into_iter()
+            .map(|c| format!("{} -> {}", c.caller_module, c.callee_module))
+            .collect();
+        assert_eq!(edges, ["MyApp.Accounts -> MyApp.Repo"]);
+    }
+}
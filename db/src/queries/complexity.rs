@@ -1,11 +1,13 @@
 use std::error::Error;
 
+use clap::ValueEnum;
 use cozo::DataValue;
 use serde::Serialize;
 use thiserror::Error;
 
-use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder};
+use crate::db::{extract_f64, extract_i64, extract_string, extract_string_or, run_query, Params};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+use crate::where_filter::{ColumnType, WhereFilter};
 
 #[derive(Error, Debug)]
 pub enum ComplexityError {
@@ -13,6 +15,47 @@ pub enum ComplexityError {
     QueryFailed { message: String },
 }
 
+/// Metric that complexity results are ranked by, highest first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ComplexityMetricKind {
+    /// Cyclomatic complexity
+    #[default]
+    Cyclomatic,
+    /// ABC size: sqrt(assignments² + branches² + conditions²)
+    Abc,
+    /// Halstead volume: program length × log2(vocabulary)
+    Halstead,
+    /// Number of arguments (arity)
+    Args,
+    /// Cognitive complexity, selected with `complexity --cognitive`
+    #[value(skip)]
+    Cognitive,
+}
+
+impl ComplexityMetricKind {
+    /// Result column holding the metric
+    fn column(&self) -> &'static str {
+        match self {
+            ComplexityMetricKind::Cyclomatic => "complexity",
+            ComplexityMetricKind::Abc => "abc_score",
+            ComplexityMetricKind::Halstead => "halstead_volume",
+            ComplexityMetricKind::Args => "arity",
+            ComplexityMetricKind::Cognitive => "cognitive_complexity",
+        }
+    }
+}
+
+/// Minimum metric values a function clause must reach to be reported
+#[derive(Debug, Clone, Default)]
+pub struct ComplexityThresholds {
+    pub complexity: i64,
+    pub cognitive: i64,
+    pub depth: i64,
+    pub abc: f64,
+    pub halstead: f64,
+    pub args: i64,
+}
+
 /// A function with complexity metrics
 #[derive(Debug, Clone, Serialize)]
 pub struct ComplexityMetric {
@@ -26,17 +69,45 @@ pub struct ComplexityMetric {
     pub end_line: i64,
     pub lines: i64,
     pub generated_by: String,
+    pub file: String,
+    /// ABC size metric, 0.0 when the extractor did not emit one
+    pub abc_score: f64,
+    /// Halstead volume, 0.0 when the extractor did not emit one
+    pub halstead_volume: f64,
+    /// SonarSource-style cognitive complexity, 0 when the extractor did not emit one
+    pub cognitive_complexity: i64,
 }
 
+/// Columns a `--where` filter may compare
+const WHERE_COLUMNS: &[(&str, ColumnType)] = &[
+    ("module", ColumnType::Text),
+    ("name", ColumnType::Text),
+    ("arity", ColumnType::Number),
+    ("line", ColumnType::Number),
+    ("complexity", ColumnType::Number),
+    ("max_nesting_depth", ColumnType::Number),
+    ("start_line", ColumnType::Number),
+    ("end_line", ColumnType::Number),
+    ("lines", ColumnType::Number),
+    ("generated_by", ColumnType::Text),
+    ("file", ColumnType::Text),
+    ("abc_score", ColumnType::Number),
+    ("halstead_volume", ColumnType::Number),
+    ("cognitive_complexity", ColumnType::Number),
+];
+
+#[allow(clippy::too_many_arguments)]
 pub fn find_complexity_metrics(
     db: &cozo::DbInstance,
-    min_complexity: i64,
-    min_depth: i64,
+    thresholds: &ComplexityThresholds,
+    order_by: ComplexityMetricKind,
     module_pattern: Option<&str>,
     project: &str,
     use_regex: bool,
     exclude_generated: bool,
+    filter: Option<&WhereFilter>,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<ComplexityMetric>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[module_pattern])?;
 
@@ -45,45 +116,64 @@ pub fn find_complexity_metrics(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     // Build optional generated filter
     let generated_filter = if exclude_generated {
-        ", generated_by == \"\"".to_string()
+        settings.generated_condition("name", "file", "generated_by")
     } else {
         String::new()
     };
 
+    let mut params = Params::new();
+    let where_cond = match filter {
+        Some(filter) => filter.condition(WHERE_COLUMNS, &mut params)?,
+        None => String::new(),
+    };
+
     let script = format!(
         r#"
-        ?[module, name, arity, line, complexity, max_nesting_depth, start_line, end_line, lines, generated_by] :=
-            *function_locations{{project, module, name, arity, line, complexity, max_nesting_depth, start_line, end_line, generated_by}},
+        ?[module, name, arity, line, complexity, max_nesting_depth, start_line, end_line, lines, generated_by, file, abc_score, halstead_volume, cognitive_complexity] :=
+            *function_locations{{project, module, name, arity, line, complexity, max_nesting_depth, start_line, end_line, generated_by, file, abc_score, halstead_volume, cognitive_complexity}},
             project == $project,
             complexity >= $min_complexity,
+            cognitive_complexity >= $min_cognitive,
             max_nesting_depth >= $min_depth,
+            abc_score >= $min_abc,
+            halstead_volume >= $min_halstead,
+            arity >= $min_args,
             lines = end_line - start_line + 1
             {module_cond}
+            {namespace_cond}
             {generated_filter}
+            {where_cond}
 
-        :order -complexity, module, name
+        :order -{order_column}, module, name
         :limit {limit}
         "#,
+        order_column = order_by.column(),
     );
 
-    let mut params = Params::new();
     params.insert("project", DataValue::Str(project.into()));
-    params.insert("min_complexity", DataValue::from(min_complexity));
-    params.insert("min_depth", DataValue::from(min_depth));
+    params.insert("min_complexity", DataValue::from(thresholds.complexity));
+    params.insert("min_cognitive", DataValue::from(thresholds.cognitive));
+    params.insert("min_depth", DataValue::from(thresholds.depth));
+    params.insert("min_abc", DataValue::from(thresholds.abc));
+    params.insert("min_halstead", DataValue::from(thresholds.halstead));
+    params.insert("min_args", DataValue::from(thresholds.args));
     if let Some(pattern) = module_pattern {
         params.insert("module_pattern", DataValue::Str(pattern.into()));
     }
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| ComplexityError::QueryFailed {
         message: e.to_string(),
     })?;
 
     let mut results = Vec::new();
     for row in rows.rows {
-        if row.len() >= 10 {
+        if row.len() >= 14 {
             let Some(module) = extract_string(&row[0]) else { continue };
             let Some(name) = extract_string(&row[1]) else { continue };
             let arity = extract_i64(&row[2], 0);
@@ -94,6 +184,10 @@ pub fn find_complexity_metrics(
             let end_line = extract_i64(&row[7], 0);
             let lines = extract_i64(&row[8], 0);
             let Some(generated_by) = extract_string(&row[9]) else { continue };
+            let file = extract_string_or(&row[10], "");
+            let abc_score = extract_f64(&row[11], 0.0);
+            let halstead_volume = extract_f64(&row[12], 0.0);
+            let cognitive_complexity = extract_i64(&row[13], 0);
 
             results.push(ComplexityMetric {
                 module,
@@ -106,9 +200,146 @@ pub fn find_complexity_metrics(
                 end_line,
                 lines,
                 generated_by,
+                file,
+                abc_score,
+                halstead_volume,
+                cognitive_complexity,
             });
         }
     }
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METRICS_JSON: &str = r#"{
+        "structs": {},
+        "calls": [],
+        "function_locations": {
+            "MyApp.Accounts": {
+                "create/3:10": {"name": "create", "arity": 3, "file": "lib/accounts.ex", "kind": "def",
+                    "line": 10, "start_line": 10, "end_line": 30, "complexity": 2, "cognitive_complexity": 9,
+                    "abc_score": 24.5, "halstead_volume": 310.2},
+                "update/4:40": {"name": "update", "arity": 4, "file": "lib/accounts.ex", "kind": "def",
+                    "line": 40, "start_line": 40, "end_line": 45, "complexity": 6, "cognitive_complexity": 3,
+                    "abc_score": 8.1, "halstead_volume": 512.0},
+                "list/0:50": {"name": "list", "arity": 0, "file": "lib/accounts.ex", "kind": "def",
+                    "line": 50, "start_line": 50, "end_line": 51}
+            }
+        }
+    }"#;
+
+    fn names(metrics: &[ComplexityMetric]) -> Vec<&str> {
+        metrics.iter().map(|m| m.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_find_complexity_metrics_orders_by_metric() {
+        let db = crate::test_utils::setup_test_db(METRICS_JSON, "default");
+        let thresholds = ComplexityThresholds::default();
+        let find = |kind| {
+            find_complexity_metrics(&db, &thresholds, kind, None, "default", false, false, None, 10, &QuerySettings::default())
+                .expect("Query should succeed")
+        };
+
+        assert_eq!(names(&find(ComplexityMetricKind::Cyclomatic)), ["update", "create", "list"]);
+        assert_eq!(names(&find(ComplexityMetricKind::Abc)), ["create", "update", "list"]);
+        assert_eq!(names(&find(ComplexityMetricKind::Halstead)), ["update", "create", "list"]);
+        assert_eq!(names(&find(ComplexityMetricKind::Args)), ["update", "create", "list"]);
+        assert_eq!(names(&find(ComplexityMetricKind::Cognitive)), ["create", "update", "list"]);
+
+        let create = &find(ComplexityMetricKind::Abc)[0];
+        assert_eq!(create.abc_score, 24.5);
+        assert_eq!(create.halstead_volume, 310.2);
+    }
+
+    #[test]
+    fn test_find_complexity_metrics_thresholds() {
+        let db = crate::test_utils::setup_test_db(METRICS_JSON, "default");
+        let thresholds = ComplexityThresholds {
+            abc: 10.0,
+            ..Default::default()
+        };
+        let metrics = find_complexity_metrics(
+            &db,
+            &thresholds,
+            ComplexityMetricKind::Cyclomatic,
+            None,
+            "default",
+            false,
+            false,
+            None,
+            10,
+            &QuerySettings::default(),
+        )
+        .expect("Query should succeed");
+        assert_eq!(names(&metrics), ["create"]);
+
+        let thresholds = ComplexityThresholds {
+            args: 4,
+            halstead: 500.0,
+            ..Default::default()
+        };
+        let metrics = find_complexity_metrics(
+            &db,
+            &thresholds,
+            ComplexityMetricKind::Cyclomatic,
+            None,
+            "default",
+            false,
+            false,
+            None,
+            10,
+            &QuerySettings::default(),
+        )
+        .expect("Query should succeed");
+        assert_eq!(names(&metrics), ["update"]);
+
+        let thresholds = ComplexityThresholds {
+            cognitive: 5,
+            ..Default::default()
+        };
+        let metrics = find_complexity_metrics(
+            &db,
+            &thresholds,
+            ComplexityMetricKind::Cognitive,
+            None,
+            "default",
+            false,
+            false,
+            None,
+            10,
+            &QuerySettings::default(),
+        )
+        .expect("Query should succeed");
+        assert_eq!(names(&metrics), ["create"]);
+        assert_eq!(metrics[0].cognitive_complexity, 9);
+    }
+
+    #[test]
+    fn test_find_complexity_metrics_where_filter() {
+        let db = crate::test_utils::setup_test_db(METRICS_JSON, "default");
+        let find = |expr: &str| {
+            let filter: WhereFilter = expr.parse().expect("Filter should parse");
+            find_complexity_metrics(
+                &db,
+                &ComplexityThresholds::default(),
+                ComplexityMetricKind::Cyclomatic,
+                None,
+                "default",
+                false,
+                false,
+                Some(&filter),
+                10,
+                &QuerySettings::default(),
+            )
+        };
+
+        assert_eq!(names(&find("lines > 10 || name == \"list\"").unwrap()), ["create", "list"]);
+        assert_eq!(names(&find("!(abc_score < 10.0) && module =~ \"Accounts$\"").unwrap()), ["create"]);
+        assert!(find("size > 1").unwrap_err().to_string().contains("unknown column"));
+    }
+}
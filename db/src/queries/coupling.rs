@@ -0,0 +1,249 @@
+//! Afferent/efferent coupling and abstractness counts per module.
+//!
+//! - Afferent coupling (Ca): number of distinct modules that call into a module
+//! - Efferent coupling (Ce): number of distinct modules a module calls
+//! - Abstractness inputs: `@callback` definitions vs public functions
+//!
+//! Ratios (instability, abstractness, distance) are derived by the command.
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use cozo::DataValue;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum CouplingError {
+    #[error("Coupling query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// Afferent and efferent coupling of a single module
+#[derive(Debug, Clone)]
+pub struct ModuleCoupling {
+    pub module: String,
+    /// Distinct modules depending on this module (Ca)
+    pub afferent: i64,
+    /// Distinct modules this module depends on (Ce)
+    pub efferent: i64,
+}
+
+/// Compute afferent/efferent coupling for every module that takes part in a
+/// cross-module call.
+pub fn find_module_coupling(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<ModuleCoupling>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+
+    let script = format!(
+        r#"
+        # Distinct module-level dependencies
+        deps[from, to] :=
+            *calls{{project, caller_module: from, callee_module: to}},
+            project == $project,
+            from != to
+
+        modules[m] := deps[m, _]
+        modules[m] := deps[_, m]
+
+        efferent_counts[m, count(to)] := deps[m, to]
+        afferent_counts[m, count(from)] := deps[from, m]
+
+        # Default missing counts to zero
+        ce[m, n] := efferent_counts[m, n]
+        ce[m, n] := modules[m], not efferent_counts[m, _], n = 0
+        ca[m, n] := afferent_counts[m, n]
+        ca[m, n] := modules[m], not afferent_counts[m, _], n = 0
+
+        ?[module, afferent, efferent] :=
+            modules[module],
+            ca[module, afferent],
+            ce[module, efferent]
+            {module_cond}
+            {namespace_cond}
+
+        :order module
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| CouplingError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 3 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let afferent = extract_i64(&row[1], 0);
+            let efferent = extract_i64(&row[2], 0);
+
+            results.push(ModuleCoupling {
+                module,
+                afferent,
+                efferent,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Count abstract and concrete elements per module.
+///
+/// Returns module -> (callback count, public function count). Callbacks are
+/// `@callback` specs; public functions are distinct `def`/`defmacro` name/arity pairs.
+pub fn get_abstractness_counts(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<HashMap<String, (i64, i64)>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+
+    let script = format!(
+        r#"
+        callback_defs[module, name, arity] :=
+            *specs{{project, module, name, arity, kind}},
+            project == $project,
+            kind == "callback"
+
+        public_fns[module, name, arity] :=
+            *function_locations{{project, module, name, arity, kind}},
+            project == $project,
+            (kind == "def" or kind == "defmacro")
+
+        callback_counts[module, count(name)] := callback_defs[module, name, arity]
+        public_counts[module, count(name)] := public_fns[module, name, arity]
+
+        all_modules[module] := callback_defs[module, _, _]
+        all_modules[module] := public_fns[module, _, _]
+
+        cb[m, n] := callback_counts[m, n]
+        cb[m, n] := all_modules[m], not callback_counts[m, _], n = 0
+        pf[m, n] := public_counts[m, n]
+        pf[m, n] := all_modules[m], not public_counts[m, _], n = 0
+
+        ?[module, callbacks, public] :=
+            all_modules[module],
+            cb[module, callbacks],
+            pf[module, public]
+            {module_cond}
+            {namespace_cond}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| CouplingError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut counts = HashMap::new();
+    for row in rows.rows {
+        if row.len() >= 3
+            && let Some(module) = extract_string(&row[0])
+        {
+            let callbacks = extract_i64(&row[1], 0);
+            let public = extract_i64(&row[2], 0);
+            counts.insert(module, (callbacks, public));
+        }
+    }
+
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn populated_db() -> cozo::DbInstance {
+        crate::test_utils::call_graph_db("default")
+    }
+
+    fn coupling_of<'a>(results: &'a [ModuleCoupling], module: &str) -> &'a ModuleCoupling {
+        results
+            .iter()
+            .find(|c| c.module == module)
+            .unwrap_or_else(|| panic!("Missing module {}", module))
+    }
+
+    #[rstest]
+    fn test_find_module_coupling_counts(populated_db: cozo::DbInstance) {
+        let results = find_module_coupling(&populated_db, "default", None, false, &QuerySettings::default())
+            .expect("Query should succeed");
+
+        // Controller calls Accounts and Service, nobody calls Controller
+        let controller = coupling_of(&results, "MyApp.Controller");
+        assert_eq!(controller.afferent, 0);
+        assert_eq!(controller.efferent, 2);
+
+        // Repo is called by Accounts and Service, calls nothing outside itself
+        let repo = coupling_of(&results, "MyApp.Repo");
+        assert_eq!(repo.afferent, 2);
+        assert_eq!(repo.efferent, 0);
+    }
+
+    #[rstest]
+    fn test_find_module_coupling_with_filter(populated_db: cozo::DbInstance) {
+        let results = find_module_coupling(&populated_db, "default", Some("MyApp.Repo"), false, &QuerySettings::default())
+            .expect("Query should succeed");
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].module, "MyApp.Repo");
+    }
+
+    #[rstest]
+    fn test_find_module_coupling_wrong_project(populated_db: cozo::DbInstance) {
+        let results = find_module_coupling(&populated_db, "other", None, false, &QuerySettings::default())
+            .expect("Query should succeed");
+        assert!(results.is_empty());
+    }
+
+    #[rstest]
+    fn test_get_abstractness_counts(populated_db: cozo::DbInstance) {
+        let counts = get_abstractness_counts(&populated_db, "default", None, false, &QuerySettings::default())
+            .expect("Query should succeed");
+
+        assert!(!counts.is_empty());
+        for (module, (callbacks, public)) in &counts {
+            assert!(*callbacks >= 0, "{} has negative callbacks", module);
+            assert!(*public >= 0, "{} has negative public count", module);
+        }
+    }
+}
@@ -11,6 +11,7 @@ use std::error::Error;
 use cozo::DataValue;
 
 use crate::db::{run_query, Params};
+use crate::query_builders::QuerySettings;
 
 /// Edge in a cycle (from module -> to module)
 #[derive(Debug, Clone)]
@@ -26,14 +27,18 @@ pub fn find_cycle_edges(
     db: &cozo::DbInstance,
     project: &str,
     module_pattern: Option<&str>,
+    settings: &QuerySettings,
 ) -> Result<Vec<CycleEdge>, Box<dyn Error>> {
     // Build the recursive query for cycle detection
-    let script = r#"
+    let namespace_cond = format!("{}{}", settings.namespace_condition("from"), settings.namespace_condition("to"));
+    let script = format!(
+        r#"
         # Build module dependency graph (deduplicated at module level)
         module_deps[from, to] :=
-            *calls{project, caller_module: from, callee_module: to},
+            *calls{{project, caller_module: from, callee_module: to}},
             project == $project,
             from != to
+            {namespace_cond}
 
         # Find reachability (transitive closure) - what modules can be reached from each module
         reaches[from, to] := module_deps[from, to]
@@ -50,11 +55,14 @@ pub fn find_cycle_edges(
 
         ?[from, to] := cycle_edge[from, to]
         :order from, to
-    "#.to_string();
+    "#
+    );
 
     let mut params = Params::new();
     params.insert("project", DataValue::Str(project.into()));
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params)?;
 
     // Parse results
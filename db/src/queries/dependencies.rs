@@ -10,8 +10,8 @@ use cozo::DataValue;
 use thiserror::Error;
 
 use crate::db::{extract_call_from_row, run_query, CallRowLayout, Params};
-use crate::types::Call;
-use crate::query_builders::ConditionBuilder;
+use crate::types::{Call, Interner};
+use crate::query_builders::{ConditionBuilder, QuerySettings};
 
 #[derive(Error, Debug)]
 pub enum DependencyError {
@@ -39,6 +39,15 @@ impl DependencyDirection {
         }
     }
 
+    /// The modules on the other end of the dependencies, which `--namespace`
+    /// restricts
+    fn other_field(&self) -> &'static str {
+        match self {
+            DependencyDirection::Outgoing => "callee_module",
+            DependencyDirection::Incoming => "caller_module",
+        }
+    }
+
     /// Returns the ORDER BY clause based on direction
     fn order_clause(&self) -> &'static str {
         match self {
@@ -65,6 +74,7 @@ pub fn find_dependencies(
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<Call>, Box<dyn Error>> {
     let filter_field = direction.filter_field();
     let order_clause = direction.order_clause();
@@ -72,19 +82,21 @@ pub fn find_dependencies(
     // Build module condition using the appropriate field name
     let module_cond =
         ConditionBuilder::new(filter_field, "module_pattern").build(use_regex);
+    let namespace_cond = settings.namespace_condition(direction.other_field());
 
     // Query calls with function_locations join for caller metadata, excluding self-references
     // Filter out struct calls (callee_function != '%')
     let script = format!(
         r#"
-        ?[caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line] :=
-            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line}},
+        ?[caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line, weight] :=
+            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line, weight}},
             *function_locations{{project, module: caller_module, name: caller_name, arity: caller_arity, kind: caller_kind, start_line: caller_start_line, end_line: caller_end_line}},
             starts_with(caller_function, caller_name),
             call_line >= caller_start_line,
             call_line <= caller_end_line,
             callee_function != '%',
-            {module_cond},
+            {module_cond}
+            {namespace_cond},
             caller_module != callee_module,
             project == $project
         :order {order_clause}
@@ -99,15 +111,18 @@ pub fn find_dependencies(
     );
     params.insert("project", DataValue::Str(project.into()));
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| DependencyError::QueryFailed {
         message: e.to_string(),
     })?;
 
     let layout = CallRowLayout::from_headers(&rows.headers)?;
+    let mut interner = Interner::default();
     let results = rows
         .rows
         .iter()
-        .filter_map(|row| extract_call_from_row(row, &layout))
+        .filter_map(|row| extract_call_from_row(row, &layout, &mut interner))
         .collect();
 
     Ok(results)
@@ -6,6 +6,7 @@
 use std::error::Error;
 
 use super::dependencies::{find_dependencies as query_dependencies, DependencyDirection};
+use crate::query_builders::QuerySettings;
 use crate::types::Call;
 
 pub fn find_dependencies(
@@ -14,6 +15,7 @@ pub fn find_dependencies(
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<Call>, Box<dyn Error>> {
     query_dependencies(
         db,
@@ -22,5 +24,6 @@ pub fn find_dependencies(
         project,
         use_regex,
         limit,
+        settings,
     )
 }
@@ -0,0 +1,157 @@
+//! Call sites of deprecated functions.
+//!
+//! A function is deprecated when the extractor emitted the message of its
+//! `@deprecated` attribute, stored in the `deprecated` column of
+//! `function_locations`. Calls made from within deprecated functions are
+//! reported too: they still have to be migrated before the function goes.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, extract_string_or, run_query, Params};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum DeprecatedError {
+    #[error("Deprecated usage query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A call to a deprecated function
+#[derive(Debug, Clone, Serialize)]
+pub struct DeprecatedCall {
+    pub caller_module: String,
+    pub caller_function: String,
+    pub file: String,
+    pub line: i64,
+    pub callee_module: String,
+    pub callee_function: String,
+    pub callee_arity: i64,
+    /// Message of the callee's @deprecated attribute
+    pub message: String,
+}
+
+/// Find calls to deprecated functions of `project`.
+///
+/// `module_pattern` narrows the calling modules. Results are ordered by caller
+/// module, then by call site.
+pub fn find_deprecated_calls(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<DeprecatedCall>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("caller_module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("caller_module");
+
+    let script = format!(
+        r#"
+        deprecated_function[module, name, arity, message] :=
+            *function_locations{{project, module, name, arity, deprecated: message}},
+            project == $project,
+            message != ""
+
+        ?[caller_module, caller_function, file, line, callee_module, callee_function, callee_arity, message] :=
+            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line}},
+            project == $project,
+            deprecated_function[callee_module, callee_function, callee_arity, message]
+            {module_cond}
+            {namespace_cond}
+
+        :order caller_module, file, line, callee_module, callee_function, callee_arity
+        :limit {limit}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| DeprecatedError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 8 {
+            let Some(caller_module) = extract_string(&row[0]) else { continue };
+            let Some(caller_function) = extract_string(&row[1]) else { continue };
+            let file = extract_string_or(&row[2], "");
+            let line = extract_i64(&row[3], 0);
+            let Some(callee_module) = extract_string(&row[4]) else { continue };
+            let Some(callee_function) = extract_string(&row[5]) else { continue };
+            let callee_arity = extract_i64(&row[6], 0);
+            let message = extract_string_or(&row[7], "");
+
+            results.push(DeprecatedCall {
+                caller_module,
+                caller_function,
+                file,
+                line,
+                callee_module,
+                callee_function,
+                callee_arity,
+                message,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPRECATED_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Legacy": {
+                "fetch/1:3": {"name": "fetch", "arity": 1, "file": "lib/legacy.ex", "kind": "def", "line": 3,
+                    "start_line": 3, "end_line": 5, "deprecated": "Use MyApp.Accounts.get_user/1"},
+                "fetch/2:7": {"name": "fetch", "arity": 2, "file": "lib/legacy.ex", "kind": "def", "line": 7,
+                    "start_line": 7, "end_line": 9}
+            },
+            "MyApp.Web": {
+                "show/2:4": {"name": "show", "arity": 2, "file": "lib/web.ex", "kind": "def", "line": 4, "start_line": 4, "end_line": 12}
+            },
+            "MyApp.Jobs": {
+                "run/0:2": {"name": "run", "arity": 0, "file": "lib/jobs.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 6}
+            }
+        },
+        "calls": [
+            {"caller": {"module": "MyApp.Web", "function": "show", "file": "lib/web.ex", "line": 8, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Legacy", "function": "fetch", "arity": 1}},
+            {"caller": {"module": "MyApp.Web", "function": "show", "file": "lib/web.ex", "line": 10, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Legacy", "function": "fetch", "arity": 2}},
+            {"caller": {"module": "MyApp.Jobs", "function": "run", "file": "lib/jobs.ex", "line": 4, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Legacy", "function": "fetch", "arity": 1}}
+        ]
+    }"#;
+
+    #[test]
+    fn test_find_deprecated_calls() {
+        let db = crate::test_utils::setup_test_db(DEPRECATED_JSON, "default");
+        let calls = find_deprecated_calls(&db, "default", None, false, 100, &QuerySettings::default()).expect("Query should succeed");
+
+        let sites: Vec<(&str, i64)> = calls.iter().map(|c| (c.caller_module.as_str(), c.line)).collect();
+        assert_eq!(sites, [("MyApp.Jobs", 4), ("MyApp.Web", 8)]);
+        assert_eq!(calls[0].callee_function, "fetch");
+        assert_eq!(calls[0].callee_arity, 1);
+        assert_eq!(calls[0].message, "Use MyApp.Accounts.get_user/1");
+
+        let web = find_deprecated_calls(&db, "default", Some("MyApp.Web"), false, 100, &QuerySettings::default()).expect("Query should succeed");
+        assert_eq!(web.len(), 1);
+    }
+}
@@ -0,0 +1,197 @@
+//! Full-text search over `@moduledoc` and `@doc` text.
+//!
+//! Matching and scoring use the `docs:text` FTS index (stemmed, stopwords
+//! removed). Snippets are cut from the matching doc in Rust, with the words
+//! matching a query term wrapped in `**`.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_f64, extract_i64, extract_string, run_query, Params};
+
+#[derive(Error, Debug)]
+pub enum DocsError {
+    #[error("Docs search failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// Words of context kept before the first match in a snippet
+const SNIPPET_LEAD: usize = 5;
+/// Maximum number of words in a snippet
+const SNIPPET_WORDS: usize = 20;
+
+/// A module or function whose documentation matches a search
+#[derive(Debug, Clone, Serialize)]
+pub struct DocMatch {
+    pub module: String,
+    /// Function name; absent for a module doc
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arity: Option<i64>,
+    /// "moduledoc" or "doc"
+    pub kind: String,
+    /// Relevance (TF-IDF) of the doc for the query
+    pub score: f64,
+    /// Excerpt around the first match, matches wrapped in `**`
+    pub snippet: String,
+}
+
+/// Search docs for `query`, most relevant first
+pub fn search_docs(
+    db: &cozo::DbInstance,
+    query: &str,
+    project: &str,
+    limit: u32,
+) -> Result<Vec<DocMatch>, Box<dyn Error>> {
+    let script = format!(
+        r#"
+        ?[module, name, arity, kind, doc, score] :=
+            ~docs:text{{project, module, name, arity, kind, doc |
+                query: $query,
+                k: {limit},
+                bind_score: score,
+                filter: project == $project
+            }}
+
+        :order -score, module, name, arity
+        :limit {limit}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("query", DataValue::Str(query.into()));
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, &script, params).map_err(|e| DocsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 6 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(name) = extract_string(&row[1]) else { continue };
+            let arity = extract_i64(&row[2], -1);
+            let Some(kind) = extract_string(&row[3]) else { continue };
+            let Some(doc) = extract_string(&row[4]) else { continue };
+            let score = extract_f64(&row[5], 0.0);
+
+            let is_module_doc = name.is_empty();
+            results.push(DocMatch {
+                module,
+                name: (!is_module_doc).then_some(name),
+                arity: (!is_module_doc).then_some(arity),
+                kind,
+                score: (score * 1000.0).round() / 1000.0,
+                snippet: highlight_snippet(&doc, query),
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Cut an excerpt of `doc` around its first word matching `query`.
+///
+/// A word matches a query term when one is a prefix of the other (at least
+/// three characters), which approximates the index's stemming: `users`
+/// matches `user`. Without any match the excerpt starts at the beginning.
+pub fn highlight_snippet(doc: &str, query: &str) -> String {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let words: Vec<&str> = doc.split_whitespace().collect();
+    let matches = |word: &str| {
+        let word: String = word
+            .trim_matches(|c: char| !c.is_alphanumeric() && c != '_')
+            .to_lowercase();
+        terms.iter().any(|term| {
+            let (short, long) = if term.len() <= word.len() { (term, &word) } else { (&word, term) };
+            short.len() >= 3 && long.starts_with(short.as_str()) || *term == word
+        })
+    };
+
+    let first = words.iter().position(|w| matches(w)).unwrap_or(0);
+    let start = first.saturating_sub(SNIPPET_LEAD);
+    let end = (start + SNIPPET_WORDS).min(words.len());
+
+    let mut snippet: Vec<String> = words[start..end]
+        .iter()
+        .map(|w| if matches(w) { format!("**{}**", w) } else { w.to_string() })
+        .collect();
+    if start > 0 {
+        snippet.insert(0, "...".to_string());
+    }
+    if end < words.len() {
+        snippet.push("...".to_string());
+    }
+    snippet.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCS_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {},
+        "calls": [],
+        "docs": {
+            "MyApp.Accounts": {
+                "moduledoc": "Manages user accounts and their credentials.",
+                "functions": [
+                    {"name": "get_user", "arity": 1, "doc": "Fetches a single user by id. Raises when the user does not exist."},
+                    {"name": "list_users", "arity": 0, "doc": "Lists all users."}
+                ]
+            },
+            "MyApp.Billing": {
+                "moduledoc": "Invoices and payment processing.",
+                "functions": []
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_search_docs() {
+        let db = crate::test_utils::setup_test_db(DOCS_JSON, "default");
+        let results = search_docs(&db, "users", "default", 10).expect("Search should succeed");
+
+        // Stemming lets "users" match "user" as well
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| r.module == "MyApp.Accounts"));
+        assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(results.iter().any(|r| r.kind == "moduledoc" && r.name.is_none()));
+
+        let get_user = results.iter().find(|r| r.name.as_deref() == Some("get_user")).unwrap();
+        assert_eq!(get_user.arity, Some(1));
+        assert!(get_user.snippet.contains("**user**"));
+    }
+
+    #[test]
+    fn test_search_docs_other_project() {
+        let db = crate::test_utils::setup_test_db(DOCS_JSON, "default");
+        assert!(search_docs(&db, "invoices", "other", 10).unwrap().is_empty());
+        assert_eq!(search_docs(&db, "invoices", "default", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_highlight_snippet() {
+        assert_eq!(
+            highlight_snippet("Fetches a single user by id.", "users"),
+            "Fetches a single **user** by id."
+        );
+
+        let long = "one two three four five six seven eight nine ten eleven twelve thirteen fourteen fifteen sixteen seventeen eighteen nineteen twenty payment twenty-two";
+        let snippet = highlight_snippet(long, "payment");
+        assert!(snippet.starts_with("... sixteen"));
+        assert!(snippet.contains("**payment**"));
+
+        assert_eq!(highlight_snippet("No match here.", "invoice"), "No match here.");
+    }
+}
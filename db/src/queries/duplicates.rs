@@ -5,7 +5,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
 
 #[derive(Error, Debug)]
 pub enum DuplicatesError {
@@ -31,6 +31,7 @@ pub fn find_duplicates(
     use_regex: bool,
     use_exact: bool,
     exclude_generated: bool,
+    settings: &QuerySettings,
 ) -> Result<Vec<DuplicateFunction>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[module_pattern])?;
 
@@ -42,10 +43,11 @@ pub fn find_duplicates(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     // Build optional generated filter
     let generated_filter = if exclude_generated {
-        ", generated_by == \"\"".to_string()
+        settings.generated_condition("name", "file", "generated_by")
     } else {
         String::new()
     };
@@ -55,7 +57,7 @@ pub fn find_duplicates(
         r#"
         # Find hashes that appear more than once (count unique functions per hash)
         hash_counts[{hash_field}, count(module)] :=
-            *function_locations{{project, module, name, arity, {hash_field}, generated_by}},
+            *function_locations{{project, module, name, arity, file, {hash_field}, generated_by}},
             project == $project,
             {hash_field} != ""
             {generated_filter}
@@ -67,6 +69,7 @@ pub fn find_duplicates(
             cnt > 1,
             project == $project
             {module_cond}
+            {namespace_cond}
             {generated_filter}
 
         :order {hash_field}, module, name, arity
@@ -79,6 +82,8 @@ pub fn find_duplicates(
         params.insert("module_pattern", DataValue::Str(pattern.into()));
     }
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| DuplicatesError::QueryFailed {
         message: e.to_string(),
     })?;
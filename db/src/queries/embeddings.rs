@@ -0,0 +1,399 @@
+//! Vector embeddings of functions for semantic search.
+//!
+//! Embeddings live in their own `embeddings` relation, created by
+//! `index-embeddings` rather than `setup`: the vector column has a fixed
+//! dimension that depends on the embedding model. The `embeddings:semantic`
+//! HNSW index answers nearest-neighbour queries by cosine distance.
+
+use std::error::Error;
+
+use cozo::{DataValue, DbInstance, Num};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{
+    extract_f64, extract_i64, extract_string, run_query, run_query_no_params, try_create_relation,
+    Params,
+};
+
+#[derive(Error, Debug)]
+pub enum EmbeddingsError {
+    #[error("Embeddings query failed: {message}")]
+    QueryFailed { message: String },
+
+    #[error("No embeddings for project '{project}'; run `code_search index-embeddings` first")]
+    NotIndexed { project: String },
+
+    #[error("Embeddings were built with model '{indexed}' but '{configured}' is configured; re-run `code_search index-embeddings`")]
+    ModelMismatch { indexed: String, configured: String },
+}
+
+/// Rows stored per `:put`
+const STORE_CHUNK_SIZE: usize = 200;
+
+/// A function together with the text its embedding is computed from
+#[derive(Debug, Clone)]
+pub struct EmbeddingInput {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    /// `@doc` text, empty when undocumented
+    pub doc: String,
+    /// `@spec` signature, empty when there is none
+    pub spec: String,
+}
+
+/// A function close to the query in embedding space
+#[derive(Debug, Clone, Serialize)]
+pub struct SemanticMatch {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    /// Cosine similarity to the query (1.0 is identical)
+    pub score: f64,
+}
+
+/// Defined functions of a project with their doc and spec text, in module order
+pub fn find_embedding_inputs(db: &DbInstance, project: &str) -> Result<Vec<EmbeddingInput>, Box<dyn Error>> {
+    let script = r#"
+        defined[module, name, arity] := *function_locations{project: $project, module, name, arity}
+
+        doc_text[module, name, arity, doc] := defined[module, name, arity],
+            *docs{project: $project, module, name, arity, doc}
+        doc_text[module, name, arity, doc] := defined[module, name, arity],
+            not *docs{project: $project, module, name, arity}, doc = ""
+
+        spec_text[module, name, arity, spec] := defined[module, name, arity],
+            *specs{project: $project, module, name, arity, full: spec}
+        spec_text[module, name, arity, spec] := defined[module, name, arity],
+            not *specs{project: $project, module, name, arity}, spec = ""
+
+        ?[module, name, arity, doc, spec] := doc_text[module, name, arity, doc],
+            spec_text[module, name, arity, spec]
+
+        :order module, name, arity
+    "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| EmbeddingsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut inputs = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 5 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(name) = extract_string(&row[1]) else { continue };
+            inputs.push(EmbeddingInput {
+                module,
+                name,
+                arity: extract_i64(&row[2], 0),
+                doc: extract_string(&row[3]).unwrap_or_default(),
+                spec: extract_string(&row[4]).unwrap_or_default(),
+            });
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// Dimension of the stored vectors, `None` when the relation does not exist
+fn stored_dimensions(db: &DbInstance) -> Result<Option<usize>, Box<dyn Error>> {
+    let relations = run_query_no_params(db, "::relations")?;
+    let exists = relations
+        .rows
+        .iter()
+        .any(|row| row.first().and_then(extract_string).as_deref() == Some("embeddings"));
+    if !exists {
+        return Ok(None);
+    }
+
+    // Column types read like `<F32;256>`
+    let columns = run_query_no_params(db, "::columns embeddings")?;
+    let type_idx = columns.headers.iter().position(|h| h == "type").unwrap_or(4);
+    let dimensions = columns.rows.iter().find_map(|row| {
+        let column_type = extract_string(row.get(type_idx)?)?;
+        let len = column_type.strip_prefix("<F32;")?.strip_suffix('>')?;
+        len.trim().parse().ok()
+    });
+    Ok(dimensions)
+}
+
+/// Create the `embeddings` relation and its HNSW index for `dimensions`-sized vectors.
+///
+/// An existing relation with a different dimension was built by another model;
+/// it is dropped (for every project) and recreated.
+pub fn ensure_embeddings_relation(db: &DbInstance, dimensions: usize) -> Result<(), Box<dyn Error>> {
+    match stored_dimensions(db)? {
+        Some(existing) if existing == dimensions => return Ok(()),
+        Some(_) => {
+            run_query_no_params(db, "::hnsw drop embeddings:semantic").ok();
+            run_query_no_params(db, "::remove embeddings")?;
+        }
+        None => {}
+    }
+
+    try_create_relation(
+        db,
+        &format!(
+            r#"
+            :create embeddings {{
+                project: String,
+                module: String,
+                name: String,
+                arity: Int
+                =>
+                model: String,
+                vec: <F32; {dimensions}>
+            }}
+            "#
+        ),
+    )?;
+    try_create_relation(
+        db,
+        &format!(
+            r#"
+            ::hnsw create embeddings:semantic {{
+                dim: {dimensions},
+                m: 32,
+                dtype: F32,
+                fields: [vec],
+                distance: Cosine,
+                ef_construction: 50
+            }}
+            "#
+        ),
+    )?;
+    Ok(())
+}
+
+/// Replace the embeddings of `project` with `vectors`, one per input.
+///
+/// The relation must already exist with the vectors' dimension (see
+/// [`ensure_embeddings_relation`]).
+pub fn store_embeddings(
+    db: &DbInstance,
+    project: &str,
+    model: &str,
+    inputs: &[EmbeddingInput],
+    vectors: &[Vec<f32>],
+) -> Result<usize, Box<dyn Error>> {
+    clear_embeddings(db, project)?;
+
+    let rows: Vec<DataValue> = inputs
+        .iter()
+        .zip(vectors)
+        .map(|(input, vector)| {
+            DataValue::List(vec![
+                DataValue::Str(project.into()),
+                DataValue::Str(input.module.as_str().into()),
+                DataValue::Str(input.name.as_str().into()),
+                DataValue::from(input.arity),
+                DataValue::Str(model.into()),
+                DataValue::List(vector.iter().map(|x| DataValue::Num(Num::Float(*x as f64))).collect()),
+            ])
+        })
+        .collect();
+
+    for chunk in rows.chunks(STORE_CHUNK_SIZE) {
+        let mut params = Params::new();
+        params.insert("rows", DataValue::List(chunk.to_vec()));
+        run_query(
+            db,
+            r#"
+            ?[project, module, name, arity, model, vec] <- $rows
+            :put embeddings { project, module, name, arity => model, vec }
+            "#,
+            params,
+        )
+        .map_err(|e| EmbeddingsError::QueryFailed {
+            message: e.to_string(),
+        })?;
+    }
+
+    Ok(rows.len())
+}
+
+/// Remove the embeddings of `project`, if the relation exists
+pub fn clear_embeddings(db: &DbInstance, project: &str) -> Result<(), Box<dyn Error>> {
+    if stored_dimensions(db)?.is_none() {
+        return Ok(());
+    }
+    let script = r#"
+        ?[project, module, name, arity] := *embeddings{project, module, name, arity}, project = $project
+        :rm embeddings { project, module, name, arity }
+        "#;
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    run_query(db, script, params)?;
+    Ok(())
+}
+
+/// Model the embeddings of `project` were built with, `None` when not indexed
+pub fn embedding_model(db: &DbInstance, project: &str) -> Result<Option<String>, Box<dyn Error>> {
+    if stored_dimensions(db)?.is_none() {
+        return Ok(None);
+    }
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    let rows = run_query(
+        db,
+        r#"
+        ?[model] := *embeddings{project: $project, model}
+        :limit 1
+        "#,
+        params,
+    )?;
+    Ok(rows.rows.first().and_then(|row| row.first()).and_then(extract_string))
+}
+
+/// Functions of `project` nearest to `query`, most similar first.
+///
+/// `model` must be the model the query vector was computed with; it is checked
+/// against the model the stored embeddings were built with.
+pub fn semantic_search(
+    db: &DbInstance,
+    project: &str,
+    model: &str,
+    query: &[f32],
+    limit: u32,
+) -> Result<Vec<SemanticMatch>, Box<dyn Error>> {
+    match embedding_model(db, project)? {
+        None => {
+            return Err(EmbeddingsError::NotIndexed {
+                project: project.to_string(),
+            }
+            .into())
+        }
+        Some(indexed) if indexed != model => {
+            return Err(EmbeddingsError::ModelMismatch {
+                indexed,
+                configured: model.to_string(),
+            }
+            .into())
+        }
+        Some(_) => {}
+    }
+
+    // The candidate pool must be at least k; a wider pool improves recall
+    let ef = (limit * 2).max(50);
+    let script = format!(
+        r#"
+        ?[module, name, arity, distance] :=
+            ~embeddings:semantic{{project, module, name, arity |
+                query: q,
+                k: {limit},
+                ef: {ef},
+                bind_distance: distance,
+                filter: project == $project
+            }},
+            q = vec($query)
+
+        :order distance, module, name, arity
+        :limit {limit}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert(
+        "query",
+        DataValue::List(query.iter().map(|x| DataValue::Num(Num::Float(*x as f64))).collect()),
+    );
+
+    let rows = run_query(db, &script, params).map_err(|e| EmbeddingsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 4 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(name) = extract_string(&row[1]) else { continue };
+            let similarity = 1.0 - extract_f64(&row[3], 1.0);
+            results.push(SemanticMatch {
+                module,
+                name,
+                arity: extract_i64(&row[2], 0),
+                score: (similarity * 1000.0).round() / 1000.0,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(module: &str, name: &str, arity: i64) -> EmbeddingInput {
+        EmbeddingInput {
+            module: module.to_string(),
+            name: name.to_string(),
+            arity,
+            doc: String::new(),
+            spec: String::new(),
+        }
+    }
+
+    fn indexed_db() -> DbInstance {
+        let db = crate::test_utils::call_graph_db("default");
+        ensure_embeddings_relation(&db, 3).expect("Relation should be created");
+        let inputs = [
+            input("MyApp.Accounts", "get_user", 1),
+            input("MyApp.Accounts", "list_users", 0),
+            input("MyApp.Repo", "insert", 1),
+        ];
+        let vectors = [vec![1.0, 0.0, 0.0], vec![0.9, 0.1, 0.0], vec![0.0, 0.0, 1.0]];
+        store_embeddings(&db, "default", "test-3", &inputs, &vectors).expect("Store should succeed");
+        db
+    }
+
+    #[test]
+    fn test_find_embedding_inputs() {
+        let db = crate::test_utils::call_graph_db("default");
+        let inputs = find_embedding_inputs(&db, "default").expect("Query should succeed");
+        assert!(!inputs.is_empty());
+        assert!(inputs.windows(2).all(|w| w[0].module <= w[1].module));
+        assert!(find_embedding_inputs(&db, "other").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_semantic_search_orders_by_similarity() {
+        let db = indexed_db();
+        let results = semantic_search(&db, "default", "test-3", &[1.0, 0.05, 0.0], 2).expect("Search should succeed");
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "get_user");
+        assert_eq!(results[1].name, "list_users");
+        assert!(results[0].score >= results[1].score);
+    }
+
+    #[test]
+    fn test_semantic_search_not_indexed() {
+        let db = crate::test_utils::call_graph_db("default");
+        let err = semantic_search(&db, "default", "test-3", &[1.0, 0.0, 0.0], 5).unwrap_err();
+        assert!(err.to_string().contains("index-embeddings"));
+    }
+
+    #[test]
+    fn test_semantic_search_model_mismatch() {
+        let db = indexed_db();
+        let err = semantic_search(&db, "default", "other-model", &[1.0, 0.0, 0.0], 5).unwrap_err();
+        assert!(err.to_string().contains("test-3"));
+    }
+
+    #[test]
+    fn test_ensure_relation_recreates_on_dimension_change() {
+        let db = indexed_db();
+        ensure_embeddings_relation(&db, 4).expect("Relation should be recreated");
+        assert_eq!(embedding_model(&db, "default").unwrap(), None);
+
+        store_embeddings(&db, "default", "test-4", &[input("MyApp.Repo", "get", 2)], &[vec![0.0, 1.0, 0.0, 0.0]])
+            .expect("Store should succeed");
+        assert_eq!(embedding_model(&db, "default").unwrap().as_deref(), Some("test-4"));
+    }
+}
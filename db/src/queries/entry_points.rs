@@ -0,0 +1,150 @@
+//! Public functions with their caller counts, the candidates for entry points.
+//!
+//! Which functions are entry points is decided by the caller from module and
+//! function name patterns; this query supplies every public function with the
+//! number of distinct functions calling it inside the project.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum EntryPointsError {
+    #[error("Entry points query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A public function and how many functions in the project call it
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicFunction {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub file: String,
+    pub line: i64,
+    /// Distinct calling functions (0 = nothing in the project calls it)
+    pub callers: i64,
+}
+
+/// Find all public (`def`) functions, ordered by module, name and arity
+pub fn find_public_functions(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<PublicFunction>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+
+    let script = format!(
+        r#"
+        defs[module, name, arity, file, min(start_line)] :=
+            *function_locations{{project, module, name, arity, kind, file, start_line}},
+            project == $project,
+            kind == "def"
+            {module_cond}
+            {namespace_cond}
+
+        caller_of[module, name, arity, caller_module, caller_function] :=
+            *calls{{project, caller_module, caller_function, callee_module: module, callee_function: name, callee_arity: arity}},
+            project == $project
+
+        caller_counts[module, name, arity, count(caller_function)] :=
+            caller_of[module, name, arity, caller_module, caller_function]
+
+        ?[module, name, arity, file, line, callers] :=
+            defs[module, name, arity, file, line],
+            caller_counts[module, name, arity, callers]
+        ?[module, name, arity, file, line, callers] :=
+            defs[module, name, arity, file, line],
+            not caller_counts[module, name, arity, _],
+            callers = 0
+
+        :order module, name, arity
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| EntryPointsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 6 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(name) = extract_string(&row[1]) else { continue };
+            let arity = extract_i64(&row[2], 0);
+            let Some(file) = extract_string(&row[3]) else { continue };
+            let line = extract_i64(&row[4], 0);
+            let callers = extract_i64(&row[5], 0);
+
+            results.push(PublicFunction {
+                module,
+                name,
+                arity,
+                file,
+                line,
+                callers,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_public_functions() {
+        let db = crate::test_utils::call_graph_db("default");
+        let functions = find_public_functions(&db, "default", None, false, &QuerySettings::default()).expect("Query should succeed");
+
+        // 15 definitions, 3 of them private
+        assert_eq!(functions.len(), 12);
+
+        let get = functions
+            .iter()
+            .find(|f| f.module == "MyApp.Repo" && f.name == "get")
+            .expect("MyApp.Repo.get/2");
+        // Called twice from get_user and once from do_fetch
+        assert_eq!(get.callers, 2);
+        assert_eq!(get.file, "lib/my_app/repo.ex");
+
+        let index = functions
+            .iter()
+            .find(|f| f.module == "MyApp.Controller" && f.name == "index")
+            .expect("MyApp.Controller.index/2");
+        assert_eq!(index.callers, 0);
+        assert_eq!(index.line, 5);
+    }
+
+    #[test]
+    fn test_find_public_functions_module_filter() {
+        let db = crate::test_utils::call_graph_db("default");
+        let functions =
+            find_public_functions(&db, "default", Some("MyApp.Controller"), false, &QuerySettings::default()).expect("Query should succeed");
+        assert_eq!(functions.len(), 3);
+        assert!(functions.iter().all(|f| f.module == "MyApp.Controller"));
+    }
+}
@@ -0,0 +1,211 @@
+//! Function definitions, call sites and function metrics for exports
+//! (ctags, cscope and the graph visualization formats).
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, extract_string_or, run_query, Params};
+
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("Export query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A function definition spanning all of its clauses in one file
+#[derive(Debug, Clone, Serialize)]
+pub struct Definition {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub kind: String,
+    pub file: String,
+    pub start_line: i64,
+    pub end_line: i64,
+}
+
+/// A call site
+#[derive(Debug, Clone, Serialize)]
+pub struct CallSite {
+    pub file: String,
+    pub line: i64,
+    pub caller_module: String,
+    pub caller_function: String,
+    pub callee_module: String,
+    pub callee_function: String,
+}
+
+/// Function definitions with a file, ordered by file and start line
+pub fn find_definitions(db: &cozo::DbInstance, project: &str) -> Result<Vec<Definition>, Box<dyn Error>> {
+    let script = r#"
+        span[file, module, name, arity, kind, min(start_line), max(end_line)] :=
+            *function_locations{project, module, name, arity, kind, file, start_line, end_line},
+            project == $project,
+            file != ""
+
+        ?[file, start_line, end_line, module, name, arity, kind] :=
+            span[file, module, name, arity, kind, start_line, end_line]
+
+        :order file, start_line, module, name, arity
+    "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| ExportError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 7 {
+            let Some(file) = extract_string(&row[0]) else { continue };
+            let Some(module) = extract_string(&row[3]) else { continue };
+            let Some(name) = extract_string(&row[4]) else { continue };
+            results.push(Definition {
+                module,
+                name,
+                arity: extract_i64(&row[5], 0),
+                kind: extract_string_or(&row[6], ""),
+                file,
+                start_line: extract_i64(&row[1], 0),
+                end_line: extract_i64(&row[2], 0),
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Cyclomatic complexity of a function, summed over its clauses and arities
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionComplexity {
+    pub module: String,
+    pub name: String,
+    pub complexity: i64,
+}
+
+/// Complexity of every located function, ordered by module and name
+pub fn find_function_complexity(db: &cozo::DbInstance, project: &str) -> Result<Vec<FunctionComplexity>, Box<dyn Error>> {
+    let script = r#"
+        ?[module, name, sum(complexity)] :=
+            *function_locations{project, module, name, complexity},
+            project == $project
+
+        :order module, name
+    "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| ExportError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 3 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(name) = extract_string(&row[1]) else { continue };
+            results.push(FunctionComplexity {
+                module,
+                name,
+                complexity: extract_i64(&row[2], 0),
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Call sites with a file, ordered by file and line
+pub fn find_call_sites(db: &cozo::DbInstance, project: &str) -> Result<Vec<CallSite>, Box<dyn Error>> {
+    let script = r#"
+        ?[file, line, caller_module, caller_function, callee_module, callee_function] :=
+            *calls{project, caller_module, caller_function, callee_module, callee_function, file, line},
+            project == $project,
+            file != ""
+
+        :order file, line, callee_module, callee_function
+    "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| ExportError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 6 {
+            let Some(file) = extract_string(&row[0]) else { continue };
+            let Some(callee_module) = extract_string(&row[4]) else { continue };
+            let Some(callee_function) = extract_string(&row[5]) else { continue };
+            results.push(CallSite {
+                file,
+                line: extract_i64(&row[1], 0),
+                caller_module: extract_string_or(&row[2], ""),
+                caller_function: extract_string_or(&row[3], ""),
+                callee_module,
+                callee_function,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Accounts": {
+                "get_user/1:2": {"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 4},
+                "get_user/1:5": {"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 5, "start_line": 5, "end_line": 7},
+                "load/1:9": {"name": "load", "arity": 1, "file": "lib/accounts.ex", "kind": "defp", "line": 9, "start_line": 9, "end_line": 11}
+            }
+        },
+        "calls": [
+            {"caller": {"module": "MyApp.Accounts", "function": "get_user/1", "file": "lib/accounts.ex", "line": 6, "column": 5}, "type": "local", "callee": {"module": "MyApp.Accounts", "function": "load", "arity": 1}},
+            {"caller": {"module": "MyApp.Accounts", "function": "get_user/1", "file": "lib/accounts.ex", "line": 3, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Repo", "function": "get", "arity": 2}}
+        ]
+    }"#;
+
+    #[test]
+    fn test_find_definitions_spans_clauses() {
+        let db = crate::test_utils::setup_test_db(EXPORT_JSON, "default");
+        let definitions = find_definitions(&db, "default").expect("Query should succeed");
+
+        let spans: Vec<(&str, i64, i64)> =
+            definitions.iter().map(|d| (d.name.as_str(), d.start_line, d.end_line)).collect();
+        assert_eq!(spans, [("get_user", 2, 7), ("load", 9, 11)]);
+        assert_eq!(definitions[1].kind, "defp");
+    }
+
+    #[test]
+    fn test_find_call_sites() {
+        let db = crate::test_utils::setup_test_db(EXPORT_JSON, "default");
+        let calls = find_call_sites(&db, "default").expect("Query should succeed");
+
+        let sites: Vec<(i64, &str)> = calls.iter().map(|c| (c.line, c.callee_function.as_str())).collect();
+        assert_eq!(sites, [(3, "get"), (6, "load")]);
+        assert_eq!(calls[0].caller_function, "get_user/1");
+    }
+
+    #[test]
+    fn test_find_function_complexity_sums_clauses() {
+        let db = crate::test_utils::setup_test_db(EXPORT_JSON, "default");
+        let metrics = find_function_complexity(&db, "default").expect("Query should succeed");
+
+        let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["get_user", "load"]);
+        assert_eq!(metrics[0].complexity, 2 * metrics[1].complexity);
+    }
+}
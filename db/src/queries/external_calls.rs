@@ -0,0 +1,229 @@
+//! Calls leaving the project for modules it does not define.
+//!
+//! A callee is external when it is not in the `modules` relation, which holds
+//! every module with function locations, specs, types or structs. That covers
+//! dependencies (Ecto, Phoenix, ...) as well as the Elixir and Erlang standard
+//! libraries; telling them apart is left to the caller. Modules imported from
+//! vendored sources (`is_external`) count as external too, and their own calls
+//! are not part of the project's surface.
+
+use std::collections::HashSet;
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum ExternalCallsError {
+    #[error("External calls query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A call site whose callee module is outside the project
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalCall {
+    pub caller_module: String,
+    pub caller_function: String,
+    pub callee_module: String,
+    pub callee_function: String,
+    pub callee_arity: i64,
+    pub file: String,
+    pub line: i64,
+}
+
+/// Find calls into modules the project does not define.
+///
+/// `module_pattern` narrows the calling modules. Results are ordered by callee,
+/// then by call site.
+pub fn find_external_calls(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<ExternalCall>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("caller_module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("caller_module");
+
+    let order =
+        settings.order_by("callee_module, callee_function, callee_arity, caller_module, caller_function, file, line");
+    let script = format!(
+        r#"
+        project_module[module] :=
+            *modules{{project, name: module, is_external}},
+            project == $project,
+            is_external == false
+        vendored_module[module] :=
+            *modules{{project, name: module, is_external}},
+            project == $project,
+            is_external == true
+
+        ?[callee_module, callee_function, callee_arity, caller_module, caller_function, file, line] :=
+            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line}},
+            project == $project,
+            callee_module != "",
+            not project_module[callee_module],
+            not vendored_module[caller_module]
+            {module_cond}
+            {namespace_cond}
+
+        {order}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| ExternalCallsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 7 {
+            let Some(callee_module) = extract_string(&row[0]) else { continue };
+            let Some(callee_function) = extract_string(&row[1]) else { continue };
+            let callee_arity = extract_i64(&row[2], 0);
+            let Some(caller_module) = extract_string(&row[3]) else { continue };
+            let Some(caller_function) = extract_string(&row[4]) else { continue };
+            let Some(file) = extract_string(&row[5]) else { continue };
+            let line = extract_i64(&row[6], 0);
+
+            results.push(ExternalCall {
+                caller_module,
+                caller_function,
+                callee_module,
+                callee_function,
+                callee_arity,
+                file,
+                line,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Names of the project's own modules, leaving out vendored ones
+pub fn find_project_modules(db: &cozo::DbInstance, project: &str) -> Result<HashSet<String>, Box<dyn Error>> {
+    let script = r#"
+        ?[name] := *modules{project, name, is_external}, project == $project, is_external == false
+        "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| ExternalCallsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    Ok(rows.rows.iter().filter_map(|row| row.first().and_then(extract_string)).collect())
+}
+
+/// Names of the project's test modules
+pub fn find_test_modules(db: &cozo::DbInstance, project: &str) -> Result<HashSet<String>, Box<dyn Error>> {
+    let script = r#"
+        ?[name] := *modules{project, name, is_test}, project == $project, is_test == true
+        "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| ExternalCallsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    Ok(rows.rows.iter().filter_map(|row| row.first().and_then(extract_string)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXTERNAL_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Accounts": {
+                "create/1:3": {"name": "create", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 8}
+            },
+            "MyApp.Repo": {
+                "get/2:2": {"name": "get", "arity": 2, "file": "lib/repo.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}
+            }
+        },
+        "calls": [
+            {"caller": {"module": "MyApp.Accounts", "function": "create", "file": "lib/accounts.ex", "line": 4, "column": 5}, "type": "remote", "callee": {"module": "Ecto.Changeset", "function": "cast", "arity": 3}},
+            {"caller": {"module": "MyApp.Accounts", "function": "create", "file": "lib/accounts.ex", "line": 5, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Repo", "function": "get", "arity": 2}},
+            {"caller": {"module": "MyApp.Repo", "function": "get", "file": "lib/repo.ex", "line": 2, "column": 5}, "type": "remote", "callee": {"module": "Enum", "function": "map", "arity": 2}}
+        ]
+    }"#;
+
+    #[test]
+    fn test_find_external_calls() {
+        let db = crate::test_utils::setup_test_db(EXTERNAL_JSON, "default");
+        let calls = find_external_calls(&db, "default", None, false, &QuerySettings::default()).expect("Query should succeed");
+
+        let callees: Vec<&str> = calls.iter().map(|c| c.callee_module.as_str()).collect();
+        assert_eq!(callees, ["Ecto.Changeset", "Enum"]);
+        assert_eq!(calls[0].caller_module, "MyApp.Accounts");
+        assert_eq!((calls[0].callee_function.as_str(), calls[0].callee_arity), ("cast", 3));
+        assert_eq!(calls[0].line, 4);
+    }
+
+    #[test]
+    fn test_find_external_calls_by_caller() {
+        let db = crate::test_utils::setup_test_db(EXTERNAL_JSON, "default");
+        let calls = find_external_calls(&db, "default", Some("MyApp.Repo"), false, &QuerySettings::default()).expect("Query should succeed");
+
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].callee_module, "Enum");
+    }
+
+    #[test]
+    fn test_find_external_calls_none_in_fixture() {
+        let db = crate::test_utils::call_graph_db("default");
+        assert!(find_external_calls(&db, "default", None, false, &QuerySettings::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_external_calls_vendored_modules() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Api": {
+                    "encode/1:3": {"name": "encode", "arity": 1, "file": "lib/api.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 5}
+                },
+                "Jason": {
+                    "encode/2:10": {"name": "encode", "arity": 2, "file": "deps/jason/lib/jason.ex", "kind": "def", "line": 10, "start_line": 10, "end_line": 14}
+                }
+            },
+            "calls": [
+                {"caller": {"module": "MyApp.Api", "function": "encode", "file": "lib/api.ex", "line": 4, "column": 5}, "type": "remote", "callee": {"module": "Jason", "function": "encode", "arity": 2}},
+                {"caller": {"module": "Jason", "function": "encode", "file": "deps/jason/lib/jason.ex", "line": 12, "column": 5}, "type": "remote", "callee": {"module": "Map", "function": "new", "arity": 0}}
+            ]
+        }"#;
+        let db = crate::test_utils::setup_test_db(json, "default");
+        let calls = find_external_calls(&db, "default", None, false, &QuerySettings::default()).expect("Query should succeed");
+
+        // Jason is vendored: calls into it are external, its own calls are not reported
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].callee_module, "Jason");
+        assert_eq!(calls[0].caller_module, "MyApp.Api");
+
+        let modules = find_project_modules(&db, "default").expect("Query should succeed");
+        assert_eq!(modules, HashSet::from(["MyApp.Api".to_string()]));
+    }
+}
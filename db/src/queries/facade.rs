@@ -0,0 +1,199 @@
+//! How the public functions of a namespace are used from outside it.
+//!
+//! A namespace is a module and its nested modules (`MyApp.Accounts` covers
+//! `MyApp.Accounts.User`), or the modules matching a regex. For each public
+//! function (`def`/`defmacro`) the call sites are split into external ones,
+//! made from modules outside the namespace, and internal ones.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, extract_string_or, run_query, Params};
+use crate::query_builders::validate_regex_patterns;
+
+#[derive(Error, Debug)]
+pub enum FacadeError {
+    #[error("Facade query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A public function of the namespace with its callers
+#[derive(Debug, Clone, Serialize)]
+pub struct FacadeFunction {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub file: String,
+    pub line: i64,
+    /// Call sites in modules outside the namespace
+    pub external_calls: usize,
+    /// Modules outside the namespace calling the function, sorted
+    pub external_callers: Vec<String>,
+    /// Call sites in modules of the namespace, the function's own module included
+    pub internal_calls: usize,
+    /// True when every call site is in the function's own module
+    pub own_module_only: bool,
+}
+
+/// Public functions of the namespace, ordered by module, name and arity
+pub fn find_facade_usage(
+    db: &cozo::DbInstance,
+    project: &str,
+    namespace: &str,
+    use_regex: bool,
+) -> Result<Vec<FacadeFunction>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[Some(namespace)])?;
+
+    let namespace_cond = if use_regex {
+        "regex_matches(module, $namespace)"
+    } else {
+        "(module == $namespace or starts_with(module, $prefix))"
+    };
+
+    let script = format!(
+        r#"
+        public_clause[module, name, arity, file, line] :=
+            *function_locations{{project, module, name, arity, kind, file, line}},
+            project == $project,
+            (kind == "def" or kind == "defmacro"),
+            {namespace_cond}
+        public_fn[module, name, arity, file, min(line)] := public_clause[module, name, arity, file, line]
+
+        ?[module, name, arity, file, line, caller_module, call_file, call_line, call_column] :=
+            public_fn[module, name, arity, file, line],
+            *calls{{project, caller_module, callee_module: module, callee_function: name, callee_arity: arity,
+                   file: call_file, line: call_line, column: call_column}},
+            project == $project
+        ?[module, name, arity, file, line, caller_module, call_file, call_line, call_column] :=
+            public_fn[module, name, arity, file, line],
+            caller_module = "",
+            call_file = "",
+            call_line = -1,
+            call_column = -1
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("namespace", DataValue::Str(namespace.into()));
+    if !use_regex {
+        params.insert("prefix", DataValue::Str(format!("{}.", namespace).into()));
+    }
+
+    let rows = run_query(db, &script, params).map_err(|e| FacadeError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let regex = if use_regex { Some(regex::Regex::new(namespace)?) } else { None };
+    let prefix = format!("{}.", namespace);
+    let in_namespace = |module: &str| match &regex {
+        Some(regex) => regex.is_match(module),
+        None => module == namespace || module.starts_with(&prefix),
+    };
+
+    let mut functions: BTreeMap<(String, String, i64), FacadeFunction> = BTreeMap::new();
+    let mut external_callers: BTreeMap<(String, String, i64), BTreeSet<String>> = BTreeMap::new();
+    for row in rows.rows {
+        if row.len() < 9 {
+            continue;
+        }
+        let Some(module) = extract_string(&row[0]) else { continue };
+        let Some(name) = extract_string(&row[1]) else { continue };
+        let arity = extract_i64(&row[2], 0);
+        let key = (module.clone(), name.clone(), arity);
+        let function = functions.entry(key.clone()).or_insert_with(|| FacadeFunction {
+            module: module.clone(),
+            name,
+            arity,
+            file: extract_string_or(&row[3], ""),
+            line: extract_i64(&row[4], 0),
+            external_calls: 0,
+            external_callers: Vec::new(),
+            internal_calls: 0,
+            own_module_only: true,
+        });
+
+        // The placeholder row of every function carries no call
+        if extract_i64(&row[7], -1) < 0 {
+            continue;
+        }
+        let caller_module = extract_string_or(&row[5], "");
+        if in_namespace(&caller_module) {
+            function.internal_calls += 1;
+        } else {
+            function.external_calls += 1;
+            external_callers.entry(key).or_default().insert(caller_module.clone());
+        }
+        if caller_module != module {
+            function.own_module_only = false;
+        }
+    }
+
+    Ok(functions
+        .into_iter()
+        .map(|(key, mut function)| {
+            if let Some(callers) = external_callers.remove(&key) {
+                function.external_callers = callers.into_iter().collect();
+            }
+            function
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facade_db() -> cozo::DbInstance {
+        crate::fixtures::FixtureGraph::new()
+            .module("MyApp.Accounts")
+            .function("get_user", 1)
+            .function("normalize", 1)
+            .private_function("secret", 0)
+            .module("MyApp.Accounts.Repo")
+            .function("load", 1)
+            .function("unused", 0)
+            .module("MyApp.Web")
+            .function("show", 2)
+            .calls("MyApp.Web", "show", 2, "MyApp.Accounts", "get_user", 1)
+            .calls("MyApp.Web", "show", 2, "MyApp.Accounts", "get_user", 1)
+            .calls("MyApp.Accounts", "get_user", 1, "MyApp.Accounts", "normalize", 1)
+            .calls("MyApp.Accounts", "get_user", 1, "MyApp.Accounts.Repo", "load", 1)
+            .db("default")
+    }
+
+    #[test]
+    fn test_find_facade_usage() {
+        let db = facade_db();
+        let functions = find_facade_usage(&db, "default", "MyApp.Accounts", false).expect("Query should succeed");
+
+        let summary: Vec<(String, usize, usize, bool)> = functions
+            .iter()
+            .map(|f| (format!("{}.{}", f.module, f.name), f.external_calls, f.internal_calls, f.own_module_only))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("MyApp.Accounts.get_user".to_string(), 2, 0, false),
+                ("MyApp.Accounts.normalize".to_string(), 0, 1, true),
+                ("MyApp.Accounts.Repo.load".to_string(), 0, 1, false),
+                ("MyApp.Accounts.Repo.unused".to_string(), 0, 0, true),
+            ]
+        );
+        assert_eq!(functions[0].external_callers, ["MyApp.Web"]);
+    }
+
+    #[test]
+    fn test_find_facade_usage_regex() {
+        let db = facade_db();
+        let functions = find_facade_usage(&db, "default", r"^MyApp\.Accounts$", true).expect("Query should succeed");
+
+        assert_eq!(functions.len(), 2);
+        // Repo is outside the namespace now, so its call to load is not counted
+        assert_eq!(functions[0].external_calls, 2);
+    }
+}
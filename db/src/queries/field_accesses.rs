@@ -0,0 +1,174 @@
+//! Reads and writes of struct fields.
+//!
+//! Access sites come from the optional `field_accesses` list of the import
+//! format. Together with [`super::structs`] they show which functions depend
+//! on each field of a struct, including fields nothing touches.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum FieldAccessError {
+    #[error("Field access query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A single read or write of a struct field
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldAccess {
+    pub struct_module: String,
+    pub field: String,
+    pub caller_module: String,
+    pub caller_function: String,
+    /// "read" or "write"
+    pub access: String,
+    pub file: String,
+    pub line: i64,
+}
+
+/// Find accesses to fields of structs matching `module_pattern`.
+///
+/// `field_pattern` narrows to matching field names and `access` to "read" or
+/// "write" sites. Results are ordered by struct, field and call site.
+#[allow(clippy::too_many_arguments)]
+pub fn find_field_accesses(
+    db: &cozo::DbInstance,
+    module_pattern: &str,
+    field_pattern: Option<&str>,
+    access: Option<&str>,
+    project: &str,
+    use_regex: bool,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<FieldAccess>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[Some(module_pattern), field_pattern])?;
+
+    let module_cond = ConditionBuilder::new("struct_module", "module_pattern").build(use_regex);
+    let namespace_cond = settings.namespace_condition("struct_module");
+    let field_cond = OptionalConditionBuilder::new("field", "field_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(field_pattern.is_some(), use_regex);
+    let access_cond = OptionalConditionBuilder::new("access", "access")
+        .with_leading_comma()
+        .build(access.is_some());
+    let project_cond = ", project == $project";
+
+    let script = format!(
+        r#"
+        ?[struct_module, field, caller_module, caller_function, access, file, line] :=
+            *field_accesses{{project, struct_module, field, caller_module, caller_function, file, line, access}},
+            {module_cond}
+            {namespace_cond}
+            {field_cond}
+            {access_cond}
+            {project_cond}
+        :order struct_module, field, caller_module, caller_function, file, line
+        :limit {limit}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("module_pattern", DataValue::Str(module_pattern.into()));
+    if let Some(field) = field_pattern {
+        params.insert("field_pattern", DataValue::Str(field.into()));
+    }
+    if let Some(access) = access {
+        params.insert("access", DataValue::Str(access.into()));
+    }
+    params.insert("project", DataValue::Str(project.into()));
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| FieldAccessError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 7 {
+            let Some(struct_module) = extract_string(&row[0]) else { continue };
+            let Some(field) = extract_string(&row[1]) else { continue };
+            let Some(caller_module) = extract_string(&row[2]) else { continue };
+            let Some(caller_function) = extract_string(&row[3]) else { continue };
+            let Some(access) = extract_string(&row[4]) else { continue };
+            let Some(file) = extract_string(&row[5]) else { continue };
+            let line = extract_i64(&row[6], 0);
+
+            results.push(FieldAccess {
+                struct_module,
+                field,
+                caller_module,
+                caller_function,
+                access,
+                file,
+                line,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn populated_db() -> cozo::DbInstance {
+        crate::test_utils::structs_db("default")
+    }
+
+    #[rstest]
+    fn test_find_field_accesses_by_struct(populated_db: cozo::DbInstance) {
+        let accesses = find_field_accesses(&populated_db, "MyApp.User", None, None, "default", false, 100, &QuerySettings::default())
+            .expect("Query should succeed");
+
+        let sites: Vec<(&str, &str, &str)> = accesses
+            .iter()
+            .map(|a| (a.field.as_str(), a.caller_function.as_str(), a.access.as_str()))
+            .collect();
+        assert_eq!(
+            sites,
+            [
+                ("email", "get_user", "read"),
+                ("email", "update_email", "write"),
+                ("email", "deliver", "read"),
+                ("name", "get_user", "read"),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_find_field_accesses_by_field_and_access(populated_db: cozo::DbInstance) {
+        let accesses = find_field_accesses(&populated_db, "MyApp.User", Some("email"), Some("write"), "default", false, 100, &QuerySettings::default())
+            .expect("Query should succeed");
+
+        assert_eq!(accesses.len(), 1);
+        assert_eq!(accesses[0].caller_module, "MyApp.Accounts");
+        assert_eq!(accesses[0].file, "lib/my_app/accounts.ex");
+        assert_eq!(accesses[0].line, 40);
+    }
+
+    #[rstest]
+    fn test_find_field_accesses_regex(populated_db: cozo::DbInstance) {
+        let accesses = find_field_accesses(&populated_db, "MyApp\\.(User|Post)", Some("^(status|name)$"), None, "default", true, 100, &QuerySettings::default())
+            .expect("Query should succeed");
+
+        let fields: Vec<&str> = accesses.iter().map(|a| a.field.as_str()).collect();
+        assert_eq!(fields, ["status", "name"]);
+    }
+
+    #[rstest]
+    fn test_find_field_accesses_invalid_regex(populated_db: cozo::DbInstance) {
+        let result = find_field_accesses(&populated_db, "[invalid", None, None, "default", true, 100, &QuerySettings::default());
+        assert!(result.is_err());
+    }
+}
@@ -5,7 +5,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder};
+use crate::query_builders::{validate_regex_patterns, ConditionBuilder, QuerySettings};
 
 #[derive(Error, Debug)]
 pub enum FileError {
@@ -37,11 +37,13 @@ pub fn find_functions_in_module(
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<FileFunctionDef>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[Some(module_pattern)])?;
 
     // Build module filter using query builder
     let module_filter = ConditionBuilder::new("module", "module_pattern").build(use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     // Query to find all functions in matching modules
     let script = format!(
@@ -50,6 +52,7 @@ pub fn find_functions_in_module(
             *function_locations{{project, module, name, arity, line, file, kind, start_line, end_line, pattern, guard}},
             project == $project,
             {module_filter}
+            {namespace_cond}
 
         :order module, start_line, name, arity, line
         :limit {limit}
@@ -60,6 +63,8 @@ pub fn find_functions_in_module(
     params.insert("project", DataValue::Str(project.into()));
     params.insert("module_pattern", DataValue::Str(module_pattern.into()));
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| FileError::QueryFailed {
         message: e.to_string(),
     })?;
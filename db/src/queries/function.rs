@@ -5,7 +5,10 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, extract_string_or, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder};
+use crate::query_builders::{
+    target_condition, targets_param, validate_regex_patterns, ConditionBuilder, FunctionTarget,
+    OptionalConditionBuilder, QuerySettings, TARGET_RULE,
+};
 
 #[derive(Error, Debug)]
 pub enum FunctionError {
@@ -24,6 +27,7 @@ pub struct FunctionSignature {
     pub return_type: String,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn find_functions(
     db: &cozo::DbInstance,
     module_pattern: &str,
@@ -32,38 +36,67 @@ pub fn find_functions(
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<FunctionSignature>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[Some(module_pattern), Some(function_pattern)])?;
 
     // Build query conditions using helpers
     let module_cond = ConditionBuilder::new("module", "module_pattern").build(use_regex);
+    let namespace_cond = settings.namespace_condition("module");
     let function_cond = ConditionBuilder::new("name", "function_pattern")
         .with_leading_comma()
         .build(use_regex);
     let arity_cond = OptionalConditionBuilder::new("arity", "arity")
         .with_leading_comma()
         .build(arity.is_some());
-    let project_cond = ", project == $project";
 
+    let mut params = Params::new();
+    params.insert("module_pattern", DataValue::Str(module_pattern.into()));
+    params.insert("function_pattern", DataValue::Str(function_pattern.into()));
+    if let Some(a) = arity {
+        params.insert("arity", DataValue::from(a));
+    }
+
+    let filter = format!("{module_cond}\n{namespace_cond}\n{function_cond}\n{arity_cond}");
+    settings.bind_namespaces(&filter, &mut params);
+    query_functions(db, "", &filter, params, project, limit)
+}
+
+/// Find the signatures of each of a list of exactly named functions, in one query
+pub fn find_functions_for_targets(
+    db: &cozo::DbInstance,
+    targets: &[FunctionTarget],
+    project: &str,
+    limit: u32,
+) -> Result<Vec<FunctionSignature>, Box<dyn Error>> {
+    let mut params = Params::new();
+    params.insert("targets", targets_param(targets));
+
+    let filter = target_condition("module", "name", "arity");
+    query_functions(db, TARGET_RULE, &filter, params, project, limit)
+}
+
+/// Run the signatures query with `filter` restricting the matched functions
+fn query_functions(
+    db: &cozo::DbInstance,
+    rules: &str,
+    filter: &str,
+    mut params: Params,
+    project: &str,
+    limit: u32,
+) -> Result<Vec<FunctionSignature>, Box<dyn Error>> {
     let script = format!(
         r#"
+        {rules}
         ?[project, module, name, arity, args, return_type] :=
             *functions{{project, module, name, arity, args, return_type}},
-            {module_cond}
-            {function_cond}
-            {arity_cond}
-            {project_cond}
+            {filter},
+            project == $project
         :order module, name, arity
         :limit {limit}
         "#,
     );
 
-    let mut params = Params::new();
-    params.insert("module_pattern", DataValue::Str(module_pattern.into()));
-    params.insert("function_pattern", DataValue::Str(function_pattern.into()));
-    if let Some(a) = arity {
-        params.insert("arity", DataValue::from(a));
-    }
     params.insert("project", DataValue::Str(project.into()));
 
     let rows = run_query(db, &script, params).map_err(|e| FunctionError::QueryFailed {
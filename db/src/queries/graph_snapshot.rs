@@ -0,0 +1,135 @@
+//! Graph snapshots: the stored rows of one project as a single JSON document.
+//!
+//! `export snapshot` writes them, and an in-memory database loads them back,
+//! which is how the WebAssembly build gets its data in a browser. Only the
+//! relations keyed by project are included; the full-text index over docs is
+//! not rebuilt on load.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use cozo::{DataValue, DbInstance, NamedRows};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::db::{run_query, Params};
+use crate::queries::schema::{create_schema, relation_names, schema_columns, schema_for_relation};
+
+/// Version of the snapshot format written
+pub const SNAPSHOT_VERSION: i64 = 1;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("Invalid graph snapshot: {message}")]
+    Invalid { message: String },
+
+    #[error("Unsupported graph snapshot version {version} (expected {SNAPSHOT_VERSION})")]
+    UnsupportedVersion { version: i64 },
+
+    #[error("Loading {relation} from the snapshot failed: {message}")]
+    LoadFailed { relation: String, message: String },
+}
+
+/// Managed relations whose rows belong to a project, with their columns
+fn project_relations() -> impl Iterator<Item = (&'static str, Vec<&'static str>)> {
+    relation_names().into_iter().filter_map(|relation| {
+        let columns = schema_columns(schema_for_relation(relation)?);
+        (columns.first() == Some(&"project")).then_some((relation, columns))
+    })
+}
+
+/// Serialize the rows of a project as a snapshot document
+pub fn export_graph_snapshot(db: &DbInstance, project: &str) -> Result<String, Box<dyn Error>> {
+    let mut relations = serde_json::Map::new();
+    for (relation, columns) in project_relations() {
+        let script = format!(
+            "?[{columns}] := *{relation}{{{columns}}}, project = $project",
+            columns = columns.join(", ")
+        );
+        let mut params = Params::new();
+        params.insert("project", DataValue::Str(project.into()));
+        let rows = run_query(db, &script, params)?;
+        if !rows.rows.is_empty() {
+            relations.insert(relation.to_string(), rows.into_json());
+        }
+    }
+
+    let snapshot = json!({
+        "version": SNAPSHOT_VERSION,
+        "project": project,
+        "relations": relations,
+    });
+    Ok(snapshot.to_string())
+}
+
+/// Load a snapshot document into a database, creating the schema first.
+///
+/// Returns the project the snapshot was taken from.
+pub fn load_graph_snapshot(db: &DbInstance, snapshot: &str) -> Result<String, Box<dyn Error>> {
+    let invalid = |message: &str| SnapshotError::Invalid {
+        message: message.to_string(),
+    };
+    let snapshot: Value = serde_json::from_str(snapshot).map_err(|e| invalid(&e.to_string()))?;
+    let version = snapshot["version"].as_i64().ok_or_else(|| invalid("missing version"))?;
+    if version != SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion { version }.into());
+    }
+    let project = snapshot["project"].as_str().ok_or_else(|| invalid("missing project"))?;
+    let relations = snapshot["relations"]
+        .as_object()
+        .ok_or_else(|| invalid("missing relations"))?;
+
+    create_schema(db)?;
+    let known: Vec<&str> = project_relations().map(|(relation, _)| relation).collect();
+    let mut data = BTreeMap::new();
+    for (relation, rows) in relations {
+        if !known.contains(&relation.as_str()) {
+            return Err(invalid(&format!("unknown relation '{}'", relation)).into());
+        }
+        let rows = NamedRows::from_json(rows).map_err(|e| SnapshotError::LoadFailed {
+            relation: relation.clone(),
+            message: e.to_string(),
+        })?;
+        data.insert(relation.clone(), rows);
+    }
+    db.import_relations(data).map_err(|e| SnapshotError::LoadFailed {
+        relation: "relations".to_string(),
+        message: e.to_string(),
+    })?;
+
+    Ok(project.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{open_mem_db, run_query_no_params};
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let source = crate::test_utils::call_graph_db("app");
+        let snapshot = export_graph_snapshot(&source, "app").unwrap();
+
+        let db = open_mem_db();
+        assert_eq!(load_graph_snapshot(&db, &snapshot).unwrap(), "app");
+        let count = |db: &DbInstance| run_query_no_params(db, "?[count(c)] := *calls{caller_module: c}").unwrap().rows;
+        assert_eq!(count(&db), count(&source));
+        assert!(!count(&db).is_empty());
+
+        // The callee index is filled by the load as well
+        let rows = run_query_no_params(&db, "?[m] := *calls:by_callee{callee_module: 'MyApp.Repo', caller_module: m}").unwrap();
+        assert!(!rows.rows.is_empty());
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_snapshots() {
+        let db = open_mem_db();
+        let err = load_graph_snapshot(&db, r#"{"version": 2, "project": "app", "relations": {}}"#).unwrap_err();
+        assert_eq!(err.to_string(), "Unsupported graph snapshot version 2 (expected 1)");
+
+        let err = load_graph_snapshot(&db, r#"{"version": 1, "project": "app", "relations": {"cache": {}}}"#).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid graph snapshot: unknown relation 'cache'");
+
+        assert!(load_graph_snapshot(&db, "[]").is_err());
+    }
+}
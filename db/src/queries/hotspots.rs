@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::error::Error;
 
 use clap::ValueEnum;
@@ -6,7 +7,8 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_f64, extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder};
+use crate::queries::aggregates::has_aggregates;
+use crate::query_builders::{app_condition, validate_regex_patterns, OptionalConditionBuilder, QuerySettings, TestScope};
 
 /// What type of hotspots to find
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -45,6 +47,278 @@ pub fn get_module_loc(
     project: &str,
     module_pattern: Option<&str>,
     use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<HashMap<String, i64>, Box<dyn Error>> {
+    if !has_aggregates(db, project) {
+        return get_module_loc_computed(db, project, module_pattern, use_regex, settings);
+    }
+    let rows = query_module_stats(db, project, module_pattern, use_regex, "module, loc", "", settings)?;
+    Ok(parse_module_values(rows))
+}
+
+/// Get function count per module
+pub fn get_function_counts(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<HashMap<String, i64>, Box<dyn Error>> {
+    if !has_aggregates(db, project) {
+        return get_function_counts_computed(db, project, module_pattern, use_regex, settings);
+    }
+    let rows = query_module_stats(db, project, module_pattern, use_regex, "module, function_count", "", settings)?;
+    Ok(parse_module_values(rows))
+}
+
+/// (module, function) -> (loc, complexity)
+pub type FunctionSizes = HashMap<(String, String), (i64, i64)>;
+
+/// Get lines of code and cyclomatic complexity per function
+///
+/// Both are summed over the clauses and arities of the function, keyed by
+/// module and name like [`Hotspot`].
+pub fn get_function_sizes(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<FunctionSizes, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+
+    let script = format!(
+        r#"
+        sizes[module, name, sum(lines), sum(complexity)] :=
+            *function_locations{{project, module, name, start_line, end_line, complexity}},
+            project == $project,
+            lines = end_line - start_line + 1
+            {module_cond}
+            {namespace_cond}
+
+        ?[module, name, loc, complexity] :=
+            sizes[module, name, loc_sum, complexity_sum],
+            loc = to_int(loc_sum),
+            complexity = to_int(complexity_sum)
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| HotspotsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut sizes = HashMap::new();
+    for row in rows.rows {
+        if row.len() >= 4
+            && let (Some(module), Some(name)) = (extract_string(&row[0]), extract_string(&row[1])) {
+                sizes.insert((module, name), (extract_i64(&row[2], 0), extract_i64(&row[3], 0)));
+            }
+    }
+    Ok(sizes)
+}
+
+/// Get module-level connectivity (aggregated incoming/outgoing calls)
+///
+/// Returns a HashMap of module name -> (incoming, outgoing) call counts.
+/// Reads the materialized module totals when the project has them.
+pub fn get_module_connectivity(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<HashMap<String, (i64, i64)>, Box<dyn Error>> {
+    if !has_aggregates(db, project) {
+        return get_module_connectivity_computed(db, project, module_pattern, use_regex, settings);
+    }
+
+    // Modules without any called function have no connectivity entry
+    let rows = query_module_stats(
+        db,
+        project,
+        module_pattern,
+        use_regex,
+        "module, incoming, outgoing",
+        ", incoming > 0",
+        settings,
+    )?;
+
+    let mut connectivity = HashMap::new();
+    for row in rows {
+        if row.len() >= 3
+            && let Some(module) = extract_string(&row[0]) {
+                connectivity.insert(module, (extract_i64(&row[1], 0), extract_i64(&row[2], 0)));
+            }
+    }
+    Ok(connectivity)
+}
+
+/// Find functions with the most incoming/outgoing calls.
+///
+/// Reads the materialized per-function counts when the project has them,
+/// otherwise counts over the calls table.
+#[allow(clippy::too_many_arguments)]
+pub fn find_hotspots(
+    db: &cozo::DbInstance,
+    kind: HotspotKind,
+    module_pattern: Option<&str>,
+    app: Option<&str>,
+    project: &str,
+    use_regex: bool,
+    limit: u32,
+    exclude_generated: bool,
+    require_outgoing: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<Hotspot>, Box<dyn Error>> {
+    // The materialized counts include calls from tests
+    if !has_aggregates(db, project) || settings.test_scope == TestScope::Exclude {
+        return find_hotspots_computed(
+            db,
+            kind,
+            module_pattern,
+            app,
+            project,
+            use_regex,
+            limit,
+            exclude_generated,
+            require_outgoing,
+            settings,
+        );
+    }
+
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+    let app_cond = app_condition("module", app.is_some());
+    let scope_cond = format!(
+        "{}{}",
+        settings.project_scope_condition("module"),
+        settings.test_scope_condition("module")
+    );
+    let (hand_written, generated_filter) = if exclude_generated {
+        (settings.hand_written_rule("hand_written"), ", hand_written[module, function]")
+    } else {
+        (String::new(), "")
+    };
+    let outgoing_filter = if require_outgoing { ", outgoing > 0" } else { "" };
+
+    let script = format!(
+        r#"
+        {hand_written}
+        ?[module, function, incoming, outgoing, total, ratio] :=
+            *function_stats{{project, module, function, incoming, outgoing}},
+            project == $project,
+            total = incoming + outgoing,
+            ratio = if(outgoing == 0, 9999.0, incoming / outgoing)
+            {generated_filter}
+            {module_cond}
+            {namespace_cond}
+            {app_cond}
+            {scope_cond}
+            {outgoing_filter}
+
+        :order -{order_by}, module, function
+        :limit {limit}
+        "#,
+        order_by = order_column(kind),
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+    if let Some(app) = app {
+        params.insert("app", DataValue::Str(app.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| HotspotsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    Ok(parse_hotspots(rows.rows))
+}
+
+/// Select columns from the project's module_stats rows
+fn query_module_stats(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    columns: &str,
+    extra_cond: &str,
+    settings: &QuerySettings,
+) -> Result<Vec<Vec<DataValue>>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+
+    let script = format!(
+        r#"
+        ?[{columns}] :=
+            *module_stats{{project, module, function_count, loc, incoming, outgoing}},
+            project == $project
+            {module_cond}
+            {namespace_cond}
+            {extra_cond}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| HotspotsError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    Ok(rows.rows)
+}
+
+fn order_column(kind: HotspotKind) -> &'static str {
+    match kind {
+        HotspotKind::Incoming => "incoming",
+        HotspotKind::Outgoing => "outgoing",
+        HotspotKind::Total => "total",
+        HotspotKind::Ratio => "ratio",
+    }
+}
+
+/// Compute lines of code per module from function_locations
+pub(crate) fn get_module_loc_computed(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    settings: &QuerySettings,
 ) -> Result<std::collections::HashMap<String, i64>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[module_pattern])?;
 
@@ -53,6 +327,7 @@ pub fn get_module_loc(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     let script = format!(
         r#"
@@ -62,6 +337,7 @@ pub fn get_module_loc(
             project == $project,
             lines = end_line - start_line + 1
             {module_cond}
+            {namespace_cond}
 
         ?[module, loc] :=
             module_loc[module, loc]
@@ -76,6 +352,8 @@ pub fn get_module_loc(
         params.insert("module_pattern", DataValue::Str(pattern.into()));
     }
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| HotspotsError::QueryFailed {
         message: e.to_string(),
     })?;
@@ -92,12 +370,13 @@ pub fn get_module_loc(
     Ok(loc_map)
 }
 
-/// Get function count per module
-pub fn get_function_counts(
+/// Compute function count per module from function_locations
+pub(crate) fn get_function_counts_computed(
     db: &cozo::DbInstance,
     project: &str,
     module_pattern: Option<&str>,
     use_regex: bool,
+    settings: &QuerySettings,
 ) -> Result<std::collections::HashMap<String, i64>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[module_pattern])?;
 
@@ -106,6 +385,7 @@ pub fn get_function_counts(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     let script = format!(
         r#"
@@ -113,6 +393,7 @@ pub fn get_function_counts(
             *function_locations{{project, module, name}},
             project == $project
             {module_cond}
+            {namespace_cond}
 
         ?[module, func_count] :=
             func_counts[module, func_count]
@@ -127,6 +408,8 @@ pub fn get_function_counts(
         params.insert("module_pattern", DataValue::Str(pattern.into()));
     }
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| HotspotsError::QueryFailed {
         message: e.to_string(),
     })?;
@@ -143,16 +426,17 @@ pub fn get_function_counts(
     Ok(counts)
 }
 
-/// Get module-level connectivity (aggregated incoming/outgoing calls)
+/// Compute module-level connectivity (aggregated incoming/outgoing calls) from the calls table
 ///
 /// Returns a HashMap of module name -> (incoming, outgoing) call counts.
 /// This aggregates function-level hotspots to module level at the database layer,
 /// avoiding the need to fetch all function hotspots.
-pub fn get_module_connectivity(
+pub(crate) fn get_module_connectivity_computed(
     db: &cozo::DbInstance,
     project: &str,
     module_pattern: Option<&str>,
     use_regex: bool,
+    settings: &QuerySettings,
 ) -> Result<std::collections::HashMap<String, (i64, i64)>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[module_pattern])?;
 
@@ -161,6 +445,7 @@ pub fn get_module_connectivity(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     // Aggregate incoming/outgoing calls at module level
     let script = format!(
@@ -220,6 +505,7 @@ pub fn get_module_connectivity(
         module_connectivity[module, sum(incoming), sum(outgoing)] :=
             func_stats[module, function, incoming, outgoing]
             {module_cond}
+            {namespace_cond}
 
         ?[module, incoming, outgoing] :=
             module_connectivity[module, incoming, outgoing]
@@ -234,6 +520,8 @@ pub fn get_module_connectivity(
         params.insert("module_pattern", DataValue::Str(pattern.into()));
     }
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| HotspotsError::QueryFailed {
         message: e.to_string(),
     })?;
@@ -251,15 +539,19 @@ pub fn get_module_connectivity(
     Ok(connectivity)
 }
 
-pub fn find_hotspots(
+/// Compute function hotspots by counting over the calls table
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn find_hotspots_computed(
     db: &cozo::DbInstance,
     kind: HotspotKind,
     module_pattern: Option<&str>,
+    app: Option<&str>,
     project: &str,
     use_regex: bool,
     limit: u32,
     exclude_generated: bool,
     require_outgoing: bool,
+    settings: &QuerySettings,
 ) -> Result<Vec<Hotspot>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[module_pattern])?;
 
@@ -268,14 +560,23 @@ pub fn find_hotspots(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     // Build optional generated filter
     let generated_filter = if exclude_generated {
-        ", generated_by == \"\"".to_string()
+        settings.generated_condition("callee_function", "file", "generated_by")
     } else {
         String::new()
     };
 
+    let app_cond = app_condition("module", app.is_some());
+    let scope_cond = format!(
+        "{}{}",
+        settings.project_scope_condition("module"),
+        settings.test_scope_condition("module")
+    );
+    let caller_test_cond = settings.test_caller_condition("caller_module");
+
     // Build optional outgoing filter (for boundaries - exclude leaf nodes)
     let outgoing_filter = if require_outgoing {
         ", outgoing > 0".to_string()
@@ -283,12 +584,7 @@ pub fn find_hotspots(
         String::new()
     };
 
-    let order_by = match kind {
-        HotspotKind::Incoming => "incoming",
-        HotspotKind::Outgoing => "outgoing",
-        HotspotKind::Total => "total",
-        HotspotKind::Ratio => "ratio",
-    };
+    let order_by = order_column(kind);
 
     // Query to find hotspots by counting incoming and outgoing calls
     // We need to combine:
@@ -304,7 +600,7 @@ pub fn find_hotspots(
         # Join with function_locations to filter generated functions
         canonical[module, function] :=
             *calls{{project, callee_module, callee_function}},
-            *function_locations{{project, module: callee_module, name: callee_function, generated_by}},
+            *function_locations{{project, module: callee_module, name: callee_function, file, generated_by}},
             project == $project,
             module = callee_module,
             function = callee_function
@@ -328,6 +624,7 @@ pub fn find_hotspots(
             *calls{{project, caller_module, caller_function, callee_module, callee_function}},
             canonical[callee_module, callee_function],
             project == $project
+            {caller_test_cond}
 
         # Count unique incoming calls per function
         incoming_counts[module, function, count(caller_function)] :=
@@ -341,6 +638,9 @@ pub fn find_hotspots(
             total = incoming + outgoing,
             ratio = if(outgoing == 0, 9999.0, incoming / outgoing)
             {module_cond}
+            {namespace_cond}
+            {app_cond}
+            {scope_cond}
             {outgoing_filter}
 
         # Functions with only incoming (no outgoing) - leaf nodes
@@ -352,6 +652,9 @@ pub fn find_hotspots(
             total = incoming,
             ratio = 9999.0
             {module_cond}
+            {namespace_cond}
+            {app_cond}
+            {scope_cond}
             {outgoing_filter}
 
         # Functions with only outgoing (no incoming)
@@ -362,6 +665,9 @@ pub fn find_hotspots(
             total = outgoing,
             ratio = 0.0
             {module_cond}
+            {namespace_cond}
+            {app_cond}
+            {scope_cond}
 
         :order -{order_by}, module, function
         :limit {limit}
@@ -373,13 +679,22 @@ pub fn find_hotspots(
     if let Some(pattern) = module_pattern {
         params.insert("module_pattern", DataValue::Str(pattern.into()));
     }
+    if let Some(app) = app {
+        params.insert("app", DataValue::Str(app.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
 
     let rows = run_query(db, &script, params).map_err(|e| HotspotsError::QueryFailed {
         message: e.to_string(),
     })?;
 
+    Ok(parse_hotspots(rows.rows))
+}
+
+fn parse_hotspots(rows: Vec<Vec<DataValue>>) -> Vec<Hotspot> {
     let mut results = Vec::new();
-    for row in rows.rows {
+    for row in rows {
         if row.len() >= 6 {
             let Some(module) = extract_string(&row[0]) else { continue };
             let Some(function) = extract_string(&row[1]) else { continue };
@@ -399,7 +714,19 @@ pub fn find_hotspots(
         }
     }
 
-    Ok(results)
+    results
+}
+
+/// Map of module name -> value from a two-column query result
+fn parse_module_values(rows: Vec<Vec<DataValue>>) -> HashMap<String, i64> {
+    let mut values = HashMap::new();
+    for row in rows {
+        if row.len() >= 2
+            && let Some(module) = extract_string(&row[0]) {
+                values.insert(module, extract_i64(&row[1], 0));
+            }
+    }
+    values
 }
 
 #[cfg(test)]
@@ -412,6 +739,19 @@ mod tests {
         crate::test_utils::call_graph_db("default")
     }
 
+    #[rstest]
+    fn test_get_function_sizes(populated_db: cozo::DbInstance) {
+        let sizes = get_function_sizes(&populated_db, "default", None, false, &QuerySettings::default()).unwrap();
+        let loc: i64 = sizes.values().map(|(loc, _)| loc).sum();
+        let module_loc: i64 = get_module_loc(&populated_db, "default", None, false, &QuerySettings::default()).unwrap().values().sum();
+        assert_eq!(loc, module_loc);
+        assert!(sizes.values().all(|&(loc, complexity)| loc > 0 && complexity > 0));
+
+        let repo = get_function_sizes(&populated_db, "default", Some("MyApp.Repo"), false, &QuerySettings::default()).unwrap();
+        assert!(repo.keys().all(|(module, _)| module == "MyApp.Repo"));
+        assert!(repo.contains_key(&("MyApp.Repo".to_string(), "get".to_string())));
+    }
+
     #[rstest]
     fn test_get_module_connectivity_returns_results(populated_db: cozo::DbInstance) {
         let result = get_module_connectivity(
@@ -419,6 +759,7 @@ mod tests {
             "default",
             None,
             false,
+            &QuerySettings::default(),
         );
 
         if let Err(ref e) = result {
@@ -436,6 +777,7 @@ mod tests {
             "default",
             None,
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         // All modules should have non-negative counts
@@ -452,6 +794,7 @@ mod tests {
             "default",
             Some("Accounts"),
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         // All modules should contain "Accounts"
@@ -468,6 +811,7 @@ mod tests {
             "default",
             None,
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         // Get function-level hotspots
@@ -475,11 +819,13 @@ mod tests {
             &populated_db,
             HotspotKind::Total,
             None,
+            None,
             "default",
             false,
             u32::MAX,
             false,
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         // Manually aggregate function hotspots by module
@@ -508,6 +854,7 @@ mod tests {
             "default",
             None,
             false,
+            &QuerySettings::default(),
         );
 
         assert!(result.is_ok());
@@ -522,6 +869,7 @@ mod tests {
             "default",
             None,
             false,
+            &QuerySettings::default(),
         );
 
         assert!(result.is_ok());
@@ -537,6 +885,7 @@ mod tests {
             "default",
             None,
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         // Get function-level hotspots (OLD approach)
@@ -544,11 +893,13 @@ mod tests {
             &populated_db,
             HotspotKind::Total,
             None,
+            None,
             "default",
             false,
             u32::MAX,
             false,
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         // The new approach should return FAR fewer rows
@@ -580,6 +931,7 @@ mod tests {
             "nonexistent_project",
             None,
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         // Should return empty for non-existent project
@@ -593,6 +945,7 @@ mod tests {
             "default",
             Some("NonExistentModule"),
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         // Should return empty when module pattern matches nothing
@@ -606,6 +959,7 @@ mod tests {
             "default",
             Some(".*Accounts.*"),
             true, // use regex
+            &QuerySettings::default(),
         ).unwrap();
 
         // Should return results matching the regex
@@ -621,6 +975,7 @@ mod tests {
             "nonexistent_project",
             None,
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         assert!(loc_map.is_empty());
@@ -633,6 +988,7 @@ mod tests {
             "nonexistent_project",
             None,
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         assert!(counts.is_empty());
@@ -645,6 +1001,7 @@ mod tests {
             "default",
             None,
             false,
+            &QuerySettings::default(),
         ).unwrap();
 
         // Verify all counts are non-negative (sanity check)
@@ -653,4 +1010,73 @@ mod tests {
             assert!(*outgoing >= 0, "Module {} has negative outgoing", module);
         }
     }
+
+    #[test]
+    fn test_find_hotspots_only_project() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Api": {
+                    "encode/1:3": {"name": "encode", "arity": 1, "file": "lib/api.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 5},
+                    "render/1:7": {"name": "render", "arity": 1, "file": "lib/api.ex", "kind": "def", "line": 7, "start_line": 7, "end_line": 9}
+                },
+                "Jason": {
+                    "encode/2:10": {"name": "encode", "arity": 2, "file": "deps/jason/lib/jason.ex", "kind": "def", "line": 10, "start_line": 10, "end_line": 14}
+                }
+            },
+            "calls": [
+                {"caller": {"module": "MyApp.Api", "function": "render", "file": "lib/api.ex", "line": 8, "column": 5}, "type": "local", "callee": {"module": "MyApp.Api", "function": "encode", "arity": 1}},
+                {"caller": {"module": "MyApp.Api", "function": "encode", "file": "lib/api.ex", "line": 4, "column": 5}, "type": "remote", "callee": {"module": "Jason", "function": "encode", "arity": 2}}
+            ]
+        }"#;
+        let db = crate::test_utils::setup_test_db(json, "default");
+        let modules = |db: &cozo::DbInstance, settings: &QuerySettings| -> Vec<String> {
+            find_hotspots_computed(db, HotspotKind::Incoming, None, None, "default", false, 10, false, false, settings)
+                .unwrap()
+                .into_iter()
+                .map(|h| h.module)
+                .collect()
+        };
+
+        assert!(modules(&db, &QuerySettings::default()).contains(&"Jason".to_string()));
+
+        let scoped = modules(&db, &QuerySettings { only_project: true, ..Default::default() });
+        assert!(!scoped.is_empty());
+        assert!(scoped.iter().all(|m| m == "MyApp.Api"));
+    }
+
+    #[test]
+    fn test_find_hotspots_test_scope() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.Api": {
+                    "encode/1:3": {"name": "encode", "arity": 1, "file": "lib/api.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 5},
+                    "render/1:7": {"name": "render", "arity": 1, "file": "lib/api.ex", "kind": "def", "line": 7, "start_line": 7, "end_line": 9}
+                },
+                "MyApp.ApiTest": {
+                    "test_encode/1:4": {"name": "test_encode", "arity": 1, "file": "test/api_test.exs", "kind": "def", "line": 4, "start_line": 4, "end_line": 8}
+                }
+            },
+            "calls": [
+                {"caller": {"module": "MyApp.Api", "function": "render", "file": "lib/api.ex", "line": 8, "column": 5}, "type": "local", "callee": {"module": "MyApp.Api", "function": "encode", "arity": 1}},
+                {"caller": {"module": "MyApp.ApiTest", "function": "test_encode", "file": "test/api_test.exs", "line": 5, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Api", "function": "encode", "arity": 1}},
+                {"caller": {"module": "MyApp.ApiTest", "function": "test_encode", "file": "test/api_test.exs", "line": 6, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Api", "function": "render", "arity": 1}}
+            ]
+        }"#;
+        let db = crate::test_utils::setup_test_db(json, "default");
+        let hotspots = |test_scope: TestScope| -> Vec<(String, String, i64)> {
+            let settings = QuerySettings { test_scope, ..Default::default() };
+            let hotspots = find_hotspots(&db, HotspotKind::Incoming, None, None, "default", false, 10, false, false, &settings).unwrap();
+            hotspots.into_iter().map(|h| (h.module, h.function, h.incoming)).collect()
+        };
+
+        assert_eq!(hotspots(TestScope::All)[0], ("MyApp.Api".to_string(), "encode".to_string(), 2));
+        // Calls from tests no longer count
+        assert_eq!(
+            hotspots(TestScope::Exclude),
+            [("MyApp.Api".to_string(), "encode".to_string(), 1), ("MyApp.Api".to_string(), "render".to_string(), 0)]
+        );
+        assert!(hotspots(TestScope::Only).iter().all(|(module, _, _)| module == "MyApp.ApiTest"));
+    }
 }
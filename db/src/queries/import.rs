@@ -1,15 +1,23 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use cozo::{DataValue, DbInstance};
 use serde::Serialize;
 use thiserror::Error;
 
-use crate::db::{escape_string, escape_string_single, run_query, run_query_no_params, Params};
-use crate::queries::import_models::CallGraph;
+use crate::db::{run_query, run_query_no_params, Params};
+use crate::sanitize::literal;
+use crate::queries::adjacency;
+use crate::queries::aggregates;
+use crate::queries::import_checkpoints::Checkpoint;
+use crate::queries::import_models::{Call, CallGraph, FieldAccess, Message};
 use crate::queries::schema;
+use crate::queries::timeline;
 
 /// Chunk size for batch database imports
-const IMPORT_CHUNK_SIZE: usize = 500;
+pub const IMPORT_CHUNK_SIZE: usize = 500;
 
 #[derive(Error, Debug)]
 pub enum ImportError {
@@ -38,10 +46,18 @@ pub struct ImportResult {
     pub modules_imported: usize,
     pub functions_imported: usize,
     pub calls_imported: usize,
+    pub messages_imported: usize,
     pub structs_imported: usize,
+    pub field_accesses_imported: usize,
     pub function_locations_imported: usize,
     pub specs_imported: usize,
     pub types_imported: usize,
+    pub behaviours_imported: usize,
+    pub docs_imported: usize,
+    pub supervision_imported: usize,
+    pub aliases_imported: usize,
+    /// Whether a metrics snapshot was recorded for `trends`
+    pub snapshot_recorded: bool,
 }
 
 /// Result of schema creation
@@ -49,8 +65,13 @@ pub struct ImportResult {
 pub struct SchemaResult {
     pub created: Vec<String>,
     pub already_existed: Vec<String>,
+    /// Columns added to relations set up by an older version
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub repaired: Vec<schema::SchemaRepair>,
 }
 
+/// Create the missing relations and add the columns that relations set up
+/// by an older version lack (see [`schema::add_missing_columns`])
 pub fn create_schema(db: &DbInstance) -> Result<SchemaResult, Box<dyn Error>> {
     let mut result = SchemaResult::default();
 
@@ -63,6 +84,7 @@ pub fn create_schema(db: &DbInstance) -> Result<SchemaResult, Box<dyn Error>> {
             result.already_existed.push(schema_result.relation);
         }
     }
+    result.repaired = schema::add_missing_columns(db)?;
 
     Ok(result)
 }
@@ -74,10 +96,16 @@ pub fn clear_project_data(db: &DbInstance, project: &str) -> Result<(), Box<dyn
         ("modules", "project, name"),
         ("functions", "project, module, name, arity"),
         ("calls", "project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column"),
+        ("messages", "project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column"),
         ("struct_fields", "project, module, field"),
+        ("field_accesses", "project, struct_module, field, caller_module, caller_function, file, line, column, access"),
         ("function_locations", "project, module, name, arity, line"),
         ("specs", "project, module, name, arity"),
         ("types", "project, module, name"),
+        ("behaviours", "project, module, behaviour"),
+        ("docs", "project, module, name, arity"),
+        ("supervision", "project, supervisor, child_id"),
+        ("aliases", "project, module, kind, target"),
     ];
 
     for (table, keys) in tables {
@@ -101,43 +129,119 @@ pub fn clear_project_data(db: &DbInstance, project: &str) -> Result<(), Box<dyn
     Ok(())
 }
 
-/// Import rows in chunks into a CozoDB table
-fn import_rows(
-    db: &DbInstance,
-    rows: Vec<String>,
-    columns: &str,
-    table_spec: &str,
-    data_type: &str,
-) -> Result<usize, Box<dyn Error>> {
-    if rows.is_empty() {
-        return Ok(0);
+/// Delete the rows of a project that come from the given source files.
+///
+/// Covers the relations keyed by file, so re-importing a file drops the
+/// clauses, calls and accesses it no longer has. Modules, functions and specs
+/// are keyed by name and stay until overwritten.
+pub fn clear_file_data(db: &DbInstance, project: &str, files: &[String]) -> Result<(), Box<dyn Error>> {
+    if files.is_empty() {
+        return Ok(());
     }
 
-    for chunk in rows.chunks(IMPORT_CHUNK_SIZE) {
+    let tables = [
+        ("calls", "project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column", ""),
+        ("messages", "project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column", ""),
+        ("field_accesses", "project, struct_module, field, caller_module, caller_function, file, line, column, access", ""),
+        ("function_locations", "project, module, name, arity, line", ", file"),
+    ];
+
+    for (table, keys, file_binding) in tables {
         let script = format!(
             r#"
-            ?[{columns}] <- [{rows}]
-            :put {table_spec}
+            ?[{keys}] := *{table}{{{keys}{file_binding}}}, project == $project, is_in(file, $files)
+            :rm {table} {{{keys}}}
             "#,
-            columns = columns,
-            rows = chunk.join(", "),
-            table_spec = table_spec
         );
 
-        run_query_no_params(db, &script).map_err(|e| ImportError::ImportFailed {
-            data_type: data_type.to_string(),
-            message: e.to_string(),
+        let mut params = Params::new();
+        params.insert("project", DataValue::Str(project.into()));
+        params.insert(
+            "files",
+            DataValue::List(files.iter().map(|f| DataValue::Str(f.as_str().into())).collect()),
+        );
+
+        run_query(db, &script, params).map_err(|e| ImportError::ClearFailed {
+            message: format!("Failed to clear {} of {} file(s): {}", table, files.len(), e),
         })?;
     }
 
+    Ok(())
+}
+
+/// Import rows in chunks into a CozoDB table.
+///
+/// Rows are sorted so that each chunk holds the same rows on every run, and
+/// chunks a resumed import already wrote are skipped.
+fn import_rows(
+    db: &DbInstance,
+    mut rows: Vec<String>,
+    columns: &str,
+    table_spec: &str,
+    data_type: &str,
+    checkpoint: Option<&Checkpoint>,
+) -> Result<usize, Box<dyn Error>> {
+    rows.sort_unstable();
+    for (index, chunk) in rows.chunks(IMPORT_CHUNK_SIZE).enumerate() {
+        if checkpoint.is_some_and(|checkpoint| checkpoint.is_written(data_type, index)) {
+            continue;
+        }
+        write_chunk(db, chunk, columns, table_spec, data_type)?;
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.record(db, data_type, index + 1)?;
+        }
+    }
+
     Ok(rows.len())
 }
 
+/// Write one chunk of rows into a CozoDB table
+fn write_chunk(
+    db: &DbInstance,
+    rows: &[String],
+    columns: &str,
+    table_spec: &str,
+    data_type: &str,
+) -> Result<(), Box<dyn Error>> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let script = format!(
+        r#"
+        ?[{columns}] <- [{rows}]
+        :put {table_spec}
+        "#,
+        columns = columns,
+        rows = rows.join(", "),
+        table_spec = table_spec
+    );
+
+    run_query_no_params(db, &script).map_err(|e| ImportError::ImportFailed {
+        data_type: data_type.to_string(),
+        message: e.to_string(),
+    })?;
+
+    Ok(())
+}
+
 pub fn import_modules(
     db: &DbInstance,
     project: &str,
     graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
 ) -> Result<usize, Box<dyn Error>> {
+    import_rows(
+        db,
+        modules_rows(project, graph),
+        "project, name, file, source, app, is_external, is_test",
+        "modules { project, name => file, source, app, is_external, is_test }",
+        "modules",
+        checkpoint,
+    )
+}
+
+fn modules_rows(project: &str, graph: &CallGraph) -> Vec<String> {
     // Collect unique modules from all data sources
     let mut modules = std::collections::HashSet::new();
     modules.extend(graph.specs.keys().cloned());
@@ -145,32 +249,105 @@ pub fn import_modules(
     modules.extend(graph.structs.keys().cloned());
     modules.extend(graph.types.keys().cloned());
 
-    let rows: Vec<String> = modules
+    let apps = module_apps(graph);
+
+    modules
         .iter()
         .map(|m| {
             format!(
-                r#"["{}", "{}", "", "unknown"]"#,
-                escape_string(project),
-                escape_string(m),
+                r#"[{}, {}, "", "unknown", {}, {}, {}]"#,
+                literal(project),
+                literal(m),
+                literal(apps.get(m.as_str()).copied().unwrap_or("")),
+                is_external_module(graph, m),
+                is_test_module(graph, m),
             )
         })
-        .collect();
+        .collect()
+}
 
-    import_rows(
-        db,
-        rows,
-        "project, name, file, source",
-        "modules { project, name => file, source }",
-        "modules",
-    )
+/// Whether a module is vendored: it has function locations and all of them
+/// are in a dependency directory.
+///
+/// Modules known only from specs, structs or types stay internal.
+fn is_external_module(graph: &CallGraph, module: &str) -> bool {
+    graph.function_locations.get(module).is_some_and(|functions| {
+        !functions.is_empty()
+            && functions
+                .values()
+                .all(|loc| loc.file.as_deref().is_some_and(is_vendored_path))
+    })
+}
+
+/// Whether a source path is inside a dependency directory (`deps/` or `_build/`)
+fn is_vendored_path(path: &str) -> bool {
+    path.split('/').any(|segment| segment == "deps" || segment == "_build")
+}
+
+/// Whether a module is test code: the importer classified it as such, or it
+/// has function locations and all of them are in test files.
+fn is_test_module(graph: &CallGraph, module: &str) -> bool {
+    graph.test_modules.contains(module)
+        || graph.function_locations.get(module).is_some_and(|functions| {
+            !functions.is_empty()
+                && functions
+                    .values()
+                    .all(|loc| loc.file.as_deref().is_some_and(is_test_path))
+        })
+}
+
+/// Whether a source path is test code: inside a `test/` directory or an ExUnit `_test.exs` file
+fn is_test_path(path: &str) -> bool {
+    path.ends_with("_test.exs") || path.split('/').any(|segment| segment == "test")
+}
+
+/// Resolve the OTP application of each module with function locations.
+///
+/// An explicit `app` on a location wins; otherwise the app is taken from an
+/// umbrella source path (`apps/<app>/...`).
+fn module_apps(graph: &CallGraph) -> std::collections::HashMap<&str, &str> {
+    let mut apps = std::collections::HashMap::new();
+    for (module, functions) in &graph.function_locations {
+        let explicit = functions.values().find_map(|loc| loc.app.as_deref().filter(|app| !app.is_empty()));
+        let from_path = || {
+            functions
+                .values()
+                .find_map(|loc| loc.file.as_deref().and_then(app_from_path))
+        };
+        if let Some(app) = explicit.or_else(from_path) {
+            apps.insert(module.as_str(), app);
+        }
+    }
+    apps
+}
+
+/// The umbrella app of a source path, e.g. `billing` for `apps/billing/lib/invoice.ex`
+fn app_from_path(path: &str) -> Option<&str> {
+    let segments: Vec<&str> = path.split('/').collect();
+    segments
+        .windows(3)
+        .find(|w| w[0] == "apps" && !w[1].is_empty())
+        .map(|w| w[1])
 }
 
 pub fn import_functions(
     db: &DbInstance,
     project: &str,
     graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
 ) -> Result<usize, Box<dyn Error>> {
-    let escaped_project = escape_string(project);
+    import_rows(
+        db,
+        functions_rows(project, graph),
+        "project, module, name, arity, return_type, args, source",
+        "functions { project, module, name, arity => return_type, args, source }",
+        "functions",
+        checkpoint,
+    )
+}
+
+fn functions_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
     let mut rows = Vec::new();
 
     // Import functions from specs data
@@ -184,63 +361,96 @@ pub fn import_functions(
                 .unwrap_or_default();
 
             rows.push(format!(
-                r#"["{}", "{}", "{}", {}, "{}", "{}", "unknown"]"#,
-                escaped_project,
-                escape_string(module),
-                escape_string(&spec.name),
+                r#"[{}, {}, {}, {}, {}, {}, "unknown"]"#,
+                project_literal,
+                literal(module),
+                literal(&spec.name),
                 spec.arity,
-                escape_string(&return_type),
-                escape_string(&args),
+                literal(&return_type),
+                literal(&args),
             ));
         }
     }
 
-    import_rows(
-        db,
-        rows,
-        "project, module, name, arity, return_type, args, source",
-        "functions { project, module, name, arity => return_type, args, source }",
-        "functions",
-    )
+    rows
 }
 
+const CALLS_COLUMNS: &str = "project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column, call_type, caller_kind, callee_args, call_kind, conditional";
+const CALLS_SPEC: &str = "calls { project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column => call_type, caller_kind, callee_args, call_kind, conditional }";
+
 pub fn import_calls(
     db: &DbInstance,
     project: &str,
     graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
 ) -> Result<usize, Box<dyn Error>> {
-    let escaped_project = escape_string(project);
-    let rows: Vec<String> = graph
+    import_rows(db, calls_rows(project, graph), CALLS_COLUMNS, CALLS_SPEC, "calls", checkpoint)
+}
+
+fn calls_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
+    graph
         .calls
         .iter()
-        .map(|call| {
-            let caller_kind = call.caller.kind.as_deref().unwrap_or("");
-            let callee_args = call.callee.args.as_deref().unwrap_or("");
+        .map(|call| call_row(&project_literal, call, &call.callee.module))
+        .collect()
+}
 
-            format!(
-                r#"["{}", "{}", "{}", "{}", "{}", {}, "{}", {}, {}, "{}", "{}", '{}']"#,
-                escaped_project,
-                escape_string(&call.caller.module),
-                escape_string(call.caller.function.as_deref().unwrap_or("<module>")),
-                escape_string(&call.callee.module),
-                escape_string(&call.callee.function),
-                call.callee.arity,
-                escape_string(&call.caller.file),
-                call.caller.line.unwrap_or(0),
-                call.caller.column.unwrap_or(0),
-                escape_string(&call.call_type),
-                escape_string(caller_kind),
-                escape_string_single(callee_args),
-            )
-        })
-        .collect();
+fn call_row(project_literal: &str, call: &Call, callee_module: &str) -> String {
+    let caller_kind = call.caller.kind.as_deref().unwrap_or("");
+    let callee_args = call.callee.args.as_deref().unwrap_or("");
+
+    format!(
+        r#"[{}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}]"#,
+        project_literal,
+        literal(&call.caller.module),
+        literal(call.caller.function.as_deref().unwrap_or("<module>")),
+        literal(callee_module),
+        literal(&call.callee.function),
+        call.callee.arity,
+        literal(&call.caller.file),
+        call.caller.line.unwrap_or(0),
+        call.caller.column.unwrap_or(0),
+        literal(&call.call_type),
+        literal(caller_kind),
+        literal(callee_args),
+        literal(&call.kind),
+        call.conditional,
+    )
+}
 
-    import_rows(
-        db,
-        rows,
-        "project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column, call_type, caller_kind, callee_args",
-        "calls { project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column => call_type, caller_kind, callee_args }",
-        "calls",
+const MESSAGES_COLUMNS: &str = "project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column, kind, topic, conditional";
+const MESSAGES_SPEC: &str = "messages { project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column => kind, topic, conditional }";
+
+pub fn import_messages(
+    db: &DbInstance,
+    project: &str,
+    graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
+) -> Result<usize, Box<dyn Error>> {
+    import_rows(db, messages_rows(project, graph), MESSAGES_COLUMNS, MESSAGES_SPEC, "messages", checkpoint)
+}
+
+fn messages_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
+    graph.messages.iter().map(|message| message_row(&project_literal, message)).collect()
+}
+
+fn message_row(project_literal: &str, message: &Message) -> String {
+    format!(
+        r#"[{}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}]"#,
+        project_literal,
+        literal(&message.caller.module),
+        literal(message.caller.function.as_deref().unwrap_or("<module>")),
+        literal(&message.callee.module),
+        literal(&message.callee.function),
+        message.callee.arity,
+        literal(&message.caller.file),
+        message.caller.line.unwrap_or(0),
+        message.caller.column.unwrap_or(0),
+        literal(&message.kind),
+        literal(message.topic.as_deref().unwrap_or("")),
+        message.conditional,
     )
 }
 
@@ -248,31 +458,76 @@ pub fn import_structs(
     db: &DbInstance,
     project: &str,
     graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
 ) -> Result<usize, Box<dyn Error>> {
-    let escaped_project = escape_string(project);
+    import_rows(
+        db,
+        structs_rows(project, graph),
+        "project, module, field, default_value, required, inferred_type",
+        "struct_fields { project, module, field => default_value, required, inferred_type }",
+        "struct_fields",
+        checkpoint,
+    )
+}
+
+fn structs_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
     let mut rows = Vec::new();
 
     for (module, def) in &graph.structs {
         for field in &def.fields {
             let inferred_type = field.inferred_type.as_deref().unwrap_or("");
             rows.push(format!(
-                r#"["{}", "{}", '{}', '{}', {}, "{}"]"#,
-                escaped_project,
-                escape_string(module),
-                escape_string_single(&field.field),
-                escape_string_single(&field.default),
+                r#"[{}, {}, {}, {}, {}, {}]"#,
+                project_literal,
+                literal(module),
+                literal(&field.field),
+                literal(&field.default),
                 field.required,
-                escape_string(inferred_type)
+                literal(inferred_type)
             ));
         }
     }
 
+    rows
+}
+
+const FIELD_ACCESSES_COLUMNS: &str = "project, struct_module, field, caller_module, caller_function, file, line, column, access";
+const FIELD_ACCESSES_SPEC: &str = "field_accesses { project, struct_module, field, caller_module, caller_function, file, line, column, access }";
+
+pub fn import_field_accesses(
+    db: &DbInstance,
+    project: &str,
+    graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
+) -> Result<usize, Box<dyn Error>> {
     import_rows(
         db,
-        rows,
-        "project, module, field, default_value, required, inferred_type",
-        "struct_fields { project, module, field => default_value, required, inferred_type }",
-        "struct_fields",
+        field_accesses_rows(project, graph),
+        FIELD_ACCESSES_COLUMNS,
+        FIELD_ACCESSES_SPEC,
+        "field_accesses",
+        checkpoint,
+    )
+}
+
+fn field_accesses_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
+    graph.field_accesses.iter().map(|access| field_access_row(&project_literal, access)).collect()
+}
+
+fn field_access_row(project_literal: &str, access: &FieldAccess) -> String {
+    format!(
+        r#"[{}, {}, {}, {}, {}, {}, {}, {}, {}]"#,
+        project_literal,
+        literal(&access.struct_module),
+        literal(&access.field),
+        literal(&access.caller.module),
+        literal(access.caller.function.as_deref().unwrap_or("<module>")),
+        literal(&access.caller.file),
+        access.caller.line.unwrap_or(0),
+        access.caller.column.unwrap_or(0),
+        literal(&access.access),
     )
 }
 
@@ -280,8 +535,20 @@ pub fn import_function_locations(
     db: &DbInstance,
     project: &str,
     graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
 ) -> Result<usize, Box<dyn Error>> {
-    let escaped_project = escape_string(project);
+    import_rows(
+        db,
+        function_locations_rows(project, graph),
+        "project, module, name, arity, line, file, source_file_absolute, column, kind, start_line, end_line, pattern, guard, source_sha, ast_sha, complexity, max_nesting_depth, generated_by, macro_source, ast_minhash, abc_score, halstead_volume, cognitive_complexity, deprecated, modified_at, commit",
+        "function_locations { project, module, name, arity, line => file, source_file_absolute, column, kind, start_line, end_line, pattern, guard, source_sha, ast_sha, complexity, max_nesting_depth, generated_by, macro_source, ast_minhash, abc_score, halstead_volume, cognitive_complexity, deprecated, modified_at, commit }",
+        "function_locations",
+        checkpoint,
+    )
+}
+
+fn function_locations_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
     let mut rows = Vec::new();
 
     for (module, functions) in &graph.function_locations {
@@ -298,47 +565,64 @@ pub fn import_function_locations(
             let ast_sha = loc.ast_sha.as_deref().unwrap_or("");
             let generated_by = loc.generated_by.as_deref().unwrap_or("");
             let macro_source = loc.macro_source.as_deref().unwrap_or("");
+            let deprecated = loc.deprecated.as_deref().unwrap_or("");
+            let commit = loc.commit.as_deref().unwrap_or("");
+
+            let ast_minhash = loc.ast_minhash.iter().map(i64::to_string).collect::<Vec<_>>().join(", ");
 
             rows.push(format!(
-                r#"["{}", "{}", "{}", {}, {}, "{}", "{}", {}, "{}", {}, {}, '{}', '{}', "{}", "{}", {}, {}, "{}", "{}"]"#,
-                escaped_project,
-                escape_string(module),
-                escape_string(name),
+                r#"[{}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, [{}], {:?}, {:?}, {}, {}, {}, {}]"#,
+                project_literal,
+                literal(module),
+                literal(name),
                 arity,
                 line,
-                escape_string(loc.file.as_deref().unwrap_or("")),
-                escape_string(source_file_absolute),
+                literal(loc.file.as_deref().unwrap_or("")),
+                literal(source_file_absolute),
                 loc.column.unwrap_or(0),
-                escape_string(&loc.kind),
+                literal(&loc.kind),
                 loc.start_line,
                 loc.end_line,
-                escape_string_single(pattern),
-                escape_string_single(guard),
-                escape_string(source_sha),
-                escape_string(ast_sha),
+                literal(pattern),
+                literal(guard),
+                literal(source_sha),
+                literal(ast_sha),
                 loc.complexity,
                 loc.max_nesting_depth,
-                escape_string(generated_by),
-                escape_string(macro_source),
+                literal(generated_by),
+                literal(macro_source),
+                ast_minhash,
+                loc.abc_score,
+                loc.halstead_volume,
+                loc.cognitive_complexity,
+                literal(deprecated),
+                loc.modified_at,
+                literal(commit),
             ));
         }
     }
 
-    import_rows(
-        db,
-        rows,
-        "project, module, name, arity, line, file, source_file_absolute, column, kind, start_line, end_line, pattern, guard, source_sha, ast_sha, complexity, max_nesting_depth, generated_by, macro_source",
-        "function_locations { project, module, name, arity, line => file, source_file_absolute, column, kind, start_line, end_line, pattern, guard, source_sha, ast_sha, complexity, max_nesting_depth, generated_by, macro_source }",
-        "function_locations",
-    )
+    rows
 }
 
 pub fn import_specs(
     db: &DbInstance,
     project: &str,
     graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
 ) -> Result<usize, Box<dyn Error>> {
-    let escaped_project = escape_string(project);
+    import_rows(
+        db,
+        specs_rows(project, graph),
+        "project, module, name, arity, kind, line, inputs_string, return_string, full",
+        "specs { project, module, name, arity => kind, line, inputs_string, return_string, full }",
+        "specs",
+        checkpoint,
+    )
+}
+
+fn specs_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
     let mut rows = Vec::new();
 
     for (module, specs) in &graph.specs {
@@ -357,35 +641,41 @@ pub fn import_specs(
                 .unwrap_or_default();
 
             rows.push(format!(
-                r#"["{}", "{}", "{}", {}, "{}", {}, "{}", "{}", "{}"]"#,
-                escaped_project,
-                escape_string(module),
-                escape_string(&spec.name),
+                r#"[{}, {}, {}, {}, {}, {}, {}, {}, {}]"#,
+                project_literal,
+                literal(module),
+                literal(&spec.name),
                 spec.arity,
-                escape_string(&spec.kind),
+                literal(&spec.kind),
                 spec.line,
-                escape_string(&inputs_string),
-                escape_string(&return_string),
-                escape_string(&full),
+                literal(&inputs_string),
+                literal(&return_string),
+                literal(&full),
             ));
         }
     }
 
-    import_rows(
-        db,
-        rows,
-        "project, module, name, arity, kind, line, inputs_string, return_string, full",
-        "specs { project, module, name, arity => kind, line, inputs_string, return_string, full }",
-        "specs",
-    )
+    rows
 }
 
 pub fn import_types(
     db: &DbInstance,
     project: &str,
     graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
 ) -> Result<usize, Box<dyn Error>> {
-    let escaped_project = escape_string(project);
+    import_rows(
+        db,
+        types_rows(project, graph),
+        "project, module, name, kind, params, line, definition",
+        "types { project, module, name => kind, params, line, definition }",
+        "types",
+        checkpoint,
+    )
+}
+
+fn types_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
     let mut rows = Vec::new();
 
     for (module, types) in &graph.types {
@@ -393,50 +683,413 @@ pub fn import_types(
             let params = type_def.params.join(", ");
 
             rows.push(format!(
-                r#"["{}", "{}", "{}", "{}", "{}", {}, '{}']"#,
-                escaped_project,
-                escape_string(module),
-                escape_string(&type_def.name),
-                escape_string(&type_def.kind),
-                escape_string(&params),
+                r#"[{}, {}, {}, {}, {}, {}, {}]"#,
+                project_literal,
+                literal(module),
+                literal(&type_def.name),
+                literal(&type_def.kind),
+                literal(&params),
                 type_def.line,
-                escape_string_single(&type_def.definition),
+                literal(&type_def.definition),
+            ));
+        }
+    }
+
+    rows
+}
+
+pub fn import_behaviours(
+    db: &DbInstance,
+    project: &str,
+    graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
+) -> Result<usize, Box<dyn Error>> {
+    import_rows(
+        db,
+        behaviours_rows(project, graph),
+        "project, module, behaviour",
+        "behaviours { project, module, behaviour }",
+        "behaviours",
+        checkpoint,
+    )
+}
+
+fn behaviours_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
+    let mut rows = Vec::new();
+
+    for (module, behaviours) in &graph.behaviours {
+        for behaviour in behaviours {
+            rows.push(format!(
+                r#"[{}, {}, {}]"#,
+                project_literal,
+                literal(module),
+                literal(behaviour),
             ));
         }
     }
 
+    rows
+}
+
+pub fn import_docs(
+    db: &DbInstance,
+    project: &str,
+    graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
+) -> Result<usize, Box<dyn Error>> {
     import_rows(
         db,
-        rows,
-        "project, module, name, kind, params, line, definition",
-        "types { project, module, name => kind, params, line, definition }",
-        "types",
+        docs_rows(project, graph),
+        "project, module, name, arity, kind, doc",
+        "docs { project, module, name, arity => kind, doc }",
+        "docs",
+        checkpoint,
     )
 }
 
+fn docs_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
+    let mut rows = Vec::new();
+
+    for (module, docs) in &graph.docs {
+        let module_literal = literal(module);
+        if let Some(moduledoc) = docs.moduledoc.as_deref().filter(|d| !d.trim().is_empty()) {
+            rows.push(format!(
+                r#"[{}, {}, "", -1, "moduledoc", {}]"#,
+                project_literal,
+                module_literal,
+                literal(moduledoc),
+            ));
+        }
+        for function in docs.functions.iter().filter(|f| !f.doc.trim().is_empty()) {
+            rows.push(format!(
+                r#"[{}, {}, {}, {}, "doc", {}]"#,
+                project_literal,
+                module_literal,
+                literal(&function.name),
+                function.arity,
+                literal(&function.doc),
+            ));
+        }
+    }
+
+    rows
+}
+
+pub fn import_supervision(
+    db: &DbInstance,
+    project: &str,
+    graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
+) -> Result<usize, Box<dyn Error>> {
+    import_rows(
+        db,
+        supervision_rows(project, graph),
+        "project, supervisor, child_id, child_module, child_type, restart, strategy, position",
+        "supervision { project, supervisor, child_id => child_module, child_type, restart, strategy, position }",
+        "supervision",
+        checkpoint,
+    )
+}
+
+fn supervision_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
+    let mut rows = Vec::new();
+
+    for sup in &graph.supervision {
+        let strategy = sup.strategy.as_deref().unwrap_or("");
+        for (position, child) in sup.children.iter().enumerate() {
+            let child_module = child.module.as_deref().unwrap_or(&child.id);
+
+            rows.push(format!(
+                r#"[{}, {}, {}, {}, {}, {}, {}, {}]"#,
+                project_literal,
+                literal(&sup.supervisor),
+                literal(&child.id),
+                literal(child_module),
+                literal(&child.child_type),
+                literal(&child.restart),
+                literal(strategy),
+                position,
+            ));
+        }
+    }
+
+    rows
+}
+
+pub fn import_aliases(
+    db: &DbInstance,
+    project: &str,
+    graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
+) -> Result<usize, Box<dyn Error>> {
+    import_rows(
+        db,
+        aliases_rows(project, graph),
+        "project, module, kind, target, alias, line",
+        "aliases { project, module, kind, target => alias, line }",
+        "aliases",
+        checkpoint,
+    )
+}
+
+fn aliases_rows(project: &str, graph: &CallGraph) -> Vec<String> {
+    let project_literal = literal(project);
+    let mut rows = Vec::new();
+
+    for (module, directives) in &graph.aliases {
+        for directive in directives {
+            rows.push(format!(
+                r#"[{}, {}, {}, {}, {}, {}]"#,
+                project_literal,
+                literal(module),
+                literal(&directive.kind),
+                literal(&directive.target),
+                literal(directive.short_name().unwrap_or("")),
+                directive.line,
+            ));
+        }
+    }
+
+    rows
+}
+
+/// Short names each module gives other modules, to their full names
+fn alias_map(graph: &CallGraph) -> HashMap<String, HashMap<String, String>> {
+    graph
+        .aliases
+        .iter()
+        .map(|(module, directives)| {
+            let names = directives
+                .iter()
+                .filter_map(|d| d.short_name().map(|short| (short.to_string(), d.target.clone())))
+                .collect();
+            (module.clone(), names)
+        })
+        .collect()
+}
+
+/// The full name of a callee module its caller refers to by an alias.
+///
+/// `Repo.get` in a module with `alias MyApp.Repo` calls `MyApp.Repo`, and
+/// `Repo.Query` is `MyApp.Repo.Query`; other names are returned as they are.
+fn expand_alias<'a>(aliases: &HashMap<String, String>, module: &'a str) -> Cow<'a, str> {
+    let (first, rest) = module.split_once('.').map_or((module, None), |(first, rest)| (first, Some(rest)));
+    match (aliases.get(first), rest) {
+        (Some(full), Some(rest)) => Cow::Owned(format!("{}.{}", full, rest)),
+        (Some(full), None) => Cow::Owned(full.clone()),
+        (None, _) => Cow::Borrowed(module),
+    }
+}
+
 /// Import a parsed CallGraph into the database.
 ///
-/// Creates schemas and imports all data (modules, functions, calls, structs, locations).
+/// Creates schemas and imports all data (modules, functions, calls, messages, structs,
+/// field accesses, locations, specs, types, behaviours, docs, supervision, aliases).
+/// Callee modules recorded under an alias of their caller are stored by their
+/// full name.
 /// This is the core import logic used by both the CLI command and test utilities.
 pub fn import_graph(
     db: &DbInstance,
     project: &str,
     graph: &CallGraph,
 ) -> Result<ImportResult, Box<dyn Error>> {
-    let mut result = ImportResult::default();
+    import_graph_with_call_sites(db, project, graph, None, |_| Ok(()))
+}
 
-    result.schemas = create_schema(db)?;
-    result.modules_imported = import_modules(db, project, graph)?;
-    result.functions_imported = import_functions(db, project, graph)?;
-    result.calls_imported = import_calls(db, project, graph)?;
-    result.structs_imported = import_structs(db, project, graph)?;
-    result.function_locations_imported = import_function_locations(db, project, graph)?;
-    result.specs_imported = import_specs(db, project, graph)?;
-    result.types_imported = import_types(db, project, graph)?;
+/// Import a CallGraph along with call sites that are not part of it.
+///
+/// `call_sites` adds them to the writer after those of the graph, e.g. while
+/// streaming them from a file; aggregates are refreshed once all are written.
+/// With a checkpoint, progress is recorded per chunk and the chunks it marks
+/// as written are skipped.
+pub fn import_graph_with_call_sites(
+    db: &DbInstance,
+    project: &str,
+    graph: &CallGraph,
+    checkpoint: Option<&Checkpoint>,
+    call_sites: impl FnOnce(&mut CallSiteWriter) -> Result<(), Box<dyn Error>>,
+) -> Result<ImportResult, Box<dyn Error>> {
+    let mut result = ImportResult {
+        schemas: create_schema(db)?,
+        ..Default::default()
+    };
+
+    result.modules_imported = import_modules(db, project, graph, checkpoint)?;
+    result.functions_imported = import_functions(db, project, graph, checkpoint)?;
+
+    let mut writer = CallSiteWriter::new(db, project, checkpoint).with_aliases(graph);
+    for call in &graph.calls {
+        writer.add_call(call)?;
+    }
+    for message in &graph.messages {
+        writer.add_message(message)?;
+    }
+    for access in &graph.field_accesses {
+        writer.add_field_access(access)?;
+    }
+    call_sites(&mut writer)?;
+    writer.finish(&mut result)?;
+
+    result.structs_imported = import_structs(db, project, graph, checkpoint)?;
+    result.function_locations_imported = import_function_locations(db, project, graph, checkpoint)?;
+    result.specs_imported = import_specs(db, project, graph, checkpoint)?;
+    result.types_imported = import_types(db, project, graph, checkpoint)?;
+    result.behaviours_imported = import_behaviours(db, project, graph, checkpoint)?;
+    result.docs_imported = import_docs(db, project, graph, checkpoint)?;
+    result.supervision_imported = import_supervision(db, project, graph, checkpoint)?;
+    result.aliases_imported = import_aliases(db, project, graph, checkpoint)?;
+
+    // Derived stats are rebuilt from the freshly imported calls and locations
+    aggregates::refresh_aggregates(db, project)?;
+
+    // First-seen times survive re-imports, so they are merged rather than replaced
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    timeline::update_function_history(db, project, now)?;
+
+    // Traversal indexes built before the import no longer match the calls relation
+    adjacency::clear_cache();
 
     Ok(result)
 }
 
+/// Writes calls, messages and field accesses a chunk at a time.
+///
+/// For imports that stream the call sites of a large graph instead of holding
+/// them in a [`CallGraph`]. Rows are written once a chunk is full and by
+/// [`CallSiteWriter::finish`], in the order they are added.
+pub struct CallSiteWriter<'a> {
+    db: &'a DbInstance,
+    checkpoint: Option<&'a Checkpoint>,
+    project_literal: String,
+    /// Aliases per caller module, expanded in callee modules
+    aliases: HashMap<String, HashMap<String, String>>,
+    calls: PendingRows,
+    messages: PendingRows,
+    field_accesses: PendingRows,
+}
+
+impl<'a> CallSiteWriter<'a> {
+    pub fn new(db: &'a DbInstance, project: &str, checkpoint: Option<&'a Checkpoint>) -> Self {
+        Self {
+            db,
+            checkpoint,
+            project_literal: literal(project),
+            aliases: HashMap::new(),
+            calls: PendingRows::new("calls", CALLS_COLUMNS, CALLS_SPEC),
+            messages: PendingRows::new("messages", MESSAGES_COLUMNS, MESSAGES_SPEC),
+            field_accesses: PendingRows::new("field_accesses", FIELD_ACCESSES_COLUMNS, FIELD_ACCESSES_SPEC),
+        }
+    }
+
+    /// Expand the aliases `graph` declares in the callee modules of calls
+    pub fn with_aliases(mut self, graph: &CallGraph) -> Self {
+        self.aliases = alias_map(graph);
+        self
+    }
+
+    pub fn add_call(&mut self, call: &Call) -> Result<(), Box<dyn Error>> {
+        let callee_module = match self.aliases.get(&call.caller.module) {
+            Some(aliases) => expand_alias(aliases, &call.callee.module),
+            None => Cow::Borrowed(call.callee.module.as_str()),
+        };
+        let row = call_row(&self.project_literal, call, &callee_module);
+        self.calls.push(self.db, self.checkpoint, row)
+    }
+
+    pub fn add_message(&mut self, message: &Message) -> Result<(), Box<dyn Error>> {
+        let row = message_row(&self.project_literal, message);
+        self.messages.push(self.db, self.checkpoint, row)
+    }
+
+    pub fn add_field_access(&mut self, access: &FieldAccess) -> Result<(), Box<dyn Error>> {
+        let row = field_access_row(&self.project_literal, access);
+        self.field_accesses.push(self.db, self.checkpoint, row)
+    }
+
+    /// Write the remaining rows and record the counts in `result`
+    pub fn finish(mut self, result: &mut ImportResult) -> Result<(), Box<dyn Error>> {
+        self.calls.flush(self.db, self.checkpoint)?;
+        self.messages.flush(self.db, self.checkpoint)?;
+        self.field_accesses.flush(self.db, self.checkpoint)?;
+        result.calls_imported = self.calls.written;
+        result.messages_imported = self.messages.written;
+        result.field_accesses_imported = self.field_accesses.written;
+        Ok(())
+    }
+}
+
+/// Rows of one relation waiting to fill a chunk
+struct PendingRows {
+    relation: &'static str,
+    columns: &'static str,
+    table_spec: &'static str,
+    rows: Vec<String>,
+    /// Chunks flushed so far, including those a resumed import skipped
+    chunks: usize,
+    written: usize,
+}
+
+impl PendingRows {
+    fn new(relation: &'static str, columns: &'static str, table_spec: &'static str) -> Self {
+        Self {
+            relation,
+            columns,
+            table_spec,
+            rows: Vec::new(),
+            chunks: 0,
+            written: 0,
+        }
+    }
+
+    fn push(&mut self, db: &DbInstance, checkpoint: Option<&Checkpoint>, row: String) -> Result<(), Box<dyn Error>> {
+        self.rows.push(row);
+        if self.rows.len() == IMPORT_CHUNK_SIZE {
+            self.flush(db, checkpoint)?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, db: &DbInstance, checkpoint: Option<&Checkpoint>) -> Result<(), Box<dyn Error>> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        if !checkpoint.is_some_and(|checkpoint| checkpoint.is_written(self.relation, self.chunks)) {
+            write_chunk(db, &self.rows, self.columns, self.table_spec, self.relation)?;
+            if let Some(checkpoint) = checkpoint {
+                checkpoint.record(db, self.relation, self.chunks + 1)?;
+            }
+        }
+        self.chunks += 1;
+        self.written += self.rows.len();
+        self.rows.clear();
+        Ok(())
+    }
+}
+
+/// Rows an import of the graph would write, per relation in import order.
+///
+/// Used by `import --dry-run`; the counts match those of [`ImportResult`].
+pub fn planned_rows(project: &str, graph: &CallGraph) -> Vec<(&'static str, usize)> {
+    vec![
+        ("modules", modules_rows(project, graph).len()),
+        ("functions", functions_rows(project, graph).len()),
+        ("calls", calls_rows(project, graph).len()),
+        ("messages", messages_rows(project, graph).len()),
+        ("struct_fields", structs_rows(project, graph).len()),
+        ("field_accesses", field_accesses_rows(project, graph).len()),
+        ("function_locations", function_locations_rows(project, graph).len()),
+        ("specs", specs_rows(project, graph).len()),
+        ("types", types_rows(project, graph).len()),
+        ("behaviours", behaviours_rows(project, graph).len()),
+        ("docs", docs_rows(project, graph).len()),
+        ("supervision", supervision_rows(project, graph).len()),
+        ("aliases", aliases_rows(project, graph).len()),
+    ]
+}
+
 /// Import a JSON string directly into the database.
 ///
 /// Convenience wrapper for tests that parses JSON and calls `import_graph`.
@@ -457,7 +1110,7 @@ pub fn import_json_str(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::{extract_string, open_db};
+    use crate::db::{extract_bool, extract_string, open_db};
     use tempfile::NamedTempFile;
 
     // Test deserialization with all new fields present
@@ -721,4 +1374,350 @@ mod tests {
         assert_eq!(types[1].0, "status");
         assert_eq!(types[1].1, r#"@type status() :: :pending | :active | :"special.status""#);
     }
+
+    // Test import of supervision child specs with defaults
+    #[test]
+    fn test_import_supervision() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {},
+            "calls": [],
+            "supervision": [
+                {
+                    "supervisor": "MyApp.Application",
+                    "strategy": "one_for_one",
+                    "children": [
+                        {"id": "MyApp.Repo", "module": "MyApp.Repo", "type": "worker"},
+                        {"id": "cache"}
+                    ]
+                }
+            ]
+        }"#;
+
+        let db_file = NamedTempFile::new().expect("Failed to create temp db file");
+        let db = open_db(db_file.path()).expect("Failed to open db");
+
+        let result = import_json_str(&db, json, "test_project").expect("Import should succeed");
+        assert_eq!(result.supervision_imported, 2);
+
+        let query = r#"
+            ?[child_id, child_module, child_type, restart, strategy] := *supervision{
+                project: "test_project",
+                supervisor: "MyApp.Application",
+                child_id,
+                child_module,
+                child_type,
+                restart,
+                strategy,
+                position: 1
+            }
+        "#;
+        let rows = run_query_no_params(&db, query).expect("Query should succeed");
+        assert_eq!(rows.rows.len(), 1);
+
+        let values: Vec<String> = rows.rows[0].iter().filter_map(extract_string).collect();
+        // Module defaults to the id, type to worker and restart to permanent
+        assert_eq!(values, ["cache", "cache", "worker", "permanent", "one_for_one"]);
+    }
+
+    #[test]
+    fn test_import_messages() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {},
+            "calls": [],
+            "messages": [
+                {
+                    "caller": {"module": "MyApp.Web", "function": "create", "file": "lib/web.ex", "line": 4},
+                    "callee": {"module": "MyApp.Worker", "function": "handle_cast", "arity": 2},
+                    "kind": "cast"
+                },
+                {
+                    "caller": {"module": "MyApp.Web", "function": "create", "file": "lib/web.ex", "line": 6},
+                    "callee": {"module": "MyApp.Feed", "function": "handle_info", "arity": 2},
+                    "kind": "broadcast",
+                    "topic": "users"
+                }
+            ]
+        }"#;
+
+        let db_file = NamedTempFile::new().expect("Failed to create temp db file");
+        let db = open_db(db_file.path()).expect("Failed to open db");
+
+        let result = import_json_str(&db, json, "test_project").expect("Import should succeed");
+        assert_eq!(result.messages_imported, 2);
+        assert_eq!(result.calls_imported, 0);
+
+        let query = r#"
+            ?[callee_module, kind, topic] := *messages{project: "test_project", callee_module, kind, topic}
+            :order callee_module
+        "#;
+        let rows = run_query_no_params(&db, query).expect("Query should succeed");
+        let values: Vec<Vec<String>> = rows
+            .rows
+            .iter()
+            .map(|row| row.iter().filter_map(extract_string).collect())
+            .collect();
+        assert_eq!(
+            values,
+            [["MyApp.Feed", "broadcast", "users"], ["MyApp.Worker", "cast", ""]]
+        );
+    }
+
+    #[test]
+    fn test_planned_rows_match_import() {
+        let json = r#"{
+            "structs": {"MyApp.User": {"fields": [{"default": "nil", "field": "name", "required": true}]}},
+            "function_locations": {},
+            "calls": [
+                {
+                    "caller": {"module": "MyApp.Web", "function": "create/1", "file": "lib/web.ex", "line": 4},
+                    "callee": {"module": "MyApp.Repo", "function": "insert", "arity": 1},
+                    "type": "remote"
+                }
+            ]
+        }"#;
+        let graph: CallGraph = serde_json::from_str(json).unwrap();
+
+        let planned = planned_rows("test_project", &graph);
+        let count = |relation: &str| planned.iter().find(|(name, _)| *name == relation).unwrap().1;
+        assert_eq!(planned.len(), 13);
+        assert_eq!((count("modules"), count("calls"), count("struct_fields")), (1, 1, 1));
+
+        let db = crate::db::open_mem_db();
+        let result = import_graph(&db, "test_project", &graph).unwrap();
+        assert_eq!(
+            (result.modules_imported, result.calls_imported, result.structs_imported),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_import_graph_with_call_sites() {
+        let graph: CallGraph = serde_json::from_str(r#"{"structs": {}, "function_locations": {}, "calls": []}"#).unwrap();
+        let calls: Vec<Call> = (0..IMPORT_CHUNK_SIZE + 1)
+            .map(|line| {
+                let json = format!(
+                    r#"{{"caller": {{"module": "A", "file": "a.ex", "line": {line}}}, "callee": {{"module": "B", "function": "f", "arity": 0}}, "type": "remote"}}"#
+                );
+                serde_json::from_str(&json).unwrap()
+            })
+            .collect();
+
+        let db = crate::db::open_mem_db();
+        let result = import_graph_with_call_sites(&db, "test_project", &graph, None, |writer| {
+            calls.iter().try_for_each(|call| writer.add_call(call))
+        })
+        .unwrap();
+        assert_eq!(result.calls_imported, IMPORT_CHUNK_SIZE + 1);
+
+        let rows = run_query_no_params(&db, "?[count(line)] := *calls{line}").unwrap();
+        assert_eq!(rows.rows[0][0], DataValue::from((IMPORT_CHUNK_SIZE + 1) as i64));
+    }
+
+    #[test]
+    fn test_import_docs() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {},
+            "calls": [],
+            "docs": {
+                "MyApp.Accounts": {
+                    "moduledoc": "Manages user accounts.",
+                    "functions": [
+                        {"name": "get_user", "arity": 1, "doc": "Fetches a \"user\" by id."},
+                        {"name": "list_users", "arity": 0, "doc": "  "}
+                    ]
+                },
+                "MyApp.Repo": {"moduledoc": null}
+            }
+        }"#;
+
+        let db_file = NamedTempFile::new().expect("Failed to create temp db file");
+        let db = open_db(db_file.path()).expect("Failed to open db");
+
+        // Blank docs are skipped
+        let result = import_json_str(&db, json, "test_project").expect("Import should succeed");
+        assert_eq!(result.docs_imported, 2);
+
+        let query = r#"
+            ?[module, name, kind, doc] := *docs{project: "test_project", module, name, kind, doc}
+            :order module, name
+        "#;
+        let rows = run_query_no_params(&db, query).expect("Query should succeed");
+        let values: Vec<Vec<String>> = rows
+            .rows
+            .iter()
+            .map(|row| row.iter().filter_map(extract_string).collect())
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ["MyApp.Accounts", "", "moduledoc", "Manages user accounts."],
+                ["MyApp.Accounts", "get_user", "doc", "Fetches a \"user\" by id."],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_module_apps() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "Billing.Invoice": {
+                    "total/1:3": {"name": "total", "arity": 1, "file": "apps/billing/lib/invoice.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 5}
+                },
+                "Web.Router": {
+                    "call/2:8": {"name": "call", "arity": 2, "file": "lib/router.ex", "kind": "def", "line": 8, "start_line": 8, "end_line": 9, "app": "web"}
+                },
+                "MyApp.Repo": {
+                    "get/2:2": {"name": "get", "arity": 2, "file": "lib/repo.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}
+                }
+            },
+            "calls": []
+        }"#;
+
+        let db_file = NamedTempFile::new().expect("Failed to create temp db file");
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        import_json_str(&db, json, "test_project").expect("Import should succeed");
+
+        let query = r#"
+            ?[name, app] := *modules{project: "test_project", name, app}
+            :order name
+        "#;
+        let rows = run_query_no_params(&db, query).expect("Query should succeed");
+        let values: Vec<Vec<String>> = rows
+            .rows
+            .iter()
+            .map(|row| row.iter().filter_map(extract_string).collect())
+            .collect();
+        assert_eq!(
+            values,
+            [["Billing.Invoice", "billing"], ["MyApp.Repo", ""], ["Web.Router", "web"]]
+        );
+    }
+
+    #[test]
+    fn test_import_module_is_external() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "Jason": {
+                    "encode/2:10": {"name": "encode", "arity": 2, "file": "deps/jason/lib/jason.ex", "kind": "def", "line": 10, "start_line": 10, "end_line": 14}
+                },
+                "MyApp.Repo": {
+                    "get/2:2": {"name": "get", "arity": 2, "file": "lib/repo.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}
+                }
+            },
+            "calls": [],
+            "specs": {
+                "MyApp.Types": []
+            }
+        }"#;
+
+        let db_file = NamedTempFile::new().expect("Failed to create temp db file");
+        let db = open_db(db_file.path()).expect("Failed to open db");
+        import_json_str(&db, json, "test_project").expect("Import should succeed");
+
+        let query = r#"
+            ?[name, is_external] := *modules{project: "test_project", name, is_external}
+            :order name
+        "#;
+        let rows = run_query_no_params(&db, query).expect("Query should succeed");
+        let values: Vec<(String, bool)> = rows
+            .rows
+            .iter()
+            .filter_map(|row| Some((extract_string(&row[0])?, extract_bool(&row[1], false))))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("Jason".to_string(), true),
+                ("MyApp.Repo".to_string(), false),
+                ("MyApp.Types".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_module_is_test() {
+        let json = r#"{
+            "structs": {},
+            "function_locations": {
+                "MyApp.RepoTest": {
+                    "test_get/1:4": {"name": "test_get", "arity": 1, "file": "test/my_app/repo_test.exs", "kind": "def", "line": 4, "start_line": 4, "end_line": 6}
+                },
+                "MyApp.Factory": {
+                    "build/1:2": {"name": "build", "arity": 1, "file": "apps/my_app/test/support/factory.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}
+                },
+                "MyApp.Fixtures": {
+                    "user/0:2": {"name": "user", "arity": 0, "file": "lib/my_app/fixtures.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}
+                },
+                "MyApp.Repo": {
+                    "get/2:2": {"name": "get", "arity": 2, "file": "lib/my_app/repo.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}
+                }
+            },
+            "calls": []
+        }"#;
+        let mut graph: CallGraph = serde_json::from_str(json).unwrap();
+        graph.test_modules.insert("MyApp.Fixtures".to_string());
+
+        let db = crate::db::open_mem_db();
+        import_graph(&db, "test_project", &graph).expect("Import should succeed");
+
+        let query = r#"
+            ?[name] := *modules{project: "test_project", name, is_test}, is_test == true
+            :order name
+        "#;
+        let rows = run_query_no_params(&db, query).expect("Query should succeed");
+        let tests: Vec<String> = rows.rows.iter().filter_map(|row| extract_string(&row[0])).collect();
+        assert_eq!(tests, ["MyApp.Factory", "MyApp.Fixtures", "MyApp.RepoTest"]);
+    }
+
+    #[test]
+    fn test_clear_file_data() {
+        let db = crate::test_utils::call_graph_db("test_project");
+        import_json_str(&db, crate::fixtures::CALL_GRAPH, "other").unwrap();
+        let count = |script: &str| run_query_no_params(&db, script).unwrap().rows[0][0].clone();
+        let functions = "?[count(name)] := *functions{project: 'test_project', module: 'MyApp.Accounts', name}";
+        let functions_before = count(functions);
+
+        clear_file_data(&db, "test_project", &["lib/my_app/accounts.ex".to_string()]).unwrap();
+
+        assert_eq!(
+            count("?[count(name)] := *function_locations{project: 'test_project', name, file: 'lib/my_app/accounts.ex'}"),
+            DataValue::from(0)
+        );
+        assert_eq!(
+            count("?[count(line)] := *calls{project: 'test_project', file: 'lib/my_app/accounts.ex', line}"),
+            DataValue::from(0)
+        );
+        // Other files, other projects and name-keyed relations are untouched
+        assert_eq!(
+            count("?[count(name)] := *function_locations{project: 'test_project', name, file: 'lib/my_app/repo.ex'}"),
+            DataValue::from(3)
+        );
+        assert_eq!(
+            count("?[count(name)] := *function_locations{project: 'other', name, file: 'lib/my_app/accounts.ex'}"),
+            DataValue::from(4)
+        );
+        assert_eq!(count(functions), functions_before);
+    }
+
+    #[test]
+    fn test_is_vendored_path() {
+        assert!(is_vendored_path("deps/jason/lib/jason.ex"));
+        assert!(is_vendored_path("/src/my_app/deps/plug/lib/plug.ex"));
+        assert!(is_vendored_path("_build/dev/lib/phoenix/lib/phoenix.ex"));
+        assert!(!is_vendored_path("lib/my_app/deps.ex"));
+        assert!(!is_vendored_path("lib/my_app.ex"));
+    }
+
+    #[test]
+    fn test_app_from_path() {
+        assert_eq!(app_from_path("apps/billing/lib/invoice.ex"), Some("billing"));
+        assert_eq!(app_from_path("/src/umbrella/apps/web/lib/router.ex"), Some("web"));
+        assert_eq!(app_from_path("lib/apps/helper.ex"), None);
+        assert_eq!(app_from_path("lib/my_app.ex"), None);
+    }
 }
@@ -0,0 +1,189 @@
+//! Import checkpoints.
+//!
+//! An import records how many chunks of each relation it has written, so one
+//! that was interrupted (Ctrl-C, out of memory) can resume from where it
+//! stopped instead of starting over. Rows are written in a deterministic
+//! order for that; a chunk written just before the interruption but not yet
+//! recorded is written again, which `:put` makes harmless. The checkpoint is
+//! removed once the import completes.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use cozo::{DataValue, DbInstance};
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, try_create_relation, Params};
+use crate::queries::schema::SCHEMA_IMPORT_CHECKPOINTS;
+
+/// Relation of the row recorded when an import starts
+const STARTED: &str = "";
+
+#[derive(Error, Debug)]
+pub enum CheckpointError {
+    #[error("No interrupted import of project '{project}' to resume")]
+    NotFound { project: String },
+
+    #[error("The interrupted import of project '{project}' was from {recorded}, not {requested}")]
+    SourceChanged {
+        project: String,
+        recorded: String,
+        requested: String,
+    },
+}
+
+/// Chunks an import of one project has written, per relation
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    project: String,
+    /// Identifies the imported file, e.g. its path and size
+    source: String,
+    /// Chunks written before the import was interrupted
+    written: BTreeMap<String, usize>,
+}
+
+impl Checkpoint {
+    /// Start recording a new import, dropping the checkpoint of an earlier one
+    pub fn start(db: &DbInstance, project: &str, source: &str) -> Result<Self, Box<dyn Error>> {
+        remove_checkpoint(db, project)?;
+        let checkpoint = Self {
+            project: project.to_string(),
+            source: source.to_string(),
+            written: BTreeMap::new(),
+        };
+        // A row with no relation marks an import interrupted before its first chunk
+        checkpoint.record(db, STARTED, 0)?;
+        Ok(checkpoint)
+    }
+
+    /// Resume the interrupted import of `source` into the project
+    pub fn resume(db: &DbInstance, project: &str, source: &str) -> Result<Self, Box<dyn Error>> {
+        try_create_relation(db, SCHEMA_IMPORT_CHECKPOINTS)?;
+
+        let rows = run_query(
+            db,
+            "?[relation, source, chunks] := *import_checkpoints{project: $project, relation, source, chunks}",
+            project_params(project),
+        )?;
+        let Some(recorded) = rows.rows.first().and_then(|row| extract_string(&row[1])) else {
+            return Err(CheckpointError::NotFound {
+                project: project.to_string(),
+            }
+            .into());
+        };
+        if recorded != source {
+            return Err(CheckpointError::SourceChanged {
+                project: project.to_string(),
+                recorded,
+                requested: source.to_string(),
+            }
+            .into());
+        }
+
+        let written = rows
+            .rows
+            .iter()
+            .filter_map(|row| Some((extract_string(&row[0])?, extract_i64(&row[2], 0) as usize)))
+            .filter(|(relation, _)| relation != STARTED)
+            .collect();
+        Ok(Self {
+            project: project.to_string(),
+            source: source.to_string(),
+            written,
+        })
+    }
+
+    /// Whether chunk `index` of `relation` was written before the interruption
+    pub fn is_written(&self, relation: &str, index: usize) -> bool {
+        index < self.written.get(relation).copied().unwrap_or(0)
+    }
+
+    /// Chunks of each relation written before the interruption
+    pub fn written(&self) -> &BTreeMap<String, usize> {
+        &self.written
+    }
+
+    /// Record that the first `chunks` chunks of `relation` are written
+    pub fn record(&self, db: &DbInstance, relation: &str, chunks: usize) -> Result<(), Box<dyn Error>> {
+        let mut params = project_params(&self.project);
+        params.insert("relation", DataValue::Str(relation.into()));
+        params.insert("source", DataValue::Str(self.source.as_str().into()));
+        params.insert("chunks", DataValue::from(chunks as i64));
+
+        run_query(
+            db,
+            r#"
+            ?[project, relation, source, chunks] <- [[$project, $relation, $source, $chunks]]
+            :put import_checkpoints {project, relation => source, chunks}
+            "#,
+            params,
+        )?;
+        Ok(())
+    }
+
+    /// Drop the checkpoint of a completed import
+    pub fn finish(self, db: &DbInstance) -> Result<(), Box<dyn Error>> {
+        remove_checkpoint(db, &self.project)
+    }
+}
+
+fn project_params(project: &str) -> Params {
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params
+}
+
+fn remove_checkpoint(db: &DbInstance, project: &str) -> Result<(), Box<dyn Error>> {
+    try_create_relation(db, SCHEMA_IMPORT_CHECKPOINTS)?;
+    run_query(
+        db,
+        r#"
+        ?[project, relation] := *import_checkpoints{project, relation}, project = $project
+        :rm import_checkpoints {project, relation}
+        "#,
+        project_params(project),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::open_mem_db;
+
+    #[test]
+    fn test_resume_reads_recorded_chunks() {
+        let db = open_mem_db();
+        let checkpoint = Checkpoint::start(&db, "app", "cg.json (10 bytes)").unwrap();
+        checkpoint.record(&db, "calls", 3).unwrap();
+
+        let resumed = Checkpoint::resume(&db, "app", "cg.json (10 bytes)").unwrap();
+        assert_eq!(resumed.written().len(), 1);
+        assert!(resumed.is_written("calls", 2));
+        assert!(!resumed.is_written("calls", 3));
+        assert!(!resumed.is_written("modules", 0));
+
+        let err = Checkpoint::resume(&db, "app", "cg.json (12 bytes)").unwrap_err();
+        assert!(err.to_string().contains("was from cg.json (10 bytes)"));
+    }
+
+    #[test]
+    fn test_resume_before_first_chunk() {
+        let db = open_mem_db();
+        Checkpoint::start(&db, "app", "cg.json").unwrap();
+
+        let resumed = Checkpoint::resume(&db, "app", "cg.json").unwrap();
+        assert!(resumed.written().is_empty());
+    }
+
+    #[test]
+    fn test_finish_removes_checkpoint() {
+        let db = open_mem_db();
+        let checkpoint = Checkpoint::start(&db, "app", "cg.json").unwrap();
+        checkpoint.record(&db, "calls", 1).unwrap();
+        checkpoint.finish(&db).unwrap();
+
+        let err = Checkpoint::resume(&db, "app", "cg.json").unwrap_err();
+        assert_eq!(err.to_string(), "No interrupted import of project 'app' to resume");
+    }
+}
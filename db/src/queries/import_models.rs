@@ -4,17 +4,84 @@
 //! call graph extractor during the import process.
 
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct CallGraph {
     pub structs: HashMap<String, StructDef>,
     pub function_locations: HashMap<String, HashMap<String, FunctionLocation>>,
     pub calls: Vec<Call>,
     #[serde(default)]
+    pub messages: Vec<Message>,
+    #[serde(default)]
+    pub field_accesses: Vec<FieldAccess>,
+    #[serde(default)]
     pub specs: HashMap<String, Vec<Spec>>,
     #[serde(default)]
     pub types: HashMap<String, Vec<TypeDef>>,
+    /// Behaviours each module declares with `@behaviour`
+    #[serde(default)]
+    pub behaviours: HashMap<String, Vec<String>>,
+    /// `@moduledoc` and `@doc` text per module
+    #[serde(default)]
+    pub docs: HashMap<String, ModuleDocs>,
+    #[serde(default)]
+    pub supervision: Vec<SupervisorDef>,
+    /// `alias`, `import` and `require` directives per module
+    #[serde(default)]
+    pub aliases: HashMap<String, Vec<AliasDirective>>,
+    /// Modules the importer classified as test code, besides those whose
+    /// source is under a test directory
+    #[serde(skip)]
+    pub test_modules: HashSet<String>,
+}
+
+/// Documentation of one module
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ModuleDocs {
+    /// `@moduledoc` text; absent for `@moduledoc false`
+    pub moduledoc: Option<String>,
+    pub functions: Vec<FunctionDoc>,
+}
+
+/// `@doc` text of one function
+#[derive(Debug, Deserialize)]
+pub struct FunctionDoc {
+    pub name: String,
+    pub arity: u32,
+    pub doc: String,
+}
+
+/// An `alias`, `import` or `require` directive of a module.
+///
+/// Format:
+/// ```json
+/// { "kind": "alias", "target": "MyApp.Repo", "as": "Repo", "line": 3 }
+/// ```
+///
+/// `as` is the name the target goes by in the module: the `as:` option, or
+/// the last segment of the target for a plain `alias`.
+#[derive(Debug, Deserialize)]
+pub struct AliasDirective {
+    /// "alias", "import" or "require"
+    pub kind: String,
+    pub target: String,
+    #[serde(rename = "as", default)]
+    pub alias: Option<String>,
+    #[serde(default)]
+    pub line: u32,
+}
+
+impl AliasDirective {
+    /// The short name the directive introduces, if any
+    pub fn short_name(&self) -> Option<&str> {
+        match (&self.alias, self.kind.as_str()) {
+            (Some(alias), _) => Some(alias),
+            (None, "alias") => self.target.rsplit('.').next(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -22,6 +89,23 @@ pub struct StructDef {
     pub fields: Vec<StructField>,
 }
 
+/// A read or write of a struct field inside a function body.
+#[derive(Debug, Deserialize)]
+pub struct FieldAccess {
+    pub caller: Caller,
+    /// Module defining the struct
+    #[serde(rename = "struct")]
+    pub struct_module: String,
+    pub field: String,
+    /// "read" (`user.email`, pattern match) or "write" (struct literal, update syntax)
+    #[serde(default = "default_access")]
+    pub access: String,
+}
+
+fn default_access() -> String {
+    "read".to_string()
+}
+
 #[derive(Debug, Deserialize)]
 pub struct StructField {
     pub default: String,
@@ -52,14 +136,38 @@ pub struct FunctionLocation {
     pub guard: Option<String>,
     pub source_sha: Option<String>,
     pub ast_sha: Option<String>,
+    /// MinHash signature of the clause's AST shingles, for similarity search
+    #[serde(default)]
+    pub ast_minhash: Vec<i64>,
     #[serde(default = "default_complexity")]
     pub complexity: u32,
     #[serde(default)]
     pub max_nesting_depth: u32,
+    /// SonarSource-style cognitive complexity
+    #[serde(default)]
+    pub cognitive_complexity: u32,
+    /// ABC size: sqrt(assignments² + branches² + conditions²)
+    #[serde(default)]
+    pub abc_score: f64,
+    /// Halstead volume: program length × log2(vocabulary)
+    #[serde(default)]
+    pub halstead_volume: f64,
     #[serde(default)]
     pub generated_by: Option<String>,
     #[serde(default)]
     pub macro_source: Option<String>,
+    /// OTP application owning the module (umbrella projects)
+    #[serde(default)]
+    pub app: Option<String>,
+    /// Message of the function's @deprecated attribute
+    #[serde(default)]
+    pub deprecated: Option<String>,
+    /// When the clause last changed, in Unix seconds (0 when unknown)
+    #[serde(default)]
+    pub modified_at: i64,
+    /// Revision that last changed the clause
+    #[serde(default)]
+    pub commit: Option<String>,
 }
 
 fn default_complexity() -> u32 {
@@ -72,6 +180,34 @@ pub struct Call {
     pub callee: Callee,
     #[serde(rename = "type")]
     pub call_type: String,
+    /// "call", "pipe" (`x |> f()`) or "capture" (`&f/1`)
+    #[serde(default = "default_call_kind")]
+    pub kind: String,
+    /// Inside a conditional branch or comprehension, so it may not run
+    #[serde(default)]
+    pub conditional: bool,
+}
+
+fn default_call_kind() -> String {
+    "call".to_string()
+}
+
+/// A message sent to a process: GenServer.call/cast, send, or a PubSub broadcast.
+///
+/// The callee is the callback that receives it, e.g. `handle_cast/2` of the
+/// GenServer module, or `handle_info/2` of a subscriber for broadcasts.
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub caller: Caller,
+    pub callee: Callee,
+    /// "call", "cast", "info" or "broadcast"
+    pub kind: String,
+    /// PubSub topic of a broadcast
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// Inside a conditional branch or comprehension, so it may not run
+    #[serde(default)]
+    pub conditional: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -143,3 +279,52 @@ pub struct TypeDef {
     pub params: Vec<String>,
     pub definition: String,
 }
+
+/// A supervisor and its child specs, from a runtime or static supervision dump.
+///
+/// Format:
+/// ```json
+/// {
+///   "supervisor": "MyApp.Application",
+///   "strategy": "one_for_one",
+///   "children": [
+///     { "id": "MyApp.Repo", "module": "MyApp.Repo", "type": "worker", "restart": "permanent" }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct SupervisorDef {
+    pub supervisor: String,
+    #[serde(default)]
+    pub strategy: Option<String>,
+    pub children: Vec<ChildSpec>,
+}
+
+/// A child spec within a supervisor, in start order.
+#[derive(Debug, Deserialize)]
+pub struct ChildSpec {
+    pub id: String,
+    /// Module started for this child (defaults to the id when it names a module)
+    #[serde(default)]
+    pub module: Option<String>,
+    /// "worker" or "supervisor"
+    #[serde(rename = "type", default = "default_child_type")]
+    pub child_type: String,
+    /// "permanent", "transient" or "temporary"
+    #[serde(default = "default_restart")]
+    pub restart: String,
+}
+
+fn default_child_type() -> String {
+    "worker".to_string()
+}
+
+fn default_restart() -> String {
+    "permanent".to_string()
+}
+
+/// A standalone supervision dump, imported alongside a call graph.
+#[derive(Debug, Deserialize)]
+pub struct SupervisionDump {
+    pub supervision: Vec<SupervisorDef>,
+}
@@ -5,7 +5,8 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder};
+use crate::query_builders::{app_condition, validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+use crate::where_filter::{ColumnType, WhereFilter};
 
 #[derive(Error, Debug)]
 pub enum LargeFunctionsError {
@@ -26,14 +27,30 @@ pub struct LargeFunction {
     pub generated_by: String,
 }
 
+/// Columns a `--where` filter may compare
+const WHERE_COLUMNS: &[(&str, ColumnType)] = &[
+    ("module", ColumnType::Text),
+    ("name", ColumnType::Text),
+    ("arity", ColumnType::Number),
+    ("start_line", ColumnType::Number),
+    ("end_line", ColumnType::Number),
+    ("lines", ColumnType::Number),
+    ("file", ColumnType::Text),
+    ("generated_by", ColumnType::Text),
+];
+
+#[allow(clippy::too_many_arguments)]
 pub fn find_large_functions(
     db: &cozo::DbInstance,
     min_lines: i64,
     module_pattern: Option<&str>,
+    app: Option<&str>,
     project: &str,
     use_regex: bool,
     include_generated: bool,
+    filter: Option<&WhereFilter>,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<LargeFunction>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[module_pattern])?;
 
@@ -42,12 +59,20 @@ pub fn find_large_functions(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+    let app_cond = app_condition("module", app.is_some());
 
     // Build optional generated filter
     let generated_filter = if include_generated {
         String::new()
     } else {
-        ", generated_by == \"\"".to_string()
+        settings.generated_condition("name", "file", "generated_by")
+    };
+
+    let mut params = Params::new();
+    let where_cond = match filter {
+        Some(filter) => filter.condition(WHERE_COLUMNS, &mut params)?,
+        None => String::new(),
     };
 
     let script = format!(
@@ -58,19 +83,26 @@ pub fn find_large_functions(
             lines = end_line - start_line + 1,
             lines >= $min_lines
             {module_cond}
+            {namespace_cond}
+            {app_cond}
             {generated_filter}
+            {where_cond}
 
         :order -lines, module, name
         :limit {limit}
         "#,
     );
 
-    let mut params = Params::new();
     params.insert("project", DataValue::Str(project.into()));
     params.insert("min_lines", DataValue::from(min_lines));
     if let Some(pattern) = module_pattern {
         params.insert("module_pattern", DataValue::Str(pattern.into()));
     }
+    if let Some(app) = app {
+        params.insert("app", DataValue::Str(app.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
 
     let rows = run_query(db, &script, params).map_err(|e| LargeFunctionsError::QueryFailed {
         message: e.to_string(),
@@ -0,0 +1,111 @@
+//! Cross-module call edges for architectural layer checking.
+//!
+//! Returns every call whose caller and callee live in different modules,
+//! together with the call site. The layering rules themselves are evaluated
+//! by the command, this query only supplies the raw edges.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum LayersError {
+    #[error("Layers query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A single call edge crossing a module boundary
+#[derive(Debug, Clone)]
+pub struct ModuleCallEdge {
+    pub caller_module: String,
+    pub caller_function: String,
+    pub callee_module: String,
+    pub callee_function: String,
+    pub callee_arity: i64,
+    pub file: String,
+    pub line: i64,
+}
+
+/// Find all call edges between distinct modules.
+///
+/// Struct construction edges (`callee_function == '%'`) are excluded.
+/// When `module_pattern` is given, only edges whose caller module matches are returned.
+pub fn find_module_call_edges(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<ModuleCallEdge>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("caller_module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("caller_module");
+
+    let script = format!(
+        r#"
+        ?[caller_module, caller_function, callee_module, callee_function, callee_arity, file, line] :=
+            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line}},
+            project == $project,
+            caller_module != callee_module,
+            callee_function != '%'
+            {module_cond}
+            {namespace_cond}
+        :order caller_module, callee_module, file, line
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| LayersError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 7 {
+            let Some(caller_module) = extract_string(&row[0]) else {
+                continue;
+            };
+            let Some(caller_function) = extract_string(&row[1]) else {
+                continue;
+            };
+            let Some(callee_module) = extract_string(&row[2]) else {
+                continue;
+            };
+            let Some(callee_function) = extract_string(&row[3]) else {
+                continue;
+            };
+            let callee_arity = extract_i64(&row[4], 0);
+            let Some(file) = extract_string(&row[5]) else {
+                continue;
+            };
+            let line = extract_i64(&row[6], 0);
+
+            results.push(ModuleCallEdge {
+                caller_module,
+                caller_function,
+                callee_module,
+                callee_function,
+                callee_arity,
+                file,
+                line,
+            });
+        }
+    }
+
+    Ok(results)
+}
@@ -5,7 +5,10 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, extract_string_or, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder};
+use crate::query_builders::{
+    target_condition, targets_param, validate_regex_patterns, ConditionBuilder, FunctionTarget,
+    OptionalConditionBuilder, QuerySettings, TARGET_RULE,
+};
 
 #[derive(Error, Debug)]
 pub enum LocationError {
@@ -29,6 +32,7 @@ pub struct FunctionLocation {
     pub guard: String,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn find_locations(
     db: &cozo::DbInstance,
     module_pattern: Option<&str>,
@@ -37,6 +41,7 @@ pub fn find_locations(
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<FunctionLocation>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[module_pattern, Some(function_pattern)])?;
 
@@ -46,6 +51,7 @@ pub fn find_locations(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     let arity_cond = if arity.is_some() {
         ", arity == $arity"
@@ -53,29 +59,55 @@ pub fn find_locations(
         ""
     };
 
-    let project_cond = ", project == $project";
+    let mut params = Params::new();
+    params.insert("function_pattern", DataValue::Str(function_pattern.into()));
+    if let Some(mod_pat) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(mod_pat.into()));
+    }
+    if let Some(a) = arity {
+        params.insert("arity", DataValue::Num(Num::Int(a)));
+    }
+
+    let filter = format!("{fn_cond}\n{module_cond}\n{namespace_cond}\n{arity_cond}");
+    settings.bind_namespaces(&filter, &mut params);
+    query_locations(db, "", &filter, params, project, limit)
+}
+
+/// Find the clauses of each of a list of exactly named functions, in one query
+pub fn find_locations_for_targets(
+    db: &cozo::DbInstance,
+    targets: &[FunctionTarget],
+    project: &str,
+    limit: u32,
+) -> Result<Vec<FunctionLocation>, Box<dyn Error>> {
+    let mut params = Params::new();
+    params.insert("targets", targets_param(targets));
+
+    let filter = target_condition("module", "name", "arity");
+    query_locations(db, TARGET_RULE, &filter, params, project, limit)
+}
 
+/// Run the locations query with `filter` restricting the matched clauses
+fn query_locations(
+    db: &cozo::DbInstance,
+    rules: &str,
+    filter: &str,
+    mut params: Params,
+    project: &str,
+    limit: u32,
+) -> Result<Vec<FunctionLocation>, Box<dyn Error>> {
     let script = format!(
         r#"
+        {rules}
         ?[project, file, line, start_line, end_line, module, kind, name, arity, pattern, guard] :=
             *function_locations{{project, module, name, arity, line, file, kind, start_line, end_line, pattern, guard}},
-            {fn_cond}
-            {module_cond}
-            {arity_cond}
-            {project_cond}
+            {filter},
+            project == $project
         :order module, name, arity, line
         :limit {limit}
         "#,
     );
 
-    let mut params = Params::new();
-    params.insert("function_pattern", DataValue::Str(function_pattern.into()));
-    if let Some(mod_pat) = module_pattern {
-        params.insert("module_pattern", DataValue::Str(mod_pat.into()));
-    }
-    if let Some(a) = arity {
-        params.insert("arity", DataValue::Num(Num::Int(a)));
-    }
     params.insert("project", DataValue::Str(project.into()));
 
     let rows = run_query(db, &script, params).map_err(|e| LocationError::QueryFailed {
@@ -5,7 +5,8 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+use crate::where_filter::{ColumnType, WhereFilter};
 
 #[derive(Error, Debug)]
 pub enum ManyClausesError {
@@ -26,6 +27,19 @@ pub struct ManyClauses {
     pub generated_by: String,
 }
 
+/// Columns a `--where` filter may compare
+const WHERE_COLUMNS: &[(&str, ColumnType)] = &[
+    ("module", ColumnType::Text),
+    ("name", ColumnType::Text),
+    ("arity", ColumnType::Number),
+    ("clauses", ColumnType::Number),
+    ("first_line", ColumnType::Number),
+    ("last_line", ColumnType::Number),
+    ("file", ColumnType::Text),
+    ("generated_by", ColumnType::Text),
+];
+
+#[allow(clippy::too_many_arguments)]
 pub fn find_many_clauses(
     db: &cozo::DbInstance,
     min_clauses: i64,
@@ -33,7 +47,9 @@ pub fn find_many_clauses(
     project: &str,
     use_regex: bool,
     include_generated: bool,
+    filter: Option<&WhereFilter>,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<ManyClauses>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[module_pattern])?;
 
@@ -42,12 +58,19 @@ pub fn find_many_clauses(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     // Build optional generated filter
     let generated_filter = if include_generated {
         String::new()
     } else {
-        ", generated_by == \"\"".to_string()
+        settings.generated_condition("name", "file", "generated_by")
+    };
+
+    let mut params = Params::new();
+    let where_cond = match filter {
+        Some(filter) => filter.condition(WHERE_COLUMNS, &mut params)?,
+        None => String::new(),
     };
 
     let script = format!(
@@ -56,24 +79,27 @@ pub fn find_many_clauses(
             *function_locations{{project, module, name, arity, line, start_line, end_line, file, generated_by}},
             project == $project
             {module_cond}
+            {namespace_cond}
             {generated_filter}
 
         ?[module, name, arity, clauses, first_line, last_line, file, generated_by] :=
             clause_counts[module, name, arity, clauses, first_line, last_line, file, generated_by],
             clauses >= $min_clauses
+            {where_cond}
 
         :order -clauses, module, name
         :limit {limit}
         "#,
     );
 
-    let mut params = Params::new();
     params.insert("project", DataValue::Str(project.into()));
     params.insert("min_clauses", DataValue::from(min_clauses));
     if let Some(pattern) = module_pattern {
         params.insert("module_pattern", DataValue::Str(pattern.into()));
     }
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| ManyClausesError::QueryFailed {
         message: e.to_string(),
     })?;
@@ -7,12 +7,21 @@
 //!
 //! ## Data Import
 //! - [`import`] - Import JSON call graph data into database relations
+//! - [`backup`] - Back up the database to a file and restore it
+//! - [`vacuum`] - Orphaned call cleanup, aggregate rebuilds and store compaction
+//! - [`cache`] - Command output cache invalidated by imports
+//! - [`import_checkpoints`] - Chunks written by an import, for resuming an interrupted one
+//! - [`graph_snapshot`] - A project's rows as one JSON document, for in-memory databases
 //!
 //! ## Basic Lookups
 //! - [`location`] - Find function definition locations by name
 //! - [`function`] - Get function signatures with type information
 //! - [`search`] - Full-text search across functions, specs, and types
+//! - [`docs`] - Full-text search over @moduledoc/@doc text
+//! - [`embeddings`] - Function embeddings and nearest-neighbour semantic search
 //! - [`file`] - List all functions defined in a module/file
+//! - [`export`] - Definitions and call sites for ctags/cscope exports
+//! - [`aliases`] - Full module names behind the short names of `alias` directives
 //!
 //! ## Call Graph Traversal
 //! - [`calls_from`] - Find all functions called by a given function
@@ -20,19 +29,43 @@
 //! - [`trace`] - Forward call trace to specified depth
 //! - [`reverse_trace`] - Backward call trace (who calls this, recursively)
 //! - [`path`] - Find call path between two functions
+//! - [`path_algorithms`] - Shortest and k-shortest path search over fetched edges
+//! - [`rename_impact`] - Definitions, call sites, specs and types affected by a rename
+//! - [`centrality`] - Function-level call edges for centrality ranking
+//! - [`adjacency`] - In-memory adjacency index for repeated traversals
+//! - [`changes`] - Function clauses by line range, for mapping a diff to functions
 //!
 //! ## Dependency Analysis
 //! - [`depends_on`] - Modules that a given module depends on
 //! - [`depended_by`] - Modules that depend on a given module
+//! - [`layers`] - Cross-module call edges for layer rule checking
+//! - [`coupling`] - Afferent/efferent coupling and abstractness counts per module
+//! - [`entry_points`] - Public functions with caller counts for entry point detection
+//! - [`supervision`] - OTP supervision tree edges and module footprints
+//! - [`apps`] - OTP application of each module and calls between umbrella apps
+//! - [`external_calls`] - Calls into modules outside the project (libraries, stdlib)
+//! - [`facade`] - Public functions of a namespace and their external callers
 //!
 //! ## Code Quality
 //! - [`unused`] - Find functions that are never called
+//! - [`deprecated`] - Call sites of functions marked @deprecated
 //! - [`hotspots`] - Find most-called functions (high fan-in)
+//! - [`aggregates`] - Materialized fan-in/fan-out and module totals maintained on import
+//! - [`summary`] - Module, function and call totals for the project summary
+//! - [`snapshots`] - Project metrics recorded per import for trend tracking
+//! - [`timeline`] - When functions were first seen and last modified
+//! - [`annotations`] - User notes, TODOs and deprecation markers on modules and functions
+//! - [`saved_queries`] - Named command invocations and CozoScripts stored by `query save`
+//! - [`similarity`] - AST MinHash fingerprints for structural similarity search
 //!
 //! ## Type System
 //! - [`specs`] - Query @spec and @callback definitions
+//! - [`callbacks`] - Behaviour callbacks and the modules implementing them
+//! - [`spec_coverage`] - Functions lacking a @spec and specs drifting from definitions
 //! - [`types`] - Query @type, @typep, and @opaque definitions
 //! - [`structs`] - Query struct definitions with field info
+//! - [`type_usage`] - Specs and type definitions referencing a type
+//! - [`field_accesses`] - Reads and writes of struct fields by function
 //!
 //! # Performance
 //!
@@ -47,35 +80,71 @@
 //! 2. Executes via `db.run_script()`
 //! 3. Extracts results into typed Rust structs
 //!
-//! Parameters are escaped using [`crate::db::escape_string`] to prevent injection.
+//! Values are bound as `$` parameters. Text that has to be part of the script
+//! goes through [`crate::sanitize::literal`], and the import rows are escaped
+//! using [`crate::db::escape_string_single`]; see [`crate::sanitize`].
 
 pub mod accepts;
+pub mod aliases;
+pub mod adjacency;
+pub mod annotations;
+pub mod aggregates;
+pub mod apps;
+pub mod backup;
+pub mod cache;
+pub mod callbacks;
 pub mod calls;
 pub mod calls_from;
 pub mod calls_to;
+pub mod centrality;
+pub mod changes;
 pub mod clusters;
 pub mod complexity;
+pub mod coupling;
 pub mod cycles;
 pub mod depended_by;
 pub mod dependencies;
 pub mod depends_on;
+pub mod deprecated;
+pub mod docs;
 pub mod duplicates;
+pub mod embeddings;
+pub mod entry_points;
+pub mod export;
+pub mod external_calls;
+pub mod facade;
+pub mod field_accesses;
 pub mod file;
 pub mod function;
+pub mod graph_snapshot;
 pub mod hotspots;
 pub mod import;
+pub mod import_checkpoints;
 pub mod import_models;
 pub mod large_functions;
+pub mod layers;
 pub mod location;
 pub mod many_clauses;
 pub mod path;
+pub mod path_algorithms;
+pub mod rename_impact;
 pub mod returns;
 pub mod reverse_trace;
+pub mod saved_queries;
 pub mod schema;
 pub mod search;
+pub mod similarity;
+pub mod snapshots;
+pub mod spec_coverage;
 pub mod specs;
 pub mod struct_usage;
 pub mod structs;
+pub mod summary;
+pub mod supervision;
+pub mod test_surface;
+pub mod timeline;
 pub mod trace;
+pub mod type_usage;
 pub mod types;
-pub mod unused;
\ No newline at end of file
+pub mod unused;
+pub mod vacuum;
\ No newline at end of file
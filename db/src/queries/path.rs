@@ -6,7 +6,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::OptionalConditionBuilder;
+use crate::query_builders::{call_edge_rules, OptionalConditionBuilder};
 
 #[derive(Error, Debug)]
 pub enum PathError {
@@ -25,12 +25,17 @@ pub struct PathStep {
     pub callee_arity: i64,
     pub file: String,
     pub line: i64,
+    /// Call sites between the same caller and callee
+    pub weight: i64,
 }
 
 /// A complete path from source to target
 #[derive(Debug, Clone, Serialize)]
 pub struct CallPath {
     pub steps: Vec<PathStep>,
+    /// Total edge weight, set when paths are ranked by weighted cost
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -45,12 +50,10 @@ pub fn find_paths(
     project: &str,
     max_depth: u32,
     limit: u32,
+    include_messages: bool,
+    min_weight: i64,
 ) -> Result<Vec<CallPath>, Box<dyn Error>> {
     // Build conditions using the ConditionBuilder utilities
-    let from_arity_cond = OptionalConditionBuilder::new("caller_arity", "from_arity")
-        .when_none("true")
-        .build(from_arity.is_some());
-
     let to_arity_cond = OptionalConditionBuilder::new("callee_arity", "to_arity")
         .when_none("true")
         .build(to_arity.is_some());
@@ -58,48 +61,27 @@ pub fn find_paths(
     // Simpler approach: trace forward from source to find all reachable calls,
     // then filter to paths that end at the target.
     // Returns edges on valid paths (may include multiple paths if they exist).
-    // Joins with function_locations to get caller arity for filtering.
     let script = format!(
         r#"
-        # Base case: direct calls from the source function
-        # Join with function_locations to get caller arity
-        # Uses starts_with to handle both "func" and "func/2" formats in caller_function
-        trace[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line] :=
-            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line}},
-            *function_locations{{project, module: caller_module, name: caller_name, arity: caller_arity}},
-            starts_with(caller_function, caller_name),
-            caller_module == $from_module,
-            starts_with(caller_function, $from_function),
-            {from_arity_cond},
-            project == $project,
-            depth = 1
-
-        # Recursive case: continue from callees we've found
-        trace[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line] :=
-            trace[prev_depth, _, _, prev_callee_module, prev_callee_function, _, _, _],
-            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line}},
-            caller_module == prev_callee_module,
-            starts_with(caller_function, prev_callee_function),
-            prev_depth < {max_depth},
-            depth = prev_depth + 1,
-            project == $project
+        {trace_rules}
 
         # Find the depth at which we reach the target
         target_depth[d] :=
-            trace[d, _, _, callee_module, callee_function, callee_arity, _, _],
+            trace[d, _, _, callee_module, callee_function, callee_arity, _, _, _],
             callee_module == $to_module,
             starts_with(callee_function, $to_function),
             {to_arity_cond}
 
         # Only return edges at depths <= minimum target depth (edges on valid paths)
-        ?[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line] :=
-            trace[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line],
+        ?[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight] :=
+            trace[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight],
             target_depth[min_d],
             depth <= min_d
 
         :order depth, caller_module, caller_function, callee_module, callee_function
         :limit {limit}
         "#,
+        trace_rules = trace_rules(from_arity.is_some(), max_depth, include_messages),
     );
 
     let mut params = Params::new();
@@ -114,46 +96,34 @@ pub fn find_paths(
         params.insert("to_arity", DataValue::from(a));
     }
     params.insert("project", DataValue::Str(project.into()));
+    params.insert("min_weight", DataValue::from(min_weight));
 
     let rows = run_query(db, &script, params).map_err(|e| PathError::QueryFailed {
         message: e.to_string(),
     })?;
 
     // Parse all edges from the query result
-    let mut edges: Vec<PathStep> = Vec::new();
+    let edges = parse_steps(rows.rows);
 
-    for row in rows.rows {
-        if row.len() >= 8 {
-            let depth = extract_i64(&row[0], 0);
-            let Some(caller_module) = extract_string(&row[1]) else { continue };
-            let Some(caller_function) = extract_string(&row[2]) else { continue };
-            let Some(callee_module) = extract_string(&row[3]) else { continue };
-            let Some(callee_function) = extract_string(&row[4]) else { continue };
-            let callee_arity = extract_i64(&row[5], 0);
-            let Some(file) = extract_string(&row[6]) else { continue };
-            let line = extract_i64(&row[7], 0);
-
-            edges.push(PathStep {
-                depth,
-                caller_module,
-                caller_function,
-                callee_module,
-                callee_function,
-                callee_arity,
-                file,
-                line,
-            });
-        }
-    }
+    Ok(build_paths(&edges, to_module, to_function, to_arity, limit))
+}
 
+/// Enumerate paths to the target over edges returned by the path query.
+pub(crate) fn build_paths(
+    edges: &[PathStep],
+    to_module: &str,
+    to_function: &str,
+    to_arity: Option<i64>,
+    limit: u32,
+) -> Vec<CallPath> {
     if edges.is_empty() {
-        return Ok(vec![]);
+        return vec![];
     }
 
     // Build adjacency list: (module, function) -> list of edges from that node
     // Key is (caller_module, caller_function), value is list of edges
     let mut adj: HashMap<(String, String), Vec<&PathStep>> = HashMap::new();
-    for edge in &edges {
+    for edge in edges {
         adj.entry((edge.caller_module.clone(), edge.caller_function.clone()))
             .or_default()
             .push(edge);
@@ -180,7 +150,199 @@ pub fn find_paths(
         );
     }
 
-    Ok(all_paths)
+    all_paths
+}
+
+/// Find every call edge reachable from the source function within `max_depth` hops.
+///
+/// Unlike [`find_paths`], edges are not cut off at the shortest target depth, so
+/// callers can run their own path search (shortest, k-shortest) over the result.
+/// Each call site is a separate edge. Like every path query, edges with fewer
+/// than `min_weight` call sites between their caller and callee are skipped.
+#[allow(clippy::too_many_arguments)]
+pub fn find_path_edges(
+    db: &cozo::DbInstance,
+    from_module: &str,
+    from_function: &str,
+    from_arity: Option<i64>,
+    project: &str,
+    max_depth: u32,
+    include_messages: bool,
+    min_weight: i64,
+) -> Result<Vec<PathStep>, Box<dyn Error>> {
+    let script = format!(
+        r#"
+        {trace_rules}
+
+        ?[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight] :=
+            trace[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight]
+
+        :order depth, caller_module, caller_function, callee_module, callee_function, file, line
+        "#,
+        trace_rules = trace_rules(from_arity.is_some(), max_depth, include_messages),
+    );
+
+    let mut params = Params::new();
+    params.insert("from_module", DataValue::Str(from_module.into()));
+    params.insert("from_function", DataValue::Str(from_function.into()));
+    if let Some(a) = from_arity {
+        params.insert("from_arity", DataValue::from(a));
+    }
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("min_weight", DataValue::from(min_weight));
+
+    let rows = run_query(db, &script, params).map_err(|e| PathError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    Ok(parse_steps(rows.rows))
+}
+
+/// Direction of a single-hop frontier expansion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontierDirection {
+    /// Calls made by the frontier functions
+    Outgoing,
+    /// Calls made to the frontier functions
+    Incoming,
+}
+
+/// Fetch the call edges leaving (or entering) a set of functions in one hop.
+///
+/// `nodes` are (module, function name) pairs. Outgoing edges match callers with or
+/// without an arity suffix. Used by bidirectional search to pull only frontier
+/// edges instead of running a full recursive trace. Returned steps have depth 0.
+pub fn find_frontier_edges(
+    db: &cozo::DbInstance,
+    project: &str,
+    nodes: &[(String, String)],
+    direction: FrontierDirection,
+    include_messages: bool,
+    min_weight: i64,
+) -> Result<Vec<PathStep>, Box<dyn Error>> {
+    if nodes.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let frontier_cond = match direction {
+        FrontierDirection::Outgoing => {
+            "frontier[caller_module, name],\n            \
+             (caller_function == name or starts_with(caller_function, concat(name, \"/\")))"
+        }
+        FrontierDirection::Incoming => "frontier[callee_module, callee_function]",
+    };
+
+    let script = format!(
+        r#"
+        frontier[module, name] <- $frontier
+        {edge_rules}
+
+        ?[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight] :=
+            {frontier_cond},
+            call_edge[caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight, _],
+            callee_function != '%',
+            weight >= $min_weight,
+            depth = 0
+
+        :order caller_module, caller_function, callee_module, callee_function, file, line
+        "#,
+        edge_rules = call_edge_rules(include_messages),
+    );
+
+    let frontier = nodes
+        .iter()
+        .map(|(module, name)| {
+            DataValue::List(vec![
+                DataValue::Str(module.as_str().into()),
+                DataValue::Str(name.as_str().into()),
+            ])
+        })
+        .collect();
+
+    let mut params = Params::new();
+    params.insert("frontier", DataValue::List(frontier));
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("min_weight", DataValue::from(min_weight));
+
+    let rows = run_query(db, &script, params).map_err(|e| PathError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    Ok(parse_steps(rows.rows))
+}
+
+/// Forward trace rules from `$from_module`/`$from_function`, shared by the path queries.
+///
+/// Joins with function_locations to get caller arity for filtering. Message
+/// edges are followed too when `include_messages` is set. Expects a
+/// `$min_weight` parameter.
+fn trace_rules(has_from_arity: bool, max_depth: u32, include_messages: bool) -> String {
+    let from_arity_cond = OptionalConditionBuilder::new("caller_arity", "from_arity")
+        .when_none("true")
+        .build(has_from_arity);
+
+    format!(
+        r#"
+        {edge_rules}
+
+        # Base case: direct calls from the source function
+        # Join with function_locations to get caller arity
+        # Uses starts_with to handle both "func" and "func/2" formats in caller_function
+        trace[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight] :=
+            call_edge[caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight, _],
+            *function_locations{{project, module: caller_module, name: caller_name, arity: caller_arity}},
+            starts_with(caller_function, caller_name),
+            caller_module == $from_module,
+            starts_with(caller_function, $from_function),
+            {from_arity_cond},
+            weight >= $min_weight,
+            project == $project,
+            depth = 1
+
+        # Recursive case: continue from callees we've found
+        trace[depth, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight] :=
+            trace[prev_depth, _, _, prev_callee_module, prev_callee_function, _, _, _, _],
+            call_edge[caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight, _],
+            caller_module == prev_callee_module,
+            starts_with(caller_function, prev_callee_function),
+            weight >= $min_weight,
+            prev_depth < {max_depth},
+            depth = prev_depth + 1
+        "#,
+        edge_rules = call_edge_rules(include_messages),
+    )
+}
+
+fn parse_steps(rows: Vec<Vec<DataValue>>) -> Vec<PathStep> {
+    let mut edges: Vec<PathStep> = Vec::new();
+
+    for row in rows {
+        if row.len() >= 9 {
+            let depth = extract_i64(&row[0], 0);
+            let Some(caller_module) = extract_string(&row[1]) else { continue };
+            let Some(caller_function) = extract_string(&row[2]) else { continue };
+            let Some(callee_module) = extract_string(&row[3]) else { continue };
+            let Some(callee_function) = extract_string(&row[4]) else { continue };
+            let callee_arity = extract_i64(&row[5], 0);
+            let Some(file) = extract_string(&row[6]) else { continue };
+            let line = extract_i64(&row[7], 0);
+            let weight = extract_i64(&row[8], 1);
+
+            edges.push(PathStep {
+                depth,
+                caller_module,
+                caller_function,
+                callee_module,
+                callee_function,
+                callee_arity,
+                file,
+                line,
+                weight,
+            });
+        }
+    }
+
+    edges
 }
 
 /// DFS to find all paths from current edge to target
@@ -206,6 +368,7 @@ fn dfs_find_paths(
         // Found a complete path
         all_paths.push(CallPath {
             steps: current_path.clone(),
+            cost: None,
         });
     } else if all_paths.len() < limit {
         // Continue searching from the callee
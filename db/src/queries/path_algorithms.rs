@@ -0,0 +1,569 @@
+//! Path search strategies over call edges fetched by [`super::path::find_path_edges`].
+//!
+//! The recursive Datalog trace finds every reachable edge cheaply, but ranking
+//! paths (shortest, k-shortest, weighted by call count) is awkward to express
+//! there. These functions post-process the edge list in Rust instead.
+//!
+//! [`bidirectional_shortest_path`] avoids the full trace altogether: it searches
+//! from both ends and pulls one hop of frontier edges at a time.
+//!
+//! Functions are identified by module and name; caller arity suffixes are ignored.
+//! Multiple call sites between the same two functions collapse into one edge whose
+//! call count is the number of sites, reported through its first call site.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::error::Error;
+
+use super::path::{CallPath, FrontierDirection, PathStep};
+use crate::db::strip_arity;
+
+/// The function a path search should end at
+#[derive(Debug, Clone, Copy)]
+pub struct PathTarget<'a> {
+    pub module: &'a str,
+    pub function: &'a str,
+    pub arity: Option<i64>,
+}
+
+impl PathTarget<'_> {
+    fn matches(&self, step: &PathStep) -> bool {
+        step.callee_module == self.module
+            && step.callee_function == self.function
+            && self.arity.is_none_or(|a| step.callee_arity == a)
+    }
+}
+
+const SOURCE: usize = 0;
+const TARGET: usize = 1;
+
+/// Call edges collapsed into a weighted graph over interned function nodes
+struct EdgeGraph<'a> {
+    /// Sorted outgoing (neighbor, cost) pairs per node
+    adjacency: Vec<Vec<(usize, f64)>>,
+    /// First call site of each (caller, callee) node pair
+    sites: HashMap<(usize, usize), &'a PathStep>,
+}
+
+impl<'a> EdgeGraph<'a> {
+    /// Build the graph. Depth-1 edges start at the source; edges whose callee
+    /// matches the target end at the target.
+    fn new(edges: &'a [PathStep], target: PathTarget, weighted: bool) -> Self {
+        let mut ids: HashMap<(&str, &str), usize> = HashMap::new();
+        let mut intern = |module: &'a str, function: &'a str| {
+            let next = ids.len() + 2;
+            *ids.entry((module, function)).or_insert(next)
+        };
+
+        let mut sites: HashMap<(usize, usize), &PathStep> = HashMap::new();
+        let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+        for edge in edges {
+            let from = if edge.depth == 1 {
+                SOURCE
+            } else {
+                intern(&edge.caller_module, strip_arity(&edge.caller_function))
+            };
+            let to = if target.matches(edge) {
+                TARGET
+            } else {
+                intern(&edge.callee_module, &edge.callee_function)
+            };
+            if from == to {
+                continue;
+            }
+
+            *counts.entry((from, to)).or_default() += 1;
+            sites
+                .entry((from, to))
+                .and_modify(|site| {
+                    if (&edge.file, edge.line) < (&site.file, site.line) {
+                        *site = edge;
+                    }
+                })
+                .or_insert(edge);
+        }
+
+        let mut adjacency = vec![Vec::new(); ids.len() + 2];
+        for (&(from, to), &count) in &counts {
+            let cost = if weighted { 1.0 / count as f64 } else { 1.0 };
+            adjacency[from].push((to, cost));
+        }
+        for neighbors in &mut adjacency {
+            neighbors.sort_by_key(|&(to, _)| to);
+        }
+
+        Self { adjacency, sites }
+    }
+
+    fn cost(&self, from: usize, to: usize) -> f64 {
+        self.adjacency[from]
+            .iter()
+            .find(|(n, _)| *n == to)
+            .map(|(_, c)| *c)
+            .unwrap_or(f64::INFINITY)
+    }
+
+    fn path_cost(&self, nodes: &[usize]) -> f64 {
+        nodes.windows(2).map(|w| self.cost(w[0], w[1])).sum()
+    }
+
+    /// Dijkstra from `start` to the target, avoiding blocked nodes and edges
+    fn shortest(
+        &self,
+        start: usize,
+        blocked_nodes: &HashSet<usize>,
+        blocked_edges: &HashSet<(usize, usize)>,
+    ) -> Option<Vec<usize>> {
+        let n = self.adjacency.len();
+        let mut dist = vec![f64::INFINITY; n];
+        let mut prev: Vec<Option<usize>> = vec![None; n];
+        let mut heap = BinaryHeap::new();
+
+        dist[start] = 0.0;
+        heap.push(State { cost: 0.0, node: start });
+
+        while let Some(State { cost, node }) = heap.pop() {
+            if node == TARGET {
+                break;
+            }
+            if cost > dist[node] {
+                continue;
+            }
+            for &(next, weight) in &self.adjacency[node] {
+                if blocked_nodes.contains(&next) || blocked_edges.contains(&(node, next)) {
+                    continue;
+                }
+                let candidate = cost + weight;
+                if candidate < dist[next] {
+                    dist[next] = candidate;
+                    prev[next] = Some(node);
+                    heap.push(State {
+                        cost: candidate,
+                        node: next,
+                    });
+                }
+            }
+        }
+
+        if dist[TARGET].is_infinite() {
+            return None;
+        }
+
+        let mut nodes = vec![TARGET];
+        let mut current = TARGET;
+        while let Some(p) = prev[current] {
+            nodes.push(p);
+            current = p;
+        }
+        nodes.reverse();
+        (nodes[0] == start).then_some(nodes)
+    }
+
+    fn to_call_path(&self, nodes: &[usize], weighted: bool) -> CallPath {
+        let steps = nodes
+            .windows(2)
+            .enumerate()
+            .map(|(i, w)| PathStep {
+                depth: i as i64 + 1,
+                ..self.sites[&(w[0], w[1])].clone()
+            })
+            .collect();
+
+        CallPath {
+            steps,
+            cost: weighted.then(|| self.path_cost(nodes)),
+        }
+    }
+}
+
+/// Heap entry ordered so that `BinaryHeap` pops the lowest cost first
+#[derive(Debug, PartialEq)]
+struct State {
+    cost: f64,
+    node: usize,
+}
+
+impl Eq for State {}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Find up to `k` loopless paths from the source to the target, cheapest first (Yen's algorithm).
+///
+/// Without `weighted` every call costs 1 (fewest hops). With `weighted` a call costs
+/// `1 / call sites`, preferring paths through heavily used call relationships.
+pub fn k_shortest_paths(edges: &[PathStep], target: PathTarget, k: usize, weighted: bool) -> Vec<CallPath> {
+    if k == 0 || edges.is_empty() {
+        return Vec::new();
+    }
+
+    let graph = EdgeGraph::new(edges, target, weighted);
+    let Some(first) = graph.shortest(SOURCE, &HashSet::new(), &HashSet::new()) else {
+        return Vec::new();
+    };
+
+    let mut found: Vec<Vec<usize>> = vec![first];
+    let mut candidates: Vec<(f64, Vec<usize>)> = Vec::new();
+
+    while found.len() < k {
+        let last = found.last().expect("at least one path").clone();
+
+        for i in 0..last.len() - 1 {
+            let spur = last[i];
+            let root = &last[..=i];
+
+            let blocked_edges: HashSet<(usize, usize)> = found
+                .iter()
+                .filter(|p| p.len() > i + 1 && &p[..=i] == root)
+                .map(|p| (p[i], p[i + 1]))
+                .collect();
+            let blocked_nodes: HashSet<usize> = root[..i].iter().copied().collect();
+
+            if let Some(spur_path) = graph.shortest(spur, &blocked_nodes, &blocked_edges) {
+                let mut candidate = root[..i].to_vec();
+                candidate.extend(spur_path);
+                if !found.contains(&candidate) && !candidates.iter().any(|(_, c)| *c == candidate) {
+                    candidates.push((graph.path_cost(&candidate), candidate));
+                }
+            }
+        }
+
+        // Cheapest candidate next; shorter, then lexicographically smaller paths win ties
+        let Some(best) = (0..candidates.len()).min_by(|&a, &b| {
+            let (ca, pa) = &candidates[a];
+            let (cb, pb) = &candidates[b];
+            ca.total_cmp(cb)
+                .then_with(|| pa.len().cmp(&pb.len()))
+                .then_with(|| pa.cmp(pb))
+        }) else {
+            break;
+        };
+        found.push(candidates.swap_remove(best).1);
+    }
+
+    found
+        .iter()
+        .map(|nodes| graph.to_call_path(nodes, weighted))
+        .collect()
+}
+
+type Node = (String, String);
+
+/// Shortest (fewest hops) path found by bidirectional breadth-first search.
+///
+/// The forward search starts from `start_edges`, the calls made by the source
+/// function, and the backward search from callers of the target. Each step
+/// expands the smaller frontier by one hop using `fetch`, so the edges loaded
+/// grow with both frontiers rather than with everything reachable from the source.
+/// Returns `None` when the searches do not meet within `max_depth` hops.
+pub fn bidirectional_shortest_path<F>(
+    source: (&str, &str),
+    start_edges: Vec<PathStep>,
+    target: PathTarget,
+    max_depth: u32,
+    mut fetch: F,
+) -> Result<Option<CallPath>, Box<dyn Error>>
+where
+    F: FnMut(&[Node], FrontierDirection) -> Result<Vec<PathStep>, Box<dyn Error>>,
+{
+    let source: Node = (source.0.to_string(), source.1.to_string());
+    // The target is matched per edge (it may be arity-specific), so it gets a
+    // key no real function can have
+    let target_key: Node = (String::new(), String::new());
+    let callee_node = |edge: &PathStep| -> Node {
+        if target.matches(edge) {
+            target_key.clone()
+        } else {
+            (edge.callee_module.clone(), edge.callee_function.clone())
+        }
+    };
+
+    let mut forward = Search::new(source.clone());
+    let mut backward = Search::new(target_key.clone());
+    let mut start_edges = Some(start_edges);
+    let mut meeting: Option<(u32, Node)> = None;
+
+    while meeting.is_none() && forward.depth + backward.depth < max_depth {
+        if forward.frontier.is_empty() || backward.frontier.is_empty() {
+            break;
+        }
+
+        if start_edges.is_some() || forward.frontier.len() <= backward.frontier.len() {
+            let edges = match start_edges.take() {
+                Some(edges) => edges,
+                None => fetch(&forward.frontier, FrontierDirection::Outgoing)?,
+            };
+            forward.depth += 1;
+            let mut next = Vec::new();
+            for edge in edges {
+                let from = if forward.depth == 1 {
+                    source.clone()
+                } else {
+                    caller_node(&edge)
+                };
+                if forward.dist.get(&from) != Some(&(forward.depth - 1)) {
+                    continue;
+                }
+                let to = callee_node(&edge);
+                if forward.dist.contains_key(&to) {
+                    continue;
+                }
+                forward.dist.insert(to.clone(), forward.depth);
+                forward.links.insert(to.clone(), edge);
+                if let Some(&d) = backward.dist.get(&to) {
+                    meeting = closer(meeting, forward.depth + d, &to);
+                }
+                next.push(to);
+            }
+            forward.frontier = next;
+        } else {
+            let query: Vec<Node> = backward
+                .frontier
+                .iter()
+                .map(|n| {
+                    if *n == target_key {
+                        (target.module.to_string(), target.function.to_string())
+                    } else {
+                        n.clone()
+                    }
+                })
+                .collect();
+            let edges = fetch(&query, FrontierDirection::Incoming)?;
+            backward.depth += 1;
+            let mut next = Vec::new();
+            for edge in edges {
+                let to = callee_node(&edge);
+                if backward.dist.get(&to) != Some(&(backward.depth - 1)) {
+                    continue;
+                }
+                let from = caller_node(&edge);
+                // The forward search owns the source: its arity filter was applied to start_edges
+                if from == source || backward.dist.contains_key(&from) {
+                    continue;
+                }
+                backward.dist.insert(from.clone(), backward.depth);
+                backward.links.insert(from.clone(), edge);
+                if let Some(&d) = forward.dist.get(&from) {
+                    meeting = closer(meeting, backward.depth + d, &from);
+                }
+                next.push(from);
+            }
+            backward.frontier = next;
+        }
+    }
+
+    let Some((_, middle)) = meeting else {
+        return Ok(None);
+    };
+
+    // Walk back to the source, then forward to the target
+    let mut steps = Vec::new();
+    let mut current = middle.clone();
+    while let Some(edge) = forward.links.get(&current) {
+        steps.push(edge.clone());
+        current = caller_node(edge);
+    }
+    steps.reverse();
+
+    let mut current = middle;
+    while let Some(edge) = backward.links.get(&current) {
+        steps.push(edge.clone());
+        current = callee_node(edge);
+    }
+
+    for (i, step) in steps.iter_mut().enumerate() {
+        step.depth = i as i64 + 1;
+    }
+    Ok(Some(CallPath { steps, cost: None }))
+}
+
+/// One side of a bidirectional search
+struct Search {
+    depth: u32,
+    frontier: Vec<Node>,
+    /// Hops from this side's starting point
+    dist: HashMap<Node, u32>,
+    /// Edge through which each node was discovered
+    links: HashMap<Node, PathStep>,
+}
+
+impl Search {
+    fn new(start: Node) -> Self {
+        Self {
+            depth: 0,
+            frontier: vec![start.clone()],
+            dist: HashMap::from([(start, 0)]),
+            links: HashMap::new(),
+        }
+    }
+}
+
+fn caller_node(edge: &PathStep) -> Node {
+    (
+        edge.caller_module.clone(),
+        strip_arity(&edge.caller_function).to_string(),
+    )
+}
+
+fn closer(best: Option<(u32, Node)>, length: u32, node: &Node) -> Option<(u32, Node)> {
+    match best {
+        Some((best_length, _)) if best_length <= length => best,
+        _ => Some((length, node.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(depth: i64, caller: (&str, &str), callee: (&str, &str), line: i64) -> PathStep {
+        PathStep {
+            depth,
+            caller_module: caller.0.to_string(),
+            caller_function: caller.1.to_string(),
+            callee_module: callee.0.to_string(),
+            callee_function: callee.1.to_string(),
+            callee_arity: 1,
+            file: format!("lib/{}.ex", caller.0.to_lowercase()),
+            line,
+            weight: 1,
+        }
+    }
+
+    const TARGET_FN: PathTarget<'static> = PathTarget {
+        module: "D",
+        function: "d",
+        arity: None,
+    };
+
+    /// A -> B -> D (one call site each) and A -> C -> C2 -> D with three sites on every hop
+    fn diamond() -> Vec<PathStep> {
+        let mut edges = vec![
+            step(1, ("A", "a"), ("B", "b"), 1),
+            step(2, ("B", "b"), ("D", "d"), 1),
+            step(1, ("A", "a"), ("C", "c"), 2),
+            step(2, ("C", "c"), ("C2", "c2"), 1),
+            step(3, ("C2", "c2"), ("D", "d"), 1),
+        ];
+        for line in 10..12 {
+            edges.push(step(1, ("A", "a"), ("C", "c"), line));
+            edges.push(step(2, ("C", "c"), ("C2", "c2"), line));
+            edges.push(step(3, ("C2", "c2"), ("D", "d"), line));
+        }
+        edges
+    }
+
+    #[test]
+    fn test_shortest_path_by_hops() {
+        let paths = k_shortest_paths(&diamond(), TARGET_FN, 1, false);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].steps.len(), 2);
+        assert_eq!(paths[0].steps[0].callee_module, "B");
+        assert_eq!(paths[0].steps[1].depth, 2);
+        assert!(paths[0].cost.is_none());
+    }
+
+    #[test]
+    fn test_shortest_path_weighted_by_call_count() {
+        let paths = k_shortest_paths(&diamond(), TARGET_FN, 1, true);
+        // Three hops at 1/3 each beat two hops at 1 each
+        assert_eq!(paths[0].steps.len(), 3);
+        assert_eq!(paths[0].steps[0].callee_module, "C");
+        // The first call site represents the edge
+        assert_eq!(paths[0].steps[0].line, 2);
+        assert!((paths[0].cost.unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_k_shortest_paths_ordered() {
+        let paths = k_shortest_paths(&diamond(), TARGET_FN, 5, false);
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].steps.len(), 2);
+        assert_eq!(paths[1].steps.len(), 3);
+    }
+
+    #[test]
+    fn test_no_path_to_target() {
+        let target = PathTarget {
+            module: "Z",
+            function: "z",
+            arity: None,
+        };
+        assert!(k_shortest_paths(&diamond(), target, 3, false).is_empty());
+    }
+
+    /// Serve frontier queries from an in-memory edge list
+    fn fetch_from(
+        edges: &[PathStep],
+    ) -> impl FnMut(&[Node], FrontierDirection) -> Result<Vec<PathStep>, Box<dyn Error>> + '_ {
+        move |nodes, direction| {
+            Ok(edges
+                .iter()
+                .filter(|e| match direction {
+                    FrontierDirection::Outgoing => nodes.contains(&caller_node(e)),
+                    FrontierDirection::Incoming => nodes
+                        .iter()
+                        .any(|(m, f)| e.callee_module == *m && e.callee_function == *f),
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_finds_shortest_path() {
+        let edges = diamond();
+        let start: Vec<PathStep> = edges.iter().filter(|e| e.caller_module == "A").cloned().collect();
+        let path = bidirectional_shortest_path(("A", "a"), start, TARGET_FN, 10, fetch_from(&edges))
+            .unwrap()
+            .expect("path exists");
+
+        assert_eq!(path.steps.len(), 2);
+        assert_eq!(path.steps[0].callee_module, "B");
+        assert_eq!(path.steps[1].caller_module, "B");
+        assert_eq!(path.steps[1].depth, 2);
+    }
+
+    #[test]
+    fn test_bidirectional_long_chain() {
+        // A -> N1 -> N2 -> ... -> N6 -> D
+        let mut edges = vec![step(1, ("A", "a"), ("N1", "n"), 1)];
+        for i in 1..6 {
+            edges.push(step(0, (&format!("N{}", i), "n"), (&format!("N{}", i + 1), "n"), 1));
+        }
+        edges.push(step(0, ("N6", "n"), ("D", "d"), 1));
+
+        let start = vec![edges[0].clone()];
+        let path = bidirectional_shortest_path(("A", "a"), start.clone(), TARGET_FN, 10, fetch_from(&edges))
+            .unwrap()
+            .expect("path exists");
+        assert_eq!(path.steps.len(), 7);
+        assert_eq!(path.steps[0].caller_module, "A");
+        assert_eq!(path.steps[6].callee_module, "D");
+
+        // Too shallow to connect the two searches
+        let none = bidirectional_shortest_path(("A", "a"), start, TARGET_FN, 6, fetch_from(&edges)).unwrap();
+        assert!(none.is_none());
+    }
+
+    #[test]
+    fn test_target_arity_filter() {
+        let target = PathTarget {
+            arity: Some(2),
+            ..TARGET_FN
+        };
+        assert!(k_shortest_paths(&diamond(), target, 3, false).is_empty());
+    }
+}
@@ -0,0 +1,403 @@
+//! Every source location that has to change when a module or function is renamed.
+//!
+//! For a function (optionally one arity) the references are its clause
+//! definitions, its call sites and its @spec/@callback. For a module they are
+//! the module definition, every remote call into it, and the specs and types of
+//! any module whose text mentions it. Specs and types carry no file of their
+//! own; they are placed in a file of their module, at their line.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, extract_string_or, run_query, Params};
+use crate::query_builders::{OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum RenameImpactError {
+    #[error("Rename impact query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// What a rename reference is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceKind {
+    /// Module definition or function clause head
+    Definition,
+    /// Call site
+    Call,
+    /// @spec or @callback mentioning the target
+    Spec,
+    /// @type, @typep or @opaque mentioning the target
+    Type,
+}
+
+impl ReferenceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReferenceKind::Definition => "definition",
+            ReferenceKind::Call => "call",
+            ReferenceKind::Spec => "spec",
+            ReferenceKind::Type => "type",
+        }
+    }
+}
+
+/// One place to edit for a rename
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RenameReference {
+    pub kind: ReferenceKind,
+    pub file: String,
+    /// 0 when the extractor did not record it (module definitions)
+    pub line: i64,
+    /// 0 when the extractor did not record it
+    pub column: i64,
+    /// Module containing the reference
+    pub module: String,
+    /// Function or type containing the reference, empty at module level
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub function: String,
+    /// What is referenced: the clause, call target, spec or type definition
+    pub text: String,
+}
+
+/// Find every reference to rename `module`, or `function` (of `arity`) in it.
+///
+/// Names are matched exactly. Results are ordered by file, line and column
+/// unless [`QuerySettings::sorted`] is off.
+pub fn find_rename_references(
+    db: &cozo::DbInstance,
+    project: &str,
+    module: &str,
+    function: Option<&str>,
+    arity: Option<i64>,
+    settings: &QuerySettings,
+) -> Result<Vec<RenameReference>, Box<dyn Error>> {
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("module", DataValue::Str(module.into()));
+
+    let mut references = match function {
+        Some(function) => {
+            params.insert("function", DataValue::Str(function.into()));
+            if let Some(arity) = arity {
+                params.insert("arity", DataValue::from(arity));
+            }
+            let mut references = function_definitions(db, &params, arity.is_some())?;
+            references.extend(function_calls(db, &params, arity.is_some())?);
+            references.extend(function_specs(db, &params, arity.is_some())?);
+            references
+        }
+        None => {
+            // Mentions of the module name that are not part of a longer name
+            let pattern = format!(r"(^|[^\w.]){}($|[^\w])", regex::escape(module));
+            let mut mention_params = Params::new();
+            mention_params.insert("project", DataValue::Str(project.into()));
+            mention_params.insert("mention", DataValue::Str(pattern.into()));
+            let mut references = module_definition(db, &params)?;
+            references.extend(module_calls(db, &params)?);
+            references.extend(module_mentions(db, &mention_params)?);
+            references
+        }
+    };
+
+    if settings.sorted {
+        references.sort_by(|a, b| {
+            (&a.file, a.line, a.column, a.kind).cmp(&(&b.file, b.line, b.column, b.kind))
+        });
+    }
+    Ok(references)
+}
+
+fn run(db: &cozo::DbInstance, script: &str, params: &Params) -> Result<Vec<Vec<DataValue>>, Box<dyn Error>> {
+    let rows = run_query(db, script, params.clone()).map_err(|e| RenameImpactError::QueryFailed {
+        message: e.to_string(),
+    })?;
+    Ok(rows.rows)
+}
+
+fn arity_condition(field: &'static str, has_arity: bool) -> String {
+    OptionalConditionBuilder::new(field, "arity")
+        .with_leading_comma()
+        .build(has_arity)
+}
+
+/// Clause heads of the function
+fn function_definitions(
+    db: &cozo::DbInstance,
+    params: &Params,
+    has_arity: bool,
+) -> Result<Vec<RenameReference>, Box<dyn Error>> {
+    let script = format!(
+        r#"
+        ?[file, line, column, name, arity, kind] :=
+            *function_locations{{project, module, name, arity, line, file, column, kind}},
+            project == $project,
+            module == $module,
+            name == $function
+            {arity_cond}
+        "#,
+        arity_cond = arity_condition("arity", has_arity),
+    );
+
+    let module = params.get("module").and_then(extract_string).unwrap_or_default();
+    Ok(run(db, &script, params)?
+        .iter()
+        .filter(|row| row.len() >= 6)
+        .map(|row| {
+            let name = extract_string_or(&row[3], "");
+            let arity = extract_i64(&row[4], 0);
+            RenameReference {
+                kind: ReferenceKind::Definition,
+                file: extract_string_or(&row[0], ""),
+                line: extract_i64(&row[1], 0),
+                column: extract_i64(&row[2], 0),
+                module: module.clone(),
+                text: format!("{} {}/{}", extract_string_or(&row[5], "def"), name, arity),
+                function: name,
+            }
+        })
+        .collect())
+}
+
+/// Call sites of the function, local calls included
+fn function_calls(
+    db: &cozo::DbInstance,
+    params: &Params,
+    has_arity: bool,
+) -> Result<Vec<RenameReference>, Box<dyn Error>> {
+    let script = format!(
+        r#"
+        ?[file, line, column, caller_module, caller_function, callee_module, callee_function, callee_arity] :=
+            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column}},
+            project == $project,
+            callee_module == $module,
+            callee_function == $function
+            {arity_cond}
+        "#,
+        arity_cond = arity_condition("callee_arity", has_arity),
+    );
+    Ok(run(db, &script, params)?.iter().filter_map(|row| call_reference(row)).collect())
+}
+
+/// The function's @spec or @callback
+fn function_specs(
+    db: &cozo::DbInstance,
+    params: &Params,
+    has_arity: bool,
+) -> Result<Vec<RenameReference>, Box<dyn Error>> {
+    let script = format!(
+        r#"
+        module_file[module, choice(file)] :=
+            *function_locations{{project, module, file}},
+            project == $project
+        ?[file, line, module, name, full] :=
+            *specs{{project, module, name, arity, line, full}},
+            module_file[module, file],
+            project == $project,
+            module == $module,
+            name == $function
+            {arity_cond}
+        "#,
+        arity_cond = arity_condition("arity", has_arity),
+    );
+    Ok(run(db, &script, params)?
+        .iter()
+        .filter_map(|row| located_reference(ReferenceKind::Spec, row))
+        .collect())
+}
+
+/// The module's definition, in each file holding its functions
+fn module_definition(db: &cozo::DbInstance, params: &Params) -> Result<Vec<RenameReference>, Box<dyn Error>> {
+    let script = r#"
+        ?[file] :=
+            *function_locations{project, module, file},
+            project == $project,
+            module == $module
+        "#;
+    let module = params.get("module").and_then(extract_string).unwrap_or_default();
+    Ok(run(db, script, params)?
+        .iter()
+        .filter_map(|row| row.first().and_then(extract_string))
+        .map(|file| RenameReference {
+            kind: ReferenceKind::Definition,
+            file,
+            line: 0,
+            column: 0,
+            module: module.clone(),
+            function: String::new(),
+            text: format!("defmodule {}", module),
+        })
+        .collect())
+}
+
+/// Remote calls into any function of the module
+fn module_calls(db: &cozo::DbInstance, params: &Params) -> Result<Vec<RenameReference>, Box<dyn Error>> {
+    let script = r#"
+        ?[file, line, column, caller_module, caller_function, callee_module, callee_function, callee_arity] :=
+            *calls{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column, call_type},
+            project == $project,
+            callee_module == $module,
+            call_type != "local"
+        "#;
+    Ok(run(db, script, params)?.iter().filter_map(|row| call_reference(row)).collect())
+}
+
+/// Specs and types, in any module, whose text mentions the module
+fn module_mentions(db: &cozo::DbInstance, params: &Params) -> Result<Vec<RenameReference>, Box<dyn Error>> {
+    let specs = r#"
+        module_file[module, choice(file)] :=
+            *function_locations{project, module, file},
+            project == $project
+        ?[file, line, module, name, full] :=
+            *specs{project, module, name, line, full},
+            module_file[module, file],
+            project == $project,
+            regex_matches(full, $mention)
+        "#;
+    let types = r#"
+        module_file[module, choice(file)] :=
+            *function_locations{project, module, file},
+            project == $project
+        ?[file, line, module, name, definition] :=
+            *types{project, module, name, line, definition},
+            module_file[module, file],
+            project == $project,
+            regex_matches(definition, $mention)
+        "#;
+
+    let mut references: Vec<RenameReference> = run(db, specs, params)?
+        .iter()
+        .filter_map(|row| located_reference(ReferenceKind::Spec, row))
+        .collect();
+    references.extend(
+        run(db, types, params)?
+            .iter()
+            .filter_map(|row| located_reference(ReferenceKind::Type, row)),
+    );
+    Ok(references)
+}
+
+/// Reference from a `[file, line, column, caller_module, caller_function, callee_module, callee_function, callee_arity]` row
+fn call_reference(row: &[DataValue]) -> Option<RenameReference> {
+    if row.len() < 8 {
+        return None;
+    }
+    Some(RenameReference {
+        kind: ReferenceKind::Call,
+        file: extract_string_or(&row[0], ""),
+        line: extract_i64(&row[1], 0),
+        column: extract_i64(&row[2], 0),
+        module: extract_string(&row[3])?,
+        function: extract_string_or(&row[4], ""),
+        text: format!(
+            "{}.{}/{}",
+            extract_string_or(&row[5], ""),
+            extract_string_or(&row[6], ""),
+            extract_i64(&row[7], 0)
+        ),
+    })
+}
+
+/// Reference from a `[file, line, module, name, text]` row of a spec or type
+fn located_reference(kind: ReferenceKind, row: &[DataValue]) -> Option<RenameReference> {
+    if row.len() < 5 {
+        return None;
+    }
+    Some(RenameReference {
+        kind,
+        file: extract_string_or(&row[0], ""),
+        line: extract_i64(&row[1], 0),
+        column: 0,
+        module: extract_string(&row[2])?,
+        function: extract_string_or(&row[3], ""),
+        text: extract_string_or(&row[4], ""),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RENAME_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Accounts": {
+                "get_user/1:10": {"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "column": 7, "kind": "def",
+                    "line": 10, "start_line": 10, "end_line": 12},
+                "get_user/2:14": {"name": "get_user", "arity": 2, "file": "lib/accounts.ex", "column": 7, "kind": "def",
+                    "line": 14, "start_line": 14, "end_line": 16},
+                "list/0:20": {"name": "list", "arity": 0, "file": "lib/accounts.ex", "column": 7, "kind": "def",
+                    "line": 20, "start_line": 20, "end_line": 22}
+            },
+            "MyApp.Web": {
+                "show/2:4": {"name": "show", "arity": 2, "file": "lib/web.ex", "column": 7, "kind": "def",
+                    "line": 4, "start_line": 4, "end_line": 12}
+            }
+        },
+        "calls": [
+            {"caller": {"module": "MyApp.Web", "function": "show", "file": "lib/web.ex", "line": 8, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Accounts", "function": "get_user", "arity": 1}},
+            {"caller": {"module": "MyApp.Accounts", "function": "list", "file": "lib/accounts.ex", "line": 21, "column": 5}, "type": "local", "callee": {"module": "MyApp.Accounts", "function": "get_user", "arity": 2}}
+        ],
+        "specs": {
+            "MyApp.Accounts": [
+                {"name": "get_user", "arity": 1, "kind": "spec", "line": 9, "clauses": [{"input_strings": ["integer()"], "return_strings": ["MyApp.Accounts.t()"], "full": "@spec get_user(integer()) :: MyApp.Accounts.t()"}]}
+            ],
+            "MyApp.Web": [
+                {"name": "show", "arity": 2, "kind": "spec", "line": 3, "clauses": [{"input_strings": ["map()", "map()"], "return_strings": ["MyApp.Accounts.t()"], "full": "@spec show(map(), map()) :: MyApp.Accounts.t()"}]}
+            ]
+        },
+        "types": {
+            "MyApp.Web": [
+                {"name": "user", "kind": "type", "params": [], "line": 2, "definition": "@type user :: MyApp.Accounts.t()"},
+                {"name": "other", "kind": "type", "params": [], "line": 3, "definition": "@type other :: MyApp.AccountsWeb.t()"}
+            ]
+        }
+    }"#;
+
+    fn locations(references: &[RenameReference]) -> Vec<String> {
+        references
+            .iter()
+            .map(|r| format!("{}:{} {}", r.file, r.line, r.kind.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_function_rename_references() {
+        let db = crate::test_utils::setup_test_db(RENAME_JSON, "default");
+
+        let references = find_rename_references(&db, "default", "MyApp.Accounts", Some("get_user"), Some(1), &QuerySettings::default())
+            .expect("Query should succeed");
+        assert_eq!(
+            locations(&references),
+            ["lib/accounts.ex:9 spec", "lib/accounts.ex:10 definition", "lib/web.ex:8 call"]
+        );
+        assert_eq!(references[1].text, "def get_user/1");
+        assert_eq!(references[2].text, "MyApp.Accounts.get_user/1");
+
+        let all_arities = find_rename_references(&db, "default", "MyApp.Accounts", Some("get_user"), None, &QuerySettings::default())
+            .expect("Query should succeed");
+        assert_eq!(all_arities.len(), 5);
+    }
+
+    #[test]
+    fn test_module_rename_references() {
+        let db = crate::test_utils::setup_test_db(RENAME_JSON, "default");
+
+        let references = find_rename_references(&db, "default", "MyApp.Accounts", None, None, &QuerySettings::default())
+            .expect("Query should succeed");
+        assert_eq!(
+            locations(&references),
+            [
+                "lib/accounts.ex:0 definition",
+                "lib/accounts.ex:9 spec",
+                "lib/web.ex:2 type",
+                "lib/web.ex:3 spec",
+                "lib/web.ex:8 call",
+            ]
+        );
+    }
+}
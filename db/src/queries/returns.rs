@@ -1,17 +1,9 @@
 use std::error::Error;
 
-use cozo::DataValue;
 use serde::Serialize;
-use thiserror::Error;
 
-use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder};
-
-#[derive(Error, Debug)]
-pub enum ReturnsError {
-    #[error("Returns query failed: {message}")]
-    QueryFailed { message: String },
-}
+use crate::queries::accepts::find_signatures;
+use crate::query_builders::QuerySettings;
 
 /// A function with its return type specification
 #[derive(Debug, Clone, Serialize)]
@@ -20,81 +12,65 @@ pub struct ReturnEntry {
     pub module: String,
     pub name: String,
     pub arity: i64,
+    pub inputs_string: String,
     pub return_string: String,
     pub line: i64,
 }
 
+/// Find functions whose return type matches `pattern`.
+///
+/// Patterns are matched like in [`find_accepts`](super::accepts::find_accepts):
+/// as a [`TypePattern`](crate::type_pattern::TypePattern) against the return
+/// type, or as a regex over its text. With `accepts_pattern`, one of the
+/// parameters has to match it as well.
+#[allow(clippy::too_many_arguments)]
 pub fn find_returns(
     db: &cozo::DbInstance,
     pattern: &str,
+    accepts_pattern: Option<&str>,
     project: &str,
     use_regex: bool,
     module_pattern: Option<&str>,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<ReturnEntry>, Box<dyn Error>> {
-    validate_regex_patterns(use_regex, &[Some(pattern), module_pattern])?;
-
-    // Build conditions using query builders
-    let pattern_cond = ConditionBuilder::new("return_string", "pattern").build(use_regex);
-    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
-        .with_leading_comma()
-        .with_regex()
-        .build_with_regex(module_pattern.is_some(), use_regex);
-
-    let script = format!(
-        r#"
-        ?[project, module, name, arity, return_string, line] :=
-            *specs{{project, module, name, arity, return_string, line}},
-            project == $project,
-            {pattern_cond}
-            {module_cond}
-
-        :order module, name, arity
-        :limit {limit}
-        "#,
-    );
-
-    let mut params = Params::new();
-    params.insert("pattern", DataValue::Str(pattern.into()));
-    params.insert("project", DataValue::Str(project.into()));
-
-    if let Some(mod_pat) = module_pattern {
-        params.insert(
-            "module_pattern",
-            DataValue::Str(mod_pat.into()),
-        );
-    }
+    let entries = find_signatures(
+        db,
+        accepts_pattern,
+        Some(pattern),
+        project,
+        use_regex,
+        module_pattern,
+        limit,
+        settings,
+    )?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| ReturnEntry {
+            project: entry.project,
+            module: entry.module,
+            name: entry.name,
+            arity: entry.arity,
+            inputs_string: entry.inputs_string,
+            return_string: entry.return_string,
+            line: entry.line,
+        })
+        .collect())
+}
 
-    let rows = run_query(db, &script, params).map_err(|e| ReturnsError::QueryFailed {
-        message: e.to_string(),
-    })?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let mut results = Vec::new();
-    for row in rows.rows {
-        if row.len() >= 6 {
-            let Some(project) = extract_string(&row[0]) else {
-                continue;
-            };
-            let Some(module) = extract_string(&row[1]) else {
-                continue;
-            };
-            let Some(name) = extract_string(&row[2]) else {
-                continue;
-            };
-            let arity = extract_i64(&row[3], 0);
-            let return_string = extract_string(&row[4]).unwrap_or_default();
-            let line = extract_i64(&row[5], 0);
+    #[test]
+    fn test_find_returns_union_pattern() {
+        let db = crate::test_utils::call_graph_db("default");
+        let entries = find_returns(&db, "{:ok, _} | {:error, _}", None, "default", false, None, 100, &QuerySettings::default()).unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["get_user"]);
+        assert_eq!(entries[0].inputs_string, "integer()");
 
-            results.push(ReturnEntry {
-                project,
-                module,
-                name,
-                arity,
-                return_string,
-                line,
-            });
-        }
+        let entries = find_returns(&db, "[User.t]", Some("integer"), "default", false, None, 100, &QuerySettings::default()).unwrap();
+        assert!(entries.is_empty());
     }
-
-    Ok(results)
 }
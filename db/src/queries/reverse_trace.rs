@@ -5,7 +5,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, extract_string_or, run_query, Params};
-use crate::query_builders::{ConditionBuilder, OptionalConditionBuilder};
+use crate::query_builders::{certain_condition, ConditionBuilder, OptionalConditionBuilder, QuerySettings};
 
 #[derive(Error, Debug)]
 pub enum ReverseTraceError {
@@ -30,6 +30,11 @@ pub struct ReverseTraceStep {
     pub line: i64,
 }
 
+/// Trace call chains backwards to the matching functions.
+///
+/// With `certain_only`, calls in a conditional branch or comprehension are
+/// not followed.
+#[allow(clippy::too_many_arguments)]
 pub fn reverse_trace_calls(
     db: &cozo::DbInstance,
     module_pattern: &str,
@@ -39,14 +44,25 @@ pub fn reverse_trace_calls(
     use_regex: bool,
     max_depth: u32,
     limit: u32,
+    certain_only: bool,
+    settings: &QuerySettings,
 ) -> Result<Vec<ReverseTraceStep>, Box<dyn Error>> {
     // Build the starting conditions for the recursive query using helpers
     // For reverse trace, we match on the callee (target)
     let module_cond = ConditionBuilder::new("callee_module", "module_pattern").build(use_regex);
+    let namespace_cond = settings.namespace_condition("callee_module");
     let function_cond = ConditionBuilder::new("callee_function", "function_pattern").build(use_regex);
     let arity_cond = OptionalConditionBuilder::new("callee_arity", "arity")
         .when_none("true")
         .build(arity.is_some());
+    // With --only-project, chains stop at stdlib and dependency callers, and
+    // with --exclude-tests at test callers
+    let scope_cond = format!(
+        "{}{}",
+        settings.project_scope_condition("caller_module"),
+        settings.test_scope_condition("caller_module")
+    );
+    let certain_cond = certain_condition(certain_only);
 
     // Recursive query to trace call chains backwards, joined with function_locations for caller metadata
     // Base case: calls TO the target function
@@ -55,33 +71,35 @@ pub fn reverse_trace_calls(
         r#"
         # Base case: calls to the target function, joined with function_locations
         trace[depth, caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line] :=
-            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line}},
+            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line, conditional}},
             *function_locations{{project, module: caller_module, name: caller_name, arity: caller_arity, kind: caller_kind, start_line: caller_start_line, end_line: caller_end_line}},
             starts_with(caller_function, caller_name),
             call_line >= caller_start_line,
-            call_line <= caller_end_line,
-            {module_cond},
+            call_line <= caller_end_line{certain_cond},
+            {module_cond}{namespace_cond},
             {function_cond},
             project == $project,
             {arity_cond},
             depth = 1
+            {scope_cond}
 
         # Recursive case: calls to the callers we've found
         # Note: prev_caller_function has arity suffix (e.g., "foo/2") but callee_function doesn't (e.g., "foo")
         # So we use starts_with to match prev_caller_function starting with callee_function
         trace[depth, caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line] :=
             trace[prev_depth, prev_caller_module, prev_caller_name, prev_caller_arity, _, _, _, _, _, _, _, _],
-            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line}},
+            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line, conditional}},
             *function_locations{{project, module: caller_module, name: caller_name, arity: caller_arity, kind: caller_kind, start_line: caller_start_line, end_line: caller_end_line}},
             callee_module == prev_caller_module,
             callee_function == prev_caller_name,
             callee_arity == prev_caller_arity,
             starts_with(caller_function, caller_name),
             call_line >= caller_start_line,
-            call_line <= caller_end_line,
+            call_line <= caller_end_line{certain_cond},
             prev_depth < {max_depth},
             depth = prev_depth + 1,
             project == $project
+            {scope_cond}
 
         ?[depth, caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line] :=
             trace[depth, caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line]
@@ -99,6 +117,8 @@ pub fn reverse_trace_calls(
     }
     params.insert("project", DataValue::Str(project.into()));
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| ReverseTraceError::QueryFailed {
         message: e.to_string(),
     })?;
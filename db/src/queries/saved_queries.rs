@@ -0,0 +1,204 @@
+//! Named queries stored with `query save`.
+//!
+//! A saved query is a command invocation (the arguments after `code_search`)
+//! or a raw CozoScript, stored under a name so a team can rerun a standard
+//! analysis with `query run <name>`. Saved queries live in the
+//! `saved_queries` relation, which re-imports leave untouched.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, extract_string_or, run_query, run_query_no_params, try_create_relation, Params};
+use crate::queries::schema::SCHEMA_SAVED_QUERIES;
+use crate::sanitize::check_sandboxed;
+
+#[derive(Error, Debug)]
+pub enum SavedQueriesError {
+    #[error("Saved queries query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A command invocation or CozoScript stored under a name
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SavedQuery {
+    pub name: String,
+    /// Arguments of the command invocation, empty for scripts
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// CozoScript, empty for command invocations
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub script: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Unix time in milliseconds
+    pub created_at: i64,
+}
+
+/// Create the relation in databases set up before saved queries existed
+fn ensure_relation(db: &cozo::DbInstance) -> Result<(), Box<dyn Error>> {
+    try_create_relation(db, SCHEMA_SAVED_QUERIES)?;
+    Ok(())
+}
+
+/// Store a query, replacing any query saved under the same name
+pub fn save_query(db: &cozo::DbInstance, query: &SavedQuery) -> Result<(), Box<dyn Error>> {
+    ensure_relation(db)?;
+
+    let script = r#"
+        ?[name, args, script, description, created_at] <- [[$name, $args, $script, $description, $created_at]]
+        :put saved_queries {name => args, script, description, created_at}
+    "#;
+
+    let mut params = Params::new();
+    params.insert("name", DataValue::Str(query.name.as_str().into()));
+    params.insert(
+        "args",
+        DataValue::List(query.args.iter().map(|arg| DataValue::Str(arg.as_str().into())).collect()),
+    );
+    params.insert("script", DataValue::Str(query.script.as_str().into()));
+    params.insert("description", DataValue::Str(query.description.as_str().into()));
+    params.insert("created_at", DataValue::from(query.created_at));
+
+    run_query(db, script, params).map_err(|e| SavedQueriesError::QueryFailed {
+        message: e.to_string(),
+    })?;
+    Ok(())
+}
+
+/// Remove a saved query, returning whether it existed
+pub fn delete_saved_query(db: &cozo::DbInstance, name: &str) -> Result<bool, Box<dyn Error>> {
+    ensure_relation(db)?;
+
+    let script = r#"
+        ?[name] := *saved_queries{name}, name == $name
+        :rm saved_queries {name}
+        :returning
+    "#;
+
+    let mut params = Params::new();
+    params.insert("name", DataValue::Str(name.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| SavedQueriesError::QueryFailed {
+        message: e.to_string(),
+    })?;
+    // :returning lists each key twice, as "requested" and as "deleted"
+    Ok(rows
+        .rows
+        .iter()
+        .any(|row| row.first().and_then(extract_string).as_deref() == Some("deleted")))
+}
+
+/// All saved queries, ordered by name
+pub fn find_saved_queries(db: &cozo::DbInstance) -> Result<Vec<SavedQuery>, Box<dyn Error>> {
+    ensure_relation(db)?;
+
+    let script = r#"
+        ?[name, args, script, description, created_at] :=
+            *saved_queries{name, args, script, description, created_at}
+        :order name
+    "#;
+
+    let rows = run_query_no_params(db, script).map_err(|e| SavedQueriesError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut queries = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 5 {
+            let Some(name) = extract_string(&row[0]) else { continue };
+            let args = match &row[1] {
+                DataValue::List(args) => args.iter().filter_map(extract_string).collect(),
+                _ => Vec::new(),
+            };
+            queries.push(SavedQuery {
+                name,
+                args,
+                script: extract_string_or(&row[2], ""),
+                description: extract_string_or(&row[3], ""),
+                created_at: extract_i64(&row[4], 0),
+            });
+        }
+    }
+
+    Ok(queries)
+}
+
+/// The query saved under `name`, if any
+pub fn find_saved_query(db: &cozo::DbInstance, name: &str) -> Result<Option<SavedQuery>, Box<dyn Error>> {
+    Ok(find_saved_queries(db)?.into_iter().find(|query| query.name == name))
+}
+
+/// Run a CozoScript without allowing writes, returning Cozo's JSON result.
+///
+/// `params_json` is a JSON object binding the script's `$` parameters. The
+/// result is `{"ok": true, "headers": [...], "rows": [...]}`, or has `"ok":
+/// false` and the error under `"message"`. Scripts changing stored relations
+/// fail this way, as do scripts [`check_sandboxed`] rejects.
+pub fn run_read_only_script(db: &cozo::DbInstance, script: &str, params_json: &str) -> String {
+    if let Err(e) = check_sandboxed(script) {
+        return serde_json::json!({"ok": false, "message": e.to_string()}).to_string();
+    }
+    db.run_script_str(script, params_json, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved(name: &str, args: &[&str], script: &str) -> SavedQuery {
+        SavedQuery {
+            name: name.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            script: script.to_string(),
+            description: String::new(),
+            created_at: 1,
+        }
+    }
+
+    #[test]
+    fn test_save_find_and_delete() {
+        let db = crate::db::open_mem_db();
+        let leaks = saved("leaks", &["calls-to", "MyApp.Repo", "--project", "{project}"], "");
+        save_query(&db, &leaks).unwrap();
+        save_query(&db, &saved("count", &[], "?[n] := n = 1")).unwrap();
+
+        let names: Vec<String> = find_saved_queries(&db).unwrap().into_iter().map(|q| q.name).collect();
+        assert_eq!(names, ["count", "leaks"]);
+        assert_eq!(find_saved_query(&db, "leaks").unwrap(), Some(leaks));
+
+        assert!(delete_saved_query(&db, "leaks").unwrap());
+        assert!(!delete_saved_query(&db, "leaks").unwrap());
+        assert_eq!(find_saved_query(&db, "leaks").unwrap(), None);
+    }
+
+    #[test]
+    fn test_run_read_only_script_rejects_writes() {
+        let db = crate::test_utils::call_graph_db("default");
+        let result = run_read_only_script(&db, "?[n] := *modules{project: $p, name: n}", r#"{"p": "default"}"#);
+        assert!(result.contains(r#""ok":true"#), "{result}");
+        assert!(result.contains("MyApp.Accounts"));
+
+        let result = run_read_only_script(&db, "?[project, name] <- [['x', 'y']] :put modules {project, name}", "{}");
+        assert!(result.contains(r#""ok":false"#), "{result}");
+    }
+
+    #[test]
+    fn test_run_read_only_script_is_sandboxed() {
+        let db = crate::test_utils::call_graph_db("default");
+        let result = run_read_only_script(&db, "::relations", "{}");
+        assert!(result.contains(r#""ok":false"#), "{result}");
+        assert!(result.contains("System operation '::relations'"), "{result}");
+
+        let result = run_read_only_script(&db, "?[line] <~ CsvReader(url: 'file:///etc/passwd', types: ['String'])", "{}");
+        assert!(result.contains("Fixed rule 'CsvReader'"), "{result}");
+
+        // Block comments hiding the rest of the script from a naive scanner
+        let script = "/* ' */ ?[line] <~ CsvReader(url: 'file:///etc/passwd', types: ['String']) /* ' */";
+        assert!(run_read_only_script(&db, script, "{}").contains("Fixed rule 'CsvReader'"));
+        let result = run_read_only_script(&db, "/* ' */ ::relations /* ' */", "{}");
+        assert!(result.contains("System operation '::relations'"), "{result}");
+    }
+}
@@ -4,19 +4,30 @@
 //! and setup commands. It defines the database schema for all relations
 //! and provides functions to create, check, and drop them.
 
+use std::collections::BTreeSet;
 use std::error::Error;
 use cozo::DbInstance;
-use crate::db::try_create_relation;
+use serde::Serialize;
+use crate::db::{extract_string, run_query_no_params, try_create_relation};
 
 // Schema definitions
 
+/// Modules of a project.
+///
+/// `app` is the OTP application owning the module in an umbrella project,
+/// empty when unknown. `is_external` marks modules whose source lives in a
+/// vendored dependency (`deps/`) rather than in the project itself, and
+/// `is_test` those whose source is test code (`test/`, `*_test.exs`).
 pub const SCHEMA_MODULES: &str = r#"
 :create modules {
     project: String,
     name: String
     =>
     file: String default "",
-    source: String default "unknown"
+    source: String default "unknown",
+    app: String default "",
+    is_external: Bool default false,
+    is_test: Bool default false
 }
 "#;
 
@@ -33,6 +44,13 @@ pub const SCHEMA_FUNCTIONS: &str = r#"
 }
 "#;
 
+/// Call edges, one row per call site.
+///
+/// `call_type` is "remote" or "local" and `call_kind` how the callee is
+/// referenced: "call", "pipe" or "capture". `conditional` marks call sites in
+/// a branch or comprehension, which may not run. `weight` is the number of
+/// call sites of the same caller and callee, set when the project's
+/// aggregates are refreshed after an import.
 pub const SCHEMA_CALLS: &str = r#"
 :create calls {
     project: String,
@@ -47,7 +65,34 @@ pub const SCHEMA_CALLS: &str = r#"
     =>
     call_type: String default "remote",
     caller_kind: String default "",
-    callee_args: String default ""
+    callee_args: String default "",
+    call_kind: String default "call",
+    conditional: Bool default false,
+    weight: Int default 1
+}
+"#;
+
+/// Message-passing edges: GenServer calls/casts and PubSub broadcasts.
+///
+/// Keyed like `calls`, so traversals can read either relation with the same
+/// bindings, `weight` included. The callee is the receiving callback (e.g. `handle_cast/2`);
+/// `kind` is "call", "cast", "info" or "broadcast".
+pub const SCHEMA_MESSAGES: &str = r#"
+:create messages {
+    project: String,
+    caller_module: String,
+    caller_function: String,
+    callee_module: String,
+    callee_function: String,
+    callee_arity: Int,
+    file: String,
+    line: Int,
+    column: Int
+    =>
+    kind: String default "cast",
+    topic: String default "",
+    conditional: Bool default false,
+    weight: Int default 1
 }
 "#;
 
@@ -63,6 +108,35 @@ pub const SCHEMA_STRUCT_FIELDS: &str = r#"
 }
 "#;
 
+/// Reads and writes of struct fields, one row per access site.
+///
+/// `access` is part of the key because a single expression can both read and
+/// write the same field (e.g. `%{user | email: String.trim(user.email)}`).
+pub const SCHEMA_FIELD_ACCESSES: &str = r#"
+:create field_accesses {
+    project: String,
+    struct_module: String,
+    field: String,
+    caller_module: String,
+    caller_function: String,
+    file: String,
+    line: Int,
+    column: Int,
+    access: String
+}
+"#;
+
+/// Function clauses, one row per clause.
+///
+/// `ast_minhash` is the clause's MinHash signature over AST shingles (empty
+/// when the extractor did not emit one); `similar-functions` compares them.
+/// `abc_score` and `halstead_volume` are size metrics ranked by
+/// `complexity --metric`, 0.0 when the extractor did not emit them.
+/// `cognitive_complexity` is the SonarSource-style metric used by
+/// `complexity --cognitive`, which unlike cyclomatic complexity does not
+/// count each pattern-matching clause or case branch as a separate path.
+/// `modified_at` is when the clause last changed, in Unix seconds (0 when
+/// unknown), and `commit` the revision that changed it; `age` reads both.
 pub const SCHEMA_FUNCTION_LOCATIONS: &str = r#"
 :create function_locations {
     project: String,
@@ -84,7 +158,14 @@ pub const SCHEMA_FUNCTION_LOCATIONS: &str = r#"
     complexity: Int default 1,
     max_nesting_depth: Int default 0,
     generated_by: String default "",
-    macro_source: String default ""
+    macro_source: String default "",
+    ast_minhash: [Int] default [],
+    abc_score: Float default 0.0,
+    halstead_volume: Float default 0.0,
+    cognitive_complexity: Int default 0,
+    deprecated: String default "",
+    modified_at: Int default 0,
+    commit: String default ""
 }
 "#;
 
@@ -103,6 +184,40 @@ pub const SCHEMA_SPECS: &str = r#"
 }
 "#;
 
+/// `@behaviour` declarations: `module` implements `behaviour`.
+pub const SCHEMA_BEHAVIOURS: &str = r#"
+:create behaviours {
+    project: String,
+    module: String,
+    behaviour: String
+}
+"#;
+
+/// `@moduledoc` and `@doc` text.
+///
+/// Module docs have `kind` "moduledoc", an empty `name` and arity -1;
+/// function docs have `kind` "doc".
+pub const SCHEMA_DOCS: &str = r#"
+:create docs {
+    project: String,
+    module: String,
+    name: String,
+    arity: Int
+    =>
+    kind: String,
+    doc: String
+}
+"#;
+
+/// Full-text index over `docs`, used by `search --kind docs`.
+pub const SCHEMA_DOCS_FTS: &str = r#"
+::fts create docs:text {
+    extractor: doc,
+    tokenizer: Simple,
+    filters: [Lowercase, Stemmer('english'), Stopwords('en')]
+}
+"#;
+
 pub const SCHEMA_TYPES: &str = r#"
 :create types {
     project: String,
@@ -116,6 +231,226 @@ pub const SCHEMA_TYPES: &str = r#"
 }
 "#;
 
+/// Supervisor -> child spec edges of the OTP supervision tree.
+///
+/// `position` is the child's start order within its supervisor; `strategy`
+/// repeats the supervisor's restart strategy on each of its children.
+pub const SCHEMA_SUPERVISION: &str = r#"
+:create supervision {
+    project: String,
+    supervisor: String,
+    child_id: String
+    =>
+    child_module: String default "",
+    child_type: String default "worker",
+    restart: String default "permanent",
+    strategy: String default "",
+    position: Int default 0
+}
+"#;
+
+/// `alias`, `import` and `require` directives of each module.
+///
+/// `kind` is the directive; `alias` is the short name it gives `target` in
+/// `module` (the `as:` option or the target's last segment), empty when the
+/// directive introduces none.
+pub const SCHEMA_ALIASES: &str = r#"
+:create aliases {
+    project: String,
+    module: String,
+    kind: String,
+    target: String
+    =>
+    alias: String default "",
+    line: Int default 0
+}
+"#;
+
+/// Derived per-function call counts, refreshed by the import pipeline.
+///
+/// Holds the same numbers `hotspots` would compute from the calls table:
+/// distinct callers (incoming) and distinct callees (outgoing) per function.
+pub const SCHEMA_FUNCTION_STATS: &str = r#"
+:create function_stats {
+    project: String,
+    module: String,
+    function: String
+    =>
+    incoming: Int,
+    outgoing: Int,
+    generated: Bool default false
+}
+"#;
+
+/// Derived per-module totals, refreshed by the import pipeline.
+pub const SCHEMA_MODULE_STATS: &str = r#"
+:create module_stats {
+    project: String,
+    module: String
+    =>
+    function_count: Int default 0,
+    loc: Int default 0,
+    incoming: Int default 0,
+    outgoing: Int default 0
+}
+"#;
+
+/// Project metrics recorded after each import, read by `trends`.
+///
+/// `taken_at` is the import time in Unix milliseconds. Rows are history, so
+/// clearing a project's data keeps them.
+pub const SCHEMA_METRIC_SNAPSHOTS: &str = r#"
+:create metric_snapshots {
+    project: String,
+    taken_at: Int
+    =>
+    label: String default "",
+    modules: Int default 0,
+    functions: Int default 0,
+    calls: Int default 0,
+    unused: Int default 0,
+    cycles: Int default 0,
+    average_complexity: Float default 0.0
+}
+"#;
+
+/// When each function was first imported, in Unix seconds.
+///
+/// `first_seen` is the earliest `modified_at` of the function's clauses over
+/// all imports, or the import time when none was known. Like snapshots, rows
+/// are history and survive re-imports.
+pub const SCHEMA_FUNCTION_HISTORY: &str = r#"
+:create function_history {
+    project: String,
+    module: String,
+    name: String,
+    arity: Int
+    =>
+    first_seen: Int
+}
+"#;
+
+/// Notes attached to modules and functions by `annotate`.
+///
+/// `function` is empty for module annotations and `arity` is -1 when a note
+/// applies to every arity. Like snapshots, annotations survive re-imports.
+pub const SCHEMA_ANNOTATIONS: &str = r#"
+:create annotations {
+    project: String,
+    module: String,
+    function: String,
+    arity: Int,
+    created_at: Int
+    =>
+    kind: String default "note",
+    text: String default ""
+}
+"#;
+
+/// Named queries stored by `query save`.
+///
+/// A saved query is either a command invocation (`args`) or a raw CozoScript
+/// (`script`, empty for invocations). Saved queries are not tied to a project
+/// and, like annotations, survive re-imports.
+pub const SCHEMA_SAVED_QUERIES: &str = r#"
+:create saved_queries {
+    name: String
+    =>
+    args: [String] default [],
+    script: String default "",
+    description: String default "",
+    created_at: Int
+}
+"#;
+
+/// Command output cached by `--cache`.
+///
+/// `key` identifies the command line and everything else its output depends
+/// on; rows from a generation older than `cache_generation` are stale. Like
+/// the generation, the cache is created on first use rather than with the
+/// schema, so it is not part of backups.
+pub const SCHEMA_CACHE: &str = r#"
+:create cache {
+    key: String
+    =>
+    generation: Int,
+    output: String
+}
+"#;
+
+/// Current cache generation, bumped by every command that changes the data.
+pub const SCHEMA_CACHE_GENERATION: &str = r#"
+:create cache_generation {
+    generation: Int
+}
+"#;
+
+/// Progress of the import of a project, kept until the import completes.
+///
+/// `chunks` counts the chunks of `relation` already written from `source`, so
+/// `import --resume` can skip them. Created on first use, like the cache.
+pub const SCHEMA_IMPORT_CHECKPOINTS: &str = r#"
+:create import_checkpoints {
+    project: String,
+    relation: String
+    =>
+    source: String,
+    chunks: Int
+}
+"#;
+
+/// Secondary index over a stored relation.
+///
+/// The index holds `columns` followed by the relation's remaining key
+/// columns. Cozo reads through it when a join binds a prefix of `columns`
+/// while leaving the relation's leading `project` key unbound (filtered
+/// with `project == $project` instead).
+#[derive(Debug, Clone, Copy)]
+pub struct IndexDefinition {
+    pub relation: &'static str,
+    pub name: &'static str,
+    pub columns: &'static [&'static str],
+}
+
+impl IndexDefinition {
+    /// Name of the index as Cozo knows it (`calls:by_callee`)
+    pub fn qualified_name(&self) -> String {
+        format!("{}:{}", self.relation, self.name)
+    }
+
+    fn create_script(&self) -> String {
+        format!("::index create {} {{{}}}", self.qualified_name(), self.columns.join(", "))
+    }
+}
+
+/// Secondary indexes created with the schema.
+///
+/// Calls and messages are keyed by caller, so lookups by callee (calls-to,
+/// reverse-trace, unused) would otherwise scan the whole relation; the same
+/// goes for function locations by file (browse-module on a file, export).
+pub const INDEXES: &[IndexDefinition] = &[
+    IndexDefinition {
+        relation: "calls",
+        name: "by_callee",
+        columns: &["callee_module", "callee_function", "callee_arity"],
+    },
+    IndexDefinition {
+        relation: "messages",
+        name: "by_callee",
+        columns: &["callee_module", "callee_function", "callee_arity"],
+    },
+    IndexDefinition {
+        relation: "function_locations",
+        name: "by_file",
+        columns: &["file"],
+    },
+    IndexDefinition {
+        relation: "field_accesses",
+        name: "by_caller",
+        columns: &["caller_module", "caller_function"],
+    },
+];
+
 /// Result of schema creation operation
 #[derive(Debug, Clone)]
 pub struct SchemaCreationResult {
@@ -125,8 +460,9 @@ pub struct SchemaCreationResult {
 
 /// Create all database schemas.
 ///
-/// Returns a list of all relations with their creation status.
-/// If a relation already exists, returns Ok with created=false for that relation.
+/// Returns a list of all relations, then of all secondary indexes, with their
+/// creation status. If a relation or index already exists, returns Ok with
+/// created=false for it.
 pub fn create_schema(db: &DbInstance) -> Result<Vec<SchemaCreationResult>, Box<dyn Error>> {
     let mut result = Vec::new();
 
@@ -134,10 +470,22 @@ pub fn create_schema(db: &DbInstance) -> Result<Vec<SchemaCreationResult>, Box<d
         ("modules", SCHEMA_MODULES),
         ("functions", SCHEMA_FUNCTIONS),
         ("calls", SCHEMA_CALLS),
+        ("messages", SCHEMA_MESSAGES),
         ("struct_fields", SCHEMA_STRUCT_FIELDS),
+        ("field_accesses", SCHEMA_FIELD_ACCESSES),
         ("function_locations", SCHEMA_FUNCTION_LOCATIONS),
         ("specs", SCHEMA_SPECS),
         ("types", SCHEMA_TYPES),
+        ("behaviours", SCHEMA_BEHAVIOURS),
+        ("docs", SCHEMA_DOCS),
+        ("supervision", SCHEMA_SUPERVISION),
+        ("aliases", SCHEMA_ALIASES),
+        ("function_stats", SCHEMA_FUNCTION_STATS),
+        ("module_stats", SCHEMA_MODULE_STATS),
+        ("metric_snapshots", SCHEMA_METRIC_SNAPSHOTS),
+        ("function_history", SCHEMA_FUNCTION_HISTORY),
+        ("annotations", SCHEMA_ANNOTATIONS),
+        ("saved_queries", SCHEMA_SAVED_QUERIES),
     ];
 
     for (name, script) in schemas {
@@ -148,19 +496,77 @@ pub fn create_schema(db: &DbInstance) -> Result<Vec<SchemaCreationResult>, Box<d
         });
     }
 
+    // The index belongs to `docs` and is not reported separately
+    try_create_relation(db, SCHEMA_DOCS_FTS)?;
+
+    result.extend(create_indexes(db)?);
+
+    Ok(result)
+}
+
+/// Create the secondary indexes missing from the database
+pub fn create_indexes(db: &DbInstance) -> Result<Vec<SchemaCreationResult>, Box<dyn Error>> {
+    let mut result = Vec::new();
+    for index in INDEXES {
+        let created = try_create_relation(db, &index.create_script())?;
+        result.push(SchemaCreationResult {
+            relation: index.qualified_name(),
+            created,
+        });
+    }
     Ok(result)
 }
 
+/// Drop and recreate every secondary index, rebuilding it from its relation
+pub fn rebuild_indexes(db: &DbInstance) -> Result<Vec<SchemaCreationResult>, Box<dyn Error>> {
+    for index in INDEXES {
+        if index_exists(db, index)? {
+            run_query_no_params(db, &format!("::index drop {}", index.qualified_name()))?;
+        }
+    }
+    create_indexes(db)
+}
+
+/// Whether a secondary index exists; false when its relation does not
+pub fn index_exists(db: &DbInstance, index: &IndexDefinition) -> Result<bool, Box<dyn Error>> {
+    let relations = run_query_no_params(db, "::relations")?;
+    let relation_exists = relations
+        .rows
+        .iter()
+        .any(|row| extract_string(&row[0]).as_deref() == Some(index.relation));
+    if !relation_exists {
+        return Ok(false);
+    }
+
+    let indices = run_query_no_params(db, &format!("::indices {}", index.relation))?;
+    Ok(indices
+        .rows
+        .iter()
+        .any(|row| extract_string(&row[0]).as_deref() == Some(index.name)))
+}
+
 /// Get list of all relation names managed by this schema
 pub fn relation_names() -> Vec<&'static str> {
     vec![
         "modules",
         "functions",
         "calls",
+        "messages",
         "struct_fields",
+        "field_accesses",
         "function_locations",
         "specs",
         "types",
+        "behaviours",
+        "docs",
+        "supervision",
+        "aliases",
+        "function_stats",
+        "module_stats",
+        "metric_snapshots",
+        "function_history",
+        "annotations",
+        "saved_queries",
     ]
 }
 
@@ -171,10 +577,492 @@ pub fn schema_for_relation(name: &str) -> Option<&'static str> {
         "modules" => Some(SCHEMA_MODULES),
         "functions" => Some(SCHEMA_FUNCTIONS),
         "calls" => Some(SCHEMA_CALLS),
+        "messages" => Some(SCHEMA_MESSAGES),
         "struct_fields" => Some(SCHEMA_STRUCT_FIELDS),
+        "field_accesses" => Some(SCHEMA_FIELD_ACCESSES),
         "function_locations" => Some(SCHEMA_FUNCTION_LOCATIONS),
         "specs" => Some(SCHEMA_SPECS),
         "types" => Some(SCHEMA_TYPES),
+        "behaviours" => Some(SCHEMA_BEHAVIOURS),
+        "docs" => Some(SCHEMA_DOCS),
+        "supervision" => Some(SCHEMA_SUPERVISION),
+        "aliases" => Some(SCHEMA_ALIASES),
+        "function_stats" => Some(SCHEMA_FUNCTION_STATS),
+        "module_stats" => Some(SCHEMA_MODULE_STATS),
+        "metric_snapshots" => Some(SCHEMA_METRIC_SNAPSHOTS),
+        "function_history" => Some(SCHEMA_FUNCTION_HISTORY),
+        "annotations" => Some(SCHEMA_ANNOTATIONS),
+        "saved_queries" => Some(SCHEMA_SAVED_QUERIES),
         _ => None,
     }
 }
+
+/// A stored relation whose columns differ from the schema of this build
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaMismatch {
+    pub relation: String,
+    /// Columns the schema defines but the stored relation lacks
+    pub missing: Vec<String>,
+    /// Stored columns the schema no longer defines
+    pub unexpected: Vec<String>,
+    /// Columns stored with another type than the schema's
+    pub type_drift: Vec<TypeDrift>,
+}
+
+impl SchemaMismatch {
+    /// One-line description, e.g. `missing app; unexpected legacy`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.missing.is_empty() {
+            parts.push(format!("missing {}", self.missing.join(", ")));
+        }
+        if !self.unexpected.is_empty() {
+            parts.push(format!("unexpected {}", self.unexpected.join(", ")));
+        }
+        for drift in &self.type_drift {
+            parts.push(format!("{} is {}, expected {}", drift.column, drift.stored, drift.expected));
+        }
+        parts.join("; ")
+    }
+
+    /// Whether [`repair_schema`] fixes the relation while keeping every row:
+    /// it only lacks non-key columns that have a default.
+    pub fn repairable(&self) -> bool {
+        let Some(script) = schema_for_relation(&self.relation) else { return false };
+        let expected = schema_column_definitions(script);
+        let addable = self.missing.iter().all(|name| {
+            expected
+                .iter()
+                .any(|column| column.name == name && !column.is_key && column.default.is_some())
+        });
+        addable && self.unexpected.is_empty() && self.type_drift.is_empty()
+    }
+}
+
+/// A stored column whose type differs from the schema's
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TypeDrift {
+    pub column: String,
+    pub expected: String,
+    pub stored: String,
+}
+
+/// A column declared by a `:create` script
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColumnDefinition<'a> {
+    pub name: &'a str,
+    /// Type as Cozo reports it, e.g. `String` or `[Int]`
+    pub column_type: &'a str,
+    pub is_key: bool,
+    /// Default value expression, if the column has one
+    pub default: Option<&'a str>,
+}
+
+/// Columns declared by a `:create` script, keys first
+pub fn schema_column_definitions(script: &str) -> Vec<ColumnDefinition<'_>> {
+    let Some(start) = script.find('{') else { return Vec::new() };
+    let mut is_key = true;
+    let mut columns = Vec::new();
+    for line in script[start + 1..].lines() {
+        let line = line.trim().trim_end_matches(',');
+        if line == "=>" {
+            is_key = false;
+        }
+        let Some((name, spec)) = line.split_once(':') else { continue };
+        let (column_type, default) = match spec.split_once(" default ") {
+            Some((column_type, default)) => (column_type, Some(default.trim())),
+            None => (spec, None),
+        };
+        columns.push(ColumnDefinition {
+            name: name.trim(),
+            column_type: column_type.trim(),
+            is_key,
+            default,
+        });
+    }
+    columns
+}
+
+/// Column names declared by a `:create` script, keys first
+pub fn schema_columns(script: &str) -> Vec<&str> {
+    schema_column_definitions(script).into_iter().map(|column| column.name).collect()
+}
+
+/// Names of the relations stored in the database
+fn stored_relations(db: &DbInstance) -> Result<BTreeSet<String>, Box<dyn Error>> {
+    let relations = run_query_no_params(db, "::relations")?;
+    Ok(relations.rows.iter().filter_map(|row| extract_string(&row[0])).collect())
+}
+
+/// Managed relations whose stored columns differ from their schema.
+///
+/// A database set up by an older build lacks the columns added since, and
+/// writes naming them fail part-way through an import.
+pub fn schema_mismatches(db: &DbInstance) -> Result<Vec<SchemaMismatch>, Box<dyn Error>> {
+    let existing = stored_relations(db)?;
+
+    let mut mismatches = Vec::new();
+    for relation in relation_names().into_iter().filter(|name| existing.contains(*name)) {
+        let Some(script) = schema_for_relation(relation) else { continue };
+        let expected = schema_column_definitions(script);
+        let rows = run_query_no_params(db, &format!("::columns {relation}"))?;
+        // Rows of `::columns` are [column, is_key, index, type, has_default]
+        let stored: Vec<(String, String)> = rows
+            .rows
+            .iter()
+            .filter_map(|row| Some((extract_string(&row[0])?, extract_string(&row[3])?)))
+            .collect();
+
+        let missing: Vec<String> = expected
+            .iter()
+            .filter(|column| !stored.iter().any(|(name, _)| name == column.name))
+            .map(|column| column.name.to_string())
+            .collect();
+        let unexpected: Vec<String> = stored
+            .iter()
+            .filter(|(name, _)| !expected.iter().any(|column| column.name == name))
+            .map(|(name, _)| name.clone())
+            .collect();
+        let type_drift: Vec<TypeDrift> = expected
+            .iter()
+            .filter_map(|column| {
+                let (_, stored_type) = stored.iter().find(|(name, _)| name == column.name)?;
+                (stored_type != column.column_type).then(|| TypeDrift {
+                    column: column.name.to_string(),
+                    expected: column.column_type.to_string(),
+                    stored: stored_type.clone(),
+                })
+            })
+            .collect();
+        if !missing.is_empty() || !unexpected.is_empty() || !type_drift.is_empty() {
+            mismatches.push(SchemaMismatch {
+                relation: relation.to_string(),
+                missing,
+                unexpected,
+                type_drift,
+            });
+        }
+    }
+    Ok(mismatches)
+}
+
+/// How the stored relations compare with the schema of this build
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SchemaVerification {
+    /// Managed relations the database lacks
+    pub missing_relations: Vec<String>,
+    pub mismatches: Vec<SchemaMismatch>,
+}
+
+impl SchemaVerification {
+    /// Whether the database matches the schema
+    pub fn matches(&self) -> bool {
+        self.missing_relations.is_empty() && self.mismatches.is_empty()
+    }
+}
+
+/// Compare the relations and columns of the database with the schema
+pub fn verify_schema(db: &DbInstance) -> Result<SchemaVerification, Box<dyn Error>> {
+    let existing = stored_relations(db)?;
+    Ok(SchemaVerification {
+        missing_relations: relation_names()
+            .into_iter()
+            .filter(|name| !existing.contains(*name))
+            .map(|name| name.to_string())
+            .collect(),
+        mismatches: schema_mismatches(db)?,
+    })
+}
+
+/// A fix applied by [`repair_schema`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaRepair {
+    pub relation: String,
+    /// Whether the relation was missing and got created
+    pub created: bool,
+    /// Columns added to a stored relation, filled with their defaults
+    pub added_columns: Vec<String>,
+}
+
+/// Apply the fixes that keep every stored row: create missing relations and
+/// add missing non-key columns that have a default.
+///
+/// Relations that also have unexpected columns or columns of another type are
+/// left alone, since fixing those would drop or convert data; they are still
+/// reported by [`verify_schema`].
+pub fn repair_schema(db: &DbInstance) -> Result<Vec<SchemaRepair>, Box<dyn Error>> {
+    let verification = verify_schema(db)?;
+    let mut repairs = Vec::new();
+
+    for relation in &verification.missing_relations {
+        let Some(script) = schema_for_relation(relation) else { continue };
+        try_create_relation(db, script)?;
+        repairs.push(SchemaRepair {
+            relation: relation.clone(),
+            created: true,
+            added_columns: Vec::new(),
+        });
+    }
+    repairs.extend(repair_mismatches(db, &verification.mismatches)?);
+
+    if !repairs.is_empty() {
+        // Missing relations and rewritten ones get their indexes back
+        create_indexes(db)?;
+        try_create_relation(db, SCHEMA_DOCS_FTS)?;
+    }
+    Ok(repairs)
+}
+
+/// Add the columns that stored relations lack, as [`repair_schema`] does,
+/// without creating missing relations.
+///
+/// Run by `setup` and before imports, so that databases set up by an older
+/// build keep working; the mismatches it cannot fix are left for
+/// [`verify_schema`] to report.
+pub fn add_missing_columns(db: &DbInstance) -> Result<Vec<SchemaRepair>, Box<dyn Error>> {
+    let repairs = repair_mismatches(db, &schema_mismatches(db)?)?;
+    if !repairs.is_empty() {
+        create_indexes(db)?;
+        try_create_relation(db, SCHEMA_DOCS_FTS)?;
+    }
+    Ok(repairs)
+}
+
+fn repair_mismatches(db: &DbInstance, mismatches: &[SchemaMismatch]) -> Result<Vec<SchemaRepair>, Box<dyn Error>> {
+    let mut repairs = Vec::new();
+    for mismatch in mismatches.iter().filter(|mismatch| mismatch.repairable()) {
+        let Some(script) = schema_for_relation(&mismatch.relation) else { continue };
+        add_columns(db, &mismatch.relation, script, &mismatch.missing)?;
+        repairs.push(SchemaRepair {
+            relation: mismatch.relation.clone(),
+            created: false,
+            added_columns: mismatch.missing.clone(),
+        });
+    }
+    Ok(repairs)
+}
+
+/// Rewrite a relation with the columns of its schema, filling `missing` with
+/// their defaults.
+///
+/// Cozo cannot add columns in place, and cannot replace a relation while
+/// indexes are attached, so its indexes are dropped first.
+fn add_columns(db: &DbInstance, relation: &str, script: &str, missing: &[String]) -> Result<(), Box<dyn Error>> {
+    for index in INDEXES.iter().filter(|index| index.relation == relation) {
+        if index_exists(db, index)? {
+            run_query_no_params(db, &format!("::index drop {}", index.qualified_name()))?;
+        }
+    }
+    if relation == "docs" {
+        let indices = run_query_no_params(db, "::indices docs")?;
+        if indices.rows.iter().any(|row| extract_string(&row[0]).as_deref() == Some("text")) {
+            run_query_no_params(db, "::fts drop docs:text")?;
+        }
+    }
+
+    let columns = schema_column_definitions(script);
+    let stored: Vec<&str> = columns
+        .iter()
+        .map(|column| column.name)
+        .filter(|name| !missing.iter().any(|m| m == name))
+        .collect();
+    let defaults: Vec<String> = columns
+        .iter()
+        .filter(|column| missing.iter().any(|m| m == column.name))
+        .filter_map(|column| Some(format!("{} = {}", column.name, column.default?)))
+        .collect();
+    let names: Vec<&str> = columns.iter().map(|column| column.name).collect();
+
+    let query = format!(
+        "?[{}] := *{}{{{}}}, {}\n{}",
+        names.join(", "),
+        relation,
+        stored.join(", "),
+        defaults.join(", "),
+        script.trim().replacen(":create", ":replace", 1)
+    );
+    run_query_no_params(db, &query)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cozo::DataValue;
+
+    #[test]
+    fn test_create_schema_creates_indexes_once() {
+        let db = crate::db::open_mem_db();
+
+        let created = create_schema(&db).unwrap();
+        let index = created.iter().find(|r| r.relation == "calls:by_callee").expect("Index reported");
+        assert!(index.created);
+        assert!(INDEXES.iter().all(|index| index_exists(&db, index).unwrap()));
+
+        let again = create_schema(&db).unwrap();
+        assert!(again.iter().all(|r| !r.created));
+    }
+
+    #[test]
+    fn test_rebuild_indexes() {
+        let db = crate::test_utils::call_graph_db("default");
+        let rebuilt = rebuild_indexes(&db).unwrap();
+        assert_eq!(rebuilt.len(), INDEXES.len());
+        assert!(rebuilt.iter().all(|r| r.created));
+
+        // Lookups through the rebuilt index see the imported calls
+        let rows = run_query_no_params(&db, "?[caller_module] := *calls:by_callee{callee_module: 'MyApp.Repo', caller_module}").unwrap();
+        assert!(!rows.rows.is_empty());
+
+        // Lookups by callee on the base relation go through the index
+        let plan = run_query_no_params(
+            &db,
+            "::explain { ?[caller_module] := callee_module = 'MyApp.Repo', *calls{callee_module, caller_module} }",
+        )
+        .unwrap();
+        assert!(plan.rows.iter().any(|row| row[5] == DataValue::from(":calls:by_callee")));
+    }
+
+    #[test]
+    fn test_index_exists_without_relation() {
+        let db = crate::db::open_mem_db();
+        assert!(!index_exists(&db, &INDEXES[0]).unwrap());
+    }
+
+    #[test]
+    fn test_schema_columns() {
+        let columns = schema_columns(SCHEMA_MODULES);
+        assert_eq!(columns, ["project", "name", "file", "source", "app", "is_external", "is_test"]);
+        assert!(relation_names()
+            .iter()
+            .all(|name| !schema_columns(schema_for_relation(name).unwrap()).is_empty()));
+    }
+
+    #[test]
+    fn test_schema_column_definitions() {
+        let columns = schema_column_definitions(SCHEMA_MODULES);
+        assert_eq!(
+            columns[1],
+            ColumnDefinition {
+                name: "name",
+                column_type: "String",
+                is_key: true,
+                default: None
+            }
+        );
+        assert_eq!(columns[3].default, Some("\"unknown\""));
+        assert!(!columns[5].is_key);
+        assert_eq!(columns[5].column_type, "Bool");
+    }
+
+    #[test]
+    fn test_schema_mismatches() {
+        let db = crate::db::open_mem_db();
+        create_schema(&db).unwrap();
+        assert!(schema_mismatches(&db).unwrap().is_empty());
+
+        // A modules relation from before `app`, `is_external` and `is_test` existed
+        run_query_no_params(&db, "::remove modules").unwrap();
+        run_query_no_params(&db, ":create modules { project: String, name: String => file: String, legacy: Int }")
+            .unwrap();
+        let mismatches = schema_mismatches(&db).unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].relation, "modules");
+        assert_eq!(mismatches[0].missing, ["source", "app", "is_external", "is_test"]);
+        assert_eq!(mismatches[0].unexpected, ["legacy"]);
+        assert!(mismatches[0].type_drift.is_empty());
+    }
+
+    #[test]
+    fn test_verify_schema_reports_type_drift() {
+        let db = crate::db::open_mem_db();
+        assert_eq!(verify_schema(&db).unwrap().missing_relations.len(), relation_names().len());
+
+        create_schema(&db).unwrap();
+        assert!(verify_schema(&db).unwrap().matches());
+
+        run_query_no_params(&db, "::remove annotations").unwrap();
+        run_query_no_params(&db, "::remove behaviours").unwrap();
+        run_query_no_params(&db, ":create behaviours { project: String, module: String, behaviour: Int }")
+            .unwrap();
+        let verification = verify_schema(&db).unwrap();
+        assert_eq!(verification.missing_relations, ["annotations"]);
+        assert_eq!(verification.mismatches.len(), 1);
+        assert_eq!(verification.mismatches[0].summary(), "behaviour is Int, expected String");
+    }
+
+    #[test]
+    fn test_repair_schema_adds_defaulted_columns() {
+        let db = crate::db::open_mem_db();
+        create_schema(&db).unwrap();
+        run_query_no_params(&db, "::remove modules").unwrap();
+        run_query_no_params(&db, ":create modules { project: String, name: String => file: String }").unwrap();
+        run_query_no_params(&db, "?[project, name, file] <- [['app', 'A', 'a.ex']] :put modules {project, name => file}")
+            .unwrap();
+        // Indexes missing before the repair are created as well
+        run_query_no_params(&db, "::index drop calls:by_callee").unwrap();
+
+        let repairs = repair_schema(&db).unwrap();
+        assert_eq!(
+            repairs,
+            [SchemaRepair {
+                relation: "modules".to_string(),
+                created: false,
+                added_columns: vec![
+                    "source".to_string(),
+                    "app".to_string(),
+                    "is_external".to_string(),
+                    "is_test".to_string(),
+                ],
+            }]
+        );
+        assert!(verify_schema(&db).unwrap().matches());
+        assert!(INDEXES.iter().all(|index| index_exists(&db, index).unwrap()));
+
+        let rows = run_query_no_params(&db, "?[file, source, is_external] := *modules{name: 'A', file, source, is_external}")
+            .unwrap();
+        assert_eq!(
+            rows.rows,
+            [[DataValue::from("a.ex"), DataValue::from("unknown"), DataValue::from(false)]]
+        );
+    }
+
+    #[test]
+    fn test_repair_schema_keeps_unsafe_mismatches() {
+        let db = crate::db::open_mem_db();
+        create_schema(&db).unwrap();
+        run_query_no_params(&db, "::fts drop docs:text").unwrap();
+        run_query_no_params(&db, "::remove docs").unwrap();
+        run_query_no_params(&db, ":create docs { project: String, module: String, name: String, arity: Int => doc: String }")
+            .unwrap();
+        run_query_no_params(&db, "::remove modules").unwrap();
+        run_query_no_params(&db, ":create modules { project: String, name: String => file: String, legacy: Int }")
+            .unwrap();
+
+        // `docs` lacks `kind`, which has no default; `modules` has an extra column
+        assert!(repair_schema(&db).unwrap().is_empty());
+        let mismatches = verify_schema(&db).unwrap().mismatches;
+        assert_eq!(mismatches.len(), 2);
+        assert!(mismatches.iter().all(|mismatch| !mismatch.repairable()));
+    }
+
+    #[test]
+    fn test_add_missing_columns_leaves_missing_relations() {
+        let db = crate::db::open_mem_db();
+        create_schema(&db).unwrap();
+        run_query_no_params(&db, "::remove annotations").unwrap();
+        run_query_no_params(&db, "::remove modules").unwrap();
+        run_query_no_params(&db, ":create modules { project: String, name: String => file: String }").unwrap();
+        assert!(schema_mismatches(&db).unwrap()[0].repairable());
+
+        let repairs = add_missing_columns(&db).unwrap();
+        assert_eq!(repairs.len(), 1);
+        assert_eq!(repairs[0].relation, "modules");
+        assert!(!repairs[0].created);
+
+        let verification = verify_schema(&db).unwrap();
+        assert!(verification.mismatches.is_empty());
+        assert_eq!(verification.missing_relations, ["annotations"]);
+        assert!(add_missing_columns(&db).unwrap().is_empty());
+    }
+}
@@ -1,3 +1,9 @@
+//! Module and function search by name.
+//!
+//! Names match exactly or by regex, optionally ignoring case. Fuzzy search
+//! instead loads every name and ranks it in Rust against the pattern, for
+//! names that are only half remembered.
+
 use std::error::Error;
 
 use cozo::DataValue;
@@ -5,7 +11,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, extract_string_or, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder};
+use crate::query_builders::{validate_regex_patterns, ConditionBuilder, QuerySettings};
 
 #[derive(Error, Debug)]
 pub enum SearchError {
@@ -13,12 +19,18 @@ pub enum SearchError {
     QueryFailed { message: String },
 }
 
+/// Lowest fuzzy score (0-1) a name needs to be reported
+pub const MIN_FUZZY_SCORE: f64 = 0.4;
+
 /// A module search result
 #[derive(Debug, Clone, Serialize)]
 pub struct ModuleResult {
     pub project: String,
     pub name: String,
     pub source: String,
+    /// Similarity to the pattern (0-1), only set by fuzzy search
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
 }
 
 /// A function search result
@@ -29,6 +41,9 @@ pub struct FunctionResult {
     pub name: String,
     pub arity: i64,
     pub return_type: String,
+    /// Similarity to the pattern (0-1), only set by fuzzy search
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
 }
 
 pub fn search_modules(
@@ -37,24 +52,154 @@ pub fn search_modules(
     project: &str,
     limit: u32,
     use_regex: bool,
+    settings: &QuerySettings,
 ) -> Result<Vec<ModuleResult>, Box<dyn Error>> {
-    validate_regex_patterns(use_regex, &[Some(pattern)])?;
+    query_modules(db, Some(pattern), project, limit, use_regex, false, settings)
+}
+
+/// Like [`search_modules`], ignoring case
+pub fn search_modules_ignore_case(
+    db: &cozo::DbInstance,
+    pattern: &str,
+    project: &str,
+    limit: u32,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<ModuleResult>, Box<dyn Error>> {
+    query_modules(db, Some(pattern), project, limit, use_regex, true, settings)
+}
+
+/// Find the modules whose names are most similar to `pattern`, best first
+pub fn fuzzy_search_modules(
+    db: &cozo::DbInstance,
+    pattern: &str,
+    project: &str,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<ModuleResult>, Box<dyn Error>> {
+    let modules = query_modules(db, None, project, u32::MAX, false, false, settings)?;
+    Ok(rank(modules, pattern, limit, |m| &m.name, |m, score| m.score = Some(score)))
+}
+
+pub fn search_functions(
+    db: &cozo::DbInstance,
+    pattern: &str,
+    project: &str,
+    limit: u32,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<FunctionResult>, Box<dyn Error>> {
+    query_functions(db, Some(pattern), project, limit, use_regex, false, settings)
+}
+
+/// Like [`search_functions`], ignoring case
+pub fn search_functions_ignore_case(
+    db: &cozo::DbInstance,
+    pattern: &str,
+    project: &str,
+    limit: u32,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<FunctionResult>, Box<dyn Error>> {
+    query_functions(db, Some(pattern), project, limit, use_regex, true, settings)
+}
 
-    let match_cond = ConditionBuilder::new("name", "pattern").build(use_regex);
+/// Find the functions whose names are most similar to `pattern`, best first
+pub fn fuzzy_search_functions(
+    db: &cozo::DbInstance,
+    pattern: &str,
+    project: &str,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<FunctionResult>, Box<dyn Error>> {
+    let functions = query_functions(db, None, project, u32::MAX, false, false, settings)?;
+    Ok(rank(functions, pattern, limit, |f| &f.name, |f, score| f.score = Some(score)))
+}
+
+/// Near-miss names for a lookup of `module` (and `function`) that matched nothing, best first.
+///
+/// When the project has no module named `module`, the most similar module
+/// names. Otherwise the functions most similarly named to `function`, in
+/// `module` when given, as `Module.function/arity`. None when a function of
+/// exactly that name exists: the lookup found it and only filtered it away.
+pub fn suggest_names(
+    db: &cozo::DbInstance,
+    project: &str,
+    module: Option<&str>,
+    function: Option<&str>,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let module = module.filter(|m| !m.is_empty());
+    if let Some(module) = module
+        && search_modules(db, module, project, 1, false, settings)?.is_empty()
+    {
+        let modules = fuzzy_search_modules(db, module, project, limit, settings)?;
+        return Ok(modules.into_iter().map(|m| m.name).collect());
+    }
+
+    let Some(function) = function.filter(|f| !f.is_empty()) else {
+        return Ok(Vec::new());
+    };
+    let functions: Vec<FunctionResult> = fuzzy_search_functions(db, function, project, u32::MAX, settings)?
+        .into_iter()
+        .filter(|f| module.is_none_or(|m| f.module == m))
+        .collect();
+    if functions.iter().any(|f| f.name == function) {
+        return Ok(Vec::new());
+    }
+    Ok(functions
+        .into_iter()
+        .take(limit as usize)
+        .map(|f| format!("{}.{}/{}", f.module, f.name, f.arity))
+        .collect())
+}
+
+/// Name condition and pattern parameter for a search; no condition without a pattern
+fn name_match(pattern: Option<&str>, use_regex: bool, ignore_case: bool) -> Result<(String, Option<String>), Box<dyn Error>> {
+    let Some(pattern) = pattern else {
+        return Ok(("true".to_string(), None));
+    };
+
+    if ignore_case && !use_regex {
+        return Ok(("lowercase(name) == lowercase($pattern)".to_string(), Some(pattern.to_string())));
+    }
+
+    let pattern = if ignore_case { format!("(?i){}", pattern) } else { pattern.to_string() };
+    validate_regex_patterns(use_regex, &[Some(&pattern)])?;
+    Ok((ConditionBuilder::new("name", "pattern").build(use_regex), Some(pattern)))
+}
+
+fn query_modules(
+    db: &cozo::DbInstance,
+    pattern: Option<&str>,
+    project: &str,
+    limit: u32,
+    use_regex: bool,
+    ignore_case: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<ModuleResult>, Box<dyn Error>> {
+    let (match_cond, pattern) = name_match(pattern, use_regex, ignore_case)?;
+    let namespace_cond = settings.namespace_condition("name");
     let script = format!(
         r#"
         ?[project, name, source] := *modules{{project, name, source}},
             project = $project,
             {match_cond}
+            {namespace_cond}
         :limit {limit}
         :order name
         "#,
     );
 
     let mut params = Params::new();
-    params.insert("pattern", DataValue::Str(pattern.into()));
+    if let Some(pattern) = pattern {
+        params.insert("pattern", DataValue::Str(pattern.into()));
+    }
     params.insert("project", DataValue::Str(project.into()));
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| SearchError::QueryFailed {
         message: e.to_string(),
     })?;
@@ -65,37 +210,43 @@ pub fn search_modules(
             let Some(project) = extract_string(&row[0]) else { continue };
             let Some(name) = extract_string(&row[1]) else { continue };
             let source = extract_string_or(&row[2], "unknown");
-            results.push(ModuleResult { project, name, source });
+            results.push(ModuleResult { project, name, source, score: None });
         }
     }
 
     Ok(results)
 }
 
-pub fn search_functions(
+fn query_functions(
     db: &cozo::DbInstance,
-    pattern: &str,
+    pattern: Option<&str>,
     project: &str,
     limit: u32,
     use_regex: bool,
+    ignore_case: bool,
+    settings: &QuerySettings,
 ) -> Result<Vec<FunctionResult>, Box<dyn Error>> {
-    validate_regex_patterns(use_regex, &[Some(pattern)])?;
-
-    let match_cond = ConditionBuilder::new("name", "pattern").build(use_regex);
+    let (match_cond, pattern) = name_match(pattern, use_regex, ignore_case)?;
+    let namespace_cond = settings.namespace_condition("module");
     let script = format!(
         r#"
         ?[project, module, name, arity, return_type] := *functions{{project, module, name, arity, return_type}},
             project = $project,
             {match_cond}
+            {namespace_cond}
         :limit {limit}
         :order module, name, arity
         "#,
     );
 
     let mut params = Params::new();
-    params.insert("pattern", DataValue::Str(pattern.into()));
+    if let Some(pattern) = pattern {
+        params.insert("pattern", DataValue::Str(pattern.into()));
+    }
     params.insert("project", DataValue::Str(project.into()));
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| SearchError::QueryFailed {
         message: e.to_string(),
     })?;
@@ -114,6 +265,7 @@ pub fn search_functions(
                 name,
                 arity,
                 return_type,
+                score: None,
             });
         }
     }
@@ -121,6 +273,84 @@ pub fn search_functions(
     Ok(results)
 }
 
+/// Keep the items scoring at least [`MIN_FUZZY_SCORE`], best first, up to `limit`
+fn rank<T>(
+    items: Vec<T>,
+    pattern: &str,
+    limit: u32,
+    name: impl Fn(&T) -> &str,
+    set_score: impl Fn(&mut T, f64),
+) -> Vec<T> {
+    let mut scored: Vec<(f64, T)> = items
+        .into_iter()
+        .map(|item| (fuzzy_score(pattern, name(&item)), item))
+        .filter(|(score, _)| *score >= MIN_FUZZY_SCORE)
+        .collect();
+    // Stable sort: equal scores keep the query order (by name)
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.truncate(limit as usize);
+
+    scored
+        .into_iter()
+        .map(|(score, mut item)| {
+            set_score(&mut item, (score * 100.0).round() / 100.0);
+            item
+        })
+        .collect()
+}
+
+/// Similarity of a name to a search pattern, from 0 (unrelated) to 1 (same, ignoring case).
+///
+/// The better of Levenshtein and trigram similarity, taken against the full
+/// name and its last segment, so `acounts` still finds `MyApp.Accounts`.
+pub fn fuzzy_score(pattern: &str, name: &str) -> f64 {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    let last_segment = name.rsplit('.').next().unwrap_or(&name);
+
+    [name.as_str(), last_segment]
+        .into_iter()
+        .map(|candidate| levenshtein_similarity(&pattern, candidate).max(trigram_similarity(&pattern, candidate)))
+        .fold(0.0, f64::max)
+}
+
+/// 1 minus the edit distance relative to the longer string
+fn levenshtein_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+/// Jaccard similarity of the padded character trigrams of both strings
+fn trigram_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (trigrams(a), trigrams(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let shared = a.intersection(&b).count();
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+fn trigrams(s: &str) -> std::collections::HashSet<[char; 3]> {
+    let padded: Vec<char> = "  ".chars().chain(s.chars()).chain(" ".chars()).collect();
+    padded.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,7 +360,7 @@ mod tests {
         let db = crate::test_utils::call_graph_db("default");
 
         // Invalid regex pattern: unclosed bracket
-        let result = search_modules(&db, "[invalid", "test_project", 10, true);
+        let result = search_modules(&db, "[invalid", "test_project", 10, true, &QuerySettings::default());
 
         assert!(result.is_err(), "Should reject invalid regex");
         let err = result.unwrap_err();
@@ -144,7 +374,7 @@ mod tests {
         let db = crate::test_utils::call_graph_db("default");
 
         // Invalid regex pattern: invalid repetition
-        let result = search_functions(&db, "*invalid", "test_project", 10, true);
+        let result = search_functions(&db, "*invalid", "test_project", 10, true, &QuerySettings::default());
 
         assert!(result.is_err(), "Should reject invalid regex");
         let err = result.unwrap_err();
@@ -158,7 +388,7 @@ mod tests {
         let db = crate::test_utils::call_graph_db("default");
 
         // Valid regex pattern should not error on validation (may or may not find results)
-        let result = search_modules(&db, "^test.*$", "test_project", 10, true);
+        let result = search_modules(&db, "^test.*$", "test_project", 10, true, &QuerySettings::default());
 
         // Should not fail on validation (may return empty results, that's fine)
         assert!(result.is_ok(), "Should accept valid regex: {:?}", result.err());
@@ -169,7 +399,7 @@ mod tests {
         let db = crate::test_utils::call_graph_db("default");
 
         // Valid regex pattern should not error on validation
-        let result = search_functions(&db, "^get_.*$", "test_project", 10, true);
+        let result = search_functions(&db, "^get_.*$", "test_project", 10, true, &QuerySettings::default());
 
         // Should not fail on validation
         assert!(result.is_ok(), "Should accept valid regex: {:?}", result.err());
@@ -180,7 +410,7 @@ mod tests {
         let db = crate::test_utils::call_graph_db("default");
 
         // Even invalid regex should work in non-regex mode (treated as literal string)
-        let result = search_modules(&db, "[invalid", "test_project", 10, false);
+        let result = search_modules(&db, "[invalid", "test_project", 10, false, &QuerySettings::default());
 
         // Should succeed (no regex validation in non-regex mode)
         assert!(result.is_ok(), "Should accept any pattern in non-regex mode: {:?}", result.err());
@@ -191,9 +421,73 @@ mod tests {
         let db = crate::test_utils::call_graph_db("default");
 
         // Even invalid regex should work in non-regex mode
-        let result = search_functions(&db, "*invalid", "test_project", 10, false);
+        let result = search_functions(&db, "*invalid", "test_project", 10, false, &QuerySettings::default());
 
         // Should succeed (no regex validation in non-regex mode)
         assert!(result.is_ok(), "Should accept any pattern in non-regex mode: {:?}", result.err());
     }
+
+    #[test]
+    fn test_search_modules_ignore_case() {
+        let db = crate::test_utils::call_graph_db("default");
+
+        let exact = search_modules_ignore_case(&db, "myapp.accounts", "default", 10, false, &QuerySettings::default()).unwrap();
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].name, "MyApp.Accounts");
+
+        let regex = search_modules_ignore_case(&db, "^myapp\\.acc", "default", 10, true, &QuerySettings::default()).unwrap();
+        assert_eq!(regex.len(), 1);
+
+        assert!(search_modules(&db, "myapp.accounts", "default", 10, false, &QuerySettings::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_search_modules() {
+        let db = crate::test_utils::call_graph_db("default");
+        let modules = fuzzy_search_modules(&db, "acounts", "default", 10, &QuerySettings::default()).unwrap();
+
+        assert_eq!(modules[0].name, "MyApp.Accounts");
+        assert!(modules[0].score.is_some_and(|s| s >= MIN_FUZZY_SCORE));
+        assert!(modules.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_fuzzy_search_functions() {
+        let db = crate::test_utils::call_graph_db("default");
+        let functions = fuzzy_search_functions(&db, "get_usr", "default", 3, &QuerySettings::default()).unwrap();
+
+        assert!(functions.len() <= 3);
+        assert_eq!(functions[0].name, "get_user");
+    }
+
+    #[test]
+    fn test_suggest_names() {
+        let db = crate::test_utils::call_graph_db("default");
+
+        let modules = suggest_names(&db, "default", Some("MyApp.Acounts"), Some("get_user"), 3, &QuerySettings::default()).unwrap();
+        assert_eq!(modules[0], "MyApp.Accounts");
+
+        let functions = suggest_names(&db, "default", Some("MyApp.Accounts"), Some("get_usr"), 3, &QuerySettings::default()).unwrap();
+        assert_eq!(functions[0], "MyApp.Accounts.get_user/1");
+        assert!(functions.iter().all(|f| f.starts_with("MyApp.Accounts.")));
+
+        // Nothing near names that exist
+        assert!(suggest_names(&db, "default", Some("MyApp.Accounts"), None, 3, &QuerySettings::default()).unwrap().is_empty());
+        assert!(suggest_names(&db, "default", Some("MyApp.Accounts"), Some("get_user"), 3, &QuerySettings::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("Accounts", "MyApp.Accounts"), 1.0);
+        assert!(fuzzy_score("get_usr", "get_user") > fuzzy_score("get_usr", "list_users"));
+        assert!(fuzzy_score("acounts", "MyApp.Accounts") >= MIN_FUZZY_SCORE);
+        assert!(fuzzy_score("invoice", "MyApp.Accounts") < MIN_FUZZY_SCORE);
+    }
+
+    #[test]
+    fn test_levenshtein_similarity() {
+        assert_eq!(levenshtein_similarity("kitten", "kitten"), 1.0);
+        assert_eq!(levenshtein_similarity("kitten", "sitting"), 1.0 - 3.0 / 7.0);
+        assert_eq!(levenshtein_similarity("", ""), 1.0);
+    }
 }
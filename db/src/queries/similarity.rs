@@ -0,0 +1,171 @@
+//! Structural similarity between functions from AST MinHash signatures.
+//!
+//! The extractor emits a MinHash signature per clause (`ast_minhash`). A
+//! function's signature is the element-wise minimum over its clauses, which is
+//! the MinHash of the union of their shingles, so multi-clause functions
+//! compare as a whole. The share of equal positions in two signatures
+//! estimates the Jaccard similarity of their AST shingle sets.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+use crate::query_builders::QuerySettings;
+
+#[derive(Error, Debug)]
+pub enum SimilarityError {
+    #[error("Similarity query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A function with its combined AST MinHash signature
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionFingerprint {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub file: String,
+    /// First line of the first clause
+    pub line: i64,
+    #[serde(skip)]
+    pub signature: Vec<i64>,
+}
+
+/// Fingerprints of every function of `project` that has a MinHash signature
+pub fn find_function_fingerprints(
+    db: &cozo::DbInstance,
+    project: &str,
+    exclude_generated: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<FunctionFingerprint>, Box<dyn Error>> {
+    let generated_filter = if exclude_generated {
+        settings.generated_condition("name", "file", "generated_by")
+    } else {
+        String::new()
+    };
+    let script = format!(
+        r#"
+        ?[module, name, arity, line, file, ast_minhash] :=
+            *function_locations{{project, module, name, arity, line, file, ast_minhash, generated_by}},
+            project == $project,
+            length(ast_minhash) > 0
+            {generated_filter}
+
+        :order module, name, arity, line
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, &script, params).map_err(|e| SimilarityError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    // Rows are ordered by line, so the first clause seen sets file and line
+    let mut functions: BTreeMap<(String, String, i64), FunctionFingerprint> = BTreeMap::new();
+    for row in rows.rows {
+        if row.len() >= 6 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(name) = extract_string(&row[1]) else { continue };
+            let arity = extract_i64(&row[2], 0);
+            let line = extract_i64(&row[3], 0);
+            let file = extract_string(&row[4]).unwrap_or_default();
+            let DataValue::List(values) = &row[5] else { continue };
+            let clause: Vec<i64> = values.iter().map(|v| extract_i64(v, i64::MAX)).collect();
+
+            functions
+                .entry((module.clone(), name.clone(), arity))
+                .and_modify(|f| combine_signatures(&mut f.signature, &clause))
+                .or_insert(FunctionFingerprint {
+                    module,
+                    name,
+                    arity,
+                    file,
+                    line,
+                    signature: clause,
+                });
+        }
+    }
+
+    Ok(functions.into_values().collect())
+}
+
+/// Merge a clause signature into a function signature (element-wise minimum)
+fn combine_signatures(signature: &mut Vec<i64>, clause: &[i64]) {
+    if clause.len() > signature.len() {
+        signature.resize(clause.len(), i64::MAX);
+    }
+    for (value, other) in signature.iter_mut().zip(clause) {
+        *value = (*value).min(*other);
+    }
+}
+
+/// Estimated Jaccard similarity of two signatures (0.0-1.0).
+///
+/// Signatures of different lengths come from different extractor settings and
+/// are compared over their common prefix.
+pub fn minhash_similarity(a: &[i64], b: &[i64]) -> f64 {
+    let len = a.len().min(b.len());
+    if len == 0 {
+        return 0.0;
+    }
+    let equal = a.iter().zip(b).filter(|(x, y)| x == y).count();
+    equal as f64 / len as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIMILAR_JSON: &str = r#"{
+        "structs": {},
+        "calls": [],
+        "function_locations": {
+            "MyApp.Accounts": {
+                "get_user/1:10": {"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "kind": "def",
+                    "line": 10, "start_line": 10, "end_line": 12, "ast_minhash": [1, 2, 3, 4]},
+                "get_user/1:14": {"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "kind": "def",
+                    "line": 14, "start_line": 14, "end_line": 15, "ast_minhash": [5, 1, 3, 9]},
+                "list_users/0:20": {"name": "list_users", "arity": 0, "file": "lib/accounts.ex", "kind": "def",
+                    "line": 20, "start_line": 20, "end_line": 22}
+            },
+            "MyApp.Users": {
+                "fetch/1:5": {"name": "fetch", "arity": 1, "file": "lib/users.ex", "kind": "def",
+                    "line": 5, "start_line": 5, "end_line": 8, "ast_minhash": [1, 1, 3, 7],
+                    "generated_by": "Ecto.Schema"}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_find_function_fingerprints_combines_clauses() {
+        let db = crate::test_utils::setup_test_db(SIMILAR_JSON, "default");
+        let fingerprints = find_function_fingerprints(&db, "default", false, &QuerySettings::default()).expect("Query should succeed");
+
+        // list_users has no signature
+        assert_eq!(fingerprints.len(), 2);
+        let get_user = &fingerprints[0];
+        assert_eq!((get_user.name.as_str(), get_user.line), ("get_user", 10));
+        assert_eq!(get_user.signature, vec![1, 1, 3, 4]);
+    }
+
+    #[test]
+    fn test_find_function_fingerprints_exclude_generated() {
+        let db = crate::test_utils::setup_test_db(SIMILAR_JSON, "default");
+        let fingerprints = find_function_fingerprints(&db, "default", true, &QuerySettings::default()).expect("Query should succeed");
+        assert_eq!(fingerprints.len(), 1);
+        assert_eq!(fingerprints[0].module, "MyApp.Accounts");
+    }
+
+    #[test]
+    fn test_minhash_similarity() {
+        assert_eq!(minhash_similarity(&[1, 1, 3, 4], &[1, 1, 3, 7]), 0.75);
+        assert_eq!(minhash_similarity(&[1, 2], &[1, 2, 3]), 1.0);
+        assert_eq!(minhash_similarity(&[], &[1]), 0.0);
+    }
+}
@@ -0,0 +1,142 @@
+//! Project metrics recorded per import, for trend tracking.
+//!
+//! The import command stores one `metric_snapshots` row after each import, so
+//! `trends` can show how size, unused functions, cycles and complexity evolve
+//! across imports. Snapshots are history: clearing a project's data keeps them.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_f64, extract_i64, extract_string_or, run_query, Params};
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("Snapshot query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// Project metrics at one import
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MetricSnapshot {
+    /// Unix time of the import in milliseconds
+    pub taken_at: i64,
+    /// Free-form label given at import (e.g. a git revision)
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub label: String,
+    pub modules: i64,
+    pub functions: i64,
+    pub calls: i64,
+    pub unused: i64,
+    pub cycles: i64,
+    pub average_complexity: f64,
+}
+
+/// Store a snapshot of `project`, replacing one taken at the same instant
+pub fn record_snapshot(
+    db: &cozo::DbInstance,
+    project: &str,
+    snapshot: &MetricSnapshot,
+) -> Result<(), Box<dyn Error>> {
+    let script = r#"
+        ?[project, taken_at, label, modules, functions, calls, unused, cycles, average_complexity] <-
+            [[$project, $taken_at, $label, $modules, $functions, $calls, $unused, $cycles, $average_complexity]]
+        :put metric_snapshots {project, taken_at => label, modules, functions, calls, unused, cycles, average_complexity}
+    "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("taken_at", DataValue::from(snapshot.taken_at));
+    params.insert("label", DataValue::Str(snapshot.label.as_str().into()));
+    params.insert("modules", DataValue::from(snapshot.modules));
+    params.insert("functions", DataValue::from(snapshot.functions));
+    params.insert("calls", DataValue::from(snapshot.calls));
+    params.insert("unused", DataValue::from(snapshot.unused));
+    params.insert("cycles", DataValue::from(snapshot.cycles));
+    params.insert("average_complexity", DataValue::from(snapshot.average_complexity));
+
+    run_query(db, script, params).map_err(|e| SnapshotError::QueryFailed {
+        message: e.to_string(),
+    })?;
+    Ok(())
+}
+
+/// The latest `limit` snapshots of `project`, oldest first
+pub fn find_snapshots(
+    db: &cozo::DbInstance,
+    project: &str,
+    limit: u32,
+) -> Result<Vec<MetricSnapshot>, Box<dyn Error>> {
+    let script = format!(
+        r#"
+        ?[taken_at, label, modules, functions, calls, unused, cycles, average_complexity] :=
+            *metric_snapshots{{project, taken_at, label, modules, functions, calls, unused, cycles, average_complexity}},
+            project == $project
+
+        :order -taken_at
+        :limit {limit}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, &script, params).map_err(|e| SnapshotError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut snapshots: Vec<MetricSnapshot> = rows
+        .rows
+        .iter()
+        .filter(|row| row.len() >= 8)
+        .map(|row| MetricSnapshot {
+            taken_at: extract_i64(&row[0], 0),
+            label: extract_string_or(&row[1], ""),
+            modules: extract_i64(&row[2], 0),
+            functions: extract_i64(&row[3], 0),
+            calls: extract_i64(&row[4], 0),
+            unused: extract_i64(&row[5], 0),
+            cycles: extract_i64(&row[6], 0),
+            average_complexity: extract_f64(&row[7], 0.0),
+        })
+        .collect();
+    snapshots.reverse();
+
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(taken_at: i64, unused: i64) -> MetricSnapshot {
+        MetricSnapshot {
+            taken_at,
+            label: format!("rev{}", taken_at),
+            modules: 5,
+            functions: 20,
+            calls: 40,
+            unused,
+            cycles: 1,
+            average_complexity: 2.5,
+        }
+    }
+
+    #[test]
+    fn test_find_snapshots_returns_latest_oldest_first() {
+        let db = crate::test_utils::setup_test_db(r#"{"structs": {}, "calls": [], "function_locations": {}}"#, "default");
+        for (taken_at, unused) in [(100, 9), (300, 5), (200, 7)] {
+            record_snapshot(&db, "default", &snapshot(taken_at, unused)).expect("Record should succeed");
+        }
+        record_snapshot(&db, "other", &snapshot(400, 1)).expect("Record should succeed");
+
+        let snapshots = find_snapshots(&db, "default", 10).expect("Query should succeed");
+        assert_eq!(snapshots.iter().map(|s| s.unused).collect::<Vec<_>>(), [9, 7, 5]);
+        assert_eq!(snapshots[0], snapshot(100, 9));
+
+        let latest = find_snapshots(&db, "default", 2).expect("Query should succeed");
+        assert_eq!(latest.iter().map(|s| s.taken_at).collect::<Vec<_>>(), [200, 300]);
+    }
+}
@@ -0,0 +1,303 @@
+//! @spec coverage of defined functions and specs that no longer match a definition.
+//!
+//! Both queries join `function_locations` (what is defined) with `specs` (what is
+//! declared). Only `@spec` entries count; `@callback` and `@macrocallback`
+//! declare a behaviour's contract rather than a function in the module.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_bool, extract_i64, extract_string, run_query, Params};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum SpecCoverageError {
+    #[error("Spec coverage query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A defined function and whether it has a @spec
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionSpecStatus {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub kind: String,
+    pub file: String,
+    pub line: i64,
+    pub has_spec: bool,
+}
+
+/// A @spec without a function definition of the same arity
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecDrift {
+    pub module: String,
+    pub name: String,
+    /// Arity declared by the spec
+    pub spec_arity: i64,
+    /// Line of the spec
+    pub line: i64,
+    /// Arities the function is actually defined with (empty when the function is gone)
+    pub defined_arities: Vec<i64>,
+}
+
+/// Find functions with their spec status, ordered by module, name and arity.
+///
+/// Generated functions are skipped; private ones only with `include_private`.
+pub fn find_spec_coverage(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    include_private: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<FunctionSpecStatus>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+    let kinds = if include_private {
+        r#"["def", "defmacro", "defp", "defmacrop"]"#
+    } else {
+        r#"["def", "defmacro"]"#
+    };
+
+    let order = settings.order_by("module, name, arity");
+    let script = format!(
+        r#"
+        defs[module, name, arity, kind, file, min(start_line)] :=
+            *function_locations{{project, module, name, arity, kind, file, start_line, generated_by}},
+            project == $project,
+            is_in(kind, {kinds}),
+            generated_by == ""
+            {module_cond}
+            {namespace_cond}
+
+        specced[module, name, arity] :=
+            *specs{{project, module, name, arity, kind}},
+            project == $project,
+            kind == "spec"
+
+        ?[module, name, arity, kind, file, line, has_spec] :=
+            defs[module, name, arity, kind, file, line],
+            specced[module, name, arity],
+            has_spec = true
+        ?[module, name, arity, kind, file, line, has_spec] :=
+            defs[module, name, arity, kind, file, line],
+            not specced[module, name, arity],
+            has_spec = false
+
+        {order}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| SpecCoverageError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 7 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(name) = extract_string(&row[1]) else { continue };
+            let arity = extract_i64(&row[2], 0);
+            let Some(kind) = extract_string(&row[3]) else { continue };
+            let Some(file) = extract_string(&row[4]) else { continue };
+            let line = extract_i64(&row[5], 0);
+            let has_spec = extract_bool(&row[6], false);
+
+            results.push(FunctionSpecStatus {
+                module,
+                name,
+                arity,
+                kind,
+                file,
+                line,
+                has_spec,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Find @specs whose arity matches no definition of the function.
+///
+/// These are specs left behind after an arity change or a removal. Each one
+/// lists the arities the function is still defined with.
+pub fn find_spec_drift(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<SpecDrift>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+
+    let order = settings.order_by("module, name, arity");
+    let script = format!(
+        r#"
+        spec[module, name, arity, line] :=
+            *specs{{project, module, name, arity, kind, line}},
+            project == $project,
+            kind == "spec"
+            {module_cond}
+            {namespace_cond}
+
+        defined[module, name, arity] :=
+            *function_locations{{project, module, name, arity}},
+            project == $project
+
+        drift[module, name, arity, line] :=
+            spec[module, name, arity, line],
+            not defined[module, name, arity]
+
+        arities[module, name, collect(arity)] :=
+            drift[module, name, _, _],
+            defined[module, name, arity]
+
+        ?[module, name, arity, line, defined_arities] :=
+            drift[module, name, arity, line],
+            arities[module, name, defined_arities]
+        ?[module, name, arity, line, defined_arities] :=
+            drift[module, name, arity, line],
+            not arities[module, name, _],
+            defined_arities = []
+
+        {order}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| SpecCoverageError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 5 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(name) = extract_string(&row[1]) else { continue };
+            let spec_arity = extract_i64(&row[2], 0);
+            let line = extract_i64(&row[3], 0);
+            let mut defined_arities: Vec<i64> = match &row[4] {
+                DataValue::List(values) => values.iter().map(|v| extract_i64(v, 0)).collect(),
+                _ => Vec::new(),
+            };
+            defined_arities.sort_unstable();
+            defined_arities.dedup();
+
+            results.push(SpecDrift {
+                module,
+                name,
+                spec_arity,
+                line,
+                defined_arities,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DRIFT_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Users": {
+                "create/2:5": {"name": "create", "arity": 2, "file": "lib/users.ex", "kind": "def", "line": 5, "start_line": 5, "end_line": 9},
+                "delete/1:12": {"name": "delete", "arity": 1, "file": "lib/users.ex", "kind": "def", "line": 12, "start_line": 12, "end_line": 14}
+            }
+        },
+        "calls": [],
+        "specs": {
+            "MyApp.Users": [
+                {"name": "create", "arity": 1, "kind": "spec", "line": 4, "clauses": []},
+                {"name": "delete", "arity": 1, "kind": "spec", "line": 11, "clauses": []},
+                {"name": "archive", "arity": 1, "kind": "spec", "line": 20, "clauses": []},
+                {"name": "handle", "arity": 3, "kind": "callback", "line": 2, "clauses": []}
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_find_spec_coverage() {
+        let db = crate::test_utils::call_graph_db("default");
+        let functions = find_spec_coverage(&db, "default", Some("MyApp.Accounts"), false, false, &QuerySettings::default())
+            .expect("Query should succeed");
+
+        let status: Vec<(&str, i64, bool)> = functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.arity, f.has_spec))
+            .collect();
+        assert_eq!(
+            status,
+            [("get_user", 1, true), ("get_user", 2, false), ("list_users", 0, true)]
+        );
+    }
+
+    #[test]
+    fn test_find_spec_coverage_private_and_callbacks() {
+        let db = crate::test_utils::call_graph_db("default");
+
+        let functions = find_spec_coverage(&db, "default", Some("MyApp.Accounts"), false, true, &QuerySettings::default())
+            .expect("Query should succeed");
+        assert!(functions.iter().any(|f| f.name == "validate_email" && f.kind == "defp"));
+
+        // A @callback does not count as a spec for the function
+        let functions = find_spec_coverage(&db, "default", Some("MyApp.Repo"), false, false, &QuerySettings::default())
+            .expect("Query should succeed");
+        assert!(functions.iter().all(|f| !f.has_spec));
+    }
+
+    #[test]
+    fn test_find_spec_drift() {
+        let db = crate::test_utils::setup_test_db(DRIFT_JSON, "default");
+        let drift = find_spec_drift(&db, "default", None, false, &QuerySettings::default()).expect("Query should succeed");
+
+        assert_eq!(drift.len(), 2);
+        assert_eq!(drift[0].name, "archive");
+        assert!(drift[0].defined_arities.is_empty());
+        assert_eq!(drift[1].name, "create");
+        assert_eq!(drift[1].spec_arity, 1);
+        assert_eq!(drift[1].line, 4);
+        assert_eq!(drift[1].defined_arities, [2]);
+    }
+
+    #[test]
+    fn test_find_spec_drift_none_in_fixture() {
+        let db = crate::test_utils::call_graph_db("default");
+        let drift = find_spec_drift(&db, "default", None, false, &QuerySettings::default()).expect("Query should succeed");
+        assert!(drift.is_empty());
+    }
+}
@@ -5,7 +5,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder};
+use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder, QuerySettings};
 
 #[derive(Error, Debug)]
 pub enum SpecsError {
@@ -27,6 +27,7 @@ pub struct SpecDef {
     pub full: String,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn find_specs(
     db: &cozo::DbInstance,
     module_pattern: &str,
@@ -35,11 +36,13 @@ pub fn find_specs(
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<SpecDef>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[Some(module_pattern), function_pattern])?;
 
     // Build conditions using query builders
     let module_cond = ConditionBuilder::new("module", "module_pattern").build(use_regex);
+    let namespace_cond = settings.namespace_condition("module");
     let function_cond = OptionalConditionBuilder::new("name", "function_pattern")
         .with_leading_comma()
         .with_regex()
@@ -54,6 +57,7 @@ pub fn find_specs(
             *specs{{project, module, name, arity, kind, line, inputs_string, return_string, full}},
             project == $project,
             {module_cond}
+            {namespace_cond}
             {function_cond}
             {kind_cond}
 
@@ -77,6 +81,8 @@ pub fn find_specs(
         params.insert("kind", DataValue::Str(kind.into()));
     }
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| SpecsError::QueryFailed {
         message: e.to_string(),
     })?;
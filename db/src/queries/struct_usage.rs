@@ -5,7 +5,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
 
 #[derive(Error, Debug)]
 pub enum StructUsageError {
@@ -32,6 +32,7 @@ pub fn find_struct_usage(
     use_regex: bool,
     module_pattern: Option<&str>,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<StructUsageEntry>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[Some(pattern), module_pattern])?;
 
@@ -47,6 +48,7 @@ pub fn find_struct_usage(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     let script = format!(
         r#"
@@ -55,6 +57,7 @@ pub fn find_struct_usage(
             project == $project,
             {match_cond}
             {module_cond}
+            {namespace_cond}
 
         :order module, name, arity
         :limit {limit}
@@ -72,6 +75,8 @@ pub fn find_struct_usage(
         );
     }
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| StructUsageError::QueryFailed {
         message: e.to_string(),
     })?;
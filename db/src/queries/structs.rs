@@ -5,7 +5,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_bool, extract_string, extract_string_or, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder};
+use crate::query_builders::{validate_regex_patterns, ConditionBuilder, QuerySettings};
 
 #[derive(Error, Debug)]
 pub enum StructError {
@@ -47,10 +47,12 @@ pub fn find_struct_fields(
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<StructField>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[Some(module_pattern)])?;
 
     let module_cond = ConditionBuilder::new("module", "module_pattern").build(use_regex);
+    let namespace_cond = settings.namespace_condition("module");
 
     let project_cond = ", project == $project";
 
@@ -59,6 +61,7 @@ pub fn find_struct_fields(
         ?[project, module, field, default_value, required, inferred_type] :=
             *struct_fields{{project, module, field, default_value, required, inferred_type}},
             {module_cond}
+            {namespace_cond}
             {project_cond}
         :order module, field
         :limit {limit}
@@ -69,6 +72,8 @@ pub fn find_struct_fields(
     params.insert("module_pattern", DataValue::Str(module_pattern.into()));
     params.insert("project", DataValue::Str(project.into()));
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| StructError::QueryFailed {
         message: e.to_string(),
     })?;
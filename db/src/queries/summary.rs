@@ -0,0 +1,111 @@
+//! Project-wide totals for the summary report.
+//!
+//! Counts modules, function definitions and call sites of a project. The other
+//! figures of the summary (hotspots, god modules, cycles, ...) come from their
+//! own query modules.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, run_query, Params};
+
+#[derive(Error, Debug)]
+pub enum SummaryError {
+    #[error("Summary query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// Size of a project's call graph
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ProjectCounts {
+    pub modules: i64,
+    /// Distinct function definitions (module, name, arity)
+    pub functions: i64,
+    /// Call sites
+    pub calls: i64,
+}
+
+/// Count the modules, functions and calls of a project.
+pub fn find_project_counts(db: &cozo::DbInstance, project: &str) -> Result<ProjectCounts, Box<dyn Error>> {
+    let modules = count(
+        db,
+        project,
+        r#"
+        ?[count(name)] := *modules{project, name}, project == $project
+        "#,
+    )?;
+
+    let functions = count(
+        db,
+        project,
+        r#"
+        defs[module, name, arity] := *function_locations{project, module, name, arity}, project == $project
+        ?[count(name)] := defs[module, name, arity]
+        "#,
+    )?;
+
+    let calls = count(
+        db,
+        project,
+        r#"
+        ?[count(caller_module)] :=
+            *calls{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column},
+            project == $project
+        "#,
+    )?;
+
+    Ok(ProjectCounts {
+        modules,
+        functions,
+        calls,
+    })
+}
+
+fn count(db: &cozo::DbInstance, project: &str, script: &str) -> Result<i64, Box<dyn Error>> {
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| SummaryError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    Ok(rows
+        .rows
+        .first()
+        .and_then(|row| row.first())
+        .map(|v| extract_i64(v, 0))
+        .unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn populated_db() -> cozo::DbInstance {
+        crate::test_utils::call_graph_db("default")
+    }
+
+    #[rstest]
+    fn test_find_project_counts(populated_db: cozo::DbInstance) {
+        let counts = find_project_counts(&populated_db, "default").expect("Query should succeed");
+        assert_eq!(
+            counts,
+            ProjectCounts {
+                modules: 5,
+                functions: 15,
+                calls: 11,
+            }
+        );
+    }
+
+    #[rstest]
+    fn test_find_project_counts_wrong_project(populated_db: cozo::DbInstance) {
+        let counts = find_project_counts(&populated_db, "other").expect("Query should succeed");
+        assert_eq!(counts, ProjectCounts::default());
+    }
+}
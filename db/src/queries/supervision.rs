@@ -0,0 +1,184 @@
+//! OTP supervision tree edges and the call-graph footprint of their modules.
+//!
+//! The `supervision` relation holds supervisor -> child spec edges imported from
+//! a runtime or static dump. Footprints come from the `module_stats` aggregates,
+//! so only modules that are part of the imported call graph have one.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+
+#[derive(Error, Debug)]
+pub enum SupervisionError {
+    #[error("Supervision query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A child spec under a supervisor
+#[derive(Debug, Clone, Serialize)]
+pub struct SupervisionChild {
+    pub supervisor: String,
+    /// Restart strategy of the supervisor (empty when unknown)
+    pub strategy: String,
+    pub child_id: String,
+    pub child_module: String,
+    pub child_type: String,
+    pub restart: String,
+    /// Start order within the supervisor
+    pub position: i64,
+}
+
+/// Size and connectivity of a module in the call graph
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleFootprint {
+    pub file: String,
+    pub function_count: i64,
+    pub loc: i64,
+    pub incoming: i64,
+    pub outgoing: i64,
+}
+
+/// Find all supervision edges of a project, ordered by supervisor and start order
+pub fn find_supervision_children(
+    db: &cozo::DbInstance,
+    project: &str,
+) -> Result<Vec<SupervisionChild>, Box<dyn Error>> {
+    let script = r#"
+        ?[supervisor, strategy, child_id, child_module, child_type, restart, position] :=
+            *supervision{project, supervisor, child_id, child_module, child_type, restart, strategy, position},
+            project == $project
+
+        :order supervisor, position
+        "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| SupervisionError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 7 {
+            let Some(supervisor) = extract_string(&row[0]) else { continue };
+            let Some(strategy) = extract_string(&row[1]) else { continue };
+            let Some(child_id) = extract_string(&row[2]) else { continue };
+            let Some(child_module) = extract_string(&row[3]) else { continue };
+            let Some(child_type) = extract_string(&row[4]) else { continue };
+            let Some(restart) = extract_string(&row[5]) else { continue };
+            let position = extract_i64(&row[6], 0);
+
+            results.push(SupervisionChild {
+                supervisor,
+                strategy,
+                child_id,
+                child_module,
+                child_type,
+                restart,
+                position,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Footprints of every supervisor and child module that is in the call graph
+pub fn find_supervision_footprints(
+    db: &cozo::DbInstance,
+    project: &str,
+) -> Result<BTreeMap<String, ModuleFootprint>, Box<dyn Error>> {
+    let script = r#"
+        tree_module[module] :=
+            *supervision{project, supervisor: module},
+            project == $project
+        tree_module[module] :=
+            *supervision{project, child_module: module},
+            project == $project,
+            module != ""
+
+        ?[module, file, function_count, loc, incoming, outgoing] :=
+            tree_module[module],
+            *module_stats{project, module, function_count, loc, incoming, outgoing},
+            *function_locations{project, module, file},
+            project == $project
+
+        :order module, file
+        "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| SupervisionError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = BTreeMap::new();
+    for row in rows.rows {
+        if row.len() >= 6 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(file) = extract_string(&row[1]) else { continue };
+
+            // A module spread over several files is reported with its first file
+            results.entry(module).or_insert_with(|| ModuleFootprint {
+                file,
+                function_count: extract_i64(&row[2], 0),
+                loc: extract_i64(&row[3], 0),
+                incoming: extract_i64(&row[4], 0),
+                outgoing: extract_i64(&row[5], 0),
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_supervision_children() {
+        let db = crate::test_utils::call_graph_db("default");
+        let children = find_supervision_children(&db, "default").expect("Query should succeed");
+
+        assert_eq!(children.len(), 4);
+
+        let ids: Vec<&str> = children.iter().map(|c| c.child_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            ["MyApp.Repo", "MyApp.Notifier", "MyApp.WorkerSupervisor", "MyApp.Service"]
+        );
+        assert_eq!(children[0].supervisor, "MyApp.Application");
+        assert_eq!(children[0].strategy, "one_for_one");
+        assert_eq!(children[2].child_type, "supervisor");
+        assert_eq!(children[3].restart, "transient");
+    }
+
+    #[test]
+    fn test_find_supervision_footprints() {
+        let db = crate::test_utils::call_graph_db("default");
+        let footprints = find_supervision_footprints(&db, "default").expect("Query should succeed");
+
+        // Application and WorkerSupervisor have no functions in the call graph
+        let modules: Vec<&str> = footprints.keys().map(String::as_str).collect();
+        assert_eq!(modules, ["MyApp.Notifier", "MyApp.Repo", "MyApp.Service"]);
+
+        let repo = &footprints["MyApp.Repo"];
+        assert_eq!(repo.file, "lib/my_app/repo.ex");
+        assert_eq!(repo.function_count, 3);
+    }
+
+    #[test]
+    fn test_find_supervision_other_project() {
+        let db = crate::test_utils::call_graph_db("default");
+        let children = find_supervision_children(&db, "other").expect("Query should succeed");
+        assert!(children.is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+//! Which tests reach a function, and what a test file reaches.
+//!
+//! Both directions walk function-level call edges: a call belongs to the
+//! function whose clause spans its line. Tests are the functions of modules
+//! marked `is_test` at import. Each function is reported once, at the
+//! shortest depth it is reached.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{run_query, Params, TypedRows};
+use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum TestSurfaceError {
+    #[error("Test surface query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A function reached from, or reaching, the starting point
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SurfaceFunction {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    /// Calls between it and the starting point, 1 for a direct call
+    pub depth: i64,
+    pub file: String,
+    /// First line of its first clause
+    pub line: i64,
+}
+
+crate::from_row!(SurfaceFunction {
+    module: String,
+    name: String,
+    arity: i64,
+    depth: i64,
+    file: String,
+    line: i64 = "min(line)",
+});
+
+/// Function-level call edges of the project, each call attributed to the
+/// clause spanning its line
+const EDGES: &str = r#"
+        edge[caller_module, caller_name, caller_arity, callee_module, callee_function, callee_arity] :=
+            *calls{project, caller_module, caller_function, callee_module, callee_function, callee_arity, line},
+            *function_locations{project, module: caller_module, name: caller_name, arity: caller_arity, start_line, end_line},
+            project == $project,
+            (caller_function == caller_name or starts_with(caller_function, concat(caller_name, "/"))),
+            line >= start_line,
+            line <= end_line
+"#;
+
+/// Find the test functions calling a function, or any function of a module,
+/// directly or through up to `max_depth - 1` other functions.
+#[allow(clippy::too_many_arguments)]
+pub fn find_covering_tests(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: &str,
+    function_pattern: Option<&str>,
+    arity: Option<i64>,
+    use_regex: bool,
+    max_depth: u32,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<SurfaceFunction>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[Some(module_pattern), function_pattern])?;
+
+    let module_cond = ConditionBuilder::new("module", "module_pattern").build(use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+    let function_cond = OptionalConditionBuilder::new("name", "function_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(function_pattern.is_some(), use_regex);
+    let arity_cond = OptionalConditionBuilder::new("arity", "arity")
+        .with_leading_comma()
+        .build(arity.is_some());
+
+    let script = format!(
+        r#"
+        {EDGES}
+        target[module, name, arity] :=
+            *function_locations{{project, module, name, arity}},
+            project == $project,
+            {module_cond}
+            {namespace_cond}
+            {function_cond}
+            {arity_cond}
+
+        reach[module, name, arity, min(depth)] :=
+            target[target_module, target_name, target_arity],
+            edge[module, name, arity, target_module, target_name, target_arity],
+            depth = 1
+        reach[module, name, arity, min(depth)] :=
+            reach[callee_module, callee_name, callee_arity, prev_depth],
+            edge[module, name, arity, callee_module, callee_name, callee_arity],
+            prev_depth < {max_depth},
+            depth = prev_depth + 1
+
+        ?[module, name, arity, depth, file, min(line)] :=
+            reach[module, name, arity, depth],
+            *modules{{project: $project, name: module, is_test}},
+            is_test == true,
+            *function_locations{{project: $project, module, name, arity, file, line}}
+
+        :order depth, module, name, arity
+        :limit {limit}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("module_pattern", DataValue::Str(module_pattern.into()));
+    if let Some(function) = function_pattern {
+        params.insert("function_pattern", DataValue::Str(function.into()));
+    }
+    if let Some(arity) = arity {
+        params.insert("arity", DataValue::from(arity));
+    }
+    settings.bind_namespaces(&script, &mut params);
+    run(db, &script, params)
+}
+
+/// Find the non-test functions the functions defined in a test file call,
+/// directly or through up to `max_depth - 1` other functions.
+///
+/// Functions without a location, such as stdlib ones, end the walk.
+pub fn find_reached_functions(
+    db: &cozo::DbInstance,
+    project: &str,
+    file: &str,
+    max_depth: u32,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<SurfaceFunction>, Box<dyn Error>> {
+    let scope_cond = settings.project_scope_condition("module");
+
+    let script = format!(
+        r#"
+        {EDGES}
+        root[module, name, arity] :=
+            *function_locations{{project, module, name, arity, file}},
+            project == $project,
+            file == $file
+
+        reach[module, name, arity, min(depth)] :=
+            root[root_module, root_name, root_arity],
+            edge[root_module, root_name, root_arity, module, name, arity],
+            depth = 1
+        reach[module, name, arity, min(depth)] :=
+            reach[caller_module, caller_name, caller_arity, prev_depth],
+            edge[caller_module, caller_name, caller_arity, module, name, arity],
+            prev_depth < {max_depth},
+            depth = prev_depth + 1
+
+        ?[module, name, arity, depth, file, min(line)] :=
+            reach[module, name, arity, depth],
+            *function_locations{{project: $project, module, name, arity, file, line}},
+            not *modules{{project: $project, name: module, is_test: true}}
+            {scope_cond}
+
+        :order depth, module, name, arity
+        :limit {limit}
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("file", DataValue::Str(file.strip_prefix("./").unwrap_or(file).into()));
+    run(db, &script, params)
+}
+
+fn run(db: &cozo::DbInstance, script: &str, params: Params) -> Result<Vec<SurfaceFunction>, Box<dyn Error>> {
+    let rows = run_query(db, script, params).map_err(|e| TestSurfaceError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    Ok(rows.into_typed()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SURFACE_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Accounts": {
+                "register/1:3": {"name": "register", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 3, "start_line": 3, "end_line": 6}
+            },
+            "MyApp.Repo": {
+                "insert/1:2": {"name": "insert", "arity": 1, "file": "lib/repo.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 4},
+                "get/1:6": {"name": "get", "arity": 1, "file": "lib/repo.ex", "kind": "def", "line": 6, "start_line": 6, "end_line": 7}
+            },
+            "MyApp.AccountsTest": {
+                "test_register/0:4": {"name": "test_register", "arity": 0, "file": "test/accounts_test.exs", "kind": "def", "line": 4, "start_line": 4, "end_line": 8}
+            },
+            "MyApp.Factory": {
+                "insert_user/0:2": {"name": "insert_user", "arity": 0, "file": "test/support/factory.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 3}
+            }
+        },
+        "calls": [
+            {"caller": {"module": "MyApp.Accounts", "function": "register/1", "file": "lib/accounts.ex", "line": 4}, "type": "remote", "callee": {"module": "MyApp.Repo", "function": "insert", "arity": 1}},
+            {"caller": {"module": "MyApp.Accounts", "function": "register/1", "file": "lib/accounts.ex", "line": 5}, "type": "remote", "callee": {"module": "Map", "function": "put", "arity": 3}},
+            {"caller": {"module": "MyApp.AccountsTest", "function": "test_register/0", "file": "test/accounts_test.exs", "line": 5}, "type": "remote", "callee": {"module": "MyApp.Accounts", "function": "register", "arity": 1}},
+            {"caller": {"module": "MyApp.AccountsTest", "function": "test_register/0", "file": "test/accounts_test.exs", "line": 6}, "type": "remote", "callee": {"module": "MyApp.Factory", "function": "insert_user", "arity": 0}},
+            {"caller": {"module": "MyApp.Factory", "function": "insert_user/0", "file": "test/support/factory.ex", "line": 3}, "type": "remote", "callee": {"module": "MyApp.Repo", "function": "insert", "arity": 1}}
+        ]
+    }"#;
+
+    fn names(functions: &[SurfaceFunction]) -> Vec<String> {
+        functions
+            .iter()
+            .map(|f| format!("{}.{}/{}@{}", f.module, f.name, f.arity, f.depth))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_covering_tests() {
+        let db = crate::test_utils::setup_test_db(SURFACE_JSON, "default");
+        let tests = find_covering_tests(&db, "default", "MyApp.Repo", Some("insert"), Some(1), false, 5, 100, &QuerySettings::default()).unwrap();
+        // The factory calls Repo.insert directly and the test through it as well
+        assert_eq!(
+            names(&tests),
+            ["MyApp.Factory.insert_user/0@1", "MyApp.AccountsTest.test_register/0@2"]
+        );
+        assert_eq!(tests[1].file, "test/accounts_test.exs");
+        assert_eq!(tests[1].line, 4);
+
+        let shallow = find_covering_tests(&db, "default", "MyApp.Repo", None, None, false, 1, 100, &QuerySettings::default()).unwrap();
+        assert_eq!(names(&shallow), ["MyApp.Factory.insert_user/0@1"]);
+        assert!(find_covering_tests(&db, "default", "MyApp.Repo", Some("get"), None, false, 5, 100, &QuerySettings::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_find_reached_functions() {
+        let db = crate::test_utils::setup_test_db(SURFACE_JSON, "default");
+        let reached = find_reached_functions(&db, "default", "./test/accounts_test.exs", 5, 100, &QuerySettings::default()).unwrap();
+        // The factory is test code; Map.put has no location
+        assert_eq!(names(&reached), ["MyApp.Accounts.register/1@1", "MyApp.Repo.insert/1@2"]);
+        assert_eq!(reached[1].file, "lib/repo.ex");
+    }
+}
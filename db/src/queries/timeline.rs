@@ -0,0 +1,298 @@
+//! When functions appeared and last changed.
+//!
+//! Function clauses carry `modified_at` and `commit`, supplied by the
+//! extractor or filled in by the import; `function_history` keeps the first
+//! time each function was seen across imports. `age` combines both with
+//! complexity and fan-in.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, extract_string_or, run_query, Params};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum TimelineError {
+    #[error("Timeline query failed: {message}")]
+    QueryFailed { message: String },
+
+    #[error("Failed to update function history: {message}")]
+    HistoryFailed { message: String },
+}
+
+/// Modification of a stored clause, by its source hash
+#[derive(Debug, Clone)]
+pub struct ClauseModification {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub source_sha: String,
+    pub modified_at: i64,
+    pub commit: String,
+}
+
+/// A function with its timeline, complexity and fan-in
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionAge {
+    pub module: String,
+    pub name: String,
+    pub arity: i64,
+    pub file: String,
+    pub line: i64,
+    /// Cyclomatic complexity summed over clauses
+    pub complexity: i64,
+    /// Distinct calling functions
+    pub fan_in: i64,
+    /// Earliest time the function was seen, in Unix seconds (0 when unknown)
+    pub first_seen: i64,
+    /// Latest change of any clause, in Unix seconds (0 when unknown)
+    pub modified_at: i64,
+    /// Revision of the latest change
+    pub commit: String,
+}
+
+/// Record the first time each function of a project was seen.
+///
+/// Functions already in the history keep the earliest time; new ones get the
+/// earliest `modified_at` of their clauses, or `now` when none is known.
+pub fn update_function_history(db: &cozo::DbInstance, project: &str, now: i64) -> Result<usize, Box<dyn Error>> {
+    let script = r#"
+        seen[module, name, arity, t] :=
+            *function_locations{project, module, name, arity, modified_at},
+            project == $project,
+            t = if(modified_at > 0, modified_at, $now)
+        seen[module, name, arity, t] :=
+            *function_locations{project, module, name, arity},
+            *function_history{project, module, name, arity, first_seen: t},
+            project == $project
+
+        earliest[module, name, arity, min(t)] := seen[module, name, arity, t]
+
+        ?[project, module, name, arity, first_seen] :=
+            earliest[module, name, arity, first_seen],
+            project = $project
+
+        :put function_history {project, module, name, arity => first_seen}
+    "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    params.insert("now", DataValue::from(now));
+
+    let rows = run_query(db, script, params).map_err(|e| TimelineError::HistoryFailed {
+        message: e.to_string(),
+    })?;
+    Ok(rows.rows.len())
+}
+
+/// Stored clauses with a source hash and a known modification
+pub fn find_clause_modifications(
+    db: &cozo::DbInstance,
+    project: &str,
+) -> Result<Vec<ClauseModification>, Box<dyn Error>> {
+    let script = r#"
+        ?[module, name, arity, source_sha, max(modified_at), commit] :=
+            *function_locations{project, module, name, arity, source_sha, modified_at, commit},
+            project == $project,
+            source_sha != "",
+            modified_at > 0
+    "#;
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+
+    let rows = run_query(db, script, params).map_err(|e| TimelineError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 6 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(name) = extract_string(&row[1]) else { continue };
+            let Some(source_sha) = extract_string(&row[3]) else { continue };
+            results.push(ClauseModification {
+                module,
+                name,
+                arity: extract_i64(&row[2], 0),
+                source_sha,
+                modified_at: extract_i64(&row[4], 0),
+                commit: extract_string_or(&row[5], ""),
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Timeline of every function of a project, one entry per module, name and arity
+pub fn find_function_ages(
+    db: &cozo::DbInstance,
+    project: &str,
+    module_pattern: Option<&str>,
+    use_regex: bool,
+    exclude_generated: bool,
+    settings: &QuerySettings,
+) -> Result<Vec<FunctionAge>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+    let generated_filter = if exclude_generated {
+        settings.generated_condition("name", "file", "generated_by")
+    } else {
+        String::new()
+    };
+
+    // Clauses are returned individually; the latest change and the first
+    // clause's location are picked per function below
+    let script = format!(
+        r#"
+        first_seen_of[module, name, arity, first_seen] :=
+            *function_history{{project, module, name, arity, first_seen}},
+            project == $project
+        first_seen_of[module, name, arity, first_seen] :=
+            *function_locations{{project, module, name, arity}},
+            project == $project,
+            not *function_history{{project, module, name, arity}},
+            first_seen = 0
+
+        fan_in_of[module, name, incoming] :=
+            *function_stats{{project, module, function: name, incoming}},
+            project == $project
+        fan_in_of[module, name, incoming] :=
+            *function_locations{{project, module, name}},
+            project == $project,
+            not *function_stats{{project, module, function: name}},
+            incoming = 0
+
+        ?[module, name, arity, line, file, complexity, modified_at, commit, first_seen, fan_in] :=
+            *function_locations{{project, module, name, arity, line, file, complexity, modified_at, commit, generated_by}},
+            project == $project,
+            first_seen_of[module, name, arity, first_seen],
+            fan_in_of[module, name, fan_in]
+            {module_cond}
+            {namespace_cond}
+            {generated_filter}
+
+        :order module, name, arity, line
+        "#,
+    );
+
+    let mut params = Params::new();
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(pattern) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(pattern.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| TimelineError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results: Vec<FunctionAge> = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 10 {
+            let Some(module) = extract_string(&row[0]) else { continue };
+            let Some(name) = extract_string(&row[1]) else { continue };
+            let arity = extract_i64(&row[2], 0);
+            let complexity = extract_i64(&row[5], 0);
+            let modified_at = extract_i64(&row[6], 0);
+            let commit = extract_string_or(&row[7], "");
+
+            match results.last_mut() {
+                Some(last) if last.module == module && last.name == name && last.arity == arity => {
+                    last.complexity += complexity;
+                    if modified_at > last.modified_at {
+                        last.modified_at = modified_at;
+                        last.commit = commit;
+                    }
+                }
+                _ => results.push(FunctionAge {
+                    module,
+                    name,
+                    arity,
+                    file: extract_string_or(&row[4], ""),
+                    line: extract_i64(&row[3], 0),
+                    complexity,
+                    fan_in: extract_i64(&row[9], 0),
+                    first_seen: extract_i64(&row[8], 0),
+                    modified_at,
+                    commit,
+                }),
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::import::import_json_str;
+
+    fn graph_json(get_user_sha: &str, get_user_modified: i64, with_load: bool) -> String {
+        let load = if with_load {
+            r#", "load/1:9": {"name": "load", "arity": 1, "file": "lib/accounts.ex", "kind": "defp", "line": 9, "start_line": 9, "end_line": 11, "complexity": 2}"#
+        } else {
+            ""
+        };
+        format!(
+            r#"{{
+                "structs": {{}},
+                "function_locations": {{
+                    "MyApp.Accounts": {{
+                        "get_user/1:2": {{"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 4, "complexity": 3, "source_sha": "{get_user_sha}", "modified_at": {get_user_modified}, "commit": "c{get_user_modified}"}},
+                        "get_user/1:5": {{"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 5, "start_line": 5, "end_line": 7, "complexity": 1, "modified_at": 100, "commit": "c100"}}{load}
+                    }}
+                }},
+                "calls": [
+                    {{"caller": {{"module": "MyApp.Web", "function": "show/1", "file": "lib/web.ex", "line": 3, "column": 5}}, "type": "remote", "callee": {{"module": "MyApp.Accounts", "function": "get_user", "arity": 1}}}}
+                ]
+            }}"#
+        )
+    }
+
+    fn import(db: &cozo::DbInstance, json: &str) {
+        import_json_str(db, json, "default").expect("Import should succeed");
+    }
+
+    #[test]
+    fn test_function_ages_aggregate_clauses() {
+        let db = crate::test_utils::setup_test_db(&graph_json("a1", 200, true), "default");
+        let ages = find_function_ages(&db, "default", None, false, false, &QuerySettings::default()).unwrap();
+
+        let get_user = ages.iter().find(|a| a.name == "get_user").unwrap();
+        assert_eq!((get_user.line, get_user.complexity, get_user.fan_in), (2, 4, 1));
+        assert_eq!((get_user.modified_at, get_user.commit.as_str()), (200, "c200"));
+        assert_eq!(get_user.first_seen, 100);
+
+        // Without a known modification, a function is first seen at import time
+        let load = ages.iter().find(|a| a.name == "load").unwrap();
+        assert_eq!((load.modified_at, load.fan_in), (0, 0));
+        assert!(load.first_seen > 1_000_000_000);
+    }
+
+    #[test]
+    fn test_history_keeps_first_seen_across_imports() {
+        let db = crate::test_utils::setup_test_db(&graph_json("a1", 200, false), "default");
+        import(&db, &graph_json("a2", 300, true));
+
+        let ages = find_function_ages(&db, "default", Some("MyApp.Accounts"), false, false, &QuerySettings::default()).unwrap();
+        let get_user = ages.iter().find(|a| a.name == "get_user").unwrap();
+        assert_eq!((get_user.first_seen, get_user.modified_at), (100, 300));
+        assert!(ages.iter().any(|a| a.name == "load"));
+
+        let modifications = find_clause_modifications(&db, "default").unwrap();
+        assert_eq!(modifications.len(), 1);
+        assert_eq!((modifications[0].source_sha.as_str(), modifications[0].modified_at), ("a2", 300));
+    }
+}
@@ -1,12 +1,14 @@
 use std::error::Error;
-use std::rc::Rc;
 
 use cozo::DataValue;
 use thiserror::Error;
 
-use crate::db::{extract_i64, extract_string, extract_string_or, run_query, Params};
-use crate::types::{Call, FunctionRef};
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder};
+use crate::db::{extract_i64, extract_shared, extract_shared_or, run_query, Params};
+use crate::types::{Call, FunctionRef, Interner};
+use crate::query_builders::{
+    call_edge_rules, certain_condition, validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder,
+    QuerySettings,
+};
 
 #[derive(Error, Debug)]
 pub enum TraceError {
@@ -14,6 +16,13 @@ pub enum TraceError {
     QueryFailed { message: String },
 }
 
+/// Trace call chains forward from the matching functions.
+///
+/// With `include_messages`, message edges (GenServer calls/casts, PubSub
+/// broadcasts) are followed alongside calls. Edges with fewer than
+/// `min_weight` call sites between their caller and callee are not followed,
+/// nor, with `certain_only`, edges in a conditional branch or comprehension.
+#[allow(clippy::too_many_arguments)]
 pub fn trace_calls(
     db: &cozo::DbInstance,
     module_pattern: &str,
@@ -23,40 +32,57 @@ pub fn trace_calls(
     use_regex: bool,
     max_depth: u32,
     limit: u32,
+    include_messages: bool,
+    min_weight: i64,
+    certain_only: bool,
+    settings: &QuerySettings,
 ) -> Result<Vec<Call>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[Some(module_pattern), Some(function_pattern)])?;
 
     // Build the starting conditions for the recursive query using helpers
     let module_cond = ConditionBuilder::new("caller_module", "module_pattern").build(use_regex);
+    let namespace_cond = settings.namespace_condition("caller_module");
     let function_cond = ConditionBuilder::new("caller_name", "function_pattern").build(use_regex);
     let arity_cond = OptionalConditionBuilder::new("caller_arity", "arity")
         .when_none("true")
         .build(arity.is_some());
+    // With --only-project, chains stop at stdlib and dependency modules, and
+    // with --exclude-tests at test modules
+    let scope_cond = format!(
+        "{}{}",
+        settings.project_scope_condition("callee_module"),
+        settings.test_scope_condition("callee_module")
+    );
+    let certain_cond = certain_condition(certain_only);
 
     // Recursive query to trace call chains, joined with function_locations for caller metadata
     // Base case: direct calls from the starting function
     // Recursive case: calls from functions we've already found
     // Filter out struct calls (callee_function != '%')
-    let script = format!(
+    let rules = format!(
         r#"
+        {edge_rules}
+
         # Base case: calls from the starting function, joined with function_locations
         trace[depth, caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line] :=
-            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line}},
+            call_edge[caller_module, caller_function, callee_module, callee_function, callee_arity, file, call_line, weight, conditional],
             *function_locations{{project, module: caller_module, name: caller_name, arity: caller_arity, kind: caller_kind, start_line: caller_start_line, end_line: caller_end_line}},
             starts_with(caller_function, caller_name),
             call_line >= caller_start_line,
             call_line <= caller_end_line,
             callee_function != '%',
-            {module_cond},
+            weight >= $min_weight{certain_cond},
+            {module_cond}{namespace_cond},
             {function_cond},
             project == $project,
             {arity_cond},
             depth = 1
+            {scope_cond}
 
         # Recursive case: calls from callees we've found
         trace[depth, caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line] :=
             trace[prev_depth, _, _, _, _, _, _, prev_callee_module, prev_callee_function, _, _, _],
-            *calls{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line: call_line}},
+            call_edge[caller_module, caller_function, callee_module, callee_function, callee_arity, file, call_line, weight, conditional],
             *function_locations{{project, module: caller_module, name: caller_name, arity: caller_arity, kind: caller_kind, start_line: caller_start_line, end_line: caller_end_line}},
             caller_module == prev_callee_module,
             starts_with(caller_function, caller_name),
@@ -64,9 +90,18 @@ pub fn trace_calls(
             call_line >= caller_start_line,
             call_line <= caller_end_line,
             callee_function != '%',
+            weight >= $min_weight{certain_cond},
             prev_depth < {max_depth},
             depth = prev_depth + 1,
             project == $project
+            {scope_cond}
+        "#,
+        edge_rules = call_edge_rules(include_messages),
+    );
+
+    let script = format!(
+        r#"
+        {rules}
 
         ?[depth, caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line] :=
             trace[depth, caller_module, caller_name, caller_arity, caller_kind, caller_start_line, caller_end_line, callee_module, callee_function, callee_arity, file, call_line]
@@ -83,50 +118,52 @@ pub fn trace_calls(
         params.insert("arity", DataValue::from(a));
     }
     params.insert("project", DataValue::Str(project.into()));
+    params.insert("min_weight", DataValue::from(min_weight));
+
+    settings.bind_namespaces(&script, &mut params);
 
     let rows = run_query(db, &script, params).map_err(|e| TraceError::QueryFailed {
         message: e.to_string(),
     })?;
 
+    let mut interner = Interner::default();
     let mut results = Vec::new();
     for row in rows.rows {
         if row.len() >= 12 {
             let depth = extract_i64(&row[0], 0);
-            let Some(caller_module) = extract_string(&row[1]) else { continue };
-            let Some(caller_name) = extract_string(&row[2]) else { continue };
+            let Some(caller_module) = extract_shared(&row[1], &mut interner) else { continue };
+            let Some(caller_name) = extract_shared(&row[2], &mut interner) else { continue };
             let caller_arity = extract_i64(&row[3], 0);
-            let caller_kind = extract_string_or(&row[4], "");
+            let caller_kind = extract_shared_or(&row[4], "", &mut interner);
             let caller_start_line = extract_i64(&row[5], 0);
             let caller_end_line = extract_i64(&row[6], 0);
-            let Some(callee_module) = extract_string(&row[7]) else { continue };
-            let Some(callee_name) = extract_string(&row[8]) else { continue };
+            let Some(callee_module) = extract_shared(&row[7], &mut interner) else { continue };
+            let Some(callee_name) = extract_shared(&row[8], &mut interner) else { continue };
             let callee_arity = extract_i64(&row[9], 0);
-            let Some(file) = extract_string(&row[10]) else { continue };
+            let Some(file) = extract_shared(&row[10], &mut interner) else { continue };
             let line = extract_i64(&row[11], 0);
 
             let caller = FunctionRef::with_definition(
-                Rc::from(caller_module.into_boxed_str()),
-                Rc::from(caller_name.into_boxed_str()),
+                caller_module,
+                caller_name,
                 caller_arity,
-                Rc::from(caller_kind.into_boxed_str()),
-                Rc::from(file.into_boxed_str()),
+                caller_kind,
+                file,
                 caller_start_line,
                 caller_end_line,
             );
 
             // Callee doesn't have definition info from this query
-            let callee = FunctionRef::new(
-                Rc::from(callee_module.into_boxed_str()),
-                Rc::from(callee_name.into_boxed_str()),
-                callee_arity,
-            );
+            let callee = FunctionRef::new(callee_module, callee_name, callee_arity);
 
             results.push(Call {
                 caller,
                 callee,
                 line,
                 call_type: None,
+                call_kind: None,
                 depth: Some(depth),
+                weight: None,
             });
         }
     }
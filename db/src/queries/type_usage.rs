@@ -0,0 +1,190 @@
+//! Specs and type definitions referencing a type.
+//!
+//! Unlike `struct_usage`, which compares whole input and return strings, this
+//! searches the full text of every `@spec`, `@callback` and `@type` for a
+//! reference to the type, wherever it appears in it.
+
+use std::error::Error;
+
+use cozo::DataValue;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query, Params};
+use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+
+#[derive(Error, Debug)]
+pub enum TypeUsageError {
+    #[error("Type usage query failed: {message}")]
+    QueryFailed { message: String },
+}
+
+/// A spec or type definition referencing a type
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeUsageEntry {
+    pub project: String,
+    pub module: String,
+    /// "spec" or "callback" for specs, "type", "typep" or "opaque" for types
+    pub kind: String,
+    pub name: String,
+    /// Arity of the function, or number of parameters of the type
+    pub arity: i64,
+    pub line: i64,
+    /// Full text of the spec or type definition
+    pub definition: String,
+}
+
+/// Regex matching a reference to the type `name` in spec or type text.
+///
+/// `User.t` matches `User.t()` and `MyApp.User.t()` but not `User.token()`
+/// or `AdminUser.t()`.
+pub fn reference_pattern(name: &str) -> String {
+    format!(r"(^|[^\w.])([A-Za-z_]\w*\.)*{}($|[^\w?!])", regex::escape(name))
+}
+
+/// Find the specs and types whose text references `type_name`.
+///
+/// Without `use_regex` the name matches as a whole type reference (see
+/// [`reference_pattern`]); with it, `type_name` is matched as a regex.
+pub fn find_type_usage(
+    db: &cozo::DbInstance,
+    type_name: &str,
+    project: &str,
+    use_regex: bool,
+    module_pattern: Option<&str>,
+    limit: u32,
+    settings: &QuerySettings,
+) -> Result<Vec<TypeUsageEntry>, Box<dyn Error>> {
+    validate_regex_patterns(use_regex, &[Some(type_name), module_pattern])?;
+
+    let module_cond = OptionalConditionBuilder::new("module", "module_pattern")
+        .with_leading_comma()
+        .with_regex()
+        .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+
+    // Specs imported without their full text are searched by inputs and return
+    let script = format!(
+        r#"
+        usage[module, kind, name, arity, line, definition] :=
+            *specs{{project, module, name, arity, kind, line, inputs_string, return_string, full}},
+            project == $project,
+            definition = if(full == '', concat(name, '(', inputs_string, ') :: ', return_string), full),
+            regex_matches(definition, $pattern)
+            {module_cond}
+            {namespace_cond}
+        usage[module, kind, name, arity, line, definition] :=
+            *types{{project, module, name, kind, params, line, definition}},
+            project == $project,
+            arity = length(regex_extract(params, '[^,\\s]+')),
+            regex_matches(definition, $pattern)
+            {module_cond}
+            {namespace_cond}
+        ?[project, module, kind, name, arity, line, definition] :=
+            usage[module, kind, name, arity, line, definition],
+            project = $project
+
+        :order module, line, name, arity
+        :limit {limit}
+        "#,
+    );
+
+    let pattern = if use_regex {
+        type_name.to_string()
+    } else {
+        reference_pattern(type_name)
+    };
+
+    let mut params = Params::new();
+    params.insert("pattern", DataValue::Str(pattern.into()));
+    params.insert("project", DataValue::Str(project.into()));
+    if let Some(mod_pat) = module_pattern {
+        params.insert("module_pattern", DataValue::Str(mod_pat.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
+
+    let rows = run_query(db, &script, params).map_err(|e| TypeUsageError::QueryFailed {
+        message: e.to_string(),
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows.rows {
+        if row.len() >= 7 {
+            let Some(project) = extract_string(&row[0]) else { continue };
+            let Some(module) = extract_string(&row[1]) else { continue };
+            let Some(kind) = extract_string(&row[2]) else { continue };
+            let Some(name) = extract_string(&row[3]) else { continue };
+            let arity = extract_i64(&row[4], 0);
+            let line = extract_i64(&row[5], 0);
+            let definition = extract_string(&row[6]).unwrap_or_default();
+
+            results.push(TypeUsageEntry {
+                project,
+                module,
+                kind,
+                name,
+                arity,
+                line,
+                definition,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(entries: &[TypeUsageEntry]) -> Vec<String> {
+        entries
+            .iter()
+            .map(|e| format!("{} {}.{}/{}", e.kind, e.module, e.name, e.arity))
+            .collect()
+    }
+
+    #[test]
+    fn test_reference_pattern() {
+        let pattern = regex::Regex::new(&reference_pattern("User.t")).unwrap();
+        assert!(pattern.is_match("@spec get(integer()) :: User.t()"));
+        assert!(pattern.is_match("@spec list() :: [MyApp.User.t()]"));
+        assert!(pattern.is_match("User.t"));
+        assert!(!pattern.is_match("@spec get() :: AdminUser.t()"));
+        assert!(!pattern.is_match("@spec get() :: User.token()"));
+    }
+
+    #[test]
+    fn test_find_type_usage_in_specs() {
+        let db = crate::test_utils::call_graph_db("default");
+        let entries = find_type_usage(&db, "User.t", "default", false, None, 100, &QuerySettings::default()).unwrap();
+
+        assert_eq!(names(&entries), ["spec MyApp.Accounts.get_user/1", "spec MyApp.Accounts.list_users/0"]);
+        assert_eq!(entries[1].definition, "@spec list_users() :: [User.t()]");
+    }
+
+    #[test]
+    fn test_find_type_usage_in_types_and_callbacks() {
+        let db = crate::test_utils::call_graph_db("default");
+        let entries = find_type_usage(&db, "String.t", "default", false, None, 100, &QuerySettings::default()).unwrap();
+        assert_eq!(names(&entries), ["type MyApp.Accounts.user/0"]);
+
+        let entries = find_type_usage(&db, "Ecto.Schema.t", "default", false, Some("MyApp.Repo"), 100, &QuerySettings::default()).unwrap();
+        assert_eq!(names(&entries), ["callback MyApp.Repo.get/2"]);
+    }
+
+    #[test]
+    fn test_find_type_usage_regex_and_project() {
+        let db = crate::test_utils::call_graph_db("default");
+        let entries = find_type_usage(&db, r"integer\(\)", "default", true, None, 100, &QuerySettings::default()).unwrap();
+        assert_eq!(
+            names(&entries),
+            ["opaque MyApp.Accounts.user_id/0", "type MyApp.Accounts.user/0", "spec MyApp.Accounts.get_user/1"]
+        );
+
+        assert!(find_type_usage(&db, "User.t", "other", false, None, 100, &QuerySettings::default())
+            .unwrap()
+            .is_empty());
+    }
+}
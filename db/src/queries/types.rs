@@ -5,7 +5,7 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder};
+use crate::query_builders::{validate_regex_patterns, ConditionBuilder, OptionalConditionBuilder, QuerySettings};
 
 #[derive(Error, Debug)]
 pub enum TypesError {
@@ -25,6 +25,7 @@ pub struct TypeInfo {
     pub definition: String,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn find_types(
     db: &cozo::DbInstance,
     module_pattern: &str,
@@ -33,11 +34,13 @@ pub fn find_types(
     project: &str,
     use_regex: bool,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<TypeInfo>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[Some(module_pattern), name_filter])?;
 
     // Build conditions using query builders
     let module_cond = ConditionBuilder::new("module", "module_pattern").build(use_regex);
+    let namespace_cond = settings.namespace_condition("module");
     let name_cond = OptionalConditionBuilder::new("name", "name_pattern")
         .with_leading_comma()
         .with_regex()
@@ -52,6 +55,7 @@ pub fn find_types(
             *types{{project, module, name, kind, params, line, definition}},
             project == $project,
             {module_cond}
+            {namespace_cond}
             {name_cond}
             {kind_cond}
 
@@ -75,6 +79,8 @@ pub fn find_types(
         params.insert("kind", DataValue::Str(kind.into()));
     }
 
+    settings.bind_namespaces(&script, &mut params);
+
     let rows = run_query(db, &script, params).map_err(|e| TypesError::QueryFailed {
         message: e.to_string(),
     })?;
@@ -5,7 +5,8 @@ use serde::Serialize;
 use thiserror::Error;
 
 use crate::db::{extract_i64, extract_string, run_query, Params};
-use crate::query_builders::{validate_regex_patterns, OptionalConditionBuilder};
+use crate::query_builders::{app_condition, validate_regex_patterns, OptionalConditionBuilder, QuerySettings};
+use crate::where_filter::{ColumnType, WhereFilter};
 
 #[derive(Error, Debug)]
 pub enum UnusedError {
@@ -22,33 +23,32 @@ pub struct UnusedFunction {
     pub kind: String,
     pub file: String,
     pub line: i64,
+    pub end_line: i64,
 }
 
-/// Generated function name patterns to exclude (Elixir compiler-generated)
-const GENERATED_PATTERNS: &[&str] = &[
-    "__struct__",
-    "__using__",
-    "__before_compile__",
-    "__after_compile__",
-    "__on_definition__",
-    "__impl__",
-    "__info__",
-    "__protocol__",
-    "__deriving__",
-    "__changeset__",
-    "__schema__",
-    "__meta__",
+/// Columns a `--where` filter may compare
+const WHERE_COLUMNS: &[(&str, ColumnType)] = &[
+    ("module", ColumnType::Text),
+    ("name", ColumnType::Text),
+    ("arity", ColumnType::Number),
+    ("kind", ColumnType::Text),
+    ("file", ColumnType::Text),
+    ("line", ColumnType::Number),
 ];
 
+#[allow(clippy::too_many_arguments)]
 pub fn find_unused_functions(
     db: &cozo::DbInstance,
     module_pattern: Option<&str>,
+    app: Option<&str>,
     project: &str,
     use_regex: bool,
     private_only: bool,
     public_only: bool,
     exclude_generated: bool,
+    filter: Option<&WhereFilter>,
     limit: u32,
+    settings: &QuerySettings,
 ) -> Result<Vec<UnusedFunction>, Box<dyn Error>> {
     validate_regex_patterns(use_regex, &[module_pattern])?;
 
@@ -57,6 +57,12 @@ pub fn find_unused_functions(
         .with_leading_comma()
         .with_regex()
         .build_with_regex(module_pattern.is_some(), use_regex);
+    let namespace_cond = settings.namespace_condition("module");
+    let app_cond = app_condition("module", app.is_some());
+    let scope_cond = settings.project_scope_condition("module");
+    let test_cond = settings.test_scope_condition("module");
+    // With --exclude-tests, functions only tests call are reported as unused
+    let caller_test_cond = settings.test_caller_condition("caller_module");
 
     // Build kind filter for private_only/public_only
     let kind_filter = if private_only {
@@ -66,6 +72,17 @@ pub fn find_unused_functions(
     } else {
         String::new()
     };
+    let generated_cond = if exclude_generated {
+        settings.generated_condition("name", "file", "generated_by")
+    } else {
+        String::new()
+    };
+
+    let mut params = Params::new();
+    let where_cond = match filter {
+        Some(filter) => filter.condition(WHERE_COLUMNS, &mut params)?,
+        None => String::new(),
+    };
 
     // Find functions that exist in function_locations but are never called
     // We use function_locations as the source of "defined functions" and check
@@ -73,35 +90,46 @@ pub fn find_unused_functions(
     let script = format!(
         r#"
         # All defined functions
-        defined[module, name, arity, kind, file, start_line] :=
-            *function_locations{{project, module, name, arity, kind, file, start_line}},
+        defined[module, name, arity, kind, file, start_line, end_line] :=
+            *function_locations{{project, module, name, arity, kind, file, start_line, end_line, generated_by}},
             project == $project
             {module_cond}
+            {namespace_cond}
+            {app_cond}
+            {scope_cond}
+            {test_cond}
             {kind_filter}
+            {generated_cond}
 
         # All functions that are called (as callees)
         called[module, name, arity] :=
-            *calls{{project, callee_module, callee_function, callee_arity}},
+            *calls{{project, caller_module, callee_module, callee_function, callee_arity}},
             project == $project,
             module = callee_module,
             name = callee_function,
             arity = callee_arity
+            {caller_test_cond}
 
         # Functions that are defined but never called
-        ?[module, name, arity, kind, file, line] :=
-            defined[module, name, arity, kind, file, line],
+        ?[module, name, arity, kind, file, line, end_line] :=
+            defined[module, name, arity, kind, file, line, end_line],
             not called[module, name, arity]
+            {where_cond}
 
         :order module, name, arity
         :limit {limit}
         "#,
     );
 
-    let mut params = Params::new();
     params.insert("project", DataValue::Str(project.into()));
     if let Some(pattern) = module_pattern {
         params.insert("module_pattern", DataValue::Str(pattern.into()));
     }
+    if let Some(app) = app {
+        params.insert("app", DataValue::Str(app.into()));
+    }
+
+    settings.bind_namespaces(&script, &mut params);
 
     let rows = run_query(db, &script, params).map_err(|e| UnusedError::QueryFailed {
         message: e.to_string(),
@@ -109,18 +137,14 @@ pub fn find_unused_functions(
 
     let mut results = Vec::new();
     for row in rows.rows {
-        if row.len() >= 6 {
+        if row.len() >= 7 {
             let Some(module) = extract_string(&row[0]) else { continue };
             let Some(name) = extract_string(&row[1]) else { continue };
             let arity = extract_i64(&row[2], 0);
             let Some(kind) = extract_string(&row[3]) else { continue };
             let Some(file) = extract_string(&row[4]) else { continue };
             let line = extract_i64(&row[5], 0);
-
-            // Filter out generated functions if requested
-            if exclude_generated && GENERATED_PATTERNS.iter().any(|p| name.starts_with(p)) {
-                continue;
-            }
+            let end_line = extract_i64(&row[6], line);
 
             results.push(UnusedFunction {
                 module,
@@ -129,6 +153,7 @@ pub fn find_unused_functions(
                 kind,
                 file,
                 line,
+                end_line,
             });
         }
     }
@@ -0,0 +1,203 @@
+//! Database maintenance: orphaned rows, aggregates and store compaction.
+//!
+//! Long-lived databases accumulate rows that no import clears (calls left
+//! behind by incremental updates of modules that were since deleted) and
+//! keep the pages freed by every re-import. The SQLite store is compacted with
+//! `VACUUM` on the database file, as Cozo's `::compact` only releases its
+//! connection pool on SQLite.
+
+use std::collections::BTreeSet;
+use std::error::Error;
+#[cfg(feature = "sqlite")]
+use std::path::Path;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::db::{extract_i64, extract_string, run_query_no_params};
+use crate::queries::aggregates::{clear_aggregates, refresh_aggregates};
+
+#[derive(Error, Debug)]
+pub enum VacuumError {
+    #[error("Orphaned call cleanup failed: {message}")]
+    OrphansFailed { message: String },
+
+    #[error("Compaction of '{path}' failed: {message}")]
+    CompactFailed { path: String, message: String },
+}
+
+/// Size of the database file before and after compaction
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CompactedSize {
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+impl CompactedSize {
+    pub fn reclaimed(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+/// Calls whose caller module is no longer defined.
+///
+/// The caller module is gone when it is in neither `modules` nor
+/// `function_locations`. Nothing narrower is checked: extractors leave out
+/// the clauses of some calling functions, and struct construction (`%`),
+/// calls to macro-generated or default-argument functions and calls into
+/// stdlib modules all have callees without definitions.
+const ORPHANED_CALLS: &str = r#"
+    known_module[project, module] := *function_locations{project, module}
+    known_module[project, module] := *modules{project, name: module}
+
+    orphan[project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column] :=
+        *calls{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column},
+        not known_module[project, caller_module]
+"#;
+
+const CALL_KEYS: &str =
+    "project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, column";
+
+/// Remove calls whose caller module no longer exists, returning how many.
+///
+/// With `dry_run` the calls are only counted.
+pub fn remove_orphaned_calls(db: &cozo::DbInstance, dry_run: bool) -> Result<usize, Box<dyn Error>> {
+    let failed = |e: Box<dyn Error>| VacuumError::OrphansFailed { message: e.to_string() };
+
+    let count_script = format!("{ORPHANED_CALLS}\n?[count(line)] := orphan[{CALL_KEYS}]");
+    let rows = run_query_no_params(db, &count_script).map_err(failed)?;
+    let count = rows.rows.first().map_or(0, |row| extract_i64(&row[0], 0)) as usize;
+
+    if count > 0 && !dry_run {
+        let remove_script = format!("{ORPHANED_CALLS}\n?[{CALL_KEYS}] := orphan[{CALL_KEYS}]\n:rm calls {{{CALL_KEYS}}}");
+        run_query_no_params(db, &remove_script).map_err(failed)?;
+    }
+
+    Ok(count)
+}
+
+/// Recompute the aggregates of every project, returning the projects refreshed.
+///
+/// Projects left with aggregate rows but no imported data are cleared.
+pub fn rebuild_aggregates(db: &cozo::DbInstance) -> Result<Vec<String>, Box<dyn Error>> {
+    let projects = project_names(db, "?[project] := *modules{project}")?;
+    let stale = project_names(db, "?[project] := *module_stats{project}")?;
+
+    for project in stale.difference(&projects) {
+        clear_aggregates(db, project)?;
+    }
+    for project in &projects {
+        refresh_aggregates(db, project)?;
+    }
+
+    Ok(projects.into_iter().collect())
+}
+
+/// Compact the SQLite file backing the database.
+///
+/// Cozo's pooled connections are released first so that `VACUUM` runs on the
+/// only open connection.
+#[cfg(feature = "sqlite")]
+pub fn compact_store(db: &cozo::DbInstance, path: &Path) -> Result<CompactedSize, Box<dyn Error>> {
+    let failed = |message: String| VacuumError::CompactFailed {
+        path: path.display().to_string(),
+        message,
+    };
+
+    let bytes_before = std::fs::metadata(path).map_err(|e| failed(e.to_string()))?.len();
+
+    run_query_no_params(db, "::compact").map_err(|e| failed(e.to_string()))?;
+    let connection = sqlite::open(path).map_err(|e| failed(e.to_string()))?;
+    connection.execute("VACUUM").map_err(|e| failed(e.to_string()))?;
+    drop(connection);
+
+    let bytes_after = std::fs::metadata(path).map_err(|e| failed(e.to_string()))?.len();
+    Ok(CompactedSize { bytes_before, bytes_after })
+}
+
+fn project_names(db: &cozo::DbInstance, script: &str) -> Result<BTreeSet<String>, Box<dyn Error>> {
+    let rows = run_query_no_params(db, script)?;
+    Ok(rows.rows.iter().filter_map(|row| extract_string(&row[0])).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VACUUM_JSON: &str = r#"{
+        "structs": {},
+        "function_locations": {
+            "MyApp.Accounts": {
+                "get_user/1:2": {"name": "get_user", "arity": 1, "file": "lib/accounts.ex", "kind": "def", "line": 2, "start_line": 2, "end_line": 4}
+            }
+        },
+        "calls": [
+            {"caller": {"module": "MyApp.Accounts", "function": "get_user/1", "file": "lib/accounts.ex", "line": 3, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Repo", "function": "get", "arity": 2}},
+            {"caller": {"module": "MyApp.Accounts", "function": "get_user/1", "file": "lib/accounts.ex", "line": 4, "column": 5}, "type": "local", "callee": {"module": "MyApp.Accounts", "function": "load", "arity": 1}},
+            {"caller": {"module": "MyApp.Accounts", "function": "delete_user/1", "file": "lib/accounts.ex", "line": 9, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Repo", "function": "delete", "arity": 1}},
+            {"caller": {"module": "MyApp.Removed", "function": "run/0", "file": "lib/removed.ex", "line": 2, "column": 5}, "type": "remote", "callee": {"module": "MyApp.Accounts", "function": "get_user", "arity": 1}}
+        ]
+    }"#;
+
+    fn call_lines(db: &cozo::DbInstance) -> Vec<i64> {
+        let rows = run_query_no_params(db, "?[line] := *calls{line} :order line").unwrap();
+        rows.rows.iter().map(|row| extract_i64(&row[0], 0)).collect()
+    }
+
+    #[test]
+    fn test_remove_orphaned_calls() {
+        let db = crate::test_utils::setup_test_db(VACUUM_JSON, "default");
+
+        // Only the call from the removed module goes
+        assert_eq!(remove_orphaned_calls(&db, true).unwrap(), 1);
+        assert_eq!(call_lines(&db).len(), 4);
+
+        assert_eq!(remove_orphaned_calls(&db, false).unwrap(), 1);
+        // Calls from functions without clauses and to undefined callees stay
+        assert_eq!(call_lines(&db), [3, 4, 9]);
+        assert_eq!(remove_orphaned_calls(&db, false).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fresh_import_has_no_orphaned_calls() {
+        // Struct construction (`%`), calls to macro-generated functions and
+        // into stdlib modules all have callees without definitions
+        for json in [
+            crate::fixtures::CALL_GRAPH,
+            crate::fixtures::STRUCTS,
+            crate::fixtures::UMBRELLA,
+            include_str!("../fixtures/extracted_trace.json"),
+        ] {
+            let db = crate::test_utils::setup_test_db(json, "default");
+            let calls = call_lines(&db).len();
+
+            assert_eq!(remove_orphaned_calls(&db, false).unwrap(), 0);
+            assert_eq!(call_lines(&db).len(), calls);
+        }
+    }
+
+    #[test]
+    fn test_rebuild_aggregates_clears_stale_projects() {
+        let db = crate::test_utils::call_graph_db("default");
+        crate::queries::import::clear_project_data(&db, "default").unwrap();
+        assert!(crate::queries::aggregates::has_aggregates(&db, "default"));
+
+        assert!(rebuild_aggregates(&db).unwrap().is_empty());
+        assert!(!crate::queries::aggregates::has_aggregates(&db, "default"));
+    }
+
+    #[test]
+    fn test_compact_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cozo.sqlite");
+        let db = crate::db::open_db(&path).unwrap();
+        crate::queries::import::import_json_str(&db, crate::fixtures::CALL_GRAPH, "default").unwrap();
+        crate::queries::import::clear_project_data(&db, "default").unwrap();
+
+        let size = compact_store(&db, &path).expect("Compaction should succeed");
+        assert!(size.bytes_after <= size.bytes_before);
+
+        // The database stays usable after compaction
+        assert!(call_lines(&db).is_empty());
+    }
+}
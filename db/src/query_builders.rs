@@ -20,9 +20,227 @@
 //!
 //! See: https://github.com/cozodb/cozo/blob/main/cozo-core/Cargo.toml for CozoDB's
 //! regex dependency version.
+//!
+//! # Pattern Compilation
+//!
+//! Compiled patterns are cached for the rest of the thread, so a pattern that is
+//! validated and then matched in memory (or reused across queries) is compiled
+//! once. Patterns whose compiled program would exceed [`MAX_PATTERN_SIZE`] are
+//! rejected up front instead of being handed to CozoDB.
+//!
+//! Without `--regex`, a pattern containing `*` is a glob: see [`PatternMatching`].
 
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::error::Error;
 
+use cozo::DataValue;
+use regex::{Regex, RegexBuilder};
+
+use crate::db::Params;
+use crate::generated::GeneratedRules;
+
+/// Upper bound on the compiled size of a pattern, in bytes
+///
+/// Far above anything a module or function filter needs; patterns such as
+/// `(\w{100}){100}` exceed it.
+pub const MAX_PATTERN_SIZE: usize = 1 << 20;
+
+/// Number of compiled patterns [`compile_pattern`] keeps per thread
+///
+/// A long-running daemon sees an open-ended stream of patterns; once the cache
+/// is full it is emptied rather than growing without bound.
+const MAX_CACHED_PATTERNS: usize = 256;
+
+thread_local! {
+    static PATTERN_CACHE: RefCell<HashMap<String, Regex>> = RefCell::new(HashMap::new());
+}
+
+/// Compile a regex pattern, reusing an earlier compilation of the same pattern
+///
+/// # Examples
+/// ```
+/// use db::query_builders::compile_pattern;
+///
+/// assert!(compile_pattern("^MyApp\\.").unwrap().is_match("MyApp.Accounts"));
+/// assert!(compile_pattern("[invalid").is_err());
+/// assert!(compile_pattern("(\\w{100}){100}").unwrap_err().to_string().contains("too complex"));
+/// ```
+pub fn compile_pattern(pattern: &str) -> Result<Regex, Box<dyn Error>> {
+    if let Some(re) = PATTERN_CACHE.with(|cache| cache.borrow().get(pattern).cloned()) {
+        return Ok(re);
+    }
+
+    let re = RegexBuilder::new(pattern)
+        .size_limit(MAX_PATTERN_SIZE)
+        .build()
+        .map_err(|e| -> Box<dyn Error> {
+            match e {
+                regex::Error::CompiledTooBig(limit) => format!(
+                    "Pattern '{}' is too complex: it compiles to more than {} bytes. \
+                     Reduce nested or counted repetitions (e.g. {{n}}).",
+                    pattern, limit
+                )
+                .into(),
+                e => format!("Invalid regex pattern '{}': {}", pattern, e).into(),
+            }
+        })?;
+
+    PATTERN_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= MAX_CACHED_PATTERNS {
+            cache.clear();
+        }
+        cache.insert(pattern.to_string(), re.clone());
+    });
+    Ok(re)
+}
+
+/// Whether a non-regex pattern is a glob (`*` matches any run of characters)
+///
+/// `\*` is a literal star, and a pattern with no letters, digits or underscores
+/// is an operator such as `*` or `**`, matched literally. `?` is not a wildcard:
+/// it is part of Elixir function names like `valid?`.
+///
+/// # Examples
+/// ```
+/// use db::query_builders::is_glob;
+///
+/// assert!(is_glob("MyApp.*"));
+/// assert!(!is_glob("*"));
+/// assert!(!is_glob("mul\\*"));
+/// ```
+pub fn is_glob(pattern: &str) -> bool {
+    let is_operator = !pattern.chars().any(|c| c.is_alphanumeric() || c == '_');
+    !is_operator && glob_parts(pattern).len() > 1
+}
+
+/// A glob split at its wildcards, with `\*` unescaped to a literal star
+fn glob_parts(glob: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        let part = parts.last_mut().expect("parts start with one part");
+        match c {
+            '\\' if chars.next_if_eq(&'*').is_some() => part.push('*'),
+            '*' => parts.push(String::new()),
+            c => part.push(c),
+        }
+    }
+    parts
+}
+
+/// Translate a glob into an anchored regex
+///
+/// # Examples
+/// ```
+/// use db::query_builders::glob_to_regex;
+///
+/// assert_eq!(glob_to_regex("MyApp.*"), "^MyApp\\..*$");
+/// assert_eq!(glob_to_regex("*_user?"), "^.*_user\\?$");
+/// assert_eq!(glob_to_regex("mul\\**"), "^mul\\*.*$");
+/// ```
+pub fn glob_to_regex(glob: &str) -> String {
+    let parts: Vec<String> = glob_parts(glob).iter().map(|part| regex::escape(part)).collect();
+    format!("^{}$", parts.join(".*"))
+}
+
+/// Translate a path glob into an anchored regex matching a path or any path below it
+///
+/// A glob without a `/` matches in any directory; `**` spans directories.
+///
+/// # Examples
+/// ```
+/// use db::query_builders::path_glob_to_regex;
+/// use regex::Regex;
+///
+/// let dir = Regex::new(&path_glob_to_regex("test/support")).unwrap();
+/// assert!(dir.is_match("test/support/fixtures.ex"));
+/// assert!(!dir.is_match("lib/test/support.ex"));
+/// assert!(Regex::new(&path_glob_to_regex("*.pb.ex")).unwrap().is_match("lib/proto/user.pb.ex"));
+/// ```
+pub fn path_glob_to_regex(glob: &str) -> String {
+    let glob = glob.trim().trim_end_matches('/');
+    let (anchored, glob) = match glob.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (glob.contains('/'), glob),
+    };
+
+    let mut regex = String::from(if anchored { "^" } else { "^(.*/)?" });
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.next_if_eq(&'*').is_some() => {
+                if chars.next_if_eq(&'/').is_some() {
+                    regex.push_str("(.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push_str("(/.*)?$");
+    regex
+}
+
+/// How the name patterns given to a command are matched.
+///
+/// Without `--regex`, globs such as `MyApp.*` are matched as anchored regexes:
+/// when any pattern is a glob, [`pattern`](Self::pattern) translates the globs
+/// and escapes the other patterns so they still match exactly. Otherwise
+/// patterns are used as given, with `\*` unescaped.
+///
+/// # Examples
+/// ```
+/// use db::query_builders::PatternMatching;
+///
+/// let matching = PatternMatching::new(false, ["MyApp.*", "valid?"]);
+/// assert!(matching.use_regex());
+/// assert_eq!(matching.pattern("MyApp.*"), "^MyApp\\..*$");
+/// assert_eq!(matching.pattern("valid?"), "^valid\\?$");
+///
+/// // The `*` operator of Kernel is not a glob
+/// let matching = PatternMatching::new(false, ["Kernel", "*"]);
+/// assert!(!matching.use_regex());
+/// assert_eq!(matching.pattern("*"), "*");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatternMatching {
+    regex: bool,
+    glob: bool,
+}
+
+impl PatternMatching {
+    /// Matching for a command's patterns, given whether `--regex` was passed
+    pub fn new<'a>(use_regex: bool, patterns: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            regex: use_regex,
+            glob: !use_regex && patterns.into_iter().any(is_glob),
+        }
+    }
+
+    /// Whether patterns are queried as regexes
+    pub fn use_regex(&self) -> bool {
+        self.regex || self.glob
+    }
+
+    /// A pattern as it is queried
+    pub fn pattern(&self, pattern: &str) -> String {
+        if self.regex {
+            pattern.to_string()
+        } else if !self.glob {
+            glob_parts(pattern).join("*")
+        } else if is_glob(pattern) {
+            glob_to_regex(pattern)
+        } else {
+            format!("^{}$", regex::escape(&glob_parts(pattern).join("*")))
+        }
+    }
+}
+
 /// Validates a regex pattern string
 ///
 /// # Arguments
@@ -40,14 +258,7 @@ use std::error::Error;
 /// assert!(validate_regex_pattern("[invalid").is_err());
 /// ```
 pub fn validate_regex_pattern(pattern: &str) -> Result<(), Box<dyn Error>> {
-    regex::Regex::new(pattern).map_err(|e| -> Box<dyn Error> {
-        format!(
-            "Invalid regex pattern '{}': {}",
-            pattern,
-            e.to_string()
-        )
-        .into()
-    })?;
+    compile_pattern(pattern)?;
     Ok(())
 }
 
@@ -245,6 +456,405 @@ impl OptionalConditionBuilder {
     }
 }
 
+/// Rules for `call_edge`, the edges the call-graph traversals follow.
+///
+/// Every call of `$project` is an edge and, with `include_messages`, so is every
+/// message-passing edge: the `messages` relation is keyed like `calls` and adds
+/// a second rule for the same relation. The columns are `caller_module,
+/// caller_function, callee_module, callee_function, callee_arity, file, line,
+/// weight, conditional`.
+///
+/// # Examples
+/// ```
+/// use db::query_builders::call_edge_rules;
+///
+/// assert!(call_edge_rules(false).contains("*calls{"));
+/// assert!(!call_edge_rules(false).contains("*messages{"));
+/// assert!(call_edge_rules(true).contains("*messages{"));
+/// ```
+pub fn call_edge_rules(include_messages: bool) -> String {
+    let relations: &[&str] = if include_messages { &["calls", "messages"] } else { &["calls"] };
+    relations
+        .iter()
+        .map(|relation| {
+            format!(
+                r#"
+        call_edge[caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight, conditional] :=
+            *{relation}{{project, caller_module, caller_function, callee_module, callee_function, callee_arity, file, line, weight, conditional}},
+            project == $project
+"#
+            )
+        })
+        .collect()
+}
+
+/// Drop call and message edges flagged `conditional` (inside a branch or
+/// comprehension) when `certain_only` is set.
+///
+/// Expects the rule to bind the `conditional` column. Returns an empty
+/// string otherwise.
+///
+/// # Examples
+/// ```
+/// use db::query_builders::certain_condition;
+///
+/// assert_eq!(certain_condition(false), "");
+/// assert_eq!(certain_condition(true), ", conditional == false");
+/// ```
+pub fn certain_condition(certain_only: bool) -> &'static str {
+    if certain_only {
+        ", conditional == false"
+    } else {
+        ""
+    }
+}
+
+/// Restrict a module variable to the modules of one OTP application.
+///
+/// Joins the `modules` relation on its `app` column and expects an `$app`
+/// parameter. Returns an empty string when no app is given.
+///
+/// # Examples
+/// ```
+/// use db::query_builders::app_condition;
+///
+/// assert_eq!(app_condition("module", false), "");
+/// assert!(app_condition("caller_module", true).contains("name: caller_module"));
+/// ```
+pub fn app_condition(module_var: &str, has_app: bool) -> String {
+    if has_app {
+        format!(", *modules{{project: $project, name: {module_var}, app: module_app}}, module_app == $app")
+    } else {
+        String::new()
+    }
+}
+
+/// A function named exactly, one of a list of targets queried together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionTarget {
+    pub module: String,
+    pub function: String,
+    /// `None` matches every arity
+    pub arity: Option<i64>,
+}
+
+impl FunctionTarget {
+    /// Parse `Module.function` or `Module.function/arity`
+    ///
+    /// # Examples
+    /// ```
+    /// use db::query_builders::FunctionTarget;
+    ///
+    /// let target = FunctionTarget::parse("MyApp.Accounts.get_user/1").unwrap();
+    /// assert_eq!((target.module.as_str(), target.function.as_str(), target.arity), ("MyApp.Accounts", "get_user", Some(1)));
+    /// assert_eq!(FunctionTarget::parse("MyApp.Accounts.get_user").unwrap().arity, None);
+    /// assert!(FunctionTarget::parse("get_user/1").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (name, arity) = match s.rsplit_once('/') {
+            Some((name, arity)) if !arity.is_empty() && arity.bytes().all(|b| b.is_ascii_digit()) => {
+                (name, Some(arity.parse::<i64>().map_err(|e| format!("`{s}`: {e}"))?))
+            }
+            _ => (s, None),
+        };
+        match name.rsplit_once('.') {
+            Some((module, function)) if !module.is_empty() && !function.is_empty() => Ok(Self {
+                module: module.to_string(),
+                function: function.to_string(),
+                arity,
+            }),
+            _ => Err(format!("`{s}` is not Module.function or Module.function/arity")),
+        }
+    }
+
+    /// `Module.function` or `Module.function/arity`
+    pub fn label(&self) -> String {
+        match self.arity {
+            Some(arity) => format!("{}.{}/{}", self.module, self.function, arity),
+            None => format!("{}.{}", self.module, self.function),
+        }
+    }
+}
+
+/// Rule binding the `$targets` parameter built by [`targets_param`]
+pub const TARGET_RULE: &str = "target[target_module, target_function, target_arity] <- $targets";
+
+/// Match module, function and arity variables against the targets of [`TARGET_RULE`].
+///
+/// # Examples
+/// ```
+/// use db::query_builders::target_condition;
+///
+/// assert_eq!(
+///     target_condition("module", "name", "arity"),
+///     "target[module, name, target_arity], (target_arity == -1 or arity == target_arity)"
+/// );
+/// ```
+pub fn target_condition(module_var: &str, function_var: &str, arity_var: &str) -> String {
+    format!("target[{module_var}, {function_var}, target_arity], (target_arity == -1 or {arity_var} == target_arity)")
+}
+
+/// The `$targets` parameter: one `[module, function, arity]` row per target, arity -1 for any
+pub fn targets_param(targets: &[FunctionTarget]) -> DataValue {
+    DataValue::List(
+        targets
+            .iter()
+            .map(|target| {
+                DataValue::List(vec![
+                    DataValue::Str(target.module.as_str().into()),
+                    DataValue::Str(target.function.as_str().into()),
+                    DataValue::from(target.arity.unwrap_or(-1)),
+                ])
+            })
+            .collect(),
+    )
+}
+
+/// Which modules queries keep with respect to test code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TestScope {
+    /// Test and non-test modules alike
+    #[default]
+    All,
+    /// Non-test modules only; calls from test modules do not count either
+    Exclude,
+    /// Test modules only
+    Only,
+}
+
+/// The scope and ordering a query runs with: the project, test and namespace
+/// scopes, the ordering of list queries and the generated-code rules.
+///
+/// The CLI builds it once per command from the global flags, the command's
+/// `--namespace` options and the config file, and passes it to every query
+/// that depends on it. Queries turn it into conditions with the methods below.
+///
+/// # Examples
+/// ```
+/// use db::query_builders::{QuerySettings, TestScope};
+///
+/// let settings = QuerySettings { test_scope: TestScope::Exclude, ..Default::default() };
+/// assert!(settings.sorted);
+/// assert!(!settings.only_project);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuerySettings {
+    /// Leave out stdlib and vendored dependency modules
+    pub only_project: bool,
+    /// How test modules are treated
+    pub test_scope: TestScope,
+    /// Module namespaces queries are restricted to; any module when empty.
+    ///
+    /// A trailing `.` or `.*` is ignored, so `MyApp.Web`, `MyApp.Web.` and
+    /// `MyApp.Web.*` are the same namespace.
+    pub namespaces: Vec<String>,
+    /// Order the rows of list queries
+    pub sorted: bool,
+    /// Rules telling generated functions from hand-written ones
+    pub generated_rules: GeneratedRules,
+}
+
+impl Default for QuerySettings {
+    fn default() -> Self {
+        Self {
+            only_project: false,
+            test_scope: TestScope::All,
+            namespaces: Vec::new(),
+            sorted: true,
+            generated_rules: GeneratedRules::default(),
+        }
+    }
+}
+
+impl QuerySettings {
+    /// Restrict a module variable to the project's own modules when
+    /// [`only_project`](Self::only_project) is set.
+    ///
+    /// Joins the `modules` relation and drops modules marked `is_external`.
+    /// Modules absent from `modules` (stdlib, Erlang, undefined dependencies)
+    /// fail the join and are dropped as well. Returns an empty string otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use db::query_builders::QuerySettings;
+    ///
+    /// assert_eq!(QuerySettings::default().project_scope_condition("module"), "");
+    /// let settings = QuerySettings { only_project: true, ..Default::default() };
+    /// assert!(settings.project_scope_condition("callee_module").contains("is_external: callee_module_external"));
+    /// ```
+    pub fn project_scope_condition(&self, module_var: &str) -> String {
+        if self.only_project {
+            format!(
+                ", *modules{{project: $project, name: {module_var}, is_external: {module_var}_external}}, {module_var}_external == false"
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    /// Restrict a module variable according to [`test_scope`](Self::test_scope).
+    ///
+    /// Test modules are those marked `is_test` in the `modules` relation. With
+    /// [`TestScope::Exclude`], modules absent from `modules` are kept; with
+    /// [`TestScope::Only`] they fail the join. Returns an empty string for
+    /// [`TestScope::All`].
+    ///
+    /// # Examples
+    /// ```
+    /// use db::query_builders::{QuerySettings, TestScope};
+    ///
+    /// assert_eq!(QuerySettings::default().test_scope_condition("module"), "");
+    /// let settings = QuerySettings { test_scope: TestScope::Only, ..Default::default() };
+    /// assert!(settings.test_scope_condition("module").contains("is_test: module_test"));
+    /// ```
+    pub fn test_scope_condition(&self, module_var: &str) -> String {
+        match self.test_scope {
+            TestScope::All => String::new(),
+            TestScope::Exclude => format!(", not *modules{{project: $project, name: {module_var}, is_test: true}}"),
+            TestScope::Only => {
+                format!(", *modules{{project: $project, name: {module_var}, is_test: {module_var}_test}}, {module_var}_test == true")
+            }
+        }
+    }
+
+    /// Drop calls made from test modules when [`test_scope`](Self::test_scope)
+    /// excludes them.
+    ///
+    /// For queries counting callers, so a function only tests call counts as
+    /// uncalled. Returns an empty string otherwise.
+    pub fn test_caller_condition(&self, caller_module_var: &str) -> String {
+        match self.test_scope {
+            TestScope::Exclude => self.test_scope_condition(caller_module_var),
+            TestScope::All | TestScope::Only => String::new(),
+        }
+    }
+
+    /// The namespaces without their trailing `.` or `.*`
+    fn normalized_namespaces(&self) -> Vec<&str> {
+        self.namespaces
+            .iter()
+            .map(|namespace| namespace.trim_end_matches('*').trim_end_matches('.'))
+            .filter(|namespace| !namespace.is_empty())
+            .collect()
+    }
+
+    /// Restrict a module variable to the [`namespaces`](Self::namespaces).
+    ///
+    /// A namespace matches the module itself and its submodules, compared with
+    /// `starts_with` rather than a regex. The namespaces are read from the
+    /// `$namespaces` and `$namespace_prefixes` parameters, which the query
+    /// binds with [`bind_namespaces`](Self::bind_namespaces). Returns an empty
+    /// string when no namespace is set.
+    ///
+    /// # Examples
+    /// ```
+    /// use db::query_builders::QuerySettings;
+    ///
+    /// assert_eq!(QuerySettings::default().namespace_condition("module"), "");
+    /// let settings = QuerySettings { namespaces: vec!["MyApp.Web.*".to_string()], ..Default::default() };
+    /// assert_eq!(
+    ///     settings.namespace_condition("module"),
+    ///     ", (module == get($namespaces, 0) or starts_with(module, get($namespace_prefixes, 0)))"
+    /// );
+    /// ```
+    pub fn namespace_condition(&self, module_var: &str) -> String {
+        let count = self.normalized_namespaces().len();
+        if count == 0 {
+            return String::new();
+        }
+        let tests: Vec<String> = (0..count)
+            .map(|i| {
+                format!(
+                    "{module_var} == get($namespaces, {i}) or starts_with({module_var}, get($namespace_prefixes, {i}))"
+                )
+            })
+            .collect();
+        format!(", ({})", tests.join(" or "))
+    }
+
+    /// Whether `module` passes [`namespace_condition`](Self::namespace_condition);
+    /// true when no namespace is set.
+    ///
+    /// For results computed outside the database, such as the in-memory engine.
+    ///
+    /// # Examples
+    /// ```
+    /// use db::query_builders::QuerySettings;
+    ///
+    /// let settings = QuerySettings { namespaces: vec!["MyApp.Web".to_string()], ..Default::default() };
+    /// assert!(settings.in_namespaces("MyApp.Web.Router"));
+    /// assert!(!settings.in_namespaces("MyApp.WebSocket"));
+    /// assert!(QuerySettings::default().in_namespaces("MyApp.WebSocket"));
+    /// ```
+    pub fn in_namespaces(&self, module: &str) -> bool {
+        let namespaces = self.normalized_namespaces();
+        namespaces.is_empty()
+            || namespaces.iter().any(|namespace| {
+                module == *namespace || module.strip_prefix(namespace).is_some_and(|rest| rest.starts_with('.'))
+            })
+    }
+
+    /// Bind the parameters of [`namespace_condition`](Self::namespace_condition)
+    /// when `script` uses them
+    pub fn bind_namespaces(&self, script: &str, params: &mut Params) {
+        if !script.contains("$namespaces") {
+            return;
+        }
+        let namespaces = self.normalized_namespaces();
+        let list = |suffix: &str| {
+            DataValue::List(
+                namespaces
+                    .iter()
+                    .map(|namespace| DataValue::Str(format!("{namespace}{suffix}").into()))
+                    .collect(),
+            )
+        };
+        params.insert("namespaces", list(""));
+        params.insert("namespace_prefixes", list("."));
+    }
+
+    /// `:order` clause of a list query, or an empty string when
+    /// [`sorted`](Self::sorted) is off.
+    ///
+    /// Only for queries whose order is presentation: ranked queries keeping the
+    /// first rows of a `:limit` order them regardless, since the order decides
+    /// which rows are kept. Without it rows come in storage order.
+    ///
+    /// # Examples
+    /// ```
+    /// use db::query_builders::QuerySettings;
+    ///
+    /// assert_eq!(QuerySettings::default().order_by("module, name, arity"), ":order module, name, arity");
+    /// let unsorted = QuerySettings { sorted: false, ..Default::default() };
+    /// assert_eq!(unsorted.order_by("module, name, arity"), "");
+    /// ```
+    pub fn order_by(&self, columns: &str) -> String {
+        if self.sorted {
+            format!(":order {columns}")
+        } else {
+            String::new()
+        }
+    }
+
+    /// [`GeneratedRules::condition`] of the [`generated_rules`](Self::generated_rules)
+    pub fn generated_condition(&self, name_var: &str, file_var: &str, generated_by_var: &str) -> String {
+        self.generated_rules.condition(name_var, file_var, generated_by_var)
+    }
+
+    /// CozoScript rule `{rule}[module, name]` holding the functions with at
+    /// least one clause the [`generated_rules`](Self::generated_rules) do not match.
+    ///
+    /// For queries over relations keyed by function name without a file, such
+    /// as `function_stats`: joining it keeps the functions that are not entirely
+    /// generated.
+    pub fn hand_written_rule(&self, rule: &str) -> String {
+        format!(
+            "{rule}[module, name] := *function_locations{{project, module, name, file, generated_by}}, project == $project{}",
+            self.generated_condition("name", "file", "generated_by")
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -430,4 +1040,169 @@ mod tests {
             Some("\\d{3}")
         ]).is_ok());
     }
+
+    #[test]
+    fn test_call_edge_rules() {
+        let calls_only = call_edge_rules(false);
+        assert_eq!(calls_only.matches("call_edge[").count(), 1);
+        assert_eq!(calls_only.matches("*calls{").count(), 1);
+
+        let with_messages = call_edge_rules(true);
+        assert_eq!(with_messages.matches("call_edge[").count(), 2);
+        assert_eq!(with_messages.matches("*messages{").count(), 1);
+    }
+
+    #[test]
+    fn test_project_scope_condition() {
+        assert_eq!(QuerySettings::default().project_scope_condition("module"), "");
+
+        let settings = QuerySettings { only_project: true, ..Default::default() };
+        assert_eq!(
+            settings.project_scope_condition("module"),
+            ", *modules{project: $project, name: module, is_external: module_external}, module_external == false"
+        );
+    }
+
+    #[test]
+    fn test_test_scope_condition() {
+        let all = QuerySettings::default();
+        assert_eq!(all.test_scope_condition("module"), "");
+        assert_eq!(all.test_caller_condition("caller_module"), "");
+
+        let exclude = QuerySettings { test_scope: TestScope::Exclude, ..Default::default() };
+        assert_eq!(
+            exclude.test_scope_condition("module"),
+            ", not *modules{project: $project, name: module, is_test: true}"
+        );
+        assert_eq!(exclude.test_caller_condition("caller_module"), exclude.test_scope_condition("caller_module"));
+
+        let only = QuerySettings { test_scope: TestScope::Only, ..Default::default() };
+        assert_eq!(
+            only.test_scope_condition("module"),
+            ", *modules{project: $project, name: module, is_test: module_test}, module_test == true"
+        );
+        assert_eq!(only.test_caller_condition("caller_module"), "");
+    }
+
+    #[test]
+    fn test_compile_pattern_is_cached() {
+        let first = compile_pattern("^Cached\\.Pattern$").unwrap();
+        let second = compile_pattern("^Cached\\.Pattern$").unwrap();
+        assert_eq!(first.as_str(), second.as_str());
+        assert!(PATTERN_CACHE.with(|cache| cache.borrow().contains_key("^Cached\\.Pattern$")));
+    }
+
+    #[test]
+    fn test_compile_pattern_rejects_oversized_patterns() {
+        let err = compile_pattern("(\\w{100}){100}").unwrap_err().to_string();
+        assert!(err.contains("too complex"), "unexpected error: {err}");
+        assert!(validate_regex_patterns(true, &[Some("(\\w{100}){100}")]).is_err());
+    }
+
+    #[test]
+    fn test_pattern_cache_is_bounded() {
+        for i in 0..MAX_CACHED_PATTERNS * 2 {
+            assert!(compile_pattern(&format!("^Mod{i}$")).unwrap().is_match(&format!("Mod{i}")));
+        }
+        assert!(PATTERN_CACHE.with(|cache| cache.borrow().len()) <= MAX_CACHED_PATTERNS);
+    }
+
+    #[test]
+    fn test_glob_to_regex() {
+        assert_eq!(glob_to_regex("MyApp.*"), "^MyApp\\..*$");
+        assert_eq!(glob_to_regex("*.Repo"), "^.*\\.Repo$");
+        assert_eq!(glob_to_regex("get_*_by"), "^get_.*_by$");
+        assert!(compile_pattern(&glob_to_regex("MyApp.*")).unwrap().is_match("MyApp.Accounts"));
+        assert!(!compile_pattern(&glob_to_regex("MyApp.*")).unwrap().is_match("Other.MyApp.Accounts"));
+    }
+
+    #[test]
+    fn test_pattern_matching() {
+        // No glob: patterns and flag unchanged
+        let matching = PatternMatching::new(false, ["MyApp.Accounts"]);
+        assert!(!matching.use_regex());
+        assert_eq!(matching.pattern("MyApp.Accounts"), "MyApp.Accounts");
+
+        // Regex mode: `*` keeps its regex meaning
+        let matching = PatternMatching::new(true, ["MyApp.*"]);
+        assert!(matching.use_regex());
+        assert_eq!(matching.pattern("MyApp.*"), "MyApp.*");
+
+        // A glob switches every pattern to regex, escaping the exact ones
+        let matching = PatternMatching::new(false, ["MyApp.*", "valid?"]);
+        assert!(matching.use_regex());
+        assert_eq!(matching.pattern("MyApp.*"), "^MyApp\\..*$");
+        assert_eq!(matching.pattern("valid?"), "^valid\\?$");
+    }
+
+    #[test]
+    fn test_pattern_matching_literal_stars() {
+        // Operators are matched literally, so `Kernel.*/2` finds only the operator
+        for operator in ["*", "**"] {
+            let matching = PatternMatching::new(false, ["Kernel", operator]);
+            assert!(!matching.use_regex());
+            assert_eq!(matching.pattern(operator), operator);
+        }
+
+        // `\*` is a literal star, alone or next to a wildcard
+        let matching = PatternMatching::new(false, ["mul\\*"]);
+        assert!(!matching.use_regex());
+        assert_eq!(matching.pattern("mul\\*"), "mul*");
+
+        let matching = PatternMatching::new(false, ["MyApp.*", "*"]);
+        assert_eq!(matching.pattern("*"), "^\\*$");
+        let re = compile_pattern(&matching.pattern("mul\\**")).unwrap();
+        assert!(re.is_match("mul*2"));
+        assert!(!re.is_match("multiply"));
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(32))]
+
+        /// Names of any content survive the import and are found, exactly and
+        /// as an escaped regex, through the built conditions
+        #[test]
+        fn prop_conditions_find_imported_names(
+            names in proptest::collection::hash_set(crate::test_utils::name_strategy(), 1..4)
+        ) {
+            let names: Vec<String> = names.into_iter().collect();
+            let function_locations: serde_json::Map<String, serde_json::Value> = names
+                .iter()
+                .map(|name| {
+                    let location = serde_json::json!({
+                        "name": name, "arity": 1, "file": name, "kind": "def",
+                        "line": 1, "start_line": 1, "end_line": 2
+                    });
+                    (name.clone(), serde_json::json!({ "f/1:1": location }))
+                })
+                .collect();
+            let graph = serde_json::json!({
+                "structs": {},
+                "function_locations": function_locations,
+                "calls": []
+            });
+            let db = crate::test_utils::setup_test_db(&graph.to_string(), "default");
+
+            for name in &names {
+                for (use_regex, pattern) in [(false, name.clone()), (true, format!("^{}$", regex::escape(name)))] {
+                    validate_regex_patterns(use_regex, &[Some(&pattern)]).unwrap();
+                    let script = format!(
+                        "?[module, name] := *function_locations{{project: $project, module, name}}, {}, {}",
+                        ConditionBuilder::new("module", "module_pattern").build(use_regex),
+                        OptionalConditionBuilder::new("name", "function_pattern")
+                            .with_regex()
+                            .build_with_regex(true, use_regex),
+                    );
+                    let mut params = crate::db::Params::new();
+                    params.insert("project", DataValue::Str("default".into()));
+                    params.insert("module_pattern", DataValue::Str(pattern.as_str().into()));
+                    params.insert("function_pattern", DataValue::Str(pattern.as_str().into()));
+
+                    let rows = crate::db::run_query(&db, &script, params).unwrap();
+                    let found: Vec<(String, String)> = crate::db::TypedRows::into_typed(rows).unwrap();
+                    proptest::prop_assert_eq!(found, vec![(name.clone(), name.clone())]);
+                }
+            }
+        }
+    }
 }
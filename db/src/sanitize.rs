@@ -0,0 +1,325 @@
+//! Query sanitization.
+//!
+//! Input from users or config reaches a CozoScript in one of two ways: bound
+//! as a `$` parameter, which is the default, or as a quoted literal made by
+//! [`literal`] where the script itself has to be built as text (the
+//! generated-code conditions, import rows). Nothing else is formatted into a
+//! script.
+//!
+//! In debug builds [`run_query`](crate::db::run_query) checks every script
+//! with [`check_placeholders`], so a parameter that is bound but unused, its
+//! value pasted into the text instead, fails the test suite instead of
+//! shipping. Tests additionally run [`check_not_inlined`] over their fixed
+//! inputs.
+//!
+//! Raw scripts from `query save --script` are user code rather than values and
+//! run read-only; [`check_sandboxed`] additionally rejects the system
+//! operations and fixed rules that reach outside the database.
+
+use cozo::DataValue;
+use thiserror::Error;
+
+use crate::db::{escape_string_single, Params};
+
+/// Fixed rules reading files or URLs
+const FILE_RULES: [&str; 2] = ["CsvReader", "JsonReader"];
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SandboxError {
+    #[error("System operation '{op}' is not allowed in query scripts")]
+    SystemOp { op: String },
+
+    #[error("Fixed rule '{rule}' reads files or URLs and is not allowed in query scripts")]
+    FileAccess { rule: String },
+}
+
+/// Single-quoted CozoScript literal holding `value`.
+///
+/// The one way to put text into a script; prefer a `$` parameter when the
+/// script can take one.
+///
+/// # Examples
+/// ```
+/// use db::sanitize::literal;
+///
+/// assert_eq!(literal("MyApp.Web"), "'MyApp.Web'");
+/// assert_eq!(literal("it's"), r"'it\'s'");
+/// ```
+pub fn literal(value: &str) -> String {
+    format!("'{}'", escape_string_single(value))
+}
+
+/// Check that every parameter of `params` is referenced as `$name` in `script`.
+///
+/// A parameter that is bound but never used usually means its value was
+/// formatted into the text instead.
+///
+/// # Examples
+/// ```
+/// use db::sanitize::check_placeholders;
+/// use db::{DataValue, Params};
+///
+/// let mut params = Params::new();
+/// params.insert("project", DataValue::Str("default".into()));
+/// assert!(check_placeholders("?[m] := *modules{project, name: m}, project == $project", &params).is_ok());
+/// assert!(check_placeholders("?[m] := *modules{project: 'default', name: m}", &params).is_err());
+/// ```
+pub fn check_placeholders(script: &str, params: &Params) -> Result<(), String> {
+    let scanned = Scanned::new(script);
+    match params.keys().find(|name| !scanned.references(name)) {
+        Some(name) => Err(format!("parameter ${name} is bound but not used")),
+        None => Ok(()),
+    }
+}
+
+/// Check that no string literal of `script` spells out a bound string value.
+///
+/// Only meaningful over fixed inputs: a literal may legitimately equal a
+/// value that came from elsewhere, so [`run_query`](crate::db::run_query)
+/// runs it in tests only.
+///
+/// # Examples
+/// ```
+/// use db::sanitize::check_not_inlined;
+/// use db::{DataValue, Params};
+///
+/// let mut params = Params::new();
+/// params.insert("module", DataValue::Str("MyApp".into()));
+/// assert!(check_not_inlined("?[m] := m = $module", &params).is_ok());
+/// assert!(check_not_inlined("?[m] := m = $module, n = 'MyApp'", &params).is_err());
+/// ```
+pub fn check_not_inlined(script: &str, params: &Params) -> Result<(), String> {
+    let scanned = Scanned::new(script);
+    for (name, value) in params {
+        if let DataValue::Str(value) = value
+            && !value.is_empty()
+            && scanned.literals.iter().any(|lit| lit == value.as_str() || *lit == escape_string_single(value))
+        {
+            return Err(format!("value of ${name} is inlined as a literal"));
+        }
+    }
+    Ok(())
+}
+
+/// Check that a raw script stays inside the database.
+///
+/// Read-only mode already stops writes; this rejects system operations
+/// (`::relations`, `::compact`, ...) and the fixed rules reading files or URLs.
+/// Text inside string literals and comments is ignored.
+///
+/// # Examples
+/// ```
+/// use db::sanitize::check_sandboxed;
+///
+/// assert!(check_sandboxed("?[n] := *modules{name: n}, n == '::running'").is_ok());
+/// assert!(check_sandboxed("::relations").is_err());
+/// assert!(check_sandboxed("?[] <~ CsvReader(url: 'file:///etc/passwd')").is_err());
+/// ```
+pub fn check_sandboxed(script: &str) -> Result<(), SandboxError> {
+    let scanned = Scanned::new(script);
+
+    if let Some(start) = scanned.code.find("::") {
+        let op: String = scanned.code[start..]
+            .chars()
+            .take_while(|c| !c.is_whitespace())
+            .collect();
+        return Err(SandboxError::SystemOp { op });
+    }
+    for rule in FILE_RULES {
+        if scanned.has_word(rule) {
+            return Err(SandboxError::FileAccess { rule: rule.to_string() });
+        }
+    }
+    Ok(())
+}
+
+/// A script split into its code, with literals emptied and comments removed,
+/// and the raw contents of its string literals.
+///
+/// Follows the CozoScript grammar: `#` line comments, nested `/* */` block
+/// comments, single-quoted strings with backslash escapes, and raw strings,
+/// which Cozo tries first: any number of underscores, a double quote, and
+/// everything up to a double quote followed by as many underscores. A plain
+/// double-quoted string is thus raw, a backslash escaping nothing.
+struct Scanned {
+    code: String,
+    literals: Vec<String>,
+}
+
+impl Scanned {
+    fn new(script: &str) -> Self {
+        let mut code = String::with_capacity(script.len());
+        let mut literals = Vec::new();
+        let mut rest = script;
+
+        while let Some(c) = rest.chars().next() {
+            let underscores = rest.len() - rest.trim_start_matches('_').len();
+            if underscores > 0 && code.ends_with(is_ident_char) {
+                // The end of an identifier, as in `a_"b"`
+                code.push_str(&rest[..underscores]);
+                rest = &rest[underscores..];
+            } else if rest[underscores..].starts_with('"') {
+                let close = format!("\"{}", &rest[..underscores]);
+                let content = &rest[underscores + 1..];
+                let end = content.find(&close).unwrap_or(content.len());
+                code.push_str("\"\"");
+                literals.push(content[..end].to_string());
+                rest = content.get(end + close.len()..).unwrap_or("");
+            } else if c == '\'' {
+                let mut content = String::new();
+                let mut chars = rest[1..].char_indices();
+                let mut end = rest.len();
+                while let Some((i, next)) = chars.next() {
+                    if next == '\'' {
+                        end = i + 2;
+                        break;
+                    }
+                    content.push(next);
+                    if next == '\\'
+                        && let Some((_, escaped)) = chars.next()
+                    {
+                        content.push(escaped);
+                    }
+                }
+                code.push_str("''");
+                literals.push(content);
+                rest = &rest[end..];
+            } else if rest.starts_with("/*") {
+                // Block comments nest; nothing is left of them in the code,
+                // so they cannot split a word the checks look for
+                let mut depth = 0;
+                let mut end = rest.len();
+                let mut i = 0;
+                while i < rest.len() {
+                    if rest[i..].starts_with("/*") {
+                        depth += 1;
+                        i += 2;
+                    } else if rest[i..].starts_with("*/") {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            end = i;
+                            break;
+                        }
+                    } else {
+                        i += rest[i..].chars().next().map_or(1, char::len_utf8);
+                    }
+                }
+                rest = &rest[end..];
+            } else if c == '#' {
+                let end = rest.find('\n').unwrap_or(rest.len());
+                rest = &rest[end..];
+            } else {
+                code.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        Scanned { code, literals }
+    }
+
+    /// Whether the code mentions `$name`
+    fn references(&self, name: &str) -> bool {
+        self.code.match_indices('$').any(|(start, _)| {
+            let rest = &self.code[start + 1..];
+            rest.starts_with(name) && !rest[name.len()..].starts_with(is_ident_char)
+        })
+    }
+
+    /// Whether the code holds `word` as a whole identifier
+    fn has_word(&self, word: &str) -> bool {
+        self.code.match_indices(word).any(|(start, _)| {
+            !self.code[..start].ends_with(is_ident_char) && !self.code[start + word.len()..].starts_with(is_ident_char)
+        })
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&'static str, &str)]) -> Params {
+        pairs.iter().map(|(name, value)| (*name, DataValue::Str((*value).into()))).collect()
+    }
+
+    #[test]
+    fn test_placeholders_need_every_param_referenced() {
+        let params = params(&[("project", "default"), ("module", "MyApp")]);
+        assert!(check_placeholders("?[x] := x = $project, y = $module", &params).is_ok());
+        assert_eq!(
+            check_placeholders("?[x] := x = $project, y = $module_name", &params),
+            Err("parameter $module is bound but not used".to_string())
+        );
+    }
+
+    #[test]
+    fn test_placeholders_ignore_references_in_literals_and_comments() {
+        let params = params(&[("project", "default")]);
+        assert!(check_placeholders("?[x] := x = '$project' # $project\n", &params).is_err());
+    }
+
+    #[test]
+    fn test_not_inlined_catches_inlined_values() {
+        let params = params(&[("module", "it's")]);
+        assert_eq!(
+            check_not_inlined(r"?[x] := x = $module, y = 'it\'s'", &params),
+            Err("value of $module is inlined as a literal".to_string())
+        );
+        // Literals that only contain the value are other text
+        assert!(check_not_inlined(r"?[x] := x = $module, y = 'it\'s here'", &params).is_ok());
+    }
+
+    #[test]
+    fn test_literal_round_trips_through_scanner() {
+        let value = "a'b\"c\\d\n";
+        let scanned = Scanned::new(&format!("?[x] <- [[{}]]", literal(value)));
+        assert_eq!(scanned.literals, [escape_string_single(value)]);
+        assert_eq!(scanned.code, "?[x] <- [['']]");
+    }
+
+    #[test]
+    fn test_sandbox_rejects_system_ops() {
+        assert_eq!(
+            check_sandboxed("?[n] := n = 1\n::remove modules"),
+            Err(SandboxError::SystemOp { op: "::remove".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_scanner_follows_cozo_comments_and_strings() {
+        let scanned = Scanned::new("a /* b /* c */ d */ e # f\ng");
+        assert_eq!(scanned.code, "a  e \ng");
+        // Double-quoted strings are raw: a backslash escapes nothing
+        let scanned = Scanned::new(r#"x = "a\", y = __"b"_"c"__, z = 'd\'e'"#);
+        assert_eq!(scanned.literals, [r"a\", r#"b"_"c"#, r"d\'e"]);
+        assert_eq!(scanned.code, r#"x = "", y = "", z = ''"#);
+    }
+
+    #[test]
+    fn test_sandbox_sees_through_block_comments_and_raw_strings() {
+        assert_eq!(
+            check_sandboxed("/* ' */ ?[l] <~ CsvReader(url: 'file:///etc/passwd', types: ['String']) /* ' */"),
+            Err(SandboxError::FileAccess { rule: "CsvReader".to_string() })
+        );
+        assert_eq!(
+            check_sandboxed("/* ' */ ::relations /* ' */"),
+            Err(SandboxError::SystemOp { op: "::relations".to_string() })
+        );
+        assert!(check_sandboxed(r#"?[x] := x = "\" ::relations "\""#).is_err());
+        assert!(check_sandboxed(r#"?[x] := x = _"'"_, y = 1 /* /* */ ' */ ::compact"#).is_err());
+        assert!(check_sandboxed("?[n] := *modules{name: n} /* ::relations, CsvReader /* nested */ */").is_ok());
+    }
+
+    #[test]
+    fn test_sandbox_rejects_file_rules() {
+        assert_eq!(
+            check_sandboxed("?[a] <~ JsonReader(url: 'http://example.com', fields: ['a'])"),
+            Err(SandboxError::FileAccess { rule: "JsonReader".to_string() })
+        );
+        assert!(check_sandboxed("?[n] := *modules{name: n}, n == 'CsvReader' # JsonReader").is_ok());
+        assert!(check_sandboxed("?[n] := *my_CsvReaders{n}").is_ok());
+    }
+}
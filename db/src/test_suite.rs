@@ -0,0 +1,324 @@
+//! Conformance suite for storage backends.
+//!
+//! Runs the query modules over the shared fixtures on databases of one backend
+//! and records what each returns, so backends can be checked to agree. A
+//! backend is whatever opens an empty `DbInstance`: the in-memory and SQLite
+//! engines of Cozo today.
+//!
+//! ```ignore
+//! let expected = db::test_suite::run_suite(db::open_mem_db);
+//! let actual = db::test_suite::run_suite(|| open_my_backend());
+//! db::test_suite::assert_same_results(&expected, &actual);
+//! ```
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use cozo::DbInstance;
+
+use crate::fixtures;
+use crate::queries::{
+    calls_from, calls_to, depended_by, depends_on, file, function, hotspots, import, location, path, reverse_trace,
+    search, specs, structs, supervision, trace, unused,
+};
+use crate::query_builders::QuerySettings;
+
+const PROJECT: &str = "default";
+
+/// One query, run on a database holding `fixture`
+pub struct QueryCase {
+    pub name: &'static str,
+    pub fixture: &'static str,
+    /// The query's results, in their `Debug` form
+    pub run: fn(&DbInstance) -> Result<String, Box<dyn Error>>,
+}
+
+/// Debug form of a query's results
+fn debug<T: std::fmt::Debug>(result: Result<T, Box<dyn Error>>) -> Result<String, Box<dyn Error>> {
+    result.map(|value| format!("{:#?}", value))
+}
+
+/// Every query the suite runs. Each returns results on its fixture, so two
+/// backends cannot agree by both returning nothing.
+pub const CASES: &[QueryCase] = &[
+    QueryCase {
+        name: "calls_from",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| {
+            debug(calls_from::find_calls_from(
+                db,
+                "MyApp.Controller",
+                None,
+                None,
+                &[],
+                PROJECT,
+                false,
+                100,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "calls_to",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| {
+            debug(calls_to::find_calls_to(
+                db,
+                "MyApp.Repo",
+                None,
+                None,
+                &[],
+                PROJECT,
+                false,
+                100,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "trace",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| {
+            debug(trace::trace_calls(
+                db,
+                "MyApp.Controller",
+                "index",
+                None,
+                PROJECT,
+                false,
+                5,
+                100,
+                false,
+                1,
+                false,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "reverse_trace",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| {
+            debug(reverse_trace::reverse_trace_calls(
+                db,
+                "MyApp.Repo",
+                "get",
+                None,
+                PROJECT,
+                false,
+                5,
+                100,
+                false,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "path",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| {
+            debug(path::find_paths(
+                db,
+                "MyApp.Controller",
+                "show",
+                None,
+                "MyApp.Repo",
+                "get",
+                None,
+                PROJECT,
+                10,
+                100,
+                false,
+                1,
+            ))
+        },
+    },
+    QueryCase {
+        name: "depends_on",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| {
+            debug(depends_on::find_dependencies(
+                db,
+                "MyApp.Controller",
+                PROJECT,
+                false,
+                100,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "depended_by",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| debug(depended_by::find_dependents(db, "MyApp.Repo", PROJECT, false, 100, &QuerySettings::default())),
+    },
+    QueryCase {
+        name: "hotspots",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| {
+            debug(hotspots::find_hotspots(
+                db,
+                hotspots::HotspotKind::Total,
+                None,
+                None,
+                PROJECT,
+                false,
+                100,
+                false,
+                false,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "unused",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| {
+            debug(unused::find_unused_functions(
+                db, None, None, PROJECT, false, false, false, false, None, 100,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "search_modules",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| debug(search::search_modules(db, "^MyApp", PROJECT, 100, true, &QuerySettings::default())),
+    },
+    QueryCase {
+        name: "search_functions",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| debug(search::search_functions(db, "^get", PROJECT, 100, true, &QuerySettings::default())),
+    },
+    QueryCase {
+        name: "location",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| {
+            debug(location::find_locations(
+                db,
+                Some("MyApp.Accounts"),
+                "get_user",
+                None,
+                PROJECT,
+                false,
+                100,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "file",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| {
+            debug(file::find_functions_in_module(
+                db,
+                "MyApp.Accounts",
+                PROJECT,
+                false,
+                100,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "supervision",
+        fixture: fixtures::CALL_GRAPH,
+        run: |db| debug(supervision::find_supervision_children(db, PROJECT)),
+    },
+    QueryCase {
+        name: "specs",
+        fixture: fixtures::TYPE_SIGNATURES,
+        run: |db| {
+            debug(specs::find_specs(
+                db,
+                "MyApp.Accounts",
+                None,
+                None,
+                PROJECT,
+                false,
+                100,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "function",
+        fixture: fixtures::TYPE_SIGNATURES,
+        run: |db| {
+            debug(function::find_functions(
+                db,
+                "MyApp\\..*",
+                ".*",
+                None,
+                PROJECT,
+                true,
+                100,
+                &QuerySettings::default(),
+            ))
+        },
+    },
+    QueryCase {
+        name: "structs",
+        fixture: fixtures::STRUCTS,
+        run: |db| debug(structs::find_struct_fields(db, "MyApp.User", PROJECT, false, 100, &QuerySettings::default())),
+    },
+];
+
+/// Run every case on databases from `open`, one per fixture, keyed by case name
+pub fn run_suite(mut open: impl FnMut() -> DbInstance) -> BTreeMap<&'static str, String> {
+    let mut databases: Vec<(&'static str, DbInstance)> = Vec::new();
+    let mut results = BTreeMap::new();
+    for case in CASES {
+        let db = match databases.iter().find(|(fixture, _)| *fixture == case.fixture) {
+            Some((_, db)) => db,
+            None => {
+                let db = open();
+                import::import_json_str(&db, case.fixture, PROJECT)
+                    .unwrap_or_else(|e| panic!("Importing the fixture of '{}' failed: {}", case.name, e));
+                databases.push((case.fixture, db));
+                &databases.last().unwrap().1
+            }
+        };
+        let result = (case.run)(db).unwrap_or_else(|e| panic!("Query '{}' failed: {}", case.name, e));
+        results.insert(case.name, result);
+    }
+    results
+}
+
+/// Assert two suite runs agree case by case, naming the first case that differs
+pub fn assert_same_results(expected: &BTreeMap<&'static str, String>, actual: &BTreeMap<&'static str, String>) {
+    for (name, expected_result) in expected {
+        assert_eq!(
+            actual.get(name),
+            Some(expected_result),
+            "Backends disagree on query '{}'",
+            name
+        );
+    }
+    assert_eq!(expected.len(), actual.len(), "Backends ran different cases");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{open_db, open_mem_db};
+
+    #[test]
+    fn test_cases_return_results() {
+        for (name, result) in run_suite(open_mem_db) {
+            assert!(!result.starts_with("[]") && !result.starts_with("{}"), "Query '{}' returned nothing", name);
+        }
+    }
+
+    #[test]
+    fn test_sqlite_matches_memory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut opened = 0;
+        let sqlite = run_suite(|| {
+            opened += 1;
+            open_db(&dir.path().join(format!("suite{}.db", opened))).unwrap()
+        });
+
+        assert_same_results(&run_suite(open_mem_db), &sqlite);
+    }
+}
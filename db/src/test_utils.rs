@@ -75,6 +75,14 @@ pub fn structs_db(project: &str) -> DbInstance {
     setup_test_db(fixtures::STRUCTS, project)
 }
 
+/// Create a test database with an umbrella project of three apps.
+///
+/// Use for: apps, --app filters
+#[cfg(any(test, feature = "test-utils"))]
+pub fn umbrella_db(project: &str) -> DbInstance {
+    setup_test_db(fixtures::UMBRELLA, project)
+}
+
 // =============================================================================
 // Output fixture helpers
 // =============================================================================
@@ -92,3 +100,187 @@ pub fn load_output_fixture(command: &str, name: &str) -> String {
     std::fs::read_to_string(&fixture_path)
         .unwrap_or_else(|e| panic!("Failed to read fixture {}: {}", fixture_path.display(), e))
 }
+
+// =============================================================================
+// Property test generators
+// =============================================================================
+
+/// Module and function names as they may reach a query: Elixir-like names,
+/// runs of quotes, backslashes and regex metacharacters, and arbitrary
+/// unicode including control characters.
+#[cfg(test)]
+pub fn name_strategy() -> impl proptest::strategy::Strategy<Value = String> {
+    use proptest::prelude::*;
+
+    prop_oneof![
+        "[A-Z][a-zA-Z0-9_]{0,8}(\\.[A-Z][a-zA-Z0-9_]{0,8}){0,3}",
+        "[a-z_][a-zA-Z0-9_]{0,12}[?!]?",
+        "[\"'\\\\$^.*+?()\\[\\]{}|/ a-z]{1,16}",
+        "\\PC{1,16}",
+        any::<String>().prop_filter("empty name", |s| !s.is_empty()),
+    ]
+}
+
+// =============================================================================
+// Scripted database
+// =============================================================================
+
+#[cfg(any(test, feature = "test-utils"))]
+use std::cell::RefCell;
+#[cfg(any(test, feature = "test-utils"))]
+use std::rc::Rc;
+
+#[cfg(any(test, feature = "test-utils"))]
+use cozo::{DataValue, NamedRows};
+
+#[cfg(any(test, feature = "test-utils"))]
+use crate::db::{Params, QUERY_HOOK};
+
+/// A query run while a [`MockDatabase`] was installed
+#[cfg(any(test, feature = "test-utils"))]
+#[derive(Debug, Clone)]
+pub struct RecordedQuery {
+    pub script: String,
+    pub params: Params,
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl RecordedQuery {
+    /// The value bound to `$name`, if it was passed
+    pub fn param(&self, name: &str) -> Option<&DataValue> {
+        self.params.get(name)
+    }
+}
+
+/// A scripted stand-in for the database.
+///
+/// While [`MockDatabase::run`] runs, every query is recorded, and queries
+/// whose script contains the text of a response get its rows back without
+/// reaching a database. Others run on an in-memory database with the schema
+/// and no data, or fail with [`MockDatabase::strict`].
+///
+/// ```ignore
+/// let mock = MockDatabase::new().respond("*calls{", &["caller_module"], vec![vec![DataValue::from("MyApp")]]);
+/// let result = mock.run(|db| cmd.execute(db));
+/// assert_eq!(mock.queries()[0].param("project"), Some(&DataValue::from("default")));
+/// ```
+#[cfg(any(test, feature = "test-utils"))]
+#[derive(Default)]
+pub struct MockDatabase {
+    responses: Vec<(String, NamedRows)>,
+    strict: bool,
+    recorded: Rc<RefCell<Vec<RecordedQuery>>>,
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl MockDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer queries whose script contains `script_part` with `rows`; the
+    /// first matching response wins
+    pub fn respond(mut self, script_part: &str, headers: &[&str], rows: Vec<Vec<DataValue>>) -> Self {
+        let headers = headers.iter().map(|h| h.to_string()).collect();
+        self.responses.push((script_part.to_string(), NamedRows::new(headers, rows)));
+        self
+    }
+
+    /// Fail queries no response matches instead of running them
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Run `f` on an empty in-memory database with the mock answering its queries
+    pub fn run<T>(&self, f: impl FnOnce(&DbInstance) -> T) -> T {
+        let db = open_mem_db();
+        crate::queries::import::create_schema(&db).expect("Schema creation should succeed");
+
+        struct Uninstall;
+        impl Drop for Uninstall {
+            fn drop(&mut self) {
+                QUERY_HOOK.with(|hook| hook.borrow_mut().take());
+            }
+        }
+
+        let responses = self.responses.clone();
+        let strict = self.strict;
+        let recorded = Rc::clone(&self.recorded);
+        QUERY_HOOK.with(|hook| {
+            *hook.borrow_mut() = Some(Box::new(move |script: &str, params: &Params| {
+                recorded.borrow_mut().push(RecordedQuery {
+                    script: script.to_string(),
+                    params: params.clone(),
+                });
+                match responses.iter().find(|(part, _)| script.contains(part.as_str())) {
+                    Some((_, rows)) => Some(Ok(rows.clone())),
+                    None if strict => Some(Err(format!("No scripted response for query:\n{}", script))),
+                    None => None,
+                }
+            }))
+        });
+        let _uninstall = Uninstall;
+        f(&db)
+    }
+
+    /// Queries run so far, in order
+    pub fn queries(&self) -> Vec<RecordedQuery> {
+        self.recorded.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::calls_from::find_calls_from;
+    use crate::query_builders::QuerySettings;
+
+    #[test]
+    fn test_mock_database_records_and_answers_queries() {
+        let mock = MockDatabase::new().strict().respond(
+            "*calls{",
+            &[
+                "caller_module", "caller_name", "caller_arity", "caller_kind", "caller_start_line",
+                "caller_end_line", "callee_module", "callee_function", "callee_arity", "file", "call_line",
+                "call_type",
+            ],
+            vec![vec![
+                DataValue::from("MyApp.Web"),
+                DataValue::from("index"),
+                DataValue::from(2),
+                DataValue::from("def"),
+                DataValue::from(3),
+                DataValue::from(9),
+                DataValue::from("MyApp.Repo"),
+                DataValue::from("all"),
+                DataValue::from(1),
+                DataValue::from("lib/web.ex"),
+                DataValue::from(5),
+                DataValue::from("remote"),
+            ]],
+        );
+
+        let calls = mock
+            .run(|db| find_calls_from(db, "MyApp.Web", None, None, &[], "default", false, 10, &QuerySettings::default()))
+            .unwrap();
+
+        assert_eq!(calls.len(), 1);
+        assert_eq!(&*calls[0].callee.module, "MyApp.Repo");
+        let queries = mock.queries();
+        assert_eq!(queries.len(), 1);
+        assert!(queries[0].script.contains("caller_module == $module_pattern"));
+        assert_eq!(queries[0].param("module_pattern"), Some(&DataValue::from("MyApp.Web")));
+        assert_eq!(queries[0].param("project"), Some(&DataValue::from("default")));
+    }
+
+    #[test]
+    fn test_strict_mock_fails_unscripted_queries() {
+        let mock = MockDatabase::new().strict();
+        let result = mock.run(|db| find_calls_from(db, "MyApp", None, None, &[], "default", false, 10, &QuerySettings::default()));
+
+        assert!(result.unwrap_err().to_string().contains("No scripted response"));
+        // Uninstalled once run returns
+        assert!(crate::db::run_query_no_params(&open_mem_db(), "?[x] <- [[1]]").is_ok());
+    }
+}
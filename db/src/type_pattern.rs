@@ -0,0 +1,350 @@
+//! Structural patterns over spec types, for `accepts` and `returns`
+//!
+//! A pattern is written like an Elixir type, with `_` for any type:
+//! `{:ok, _}`, `[User.t]`, `%{id: _}`, `{:ok, _} | {:error, _}`. It is matched
+//! against the types parsed from specs rather than their text, so tuples must
+//! have the same size, a union matches when one of its members does, and a
+//! type name matches qualified (`User.t` matches `MyApp.User.t()`). Pattern
+//! names without arguments match whatever arguments the type has.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A type, parsed from a spec or a pattern
+#[derive(Debug, Clone, PartialEq)]
+enum TypeExpr {
+    /// `_`, in patterns only
+    Any,
+    Union(Vec<TypeExpr>),
+    Tuple(Vec<TypeExpr>),
+    /// `[t]`, `list(t)` or `[]`; keyword items are `{:key, t}` tuples
+    List(Vec<TypeExpr>),
+    /// `%{...}` or `%Struct{...}`
+    Map { name: Option<String>, entries: Vec<(TypeExpr, TypeExpr)> },
+    /// An atom, without its colon: `ok`, `nil`, `true`
+    Atom(String),
+    /// A local or remote type: `integer()`, `User.t()`, `keyword(t)`
+    Named { name: String, args: Vec<TypeExpr> },
+    /// Anything else (binaries, ranges, integers, functions), by its text
+    Literal(String),
+}
+
+/// A parsed type pattern
+///
+/// # Examples
+/// ```
+/// use db::type_pattern::TypePattern;
+///
+/// let pattern: TypePattern = "{:ok, User.t}".parse().unwrap();
+/// assert!(pattern.returned_by("{:ok, MyApp.User.t()} | {:error, term()}"));
+/// assert!(!pattern.returned_by("{:ok, [User.t()]}"));
+///
+/// let changeset: TypePattern = "Changeset.t".parse().unwrap();
+/// assert!(changeset.accepted_by("map(), Ecto.Changeset.t()"));
+///
+/// assert!("{:ok, _".parse::<TypePattern>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypePattern {
+    source: String,
+    expr: TypeExpr,
+}
+
+impl fmt::Display for TypePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl FromStr for TypePattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            source: s.to_string(),
+            expr: parse(s).map_err(|e| format!("invalid type pattern `{}`: {}", s, e))?,
+        })
+    }
+}
+
+impl TypePattern {
+    /// Whether one of the parameters of a spec's `inputs_string` matches
+    pub fn accepted_by(&self, inputs: &str) -> bool {
+        split_top(inputs, ",")
+            .unwrap_or_default()
+            .into_iter()
+            .any(|param| matches(&self.expr, &parse_lenient(param)))
+    }
+
+    /// Whether a spec's `return_string` matches
+    pub fn returned_by(&self, returns: &str) -> bool {
+        matches(&self.expr, &parse_lenient(returns))
+    }
+}
+
+/// Whether `ty` matches `pattern`
+fn matches(pattern: &TypeExpr, ty: &TypeExpr) -> bool {
+    match (pattern, ty) {
+        (TypeExpr::Any, _) => true,
+        // Every member of a pattern union has to be among the type's members
+        (TypeExpr::Union(patterns), _) => {
+            let members = union_members(ty);
+            patterns.iter().all(|p| members.iter().any(|t| matches(p, t)))
+        }
+        (_, TypeExpr::Union(members)) => members.iter().any(|t| matches(pattern, t)),
+        (TypeExpr::Tuple(patterns), TypeExpr::Tuple(types)) | (TypeExpr::List(patterns), TypeExpr::List(types)) => {
+            patterns.len() == types.len() && patterns.iter().zip(types).all(|(p, t)| matches(p, t))
+        }
+        (TypeExpr::Map { name: pattern_name, entries: patterns }, TypeExpr::Map { name, entries }) => {
+            let names_match = match (pattern_name, name) {
+                (Some(pattern_name), Some(name)) => name_matches(pattern_name, name),
+                (Some(_), None) => false,
+                (None, _) => true,
+            };
+            names_match
+                && patterns
+                    .iter()
+                    .all(|(pk, pv)| entries.iter().any(|(k, v)| matches(pk, k) && matches(pv, v)))
+        }
+        (TypeExpr::Atom(pattern), TypeExpr::Atom(atom)) => pattern == atom,
+        (TypeExpr::Named { name: pattern_name, args: patterns }, TypeExpr::Named { name, args }) => {
+            name_matches(pattern_name, name)
+                && (patterns.is_empty() || (patterns.len() == args.len() && patterns.iter().zip(args).all(|(p, t)| matches(p, t))))
+        }
+        (TypeExpr::Literal(pattern), TypeExpr::Literal(text)) => pattern == text,
+        _ => false,
+    }
+}
+
+fn union_members(ty: &TypeExpr) -> &[TypeExpr] {
+    match ty {
+        TypeExpr::Union(members) => members,
+        other => std::slice::from_ref(other),
+    }
+}
+
+/// `User.t` names `User.t` and `MyApp.User.t`, not `AdminUser.t`
+fn name_matches(pattern: &str, name: &str) -> bool {
+    name == pattern || name.strip_suffix(pattern).is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Parse a type from a spec, keeping what does not parse as its text
+fn parse_lenient(text: &str) -> TypeExpr {
+    parse(text).unwrap_or_else(|_| TypeExpr::Literal(normalize(text)))
+}
+
+fn parse(text: &str) -> Result<TypeExpr, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("expected a type".to_string());
+    }
+    let members = split_top(text, "|")?;
+    if members.len() > 1 {
+        return members.into_iter().map(parse).collect::<Result<_, _>>().map(TypeExpr::Union);
+    }
+    // `name :: type` annotates a parameter or element with a name
+    if let [_, annotated] = split_top(text, "::")?.as_slice() {
+        return parse(annotated);
+    }
+
+    if text == "_" {
+        return Ok(TypeExpr::Any);
+    }
+    if let Some(inner) = enclosed(text, "{", "}") {
+        return items(inner).map(TypeExpr::Tuple);
+    }
+    if let Some(inner) = enclosed(text, "[", "]") {
+        return items(inner).map(TypeExpr::List);
+    }
+    if let Some(rest) = text.strip_prefix('%')
+        && let Some(brace) = rest.find('{')
+        && let Some(inner) = enclosed(&rest[brace..], "{", "}")
+    {
+        let name = rest[..brace].trim();
+        let entries = split_top(inner, ",")?
+            .into_iter()
+            .filter(|entry| !entry.is_empty())
+            .map(map_entry)
+            .collect::<Result<_, _>>()?;
+        return Ok(TypeExpr::Map {
+            name: (!name.is_empty()).then(|| name.to_string()),
+            entries,
+        });
+    }
+    if let Some(inner) = enclosed(text, "(", ")") {
+        return match split_top(inner, "->")?.len() {
+            1 => parse(inner),
+            _ => Ok(TypeExpr::Literal(normalize(text))),
+        };
+    }
+    if let Some(atom) = text.strip_prefix(':').filter(|atom| !atom.contains('(')) {
+        return Ok(TypeExpr::Atom(atom.trim_matches('"').to_string()));
+    }
+    if matches!(text, "nil" | "true" | "false") {
+        return Ok(TypeExpr::Atom(text.to_string()));
+    }
+
+    let (name, args) = match text.find('(') {
+        Some(open) if text.ends_with(')') => (text[..open].trim(), items(&text[open + 1..text.len() - 1])?),
+        _ => (text, Vec::new()),
+    };
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | ':' | '?' | '!')) {
+        return Ok(TypeExpr::Literal(normalize(text)));
+    }
+    if name == "list" && args.len() == 1 {
+        return Ok(TypeExpr::List(args));
+    }
+    Ok(TypeExpr::Named {
+        name: name.to_string(),
+        args,
+    })
+}
+
+/// The items of a tuple, list or argument list; `key: t` is `{:key, t}`
+fn items(inner: &str) -> Result<Vec<TypeExpr>, String> {
+    split_top(inner, ",")?
+        .into_iter()
+        .filter(|item| !item.is_empty())
+        .map(|item| match keyword(item) {
+            Some((key, value)) => Ok(TypeExpr::Tuple(vec![TypeExpr::Atom(key.to_string()), parse(value)?])),
+            None => parse(item),
+        })
+        .collect()
+}
+
+/// `key: t` or `k => v` inside a map
+fn map_entry(entry: &str) -> Result<(TypeExpr, TypeExpr), String> {
+    if let Some((key, value)) = keyword(entry) {
+        return Ok((TypeExpr::Atom(key.to_string()), parse(value)?));
+    }
+    match split_top(entry, "=>")?.as_slice() {
+        [key, value] => Ok((parse(key)?, parse(value)?)),
+        _ => Err(format!("expected `key: type` or `key => type`, found `{}`", entry)),
+    }
+}
+
+/// The key and value of a `key: value` item
+fn keyword(item: &str) -> Option<(&str, &str)> {
+    let (key, value) = item.split_once(": ")?;
+    key.chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '?' | '!'))
+        .then_some((key, value))
+}
+
+/// The text between `open` and `close` when they enclose all of `text`
+fn enclosed<'a>(text: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let inner = text.strip_prefix(open)?.strip_suffix(close)?;
+    // `{a}, {b}` starts and ends with braces without being one tuple
+    split_top(inner, ",").is_ok().then_some(inner)
+}
+
+/// Split `text` on `separator` outside brackets, parentheses and binaries
+fn split_top<'a>(text: &'a str, separator: &str) -> Result<Vec<&'a str>, String> {
+    let mut parts = Vec::new();
+    let mut depth: Vec<char> = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let rest = &text[i..];
+        match c {
+            '<' if rest.starts_with("<<") => {
+                depth.push('>');
+                chars.next();
+            }
+            '>' if rest.starts_with(">>") && depth.last() == Some(&'>') => {
+                depth.pop();
+                chars.next();
+            }
+            '(' => depth.push(')'),
+            '[' => depth.push(']'),
+            '{' => depth.push('}'),
+            ')' | ']' | '}' if depth.pop() != Some(c) => {
+                return Err(format!("unbalanced `{}`", c));
+            }
+            _ if depth.is_empty() && rest.starts_with(separator) => {
+                parts.push(text[start..i].trim());
+                start = i + separator.len();
+                for _ in 1..separator.chars().count() {
+                    chars.next();
+                }
+            }
+            _ => {}
+        }
+    }
+    match depth.last() {
+        Some('>') => return Err("missing `>>`".to_string()),
+        Some(close) => return Err(format!("missing `{}`", close)),
+        None => {}
+    }
+    parts.push(text[start..].trim());
+    Ok(parts)
+}
+
+/// Collapse whitespace, so literals compare by their tokens
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(text: &str) -> TypePattern {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_tuples_and_unions() {
+        let ok = pattern("{:ok, _}");
+        assert!(ok.returned_by("{:ok, User.t()} | {:error, Ecto.Changeset.t()}"));
+        assert!(!ok.returned_by("{:ok, User.t(), map()}"));
+        assert!(!ok.returned_by(":ok"));
+
+        let both = pattern("{:ok, _} | {:error, _}");
+        assert!(both.returned_by("{:ok, User.t()} | {:error, Ecto.Changeset.t()}"));
+        assert!(!both.returned_by("{:ok, User.t()} | nil"));
+
+        // A union nested in a tuple matches through any member
+        assert!(pattern("{:ok, User.t}").returned_by("{:ok, User.t() | nil}"));
+    }
+
+    #[test]
+    fn test_names_lists_and_maps() {
+        assert!(pattern("User.t").returned_by("MyApp.User.t()"));
+        assert!(!pattern("User.t").returned_by("MyApp.AdminUser.t()"));
+        assert!(pattern("[User.t]").returned_by("list(User.t())"));
+        assert!(pattern("keyword(_)").returned_by("keyword(String.t())"));
+        assert!(!pattern("keyword(integer)").returned_by("keyword(String.t())"));
+
+        let map = pattern("%{id: integer}");
+        assert!(map.returned_by("%{id: integer(), name: String.t()}"));
+        assert!(!map.returned_by("%{name: String.t()}"));
+        assert!(pattern("%User{}").returned_by("%MyApp.User{name: String.t()}"));
+        assert!(pattern("%{}").returned_by("%{optional(atom()) => term()}"));
+    }
+
+    #[test]
+    fn test_accepted_by_any_parameter() {
+        let changeset = pattern("Changeset.t");
+        assert!(changeset.accepted_by("Ecto.Changeset.t(), keyword()"));
+        assert!(changeset.accepted_by("changeset :: Ecto.Changeset.t()"));
+        assert!(!changeset.accepted_by("map(), keyword()"));
+        // Commas inside a parameter do not split it
+        assert!(pattern("{:ok, _}").accepted_by("{:ok, integer()}, atom()"));
+    }
+
+    #[test]
+    fn test_unparsed_types_are_literals() {
+        assert!(pattern("<<_::8>>").returned_by("<<_::8>>"));
+        assert!(pattern("1..10").returned_by("1..10"));
+        assert!(!pattern("binary").returned_by("(integer() -> binary())"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (text, message) in [("{:ok, _", "missing `}`"), ("[User.t", "missing `]`"), ("User.t)", "unbalanced `)`"), ("", "expected a type")] {
+            let err = text.parse::<TypePattern>().unwrap_err();
+            assert!(err.contains(message), "{text}: {err}");
+        }
+    }
+}
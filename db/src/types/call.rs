@@ -163,8 +163,14 @@ pub struct Call {
     pub line: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub call_type: Option<String>,
+    /// "call", "pipe" or "capture", when the query reads it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub call_kind: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub depth: Option<i64>,
+    /// Call sites between the same caller and callee, when the query reads it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<i64>,
 }
 
 impl Call {
@@ -173,7 +179,21 @@ impl Call {
         self.callee.name.as_ref() == "%"
     }
 
-    /// Format as outgoing call: "→ @ L37 name/arity [kind] (location)"
+    /// Format the weight as " ×3", or an empty string for a single call site.
+    pub fn format_weight(&self) -> String {
+        self.weight.filter(|&w| w > 1).map(|w| format!(" ×{}", w)).unwrap_or_default()
+    }
+
+    /// Prefix of the called name for pipes ("|> ") and captures ("&").
+    pub fn format_call_kind(&self) -> &'static str {
+        match self.call_kind.as_deref() {
+            Some("pipe") => "|> ",
+            Some("capture") => "&",
+            _ => "",
+        }
+    }
+
+    /// Format as outgoing call: "→ @ L37 name/arity [kind] (location) ×weight"
     pub fn format_outgoing(&self, context_module: &str, context_file: &str) -> String {
         let name = self.callee.format_name(Some(context_module));
         let kind = self.callee.format_kind();
@@ -183,10 +203,10 @@ impl Call {
             .map(|loc| format!(" ({})", loc))
             .unwrap_or_default();
 
-        format!("→ @ L{} {}{}{}", self.line, name, kind, location)
+        format!("→ @ L{} {}{}{}{}{}", self.line, self.format_call_kind(), name, kind, location, self.format_weight())
     }
 
-    /// Format as incoming call: "← @ L37 name/arity [kind] (location)"
+    /// Format as incoming call: "← @ L37 name/arity [kind] (location) ×weight"
     pub fn format_incoming(&self, context_module: &str, context_file: &str) -> String {
         let name = self.caller.format_name(Some(context_module));
         let kind = self.caller.format_kind();
@@ -196,7 +216,7 @@ impl Call {
             .map(|loc| format!(" ({})", loc))
             .unwrap_or_default();
 
-        format!("← @ L{} {}{}{}", self.line, name, kind, location)
+        format!("← @ L{} {}{}{}{}{}", self.line, self.format_call_kind(), name, kind, location, self.format_weight())
     }
 }
 
@@ -276,7 +296,9 @@ mod tests {
             ),
             line: 25,
             call_type: None,
+            call_kind: None,
             depth: None,
+            weight: None,
         };
 
         assert_eq!(
@@ -300,7 +322,9 @@ mod tests {
             ),
             line: 12,
             call_type: None,
+            call_kind: None,
             depth: None,
+            weight: None,
         };
 
         assert_eq!(
@@ -309,6 +333,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_call_format_incoming_weight() {
+        let mut call = Call {
+            caller: FunctionRef::new("OtherModule", "caller_func", 1),
+            callee: FunctionRef::new("MyModule", "callee_func", 0),
+            line: 7,
+            call_type: None,
+            call_kind: None,
+            depth: None,
+            weight: Some(3),
+        };
+        assert_eq!(call.format_incoming("MyModule", ""), "← @ L7 OtherModule.caller_func/1 ×3");
+
+        call.weight = Some(1);
+        assert_eq!(call.format_incoming("MyModule", ""), "← @ L7 OtherModule.caller_func/1");
+    }
+
+    #[test]
+    fn test_call_format_outgoing_call_kind() {
+        let mut call = Call {
+            caller: FunctionRef::new("MyModule", "caller_func", 1),
+            callee: FunctionRef::new("OtherModule", "callee_func", 1),
+            line: 7,
+            call_type: None,
+            call_kind: Some("capture".to_string()),
+            depth: None,
+            weight: None,
+        };
+        assert_eq!(call.format_outgoing("MyModule", ""), "→ @ L7 &OtherModule.callee_func/1");
+
+        call.call_kind = Some("pipe".to_string());
+        assert_eq!(call.format_outgoing("MyModule", ""), "→ @ L7 |> OtherModule.callee_func/1");
+    }
+
     #[test]
     fn test_is_struct_call() {
         let struct_call = Call {
@@ -316,7 +374,9 @@ mod tests {
             callee: FunctionRef::new("MyStruct", "%", 2),
             line: 10,
             call_type: None,
+            call_kind: None,
             depth: None,
+            weight: None,
         };
         assert!(struct_call.is_struct_call());
 
@@ -325,7 +385,9 @@ mod tests {
             callee: FunctionRef::new("OtherModule", "other", 0),
             line: 10,
             call_type: None,
+            call_kind: None,
             depth: None,
+            weight: None,
         };
         assert!(!normal_call.is_struct_call());
     }
@@ -0,0 +1,54 @@
+//! Sharing the strings repeated across the rows of a query result.
+
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use super::SharedStr;
+
+/// Hands out one [`SharedStr`] per distinct string.
+///
+/// A trace of 100k rows names a few hundred modules and functions; interning
+/// them while extracting rows allocates each name once instead of once per cell.
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: HashSet<SharedStr>,
+}
+
+impl Interner {
+    /// The shared copy of `s`, allocated the first time it is seen
+    pub fn intern(&mut self, s: &str) -> SharedStr {
+        if let Some(shared) = self.strings.get(s) {
+            return Rc::clone(shared);
+        }
+        let shared: SharedStr = Rc::from(s);
+        self.strings.insert(Rc::clone(&shared));
+        shared
+    }
+
+    /// Number of distinct strings interned
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_shares_equal_strings() {
+        let mut interner = Interner::default();
+        let a = interner.intern("MyApp.Accounts");
+        let b = interner.intern(&String::from("MyApp.Accounts"));
+        let c = interner.intern("MyApp.Repo");
+
+        assert!(Rc::ptr_eq(&a, &b));
+        assert!(!Rc::ptr_eq(&a, &c));
+        assert_eq!(&*c, "MyApp.Repo");
+        assert_eq!(interner.len(), 2);
+    }
+}
@@ -3,11 +3,13 @@
 use std::rc::Rc;
 
 mod call;
+mod interner;
 mod results;
 mod trace;
 
 pub use call::{Call, FunctionRef};
-pub use results::{ModuleGroupResult, ModuleCollectionResult, ModuleGroup};
+pub use interner::Interner;
+pub use results::{CallGroupCount, CallGroupsResult, ModuleGroupResult, ModuleCollectionResult, ModuleGroup};
 pub use trace::{TraceDirection, TraceEntry, TraceResult};
 
 /// Type alias for shared, reference-counted strings.
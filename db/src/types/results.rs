@@ -36,3 +36,26 @@ pub struct ModuleGroup<E> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub function_count: Option<i64>,
 }
+
+/// Result structure for call counts per group
+/// Used by calls_from and calls_to with `--group-by`
+#[derive(Debug, Default, Serialize)]
+pub struct CallGroupsResult {
+    pub module_pattern: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_pattern: Option<String>,
+    /// Direction of the counted calls ("to" or "from")
+    pub direction: String,
+    /// What the calls are grouped by ("caller module", "callee module" or "file")
+    pub group_by: String,
+    pub total_calls: i64,
+    pub total_items: usize,
+    pub items: Vec<CallGroupCount>,
+}
+
+/// Number of call sites in one group
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CallGroupCount {
+    pub group: String,
+    pub calls: i64,
+}
@@ -0,0 +1,124 @@
+//! JavaScript bindings for the WebAssembly build.
+//!
+//! Built with `--no-default-features --features wasm` for
+//! `wasm32-unknown-unknown`, the crate runs the queries of a graph snapshot
+//! (`code_search export snapshot`) against an in-memory database, so a static
+//! page can search and trace a codebase without a backend. Results are
+//! returned as JSON strings, shaped like the JSON output of the matching
+//! commands' rows.
+//!
+//! ```js
+//! import init, { CodeSearch } from "./db.js";
+//! await init();
+//! const graph = CodeSearch.fromSnapshot(await (await fetch("graph.json")).text());
+//! const calls = JSON.parse(graph.trace("MyApp.Web", "index", 5, 100));
+//! ```
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::db::open_mem_db;
+use crate::queries::graph_snapshot::load_graph_snapshot;
+use crate::queries::path::find_paths;
+use crate::queries::search::{search_functions, search_modules};
+use crate::queries::trace::trace_calls;
+use crate::query_builders::QuerySettings;
+use cozo::DbInstance;
+
+/// A graph snapshot loaded into an in-memory database
+#[wasm_bindgen]
+pub struct CodeSearch {
+    db: DbInstance,
+    project: String,
+    /// Every module is in scope: a snapshot has no project or test flags
+    settings: QuerySettings,
+}
+
+#[wasm_bindgen]
+impl CodeSearch {
+    /// Load a snapshot written by `code_search export snapshot`
+    #[wasm_bindgen(js_name = fromSnapshot)]
+    pub fn from_snapshot(snapshot: &str) -> Result<CodeSearch, JsError> {
+        let db = open_mem_db();
+        let project = load_graph_snapshot(&db, snapshot).map_err(js_error)?;
+        Ok(CodeSearch {
+            db,
+            project,
+            settings: QuerySettings::default(),
+        })
+    }
+
+    /// Project the snapshot was taken from
+    #[wasm_bindgen(getter)]
+    pub fn project(&self) -> String {
+        self.project.clone()
+    }
+
+    /// Modules matching a pattern (substring, or regex with `regex`)
+    #[wasm_bindgen(js_name = searchModules)]
+    pub fn search_modules(&self, pattern: &str, limit: u32, regex: bool) -> Result<String, JsError> {
+        to_json(search_modules(&self.db, pattern, &self.project, limit, regex, &self.settings).map_err(js_error)?)
+    }
+
+    /// Functions matching a pattern (substring, or regex with `regex`)
+    #[wasm_bindgen(js_name = searchFunctions)]
+    pub fn search_functions(&self, pattern: &str, limit: u32, regex: bool) -> Result<String, JsError> {
+        to_json(search_functions(&self.db, pattern, &self.project, limit, regex, &self.settings).map_err(js_error)?)
+    }
+
+    /// Calls reachable from a function, each with its depth
+    pub fn trace(&self, module: &str, function: &str, depth: u32, limit: u32) -> Result<String, JsError> {
+        let calls = trace_calls(
+            &self.db,
+            module,
+            function,
+            None,
+            &self.project,
+            false,
+            depth,
+            limit,
+            false,
+            1,
+            false,
+            &self.settings,
+        )
+        .map_err(js_error)?;
+        to_json(calls)
+    }
+
+    /// Call paths between two functions, shortest first
+    pub fn path(
+        &self,
+        from_module: &str,
+        from_function: &str,
+        to_module: &str,
+        to_function: &str,
+        depth: u32,
+        limit: u32,
+    ) -> Result<String, JsError> {
+        let paths = find_paths(
+            &self.db,
+            from_module,
+            from_function,
+            None,
+            to_module,
+            to_function,
+            None,
+            &self.project,
+            depth,
+            limit,
+            false,
+            1,
+        )
+        .map_err(js_error)?;
+        to_json(paths)
+    }
+}
+
+fn js_error(error: Box<dyn std::error::Error>) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+fn to_json(value: impl Serialize) -> Result<String, JsError> {
+    serde_json::to_string(&value).map_err(|e| JsError::new(&e.to_string()))
+}
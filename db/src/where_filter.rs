@@ -0,0 +1,400 @@
+//! Filter expressions for `--where`
+//!
+//! A small language of column predicates, such as
+//! `arity > 2 && module =~ "Web"`, compiled into a CozoScript condition over
+//! the output columns of a query. Comparisons are `==`, `!=`, `<`, `<=`, `>`,
+//! `>=`, and `=~` / `!~` for regex matches; they combine with `&&`, `||`, `!`
+//! and parentheses. Values are numbers or quoted strings, bound as query
+//! parameters rather than spliced into the script.
+
+use std::fmt;
+use std::str::FromStr;
+
+use cozo::DataValue;
+
+use crate::db::Params;
+use crate::query_builders::compile_pattern;
+
+/// Parameter names bound by a filter, one per comparison
+const PARAM_NAMES: [&str; 16] = [
+    "where_0", "where_1", "where_2", "where_3", "where_4", "where_5", "where_6", "where_7", "where_8",
+    "where_9", "where_10", "where_11", "where_12", "where_13", "where_14", "where_15",
+];
+
+/// Type of a column a filter may compare
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Text,
+    Number,
+}
+
+/// A comparison operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Matches,
+    NotMatches,
+}
+
+/// A literal compared against a column
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Text(String),
+    Integer(i64),
+    Float(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Compare { column: String, op: Comparison, value: Literal },
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// A parsed `--where` expression
+///
+/// # Examples
+/// ```
+/// use db::where_filter::{ColumnType, WhereFilter};
+///
+/// let filter: WhereFilter = "arity > 2 && module =~ \"Web\"".parse().unwrap();
+/// let mut params = db::Params::new();
+/// let columns = [("module", ColumnType::Text), ("arity", ColumnType::Number)];
+/// let condition = filter.condition(&columns, &mut params).unwrap();
+/// assert_eq!(condition, ", (arity > $where_0 && regex_matches(module, $where_1))");
+///
+/// assert!("arity >".parse::<WhereFilter>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhereFilter {
+    source: String,
+    expr: Expr,
+}
+
+impl fmt::Display for WhereFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl FromStr for WhereFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser { tokens, position: 0 };
+        let expr = parser.or()?;
+        if let Some(token) = parser.tokens.get(parser.position) {
+            return Err(format!("unexpected {} in --where expression", token));
+        }
+        Ok(Self {
+            source: s.to_string(),
+            expr,
+        })
+    }
+}
+
+impl WhereFilter {
+    /// Build the condition, with a leading comma, binding its values in `params`
+    ///
+    /// `columns` are the columns of the query the filter may compare; any other
+    /// name, or a value of the wrong type, is an error.
+    pub fn condition(&self, columns: &[(&str, ColumnType)], params: &mut Params) -> Result<String, String> {
+        let mut bound = 0;
+        let condition = build(&self.expr, columns, params, &mut bound)?;
+        Ok(format!(", {}", condition))
+    }
+}
+
+fn build(expr: &Expr, columns: &[(&str, ColumnType)], params: &mut Params, bound: &mut usize) -> Result<String, String> {
+    match expr {
+        Expr::Not(inner) => Ok(format!("!({})", build(inner, columns, params, bound)?)),
+        Expr::And(left, right) => Ok(format!(
+            "({} && {})",
+            build(left, columns, params, bound)?,
+            build(right, columns, params, bound)?
+        )),
+        Expr::Or(left, right) => Ok(format!(
+            "({} || {})",
+            build(left, columns, params, bound)?,
+            build(right, columns, params, bound)?
+        )),
+        Expr::Compare { column, op, value } => {
+            let Some(&(name, column_type)) = columns.iter().find(|(name, _)| name == column) else {
+                let names: Vec<&str> = columns.iter().map(|(name, _)| *name).collect();
+                return Err(format!("unknown column `{}` in --where, expected one of: {}", column, names.join(", ")));
+            };
+            let param = *PARAM_NAMES
+                .get(*bound)
+                .ok_or_else(|| format!("--where supports at most {} comparisons", PARAM_NAMES.len()))?;
+            *bound += 1;
+
+            let data = match (column_type, value) {
+                (ColumnType::Text, Literal::Text(text)) => {
+                    if matches!(op, Comparison::Matches | Comparison::NotMatches) {
+                        compile_pattern(text).map_err(|e| e.to_string())?;
+                    }
+                    DataValue::Str(text.as_str().into())
+                }
+                (ColumnType::Number, Literal::Integer(n)) if !is_regex(*op) => DataValue::from(*n),
+                (ColumnType::Number, Literal::Float(n)) if !is_regex(*op) => DataValue::from(*n),
+                (ColumnType::Number, Literal::Text(_)) => {
+                    return Err(format!("`{}` is a number, compare it with a number", column));
+                }
+                (ColumnType::Number, _) => {
+                    return Err(format!("`{}` is a number, =~ and !~ only match text columns", column));
+                }
+                (ColumnType::Text, _) => {
+                    return Err(format!("`{}` is text, compare it with a quoted string", column));
+                }
+            };
+            params.insert(param, data);
+
+            Ok(match op {
+                Comparison::Matches => format!("regex_matches({}, ${})", name, param),
+                Comparison::NotMatches => format!("!regex_matches({}, ${})", name, param),
+                op => format!("{} {} ${}", name, operator(*op), param),
+            })
+        }
+    }
+}
+
+fn is_regex(op: Comparison) -> bool {
+    matches!(op, Comparison::Matches | Comparison::NotMatches)
+}
+
+fn operator(op: Comparison) -> &'static str {
+    match op {
+        Comparison::Eq => "==",
+        Comparison::Ne => "!=",
+        Comparison::Lt => "<",
+        Comparison::Le => "<=",
+        Comparison::Gt => ">",
+        Comparison::Ge => ">=",
+        Comparison::Matches => "=~",
+        Comparison::NotMatches => "!~",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Literal(Literal),
+    Op(Comparison),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "`{}`", name),
+            Token::Literal(Literal::Text(text)) => write!(f, "\"{}\"", text),
+            Token::Literal(Literal::Integer(n)) => write!(f, "`{}`", n),
+            Token::Literal(Literal::Float(n)) => write!(f, "`{}`", n),
+            Token::Op(op) => write!(f, "`{}`", operator(*op)),
+            Token::And => f.write_str("`&&`"),
+            Token::Or => f.write_str("`||`"),
+            Token::Not => f.write_str("`!`"),
+            Token::Open => f.write_str("`(`"),
+            Token::Close => f.write_str("`)`"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' if chars.next_if_eq(&'&').is_some() => Token::And,
+            '|' if chars.next_if_eq(&'|').is_some() => Token::Or,
+            '=' if chars.next_if_eq(&'=').is_some() => Token::Op(Comparison::Eq),
+            '=' if chars.next_if_eq(&'~').is_some() => Token::Op(Comparison::Matches),
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Op(Comparison::Ne),
+            '!' if chars.next_if_eq(&'~').is_some() => Token::Op(Comparison::NotMatches),
+            '!' => Token::Not,
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Op(Comparison::Le),
+            '<' => Token::Op(Comparison::Lt),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Op(Comparison::Ge),
+            '>' => Token::Op(Comparison::Gt),
+            '"' | '\'' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err("unterminated string in --where expression".to_string()),
+                        Some('\\') => text.extend(chars.next()),
+                        Some(end) if end == c => break,
+                        Some(other) => text.push(other),
+                    }
+                }
+                Token::Literal(Literal::Text(text))
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut number = c.to_string();
+                while let Some(digit) = chars.next_if(|d| d.is_ascii_digit() || *d == '.') {
+                    number.push(digit);
+                }
+                let literal = match number.parse::<i64>() {
+                    Ok(n) => Literal::Integer(n),
+                    Err(_) => Literal::Float(number.parse().map_err(|_| format!("`{}` is not a number", number))?),
+                };
+                Token::Literal(literal)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut name = c.to_string();
+                while let Some(next) = chars.next_if(|n| n.is_alphanumeric() || *n == '_') {
+                    name.push(next);
+                }
+                Token::Ident(name)
+            }
+            c => return Err(format!("unexpected `{}` in --where expression", c)),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens: `||` binds loosest, then `&&`, then `!`
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.position) == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.eat(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                if !self.eat(&Token::Close) {
+                    return Err("missing `)` in --where expression".to_string());
+                }
+                Ok(expr)
+            }
+            Some(Token::Ident(column)) => {
+                let op = match self.next() {
+                    Some(Token::Op(op)) => op,
+                    Some(token) => return Err(format!("expected a comparison after `{}`, found {}", column, token)),
+                    None => return Err(format!("expected a comparison after `{}`", column)),
+                };
+                let value = match self.next() {
+                    Some(Token::Literal(value)) => value,
+                    Some(token) => return Err(format!("expected a number or quoted string, found {}", token)),
+                    None => return Err(format!("expected a value after `{} {}`", column, operator(op))),
+                };
+                Ok(Expr::Compare { column, op, value })
+            }
+            Some(token) => Err(format!("expected a column name, found {}", token)),
+            None => Err("incomplete --where expression".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: &[(&str, ColumnType)] = &[
+        ("module", ColumnType::Text),
+        ("name", ColumnType::Text),
+        ("arity", ColumnType::Number),
+        ("lines", ColumnType::Number),
+    ];
+
+    fn condition(expr: &str) -> Result<(String, Params), String> {
+        let mut params = Params::new();
+        let condition = expr.parse::<WhereFilter>()?.condition(COLUMNS, &mut params)?;
+        Ok((condition, params))
+    }
+
+    #[test]
+    fn test_precedence_and_grouping() {
+        let (cond, params) = condition("arity > 2 || lines >= 10 && !(name == 'init')").unwrap();
+        assert_eq!(
+            cond,
+            ", (arity > $where_0 || (lines >= $where_1 && !(name == $where_2)))"
+        );
+        assert_eq!(params["where_0"], DataValue::from(2));
+        assert_eq!(params["where_2"], DataValue::Str("init".into()));
+    }
+
+    #[test]
+    fn test_regex_and_float_values() {
+        let (cond, params) = condition("module !~ \"Test$\" && lines < 2.5").unwrap();
+        assert_eq!(cond, ", (!regex_matches(module, $where_0) && lines < $where_1)");
+        assert_eq!(params["where_1"], DataValue::from(2.5));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (expr, message) in [
+            ("arity >", "expected a value"),
+            ("arity 2", "expected a comparison"),
+            ("(arity > 2", "missing `)`"),
+            ("name == \"open", "unterminated string"),
+            ("arity > 2 arity", "unexpected `arity`"),
+            ("", "incomplete"),
+        ] {
+            let err = expr.parse::<WhereFilter>().unwrap_err();
+            assert!(err.contains(message), "{expr}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_condition_errors() {
+        for (expr, message) in [
+            ("size > 2", "unknown column `size`"),
+            ("arity == \"two\"", "is a number"),
+            ("arity =~ 2", "only match text"),
+            ("module == 3", "is text"),
+            ("module =~ \"[\"", "Invalid regex"),
+        ] {
+            let err = condition(expr).unwrap_err();
+            assert!(err.contains(message), "{expr}: {err}");
+        }
+    }
+}